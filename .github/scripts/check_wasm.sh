@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+set -eo pipefail
+
+wasm_packages=(
+  # protocol crates
+  kona-genesis
+  kona-hardforks
+  kona-protocol
+  kona-derive
+
+  # bindings
+  kona-derive-wasm
+)
+
+for package in "${wasm_packages[@]}"; do
+  cmd="cargo +stable build -p $package --target wasm32-unknown-unknown --no-default-features"
+  if [ -n "$CI" ]; then
+    echo "::group::$cmd"
+  else
+    printf "\n%s:\n  %s\n" "$package" "$cmd"
+  fi
+
+  $cmd
+
+  if [ -n "$CI" ]; then
+    echo "::endgroup::"
+  fi
+done
@@ -16,20 +16,21 @@ use kona_registry::{HashMap, ROLLUP_CONFIGS};
 use op_alloy_consensus::OpTxEnvelope;
 use op_revm::OpSpecId;
 use revm::context::BlockEnv;
-use tracing::{error, info};
+use tracing::info;
 
 /// Executes the consolidation phase of the interop proof with the given [PreimageOracleClient] and
 /// [HintWriterClient].
 ///
 /// This phase is responsible for checking the dependencies between [OptimisticBlock]s in the
-/// superchain and ensuring that all dependencies are satisfied.
+/// superchain and ensuring that all dependencies are satisfied. Returns the super root at the next
+/// timestamp.
 ///
 /// [OptimisticBlock]: kona_proof_interop::OptimisticBlock
 pub(crate) async fn consolidate_dependencies<P, H, Evm>(
     oracle: Arc<CachingOracle<P, H>>,
     mut boot: BootInfo,
     evm_factory: Evm,
-) -> Result<(), FaultProofProgramError>
+) -> Result<PreState, FaultProofProgramError>
 where
     P: PreimageOracleClient + Send + Sync + Debug + Clone,
     H: HintWriterClient + Send + Sync + Debug + Clone,
@@ -118,23 +119,11 @@ where
         .agreed_pre_state
         .transition(None)
         .ok_or(FaultProofProgramError::StateTransitionFailed)?;
-    let post_commitment = post.hash();
-
-    // Ensure that the post-state matches the claimed post-state.
-    if post_commitment != boot.claimed_post_state {
-        error!(
-            target: "client_interop",
-            claimed = ?boot.claimed_post_state,
-            actual = ?post_commitment,
-            "Post state validation failed",
-        );
-        return Err(FaultProofProgramError::InvalidClaim(boot.claimed_post_state, post_commitment));
-    }
 
     info!(
         target: "client_interop",
-        root = ?boot.claimed_post_state,
-        "Super root validation succeeded"
+        timestamp = post.timestamp(),
+        "Consolidated super root"
     );
-    Ok(())
+    Ok(post)
 }
@@ -1,6 +1,8 @@
 //! Multi-chain, interoperable fault proof program entrypoint.
 
 use alloc::{boxed::Box, sync::Arc};
+use alloy_evm::{EvmFactory, FromRecoveredTx, FromTxWithEncoded};
+use alloy_op_evm::block::OpTxEnv;
 use alloy_primitives::B256;
 use consolidate::consolidate_dependencies;
 use core::{cmp::Ordering, fmt::Debug};
@@ -10,8 +12,12 @@ use kona_executor::ExecutorError;
 use kona_preimage::{HintWriterClient, PreimageOracleClient};
 use kona_proof::{CachingOracle, errors::OracleProviderError};
 use kona_proof_interop::{
-    BootInfo, ConsolidationError, PreState, TRANSITION_STATE_MAX_STEPS, boot::BootstrapError,
+    BootInfo, ConsolidationError, INVALID_TRANSITION_HASH, PreState, TRANSITION_STATE_MAX_STEPS,
+    boot::BootstrapError,
 };
+use op_alloy_consensus::OpTxEnvelope;
+use op_revm::OpSpecId;
+use revm::context::BlockEnv;
 use thiserror::Error;
 use tracing::{error, info};
 use transition::sub_transition;
@@ -53,8 +59,42 @@ pub enum FaultProofProgramError {
 
 /// Executes the interop fault proof program with the given [PreimageOracleClient] and
 /// [HintWriterClient].
+///
+/// The program executes a single step of the superchain state transition, as claimed in the fault
+/// dispute game, and checks that the resulting post-state matches the claimed post-state.
 #[inline]
 pub async fn run<P, H>(oracle_client: P, hint_client: H) -> Result<(), FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+{
+    execute(oracle_client, hint_client, false).await
+}
+
+/// Executes the interop fault proof program over a claim covering several steps of the superchain
+/// state transition.
+///
+/// The number of steps is read from a local key only served by the host, so this entrypoint may
+/// only be used by a natively running program.
+#[inline]
+pub async fn run_multi_step<P, H>(
+    oracle_client: P,
+    hint_client: H,
+) -> Result<(), FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+{
+    execute(oracle_client, hint_client, true).await
+}
+
+/// Executes the steps of the superchain state transition covered by the claim, and checks that
+/// the resulting post-state matches the claimed post-state.
+async fn execute<P, H>(
+    oracle_client: P,
+    hint_client: H,
+    multi_step: bool,
+) -> Result<(), FaultProofProgramError>
 where
     P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
     H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
@@ -64,7 +104,12 @@ where
     // Instantiate the oracle and bootstrap the program from local inputs.
     let oracle =
         Arc::new(CachingOracle::new(ORACLE_LRU_SIZE, oracle_client.clone(), hint_client.clone()));
-    let boot = match BootInfo::load(oracle.as_ref()).await {
+    let boot = if multi_step {
+        BootInfo::load_multi_step(oracle.as_ref()).await
+    } else {
+        BootInfo::load(oracle.as_ref()).await
+    };
+    let mut boot = match boot {
         Ok(boot) => boot,
         Err(BootstrapError::InvalidToInvalid) => {
             info!(target: "client_interop", "No-op transition, short-circuiting.");
@@ -78,40 +123,93 @@ where
 
     let evm_factory = FpvmOpEvmFactory::new(hint_client, oracle_client);
 
-    // Load in the agreed pre-state from the preimage oracle in order to determine the active
-    // sub-problem.
-    match boot.agreed_pre_state {
-        PreState::SuperRoot(ref super_root) => {
+    // Execute the steps of the superchain state transition covered by the claim, starting from
+    // the agreed pre-state.
+    let mut post_state_commitment = boot.agreed_pre_state_commitment;
+    for step in 0..boot.claimed_steps {
+        match boot.agreed_pre_state {
             // If the claimed L2 block timestamp is less than the super root timestamp, the
-            // post-state must be the agreed pre-state to accommodate trace extension.
-            if super_root.timestamp >= boot.claimed_l2_timestamp {
-                if boot.agreed_pre_state_commitment == boot.claimed_post_state {
-                    return Ok(());
-                } else {
-                    return Err(FaultProofProgramError::InvalidClaim(
-                        boot.agreed_pre_state_commitment,
-                        boot.claimed_post_state,
-                    ));
-                }
+            // post-state must be the pre-state to accommodate trace extension.
+            PreState::SuperRoot(ref super_root)
+                if super_root.timestamp >= boot.claimed_l2_timestamp =>
+            {
+                break;
             }
-
-            // If the pre-state is a super root, the first sub-problem is always selected.
-            sub_transition(oracle, boot, evm_factory).await
-        }
-        PreState::TransitionState(ref transition_state) => {
             // If the claimed L2 block timestamp is less than the prestate timestamp, the
             // claim must be invalid.
-            if transition_state.pre_state.timestamp >= boot.claimed_l2_timestamp {
+            PreState::TransitionState(ref transition_state)
+                if transition_state.pre_state.timestamp >= boot.claimed_l2_timestamp =>
+            {
                 return Err(FaultProofProgramError::InvalidClaim(
                     boot.agreed_pre_state_commitment,
                     boot.claimed_post_state,
                 ));
             }
+            _ => {}
+        }
+
+        let Some(post_state) =
+            transition_step(oracle.clone(), boot.clone(), evm_factory.clone()).await?
+        else {
+            // The data source was exhausted, and the state transitions to the invalid state.
+            post_state_commitment = INVALID_TRANSITION_HASH;
+            break;
+        };
+
+        post_state_commitment = post_state.hash();
+        info!(
+            target: "client_interop",
+            step,
+            timestamp = post_state.timestamp(),
+            "Transitioned to post-state with commitment {post_state_commitment}"
+        );
+        boot.agreed_pre_state = post_state;
+    }
+
+    if post_state_commitment != boot.claimed_post_state {
+        error!(
+            target: "client_interop",
+            "Failed to validate post-state. Expected post-state commitment: {expected}, actual: {actual}",
+            expected = boot.claimed_post_state,
+            actual = post_state_commitment
+        );
+        return Err(FaultProofProgramError::InvalidClaim(
+            boot.claimed_post_state,
+            post_state_commitment,
+        ));
+    }
+
+    info!(
+        target: "client_interop",
+        "Successfully validated post-state claim with commitment {post_state_commitment}"
+    );
+    Ok(())
+}
 
-            // If the pre-state is a transition state, the sub-problem is selected based on the
-            // current step.
+/// Executes a single step of the superchain state transition from the agreed pre-state of the
+/// [BootInfo], returning the post-state, or [None] if the state transitions to the invalid state.
+async fn transition_step<P, H, Evm>(
+    oracle: Arc<CachingOracle<P, H>>,
+    boot: BootInfo,
+    evm_factory: Evm,
+) -> Result<Option<PreState>, FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone,
+    H: HintWriterClient + Send + Sync + Debug + Clone,
+    Evm: EvmFactory<Spec = OpSpecId, BlockEnv = BlockEnv> + Send + Sync + Debug + Clone + 'static,
+    <Evm as EvmFactory>::Tx:
+        FromTxWithEncoded<OpTxEnvelope> + FromRecoveredTx<OpTxEnvelope> + OpTxEnv,
+{
+    match boot.agreed_pre_state {
+        // If the pre-state is a super root, the first sub-problem is always selected.
+        PreState::SuperRoot(_) => sub_transition(oracle, boot, evm_factory).await,
+        // If the pre-state is a transition state, the sub-problem is selected based on the
+        // current step.
+        PreState::TransitionState(ref transition_state) => {
             match transition_state.step.cmp(&TRANSITION_STATE_MAX_STEPS) {
-                Ordering::Equal => consolidate_dependencies(oracle, boot, evm_factory).await,
+                Ordering::Equal => {
+                    consolidate_dependencies(oracle, boot, evm_factory).await.map(Some)
+                }
                 Ordering::Less => sub_transition(oracle, boot, evm_factory).await,
                 Ordering::Greater => {
                     error!(
@@ -6,7 +6,6 @@ use alloc::{boxed::Box, sync::Arc};
 use alloy_consensus::Sealed;
 use alloy_evm::{EvmFactory, FromRecoveredTx, FromTxWithEncoded};
 use alloy_op_evm::block::OpTxEnv;
-use alloy_primitives::B256;
 use core::fmt::Debug;
 use kona_derive::{EthereumDataSource, PipelineError, PipelineErrorKind};
 use kona_driver::{Driver, DriverError};
@@ -19,19 +18,19 @@ use kona_proof::{
     l2::OracleL2ChainProvider,
     sync::new_oracle_pipeline_cursor,
 };
-use kona_proof_interop::{BootInfo, INVALID_TRANSITION_HASH, OptimisticBlock, PreState};
+use kona_proof_interop::{BootInfo, OptimisticBlock, PreState};
 use op_alloy_consensus::OpTxEnvelope;
 use op_revm::OpSpecId;
 use revm::context::BlockEnv;
 use tracing::{error, info, warn};
 
 /// Executes a sub-transition of the interop proof with the given [PreimageOracleClient] and
-/// [HintWriterClient].
+/// [HintWriterClient], returning the post-state, or [None] if the data source was exhausted.
 pub(crate) async fn sub_transition<P, H, Evm>(
     oracle: Arc<CachingOracle<P, H>>,
     boot: BootInfo,
     evm_factory: Evm,
-) -> Result<(), FaultProofProgramError>
+) -> Result<Option<PreState>, FaultProofProgramError>
 where
     P: PreimageOracleClient + Send + Sync + Debug + Clone,
     H: HintWriterClient + Send + Sync + Debug + Clone,
@@ -47,7 +46,7 @@ where
                 "No derivation/execution required, transition state is already saturated."
             );
 
-            return transition_and_check(boot.agreed_pre_state, None, boot.claimed_post_state);
+            return transition(boot.agreed_pre_state, None).map(Some);
         }
     }
 
@@ -90,11 +89,7 @@ where
             .active_l2_output_root()
             .ok_or(FaultProofProgramError::StateTransitionFailed)?;
         let optimistic_block = OptimisticBlock::new(safe_head.hash(), active_root.output_root);
-        return transition_and_check(
-            boot.agreed_pre_state,
-            Some(optimistic_block),
-            boot.claimed_post_state,
-        );
+        return transition(boot.agreed_pre_state, Some(optimistic_block)).map(Some);
     }
 
     // Create a new derivation driver with the given boot information and oracle.
@@ -134,19 +129,7 @@ where
     match driver.advance_to_target(rollup_config.as_ref(), Some(disputed_l2_block_number)).await {
         Ok((safe_head, output_root)) => {
             let optimistic_block = OptimisticBlock::new(safe_head.block_info.hash, output_root);
-            transition_and_check(
-                boot.agreed_pre_state,
-                Some(optimistic_block),
-                boot.claimed_post_state,
-            )?;
-
-            info!(
-                target: "interop_client",
-                "Successfully validated progressed transition state claim with commitment {post_state_commitment}",
-                post_state_commitment = boot.claimed_post_state
-            );
-
-            Ok(())
+            transition(boot.agreed_pre_state, Some(optimistic_block)).map(Some)
         }
         Err(DriverError::Pipeline(PipelineErrorKind::Critical(PipelineError::EndOfSource))) => {
             warn!(
@@ -154,12 +137,7 @@ where
                 "Exhausted data source; Transitioning to invalid state."
             );
 
-            (boot.claimed_post_state == INVALID_TRANSITION_HASH).then_some(()).ok_or(
-                FaultProofProgramError::InvalidClaim(
-                    INVALID_TRANSITION_HASH,
-                    boot.claimed_post_state,
-                ),
-            )
+            Ok(None)
         }
         Err(e) => {
             error!(
@@ -172,18 +150,15 @@ where
     }
 }
 
-/// Transitions the [PreState] with the given [OptimisticBlock] and checks if the resulting state
-/// commitment matches the expected commitment.
-fn transition_and_check(
+/// Transitions the [PreState] with the given [OptimisticBlock], returning the post-state.
+fn transition(
     pre_state: PreState,
     optimistic_block: Option<OptimisticBlock>,
-    expected_post_state: B256,
-) -> Result<(), FaultProofProgramError> {
+) -> Result<PreState, FaultProofProgramError> {
     let did_append = optimistic_block.is_some();
     let post_state = pre_state
         .transition(optimistic_block)
         .ok_or(FaultProofProgramError::StateTransitionFailed)?;
-    let post_state_commitment = post_state.hash();
 
     if did_append {
         info!(
@@ -192,24 +167,5 @@ fn transition_and_check(
         );
     }
 
-    if post_state_commitment != expected_post_state {
-        error!(
-            target: "interop_client",
-            "Failed to validate progressed transition state. Expected post-state commitment: {expected}, actual: {actual}",
-            expected = expected_post_state,
-            actual = post_state_commitment
-        );
-
-        return Err(FaultProofProgramError::InvalidClaim(
-            expected_post_state,
-            post_state_commitment,
-        ));
-    }
-
-    info!(
-        target: "interop_client",
-        "Successfully validated progressed transition state with commitment {post_state_commitment}",
-    );
-
-    Ok(())
+    Ok(post_state)
 }
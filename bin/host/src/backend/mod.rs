@@ -6,4 +6,7 @@ pub use offline::OfflineHostBackend;
 mod online;
 pub use online::{HintHandler, OnlineHostBackend, OnlineHostBackendCfg};
 
+mod remote;
+pub use remote::RemoteHostBackend;
+
 pub(crate) mod util;
@@ -0,0 +1,60 @@
+//! Contains the [RemoteHostBackend] definition.
+
+use async_trait::async_trait;
+use kona_preimage::{
+    Channel, HintRouter, HintWriter, HintWriterClient, OracleReader, PreimageFetcher, PreimageKey,
+    PreimageOracleClient, errors::PreimageOracleResult,
+};
+use tokio::sync::Mutex;
+use tracing::trace;
+
+/// The [RemoteHostBackend] is a [HintRouter] and [PreimageFetcher] that forwards hints and
+/// preimage requests to a remote preimage server, such as another host serving preimages over a
+/// [SocketChannel]. Hints and preimage requests are forwarded one at a time.
+///
+/// [SocketChannel]: crate::SocketChannel
+#[derive(Debug)]
+pub struct RemoteHostBackend<C> {
+    /// The hint channel of the remote preimage server.
+    hint_writer: Mutex<HintWriter<C>>,
+    /// The preimage channel of the remote preimage server.
+    oracle_reader: Mutex<OracleReader<C>>,
+}
+
+impl<C> RemoteHostBackend<C>
+where
+    C: Channel + Send + Sync,
+{
+    /// Creates a new [RemoteHostBackend] from the hint and preimage channels of a remote preimage
+    /// server.
+    pub fn new(hint: C, preimage: C) -> Self {
+        Self {
+            hint_writer: Mutex::new(HintWriter::new(hint)),
+            oracle_reader: Mutex::new(OracleReader::new(preimage)),
+        }
+    }
+}
+
+#[async_trait]
+impl<C> HintRouter for RemoteHostBackend<C>
+where
+    C: Channel + Send + Sync,
+{
+    /// Forwards the hint to the remote preimage server.
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        trace!(target: "host_backend", "Forwarding hint: {hint}");
+        self.hint_writer.lock().await.write(&hint).await
+    }
+}
+
+#[async_trait]
+impl<C> PreimageFetcher for RemoteHostBackend<C>
+where
+    C: Channel + Send + Sync,
+{
+    /// Requests the preimage for the given key from the remote preimage server.
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        trace!(target: "host_backend", "Requesting pre-image from remote server. Key: {key}");
+        self.oracle_reader.lock().await.get(key).await
+    }
+}
@@ -3,8 +3,8 @@
 use super::{InteropHintHandler, InteropLocalInputs};
 use crate::{
     DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::rpc_provider, server::PreimageServerError,
+    OnlineHostBackendCfg, PreimageServer, RemoteHostBackend, SharedKeyValueStore, SocketChannel,
+    SocketListener, SplitKeyValueStore, eth::rpc_provider, server::PreimageServerError,
 };
 use alloy_primitives::{B256, Bytes};
 use alloy_provider::{Provider, RootProvider};
@@ -19,11 +19,12 @@ use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
-use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, str::FromStr, sync::Arc};
 use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::{error, info};
 
 /// The interop host application.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
@@ -44,6 +45,11 @@ pub struct InteropHost {
     /// Claimed L2 timestamp, corresponding to the L2 post-state.
     #[arg(long, visible_alias = "l2-timestamp", env)]
     pub claimed_l2_timestamp: u64,
+    /// Number of steps of the superchain state transition from the agreed L2 pre-state to the
+    /// claimed L2 post-state. Claims of the fault dispute game cover a single step, and claims
+    /// over several steps may only be validated in native mode.
+    #[arg(long, visible_alias = "l2-steps", default_value_t = 1, requires = "native", env)]
+    pub claimed_l2_steps: u64,
     /// Addresses of L2 JSON-RPC endpoints to use (eth and debug namespace required).
     #[arg(
         long,
@@ -78,6 +84,7 @@ pub struct InteropHost {
         long,
         visible_alias = "db",
         required_unless_present_all = ["l2_node_addresses", "l1_node_address", "l1_beacon_address"],
+        required_unless_present_any = ["remote_oracle"],
         env
     )]
     pub data_dir: Option<PathBuf>,
@@ -88,6 +95,26 @@ pub struct InteropHost {
     /// host will run the client program in the host process.
     #[arg(long, conflicts_with = "native", required_unless_present = "native")]
     pub server: bool,
+    /// Address to serve pre-images over TCP at, instead of over the FPVM file descriptors. Clients
+    /// are served one at a time, each opening a hint and a pre-image connection. Requires
+    /// `--socket-secret`.
+    #[arg(long, requires_all = ["server", "socket_secret"], env)]
+    pub listen_addr: Option<SocketAddr>,
+    /// Address of a remote pre-image server to forward hints and pre-image requests to over TCP,
+    /// instead of serving them from the data directory or the RPC endpoints. Requires
+    /// `--socket-secret`.
+    #[arg(
+        long,
+        requires = "socket_secret",
+        conflicts_with_all = ["l2_node_addresses", "l1_node_address", "l1_beacon_address", "data_dir"],
+        env
+    )]
+    pub remote_oracle: Option<SocketAddr>,
+    /// Secret shared by a pre-image server listening over TCP and its clients, which must send it
+    /// when connecting. Required with `--listen-addr` and `--remote-oracle`. The secret is sent in
+    /// plaintext.
+    #[arg(long, env)]
+    pub socket_secret: Option<B256>,
     /// Path to rollup configs. If provided, the host will use this config instead of attempting to
     /// look up the configs in the superchain registry.
     /// The rollup configs should be stored as serde-JSON serialized files.
@@ -133,6 +160,10 @@ impl InteropHost {
     /// Starts the [InteropHost] application.
     pub async fn start(self) -> Result<(), InteropHostError> {
         if self.server {
+            if let Some(addr) = self.listen_addr {
+                return self.start_socket_server(addr).await;
+            }
+
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
@@ -152,6 +183,22 @@ impl InteropHost {
     where
         C: Channel + Send + Sync + 'static,
     {
+        if let Some(addr) = self.remote_oracle {
+            let (remote_hint, remote_preimage) =
+                SocketChannel::connect_session(addr, self.socket_secret()?).await?;
+            let backend = RemoteHostBackend::new(remote_hint, remote_preimage);
+            return Ok(task::spawn(async {
+                PreimageServer::new(
+                    OracleServer::new(preimage),
+                    HintReader::new(hint),
+                    Arc::new(backend),
+                )
+                .start()
+                .await
+                .map_err(InteropHostError::from)
+            }));
+        }
+
         let kv_store = self.create_key_value_store()?;
 
         let task_handle = if self.is_offline() {
@@ -190,6 +237,25 @@ impl InteropHost {
         Ok(task_handle)
     }
 
+    /// Returns the secret shared with the clients or server of the pre-image socket.
+    fn socket_secret(&self) -> Result<B256, InteropHostError> {
+        self.socket_secret.ok_or(InteropHostError::Other("Socket secret must be set"))
+    }
+
+    /// Starts the preimage server, serving the clients connecting to the given address one at a
+    /// time.
+    async fn start_socket_server(&self, addr: SocketAddr) -> Result<(), InteropHostError> {
+        let mut listener = SocketListener::bind(addr, self.socket_secret()?).await?;
+        info!(target: "host", %addr, "Serving pre-images over TCP");
+
+        loop {
+            let (hint, preimage) = listener.accept_session().await?;
+            if let Err(e) = self.start_server(hint, preimage).await?.await? {
+                error!(target: "host", "Failed to serve client: {e}");
+            }
+        }
+    }
+
     /// Starts the host in native mode, running both the client and preimage server in the same
     /// process.
     async fn start_native(&self) -> Result<(), InteropHostError> {
@@ -197,7 +263,7 @@ impl InteropHost {
         let preimage = BidirectionalChannel::new()?;
 
         let server_task = self.start_server(hint.host, preimage.host).await?;
-        let client_task = task::spawn(kona_client::interop::run(
+        let client_task = task::spawn(kona_client::interop::run_multi_step(
             OracleReader::new(preimage.client),
             HintWriter::new(hint.client),
         ));
@@ -348,9 +414,92 @@ mod tests {
         assert_eq!(host.agreed_l2_pre_state, Bytes::from(hash.0));
         assert_eq!(host.claimed_l2_post_state, hash);
         assert_eq!(host.claimed_l2_timestamp, 0);
+        assert_eq!(host.claimed_l2_steps, 1);
         assert!(host.native);
     }
 
+    #[test]
+    fn test_parse_interop_claimed_steps() {
+        let hash = b256!("ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68");
+        let host = InteropHost::parse_from([
+            "interop-host",
+            "--l1-head",
+            &hash.to_string(),
+            "--l2-pre-state",
+            "ff",
+            "--claimed-l2-post-state",
+            &hash.to_string(),
+            "--claimed-l2-timestamp",
+            "10",
+            "--l2-steps",
+            "256",
+            "--native",
+            "--l2-node-addresses",
+            "http://localhost:8545",
+            "--l1-node-address",
+            "http://localhost:8546",
+            "--l1-beacon-address",
+            "http://localhost:8547",
+        ]);
+        assert_eq!(host.claimed_l2_steps, 256);
+
+        // The number of steps is served by the host to a natively running client only.
+        assert!(
+            InteropHost::try_parse_from([
+                "interop-host",
+                "--l1-head",
+                &hash.to_string(),
+                "--l2-pre-state",
+                "ff",
+                "--claimed-l2-post-state",
+                &hash.to_string(),
+                "--claimed-l2-timestamp",
+                "10",
+                "--l2-steps",
+                "256",
+                "--server",
+                "--data-dir",
+                "dummy",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_interop_socket_secret_required() {
+        let hash = b256!("ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68");
+        let args = |socket: &[&str]| {
+            let mut args = vec![
+                "interop-host".to_string(),
+                "--l1-head".to_string(),
+                hash.to_string(),
+                "--l2-pre-state".to_string(),
+                "ff".to_string(),
+                "--claimed-l2-post-state".to_string(),
+                hash.to_string(),
+                "--claimed-l2-timestamp".to_string(),
+                "0".to_string(),
+                "--server".to_string(),
+            ];
+            args.extend(socket.iter().map(|arg| arg.to_string()));
+            args
+        };
+
+        let secret = hash.to_string();
+        for socket in [
+            ["--data-dir", "dummy", "--listen-addr", "127.0.0.1:7300"].as_slice(),
+            ["--remote-oracle", "127.0.0.1:7300"].as_slice(),
+        ] {
+            assert!(InteropHost::try_parse_from(args(socket)).is_err());
+
+            let host = InteropHost::try_parse_from(args(
+                &[socket, &["--socket-secret", &secret]].concat(),
+            ))
+            .unwrap();
+            assert_eq!(host.socket_secret, Some(hash));
+        }
+    }
+
     #[test]
     fn test_parse_interop_hex_bytes() {
         let hash = b256!("ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68");
@@ -10,7 +10,12 @@ pub use kv::{
 };
 
 mod backend;
-pub use backend::{HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg};
+pub use backend::{
+    HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg, RemoteHostBackend,
+};
+
+mod socket;
+pub use socket::{HANDSHAKE_LENGTH, SocketChannel, SocketChannelKind, SocketListener};
 
 pub mod eth;
 
@@ -3,8 +3,8 @@
 use super::{SingleChainHintHandler, SingleChainLocalInputs};
 use crate::{
     DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::rpc_provider, server::PreimageServerError,
+    OnlineHostBackendCfg, PreimageServer, RemoteHostBackend, SharedKeyValueStore, SocketChannel,
+    SocketListener, SplitKeyValueStore, eth::rpc_provider, server::PreimageServerError,
 };
 use alloy_primitives::B256;
 use alloy_provider::RootProvider;
@@ -19,11 +19,12 @@ use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
-use std::{path::PathBuf, sync::Arc};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
 use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::{error, info};
 
 /// The host binary CLI application arguments.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
@@ -77,6 +78,7 @@ pub struct SingleChainHost {
         long,
         visible_alias = "db",
         required_unless_present_all = ["l2_node_address", "l1_node_address", "l1_beacon_address"],
+        required_unless_present_any = ["remote_oracle"],
         env
     )]
     pub data_dir: Option<PathBuf>,
@@ -87,6 +89,26 @@ pub struct SingleChainHost {
     /// host will run the client program in the host process.
     #[arg(long, conflicts_with = "native", required_unless_present = "native")]
     pub server: bool,
+    /// Address to serve pre-images over TCP at, instead of over the FPVM file descriptors. Clients
+    /// are served one at a time, each opening a hint and a pre-image connection. Requires
+    /// `--socket-secret`.
+    #[arg(long, requires_all = ["server", "socket_secret"], env)]
+    pub listen_addr: Option<SocketAddr>,
+    /// Address of a remote pre-image server to forward hints and pre-image requests to over TCP,
+    /// instead of serving them from the data directory or the RPC endpoints. Requires
+    /// `--socket-secret`.
+    #[arg(
+        long,
+        requires = "socket_secret",
+        conflicts_with_all = ["l2_node_address", "l1_node_address", "l1_beacon_address", "data_dir"],
+        env
+    )]
+    pub remote_oracle: Option<SocketAddr>,
+    /// Secret shared by a pre-image server listening over TCP and its clients, which must send it
+    /// when connecting. Required with `--listen-addr` and `--remote-oracle`. The secret is sent in
+    /// plaintext.
+    #[arg(long, env)]
+    pub socket_secret: Option<B256>,
     /// The L2 chain ID of a supported chain. If provided, the host will look for the corresponding
     /// rollup config in the superchain registry.
     #[arg(
@@ -146,6 +168,10 @@ impl SingleChainHost {
     /// Starts the [SingleChainHost] application.
     pub async fn start(self) -> Result<(), SingleChainHostError> {
         if self.server {
+            if let Some(addr) = self.listen_addr {
+                return self.start_socket_server(addr).await;
+            }
+
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
@@ -165,6 +191,22 @@ impl SingleChainHost {
     where
         C: Channel + Send + Sync + 'static,
     {
+        if let Some(addr) = self.remote_oracle {
+            let (remote_hint, remote_preimage) =
+                SocketChannel::connect_session(addr, self.socket_secret()?).await?;
+            let backend = RemoteHostBackend::new(remote_hint, remote_preimage);
+            return Ok(task::spawn(async {
+                PreimageServer::new(
+                    OracleServer::new(preimage),
+                    HintReader::new(hint),
+                    Arc::new(backend),
+                )
+                .start()
+                .await
+                .map_err(SingleChainHostError::from)
+            }));
+        }
+
         let kv_store = self.create_key_value_store()?;
 
         let task_handle = if self.is_offline() {
@@ -203,6 +245,25 @@ impl SingleChainHost {
         Ok(task_handle)
     }
 
+    /// Returns the secret shared with the clients or server of the pre-image socket.
+    fn socket_secret(&self) -> Result<B256, SingleChainHostError> {
+        self.socket_secret.ok_or(SingleChainHostError::Other("Socket secret must be set"))
+    }
+
+    /// Starts the preimage server, serving the clients connecting to the given address one at a
+    /// time.
+    async fn start_socket_server(&self, addr: SocketAddr) -> Result<(), SingleChainHostError> {
+        let mut listener = SocketListener::bind(addr, self.socket_secret()?).await?;
+        info!(target: "host", %addr, "Serving pre-images over TCP");
+
+        loop {
+            let (hint, preimage) = listener.accept_session().await?;
+            if let Err(e) = self.start_server(hint, preimage).await?.await? {
+                error!(target: "host", "Failed to serve client: {e}");
+            }
+        }
+    }
+
     /// Starts the host in native mode, running both the client and preimage server in the same
     /// process.
     async fn start_native(&self) -> Result<(), SingleChainHostError> {
@@ -366,8 +427,92 @@ mod test {
                 .as_slice(),
                 true,
             ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--listen-addr",
+                    "127.0.0.1:7300",
+                    "--socket-secret",
+                    zero_hash_str,
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--remote-oracle",
+                    "127.0.0.1:7300",
+                    "--socket-secret",
+                    zero_hash_str,
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--remote-oracle",
+                    "127.0.0.1:7300",
+                    "--socket-secret",
+                    zero_hash_str,
+                ]
+                .as_slice(),
+                true,
+            ),
             // invalid
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--listen-addr",
+                    "127.0.0.1:7300",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                ["--native", "--l2-chain-id", "0", "--remote-oracle", "127.0.0.1:7300"].as_slice(),
+                false,
+            ),
             (["--server", "--native", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--listen-addr",
+                    "127.0.0.1:7300",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--remote-oracle",
+                    "127.0.0.1:7300",
+                ]
+                .as_slice(),
+                false,
+            ),
             (["--l2-chain-id", "0", "--rollup-config-path", "dummy", "--server"].as_slice(), false),
             (["--server"].as_slice(), false),
             (["--native"].as_slice(), false),
@@ -0,0 +1,390 @@
+//! This module contains the [SocketChannel], a [Channel] for serving preimages over TCP, and the
+//! [SocketListener] accepting them.
+//!
+//! A client of a preimage socket server opens two connections to it: one for hints, and one for
+//! preimage requests. Each connection starts with a handshake of [HANDSHAKE_LENGTH] bytes: the
+//! [SocketChannelKind] of the connection, the 32 byte ID of the client's session, and the 32 byte
+//! secret of the server. The server pairs the hint and preimage connections sharing a session ID,
+//! after which they carry the same hint and preimage oracle protocol as the file descriptors of
+//! the FPVMs.
+//!
+//! The secret is sent in plaintext, and only keeps out clients that do not know it. Servers
+//! reachable from untrusted networks should be placed behind an encrypted tunnel.
+
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use kona_preimage::{
+    Channel,
+    errors::{ChannelError, ChannelResult},
+};
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        TcpListener, TcpStream, ToSocketAddrs,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::Mutex,
+    task::JoinSet,
+    time::timeout,
+};
+use tracing::{debug, warn};
+
+/// The length of the handshake starting each connection: the connection kind, the session ID and
+/// the server secret.
+pub const HANDSHAKE_LENGTH: usize = 1 + 32 + 32;
+
+/// The time a client has to complete the handshake of a connection.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum number of connections whose handshake is being read.
+const MAX_PENDING_HANDSHAKES: usize = 64;
+
+/// The maximum number of sessions waiting for their second connection.
+const MAX_PENDING_SESSIONS: usize = 64;
+
+/// The time a session waits for its second connection before being dropped.
+const PENDING_SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The kind of a connection to a preimage socket server, sent by the client as the first byte of
+/// the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SocketChannelKind {
+    /// The connection carries hints.
+    Hint = 0,
+    /// The connection carries preimage requests.
+    Preimage = 1,
+}
+
+impl TryFrom<u8> for SocketChannelKind {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Hint),
+            1 => Ok(Self::Preimage),
+            _ => Err(value),
+        }
+    }
+}
+
+/// A [Channel] over a TCP connection to or from a preimage socket server.
+#[derive(Debug, Clone)]
+pub struct SocketChannel {
+    /// The read half of the connection.
+    reader: Arc<Mutex<OwnedReadHalf>>,
+    /// The write half of the connection.
+    writer: Arc<Mutex<OwnedWriteHalf>>,
+}
+
+impl SocketChannel {
+    /// Creates a new [SocketChannel] over the given connection.
+    pub fn new(stream: TcpStream) -> Self {
+        let (reader, writer) = stream.into_split();
+        Self { reader: Arc::new(Mutex::new(reader)), writer: Arc::new(Mutex::new(writer)) }
+    }
+
+    /// Connects to the preimage socket server at the given address, opening a channel of the
+    /// given kind in the given session.
+    pub async fn connect(
+        addr: SocketAddr,
+        kind: SocketChannelKind,
+        session: B256,
+        secret: B256,
+    ) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(addr).await?;
+        stream.set_nodelay(true)?;
+
+        let mut handshake = [0u8; HANDSHAKE_LENGTH];
+        handshake[0] = kind as u8;
+        handshake[1..33].copy_from_slice(session.as_slice());
+        handshake[33..].copy_from_slice(secret.as_slice());
+        stream.write_all(&handshake).await?;
+
+        Ok(Self::new(stream))
+    }
+
+    /// Opens a new session with the preimage socket server at the given address, returning the
+    /// hint and preimage channels.
+    pub async fn connect_session(addr: SocketAddr, secret: B256) -> io::Result<(Self, Self)> {
+        let session = B256::random();
+        let hint = Self::connect(addr, SocketChannelKind::Hint, session, secret).await?;
+        let preimage = Self::connect(addr, SocketChannelKind::Preimage, session, secret).await?;
+        Ok((hint, preimage))
+    }
+}
+
+/// The listener of a preimage socket server, pairing the hint and preimage connections of each
+/// client by their session ID.
+#[derive(Debug)]
+pub struct SocketListener {
+    /// The TCP listener.
+    listener: TcpListener,
+    /// The secret clients must send in their handshake.
+    secret: B256,
+    /// The connections whose handshake is being read.
+    handshakes: JoinSet<Option<Handshake>>,
+    /// The first connection of each session waiting for its second connection, with the time it
+    /// was accepted at.
+    pending: HashMap<B256, (SocketChannelKind, SocketChannel, Instant)>,
+}
+
+/// A connection to a [SocketListener] that completed a valid handshake.
+#[derive(Debug)]
+struct Handshake {
+    /// The address of the client.
+    peer: SocketAddr,
+    /// The kind of the connection.
+    kind: SocketChannelKind,
+    /// The ID of the client's session.
+    session: B256,
+    /// The channel over the connection.
+    channel: SocketChannel,
+}
+
+impl Handshake {
+    /// Reads the handshake of a connection, returning [None] if it is invalid or not sent in
+    /// time.
+    async fn read(mut stream: TcpStream, peer: SocketAddr, secret: B256) -> Option<Self> {
+        stream.set_nodelay(true).ok()?;
+
+        let mut handshake = [0u8; HANDSHAKE_LENGTH];
+        match timeout(HANDSHAKE_TIMEOUT, stream.read_exact(&mut handshake)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => {
+                debug!(target: "host_socket", %peer, "Failed to read handshake: {e}");
+                return None;
+            }
+            Err(_) => {
+                debug!(target: "host_socket", %peer, "Timed out reading handshake");
+                return None;
+            }
+        }
+
+        let kind = match SocketChannelKind::try_from(handshake[0]) {
+            Ok(kind) => kind,
+            Err(kind) => {
+                warn!(target: "host_socket", %peer, kind, "Dropping connection of unknown kind");
+                return None;
+            }
+        };
+        // Compare the secret in constant time.
+        let mismatch =
+            handshake[33..].iter().zip(secret.iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+        if mismatch != 0 {
+            warn!(target: "host_socket", %peer, "Dropping connection with an invalid secret");
+            return None;
+        }
+
+        let session = B256::from_slice(&handshake[1..33]);
+        Some(Self { peer, kind, session, channel: SocketChannel::new(stream) })
+    }
+}
+
+impl SocketListener {
+    /// Binds a [SocketListener] to the given address, accepting clients that send the given
+    /// secret.
+    pub async fn bind(addr: impl ToSocketAddrs, secret: B256) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr).await?,
+            secret,
+            handshakes: JoinSet::new(),
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Returns the local address the listener is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections until both the hint and preimage connections of a session are open,
+    /// returning the hint and preimage channels of the session.
+    ///
+    /// Handshakes are read concurrently, so that clients that are slow to send theirs don't hold
+    /// up the others. Connections with an invalid handshake are dropped. The first connection of
+    /// other sessions is kept until their second connection is accepted, or until it times out.
+    pub async fn accept_session(&mut self) -> io::Result<(SocketChannel, SocketChannel)> {
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    let (stream, peer) = accepted?;
+                    if self.handshakes.len() >= MAX_PENDING_HANDSHAKES {
+                        warn!(
+                            target: "host_socket",
+                            %peer,
+                            "Dropping connection, too many handshakes are pending"
+                        );
+                        continue;
+                    }
+                    self.handshakes.spawn(Handshake::read(stream, peer, self.secret));
+                }
+                Some(joined) = self.handshakes.join_next() => {
+                    let Ok(Some(handshake)) = joined else {
+                        continue;
+                    };
+                    if let Some(session) = self.pair(handshake) {
+                        return Ok(session);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pairs a connection with the pending connection of its session, returning the hint and
+    /// preimage channels of the session once both are open.
+    fn pair(&mut self, handshake: Handshake) -> Option<(SocketChannel, SocketChannel)> {
+        let Handshake { peer, kind, session, channel } = handshake;
+        self.pending.retain(|_, (_, _, accepted)| accepted.elapsed() < PENDING_SESSION_TIMEOUT);
+
+        match self.pending.remove(&session) {
+            Some((pending_kind, pending, _)) if pending_kind != kind => {
+                return Some(match kind {
+                    SocketChannelKind::Hint => (channel, pending),
+                    SocketChannelKind::Preimage => (pending, channel),
+                });
+            }
+            Some(_) => {
+                warn!(
+                    target: "host_socket",
+                    %peer,
+                    %session,
+                    "Dropping session with two connections of the same kind"
+                );
+            }
+            None if self.pending.len() >= MAX_PENDING_SESSIONS => {
+                warn!(
+                    target: "host_socket",
+                    %peer,
+                    "Dropping connection, too many sessions are pending"
+                );
+            }
+            None => {
+                self.pending.insert(session, (kind, channel, Instant::now()));
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl Channel for SocketChannel {
+    async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.reader.lock().await.read(buf).await.map_err(|_| ChannelError::Closed)
+    }
+
+    async fn read_exact(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.reader.lock().await.read_exact(buf).await.map_err(|_| ChannelError::UnexpectedEOF)
+    }
+
+    async fn write(&self, buf: &[u8]) -> ChannelResult<usize> {
+        self.writer.lock().await.write_all(buf).await.map_err(|_| ChannelError::Closed)?;
+        Ok(buf.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        KeyValueStore, MemoryKeyValueStore, OfflineHostBackend, PreimageServer, RemoteHostBackend,
+    };
+    use alloy_primitives::keccak256;
+    use kona_preimage::{HintReader, HintRouter, OracleServer, PreimageFetcher, PreimageKey};
+    use tokio::sync::RwLock;
+
+    const SECRET: B256 = B256::repeat_byte(0x5e);
+
+    #[tokio::test]
+    async fn test_socket_session() {
+        let preimage = b"hello, world".to_vec();
+        let key = PreimageKey::new_keccak256(*keccak256(&preimage));
+        let mut kv = MemoryKeyValueStore::new();
+        kv.set(key.into(), preimage.clone()).unwrap();
+        let backend = Arc::new(OfflineHostBackend::new(Arc::new(RwLock::new(kv))));
+
+        let mut listener = SocketListener::bind("127.0.0.1:0", SECRET).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (hint, preimage) = listener.accept_session().await.unwrap();
+            PreimageServer::new(OracleServer::new(preimage), HintReader::new(hint), backend)
+                .start()
+                .await
+        });
+
+        let (hint, preimage_channel) = SocketChannel::connect_session(addr, SECRET).await.unwrap();
+        let remote = RemoteHostBackend::new(hint, preimage_channel);
+        remote.route_hint("l1-block-header 0x00".to_string()).await.unwrap();
+        assert_eq!(remote.get_preimage(key).await.unwrap(), preimage);
+
+        // The session ends once the client disconnects.
+        drop(remote);
+        assert!(server.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_idle_connection_does_not_block_sessions() {
+        let mut listener = SocketListener::bind("127.0.0.1:0", SECRET).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // A connection that never sends its handshake.
+        let _idle = TcpStream::connect(addr).await.unwrap();
+        let (hint, preimage) = SocketChannel::connect_session(addr, SECRET).await.unwrap();
+
+        let (server_hint, server_preimage) =
+            timeout(HANDSHAKE_TIMEOUT / 2, listener.accept_session()).await.unwrap().unwrap();
+        hint.write(&[1]).await.unwrap();
+        preimage.write(&[2]).await.unwrap();
+
+        let mut buf = [0u8; 1];
+        server_hint.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [1]);
+        server_preimage.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [2]);
+    }
+
+    #[tokio::test]
+    async fn test_socket_sessions_are_paired_by_id() {
+        let mut listener = SocketListener::bind("127.0.0.1:0", SECRET).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (a, b) = (B256::repeat_byte(0xaa), B256::repeat_byte(0xbb));
+
+        // Interleave the connections of two clients, with a connection sent with a wrong secret.
+        let hint_a =
+            SocketChannel::connect(addr, SocketChannelKind::Hint, a, SECRET).await.unwrap();
+        let hint_b =
+            SocketChannel::connect(addr, SocketChannelKind::Hint, b, SECRET).await.unwrap();
+        let _intruder =
+            SocketChannel::connect(addr, SocketChannelKind::Preimage, a, B256::ZERO).await.unwrap();
+        let preimage_b =
+            SocketChannel::connect(addr, SocketChannelKind::Preimage, b, SECRET).await.unwrap();
+        let preimage_a =
+            SocketChannel::connect(addr, SocketChannelKind::Preimage, a, SECRET).await.unwrap();
+
+        for (hint, preimage, id) in [(&hint_a, &preimage_a, 0xaa), (&hint_b, &preimage_b, 0xbb)] {
+            hint.write(&[id]).await.unwrap();
+            preimage.write(&[id, 1]).await.unwrap();
+        }
+
+        // Handshakes complete concurrently, so the sessions may be accepted in any order.
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let (server_hint, server_preimage) = listener.accept_session().await.unwrap();
+            let mut hint_buf = [0u8; 1];
+            server_hint.read_exact(&mut hint_buf).await.unwrap();
+            let mut preimage_buf = [0u8; 2];
+            server_preimage.read_exact(&mut preimage_buf).await.unwrap();
+            assert_eq!(preimage_buf, [hint_buf[0], 1]);
+            ids.push(hint_buf[0]);
+        }
+        ids.sort_unstable();
+        assert_eq!(ids, [0xaa, 0xbb]);
+    }
+}
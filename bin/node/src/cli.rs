@@ -1,7 +1,11 @@
 //! Contains the node CLI.
 
 use crate::{
-    commands::{BootstoreCommand, InfoCommand, NetCommand, NodeCommand, RegistryCommand},
+    commands::{
+        BenchCommand, BootstoreCommand, ConfigCommand, ConformanceCommand, DbCommand,
+        DoctorCommand, GenesisCommand, InfoCommand, KeysCommand, NetCommand, NodeCommand,
+        RegistryCommand, TopCommand, VerifyCommand,
+    },
     flags::{GlobalArgs, init_unified_metrics},
     version,
 };
@@ -28,6 +32,28 @@ pub enum Commands {
     Bootstore(BootstoreCommand),
     /// Get info about op chain.
     Info(InfoCommand),
+    /// Runs connectivity and configuration checks for the node.
+    #[command(alias = "d", alias = "check")]
+    Doctor(DoctorCommand),
+    /// Utilities for configuration files.
+    #[command(alias = "cfg")]
+    Config(ConfigCommand),
+    /// Generates the L2 genesis and rollup config of a new chain.
+    #[command(alias = "gen")]
+    Genesis(GenesisCommand),
+    /// Generates and inspects p2p and sequencer keys.
+    #[command(alias = "k", alias = "key")]
+    Keys(KeysCommand),
+    /// Maintains the node database.
+    Db(DbCommand),
+    /// Cross-checks the proof executor against the canonical L2 chain.
+    Conformance(ConformanceCommand),
+    /// Benchmarks the engine API of an execution client with derived blocks.
+    Bench(BenchCommand),
+    /// Verifies derived attributes against the headers of a trusted L2 RPC.
+    Verify(VerifyCommand),
+    /// Shows a live dashboard of a running node.
+    Top(TopCommand),
 }
 
 /// The node CLI.
@@ -59,8 +85,20 @@ impl Cli {
             Commands::Registry(ref registry) => registry.init_logs(&self.global)?,
             Commands::Bootstore(ref bootstore) => bootstore.init_logs(&self.global)?,
             Commands::Info(ref info) => info.init_logs(&self.global)?,
+            Commands::Doctor(ref doctor) => doctor.init_logs(&self.global)?,
+            Commands::Config(ref config) => config.init_logs(&self.global)?,
+            Commands::Genesis(ref genesis) => genesis.init_logs(&self.global)?,
+            Commands::Keys(ref keys) => keys.init_logs(&self.global)?,
+            Commands::Db(ref db) => db.init_logs(&self.global)?,
+            Commands::Conformance(ref conformance) => conformance.init_logs(&self.global)?,
+            Commands::Bench(ref bench) => bench.init_logs(&self.global)?,
+            Commands::Verify(ref verify) => verify.init_logs(&self.global)?,
+            Commands::Top(ref top) => top.init_logs(&self.global)?,
         }
 
+        // Load the local chain registry before any subcommand resolves chain configurations.
+        self.global.init_registry()?;
+
         // Initialize unified metrics
         init_unified_metrics(&self.global.metrics)?;
 
@@ -73,13 +111,28 @@ impl Cli {
         }
 
         // Run the subcommand.
-        match self.subcommand {
-            Commands::Node(node) => Self::run_until_ctrl_c(node.run(&self.global)),
+        let result = match self.subcommand {
+            Commands::Node(node) => Self::run_until_stopped(node.run(&self.global)),
             Commands::Net(net) => Self::run_until_ctrl_c(net.run(&self.global)),
             Commands::Registry(registry) => registry.run(&self.global),
             Commands::Bootstore(bootstore) => bootstore.run(&self.global),
             Commands::Info(info) => info.run(&self.global),
-        }
+            Commands::Doctor(doctor) => Self::run_until_ctrl_c(doctor.run(&self.global)),
+            Commands::Config(config) => config.run(&self.global),
+            Commands::Genesis(genesis) => Self::run_until_ctrl_c(genesis.run(&self.global)),
+            Commands::Keys(keys) => keys.run(&self.global),
+            Commands::Db(db) => db.run(&self.global),
+            Commands::Conformance(conformance) => {
+                Self::run_until_ctrl_c(conformance.run(&self.global))
+            }
+            Commands::Bench(bench) => Self::run_until_ctrl_c(bench.run(&self.global)),
+            Commands::Verify(verify) => Self::run_until_ctrl_c(verify.run(&self.global)),
+            Commands::Top(top) => Self::run_until_ctrl_c(top.run(&self.global)),
+        };
+
+        // Flush any spans buffered for export before exiting.
+        kona_cli::shutdown_otlp_tracing();
+        result
     }
 
     /// Run until ctrl-c is pressed.
@@ -99,6 +152,15 @@ impl Cli {
         })
     }
 
+    /// Run until the future completes. Unlike [`Cli::run_until_ctrl_c`], the handling of signals
+    /// is left to the future.
+    pub fn run_until_stopped<F>(fut: F) -> Result<()>
+    where
+        F: std::future::Future<Output = Result<()>>,
+    {
+        Self::tokio_runtime().map_err(|e| anyhow::anyhow!(e))?.block_on(fut)
+    }
+
     /// Creates a new default tokio multi-thread [Runtime](tokio::runtime::Runtime) with all
     /// features enabled
     pub fn tokio_runtime() -> Result<tokio::runtime::Runtime, std::io::Error> {
@@ -123,6 +185,14 @@ mod tests {
     #[case::bootstore_subcommand_long(Commands::Bootstore(Default::default()), "boot")]
     #[case::bootstore_subcommand_long2(Commands::Bootstore(Default::default()), "store")]
     #[case::info_subcommand(Commands::Info(Default::default()), "info")]
+    #[case::doctor_subcommand(Commands::Doctor(Default::default()), "doctor")]
+    #[case::doctor_subcommand_short(Commands::Doctor(Default::default()), "d")]
+    #[case::doctor_subcommand_alias(Commands::Doctor(Default::default()), "check")]
+    #[case::config_subcommand(Commands::Config(Default::default()), "config")]
+    #[case::config_subcommand_short(Commands::Config(Default::default()), "cfg")]
+    #[case::genesis_subcommand(Commands::Genesis(Default::default()), "genesis")]
+    #[case::genesis_subcommand_short(Commands::Genesis(Default::default()), "gen")]
+    #[case::top_subcommand(Commands::Top(Default::default()), "top")]
     fn test_parse_cli(#[case] subcommand: Commands, #[case] subcommand_alias: &str) {
         let args = vec!["kona-node", subcommand_alias, "--help"];
         let cli = Cli::parse_from(args);
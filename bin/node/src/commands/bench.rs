@@ -0,0 +1,350 @@
+//! Bench Subcommand
+
+use super::conformance::parse_block_range;
+use crate::flags::GlobalArgs;
+use alloy_consensus::Sealable;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{B256, Bytes};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_engine::{
+    ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+    ForkchoiceState, JwtSecret, PayloadStatusEnum,
+};
+use alloy_transport_http::Http;
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_engine::{HyperAuthClient, OpEngineClient};
+use kona_genesis::RollupConfig;
+use kona_registry::scr_rollup_config_by_alloy_ident;
+use op_alloy_consensus::OpBlock;
+use op_alloy_network::Optimism;
+use op_alloy_provider::ext::engine::OpEngineApi;
+use op_alloy_rpc_types_engine::{OpExecutionPayload, OpExecutionPayloadV4};
+use std::{
+    fmt,
+    ops::Range,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::info;
+use url::Url;
+
+/// The `bench` Subcommand
+///
+/// The `bench` subcommand replays a range of already-derived blocks of the canonical L2 chain
+/// against the engine API of an execution client, with the calls the node makes to consolidate a
+/// derived payload: `engine_newPayload` to insert the block, followed by
+/// `engine_forkchoiceUpdated` to make it the safe head. It reports the throughput in blocks per
+/// second, and the latency percentiles of each call, to compare execution clients under the call
+/// pattern of the node.
+///
+/// The blocks are fetched with `debug_getRawBlock` from the L2 RPC before the benchmark starts.
+/// The execution client under test must be synced to the parent of the first block of the range,
+/// and must not be followed by another consensus client.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node --chain optimism bench --l2-rpc <URL> --engine-rpc <URL> \
+///     --engine.jwt-secret <PATH> --range 130000000..130001000
+/// ```
+#[derive(Parser, PartialEq, Eq, Debug, Clone)]
+#[command(about = "Benchmarks the engine API of an execution client with derived blocks")]
+pub struct BenchCommand {
+    /// URL of the L2 execution client RPC serving the canonical blocks to replay.
+    #[arg(long = "l2-rpc", value_name = "URL", env = "KONA_NODE_BENCH_L2_RPC")]
+    pub l2_rpc: Url,
+    /// URL of the engine API of the execution client under test.
+    #[arg(long = "engine-rpc", value_name = "URL", env = "KONA_NODE_BENCH_ENGINE_RPC")]
+    pub engine_rpc: Url,
+    /// Path to the file containing the hex-encoded JWT secret of the engine API under test.
+    #[arg(long = "engine.jwt-secret", value_name = "PATH", env = "KONA_NODE_BENCH_ENGINE_AUTH")]
+    pub engine_jwt_secret: PathBuf,
+    /// The range of blocks to replay, as `start..end` with `end` exclusive.
+    #[arg(long = "range", value_name = "START..END", value_parser = parse_block_range)]
+    pub range: Range<u64>,
+}
+
+/// A block to replay, as the payload of its `engine_newPayload` call.
+#[derive(Debug, Clone)]
+struct BenchPayload {
+    /// The execution payload of the block.
+    payload: OpExecutionPayload,
+    /// The parent beacon block root of the block, since Ecotone.
+    parent_beacon_block_root: Option<B256>,
+}
+
+impl BenchPayload {
+    /// Returns the payload of the given block, in the version active at its timestamp.
+    fn from_block(cfg: &RollupConfig, block: &OpBlock) -> Self {
+        let hash = block.header.hash_slow();
+        let timestamp = block.header.timestamp;
+        let payload = if cfg.is_isthmus_active(timestamp) {
+            OpExecutionPayload::V4(OpExecutionPayloadV4::from_v3_with_withdrawals_root(
+                ExecutionPayloadV3::from_block_unchecked(hash, block),
+                block.header.withdrawals_root.unwrap_or_default(),
+            ))
+        } else if cfg.is_ecotone_active(timestamp) {
+            OpExecutionPayload::V3(ExecutionPayloadV3::from_block_unchecked(hash, block))
+        } else if cfg.is_canyon_active(timestamp) {
+            OpExecutionPayload::V2(ExecutionPayloadV2::from_block_unchecked(hash, block))
+        } else {
+            OpExecutionPayload::V1(ExecutionPayloadV1::from_block_unchecked(hash, block))
+        };
+        Self { payload, parent_beacon_block_root: block.header.parent_beacon_block_root }
+    }
+}
+
+/// The latencies of the calls of one engine API method.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyReport {
+    /// The latencies of the calls, in the order they were made.
+    latencies: Vec<Duration>,
+}
+
+impl LatencyReport {
+    /// Records the latency of a call.
+    pub fn record(&mut self, latency: Duration) {
+        self.latencies.push(latency);
+    }
+
+    /// Returns the latency below which the given percentage of the calls completed, using the
+    /// nearest-rank method.
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        let mut sorted = self.latencies.clone();
+        sorted.sort_unstable();
+        let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied().unwrap_or_default()
+    }
+
+    /// Returns the mean latency of the calls.
+    pub fn mean(&self) -> Duration {
+        let total = self.latencies.iter().sum::<Duration>();
+        total.checked_div(self.latencies.len() as u32).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for LatencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:?}, p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+            self.mean(),
+            self.percentile(50.0),
+            self.percentile(90.0),
+            self.percentile(99.0),
+            self.percentile(100.0),
+        )
+    }
+}
+
+/// The outcome of a benchmark.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchReport {
+    /// The number of blocks replayed.
+    pub blocks: u64,
+    /// The time spent replaying the blocks.
+    pub elapsed: Duration,
+    /// The latencies of the `engine_newPayload` calls.
+    pub new_payload: LatencyReport,
+    /// The latencies of the `engine_forkchoiceUpdated` calls.
+    pub forkchoice_updated: LatencyReport,
+}
+
+impl BenchReport {
+    /// Returns the number of blocks replayed per second.
+    pub fn blocks_per_second(&self) -> f64 {
+        let elapsed = self.elapsed.as_secs_f64();
+        if elapsed == 0.0 { 0.0 } else { self.blocks as f64 / elapsed }
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Replayed {} blocks in {:?} ({:.2} blocks/s)",
+            self.blocks,
+            self.elapsed,
+            self.blocks_per_second()
+        )?;
+        writeln!(f, "engine_newPayload:         {}", self.new_payload)?;
+        write!(f, "engine_forkchoiceUpdated:  {}", self.forkchoice_updated)
+    }
+}
+
+impl BenchCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> Result<()> {
+        let Some(cfg) = scr_rollup_config_by_alloy_ident(&args.l2_chain_id) else {
+            bail!("Failed to find l2 config for chain ID {}", args.l2_chain_id);
+        };
+        let cfg = args.apply_overrides(cfg.clone())?;
+
+        let provider = RootProvider::new_http(self.l2_rpc.clone());
+        let chain_id = provider.get_chain_id().await.context("Failed to reach the L2 RPC")?;
+        if chain_id != cfg.l2_chain_id.id() {
+            bail!("The L2 RPC serves chain {chain_id}, expected {}", cfg.l2_chain_id.id());
+        }
+
+        let secret = std::fs::read_to_string(&self.engine_jwt_secret).with_context(|| {
+            format!("Failed to read the JWT secret from {}", self.engine_jwt_secret.display())
+        })?;
+        let jwt_secret = JwtSecret::from_hex(secret.trim())
+            .map_err(|e| anyhow::anyhow!("Failed to parse JWT secret: {e}"))?;
+        let engine = OpEngineClient::<RootProvider, RootProvider<Optimism>>::rpc_client::<Optimism>(
+            self.engine_rpc.clone(),
+            jwt_secret,
+        );
+
+        info!(
+            target: "bench",
+            start = self.range.start,
+            end = self.range.end,
+            "Fetching blocks"
+        );
+        let finalized = fetch_block(&provider, self.range.start - 1).await?.header.hash_slow();
+        let mut payloads = Vec::with_capacity((self.range.end - self.range.start) as usize);
+        for number in self.range.clone() {
+            payloads.push(BenchPayload::from_block(&cfg, &fetch_block(&provider, number).await?));
+        }
+
+        info!(target: "bench", blocks = payloads.len(), "Replaying blocks");
+        let report = replay(&engine, &payloads, finalized).await?;
+        println!("{report}");
+        Ok(())
+    }
+}
+
+/// Fetches the canonical block with the given number from the L2 RPC.
+async fn fetch_block(provider: &RootProvider, number: u64) -> Result<OpBlock> {
+    let raw = provider
+        .client()
+        .request::<_, Bytes>("debug_getRawBlock", (BlockNumberOrTag::Number(number),))
+        .await
+        .with_context(|| format!("Failed to fetch block {number}"))?;
+    OpBlock::decode(&mut raw.as_ref()).with_context(|| format!("Failed to decode block {number}"))
+}
+
+/// Inserts each payload into the engine and makes it the safe head, in order, measuring the
+/// latency of each call.
+async fn replay<E: OpEngineApi<Optimism, Http<HyperAuthClient>>>(
+    engine: &E,
+    payloads: &[BenchPayload],
+    finalized: B256,
+) -> Result<BenchReport> {
+    let mut report = BenchReport::default();
+    let started = Instant::now();
+    for BenchPayload { payload, parent_beacon_block_root } in payloads.iter().cloned() {
+        let number = payload.block_number();
+        let hash = payload.block_hash();
+        let parent_beacon_block_root = parent_beacon_block_root.unwrap_or_default();
+        let ecotone = matches!(payload, OpExecutionPayload::V3(_) | OpExecutionPayload::V4(_));
+
+        let call = Instant::now();
+        let status = match payload {
+            OpExecutionPayload::V1(payload) => {
+                let payload =
+                    ExecutionPayloadInputV2 { execution_payload: payload, withdrawals: None };
+                engine.new_payload_v2(payload).await
+            }
+            OpExecutionPayload::V2(payload) => {
+                let payload = ExecutionPayloadInputV2 {
+                    execution_payload: payload.payload_inner,
+                    withdrawals: Some(payload.withdrawals),
+                };
+                engine.new_payload_v2(payload).await
+            }
+            OpExecutionPayload::V3(payload) => {
+                engine.new_payload_v3(payload, parent_beacon_block_root).await
+            }
+            OpExecutionPayload::V4(payload) => {
+                engine.new_payload_v4(payload, parent_beacon_block_root).await
+            }
+        }
+        .with_context(|| format!("engine_newPayload failed for block {number}"))?;
+        report.new_payload.record(call.elapsed());
+        if status.status != PayloadStatusEnum::Valid {
+            bail!(
+                "engine_newPayload returned {} for block {number}, is the engine synced to its parent?",
+                status.status
+            );
+        }
+
+        let state = ForkchoiceState {
+            head_block_hash: hash,
+            safe_block_hash: hash,
+            finalized_block_hash: finalized,
+        };
+        let call = Instant::now();
+        let updated = if ecotone {
+            engine.fork_choice_updated_v3(state, None).await
+        } else {
+            engine.fork_choice_updated_v2(state, None).await
+        }
+        .with_context(|| format!("engine_forkchoiceUpdated failed for block {number}"))?;
+        report.forkchoice_updated.record(call.elapsed());
+        if !updated.is_valid() {
+            bail!(
+                "engine_forkchoiceUpdated returned {} for block {number}",
+                updated.payload_status.status
+            );
+        }
+        report.blocks += 1;
+    }
+    report.elapsed = started.elapsed();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bench_command() {
+        let command = BenchCommand::parse_from([
+            "bench",
+            "--l2-rpc",
+            "http://localhost:8545",
+            "--engine-rpc",
+            "http://localhost:8551",
+            "--engine.jwt-secret",
+            "/tmp/jwt.hex",
+            "--range",
+            "5..7",
+        ]);
+        assert_eq!(command.range, 5..7);
+        assert_eq!(command.engine_rpc.as_str(), "http://localhost:8551/");
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let mut latencies = LatencyReport::default();
+        assert_eq!(latencies.percentile(50.0), Duration::ZERO);
+        assert_eq!(latencies.mean(), Duration::ZERO);
+
+        for ms in (1..=100).rev() {
+            latencies.record(Duration::from_millis(ms));
+        }
+        assert_eq!(latencies.percentile(50.0), Duration::from_millis(50));
+        assert_eq!(latencies.percentile(99.0), Duration::from_millis(99));
+        assert_eq!(latencies.percentile(100.0), Duration::from_millis(100));
+        assert_eq!(latencies.mean(), Duration::from_micros(50_500));
+    }
+
+    #[test]
+    fn test_blocks_per_second() {
+        let report =
+            BenchReport { blocks: 30, elapsed: Duration::from_secs(2), ..Default::default() };
+        assert_eq!(report.blocks_per_second(), 15.0);
+        assert_eq!(BenchReport::default().blocks_per_second(), 0.0);
+    }
+}
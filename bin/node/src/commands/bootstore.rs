@@ -55,8 +55,7 @@ impl BootstoreCommand {
 
     /// Prints information for the bootstore with the given chain ID.
     pub fn info(&self, chain_id: u64) -> anyhow::Result<()> {
-        let chain = kona_registry::OPCHAINS
-            .get(&chain_id)
+        let chain = kona_registry::chain_config_by_chain_id(chain_id)
             .ok_or(anyhow::anyhow!("Chain ID {chain_id} not found in the registry"))?;
         println!("{} Bootstore (Chain ID: {chain_id})", chain.name);
         let bootstore: BootStoreFile = self
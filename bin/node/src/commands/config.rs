@@ -0,0 +1,67 @@
+//! Config Subcommand
+
+use crate::{cli::Cli, config::ConfigFile, flags::GlobalArgs};
+use clap::{CommandFactory, Parser, Subcommand};
+use kona_cli::LogConfig;
+use std::ffi::OsString;
+
+/// The `config` Subcommand
+///
+/// The `config` subcommand provides utilities for working with `kona-node` configuration files.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node --config <FILE> config check
+/// ```
+#[derive(Parser, Default, PartialEq, Eq, Debug, Clone)]
+#[command(about = "Utilities for kona-node configuration files")]
+pub struct ConfigCommand {
+    /// The config subcommand to run.
+    #[command(subcommand)]
+    pub subcommand: Option<ConfigSubcommand>,
+}
+
+/// Subcommands of the `config` subcommand.
+#[derive(Subcommand, Default, PartialEq, Eq, Debug, Clone)]
+pub enum ConfigSubcommand {
+    /// Validates the configuration file against the flags of the `node` subcommand.
+    #[default]
+    Check,
+}
+
+impl ConfigCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        match self.subcommand.unwrap_or_default() {
+            ConfigSubcommand::Check => Self::check(args),
+        }
+    }
+
+    /// Checks that the configuration file only sets known flags, and that the `node` subcommand
+    /// parses successfully with the file applied.
+    pub fn check(args: &GlobalArgs) -> anyhow::Result<()> {
+        let Some(path) = &args.config else {
+            anyhow::bail!("No config file set. Pass one with `--config <FILE>`");
+        };
+
+        let config = ConfigFile::load(path)?;
+        let node_args = config.apply(&Cli::command(), Self::node_args())?;
+        Cli::try_parse_from(node_args)
+            .map_err(|e| anyhow::anyhow!("Invalid config file {}:\n{e}", path.display()))?;
+
+        println!("Config file {} is valid ({} keys)", path.display(), config.entries.len());
+        Ok(())
+    }
+
+    /// The command line arguments the config file is checked against.
+    fn node_args() -> Vec<OsString> {
+        vec!["kona-node".into(), "node".into()]
+    }
+}
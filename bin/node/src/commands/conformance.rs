@@ -0,0 +1,368 @@
+//! Conformance Subcommand
+
+use crate::flags::GlobalArgs;
+use alloy_consensus::{Header, Sealable};
+use alloy_op_evm::OpEvmFactory;
+use alloy_primitives::{B256, Bytes};
+use alloy_provider::{Provider, RootProvider, network::primitives::BlockTransactions};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_engine::PayloadAttributes;
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_executor::{StatelessL2Builder, TrieDBProvider};
+use kona_genesis::RollupConfig;
+use kona_mpt::{NoopTrieHinter, TrieNode, TrieProvider};
+use kona_registry::scr_rollup_config_by_alloy_ident;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use std::{fmt, ops::Range};
+use tokio::runtime::Handle;
+use tracing::info;
+use url::Url;
+
+/// The `conformance` Subcommand
+///
+/// The `conformance` subcommand executes a range of blocks of the canonical L2 chain with the
+/// stateless block builder of the proof program, and cross-checks the gas used, receipts root and
+/// state root of each built block against the canonical block. The L2 execution client must serve
+/// the `debug_dbGet`, `debug_getRawHeader` and `debug_getRawTransaction` methods.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node --chain optimism conformance --l2-rpc <URL> --range 130000000..130000100
+/// ```
+#[derive(Parser, PartialEq, Eq, Debug, Clone)]
+#[command(about = "Cross-checks the proof executor against the canonical L2 chain")]
+pub struct ConformanceCommand {
+    /// URL of the L2 execution client RPC serving the canonical chain and its state preimages.
+    #[arg(long = "l2-rpc", value_name = "URL", env = "KONA_NODE_CONFORMANCE_L2_RPC")]
+    pub l2_rpc: Url,
+    /// The range of blocks to execute, as `start..end` with `end` exclusive.
+    #[arg(long = "range", value_name = "START..END", value_parser = parse_block_range)]
+    pub range: Range<u64>,
+}
+
+/// Parses a `start..end` range of block numbers.
+pub(crate) fn parse_block_range(range: &str) -> Result<Range<u64>, String> {
+    let (start, end) =
+        range.split_once("..").ok_or_else(|| format!("Expected `start..end`, got `{range}`"))?;
+    let start = start.parse::<u64>().map_err(|e| format!("Invalid start block `{start}`: {e}"))?;
+    let end = end.parse::<u64>().map_err(|e| format!("Invalid end block `{end}`: {e}"))?;
+    if start == 0 || start >= end {
+        return Err(format!("Expected a non-empty range of non-genesis blocks, got `{range}`"));
+    }
+    Ok(start..end)
+}
+
+/// A field of a built block that differs from the canonical block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The name of the field.
+    pub field: &'static str,
+    /// The value of the field in the canonical block.
+    pub expected: String,
+    /// The value of the field in the built block.
+    pub actual: String,
+}
+
+impl Mismatch {
+    /// Returns a [`Mismatch`] of the given field if its values differ.
+    pub(crate) fn check<T: PartialEq + fmt::Display>(
+        field: &'static str,
+        expected: T,
+        actual: T,
+    ) -> Option<Self> {
+        (expected != actual).then(|| Self {
+            field,
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        })
+    }
+
+    /// Returns the fields of the built `actual` header that differ from the `expected` canonical
+    /// header.
+    pub fn compare(expected: &Header, actual: &Header) -> Vec<Self> {
+        [
+            Self::check("gas used", expected.gas_used, actual.gas_used),
+            Self::check("receipts root", expected.receipts_root, actual.receipts_root),
+            Self::check("state root", expected.state_root, actual.state_root),
+            Self::check("block hash", expected.hash_slow(), actual.hash_slow()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, got {}", self.field, self.expected, self.actual)
+    }
+}
+
+/// The outcome of executing a single block.
+#[derive(Debug)]
+pub struct BlockReport {
+    /// The number of the block.
+    pub number: u64,
+    /// The fields of the built block that differ from the canonical block, or the reason the
+    /// block could not be built.
+    pub outcome: Result<Vec<Mismatch>>,
+}
+
+impl BlockReport {
+    /// Returns `true` if the built block matches the canonical block.
+    pub fn passed(&self) -> bool {
+        self.outcome.as_ref().is_ok_and(Vec::is_empty)
+    }
+}
+
+impl fmt::Display for BlockReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            Ok(mismatches) if mismatches.is_empty() => write!(f, "[PASS] block {}", self.number),
+            Ok(mismatches) => {
+                write!(f, "[FAIL] block {}: ", self.number)?;
+                for (i, mismatch) in mismatches.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{mismatch}")?;
+                }
+                Ok(())
+            }
+            Err(e) => write!(f, "[FAIL] block {}: {e:#}", self.number),
+        }
+    }
+}
+
+impl ConformanceCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> Result<()> {
+        let Some(cfg) = scr_rollup_config_by_alloy_ident(&args.l2_chain_id) else {
+            bail!("Failed to find l2 config for chain ID {}", args.l2_chain_id);
+        };
+        let cfg = args.apply_overrides(cfg.clone())?;
+
+        let provider = RootProvider::new_http(self.l2_rpc.clone());
+        let chain_id = provider.get_chain_id().await.context("Failed to reach the L2 RPC")?;
+        if chain_id != cfg.l2_chain_id.id() {
+            bail!("The L2 RPC serves chain {chain_id}, expected {}", cfg.l2_chain_id.id());
+        }
+
+        info!(
+            target: "conformance",
+            start = self.range.start,
+            end = self.range.end,
+            "Executing blocks"
+        );
+
+        let rpc = RpcTrieDBProvider { provider };
+        let mut failed = 0;
+        for number in self.range.clone() {
+            let report = BlockReport { number, outcome: rpc.execute_block(&cfg, number).await };
+            println!("{report}");
+            if !report.passed() {
+                failed += 1;
+            }
+        }
+
+        let total = self.range.end - self.range.start;
+        if failed > 0 {
+            bail!("{failed} of {total} blocks diverged from the canonical chain");
+        }
+        println!("All {total} blocks match the canonical chain");
+        Ok(())
+    }
+}
+
+/// A [`TrieDBProvider`] fetching state preimages and headers from an L2 execution client RPC.
+#[derive(Debug, Clone)]
+struct RpcTrieDBProvider {
+    /// The L2 execution client RPC.
+    provider: RootProvider,
+}
+
+impl RpcTrieDBProvider {
+    /// Executes the canonical block with the given number on top of its parent, and returns the
+    /// fields of the built block that differ from the canonical block.
+    async fn execute_block(&self, cfg: &RollupConfig, number: u64) -> Result<Vec<Mismatch>> {
+        let block = self
+            .provider
+            .get_block_by_number(number.into())
+            .await?
+            .with_context(|| format!("Block {number} not found"))?;
+        let parent = self
+            .provider
+            .get_block_by_number((number - 1).into())
+            .await?
+            .with_context(|| format!("Block {} not found", number - 1))?;
+
+        let BlockTransactions::Hashes(tx_hashes) = block.transactions else {
+            bail!("Expected the transaction hashes of block {number}");
+        };
+        let mut transactions = Vec::with_capacity(tx_hashes.len());
+        for tx_hash in tx_hashes {
+            let tx = self
+                .provider
+                .client()
+                .request::<_, Bytes>("debug_getRawTransaction", (tx_hash,))
+                .await
+                .with_context(|| format!("Failed to fetch transaction {tx_hash}"))?;
+            transactions.push(tx);
+        }
+
+        let header = block.header.inner;
+        let attributes = payload_attributes(cfg, &header, transactions)?;
+        let mut builder = StatelessL2Builder::new(
+            cfg,
+            OpEvmFactory::default(),
+            self.clone(),
+            NoopTrieHinter,
+            parent.header.inner.seal_slow(),
+        );
+        let outcome = builder.build_block(attributes)?;
+
+        Ok(Mismatch::compare(&header, outcome.header.inner()))
+    }
+
+    /// Fetches a preimage with the given RPC method and hash, from within the synchronous
+    /// [`TrieDBProvider`] interface.
+    fn preimage(&self, method: &'static str, hash: Bytes) -> Result<Bytes, RpcProviderError> {
+        tokio::task::block_in_place(|| {
+            Handle::current().block_on(async {
+                self.provider
+                    .client()
+                    .request::<_, Bytes>(method, (hash.clone(),))
+                    .await
+                    .map_err(|e| RpcProviderError::Request(method, hash.clone(), e.to_string()))
+            })
+        })
+    }
+}
+
+/// Returns the payload attributes that the canonical block with the given header was built from.
+fn payload_attributes(
+    cfg: &RollupConfig,
+    header: &Header,
+    transactions: Vec<Bytes>,
+) -> Result<OpPayloadAttributes> {
+    let eip_1559_params = if cfg.is_holocene_active(header.timestamp) {
+        let params = header.extra_data.get(1..9).context("Invalid Holocene extra data")?;
+        Some(params.try_into()?)
+    } else {
+        None
+    };
+    let min_base_fee = if cfg.is_jovian_active(header.timestamp) {
+        let min_base_fee = header.extra_data.get(9..17).context("Invalid Jovian extra data")?;
+        Some(u64::from_be_bytes(min_base_fee.try_into()?))
+    } else {
+        None
+    };
+
+    Ok(OpPayloadAttributes {
+        payload_attributes: PayloadAttributes {
+            timestamp: header.timestamp,
+            prev_randao: header.mix_hash,
+            suggested_fee_recipient: header.beneficiary,
+            withdrawals: cfg.is_canyon_active(header.timestamp).then(Vec::new),
+            parent_beacon_block_root: header.parent_beacon_block_root,
+        },
+        transactions: Some(transactions),
+        no_tx_pool: Some(true),
+        gas_limit: Some(header.gas_limit),
+        eip_1559_params,
+        min_base_fee,
+    })
+}
+
+impl TrieProvider for RpcTrieDBProvider {
+    type Error = RpcProviderError;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        let preimage = self.preimage("debug_dbGet", key.into())?;
+        TrieNode::decode(&mut preimage.as_ref()).map_err(RpcProviderError::Rlp)
+    }
+}
+
+impl TrieDBProvider for RpcTrieDBProvider {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        // geth hashdb scheme code hash key prefix
+        const CODE_PREFIX: u8 = b'c';
+
+        // Bytecode is keyed by its prefixed hash in the path scheme, and by its hash otherwise.
+        let prefixed = [&[CODE_PREFIX], code_hash.as_slice()].concat();
+        self.preimage("debug_dbGet", prefixed.into())
+            .or_else(|_| self.preimage("debug_dbGet", code_hash.into()))
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        let encoded = self.preimage("debug_getRawHeader", hash.into())?;
+        Header::decode(&mut encoded.as_ref()).map_err(RpcProviderError::Rlp)
+    }
+}
+
+/// An error fetching preimages from the L2 execution client RPC.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum RpcProviderError {
+    /// The request for the preimage failed.
+    #[error("`{0}` failed for {1}: {2}")]
+    Request(&'static str, Bytes, String),
+    /// Failed to decode the RLP-encoded preimage.
+    #[error("Failed to decode RLP: {0}")]
+    Rlp(alloy_rlp::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_block_range() {
+        assert_eq!(parse_block_range("100..200"), Ok(100..200));
+        assert!(parse_block_range("0..10").is_err());
+        assert!(parse_block_range("10..10").is_err());
+        assert!(parse_block_range("10-20").is_err());
+        assert!(parse_block_range("a..b").is_err());
+    }
+
+    #[test]
+    fn test_parse_conformance_command() {
+        let command = ConformanceCommand::parse_from([
+            "conformance",
+            "--l2-rpc",
+            "http://localhost:8545",
+            "--range",
+            "5..7",
+        ]);
+        assert_eq!(command.range, 5..7);
+        assert_eq!(command.l2_rpc.as_str(), "http://localhost:8545/");
+    }
+
+    #[test]
+    fn test_compare_headers() {
+        let expected = Header { gas_used: 21_000, ..Default::default() };
+        assert!(Mismatch::compare(&expected, &expected).is_empty());
+
+        let actual =
+            Header { gas_used: 42_000, state_root: B256::repeat_byte(1), ..expected.clone() };
+        let fields = Mismatch::compare(&expected, &actual)
+            .into_iter()
+            .map(|mismatch| mismatch.field)
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["gas used", "state root", "block hash"]);
+
+        let report = BlockReport { number: 3, outcome: Ok(Mismatch::compare(&expected, &actual)) };
+        assert!(!report.passed());
+        assert!(
+            report.to_string().starts_with("[FAIL] block 3: gas used: expected 21000, got 42000")
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Database Subcommand
+
+use crate::flags::{DbArgs, GlobalArgs};
+use clap::{Parser, Subcommand};
+use kona_cli::LogConfig;
+use kona_node_service::NodeDb;
+
+/// The `db` Subcommand
+///
+/// The `db` subcommand maintains the node database configured with `--db.path`. The node must not
+/// be running while the database is maintained.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node db stats --db.path ./db
+/// kona-node db prune --db.path ./db --db.retain.safe-heads 100000
+/// kona-node db verify --db.path ./db
+/// ```
+#[derive(Parser, PartialEq, Eq, Debug, Clone)]
+#[command(about = "Maintains the node database")]
+pub struct DbCommand {
+    /// The db subcommand to run.
+    #[command(subcommand)]
+    pub subcommand: DbSubcommand,
+    /// The node database.
+    #[command(flatten)]
+    pub db: DbArgs,
+}
+
+/// Subcommands of the `db` subcommand.
+#[derive(Subcommand, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum DbSubcommand {
+    /// Prints the number of entries, size and block range of each table.
+    Stats,
+    /// Prunes each table down to its retention.
+    Prune,
+    /// Checks that every record decodes and is consistent with its neighbours.
+    Verify,
+}
+
+impl DbCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub fn run(self, _args: &GlobalArgs) -> anyhow::Result<()> {
+        let Some(path) = &self.db.path else {
+            anyhow::bail!("`--db.path` is required");
+        };
+        if !path.try_exists()? {
+            anyhow::bail!("No node database at {}", path.display());
+        }
+        let db = self.db.open()?.ok_or_else(|| anyhow::anyhow!("`--db.path` is required"))?;
+        self.subcommand.run(&db)
+    }
+}
+
+impl DbSubcommand {
+    /// Runs the subcommand against `db`.
+    pub fn run(self, db: &NodeDb) -> anyhow::Result<()> {
+        match self {
+            Self::Stats => {
+                for stats in db.stats()? {
+                    println!("{stats}");
+                }
+            }
+            Self::Prune => {
+                for (table, deleted) in db.prune()? {
+                    println!("{table}: pruned {deleted} entries");
+                }
+            }
+            Self::Verify => {
+                let issues = db.verify()?;
+                for issue in &issues {
+                    println!("{issue}");
+                }
+                if !issues.is_empty() {
+                    anyhow::bail!("Found {} inconsistent entries", issues.len());
+                }
+                println!("No inconsistencies found");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_node_service::PruningConfig;
+
+    #[test]
+    fn test_parse_db_command() {
+        let command = DbCommand::parse_from(["db", "prune", "--db.path", "./db"]);
+        assert_eq!(command.subcommand, DbSubcommand::Prune);
+        assert_eq!(command.db.path.as_deref(), Some(std::path::Path::new("./db")));
+    }
+
+    #[test]
+    fn test_verify_empty_db() {
+        let db = NodeDb::in_memory(PruningConfig::default());
+        assert!(DbSubcommand::Verify.run(&db).is_ok());
+        assert!(DbSubcommand::Stats.run(&db).is_ok());
+    }
+}
@@ -0,0 +1,260 @@
+//! Doctor Subcommand
+
+use crate::{commands::NodeCommand, flags::GlobalArgs};
+use alloy_provider::{Provider, RootProvider};
+use alloy_transport_http::Http;
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_engine::{HyperAuthClient, OpEngineClient};
+use kona_genesis::RollupConfig;
+use kona_providers_alloy::{BeaconClient, OnlineBeaconClient};
+use op_alloy_network::Optimism;
+use op_alloy_provider::ext::engine::OpEngineApi;
+use std::fmt;
+use tracing::info;
+
+/// The engine API methods that the execution client must support for the node to operate.
+const REQUIRED_ENGINE_CAPABILITIES: [&str; 6] = [
+    "engine_forkchoiceUpdatedV3",
+    "engine_newPayloadV3",
+    "engine_newPayloadV4",
+    "engine_getPayloadV3",
+    "engine_getPayloadV4",
+    "engine_getPayloadBodiesByRangeV1",
+];
+
+/// The `doctor` Subcommand
+///
+/// The `doctor` subcommand runs a set of end-to-end connectivity and configuration checks using
+/// the same flags as the `node` subcommand, and prints a pass/fail report.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node doctor [FLAGS] [OPTIONS]
+/// ```
+#[derive(Parser, Default, Debug, Clone)]
+#[command(about = "Runs connectivity and configuration checks for the kona-node")]
+pub struct DoctorCommand {
+    /// The node configuration to check.
+    #[command(flatten)]
+    pub node: NodeCommand,
+}
+
+/// The outcome of a single doctor check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckReport {
+    /// The name of the check.
+    pub name: &'static str,
+    /// The detail of a passing check, or the reason for a failing check.
+    pub outcome: Result<String, String>,
+}
+
+impl CheckReport {
+    /// Creates a new [`CheckReport`] from the result of a check.
+    pub fn new(name: &'static str, outcome: Result<String>) -> Self {
+        Self { name, outcome: outcome.map_err(|e| format!("{e:#}")) }
+    }
+
+    /// Returns `true` if the check passed.
+    pub const fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            Ok(detail) => write!(f, "[PASS] {}: {detail}", self.name),
+            Err(reason) => write!(f, "[FAIL] {}: {reason}", self.name),
+        }
+    }
+}
+
+impl DoctorCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the doctor subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        info!(target: "doctor", "Running node diagnostics");
+
+        let cfg = self.node.get_l2_config(args);
+        let reports = vec![
+            CheckReport::new(
+                "Rollup config",
+                cfg.as_ref().map_err(|e| anyhow!("{e}")).and_then(Self::check_rollup_config),
+            ),
+            CheckReport::new("L1 RPC", self.check_l1_rpc(cfg.as_ref().ok()).await),
+            CheckReport::new("L1 beacon API", self.check_beacon().await),
+            CheckReport::new("L2 engine API", self.check_engine().await),
+            CheckReport::new("P2P ports", self.check_p2p_ports()),
+        ];
+
+        for report in &reports {
+            println!("{report}");
+        }
+
+        let failed = reports.iter().filter(|r| !r.passed()).count();
+        if failed > 0 {
+            bail!("{failed} of {} checks failed", reports.len());
+        }
+        println!("All {} checks passed", reports.len());
+        Ok(())
+    }
+
+    /// Checks that the rollup config's scheduled hardforks activate in order, and that the
+    /// configured block time is non-zero.
+    pub fn check_rollup_config(cfg: &RollupConfig) -> Result<String> {
+        if cfg.block_time == 0 {
+            bail!("block time must be non-zero");
+        }
+
+        cfg.hardforks.check_activation_order()?;
+        Ok(format!("chain {} with hardforks scheduled in order", cfg.l2_chain_id.id()))
+    }
+
+    /// Checks that the L1 RPC is reachable, and that it serves the rollup config's L1 chain.
+    async fn check_l1_rpc(&self, cfg: Option<&RollupConfig>) -> Result<String> {
+        let provider = RootProvider::new_http(self.node.l1_rpc_args.l1_eth_rpc.clone());
+        let chain_id = provider.get_chain_id().await?;
+        let head = provider.get_block_number().await?;
+
+        if let Some(cfg) = cfg &&
+            cfg.l1_chain_id != chain_id
+        {
+            bail!("L1 chain ID is {chain_id}, but the rollup config expects {}", cfg.l1_chain_id);
+        }
+        Ok(format!("chain ID {chain_id}, head block {head}"))
+    }
+
+    /// Checks that the L1 beacon API is reachable, and that it serves blobs for the head slot.
+    async fn check_beacon(&self) -> Result<String> {
+        let mut client = OnlineBeaconClient::new_http(self.node.l1_rpc_args.l1_beacon.to_string());
+        if let Some(duration) = self.node.l1_rpc_args.l1_slot_duration_override {
+            client = client.with_l1_slot_duration_override(duration);
+        }
+
+        let slot_duration = client
+            .slot_interval()
+            .await
+            .map_err(|e| anyhow!("failed to fetch beacon spec: {e}"))?
+            .data
+            .seconds_per_slot;
+        client.genesis_time().await.map_err(|e| anyhow!("failed to fetch beacon genesis: {e}"))?;
+
+        // Prefer the blobs endpoint, falling back to the deprecated blob sidecars endpoint for
+        // beacon nodes that do not support it yet.
+        for method in ["eth/v1/beacon/blobs/head", "eth/v1/beacon/blob_sidecars/head"] {
+            let response = client.inner.get(format!("{}/{method}", client.base)).send().await?;
+            if response.status().is_success() {
+                return Ok(format!("slot duration {slot_duration}s, blobs served from {method}"));
+            }
+        }
+        bail!("beacon node does not serve blobs for the head slot")
+    }
+
+    /// Checks that the L2 engine API accepts the configured JWT secret, and that it supports the
+    /// engine API methods used by the node.
+    async fn check_engine(&self) -> Result<String> {
+        let jwt_secret = self.node.l2_jwt_secret()?;
+        let engine = OpEngineClient::<RootProvider, RootProvider<Optimism>>::rpc_client::<Optimism>(
+            self.node.l2_client_args.l2_engine_rpc.clone(),
+            jwt_secret,
+        );
+
+        let capabilities = <RootProvider<Optimism> as OpEngineApi<
+            Optimism,
+            Http<HyperAuthClient>,
+        >>::exchange_capabilities(&engine, vec![])
+        .await
+        .map_err(|e| {
+            if NodeCommand::is_jwt_signature_error(&e) {
+                anyhow!("engine rejected the JWT secret: {e}")
+            } else {
+                anyhow!("failed to exchange capabilities: {e}")
+            }
+        })?;
+
+        let missing = REQUIRED_ENGINE_CAPABILITIES
+            .iter()
+            .filter(|method| !capabilities.iter().any(|c| c == *method))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            bail!("engine is missing required capabilities: {missing:?}");
+        }
+        Ok(format!("authenticated, {} capabilities", capabilities.len()))
+    }
+
+    /// Checks that the P2P listen ports are available.
+    fn check_p2p_ports(&self) -> Result<String> {
+        let p2p = &self.node.p2p_flags;
+        p2p.check_ports()?;
+        Ok(format!(
+            "tcp {} and udp {} available on {}",
+            p2p.listen_tcp_port, p2p.listen_udp_port, p2p.listen_ip
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_genesis::HardForkConfig;
+
+    #[test]
+    fn test_check_registry_rollup_configs() {
+        for cfg in kona_registry::ROLLUP_CONFIGS.values() {
+            assert!(DoctorCommand::check_rollup_config(cfg).is_ok(), "{}", cfg.l2_chain_id);
+        }
+    }
+
+    #[test]
+    fn test_check_rollup_config_out_of_order() {
+        let cfg = RollupConfig {
+            block_time: 2,
+            hardforks: HardForkConfig {
+                canyon_time: Some(0),
+                delta_time: Some(0),
+                ecotone_time: Some(10),
+                fjord_time: Some(5),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = DoctorCommand::check_rollup_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("Fjord activates at 5, before the prior Ecotone"));
+    }
+
+    #[test]
+    fn test_check_rollup_config_missing_prior() {
+        let cfg = RollupConfig {
+            block_time: 2,
+            hardforks: HardForkConfig { fjord_time: Some(5), ..Default::default() },
+            ..Default::default()
+        };
+        let err = DoctorCommand::check_rollup_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("Fjord is scheduled, but the prior Ecotone"), "{err}");
+    }
+
+    #[test]
+    fn test_check_rollup_config_zero_block_time() {
+        let cfg = RollupConfig { block_time: 0, ..Default::default() };
+        assert!(DoctorCommand::check_rollup_config(&cfg).is_err());
+    }
+
+    #[test]
+    fn test_check_report_display() {
+        let pass = CheckReport::new("L1 RPC", Ok(String::from("chain ID 1")));
+        assert!(pass.passed());
+        assert_eq!(pass.to_string(), "[PASS] L1 RPC: chain ID 1");
+
+        let fail = CheckReport::new("L1 RPC", Err(anyhow!("connection refused")));
+        assert!(!fail.passed());
+        assert_eq!(fail.to_string(), "[FAIL] L1 RPC: connection refused");
+    }
+}
@@ -0,0 +1,625 @@
+//! Genesis Subcommand
+
+use crate::flags::GlobalArgs;
+use alloy_chains::Chain;
+use alloy_consensus::{EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH, Header};
+use alloy_eips::{
+    BlockNumHash,
+    eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, INITIAL_BASE_FEE},
+    eip7685::EMPTY_REQUESTS_HASH,
+};
+use alloy_genesis::{ChainConfig, Genesis, GenesisAccount};
+use alloy_primitives::{Address, B64, B256, Bytes, U64, U256, keccak256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_trie::{
+    KECCAK_EMPTY, TrieAccount,
+    root::{state_root_unhashed, storage_root_unhashed},
+};
+use anyhow::{Context, Result};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_genesis::{
+    BaseFeeConfig, ChainGenesis, CustomGasTokenConfig, DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
+    GRANITE_CHANNEL_TIMEOUT, HardForkConfig, RollupConfig, SystemConfig,
+};
+use kona_protocol::{BlockInfo, Predeploys};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+use url::Url;
+
+/// The extra data of the L2 genesis block, if Holocene is not active at genesis.
+const BEDROCK_EXTRA_DATA: &[u8] = b"BEDROCK";
+
+/// The default gas price oracle overhead, used before Ecotone.
+const DEFAULT_GAS_PRICE_ORACLE_OVERHEAD: u64 = 2100;
+
+/// The default gas price oracle scalar, used before Ecotone.
+const DEFAULT_GAS_PRICE_ORACLE_SCALAR: u64 = 1_000_000;
+
+/// The `genesis` Subcommand
+///
+/// The `genesis` subcommand generates the L2 genesis and rollup config of a freshly deployed
+/// chain, from its deploy config, the L2 allocs and the L1 starting block.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node genesis --deploy-config <FILE> --l2-allocs <FILE> --l1-rpc <URL> \
+///     --outfile.l2 <FILE> --outfile.rollup <FILE>
+/// ```
+#[derive(Parser, Default, PartialEq, Eq, Debug, Clone)]
+#[command(about = "Generates the L2 genesis and rollup config of a new OP Stack chain")]
+pub struct GenesisCommand {
+    /// Path to the deploy config JSON file.
+    #[arg(long = "deploy-config", value_name = "FILE")]
+    pub deploy_config: PathBuf,
+    /// Path to the JSON dump of the L2 genesis allocs.
+    #[arg(long = "l2-allocs", value_name = "FILE")]
+    pub l2_allocs: PathBuf,
+    /// URL of the L1 execution client RPC, used to fetch the L1 starting block.
+    #[arg(long = "l1-rpc", value_name = "URL")]
+    pub l1_rpc: Option<Url>,
+    /// Hash of the L1 starting block. Overrides `l1StartingBlockTag` in the deploy config.
+    #[arg(long = "l1-starting-block", value_name = "HASH")]
+    pub l1_starting_block: Option<B256>,
+    /// Path to write the L2 genesis JSON to.
+    #[arg(long = "outfile.l2", value_name = "FILE")]
+    pub outfile_l2: PathBuf,
+    /// Path to write the rollup config JSON to.
+    #[arg(long = "outfile.rollup", value_name = "FILE")]
+    pub outfile_rollup: PathBuf,
+}
+
+impl GenesisCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, _args: &GlobalArgs) -> Result<()> {
+        let deploy_config: DeployConfig = read_json(&self.deploy_config)?;
+        let allocs: BTreeMap<Address, GenesisAccount> = read_json(&self.l2_allocs)?;
+
+        let l1_hash = self
+            .l1_starting_block
+            .or(deploy_config.l1_starting_block_tag)
+            .context("No L1 starting block. Set `l1StartingBlockTag` or `--l1-starting-block`")?;
+        let l1_rpc =
+            self.l1_rpc.context("`--l1-rpc` is required to fetch the L1 starting block")?;
+        let l1_block = RootProvider::new_http(l1_rpc)
+            .get_block_by_hash(l1_hash)
+            .await?
+            .with_context(|| format!("L1 starting block {l1_hash} not found"))?;
+        let l1_block = BlockInfo::new(
+            l1_block.header.hash,
+            l1_block.header.inner.number,
+            l1_block.header.inner.parent_hash,
+            l1_block.header.inner.timestamp,
+        );
+
+        let (genesis, rollup) = deploy_config.build(l1_block, allocs);
+        fs::write(&self.outfile_l2, serde_json::to_string_pretty(&genesis)?)
+            .with_context(|| format!("Failed to write {}", self.outfile_l2.display()))?;
+        fs::write(&self.outfile_rollup, serde_json::to_string_pretty(&rollup)?)
+            .with_context(|| format!("Failed to write {}", self.outfile_rollup.display()))?;
+
+        info!(
+            target: "genesis",
+            l1 = %l1_block.hash,
+            l2 = %rollup.genesis.l2.hash,
+            "Wrote L2 genesis to {} and rollup config to {}",
+            self.outfile_l2.display(),
+            self.outfile_rollup.display()
+        );
+        Ok(())
+    }
+}
+
+/// Reads and deserializes a JSON file.
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// The subset of the OP Stack deploy config used to generate the L2 genesis and rollup config.
+///
+/// Unknown fields are ignored, so a full `op-deployer` or `op-chain-ops` deploy config can be
+/// passed as-is.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployConfig {
+    /// The L1 chain ID.
+    #[serde(rename = "l1ChainID")]
+    pub l1_chain_id: u64,
+    /// The L2 chain ID.
+    #[serde(rename = "l2ChainID")]
+    pub l2_chain_id: u64,
+    /// The hash of the L1 block the chain starts deriving from.
+    pub l1_starting_block_tag: Option<B256>,
+    /// The L2 block time, in seconds.
+    pub l2_block_time: u64,
+    /// The maximum drift of the L2 timestamp from its L1 origin, in seconds.
+    pub max_sequencer_drift: u64,
+    /// The sequencing window size, in L1 blocks.
+    pub sequencer_window_size: u64,
+    /// The channel timeout, in L1 blocks.
+    pub channel_timeout: u64,
+    /// The address batches are submitted to.
+    pub batch_inbox_address: Address,
+    /// The initial batcher address.
+    pub batch_sender_address: Address,
+    /// The `OptimismPortal` proxy address on L1.
+    pub optimism_portal_proxy: Address,
+    /// The `SystemConfig` proxy address on L1.
+    pub system_config_proxy: Address,
+    /// The `ProtocolVersions` proxy address on L1.
+    #[serde(default)]
+    pub protocol_versions_proxy: Address,
+    /// The `SuperchainConfig` proxy address on L1.
+    pub superchain_config_proxy: Option<Address>,
+    /// The gas limit of the L2 genesis block.
+    pub l2_genesis_block_gas_limit: Option<U64>,
+    /// The base fee of the L2 genesis block.
+    pub l2_genesis_block_base_fee_per_gas: Option<U256>,
+    /// The legacy gas price oracle overhead, used if Ecotone is not active at genesis.
+    pub gas_price_oracle_overhead: Option<u64>,
+    /// The legacy gas price oracle scalar, used if Ecotone is not active at genesis.
+    pub gas_price_oracle_scalar: Option<u64>,
+    /// The gas price oracle base fee scalar, used if Ecotone is active at genesis.
+    #[serde(default)]
+    pub gas_price_oracle_base_fee_scalar: u32,
+    /// The gas price oracle blob base fee scalar, used if Ecotone is active at genesis.
+    #[serde(default)]
+    pub gas_price_oracle_blob_base_fee_scalar: u32,
+    /// The EIP-1559 elasticity multiplier.
+    pub eip1559_elasticity: u64,
+    /// The EIP-1559 base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// The EIP-1559 base fee max change denominator after Canyon.
+    pub eip1559_denominator_canyon: u64,
+    /// The Regolith activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_regolith_time_offset: Option<U64>,
+    /// The Canyon activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_canyon_time_offset: Option<U64>,
+    /// The Delta activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_delta_time_offset: Option<U64>,
+    /// The Ecotone activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_ecotone_time_offset: Option<U64>,
+    /// The Fjord activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_fjord_time_offset: Option<U64>,
+    /// The Granite activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_granite_time_offset: Option<U64>,
+    /// The Holocene activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_holocene_time_offset: Option<U64>,
+    /// The Isthmus activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_isthmus_time_offset: Option<U64>,
+    /// The Jovian activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_jovian_time_offset: Option<U64>,
+    /// The Interop activation offset from the L2 genesis timestamp, in seconds.
+    pub l2_genesis_interop_time_offset: Option<U64>,
+    /// Whether the chain pays for gas with a custom gas token instead of ETH.
+    #[serde(default)]
+    pub use_custom_gas_token: bool,
+    /// The L1 address of the custom gas token.
+    pub custom_gas_token_address: Option<Address>,
+}
+
+impl DeployConfig {
+    /// Builds the L2 [`Genesis`] and the [`RollupConfig`] of the chain, starting from the given
+    /// L1 block with the given L2 allocs.
+    ///
+    /// The L2 genesis timestamp is the timestamp of the L1 starting block.
+    pub fn build(
+        &self,
+        l1_block: BlockInfo,
+        allocs: BTreeMap<Address, GenesisAccount>,
+    ) -> (Genesis, RollupConfig) {
+        let mut rollup = self.rollup_config(l1_block);
+        let genesis = self.genesis(&rollup, allocs);
+        rollup.genesis.l2.hash = genesis_header(&rollup, &genesis).hash_slow();
+        (genesis, rollup)
+    }
+
+    /// Returns the [`HardForkConfig`], with activation times relative to the `genesis_time`.
+    pub fn hardforks(&self, genesis_time: u64) -> HardForkConfig {
+        let at = |offset: Option<U64>| offset.map(|offset| genesis_time + offset.to::<u64>());
+        HardForkConfig {
+            regolith_time: at(self.l2_genesis_regolith_time_offset),
+            canyon_time: at(self.l2_genesis_canyon_time_offset),
+            delta_time: at(self.l2_genesis_delta_time_offset),
+            ecotone_time: at(self.l2_genesis_ecotone_time_offset),
+            fjord_time: at(self.l2_genesis_fjord_time_offset),
+            granite_time: at(self.l2_genesis_granite_time_offset),
+            holocene_time: at(self.l2_genesis_holocene_time_offset),
+            pectra_blob_schedule_time: None,
+            isthmus_time: at(self.l2_genesis_isthmus_time_offset),
+            jovian_time: at(self.l2_genesis_jovian_time_offset),
+            interop_time: at(self.l2_genesis_interop_time_offset),
+        }
+    }
+
+    /// Returns the [`RollupConfig`] of the chain, with an unset L2 genesis hash.
+    pub fn rollup_config(&self, l1_block: BlockInfo) -> RollupConfig {
+        let genesis_time = l1_block.timestamp;
+        let hardforks = self.hardforks(genesis_time);
+        let ecotone_at_genesis = hardforks.ecotone_time.is_some_and(|t| t <= genesis_time);
+
+        let (overhead, scalar) = if ecotone_at_genesis {
+            (U256::ZERO, self.ecotone_scalar())
+        } else {
+            (
+                U256::from(
+                    self.gas_price_oracle_overhead.unwrap_or(DEFAULT_GAS_PRICE_ORACLE_OVERHEAD),
+                ),
+                U256::from(self.gas_price_oracle_scalar.unwrap_or(DEFAULT_GAS_PRICE_ORACLE_SCALAR)),
+            )
+        };
+
+        RollupConfig {
+            genesis: ChainGenesis {
+                l1: l1_block.id(),
+                l2: BlockNumHash { number: 0, hash: B256::ZERO },
+                l2_time: genesis_time,
+                system_config: Some(SystemConfig {
+                    batcher_address: self.batch_sender_address,
+                    overhead,
+                    scalar,
+                    gas_limit: self.gas_limit(),
+                    ..Default::default()
+                }),
+            },
+            block_time: self.l2_block_time,
+            max_sequencer_drift: self.max_sequencer_drift,
+            seq_window_size: self.sequencer_window_size,
+            channel_timeout: self.channel_timeout,
+            granite_channel_timeout: GRANITE_CHANNEL_TIMEOUT,
+            l1_chain_id: self.l1_chain_id,
+            l2_chain_id: Chain::from_id(self.l2_chain_id),
+            hardforks,
+            batch_inbox_address: self.batch_inbox_address,
+            deposit_contract_address: self.optimism_portal_proxy,
+            l1_system_config_address: self.system_config_proxy,
+            protocol_versions_address: self.protocol_versions_proxy,
+            superchain_config_address: self.superchain_config_proxy,
+            interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
+            custom_gas_token: self
+                .custom_gas_token_address
+                .filter(|_| self.use_custom_gas_token)
+                .map(CustomGasTokenConfig::new),
+            chain_op_config: BaseFeeConfig {
+                eip1559_elasticity: self.eip1559_elasticity,
+                eip1559_denominator: self.eip1559_denominator,
+                eip1559_denominator_canyon: self.eip1559_denominator_canyon,
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Returns the L2 [`Genesis`] of the chain described by the `rollup` config.
+    pub fn genesis(
+        &self,
+        rollup: &RollupConfig,
+        allocs: BTreeMap<Address, GenesisAccount>,
+    ) -> Genesis {
+        let genesis_time = rollup.genesis.l2_time;
+        let ecotone_at_genesis = rollup.is_ecotone_active(genesis_time);
+        Genesis {
+            config: self.chain_config(&rollup.hardforks),
+            timestamp: genesis_time,
+            extra_data: self.extra_data(rollup),
+            gas_limit: self.gas_limit(),
+            coinbase: Predeploys::SEQUENCER_FEE_VAULT,
+            alloc: allocs,
+            base_fee_per_gas: Some(self.base_fee().to::<u128>()),
+            excess_blob_gas: ecotone_at_genesis.then_some(0),
+            blob_gas_used: ecotone_at_genesis.then_some(0),
+            number: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the execution layer [`ChainConfig`], in the format expected by `op-geth` and
+    /// `op-reth`.
+    fn chain_config(&self, hardforks: &HardForkConfig) -> ChainConfig {
+        let mut config = Map::new();
+        config.insert("chainId".into(), self.l2_chain_id.into());
+        for block in [
+            "homesteadBlock",
+            "eip150Block",
+            "eip155Block",
+            "eip158Block",
+            "byzantiumBlock",
+            "constantinopleBlock",
+            "petersburgBlock",
+            "istanbulBlock",
+            "muirGlacierBlock",
+            "berlinBlock",
+            "londonBlock",
+            "arrowGlacierBlock",
+            "grayGlacierBlock",
+            "mergeNetsplitBlock",
+            "bedrockBlock",
+        ] {
+            config.insert(block.into(), 0.into());
+        }
+        for (name, time) in [
+            ("shanghaiTime", hardforks.canyon_time),
+            ("cancunTime", hardforks.ecotone_time),
+            ("pragueTime", hardforks.isthmus_time),
+            ("regolithTime", hardforks.regolith_time),
+            ("canyonTime", hardforks.canyon_time),
+            ("deltaTime", hardforks.delta_time),
+            ("ecotoneTime", hardforks.ecotone_time),
+            ("fjordTime", hardforks.fjord_time),
+            ("graniteTime", hardforks.granite_time),
+            ("holoceneTime", hardforks.holocene_time),
+            ("isthmusTime", hardforks.isthmus_time),
+            ("jovianTime", hardforks.jovian_time),
+            ("interopTime", hardforks.interop_time),
+        ] {
+            if let Some(time) = time {
+                config.insert(name.into(), time.into());
+            }
+        }
+        config.insert("terminalTotalDifficulty".into(), 0.into());
+        config.insert("terminalTotalDifficultyPassed".into(), true.into());
+        config.insert(
+            "optimism".into(),
+            json!({
+                "eip1559Elasticity": self.eip1559_elasticity,
+                "eip1559Denominator": self.eip1559_denominator,
+                "eip1559DenominatorCanyon": self.eip1559_denominator_canyon,
+            }),
+        );
+        serde_json::from_value(Value::Object(config))
+            .expect("the chain config is built from valid JSON values")
+    }
+
+    /// Returns the extra data of the L2 genesis block.
+    ///
+    /// From Holocene, the extra data encodes the EIP-1559 parameters, and from Jovian, the
+    /// minimum base fee as well.
+    fn extra_data(&self, rollup: &RollupConfig) -> Bytes {
+        let genesis_time = rollup.genesis.l2_time;
+        if !rollup.is_holocene_active(genesis_time) {
+            return Bytes::from_static(BEDROCK_EXTRA_DATA);
+        }
+
+        let jovian = rollup.is_jovian_active(genesis_time);
+        let mut extra_data = vec![u8::from(jovian)];
+        extra_data.extend_from_slice(&(self.eip1559_denominator_canyon as u32).to_be_bytes());
+        extra_data.extend_from_slice(&(self.eip1559_elasticity as u32).to_be_bytes());
+        if jovian {
+            extra_data.extend_from_slice(&0u64.to_be_bytes());
+        }
+        extra_data.into()
+    }
+
+    /// Returns the Ecotone encoded fee scalar: a version byte of `1`, followed by the blob base
+    /// fee scalar and the base fee scalar in the lowest 8 bytes.
+    fn ecotone_scalar(&self) -> U256 {
+        (U256::from(1) << 248) |
+            (U256::from(self.gas_price_oracle_blob_base_fee_scalar) << 32) |
+            U256::from(self.gas_price_oracle_base_fee_scalar)
+    }
+
+    /// Returns the gas limit of the L2 genesis block.
+    fn gas_limit(&self) -> u64 {
+        self.l2_genesis_block_gas_limit.map_or(ETHEREUM_BLOCK_GAS_LIMIT_30M, |limit| limit.to())
+    }
+
+    /// Returns the base fee of the L2 genesis block.
+    fn base_fee(&self) -> U256 {
+        self.l2_genesis_block_base_fee_per_gas.unwrap_or(U256::from(INITIAL_BASE_FEE))
+    }
+}
+
+/// Builds the L2 genesis block [`Header`] from the [`Genesis`].
+pub fn genesis_header(rollup: &RollupConfig, genesis: &Genesis) -> Header {
+    let genesis_time = rollup.genesis.l2_time;
+    let ecotone = rollup.is_ecotone_active(genesis_time);
+    let withdrawals_root = if rollup.is_isthmus_active(genesis_time) {
+        // From Isthmus, the withdrawals root commits to the storage of the message passer.
+        Some(
+            genesis
+                .alloc
+                .get(&Predeploys::L2_TO_L1_MESSAGE_PASSER)
+                .map_or(EMPTY_ROOT_HASH, account_storage_root),
+        )
+    } else {
+        rollup.is_canyon_active(genesis_time).then_some(EMPTY_ROOT_HASH)
+    };
+
+    Header {
+        parent_hash: B256::ZERO,
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        beneficiary: genesis.coinbase,
+        state_root: state_root_unhashed(
+            genesis.alloc.iter().map(|(address, account)| (*address, trie_account(account))),
+        ),
+        transactions_root: EMPTY_ROOT_HASH,
+        receipts_root: EMPTY_ROOT_HASH,
+        difficulty: genesis.difficulty,
+        number: 0,
+        gas_limit: genesis.gas_limit,
+        gas_used: 0,
+        timestamp: genesis.timestamp,
+        extra_data: genesis.extra_data.clone(),
+        mix_hash: genesis.mix_hash,
+        nonce: B64::from(genesis.nonce),
+        base_fee_per_gas: genesis.base_fee_per_gas.map(|fee| fee as u64),
+        withdrawals_root,
+        blob_gas_used: ecotone.then_some(0),
+        excess_blob_gas: ecotone.then_some(0),
+        parent_beacon_block_root: ecotone.then_some(B256::ZERO),
+        requests_hash: rollup.is_isthmus_active(genesis_time).then_some(EMPTY_REQUESTS_HASH),
+        ..Default::default()
+    }
+}
+
+/// Returns the [`TrieAccount`] of a [`GenesisAccount`].
+fn trie_account(account: &GenesisAccount) -> TrieAccount {
+    TrieAccount {
+        nonce: account.nonce.unwrap_or_default(),
+        balance: account.balance,
+        storage_root: account_storage_root(account),
+        code_hash: account.code.as_ref().map_or(KECCAK_EMPTY, keccak256),
+    }
+}
+
+/// Returns the storage root of a [`GenesisAccount`], skipping empty slots.
+fn account_storage_root(account: &GenesisAccount) -> B256 {
+    account.storage.as_ref().map_or(EMPTY_ROOT_HASH, |storage| {
+        storage_root_unhashed(
+            storage
+                .iter()
+                .filter(|(_, value)| !value.is_zero())
+                .map(|(slot, value)| (*slot, U256::from_be_bytes(value.0))),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    fn deploy_config() -> DeployConfig {
+        serde_json::from_value(json!({
+            "l1ChainID": 11155111,
+            "l2ChainID": 42069,
+            "l1StartingBlockTag": B256::repeat_byte(0xAA),
+            "l2BlockTime": 2,
+            "maxSequencerDrift": 600,
+            "sequencerWindowSize": 3600,
+            "channelTimeout": 300,
+            "batchInboxAddress": "0xff00000000000000000000000000000000042069",
+            "batchSenderAddress": "0x0000000000000000000000000000000000000b47",
+            "optimismPortalProxy": "0x0000000000000000000000000000000000000001",
+            "systemConfigProxy": "0x0000000000000000000000000000000000000002",
+            "l2GenesisBlockGasLimit": "0x2faf080",
+            "l2GenesisBlockBaseFeePerGas": "0x3b9aca00",
+            "gasPriceOracleBaseFeeScalar": 1368,
+            "gasPriceOracleBlobBaseFeeScalar": 810949,
+            "eip1559Elasticity": 6,
+            "eip1559Denominator": 50,
+            "eip1559DenominatorCanyon": 250,
+            "l2GenesisRegolithTimeOffset": "0x0",
+            "l2GenesisCanyonTimeOffset": "0x0",
+            "l2GenesisDeltaTimeOffset": "0x0",
+            "l2GenesisEcotoneTimeOffset": "0x0",
+            "l2GenesisFjordTimeOffset": "0x0",
+            "l2GenesisGraniteTimeOffset": "0x0",
+            "l2GenesisHoloceneTimeOffset": "0x0",
+            "l2GenesisIsthmusTimeOffset": "0x0",
+            "l2GenesisJovianTimeOffset": "0x64",
+            "fundDevAccounts": true
+        }))
+        .unwrap()
+    }
+
+    fn l1_block() -> BlockInfo {
+        BlockInfo::new(B256::repeat_byte(0xAA), 100, B256::repeat_byte(0x99), 1_700_000_000)
+    }
+
+    #[test]
+    fn test_rollup_config() {
+        let rollup = deploy_config().rollup_config(l1_block());
+
+        assert_eq!(rollup.genesis.l1, l1_block().id());
+        assert_eq!(rollup.genesis.l2_time, 1_700_000_000);
+        assert_eq!(rollup.l2_chain_id.id(), 42069);
+        assert_eq!(rollup.hardforks.isthmus_time, Some(1_700_000_000));
+        assert_eq!(rollup.hardforks.jovian_time, Some(1_700_000_100));
+        assert_eq!(rollup.hardforks.interop_time, None);
+        assert!(rollup.hardforks.check_activation_order().is_ok());
+
+        let system_config = rollup.genesis.system_config.unwrap();
+        assert_eq!(system_config.gas_limit, 50_000_000);
+        assert_eq!(system_config.overhead, U256::ZERO);
+        assert_eq!(
+            system_config.scalar,
+            U256::from_be_bytes(
+                b256!("0x010000000000000000000000000000000000000000000000000c5fc500000558").0
+            )
+        );
+    }
+
+    #[test]
+    fn test_legacy_scalar_before_ecotone() {
+        let mut config = deploy_config();
+        config.l2_genesis_ecotone_time_offset = Some(U64::from(10));
+
+        let system_config = config.rollup_config(l1_block()).genesis.system_config.unwrap();
+        assert_eq!(system_config.overhead, U256::from(DEFAULT_GAS_PRICE_ORACLE_OVERHEAD));
+        assert_eq!(system_config.scalar, U256::from(DEFAULT_GAS_PRICE_ORACLE_SCALAR));
+    }
+
+    #[test]
+    fn test_custom_gas_token() {
+        let token = address!("0x0000000000000000000000000000000000000a11");
+        let mut config = deploy_config();
+        config.custom_gas_token_address = Some(token);
+        assert_eq!(config.rollup_config(l1_block()).custom_gas_token, None);
+
+        config.use_custom_gas_token = true;
+        assert_eq!(
+            config.rollup_config(l1_block()).custom_gas_token,
+            Some(CustomGasTokenConfig::new(token))
+        );
+    }
+
+    #[test]
+    fn test_genesis() {
+        let allocs = BTreeMap::from([(
+            address!("0x0000000000000000000000000000000000000b47"),
+            GenesisAccount::default().with_balance(U256::from(1)),
+        )]);
+        let (genesis, rollup) = deploy_config().build(l1_block(), allocs);
+
+        assert_eq!(genesis.config.chain_id, 42069);
+        assert_eq!(genesis.config.shanghai_time, Some(1_700_000_000));
+        assert_eq!(genesis.config.extra_fields.get("jovianTime"), Some(&json!(1_700_000_100)));
+        assert_eq!(genesis.coinbase, Predeploys::SEQUENCER_FEE_VAULT);
+        assert_eq!(genesis.gas_limit, 50_000_000);
+        // Holocene extra data: version 0, denominator 250, elasticity 6.
+        assert_eq!(genesis.extra_data, Bytes::from(vec![0, 0, 0, 0, 250, 0, 0, 0, 6]));
+
+        let header = genesis_header(&rollup, &genesis);
+        assert_eq!(rollup.genesis.l2, BlockNumHash { number: 0, hash: header.hash_slow() });
+        assert_eq!(header.withdrawals_root, Some(EMPTY_ROOT_HASH));
+        assert_eq!(header.requests_hash, Some(EMPTY_REQUESTS_HASH));
+        assert_eq!(header.parent_beacon_block_root, Some(B256::ZERO));
+        assert_ne!(header.state_root, EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn test_bedrock_genesis() {
+        let config = DeployConfig {
+            l2_chain_id: 10,
+            eip1559_elasticity: 6,
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            ..Default::default()
+        };
+        let (genesis, rollup) = config.build(l1_block(), BTreeMap::new());
+
+        assert_eq!(genesis.extra_data, Bytes::from_static(BEDROCK_EXTRA_DATA));
+        assert_eq!(genesis.gas_limit, ETHEREUM_BLOCK_GAS_LIMIT_30M);
+        assert!(genesis.config.shanghai_time.is_none());
+
+        let header = genesis_header(&rollup, &genesis);
+        assert_eq!(header.state_root, EMPTY_ROOT_HASH);
+        assert!(header.withdrawals_root.is_none());
+        assert!(header.parent_beacon_block_root.is_none());
+    }
+}
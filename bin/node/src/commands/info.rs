@@ -3,7 +3,7 @@
 use crate::flags::GlobalArgs;
 use clap::Parser;
 use kona_cli::LogConfig;
-use kona_registry::{OPCHAINS, ROLLUP_CONFIGS};
+use kona_registry::{chain_config_by_chain_id, rollup_config_by_chain_id};
 use tracing::info;
 
 /// The `info` Subcommand
@@ -31,9 +31,10 @@ impl InfoCommand {
     pub fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
         info!(target: "node_info", "Running info command");
 
-        let op_chain_config = OPCHAINS.get(&args.l2_chain_id.id()).expect("No Chain config found");
+        let op_chain_config =
+            chain_config_by_chain_id(args.l2_chain_id.id()).expect("No Chain config found");
         let op_rollup_config =
-            ROLLUP_CONFIGS.get(&args.l2_chain_id.id()).expect("No Rollup config found");
+            rollup_config_by_chain_id(args.l2_chain_id.id()).expect("No Rollup config found");
 
         println!("Name: {}", op_chain_config.name);
         println!("Block Time: {}", op_chain_config.block_time);
@@ -0,0 +1,313 @@
+//! Keys Subcommand
+
+use crate::flags::GlobalArgs;
+use alloy_primitives::{Address, B256, hex};
+use alloy_signer::k256::elliptic_curve::rand_core::OsRng;
+use alloy_signer_local::PrivateKeySigner;
+use clap::{Args, Parser, Subcommand};
+use discv5::{Enr, enr::k256::ecdsa::SigningKey};
+use kona_cli::{LogConfig, SecretKeyLoader};
+use kona_disc::LocalNode;
+use libp2p::{PeerId, identity::secp256k1::SecretKey};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::Write,
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The `keys` Subcommand
+///
+/// The `keys` subcommand generates and inspects the secp256k1 keys used by the node: the p2p
+/// identity key, and the sequencer key signing unsafe blocks.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node keys generate p2p --out ./p2p.key
+/// kona-node keys generate sequencer --out ./sequencer.json --password <PASSWORD>
+/// kona-node keys inspect ./p2p.key
+/// ```
+#[derive(Parser, PartialEq, Eq, Debug, Clone)]
+#[command(about = "Generates and inspects p2p and sequencer keys")]
+pub struct KeysCommand {
+    /// The keys subcommand to run.
+    #[command(subcommand)]
+    pub subcommand: KeysSubcommand,
+}
+
+/// Subcommands of the `keys` subcommand.
+#[derive(Subcommand, PartialEq, Eq, Debug, Clone)]
+pub enum KeysSubcommand {
+    /// Generates a new key, and prints its public identities.
+    Generate(GenerateKeyArgs),
+    /// Prints the public identities of an existing key.
+    Inspect(InspectKeyArgs),
+}
+
+/// The type of key to generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum KeyType {
+    /// A p2p identity key, loaded with `--p2p.priv.path`.
+    P2p,
+    /// A sequencer key, loaded with `--p2p.sequencer.key.path`, or with
+    /// `--p2p.sequencer.keystore` when encrypted.
+    Sequencer,
+}
+
+/// Arguments of `keys generate`.
+#[derive(Args, PartialEq, Eq, Debug, Clone)]
+pub struct GenerateKeyArgs {
+    /// The type of key to generate: `p2p` or `sequencer`.
+    pub key_type: KeyType,
+    /// The file to write the key to. The hex encoded private key is printed if not set.
+    #[arg(long, short)]
+    pub out: Option<PathBuf>,
+    /// Encrypts the sequencer key into a JSON keystore with this password.
+    #[arg(long, env = "KONA_NODE_KEYS_PASSWORD", hide_env_values = true, requires = "out")]
+    pub password: Option<String>,
+    /// The address advertised in the printed ENR.
+    #[command(flatten)]
+    pub advertise: AdvertiseArgs,
+}
+
+/// Arguments of `keys inspect`.
+#[derive(Args, PartialEq, Eq, Debug, Clone)]
+pub struct InspectKeyArgs {
+    /// The key file: a hex encoded private key, or a JSON keystore.
+    pub path: PathBuf,
+    /// The password of the JSON keystore.
+    #[arg(long, env = "KONA_NODE_KEYS_PASSWORD", hide_env_values = true)]
+    pub password: Option<String>,
+    /// The address advertised in the printed ENR.
+    #[command(flatten)]
+    pub advertise: AdvertiseArgs,
+}
+
+/// The network address advertised in a printed ENR.
+#[derive(Args, PartialEq, Eq, Debug, Clone)]
+pub struct AdvertiseArgs {
+    /// The IP advertised in the ENR.
+    #[arg(long = "advertise.ip", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub ip: IpAddr,
+    /// The TCP port advertised in the ENR.
+    #[arg(long = "advertise.tcp", default_value = "9222")]
+    pub tcp_port: u16,
+    /// The UDP port advertised in the ENR.
+    #[arg(long = "advertise.udp", default_value = "9223")]
+    pub udp_port: u16,
+}
+
+/// The public identities derived from a secp256k1 private key.
+#[derive(Debug, Clone)]
+pub struct KeyIdentities {
+    /// The address signing unsafe blocks when the key is used as the sequencer key.
+    pub address: Address,
+    /// The libp2p peer ID when the key is used as the p2p key.
+    pub peer_id: PeerId,
+    /// The discovery ENR when the key is used as the p2p key.
+    pub enr: Enr,
+}
+
+impl KeyIdentities {
+    /// Derives the identities of `key`, advertising the given address in the ENR.
+    pub fn new(key: B256, advertise: &AdvertiseArgs, chain_id: u64) -> anyhow::Result<Self> {
+        let address = PrivateKeySigner::from_bytes(&key)?.address();
+        let peer_id = SecretKeyLoader::parse(&mut { key.0 })?.public().to_peer_id();
+        let signing_key = SigningKey::from_bytes(&key.0.into())?;
+        let enr = LocalNode::new(signing_key, advertise.ip, advertise.tcp_port, advertise.udp_port)
+            .build_enr(chain_id)
+            .map_err(|e| anyhow::anyhow!("Failed to build the ENR: {e}"))?;
+        Ok(Self { address, peer_id, enr })
+    }
+}
+
+impl fmt::Display for KeyIdentities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Address: {}", self.address)?;
+        writeln!(f, "Peer ID: {}", self.peer_id)?;
+        write!(f, "ENR:     {}", self.enr)
+    }
+}
+
+impl KeysCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let chain_id = args.l2_chain_id.id();
+        match self.subcommand {
+            KeysSubcommand::Generate(generate) => {
+                let key = B256::from(SecretKey::generate().to_bytes());
+                match &generate.out {
+                    Some(out) => {
+                        write_key(out, key, generate.key_type, generate.password.as_deref())?;
+                        println!("Wrote {} key to {}", generate.key_type, out.display());
+                    }
+                    None => println!("Private key: {key}"),
+                }
+                println!("{}", KeyIdentities::new(key, &generate.advertise, chain_id)?);
+            }
+            KeysSubcommand::Inspect(inspect) => {
+                let key = read_key(&inspect.path, inspect.password.as_deref())?;
+                println!("{}", KeyIdentities::new(key, &inspect.advertise, chain_id)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes `key` to `path` in a format the node loads for the given key type.
+///
+/// Keys are written hex encoded, or as a JSON keystore for sequencer keys with a password.
+/// Existing files are never overwritten. On unix, the file is only readable by its owner.
+fn write_key(
+    path: &Path,
+    key: B256,
+    key_type: KeyType,
+    password: Option<&str>,
+) -> anyhow::Result<()> {
+    if path.try_exists()? {
+        anyhow::bail!("Refusing to overwrite existing file {}", path.display());
+    }
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    match (key_type, password) {
+        (KeyType::P2p, Some(_)) => {
+            anyhow::bail!("p2p keys can't be encrypted, the node only loads them hex encoded")
+        }
+        (KeyType::Sequencer, Some(password)) => {
+            let dir = path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."));
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| anyhow::anyhow!("Invalid keystore path {}", path.display()))?;
+            PrivateKeySigner::encrypt_keystore(dir, &mut OsRng, key, password, Some(name))?;
+            #[cfg(unix)]
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        (_, None) => {
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            options.mode(0o600);
+            options.open(path)?.write_all(hex::encode(key).as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads a private key from a hex encoded key file, or from a JSON keystore.
+pub(crate) fn read_key(path: &Path, password: Option<&str>) -> anyhow::Result<B256> {
+    let contents = std::fs::read_to_string(path)?;
+    if contents.trim_start().starts_with('{') {
+        let password = password.ok_or_else(|| {
+            anyhow::anyhow!("`--password` is required to decrypt the keystore {}", path.display())
+        })?;
+        return Ok(PrivateKeySigner::decrypt_keystore(path, password)?.to_bytes());
+    }
+    Ok(B256::from_str(contents.trim())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    const KEY: B256 = b256!("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+
+    fn advertise() -> AdvertiseArgs {
+        AdvertiseArgs { ip: IpAddr::V4(Ipv4Addr::LOCALHOST), tcp_port: 9222, udp_port: 9223 }
+    }
+
+    #[test]
+    fn test_key_identities() {
+        let identities = KeyIdentities::new(KEY, &advertise(), 10).unwrap();
+        assert_eq!(identities.address, address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+        assert_eq!(identities.enr.tcp4(), Some(9222));
+        assert_eq!(identities.enr.udp4(), Some(9223));
+        // The peer ID and the ENR are derived from the same public key.
+        assert_eq!(
+            identities.peer_id,
+            kona_peers::enr_to_multiaddr(&identities.enr)
+                .and_then(|addr| addr.iter().find_map(|p| match p {
+                    libp2p::multiaddr::Protocol::P2p(peer_id) => Some(peer_id),
+                    _ => None,
+                }))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_and_read_hex_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys/p2p.key");
+
+        write_key(&path, KEY, KeyType::P2p, None).unwrap();
+        assert_eq!(read_key(&path, None).unwrap(), KEY);
+        // The node loads the written file.
+        assert!(SecretKeyLoader::load(&path).is_ok());
+        // Existing keys are never overwritten.
+        assert!(write_key(&path, B256::ZERO, KeyType::P2p, None).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_written_keys_are_owner_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let hex_path = dir.path().join("sequencer.key");
+        write_key(&hex_path, KEY, KeyType::Sequencer, None).unwrap();
+        assert_eq!(mode(&hex_path), 0o600);
+
+        let keystore_path = dir.path().join("sequencer.json");
+        write_key(&keystore_path, KEY, KeyType::Sequencer, Some("password")).unwrap();
+        assert_eq!(mode(&keystore_path), 0o600);
+    }
+
+    #[test]
+    fn test_p2p_key_not_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("p2p.json");
+        assert!(write_key(&path, KEY, KeyType::P2p, Some("password")).is_err());
+    }
+
+    #[test]
+    fn test_parse_keys_command() {
+        let command = KeysCommand::parse_from([
+            "keys",
+            "generate",
+            "sequencer",
+            "--out",
+            "sequencer.json",
+            "--password",
+            "password",
+        ]);
+        let KeysSubcommand::Generate(generate) = command.subcommand else {
+            panic!("Expected the generate subcommand");
+        };
+        assert_eq!(generate.key_type, KeyType::Sequencer);
+        assert_eq!(generate.password.as_deref(), Some("password"));
+
+        // A password requires an output file.
+        assert!(
+            KeysCommand::try_parse_from(["keys", "generate", "sequencer", "--password", "x"])
+                .is_err()
+        );
+    }
+}
@@ -12,5 +12,38 @@ pub use bootstore::BootstoreCommand;
 mod net;
 pub use net::NetCommand;
 
+mod record;
+pub use record::{DecodeArgs, EncodeArgs, NetSubcommand};
+
+mod config;
+pub use config::{ConfigCommand, ConfigSubcommand};
+
+mod doctor;
+pub use doctor::{CheckReport, DoctorCommand};
+
 mod registry;
-pub use registry::RegistryCommand;
+pub use registry::{RegistryCommand, RegistrySubcommand};
+
+mod keys;
+pub use keys::{
+    AdvertiseArgs, GenerateKeyArgs, InspectKeyArgs, KeyIdentities, KeyType, KeysCommand,
+    KeysSubcommand,
+};
+
+mod genesis;
+pub use genesis::{DeployConfig, GenesisCommand, genesis_header};
+
+mod db;
+pub use db::{DbCommand, DbSubcommand};
+
+mod conformance;
+pub use conformance::{BlockReport, ConformanceCommand, Mismatch};
+
+mod bench;
+pub use bench::{BenchCommand, BenchReport, LatencyReport};
+
+mod verify;
+pub use verify::{VerifyCommand, verify_attributes};
+
+mod top;
+pub use top::{NodeSnapshot, TopCommand};
@@ -1,16 +1,26 @@
 //! Net Subcommand
 
-use crate::flags::{GlobalArgs, P2PArgs, RpcArgs};
+use crate::{
+    commands::NetSubcommand,
+    flags::{GlobalArgs, P2PArgs, RpcArgs},
+};
 use clap::Parser;
 use futures::future::OptionFuture;
-use jsonrpsee::{RpcModule, server::Server};
+use jsonrpsee::{
+    RpcModule,
+    server::{RpcServiceBuilder, Server},
+};
 use kona_cli::LogConfig;
 use kona_gossip::P2pRpcRequest;
 use kona_node_service::{
-    NetworkActor, NetworkBuilder, NetworkContext, NetworkInboundData, NodeActor,
+    NetworkActor, NetworkBuilder, NetworkContext, NetworkInboundData, NodeActor, metered_channel,
 };
 use kona_registry::scr_rollup_config_by_alloy_ident;
-use kona_rpc::{OpP2PApiServer, P2pRpc, RpcBuilder};
+use kona_rpc::{
+    DebugP2PApiServer, MethodPolicyService, MethodPolicyState, OpP2PApiServer, P2pRpc,
+    RequestIdService, RpcBuilder,
+};
+use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 use url::Url;
@@ -23,10 +33,18 @@ use url::Url;
 ///
 /// ```sh
 /// kona-node net [FLAGS] [OPTIONS]
+/// kona-node net decode <ENR|MULTIADDR>
+/// kona-node net encode <P2P_KEY_FILE> --advertise.ip <IP>
 /// ```
 #[derive(Parser, Default, PartialEq, Debug, Clone)]
-#[command(about = "Runs the networking stack for the kona-node.")]
+#[command(
+    about = "Runs the networking stack for the kona-node.",
+    args_conflicts_with_subcommands = true
+)]
 pub struct NetCommand {
+    /// Decodes and encodes peer records instead of running the networking stack.
+    #[command(subcommand)]
+    pub subcommand: Option<NetSubcommand>,
     /// URL of the L1 execution client RPC API.
     /// This is used to load the unsafe block signer at startup.
     /// Without this, the rollup config unsafe block signer will be used which may be outdated.
@@ -55,6 +73,10 @@ impl NetCommand {
 
     /// Run the Net subcommand.
     pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        if let Some(subcommand) = self.subcommand {
+            return subcommand.run(args);
+        }
+
         let signer = args.genesis_signer()?;
         info!(target: "net", "Genesis block signer: {:?}", signer);
 
@@ -71,8 +93,14 @@ impl NetCommand {
         let (NetworkInboundData { p2p_rpc: rpc, .. }, network) =
             NetworkActor::new(NetworkBuilder::from(p2p_config));
 
-        let (blocks, mut blocks_rx) = tokio::sync::mpsc::channel(1024);
-        network.start(NetworkContext { blocks, cancellation: CancellationToken::new() }).await?;
+        let (blocks, mut blocks_rx) = metered_channel("unsafe_blocks", 1024, Default::default());
+        network
+            .start(NetworkContext {
+                blocks,
+                runtime_flags: Default::default(),
+                cancellation: CancellationToken::new(),
+            })
+            .await?;
 
         info!(target: "net", "Network started, receiving blocks.");
 
@@ -84,9 +112,19 @@ impl NetCommand {
 
             // Setup the RPC server with the P2P RPC Module
             let mut launcher = RpcModule::new(());
-            launcher.merge(P2pRpc::new(rpc.clone()).into_rpc())?;
-
-            let server = Server::builder().build(config.socket).await?;
+            let p2p_rpc = P2pRpc::new(rpc.clone());
+            launcher.merge(DebugP2PApiServer::into_rpc(p2p_rpc.clone()))?;
+            launcher.merge(OpP2PApiServer::into_rpc(p2p_rpc))?;
+
+            let policy = Arc::new(MethodPolicyState::new(config.method_policy()));
+            let server = Server::builder()
+                .set_rpc_middleware(
+                    RpcServiceBuilder::new().layer_fn(RequestIdService::new).layer_fn(
+                        move |service| MethodPolicyService::new(service, Arc::clone(&policy)),
+                    ),
+                )
+                .build(config.socket)
+                .await?;
             Some(server.start(launcher))
         } else {
             info!(target: "net", "RPC server disabled");
@@ -100,7 +138,7 @@ impl NetCommand {
                 }
                 _ = interval.tick(), if !rpc.is_closed() => {
                     let (otx, mut orx) = tokio::sync::oneshot::channel();
-                    if let Err(e) = rpc.send(P2pRpcRequest::PeerCount(otx)).await {
+                    if let Err(e) = rpc.send(P2pRpcRequest::PeerCount(otx).into()).await {
                         warn!(target: "net", "Failed to send network rpc request: {:?}", e);
                         continue;
                     }
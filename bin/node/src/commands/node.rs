@@ -1,9 +1,13 @@
 //! Node Subcommand.
 
 use crate::{
+    cli::{Cli, Commands},
+    config,
     flags::{
-        BuilderClientArgs, GlobalArgs, L1ClientArgs, L2ClientArgs, P2PArgs, RollupBoostFlags,
-        RpcArgs, SequencerArgs,
+        ArchiveArgs, BatcherArgs, BuilderClientArgs, ChannelAlarmArgs, DbArgs, DerivationArgs,
+        GlobalArgs, InteropArgs, L1ClientArgs, L2ClientArgs, P2PArgs, ProposerArgs,
+        PruningHintArgs, RollupBoostFlags, RpcArgs, SequencerArgs, ShadowForkArgs, SnapshotArgs,
+        SyncArgs, parse_secs,
     },
     metrics::{CliMetrics, init_rollup_config_metrics},
 };
@@ -12,18 +16,21 @@ use alloy_rpc_types_engine::JwtSecret;
 use alloy_transport_http::Http;
 use anyhow::{Result, bail};
 use backon::{ExponentialBuilder, Retryable};
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use kona_cli::{LogConfig, MetricsArgs};
 use kona_engine::{HyperAuthClient, OpEngineClient};
 use kona_genesis::{L1ChainConfig, RollupConfig};
-use kona_node_service::{EngineConfig, L1ConfigBuilder, NodeMode, RollupNodeBuilder};
+use kona_node_service::{
+    EngineConfig, L1ConfigBuilder, NodeMode, NodeSignal, NodeSignals, RollupNodeBuilder,
+    RollupNodeHandle, WsL1BlockSource,
+};
 use kona_registry::{L1Config, scr_rollup_config_by_alloy_ident};
 use op_alloy_network::Optimism;
 use op_alloy_provider::ext::engine::OpEngineApi;
 use serde_json::from_reader;
 use std::{fs::File, io::Write, path::PathBuf, sync::Arc, time::Duration};
 use strum::IntoEnumIterator;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 /// A JWT token validation error.
 #[derive(Debug, thiserror::Error)]
@@ -106,6 +113,15 @@ pub struct NodeCommand {
     /// (overrides the default rollup configuration from the registry)
     #[arg(long, visible_alias = "rollup-l1-cfg", env = "KONA_NODE_L1_CHAIN_CONFIG")]
     pub l1_config_file: Option<PathBuf>,
+    /// Time given to the node to finish its in-flight work when it is shut down or reloaded,
+    /// in seconds.
+    #[arg(
+        long = "shutdown-timeout",
+        default_value = "30",
+        env = "KONA_NODE_SHUTDOWN_TIMEOUT",
+        value_parser = parse_secs
+    )]
+    pub shutdown_timeout: Duration,
     /// P2P CLI arguments.
     #[command(flatten)]
     pub p2p_flags: P2PArgs,
@@ -115,10 +131,59 @@ pub struct NodeCommand {
     /// SEQUENCER CLI arguments.
     #[command(flatten)]
     pub sequencer_flags: SequencerArgs,
+    /// PROPOSER CLI arguments.
+    #[command(flatten)]
+    pub proposer_flags: ProposerArgs,
+    /// BATCHER CLI arguments.
+    #[command(flatten)]
+    pub batcher_flags: BatcherArgs,
+    /// Node state snapshot CLI arguments.
+    #[command(flatten)]
+    pub snapshot_flags: SnapshotArgs,
+    /// Pruning hint CLI arguments.
+    #[command(flatten)]
+    pub pruning_hint_flags: PruningHintArgs,
+    /// Derived attributes archive CLI arguments.
+    #[command(flatten)]
+    pub archive_flags: ArchiveArgs,
 
     /// Rollup boost CLI arguments - contains the builder and l2 engine arguments.
     #[command(flatten)]
     pub rollup_boost_flags: RollupBoostFlags,
+
+    /// Shadow fork CLI arguments.
+    #[command(flatten)]
+    pub shadow_fork_flags: ShadowForkArgs,
+
+    /// Sync CLI arguments.
+    #[command(flatten)]
+    pub sync_flags: SyncArgs,
+
+    /// Node database CLI arguments.
+    #[command(flatten)]
+    pub db_flags: DbArgs,
+
+    /// Derivation CLI arguments.
+    #[command(flatten)]
+    pub derivation_flags: DerivationArgs,
+
+    /// Interop CLI arguments.
+    #[command(flatten)]
+    pub interop_flags: InteropArgs,
+
+    /// Internal channel alarm CLI arguments.
+    #[command(flatten)]
+    pub channel_alarm_flags: ChannelAlarmArgs,
+
+    /// Celestia CLI arguments.
+    #[cfg(feature = "celestia")]
+    #[command(flatten)]
+    pub celestia_flags: crate::flags::CelestiaArgs,
+
+    /// EigenDA CLI arguments.
+    #[cfg(feature = "eigenda")]
+    #[command(flatten)]
+    pub eigenda_flags: crate::flags::EigenDaArgs,
 }
 
 impl Default for NodeCommand {
@@ -129,11 +194,27 @@ impl Default for NodeCommand {
             builder_client_args: BuilderClientArgs::default(),
             l2_config_file: None,
             l1_config_file: None,
+            shutdown_timeout: Duration::from_secs(30),
             node_mode: NodeMode::Validator,
             p2p_flags: P2PArgs::default(),
             rpc_flags: RpcArgs::default(),
             sequencer_flags: SequencerArgs::default(),
+            proposer_flags: ProposerArgs::default(),
+            batcher_flags: BatcherArgs::default(),
+            snapshot_flags: SnapshotArgs::default(),
+            pruning_hint_flags: PruningHintArgs::default(),
+            archive_flags: ArchiveArgs::default(),
             rollup_boost_flags: RollupBoostFlags::default(),
+            shadow_fork_flags: ShadowForkArgs::default(),
+            sync_flags: SyncArgs::default(),
+            db_flags: DbArgs::default(),
+            derivation_flags: DerivationArgs::default(),
+            interop_flags: InteropArgs::default(),
+            channel_alarm_flags: ChannelAlarmArgs::default(),
+            #[cfg(feature = "celestia")]
+            celestia_flags: crate::flags::CelestiaArgs::default(),
+            #[cfg(feature = "eigenda")]
+            eigenda_flags: crate::flags::EigenDaArgs::default(),
         }
     }
 }
@@ -141,14 +222,16 @@ impl Default for NodeCommand {
 impl NodeCommand {
     /// Initializes the logging system based on global arguments.
     pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
-        // Filter out discovery warnings since they're very very noisy.
-        let filter = tracing_subscriber::EnvFilter::from_default_env()
-            .add_directive("discv5=error".parse()?);
-
-        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(Some(filter))?;
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(Some(Self::log_filter()?))?;
         Ok(())
     }
 
+    /// Returns the base filter of the node logs.
+    fn log_filter() -> anyhow::Result<tracing_subscriber::EnvFilter> {
+        // Filter out discovery warnings since they're very very noisy.
+        Ok(tracing_subscriber::EnvFilter::from_default_env().add_directive("discv5=error".parse()?))
+    }
+
     /// Initializes CLI metrics for the Node subcommand.
     pub fn init_cli_metrics(&self, args: &MetricsArgs) -> anyhow::Result<()> {
         if !args.enabled {
@@ -200,7 +283,7 @@ impl NodeCommand {
     }
 
     /// Check if the error is related to JWT signature validation
-    fn is_jwt_signature_error(error: &dyn std::error::Error) -> bool {
+    pub(super) fn is_jwt_signature_error(error: &dyn std::error::Error) -> bool {
         let mut source = Some(error);
         while let Some(err) = source {
             let err_str = err.to_string().to_lowercase();
@@ -267,8 +350,135 @@ impl NodeCommand {
             .await
     }
 
-    /// Run the Node subcommand.
+    /// Run the Node subcommand, until the node stops or a [`NodeSignal::Shutdown`] is received.
+    ///
+    /// A [`NodeSignal::Shutdown`] drains the node within the shutdown timeout. A
+    /// [`NodeSignal::Reload`] checks that the command line, the environment and the configuration
+    /// file still parse, drains the node, and replaces the process with a new instance of the node,
+    /// which reads the configuration, JWT secrets and log levels again. A
+    /// [`NodeSignal::DebugReport`] logs the state of the node.
     pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let mut signals = NodeSignals::new()?;
+        let Some(handle) = self.launch_until_signal(args, &mut signals).await? else {
+            return Ok(());
+        };
+        if self.supervise(handle, &mut signals).await? {
+            return Err(Self::restart());
+        }
+        Ok(())
+    }
+
+    /// Launches the node, unless a [`NodeSignal::Shutdown`] is received while it is starting.
+    async fn launch_until_signal(
+        &self,
+        args: &GlobalArgs,
+        signals: &mut NodeSignals,
+    ) -> anyhow::Result<Option<RollupNodeHandle>> {
+        let launch = self.launch(args);
+        tokio::pin!(launch);
+        loop {
+            tokio::select! {
+                handle = &mut launch => return handle.map(Some),
+                signal = signals.recv() => match signal {
+                    NodeSignal::Shutdown => {
+                        info!(target: "rollup_node", "Received shutdown signal while starting");
+                        return Ok(None);
+                    }
+                    signal => {
+                        warn!(target: "rollup_node", %signal, "Ignoring signal while starting");
+                    }
+                },
+            }
+        }
+    }
+
+    /// Handles the signals received while the node is running, until it stops or is drained.
+    ///
+    /// Returns `true` if the node was drained to be restarted with a reloaded configuration.
+    async fn supervise(
+        &self,
+        mut handle: RollupNodeHandle,
+        signals: &mut NodeSignals,
+    ) -> anyhow::Result<bool> {
+        loop {
+            tokio::select! {
+                result = handle.stopped() => {
+                    return result.map(|()| false).map_err(|e| {
+                        error!(target: "rollup_node", "Rollup node service stopped: {e}");
+                        anyhow::anyhow!("{e}")
+                    });
+                }
+                signal = signals.recv() => match signal {
+                    NodeSignal::DebugReport => handle.log_debug_report(),
+                    NodeSignal::Shutdown => {
+                        info!(
+                            target: "rollup_node",
+                            timeout = self.shutdown_timeout.as_secs(),
+                            "Received shutdown signal, draining the node"
+                        );
+                        self.drain(handle).await?;
+                        return Ok(false);
+                    }
+                    NodeSignal::Reload => match Self::check_reload() {
+                        Ok(()) => {
+                            info!(
+                                target: "rollup_node",
+                                timeout = self.shutdown_timeout.as_secs(),
+                                "Received reload signal, draining the node to restart it"
+                            );
+                            // The restart releases whatever the node did not release in time.
+                            if let Err(e) = self.drain(handle).await {
+                                warn!(target: "rollup_node", "Restarting undrained node: {e}");
+                            }
+                            return Ok(true);
+                        }
+                        Err(e) => error!(
+                            target: "rollup_node",
+                            "Failed to reload the configuration, keeping the current one: {e}"
+                        ),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Drains the node within the shutdown timeout.
+    async fn drain(&self, handle: RollupNodeHandle) -> anyhow::Result<()> {
+        handle.drain(self.shutdown_timeout).await.map_err(|e| anyhow::anyhow!("{e}"))
+    }
+
+    /// Checks that the command line, the environment and the configuration file of the process
+    /// still parse into a node command.
+    fn check_reload() -> anyhow::Result<()> {
+        let args = config::args_with_config(&Cli::command(), std::env::args_os().collect())?;
+        match Cli::try_parse_from(args)?.subcommand {
+            Commands::Node(_) => Ok(()),
+            command => bail!("Expected the node subcommand, found {command}"),
+        }
+    }
+
+    /// Replaces the process with a new instance of the node, started with the same command line
+    /// and environment. The process keeps its ID, and its database lock, sockets and ports are
+    /// released before the new instance starts. Only returns if the process cannot be replaced.
+    #[cfg(unix)]
+    fn restart() -> anyhow::Error {
+        use std::os::unix::process::CommandExt;
+
+        let err = std::env::current_exe().map_or_else(
+            |e| e,
+            |exe| std::process::Command::new(exe).args(std::env::args_os().skip(1)).exec(),
+        );
+        anyhow::anyhow!("Failed to restart the node: {err}")
+    }
+
+    /// Reloading is not supported on this platform, as no signal requests it.
+    #[cfg(not(unix))]
+    fn restart() -> anyhow::Error {
+        anyhow::anyhow!("Restarting the node is not supported on this platform")
+    }
+
+    /// Builds the rollup node from the command and launches it in the background.
+    async fn launch(&self, args: &GlobalArgs) -> anyhow::Result<RollupNodeHandle> {
         let cfg = self.get_l2_config(args)?;
 
         info!(
@@ -288,10 +498,33 @@ impl NodeCommand {
             slot_duration_override: self.l1_rpc_args.l1_slot_duration_override,
         };
 
+        let jwt_secret = self.validate_jwt().await?;
+
+        if self.shadow_fork_flags.enabled() && self.sync_flags.checkpoint_config().is_some() {
+            anyhow::bail!("Checkpoint sync can't be used in shadow fork mode");
+        }
+
+        // In shadow fork mode, the genesis is moved to the fork point before any of the services
+        // observe the rollup config.
+        let cfg = self
+            .shadow_fork_flags
+            .apply(
+                cfg,
+                self.l1_rpc_args.l1_eth_rpc.clone(),
+                self.l2_client_args.l2_engine_rpc.clone(),
+                jwt_secret,
+            )
+            .await?;
+
         // If metrics are enabled, initialize the global cli metrics.
         args.metrics.enabled.then(|| init_rollup_config_metrics(&cfg));
 
-        let jwt_secret = self.validate_jwt().await?;
+        if self.shadow_fork_flags.enabled() && !self.p2p_flags.no_discovery {
+            warn!(
+                target: "rollup_node",
+                "Shadow fork mode is enabled with peer discovery on. Unsafe blocks gossiped by the live network will compete with the forked chain; consider `--p2p.no-discovery`."
+            );
+        }
 
         self.p2p_flags.check_ports()?;
         let p2p_config = self
@@ -312,9 +545,20 @@ impl NodeCommand {
             l1_url: self.l1_rpc_args.l1_eth_rpc.clone(),
             mode: self.node_mode,
             rollup_boost: self.rollup_boost_flags.as_rollup_boost_args(),
+            channel_alarms: (&self.channel_alarm_flags).into(),
+            attributes_watermarks: self.derivation_flags.attributes_watermarks()?,
+            checkpoint: self.sync_flags.checkpoint_config(),
+            auto_sync: self.sync_flags.auto_sync_config(),
+            unsafe_gap_limit: self.sync_flags.unsafe_gap_limit,
+            forkchoice_batching: self.sync_flags.forkchoice_batching(),
+            derivation_halt: self.derivation_flags.halt_config()?,
+            db: self.db_flags.open()?,
         };
 
-        RollupNodeBuilder::new(
+        let dependency_set = self.interop_flags.dependency_set(&cfg)?;
+        let derivation_memory_budget = self.derivation_flags.memory_budget(&cfg)?;
+
+        let mut builder = RollupNodeBuilder::new(
             cfg,
             l1_config,
             self.l2_client_args.l2_trust_rpc,
@@ -323,15 +567,46 @@ impl NodeCommand {
             rpc_config,
         )
         .with_sequencer_config(self.sequencer_flags.config())
-        .build()
-        .start()
-        .await
-        .map_err(|e| {
-            error!(target: "rollup_node", "Failed to start rollup node service: {e}");
-            anyhow::anyhow!("{e}")
-        })?;
+        .with_proposer_config(self.proposer_flags.config()?)
+        .with_batcher_config(self.batcher_flags.config()?)
+        .with_snapshot_config(self.snapshot_flags.config()?)
+        .with_pruning_hint_config(self.pruning_hint_flags.config()?)
+        .with_archive_config(self.archive_flags.config()?)
+        .with_archive_source(self.archive_flags.source()?)
+        .with_derivation_memory_budget(derivation_memory_budget);
+
+        if let Some(ws_url) = &self.l1_rpc_args.l1_ws_rpc {
+            builder = builder.with_l1_block_source(Arc::new(WsL1BlockSource::new(
+                ws_url.clone(),
+                self.l1_rpc_args.l1_eth_rpc.clone(),
+            )));
+        }
+        if let Some(dependency_set) = dependency_set {
+            builder = builder.with_dependency_set(dependency_set);
+        }
+        #[cfg(feature = "celestia")]
+        if let Some(config) = self.celestia_flags.config()? {
+            use kona_providers_alloy::{CELESTIA_DA_LAYER, CelestiaProvider};
+            builder = builder.with_alt_da_provider(
+                kona_derive::CommitmentType::Generic(CELESTIA_DA_LAYER),
+                CelestiaProvider::new(config)?,
+            );
+        }
+        #[cfg(feature = "eigenda")]
+        if let Some(config) = self.eigenda_flags.config() {
+            use kona_providers_alloy::{EIGENDA_DA_LAYER, EigenDaProvider};
+            let mut provider = EigenDaProvider::new(config)?;
+            if let Some(address) = self.eigenda_flags.service_manager {
+                let l1_provider = RootProvider::new_http(self.l1_rpc_args.l1_eth_rpc.clone());
+                provider = provider.with_service_manager(l1_provider, address);
+            }
+            builder = builder.with_alt_da_provider(
+                kona_derive::CommitmentType::Generic(EIGENDA_DA_LAYER),
+                provider,
+            );
+        }
 
-        Ok(())
+        Ok(builder.build().launch())
     }
 
     /// Get the L1 config, either from a file or the known chains.
@@ -353,23 +628,25 @@ impl NodeCommand {
         }
     }
 
-    /// Get the L2 rollup config, either from a file or the superchain registry.
+    /// Get the L2 rollup config, either from a file or the superchain registry, with the
+    /// hardfork overrides applied.
     pub fn get_l2_config(&self, args: &GlobalArgs) -> Result<RollupConfig> {
-        match &self.l2_config_file {
+        let cfg = match &self.l2_config_file {
             Some(path) => {
                 debug!("Loading l2 config from file: {:?}", path);
                 let file = File::open(path)
                     .map_err(|e| anyhow::anyhow!("Failed to open l2 config file: {e}"))?;
-                from_reader(file).map_err(|e| anyhow::anyhow!("Failed to parse l2 config: {e}"))
+                from_reader(file).map_err(|e| anyhow::anyhow!("Failed to parse l2 config: {e}"))?
             }
             None => {
                 debug!("Loading l2 config from superchain registry");
                 let Some(cfg) = scr_rollup_config_by_alloy_ident(&args.l2_chain_id) else {
                     bail!("Failed to find l2 config for chain ID {}", args.l2_chain_id);
                 };
-                Ok(cfg.clone())
+                cfg.clone()
             }
-        }
+        };
+        args.apply_overrides(cfg)
     }
 
     /// Returns the L2 JWT secret for the engine API
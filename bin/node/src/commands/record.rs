@@ -0,0 +1,292 @@
+//! Peer record utilities of the `net` subcommand.
+
+use crate::{
+    commands::keys::{AdvertiseArgs, KeyIdentities, read_key},
+    flags::GlobalArgs,
+};
+use alloy_primitives::{B512, hex, keccak256};
+use clap::{Args, Subcommand};
+use discv5::{Enr, enr::EnrPublicKey};
+use kona_peers::{
+    EnrValidation, NodeRecord, OpStackEnr, enr_to_multiaddr, peer_id_to_secp256k1_pubkey,
+};
+use libp2p::{Multiaddr, PeerId, identity, multiaddr::Protocol};
+use std::{net::IpAddr, path::PathBuf, str::FromStr};
+
+/// The multihash code of an identity hash, used by peer IDs of secp256k1 keys.
+const IDENTITY_MULTIHASH_CODE: u64 = 0x00;
+
+/// Subcommands of the `net` subcommand.
+#[derive(Subcommand, PartialEq, Eq, Debug, Clone)]
+pub enum NetSubcommand {
+    /// Decodes an ENR or a multiaddr, and prints its fields.
+    Decode(DecodeArgs),
+    /// Encodes a signed ENR from a p2p key, and prints it with the matching multiaddr and enode.
+    Encode(EncodeArgs),
+}
+
+/// Arguments of `net decode`.
+#[derive(Args, PartialEq, Eq, Debug, Clone)]
+pub struct DecodeArgs {
+    /// The record to decode: an `enr:` string, or a multiaddr such as
+    /// `/ip4/127.0.0.1/tcp/9222/p2p/<PEER_ID>`.
+    pub record: String,
+}
+
+/// Arguments of `net encode`.
+#[derive(Args, PartialEq, Eq, Debug, Clone)]
+pub struct EncodeArgs {
+    /// The p2p key signing the ENR: a hex encoded private key file.
+    pub key: PathBuf,
+    /// The address advertised in the ENR.
+    #[command(flatten)]
+    pub advertise: AdvertiseArgs,
+}
+
+impl NetSubcommand {
+    /// Runs the subcommand.
+    pub fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let chain_id = args.l2_chain_id.id();
+        let fields = match self {
+            Self::Decode(decode) => decode_record(&decode.record, chain_id)?,
+            Self::Encode(encode) => {
+                let key = read_key(&encode.key, None)?;
+                let identities = KeyIdentities::new(key, &encode.advertise, chain_id)?;
+                encode_fields(&identities.enr)
+            }
+        };
+
+        let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+        for (name, value) in fields {
+            println!("{:<width$}  {value}", format!("{name}:"), width = width + 1);
+        }
+        Ok(())
+    }
+}
+
+/// Decodes an ENR or a multiaddr into a list of named fields.
+///
+/// ENRs are only decoded if their signature is valid, and their `opstack` key is validated
+/// against `chain_id`.
+fn decode_record(record: &str, chain_id: u64) -> anyhow::Result<Vec<(&'static str, String)>> {
+    let record = record.trim();
+    if record.starts_with("enr:") {
+        let enr = Enr::from_str(record).map_err(|e| anyhow::anyhow!("Invalid ENR: {e}"))?;
+        return Ok(decode_enr(&enr, chain_id));
+    }
+
+    let addr = Multiaddr::from_str(record)
+        .map_err(|e| anyhow::anyhow!("Expected an `enr:` record or a multiaddr: {e}"))?;
+    decode_multiaddr(&addr)
+}
+
+/// Returns the fields of a decoded ENR.
+fn decode_enr(enr: &Enr, chain_id: u64) -> Vec<(&'static str, String)> {
+    let mut fields = vec![
+        ("Node ID", hex::encode_prefixed(enr.node_id().raw())),
+        ("Sequence", enr.seq().to_string()),
+        ("Signature", if enr.verify() { "valid" } else { "invalid" }.to_string()),
+    ];
+
+    let public_key = B512::from_slice(&enr.public_key().encode_uncompressed());
+    fields.push(("Public Key", public_key.to_string()));
+    if let Some(peer_id) = libp2p_peer_id(public_key) {
+        fields.push(("Peer ID", peer_id.to_string()));
+    }
+
+    push_endpoint(&mut fields, "IPv4", enr.ip4().map(IpAddr::V4), enr.tcp4(), enr.udp4());
+    push_endpoint(&mut fields, "IPv6", enr.ip6().map(IpAddr::V6), enr.tcp6(), enr.udp6());
+
+    match OpStackEnr::try_from(enr) {
+        Ok(opstack) => {
+            fields.push(("Chain ID", opstack.chain_id.to_string()));
+            fields.push(("Version", opstack.version.to_string()));
+        }
+        Err(e) => fields.push(("OP Stack", e.to_string())),
+    }
+    fields.push(("Validation", EnrValidation::validate(enr, chain_id).to_string()));
+
+    fields.extend(encode_fields(enr).into_iter().filter(|(name, _)| *name != "ENR"));
+    fields
+}
+
+/// Returns the fields of a decoded multiaddr.
+///
+/// The public key, node ID and enode are only derived if the peer ID embeds a secp256k1 key.
+fn decode_multiaddr(addr: &Multiaddr) -> anyhow::Result<Vec<(&'static str, String)>> {
+    let mut fields = Vec::new();
+    let (mut ip, mut tcp, mut peer_id) = (None, None, None);
+    for protocol in addr.iter() {
+        match protocol {
+            Protocol::Ip4(addr) => ip = Some(IpAddr::V4(addr)),
+            Protocol::Ip6(addr) => ip = Some(IpAddr::V6(addr)),
+            Protocol::Dns(host) | Protocol::Dns4(host) | Protocol::Dns6(host) => {
+                fields.push(("DNS", host.to_string()))
+            }
+            Protocol::Tcp(port) => tcp = Some(port),
+            Protocol::Udp(port) => fields.push(("UDP", port.to_string())),
+            Protocol::P2p(id) => peer_id = Some(id),
+            other => fields.push(("Protocol", other.to_string())),
+        }
+    }
+    if let Some(ip) = ip {
+        fields.push(("IP", ip.to_string()));
+    }
+    if let Some(tcp) = tcp {
+        fields.push(("TCP", tcp.to_string()));
+    }
+
+    let Some(peer_id) = peer_id else {
+        fields.push(("Peer ID", "missing".to_string()));
+        return Ok(fields);
+    };
+    fields.push(("Peer ID", peer_id.to_string()));
+
+    let public_key = secp256k1_public_key(&peer_id)
+        .ok_or_else(|| anyhow::anyhow!("Peer ID {peer_id} does not embed a secp256k1 key"))?;
+    fields.push(("Public Key", public_key.to_string()));
+    fields.push(("Node ID", hex::encode_prefixed(keccak256(public_key))));
+    if let (Some(ip), Some(tcp)) = (ip, tcp) {
+        let record = NodeRecord::new_with_ports(ip, tcp, None, public_key);
+        fields.push(("Enode", record.to_string()));
+    }
+    Ok(fields)
+}
+
+/// Returns the ENR with the multiaddr and enode it is dialed with.
+fn encode_fields(enr: &Enr) -> Vec<(&'static str, String)> {
+    let mut fields = vec![("ENR", enr.to_base64())];
+    if let Some(addr) = enr_to_multiaddr(enr) {
+        fields.push(("Multiaddr", addr.to_string()));
+    }
+    if let (Some(ip), Some(tcp)) = (enr.ip4(), enr.tcp4()) {
+        let public_key = B512::from_slice(&enr.public_key().encode_uncompressed());
+        let record = NodeRecord::new_with_ports(ip.into(), tcp, enr.udp4(), public_key);
+        fields.push(("Enode", record.to_string()));
+    }
+    fields
+}
+
+/// Pushes the IP and ports of an ENR endpoint, if the IP is set.
+fn push_endpoint(
+    fields: &mut Vec<(&'static str, String)>,
+    name: &'static str,
+    ip: Option<IpAddr>,
+    tcp: Option<u16>,
+    udp: Option<u16>,
+) {
+    let Some(ip) = ip else {
+        return;
+    };
+    let port = |port: Option<u16>| port.map_or_else(|| "-".to_string(), |p| p.to_string());
+    fields.push((name, format!("{ip} (tcp: {}, udp: {})", port(tcp), port(udp))));
+}
+
+/// Returns the libp2p peer ID of an uncompressed secp256k1 public key.
+fn libp2p_peer_id(public_key: B512) -> Option<PeerId> {
+    let public_key = peer_id_to_secp256k1_pubkey(public_key).ok()?.serialize();
+    let public_key = identity::secp256k1::PublicKey::try_from_bytes(&public_key).ok()?;
+    Some(identity::PublicKey::from(public_key).to_peer_id())
+}
+
+/// Returns the uncompressed secp256k1 public key embedded in a libp2p peer ID.
+fn secp256k1_public_key(peer_id: &PeerId) -> Option<B512> {
+    let multihash = peer_id.as_ref();
+    if multihash.code() != IDENTITY_MULTIHASH_CODE {
+        return None;
+    }
+    let public_key = identity::PublicKey::try_decode_protobuf(multihash.digest()).ok()?;
+    let uncompressed = public_key.try_into_secp256k1().ok()?.to_bytes_uncompressed();
+    Some(B512::from_slice(&uncompressed[1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::NetCommand;
+    use alloy_primitives::{B256, b256};
+    use clap::Parser;
+    use std::net::Ipv4Addr;
+
+    const KEY: B256 = b256!("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+
+    fn enr(chain_id: u64) -> Enr {
+        let advertise =
+            AdvertiseArgs { ip: IpAddr::V4(Ipv4Addr::LOCALHOST), tcp_port: 9222, udp_port: 9223 };
+        KeyIdentities::new(KEY, &advertise, chain_id).unwrap().enr
+    }
+
+    fn field<'a>(fields: &'a [(&'static str, String)], name: &str) -> &'a str {
+        fields.iter().find(|(n, _)| *n == name).map(|(_, v)| v.as_str()).unwrap()
+    }
+
+    #[test]
+    fn test_decode_enr() {
+        let enr = enr(10);
+        let fields = decode_record(&enr.to_base64(), 10).unwrap();
+
+        assert_eq!(field(&fields, "Signature"), "valid");
+        assert_eq!(field(&fields, "IPv4"), "127.0.0.1 (tcp: 9222, udp: 9223)");
+        assert_eq!(field(&fields, "Chain ID"), "10");
+        assert_eq!(field(&fields, "Version"), "0");
+        assert_eq!(field(&fields, "Validation"), "Valid ENR");
+        assert_eq!(field(&fields, "Multiaddr"), enr_to_multiaddr(&enr).unwrap().to_string());
+
+        // The chain ID is validated against the configured chain.
+        let fields = decode_record(&enr.to_base64(), 8453).unwrap();
+        assert_eq!(field(&fields, "Validation"), "Invalid Chain ID: 10");
+    }
+
+    #[test]
+    fn test_decode_enr_invalid_signature() {
+        // Flip a character of the signature, which follows the RLP list and string headers.
+        let mut encoded = enr(10).to_base64().into_bytes();
+        encoded[12] = if encoded[12] == b'A' { b'B' } else { b'A' };
+        let encoded = String::from_utf8(encoded).unwrap();
+        assert!(decode_record(&encoded, 10).is_err());
+    }
+
+    #[test]
+    fn test_decode_multiaddr_roundtrip() {
+        let enr = enr(10);
+        let addr = enr_to_multiaddr(&enr).unwrap();
+        let fields = decode_record(&addr.to_string(), 10).unwrap();
+
+        assert_eq!(field(&fields, "IP"), "127.0.0.1");
+        assert_eq!(field(&fields, "TCP"), "9222");
+        assert_eq!(field(&fields, "Node ID"), hex::encode_prefixed(enr.node_id().raw()));
+        assert_eq!(
+            field(&fields, "Public Key"),
+            B512::from_slice(&enr.public_key().encode_uncompressed()).to_string()
+        );
+        assert!(field(&fields, "Enode").starts_with("enode://"));
+    }
+
+    #[test]
+    fn test_decode_invalid_record() {
+        assert!(decode_record("not a record", 10).is_err());
+        assert!(decode_record("enr:invalid", 10).is_err());
+    }
+
+    #[test]
+    fn test_parse_net_subcommands() {
+        let command = NetCommand::parse_from(["net", "decode", "/ip4/127.0.0.1/tcp/9222"]);
+        assert_eq!(
+            command.subcommand,
+            Some(NetSubcommand::Decode(DecodeArgs { record: "/ip4/127.0.0.1/tcp/9222".into() }))
+        );
+        assert!(NetCommand::parse_from(["net"]).subcommand.is_none());
+
+        // The flags of the networking stack don't apply to the subcommands.
+        assert!(
+            NetCommand::try_parse_from([
+                "net",
+                "--l1-eth-rpc",
+                "http://localhost:8545",
+                "decode",
+                "x"
+            ])
+            .is_err()
+        );
+    }
+}
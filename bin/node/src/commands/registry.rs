@@ -1,21 +1,36 @@
 //! Registry Subcommand
 
 use crate::flags::GlobalArgs;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use kona_cli::LogConfig;
 
 /// The `registry` Subcommand
 ///
-/// The `registry` subcommand lists the OP Stack chains available in the `superchain-registry`.
+/// The `registry` subcommand lists the OP Stack chains available in the `superchain-registry`,
+/// and validates their rollup configs.
 ///
 /// # Usage
 ///
 /// ```sh
-/// kona-node registry [FLAGS] [OPTIONS]
+/// kona-node registry [FLAGS] [OPTIONS] [list|validate]
 /// ```
 #[derive(Parser, Default, PartialEq, Debug, Clone)]
 #[command(about = "Lists the OP Stack chains available in the superchain-registry")]
-pub struct RegistryCommand;
+pub struct RegistryCommand {
+    /// The registry subcommand to run.
+    #[command(subcommand)]
+    pub subcommand: Option<RegistrySubcommand>,
+}
+
+/// Subcommands of the `registry` subcommand.
+#[derive(Subcommand, Default, PartialEq, Eq, Debug, Clone)]
+pub enum RegistrySubcommand {
+    /// Lists the chains of the registry.
+    #[default]
+    List,
+    /// Validates the rollup config of every chain of the registry.
+    Validate,
+}
 
 impl RegistryCommand {
     /// Initializes the logging system based on global arguments.
@@ -26,7 +41,21 @@ impl RegistryCommand {
 
     /// Runs the subcommand.
     pub fn run(self, _args: &GlobalArgs) -> anyhow::Result<()> {
-        let chains = kona_registry::CHAINS.chains.clone();
+        match self.subcommand.unwrap_or_default() {
+            RegistrySubcommand::List => Self::list(),
+            RegistrySubcommand::Validate => Self::validate(),
+        }
+    }
+
+    /// Prints the chains of the registry, including the chains of the local registry.
+    pub fn list() -> anyhow::Result<()> {
+        let mut chains = kona_registry::CHAINS.chains.clone();
+        if let Some(local) = kona_registry::local_registry() {
+            for chain in &local.chain_list.chains {
+                chains.retain(|c| c.chain_id != chain.chain_id);
+                chains.push(chain.clone());
+            }
+        }
         let mut table = tabled::Table::new(chains);
         table.with(tabled::settings::Style::modern());
         table.modify(
@@ -36,4 +65,41 @@ impl RegistryCommand {
         println!("{table}");
         Ok(())
     }
+
+    /// Validates the rollup config of every chain of the registry, including the chains of the
+    /// local registry.
+    ///
+    /// Returns an error if any rollup config is invalid.
+    pub fn validate() -> anyhow::Result<()> {
+        let errors = kona_registry::validate_rollup_configs();
+        for (chain_id, error) in &errors {
+            let name = kona_registry::chain_config_by_chain_id(*chain_id)
+                .map_or("unknown", |config| config.name.as_str());
+            println!("{name} ({chain_id}): {error}");
+        }
+        if !errors.is_empty() {
+            anyhow::bail!("{} invalid rollup configs", errors.len());
+        }
+        println!("All rollup configs are valid");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_registry_command() {
+        let command = RegistryCommand::parse_from(["registry"]);
+        assert_eq!(command.subcommand.unwrap_or_default(), RegistrySubcommand::List);
+
+        let command = RegistryCommand::parse_from(["registry", "validate"]);
+        assert_eq!(command.subcommand, Some(RegistrySubcommand::Validate));
+    }
+
+    #[test]
+    fn test_validate_registry() {
+        assert!(RegistryCommand::validate().is_ok());
+    }
 }
@@ -0,0 +1,423 @@
+//! Top Subcommand
+
+use crate::flags::GlobalArgs;
+use anyhow::{Context, Result};
+use clap::Parser;
+use jsonrpsee::{
+    core::client::ClientT, http_client::HttpClientBuilder, ws_client::WsClientBuilder,
+};
+use kona_cli::{LogArgs, LogConfig};
+use kona_gossip::PeerCount;
+use kona_protocol::{BlockInfo, L2BlockInfo, SyncStatus};
+use kona_rpc::{DevEngineApiClient, OpP2PApiClient, RollupNodeApiClient};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph, Row, Sparkline, Table},
+};
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use url::Url;
+
+/// The number of samples kept for the sparklines of the dashboard.
+const HISTORY_LEN: usize = 240;
+
+/// The `top` Subcommand
+///
+/// The `top` subcommand is a terminal dashboard of a running node. It polls the RPC of the node
+/// for its sync status, gossip peer count and engine task queue length, and shows the L1 and L2
+/// heads, the L1 origin of the derivation pipeline, and sparklines of the head progress, peer
+/// count and queue depth. The engine queue depth is only available if the `dev` RPC namespace of
+/// the node is enabled.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node top --rpc ws://127.0.0.1:9545
+/// ```
+#[derive(Parser, PartialEq, Eq, Debug, Clone)]
+#[command(about = "Shows a live dashboard of a running node")]
+pub struct TopCommand {
+    /// URL of the RPC of the node, over HTTP or websockets.
+    #[arg(
+        long = "rpc",
+        value_name = "URL",
+        default_value = "http://127.0.0.1:9545",
+        env = "KONA_NODE_TOP_RPC"
+    )]
+    pub rpc: Url,
+    /// The refresh interval of the dashboard, in seconds.
+    #[arg(
+        long = "interval",
+        value_name = "SECONDS",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u64).range(1..),
+        env = "KONA_NODE_TOP_INTERVAL"
+    )]
+    pub interval: u64,
+}
+
+impl Default for TopCommand {
+    fn default() -> Self {
+        Self::parse_from(["top"])
+    }
+}
+
+impl TopCommand {
+    /// Initializes the logging system based on global arguments. Logs are never written to
+    /// stdout, which is taken by the dashboard.
+    pub fn init_logs(&self, args: &GlobalArgs) -> Result<()> {
+        let log_args = LogArgs { stdout_quiet: true, ..args.log_args.clone() };
+        LogConfig::new(log_args).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the top subcommand until `q` or `Esc` is pressed.
+    pub async fn run(self, _args: &GlobalArgs) -> Result<()> {
+        match self.rpc.scheme() {
+            "ws" | "wss" => {
+                let client = WsClientBuilder::default()
+                    .build(self.rpc.as_str())
+                    .await
+                    .context("Failed to connect to the node RPC")?;
+                self.run_dashboard(&client).await
+            }
+            _ => {
+                let client = HttpClientBuilder::default()
+                    .build(self.rpc.as_str())
+                    .context("Failed to build the node RPC client")?;
+                self.run_dashboard(&client).await
+            }
+        }
+    }
+
+    /// Polls the node with the given client and renders the dashboard, restoring the terminal
+    /// once it exits.
+    async fn run_dashboard<C: ClientT + Sync>(&self, client: &C) -> Result<()> {
+        let version = RollupNodeApiClient::op_version(client)
+            .await
+            .context("Failed to query the version of the node")?;
+
+        let mut terminal = ratatui::init();
+        let result = self.draw_loop(&mut terminal, client, &version).await;
+        ratatui::restore();
+        result
+    }
+
+    /// Refreshes the dashboard every interval until the user quits.
+    async fn draw_loop<C: ClientT + Sync>(
+        &self,
+        terminal: &mut DefaultTerminal,
+        client: &C,
+        version: &str,
+    ) -> Result<()> {
+        let interval = Duration::from_secs(self.interval);
+        let mut dashboard = Dashboard::default();
+        loop {
+            dashboard.update(NodeSnapshot::fetch(client).await);
+            terminal.draw(|frame| dashboard.render(frame, &self.rpc, version))?;
+
+            // Crossterm only exposes blocking reads of the terminal events.
+            let quit = tokio::task::spawn_blocking(move || wait_for_quit(interval)).await??;
+            if quit {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Waits up to `timeout` for the user to press `q` or `Esc`, returning whether they did.
+fn wait_for_quit(timeout: Duration) -> std::io::Result<bool> {
+    if !event::poll(timeout)? {
+        return Ok(false);
+    }
+    let Event::Key(key) = event::read()? else {
+        return Ok(false);
+    };
+    Ok(key.kind == KeyEventKind::Press && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc))
+}
+
+/// The state of the node polled at each refresh of the dashboard. Each field is unset if its
+/// RPC call failed.
+#[derive(Debug, Clone, Default)]
+pub struct NodeSnapshot {
+    /// The sync status of the node, from `optimism_syncStatus`.
+    pub sync_status: Option<SyncStatus>,
+    /// The peer count of the node, from `opp2p_peerCount`.
+    pub peer_count: Option<PeerCount>,
+    /// The length of the engine task queue, from `dev_taskQueueLength`.
+    pub engine_queue: Option<usize>,
+}
+
+impl NodeSnapshot {
+    /// Polls the node with the given client.
+    pub async fn fetch<C: ClientT + Sync>(client: &C) -> Self {
+        let (sync_status, peer_count, engine_queue) = tokio::join!(
+            RollupNodeApiClient::op_sync_status(client),
+            OpP2PApiClient::opp2p_peer_count(client),
+            DevEngineApiClient::dev_task_queue_length(client),
+        );
+        Self {
+            sync_status: sync_status.ok(),
+            peer_count: peer_count.ok(),
+            engine_queue: engine_queue.ok(),
+        }
+    }
+}
+
+/// A bounded history of samples, shown as a sparkline.
+#[derive(Debug, Clone, Default)]
+struct History(VecDeque<u64>);
+
+impl History {
+    /// Records a sample, dropping the oldest one once the history holds [`HISTORY_LEN`] samples.
+    fn push(&mut self, sample: u64) {
+        if self.0.len() == HISTORY_LEN {
+            self.0.pop_front();
+        }
+        self.0.push_back(sample);
+    }
+
+    /// Returns the most recent samples, up to `width`, oldest first.
+    fn tail(&self, width: usize) -> Vec<u64> {
+        self.0.iter().skip(self.0.len().saturating_sub(width)).copied().collect()
+    }
+}
+
+/// The state of the dashboard: the latest snapshot of the node, and the history of its metrics.
+#[derive(Debug, Clone, Default)]
+struct Dashboard {
+    /// The latest snapshot of the node.
+    latest: NodeSnapshot,
+    /// The number of blocks the unsafe head moved forward by at each refresh.
+    unsafe_progress: History,
+    /// The number of blocks the safe head moved forward by at each refresh.
+    safe_progress: History,
+    /// The number of connected gossip peers at each refresh.
+    peers: History,
+    /// The length of the engine task queue at each refresh.
+    engine_queue: History,
+}
+
+impl Dashboard {
+    /// Records a new snapshot of the node. The head progress is only recorded if the sync status
+    /// is known at both the previous and the new snapshot, and counts a reorged head as no
+    /// progress.
+    fn update(&mut self, snapshot: NodeSnapshot) {
+        if let (Some(previous), Some(current)) = (&self.latest.sync_status, &snapshot.sync_status) {
+            let progress = |previous: &L2BlockInfo, current: &L2BlockInfo| {
+                current.block_info.number.saturating_sub(previous.block_info.number)
+            };
+            self.unsafe_progress.push(progress(&previous.unsafe_l2, &current.unsafe_l2));
+            self.safe_progress.push(progress(&previous.safe_l2, &current.safe_l2));
+        }
+        if let Some(peer_count) = &snapshot.peer_count {
+            self.peers.push(peer_count.connected_gossip as u64);
+        }
+        if let Some(engine_queue) = snapshot.engine_queue {
+            self.engine_queue.push(engine_queue as u64);
+        }
+        self.latest = snapshot;
+    }
+
+    /// Renders the dashboard to the frame.
+    fn render(&self, frame: &mut Frame<'_>, rpc: &Url, version: &str) {
+        let [header, heads, sparklines] =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(11), Constraint::Min(8)])
+                .areas(frame.area());
+        let [top_row, bottom_row] =
+            Layout::vertical([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).areas(sparklines);
+        let [unsafe_area, safe_area] =
+            Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).areas(top_row);
+        let [peers_area, queue_area] =
+            Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                .areas(bottom_row);
+
+        let status = if self.latest.sync_status.is_some() {
+            Line::styled("connected", Style::default().fg(Color::Green))
+        } else {
+            Line::styled("unreachable", Style::default().fg(Color::Red))
+        };
+        frame.render_widget(
+            Paragraph::new(status).block(
+                Block::bordered()
+                    .title(format!(" kona-node {version} @ {rpc} "))
+                    .title_bottom(" q to quit "),
+            ),
+            header,
+        );
+
+        self.render_heads(frame, heads);
+
+        let unsafe_title = format!(
+            " Unsafe head: {} ",
+            self.latest
+                .sync_status
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |s| s.unsafe_l2.block_info.number.to_string())
+        );
+        Self::render_sparkline(
+            frame,
+            unsafe_area,
+            unsafe_title,
+            &self.unsafe_progress,
+            Color::Cyan,
+        );
+        let safe_title = format!(
+            " Safe head: {} ",
+            self.latest
+                .sync_status
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |s| s.safe_l2.block_info.number.to_string())
+        );
+        Self::render_sparkline(frame, safe_area, safe_title, &self.safe_progress, Color::Green);
+        let peers_title = format!(
+            " Gossip peers: {} ",
+            self.latest
+                .peer_count
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |p| p.connected_gossip.to_string())
+        );
+        Self::render_sparkline(frame, peers_area, peers_title, &self.peers, Color::Magenta);
+        let queue_title = format!(
+            " Engine queue: {} ",
+            self.latest.engine_queue.map_or_else(|| "-".to_string(), |q| q.to_string())
+        );
+        Self::render_sparkline(frame, queue_area, queue_title, &self.engine_queue, Color::Yellow);
+    }
+
+    /// Renders the table of the L1 and L2 heads of the node.
+    fn render_heads(&self, frame: &mut Frame<'_>, area: Rect) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let rows = self.latest.sync_status.as_ref().map_or_else(Vec::new, |s| {
+            [
+                ("Unsafe L2", &s.unsafe_l2.block_info),
+                ("Safe L2", &s.safe_l2.block_info),
+                ("Finalized L2", &s.finalized_l2.block_info),
+                ("Derivation origin", &s.current_l1),
+                ("L1 head", &s.head_l1),
+                ("L1 safe", &s.safe_l1),
+                ("L1 finalized", &s.finalized_l1),
+            ]
+            .into_iter()
+            .map(|(label, block)| head_row(label, block, now))
+            .collect()
+        });
+        let header = Row::new(["Head", "Number", "Hash", "Age"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let widths = [
+            Constraint::Length(18),
+            Constraint::Length(12),
+            Constraint::Length(68),
+            Constraint::Min(8),
+        ];
+        frame.render_widget(
+            Table::new(rows, widths).header(header).block(Block::bordered().title(" Heads ")),
+            area,
+        );
+    }
+
+    /// Renders the history as a sparkline, with the most recent sample on the right.
+    fn render_sparkline(
+        frame: &mut Frame<'_>,
+        area: Rect,
+        title: String,
+        history: &History,
+        color: Color,
+    ) {
+        let data = history.tail(area.width.saturating_sub(2) as usize);
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::bordered().title(title))
+                .data(&data)
+                .style(Style::default().fg(color)),
+            area,
+        );
+    }
+}
+
+/// Returns the row of the heads table for the given block, with its age relative to `now`.
+fn head_row(label: &'static str, block: &BlockInfo, now: u64) -> Row<'static> {
+    let age = if block.timestamp == 0 {
+        "-".to_string()
+    } else {
+        format!("{}s", now.saturating_sub(block.timestamp))
+    };
+    Row::new([label.to_string(), block.number.to_string(), block.hash.to_string(), age])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(unsafe_head: u64, safe_head: u64, peers: usize) -> NodeSnapshot {
+        let head = |number| L2BlockInfo {
+            block_info: BlockInfo { number, ..Default::default() },
+            ..Default::default()
+        };
+        NodeSnapshot {
+            sync_status: Some(SyncStatus {
+                current_l1: BlockInfo::default(),
+                current_l1_finalized: BlockInfo::default(),
+                head_l1: BlockInfo::default(),
+                safe_l1: BlockInfo::default(),
+                finalized_l1: BlockInfo::default(),
+                unsafe_l2: head(unsafe_head),
+                safe_l2: head(safe_head),
+                finalized_l2: L2BlockInfo::default(),
+                pending_safe_l2: L2BlockInfo::default(),
+                cross_unsafe_l2: L2BlockInfo::default(),
+                local_safe_l2: L2BlockInfo::default(),
+                sync_mode: None,
+                retention_horizon: None,
+            }),
+            peer_count: Some(PeerCount { connected_discovery: None, connected_gossip: peers }),
+            engine_queue: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_top_command() {
+        let command = TopCommand::default();
+        assert_eq!(command.rpc.as_str(), "http://127.0.0.1:9545/");
+        assert_eq!(command.interval, 1);
+
+        let command =
+            TopCommand::parse_from(["top", "--rpc", "ws://localhost:8545", "--interval", "5"]);
+        assert_eq!(command.rpc.scheme(), "ws");
+        assert_eq!(command.interval, 5);
+        assert!(TopCommand::try_parse_from(["top", "--interval", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_dashboard_update() {
+        let mut dashboard = Dashboard::default();
+        dashboard.update(snapshot(10, 5, 3));
+        dashboard.update(snapshot(14, 6, 4));
+        // A reorged unsafe head counts as no progress.
+        dashboard.update(snapshot(12, 6, 4));
+        // A failed poll of the sync status skips the head progress.
+        dashboard.update(NodeSnapshot { engine_queue: Some(2), ..Default::default() });
+        dashboard.update(snapshot(20, 8, 5));
+
+        assert_eq!(dashboard.unsafe_progress.tail(HISTORY_LEN), [4, 0]);
+        assert_eq!(dashboard.safe_progress.tail(HISTORY_LEN), [1, 0]);
+        assert_eq!(dashboard.peers.tail(HISTORY_LEN), [3, 4, 4, 5]);
+        assert_eq!(dashboard.engine_queue.tail(HISTORY_LEN), [2]);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = History::default();
+        for sample in 0..(HISTORY_LEN as u64 + 10) {
+            history.push(sample);
+        }
+        assert_eq!(history.0.len(), HISTORY_LEN);
+        assert_eq!(history.tail(2), [HISTORY_LEN as u64 + 8, HISTORY_LEN as u64 + 9]);
+    }
+}
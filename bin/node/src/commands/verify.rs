@@ -0,0 +1,334 @@
+//! Verify Subcommand
+
+use super::conformance::{BlockReport, Mismatch, parse_block_range};
+use crate::flags::{GlobalArgs, L1ClientArgs};
+use alloy_consensus::Header;
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{Provider, RootProvider};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_derive::{
+    ActivationSignal, ChainProvider, OriginProvider, Pipeline, PipelineError, PipelineErrorKind,
+    ResetError, ResetSignal, SignalReceiver, StepResult,
+};
+use kona_protocol::{BatchValidationProvider, L2BlockInfo, OpAttributesWithParent};
+use kona_providers_alloy::{
+    AlloyChainProvider, AlloyL2ChainProvider, AlloyL2ChainProviderError, OnlineBeaconClient,
+    OnlineBlobProvider, OnlinePipeline,
+};
+use kona_registry::{L1Config, scr_rollup_config_by_alloy_ident};
+use op_alloy_consensus::OpBlock;
+use op_alloy_network::Optimism;
+use std::{ops::Range, sync::Arc, time::Duration};
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// The size of the provider caches of the derivation pipeline.
+const VERIFY_PROVIDER_CACHE_SIZE: usize = 1024;
+
+/// The interval at which the pipeline and the trusted L2 RPC are polled for new data.
+const VERIFY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The `verify` Subcommand
+///
+/// The `verify` subcommand runs the derivation pipeline without an execution client. Instead of
+/// executing the derived payload attributes, it checks them against the headers of the blocks
+/// served by a trusted L2 RPC: the parent hash, the timestamp and the transactions root of each
+/// header must match the attributes derived from L1. Divergent blocks are reported, and
+/// derivation carries on from the trusted block, so that a deployment of this subcommand can
+/// monitor the honesty of a sequencer at the cost of an L1 RPC and beacon API.
+///
+/// Without a `--range`, the subcommand starts after the safe head of the trusted L2 RPC and
+/// follows the chain until it is stopped.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node --chain optimism verify --l1-eth-rpc <URL> --l1-beacon <URL> --l2-rpc <URL>
+/// ```
+#[derive(Parser, Debug, Clone)]
+#[command(about = "Verifies derived attributes against the headers of a trusted L2 RPC")]
+pub struct VerifyCommand {
+    /// The L1 RPC and beacon API to derive from.
+    #[command(flatten)]
+    pub l1_rpc_args: L1ClientArgs,
+    /// URL of the trusted L2 RPC serving the headers to verify.
+    #[arg(long = "l2-rpc", value_name = "URL", env = "KONA_NODE_VERIFY_L2_RPC")]
+    pub l2_rpc: Url,
+    /// The range of blocks to verify, as `start..end` with `end` exclusive.
+    #[arg(long = "range", value_name = "START..END", value_parser = parse_block_range)]
+    pub range: Option<Range<u64>>,
+}
+
+/// Returns the fields of the trusted `header` that differ from the derived `attributes`.
+pub fn verify_attributes(attributes: &OpAttributesWithParent, header: &Header) -> Vec<Mismatch> {
+    let transactions = attributes.attributes.transactions.as_deref().unwrap_or_default();
+    let transactions_root =
+        alloy_consensus::proofs::ordered_trie_root_with_encoder(transactions, |tx, buf| {
+            buf.extend_from_slice(tx)
+        });
+
+    [
+        Mismatch::check("parent hash", header.parent_hash, attributes.parent.block_info.hash),
+        Mismatch::check(
+            "timestamp",
+            header.timestamp,
+            attributes.attributes.payload_attributes.timestamp,
+        ),
+        Mismatch::check("transactions root", header.transactions_root, transactions_root),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+impl VerifyCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> Result<()> {
+        let Some(cfg) = scr_rollup_config_by_alloy_ident(&args.l2_chain_id) else {
+            bail!("Failed to find l2 config for chain ID {}", args.l2_chain_id);
+        };
+        let cfg = Arc::new(args.apply_overrides(cfg.clone())?);
+        let l1_cfg = L1Config::get_l1_genesis(cfg.l1_chain_id).map_err(|e| {
+            anyhow!("Failed to find l1 config for chain ID {}: {e}", cfg.l1_chain_id)
+        })?;
+
+        let provider = RootProvider::<Optimism>::new_http(self.l2_rpc.clone());
+        let chain_id = provider.get_chain_id().await.context("Failed to reach the L2 RPC")?;
+        if chain_id != cfg.l2_chain_id.id() {
+            bail!("The L2 RPC serves chain {chain_id}, expected {}", cfg.l2_chain_id.id());
+        }
+
+        let start = match &self.range {
+            Some(range) => range.start,
+            None => {
+                let safe = provider
+                    .get_block_by_number(BlockNumberOrTag::Safe)
+                    .await?
+                    .context("The L2 RPC has no safe block")?;
+                safe.header.number + 1
+            }
+        };
+        let end = self.range.as_ref().map(|range| range.end);
+
+        let mut l1_provider = AlloyChainProvider::new_with_trust(
+            RootProvider::new_http(self.l1_rpc_args.l1_eth_rpc.clone()),
+            VERIFY_PROVIDER_CACHE_SIZE,
+            self.l1_rpc_args.l1_trust_rpc,
+        );
+        let mut l2_provider =
+            AlloyL2ChainProvider::new(provider, cfg.clone(), VERIFY_PROVIDER_CACHE_SIZE);
+        let mut beacon = OnlineBeaconClient::new_http(self.l1_rpc_args.l1_beacon.to_string());
+        if let Some(duration) = self.l1_rpc_args.l1_slot_duration_override {
+            beacon = beacon.with_l1_slot_duration_override(duration);
+        }
+
+        // Start derivation from the parent of the first block, rewinding the L1 origin by a
+        // channel timeout to pick up the channels that were opened before it, as an engine reset
+        // does.
+        let mut cursor = l2_provider
+            .l2_block_info_by_number(start - 1)
+            .await
+            .with_context(|| format!("Failed to fetch L2 block {}", start - 1))?;
+        let origin_number = cursor
+            .l1_origin
+            .number
+            .saturating_sub(cfg.channel_timeout(cursor.block_info.timestamp));
+        let l1_origin = l1_provider
+            .block_info_by_number(origin_number)
+            .await
+            .with_context(|| format!("Failed to fetch L1 block {origin_number}"))?;
+        let mut pipeline = OnlinePipeline::new(
+            cfg.clone(),
+            Arc::new(l1_cfg.into()),
+            cursor,
+            l1_origin,
+            OnlineBlobProvider::init(beacon).await,
+            l1_provider,
+            l2_provider.clone(),
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to initialize the derivation pipeline: {e}"))?;
+
+        info!(target: "verify", start, ?end, l1_origin = origin_number, "Verifying derived blocks");
+
+        let mut verified = 0;
+        let mut failed = 0;
+        while end.is_none_or(|end| cursor.block_info.number + 1 < end) {
+            let attributes = next_attributes(&mut pipeline, cursor).await?;
+            let number = cursor.block_info.number + 1;
+            let block = trusted_block(&mut l2_provider, number).await?;
+
+            let report =
+                BlockReport { number, outcome: Ok(verify_attributes(&attributes, &block.header)) };
+            println!("{report}");
+            if !report.passed() {
+                warn!(target: "verify", number, "Derived attributes diverge from the trusted block");
+                failed += 1;
+            }
+            verified += 1;
+
+            // Carry on from the trusted block, even if it diverges from the derived attributes.
+            cursor = L2BlockInfo::from_block_and_genesis(&block, &cfg.genesis)
+                .with_context(|| format!("Failed to read the L1 origin of L2 block {number}"))?;
+        }
+
+        if failed > 0 {
+            bail!("{failed} of {verified} blocks diverged from the derived attributes");
+        }
+        println!("All {verified} blocks match the derived attributes");
+        Ok(())
+    }
+}
+
+/// Steps the pipeline until it derives the attributes of the block after `cursor`, waiting for
+/// new L1 data when the pipeline is exhausted.
+async fn next_attributes(
+    pipeline: &mut OnlinePipeline,
+    cursor: L2BlockInfo,
+) -> Result<OpAttributesWithParent> {
+    loop {
+        if let Some(attributes) = pipeline.next() {
+            return Ok(attributes);
+        }
+
+        match pipeline.step(cursor).await {
+            StepResult::PreparedAttributes => {}
+            StepResult::AdvancedOrigin => {
+                debug!(target: "verify", l1_origin = ?pipeline.origin(), "Advanced origin");
+            }
+            StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => match e {
+                PipelineErrorKind::Temporary(PipelineError::Eof) => {
+                    tokio::time::sleep(VERIFY_POLL_INTERVAL).await;
+                }
+                PipelineErrorKind::Temporary(_) => {}
+                PipelineErrorKind::Reset(e) => {
+                    warn!(target: "verify", ?e, "Resetting the derivation pipeline");
+                    let l1_origin = pipeline
+                        .origin()
+                        .ok_or_else(|| anyhow!("The derivation pipeline has no L1 origin"))?;
+                    let system_config = pipeline
+                        .system_config_by_number(cursor.block_info.number)
+                        .await
+                        .map_err(|e| anyhow!("Failed to fetch the system config: {e}"))?;
+                    let signal = if matches!(e, ResetError::HoloceneActivation) {
+                        ActivationSignal {
+                            l2_safe_head: cursor,
+                            l1_origin,
+                            system_config: Some(system_config),
+                        }
+                        .signal()
+                    } else {
+                        ResetSignal {
+                            l2_safe_head: cursor,
+                            l1_origin,
+                            system_config: Some(system_config),
+                        }
+                        .signal()
+                    };
+                    pipeline
+                        .signal(signal)
+                        .await
+                        .map_err(|e| anyhow!("Failed to reset the derivation pipeline: {e}"))?;
+                }
+                PipelineErrorKind::Critical(e) => {
+                    bail!("Derivation failed after L2 block {}: {e}", cursor.block_info.number);
+                }
+            },
+        }
+    }
+}
+
+/// Fetches a block from the trusted L2 RPC, waiting for it to be produced if it is not yet
+/// known.
+async fn trusted_block(provider: &mut AlloyL2ChainProvider, number: u64) -> Result<OpBlock> {
+    loop {
+        match provider.block_by_number(number).await {
+            Ok(block) => return Ok(block),
+            Err(AlloyL2ChainProviderError::BlockNotFound(_)) => {
+                tokio::time::sleep(VERIFY_POLL_INTERVAL).await;
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to fetch L2 block {number}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, Bytes};
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use kona_protocol::BlockInfo;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    fn attributes(transactions: Vec<Bytes>) -> OpAttributesWithParent {
+        let parent = L2BlockInfo {
+            block_info: BlockInfo { hash: B256::repeat_byte(1), number: 9, ..Default::default() },
+            ..Default::default()
+        };
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes { timestamp: 20, ..Default::default() },
+            transactions: Some(transactions),
+            ..Default::default()
+        };
+        OpAttributesWithParent::new(attributes, parent, None, false)
+    }
+
+    #[test]
+    fn test_parse_verify_command() {
+        let command = VerifyCommand::parse_from([
+            "verify",
+            "--l1-eth-rpc",
+            "http://localhost:8545",
+            "--l1-beacon",
+            "http://localhost:5052",
+            "--l2-rpc",
+            "http://localhost:9545",
+        ]);
+        assert_eq!(command.l2_rpc.as_str(), "http://localhost:9545/");
+        assert_eq!(command.range, None);
+
+        let command = VerifyCommand::parse_from([
+            "verify",
+            "--l1-eth-rpc",
+            "http://localhost:8545",
+            "--l1-beacon",
+            "http://localhost:5052",
+            "--l2-rpc",
+            "http://localhost:9545",
+            "--range",
+            "10..20",
+        ]);
+        assert_eq!(command.range, Some(10..20));
+    }
+
+    #[test]
+    fn test_verify_attributes() {
+        let transactions = vec![Bytes::from_static(&[0x7e, 0x01]), Bytes::from_static(&[0x02])];
+        let attributes = attributes(transactions.clone());
+        let header = Header {
+            parent_hash: B256::repeat_byte(1),
+            timestamp: 20,
+            transactions_root: alloy_consensus::proofs::ordered_trie_root_with_encoder(
+                &transactions,
+                |tx, buf| buf.extend_from_slice(tx),
+            ),
+            ..Default::default()
+        };
+        assert!(verify_attributes(&attributes, &header).is_empty());
+
+        let divergent = Header { timestamp: 22, transactions_root: B256::ZERO, ..header };
+        let fields = verify_attributes(&attributes, &divergent)
+            .into_iter()
+            .map(|mismatch| mismatch.field)
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["timestamp", "transactions root"]);
+    }
+}
@@ -0,0 +1,357 @@
+//! TOML configuration file support.
+//!
+//! A configuration file sets CLI flags by their long name, either as top-level keys or nested in
+//! tables, where the table path is joined with `.`:
+//!
+//! ```toml
+//! chain = "optimism"
+//! l1-eth-rpc = "http://localhost:8545"
+//!
+//! [p2p.listen]
+//! tcp = 9223
+//! ```
+//!
+//! Values from the file are inserted into the command line before it is parsed, so that the
+//! precedence is CLI flags > environment variables > config file > defaults. A value from the
+//! file is skipped if its flag is passed on the command line, or its environment variable is set.
+
+use clap::{Arg, Command};
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+/// The long name of the config file flag.
+pub const CONFIG_FLAG: &str = "config";
+
+/// The environment variable for the config file flag.
+pub const CONFIG_ENV: &str = "KONA_NODE_CONFIG";
+
+/// An error loading or applying a configuration file.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigFileError {
+    /// The `--config` flag was passed without a value.
+    #[error("Missing value for --{CONFIG_FLAG}")]
+    MissingPath,
+    /// The config file could not be read.
+    #[error("Failed to read config file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    /// The config file is not valid TOML.
+    #[error("Failed to parse config file {0}: {1}")]
+    Toml(PathBuf, toml::de::Error),
+    /// The config file contains a key that does not match any flag.
+    #[error("Unknown config key `{0}`")]
+    UnknownKey(String),
+    /// The config file contains a value of the wrong type for its flag.
+    #[error("Invalid value for config key `{0}`: {1}")]
+    InvalidValue(String, &'static str),
+}
+
+/// A parsed configuration file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigFile {
+    /// The path the config file was loaded from.
+    pub path: PathBuf,
+    /// The flattened `(key, value)` entries of the config file.
+    pub entries: Vec<(String, Value)>,
+}
+
+impl ConfigFile {
+    /// Loads a [`ConfigFile`] from the given path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigFileError> {
+        let path = path.as_ref().to_path_buf();
+        let contents =
+            std::fs::read_to_string(&path).map_err(|e| ConfigFileError::Io(path.clone(), e))?;
+        let table =
+            contents.parse::<Table>().map_err(|e| ConfigFileError::Toml(path.clone(), e))?;
+        Ok(Self::from_table(path, table))
+    }
+
+    /// Creates a [`ConfigFile`] from a parsed TOML table.
+    pub fn from_table(path: PathBuf, table: Table) -> Self {
+        let mut entries = Vec::new();
+        flatten(None, table, &mut entries);
+        Self { path, entries }
+    }
+
+    /// Returns the config file path passed on the command line with `--config`, falling back to
+    /// the [`CONFIG_ENV`] environment variable.
+    pub fn path_from_args(args: &[OsString]) -> Result<Option<PathBuf>, ConfigFileError> {
+        let flag = format!("--{CONFIG_FLAG}");
+        let mut args = args.iter().skip(1).map(|a| a.to_string_lossy());
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                break;
+            }
+            if arg == flag {
+                return args
+                    .next()
+                    .map(|path| Some(PathBuf::from(path.as_ref())))
+                    .ok_or(ConfigFileError::MissingPath);
+            }
+            if let Some(path) = arg.strip_prefix(&format!("{flag}=")) {
+                return Ok(Some(PathBuf::from(path)));
+            }
+        }
+        Ok(std::env::var_os(CONFIG_ENV).map(PathBuf::from))
+    }
+
+    /// Inserts the values of the config file into the given command line arguments.
+    ///
+    /// Values for top-level flags are inserted directly after the binary name, and values for
+    /// subcommand flags directly after the subcommand. Keys that only apply to other subcommands
+    /// are ignored, and keys that do not match any flag are rejected.
+    pub fn apply(
+        &self,
+        cmd: &Command,
+        args: Vec<OsString>,
+    ) -> Result<Vec<OsString>, ConfigFileError> {
+        let subcommand = find_subcommand(cmd, &args);
+
+        let mut root_args = Vec::new();
+        let mut sub_args = Vec::new();
+        for (key, value) in &self.entries {
+            let (arg, target) = if let Some(arg) = find_arg(cmd, key) {
+                (arg, &mut root_args)
+            } else if let Some(arg) = subcommand.and_then(|(_, sub)| find_arg(sub, key)) {
+                (arg, &mut sub_args)
+            } else if cmd.get_subcommands().any(|sub| find_arg(sub, key).is_some()) {
+                continue;
+            } else {
+                return Err(ConfigFileError::UnknownKey(key.clone()));
+            };
+
+            let on_env = arg.get_env().is_some_and(|env| std::env::var_os(env).is_some());
+            if on_env || is_on_cli(arg, &args) {
+                continue;
+            }
+            target.extend(render(arg, key, value)?);
+        }
+
+        let mut args = args.into_iter();
+        let mut out = Vec::new();
+        out.extend(args.next());
+        out.extend(root_args);
+        match subcommand {
+            Some((index, _)) => {
+                out.extend(args.by_ref().take(index));
+                out.extend(sub_args);
+                out.extend(args);
+            }
+            None => out.extend(args),
+        }
+        Ok(out)
+    }
+}
+
+/// Loads the config file referenced by the command line arguments, if any, and inserts its values
+/// into the arguments.
+pub fn args_with_config(
+    cmd: &Command,
+    args: Vec<OsString>,
+) -> Result<Vec<OsString>, ConfigFileError> {
+    match ConfigFile::path_from_args(&args)? {
+        Some(path) => ConfigFile::load(path)?.apply(cmd, args),
+        None => Ok(args),
+    }
+}
+
+/// Flattens nested TOML tables into `(key, value)` entries, joining table paths with `.`.
+fn flatten(prefix: Option<&str>, table: Table, entries: &mut Vec<(String, Value)>) {
+    for (key, value) in table {
+        let key = prefix.map_or_else(|| key.clone(), |prefix| format!("{prefix}.{key}"));
+        match value {
+            Value::Table(table) => flatten(Some(&key), table, entries),
+            value => entries.push((key, value)),
+        }
+    }
+}
+
+/// Finds the argument of the command whose long name or alias is `name`.
+fn find_arg<'a>(cmd: &'a Command, name: &str) -> Option<&'a Arg> {
+    cmd.get_arguments().find(|arg| {
+        arg.get_long() == Some(name) ||
+            arg.get_all_aliases().is_some_and(|aliases| aliases.contains(&name))
+    })
+}
+
+/// Finds the subcommand in the command line arguments, returning its index in the arguments and
+/// its definition.
+fn find_subcommand<'a>(cmd: &'a Command, args: &[OsString]) -> Option<(usize, &'a Command)> {
+    let mut args = args.iter().map(|a| a.to_string_lossy()).enumerate().skip(1);
+    while let Some((index, arg)) = args.next() {
+        if arg == "--" {
+            return None;
+        }
+        if let Some(long) = arg.strip_prefix("--") {
+            if !long.contains('=') && find_arg(cmd, long).is_some_and(takes_values) {
+                args.next();
+            }
+            continue;
+        }
+        if let Some(short) = arg.strip_prefix('-') {
+            // A short flag only consumes the next argument if its value is not attached.
+            let mut chars = short.chars();
+            let (short, attached) = (chars.next(), chars.next().is_some());
+            let arg = cmd.get_arguments().find(|a| short.is_some() && a.get_short() == short);
+            if !attached && arg.is_some_and(takes_values) {
+                args.next();
+            }
+            continue;
+        }
+        if let Some(sub) = cmd.find_subcommand(OsStr::new(arg.as_ref())) {
+            return Some((index, sub));
+        }
+    }
+    None
+}
+
+/// Returns `true` if the argument takes values.
+fn takes_values(arg: &Arg) -> bool {
+    arg.get_action().takes_values()
+}
+
+/// Returns `true` if the argument is passed on the command line.
+fn is_on_cli(arg: &Arg, args: &[OsString]) -> bool {
+    let longs = arg.get_long().into_iter().chain(arg.get_all_aliases().unwrap_or_default());
+    let flags = longs.map(|long| format!("--{long}")).collect::<Vec<_>>();
+    let short = arg.get_short().map(|short| format!("-{short}"));
+
+    args.iter().skip(1).map(|a| a.to_string_lossy()).any(|a| {
+        flags.iter().any(|flag| a == *flag || a.starts_with(&format!("{flag}="))) ||
+            short.as_ref().is_some_and(|short| !a.starts_with("--") && a.starts_with(short))
+    })
+}
+
+/// Renders a config value as command line arguments for the given flag.
+fn render(arg: &Arg, key: &str, value: &Value) -> Result<Vec<OsString>, ConfigFileError> {
+    let flag = format!("--{}", arg.get_long().unwrap_or(key));
+
+    if !takes_values(arg) {
+        return match value {
+            Value::Boolean(true) => Ok(vec![flag.into()]),
+            Value::Boolean(false) => Ok(vec![]),
+            _ => Err(ConfigFileError::InvalidValue(key.to_string(), "expected a boolean")),
+        };
+    }
+
+    let values = match value {
+        Value::Array(values) => values.iter().collect::<Vec<_>>(),
+        value => vec![value],
+    };
+    values
+        .into_iter()
+        .map(|value| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Integer(i) => i.to_string(),
+                Value::Float(f) => f.to_string(),
+                Value::Boolean(b) => b.to_string(),
+                _ => {
+                    return Err(ConfigFileError::InvalidValue(
+                        key.to_string(),
+                        "expected a string, number or boolean",
+                    ));
+                }
+            };
+            Ok(format!("{flag}={value}").into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::CommandFactory;
+
+    fn config(contents: &str) -> ConfigFile {
+        ConfigFile::from_table(PathBuf::from("kona.toml"), contents.parse().unwrap())
+    }
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_flatten_nested_tables() {
+        let config = config("chain = 10\n[p2p.listen]\ntcp = 9223\n");
+        assert_eq!(
+            config.entries,
+            vec![
+                (String::from("chain"), Value::Integer(10)),
+                (String::from("p2p.listen.tcp"), Value::Integer(9223)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_inserts_root_and_subcommand_args() {
+        let config = config(
+            r#"
+            chain = 8453
+            l1-eth-rpc = "http://localhost:8545"
+            p2p.no-discovery = true
+            p2p.scoring = "off"
+            "#,
+        );
+        let out = config.apply(&Cli::command(), args(&["kona-node", "node"])).unwrap();
+        assert_eq!(
+            out,
+            args(&[
+                "kona-node",
+                "--chain=8453",
+                "node",
+                "--l1-eth-rpc=http://localhost:8545",
+                "--p2p.no-discovery",
+                "--p2p.scoring=off",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_apply_cli_takes_precedence() {
+        let config = config("chain = 8453\nl1-eth-rpc = \"http://localhost:8545\"\n");
+        let out = config
+            .apply(
+                &Cli::command(),
+                args(&["kona-node", "-c", "10", "node", "--l1-eth-rpc", "http://l1:8545"]),
+            )
+            .unwrap();
+        assert_eq!(out, args(&["kona-node", "-c", "10", "node", "--l1-eth-rpc", "http://l1:8545"]));
+    }
+
+    #[test]
+    fn test_apply_ignores_other_subcommand_keys() {
+        let config = config("l1-eth-rpc = \"http://localhost:8545\"\n");
+        let out = config.apply(&Cli::command(), args(&["kona-node", "registry"])).unwrap();
+        assert_eq!(out, args(&["kona-node", "registry"]));
+    }
+
+    #[test]
+    fn test_apply_unknown_key() {
+        let config = config("not-a-flag = 1\n");
+        let err = config.apply(&Cli::command(), args(&["kona-node", "node"])).unwrap_err();
+        assert!(matches!(err, ConfigFileError::UnknownKey(key) if key == "not-a-flag"));
+    }
+
+    #[test]
+    fn test_apply_invalid_flag_value() {
+        let config = config("p2p.no-discovery = \"yes\"\n");
+        let err = config.apply(&Cli::command(), args(&["kona-node", "node"])).unwrap_err();
+        assert!(matches!(err, ConfigFileError::InvalidValue(..)));
+    }
+
+    #[test]
+    fn test_path_from_args() {
+        let path = ConfigFile::path_from_args(&args(&["kona-node", "--config", "a.toml", "node"]));
+        assert_eq!(path.unwrap(), Some(PathBuf::from("a.toml")));
+
+        let path = ConfigFile::path_from_args(&args(&["kona-node", "node", "--config=b.toml"]));
+        assert_eq!(path.unwrap(), Some(PathBuf::from("b.toml")));
+
+        let path = ConfigFile::path_from_args(&args(&["kona-node", "--config"]));
+        assert!(matches!(path, Err(ConfigFileError::MissingPath)));
+    }
+}
@@ -0,0 +1,215 @@
+//! Derived Attributes Archive CLI Flags
+
+use clap::Parser;
+use kona_node_service::{ArchiveConfig, ArchiveTarget, S3Target};
+use std::path::PathBuf;
+use url::Url;
+
+/// Derived Attributes Archive CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveArgs {
+    /// Directory to store every derived payload attributes in, as JSON files. Providing this
+    /// value, or `archive.s3.url`, enables the archive.
+    #[arg(long = "archive.dir", env = "KONA_NODE_ARCHIVE_DIR", conflicts_with = "s3_url")]
+    pub dir: Option<PathBuf>,
+
+    /// URL of the S3-compatible bucket to upload every derived payload attributes to, in path
+    /// style, including the optional prefix of the objects, e.g.
+    /// `https://s3.us-east-1.amazonaws.com/my-bucket/kona/`.
+    #[arg(
+        long = "archive.s3.url",
+        env = "KONA_NODE_ARCHIVE_S3_URL",
+        requires_all = ["s3_access_key_id", "s3_secret_access_key"]
+    )]
+    pub s3_url: Option<Url>,
+
+    /// The region of the S3-compatible bucket.
+    #[arg(
+        long = "archive.s3.region",
+        default_value = "us-east-1",
+        env = "KONA_NODE_ARCHIVE_S3_REGION"
+    )]
+    pub s3_region: String,
+
+    /// The access key ID the uploads to the S3-compatible bucket are signed with.
+    #[arg(
+        long = "archive.s3.access-key-id",
+        env = "KONA_NODE_ARCHIVE_S3_ACCESS_KEY_ID",
+        requires = "s3_url"
+    )]
+    pub s3_access_key_id: Option<String>,
+
+    /// The secret access key the uploads to the S3-compatible bucket are signed with.
+    #[arg(
+        long = "archive.s3.secret-access-key",
+        env = "KONA_NODE_ARCHIVE_S3_SECRET_ACCESS_KEY",
+        requires = "s3_url",
+        hide_env_values = true
+    )]
+    pub s3_secret_access_key: Option<String>,
+
+    /// Read the payload attributes from the archive instead of deriving them from L1, and stop
+    /// archiving the attributes. Used to replay a chain without reading its batches from L1.
+    #[arg(long = "archive.replay", default_value = "false", env = "KONA_NODE_ARCHIVE_REPLAY")]
+    pub replay: bool,
+}
+
+impl Default for ArchiveArgs {
+    fn default() -> Self {
+        // Construct default values using the clap parser.
+        // This works since none of the cli flags are required.
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl ArchiveArgs {
+    /// Creates an [`ArchiveConfig`] from the [`ArchiveArgs`].
+    ///
+    /// Returns [`None`] if no archive target is configured, or if the archive is replayed.
+    pub fn config(&self) -> anyhow::Result<Option<ArchiveConfig>> {
+        if self.replay {
+            return Ok(None);
+        }
+        Ok(self.target()?.map(|target| ArchiveConfig { target }))
+    }
+
+    /// Returns the [`ArchiveTarget`] the payload attributes are read from, if the archive is
+    /// replayed.
+    pub fn source(&self) -> anyhow::Result<Option<ArchiveTarget>> {
+        let target = self.target()?;
+        if self.replay && target.is_none() {
+            anyhow::bail!("`--archive.replay` requires `--archive.dir` or `--archive.s3.url`");
+        }
+        Ok(target.filter(|_| self.replay))
+    }
+
+    /// Returns the configured [`ArchiveTarget`], if any.
+    fn target(&self) -> anyhow::Result<Option<ArchiveTarget>> {
+        let target = match (&self.dir, &self.s3_url) {
+            (Some(dir), None) => ArchiveTarget::Directory(dir.clone()),
+            (None, Some(url)) => {
+                let (Some(access_key_id), Some(secret_access_key)) =
+                    (&self.s3_access_key_id, &self.s3_secret_access_key)
+                else {
+                    anyhow::bail!(
+                        "`--archive.s3.url` requires `--archive.s3.access-key-id` and \
+                         `--archive.s3.secret-access-key`"
+                    )
+                };
+                // Object keys are joined to the URL, which must then end with a slash to keep its
+                // last segment.
+                let mut url = url.clone();
+                if !url.path().ends_with('/') {
+                    url.set_path(&format!("{}/", url.path()));
+                }
+                ArchiveTarget::S3(S3Target {
+                    url,
+                    region: self.s3_region.clone(),
+                    access_key_id: access_key_id.clone(),
+                    secret_access_key: secret_access_key.clone(),
+                })
+            }
+            (None, None) => return Ok(None),
+            (Some(_), Some(_)) => {
+                anyhow::bail!("`--archive.dir` and `--archive.s3.url` are mutually exclusive")
+            }
+        };
+
+        Ok(Some(target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the archive args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Archive CLI Flags
+        #[clap(flatten)]
+        pub archive: ArchiveArgs,
+    }
+
+    #[test]
+    fn test_archive_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.archive.config().unwrap().is_none());
+        assert!(args.archive.source().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_directory_archive() {
+        let args = MockCommand::parse_from(["test", "--archive.dir", "/var/lib/kona/archive"]);
+        assert_eq!(
+            args.archive.config().unwrap(),
+            Some(ArchiveConfig {
+                target: ArchiveTarget::Directory(PathBuf::from("/var/lib/kona/archive")),
+            })
+        );
+    }
+
+    #[test]
+    fn test_replay_archive() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--archive.dir",
+            "/var/lib/kona/archive",
+            "--archive.replay",
+        ]);
+        assert!(args.archive.config().unwrap().is_none());
+        assert_eq!(
+            args.archive.source().unwrap(),
+            Some(ArchiveTarget::Directory(PathBuf::from("/var/lib/kona/archive")))
+        );
+
+        let args = MockCommand::parse_from(["test", "--archive.replay"]);
+        assert!(args.archive.source().is_err());
+    }
+
+    #[test]
+    fn test_s3_archive() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--archive.s3.url",
+            "https://s3.eu-west-1.amazonaws.com/bucket/kona",
+            "--archive.s3.region",
+            "eu-west-1",
+            "--archive.s3.access-key-id",
+            "AKID",
+            "--archive.s3.secret-access-key",
+            "secret",
+        ]);
+        assert_eq!(
+            args.archive.config().unwrap(),
+            Some(ArchiveConfig {
+                target: ArchiveTarget::S3(S3Target {
+                    url: "https://s3.eu-west-1.amazonaws.com/bucket/kona/".parse().unwrap(),
+                    region: "eu-west-1".to_string(),
+                    access_key_id: "AKID".to_string(),
+                    secret_access_key: "secret".to_string(),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn test_archive_invalid_args() {
+        let result = MockCommand::try_parse_from([
+            "test",
+            "--archive.dir",
+            "archive",
+            "--archive.s3.url",
+            "https://s3.amazonaws.com/bucket/",
+        ]);
+        assert!(result.is_err());
+
+        let result = MockCommand::try_parse_from([
+            "test",
+            "--archive.s3.url",
+            "https://s3.amazonaws.com/b/",
+        ]);
+        assert!(result.is_err());
+    }
+}
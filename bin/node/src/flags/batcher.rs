@@ -0,0 +1,191 @@
+//! Batcher CLI Flags
+//!
+//! These are based on the flags of the [`op-batcher`][op-batcher] CLI.
+//!
+//! [op-batcher]: https://github.com/ethereum-optimism/optimism/blob/develop/op-batcher/flags/flags.go
+
+use crate::flags::parse_secs;
+use alloy_primitives::B256;
+use alloy_signer_local::PrivateKeySigner;
+use clap::Parser;
+use kona_node_service::{BatcherConfig, DataAvailabilityType};
+use std::time::Duration;
+
+/// Batcher CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq)]
+pub struct BatcherArgs {
+    /// The private key used to sign batch transactions on L1. Providing this value enables the
+    /// batcher.
+    #[arg(long = "batcher.private-key", env = "KONA_NODE_BATCHER_PRIVATE_KEY")]
+    pub private_key: Option<B256>,
+
+    /// The data availability type used to submit batches: `calldata`, `blobs` or `auto`.
+    #[arg(
+        long = "batcher.data-availability-type",
+        default_value = "calldata",
+        env = "KONA_NODE_BATCHER_DATA_AVAILABILITY_TYPE"
+    )]
+    pub data_availability_type: DataAvailabilityType,
+
+    /// The maximum number of L1 blocks a channel may stay open for. `0` disables the limit.
+    #[arg(
+        long = "batcher.max-channel-duration",
+        default_value = "10",
+        env = "KONA_NODE_BATCHER_MAX_CHANNEL_DURATION"
+    )]
+    pub max_channel_duration: u64,
+
+    /// The target number of frames per channel. With blobs, this is also the number of blobs per
+    /// transaction.
+    #[arg(
+        long = "batcher.target-num-frames",
+        default_value = "1",
+        env = "KONA_NODE_BATCHER_TARGET_NUM_FRAMES"
+    )]
+    pub target_num_frames: usize,
+
+    /// The approximate compression ratio of the channel data.
+    #[arg(
+        long = "batcher.approx-compr-ratio",
+        default_value = "0.6",
+        env = "KONA_NODE_BATCHER_APPROX_COMPR_RATIO"
+    )]
+    pub approx_compr_ratio: f64,
+
+    /// The interval at which the batcher checks for new L2 blocks, in seconds.
+    #[arg(
+        long = "batcher.poll-interval",
+        default_value = "6",
+        env = "KONA_NODE_BATCHER_POLL_INTERVAL",
+        value_parser = parse_secs
+    )]
+    pub poll_interval: Duration,
+
+    /// The time to wait for a batch transaction to be included before resubmitting it with
+    /// higher fees, in seconds.
+    #[arg(
+        long = "batcher.resubmission-timeout",
+        default_value = "48",
+        env = "KONA_NODE_BATCHER_RESUBMISSION_TIMEOUT",
+        value_parser = parse_secs
+    )]
+    pub resubmission_timeout: Duration,
+}
+
+impl Default for BatcherArgs {
+    fn default() -> Self {
+        // Construct default values using the clap parser.
+        // This works since none of the cli flags are required.
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl BatcherArgs {
+    /// Creates a [`BatcherConfig`] from the [`BatcherArgs`].
+    ///
+    /// Returns [`None`] if no batcher private key is configured.
+    pub fn config(&self) -> anyhow::Result<Option<BatcherConfig>> {
+        let Some(key) = self.private_key else {
+            return Ok(None);
+        };
+        let signer = PrivateKeySigner::from_bytes(&key)
+            .map_err(|e| anyhow::anyhow!("Invalid batcher private key: {e}"))?;
+
+        if !(self.approx_compr_ratio > 0.0 && self.approx_compr_ratio <= 1.0) {
+            anyhow::bail!("`--batcher.approx-compr-ratio` must be in (0, 1]");
+        }
+        if self.target_num_frames == 0 {
+            anyhow::bail!("`--batcher.target-num-frames` must be at least 1");
+        }
+
+        Ok(Some(BatcherConfig {
+            signer,
+            data_availability: self.data_availability_type,
+            max_channel_duration: self.max_channel_duration,
+            target_num_frames: self.target_num_frames,
+            approx_compression_ratio: self.approx_compr_ratio,
+            poll_interval: self.poll_interval,
+            resubmission_timeout: self.resubmission_timeout,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    /// A mock command that uses the batcher args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Batcher CLI Flags
+        #[clap(flatten)]
+        pub batcher: BatcherArgs,
+    }
+
+    #[test]
+    fn test_batcher_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.batcher.config().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_batcher_defaults() {
+        let args = MockCommand::parse_from(["test", "--batcher.private-key", KEY]);
+        let config = args.batcher.config().unwrap().unwrap();
+        assert_eq!(config.signer.address(), address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+        assert_eq!(config.data_availability, DataAvailabilityType::Calldata);
+        assert_eq!(config.max_channel_duration, 10);
+        assert_eq!(config.target_num_frames, 1);
+        assert_eq!(config.approx_compression_ratio, 0.6);
+        assert_eq!(config.poll_interval, Duration::from_secs(6));
+        assert_eq!(config.resubmission_timeout, Duration::from_secs(48));
+    }
+
+    #[test]
+    fn test_batcher_flags() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--batcher.private-key",
+            KEY,
+            "--batcher.data-availability-type",
+            "blobs",
+            "--batcher.max-channel-duration",
+            "0",
+            "--batcher.target-num-frames",
+            "6",
+            "--batcher.approx-compr-ratio",
+            "0.4",
+            "--batcher.poll-interval",
+            "2",
+            "--batcher.resubmission-timeout",
+            "24",
+        ]);
+        let config = args.batcher.config().unwrap().unwrap();
+        assert_eq!(config.data_availability, DataAvailabilityType::Blobs);
+        assert_eq!(config.max_channel_duration, 0);
+        assert_eq!(config.target_num_frames, 6);
+        assert_eq!(config.approx_compression_ratio, 0.4);
+        assert_eq!(config.poll_interval, Duration::from_secs(2));
+        assert_eq!(config.resubmission_timeout, Duration::from_secs(24));
+    }
+
+    #[test]
+    fn test_batcher_invalid_flags() {
+        let result =
+            MockCommand::try_parse_from(["test", "--batcher.data-availability-type", "ipfs"]);
+        assert!(result.is_err());
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--batcher.private-key",
+            KEY,
+            "--batcher.approx-compr-ratio",
+            "1.5",
+        ]);
+        assert!(args.batcher.config().is_err());
+    }
+}
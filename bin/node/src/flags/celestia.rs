@@ -0,0 +1,96 @@
+//! Celestia CLI Flags
+
+use alloy_primitives::FixedBytes;
+use clap::Parser;
+use kona_providers_alloy::{CelestiaConfig, NAMESPACE_SIZE};
+use url::Url;
+
+/// Celestia CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct CelestiaArgs {
+    /// URL of the RPC of the Celestia light node the blobs of the chain are fetched from.
+    /// Providing this value enables the derivation of chains posting their data to Celestia.
+    #[arg(long = "celestia.rpc", env = "KONA_NODE_CELESTIA_RPC", requires = "namespace")]
+    pub rpc: Option<Url>,
+
+    /// Authentication token of the Celestia light node RPC.
+    #[arg(long = "celestia.auth-token", env = "KONA_NODE_CELESTIA_AUTH_TOKEN")]
+    pub auth_token: Option<String>,
+
+    /// Hex-encoded 29 byte namespace the batcher posts the blobs of the chain to.
+    #[arg(long = "celestia.namespace", env = "KONA_NODE_CELESTIA_NAMESPACE")]
+    pub namespace: Option<FixedBytes<NAMESPACE_SIZE>>,
+}
+
+impl Default for CelestiaArgs {
+    fn default() -> Self {
+        // Construct default values using the clap parser.
+        // This works since none of the cli flags are required.
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl CelestiaArgs {
+    /// Creates a [`CelestiaConfig`] from the [`CelestiaArgs`].
+    ///
+    /// Returns [`None`] if no Celestia light node is configured.
+    pub fn config(&self) -> anyhow::Result<Option<CelestiaConfig>> {
+        let Some(rpc) = self.rpc.clone() else { return Ok(None) };
+        let Some(namespace) = self.namespace else {
+            anyhow::bail!("`--celestia.namespace` is required with `--celestia.rpc`");
+        };
+        Ok(Some(CelestiaConfig {
+            rpc,
+            auth_token: self.auth_token.clone(),
+            namespace: namespace.0,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the celestia args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Celestia CLI Flags
+        #[clap(flatten)]
+        pub celestia: CelestiaArgs,
+    }
+
+    #[test]
+    fn test_celestia_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.celestia.config().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_celestia_config() {
+        let namespace = format!("0x{}", "00".repeat(28) + "42");
+        let args = MockCommand::parse_from([
+            "test",
+            "--celestia.rpc",
+            "http://localhost:26658",
+            "--celestia.auth-token",
+            "token",
+            "--celestia.namespace",
+            &namespace,
+        ]);
+        let config = args.celestia.config().unwrap().unwrap();
+        assert_eq!(config.rpc.as_str(), "http://localhost:26658/");
+        assert_eq!(config.auth_token.as_deref(), Some("token"));
+        assert_eq!(config.namespace[NAMESPACE_SIZE - 1], 0x42);
+    }
+
+    #[test]
+    fn test_celestia_invalid_args() {
+        // The namespace is required with the light node RPC.
+        let res = MockCommand::try_parse_from(["test", "--celestia.rpc", "http://localhost:26658"]);
+        assert!(res.is_err());
+        // The namespace must be 29 bytes long.
+        let res = MockCommand::try_parse_from(["test", "--celestia.namespace", "0x42"]);
+        assert!(res.is_err());
+    }
+}
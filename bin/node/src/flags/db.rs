@@ -0,0 +1,127 @@
+//! Database CLI Flags
+//!
+//! The node database records the safe head at each L1 block, the anchors the derivation pipeline
+//! was reset to, the unsafe payloads received from the network, and the L1 batcher transactions
+//! each derived L2 block came from.
+
+use clap::Parser;
+use kona_node_service::{NodeDb, PruningConfig};
+use std::path::PathBuf;
+
+/// The default number of L2 blocks of unsafe payloads retained in the node database.
+const DEFAULT_RETAINED_UNSAFE_PAYLOADS: u64 = 7_200;
+
+/// Database CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct DbArgs {
+    /// The directory of the node database. The node keeps no database if unset.
+    #[arg(long = "db.path", env = "KONA_NODE_DB_PATH")]
+    pub path: Option<PathBuf>,
+    /// The number of L1 blocks of safe head records retained. `0` retains all records.
+    #[arg(
+        long = "db.retain.safe-heads",
+        default_value = "0",
+        env = "KONA_NODE_DB_RETAIN_SAFE_HEADS"
+    )]
+    pub retain_safe_heads: u64,
+    /// The number of L2 blocks of derivation checkpoints retained. `0` retains all checkpoints.
+    #[arg(
+        long = "db.retain.checkpoints",
+        default_value = "0",
+        env = "KONA_NODE_DB_RETAIN_CHECKPOINTS"
+    )]
+    pub retain_checkpoints: u64,
+    /// The number of L2 blocks of unsafe payloads retained. `0` retains all payloads.
+    #[arg(
+        long = "db.retain.unsafe-payloads",
+        default_value_t = DEFAULT_RETAINED_UNSAFE_PAYLOADS,
+        env = "KONA_NODE_DB_RETAIN_UNSAFE_PAYLOADS"
+    )]
+    pub retain_unsafe_payloads: u64,
+    /// The number of L2 blocks of L1 provenance records retained. `0` retains all records.
+    #[arg(
+        long = "db.retain.l1-provenance",
+        default_value = "0",
+        env = "KONA_NODE_DB_RETAIN_L1_PROVENANCE"
+    )]
+    pub retain_l1_provenance: u64,
+}
+
+impl Default for DbArgs {
+    fn default() -> Self {
+        Self {
+            path: None,
+            retain_safe_heads: 0,
+            retain_checkpoints: 0,
+            retain_unsafe_payloads: DEFAULT_RETAINED_UNSAFE_PAYLOADS,
+            retain_l1_provenance: 0,
+        }
+    }
+}
+
+impl DbArgs {
+    /// Returns the [`PruningConfig`] of the node database.
+    pub fn pruning(&self) -> PruningConfig {
+        let horizon = |retained: u64| (retained > 0).then_some(retained);
+        PruningConfig {
+            safe_heads: horizon(self.retain_safe_heads),
+            derivation_checkpoints: horizon(self.retain_checkpoints),
+            unsafe_payloads: horizon(self.retain_unsafe_payloads),
+            l1_provenance: horizon(self.retain_l1_provenance),
+        }
+    }
+
+    /// Opens the node database, if a path is configured.
+    pub fn open(&self) -> anyhow::Result<Option<NodeDb>> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+        std::fs::create_dir_all(path)?;
+        let db = NodeDb::open(path, self.pruning()).map_err(|e| {
+            anyhow::anyhow!("Failed to open the node database at {}: {e}", path.display())
+        })?;
+        Ok(Some(db))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the database args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Database flags.
+        #[clap(flatten)]
+        pub db: DbArgs,
+    }
+
+    #[test]
+    fn test_db_args_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.db, DbArgs::default());
+        assert!(args.db.open().unwrap().is_none());
+        assert_eq!(args.db.pruning(), PruningConfig::default());
+    }
+
+    #[test]
+    fn test_db_retention() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--db.retain.safe-heads",
+            "1000",
+            "--db.retain.unsafe-payloads",
+            "0",
+        ]);
+        assert_eq!(
+            args.db.pruning(),
+            PruningConfig {
+                safe_heads: Some(1000),
+                derivation_checkpoints: None,
+                unsafe_payloads: None,
+                l1_provenance: None,
+            }
+        );
+    }
+}
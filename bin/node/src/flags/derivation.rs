@@ -0,0 +1,216 @@
+//! Derivation CLI Flags
+//!
+//! The derivation pipeline buffers the channels it assembles from L1 frames, and the batches it
+//! decodes from them. Its memory budget bounds the bytes held in these buffers.
+//!
+//! The attributes it derives are queued for the engine. Derivation pauses once the engine falls
+//! behind by the high watermark of that queue, and resumes once it catches up to the low one.
+//!
+//! The engine can halt derivation once too many derived payloads are rejected as invalid, until
+//! the halt is acknowledged with `admin_acknowledgeDerivationHalt`.
+
+use anyhow::{Result, ensure};
+use clap::Parser;
+use kona_genesis::RollupConfig;
+use kona_node_service::{DerivationHaltConfig, Watermarks};
+
+/// Derivation CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct DerivationArgs {
+    /// The maximum number of bytes of channels and batches buffered by the derivation pipeline.
+    ///
+    /// While the budget is exceeded, the pipeline stops fetching L1 data until its buffers drain.
+    /// Derivation fails if they can't drain without more L1 data. It may not be lower than the
+    /// channel bank size of the latest fork of the chain. The buffers are unbounded if unset.
+    #[arg(long = "derivation.memory-budget", env = "KONA_NODE_DERIVATION_MEMORY_BUDGET")]
+    pub memory_budget: Option<usize>,
+
+    /// The number of derived attributes queued for the engine at which derivation pauses.
+    #[arg(
+        long = "derivation.attributes-high-watermark",
+        default_value_t = Watermarks::default().high,
+        env = "KONA_NODE_DERIVATION_ATTRIBUTES_HIGH_WATERMARK"
+    )]
+    pub attributes_high_watermark: usize,
+
+    /// The number of derived attributes queued for the engine at which a paused derivation
+    /// resumes. Must be lower than the high watermark.
+    #[arg(
+        long = "derivation.attributes-low-watermark",
+        default_value_t = Watermarks::default().low,
+        env = "KONA_NODE_DERIVATION_ATTRIBUTES_LOW_WATERMARK"
+    )]
+    pub attributes_low_watermark: usize,
+
+    /// The number of consecutive derived payloads rejected as invalid by the execution layer
+    /// after which derivation halts, until the halt is acknowledged through the admin RPC.
+    /// Disabled if unset.
+    #[arg(
+        long = "derivation.halt.invalid-streak",
+        env = "KONA_NODE_DERIVATION_HALT_INVALID_STREAK"
+    )]
+    pub halt_invalid_streak: Option<u64>,
+
+    /// The number of invalid derived payloads replaced by deposits-only blocks within
+    /// `--derivation.halt.deposit-only-window` L2 blocks after which derivation halts, until the
+    /// halt is acknowledged through the admin RPC. Disabled if unset.
+    #[arg(
+        long = "derivation.halt.deposit-only-count",
+        env = "KONA_NODE_DERIVATION_HALT_DEPOSIT_ONLY_COUNT"
+    )]
+    pub halt_deposit_only_count: Option<u64>,
+
+    /// The number of L2 blocks the deposits-only replacements are counted over.
+    #[arg(
+        long = "derivation.halt.deposit-only-window",
+        default_value_t = 1800,
+        env = "KONA_NODE_DERIVATION_HALT_DEPOSIT_ONLY_WINDOW"
+    )]
+    pub halt_deposit_only_window: u64,
+}
+
+impl Default for DerivationArgs {
+    fn default() -> Self {
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl DerivationArgs {
+    /// Returns the memory budget of the derivation pipeline, if any.
+    ///
+    /// The channel bank holds up to the max channel bank size of the spec, so a lower budget could
+    /// stall derivation on valid L1 data.
+    pub fn memory_budget(&self, rollup_config: &RollupConfig) -> Result<Option<usize>> {
+        let Some(budget) = self.memory_budget else {
+            return Ok(None);
+        };
+        let min_budget = rollup_config.max_channel_bank_size(u64::MAX);
+        ensure!(
+            budget >= min_budget,
+            "The derivation memory budget ({budget} bytes) must be at least the max channel bank \
+             size of the chain ({min_budget} bytes)"
+        );
+        Ok(Some(budget))
+    }
+
+    /// Returns the [`Watermarks`] of the queue of derived attributes.
+    pub fn attributes_watermarks(&self) -> Result<Watermarks> {
+        ensure!(
+            self.attributes_low_watermark < self.attributes_high_watermark,
+            "The attributes low watermark ({}) must be lower than the high watermark ({})",
+            self.attributes_low_watermark,
+            self.attributes_high_watermark
+        );
+        Ok(Watermarks { high: self.attributes_high_watermark, low: self.attributes_low_watermark })
+    }
+
+    /// Returns the [`DerivationHaltConfig`], if any of the thresholds halting derivation is set.
+    pub fn halt_config(&self) -> Result<Option<DerivationHaltConfig>> {
+        if self.halt_invalid_streak.is_none() && self.halt_deposit_only_count.is_none() {
+            return Ok(None);
+        }
+        ensure!(
+            self.halt_invalid_streak != Some(0) && self.halt_deposit_only_count != Some(0),
+            "The thresholds halting derivation must be positive"
+        );
+        ensure!(
+            self.halt_deposit_only_window > 0,
+            "The window of deposits-only blocks halting derivation must be positive"
+        );
+        Ok(Some(DerivationHaltConfig {
+            invalid_payload_streak: self.halt_invalid_streak,
+            deposit_only_blocks: self.halt_deposit_only_count,
+            deposit_only_window: self.halt_deposit_only_window,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the derivation args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Derivation flags.
+        #[clap(flatten)]
+        pub derivation: DerivationArgs,
+    }
+
+    #[test]
+    fn test_derivation_args_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.derivation, DerivationArgs::default());
+        assert_eq!(args.derivation.memory_budget, None);
+        assert_eq!(args.derivation.attributes_watermarks().unwrap(), Watermarks::default());
+        assert_eq!(args.derivation.halt_config().unwrap(), None);
+    }
+
+    #[test]
+    fn test_derivation_memory_budget() {
+        let args = MockCommand::parse_from(["test", "--derivation.memory-budget", "268435456"]);
+        assert_eq!(args.derivation.memory_budget, Some(268_435_456));
+
+        let mut rollup_config = RollupConfig::default();
+        assert_eq!(args.derivation.memory_budget(&rollup_config).unwrap(), Some(268_435_456));
+
+        // Fjord raises the max channel bank size above the budget.
+        rollup_config.hardforks.fjord_time = Some(0);
+        assert!(args.derivation.memory_budget(&rollup_config).is_err());
+
+        let args = MockCommand::parse_from(["test", "--derivation.memory-budget", "99999999"]);
+        assert!(args.derivation.memory_budget(&RollupConfig::default()).is_err());
+
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.derivation.memory_budget(&rollup_config).unwrap(), None);
+    }
+
+    #[test]
+    fn test_derivation_attributes_watermarks() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--derivation.attributes-high-watermark",
+            "128",
+            "--derivation.attributes-low-watermark",
+            "32",
+        ]);
+        assert_eq!(
+            args.derivation.attributes_watermarks().unwrap(),
+            Watermarks { high: 128, low: 32 }
+        );
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--derivation.attributes-high-watermark",
+            "8",
+            "--derivation.attributes-low-watermark",
+            "8",
+        ]);
+        assert!(args.derivation.attributes_watermarks().is_err());
+    }
+
+    #[test]
+    fn test_derivation_halt_config() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--derivation.halt.invalid-streak",
+            "3",
+            "--derivation.halt.deposit-only-count",
+            "5",
+            "--derivation.halt.deposit-only-window",
+            "100",
+        ]);
+        assert_eq!(
+            args.derivation.halt_config().unwrap(),
+            Some(DerivationHaltConfig {
+                invalid_payload_streak: Some(3),
+                deposit_only_blocks: Some(5),
+                deposit_only_window: 100,
+            })
+        );
+
+        let args = MockCommand::parse_from(["test", "--derivation.halt.invalid-streak", "0"]);
+        assert!(args.derivation.halt_config().is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! EigenDA CLI Flags
+
+use crate::flags::parse_secs;
+use alloy_primitives::Address;
+use clap::Parser;
+use kona_providers_alloy::EigenDaConfig;
+use std::time::Duration;
+use url::Url;
+
+/// EigenDA CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct EigenDaArgs {
+    /// URL of the EigenDA proxy the blobs of the chain are fetched from. Providing this value
+    /// enables the derivation of chains posting their data to EigenDA.
+    #[arg(long = "eigenda.proxy", env = "KONA_NODE_EIGENDA_PROXY")]
+    pub proxy: Option<Url>,
+
+    /// Number of L1 blocks after the reference block of a certificate within which it must be
+    /// included. Certificates are not checked for recency if unset.
+    #[arg(long = "eigenda.recency-window", env = "KONA_NODE_EIGENDA_RECENCY_WINDOW")]
+    pub recency_window: Option<u64>,
+
+    /// Address of the EigenDA service manager. If set, the batches of the certificates are
+    /// checked to be confirmed on L1.
+    #[arg(long = "eigenda.service-manager", env = "KONA_NODE_EIGENDA_SERVICE_MANAGER")]
+    pub service_manager: Option<Address>,
+
+    /// Timeout of the requests to the EigenDA proxy, in seconds.
+    #[arg(
+        long = "eigenda.timeout",
+        default_value = "30",
+        env = "KONA_NODE_EIGENDA_TIMEOUT",
+        value_parser = parse_secs
+    )]
+    pub timeout: Duration,
+
+    /// Number of times a request to the EigenDA proxy is retried after a network or server
+    /// failure.
+    #[arg(
+        long = "eigenda.max-retries",
+        default_value = "3",
+        env = "KONA_NODE_EIGENDA_MAX_RETRIES"
+    )]
+    pub max_retries: usize,
+}
+
+impl Default for EigenDaArgs {
+    fn default() -> Self {
+        // Construct default values using the clap parser.
+        // This works since none of the cli flags are required.
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl EigenDaArgs {
+    /// Creates an [`EigenDaConfig`] from the [`EigenDaArgs`].
+    ///
+    /// Returns [`None`] if no EigenDA proxy is configured.
+    pub fn config(&self) -> Option<EigenDaConfig> {
+        let proxy = self.proxy.clone()?;
+        Some(EigenDaConfig {
+            recency_window: self.recency_window,
+            timeout: self.timeout,
+            max_retries: self.max_retries,
+            ..EigenDaConfig::new(proxy)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the eigenda args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// EigenDA CLI Flags
+        #[clap(flatten)]
+        pub eigenda: EigenDaArgs,
+    }
+
+    #[test]
+    fn test_eigenda_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.eigenda.config().is_none());
+        assert!(args.eigenda.service_manager.is_none());
+    }
+
+    #[test]
+    fn test_eigenda_config() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--eigenda.proxy",
+            "http://localhost:3100",
+            "--eigenda.recency-window",
+            "100",
+            "--eigenda.timeout",
+            "10",
+            "--eigenda.max-retries",
+            "5",
+        ]);
+        let config = args.eigenda.config().unwrap();
+        assert_eq!(config.proxy.as_str(), "http://localhost:3100/");
+        assert_eq!(config.recency_window, Some(100));
+        assert_eq!(config.timeout, Duration::from_secs(10));
+        assert_eq!(config.max_retries, 5);
+    }
+}
@@ -75,6 +75,12 @@ pub struct L1ClientArgs {
         env = "KONA_NODE_L1_SLOT_DURATION_OVERRIDE"
     )]
     pub l1_slot_duration_override: Option<u64>,
+    /// URL of the L1 execution client WebSocket RPC API.
+    ///
+    /// If set, new L1 heads are streamed from a `newHeads` subscription rather than polled from
+    /// the L1 RPC, which remains used while the subscription is down.
+    #[arg(long, visible_alias = "l1.ws-rpc", env = "KONA_NODE_L1_WS_RPC")]
+    pub l1_ws_rpc: Option<Url>,
 }
 
 impl Default for L1ClientArgs {
@@ -84,6 +90,7 @@ impl Default for L1ClientArgs {
             l1_trust_rpc: DEFAULT_L1_TRUST_RPC,
             l1_beacon: Url::parse("http://localhost:5052").unwrap(),
             l1_slot_duration_override: None,
+            l1_ws_rpc: None,
         }
     }
 }
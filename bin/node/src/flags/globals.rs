@@ -4,7 +4,8 @@ use alloy_primitives::Address;
 use clap::Parser;
 use kona_cli::{LogArgs, MetricsArgs};
 use kona_genesis::RollupConfig;
-use kona_registry::OPCHAINS;
+use kona_registry::{Registry, chain_config_by_chain_id, install_local_registry};
+use std::path::PathBuf;
 
 /// Global arguments for the CLI.
 #[derive(Parser, Default, Clone, Debug)]
@@ -23,6 +24,18 @@ pub struct GlobalArgs {
         help = "The L2 chain ID to use"
     )]
     pub l2_chain_id: alloy_chains::Chain,
+    /// Path to a local directory of chain configurations in the superchain-registry format.
+    ///
+    /// Chains in this directory supplement (and take precedence over) the chains embedded in the
+    /// registry, which allows running chains that are not yet part of a registry release.
+    #[arg(long = "registry-path", global = true, env = "KONA_NODE_REGISTRY_PATH")]
+    pub registry_path: Option<PathBuf>,
+    /// Path to a TOML configuration file setting CLI flags by their long name.
+    ///
+    /// Flags passed on the command line or set through environment variables take precedence
+    /// over values in the configuration file.
+    #[arg(long = "config", global = true, env = "KONA_NODE_CONFIG")]
+    pub config: Option<PathBuf>,
     /// Embed the override flags globally to provide override values adjacent to the configs.
     #[command(flatten)]
     pub override_args: super::OverrideArgs,
@@ -35,15 +48,37 @@ impl GlobalArgs {
     /// Applies the specified overrides to the given rollup config.
     ///
     /// Transforms the rollup config and returns the updated config with the overrides applied.
-    pub fn apply_overrides(&self, config: RollupConfig) -> RollupConfig {
-        self.override_args.apply(config)
+    /// Returns an error if the overridden hardforks are not scheduled in order.
+    pub fn apply_overrides(&self, config: RollupConfig) -> anyhow::Result<RollupConfig> {
+        self.override_args
+            .apply(config)
+            .map_err(|e| anyhow::anyhow!("Invalid hardfork overrides: {e}"))
+    }
+
+    /// Loads the local chain registry, if a registry path is configured, and installs it so that
+    /// registry lookups observe the local chains.
+    pub fn init_registry(&self) -> anyhow::Result<()> {
+        let Some(path) = &self.registry_path else {
+            return Ok(());
+        };
+
+        let registry = Registry::from_directory(path).map_err(|e| {
+            anyhow::anyhow!("Failed to load local registry from {}: {e}", path.display())
+        })?;
+        tracing::info!(
+            target: "cli",
+            path = %path.display(),
+            chains = registry.chain_list.chains.len(),
+            "Loaded local chain registry"
+        );
+        install_local_registry(registry);
+        Ok(())
     }
 
     /// Returns the signer [`Address`] from the rollup config for the given l2 chain id.
     pub fn genesis_signer(&self) -> anyhow::Result<Address> {
         let id = self.l2_chain_id;
-        OPCHAINS
-            .get(&id.id())
+        chain_config_by_chain_id(id.id())
             .ok_or(anyhow::anyhow!("No chain config found for chain ID: {id}"))?
             .roles
             .as_ref()
@@ -105,6 +140,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_registry_path() {
+        let args =
+            GlobalArgs::try_parse_from(["test", "--registry-path", "/tmp/registry"]).unwrap();
+        assert_eq!(args.registry_path, Some(PathBuf::from("/tmp/registry")));
+
+        let args = GlobalArgs::try_parse_from(["test"]).unwrap();
+        assert!(args.registry_path.is_none());
+        assert!(args.init_registry().is_ok());
+    }
+
+    #[test]
+    fn test_init_registry_missing_directory() {
+        let args =
+            GlobalArgs::try_parse_from(["test", "--registry-path", "/does/not/exist"]).unwrap();
+        assert!(args.init_registry().is_err());
+    }
+
     #[test]
     fn test_l2_chain_id_default() {
         // Test that the default value is chain ID 10 (Optimism)
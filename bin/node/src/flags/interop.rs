@@ -0,0 +1,99 @@
+//! Interop CLI Flags
+//!
+//! Once Interop activates, the chain may exchange messages with the other chains of its
+//! dependency set. The dependency set is loaded from a file if given, and otherwise from the
+//! registry for chains scheduling Interop.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use kona_genesis::RollupConfig;
+use kona_interop::DependencySet;
+use kona_registry::op_chains;
+use std::path::PathBuf;
+
+/// Interop CLI Flags
+#[derive(Parser, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InteropArgs {
+    /// Path to the JSON file of the interop dependency set of the chain, in the format of the
+    /// op-supervisor.
+    ///
+    /// If unset, the dependency set is loaded from the registry for chains scheduling Interop.
+    #[arg(long = "interop.dependency-set", env = "KONA_NODE_INTEROP_DEPENDENCY_SET")]
+    pub dependency_set: Option<PathBuf>,
+}
+
+impl InteropArgs {
+    /// Returns the [`DependencySet`] of the chain, if it has one.
+    ///
+    /// The dependency set is only loaded from the registry if the rollup config schedules
+    /// Interop. It is validated against the rollup config when the node starts.
+    pub fn dependency_set(&self, rollup_config: &RollupConfig) -> Result<Option<DependencySet>> {
+        match &self.dependency_set {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path).with_context(|| {
+                    format!("Failed to read the dependency set from {}", path.display())
+                })?;
+                let dependency_set = serde_json::from_str(&contents).with_context(|| {
+                    format!("Failed to parse the dependency set from {}", path.display())
+                })?;
+                Ok(Some(dependency_set))
+            }
+            None if rollup_config.hardforks.interop_time.is_some() => {
+                let dependency_set = DependencySet::from_registry(
+                    rollup_config.l2_chain_id.id(),
+                    op_chains().into_values(),
+                )
+                .context("Failed to load the dependency set from the registry")?;
+                Ok(Some(dependency_set))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_genesis::HardForkConfig;
+    use std::io::Write;
+
+    /// A mock command that uses the interop args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Interop flags.
+        #[clap(flatten)]
+        pub interop: InteropArgs,
+    }
+
+    #[test]
+    fn test_interop_args_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.interop, InteropArgs::default());
+        assert_eq!(args.interop.dependency_set(&RollupConfig::default()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_interop_dependency_set_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, r#"{{"dependencies":{{"10":{{}},"8453":{{}}}}}}"#).unwrap();
+
+        let path = file.path().to_str().unwrap();
+        let args = MockCommand::parse_from(["test", "--interop.dependency-set", path]);
+        let rollup_config = RollupConfig {
+            l2_chain_id: 10.into(),
+            hardforks: HardForkConfig { interop_time: Some(1_750_000_000), ..Default::default() },
+            ..Default::default()
+        };
+        let set = args.interop.dependency_set(&rollup_config).unwrap().unwrap();
+        assert!(set.contains(8453));
+        set.validate(&rollup_config).unwrap();
+    }
+
+    #[test]
+    fn test_interop_dependency_set_missing_file() {
+        let args =
+            MockCommand::parse_from(["test", "--interop.dependency-set", "/nonexistent.json"]);
+        assert!(args.interop.dependency_set(&RollupConfig::default()).is_err());
+    }
+}
@@ -3,7 +3,44 @@
 //! Specifies the available flags for prometheus metric configuration inside CLI
 
 use crate::metrics::VersionInfo;
+use clap::Parser;
 use kona_cli::MetricsArgs;
+use kona_node_service::ChannelAlarms;
+use std::time::Duration;
+
+/// Thresholds past which the node's internal channels log warnings.
+#[derive(Parser, Debug, Clone, PartialEq, Eq)]
+pub struct ChannelAlarmArgs {
+    /// The number of queued messages past which an internal channel, or the engine task queue,
+    /// logs a warning.
+    #[arg(
+        long = "metrics.channels.max-depth",
+        default_value_t = ChannelAlarms::default().max_depth,
+        env = "KONA_NODE_METRICS_CHANNELS_MAX_DEPTH"
+    )]
+    pub max_depth: usize,
+
+    /// The age in seconds of the oldest queued message past which an internal channel logs a
+    /// warning.
+    #[arg(
+        long = "metrics.channels.max-age",
+        default_value_t = ChannelAlarms::default().max_age.as_secs(),
+        env = "KONA_NODE_METRICS_CHANNELS_MAX_AGE"
+    )]
+    pub max_age: u64,
+}
+
+impl Default for ChannelAlarmArgs {
+    fn default() -> Self {
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl From<&ChannelAlarmArgs> for ChannelAlarms {
+    fn from(args: &ChannelAlarmArgs) -> Self {
+        Self { max_depth: args.max_depth, max_age: Duration::from_secs(args.max_age) }
+    }
+}
 
 /// Initializes metrics for a Kona application, including Prometheus and node-specific metrics.
 /// Initialize the tracing stack and Prometheus metrics recorder.
@@ -26,7 +63,6 @@ pub fn init_unified_metrics(args: &MetricsArgs) -> anyhow::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use clap::Parser;
     use std::net::IpAddr;
 
     /// A mock command that uses the MetricsArgs.
@@ -59,4 +95,22 @@ mod tests {
         let args = MockCommand::parse_from(["test", "--metrics.port", "1234"]);
         assert_eq!(args.metrics.port, 1234);
     }
+
+    #[test]
+    fn test_channel_alarm_args() {
+        let args = ChannelAlarmArgs::default();
+        assert_eq!(ChannelAlarms::from(&args), ChannelAlarms::default());
+
+        let args = ChannelAlarmArgs::parse_from([
+            "test",
+            "--metrics.channels.max-depth",
+            "64",
+            "--metrics.channels.max-age",
+            "4",
+        ]);
+        assert_eq!(
+            ChannelAlarms::from(&args),
+            ChannelAlarms { max_depth: 64, max_age: Duration::from_secs(4) }
+        );
+    }
 }
@@ -1,5 +1,7 @@
 //! CLI Flags
 
+use std::{num::ParseIntError, time::Duration};
+
 mod globals;
 pub use globals::GlobalArgs;
 
@@ -13,16 +15,61 @@ mod overrides;
 pub use overrides::OverrideArgs;
 
 mod metrics;
-pub use metrics::init_unified_metrics;
+pub use metrics::{ChannelAlarmArgs, init_unified_metrics};
 
 mod sequencer;
 pub use sequencer::SequencerArgs;
 
+mod proposer;
+pub use proposer::ProposerArgs;
+
+mod batcher;
+pub use batcher::BatcherArgs;
+
+mod snapshot;
+pub use snapshot::SnapshotArgs;
+
+mod pruning_hint;
+pub use pruning_hint::PruningHintArgs;
+
+mod archive;
+pub use archive::ArchiveArgs;
+
 mod signer;
-pub use signer::{SignerArgs, SignerArgsParseError};
+pub use signer::{RemoteSignerType, SignerArgs, SignerArgsParseError};
 
 mod engine;
 pub use engine::{
     BuilderClientArgs, FlashblocksFlags, FlashblocksWebsocketFlags, L1ClientArgs, L2ClientArgs,
     RollupBoostFlags,
 };
+
+mod shadow_fork;
+pub use shadow_fork::ShadowForkArgs;
+
+mod sync;
+pub use sync::{SyncArgs, SyncMode};
+
+mod db;
+pub use db::DbArgs;
+
+mod derivation;
+pub use derivation::DerivationArgs;
+
+mod interop;
+pub use interop::InteropArgs;
+
+#[cfg(feature = "celestia")]
+mod celestia;
+#[cfg(feature = "celestia")]
+pub use celestia::CelestiaArgs;
+
+#[cfg(feature = "eigenda")]
+mod eigenda;
+#[cfg(feature = "eigenda")]
+pub use eigenda::EigenDaArgs;
+
+/// Parses a duration in seconds.
+pub(crate) fn parse_secs(arg: &str) -> Result<Duration, ParseIntError> {
+    Ok(Duration::from_secs(arg.parse()?))
+}
@@ -1,41 +1,45 @@
 //! Flags that allow overriding derived values.
 
 use clap::Parser;
-use kona_genesis::RollupConfig;
+use kona_genesis::{HardForkOrderError, RollupConfig};
 
 /// Override Flags.
 #[derive(Parser, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct OverrideArgs {
     /// Manually specify the timestamp for the Canyon fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_CANYON")]
+    #[arg(long, visible_alias = "override.canyon", env = "KONA_NODE_OVERRIDE_CANYON")]
     pub canyon_override: Option<u64>,
     /// Manually specify the timestamp for the Delta fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_DELTA")]
+    #[arg(long, visible_alias = "override.delta", env = "KONA_NODE_OVERRIDE_DELTA")]
     pub delta_override: Option<u64>,
     /// Manually specify the timestamp for the Ecotone fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_ECOTONE")]
+    #[arg(long, visible_alias = "override.ecotone", env = "KONA_NODE_OVERRIDE_ECOTONE")]
     pub ecotone_override: Option<u64>,
     /// Manually specify the timestamp for the Fjord fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_FJORD")]
+    #[arg(long, visible_alias = "override.fjord", env = "KONA_NODE_OVERRIDE_FJORD")]
     pub fjord_override: Option<u64>,
     /// Manually specify the timestamp for the Granite fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_GRANITE")]
+    #[arg(long, visible_alias = "override.granite", env = "KONA_NODE_OVERRIDE_GRANITE")]
     pub granite_override: Option<u64>,
     /// Manually specify the timestamp for the Holocene fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_HOLOCENE")]
+    #[arg(long, visible_alias = "override.holocene", env = "KONA_NODE_OVERRIDE_HOLOCENE")]
     pub holocene_override: Option<u64>,
     /// Manually specify the timestamp for the Isthmus fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_ISTHMUS")]
+    #[arg(long, visible_alias = "override.isthmus", env = "KONA_NODE_OVERRIDE_ISTHMUS")]
     pub isthmus_override: Option<u64>,
     /// Manually specify the timestamp for the Jovian fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_JOVIAN")]
+    #[arg(long, visible_alias = "override.jovian", env = "KONA_NODE_OVERRIDE_JOVIAN")]
     pub jovian_override: Option<u64>,
     /// Manually specify the timestamp for the pectra blob schedule, overriding the bundled
     /// setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_PECTRA_BLOB_SCHEDULE")]
+    #[arg(
+        long,
+        visible_alias = "override.pectrablobschedule",
+        env = "KONA_NODE_OVERRIDE_PECTRA_BLOB_SCHEDULE"
+    )]
     pub pectra_blob_schedule_override: Option<u64>,
     /// Manually specify the timestamp for the Interop fork, overriding the bundled setting.
-    #[arg(long, env = "KONA_NODE_OVERRIDE_INTEROP")]
+    #[arg(long, visible_alias = "override.interop", env = "KONA_NODE_OVERRIDE_INTEROP")]
     pub interop_override: Option<u64>,
 }
 
@@ -49,7 +53,9 @@ impl Default for OverrideArgs {
 
 impl OverrideArgs {
     /// Applies the override args to the given rollup config.
-    pub fn apply(&self, config: RollupConfig) -> RollupConfig {
+    ///
+    /// Returns an error if the hardforks of the resulting config are not scheduled in order.
+    pub fn apply(&self, config: RollupConfig) -> Result<RollupConfig, HardForkOrderError> {
         let hardforks = kona_genesis::HardForkConfig {
             regolith_time: config.hardforks.regolith_time,
             canyon_time: self.canyon_override.map(Some).unwrap_or(config.hardforks.canyon_time),
@@ -69,7 +75,8 @@ impl OverrideArgs {
             jovian_time: self.jovian_override.map(Some).unwrap_or(config.hardforks.jovian_time),
             interop_time: self.interop_override.map(Some).unwrap_or(config.hardforks.interop_time),
         };
-        RollupConfig { hardforks, ..config }
+        hardforks.check_activation_order()?;
+        Ok(RollupConfig { hardforks, ..config })
     }
 }
 
@@ -111,12 +118,18 @@ mod tests {
             "--interop-override",
             "1750000000",
         ]);
-        let config = RollupConfig::default();
-        let updated_config = args.override_flags.apply(config);
+        let config = RollupConfig {
+            hardforks: kona_genesis::HardForkConfig {
+                regolith_time: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let updated_config = args.override_flags.apply(config).unwrap();
         assert_eq!(
             updated_config.hardforks,
             kona_genesis::HardForkConfig {
-                regolith_time: Default::default(),
+                regolith_time: Some(0),
                 canyon_time: Some(1699981200),
                 delta_time: Some(1703203200),
                 ecotone_time: Some(1708534800),
@@ -140,10 +153,30 @@ mod tests {
             .clone();
         let init_forks = config.hardforks;
         let args = MockCommand::parse_from(["test"]);
-        let updated_config = args.override_flags.apply(config);
+        let updated_config = args.override_flags.apply(config).unwrap();
         assert_eq!(updated_config.hardforks, init_forks);
     }
 
+    #[test]
+    fn test_override_alias() {
+        let args = MockCommand::parse_from(["test", "--override.isthmus", "1740000000"]);
+        assert_eq!(args.override_flags.isthmus_override, Some(1740000000));
+    }
+
+    #[test]
+    fn test_apply_out_of_order_overrides() {
+        // Use OP Mainnet rollup config.
+        let config = kona_registry::ROLLUP_CONFIGS
+            .get(&10)
+            .expect("No config found for chain ID 10")
+            .clone();
+        let args = MockCommand::parse_from(["test", "--override.holocene", "1"]);
+        assert!(matches!(
+            args.override_flags.apply(config),
+            Err(HardForkOrderError::OutOfOrder { fork: "Holocene", .. })
+        ));
+    }
+
     #[test]
     fn test_default_override_flags() {
         let args = MockCommand::parse_from(["test"]);
@@ -14,7 +14,10 @@ use discv5::enr::k256;
 use kona_derive::ChainProvider;
 use kona_disc::LocalNode;
 use kona_genesis::RollupConfig;
-use kona_gossip::GaterConfig;
+use kona_gossip::{
+    DEFAULT_RECENT_PAYLOADS_CAPACITY, GaterConfig, LatencyPreference, PayloadValidation,
+    PeerTargets, TopicMesh,
+};
 use kona_node_service::NetworkConfig;
 use kona_peers::{BootNode, BootStoreFile, PeerMonitoring, PeerScoreLevel};
 use kona_providers_alloy::AlloyChainProvider;
@@ -69,12 +72,16 @@ pub struct P2PArgs {
     /// UDP port to bind Discv5 to. Same as TCP port if left 0.
     #[arg(long = "p2p.listen.udp", default_value = "9223", env = "KONA_NODE_P2P_LISTEN_UDP_PORT")]
     pub listen_udp_port: u16,
+    /// UDP port to accept QUIC LibP2P connections on, advertised to peers in the ENR. QUIC
+    /// connections are disabled if unset. Must differ from `p2p.listen.udp`.
+    #[arg(long = "p2p.quic.port", env = "KONA_NODE_P2P_QUIC_PORT")]
+    pub quic_port: Option<u16>,
     /// Low-tide peer count. The node actively searches for new peer connections if below this
-    /// amount.
+    /// amount. Can be updated at runtime with `admin_setPeerTargets`.
     #[arg(long = "p2p.peers.lo", default_value = "20", env = "KONA_NODE_P2P_PEERS_LO")]
     pub peers_lo: u32,
     /// High-tide peer count. The node starts pruning peer connections slowly after reaching this
-    /// number.
+    /// number. Can be updated at runtime with `admin_setPeerTargets`.
     #[arg(long = "p2p.peers.hi", default_value = "30", env = "KONA_NODE_P2P_PEERS_HI")]
     pub peers_hi: u32,
     /// Grace period to keep a newly connected peer around, if it is not misbehaving.
@@ -85,6 +92,15 @@ pub struct P2PArgs {
         value_parser = |arg: &str| -> Result<Duration, ParseIntError> {Ok(Duration::from_secs(arg.parse()?))}
     )]
     pub peers_grace: Duration,
+    /// Percentage of the high-tide peer count reserved for the peers with the lowest ping
+    /// latency, which are never pruned. While set, the node keeps dialing one peer above the
+    /// high-tide count to find closer peers. Disabled if 0.
+    #[arg(
+        long = "p2p.peers.low-latency-pct",
+        default_value = "0",
+        env = "KONA_NODE_P2P_PEERS_LOW_LATENCY_PCT"
+    )]
+    pub peers_low_latency_pct: u8,
     /// Configure GossipSub topic stable mesh target count.
     /// Aka: The desired outbound degree (numbers of peers to gossip to).
     #[arg(long = "p2p.gossip.mesh.d", default_value = "8", env = "KONA_NODE_P2P_GOSSIP_MESH_D")]
@@ -118,6 +134,53 @@ pub struct P2PArgs {
         env = "KONA_NODE_P2P_GOSSIP_FLOOD_PUBLISH"
     )]
     pub gossip_flood_publish: bool,
+    /// Configure the GossipSub heartbeat interval, in milliseconds.
+    ///
+    /// The mesh of each topic is maintained on every heartbeat. Chains with sub-second blocks
+    /// may lower it to graft and prune peers faster.
+    #[arg(
+        long = "p2p.gossip.heartbeat",
+        default_value = "500",
+        env = "KONA_NODE_P2P_GOSSIP_HEARTBEAT",
+        value_parser = |arg: &str| -> Result<Duration, ParseIntError> {Ok(Duration::from_millis(arg.parse()?))}
+    )]
+    pub gossip_heartbeat: Duration,
+    /// Overrides the GossipSub mesh degrees of a block topic, formatted as
+    /// `<topic>=<d>,<d_lo>,<d_hi>` where the topic is one of v1, v2, v3 or v4. Can be repeated.
+    ///
+    /// The mesh degrees of the other topics are set by `p2p.gossip.mesh.d`, `p2p.gossip.mesh.lo`
+    /// and `p2p.gossip.mesh.dhi`. Chains with sub-second blocks usually need a denser mesh on the
+    /// active block topic than these defaults, which are tuned for 2s blocks.
+    #[arg(
+        long = "p2p.gossip.topic-mesh",
+        value_delimiter = ' ',
+        env = "KONA_NODE_P2P_GOSSIP_TOPIC_MESH"
+    )]
+    pub gossip_topic_mesh: Vec<TopicMesh>,
+    /// Sets how strictly blocks received over gossip are validated before engine insertion.
+    /// Can be one of: strict or lenient.
+    ///
+    /// Strict validation recomputes the block hash of each payload and checks its signature.
+    /// Lenient validation only checks the signature, which saves the cost of recomputing the
+    /// block hash on high-throughput chains. The payload is then only validated by the execution
+    /// layer.
+    #[arg(
+        long = "p2p.gossip.payload-validation",
+        default_value = "strict",
+        env = "KONA_NODE_P2P_GOSSIP_PAYLOAD_VALIDATION"
+    )]
+    pub gossip_payload_validation: PayloadValidation,
+    /// Sets the number of recent unsafe blocks kept to serve peers that join mid-epoch.
+    ///
+    /// Recent blocks are served over the `payload_by_number` request/response protocol, and blocks
+    /// published while no peer could receive them are re-published once a peer subscribes to the
+    /// block topics. Disabled if 0.
+    #[arg(
+        long = "p2p.gossip.recent-payloads",
+        default_value_t = DEFAULT_RECENT_PAYLOADS_CAPACITY,
+        env = "KONA_NODE_P2P_GOSSIP_RECENT_PAYLOADS"
+    )]
+    pub gossip_recent_payloads: usize,
     /// Sets the peer scoring strategy for the P2P stack.
     /// Can be one of: none or light.
     #[arg(long = "p2p.scoring", default_value = "light", env = "KONA_NODE_P2P_SCORING")]
@@ -363,21 +426,40 @@ impl P2PArgs {
         let local_node_key = k256::ecdsa::SigningKey::from_bytes(&secp256k1_key.into())
             .map_err(|e| anyhow::anyhow!("Impossible to convert keypair to k256 signing key. This is a bug since we only support secp256k1 keys: {e}"))?;
 
+        if let Some(quic_port) = self.quic_port {
+            anyhow::ensure!(quic_port != 0, "The QUIC port must be set to a non-zero port");
+            anyhow::ensure!(
+                quic_port != self.listen_udp_port,
+                "The QUIC port {quic_port} is already used by the discovery service"
+            );
+        }
+
         let discovery_address =
-            LocalNode::new(local_node_key, advertise_ip, advertise_tcp_port, advertise_udp_port);
-        let gossip_config = kona_gossip::default_config_builder()
+            LocalNode::new(local_node_key, advertise_ip, advertise_tcp_port, advertise_udp_port)
+                .with_quic_port(self.quic_port);
+        let mut gossip_config = kona_gossip::default_config_builder();
+        gossip_config
             .mesh_n(self.gossip_mesh_d)
             .mesh_n_low(self.gossip_mesh_dlo)
             .mesh_n_high(self.gossip_mesh_dhi)
             .gossip_lazy(self.gossip_mesh_dlazy)
-            .flood_publish(self.gossip_flood_publish)
-            .build()?;
+            .heartbeat_interval(self.gossip_heartbeat)
+            .flood_publish(self.gossip_flood_publish);
+        for topic_mesh in &self.gossip_topic_mesh {
+            topic_mesh.apply(&mut gossip_config, config.l2_chain_id.id());
+        }
+        let gossip_config = gossip_config.build()?;
 
         let monitor_peers = self.ban_enabled.then_some(PeerMonitoring {
             ban_duration: Duration::from_secs(60 * self.ban_duration),
             ban_threshold: self.ban_threshold as f64,
         });
 
+        let peer_targets =
+            PeerTargets::new(self.peers_lo as usize, self.peers_hi as usize, self.peers_grace)?;
+        let latency_preference =
+            LatencyPreference::new(f64::from(self.peers_low_latency_pct) / 100.0)?;
+
         let discovery_listening_address = SocketAddr::new(self.listen_ip, self.listen_udp_port);
         let discovery_config =
             NetworkConfig::discv5_config(discovery_listening_address.into(), static_ip);
@@ -385,6 +467,13 @@ impl P2PArgs {
         let mut gossip_address = libp2p::Multiaddr::from(self.listen_ip);
         gossip_address.push(libp2p::multiaddr::Protocol::Tcp(self.listen_tcp_port));
 
+        let gossip_quic_address = self.quic_port.map(|port| {
+            let mut addr = libp2p::Multiaddr::from(self.listen_ip);
+            addr.push(libp2p::multiaddr::Protocol::Udp(port));
+            addr.push(libp2p::multiaddr::Protocol::QuicV1);
+            addr
+        });
+
         let unsafe_block_signer = self.unsafe_block_signer(args, config, l1_rpc).await?;
 
         let bootstore = if self.disable_bootstore {
@@ -410,6 +499,7 @@ impl P2PArgs {
             discovery_randomize: self.discovery_randomize.map(Duration::from_secs),
             enr_update: !static_ip,
             gossip_address,
+            gossip_quic_address,
             keypair,
             unsafe_block_signer,
             gossip_config,
@@ -421,6 +511,10 @@ impl P2PArgs {
                 peer_redialing: self.peer_redial,
                 dial_period: Duration::from_secs(60 * self.redial_period),
             },
+            peer_targets,
+            latency_preference,
+            payload_validation: self.gossip_payload_validation,
+            recent_payloads: self.gossip_recent_payloads,
             bootnodes,
             rollup_config: config.clone(),
             gossip_signer: self.signer.config(args)?,
@@ -567,6 +661,69 @@ mod tests {
         assert_eq!(args.p2p.listen_udp_port, 1234);
     }
 
+    #[test]
+    fn test_p2p_args_gossip_heartbeat() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.gossip_heartbeat, Duration::from_millis(500));
+        let args = MockCommand::parse_from(["test", "--p2p.gossip.heartbeat", "250"]);
+        assert_eq!(args.p2p.gossip_heartbeat, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_p2p_args_gossip_topic_mesh() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.p2p.gossip_topic_mesh.is_empty());
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.gossip.topic-mesh",
+            "v4=16,12,24",
+            "--p2p.gossip.topic-mesh",
+            "v3=10,8,16",
+        ]);
+        assert_eq!(
+            args.p2p.gossip_topic_mesh,
+            vec![
+                TopicMesh::new(kona_gossip::BlockTopic::V4, 16, 12, 24).unwrap(),
+                TopicMesh::new(kona_gossip::BlockTopic::V3, 10, 8, 16).unwrap(),
+            ]
+        );
+        assert!(
+            MockCommand::try_parse_from(["test", "--p2p.gossip.topic-mesh", "v4=8,12,24"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_p2p_args_quic_port() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.quic_port, None);
+        let args = MockCommand::parse_from(["test", "--p2p.quic.port", "9224"]);
+        assert_eq!(args.p2p.quic_port, Some(9224));
+    }
+
+    #[test]
+    fn test_p2p_args_peers_low_latency_pct() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.peers_low_latency_pct, 0);
+        let args = MockCommand::parse_from(["test", "--p2p.peers.low-latency-pct", "25"]);
+        assert_eq!(args.p2p.peers_low_latency_pct, 25);
+    }
+
+    #[test]
+    fn test_p2p_args_gossip_payload_validation() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.gossip_payload_validation, PayloadValidation::Strict);
+        let args = MockCommand::parse_from(["test", "--p2p.gossip.payload-validation", "lenient"]);
+        assert_eq!(args.p2p.gossip_payload_validation, PayloadValidation::Lenient);
+    }
+
+    #[test]
+    fn test_p2p_args_gossip_recent_payloads() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.gossip_recent_payloads, DEFAULT_RECENT_PAYLOADS_CAPACITY);
+        let args = MockCommand::parse_from(["test", "--p2p.gossip.recent-payloads", "0"]);
+        assert_eq!(args.p2p.gossip_recent_payloads, 0);
+    }
+
     #[test]
     fn test_p2p_args_bootnodes() {
         let args = MockCommand::parse_from([
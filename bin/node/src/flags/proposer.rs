@@ -0,0 +1,213 @@
+//! Proposer CLI Flags
+//!
+//! These are based on the flags of the [`op-proposer`][op-proposer] CLI.
+//!
+//! [op-proposer]: https://github.com/ethereum-optimism/optimism/blob/develop/op-proposer/flags/flags.go
+
+use crate::flags::parse_secs;
+use alloy_primitives::{Address, B256};
+use alloy_signer_local::PrivateKeySigner;
+use clap::Parser;
+use kona_node_service::{ProposalTarget, ProposerConfig};
+use std::time::Duration;
+
+/// Proposer CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct ProposerArgs {
+    /// Address of the `L2OutputOracle` to propose outputs to. Providing this value, or
+    /// `proposer.game-factory-address`, enables the output root proposer.
+    #[arg(
+        long = "proposer.l2oo-address",
+        env = "KONA_NODE_PROPOSER_L2OO_ADDRESS",
+        conflicts_with = "game_factory_address"
+    )]
+    pub l2oo_address: Option<Address>,
+
+    /// Address of the `DisputeGameFactory` to create dispute games with.
+    #[arg(long = "proposer.game-factory-address", env = "KONA_NODE_PROPOSER_GAME_FACTORY_ADDRESS")]
+    pub game_factory_address: Option<Address>,
+
+    /// The type of dispute game to create.
+    #[arg(
+        long = "proposer.game-type",
+        default_value = "0",
+        env = "KONA_NODE_PROPOSER_GAME_TYPE",
+        requires = "game_factory_address"
+    )]
+    pub game_type: u32,
+
+    /// The minimum interval between two dispute games, in seconds. Required with
+    /// `proposer.game-factory-address`.
+    #[arg(
+        long = "proposer.proposal-interval",
+        env = "KONA_NODE_PROPOSER_PROPOSAL_INTERVAL",
+        requires = "game_factory_address",
+        value_parser = parse_secs
+    )]
+    pub proposal_interval: Option<Duration>,
+
+    /// The interval at which the proposer checks whether a new proposal is due, in seconds.
+    #[arg(
+        long = "proposer.poll-interval",
+        default_value = "6",
+        env = "KONA_NODE_PROPOSER_POLL_INTERVAL",
+        value_parser = parse_secs
+    )]
+    pub poll_interval: Duration,
+
+    /// Propose outputs at the safe head instead of the finalized head.
+    #[arg(
+        long = "proposer.allow-non-finalized",
+        default_value = "false",
+        env = "KONA_NODE_PROPOSER_ALLOW_NON_FINALIZED"
+    )]
+    pub allow_non_finalized: bool,
+
+    /// The private key used to sign proposal transactions on L1.
+    #[arg(long = "proposer.private-key", env = "KONA_NODE_PROPOSER_PRIVATE_KEY")]
+    pub private_key: Option<B256>,
+}
+
+impl Default for ProposerArgs {
+    fn default() -> Self {
+        // Construct default values using the clap parser.
+        // This works since none of the cli flags are required.
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl ProposerArgs {
+    /// Creates a [`ProposerConfig`] from the [`ProposerArgs`].
+    ///
+    /// Returns [`None`] if no proposal target is configured.
+    pub fn config(&self) -> anyhow::Result<Option<ProposerConfig>> {
+        let target = match (self.l2oo_address, self.game_factory_address) {
+            (Some(oracle), None) => ProposalTarget::L2OutputOracle(oracle),
+            (None, Some(address)) => ProposalTarget::DisputeGameFactory {
+                address,
+                game_type: self.game_type,
+                proposal_interval: self.proposal_interval.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "`--proposer.proposal-interval` is required with `--proposer.game-factory-address`"
+                    )
+                })?,
+            },
+            (None, None) => return Ok(None),
+            (Some(_), Some(_)) => anyhow::bail!(
+                "`--proposer.l2oo-address` and `--proposer.game-factory-address` are mutually exclusive"
+            ),
+        };
+
+        let key = self.private_key.ok_or_else(|| {
+            anyhow::anyhow!("`--proposer.private-key` is required by the proposer")
+        })?;
+        let signer = PrivateKeySigner::from_bytes(&key)
+            .map_err(|e| anyhow::anyhow!("Invalid proposer private key: {e}"))?;
+
+        Ok(Some(ProposerConfig {
+            signer,
+            target,
+            poll_interval: self.poll_interval,
+            allow_non_finalized: self.allow_non_finalized,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    /// A mock command that uses the proposer args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Proposer CLI Flags
+        #[clap(flatten)]
+        pub proposer: ProposerArgs,
+    }
+
+    #[test]
+    fn test_proposer_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.proposer.config().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_l2oo_proposer() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--proposer.l2oo-address",
+            "0x0000000000000000000000000000000000000001",
+            "--proposer.private-key",
+            KEY,
+        ]);
+        let config = args.proposer.config().unwrap().unwrap();
+        assert_eq!(
+            config.target,
+            ProposalTarget::L2OutputOracle(address!("0x0000000000000000000000000000000000000001"))
+        );
+        assert_eq!(config.signer.address(), address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+        assert_eq!(config.poll_interval, Duration::from_secs(6));
+        assert!(!config.allow_non_finalized);
+    }
+
+    #[test]
+    fn test_dispute_game_proposer() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--proposer.game-factory-address",
+            "0x0000000000000000000000000000000000000002",
+            "--proposer.game-type",
+            "1",
+            "--proposer.proposal-interval",
+            "3600",
+            "--proposer.allow-non-finalized",
+            "--proposer.private-key",
+            KEY,
+        ]);
+        let config = args.proposer.config().unwrap().unwrap();
+        assert_eq!(
+            config.target,
+            ProposalTarget::DisputeGameFactory {
+                address: address!("0x0000000000000000000000000000000000000002"),
+                game_type: 1,
+                proposal_interval: Duration::from_secs(3600),
+            }
+        );
+        assert!(config.allow_non_finalized);
+    }
+
+    #[test]
+    fn test_proposer_requires_key_and_interval() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--proposer.l2oo-address",
+            "0x0000000000000000000000000000000000000001",
+        ]);
+        assert!(args.proposer.config().is_err());
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--proposer.game-factory-address",
+            "0x0000000000000000000000000000000000000002",
+            "--proposer.private-key",
+            KEY,
+        ]);
+        assert!(args.proposer.config().is_err());
+    }
+
+    #[test]
+    fn test_proposer_targets_conflict() {
+        let result = MockCommand::try_parse_from([
+            "test",
+            "--proposer.l2oo-address",
+            "0x0000000000000000000000000000000000000001",
+            "--proposer.game-factory-address",
+            "0x0000000000000000000000000000000000000002",
+        ]);
+        assert!(result.is_err());
+    }
+}
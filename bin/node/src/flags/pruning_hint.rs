@@ -0,0 +1,100 @@
+//! Pruning Hint CLI Flags
+
+use crate::flags::parse_secs;
+use clap::Parser;
+use kona_node_service::PruningHintConfig;
+use std::time::Duration;
+use url::Url;
+
+/// Pruning Hint CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct PruningHintArgs {
+    /// HTTP endpoint to periodically `POST` a JSON hint of the lowest L2 block the node may still
+    /// need for derivation resets to, so that a co-located execution layer can safely prune below
+    /// it. Providing this value enables the pruning hints.
+    #[arg(long = "pruning-hints.url", env = "KONA_NODE_PRUNING_HINTS_URL")]
+    pub url: Option<Url>,
+
+    /// The interval at which pruning hints are sent, in seconds.
+    #[arg(
+        long = "pruning-hints.interval",
+        default_value = "60",
+        env = "KONA_NODE_PRUNING_HINTS_INTERVAL",
+        value_parser = parse_secs
+    )]
+    pub interval: Duration,
+}
+
+impl Default for PruningHintArgs {
+    fn default() -> Self {
+        // Construct default values using the clap parser.
+        // This works since none of the cli flags are required.
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl PruningHintArgs {
+    /// Creates a [`PruningHintConfig`] from the [`PruningHintArgs`].
+    ///
+    /// Returns [`None`] if no pruning hint endpoint is configured.
+    pub fn config(&self) -> anyhow::Result<Option<PruningHintConfig>> {
+        let Some(url) = self.url.clone() else {
+            return Ok(None);
+        };
+        if self.interval.is_zero() {
+            anyhow::bail!("`--pruning-hints.interval` must be greater than zero");
+        }
+
+        Ok(Some(PruningHintConfig { url, interval: self.interval }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the pruning hint args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Pruning Hint CLI Flags
+        #[clap(flatten)]
+        pub pruning_hints: PruningHintArgs,
+    }
+
+    #[test]
+    fn test_pruning_hints_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.pruning_hints.config().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_pruning_hints() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--pruning-hints.url",
+            "http://localhost:8551/prune",
+            "--pruning-hints.interval",
+            "30",
+        ]);
+        assert_eq!(
+            args.pruning_hints.config().unwrap(),
+            Some(PruningHintConfig {
+                url: "http://localhost:8551/prune".parse().unwrap(),
+                interval: Duration::from_secs(30),
+            })
+        );
+    }
+
+    #[test]
+    fn test_pruning_hints_zero_interval() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--pruning-hints.url",
+            "http://localhost:8551/prune",
+            "--pruning-hints.interval",
+            "0",
+        ]);
+        assert!(args.pruning_hints.config().is_err());
+    }
+}
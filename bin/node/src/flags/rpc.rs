@@ -3,9 +3,10 @@
 //! Flags for configuring the RPC server.
 
 use clap::Parser;
-use kona_rpc::RpcBuilder;
+use kona_rpc::{RpcBuilder, RpcMethodPolicy, RpcTlsConfig, TlsCertKeyPaths};
 use std::{
     net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
     path::PathBuf,
 };
 
@@ -37,6 +38,76 @@ pub struct RpcArgs {
     /// Enables development RPC endpoints for engine state introspection
     #[arg(long = "rpc.dev-enabled", default_value = "false", env = "KONA_NODE_RPC_DEV_ENABLED")]
     pub dev_enabled: bool,
+    /// Path to the PEM certificate chain the RPC server terminates TLS with. The RPC server
+    /// serves plaintext if not set. Reloaded when the file changes.
+    #[arg(long = "rpc.tls-cert", requires = "tls_key", env = "KONA_NODE_RPC_TLS_CERT")]
+    pub tls_cert: Option<PathBuf>,
+    /// Path to the PEM private key of the `--rpc.tls-cert` certificate. Reloaded when the file
+    /// changes.
+    #[arg(long = "rpc.tls-key", requires = "tls_cert", env = "KONA_NODE_RPC_TLS_KEY")]
+    pub tls_key: Option<PathBuf>,
+    /// A certificate served to clients requesting the given server name, as
+    /// `<NAME>=<CERT>,<KEY>`. The name may start with `*.` to match any subdomain. Can be
+    /// repeated. Clients requesting other server names are served `--rpc.tls-cert`.
+    #[arg(
+        long = "rpc.tls-sni",
+        value_name = "NAME=CERT,KEY",
+        value_parser = parse_sni_cert,
+        requires = "tls_cert",
+        env = "KONA_NODE_RPC_TLS_SNI"
+    )]
+    pub tls_sni: Vec<(String, TlsCertKeyPaths)>,
+    /// Methods of the `opp2p` and `admin` namespaces that are not served, e.g.
+    /// `opp2p_discoveryTable`.
+    #[arg(
+        long = "rpc.disabled-methods",
+        value_delimiter = ',',
+        value_parser = parse_restrictable_method,
+        env = "KONA_NODE_RPC_DISABLED_METHODS"
+    )]
+    pub disabled_methods: Vec<String>,
+    /// Rate limits of methods of the `opp2p` and `admin` namespaces, as `<METHOD>=<CALLS>`, the
+    /// maximum number of calls to the method per second across all clients, e.g.
+    /// `opp2p_peers=5`.
+    #[arg(
+        long = "rpc.rate-limits",
+        value_name = "METHOD=CALLS",
+        value_delimiter = ',',
+        value_parser = parse_rate_limit,
+        env = "KONA_NODE_RPC_RATE_LIMITS"
+    )]
+    pub rate_limits: Vec<(String, NonZeroU32)>,
+}
+
+/// Parses the name of a method that can be disabled or rate limited.
+fn parse_restrictable_method(method: &str) -> Result<String, String> {
+    if !RpcMethodPolicy::is_restrictable(method) {
+        return Err(format!(
+            "{method} is not a method of the {} namespaces",
+            RpcMethodPolicy::NAMESPACES.join(" or ")
+        ));
+    }
+    Ok(method.to_string())
+}
+
+/// Parses the rate limit of a method, formatted as `<METHOD>=<CALLS>`.
+fn parse_rate_limit(arg: &str) -> Result<(String, NonZeroU32), String> {
+    let (method, limit) =
+        arg.split_once('=').ok_or_else(|| format!("expected <METHOD>=<CALLS>, got {arg}"))?;
+    let limit = limit.parse().map_err(|err| format!("invalid rate limit {limit}: {err}"))?;
+    Ok((parse_restrictable_method(method)?, limit))
+}
+
+/// Parses a certificate selected by server name, formatted as `<NAME>=<CERT>,<KEY>`.
+fn parse_sni_cert(arg: &str) -> Result<(String, TlsCertKeyPaths), String> {
+    let (name, paths) =
+        arg.split_once('=').ok_or_else(|| format!("expected <NAME>=<CERT>,<KEY>, got {arg}"))?;
+    let (cert, key) =
+        paths.split_once(',').ok_or_else(|| format!("expected <CERT>,<KEY>, got {paths}"))?;
+    if name.is_empty() || cert.is_empty() || key.is_empty() {
+        return Err(format!("expected <NAME>=<CERT>,<KEY>, got {arg}"));
+    }
+    Ok((name.to_string(), TlsCertKeyPaths { cert: cert.into(), key: key.into() }))
 }
 
 impl Default for RpcArgs {
@@ -59,6 +130,15 @@ impl From<RpcArgs> for Option<RpcBuilder> {
             admin_persistence: args.admin_persistence,
             ws_enabled: args.ws_enabled,
             dev_enabled: args.dev_enabled,
+            tls: args.tls_cert.zip(args.tls_key).map(|(cert, key)| RpcTlsConfig {
+                default: TlsCertKeyPaths { cert, key },
+                sni: args.tls_sni,
+            }),
+            method_policy: RpcMethodPolicy {
+                disabled: args.disabled_methods.into_iter().collect(),
+                rate_limits: args.rate_limits.into_iter().collect(),
+            },
+            build_info: Some(crate::version::build_info()),
         })
     }
 }
@@ -77,6 +157,28 @@ mod tests {
     #[case::disable_rpc_alias(&["--rpc.port", "8743"], |args: &mut RpcArgs| { args.listen_port = 8743; })]
     #[case::disable_rpc(&["--rpc.enable-admin"], |args: &mut RpcArgs| { args.enable_admin = true; })]
     #[case::disable_rpc(&["--rpc.admin-state", "/"], |args: &mut RpcArgs| { args.admin_persistence = Some(PathBuf::from("/")); })]
+    #[case::disabled_methods(&["--rpc.disabled-methods", "opp2p_discoveryTable,admin_startSequencer"], |args: &mut RpcArgs| {
+        args.disabled_methods =
+            vec!["opp2p_discoveryTable".to_string(), "admin_startSequencer".to_string()];
+    })]
+    #[case::rate_limits(&["--rpc.rate-limits", "opp2p_peers=5,opp2p_peerStats=10"], |args: &mut RpcArgs| {
+        args.rate_limits = vec![
+            ("opp2p_peers".to_string(), NonZeroU32::new(5).unwrap()),
+            ("opp2p_peerStats".to_string(), NonZeroU32::new(10).unwrap()),
+        ];
+    })]
+    #[case::tls(&["--rpc.tls-cert", "cert.pem", "--rpc.tls-key", "key.pem"], |args: &mut RpcArgs| {
+        args.tls_cert = Some(PathBuf::from("cert.pem"));
+        args.tls_key = Some(PathBuf::from("key.pem"));
+    })]
+    #[case::tls_sni(&["--rpc.tls-cert", "cert.pem", "--rpc.tls-key", "key.pem", "--rpc.tls-sni", "*.example.com=a.pem,b.pem"], |args: &mut RpcArgs| {
+        args.tls_cert = Some(PathBuf::from("cert.pem"));
+        args.tls_key = Some(PathBuf::from("key.pem"));
+        args.tls_sni = vec![(
+            "*.example.com".to_string(),
+            TlsCertKeyPaths { cert: PathBuf::from("a.pem"), key: PathBuf::from("b.pem") },
+        )];
+    })]
     fn test_parse_rpc_args(#[case] args: &[&str], #[case] mutate: impl Fn(&mut RpcArgs)) {
         let args = [&["kona-node"], args].concat();
         let cli = RpcArgs::parse_from(args);
@@ -84,4 +186,25 @@ mod tests {
         mutate(&mut expected);
         assert_eq!(cli, expected);
     }
+
+    #[rstest]
+    #[case::cert_without_key(&["--rpc.tls-cert", "cert.pem"])]
+    #[case::key_without_cert(&["--rpc.tls-key", "key.pem"])]
+    #[case::sni_without_cert(&["--rpc.tls-sni", "example.com=a.pem,b.pem"])]
+    #[case::sni_without_key(&["--rpc.tls-cert", "c.pem", "--rpc.tls-key", "k.pem", "--rpc.tls-sni", "example.com=a.pem"])]
+    #[case::sni_without_name(&["--rpc.tls-cert", "c.pem", "--rpc.tls-key", "k.pem", "--rpc.tls-sni", "=a.pem,b.pem"])]
+    fn test_parse_invalid_tls_args(#[case] args: &[&str]) {
+        let args = [&["kona-node"], args].concat();
+        assert!(RpcArgs::try_parse_from(args).is_err());
+    }
+
+    #[rstest]
+    #[case::disabled_other_namespace(&["--rpc.disabled-methods", "optimism_syncStatus"])]
+    #[case::rate_limit_other_namespace(&["--rpc.rate-limits", "optimism_syncStatus=1"])]
+    #[case::rate_limit_zero(&["--rpc.rate-limits", "opp2p_peers=0"])]
+    #[case::rate_limit_missing(&["--rpc.rate-limits", "opp2p_peers"])]
+    fn test_parse_invalid_method_policy_args(#[case] args: &[&str]) {
+        let args = [&["kona-node"], args].concat();
+        assert!(RpcArgs::try_parse_from(args).is_err());
+    }
 }
@@ -5,7 +5,7 @@
 //! [op-node]: https://github.com/ethereum-optimism/optimism/blob/develop/op-node/flags/flags.go#L233-L265
 
 use clap::Parser;
-use kona_node_service::SequencerConfig;
+use kona_node_service::{DaThrottleConfig, PayloadBuildConfig, SequencerConfig};
 use std::{num::ParseIntError, time::Duration};
 use url::Url;
 
@@ -43,6 +43,64 @@ pub struct SequencerArgs {
     )]
     pub recover: bool,
 
+    /// Maximum time, in milliseconds, to build a payload for before sealing it. Payloads are
+    /// always sealed by their slot. If unset, payloads are built until their slot.
+    #[arg(
+        long = "sequencer.build-deadline",
+        env = "KONA_NODE_SEQUENCER_BUILD_DEADLINE",
+        value_parser = |arg: &str| -> Result<Duration, ParseIntError> {Ok(Duration::from_millis(arg.parse()?))}
+    )]
+    pub build_deadline: Option<Duration>,
+
+    /// Gas utilization of the previous block, in percent of its gas limit, below which the build
+    /// deadline is lifted so the next payload may use its full slot.
+    #[arg(
+        long = "sequencer.target-gas-utilization",
+        env = "KONA_NODE_SEQUENCER_TARGET_GAS_UTILIZATION",
+        value_parser = clap::value_parser!(u8).range(0..=100)
+    )]
+    pub target_gas_utilization: Option<u8>,
+
+    /// Maximum number of deposits in a block that may also include transaction pool
+    /// transactions. Blocks with more deposits are built with their deposits only.
+    #[arg(
+        long = "sequencer.max-deposits-per-block",
+        env = "KONA_NODE_SEQUENCER_MAX_DEPOSITS_PER_BLOCK"
+    )]
+    pub max_deposits_per_block: Option<usize>,
+
+    /// DA backlog of the batcher, in bytes, above which sequenced blocks are throttled. Providing
+    /// this value enables DA throttling, which requires the batcher to run in the same node.
+    #[arg(
+        long = "sequencer.da-throttle.threshold",
+        env = "KONA_NODE_SEQUENCER_DA_THROTTLE_THRESHOLD"
+    )]
+    pub da_throttle_threshold: Option<u64>,
+
+    /// Maximum DA size of a transaction in a throttled block.
+    #[arg(
+        long = "sequencer.da-throttle.tx-size",
+        default_value = "300",
+        env = "KONA_NODE_SEQUENCER_DA_THROTTLE_TX_SIZE"
+    )]
+    pub da_throttle_tx_size: u64,
+
+    /// Maximum DA size of a throttled block once the DA backlog reaches twice the threshold.
+    #[arg(
+        long = "sequencer.da-throttle.block-size-lower",
+        default_value = "2000",
+        env = "KONA_NODE_SEQUENCER_DA_THROTTLE_BLOCK_SIZE_LOWER"
+    )]
+    pub da_throttle_block_size_lower: u64,
+
+    /// Maximum DA size of a throttled block as the DA backlog crosses the threshold.
+    #[arg(
+        long = "sequencer.da-throttle.block-size-upper",
+        default_value = "130000",
+        env = "KONA_NODE_SEQUENCER_DA_THROTTLE_BLOCK_SIZE_UPPER"
+    )]
+    pub da_throttle_block_size_upper: u64,
+
     /// Conductor service rpc endpoint. Providing this value will enable the conductor service.
     #[arg(long = "conductor.rpc", env = "KONA_NODE_CONDUCTOR_RPC")]
     pub conductor_rpc: Option<Url>,
@@ -73,6 +131,17 @@ impl SequencerArgs {
             sequencer_recovery_mode: self.recover,
             conductor_rpc_url: self.conductor_rpc.clone(),
             l1_conf_delay: self.l1_confs,
+            payload_build: PayloadBuildConfig {
+                build_deadline: self.build_deadline,
+                target_gas_utilization: self.target_gas_utilization,
+                max_deposits_per_block: self.max_deposits_per_block,
+            },
+            da_throttle: self.da_throttle_threshold.map(|threshold| DaThrottleConfig {
+                threshold,
+                max_tx_size: self.da_throttle_tx_size,
+                block_size_lower_limit: self.da_throttle_block_size_lower,
+                block_size_upper_limit: self.da_throttle_block_size_upper,
+            }),
         }
     }
 }
@@ -0,0 +1,178 @@
+//! Shadow Fork CLI Flags
+//!
+//! A shadow fork starts the node from an existing L2 block of the configured chain, but derives
+//! the L2 chain beyond that block from a private fork of the L1 chain. This is commonly used to
+//! rehearse network upgrades against real chain state without touching the live network.
+
+use alloy_rpc_types_engine::JwtSecret;
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+use kona_derive::{ChainProvider, L2ChainProvider};
+use kona_genesis::{ChainGenesis, RollupConfig};
+use kona_protocol::BatchValidationProvider;
+use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider};
+use std::sync::Arc;
+use tracing::info;
+use url::Url;
+
+/// The size of the provider caches used while resolving the shadow fork point.
+const SHADOW_FORK_PROVIDER_CACHE_SIZE: usize = 16;
+
+/// Shadow Fork CLI Flags
+#[derive(Parser, Default, Clone, Debug, PartialEq, Eq)]
+pub struct ShadowForkArgs {
+    /// The L2 block number to fork from. Providing this value enables shadow fork mode.
+    ///
+    /// The block must already be known to the L2 execution client. It becomes the genesis of the
+    /// forked chain, and every block after it is derived from the L1 chain served by
+    /// `--l1-eth-rpc`.
+    #[arg(long = "shadow-fork.l2-block", env = "KONA_NODE_SHADOW_FORK_L2_BLOCK")]
+    pub l2_block: Option<u64>,
+
+    /// The block number on the private L1 fork to anchor the forked L2 chain to.
+    ///
+    /// Defaults to the L1 origin of the L2 fork block. The block's timestamp must not be greater
+    /// than the timestamp of the L2 fork block.
+    #[arg(
+        long = "shadow-fork.l1-block",
+        env = "KONA_NODE_SHADOW_FORK_L1_BLOCK",
+        requires = "l2_block"
+    )]
+    pub l1_block: Option<u64>,
+}
+
+impl ShadowForkArgs {
+    /// Returns `true` if shadow fork mode is enabled.
+    pub const fn enabled(&self) -> bool {
+        self.l2_block.is_some()
+    }
+
+    /// Rewrites the genesis of the given [`RollupConfig`] so that the chain starts at the shadow
+    /// fork point. Returns the config unchanged if shadow fork mode is disabled.
+    ///
+    /// The L2 fork block is fetched from the L2 execution client, and the L1 anchor block is
+    /// fetched from the (private) L1 RPC. The system config at the fork block is carried over
+    /// into the new genesis, so that the attributes builder continues from the live chain's
+    /// fee and gas parameters.
+    pub async fn apply(
+        &self,
+        config: RollupConfig,
+        l1_rpc: Url,
+        l2_engine_rpc: Url,
+        l2_jwt_secret: JwtSecret,
+    ) -> Result<RollupConfig> {
+        let Some(l2_number) = self.l2_block else {
+            return Ok(config);
+        };
+
+        if l2_number < config.genesis.l2.number {
+            bail!(
+                "Shadow fork block {l2_number} is below the chain's L2 genesis block {}",
+                config.genesis.l2.number
+            );
+        }
+
+        let config = Arc::new(config);
+        let mut l2_provider = AlloyL2ChainProvider::new_http(
+            l2_engine_rpc,
+            config.clone(),
+            SHADOW_FORK_PROVIDER_CACHE_SIZE,
+            l2_jwt_secret,
+        );
+        let l2_block = l2_provider
+            .l2_block_info_by_number(l2_number)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch shadow fork L2 block {l2_number}: {e}"))?;
+        let system_config = l2_provider
+            .system_config_by_number(l2_number, config.clone())
+            .await
+            .map_err(|e| anyhow!("Failed to load system config at L2 block {l2_number}: {e}"))?;
+
+        let l1_number = self.l1_block.unwrap_or(l2_block.l1_origin.number);
+        let mut l1_provider = AlloyChainProvider::new_http(l1_rpc, SHADOW_FORK_PROVIDER_CACHE_SIZE);
+        let l1_block = l1_provider
+            .block_info_by_number(l1_number)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch shadow fork L1 block {l1_number}: {e}"))?;
+
+        // The first L2 block after the fork point must be able to use the L1 anchor block as its
+        // origin without breaking the L1/L2 time invariant.
+        if l1_block.timestamp > l2_block.block_info.timestamp {
+            bail!(
+                "Shadow fork L1 block {l1_number} (timestamp {}) is newer than L2 block {l2_number} (timestamp {})",
+                l1_block.timestamp,
+                l2_block.block_info.timestamp
+            );
+        }
+
+        info!(
+            target: "shadow_fork",
+            l2_block = %l2_block.block_info.number,
+            l2_hash = %l2_block.block_info.hash,
+            l1_block = %l1_block.number,
+            l1_hash = %l1_block.hash,
+            "Shadow fork enabled, rewriting rollup config genesis"
+        );
+
+        let mut config = Arc::unwrap_or_clone(config);
+        config.genesis = ChainGenesis {
+            l1: l1_block.id(),
+            l2: l2_block.block_info.id(),
+            l2_time: l2_block.block_info.timestamp,
+            system_config: Some(system_config),
+        };
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the shadow fork args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Shadow fork flags.
+        #[clap(flatten)]
+        pub shadow_fork: ShadowForkArgs,
+    }
+
+    #[test]
+    fn test_shadow_fork_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(!args.shadow_fork.enabled());
+        assert_eq!(args.shadow_fork, ShadowForkArgs::default());
+    }
+
+    #[test]
+    fn test_shadow_fork_flags() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--shadow-fork.l2-block",
+            "1000",
+            "--shadow-fork.l1-block",
+            "200",
+        ]);
+        assert!(args.shadow_fork.enabled());
+        assert_eq!(args.shadow_fork.l2_block, Some(1000));
+        assert_eq!(args.shadow_fork.l1_block, Some(200));
+    }
+
+    #[test]
+    fn test_shadow_fork_l1_block_requires_l2_block() {
+        let err = MockCommand::try_parse_from(["test", "--shadow-fork.l1-block", "200"]);
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_disabled_is_noop() {
+        let config = RollupConfig::default();
+        let url = Url::parse("http://localhost:8545").unwrap();
+        let updated = ShadowForkArgs::default()
+            .apply(config.clone(), url.clone(), url, JwtSecret::random())
+            .await
+            .unwrap();
+        assert_eq!(updated, config);
+    }
+}
@@ -5,13 +5,24 @@ use alloy_signer::{Signer, k256::ecdsa};
 use alloy_signer_local::PrivateKeySigner;
 use clap::{Parser, arg};
 use kona_cli::SecretKeyLoader;
-use kona_sources::{BlockSigner, ClientCert, RemoteSigner};
+use kona_sources::{BlockSigner, ClientCert, RemoteSigner, Web3Signer};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use std::str::FromStr;
 use url::Url;
 
 use crate::flags::GlobalArgs;
 
+/// The API of the remote signer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum RemoteSignerType {
+    /// An op-signer server, signing blocks with `opsigner_signBlockPayload`.
+    #[default]
+    OpSigner,
+    /// A web3signer server, signing blocks with its eth1 signing API.
+    Web3signer,
+}
+
 /// Signer CLI Flags
 #[derive(Debug, Clone, Parser, Default, PartialEq, Eq)]
 pub struct SignerArgs {
@@ -30,6 +41,23 @@ pub struct SignerArgs {
         conflicts_with = "sequencer_key"
     )]
     pub sequencer_key_path: Option<PathBuf>,
+    /// An optional path to an encrypted JSON keystore holding the sequencer private key.
+    /// This is mutually exclusive with `p2p.sequencer.key` and `p2p.sequencer.key.path`.
+    #[arg(
+        long = "p2p.sequencer.keystore",
+        env = "KONA_NODE_P2P_SEQUENCER_KEYSTORE",
+        conflicts_with_all = ["sequencer_key", "sequencer_key_path"],
+        requires = "keystore_password"
+    )]
+    pub keystore: Option<PathBuf>,
+    /// The password of the sequencer keystore.
+    #[arg(
+        long = "p2p.sequencer.keystore.password",
+        env = "KONA_NODE_P2P_SEQUENCER_KEYSTORE_PASSWORD",
+        requires = "keystore",
+        hide_env_values = true
+    )]
+    pub keystore_password: Option<String>,
     /// The URL of the remote signer endpoint. If not provided, remote signer will be disabled.
     /// This is mutually exclusive with `p2p.sequencer.key`.
     /// This is required if any of the other signer flags are provided.
@@ -39,6 +67,14 @@ pub struct SignerArgs {
         requires = "address"
     )]
     pub endpoint: Option<Url>,
+    /// The API of the remote signer: `op-signer` or `web3signer`.
+    #[arg(
+        long = "p2p.signer.type",
+        env = "KONA_NODE_P2P_SIGNER_TYPE",
+        default_value = "op-signer",
+        requires = "endpoint"
+    )]
+    pub signer_type: RemoteSignerType,
     /// The address to sign transactions for. Required if `signer.endpoint` is provided.
     #[arg(
         long = "p2p.signer.address",
@@ -91,6 +127,9 @@ pub enum SignerArgsParseError {
     /// Failed to load sequencer key from file.
     #[error("Failed to load sequencer key from file")]
     SequencerKeyFileError(#[from] kona_cli::KeypairError),
+    /// Failed to decrypt the sequencer keystore.
+    #[error("Failed to decrypt the sequencer keystore: {0}")]
+    KeystoreError(#[from] alloy_signer_local::LocalSignerError),
     /// The address is required if `signer.endpoint` is provided.
     #[error("The address is required if `signer.endpoint` is provided.")]
     AddressRequired,
@@ -123,15 +162,24 @@ impl SignerArgs {
                     .into();
                 Some(signer)
             }
-            (None, Some(signer)) => Some(signer.into()),
+            (None, Some(signer)) => Some(signer),
             (None, None) => None,
         };
 
         Ok(gossip_signer)
     }
 
-    /// Resolves the sequencer key from either the raw key or the key file.
+    /// Resolves the sequencer key from either the raw key, the key file or the keystore.
     fn resolve_sequencer_key(&self) -> Result<Option<B256>, SignerArgsParseError> {
+        if let Some(keystore) = &self.keystore {
+            if self.sequencer_key.is_some() || self.sequencer_key_path.is_some() {
+                return Err(SignerArgsParseError::ConflictingSequencerKeyInputs);
+            }
+            let password = self.keystore_password.as_deref().unwrap_or_default();
+            let signer = PrivateKeySigner::decrypt_keystore(keystore, password)?;
+            return Ok(Some(signer.to_bytes()));
+        }
+
         match (self.sequencer_key, &self.sequencer_key_path) {
             (Some(key), None) => Ok(Some(key)),
             (None, Some(path)) => {
@@ -151,8 +199,8 @@ impl SignerArgs {
         }
     }
 
-    /// Creates a [`RemoteSigner`] from the [`SignerArgs`].
-    fn config_remote(self) -> Result<Option<RemoteSigner>, SignerArgsParseError> {
+    /// Creates a remote [`BlockSigner`] from the [`SignerArgs`].
+    fn config_remote(self) -> Result<Option<BlockSigner>, SignerArgsParseError> {
         let Some(endpoint) = self.endpoint else {
             return Ok(None);
         };
@@ -181,12 +229,11 @@ impl SignerArgs {
             })
             .transpose()?;
 
-        Ok(Some(RemoteSigner {
-            address,
-            endpoint,
-            ca_cert: self.ca_cert.clone(),
-            client_cert,
-            headers,
+        let remote =
+            RemoteSigner { address, endpoint, ca_cert: self.ca_cert.clone(), client_cert, headers };
+        Ok(Some(match self.signer_type {
+            RemoteSignerType::OpSigner => remote.into(),
+            RemoteSignerType::Web3signer => Web3Signer(remote).into(),
         }))
     }
 }
@@ -216,6 +263,38 @@ mod tests {
         assert_eq!(resolved, Some(key));
     }
 
+    #[test]
+    fn test_resolve_sequencer_key_from_invalid_keystore() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{{}}").unwrap();
+
+        let signer_args = SignerArgs {
+            keystore: Some(temp_file.path().to_path_buf()),
+            keystore_password: Some("password".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            signer_args.resolve_sequencer_key(),
+            Err(SignerArgsParseError::KeystoreError(_))
+        ));
+    }
+
+    #[test]
+    fn test_web3signer_remote_signer() {
+        let signer_args = SignerArgs {
+            endpoint: Some(Url::parse("https://web3signer:9000").unwrap()),
+            address: Some(Address::repeat_byte(0x01)),
+            signer_type: RemoteSignerType::Web3signer,
+            ..Default::default()
+        };
+        let signer = signer_args.config_remote().unwrap().unwrap();
+        assert!(matches!(
+            signer,
+            BlockSigner::Web3Signer(Web3Signer(RemoteSigner { address, .. }))
+                if address == Address::repeat_byte(0x01)
+        ));
+    }
+
     #[test]
     fn test_resolve_sequencer_key_conflicting_inputs() {
         let signer_args = SignerArgs {
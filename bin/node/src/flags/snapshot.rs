@@ -0,0 +1,130 @@
+//! Node State Snapshot CLI Flags
+
+use crate::flags::parse_secs;
+use clap::Parser;
+use kona_node_service::{SnapshotConfig, SnapshotTarget};
+use std::{path::PathBuf, time::Duration};
+use url::Url;
+
+/// Node State Snapshot CLI Flags
+#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotArgs {
+    /// Path of the file to periodically write a JSON snapshot of the node state to. Providing
+    /// this value, or `snapshot.url`, enables the snapshot export.
+    #[arg(long = "snapshot.file", env = "KONA_NODE_SNAPSHOT_FILE", conflicts_with = "url")]
+    pub file: Option<PathBuf>,
+
+    /// HTTP endpoint to periodically `POST` a JSON snapshot of the node state to.
+    #[arg(long = "snapshot.url", env = "KONA_NODE_SNAPSHOT_URL")]
+    pub url: Option<Url>,
+
+    /// The interval at which node state snapshots are exported, in seconds.
+    #[arg(
+        long = "snapshot.interval",
+        default_value = "12",
+        env = "KONA_NODE_SNAPSHOT_INTERVAL",
+        value_parser = parse_secs
+    )]
+    pub interval: Duration,
+}
+
+impl Default for SnapshotArgs {
+    fn default() -> Self {
+        // Construct default values using the clap parser.
+        // This works since none of the cli flags are required.
+        Self::parse_from::<[_; 0], &str>([])
+    }
+}
+
+impl SnapshotArgs {
+    /// Creates a [`SnapshotConfig`] from the [`SnapshotArgs`].
+    ///
+    /// Returns [`None`] if no snapshot target is configured.
+    pub fn config(&self) -> anyhow::Result<Option<SnapshotConfig>> {
+        let target = match (&self.file, &self.url) {
+            (Some(path), None) => SnapshotTarget::File(path.clone()),
+            (None, Some(url)) => SnapshotTarget::Http(url.clone()),
+            (None, None) => return Ok(None),
+            (Some(_), Some(_)) => {
+                anyhow::bail!("`--snapshot.file` and `--snapshot.url` are mutually exclusive")
+            }
+        };
+        if self.interval.is_zero() {
+            anyhow::bail!("`--snapshot.interval` must be greater than zero");
+        }
+
+        Ok(Some(SnapshotConfig { target, interval: self.interval }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the snapshot args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Snapshot CLI Flags
+        #[clap(flatten)]
+        pub snapshot: SnapshotArgs,
+    }
+
+    #[test]
+    fn test_snapshot_disabled_by_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.snapshot.config().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_file_snapshot() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--snapshot.file",
+            "/var/lib/kona/snapshot.json",
+            "--snapshot.interval",
+            "30",
+        ]);
+        assert_eq!(
+            args.snapshot.config().unwrap(),
+            Some(SnapshotConfig {
+                target: SnapshotTarget::File(PathBuf::from("/var/lib/kona/snapshot.json")),
+                interval: Duration::from_secs(30),
+            })
+        );
+    }
+
+    #[test]
+    fn test_http_snapshot() {
+        let args =
+            MockCommand::parse_from(["test", "--snapshot.url", "http://collector:8080/nodes"]);
+        assert_eq!(
+            args.snapshot.config().unwrap(),
+            Some(SnapshotConfig {
+                target: SnapshotTarget::Http("http://collector:8080/nodes".parse().unwrap()),
+                interval: Duration::from_secs(12),
+            })
+        );
+    }
+
+    #[test]
+    fn test_snapshot_invalid_args() {
+        let result = MockCommand::try_parse_from([
+            "test",
+            "--snapshot.file",
+            "snapshot.json",
+            "--snapshot.url",
+            "http://collector:8080/nodes",
+        ]);
+        assert!(result.is_err());
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--snapshot.file",
+            "snapshot.json",
+            "--snapshot.interval",
+            "0",
+        ]);
+        assert!(args.snapshot.config().is_err());
+    }
+}
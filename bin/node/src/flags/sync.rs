@@ -0,0 +1,220 @@
+//! Sync CLI Flags
+//!
+//! By default, the node derives the L2 chain from the L2 genesis, or from the execution layer's
+//! finalized block. Checkpoint sync instead starts the node from a recent block of a trusted
+//! rollup node, and skips deriving the L2 chain's history. The auto mode selects between the two
+//! on startup, from the state of the execution layer.
+
+use clap::Parser;
+use kona_engine::ForkchoiceBatching;
+use kona_node_service::{AutoSyncConfig, CheckpointBlock, CheckpointConfig};
+use std::time::Duration;
+use url::Url;
+
+/// The strategy used to sync the L2 chain.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SyncMode {
+    /// Derives the L2 chain from the L1 chain.
+    #[default]
+    ConsensusLayer,
+    /// Starts from a block of a trusted rollup node, letting the execution layer sync the chain up
+    /// to it from its own peers.
+    Checkpoint,
+    /// Selects checkpoint sync if the execution layer has no finalized chain and is far enough
+    /// behind the trusted rollup node, and derives the L2 chain from L1 otherwise.
+    Auto,
+}
+
+/// Sync CLI Flags
+#[derive(Parser, Default, Clone, Debug, PartialEq, Eq)]
+pub struct SyncArgs {
+    /// The strategy used to sync the L2 chain: `consensus-layer`, `checkpoint` or `auto`.
+    ///
+    /// `checkpoint` requires an execution client that can sync from a forkchoice update. `auto`
+    /// only selects checkpoint sync if `--checkpoint.url` is set.
+    #[arg(long = "syncmode", default_value_t = SyncMode::ConsensusLayer, env = "KONA_NODE_SYNCMODE")]
+    pub mode: SyncMode,
+
+    /// The RPC url of the trusted rollup node to fetch the checkpoint from.
+    #[arg(
+        long = "checkpoint.url",
+        env = "KONA_NODE_CHECKPOINT_URL",
+        required_if_eq("mode", "checkpoint")
+    )]
+    pub url: Option<Url>,
+
+    /// The block of the trusted rollup node used as the checkpoint: `safe` or `finalized`.
+    #[arg(
+        long = "checkpoint.block",
+        default_value_t = CheckpointBlock::Finalized,
+        env = "KONA_NODE_CHECKPOINT_BLOCK"
+    )]
+    pub block: CheckpointBlock,
+
+    /// In `auto` mode, the minimum number of L2 blocks the execution layer must be behind the
+    /// checkpoint for checkpoint sync to be selected.
+    #[arg(
+        long = "syncmode.el-sync-distance",
+        default_value_t = 43_200,
+        env = "KONA_NODE_SYNCMODE_EL_SYNC_DISTANCE"
+    )]
+    pub el_sync_distance: u64,
+
+    /// The number of L2 blocks a gossiped unsafe block may be ahead of the safe head before the
+    /// node restarts EL sync towards it, instead of deriving the gap from L1. Disabled if unset.
+    #[arg(long = "syncmode.unsafe-gap-limit", env = "KONA_NODE_SYNCMODE_UNSAFE_GAP_LIMIT")]
+    pub unsafe_gap_limit: Option<u64>,
+
+    /// The maximum number of consecutive inserted or consolidated blocks the forkchoice labels
+    /// may move past with a single forkchoice update. Sends a forkchoice update per block if
+    /// unset.
+    ///
+    /// Batching speeds up long catch-ups, at the cost of the labels of the execution client
+    /// lagging behind the ones reported by the node while a batch is open. A batch is closed
+    /// at the last queued block, so that the labels are up to date once the node has caught
+    /// up.
+    #[arg(long = "syncmode.fcu-batch-blocks", env = "KONA_NODE_SYNCMODE_FCU_BATCH_BLOCKS")]
+    pub fcu_batch_blocks: Option<u64>,
+
+    /// The maximum time in milliseconds a forkchoice update may be deferred for, when
+    /// `--syncmode.fcu-batch-blocks` is set.
+    #[arg(
+        long = "syncmode.fcu-batch-interval",
+        default_value_t = 1000,
+        env = "KONA_NODE_SYNCMODE_FCU_BATCH_INTERVAL"
+    )]
+    pub fcu_batch_interval: u64,
+}
+
+impl SyncArgs {
+    /// Returns the [`CheckpointConfig`], if checkpoint sync is enabled.
+    pub fn checkpoint_config(&self) -> Option<CheckpointConfig> {
+        match self.mode {
+            SyncMode::ConsensusLayer => None,
+            SyncMode::Checkpoint | SyncMode::Auto => {
+                self.url.clone().map(|url| CheckpointConfig { url, block: self.block })
+            }
+        }
+    }
+
+    /// Returns the [`AutoSyncConfig`], if the sync strategy is selected automatically.
+    pub fn auto_sync_config(&self) -> Option<AutoSyncConfig> {
+        (self.mode == SyncMode::Auto)
+            .then_some(AutoSyncConfig { min_el_sync_distance: self.el_sync_distance })
+    }
+
+    /// Returns the [`ForkchoiceBatching`], if forkchoice updates are batched.
+    pub fn forkchoice_batching(&self) -> Option<ForkchoiceBatching> {
+        self.fcu_batch_blocks.filter(|max_blocks| *max_blocks > 1).map(|max_blocks| {
+            ForkchoiceBatching {
+                max_blocks,
+                max_interval: Duration::from_millis(self.fcu_batch_interval),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mock command that uses the sync args.
+    #[derive(Parser, Debug, Clone)]
+    #[command(about = "Mock command")]
+    struct MockCommand {
+        /// Sync flags.
+        #[clap(flatten)]
+        pub sync: SyncArgs,
+    }
+
+    #[test]
+    fn test_sync_args_default() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.sync.mode, SyncMode::ConsensusLayer);
+        assert_eq!(args.sync.checkpoint_config(), None);
+        assert_eq!(args.sync.auto_sync_config(), None);
+        assert_eq!(args.sync.unsafe_gap_limit, None);
+        assert_eq!(args.sync.forkchoice_batching(), None);
+    }
+
+    #[test]
+    fn test_forkchoice_batching() {
+        let args = MockCommand::parse_from(["test", "--syncmode.fcu-batch-blocks", "64"]);
+        assert_eq!(
+            args.sync.forkchoice_batching(),
+            Some(ForkchoiceBatching { max_blocks: 64, max_interval: Duration::from_secs(1) })
+        );
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--syncmode.fcu-batch-blocks",
+            "64",
+            "--syncmode.fcu-batch-interval",
+            "250",
+        ]);
+        assert_eq!(
+            args.sync.forkchoice_batching().map(|batching| batching.max_interval),
+            Some(Duration::from_millis(250))
+        );
+
+        let args = MockCommand::parse_from(["test", "--syncmode.fcu-batch-blocks", "1"]);
+        assert_eq!(args.sync.forkchoice_batching(), None);
+    }
+
+    #[test]
+    fn test_unsafe_gap_limit() {
+        let args = MockCommand::parse_from(["test", "--syncmode.unsafe-gap-limit", "3600"]);
+        assert_eq!(args.sync.unsafe_gap_limit, Some(3600));
+    }
+
+    #[test]
+    fn test_checkpoint_sync() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--syncmode",
+            "checkpoint",
+            "--checkpoint.url",
+            "http://localhost:9545",
+            "--checkpoint.block",
+            "safe",
+        ]);
+        assert_eq!(
+            args.sync.checkpoint_config(),
+            Some(CheckpointConfig {
+                url: Url::parse("http://localhost:9545").unwrap(),
+                block: CheckpointBlock::Safe,
+            })
+        );
+    }
+
+    #[test]
+    fn test_auto_sync() {
+        let args = MockCommand::parse_from(["test", "--syncmode", "auto"]);
+        assert_eq!(args.sync.checkpoint_config(), None);
+        assert_eq!(
+            args.sync.auto_sync_config(),
+            Some(AutoSyncConfig { min_el_sync_distance: 43_200 })
+        );
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--syncmode",
+            "auto",
+            "--checkpoint.url",
+            "http://localhost:9545",
+            "--syncmode.el-sync-distance",
+            "100",
+        ]);
+        assert!(args.sync.checkpoint_config().is_some());
+        assert_eq!(
+            args.sync.auto_sync_config(),
+            Some(AutoSyncConfig { min_el_sync_distance: 100 })
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_sync_requires_url() {
+        assert!(MockCommand::try_parse_from(["test", "--syncmode", "checkpoint"]).is_err());
+    }
+}
@@ -8,18 +8,27 @@
 
 pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod flags;
 pub mod metrics;
 
 pub(crate) mod version;
 
 fn main() {
-    use clap::Parser;
+    use clap::{CommandFactory, Parser};
 
     kona_cli::sigsegv_handler::install();
     kona_cli::backtrace::enable();
 
-    if let Err(err) = cli::Cli::parse().run() {
+    let args = match config::args_with_config(&cli::Cli::command(), std::env::args_os().collect()) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(err) = cli::Cli::parse_from(args).run() {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
@@ -1,8 +1,17 @@
 //! Version information for kona-node.
 
+use kona_rpc::BuildInfo;
+
 /// The latest version from Cargo.toml.
 pub(crate) const CARGO_PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The version from Cargo.toml, suffixed with `-dev` for untagged or dirty builds.
+pub(crate) const KONA_NODE_VERSION: &str =
+    concat!(env!("CARGO_PKG_VERSION"), env!("KONA_NODE_VERSION_SUFFIX"));
+
+/// The full SHA of the latest commit.
+pub(crate) const VERGEN_GIT_SHA_LONG: &str = env!("VERGEN_GIT_SHA");
+
 /// The 8 character short SHA of the latest commit.
 pub(crate) const VERGEN_GIT_SHA: &str = env!("VERGEN_GIT_SHA_SHORT");
 
@@ -33,3 +42,13 @@ pub(crate) const LONG_VERSION: &str = concat!(
 
 /// The build profile name.
 pub(crate) const BUILD_PROFILE_NAME: &str = env!("KONA_NODE_BUILD_PROFILE");
+
+/// Returns the [`BuildInfo`] of kona-node, served over RPC.
+pub(crate) fn build_info() -> BuildInfo {
+    BuildInfo::new(
+        KONA_NODE_VERSION,
+        VERGEN_GIT_SHA_LONG,
+        BUILD_PROFILE_NAME,
+        VERGEN_CARGO_FEATURES,
+    )
+}
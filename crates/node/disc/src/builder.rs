@@ -1,7 +1,7 @@
 //! Contains a builder for the discovery service.
 
 use discv5::{Config, Discv5, Enr, enr::k256};
-use kona_peers::{BootNodes, BootStoreFile, OpStackEnr};
+use kona_peers::{BootNodes, BootStoreFile, ENR_QUIC_KEY, ENR_QUIC6_KEY, OpStackEnr};
 use std::net::IpAddr;
 use tokio::time::Duration;
 
@@ -19,6 +19,8 @@ pub struct LocalNode {
     pub tcp_port: u16,
     /// Fallback UDP port.
     pub udp_port: u16,
+    /// The QUIC port to advertise, if the node accepts QUIC connections.
+    pub quic_port: Option<u16>,
 }
 
 impl From<&LocalNode> for discv5::ListenConfig {
@@ -38,7 +40,13 @@ impl LocalNode {
         tcp_port: u16,
         udp_port: u16,
     ) -> Self {
-        Self { signing_key, ip, tcp_port, udp_port }
+        Self { signing_key, ip, tcp_port, udp_port, quic_port: None }
+    }
+
+    /// Sets the QUIC port to advertise.
+    pub const fn with_quic_port(mut self, quic_port: Option<u16>) -> Self {
+        self.quic_port = quic_port;
+        self
     }
 }
 
@@ -47,7 +55,7 @@ impl LocalNode {
     /// broadcast to the other nodes in the network. See
     /// [the op-node implementation](https://github.com/ethereum-optimism/optimism/blob/174e55f0a1e73b49b80a561fd3fedd4fea5770c6/op-node/p2p/discovery.go#L61-L97)
     /// for the go equivalent
-    fn build_enr(self, chain_id: u64) -> Result<Enr, discv5::enr::Error> {
+    pub fn build_enr(self, chain_id: u64) -> Result<Enr, discv5::enr::Error> {
         let opstack = OpStackEnr::from_chain_id(chain_id);
         let mut opstack_data = Vec::new();
         use alloy_rlp::Encodable;
@@ -58,9 +66,15 @@ impl LocalNode {
         match self.ip {
             IpAddr::V4(addr) => {
                 enr_builder.ip4(addr).tcp4(self.tcp_port).udp4(self.udp_port);
+                if let Some(quic_port) = self.quic_port {
+                    enr_builder.add_value(ENR_QUIC_KEY, &quic_port);
+                }
             }
             IpAddr::V6(addr) => {
                 enr_builder.ip6(addr).tcp6(self.tcp_port).udp6(self.udp_port);
+                if let Some(quic_port) = self.quic_port {
+                    enr_builder.add_value(ENR_QUIC6_KEY, &quic_port);
+                }
             }
         }
 
@@ -213,4 +227,20 @@ mod tests {
         let enr = driver.disc.local_enr();
         assert!(EnrValidation::validate(&enr, 10).is_valid());
     }
+
+    #[test]
+    fn test_enr_advertises_quic_port() {
+        let CombinedKey::Secp256k1(k256_key) = CombinedKey::generate_secp256k1() else {
+            unreachable!()
+        };
+
+        let node = LocalNode::new(k256_key, IpAddr::V4(Ipv4Addr::LOCALHOST), 9099, 9099);
+        let enr = node.clone().build_enr(10).unwrap();
+        assert!(enr.get_decodable::<u16>(ENR_QUIC_KEY).is_none());
+
+        let enr = node.with_quic_port(Some(9100)).build_enr(10).unwrap();
+        assert_eq!(enr.get_decodable::<u16>(ENR_QUIC_KEY).unwrap().unwrap(), 9100);
+        let addr = kona_peers::enr_to_quic_multiaddr(&enr).unwrap();
+        assert!(addr.to_string().starts_with("/ip4/127.0.0.1/udp/9100/quic-v1/p2p/"));
+    }
 }
@@ -167,7 +167,7 @@ impl AttributesMatch {
     }
 
     /// Validates and compares EIP1559 parameters for consolidation.
-    fn check_eip1559(
+    pub(crate) fn check_eip1559(
         config: &RollupConfig,
         attributes: &OpAttributesWithParent,
         block: &Block<Transaction>,
@@ -414,6 +414,7 @@ mod tests {
             parent: L2BlockInfo::default(),
             derived_from: Some(BlockInfo::default()),
             is_last_in_span: true,
+            channel_id: None,
         }
     }
 
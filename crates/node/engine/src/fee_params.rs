@@ -0,0 +1,175 @@
+//! The fee parameters in effect at an L2 block.
+
+use alloy_consensus::Typed2718;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, U256};
+use kona_genesis::RollupConfig;
+use kona_protocol::{L1BlockInfoTx, OpBlockConversionError, to_system_config};
+use op_alloy_consensus::OpBlock;
+use serde::{Deserialize, Serialize};
+
+/// The fee parameters in effect at an L2 block, answering [`EngineQueries::FeeParams`].
+///
+/// The L1 fee parameters are read from the L1 info deposit of the block, and the base fee
+/// parameters from its system config, falling back to the chain's defaults before Holocene.
+/// On custom gas token chains, the fees are paid in the gas paying token.
+///
+/// [`EngineQueries::FeeParams`]: crate::EngineQueries::FeeParams
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeParams {
+    /// The L2 block.
+    pub l2_block: BlockNumHash,
+    /// The L1 origin of the L2 block, whose fees the L1 fee parameters are taken from.
+    pub l1_origin: BlockNumHash,
+    /// The base fee of the L2 block.
+    pub base_fee: Option<u64>,
+    /// The EIP-1559 base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// The EIP-1559 elasticity multiplier.
+    pub eip1559_elasticity: u64,
+    /// The minimum base fee, post-Jovian.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_base_fee: Option<u64>,
+    /// The base fee of the L1 origin.
+    pub l1_base_fee: U256,
+    /// The blob base fee of the L1 origin, post-Ecotone.
+    pub blob_base_fee: U256,
+    /// The L1 fee scalar, pre-Ecotone, or the L1 base fee scalar.
+    pub base_fee_scalar: U256,
+    /// The L1 blob base fee scalar, post-Ecotone.
+    pub blob_base_fee_scalar: U256,
+    /// The L1 fee overhead, pre-Ecotone.
+    pub l1_fee_overhead: U256,
+    /// The operator fee scalar, post-Isthmus.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_fee_scalar: Option<u32>,
+    /// The operator fee constant, post-Isthmus.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_fee_constant: Option<u64>,
+    /// The data availability footprint gas scalar, post-Jovian.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub da_footprint_gas_scalar: Option<u16>,
+    /// The L1 address of the token the fees are paid in, on custom gas token chains.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_paying_token: Option<Address>,
+}
+
+impl FeeParams {
+    /// Reads the fee parameters in effect at `block`.
+    ///
+    /// The L2 genesis block carries no L1 info deposit, and is rejected.
+    pub fn from_block(
+        block: &OpBlock,
+        rollup_config: &RollupConfig,
+    ) -> Result<Self, OpBlockConversionError> {
+        let system_config = to_system_config(block, rollup_config)?;
+
+        let Some(tx) = block.body.transactions.first() else {
+            return Err(OpBlockConversionError::EmptyTransactions(block.header.hash_slow()));
+        };
+        let Some(deposit) = tx.as_deposit() else {
+            return Err(OpBlockConversionError::InvalidTxType(tx.ty()));
+        };
+        let l1_info = L1BlockInfoTx::decode_calldata(deposit.input.as_ref())?;
+
+        // Before Holocene, and in its activation block, the chain's defaults apply.
+        let (eip1559_denominator, eip1559_elasticity) =
+            match (system_config.eip1559_denominator, system_config.eip1559_elasticity) {
+                (Some(denominator), Some(elasticity)) if denominator != 0 => {
+                    (denominator as u64, elasticity as u64)
+                }
+                _ => {
+                    let params = if rollup_config.is_canyon_active(block.header.timestamp) {
+                        rollup_config.chain_op_config.post_canyon_params()
+                    } else {
+                        rollup_config.chain_op_config.pre_canyon_params()
+                    };
+                    (params.max_change_denominator as u64, params.elasticity_multiplier as u64)
+                }
+            };
+
+        Ok(Self {
+            l2_block: BlockNumHash::new(block.header.number, block.header.hash_slow()),
+            l1_origin: l1_info.id(),
+            base_fee: block.header.base_fee_per_gas,
+            eip1559_denominator,
+            eip1559_elasticity,
+            min_base_fee: system_config.min_base_fee,
+            l1_base_fee: l1_info.l1_base_fee(),
+            blob_base_fee: l1_info.blob_base_fee(),
+            base_fee_scalar: l1_info.l1_fee_scalar(),
+            blob_base_fee_scalar: l1_info.blob_base_fee_scalar(),
+            l1_fee_overhead: l1_info.l1_fee_overhead(),
+            operator_fee_scalar: system_config.operator_fee_scalar,
+            operator_fee_constant: system_config.operator_fee_constant,
+            da_footprint_gas_scalar: system_config.da_footprint_gas_scalar,
+            gas_paying_token: rollup_config.custom_gas_token.map(|token| token.address),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody, Header};
+    use alloy_primitives::{B256, Sealed};
+    use kona_genesis::HardForkConfig;
+    use kona_protocol::L1BlockInfoEcotone;
+    use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
+
+    fn block(l1_info: L1BlockInfoTx) -> OpBlock {
+        OpBlock {
+            header: Header { number: 1, base_fee_per_gas: Some(250), ..Default::default() },
+            body: BlockBody {
+                transactions: vec![OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+                    input: l1_info.encode_calldata(),
+                    ..Default::default()
+                }))],
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_fee_params_from_ecotone_block() {
+        let l1_info = L1BlockInfoTx::Ecotone(L1BlockInfoEcotone {
+            number: 5,
+            block_hash: B256::with_last_byte(5),
+            base_fee: 7,
+            blob_base_fee: 3,
+            blob_base_fee_scalar: 2,
+            base_fee_scalar: 1,
+            ..Default::default()
+        });
+        let rollup_config = RollupConfig {
+            hardforks: HardForkConfig { canyon_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+
+        let params = FeeParams::from_block(&block(l1_info), &rollup_config).unwrap();
+        assert_eq!(params.l1_origin, BlockNumHash::new(5, B256::with_last_byte(5)));
+        assert_eq!(params.base_fee, Some(250));
+        assert_eq!(params.l1_base_fee, U256::from(7));
+        assert_eq!(params.blob_base_fee, U256::from(3));
+        assert_eq!(params.base_fee_scalar, U256::from(1));
+        assert_eq!(params.blob_base_fee_scalar, U256::from(2));
+        assert_eq!(
+            params.eip1559_denominator,
+            rollup_config.chain_op_config.eip1559_denominator_canyon
+        );
+        assert_eq!(params.eip1559_elasticity, rollup_config.chain_op_config.eip1559_elasticity);
+        assert_eq!(params.operator_fee_scalar, None);
+        assert_eq!(params.gas_paying_token, None);
+    }
+
+    #[test]
+    fn test_fee_params_without_l1_info() {
+        let mut block = block(L1BlockInfoTx::Ecotone(Default::default()));
+        block.body.transactions.clear();
+        assert!(matches!(
+            FeeParams::from_block(&block, &RollupConfig::default()),
+            Err(OpBlockConversionError::EmptyTransactions(_))
+        ));
+    }
+}
@@ -0,0 +1,794 @@
+//! The [`InProcessEngineClient`], an [`EngineClient`] executing blocks in-process.
+
+use super::InMemoryStateProvider;
+use crate::{EngineClient, EngineClientError, HyperAuthClient};
+use alloy_consensus::{
+    BlockBody, Header, Sealable, Sealed,
+    transaction::{Recovered, SignerRecoverable},
+};
+use alloy_eips::{
+    BlockId,
+    eip1898::BlockNumberOrTag,
+    eip2718::{Decodable2718, Encodable2718},
+    eip4895::Withdrawals,
+};
+use alloy_network::{Ethereum, Network};
+use alloy_op_evm::OpEvmFactory;
+use alloy_primitives::{Address, B64, B256, BlockHash, StorageKey, U256};
+use alloy_provider::{EthGetBlock, Provider, ProviderCall, RpcWithBlock};
+use alloy_rpc_types_engine::{
+    BlobsBundleV1, ClientVersionV1, ExecutionPayloadBodiesV1, ExecutionPayloadBodyV1,
+    ExecutionPayloadEnvelopeV2, ExecutionPayloadFieldV2, ExecutionPayloadInputV2,
+    ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated,
+    PayloadAttributes, PayloadId, PayloadStatus, PayloadStatusEnum,
+};
+use alloy_rpc_types_eth::{
+    Block, BlockTransactions, BlockTransactionsKind, EIP1186AccountProofResponse,
+    Header as RpcHeader,
+};
+use alloy_transport::{TransportError, TransportErrorKind, TransportResult};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use kona_executor::{ExecutorError, StatelessL2Builder, TrieDB};
+use kona_genesis::RollupConfig;
+use kona_mpt::NoopTrieHinter;
+use kona_protocol::{L2BlockInfo, payload_to_block};
+use op_alloy_consensus::{OpBlock, OpTxEnvelope};
+use op_alloy_network::Optimism;
+use op_alloy_provider::ext::engine::OpEngineApi;
+use op_alloy_rpc_types::Transaction;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayload, OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4,
+    OpExecutionPayloadV4, OpPayloadAttributes, ProtocolVersion,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use tokio::sync::RwLock;
+
+/// The Engine API methods supported by the [`InProcessEngineClient`].
+const CAPABILITIES: &[&str] = &[
+    "engine_forkchoiceUpdatedV2",
+    "engine_forkchoiceUpdatedV3",
+    "engine_newPayloadV2",
+    "engine_newPayloadV3",
+    "engine_newPayloadV4",
+    "engine_getPayloadV2",
+    "engine_getPayloadV3",
+    "engine_getPayloadV4",
+    "engine_getPayloadBodiesByHashV1",
+    "engine_getPayloadBodiesByRangeV1",
+];
+
+/// An [`EngineClient`] that embeds the execution layer in the rollup node process.
+///
+/// Blocks are executed with the [`StatelessL2Builder`] of the fault proof program, on top of the
+/// state held by an [`InMemoryStateProvider`]. Payloads built from `engine_forkchoiceUpdated`
+/// attributes and payloads imported through `engine_newPayload` are both executed, and imported
+/// payloads are rejected if the executed block does not match their block hash.
+///
+/// The client has no transaction pool and no peer-to-peer sync: blocks whose parent is unknown
+/// are answered with a `SYNCING` status. L1 blocks are fetched from the L1 provider.
+///
+/// Account proofs returned by [`EngineClient::get_proof`] only carry the account fields, without
+/// merkle proofs.
+#[derive(Debug, Clone)]
+pub struct InProcessEngineClient<L1Provider: Provider> {
+    /// The L1 chain provider for reading L1 data.
+    l1_provider: L1Provider,
+    /// The [`RollupConfig`].
+    cfg: Arc<RollupConfig>,
+    /// The in-process L2 chain.
+    chain: Arc<RwLock<InProcessChain>>,
+}
+
+impl<L1Provider: Provider> InProcessEngineClient<L1Provider> {
+    /// Creates a new [`InProcessEngineClient`] starting at the `anchor` block, whose state must be
+    /// held by the `state` provider.
+    pub fn new(
+        cfg: Arc<RollupConfig>,
+        l1_provider: L1Provider,
+        anchor: OpBlock,
+        state: InMemoryStateProvider,
+    ) -> Self {
+        let anchor_hash = anchor.header.hash_slow();
+        state.insert_header(anchor.header.clone());
+
+        let chain = InProcessChain {
+            cfg: cfg.clone(),
+            state,
+            canonical: BTreeMap::from([(anchor.header.number, anchor_hash)]),
+            blocks: HashMap::from([(anchor_hash, anchor)]),
+            forkchoice: ForkchoiceState {
+                head_block_hash: anchor_hash,
+                safe_block_hash: anchor_hash,
+                finalized_block_hash: anchor_hash,
+            },
+            payloads: HashMap::new(),
+            next_payload_id: 0,
+        };
+        Self { l1_provider, cfg, chain: Arc::new(RwLock::new(chain)) }
+    }
+
+    /// Returns the latest forkchoice state accepted by the client.
+    pub async fn forkchoice(&self) -> ForkchoiceState {
+        self.chain.read().await.forkchoice
+    }
+}
+
+#[async_trait]
+impl<L1Provider: Provider> EngineClient for InProcessEngineClient<L1Provider> {
+    fn cfg(&self) -> &RollupConfig {
+        self.cfg.as_ref()
+    }
+
+    fn get_l1_block(&self, block: BlockId) -> EthGetBlock<<Ethereum as Network>::BlockResponse> {
+        self.l1_provider.get_block(block)
+    }
+
+    fn get_l2_block(&self, block: BlockId) -> EthGetBlock<<Optimism as Network>::BlockResponse> {
+        let chain = Arc::clone(&self.chain);
+
+        EthGetBlock::new_provider(
+            block,
+            Box::new(move |kind| {
+                let chain = Arc::clone(&chain);
+                let full = matches!(kind, BlockTransactionsKind::Full);
+
+                ProviderCall::BoxedFuture(Box::pin(async move {
+                    Ok(chain.read().await.block(block).map(|block| rpc_block(block, full)))
+                }))
+            }),
+        )
+    }
+
+    fn get_proof(
+        &self,
+        address: Address,
+        _keys: Vec<StorageKey>,
+    ) -> RpcWithBlock<(Address, Vec<StorageKey>), EIP1186AccountProofResponse> {
+        let chain = Arc::clone(&self.chain);
+
+        RpcWithBlock::new_provider(move |block_id| {
+            let chain = Arc::clone(&chain);
+
+            ProviderCall::BoxedFuture(Box::pin(async move {
+                chain.read().await.account(address, block_id).map_err(custom_error)
+            }))
+        })
+    }
+
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> TransportResult<PayloadStatus> {
+        Ok(self.chain.write().await.new_payload(OpExecutionPayload::V1(payload), None))
+    }
+
+    async fn l2_block_by_label(
+        &self,
+        numtag: BlockNumberOrTag,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError> {
+        Ok(self.chain.read().await.block(numtag.into()).map(|block| rpc_block(block, true)))
+    }
+
+    async fn l2_block_info_by_label(
+        &self,
+        numtag: BlockNumberOrTag,
+    ) -> Result<Option<L2BlockInfo>, EngineClientError> {
+        let Some(block) = self.l2_block_by_label(numtag).await? else {
+            return Ok(None);
+        };
+        Ok(Some(L2BlockInfo::from_block_and_genesis(&block.into_consensus(), &self.cfg.genesis)?))
+    }
+}
+
+#[async_trait]
+impl<L1Provider: Provider> OpEngineApi<Optimism, Http<HyperAuthClient>>
+    for InProcessEngineClient<L1Provider>
+{
+    async fn new_payload_v2(
+        &self,
+        payload: ExecutionPayloadInputV2,
+    ) -> TransportResult<PayloadStatus> {
+        let payload = match payload.withdrawals {
+            Some(withdrawals) => OpExecutionPayload::V2(ExecutionPayloadV2 {
+                payload_inner: payload.execution_payload,
+                withdrawals,
+            }),
+            None => OpExecutionPayload::V1(payload.execution_payload),
+        };
+        Ok(self.chain.write().await.new_payload(payload, None))
+    }
+
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        Ok(self
+            .chain
+            .write()
+            .await
+            .new_payload(OpExecutionPayload::V3(payload), Some(parent_beacon_block_root)))
+    }
+
+    async fn new_payload_v4(
+        &self,
+        payload: OpExecutionPayloadV4,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        Ok(self
+            .chain
+            .write()
+            .await
+            .new_payload(OpExecutionPayload::V4(payload), Some(parent_beacon_block_root)))
+    }
+
+    async fn fork_choice_updated_v2(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        self.chain.write().await.forkchoice_updated(fork_choice_state, payload_attributes)
+    }
+
+    async fn fork_choice_updated_v3(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        self.chain.write().await.forkchoice_updated(fork_choice_state, payload_attributes)
+    }
+
+    async fn get_payload_v2(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<ExecutionPayloadEnvelopeV2> {
+        let execution_payload = match self.chain.read().await.payload(payload_id)?.0 {
+            OpExecutionPayload::V1(payload) => ExecutionPayloadFieldV2::V1(payload),
+            OpExecutionPayload::V2(payload) => ExecutionPayloadFieldV2::V2(payload),
+            _ => return Err(custom_error("Unsupported fork")),
+        };
+        Ok(ExecutionPayloadEnvelopeV2 { execution_payload, block_value: U256::ZERO })
+    }
+
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV3> {
+        let (OpExecutionPayload::V3(execution_payload), parent_beacon_block_root) =
+            self.chain.read().await.payload(payload_id)?
+        else {
+            return Err(custom_error("Unsupported fork"));
+        };
+        Ok(OpExecutionPayloadEnvelopeV3 {
+            execution_payload,
+            block_value: U256::ZERO,
+            blobs_bundle: empty_blobs_bundle(),
+            should_override_builder: false,
+            parent_beacon_block_root: parent_beacon_block_root.unwrap_or_default(),
+        })
+    }
+
+    async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV4> {
+        let (OpExecutionPayload::V4(execution_payload), parent_beacon_block_root) =
+            self.chain.read().await.payload(payload_id)?
+        else {
+            return Err(custom_error("Unsupported fork"));
+        };
+        Ok(OpExecutionPayloadEnvelopeV4 {
+            execution_payload,
+            block_value: U256::ZERO,
+            blobs_bundle: empty_blobs_bundle(),
+            should_override_builder: false,
+            parent_beacon_block_root: parent_beacon_block_root.unwrap_or_default(),
+            execution_requests: vec![],
+        })
+    }
+
+    async fn get_payload_bodies_by_hash_v1(
+        &self,
+        block_hashes: Vec<BlockHash>,
+    ) -> TransportResult<ExecutionPayloadBodiesV1> {
+        let chain = self.chain.read().await;
+        Ok(block_hashes
+            .into_iter()
+            .map(|hash| chain.block(hash.into()).map(payload_body))
+            .collect())
+    }
+
+    async fn get_payload_bodies_by_range_v1(
+        &self,
+        start: u64,
+        count: u64,
+    ) -> TransportResult<ExecutionPayloadBodiesV1> {
+        let chain = self.chain.read().await;
+        Ok((start..start.saturating_add(count))
+            .map(|number| chain.block(number.into()).map(payload_body))
+            .collect())
+    }
+
+    async fn get_client_version_v1(
+        &self,
+        _client_version: ClientVersionV1,
+    ) -> TransportResult<Vec<ClientVersionV1>> {
+        Ok(vec![])
+    }
+
+    async fn signal_superchain_v1(
+        &self,
+        recommended: ProtocolVersion,
+        _required: ProtocolVersion,
+    ) -> TransportResult<ProtocolVersion> {
+        Ok(recommended)
+    }
+
+    async fn exchange_capabilities(
+        &self,
+        _capabilities: Vec<String>,
+    ) -> TransportResult<Vec<String>> {
+        Ok(CAPABILITIES.iter().map(|capability| capability.to_string()).collect())
+    }
+}
+
+/// The L2 chain of the [`InProcessEngineClient`].
+#[derive(Debug)]
+struct InProcessChain {
+    /// The rollup config.
+    cfg: Arc<RollupConfig>,
+    /// The L2 state.
+    state: InMemoryStateProvider,
+    /// Every imported block, keyed by hash.
+    blocks: HashMap<B256, OpBlock>,
+    /// The hashes of the canonical chain, keyed by number.
+    canonical: BTreeMap<u64, B256>,
+    /// The latest accepted forkchoice state.
+    forkchoice: ForkchoiceState,
+    /// The built payloads, with their parent beacon block root.
+    payloads: HashMap<PayloadId, (OpExecutionPayload, Option<B256>)>,
+    /// The ID of the next built payload.
+    next_payload_id: u64,
+}
+
+impl InProcessChain {
+    /// Handles `engine_forkchoiceUpdated`, building a payload if attributes are given.
+    fn forkchoice_updated(
+        &mut self,
+        forkchoice: ForkchoiceState,
+        attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        // Without peer-to-peer sync, an unknown head can never be reached.
+        if !self.blocks.contains_key(&forkchoice.head_block_hash) {
+            return Ok(ForkchoiceUpdated { payload_status: syncing(), payload_id: None });
+        }
+
+        self.canonicalize(forkchoice.head_block_hash);
+        self.forkchoice = forkchoice;
+
+        let payload_id = attributes
+            .map(|attributes| self.build_payload(forkchoice.head_block_hash, attributes))
+            .transpose()?;
+        Ok(ForkchoiceUpdated { payload_status: valid(forkchoice.head_block_hash), payload_id })
+    }
+
+    /// Handles `engine_newPayload`, executing the payload if its parent is known.
+    fn new_payload(
+        &mut self,
+        payload: OpExecutionPayload,
+        parent_beacon_block_root: Option<B256>,
+    ) -> PayloadStatus {
+        let parent_hash = payload_v1(&payload).parent_hash;
+        let block_hash = payload_v1(&payload).block_hash;
+
+        let block = match payload_to_block(payload, parent_beacon_block_root, &self.cfg) {
+            Ok(block) => block,
+            Err(e) => return invalid(Some(parent_hash), e.to_string()),
+        };
+        if block.header.hash_slow() != block_hash {
+            return invalid(Some(parent_hash), "blockhash mismatch".to_string());
+        }
+        if self.blocks.contains_key(&block_hash) {
+            return valid(block_hash);
+        }
+        if !self.blocks.contains_key(&parent_hash) {
+            return syncing();
+        }
+
+        // Payloads built by the client have already been executed.
+        let built =
+            self.payloads.values().any(|(built, _)| payload_v1(built).block_hash == block_hash);
+        if !built {
+            let attributes = self.payload_attributes(&block);
+            match self.execute(parent_hash, attributes) {
+                Ok(header) if header.seal() == block_hash => {}
+                Ok(header) => {
+                    return invalid(
+                        Some(parent_hash),
+                        format!("executed block hash mismatch: {}", header.seal()),
+                    );
+                }
+                Err(e) => return invalid(Some(parent_hash), e.to_string()),
+            }
+        }
+
+        self.blocks.insert(block_hash, block);
+        valid(block_hash)
+    }
+
+    /// Returns the payload built under the given ID.
+    fn payload(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<(OpExecutionPayload, Option<B256>)> {
+        self.payloads.get(&payload_id).cloned().ok_or_else(|| custom_error("Unknown payload"))
+    }
+
+    /// Returns the block with the given ID.
+    fn block(&self, block: BlockId) -> Option<&OpBlock> {
+        let hash = match block {
+            BlockId::Hash(hash) => hash.block_hash,
+            BlockId::Number(number) => match number {
+                BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => {
+                    self.forkchoice.head_block_hash
+                }
+                BlockNumberOrTag::Safe => self.forkchoice.safe_block_hash,
+                BlockNumberOrTag::Finalized => self.forkchoice.finalized_block_hash,
+                BlockNumberOrTag::Earliest => *self.canonical.first_key_value()?.1,
+                BlockNumberOrTag::Number(number) => *self.canonical.get(&number)?,
+            },
+        };
+        self.blocks.get(&hash)
+    }
+
+    /// Returns the account at the given address, in the state of the given block.
+    fn account(
+        &self,
+        address: Address,
+        block: BlockId,
+    ) -> Result<EIP1186AccountProofResponse, String> {
+        let block = self.block(block).ok_or("Unknown block")?;
+        let number = block.header.number;
+        let mut trie_db =
+            TrieDB::new(block.header.clone().seal_slow(), self.state.clone(), NoopTrieHinter);
+        let account = trie_db
+            .get_trie_account(&address, number)
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+
+        Ok(EIP1186AccountProofResponse {
+            address,
+            balance: account.balance,
+            code_hash: account.code_hash,
+            nonce: account.nonce,
+            storage_hash: account.storage_root,
+            account_proof: vec![],
+            storage_proof: vec![],
+        })
+    }
+
+    /// Makes the chain ending at the given head canonical.
+    fn canonicalize(&mut self, head: B256) {
+        let number = self.blocks[&head].header.number;
+        self.canonical.split_off(&(number + 1));
+
+        let mut hash = head;
+        while let Some(block) = self.blocks.get(&hash) {
+            if self.canonical.insert(block.header.number, hash) == Some(hash) {
+                break;
+            }
+            hash = block.header.parent_hash;
+        }
+    }
+
+    /// Executes a block on top of the given parent, committing its state.
+    fn execute(
+        &self,
+        parent_hash: B256,
+        attributes: OpPayloadAttributes,
+    ) -> Result<Sealed<Header>, ExecutorError> {
+        let parent = Sealed::new_unchecked(self.blocks[&parent_hash].header.clone(), parent_hash);
+        let mut builder = StatelessL2Builder::new(
+            self.cfg.as_ref(),
+            OpEvmFactory::default(),
+            self.state.clone(),
+            NoopTrieHinter,
+            parent,
+        );
+
+        let outcome = builder.build_block(attributes)?;
+        self.state.commit(builder.trie_db(), &outcome);
+        Ok(outcome.header)
+    }
+
+    /// Builds a payload on top of the given parent, in the version active at its timestamp.
+    fn build_payload(
+        &mut self,
+        parent_hash: B256,
+        attributes: OpPayloadAttributes,
+    ) -> TransportResult<PayloadId> {
+        let parent_beacon_block_root = attributes.payload_attributes.parent_beacon_block_root;
+        let withdrawals = attributes.payload_attributes.withdrawals.clone();
+        let transactions = attributes
+            .transactions
+            .iter()
+            .flatten()
+            .map(|tx| OpTxEnvelope::decode_2718(&mut tx.as_ref()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| custom_error(e.to_string()))?;
+
+        let (header, block_hash) = self
+            .execute(parent_hash, attributes)
+            .map_err(|e| custom_error(e.to_string()))?
+            .into_parts();
+        let block = OpBlock {
+            header,
+            body: BlockBody {
+                transactions,
+                ommers: vec![],
+                withdrawals: withdrawals.map(Withdrawals::new),
+            },
+        };
+
+        self.next_payload_id += 1;
+        let payload_id = PayloadId::new(self.next_payload_id.to_be_bytes());
+        self.payloads.insert(
+            payload_id,
+            (self.execution_payload(block_hash, &block), parent_beacon_block_root),
+        );
+        Ok(payload_id)
+    }
+
+    /// Returns the execution payload of the block, in the version active at its timestamp.
+    fn execution_payload(&self, block_hash: B256, block: &OpBlock) -> OpExecutionPayload {
+        let timestamp = block.header.timestamp;
+        if self.cfg.is_isthmus_active(timestamp) {
+            OpExecutionPayload::V4(OpExecutionPayloadV4::from_v3_with_withdrawals_root(
+                ExecutionPayloadV3::from_block_unchecked(block_hash, block),
+                block.header.withdrawals_root.unwrap_or_default(),
+            ))
+        } else if self.cfg.is_ecotone_active(timestamp) {
+            OpExecutionPayload::V3(ExecutionPayloadV3::from_block_unchecked(block_hash, block))
+        } else if self.cfg.is_canyon_active(timestamp) {
+            OpExecutionPayload::V2(ExecutionPayloadV2::from_block_unchecked(block_hash, block))
+        } else {
+            OpExecutionPayload::V1(ExecutionPayloadV1::from_block_unchecked(block_hash, block))
+        }
+    }
+
+    /// Recovers the payload attributes a block was built from.
+    fn payload_attributes(&self, block: &OpBlock) -> OpPayloadAttributes {
+        let header = &block.header;
+
+        // Since Holocene, the EIP-1559 parameters are committed to in the header's extra data,
+        // followed by the minimum base fee since Jovian.
+        let eip_1559_params = self
+            .cfg
+            .is_holocene_active(header.timestamp)
+            .then(|| header.extra_data.get(1..9).map(B64::from_slice))
+            .flatten();
+        let min_base_fee = self
+            .cfg
+            .is_jovian_active(header.timestamp)
+            .then(|| header.extra_data.get(9..17))
+            .flatten()
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_be_bytes);
+
+        OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: header.timestamp,
+                prev_randao: header.mix_hash,
+                suggested_fee_recipient: header.beneficiary,
+                withdrawals: block.body.withdrawals.clone().map(Withdrawals::into_inner),
+                parent_beacon_block_root: header.parent_beacon_block_root,
+            },
+            transactions: Some(
+                block.body.transactions.iter().map(|tx| tx.encoded_2718().into()).collect(),
+            ),
+            no_tx_pool: Some(true),
+            gas_limit: Some(header.gas_limit),
+            eip_1559_params,
+            min_base_fee,
+        }
+    }
+}
+
+/// Returns the V1 fields of the payload.
+const fn payload_v1(payload: &OpExecutionPayload) -> &ExecutionPayloadV1 {
+    match payload {
+        OpExecutionPayload::V1(payload) => payload,
+        OpExecutionPayload::V2(payload) => &payload.payload_inner,
+        OpExecutionPayload::V3(payload) => &payload.payload_inner.payload_inner,
+        OpExecutionPayload::V4(payload) => &payload.payload_inner.payload_inner.payload_inner,
+    }
+}
+
+/// Returns the transactions and withdrawals of a block, as an `engine_getPayloadBodies*` entry.
+fn payload_body(block: &OpBlock) -> ExecutionPayloadBodyV1 {
+    ExecutionPayloadBodyV1 {
+        transactions: block.body.transactions.iter().map(|tx| tx.encoded_2718().into()).collect(),
+        withdrawals: block.body.withdrawals.clone().map(Withdrawals::into_inner),
+    }
+}
+
+/// Renders a block as an `eth_getBlockBy*` response.
+fn rpc_block(block: &OpBlock, full: bool) -> Block<Transaction> {
+    let hash = block.header.hash_slow();
+    let transactions = if full {
+        BlockTransactions::Full(
+            block
+                .body
+                .transactions
+                .iter()
+                .enumerate()
+                .map(|(index, tx)| Transaction {
+                    inner: alloy_rpc_types_eth::Transaction {
+                        inner: Recovered::new_unchecked(
+                            tx.clone(),
+                            tx.recover_signer().unwrap_or_default(),
+                        ),
+                        block_hash: Some(hash),
+                        block_number: Some(block.header.number),
+                        transaction_index: Some(index as u64),
+                        effective_gas_price: None,
+                    },
+                    deposit_nonce: None,
+                    deposit_receipt_version: None,
+                })
+                .collect(),
+        )
+    } else {
+        BlockTransactions::Hashes(block.body.transactions.iter().map(|tx| tx.tx_hash()).collect())
+    };
+
+    Block {
+        header: RpcHeader {
+            hash,
+            inner: block.header.clone(),
+            total_difficulty: Some(U256::ZERO),
+            size: None,
+        },
+        uncles: vec![],
+        transactions,
+        withdrawals: block.body.withdrawals.clone(),
+    }
+}
+
+/// Returns a `VALID` payload status.
+const fn valid(latest_valid_hash: B256) -> PayloadStatus {
+    PayloadStatus { status: PayloadStatusEnum::Valid, latest_valid_hash: Some(latest_valid_hash) }
+}
+
+/// Returns an `INVALID` payload status.
+const fn invalid(latest_valid_hash: Option<B256>, validation_error: String) -> PayloadStatus {
+    PayloadStatus { status: PayloadStatusEnum::Invalid { validation_error }, latest_valid_hash }
+}
+
+/// Returns a `SYNCING` payload status.
+const fn syncing() -> PayloadStatus {
+    PayloadStatus { status: PayloadStatusEnum::Syncing, latest_valid_hash: None }
+}
+
+/// Returns a transport error with the given message.
+fn custom_error(message: impl std::fmt::Display) -> TransportError {
+    TransportErrorKind::custom_str(&message.to_string())
+}
+
+/// Returns an empty blobs bundle. OP Stack payloads never carry blobs.
+const fn empty_blobs_bundle() -> BlobsBundleV1 {
+    BlobsBundleV1 { commitments: vec![], proofs: vec![], blobs: vec![] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::EMPTY_ROOT_HASH;
+    use alloy_provider::RootProvider;
+    use kona_protocol::compute_block_hash;
+
+    fn client() -> (InProcessEngineClient<RootProvider>, B256) {
+        let cfg = Arc::new(RollupConfig::default());
+        let anchor = OpBlock {
+            header: Header {
+                state_root: EMPTY_ROOT_HASH,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(1_000_000_000),
+                ..Default::default()
+            },
+            body: BlockBody::default(),
+        };
+        let anchor_hash = anchor.header.hash_slow();
+        let l1_provider = RootProvider::new_http("http://localhost:8545".parse().unwrap());
+        (
+            InProcessEngineClient::new(cfg, l1_provider, anchor, InMemoryStateProvider::new()),
+            anchor_hash,
+        )
+    }
+
+    fn attributes(timestamp: u64) -> OpPayloadAttributes {
+        OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: Some(vec![]),
+            no_tx_pool: Some(true),
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+            min_base_fee: None,
+        }
+    }
+
+    fn forkchoice(head: B256) -> ForkchoiceState {
+        ForkchoiceState { head_block_hash: head, safe_block_hash: head, finalized_block_hash: head }
+    }
+
+    #[tokio::test]
+    async fn test_build_and_import_payload() {
+        let (client, anchor_hash) = client();
+
+        let updated = client
+            .fork_choice_updated_v2(forkchoice(anchor_hash), Some(attributes(2)))
+            .await
+            .unwrap();
+        assert_eq!(updated.payload_status, valid(anchor_hash));
+
+        let envelope = client.get_payload_v2(updated.payload_id.unwrap()).await.unwrap();
+        let ExecutionPayloadFieldV2::V1(payload) = envelope.execution_payload else {
+            panic!("Expected a V1 payload before Canyon");
+        };
+        let block_hash = payload.block_hash;
+        assert_eq!(payload.parent_hash, anchor_hash);
+
+        let status = client.new_payload_v1(payload).await.unwrap();
+        assert_eq!(status, valid(block_hash));
+
+        client.fork_choice_updated_v2(forkchoice(block_hash), None).await.unwrap();
+        let head = client.l2_block_by_label(BlockNumberOrTag::Latest).await.unwrap().unwrap();
+        assert_eq!(head.header.hash, block_hash);
+        assert_eq!(head.header.number, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reexecutes_imported_payload() {
+        let (builder, anchor_hash) = client();
+        let updated = builder
+            .fork_choice_updated_v2(forkchoice(anchor_hash), Some(attributes(2)))
+            .await
+            .unwrap();
+        let ExecutionPayloadFieldV2::V1(payload) =
+            builder.get_payload_v2(updated.payload_id.unwrap()).await.unwrap().execution_payload
+        else {
+            panic!("Expected a V1 payload before Canyon");
+        };
+
+        // A second client has never built the payload, and must execute it to import it.
+        let (importer, _) = client();
+        let block_hash = payload.block_hash;
+        assert_eq!(importer.new_payload_v1(payload.clone()).await.unwrap(), valid(block_hash));
+
+        // A payload committing to a different state is rejected.
+        let mut tampered = payload;
+        tampered.gas_used += 1;
+        tampered.block_hash = compute_block_hash(
+            &OpExecutionPayload::V1(tampered.clone()),
+            None,
+            &RollupConfig::default(),
+        )
+        .unwrap();
+        let status = importer.new_payload_v1(tampered).await.unwrap();
+        assert!(matches!(status.status, PayloadStatusEnum::Invalid { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_parent_is_syncing() {
+        let (client, _) = client();
+        let updated = client.fork_choice_updated_v2(forkchoice(B256::ZERO), None).await.unwrap();
+        assert_eq!(updated.payload_status, syncing());
+    }
+}
@@ -0,0 +1,7 @@
+//! An in-process execution layer, executing blocks with the stateless L2 block builder.
+
+mod state;
+pub use state::{InMemoryStateError, InMemoryStateProvider};
+
+mod client;
+pub use client::InProcessEngineClient;
@@ -0,0 +1,182 @@
+//! The [`InMemoryStateProvider`], holding the L2 state of the [`InProcessEngineClient`].
+//!
+//! [`InProcessEngineClient`]: super::InProcessEngineClient
+
+use alloy_consensus::Header;
+use alloy_primitives::{B256, Bytes, KECCAK256_EMPTY, keccak256};
+use alloy_rlp::{Decodable, Encodable};
+use kona_executor::{BlockBuildingOutcome, TrieDB, TrieDBProvider};
+use kona_mpt::{TrieHinter, TrieNode, TrieProvider};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use thiserror::Error;
+
+/// An error returned by the [`InMemoryStateProvider`].
+#[derive(Error, Debug)]
+pub enum InMemoryStateError {
+    /// The preimage of a trie node is missing.
+    #[error("Missing trie node: {0}")]
+    MissingTrieNode(B256),
+    /// The bytecode of a contract is missing.
+    #[error("Missing bytecode: {0}")]
+    MissingBytecode(B256),
+    /// A block header is missing.
+    #[error("Missing header: {0}")]
+    MissingHeader(B256),
+    /// A trie node failed to decode.
+    #[error("Failed to decode trie node: {0}")]
+    Decode(#[from] alloy_rlp::Error),
+}
+
+/// A content-addressed store of the trie nodes, contract bytecode and block headers backing the
+/// [`TrieDB`] of the stateless L2 block builder.
+///
+/// The store must be seeded with the state of the block the [`InProcessEngineClient`] starts
+/// from, for example from an execution witness. The state of every block executed afterwards is
+/// committed to the store, so that its descendants can be executed.
+///
+/// [`InProcessEngineClient`]: super::InProcessEngineClient
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStateProvider {
+    inner: Arc<RwLock<InMemoryState>>,
+}
+
+/// The preimages held by the [`InMemoryStateProvider`], keyed by hash.
+#[derive(Debug, Default)]
+struct InMemoryState {
+    trie_nodes: HashMap<B256, Bytes>,
+    bytecodes: HashMap<B256, Bytes>,
+    headers: HashMap<B256, Header>,
+}
+
+impl InMemoryStateProvider {
+    /// Creates an empty [`InMemoryStateProvider`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the RLP encoding of a trie node.
+    pub fn insert_trie_node(&self, rlp: Bytes) {
+        self.write().trie_nodes.insert(keccak256(&rlp), rlp);
+    }
+
+    /// Inserts the bytecode of a contract.
+    pub fn insert_bytecode(&self, code: Bytes) {
+        self.write().bytecodes.insert(keccak256(&code), code);
+    }
+
+    /// Inserts a block header.
+    pub fn insert_header(&self, header: Header) {
+        self.write().headers.insert(header.hash_slow(), header);
+    }
+
+    /// Commits the state of a block built on top of `trie_db`'s parent block: the touched trie
+    /// nodes of the state and storage tries, the created contracts and the block header.
+    pub(crate) fn commit<H: TrieHinter>(
+        &self,
+        trie_db: &TrieDB<Self, H>,
+        outcome: &BlockBuildingOutcome,
+    ) {
+        let mut state = self.write();
+        collect_trie_nodes(trie_db.root(), &mut state.trie_nodes);
+        for storage_root in trie_db.storage_roots().values() {
+            collect_trie_nodes(storage_root, &mut state.trie_nodes);
+        }
+        for code in &outcome.bytecodes {
+            state.bytecodes.insert(keccak256(code), code.clone());
+        }
+        state.headers.insert(outcome.header.seal(), outcome.header.inner().clone());
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, InMemoryState> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, InMemoryState> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl TrieProvider for InMemoryStateProvider {
+    type Error = InMemoryStateError;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        let state = self.read();
+        let rlp = state.trie_nodes.get(&key).ok_or(InMemoryStateError::MissingTrieNode(key))?;
+        Ok(TrieNode::decode(&mut rlp.as_ref())?)
+    }
+}
+
+impl TrieDBProvider for InMemoryStateProvider {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        if code_hash == KECCAK256_EMPTY {
+            return Ok(Bytes::new());
+        }
+        self.read()
+            .bytecodes
+            .get(&code_hash)
+            .cloned()
+            .ok_or(InMemoryStateError::MissingBytecode(code_hash))
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        self.read().headers.get(&hash).cloned().ok_or(InMemoryStateError::MissingHeader(hash))
+    }
+}
+
+/// Inserts the RLP encoding of every unblinded node of the trie into `nodes`, keyed by hash.
+fn collect_trie_nodes(node: &TrieNode, nodes: &mut HashMap<B256, Bytes>) {
+    match node {
+        TrieNode::Empty | TrieNode::Blinded { .. } => return,
+        TrieNode::Leaf { .. } => {}
+        TrieNode::Extension { node, .. } => collect_trie_nodes(node, nodes),
+        TrieNode::Branch { stack } => {
+            stack.iter().for_each(|child| collect_trie_nodes(child, nodes));
+        }
+    }
+
+    let mut rlp = Vec::with_capacity(node.length());
+    node.encode(&mut rlp);
+    nodes.insert(keccak256(&rlp), rlp.into());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_mpt::{Nibbles, NoopTrieProvider};
+
+    #[test]
+    fn test_committed_trie_nodes_reopen() {
+        let mut trie = TrieNode::Empty;
+        for i in 0u8..32 {
+            let path = Nibbles::unpack(keccak256([i]));
+            trie.insert(&path, Bytes::from(vec![i; 40]), &NoopTrieProvider).unwrap();
+        }
+        let root = trie.blind();
+
+        let mut nodes = HashMap::new();
+        collect_trie_nodes(&trie, &mut nodes);
+        let provider = InMemoryStateProvider::new();
+        nodes.into_values().for_each(|rlp| provider.insert_trie_node(rlp));
+
+        let mut reopened = TrieNode::new_blinded(root);
+        let value = reopened.open(&Nibbles::unpack(keccak256([7u8])), &provider).unwrap();
+        assert_eq!(value.cloned(), Some(Bytes::from(vec![7u8; 40])));
+    }
+
+    #[test]
+    fn test_missing_preimages() {
+        let provider = InMemoryStateProvider::new();
+        assert!(matches!(
+            provider.trie_node_by_hash(B256::ZERO),
+            Err(InMemoryStateError::MissingTrieNode(_))
+        ));
+        assert!(provider.bytecode_by_hash(KECCAK256_EMPTY).unwrap().is_empty());
+
+        let header = Header { number: 1, ..Default::default() };
+        provider.insert_header(header.clone());
+        assert_eq!(provider.header_by_hash(header.hash_slow()).unwrap(), header);
+    }
+}
@@ -40,10 +40,12 @@ extern crate tracing;
 
 mod task_queue;
 pub use task_queue::{
-    BuildTask, BuildTaskError, ConsolidateTask, ConsolidateTaskError, Engine, EngineBuildError,
+    BuildTask, BuildTaskError, ConsolidateTask, ConsolidateTaskError, ConsolidationMismatch,
+    DepositOnlyBlock, Engine, EngineApiError, EngineApiErrorKind, EngineBuildError,
     EngineResetError, EngineTask, EngineTaskError, EngineTaskErrorSeverity, EngineTaskErrors,
-    EngineTaskExt, FinalizeTask, FinalizeTaskError, InsertTask, InsertTaskError, SealTask,
-    SealTaskError, SynchronizeTask, SynchronizeTaskError,
+    EngineTaskExt, FieldDiff, FinalizeTask, FinalizeTaskError, ForkchoiceBatching, InsertTask,
+    InsertTaskError, ResetAnchorsError, SealTask, SealTaskError, SynchronizeTask,
+    SynchronizeTaskError,
 };
 
 mod attributes;
@@ -70,6 +72,9 @@ pub use state::{EngineState, EngineSyncState, EngineSyncStateUpdate};
 mod kinds;
 pub use kinds::EngineKind;
 
+mod fee_params;
+pub use fee_params::FeeParams;
+
 mod query;
 pub use query::{EngineQueries, EngineQueriesError, EngineQuerySender};
 
@@ -77,7 +82,12 @@ mod metrics;
 pub use metrics::Metrics;
 
 mod sync;
-pub use sync::{L2ForkchoiceState, SyncStartError, find_starting_forkchoice};
+pub use sync::{L2ForkchoiceState, SyncStartError, find_starting_forkchoice, retention_horizon};
+
+#[cfg(feature = "in-process")]
+mod in_process;
+#[cfg(feature = "in-process")]
+pub use in_process::{InMemoryStateError, InMemoryStateProvider, InProcessEngineClient};
 
 #[cfg(any(test, feature = "test-utils"))]
 /// Utilities that are useful when creating unit tests using structs within this library.
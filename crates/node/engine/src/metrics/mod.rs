@@ -37,6 +37,8 @@ impl Metrics {
     pub const UNSAFE_BLOCK_LABEL: &str = "unsafe";
     /// Cross-unsafe block label.
     pub const CROSS_UNSAFE_BLOCK_LABEL: &str = "cross-unsafe";
+    /// Pending-safe block label.
+    pub const PENDING_SAFE_BLOCK_LABEL: &str = "pending-safe";
     /// Local-safe block label.
     pub const LOCAL_SAFE_BLOCK_LABEL: &str = "local-safe";
     /// Safe block label.
@@ -49,6 +51,12 @@ impl Metrics {
     /// Identifier for the counter that records engine task counts.
     pub const ENGINE_TASK_FAILURE: &str = "kona_node_engine_task_failure";
 
+    /// Identifier for the counter that records failed Engine API calls, labelled by task and by
+    /// [`EngineApiErrorKind`].
+    ///
+    /// [`EngineApiErrorKind`]: crate::EngineApiErrorKind
+    pub const ENGINE_API_ERRORS: &str = "kona_node_engine_api_errors";
+
     /// Insert task label.
     pub const INSERT_TASK_LABEL: &str = "insert";
     /// Consolidate task label.
@@ -74,6 +82,17 @@ impl Metrics {
     /// Identifier for the counter that tracks the number of times the engine has been reset.
     pub const ENGINE_RESET_COUNT: &str = "kona_node_engine_reset_count";
 
+    /// Identifier for the counter that tracks invalid payloads replaced by deposits-only blocks.
+    pub const DEPOSIT_ONLY_BLOCK_COUNT: &str = "kona_node_engine_deposit_only_blocks";
+    /// Identifier for the gauge that tracks the number of transactions dropped by the latest
+    /// deposits-only replacement.
+    pub const DEPOSIT_ONLY_DROPPED_TRANSACTIONS: &str =
+        "kona_node_engine_deposit_only_dropped_transactions";
+
+    /// Identifier for the counter that tracks derived attributes that did not match the unsafe
+    /// block they were consolidated against.
+    pub const CONSOLIDATION_MISMATCH_COUNT: &str = "kona_node_engine_consolidation_mismatches";
+
     /// Initializes metrics for the engine.
     ///
     /// This does two things:
@@ -94,6 +113,11 @@ impl Metrics {
         // Engine task counts
         metrics::describe_counter!(Self::ENGINE_TASK_SUCCESS, "Engine tasks successfully executed");
         metrics::describe_counter!(Self::ENGINE_TASK_FAILURE, "Engine tasks failed");
+        metrics::describe_counter!(
+            Self::ENGINE_API_ERRORS,
+            metrics::Unit::Count,
+            "Failed Engine API calls by task and error kind"
+        );
 
         // Engine method request duration histogram
         metrics::describe_histogram!(
@@ -108,6 +132,24 @@ impl Metrics {
             metrics::Unit::Count,
             "Engine reset count"
         );
+
+        // Deposits-only replacements
+        metrics::describe_counter!(
+            Self::DEPOSIT_ONLY_BLOCK_COUNT,
+            metrics::Unit::Count,
+            "Invalid payloads replaced by deposits-only blocks"
+        );
+        metrics::describe_gauge!(
+            Self::DEPOSIT_ONLY_DROPPED_TRANSACTIONS,
+            "Transactions dropped by the latest deposits-only replacement"
+        );
+
+        // Consolidation mismatches
+        metrics::describe_counter!(
+            Self::CONSOLIDATION_MISMATCH_COUNT,
+            metrics::Unit::Count,
+            "Derived attributes that did not match the unsafe block they were consolidated against"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -127,5 +169,11 @@ impl Metrics {
 
         // Engine reset count
         kona_macros::set!(counter, Self::ENGINE_RESET_COUNT, 0);
+
+        // Deposits-only replacements
+        kona_macros::set!(counter, Self::DEPOSIT_ONLY_BLOCK_COUNT, 0);
+
+        // Consolidation mismatches
+        kona_macros::set!(counter, Self::CONSOLIDATION_MISMATCH_COUNT, 0);
     }
 }
@@ -4,15 +4,18 @@
 //! from external actors. Uses oneshot channels for responses to maintain
 //! clean async communication patterns.
 
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
 use alloy_eips::BlockNumberOrTag;
 use alloy_transport::{RpcError, TransportErrorKind};
 use kona_genesis::RollupConfig;
-use kona_protocol::{L2BlockInfo, OutputRoot, Predeploys};
+use kona_protocol::{L2BlockInfo, OpBlockConversionError, OutputRoot, Predeploys};
 use tokio::sync::oneshot::Sender;
 
-use crate::{EngineClient, EngineClientError, EngineState};
+use crate::{
+    ConsolidationMismatch, DepositOnlyBlock, EngineClient, EngineClientError, EngineState,
+    FeeParams,
+};
 
 /// Channel sender for submitting [`EngineQueries`] to the engine.
 pub type EngineQuerySender = tokio::sync::mpsc::Sender<EngineQueries>;
@@ -43,6 +46,18 @@ pub enum EngineQueries {
     QueueLengthReceiver(Sender<tokio::sync::watch::Receiver<usize>>),
     /// Development API: Get the current number of pending tasks in the queue.
     TaskQueueLength(Sender<usize>),
+    /// Request the recent invalid payloads replaced by deposits-only blocks, oldest first.
+    DepositOnlyBlocks(Sender<Vec<DepositOnlyBlock>>),
+    /// Request the [`FeeParams`] in effect at a specific block.
+    FeeParams {
+        /// The block number or tag to retrieve the fee parameters for.
+        block: BlockNumberOrTag,
+        /// Response channel for the fee parameters.
+        sender: Sender<FeeParams>,
+    },
+    /// Request the last mismatch between derived attributes and the unsafe block they were
+    /// consolidated against, if any.
+    LastConsolidationMismatch(Sender<Option<ConsolidationMismatch>>),
 }
 
 /// An error that can occur when querying the engine.
@@ -63,6 +78,9 @@ pub enum EngineQueriesError {
     /// Impossible to retrieve L2 withdrawals root from state.
     #[error("Impossible to retrieve L2 withdrawals root from state. {0}")]
     FailedToRetrieveWithdrawalsRoot(#[from] RpcError<TransportErrorKind>),
+    /// The L2 block does not carry valid fee parameters.
+    #[error("Invalid L2 block: {0}")]
+    InvalidBlock(#[from] OpBlockConversionError),
 }
 
 impl EngineQueries {
@@ -71,6 +89,8 @@ impl EngineQueries {
         self,
         state_recv: &tokio::sync::watch::Receiver<EngineState>,
         queue_length_recv: &tokio::sync::watch::Receiver<usize>,
+        deposit_only_blocks_recv: &tokio::sync::watch::Receiver<VecDeque<DepositOnlyBlock>>,
+        consolidation_mismatch_recv: &tokio::sync::watch::Receiver<Option<ConsolidationMismatch>>,
         client: &Arc<EngineClient_>,
         rollup_config: &Arc<RollupConfig>,
     ) -> Result<(), EngineQueriesError> {
@@ -138,6 +158,23 @@ impl EngineQueries {
                 }
                 Ok(())
             }
+            Self::DepositOnlyBlocks(sender) => {
+                let history = deposit_only_blocks_recv.borrow().iter().cloned().collect();
+                sender.send(history).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
+            Self::FeeParams { block, sender } => {
+                let l2_block = client.l2_block_by_label(block).await?;
+                let l2_block = l2_block.ok_or(EngineQueriesError::NoL2BlockFound(block))?;
+                let consensus_block =
+                    l2_block.into_consensus().map_transactions(|tx| tx.inner.inner.into_inner());
+
+                let fee_params = FeeParams::from_block(&consensus_block, rollup_config)?;
+                sender.send(fee_params).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
+            Self::LastConsolidationMismatch(sender) => {
+                let mismatch = consolidation_mismatch_recv.borrow().clone();
+                sender.send(mismatch).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
         }
     }
 }
@@ -17,9 +17,10 @@ use serde::{Deserialize, Serialize};
 ///
 /// 1. **Unsafe** - Most recent blocks from P2P network (unverified)
 /// 2. **Cross-unsafe** - Unsafe blocks with cross-layer verification
-/// 3. **Local-safe** - Derived from L1 data, completed span-batch
-/// 4. **Safe** - Cross-verified with safe L1 dependencies
-/// 5. **Finalized** - Derived from finalized L1 data only
+/// 3. **Pending-safe** - Derived from L1 data, possibly part of an incomplete span-batch
+/// 4. **Local-safe** - Derived from L1 data, completed span-batch
+/// 5. **Safe** - Cross-verified with safe L1 dependencies
+/// 6. **Finalized** - Derived from finalized L1 data only
 ///
 /// See the [OP Stack specifications](https://specs.optimism.io) for detailed safety definitions.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
@@ -28,6 +29,8 @@ pub struct EngineSyncState {
     unsafe_head: L2BlockInfo,
     /// Cross-verified unsafe head (equal to unsafe_head pre-interop).
     cross_unsafe_head: L2BlockInfo,
+    /// Derived from L1 data, but possibly part of a span-batch that is not yet fully validated.
+    pending_safe_head: L2BlockInfo,
     /// Derived from L1 data as a completed span-batch, but not yet cross-verified.
     local_safe_head: L2BlockInfo,
     /// Derived from L1 data and cross-verified to have safe L1 dependencies.
@@ -47,6 +50,11 @@ impl EngineSyncState {
         self.cross_unsafe_head
     }
 
+    /// Returns the current pending safe head.
+    pub const fn pending_safe_head(&self) -> L2BlockInfo {
+        self.pending_safe_head
+    }
+
     /// Returns the current local safe head.
     pub const fn local_safe_head(&self) -> L2BlockInfo {
         self.local_safe_head
@@ -92,6 +100,12 @@ impl EngineSyncState {
                 cross_unsafe_head.block_info.number,
             );
         }
+        if let Some(pending_safe_head) = sync_state_update.pending_safe_head {
+            Self::update_block_label_metric(
+                Metrics::PENDING_SAFE_BLOCK_LABEL,
+                pending_safe_head.block_info.number,
+            );
+        }
         if let Some(local_safe_head) = sync_state_update.local_safe_head {
             Self::update_block_label_metric(
                 Metrics::LOCAL_SAFE_BLOCK_LABEL,
@@ -113,6 +127,9 @@ impl EngineSyncState {
             cross_unsafe_head: sync_state_update
                 .cross_unsafe_head
                 .unwrap_or(self.cross_unsafe_head),
+            pending_safe_head: sync_state_update
+                .pending_safe_head
+                .unwrap_or(self.pending_safe_head),
             local_safe_head: sync_state_update.local_safe_head.unwrap_or(self.local_safe_head),
             safe_head: sync_state_update.safe_head.unwrap_or(self.safe_head),
             finalized_head: sync_state_update.finalized_head.unwrap_or(self.finalized_head),
@@ -133,6 +150,8 @@ pub struct EngineSyncStateUpdate {
     pub unsafe_head: Option<L2BlockInfo>,
     /// Cross-verified unsafe head, always equal to the unsafe head pre-interop
     pub cross_unsafe_head: Option<L2BlockInfo>,
+    /// Derived from L1, but possibly part of a span-batch that is not yet fully validated.
+    pub pending_safe_head: Option<L2BlockInfo>,
     /// Derived from L1, and known to be a completed span-batch,
     /// but not cross-verified yet.
     pub local_safe_head: Option<L2BlockInfo>,
@@ -164,12 +183,12 @@ impl EngineState {
     /// Returns if consolidation is needed.
     ///
     /// [Consolidation] is only performed by a rollup node when the unsafe head
-    /// is ahead of the safe head. When the two are equal, consolidation isn't
+    /// is ahead of the pending safe head. When the two are equal, consolidation isn't
     /// required and the [`crate::BuildTask`] can be used to build the block.
     ///
     /// [Consolidation]: https://specs.optimism.io/protocol/derivation.html#l1-consolidation-payload-attributes-matching
     pub fn needs_consolidation(&self) -> bool {
-        self.sync_state.safe_head() != self.sync_state.unsafe_head()
+        self.sync_state.pending_safe_head() != self.sync_state.unsafe_head()
     }
 }
 
@@ -198,6 +217,14 @@ mod test {
             });
         }
 
+        /// Set the pending safe head.
+        pub fn set_pending_safe_head(&mut self, pending_safe_head: L2BlockInfo) {
+            self.sync_state.apply_update(EngineSyncStateUpdate {
+                pending_safe_head: Some(pending_safe_head),
+                ..Default::default()
+            });
+        }
+
         /// Set the local safe head.
         pub fn set_local_safe_head(&mut self, local_safe_head: L2BlockInfo) {
             self.sync_state.apply_update(EngineSyncStateUpdate {
@@ -223,6 +250,33 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_pending_safe_head_tracked_separately() {
+        let block = |number| L2BlockInfo {
+            block_info: BlockInfo { number, ..Default::default() },
+            ..Default::default()
+        };
+
+        let mut state = EngineState::default();
+        state.sync_state = state.sync_state.apply_update(EngineSyncStateUpdate {
+            unsafe_head: Some(block(2)),
+            pending_safe_head: Some(block(2)),
+            safe_head: Some(block(1)),
+            ..Default::default()
+        });
+
+        assert_eq!(state.sync_state.pending_safe_head(), block(2));
+        assert_eq!(state.sync_state.safe_head(), block(1));
+        assert_eq!(state.sync_state.create_forkchoice_state().safe_block_hash, block(1).hash());
+        assert!(!state.needs_consolidation());
+
+        state.sync_state = state.sync_state.apply_update(EngineSyncStateUpdate {
+            unsafe_head: Some(block(3)),
+            ..Default::default()
+        });
+        assert!(state.needs_consolidation());
+    }
+
     #[rstest]
     #[case::set_unsafe(EngineState::set_unsafe_head, Metrics::UNSAFE_BLOCK_LABEL, 1)]
     #[case::set_cross_unsafe(
@@ -230,6 +284,11 @@ mod test {
         Metrics::CROSS_UNSAFE_BLOCK_LABEL,
         2
     )]
+    #[case::set_pending_safe(
+        EngineState::set_pending_safe_head,
+        Metrics::PENDING_SAFE_BLOCK_LABEL,
+        6
+    )]
     #[case::set_local_safe(EngineState::set_local_safe_head, Metrics::LOCAL_SAFE_BLOCK_LABEL, 3)]
     #[case::set_safe_head(EngineState::set_safe_head, Metrics::SAFE_BLOCK_LABEL, 4)]
     #[case::set_finalized_head(EngineState::set_finalized_head, Metrics::FINALIZED_BLOCK_LABEL, 5)]
@@ -39,9 +39,10 @@ impl Display for L2ForkchoiceState {
 impl L2ForkchoiceState {
     /// Fetches the current forkchoice state of the L2 execution layer.
     ///
-    /// - The finalized block may not always be available. If it is not, we fall back to genesis.
-    /// - The safe block may not always be available. If it is not, we fall back to the finalized
-    ///   block.
+    /// - The finalized block may not always be available. If it is not, or if it is below the
+    ///   genesis block, we fall back to genesis.
+    /// - The safe block may not always be available. If it is not, or if it is below the genesis
+    ///   block, we fall back to the finalized block.
     /// - The unsafe block is always assumed to be available.
     pub async fn current<EngineClient_: EngineClient>(
         cfg: &RollupConfig,
@@ -50,8 +51,10 @@ impl L2ForkchoiceState {
         let finalized = {
             let rpc_block =
                 match get_block_compat(engine_client, BlockNumberOrTag::Finalized.into()).await {
-                    Ok(Some(block)) => block,
-                    Ok(None) => engine_client
+                    // A finalized block below the genesis block (e.g. when the genesis has been
+                    // moved forward for a shadow fork) is treated as if it were unavailable.
+                    Ok(Some(block)) if block.header.number >= cfg.genesis.l2.number => block,
+                    Ok(_) => engine_client
                         .get_l2_block(cfg.genesis.l2.number.into())
                         .full()
                         .await?
@@ -63,10 +66,10 @@ impl L2ForkchoiceState {
             L2BlockInfo::from_block_and_genesis(&rpc_block, &cfg.genesis)?
         };
         let safe = match get_block_compat(engine_client, BlockNumberOrTag::Safe.into()).await {
-            Ok(Some(block)) => {
+            Ok(Some(block)) if block.header.number >= cfg.genesis.l2.number => {
                 L2BlockInfo::from_block_and_genesis(&block.into_consensus(), &cfg.genesis)?
             }
-            Ok(None) => finalized,
+            Ok(_) => finalized,
             Err(e) => return Err(e.into()),
         };
         let un_safe = {
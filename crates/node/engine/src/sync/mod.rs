@@ -111,10 +111,39 @@ pub async fn find_starting_forkchoice<EngineClient_: EngineClient>(
         }
     }
 
-    // Leave the finalized block as-is, and return the current forkchoice.
+    // The unsafe head may have been rewound below the EL's safe or finalized blocks, e.g. when the
+    // rollup config genesis was moved onto a diverging L1 chain (shadow forks). The forkchoice
+    // must never point at a finalized block ahead of the head, so clamp it to the new safe head.
+    if current_fc.finalized.block_info.number > current_fc.safe.block_info.number {
+        info!(
+            target: "sync_start",
+            l2_finalized = %current_fc.finalized.block_info.number,
+            l2_safe = %current_fc.safe.block_info.number,
+            "Finalized block is ahead of the safe block, clamping to the safe block"
+        );
+        current_fc.finalized = current_fc.safe;
+    }
+
     Ok(current_fc)
 }
 
+/// The assumed L1 block time, in seconds, used to bound the L2 blocks spanned by a channel.
+const L1_BLOCK_TIME: u64 = 12;
+
+/// Returns the number of the lowest L2 block the node may still need to derive from after a reset.
+///
+/// [`find_starting_forkchoice`] never rewinds the safe head below the finalized L2 block, and the
+/// derivation pipeline restarts from the safe head's L1 origin rewound by the channel timeout.
+/// Batches read from that L1 range can still reference L2 blocks built from it, so the horizon
+/// trails the finalized L2 block by the number of L2 blocks produced during one channel timeout.
+///
+/// An execution layer can safely prune the L2 chain state below the horizon.
+pub fn retention_horizon(cfg: &RollupConfig, finalized: &L2BlockInfo) -> u64 {
+    let channel_blocks =
+        cfg.channel_timeout(finalized.block_info.timestamp) * L1_BLOCK_TIME / cfg.block_time.max(1);
+    finalized.block_info.number.saturating_sub(channel_blocks).max(cfg.genesis.l2.number)
+}
+
 #[cfg(test)]
 mod test {
     use alloy_provider::Network;
@@ -147,4 +176,27 @@ mod test {
             L2BlockInfo::from_block_and_genesis(&consensus_block, &rollup_config.genesis).unwrap();
         assert_eq!(rpc_reported_hash, l2_block_info.block_info.hash);
     }
+
+    #[test]
+    fn test_retention_horizon() {
+        let cfg = kona_genesis::RollupConfig {
+            block_time: 2,
+            channel_timeout: 300,
+            granite_channel_timeout: 50,
+            ..Default::default()
+        };
+        let finalized = |number| L2BlockInfo {
+            block_info: kona_protocol::BlockInfo { number, ..Default::default() },
+            ..Default::default()
+        };
+
+        // 300 L1 blocks of 12 seconds span 1800 L2 blocks of 2 seconds.
+        assert_eq!(super::retention_horizon(&cfg, &finalized(2_000)), 200);
+        assert_eq!(super::retention_horizon(&cfg, &finalized(1_000)), 0);
+
+        // Granite shortens the channel timeout to 50 L1 blocks.
+        let mut cfg = cfg;
+        cfg.hardforks.granite_time = Some(0);
+        assert_eq!(super::retention_horizon(&cfg, &finalized(2_000)), 1_700);
+    }
 }
@@ -1,18 +1,22 @@
 //! The [`Engine`] is a task queue that receives and executes [`EngineTask`]s.
 
-use super::EngineTaskExt;
+use super::{ConsolidatePipeline, EngineTaskExt, ForkchoiceBatch, InsertPipeline};
 use crate::{
-    EngineClient, EngineState, EngineSyncStateUpdate, EngineTask, EngineTaskError,
-    EngineTaskErrorSeverity, Metrics, SyncStartError, SynchronizeTask, SynchronizeTaskError,
+    ConsolidateTask, ConsolidationMismatch, DepositOnlyBlock, EngineClient, EngineState,
+    EngineSyncStateUpdate, EngineTask, EngineTaskError, EngineTaskErrorSeverity,
+    ForkchoiceBatching, Metrics, SyncStartError, SynchronizeTask, SynchronizeTaskError,
     find_starting_forkchoice, task_queue::EngineTaskErrors,
 };
 use alloy_rpc_types_eth::Transaction;
 use kona_genesis::{RollupConfig, SystemConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpBlockConversionError, to_system_config};
 use op_alloy_consensus::OpTxEnvelope;
-use std::{collections::BinaryHeap, sync::Arc};
+use std::{
+    collections::{BinaryHeap, VecDeque},
+    sync::Arc,
+};
 use thiserror::Error;
-use tokio::sync::watch::Sender;
+use tokio::sync::watch::{self, Sender};
 
 /// The [`Engine`] task queue.
 ///
@@ -24,9 +28,20 @@ use tokio::sync::watch::Sender;
 ///  Because tasks are executed one at a time, they are considered to be atomic operations over the
 /// [`EngineState`], and are given exclusive access to the engine state during execution.
 ///
+/// Consecutive [`InsertTask`]s are pipelined: while the forkchoice update of one payload is in
+/// flight, the queued payload extending it is already inserted into the execution layer.
+/// Consecutive [`ConsolidateTask`]s are pipelined likewise: while one block is consolidated, the
+/// unsafe block the queued attributes extending it are checked against is already fetched.
+///
+/// With [`ForkchoiceBatching`], the forkchoice updates of runs of consecutive inserted or
+/// consolidated blocks are batched, to speed up long catch-ups.
+///
 /// Tasks within the queue are also considered fallible. If they fail with a temporary error,
 /// they are not popped from the queue, the error is returned, and they are retried on the
 /// next call to [`Engine::drain`].
+///
+/// [`InsertTask`]: crate::InsertTask
+/// [`ConsolidateTask`]: crate::ConsolidateTask
 #[derive(Debug)]
 pub struct Engine<EngineClient_: EngineClient> {
     /// The state of the engine.
@@ -35,18 +50,55 @@ pub struct Engine<EngineClient_: EngineClient> {
     state_sender: Sender<EngineState>,
     /// A sender that can be used to notify the engine actor of task queue length changes.
     task_queue_length: Sender<usize>,
+    /// A sender that can be used to notify the engine actor of new deposits-only replacements.
+    deposit_only_blocks: Sender<VecDeque<DepositOnlyBlock>>,
+    /// A sender that can be used to notify the engine actor of new consolidation mismatches.
+    consolidation_mismatch: Sender<Option<ConsolidationMismatch>>,
     /// The task queue.
     tasks: BinaryHeap<EngineTask<EngineClient_>>,
+    /// The payload inserted ahead of its queued [`InsertTask`](crate::InsertTask), if any.
+    insert_pipeline: InsertPipeline,
+    /// The unsafe block fetched ahead of its queued [`ConsolidateTask`](crate::ConsolidateTask),
+    /// if any.
+    consolidate_pipeline: ConsolidatePipeline,
+    /// The forkchoice updates deferred by [`ForkchoiceBatching`].
+    forkchoice_batch: ForkchoiceBatch,
 }
 
 impl<EngineClient_: EngineClient> Engine<EngineClient_> {
+    /// The maximum number of [`DepositOnlyBlock`]s kept in the replacement history.
+    pub const DEPOSIT_ONLY_HISTORY_SIZE: usize = 128;
+
     /// Creates a new [`Engine`] with an empty task queue and the passed initial [`EngineState`].
     pub fn new(
         initial_state: EngineState,
         state_sender: Sender<EngineState>,
         task_queue_length: Sender<usize>,
     ) -> Self {
-        Self { state: initial_state, state_sender, task_queue_length, tasks: BinaryHeap::default() }
+        Self {
+            state: initial_state,
+            state_sender,
+            task_queue_length,
+            deposit_only_blocks: watch::channel(VecDeque::new()).0,
+            consolidation_mismatch: watch::channel(None).0,
+            tasks: BinaryHeap::default(),
+            insert_pipeline: InsertPipeline::default(),
+            consolidate_pipeline: ConsolidatePipeline::default(),
+            forkchoice_batch: ForkchoiceBatch::default(),
+        }
+    }
+
+    /// Batches the forkchoice updates of consecutive inserted or consolidated blocks with the
+    /// given [`ForkchoiceBatching`].
+    pub const fn with_forkchoice_batching(mut self, batching: ForkchoiceBatching) -> Self {
+        self.forkchoice_batch = ForkchoiceBatch::new(Some(batching));
+        self
+    }
+
+    /// Restores the last [`ConsolidationMismatch`] recorded by a previous run of the engine.
+    pub fn with_consolidation_mismatch(self, mismatch: ConsolidationMismatch) -> Self {
+        self.consolidation_mismatch.send_replace(Some(mismatch));
+        self
     }
 
     /// Returns a reference to the inner [`EngineState`].
@@ -64,6 +116,22 @@ impl<EngineClient_: EngineClient> Engine<EngineClient_> {
         self.task_queue_length.subscribe()
     }
 
+    /// Returns a receiver that can be used to read the history of invalid payloads replaced by
+    /// deposits-only blocks, oldest first.
+    pub fn deposit_only_blocks_subscribe(
+        &self,
+    ) -> tokio::sync::watch::Receiver<VecDeque<DepositOnlyBlock>> {
+        self.deposit_only_blocks.subscribe()
+    }
+
+    /// Returns a receiver that can be used to read the last [`ConsolidationMismatch`] between
+    /// derived attributes and the unsafe block they were consolidated against.
+    pub fn consolidation_mismatch_subscribe(
+        &self,
+    ) -> tokio::sync::watch::Receiver<Option<ConsolidationMismatch>> {
+        self.consolidation_mismatch.subscribe()
+    }
+
     /// Enqueues a new [`EngineTask`] for execution.
     /// Updates the queue length and notifies listeners of the change.
     pub fn enqueue(&mut self, task: EngineTask<EngineClient_>) {
@@ -91,6 +159,7 @@ impl<EngineClient_: EngineClient> Engine<EngineClient_> {
             EngineSyncStateUpdate {
                 unsafe_head: Some(start.un_safe),
                 cross_unsafe_head: Some(start.un_safe),
+                pending_safe_head: Some(start.safe),
                 local_safe_head: Some(start.safe),
                 safe_head: Some(start.safe),
                 finalized_head: Some(start.finalized),
@@ -140,9 +209,178 @@ impl<EngineClient_: EngineClient> Engine<EngineClient_> {
         Ok((start.safe, l1_origin_info, system_config))
     }
 
+    /// Resets the engine to a pinned L2 safe head and the L1 origin derivation restarts from,
+    /// rewinding the unsafe and safe heads to the pinned safe head. The finalized head is kept.
+    ///
+    /// The anchors are validated before the forkchoice changes: the safe head must be canonical in
+    /// the execution layer and not behind the finalized head, and the L1 origin must be canonical
+    /// and not ahead of the L1 origin of the safe head.
+    pub async fn reset_to(
+        &mut self,
+        client: Arc<EngineClient_>,
+        config: Arc<RollupConfig>,
+        l2_safe_head: L2BlockInfo,
+        l1_origin: BlockInfo,
+    ) -> Result<(L2BlockInfo, BlockInfo, SystemConfig), EngineResetError> {
+        let number = l2_safe_head.block_info.number;
+        let finalized = self.state.sync_state.finalized_head().block_info.number;
+        if number < finalized {
+            return Err(
+                ResetAnchorsError::SafeHeadBehindFinalized { safe: number, finalized }.into()
+            );
+        }
+        if l1_origin.number > l2_safe_head.l1_origin.number {
+            return Err(ResetAnchorsError::L1OriginAheadOfSafeHead {
+                origin: l1_origin.number,
+                safe_origin: l2_safe_head.l1_origin.number,
+            }
+            .into());
+        }
+
+        let l2_safe_block = client
+            .get_l2_block(number.into())
+            .full()
+            .await
+            .map_err(SyncStartError::RpcError)?
+            .ok_or(SyncStartError::BlockNotFound(number.into()))?
+            .into_consensus();
+        if L2BlockInfo::from_block_and_genesis(&l2_safe_block, &config.genesis).ok() !=
+            Some(l2_safe_head)
+        {
+            return Err(ResetAnchorsError::NonCanonicalSafeHead(number).into());
+        }
+        let canonical_origin: BlockInfo = client
+            .get_l1_block(l1_origin.number.into())
+            .await
+            .map_err(SyncStartError::RpcError)?
+            .ok_or(SyncStartError::BlockNotFound(l1_origin.number.into()))?
+            .into_consensus()
+            .into();
+        if canonical_origin != l1_origin {
+            return Err(ResetAnchorsError::NonCanonicalL1Origin(l1_origin.number).into());
+        }
+        let l2_safe_block = l2_safe_block
+            .map_transactions(|t| <Transaction<OpTxEnvelope> as Clone>::clone(&t).into_inner());
+        let system_config = to_system_config(&l2_safe_block, &config)?;
+
+        // Clear any outstanding tasks, and rewind the forkchoice to the pinned safe head.
+        self.clear();
+        let task = SynchronizeTask::new(
+            client,
+            config,
+            EngineSyncStateUpdate {
+                unsafe_head: Some(l2_safe_head),
+                cross_unsafe_head: Some(l2_safe_head),
+                pending_safe_head: Some(l2_safe_head),
+                local_safe_head: Some(l2_safe_head),
+                safe_head: Some(l2_safe_head),
+                ..Default::default()
+            },
+        );
+        while let Err(err) = task.execute(&mut self.state).await {
+            if !matches!(err.severity(), EngineTaskErrorSeverity::Temporary) {
+                return Err(err.into());
+            }
+            warn!(target: "engine", ?err, "Forkchoice update to the pinned head failed, retrying");
+        }
+
+        kona_macros::inc!(counter, Metrics::ENGINE_RESET_COUNT);
+
+        Ok((l2_safe_head, l1_origin, system_config))
+    }
+
+    /// Points the execution layer's forkchoice at a trusted `checkpoint`, setting all of the heads
+    /// to it. The execution layer syncs up to the checkpoint from its own peers, and
+    /// [`EngineState::el_sync_finished`] is set once it reports the checkpoint as valid.
+    pub async fn checkpoint(
+        &mut self,
+        client: Arc<EngineClient_>,
+        config: Arc<RollupConfig>,
+        checkpoint: L2BlockInfo,
+    ) -> Result<(), SynchronizeTaskError> {
+        let task = SynchronizeTask::new(
+            client,
+            config,
+            EngineSyncStateUpdate {
+                unsafe_head: Some(checkpoint),
+                cross_unsafe_head: Some(checkpoint),
+                pending_safe_head: Some(checkpoint),
+                local_safe_head: Some(checkpoint),
+                safe_head: Some(checkpoint),
+                finalized_head: Some(checkpoint),
+            },
+        );
+
+        // Retry to synchronize the engine until we succeed or a non-temporary error occurs.
+        while let Err(err) = task.execute(&mut self.state).await {
+            if !matches!(err.severity(), EngineTaskErrorSeverity::Temporary) {
+                return Err(err);
+            }
+            warn!(target: "engine", ?err, "Forkchoice update to the checkpoint failed. Trying again...");
+        }
+
+        self.state_sender.send_replace(self.state);
+        Ok(())
+    }
+
+    /// Points the execution layer's forkchoice at the `unsafe_head` it synced to, keeping the safe
+    /// and finalized heads. The unsafe blocks between the safe and unsafe heads are left for
+    /// derivation to verify by consolidating them.
+    pub async fn sync_unsafe_head(
+        &mut self,
+        client: Arc<EngineClient_>,
+        config: Arc<RollupConfig>,
+        unsafe_head: L2BlockInfo,
+    ) -> Result<(), SynchronizeTaskError> {
+        let task = SynchronizeTask::new(
+            client,
+            config,
+            EngineSyncStateUpdate {
+                unsafe_head: Some(unsafe_head),
+                cross_unsafe_head: Some(unsafe_head),
+                ..Default::default()
+            },
+        );
+
+        // Retry to synchronize the engine until we succeed or a non-temporary error occurs.
+        while let Err(err) = task.execute(&mut self.state).await {
+            if !matches!(err.severity(), EngineTaskErrorSeverity::Temporary) {
+                return Err(err);
+            }
+            warn!(target: "engine", ?err, "Forkchoice update to the unsafe head failed, retrying");
+        }
+
+        self.state_sender.send_replace(self.state);
+        Ok(())
+    }
+
+    /// Restarts execution layer sync, clearing the task queue. The unsafe payloads inserted next
+    /// drive the execution layer's sync, and [`EngineState::el_sync_finished`] is set again once it
+    /// reports the unsafe head as valid.
+    ///
+    /// Returns the queued [`ConsolidateTask`]s, which can only execute once the execution layer
+    /// holds the unsafe blocks they consolidate.
+    pub fn restart_el_sync(&mut self) -> Vec<ConsolidateTask<EngineClient_>> {
+        let mut consolidations: Vec<_> = core::mem::take(&mut self.tasks)
+            .into_iter()
+            .filter_map(|task| match task {
+                EngineTask::Consolidate(task) => Some(*task),
+                _ => None,
+            })
+            .collect();
+        consolidations.sort_by_key(|task| task.attributes.block_number());
+        self.clear();
+        self.task_queue_length.send_replace(0);
+        self.state.el_sync_finished = false;
+        self.state_sender.send_replace(self.state);
+        consolidations
+    }
+
     /// Clears the task queue.
     pub fn clear(&mut self) {
         self.tasks.clear();
+        self.insert_pipeline.clear();
+        self.consolidate_pipeline.clear();
     }
 
     /// Attempts to drain the queue by executing all [`EngineTask`]s in-order. If any task returns
@@ -151,8 +389,48 @@ impl<EngineClient_: EngineClient> Engine<EngineClient_> {
     pub async fn drain(&mut self) -> Result<(), EngineTaskErrors> {
         // Drain tasks in order of priority, halting on errors for a retry to be attempted.
         while let Some(task) = self.tasks.peek() {
+            // Find the queued task extending the block of the task, if any, to insert its payload
+            // or fetch its unsafe block while the task executes.
+            let next = self.tasks.iter().find(|queued| match (task, queued) {
+                (EngineTask::Insert(insert), EngineTask::Insert(next)) => {
+                    next.parent_hash() == insert.block_hash()
+                }
+                (EngineTask::Consolidate(consolidate), EngineTask::Consolidate(next)) => {
+                    next.attributes.parent.block_info.number ==
+                        consolidate.attributes.block_number()
+                }
+                _ => false,
+            });
+
+            // Find the parent of the block queued after the one of the task, if any, to batch
+            // their forkchoice updates.
+            let next_parent = match next {
+                Some(EngineTask::Insert(next)) => Some(next.parent_hash()),
+                Some(EngineTask::Consolidate(next)) => Some(next.attributes.parent.block_info.hash),
+                _ => None,
+            };
+            self.forkchoice_batch.queue_next(next_parent);
+
             // Execute the task
-            task.execute(&mut self.state).await?;
+            match task
+                .execute_pipelined(
+                    &mut self.state,
+                    &mut self.insert_pipeline,
+                    &mut self.consolidate_pipeline,
+                    &mut self.forkchoice_batch,
+                    next,
+                )
+                .await
+            {
+                Ok(Some(mismatch)) => self.record_consolidation_mismatch(mismatch),
+                Ok(None) => {}
+                Err(err) => {
+                    if let Some(block) = err.deposit_only_block() {
+                        self.record_deposit_only_block(block.clone());
+                    }
+                    return Err(err);
+                }
+            }
 
             // Update the state and notify the engine actor.
             self.state_sender.send_replace(self.state);
@@ -165,6 +443,49 @@ impl<EngineClient_: EngineClient> Engine<EngineClient_> {
 
         Ok(())
     }
+
+    /// Records an invalid payload replaced by a deposits-only block, evicting the oldest record
+    /// once the history is full.
+    fn record_deposit_only_block(&mut self, block: DepositOnlyBlock) {
+        warn!(
+            target: "engine",
+            block_number = block.block_number,
+            dropped_transactions = block.dropped_transactions,
+            reason = %block.reason,
+            "Invalid payload replaced by a deposits-only block"
+        );
+
+        kona_macros::inc!(counter, Metrics::DEPOSIT_ONLY_BLOCK_COUNT);
+        kona_macros::set!(
+            gauge,
+            Metrics::DEPOSIT_ONLY_DROPPED_TRANSACTIONS,
+            block.dropped_transactions as f64
+        );
+
+        self.deposit_only_blocks.send_modify(|history| {
+            if history.len() == Self::DEPOSIT_ONLY_HISTORY_SIZE {
+                history.pop_front();
+            }
+            history.push_back(block);
+        });
+    }
+
+    /// Records the mismatch between derived attributes and the unsafe block they were
+    /// consolidated against, replacing the previous one.
+    fn record_consolidation_mismatch(&mut self, mismatch: ConsolidationMismatch) {
+        warn!(
+            target: "engine",
+            block_number = mismatch.block_number,
+            block_hash = %mismatch.block_hash,
+            reason = %mismatch.reason,
+            fields = mismatch.fields.len(),
+            "Derived attributes do not match the unsafe block, reorging the unsafe chain"
+        );
+
+        kona_macros::inc!(counter, Metrics::CONSOLIDATION_MISMATCH_COUNT);
+
+        self.consolidation_mismatch.send_replace(Some(mismatch));
+    }
 }
 
 /// An error occurred while attempting to reset the [`Engine`].
@@ -179,4 +500,321 @@ pub enum EngineResetError {
     /// An error occurred while constructing the SystemConfig for the new safe head.
     #[error(transparent)]
     SystemConfigConversion(#[from] OpBlockConversionError),
+    /// The anchors pinned for the reset are invalid.
+    #[error(transparent)]
+    Anchors(#[from] ResetAnchorsError),
+}
+
+/// The anchors pinned for an [`Engine::reset_to`] are invalid.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ResetAnchorsError {
+    /// The pinned L2 safe head is not canonical in the execution layer.
+    #[error("The pinned L2 safe head #{0} is not canonical")]
+    NonCanonicalSafeHead(u64),
+    /// The pinned L2 safe head is behind the finalized head.
+    #[error("The pinned L2 safe head #{safe} is behind the finalized head #{finalized}")]
+    SafeHeadBehindFinalized {
+        /// The number of the pinned L2 safe head.
+        safe: u64,
+        /// The number of the finalized head.
+        finalized: u64,
+    },
+    /// The pinned L1 origin is not canonical.
+    #[error("The pinned L1 origin #{0} is not canonical")]
+    NonCanonicalL1Origin(u64),
+    /// The pinned L1 origin is ahead of the L1 origin of the pinned L2 safe head.
+    #[error(
+        "The pinned L1 origin #{origin} is ahead of the L1 origin #{safe_origin} of the safe head"
+    )]
+    L1OriginAheadOfSafeHead {
+        /// The number of the pinned L1 origin.
+        origin: u64,
+        /// The number of the L1 origin of the pinned L2 safe head.
+        safe_origin: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        InsertTask,
+        test_utils::{
+            MockEngineCall, MockEngineClient, TestEngineStateBuilder, test_block_info,
+            test_engine_client_builder, test_l1_info_deposit, test_unsafe_block,
+        },
+    };
+    use alloy_eips::{BlockNumHash, eip2718::Encodable2718};
+    use alloy_primitives::{Address, B256, Bloom, Bytes, U256};
+    use alloy_rpc_types_engine::{
+        ExecutionPayloadV1, ForkchoiceUpdated, PayloadAttributes, PayloadStatus, PayloadStatusEnum,
+    };
+    use alloy_rpc_types_eth::Block;
+    use kona_protocol::OpAttributesWithParent;
+    use op_alloy_rpc_types::Transaction as OpTransaction;
+    use op_alloy_rpc_types_engine::{
+        OpExecutionPayload, OpExecutionPayloadEnvelope, OpPayloadAttributes,
+    };
+    use std::time::Duration;
+    use tracing::Span;
+
+    struct Setup {
+        engine: Engine<MockEngineClient>,
+        client: Arc<MockEngineClient>,
+        config: Arc<RollupConfig>,
+        state: EngineState,
+        l2_genesis: L2BlockInfo,
+        l1_genesis: BlockInfo,
+    }
+
+    /// Sets up an engine with its unsafe and safe heads ahead of a genesis that is canonical in
+    /// both the L1 and L2 chains. The finalized head defaults to the L2 genesis.
+    fn setup(finalized_head: Option<L2BlockInfo>) -> Setup {
+        let l1_block = Block::<Transaction>::default();
+        let l2_block = Block::<OpTransaction>::default();
+        let l1_genesis: BlockInfo = l1_block.clone().into_consensus().into();
+
+        let mut config = RollupConfig::default();
+        config.genesis.l1 = l1_genesis.id();
+        config.genesis.l2 = BlockInfo::from(&l2_block.clone().into_consensus()).id();
+        config.genesis.system_config = Some(SystemConfig::default());
+        let config = Arc::new(config);
+        let l2_genesis = L2BlockInfo::from_block_and_genesis(
+            &l2_block.clone().into_consensus(),
+            &config.genesis,
+        )
+        .unwrap();
+
+        let client = Arc::new(
+            test_engine_client_builder()
+                .with_config(config.clone())
+                .with_fork_choice_updated_v3_response(ForkchoiceUpdated {
+                    payload_status: PayloadStatus {
+                        status: PayloadStatusEnum::Valid,
+                        latest_valid_hash: None,
+                    },
+                    payload_id: None,
+                })
+                .with_l1_block(0u64.into(), l1_block)
+                .with_l2_block(0u64.into(), l2_block)
+                .build(),
+        );
+        let state = TestEngineStateBuilder::new()
+            .with_unsafe_head(test_block_info(5))
+            .with_safe_head(test_block_info(3))
+            .with_finalized_head(finalized_head.unwrap_or(l2_genesis))
+            .build();
+        let engine = Engine::new(state, watch::channel(state).0, watch::channel(0).0);
+        Setup { engine, client, config, state, l2_genesis, l1_genesis }
+    }
+
+    #[tokio::test]
+    async fn test_reset_to_rewinds_to_pinned_safe_head() {
+        let Setup { mut engine, client, config, l2_genesis, l1_genesis, .. } = setup(None);
+
+        let (safe_head, l1_origin, system_config) =
+            engine.reset_to(client, config.clone(), l2_genesis, l1_genesis).await.unwrap();
+        assert_eq!(safe_head, l2_genesis);
+        assert_eq!(l1_origin, l1_genesis);
+        assert_eq!(Some(system_config), config.genesis.system_config);
+
+        let sync_state = engine.state().sync_state;
+        assert_eq!(sync_state.unsafe_head(), l2_genesis);
+        assert_eq!(sync_state.safe_head(), l2_genesis);
+        assert_eq!(sync_state.finalized_head(), l2_genesis);
+    }
+
+    #[tokio::test]
+    async fn test_reset_to_rejects_non_canonical_safe_head() {
+        let Setup { mut engine, client, config, state, l1_genesis, .. } = setup(None);
+
+        let pinned = L2BlockInfo { l1_origin: l1_genesis.id(), ..test_block_info(0) };
+        let err = engine.reset_to(client, config, pinned, l1_genesis).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EngineResetError::Anchors(ResetAnchorsError::NonCanonicalSafeHead(0))
+        ));
+        assert_eq!(*engine.state(), state);
+    }
+
+    #[tokio::test]
+    async fn test_reset_to_rejects_non_canonical_l1_origin() {
+        let Setup { mut engine, client, config, state, l2_genesis, l1_genesis } = setup(None);
+
+        let origin = BlockInfo { hash: B256::random(), ..l1_genesis };
+        let err = engine.reset_to(client, config, l2_genesis, origin).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EngineResetError::Anchors(ResetAnchorsError::NonCanonicalL1Origin(0))
+        ));
+        assert_eq!(*engine.state(), state);
+    }
+
+    #[tokio::test]
+    async fn test_reset_to_rejects_safe_head_behind_finalized() {
+        let Setup { mut engine, client, config, state, l2_genesis, l1_genesis } =
+            setup(Some(test_block_info(2)));
+
+        let err = engine.reset_to(client, config, l2_genesis, l1_genesis).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EngineResetError::Anchors(ResetAnchorsError::SafeHeadBehindFinalized {
+                safe: 0,
+                finalized: 2
+            })
+        ));
+        assert_eq!(*engine.state(), state);
+    }
+
+    /// The simulated latency of the engine calls in the pipelining tests.
+    const LATENCY: Duration = Duration::from_millis(100);
+
+    /// Returns a valid forkchoice updated response.
+    const fn fcu_valid() -> ForkchoiceUpdated {
+        ForkchoiceUpdated {
+            payload_status: PayloadStatus {
+                status: PayloadStatusEnum::Valid,
+                latest_valid_hash: None,
+            },
+            payload_id: None,
+        }
+    }
+
+    /// Returns the attributes the unsafe `block` was built from, on top of `parent`.
+    fn attributes_of(block: &Block<OpTransaction>, parent: L2BlockInfo) -> OpAttributesWithParent {
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: block.header.timestamp,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: Some(vec![test_l1_info_deposit().encoded_2718().into()]),
+            no_tx_pool: Some(true),
+            gas_limit: Some(block.header.gas_limit),
+            eip_1559_params: None,
+            min_base_fee: None,
+        };
+        OpAttributesWithParent::new(attributes, parent, None, true)
+    }
+
+    /// Returns the payload at `number`, extending the payload at `number - 1`.
+    fn payload(number: u64) -> OpExecutionPayloadEnvelope {
+        OpExecutionPayloadEnvelope {
+            parent_beacon_block_root: None,
+            execution_payload: OpExecutionPayload::V1(ExecutionPayloadV1 {
+                parent_hash: B256::with_last_byte(number as u8 - 1),
+                fee_recipient: Address::ZERO,
+                state_root: B256::ZERO,
+                receipts_root: B256::ZERO,
+                logs_bloom: Bloom::ZERO,
+                prev_randao: B256::ZERO,
+                block_number: number,
+                gas_limit: 30_000_000,
+                gas_used: 0,
+                timestamp: number * 2,
+                extra_data: Bytes::new(),
+                base_fee_per_gas: U256::ZERO,
+                block_hash: B256::with_last_byte(number as u8),
+                transactions: vec![test_l1_info_deposit().encoded_2718().into()],
+            }),
+        }
+    }
+
+    /// Returns the calls to `method`, in the order they finished.
+    fn calls_to(calls: &[MockEngineCall], method: &str) -> Vec<MockEngineCall> {
+        calls.iter().filter(|call| call.method == method).cloned().collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_drain_inserts_next_payload_during_forkchoice_update() {
+        let config = Arc::new(RollupConfig::default());
+        let client = Arc::new(
+            test_engine_client_builder()
+                .with_config(config.clone())
+                .with_latency(LATENCY)
+                .with_new_payload_v1_response(PayloadStatus {
+                    status: PayloadStatusEnum::Valid,
+                    latest_valid_hash: None,
+                })
+                .with_fork_choice_updated_v3_response(fcu_valid())
+                .build(),
+        );
+        let state = TestEngineStateBuilder::new().build();
+        let mut engine = Engine::new(state, watch::channel(state).0, watch::channel(0).0);
+        for number in 1..=2 {
+            let task = InsertTask::new(client.clone(), config.clone(), payload(number), false);
+            engine.enqueue(EngineTask::Insert(Box::new(task)));
+        }
+
+        engine.drain().await.unwrap();
+        assert_eq!(engine.state().sync_state.unsafe_head().block_info.number, 2);
+
+        // Each payload is inserted once, the second one while the first one is canonicalized.
+        let calls = client.calls().await;
+        let inserts = calls_to(&calls, "new_payload_v1");
+        let updates = calls_to(&calls, "fork_choice_updated_v3");
+        assert_eq!(inserts.len(), 2);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(inserts[1].block_number, Some(2));
+        assert!(inserts[1].overlaps(&updates[0]));
+
+        // The forkchoice updates remain sequential.
+        assert!(!updates[0].overlaps(&updates[1]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_drain_fetches_next_unsafe_block_during_consolidation() {
+        let config = Arc::new(RollupConfig::default());
+        let first = test_unsafe_block(1, B256::ZERO);
+        let first_ref =
+            L2BlockInfo::from_block_and_genesis(&first.clone().into_consensus(), &config.genesis)
+                .unwrap();
+        let second = test_unsafe_block(2, first_ref.block_info.hash);
+        let client = Arc::new(
+            test_engine_client_builder()
+                .with_config(config.clone())
+                .with_latency(LATENCY)
+                .with_fork_choice_updated_v3_response(fcu_valid())
+                .with_l2_block_by_label(1u64.into(), first.clone())
+                .with_l2_block_by_label(2u64.into(), second.clone())
+                .build(),
+        );
+        let state = TestEngineStateBuilder::new()
+            .with_unsafe_head(test_block_info(5))
+            .with_safe_head(L2BlockInfo::default())
+            .with_finalized_head(L2BlockInfo::default())
+            .build();
+        let mut engine = Engine::new(state, watch::channel(state).0, watch::channel(0).0);
+        for attributes in
+            [attributes_of(&first, L2BlockInfo::default()), attributes_of(&second, first_ref)]
+        {
+            let task = ConsolidateTask::new(
+                client.clone(),
+                config.clone(),
+                attributes,
+                true,
+                Span::none(),
+            );
+            engine.enqueue(EngineTask::Consolidate(Box::new(task)));
+        }
+
+        engine.drain().await.unwrap();
+        assert_eq!(engine.state().sync_state.safe_head().block_info.number, 2);
+
+        // Each unsafe block is fetched once, the second one while the first one is consolidated.
+        let calls = client.calls().await;
+        let fetches = calls_to(&calls, "l2_block_by_label");
+        let updates = calls_to(&calls, "fork_choice_updated_v3");
+        assert_eq!(fetches.len(), 2);
+        assert_eq!(updates.len(), 2);
+        let [first_fetch, second_fetch] = [1, 2].map(|number| {
+            fetches.iter().find(|call| call.block_number == Some(number)).unwrap().clone()
+        });
+        assert!(second_fetch.overlaps(&first_fetch));
+
+        // The forkchoice updates promoting the blocks remain sequential.
+        assert!(!updates[0].overlaps(&updates[1]));
+    }
 }
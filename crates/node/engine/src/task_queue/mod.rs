@@ -1,7 +1,7 @@
 //! The [`Engine`] task queue and the [`EngineTask`]s it can execute.
 
 mod core;
-pub use core::{Engine, EngineResetError};
+pub use core::{Engine, EngineResetError, ResetAnchorsError};
 
 mod tasks;
 pub use tasks::*;
@@ -0,0 +1,181 @@
+//! Typed errors for the Engine API calls made by the engine tasks.
+
+use crate::EngineTaskErrorSeverity;
+use alloy_rpc_types_engine::PayloadStatusEnum;
+use alloy_transport::{RpcError, TransportErrorKind};
+use derive_more::Display;
+use thiserror::Error;
+
+/// The JSON-RPC error code returned for a malformed request.
+const INVALID_REQUEST_ERROR: i64 = -32600;
+/// The JSON-RPC error code returned for an unknown method.
+const METHOD_NOT_FOUND_ERROR: i64 = -32601;
+/// The JSON-RPC error code returned for invalid method parameters.
+const INVALID_PARAMS_ERROR: i64 = -32602;
+/// The Engine API error code returned for an invalid forkchoice state.
+const INVALID_FORKCHOICE_STATE_ERROR: i64 = -38002;
+/// The Engine API error code returned for invalid payload attributes.
+const INVALID_PAYLOAD_ATTRIBUTES_ERROR: i64 = -38003;
+/// The Engine API error code returned when the method version does not match the fork of the
+/// payload.
+const UNSUPPORTED_FORK_ERROR: i64 = -38005;
+
+/// The category of an Engine API failure.
+///
+/// The category determines how the engine task queue handles the failure, and is reported by the
+/// [`ENGINE_API_ERRORS`] metric so that a restarting execution layer can be told apart from a
+/// consensus failure.
+///
+/// [`ENGINE_API_ERRORS`]: crate::Metrics::ENGINE_API_ERRORS
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EngineApiErrorKind {
+    /// The call failed in a way that may succeed when retried, e.g. the execution layer is
+    /// unreachable or returned an internal error.
+    #[display("transient")]
+    Transient,
+    /// The execution layer rejected the JWT authentication of the call.
+    #[display("auth")]
+    Auth,
+    /// The execution layer is syncing and cannot process the call yet.
+    #[display("syncing")]
+    Syncing,
+    /// The execution layer rejected the request or the payload as invalid.
+    #[display("invalid")]
+    Invalid,
+    /// The execution layer does not support the method version required by the active fork.
+    #[display("unsupported_fork")]
+    UnsupportedFork,
+}
+
+impl EngineApiErrorKind {
+    /// Classifies a failed Engine API call.
+    pub fn from_rpc_error(error: &RpcError<TransportErrorKind>) -> Self {
+        match error {
+            RpcError::Transport(TransportErrorKind::HttpError(e))
+                if matches!(e.status, 401 | 403) =>
+            {
+                Self::Auth
+            }
+            RpcError::ErrorResp(payload) => match payload.code {
+                UNSUPPORTED_FORK_ERROR | METHOD_NOT_FOUND_ERROR => Self::UnsupportedFork,
+                INVALID_REQUEST_ERROR |
+                INVALID_PARAMS_ERROR |
+                INVALID_FORKCHOICE_STATE_ERROR |
+                INVALID_PAYLOAD_ATTRIBUTES_ERROR => Self::Invalid,
+                _ => Self::Transient,
+            },
+            RpcError::SerError(_) | RpcError::DeserError { .. } => Self::Invalid,
+            _ => Self::Transient,
+        }
+    }
+
+    /// Classifies a payload status returned by the execution layer. Returns [`None`] for a `VALID`
+    /// status.
+    pub const fn from_payload_status(status: &PayloadStatusEnum) -> Option<Self> {
+        match status {
+            PayloadStatusEnum::Valid => None,
+            PayloadStatusEnum::Invalid { .. } => Some(Self::Invalid),
+            PayloadStatusEnum::Syncing | PayloadStatusEnum::Accepted => Some(Self::Syncing),
+        }
+    }
+
+    /// Returns the severity of a failed Engine API call of this category.
+    ///
+    /// - Transient and syncing failures are retried.
+    /// - Invalid requests reset the engine, so that it resumes from a state consistent with the
+    ///   execution layer.
+    /// - Authentication and fork support failures are configuration errors that retrying cannot
+    ///   recover from.
+    pub const fn severity(&self) -> EngineTaskErrorSeverity {
+        match self {
+            Self::Transient | Self::Syncing => EngineTaskErrorSeverity::Temporary,
+            Self::Invalid => EngineTaskErrorSeverity::Reset,
+            Self::Auth | Self::UnsupportedFork => EngineTaskErrorSeverity::Critical,
+        }
+    }
+}
+
+/// A failed Engine API call, classified by [`EngineApiErrorKind`].
+#[derive(Debug, Error)]
+#[error("Engine API call failed ({kind}): {source}")]
+pub struct EngineApiError {
+    /// The category of the failure.
+    pub kind: EngineApiErrorKind,
+    /// The underlying RPC error.
+    #[source]
+    pub source: RpcError<TransportErrorKind>,
+}
+
+impl EngineApiError {
+    /// Returns the severity of the failure.
+    pub const fn severity(&self) -> EngineTaskErrorSeverity {
+        self.kind.severity()
+    }
+}
+
+impl From<RpcError<TransportErrorKind>> for EngineApiError {
+    fn from(source: RpcError<TransportErrorKind>) -> Self {
+        Self { kind: EngineApiErrorKind::from_rpc_error(&source), source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::ErrorPayload;
+    use alloy_transport::HttpError;
+    use rstest::rstest;
+
+    fn error_resp(code: i64) -> RpcError<TransportErrorKind> {
+        RpcError::ErrorResp(ErrorPayload { code, message: "error".into(), data: None })
+    }
+
+    fn http_error(status: u16) -> RpcError<TransportErrorKind> {
+        RpcError::Transport(TransportErrorKind::HttpError(HttpError {
+            status,
+            body: String::new(),
+        }))
+    }
+
+    #[rstest]
+    #[case::internal(error_resp(-32603), EngineApiErrorKind::Transient)]
+    #[case::unknown_payload(error_resp(-38001), EngineApiErrorKind::Transient)]
+    #[case::invalid_params(error_resp(INVALID_PARAMS_ERROR), EngineApiErrorKind::Invalid)]
+    #[case::invalid_forkchoice(
+        error_resp(INVALID_FORKCHOICE_STATE_ERROR),
+        EngineApiErrorKind::Invalid
+    )]
+    #[case::unsupported_fork(
+        error_resp(UNSUPPORTED_FORK_ERROR),
+        EngineApiErrorKind::UnsupportedFork
+    )]
+    #[case::method_not_found(
+        error_resp(METHOD_NOT_FOUND_ERROR),
+        EngineApiErrorKind::UnsupportedFork
+    )]
+    #[case::backend_gone(TransportErrorKind::backend_gone(), EngineApiErrorKind::Transient)]
+    #[case::unauthorized(http_error(401), EngineApiErrorKind::Auth)]
+    #[case::unavailable(http_error(503), EngineApiErrorKind::Transient)]
+    fn test_classify_rpc_error(
+        #[case] error: RpcError<TransportErrorKind>,
+        #[case] expected: EngineApiErrorKind,
+    ) {
+        let error = EngineApiError::from(error);
+        assert_eq!(error.kind, expected);
+    }
+
+    #[test]
+    fn test_classify_payload_status() {
+        assert_eq!(EngineApiErrorKind::from_payload_status(&PayloadStatusEnum::Valid), None);
+        assert_eq!(
+            EngineApiErrorKind::from_payload_status(&PayloadStatusEnum::Syncing),
+            Some(EngineApiErrorKind::Syncing)
+        );
+        assert_eq!(
+            EngineApiErrorKind::from_payload_status(&PayloadStatusEnum::Invalid {
+                validation_error: "bad block".to_string()
+            }),
+            Some(EngineApiErrorKind::Invalid)
+        );
+    }
+}
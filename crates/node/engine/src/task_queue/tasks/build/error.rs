@@ -1,8 +1,10 @@
 //! Contains error types for the [crate::SynchronizeTask].
 
-use crate::{EngineTaskError, task_queue::tasks::task::EngineTaskErrorSeverity};
+use crate::{
+    EngineApiError, EngineApiErrorKind, EngineTaskError,
+    task_queue::tasks::task::EngineTaskErrorSeverity,
+};
 use alloy_rpc_types_engine::{PayloadId, PayloadStatusEnum};
-use alloy_transport::{RpcError, TransportErrorKind};
 use thiserror::Error;
 use tokio::sync::mpsc;
 
@@ -27,7 +29,7 @@ pub enum EngineBuildError {
     FinalizedAheadOfUnsafe(u64, u64),
     /// The forkchoice update call to the engine api failed.
     #[error("Failed to build payload attributes in the engine. Forkchoice RPC error: {0}")]
-    AttributesInsertionFailed(#[from] RpcError<TransportErrorKind>),
+    AttributesInsertionFailed(#[from] EngineApiError),
     /// The inserted payload is invalid.
     #[error("The inserted payload is invalid: {0}")]
     InvalidPayload(String),
@@ -59,8 +61,8 @@ impl EngineTaskError for BuildTaskError {
             Self::EngineBuildError(EngineBuildError::FinalizedAheadOfUnsafe(_, _)) => {
                 EngineTaskErrorSeverity::Critical
             }
-            Self::EngineBuildError(EngineBuildError::AttributesInsertionFailed(_)) => {
-                EngineTaskErrorSeverity::Temporary
+            Self::EngineBuildError(EngineBuildError::AttributesInsertionFailed(inner)) => {
+                inner.severity()
             }
             Self::EngineBuildError(EngineBuildError::InvalidPayload(_)) => {
                 EngineTaskErrorSeverity::Temporary
@@ -77,4 +79,22 @@ impl EngineTaskError for BuildTaskError {
             Self::MpscSend(_) => EngineTaskErrorSeverity::Critical,
         }
     }
+
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        match self {
+            Self::EngineBuildError(EngineBuildError::AttributesInsertionFailed(inner)) => {
+                Some(inner.kind)
+            }
+            Self::EngineBuildError(EngineBuildError::InvalidPayload(_)) => {
+                Some(EngineApiErrorKind::Invalid)
+            }
+            Self::EngineBuildError(EngineBuildError::UnexpectedPayloadStatus(status)) => {
+                EngineApiErrorKind::from_payload_status(status)
+            }
+            Self::EngineBuildError(EngineBuildError::EngineSyncing) => {
+                Some(EngineApiErrorKind::Syncing)
+            }
+            _ => None,
+        }
+    }
 }
@@ -127,7 +127,7 @@ impl<EngineClient_: EngineClient> BuildTask<EngineClient_> {
         }
         .map_err(|e| {
             error!(target: "engine_builder", "Forkchoice update failed: {}", e);
-            BuildTaskError::EngineBuildError(EngineBuildError::AttributesInsertionFailed(e))
+            BuildTaskError::EngineBuildError(EngineBuildError::AttributesInsertionFailed(e.into()))
         })?;
 
         Self::validate_forkchoice_status(update.payload_status.status)?;
@@ -1,7 +1,7 @@
 //! Contains error types for the [`crate::ConsolidateTask`].
 
 use crate::{
-    BuildTaskError, EngineTaskError, SealTaskError, SynchronizeTaskError,
+    BuildTaskError, EngineApiErrorKind, EngineTaskError, SealTaskError, SynchronizeTaskError,
     task_queue::tasks::{BuildAndSealError, task::EngineTaskErrorSeverity},
 };
 use thiserror::Error;
@@ -45,4 +45,14 @@ impl EngineTaskError for ConsolidateTaskError {
             Self::ForkchoiceUpdateFailed(inner) => inner.severity(),
         }
     }
+
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        match self {
+            Self::MissingUnsafeL2Block(_) => None,
+            Self::FailedToFetchUnsafeL2Block => None,
+            Self::BuildTaskFailed(inner) => inner.api_error_kind(),
+            Self::SealTaskFailed(inner) => inner.api_error_kind(),
+            Self::ForkchoiceUpdateFailed(inner) => inner.api_error_kind(),
+        }
+    }
 }
@@ -0,0 +1,201 @@
+//! Contains the record of derived attributes that failed to consolidate against an unsafe block.
+
+use crate::{AttributesMatch, AttributesMismatch};
+use alloy_primitives::{B256, keccak256};
+use alloy_rpc_types_eth::Block;
+use kona_genesis::RollupConfig;
+use kona_protocol::OpAttributesWithParent;
+use op_alloy_rpc_types::Transaction;
+use serde::{Deserialize, Serialize};
+
+/// Derived payload attributes that did not match the unsafe block at their height, forcing the
+/// engine to reorg the unsafe chain.
+///
+/// [`AttributesMatch::check`] stops at the first difference, so the record compares every field of
+/// the attributes against the block, to show the full extent of the divergence between the batch
+/// and the unsafe chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationMismatch {
+    /// The number of the unsafe block.
+    pub block_number: u64,
+    /// The hash of the unsafe block.
+    pub block_hash: B256,
+    /// The first [`AttributesMismatch`] found by [`AttributesMatch::check`].
+    pub reason: String,
+    /// The fields of the attributes that differ from the block.
+    pub fields: Vec<FieldDiff>,
+}
+
+/// A field of derived payload attributes that differs from the unsafe block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiff {
+    /// The name of the field. Transactions are compared by hash, as `transactions[<index>]`.
+    pub field: String,
+    /// The value of the field in the attributes.
+    pub attributes: String,
+    /// The value of the field in the block.
+    pub block: String,
+}
+
+impl ConsolidationMismatch {
+    /// The value shown for a field missing from the attributes or the block.
+    const MISSING: &str = "none";
+
+    /// Compares the `attributes` against the `block` they failed to consolidate against with the
+    /// given `mismatch`.
+    pub fn new(
+        config: &RollupConfig,
+        attributes: &OpAttributesWithParent,
+        block: &Block<Transaction>,
+        mismatch: AttributesMismatch,
+    ) -> Self {
+        let payload = &attributes.attributes().payload_attributes;
+        let header = &block.header.inner;
+        let mut fields = Vec::new();
+        let mut diff = |field: &str, attributes: String, block: String| {
+            if attributes != block {
+                fields.push(FieldDiff { field: field.to_string(), attributes, block });
+            }
+        };
+
+        diff(
+            "parentHash",
+            attributes.parent.block_info.hash.to_string(),
+            header.parent_hash.to_string(),
+        );
+        diff("timestamp", payload.timestamp.to_string(), header.timestamp.to_string());
+        diff("prevRandao", payload.prev_randao.to_string(), header.mix_hash.to_string());
+        diff(
+            "gasLimit",
+            attributes
+                .attributes()
+                .gas_limit
+                .map_or_else(|| Self::MISSING.to_string(), |gas_limit| gas_limit.to_string()),
+            header.gas_limit.to_string(),
+        );
+        diff(
+            "feeRecipient",
+            payload.suggested_fee_recipient.to_string(),
+            header.beneficiary.to_string(),
+        );
+        diff(
+            "parentBeaconBlockRoot",
+            Self::optional(payload.parent_beacon_block_root),
+            Self::optional(header.parent_beacon_block_root),
+        );
+
+        // The EIP-1559 parameters of the attributes are encoded differently from the extra data
+        // of the block, so they are only shown if the check comparing them fails.
+        if AttributesMatch::check_eip1559(config, attributes, block).is_mismatch() {
+            fields.push(FieldDiff {
+                field: "eip1559Params".to_string(),
+                attributes: Self::optional(attributes.attributes().eip_1559_params),
+                block: header.extra_data.to_string(),
+            });
+        }
+
+        let attributes_txs = attributes
+            .attributes()
+            .transactions
+            .as_ref()
+            .map_or_else(Vec::new, |txs| txs.iter().map(keccak256).collect());
+        let block_txs = block.transactions.hashes().collect::<Vec<_>>();
+        if attributes_txs.len() != block_txs.len() {
+            fields.push(FieldDiff {
+                field: "transactions.len".to_string(),
+                attributes: attributes_txs.len().to_string(),
+                block: block_txs.len().to_string(),
+            });
+        }
+        for index in 0..attributes_txs.len().max(block_txs.len()) {
+            let attributes_tx = attributes_txs.get(index).copied();
+            let block_tx = block_txs.get(index).copied();
+            if attributes_tx != block_tx {
+                fields.push(FieldDiff {
+                    field: format!("transactions[{index}]"),
+                    attributes: Self::optional(attributes_tx),
+                    block: Self::optional(block_tx),
+                });
+            }
+        }
+
+        Self {
+            block_number: header.number,
+            block_hash: block.header.hash,
+            reason: format!("{mismatch:?}"),
+            fields,
+        }
+    }
+
+    /// Formats an optional field, showing [`Self::MISSING`] if it is unset.
+    fn optional<T: ToString>(value: Option<T>) -> String {
+        value.map_or_else(|| Self::MISSING.to_string(), |value| value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Bytes};
+    use alloy_rpc_types_eth::BlockTransactions;
+    use kona_protocol::L2BlockInfo;
+    use kona_registry::ROLLUP_CONFIGS;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    #[test]
+    fn test_consolidation_mismatch_diffs_every_field() {
+        let cfg = ROLLUP_CONFIGS.get(&10).unwrap();
+        let transactions = vec![Bytes::from_static(&[0x7e, 0x1]), Bytes::from_static(&[0x2])];
+        let attributes = OpAttributesWithParent::new(
+            OpPayloadAttributes {
+                gas_limit: Some(30_000_000),
+                transactions: Some(transactions.clone()),
+                ..Default::default()
+            },
+            L2BlockInfo::default(),
+            None,
+            true,
+        );
+        let mut block = Block::<Transaction>::default();
+        block.header.inner.number = 7;
+        block.header.inner.gas_limit = 60_000_000;
+        block.header.inner.beneficiary = Address::repeat_byte(1);
+        block.transactions = BlockTransactions::Hashes(vec![keccak256(&transactions[0])]);
+
+        let mismatch = ConsolidationMismatch::new(
+            cfg,
+            &attributes,
+            &block,
+            AttributesMismatch::TransactionLen(2, 1),
+        );
+        assert_eq!(mismatch.block_number, 7);
+        assert_eq!(mismatch.reason, "TransactionLen(2, 1)");
+
+        let fields = mismatch.fields.iter().map(|diff| diff.field.as_str()).collect::<Vec<_>>();
+        assert_eq!(fields, ["gasLimit", "feeRecipient", "transactions.len", "transactions[1]"]);
+        assert_eq!(mismatch.fields[0].attributes, "30000000");
+        assert_eq!(mismatch.fields[0].block, "60000000");
+        assert_eq!(mismatch.fields[3].attributes, keccak256(&transactions[1]).to_string());
+        assert_eq!(mismatch.fields[3].block, "none");
+    }
+
+    #[test]
+    fn test_consolidation_mismatch_serde() {
+        let mismatch = ConsolidationMismatch {
+            block_number: 1,
+            block_hash: B256::ZERO,
+            reason: "GasLimit(1, 2)".to_string(),
+            fields: vec![FieldDiff {
+                field: "gasLimit".to_string(),
+                attributes: "1".to_string(),
+                block: "2".to_string(),
+            }],
+        };
+        let json = serde_json::to_value(&mismatch).unwrap();
+        assert_eq!(json["blockNumber"], 1);
+        assert_eq!(json["fields"][0]["field"], "gasLimit");
+        assert_eq!(serde_json::from_value::<ConsolidationMismatch>(json).unwrap(), mismatch);
+    }
+}
@@ -5,3 +5,9 @@ pub use error::ConsolidateTaskError;
 
 mod task;
 pub use task::ConsolidateTask;
+
+mod pipeline;
+pub(crate) use pipeline::ConsolidatePipeline;
+
+mod mismatch;
+pub use mismatch::{ConsolidationMismatch, FieldDiff};
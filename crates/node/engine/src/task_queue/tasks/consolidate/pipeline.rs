@@ -0,0 +1,47 @@
+//! Tracks the unsafe blocks fetched ahead of their [`ConsolidateTask`].
+
+use super::ConsolidateTask;
+use crate::EngineClient;
+use alloy_rpc_types_eth::Block;
+use op_alloy_rpc_types::Transaction;
+
+/// Tracks an unsafe block fetched from the execution layer ahead of its [`ConsolidateTask`].
+///
+/// While a [`ConsolidateTask`] checks its unsafe block against its attributes and sends the
+/// forkchoice update promoting it to safe, the unsafe block of the queued attributes extending it
+/// is fetched concurrently. The block is kept here until the queued task executes, which then only
+/// has to check it against its attributes.
+///
+/// Forkchoice updates remain sequential: a block is only promoted by its own task. As an unsafe
+/// payload may replace the fetched block in the meantime, the block is dropped once any other
+/// task executes, and only used if it still extends the parent of the attributes.
+#[derive(Debug, Default)]
+pub(crate) struct ConsolidatePipeline {
+    /// The unsafe block fetched ahead of its task, if any.
+    fetched: Option<Block<Transaction>>,
+}
+
+impl ConsolidatePipeline {
+    /// Records the unsafe block fetched ahead of its task.
+    pub(crate) fn insert(&mut self, block: Block<Transaction>) {
+        self.fetched = Some(block);
+    }
+
+    /// Takes the unsafe block of the given task, if it was already fetched.
+    ///
+    /// The pipeline is emptied either way, as it only ever looks one block ahead.
+    pub(crate) fn take<EngineClient_: EngineClient>(
+        &mut self,
+        task: &ConsolidateTask<EngineClient_>,
+    ) -> Option<Block<Transaction>> {
+        self.fetched.take().filter(|block| {
+            block.header.number == task.attributes.block_number() &&
+                block.header.parent_hash == task.attributes.parent.block_info.hash
+        })
+    }
+
+    /// Forgets the unsafe block fetched ahead of its task, if any.
+    pub(crate) fn clear(&mut self) {
+        self.fetched = None;
+    }
+}
@@ -1,14 +1,20 @@
 //! A task to consolidate the engine state.
 
+use super::ConsolidatePipeline;
 use crate::{
-    ConsolidateTaskError, EngineClient, EngineState, EngineTaskExt, SynchronizeTask,
-    state::EngineSyncStateUpdate, task_queue::build_and_seal,
+    AttributesMatch, ConsolidateTaskError, ConsolidationMismatch, EngineClient, EngineState,
+    EngineTaskExt, SynchronizeTask,
+    state::EngineSyncStateUpdate,
+    task_queue::{ForkchoiceBatch, build_and_seal},
 };
+use alloy_rpc_types_eth::Block;
 use async_trait::async_trait;
 use derive_more::Constructor;
 use kona_genesis::RollupConfig;
 use kona_protocol::{L2BlockInfo, OpAttributesWithParent};
+use op_alloy_rpc_types::Transaction;
 use std::{sync::Arc, time::Instant};
+use tracing::{Instrument, Span};
 
 /// The [`ConsolidateTask`] attempts to consolidate the engine state
 /// using the specified payload attributes and the oldest unsafe head.
@@ -24,6 +30,9 @@ pub struct ConsolidateTask<EngineClient_: EngineClient> {
     pub attributes: OpAttributesWithParent,
     /// Whether or not the payload was derived, or created by the sequencer.
     pub is_attributes_derived: bool,
+    /// The [`Span`] the task is executed in. This links the engine work back to the L1 block
+    /// that the attributes were derived from.
+    pub span: Span,
 }
 
 impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
@@ -46,21 +55,52 @@ impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
 
     /// Attempts consolidation on the engine state.
     pub async fn consolidate(&self, state: &mut EngineState) -> Result<(), ConsolidateTaskError> {
-        let global_start = Instant::now();
+        self.consolidate_batched(
+            state,
+            &mut ForkchoiceBatch::default(),
+            &mut ConsolidatePipeline::default(),
+        )
+        .await?;
+        Ok(())
+    }
 
-        // Fetch the unsafe l2 block after the attributes parent.
+    /// Fetches the unsafe l2 block after the attributes parent.
+    async fn fetch_unsafe_block(&self) -> Result<Block<Transaction>, ConsolidateTaskError> {
         let block_num = self.attributes.block_number();
-        let fetch_start = Instant::now();
-        let block = match self.client.l2_block_by_label(block_num.into()).await {
-            Ok(Some(block)) => block,
+        match self.client.l2_block_by_label(block_num.into()).await {
+            Ok(Some(block)) => Ok(block),
             Ok(None) => {
                 warn!(target: "engine", "Received `None` block for {}", block_num);
-                return Err(ConsolidateTaskError::MissingUnsafeL2Block(block_num));
+                Err(ConsolidateTaskError::MissingUnsafeL2Block(block_num))
             }
             Err(_) => {
                 warn!(target: "engine", "Failed to fetch unsafe l2 block for consolidation");
-                return Err(ConsolidateTaskError::FailedToFetchUnsafeL2Block);
+                Err(ConsolidateTaskError::FailedToFetchUnsafeL2Block)
             }
+        }
+    }
+
+    /// Attempts consolidation on the engine state, deferring the forkchoice update promoting the
+    /// consolidated block to safe if the [`ForkchoiceBatch`] allows it.
+    ///
+    /// The unsafe block is not fetched again if the [`ConsolidatePipeline`] holds it already.
+    ///
+    /// Returns the [`ConsolidationMismatch`] between the attributes and the unsafe block if they
+    /// did not match, and the block was rebuilt from the attributes.
+    async fn consolidate_batched(
+        &self,
+        state: &mut EngineState,
+        batch: &mut ForkchoiceBatch,
+        pipeline: &mut ConsolidatePipeline,
+    ) -> Result<Option<ConsolidationMismatch>, ConsolidateTaskError> {
+        let global_start = Instant::now();
+
+        // Fetch the unsafe l2 block after the attributes parent, unless it was fetched while the
+        // parent was consolidated.
+        let fetch_start = Instant::now();
+        let block = match pipeline.take(self) {
+            Some(block) => block,
+            None => self.fetch_unsafe_block().await?,
         };
         let block_fetch_duration = fetch_start.elapsed();
 
@@ -68,7 +108,13 @@ impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
         // If this is successful, the forkchoice change synchronizes.
         // Otherwise, the attributes need to be processed.
         let block_hash = block.header.hash;
-        if crate::AttributesMatch::check(&self.cfg, &self.attributes, &block).is_match() {
+        let mismatch = match AttributesMatch::check(&self.cfg, &self.attributes, &block) {
+            AttributesMatch::Match => None,
+            AttributesMatch::Mismatch(mismatch) => {
+                Some(ConsolidationMismatch::new(&self.cfg, &self.attributes, &block, mismatch))
+            }
+        };
+        if mismatch.is_none() {
             trace!(
                 target: "engine",
                 attributes = ?self.attributes,
@@ -83,10 +129,10 @@ impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
                 Ok(block_info) if !self.attributes.is_last_in_span => {
                     let total_duration = global_start.elapsed();
 
-                    // Apply a transient update to the safe head.
+                    // Only advance the pending safe head. The block is promoted to safe once the
+                    // span batch has been fully validated.
                     state.sync_state = state.sync_state.apply_update(EngineSyncStateUpdate {
-                        safe_head: Some(block_info),
-                        local_safe_head: Some(block_info),
+                        pending_safe_head: Some(block_info),
                         ..Default::default()
                     });
 
@@ -96,29 +142,45 @@ impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
                         number = block_info.block_info.number,
                         ?total_duration,
                         ?block_fetch_duration,
-                        "Updated safe head via L1 consolidation"
+                        "Updated pending safe head via L1 consolidation"
                     );
 
-                    return Ok(());
+                    return Ok(None);
                 }
                 Ok(block_info) => {
+                    // Pre-interop, the local safe head is cross-safe as soon as its span batch is
+                    // complete, so it is promoted to safe alongside the local safe head.
+                    let update = EngineSyncStateUpdate {
+                        pending_safe_head: Some(block_info),
+                        local_safe_head: Some(block_info),
+                        safe_head: Some(block_info),
+                        ..Default::default()
+                    };
+
+                    if batch.defer(state, block_info.block_info.hash) {
+                        state.sync_state = state.sync_state.apply_update(update);
+
+                        debug!(
+                            target: "engine",
+                            hash = %block_info.block_info.hash,
+                            number = block_info.block_info.number,
+                            total_duration = ?global_start.elapsed(),
+                            ?block_fetch_duration,
+                            "Updated safe head via L1 consolidation, deferring forkchoice update"
+                        );
+
+                        return Ok(None);
+                    }
+
                     let fcu_start = Instant::now();
 
-                    SynchronizeTask::new(
-                        Arc::clone(&self.client),
-                        self.cfg.clone(),
-                        EngineSyncStateUpdate {
-                            safe_head: Some(block_info),
-                            local_safe_head: Some(block_info),
-                            ..Default::default()
-                        },
-                    )
-                    .execute(state)
-                    .await
-                    .map_err(|e| {
-                        warn!(target: "engine", ?e, "Consolidation failed");
-                        e
-                    })?;
+                    SynchronizeTask::new(Arc::clone(&self.client), self.cfg.clone(), update)
+                        .execute(state)
+                        .await
+                        .map_err(|e| {
+                            warn!(target: "engine", ?e, "Consolidation failed");
+                            e
+                        })?;
 
                     let fcu_duration = fcu_start.elapsed();
 
@@ -134,7 +196,7 @@ impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
                         "Updated safe head via L1 consolidation"
                     );
 
-                    return Ok(());
+                    return Ok(None);
                 }
                 Err(e) => {
                     // Continue on to build the block since we failed to construct the block info.
@@ -150,7 +212,61 @@ impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
             block_hash = %block_hash,
             "Attributes mismatch! Executing build task to initiate reorg",
         );
-        self.execute_build_and_seal_tasks(state).await
+        self.execute_build_and_seal_tasks(state).await?;
+        Ok(mismatch)
+    }
+
+    /// Executes the task, deferring the forkchoice update of a consolidated block if the
+    /// [`ForkchoiceBatch`] allows it, and fetching the unsafe block of the `next` queued
+    /// attributes while the block is consolidated, if they extend it.
+    ///
+    /// A failure to fetch the `next` unsafe block is not an error of this task: the `next` task
+    /// fetches its block again when it executes. The fetched block is dropped if this task rebuilt
+    /// its unsafe block, as the `next` block no longer extends it.
+    ///
+    /// Returns the [`ConsolidationMismatch`] that caused the unsafe block to be rebuilt, if any.
+    pub(crate) async fn execute_pipelined(
+        &self,
+        state: &mut EngineState,
+        batch: &mut ForkchoiceBatch,
+        pipeline: &mut ConsolidatePipeline,
+        next: Option<&Self>,
+    ) -> Result<Option<ConsolidationMismatch>, ConsolidateTaskError> {
+        async {
+            // Skip to building the payload attributes if consolidation is not needed.
+            if state.sync_state.pending_safe_head().block_info.number <
+                state.sync_state.unsafe_head().block_info.number
+            {
+                let next = next.filter(|next| {
+                    next.attributes.parent.block_info.number == self.attributes.block_number()
+                });
+                let next_fetch = async {
+                    match next {
+                        Some(next) => Some(next.fetch_unsafe_block().await),
+                        None => None,
+                    }
+                };
+                let (consolidated, next_fetched) =
+                    tokio::join!(self.consolidate_batched(state, batch, pipeline), next_fetch);
+                match (&consolidated, next_fetched) {
+                    (Ok(None), Some(Ok(block))) => pipeline.insert(block),
+                    (_, Some(Err(err))) => {
+                        debug!(
+                            target: "engine",
+                            ?err,
+                            "Failed to fetch the next unsafe block ahead of its task"
+                        );
+                    }
+                    _ => {}
+                }
+                consolidated
+            } else {
+                self.execute_build_and_seal_tasks(state).await?;
+                Ok(None)
+            }
+        }
+        .instrument(self.span.clone())
+        .await
     }
 }
 
@@ -161,13 +277,13 @@ impl<EngineClient_: EngineClient> EngineTaskExt for ConsolidateTask<EngineClient
     type Error = ConsolidateTaskError;
 
     async fn execute(&self, state: &mut EngineState) -> Result<(), ConsolidateTaskError> {
-        // Skip to building the payload attributes if consolidation is not needed.
-        if state.sync_state.safe_head().block_info.number <
-            state.sync_state.unsafe_head().block_info.number
-        {
-            self.consolidate(state).await
-        } else {
-            self.execute_build_and_seal_tasks(state).await
-        }
+        self.execute_pipelined(
+            state,
+            &mut ForkchoiceBatch::default(),
+            &mut ConsolidatePipeline::default(),
+            None,
+        )
+        .await?;
+        Ok(())
     }
 }
@@ -1,9 +1,9 @@
 //! Contains error types for the [crate::FinalizeTask].
 
 use crate::{
-    EngineTaskError, SynchronizeTaskError, task_queue::tasks::task::EngineTaskErrorSeverity,
+    EngineApiError, EngineApiErrorKind, EngineTaskError, SynchronizeTaskError,
+    task_queue::tasks::task::EngineTaskErrorSeverity,
 };
-use alloy_transport::{RpcError, TransportErrorKind};
 use kona_protocol::FromBlockError;
 use thiserror::Error;
 
@@ -21,9 +21,9 @@ pub enum FinalizeTaskError {
     /// [`L2BlockInfo`]: kona_protocol::L2BlockInfo
     #[error(transparent)]
     FromBlock(#[from] FromBlockError),
-    /// A temporary RPC failure.
+    /// The RPC call fetching the block to finalize failed.
     #[error(transparent)]
-    TransportError(#[from] RpcError<TransportErrorKind>),
+    TransportError(#[from] EngineApiError),
     /// The forkchoice update call to finalize the block failed.
     #[error(transparent)]
     ForkchoiceUpdateFailed(#[from] SynchronizeTaskError),
@@ -35,8 +35,16 @@ impl EngineTaskError for FinalizeTaskError {
             Self::BlockNotSafe => EngineTaskErrorSeverity::Critical,
             Self::BlockNotFound(_) => EngineTaskErrorSeverity::Critical,
             Self::FromBlock(_) => EngineTaskErrorSeverity::Critical,
-            Self::TransportError(_) => EngineTaskErrorSeverity::Temporary,
+            Self::TransportError(inner) => inner.severity(),
             Self::ForkchoiceUpdateFailed(inner) => inner.severity(),
         }
     }
+
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        match self {
+            Self::TransportError(inner) => Some(inner.kind),
+            Self::ForkchoiceUpdateFailed(inner) => inner.api_error_kind(),
+            _ => None,
+        }
+    }
 }
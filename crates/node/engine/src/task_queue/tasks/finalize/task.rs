@@ -40,7 +40,7 @@ impl<EngineClient_: EngineClient> EngineTaskExt for FinalizeTask<EngineClient_>
             .get_l2_block(self.block_number.into())
             .full()
             .await
-            .map_err(FinalizeTaskError::TransportError)?
+            .map_err(|e| FinalizeTaskError::TransportError(e.into()))?
             .ok_or(FinalizeTaskError::BlockNotFound(self.block_number))?
             .into_consensus();
         let block_info = L2BlockInfo::from_block_and_genesis(&block, &self.client.cfg().genesis)
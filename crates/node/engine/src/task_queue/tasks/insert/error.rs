@@ -3,10 +3,10 @@
 //! [InsertTask]: crate::InsertTask
 
 use crate::{
-    EngineTaskError, SynchronizeTaskError, task_queue::tasks::task::EngineTaskErrorSeverity,
+    EngineApiError, EngineApiErrorKind, EngineTaskError, SynchronizeTaskError,
+    task_queue::tasks::task::EngineTaskErrorSeverity,
 };
 use alloy_rpc_types_engine::PayloadStatusEnum;
-use alloy_transport::{RpcError, TransportErrorKind};
 use kona_protocol::FromBlockError;
 use op_alloy_rpc_types_engine::OpPayloadError;
 
@@ -20,7 +20,7 @@ pub enum InsertTaskError {
     FromBlockError(#[from] OpPayloadError),
     /// Failed to insert new payload.
     #[error("Failed to insert new payload: {0}")]
-    InsertFailed(RpcError<TransportErrorKind>),
+    InsertFailed(EngineApiError),
     /// Unexpected payload status
     #[error("Unexpected payload status: {0}")]
     UnexpectedPayloadStatus(PayloadStatusEnum),
@@ -36,10 +36,22 @@ impl EngineTaskError for InsertTaskError {
     fn severity(&self) -> EngineTaskErrorSeverity {
         match self {
             Self::FromBlockError(_) => EngineTaskErrorSeverity::Critical,
-            Self::InsertFailed(_) => EngineTaskErrorSeverity::Temporary,
+            Self::InsertFailed(inner) => inner.severity(),
             Self::UnexpectedPayloadStatus(_) => EngineTaskErrorSeverity::Temporary,
             Self::L2BlockInfoConstruction(_) => EngineTaskErrorSeverity::Critical,
             Self::ForkchoiceUpdateFailed(inner) => inner.severity(),
         }
     }
+
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        match self {
+            Self::FromBlockError(_) => None,
+            Self::InsertFailed(inner) => Some(inner.kind),
+            Self::UnexpectedPayloadStatus(status) => {
+                EngineApiErrorKind::from_payload_status(status)
+            }
+            Self::L2BlockInfoConstruction(_) => None,
+            Self::ForkchoiceUpdateFailed(inner) => inner.api_error_kind(),
+        }
+    }
 }
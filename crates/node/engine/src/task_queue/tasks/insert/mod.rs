@@ -5,3 +5,6 @@ pub use task::InsertTask;
 
 mod error;
 pub use error::InsertTaskError;
+
+mod pipeline;
+pub(crate) use pipeline::InsertPipeline;
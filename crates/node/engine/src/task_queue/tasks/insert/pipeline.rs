@@ -0,0 +1,42 @@
+//! Tracks the payloads inserted ahead of their [`InsertTask`].
+
+use super::InsertTask;
+use crate::EngineClient;
+use alloy_primitives::B256;
+use kona_protocol::L2BlockInfo;
+
+/// Tracks a payload inserted into the execution layer ahead of its [`InsertTask`].
+///
+/// While an [`InsertTask`] sends the forkchoice update canonicalizing its payload, the
+/// `engine_newPayload` call of the queued payload extending it is sent concurrently. Its result is
+/// kept here until the queued task executes, which then only has to update the forkchoice.
+///
+/// Forkchoice updates remain sequential: a payload is only canonicalized by its own task, once the
+/// forkchoice update of its parent has succeeded.
+#[derive(Debug, Default)]
+pub(crate) struct InsertPipeline {
+    /// The hash and block reference of the payload inserted ahead of its task, if any.
+    inserted: Option<(B256, L2BlockInfo)>,
+}
+
+impl InsertPipeline {
+    /// Records the payload with the given hash as inserted into the execution layer.
+    pub(crate) const fn insert(&mut self, hash: B256, block: L2BlockInfo) {
+        self.inserted = Some((hash, block));
+    }
+
+    /// Takes the block reference of the payload of the given task, if it was already inserted.
+    ///
+    /// The pipeline is emptied either way, as it only ever looks one payload ahead.
+    pub(crate) fn take<EngineClient_: EngineClient>(
+        &mut self,
+        task: &InsertTask<EngineClient_>,
+    ) -> Option<L2BlockInfo> {
+        self.inserted.take().filter(|(hash, _)| *hash == task.block_hash()).map(|(_, block)| block)
+    }
+
+    /// Forgets the payload inserted ahead of its task, if any.
+    pub(crate) const fn clear(&mut self) {
+        self.inserted = None;
+    }
+}
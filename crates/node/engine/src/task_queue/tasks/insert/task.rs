@@ -1,22 +1,25 @@
 //! A task to insert an unsafe payload into the execution engine.
 
+use super::InsertPipeline;
 use crate::{
     EngineClient, EngineState, EngineTaskExt, InsertTaskError, SynchronizeTask,
-    state::EngineSyncStateUpdate,
-};
-use alloy_eips::eip7685::EMPTY_REQUESTS_HASH;
-use alloy_rpc_types_engine::{
-    CancunPayloadFields, ExecutionPayloadInputV2, PayloadStatusEnum, PraguePayloadFields,
+    state::EngineSyncStateUpdate, task_queue::ForkchoiceBatch,
 };
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{ExecutionPayloadInputV2, PayloadStatusEnum};
 use async_trait::async_trait;
 use kona_genesis::RollupConfig;
-use kona_protocol::L2BlockInfo;
-use op_alloy_consensus::OpBlock;
-use op_alloy_rpc_types_engine::{
-    OpExecutionPayload, OpExecutionPayloadEnvelope, OpExecutionPayloadSidecar,
-};
+use kona_protocol::{L2BlockInfo, payload_to_block};
+use op_alloy_rpc_types_engine::{OpExecutionPayload, OpExecutionPayloadEnvelope};
 use std::{sync::Arc, time::Instant};
 
+/// The maximum number of missing unsafe blocks backfilled from the execution layer before a
+/// payload is inserted. Larger gaps are left to the execution layer's own sync.
+const MAX_BACKFILL_BLOCKS: u64 = 1024;
+
+/// The number of payload bodies requested at once from the execution layer while backfilling.
+const BACKFILL_BATCH_SIZE: u64 = 128;
+
 /// The task to insert a payload into the execution engine.
 #[derive(Debug, Clone)]
 pub struct InsertTask<EngineClient_: EngineClient> {
@@ -29,6 +32,9 @@ pub struct InsertTask<EngineClient_: EngineClient> {
     /// If the payload is safe this is true.
     /// A payload is safe if it is derived from a safe block.
     is_payload_safe: bool,
+    /// Whether the payload is the last block of its span batch. Safe payloads only advance the
+    /// pending safe head until their span batch is complete.
+    is_last_in_span: bool,
 }
 
 impl<EngineClient_: EngineClient> InsertTask<EngineClient_> {
@@ -39,105 +45,268 @@ impl<EngineClient_: EngineClient> InsertTask<EngineClient_> {
         envelope: OpExecutionPayloadEnvelope,
         is_attributes_derived: bool,
     ) -> Self {
-        Self { client, rollup_config, envelope, is_payload_safe: is_attributes_derived }
+        Self {
+            client,
+            rollup_config,
+            envelope,
+            is_payload_safe: is_attributes_derived,
+            is_last_in_span: true,
+        }
+    }
+
+    /// Sets whether the payload is the last block of its span batch.
+    pub const fn with_last_in_span(mut self, is_last_in_span: bool) -> Self {
+        self.is_last_in_span = is_last_in_span;
+        self
+    }
+
+    /// Backfills the unsafe blocks missing between the unsafe head and the payload from the
+    /// execution layer.
+    ///
+    /// Unsafe blocks may be missed by gossip, for example while the node restarts, while the
+    /// execution layer already holds them. Rather than waiting for the safe head to catch up, the
+    /// missing range is found with `engine_getPayloadBodiesByRange`, [`BACKFILL_BATCH_SIZE`]
+    /// blocks at a time, and the unsafe head is moved to the last block the execution layer holds
+    /// if its range extends the current unsafe head. As the bodies carry no headers, only the first
+    /// and last blocks of the range are fetched.
+    async fn backfill(&self, state: &mut EngineState) -> Result<(), InsertTaskError> {
+        let unsafe_head = state.sync_state.unsafe_head();
+        let number = self.envelope.execution_payload.block_number();
+        if self.is_payload_safe ||
+            !state.el_sync_finished ||
+            number <= unsafe_head.block_info.number.saturating_add(1)
+        {
+            return Ok(());
+        }
+
+        let missing = number - unsafe_head.block_info.number - 1;
+        if missing > MAX_BACKFILL_BLOCKS {
+            debug!(target: "engine", missing, "Unsafe block gap too large to backfill");
+            return Ok(());
+        }
+
+        // Count the missing blocks the execution layer holds, up to the first it doesn't.
+        let start = unsafe_head.block_info.number + 1;
+        let mut held = 0;
+        while held < missing {
+            let count = (missing - held).min(BACKFILL_BATCH_SIZE);
+            let bodies = match self.client.get_payload_bodies_by_range_v1(start + held, count).await
+            {
+                Ok(bodies) => bodies,
+                Err(err) => {
+                    warn!(
+                        target: "engine",
+                        ?err,
+                        start = start + held,
+                        "Failed to fetch payload bodies to backfill"
+                    );
+                    break;
+                }
+            };
+            let batch_held = bodies.iter().take_while(|body| body.is_some()).count() as u64;
+            held += batch_held;
+            if batch_held < count {
+                break;
+            }
+        }
+        if held == 0 {
+            return Ok(());
+        }
+
+        // The execution layer serves its canonical chain by number, so the range extends the
+        // unsafe head if its first block does.
+        let Some(first) = self.fetch_block_ref(start).await else { return Ok(()) };
+        if first.block_info.parent_hash != unsafe_head.block_info.hash {
+            debug!(
+                target: "engine",
+                block_number = start,
+                "Execution layer block does not extend the unsafe head, skipping backfill"
+            );
+            return Ok(());
+        }
+        let head = match held {
+            1 => first,
+            _ => self.fetch_block_ref(start + held - 1).await.unwrap_or(first),
+        };
+
+        SynchronizeTask::new(
+            Arc::clone(&self.client),
+            self.rollup_config.clone(),
+            EngineSyncStateUpdate {
+                unsafe_head: Some(head),
+                cross_unsafe_head: Some(head),
+                ..Default::default()
+            },
+        )
+        .execute(state)
+        .await?;
+
+        info!(
+            target: "engine",
+            from = start,
+            to = head.block_info.number,
+            "Backfilled missing unsafe blocks from the execution layer"
+        );
+        Ok(())
+    }
+
+    /// Fetches the block reference of the unsafe block with the given number from the execution
+    /// layer, if it holds a valid one.
+    async fn fetch_block_ref(&self, block_number: u64) -> Option<L2BlockInfo> {
+        let block = match self.client.get_l2_block(block_number.into()).full().await {
+            Ok(block) => block?.into_consensus(),
+            Err(err) => {
+                warn!(
+                    target: "engine",
+                    ?err,
+                    block_number,
+                    "Failed to fetch unsafe block to backfill"
+                );
+                return None;
+            }
+        };
+        L2BlockInfo::from_block_and_genesis(&block, &self.rollup_config.genesis)
+            .inspect_err(|err| {
+                warn!(target: "engine", ?err, block_number, "Invalid unsafe block to backfill");
+            })
+            .ok()
     }
 
     /// Checks the response of the `engine_newPayload` call.
     const fn check_new_payload_status(&self, status: &PayloadStatusEnum) -> bool {
         matches!(status, PayloadStatusEnum::Valid | PayloadStatusEnum::Syncing)
     }
-}
-
-#[async_trait]
-impl<EngineClient_: EngineClient> EngineTaskExt for InsertTask<EngineClient_> {
-    type Output = ();
 
-    type Error = InsertTaskError;
+    /// Returns the hash of the payload.
+    pub fn block_hash(&self) -> B256 {
+        self.envelope.execution_payload.block_hash()
+    }
 
-    async fn execute(&self, state: &mut EngineState) -> Result<(), InsertTaskError> {
-        let time_start = Instant::now();
+    /// Returns the hash of the payload's parent.
+    pub fn parent_hash(&self) -> B256 {
+        self.envelope.execution_payload.parent_hash()
+    }
 
-        // Insert the new payload.
-        // Form the new unsafe block ref from the execution payload.
+    /// Inserts the payload into the execution layer with `engine_newPayload`, returning its block
+    /// reference.
+    async fn new_payload(&self) -> Result<L2BlockInfo, InsertTaskError> {
         let parent_beacon_block_root = self.envelope.parent_beacon_block_root.unwrap_or_default();
-        let insert_time_start = Instant::now();
-        let (response, block): (_, OpBlock) = match self.envelope.execution_payload.clone() {
-            OpExecutionPayload::V1(payload) => (
-                self.client.new_payload_v1(payload).await,
-                self.envelope
-                    .execution_payload
-                    .clone()
-                    .try_into_block()
-                    .map_err(InsertTaskError::FromBlockError)?,
-            ),
+        let response = match self.envelope.execution_payload.clone() {
+            OpExecutionPayload::V1(payload) => self.client.new_payload_v1(payload).await,
             OpExecutionPayload::V2(payload) => {
                 let payload_input = ExecutionPayloadInputV2 {
                     execution_payload: payload.payload_inner,
                     withdrawals: Some(payload.withdrawals),
                 };
-                (
-                    self.client.new_payload_v2(payload_input).await,
-                    self.envelope
-                        .execution_payload
-                        .clone()
-                        .try_into_block()
-                        .map_err(InsertTaskError::FromBlockError)?,
-                )
+                self.client.new_payload_v2(payload_input).await
+            }
+            OpExecutionPayload::V3(payload) => {
+                self.client.new_payload_v3(payload, parent_beacon_block_root).await
+            }
+            OpExecutionPayload::V4(payload) => {
+                self.client.new_payload_v4(payload, parent_beacon_block_root).await
             }
-            OpExecutionPayload::V3(payload) => (
-                self.client.new_payload_v3(payload, parent_beacon_block_root).await,
-                self.envelope
-                    .execution_payload
-                    .clone()
-                    .try_into_block_with_sidecar(&OpExecutionPayloadSidecar::v3(
-                        CancunPayloadFields::new(parent_beacon_block_root, vec![]),
-                    ))
-                    .map_err(InsertTaskError::FromBlockError)?,
-            ),
-            OpExecutionPayload::V4(payload) => (
-                self.client.new_payload_v4(payload, parent_beacon_block_root).await,
-                self.envelope
-                    .execution_payload
-                    .clone()
-                    .try_into_block_with_sidecar(&OpExecutionPayloadSidecar::v4(
-                        CancunPayloadFields::new(parent_beacon_block_root, vec![]),
-                        PraguePayloadFields::new(EMPTY_REQUESTS_HASH),
-                    ))
-                    .map_err(InsertTaskError::FromBlockError)?,
-            ),
         };
+        let block = payload_to_block(
+            self.envelope.execution_payload.clone(),
+            self.envelope.parent_beacon_block_root,
+            &self.rollup_config,
+        )
+        .map_err(InsertTaskError::FromBlockError)?;
 
         // Check the `engine_newPayload` response.
         let response = match response {
             Ok(resp) => resp,
             Err(e) => {
                 warn!(target: "engine", "Failed to insert new payload: {e}");
-                return Err(InsertTaskError::InsertFailed(e));
+                return Err(InsertTaskError::InsertFailed(e.into()));
             }
         };
         if !self.check_new_payload_status(&response.status) {
             return Err(InsertTaskError::UnexpectedPayloadStatus(response.status));
         }
-        let insert_duration = insert_time_start.elapsed();
 
-        let new_unsafe_ref =
-            L2BlockInfo::from_block_and_genesis(&block, &self.rollup_config.genesis)
-                .map_err(InsertTaskError::L2BlockInfoConstruction)?;
+        L2BlockInfo::from_block_and_genesis(&block, &self.rollup_config.genesis)
+            .map_err(InsertTaskError::L2BlockInfoConstruction)
+    }
 
-        // Send a FCU to canonicalize the imported block.
-        SynchronizeTask::new(
-            Arc::clone(&self.client),
-            self.rollup_config.clone(),
-            EngineSyncStateUpdate {
-                cross_unsafe_head: Some(new_unsafe_ref),
-                unsafe_head: Some(new_unsafe_ref),
-                local_safe_head: self.is_payload_safe.then_some(new_unsafe_ref),
-                safe_head: self.is_payload_safe.then_some(new_unsafe_ref),
-                ..Default::default()
-            },
-        )
-        .execute(state)
-        .await?;
+    /// Sends a forkchoice update to canonicalize the inserted payload, unless the
+    /// [`ForkchoiceBatch`] defers it.
+    async fn canonicalize(
+        &self,
+        state: &mut EngineState,
+        batch: &mut ForkchoiceBatch,
+        new_unsafe_ref: L2BlockInfo,
+    ) -> Result<(), InsertTaskError> {
+        let is_span_safe = self.is_payload_safe && self.is_last_in_span;
+        let update = EngineSyncStateUpdate {
+            cross_unsafe_head: Some(new_unsafe_ref),
+            unsafe_head: Some(new_unsafe_ref),
+            pending_safe_head: self.is_payload_safe.then_some(new_unsafe_ref),
+            local_safe_head: is_span_safe.then_some(new_unsafe_ref),
+            safe_head: is_span_safe.then_some(new_unsafe_ref),
+            ..Default::default()
+        };
+
+        if batch.defer(state, new_unsafe_ref.block_info.hash) {
+            state.sync_state = state.sync_state.apply_update(update);
+            return Ok(());
+        }
+
+        SynchronizeTask::new(Arc::clone(&self.client), self.rollup_config.clone(), update)
+            .execute(state)
+            .await?;
+        Ok(())
+    }
+
+    /// Executes the task, overlapping the forkchoice update canonicalizing the payload with the
+    /// insertion of the `next` queued payload, if it extends this one.
+    ///
+    /// The payload is not inserted again if the [`InsertPipeline`] holds it already. A failure to
+    /// insert the `next` payload is not an error of this task: the `next` task inserts its payload
+    /// again when it executes. The forkchoice update is deferred if the [`ForkchoiceBatch`] allows
+    /// it.
+    pub(crate) async fn execute_pipelined(
+        &self,
+        state: &mut EngineState,
+        pipeline: &mut InsertPipeline,
+        batch: &mut ForkchoiceBatch,
+        next: Option<&Self>,
+    ) -> Result<(), InsertTaskError> {
+        let time_start = Instant::now();
+
+        // Fill any gap between the unsafe head and the payload with blocks the execution layer
+        // already holds.
+        self.backfill(state).await?;
+
+        // Insert the new payload, unless it was inserted while its parent was canonicalized.
+        let insert_time_start = Instant::now();
+        let new_unsafe_ref = match pipeline.take(self) {
+            Some(new_unsafe_ref) => new_unsafe_ref,
+            None => self.new_payload().await?,
+        };
+        let insert_duration = insert_time_start.elapsed();
+
+        // The execution layer holds the payload once `engine_newPayload` returns, so the next
+        // payload extending it can be validated while the forkchoice update is in flight.
+        let next = next.filter(|next| next.parent_hash() == self.block_hash());
+        let next_insert = async {
+            match next {
+                Some(next) => Some(next.new_payload().await),
+                None => None,
+            }
+        };
+        let (canonicalized, next_inserted) =
+            tokio::join!(self.canonicalize(state, batch, new_unsafe_ref), next_insert);
+        if let (Some(next), Some(next_inserted)) = (next, next_inserted) {
+            match next_inserted {
+                Ok(next_ref) => pipeline.insert(next.block_hash(), next_ref),
+                Err(err) => {
+                    debug!(target: "engine", ?err, "Failed to insert the next payload ahead of its task")
+                }
+            }
+        }
+        canonicalized?;
 
         let total_duration = time_start.elapsed();
 
@@ -147,9 +316,148 @@ impl<EngineClient_: EngineClient> EngineTaskExt for InsertTask<EngineClient_> {
             number = new_unsafe_ref.block_info.number,
             total_duration = ?total_duration,
             insert_duration = ?insert_duration,
+            pipelined = next.is_some(),
             "Inserted new unsafe block"
         );
 
         Ok(())
     }
 }
+
+#[async_trait]
+impl<EngineClient_: EngineClient> EngineTaskExt for InsertTask<EngineClient_> {
+    type Output = ();
+
+    type Error = InsertTaskError;
+
+    async fn execute(&self, state: &mut EngineState) -> Result<(), InsertTaskError> {
+        self.execute_pipelined(
+            state,
+            &mut InsertPipeline::default(),
+            &mut ForkchoiceBatch::default(),
+            None,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        MockEngineClient, MockEngineClientBuilder, TestEngineStateBuilder,
+        test_engine_client_builder, test_unsafe_block,
+    };
+    use alloy_eips::BlockId;
+    use alloy_primitives::{Address, Bloom, Bytes, U256};
+    use alloy_rpc_types_engine::{ExecutionPayloadV1, ForkchoiceUpdated, PayloadStatus};
+
+    /// Returns a mock client builder holding the unsafe blocks `1..=count`, the first one
+    /// extending `parent_hash`, along with the hash of the last block.
+    fn client_holding(
+        config: Arc<RollupConfig>,
+        count: u64,
+        mut parent_hash: B256,
+    ) -> (MockEngineClientBuilder, B256) {
+        let mut builder = test_engine_client_builder()
+            .with_config(config)
+            .with_fork_choice_updated_v3_response(ForkchoiceUpdated {
+                payload_status: PayloadStatus {
+                    status: PayloadStatusEnum::Valid,
+                    latest_valid_hash: None,
+                },
+                payload_id: None,
+            });
+        for number in 1..=count {
+            let block = test_unsafe_block(number, parent_hash);
+            parent_hash = block.header.hash;
+            builder = builder.with_l2_block(BlockId::number(number), block);
+        }
+        (builder, parent_hash)
+    }
+
+    /// Returns a task inserting an unsafe payload at `number`.
+    fn insert_task(
+        client: Arc<MockEngineClient>,
+        config: Arc<RollupConfig>,
+        number: u64,
+    ) -> InsertTask<MockEngineClient> {
+        let envelope = OpExecutionPayloadEnvelope {
+            parent_beacon_block_root: None,
+            execution_payload: OpExecutionPayload::V1(ExecutionPayloadV1 {
+                parent_hash: B256::ZERO,
+                fee_recipient: Address::ZERO,
+                state_root: B256::ZERO,
+                receipts_root: B256::ZERO,
+                logs_bloom: Bloom::ZERO,
+                prev_randao: B256::ZERO,
+                block_number: number,
+                gas_limit: 30_000_000,
+                gas_used: 0,
+                timestamp: number * 2,
+                extra_data: Bytes::new(),
+                base_fee_per_gas: U256::ZERO,
+                block_hash: B256::ZERO,
+                transactions: vec![],
+            }),
+        };
+        InsertTask::new(client, config, envelope, false)
+    }
+
+    /// Returns an engine state whose heads are all at the default block.
+    fn state() -> EngineState {
+        TestEngineStateBuilder::new()
+            .with_unsafe_head(L2BlockInfo::default())
+            .with_safe_head(L2BlockInfo::default())
+            .with_finalized_head(L2BlockInfo::default())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_backfill_fetches_payload_bodies_in_batches() {
+        let config = Arc::new(RollupConfig::default());
+        // The execution layer holds all but the last of the blocks missing before the payload.
+        let held = BACKFILL_BATCH_SIZE + 1;
+        let (builder, last_hash) = client_holding(config.clone(), held, B256::ZERO);
+        let client = Arc::new(builder.build());
+        let mut state = state();
+
+        insert_task(client.clone(), config, held + 2).backfill(&mut state).await.unwrap();
+        let unsafe_head = state.sync_state.unsafe_head();
+        assert_eq!(unsafe_head.block_info.number, held);
+        assert_eq!(unsafe_head.block_info.hash, last_hash);
+
+        // The bodies are fetched a batch at a time, up to the first block that isn't held.
+        let starts = client
+            .calls()
+            .await
+            .into_iter()
+            .filter(|call| call.method == "get_payload_bodies_by_range_v1")
+            .map(|call| call.block_number)
+            .collect::<Vec<_>>();
+        assert_eq!(starts, vec![Some(1), Some(BACKFILL_BATCH_SIZE + 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_skips_blocks_not_extending_unsafe_head() {
+        let config = Arc::new(RollupConfig::default());
+        let (builder, _) = client_holding(config.clone(), 3, B256::with_last_byte(1));
+        let client = Arc::new(builder.build());
+        let mut state = state();
+
+        insert_task(client, config, 5).backfill(&mut state).await.unwrap();
+        assert_eq!(state.sync_state.unsafe_head(), L2BlockInfo::default());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_without_held_blocks() {
+        let config = Arc::new(RollupConfig::default());
+        let (builder, _) = client_holding(config.clone(), 0, B256::ZERO);
+        let client = Arc::new(builder.build());
+        let mut state = state();
+
+        insert_task(client.clone(), config, 5).backfill(&mut state).await.unwrap();
+        assert_eq!(state.sync_state.unsafe_head(), L2BlockInfo::default());
+        assert!(client.calls().await.iter().all(|call| !call.method.starts_with("fork_choice")));
+    }
+}
@@ -5,20 +5,26 @@ pub use task::{
     EngineTask, EngineTaskError, EngineTaskErrorSeverity, EngineTaskErrors, EngineTaskExt,
 };
 
+mod api_error;
+pub use api_error::{EngineApiError, EngineApiErrorKind};
+
 mod synchronize;
-pub use synchronize::{SynchronizeTask, SynchronizeTaskError};
+pub(crate) use synchronize::ForkchoiceBatch;
+pub use synchronize::{ForkchoiceBatching, SynchronizeTask, SynchronizeTaskError};
 
 mod insert;
+pub(crate) use insert::InsertPipeline;
 pub use insert::{InsertTask, InsertTaskError};
 
 mod build;
 pub use build::{BuildTask, BuildTaskError, EngineBuildError};
 
 mod seal;
-pub use seal::{SealTask, SealTaskError};
+pub use seal::{DepositOnlyBlock, SealTask, SealTaskError};
 
 mod consolidate;
-pub use consolidate::{ConsolidateTask, ConsolidateTaskError};
+pub(crate) use consolidate::ConsolidatePipeline;
+pub use consolidate::{ConsolidateTask, ConsolidateTaskError, ConsolidationMismatch, FieldDiff};
 
 mod finalize;
 pub use finalize::{FinalizeTask, FinalizeTaskError};
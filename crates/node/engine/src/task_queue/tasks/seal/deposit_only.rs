@@ -0,0 +1,81 @@
+//! Contains the record of a payload replaced by a deposits-only block.
+
+use kona_protocol::{ChannelId, OpAttributesWithParent};
+use serde::{Deserialize, Serialize};
+
+/// A payload that the execution layer rejected as `INVALID`, and that was replaced by a
+/// deposits-only block post-Holocene.
+///
+/// Replacements indicate either a bug in the node or the execution layer, or a batch carrying
+/// invalid transactions, so they are recorded by the [`Engine`] rather than only logged.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositOnlyBlock {
+    /// The number of the L2 block that was replaced.
+    pub block_number: u64,
+    /// The number of non-deposit transactions dropped from the payload.
+    pub dropped_transactions: usize,
+    /// The reason the execution layer gave for rejecting the payload.
+    pub reason: String,
+    /// The [`ChannelId`] of the channel the payload's batch was read from, if known. Only that
+    /// channel is flushed from the derivation pipeline.
+    #[serde(skip)]
+    pub channel_id: Option<ChannelId>,
+}
+
+impl DepositOnlyBlock {
+    /// Records the replacement of the payload built from `attributes` with a deposits-only block.
+    pub fn new(attributes: &OpAttributesWithParent, reason: String) -> Self {
+        let transactions = attributes.attributes().transactions.as_ref().map_or(0, |txs| txs.len());
+        let deposits = attributes
+            .as_deposits_only()
+            .attributes()
+            .transactions
+            .as_ref()
+            .map_or(0, |txs| txs.len());
+
+        Self {
+            block_number: attributes.block_number(),
+            dropped_transactions: transactions - deposits,
+            reason,
+            channel_id: attributes.channel_id(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_protocol::L2BlockInfo;
+    use op_alloy_consensus::OpTxType;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    #[test]
+    fn test_deposit_only_block_counts_dropped_transactions() {
+        let attributes = OpPayloadAttributes {
+            transactions: Some(vec![
+                vec![OpTxType::Deposit as u8, 0x0].into(),
+                vec![OpTxType::Deposit as u8, 0x1].into(),
+                vec![OpTxType::Eip1559 as u8, 0x2].into(),
+            ]),
+            ..OpPayloadAttributes::default()
+        };
+        let mut parent = L2BlockInfo::default();
+        parent.block_info.number = 9;
+        let attributes = OpAttributesWithParent::new(attributes, parent, None, false)
+            .with_channel_id(Some([0xAA; 16]));
+
+        let block = DepositOnlyBlock::new(&attributes, "invalid nonce".to_string());
+        assert_eq!(
+            block,
+            DepositOnlyBlock {
+                block_number: 10,
+                dropped_transactions: 1,
+                reason: "invalid nonce".to_string(),
+                channel_id: Some([0xAA; 16]),
+            }
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! Contains error types for the [crate::SynchronizeTask].
 
-use crate::{EngineTaskError, InsertTaskError, task_queue::tasks::task::EngineTaskErrorSeverity};
-use alloy_transport::{RpcError, TransportErrorKind};
+use crate::{
+    DepositOnlyBlock, EngineApiError, EngineApiErrorKind, EngineTaskError, InsertTaskError,
+    task_queue::tasks::task::EngineTaskErrorSeverity,
+};
 use kona_protocol::FromBlockError;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
 use thiserror::Error;
@@ -15,17 +17,17 @@ pub enum SealTaskError {
     PayloadInsertionFailed(#[from] Box<InsertTaskError>),
     /// The get payload call to the engine api failed.
     #[error(transparent)]
-    GetPayloadFailed(RpcError<TransportErrorKind>),
+    GetPayloadFailed(EngineApiError),
     /// A deposit-only payload failed to import.
     #[error("Deposit-only payload failed to import")]
     DepositOnlyPayloadFailed,
     /// Failed to re-attempt payload import with deposit-only payload.
     #[error("Failed to re-attempt payload import with deposit-only payload")]
     DepositOnlyPayloadReattemptFailed,
-    /// The payload is invalid, and the derivation pipeline must
-    /// be flushed post-holocene.
+    /// The payload is invalid and was replaced by a deposits-only block. The derivation pipeline
+    /// must be flushed post-holocene.
     #[error("Invalid payload, must flush post-holocene")]
-    HoloceneInvalidFlush,
+    HoloceneInvalidFlush(DepositOnlyBlock),
     /// Failed to convert a [`OpExecutionPayload`] to a [`L2BlockInfo`].
     ///
     /// [`OpExecutionPayload`]: op_alloy_rpc_types_engine::OpExecutionPayload
@@ -54,8 +56,8 @@ impl EngineTaskError for SealTaskError {
     fn severity(&self) -> EngineTaskErrorSeverity {
         match self {
             Self::PayloadInsertionFailed(inner) => inner.severity(),
-            Self::GetPayloadFailed(_) => EngineTaskErrorSeverity::Temporary,
-            Self::HoloceneInvalidFlush => EngineTaskErrorSeverity::Flush,
+            Self::GetPayloadFailed(inner) => inner.severity(),
+            Self::HoloceneInvalidFlush(_) => EngineTaskErrorSeverity::Flush,
             Self::DepositOnlyPayloadReattemptFailed => EngineTaskErrorSeverity::Critical,
             Self::DepositOnlyPayloadFailed => EngineTaskErrorSeverity::Critical,
             Self::FromBlock(_) => EngineTaskErrorSeverity::Critical,
@@ -64,4 +66,12 @@ impl EngineTaskError for SealTaskError {
             Self::UnsafeHeadChangedSinceBuild => EngineTaskErrorSeverity::Critical,
         }
     }
+
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        match self {
+            Self::PayloadInsertionFailed(inner) => inner.api_error_kind(),
+            Self::GetPayloadFailed(inner) => Some(inner.kind),
+            _ => None,
+        }
+    }
 }
@@ -5,3 +5,6 @@ pub use task::SealTask;
 
 mod error;
 pub use error::SealTaskError;
+
+mod deposit_only;
+pub use deposit_only::DepositOnlyBlock;
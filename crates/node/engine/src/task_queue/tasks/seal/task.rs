@@ -1,7 +1,8 @@
 //! A task for importing a block that has already been started.
 use super::SealTaskError;
 use crate::{
-    EngineClient, EngineGetPayloadVersion, EngineState, EngineTaskExt, InsertTask,
+    DepositOnlyBlock, EngineClient, EngineGetPayloadVersion, EngineState, EngineTaskExt,
+    InsertTask,
     InsertTaskError::{self},
     task_queue::build_and_seal,
 };
@@ -76,7 +77,7 @@ impl<EngineClient_: EngineClient> SealTask<EngineClient_> {
             EngineGetPayloadVersion::V4 => {
                 let payload = engine.get_payload_v4(payload_id).await.map_err(|e| {
                     error!(target: "engine", "Payload fetch failed: {e}");
-                    SealTaskError::GetPayloadFailed(e)
+                    SealTaskError::GetPayloadFailed(e.into())
                 })?;
 
                 OpExecutionPayloadEnvelope {
@@ -87,7 +88,7 @@ impl<EngineClient_: EngineClient> SealTask<EngineClient_> {
             EngineGetPayloadVersion::V3 => {
                 let payload = engine.get_payload_v3(payload_id).await.map_err(|e| {
                     error!(target: "engine", "Payload fetch failed: {e}");
-                    SealTaskError::GetPayloadFailed(e)
+                    SealTaskError::GetPayloadFailed(e.into())
                 })?;
 
                 OpExecutionPayloadEnvelope {
@@ -98,7 +99,7 @@ impl<EngineClient_: EngineClient> SealTask<EngineClient_> {
             EngineGetPayloadVersion::V2 => {
                 let payload = engine.get_payload_v2(payload_id).await.map_err(|e| {
                     error!(target: "engine", "Payload fetch failed: {e}");
-                    SealTaskError::GetPayloadFailed(e)
+                    SealTaskError::GetPayloadFailed(e.into())
                 })?;
 
                 OpExecutionPayloadEnvelope {
@@ -135,6 +136,7 @@ impl<EngineClient_: EngineClient> SealTask<EngineClient_> {
             new_payload.clone(),
             self.is_attributes_derived,
         )
+        .with_last_in_span(self.attributes.is_last_in_span())
         .execute(state)
         .await
         {
@@ -153,7 +155,9 @@ impl<EngineClient_: EngineClient> SealTask<EngineClient_> {
 
                 // HOLOCENE: Re-attempt payload import with deposits only
                 // First build the deposits-only payload, then seal it
-                let deposits_only_attrs = self.attributes.as_deposits_only();
+                // The rest of the span batch is flushed, so the deposits-only block completes it.
+                let mut deposits_only_attrs = self.attributes.as_deposits_only();
+                deposits_only_attrs.is_last_in_span = true;
 
                 return match build_and_seal(
                     state,
@@ -166,7 +170,10 @@ impl<EngineClient_: EngineClient> SealTask<EngineClient_> {
                 {
                     Ok(_) => {
                         info!(target: "engine", "Successfully imported deposits-only payload");
-                        Err(SealTaskError::HoloceneInvalidFlush)
+                        Err(SealTaskError::HoloceneInvalidFlush(DepositOnlyBlock::new(
+                            &self.attributes,
+                            e.to_string(),
+                        )))
                     }
                     Err(_) => Err(SealTaskError::DepositOnlyPayloadReattemptFailed),
                 }
@@ -0,0 +1,121 @@
+//! Batches the forkchoice updates of consecutive blocks.
+
+use crate::EngineState;
+use alloy_primitives::B256;
+use std::time::{Duration, Instant};
+
+/// The configuration of forkchoice update batching.
+///
+/// While the engine inserts or consolidates a run of consecutive blocks, the forkchoice update
+/// moving the labels to each block can be deferred, so that a single update moves them past many
+/// blocks at once. An update is sent at least every [`Self::max_blocks`] blocks, or every
+/// [`Self::max_interval`], whichever comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForkchoiceBatching {
+    /// The maximum number of blocks the labels may move past with a single forkchoice update.
+    pub max_blocks: u64,
+    /// The maximum time a forkchoice update may be deferred for.
+    pub max_interval: Duration,
+}
+
+/// Tracks the forkchoice updates deferred by [`ForkchoiceBatching`].
+///
+/// A block's forkchoice update is only deferred if the task queued after it extends the block,
+/// so that the last block of a run always sends the update for the whole run, and if the
+/// execution layer has finished syncing, since forkchoice updates drive its sync otherwise.
+/// Deferred labels are applied to the [`EngineState`] right away, and any later forkchoice update
+/// sent by the engine carries them to the execution layer.
+#[derive(Debug, Default)]
+pub(crate) struct ForkchoiceBatch {
+    /// The batching configuration. Forkchoice updates are never deferred if unset.
+    config: Option<ForkchoiceBatching>,
+    /// The parent hash of the block of the task queued after the executing task, if any.
+    next_parent: Option<B256>,
+    /// The number of forkchoice updates deferred since the last one was sent.
+    deferred: u64,
+    /// The time the first forkchoice update of the current batch was deferred at.
+    since: Option<Instant>,
+}
+
+impl ForkchoiceBatch {
+    /// Creates a new [`ForkchoiceBatch`] with the given configuration.
+    pub(crate) const fn new(config: Option<ForkchoiceBatching>) -> Self {
+        Self { config, next_parent: None, deferred: 0, since: None }
+    }
+
+    /// Sets the parent hash of the block of the task queued after the executing task.
+    pub(crate) const fn queue_next(&mut self, next_parent: Option<B256>) {
+        self.next_parent = next_parent;
+    }
+
+    /// Returns `true` if the forkchoice update moving the labels to the block with the given hash
+    /// can be deferred. Otherwise, the batch is closed and the update must be sent.
+    pub(crate) fn defer(&mut self, state: &EngineState, block_hash: B256) -> bool {
+        let Some(config) = self.config else {
+            return false;
+        };
+
+        let since = *self.since.get_or_insert_with(Instant::now);
+        let deferrable = state.el_sync_finished &&
+            self.next_parent == Some(block_hash) &&
+            self.deferred + 1 < config.max_blocks &&
+            since.elapsed() < config.max_interval;
+        if deferrable {
+            self.deferred += 1;
+        } else {
+            self.deferred = 0;
+            self.since = None;
+        }
+        deferrable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synced_state() -> EngineState {
+        EngineState { el_sync_finished: true, ..Default::default() }
+    }
+
+    #[test]
+    fn test_batch_disabled() {
+        let mut batch = ForkchoiceBatch::default();
+        batch.queue_next(Some(B256::ZERO));
+        assert!(!batch.defer(&synced_state(), B256::ZERO));
+    }
+
+    #[test]
+    fn test_batch_max_blocks() {
+        let config = ForkchoiceBatching { max_blocks: 3, max_interval: Duration::from_secs(60) };
+        let mut batch = ForkchoiceBatch::new(Some(config));
+        batch.queue_next(Some(B256::ZERO));
+
+        let state = synced_state();
+        let deferred = (0..6).map(|_| batch.defer(&state, B256::ZERO)).collect::<Vec<_>>();
+        assert_eq!(deferred, [true, true, false, true, true, false]);
+    }
+
+    #[test]
+    fn test_batch_max_interval() {
+        let config = ForkchoiceBatching { max_blocks: 100, max_interval: Duration::ZERO };
+        let mut batch = ForkchoiceBatch::new(Some(config));
+        batch.queue_next(Some(B256::ZERO));
+        assert!(!batch.defer(&synced_state(), B256::ZERO));
+    }
+
+    #[test]
+    fn test_batch_requires_extending_next_block() {
+        let config = ForkchoiceBatching { max_blocks: 100, max_interval: Duration::from_secs(60) };
+        let mut batch = ForkchoiceBatch::new(Some(config));
+        let state = synced_state();
+
+        batch.queue_next(None);
+        assert!(!batch.defer(&state, B256::ZERO));
+        batch.queue_next(Some(B256::repeat_byte(1)));
+        assert!(!batch.defer(&state, B256::ZERO));
+        batch.queue_next(Some(B256::ZERO));
+        assert!(!batch.defer(&EngineState::default(), B256::ZERO));
+        assert!(batch.defer(&state, B256::ZERO));
+    }
+}
@@ -1,16 +1,18 @@
 //! Contains error types for the [crate::SynchronizeTask].
 
-use crate::{EngineTaskError, task_queue::tasks::task::EngineTaskErrorSeverity};
+use crate::{
+    EngineApiError, EngineApiErrorKind, EngineTaskError,
+    task_queue::tasks::task::EngineTaskErrorSeverity,
+};
 use alloy_rpc_types_engine::PayloadStatusEnum;
-use alloy_transport::{RpcError, TransportErrorKind};
 use thiserror::Error;
 
 /// An error that occurs when running the [crate::SynchronizeTask].
 #[derive(Debug, Error)]
 pub enum SynchronizeTaskError {
     /// The forkchoice update call to the engine api failed.
-    #[error("Forkchoice update engine api call failed due to an RPC error: {0}")]
-    ForkchoiceUpdateFailed(RpcError<TransportErrorKind>),
+    #[error("Forkchoice update engine api call failed: {0}")]
+    ForkchoiceUpdateFailed(EngineApiError),
     /// The finalized head is behind the unsafe head.
     #[error("Invalid forkchoice state: unsafe head {0} is ahead of finalized head {1}")]
     FinalizedAheadOfUnsafe(u64, u64),
@@ -26,9 +28,20 @@ impl EngineTaskError for SynchronizeTaskError {
     fn severity(&self) -> EngineTaskErrorSeverity {
         match self {
             Self::FinalizedAheadOfUnsafe(_, _) => EngineTaskErrorSeverity::Critical,
-            Self::ForkchoiceUpdateFailed(_) => EngineTaskErrorSeverity::Temporary,
+            Self::ForkchoiceUpdateFailed(inner) => inner.severity(),
             Self::UnexpectedPayloadStatus(_) => EngineTaskErrorSeverity::Temporary,
             Self::InvalidForkchoiceState => EngineTaskErrorSeverity::Reset,
         }
     }
+
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        match self {
+            Self::FinalizedAheadOfUnsafe(_, _) => None,
+            Self::ForkchoiceUpdateFailed(inner) => Some(inner.kind),
+            Self::UnexpectedPayloadStatus(status) => {
+                EngineApiErrorKind::from_payload_status(status)
+            }
+            Self::InvalidForkchoiceState => Some(EngineApiErrorKind::Invalid),
+        }
+    }
 }
@@ -5,3 +5,7 @@ pub use task::SynchronizeTask;
 
 mod error;
 pub use error::SynchronizeTaskError;
+
+mod batch;
+pub(crate) use batch::ForkchoiceBatch;
+pub use batch::ForkchoiceBatching;
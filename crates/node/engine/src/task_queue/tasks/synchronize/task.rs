@@ -130,7 +130,7 @@ impl<EngineClient_: EngineClient> EngineTaskExt for SynchronizeTask<EngineClient
                     (e.code == INVALID_FORK_CHOICE_STATE_ERROR as i64)
                         .then_some(SynchronizeTaskError::InvalidForkchoiceState)
                 })
-                .unwrap_or_else(|| SynchronizeTaskError::ForkchoiceUpdateFailed(e));
+                .unwrap_or_else(|| SynchronizeTaskError::ForkchoiceUpdateFailed(e.into()));
 
             debug!(target: "engine", error = ?error, "Unexpected forkchoice update error");
 
@@ -2,10 +2,13 @@
 //!
 //! [`Engine`]: crate::Engine
 
-use super::{BuildTask, ConsolidateTask, FinalizeTask, InsertTask};
+use super::{
+    BuildTask, ConsolidatePipeline, ConsolidateTask, FinalizeTask, ForkchoiceBatch, InsertPipeline,
+    InsertTask,
+};
 use crate::{
-    BuildTaskError, ConsolidateTaskError, EngineClient, EngineState, FinalizeTaskError,
-    InsertTaskError,
+    BuildTaskError, ConsolidateTaskError, ConsolidationMismatch, DepositOnlyBlock,
+    EngineApiErrorKind, EngineClient, EngineState, FinalizeTaskError, InsertTaskError,
     task_queue::{SealTask, SealTaskError},
 };
 use async_trait::async_trait;
@@ -40,6 +43,11 @@ pub enum EngineTaskErrorSeverity {
 pub trait EngineTaskError {
     /// The severity of the error.
     fn severity(&self) -> EngineTaskErrorSeverity;
+
+    /// The category of the failed Engine API call that caused the error, if any.
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        None
+    }
 }
 
 /// The interface for an engine task.
@@ -75,6 +83,34 @@ pub enum EngineTaskErrors {
     Finalize(#[from] FinalizeTaskError),
 }
 
+impl EngineTaskErrors {
+    /// Returns the [`DepositOnlyBlock`] that replaced an invalid payload, if the task failed
+    /// because of such a replacement.
+    pub const fn deposit_only_block(&self) -> Option<&DepositOnlyBlock> {
+        match self {
+            Self::Seal(SealTaskError::HoloceneInvalidFlush(block)) |
+            Self::Consolidate(ConsolidateTaskError::SealTaskFailed(
+                SealTaskError::HoloceneInvalidFlush(block),
+            )) => Some(block),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the task failed because the execution layer rejected a derived payload as
+    /// invalid, whether or not the payload was then replaced by a deposits-only block.
+    pub fn is_invalid_derived_payload(&self) -> bool {
+        match self {
+            Self::Consolidate(
+                ConsolidateTaskError::BuildTaskFailed(_) | ConsolidateTaskError::SealTaskFailed(_),
+            ) => {
+                self.deposit_only_block().is_some() ||
+                    self.api_error_kind() == Some(EngineApiErrorKind::Invalid)
+            }
+            _ => false,
+        }
+    }
+}
+
 impl EngineTaskError for EngineTaskErrors {
     fn severity(&self) -> EngineTaskErrorSeverity {
         match self {
@@ -85,6 +121,16 @@ impl EngineTaskError for EngineTaskErrors {
             Self::Finalize(inner) => inner.severity(),
         }
     }
+
+    fn api_error_kind(&self) -> Option<EngineApiErrorKind> {
+        match self {
+            Self::Insert(inner) => inner.api_error_kind(),
+            Self::Build(inner) => inner.api_error_kind(),
+            Self::Seal(inner) => inner.api_error_kind(),
+            Self::Consolidate(inner) => inner.api_error_kind(),
+            Self::Finalize(inner) => inner.api_error_kind(),
+        }
+    }
 }
 
 /// Tasks that may be inserted into and executed by the [`Engine`].
@@ -108,18 +154,115 @@ pub enum EngineTask<EngineClient_: EngineClient> {
 
 impl<EngineClient_: EngineClient> EngineTask<EngineClient_> {
     /// Executes the task without consuming it.
-    async fn execute_inner(&self, state: &mut EngineState) -> Result<(), EngineTaskErrors> {
+    async fn execute_inner(
+        &self,
+        state: &mut EngineState,
+        insert_pipeline: &mut InsertPipeline,
+        consolidate_pipeline: &mut ConsolidatePipeline,
+        batch: &mut ForkchoiceBatch,
+        next: Option<&Self>,
+    ) -> Result<Option<ConsolidationMismatch>, EngineTaskErrors> {
+        // Any other task may replace the unsafe block fetched ahead of the next consolidation.
+        if !matches!(self, Self::Consolidate(_)) {
+            consolidate_pipeline.clear();
+        }
+
         match self {
-            Self::Insert(task) => task.execute(state).await?,
+            Self::Insert(task) => {
+                let next = match next {
+                    Some(Self::Insert(next)) => Some(next.as_ref()),
+                    _ => None,
+                };
+                task.execute_pipelined(state, insert_pipeline, batch, next).await?
+            }
             Self::Seal(task) => task.execute(state).await?,
-            Self::Consolidate(task) => task.execute(state).await?,
+            Self::Consolidate(task) => {
+                let next = match next {
+                    Some(Self::Consolidate(next)) => Some(next.as_ref()),
+                    _ => None,
+                };
+                return Ok(task.execute_pipelined(state, batch, consolidate_pipeline, next).await?);
+            }
             Self::Finalize(task) => task.execute(state).await?,
             Self::Build(task) => {
                 task.execute(state).await?;
             }
         };
 
-        Ok(())
+        Ok(None)
+    }
+
+    /// Executes the task, retrying it until it succeeds or a non-temporary error occurs.
+    ///
+    /// If the task is an [`InsertTask`], the insertion of the `next` queued payload is overlapped
+    /// with its forkchoice update. See [`InsertPipeline`]. If the task is a [`ConsolidateTask`],
+    /// the unsafe block of the `next` queued attributes is fetched while it consolidates. See
+    /// [`ConsolidatePipeline`]. The forkchoice updates of [`InsertTask`]s and
+    /// [`ConsolidateTask`]s are batched by the [`ForkchoiceBatch`].
+    ///
+    /// Returns the [`ConsolidationMismatch`] that caused a [`ConsolidateTask`] to rebuild the
+    /// unsafe block, if any.
+    pub(crate) async fn execute_pipelined(
+        &self,
+        state: &mut EngineState,
+        insert_pipeline: &mut InsertPipeline,
+        consolidate_pipeline: &mut ConsolidatePipeline,
+        batch: &mut ForkchoiceBatch,
+        next: Option<&Self>,
+    ) -> Result<Option<ConsolidationMismatch>, EngineTaskErrors> {
+        // Retry the task until it succeeds or a critical error occurs.
+        let mismatch = loop {
+            let e = match self
+                .execute_inner(state, insert_pipeline, consolidate_pipeline, batch, next)
+                .await
+            {
+                Ok(mismatch) => break mismatch,
+                Err(e) => e,
+            };
+            let severity = e.severity();
+            let kind = e.api_error_kind();
+
+            kona_macros::inc!(
+                counter,
+                crate::Metrics::ENGINE_TASK_FAILURE,
+                self.task_metrics_label() => severity.to_string()
+            );
+            if let Some(kind) = kind {
+                kona_macros::inc!(
+                    counter,
+                    crate::Metrics::ENGINE_API_ERRORS,
+                    "task" => self.task_metrics_label(),
+                    "kind" => kind.to_string()
+                );
+            }
+
+            match severity {
+                EngineTaskErrorSeverity::Temporary => {
+                    trace!(target: "engine", kind = ?kind, "{e}");
+
+                    // Yield the task to allow other tasks to execute to avoid starvation.
+                    yield_now().await;
+
+                    continue;
+                }
+                EngineTaskErrorSeverity::Critical => {
+                    error!(target: "engine", kind = ?kind, "{e}");
+                    return Err(e);
+                }
+                EngineTaskErrorSeverity::Reset => {
+                    warn!(target: "engine", "Engine requested derivation reset");
+                    return Err(e);
+                }
+                EngineTaskErrorSeverity::Flush => {
+                    warn!(target: "engine", "Engine requested derivation flush");
+                    return Err(e);
+                }
+            }
+        };
+
+        kona_macros::inc!(counter, crate::Metrics::ENGINE_TASK_SUCCESS, self.task_metrics_label());
+
+        Ok(mismatch)
     }
 
     const fn task_metrics_label(&self) -> &'static str {
@@ -201,42 +344,14 @@ impl<EngineClient_: EngineClient> EngineTaskExt for EngineTask<EngineClient_> {
     type Error = EngineTaskErrors;
 
     async fn execute(&self, state: &mut EngineState) -> Result<(), Self::Error> {
-        // Retry the task until it succeeds or a critical error occurs.
-        while let Err(e) = self.execute_inner(state).await {
-            let severity = e.severity();
-
-            kona_macros::inc!(
-                counter,
-                crate::Metrics::ENGINE_TASK_FAILURE,
-                self.task_metrics_label() => severity.to_string()
-            );
-
-            match severity {
-                EngineTaskErrorSeverity::Temporary => {
-                    trace!(target: "engine", "{e}");
-
-                    // Yield the task to allow other tasks to execute to avoid starvation.
-                    yield_now().await;
-
-                    continue;
-                }
-                EngineTaskErrorSeverity::Critical => {
-                    error!(target: "engine", "{e}");
-                    return Err(e);
-                }
-                EngineTaskErrorSeverity::Reset => {
-                    warn!(target: "engine", "Engine requested derivation reset");
-                    return Err(e);
-                }
-                EngineTaskErrorSeverity::Flush => {
-                    warn!(target: "engine", "Engine requested derivation flush");
-                    return Err(e);
-                }
-            }
-        }
-
-        kona_macros::inc!(counter, crate::Metrics::ENGINE_TASK_SUCCESS, self.task_metrics_label());
-
+        self.execute_pipelined(
+            state,
+            &mut InsertPipeline::default(),
+            &mut ConsolidatePipeline::default(),
+            &mut ForkchoiceBatch::default(),
+            None,
+        )
+        .await?;
         Ok(())
     }
 }
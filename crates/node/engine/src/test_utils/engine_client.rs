@@ -1,14 +1,14 @@
 //! Mock implementations for testing engine client functionality.
 
 use crate::{EngineClient, HyperAuthClient};
-use alloy_eips::{BlockId, eip1898::BlockNumberOrTag};
+use alloy_eips::{BlockId, eip1898::BlockNumberOrTag, eip2718::Encodable2718};
 use alloy_network::{Ethereum, Network};
 use alloy_primitives::{Address, B256, BlockHash, StorageKey};
 use alloy_provider::{EthGetBlock, ProviderCall, RpcWithBlock};
 use alloy_rpc_types_engine::{
-    ClientVersionV1, ExecutionPayloadBodiesV1, ExecutionPayloadEnvelopeV2, ExecutionPayloadInputV2,
-    ExecutionPayloadV1, ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated, PayloadId,
-    PayloadStatus,
+    ClientVersionV1, ExecutionPayloadBodiesV1, ExecutionPayloadBodyV1, ExecutionPayloadEnvelopeV2,
+    ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3, ForkchoiceState,
+    ForkchoiceUpdated, PayloadId, PayloadStatus,
 };
 use alloy_rpc_types_eth::{Block, EIP1186AccountProofResponse, Transaction as EthTransaction};
 use alloy_transport::{TransportError, TransportErrorKind, TransportResult};
@@ -23,8 +23,8 @@ use op_alloy_rpc_types_engine::{
     OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4, OpExecutionPayloadV4,
     OpPayloadAttributes, ProtocolVersion,
 };
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::{sync::RwLock, time::Instant};
 
 use crate::EngineClientError;
 
@@ -91,6 +91,33 @@ pub struct MockEngineStorage {
     pub l2_blocks_by_id: HashMap<String, Block<OpTransaction>>,
     /// Storage for proofs by (address, stringified BlockId) key.
     pub proofs_by_address: HashMap<(Address, String), EIP1186AccountProofResponse>,
+
+    // Call recording
+    /// The simulated latency of the payload, forkchoice and block calls.
+    pub latency: Option<Duration>,
+    /// The payload, forkchoice and block calls made to the client, in the order they finished.
+    pub calls: Vec<MockEngineCall>,
+}
+
+/// A call made to a [`MockEngineClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockEngineCall {
+    /// The name of the called method.
+    pub method: &'static str,
+    /// The number of the block the call refers to, if any.
+    pub block_number: Option<u64>,
+    /// The instant the call started.
+    pub started: Instant,
+    /// The instant the call finished.
+    pub finished: Instant,
+}
+
+impl MockEngineCall {
+    /// Returns whether the call started before the `other` call finished, and finished after it
+    /// started.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.started < other.finished && other.started < self.finished
+    }
 }
 
 /// Builder for constructing a [`MockEngineClient`] with pre-configured responses.
@@ -211,6 +238,8 @@ impl MockEngineClientBuilder {
     }
 
     /// Sets the get_payload_bodies_by_range_v1 response.
+    ///
+    /// If unset, the bodies of the L2 blocks set by number are served, and `null` for the others.
     pub fn with_payload_bodies_by_range_response(
         mut self,
         bodies: ExecutionPayloadBodiesV1,
@@ -263,6 +292,12 @@ impl MockEngineClientBuilder {
         self
     }
 
+    /// Sets the simulated latency of the payload, forkchoice and block calls.
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.storage.latency = Some(latency);
+        self
+    }
+
     /// Builds the [`MockEngineClient`] with the configured values.
     ///
     /// # Panics
@@ -310,6 +345,23 @@ impl MockEngineClient {
         Arc::clone(&self.storage)
     }
 
+    /// Returns the payload, forkchoice and block calls made to the client, in the order they
+    /// finished.
+    pub async fn calls(&self) -> Vec<MockEngineCall> {
+        self.storage.read().await.calls.clone()
+    }
+
+    /// Waits for the simulated latency of a call to `method`, and records the call.
+    async fn record(&self, method: &'static str, block_number: Option<u64>) {
+        let started = Instant::now();
+        let latency = self.storage.read().await.latency;
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+        let call = MockEngineCall { method, block_number, started, finished: Instant::now() };
+        self.storage.write().await.calls.push(call);
+    }
+
     /// Sets a block response for a specific tag.
     pub async fn set_l2_block_by_label(&self, tag: BlockNumberOrTag, block: Block<OpTransaction>) {
         self.storage.write().await.l2_blocks_by_label.insert(tag, block);
@@ -482,7 +534,8 @@ impl EngineClient for MockEngineClient {
         })
     }
 
-    async fn new_payload_v1(&self, _payload: ExecutionPayloadV1) -> TransportResult<PayloadStatus> {
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> TransportResult<PayloadStatus> {
+        self.record("new_payload_v1", Some(payload.block_number)).await;
         let storage = self.storage.read().await;
         storage.new_payload_v1_response.clone().ok_or_else(|| {
             TransportError::from(TransportErrorKind::custom_str(
@@ -496,6 +549,7 @@ impl EngineClient for MockEngineClient {
         &self,
         numtag: BlockNumberOrTag,
     ) -> Result<Option<Block<OpTransaction>>, EngineClientError> {
+        self.record("l2_block_by_label", numtag.as_number()).await;
         let storage = self.storage.read().await;
         Ok(storage.l2_blocks_by_label.get(&numtag).cloned())
     }
@@ -513,8 +567,9 @@ impl EngineClient for MockEngineClient {
 impl OpEngineApi<Optimism, Http<HyperAuthClient>> for MockEngineClient {
     async fn new_payload_v2(
         &self,
-        _payload: ExecutionPayloadInputV2,
+        payload: ExecutionPayloadInputV2,
     ) -> TransportResult<PayloadStatus> {
+        self.record("new_payload_v2", Some(payload.execution_payload.block_number)).await;
         let storage = self.storage.read().await;
         storage.new_payload_v2_response.clone().ok_or_else(|| {
             TransportError::from(TransportErrorKind::custom_str(
@@ -526,9 +581,10 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for MockEngineClient {
 
     async fn new_payload_v3(
         &self,
-        _payload: ExecutionPayloadV3,
+        payload: ExecutionPayloadV3,
         _parent_beacon_block_root: B256,
     ) -> TransportResult<PayloadStatus> {
+        self.record("new_payload_v3", Some(payload.payload_inner.payload_inner.block_number)).await;
         let storage = self.storage.read().await;
         storage.new_payload_v3_response.clone().ok_or_else(|| {
             TransportError::from(TransportErrorKind::custom_str(
@@ -540,9 +596,14 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for MockEngineClient {
 
     async fn new_payload_v4(
         &self,
-        _payload: OpExecutionPayloadV4,
+        payload: OpExecutionPayloadV4,
         _parent_beacon_block_root: B256,
     ) -> TransportResult<PayloadStatus> {
+        self.record(
+            "new_payload_v4",
+            Some(payload.payload_inner.payload_inner.payload_inner.block_number),
+        )
+        .await;
         let storage = self.storage.read().await;
         storage.new_payload_v4_response.clone().ok_or_else(|| {
             TransportError::from(TransportErrorKind::custom_str(
@@ -557,6 +618,7 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for MockEngineClient {
         _fork_choice_state: ForkchoiceState,
         _payload_attributes: Option<OpPayloadAttributes>,
     ) -> TransportResult<ForkchoiceUpdated> {
+        self.record("fork_choice_updated_v2", None).await;
         let storage = self.storage.read().await;
         storage.fork_choice_updated_v2_response.clone().ok_or_else(|| {
             TransportError::from(TransportErrorKind::custom_str(
@@ -571,6 +633,7 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for MockEngineClient {
         _fork_choice_state: ForkchoiceState,
         _payload_attributes: Option<OpPayloadAttributes>,
     ) -> TransportResult<ForkchoiceUpdated> {
+        self.record("fork_choice_updated_v3", None).await;
         let storage = self.storage.read().await;
         storage.fork_choice_updated_v3_response.clone().ok_or_else(|| {
             TransportError::from(TransportErrorKind::custom_str(
@@ -631,16 +694,30 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for MockEngineClient {
 
     async fn get_payload_bodies_by_range_v1(
         &self,
-        _start: u64,
-        _count: u64,
+        start: u64,
+        count: u64,
     ) -> TransportResult<ExecutionPayloadBodiesV1> {
+        self.record("get_payload_bodies_by_range_v1", Some(start)).await;
         let storage = self.storage.read().await;
-        storage.get_payload_bodies_by_range_v1_response.clone().ok_or_else(|| {
-            TransportError::from(TransportErrorKind::custom_str(
-                "get_payload_bodies_by_range_v1 was called but no response configured. \
-                 Use with_payload_bodies_by_range_response() or set_payload_bodies_by_range_response() to set a response."
-            ))
-        })
+        if let Some(bodies) = &storage.get_payload_bodies_by_range_v1_response {
+            return Ok(bodies.clone());
+        }
+
+        // Serve the bodies of the L2 blocks held by number, if no response is configured.
+        Ok((start..start + count)
+            .map(|number| {
+                let block = storage.l2_blocks_by_id.get(&block_id_to_key(&number.into()))?;
+                let transactions = block
+                    .transactions
+                    .txns()
+                    .map(|tx| tx.inner.inner.inner().encoded_2718().into())
+                    .collect();
+                Some(ExecutionPayloadBodyV1 {
+                    transactions,
+                    withdrawals: block.withdrawals.clone().map(|w| w.into_inner()),
+                })
+            })
+            .collect())
     }
 
     async fn get_client_version_v1(
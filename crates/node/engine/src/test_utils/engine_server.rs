@@ -0,0 +1,908 @@
+//! An in-process Engine API server that emulates op-geth, for end-to-end node tests.
+
+use alloy_consensus::{BlockBody, Header, transaction::Recovered};
+use alloy_eips::{BlockNumberOrTag, eip1559::INITIAL_BASE_FEE};
+use alloy_primitives::{B256, Bloom, Bytes, U64, U256};
+use alloy_rpc_types_engine::{
+    BlobsBundleV1, ExecutionPayloadEnvelopeV2, ExecutionPayloadFieldV2, ExecutionPayloadInputV2,
+    ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated,
+    PayloadId, PayloadStatus, PayloadStatusEnum,
+};
+use alloy_rpc_types_eth::{Block, BlockTransactions, Header as RpcHeader};
+use jsonrpsee::{
+    RpcModule,
+    core::{RpcResult, async_trait},
+    proc_macros::rpc,
+    server::{Server, ServerHandle},
+    types::{ErrorCode, ErrorObject, ErrorObjectOwned},
+};
+use kona_genesis::RollupConfig;
+use kona_protocol::{compute_block_hash, payload_to_block};
+use op_alloy_consensus::OpBlock;
+use op_alloy_rpc_types::Transaction as OpTransaction;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayload, OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4,
+    OpExecutionPayloadV4, OpPayloadAttributes,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+    sync::Arc,
+};
+use tokio::sync::RwLock;
+use url::Url;
+
+/// The error code returned for unknown payload IDs, as defined by the Engine API.
+const UNKNOWN_PAYLOAD_CODE: i32 = -38001;
+
+/// The error code returned when a payload is requested from the wrong `engine_getPayload` version.
+const UNSUPPORTED_FORK_CODE: i32 = -38005;
+
+/// How the [`MockEngineServer`] responds to a kind of Engine API call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MockEngineResponse {
+    /// Process the call like a synced op-geth.
+    #[default]
+    Valid,
+    /// Reject the call with an `INVALID` status.
+    Invalid,
+    /// Answer the call with a `SYNCING` status, like an execution layer that is still syncing.
+    Syncing,
+}
+
+/// An in-process Engine API server that emulates op-geth.
+///
+/// The server keeps an in-memory L2 chain, starting at a genesis block. It builds payloads from
+/// the attributes of `engine_forkchoiceUpdated`, imports them through `engine_newPayload`, and
+/// serves the resulting blocks over `eth_getBlockByNumber` and `eth_getBlockByHash`. Blocks are
+/// not executed: their state and receipts roots are carried over from the parent.
+///
+/// The server does not check the JWT of incoming requests, so any secret can be used to connect.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use kona_engine::test_utils::{MockEngineResponse, MockEngineServer};
+///
+/// let server = MockEngineServer::new(rollup_config.clone());
+/// // The node expects the rollup config to commit to the genesis block of the mock.
+/// rollup_config.genesis.l2.hash = server.genesis_hash();
+///
+/// let handle = server.spawn().await?;
+/// // Point the node's L2 engine at `handle.url()`, then script the execution layer.
+/// handle.set_new_payload_response(MockEngineResponse::Invalid).await;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockEngineServer {
+    /// The rollup config, used to pick the payload version of built blocks.
+    cfg: Arc<RollupConfig>,
+    /// The genesis block of the L2 chain.
+    genesis: OpBlock,
+    /// The response to `engine_newPayload` calls.
+    new_payload_response: MockEngineResponse,
+    /// The response to `engine_forkchoiceUpdated` calls.
+    forkchoice_response: MockEngineResponse,
+}
+
+impl MockEngineServer {
+    /// Creates a new [`MockEngineServer`], with a genesis block derived from the rollup config.
+    pub fn new(cfg: Arc<RollupConfig>) -> Self {
+        let gas_limit = cfg.genesis.system_config.as_ref().map_or(30_000_000, |c| c.gas_limit);
+        let genesis = OpBlock {
+            header: Header {
+                number: cfg.genesis.l2.number,
+                timestamp: cfg.genesis.l2_time,
+                gas_limit,
+                base_fee_per_gas: Some(INITIAL_BASE_FEE),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: vec![],
+                ommers: vec![],
+                withdrawals: cfg.is_canyon_active(cfg.genesis.l2_time).then(Default::default),
+            },
+        };
+        Self {
+            cfg,
+            genesis,
+            new_payload_response: MockEngineResponse::Valid,
+            forkchoice_response: MockEngineResponse::Valid,
+        }
+    }
+
+    /// Sets the genesis block of the L2 chain.
+    pub fn with_genesis(mut self, genesis: OpBlock) -> Self {
+        self.genesis = genesis;
+        self
+    }
+
+    /// Sets the initial response to `engine_newPayload` calls.
+    pub const fn with_new_payload_response(mut self, response: MockEngineResponse) -> Self {
+        self.new_payload_response = response;
+        self
+    }
+
+    /// Sets the initial response to `engine_forkchoiceUpdated` calls.
+    pub const fn with_forkchoice_response(mut self, response: MockEngineResponse) -> Self {
+        self.forkchoice_response = response;
+        self
+    }
+
+    /// Returns the hash of the genesis block.
+    pub fn genesis_hash(&self) -> B256 {
+        self.genesis.header.hash_slow()
+    }
+
+    /// Starts the server on a random local port.
+    pub async fn spawn(self) -> std::io::Result<MockEngineServerHandle> {
+        let genesis_hash = self.genesis_hash();
+        let state = Arc::new(RwLock::new(MockEngineState {
+            cfg: self.cfg,
+            canonical: BTreeMap::from([(self.genesis.header.number, genesis_hash)]),
+            blocks: HashMap::from([(genesis_hash, self.genesis)]),
+            forkchoice: ForkchoiceState {
+                head_block_hash: genesis_hash,
+                safe_block_hash: genesis_hash,
+                finalized_block_hash: genesis_hash,
+            },
+            payloads: HashMap::new(),
+            next_payload_id: 0,
+            new_payload_response: self.new_payload_response,
+            forkchoice_response: self.forkchoice_response,
+        }));
+
+        let rpc = MockEngineRpc { state: state.clone() };
+        let mut module = RpcModule::new(());
+        module.merge(EngineApiServer::into_rpc(rpc.clone())).map_err(std::io::Error::other)?;
+        module.merge(EthApiServer::into_rpc(rpc)).map_err(std::io::Error::other)?;
+
+        let server = Server::builder().build("127.0.0.1:0").await?;
+        let addr = server.local_addr()?;
+        Ok(MockEngineServerHandle { addr, handle: server.start(module), state })
+    }
+}
+
+/// A handle to a running [`MockEngineServer`], used to script its responses and inspect its
+/// chain.
+#[derive(Debug)]
+pub struct MockEngineServerHandle {
+    /// The address the server is bound to.
+    addr: SocketAddr,
+    /// The jsonrpsee server handle.
+    handle: ServerHandle,
+    /// The state of the server.
+    state: Arc<RwLock<MockEngineState>>,
+}
+
+impl MockEngineServerHandle {
+    /// Returns the address the server is bound to.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the HTTP URL of the server.
+    pub fn url(&self) -> Url {
+        Url::parse(&format!("http://{}", self.addr)).expect("Socket addresses are valid URLs")
+    }
+
+    /// Sets the response to subsequent `engine_newPayload` calls.
+    pub async fn set_new_payload_response(&self, response: MockEngineResponse) {
+        self.state.write().await.new_payload_response = response;
+    }
+
+    /// Sets the response to subsequent `engine_forkchoiceUpdated` calls.
+    pub async fn set_forkchoice_response(&self, response: MockEngineResponse) {
+        self.state.write().await.forkchoice_response = response;
+    }
+
+    /// Returns the latest forkchoice state accepted by the server.
+    pub async fn forkchoice(&self) -> ForkchoiceState {
+        self.state.read().await.forkchoice
+    }
+
+    /// Returns the canonical block with the given number.
+    pub async fn block_by_number(&self, number: u64) -> Option<OpBlock> {
+        let state = self.state.read().await;
+        state.canonical.get(&number).and_then(|hash| state.blocks.get(hash)).cloned()
+    }
+
+    /// Stops the server, and waits for it to shut down.
+    pub async fn stop(self) {
+        // The server may already have stopped.
+        let _ = self.handle.stop();
+        self.handle.stopped().await;
+    }
+}
+
+/// The state of a running [`MockEngineServer`].
+#[derive(Debug)]
+struct MockEngineState {
+    /// The rollup config.
+    cfg: Arc<RollupConfig>,
+    /// Every imported block, keyed by hash.
+    blocks: HashMap<B256, OpBlock>,
+    /// The hashes of the canonical chain, keyed by number.
+    canonical: BTreeMap<u64, B256>,
+    /// The latest accepted forkchoice state.
+    forkchoice: ForkchoiceState,
+    /// The built payloads, with their parent beacon block root.
+    payloads: HashMap<PayloadId, (OpExecutionPayload, Option<B256>)>,
+    /// The ID of the next built payload.
+    next_payload_id: u64,
+    /// The response to `engine_newPayload` calls.
+    new_payload_response: MockEngineResponse,
+    /// The response to `engine_forkchoiceUpdated` calls.
+    forkchoice_response: MockEngineResponse,
+}
+
+impl MockEngineState {
+    /// Handles `engine_forkchoiceUpdated`, building a payload if attributes are given.
+    fn forkchoice_updated(
+        &mut self,
+        forkchoice: ForkchoiceState,
+        attributes: Option<OpPayloadAttributes>,
+    ) -> RpcResult<ForkchoiceUpdated> {
+        match self.forkchoice_response {
+            MockEngineResponse::Valid => {}
+            MockEngineResponse::Invalid => {
+                return Ok(ForkchoiceUpdated {
+                    payload_status: invalid(None, "mock: forkchoice update rejected"),
+                    payload_id: None,
+                });
+            }
+            MockEngineResponse::Syncing => return Ok(syncing_forkchoice()),
+        }
+
+        // Like op-geth, report an unknown head as a sync target.
+        if !self.blocks.contains_key(&forkchoice.head_block_hash) {
+            return Ok(syncing_forkchoice());
+        }
+
+        self.canonicalize(forkchoice.head_block_hash);
+        self.forkchoice = forkchoice;
+
+        let payload_id = attributes
+            .map(|attributes| self.build_payload(forkchoice.head_block_hash, attributes))
+            .transpose()?;
+        Ok(ForkchoiceUpdated {
+            payload_status: PayloadStatus {
+                status: PayloadStatusEnum::Valid,
+                latest_valid_hash: Some(forkchoice.head_block_hash),
+            },
+            payload_id,
+        })
+    }
+
+    /// Handles `engine_newPayload`, importing the payload if its parent is known.
+    fn new_payload(
+        &mut self,
+        payload: OpExecutionPayload,
+        parent_beacon_block_root: Option<B256>,
+    ) -> PayloadStatus {
+        let parent_hash = payload_v1(&payload).parent_hash;
+        let block_hash = payload_v1(&payload).block_hash;
+        match self.new_payload_response {
+            MockEngineResponse::Valid => {}
+            MockEngineResponse::Invalid => {
+                return invalid(Some(parent_hash), "mock: payload rejected");
+            }
+            MockEngineResponse::Syncing => return syncing(),
+        }
+
+        let block = match payload_to_block(payload, parent_beacon_block_root, &self.cfg) {
+            Ok(block) => block,
+            Err(e) => return invalid(Some(parent_hash), &e.to_string()),
+        };
+        if block.header.hash_slow() != block_hash {
+            return invalid(Some(parent_hash), "blockhash mismatch");
+        }
+        if !self.blocks.contains_key(&parent_hash) {
+            return syncing();
+        }
+
+        self.blocks.insert(block_hash, block);
+        PayloadStatus { status: PayloadStatusEnum::Valid, latest_valid_hash: Some(block_hash) }
+    }
+
+    /// Returns the payload built under the given ID.
+    fn payload(&self, payload_id: PayloadId) -> RpcResult<(OpExecutionPayload, Option<B256>)> {
+        self.payloads
+            .get(&payload_id)
+            .cloned()
+            .ok_or_else(|| ErrorObject::owned(UNKNOWN_PAYLOAD_CODE, "Unknown payload", None::<()>))
+    }
+
+    /// Returns the block with the given number or tag.
+    fn block_by_number(&self, number: BlockNumberOrTag) -> Option<&OpBlock> {
+        let hash = match number {
+            BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => self.forkchoice.head_block_hash,
+            BlockNumberOrTag::Safe => self.forkchoice.safe_block_hash,
+            BlockNumberOrTag::Finalized => self.forkchoice.finalized_block_hash,
+            BlockNumberOrTag::Earliest => *self.canonical.first_key_value()?.1,
+            BlockNumberOrTag::Number(number) => *self.canonical.get(&number)?,
+        };
+        self.blocks.get(&hash)
+    }
+
+    /// Makes the chain ending at the given head canonical.
+    fn canonicalize(&mut self, head: B256) {
+        let number = self.blocks[&head].header.number;
+        self.canonical.split_off(&(number + 1));
+
+        let mut hash = head;
+        while let Some(block) = self.blocks.get(&hash) {
+            if self.canonical.insert(block.header.number, hash) == Some(hash) {
+                break;
+            }
+            hash = block.header.parent_hash;
+        }
+    }
+
+    /// Builds a payload on top of the given parent, in the version active at its timestamp.
+    fn build_payload(
+        &mut self,
+        parent_hash: B256,
+        attributes: OpPayloadAttributes,
+    ) -> RpcResult<PayloadId> {
+        let parent = &self.blocks[&parent_hash].header;
+        let timestamp = attributes.payload_attributes.timestamp;
+
+        let mut extra_data = Vec::new();
+        if let Some(params) =
+            attributes.eip_1559_params.filter(|_| self.cfg.is_holocene_active(timestamp))
+        {
+            extra_data.push(u8::from(self.cfg.is_jovian_active(timestamp)));
+            extra_data.extend_from_slice(params.as_slice());
+            if self.cfg.is_jovian_active(timestamp) {
+                extra_data.extend(attributes.min_base_fee.unwrap_or_default().to_be_bytes());
+            }
+        }
+
+        let payload_v1 = ExecutionPayloadV1 {
+            parent_hash,
+            fee_recipient: attributes.payload_attributes.suggested_fee_recipient,
+            state_root: parent.state_root,
+            receipts_root: parent.receipts_root,
+            logs_bloom: Bloom::ZERO,
+            prev_randao: attributes.payload_attributes.prev_randao,
+            block_number: parent.number + 1,
+            gas_limit: attributes.gas_limit.unwrap_or(parent.gas_limit),
+            gas_used: 0,
+            timestamp,
+            extra_data: extra_data.into(),
+            base_fee_per_gas: U256::from(parent.base_fee_per_gas.unwrap_or(INITIAL_BASE_FEE)),
+            block_hash: B256::ZERO,
+            transactions: attributes.transactions.unwrap_or_default(),
+        };
+        let mut payload = if self.cfg.is_canyon_active(timestamp) {
+            let payload_v2 = ExecutionPayloadV2 {
+                payload_inner: payload_v1,
+                withdrawals: attributes.payload_attributes.withdrawals.unwrap_or_default(),
+            };
+            if self.cfg.is_ecotone_active(timestamp) {
+                let payload_v3 = ExecutionPayloadV3 {
+                    payload_inner: payload_v2,
+                    blob_gas_used: 0,
+                    excess_blob_gas: 0,
+                };
+                if self.cfg.is_isthmus_active(timestamp) {
+                    OpExecutionPayload::V4(OpExecutionPayloadV4 {
+                        payload_inner: payload_v3,
+                        withdrawals_root: parent.withdrawals_root.unwrap_or_default(),
+                    })
+                } else {
+                    OpExecutionPayload::V3(payload_v3)
+                }
+            } else {
+                OpExecutionPayload::V2(payload_v2)
+            }
+        } else {
+            OpExecutionPayload::V1(payload_v1)
+        };
+
+        // Seal the payload the same way the engine reconstructs blocks from payloads.
+        let parent_beacon_block_root = attributes.payload_attributes.parent_beacon_block_root;
+        payload_v1_mut(&mut payload).block_hash =
+            compute_block_hash(&payload, parent_beacon_block_root, &self.cfg).map_err(|e| {
+                ErrorObject::owned(ErrorCode::InvalidParams.code(), e.to_string(), None::<()>)
+            })?;
+
+        self.next_payload_id += 1;
+        let payload_id = PayloadId::new(self.next_payload_id.to_be_bytes());
+        self.payloads.insert(payload_id, (payload, parent_beacon_block_root));
+        Ok(payload_id)
+    }
+}
+
+/// The Engine API methods served by the [`MockEngineServer`].
+#[rpc(server, namespace = "engine")]
+trait EngineApi {
+    #[method(name = "forkchoiceUpdatedV2")]
+    async fn forkchoice_updated_v2(
+        &self,
+        forkchoice: ForkchoiceState,
+        attributes: Option<OpPayloadAttributes>,
+    ) -> RpcResult<ForkchoiceUpdated>;
+
+    #[method(name = "forkchoiceUpdatedV3")]
+    async fn forkchoice_updated_v3(
+        &self,
+        forkchoice: ForkchoiceState,
+        attributes: Option<OpPayloadAttributes>,
+    ) -> RpcResult<ForkchoiceUpdated>;
+
+    #[method(name = "newPayloadV1")]
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> RpcResult<PayloadStatus>;
+
+    #[method(name = "newPayloadV2")]
+    async fn new_payload_v2(&self, payload: ExecutionPayloadInputV2) -> RpcResult<PayloadStatus>;
+
+    #[method(name = "newPayloadV3")]
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+    ) -> RpcResult<PayloadStatus>;
+
+    #[method(name = "newPayloadV4")]
+    async fn new_payload_v4(
+        &self,
+        payload: OpExecutionPayloadV4,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        execution_requests: Vec<Bytes>,
+    ) -> RpcResult<PayloadStatus>;
+
+    #[method(name = "getPayloadV2")]
+    async fn get_payload_v2(&self, payload_id: PayloadId) -> RpcResult<ExecutionPayloadEnvelopeV2>;
+
+    #[method(name = "getPayloadV3")]
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<OpExecutionPayloadEnvelopeV3>;
+
+    #[method(name = "getPayloadV4")]
+    async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<OpExecutionPayloadEnvelopeV4>;
+}
+
+/// The `eth` namespace methods served by the [`MockEngineServer`].
+#[rpc(server, namespace = "eth")]
+trait EthApi {
+    #[method(name = "chainId")]
+    async fn chain_id(&self) -> RpcResult<U64>;
+
+    #[method(name = "getBlockByNumber")]
+    async fn block_by_number(
+        &self,
+        number: BlockNumberOrTag,
+        full: bool,
+    ) -> RpcResult<Option<Block<OpTransaction>>>;
+
+    #[method(name = "getBlockByHash")]
+    async fn block_by_hash(
+        &self,
+        hash: B256,
+        full: bool,
+    ) -> RpcResult<Option<Block<OpTransaction>>>;
+}
+
+/// Serves the RPC methods of the [`MockEngineServer`].
+#[derive(Debug, Clone)]
+struct MockEngineRpc {
+    /// The state of the server.
+    state: Arc<RwLock<MockEngineState>>,
+}
+
+#[async_trait]
+impl EngineApiServer for MockEngineRpc {
+    async fn forkchoice_updated_v2(
+        &self,
+        forkchoice: ForkchoiceState,
+        attributes: Option<OpPayloadAttributes>,
+    ) -> RpcResult<ForkchoiceUpdated> {
+        self.state.write().await.forkchoice_updated(forkchoice, attributes)
+    }
+
+    async fn forkchoice_updated_v3(
+        &self,
+        forkchoice: ForkchoiceState,
+        attributes: Option<OpPayloadAttributes>,
+    ) -> RpcResult<ForkchoiceUpdated> {
+        self.state.write().await.forkchoice_updated(forkchoice, attributes)
+    }
+
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> RpcResult<PayloadStatus> {
+        Ok(self.state.write().await.new_payload(OpExecutionPayload::V1(payload), None))
+    }
+
+    async fn new_payload_v2(&self, payload: ExecutionPayloadInputV2) -> RpcResult<PayloadStatus> {
+        let payload = match payload.withdrawals {
+            Some(withdrawals) => OpExecutionPayload::V2(ExecutionPayloadV2 {
+                payload_inner: payload.execution_payload,
+                withdrawals,
+            }),
+            None => OpExecutionPayload::V1(payload.execution_payload),
+        };
+        Ok(self.state.write().await.new_payload(payload, None))
+    }
+
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        _versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+    ) -> RpcResult<PayloadStatus> {
+        Ok(self
+            .state
+            .write()
+            .await
+            .new_payload(OpExecutionPayload::V3(payload), Some(parent_beacon_block_root)))
+    }
+
+    async fn new_payload_v4(
+        &self,
+        payload: OpExecutionPayloadV4,
+        _versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        _execution_requests: Vec<Bytes>,
+    ) -> RpcResult<PayloadStatus> {
+        Ok(self
+            .state
+            .write()
+            .await
+            .new_payload(OpExecutionPayload::V4(payload), Some(parent_beacon_block_root)))
+    }
+
+    async fn get_payload_v2(&self, payload_id: PayloadId) -> RpcResult<ExecutionPayloadEnvelopeV2> {
+        let execution_payload = match self.state.read().await.payload(payload_id)?.0 {
+            OpExecutionPayload::V1(payload) => ExecutionPayloadFieldV2::V1(payload),
+            OpExecutionPayload::V2(payload) => ExecutionPayloadFieldV2::V2(payload),
+            _ => return Err(unsupported_fork()),
+        };
+        Ok(ExecutionPayloadEnvelopeV2 { execution_payload, block_value: U256::ZERO })
+    }
+
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<OpExecutionPayloadEnvelopeV3> {
+        let (OpExecutionPayload::V3(execution_payload), parent_beacon_block_root) =
+            self.state.read().await.payload(payload_id)?
+        else {
+            return Err(unsupported_fork());
+        };
+        Ok(OpExecutionPayloadEnvelopeV3 {
+            execution_payload,
+            block_value: U256::ZERO,
+            blobs_bundle: empty_blobs_bundle(),
+            should_override_builder: false,
+            parent_beacon_block_root: parent_beacon_block_root.unwrap_or_default(),
+        })
+    }
+
+    async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<OpExecutionPayloadEnvelopeV4> {
+        let (OpExecutionPayload::V4(execution_payload), parent_beacon_block_root) =
+            self.state.read().await.payload(payload_id)?
+        else {
+            return Err(unsupported_fork());
+        };
+        Ok(OpExecutionPayloadEnvelopeV4 {
+            execution_payload,
+            block_value: U256::ZERO,
+            blobs_bundle: empty_blobs_bundle(),
+            should_override_builder: false,
+            parent_beacon_block_root: parent_beacon_block_root.unwrap_or_default(),
+            execution_requests: vec![],
+        })
+    }
+}
+
+#[async_trait]
+impl EthApiServer for MockEngineRpc {
+    async fn chain_id(&self) -> RpcResult<U64> {
+        Ok(U64::from(self.state.read().await.cfg.l2_chain_id.id()))
+    }
+
+    async fn block_by_number(
+        &self,
+        number: BlockNumberOrTag,
+        full: bool,
+    ) -> RpcResult<Option<Block<OpTransaction>>> {
+        Ok(self.state.read().await.block_by_number(number).map(|block| rpc_block(block, full)))
+    }
+
+    async fn block_by_hash(
+        &self,
+        hash: B256,
+        full: bool,
+    ) -> RpcResult<Option<Block<OpTransaction>>> {
+        Ok(self.state.read().await.blocks.get(&hash).map(|block| rpc_block(block, full)))
+    }
+}
+
+/// Returns the V1 fields of the payload.
+const fn payload_v1(payload: &OpExecutionPayload) -> &ExecutionPayloadV1 {
+    match payload {
+        OpExecutionPayload::V1(payload) => payload,
+        OpExecutionPayload::V2(payload) => &payload.payload_inner,
+        OpExecutionPayload::V3(payload) => &payload.payload_inner.payload_inner,
+        OpExecutionPayload::V4(payload) => &payload.payload_inner.payload_inner.payload_inner,
+    }
+}
+
+/// Returns the V1 fields of the payload, mutably.
+const fn payload_v1_mut(payload: &mut OpExecutionPayload) -> &mut ExecutionPayloadV1 {
+    match payload {
+        OpExecutionPayload::V1(payload) => payload,
+        OpExecutionPayload::V2(payload) => &mut payload.payload_inner,
+        OpExecutionPayload::V3(payload) => &mut payload.payload_inner.payload_inner,
+        OpExecutionPayload::V4(payload) => &mut payload.payload_inner.payload_inner.payload_inner,
+    }
+}
+
+/// Renders a block as an `eth_getBlockBy*` response.
+///
+/// Signatures are not recovered: non-deposit transactions are reported as sent from the zero
+/// address.
+fn rpc_block(block: &OpBlock, full: bool) -> Block<OpTransaction> {
+    let hash = block.header.hash_slow();
+    let transactions = if full {
+        BlockTransactions::Full(
+            block
+                .body
+                .transactions
+                .iter()
+                .enumerate()
+                .map(|(index, tx)| OpTransaction {
+                    inner: alloy_rpc_types_eth::Transaction {
+                        inner: Recovered::new_unchecked(
+                            tx.clone(),
+                            tx.as_deposit().map(|deposit| deposit.from).unwrap_or_default(),
+                        ),
+                        block_hash: Some(hash),
+                        block_number: Some(block.header.number),
+                        transaction_index: Some(index as u64),
+                        effective_gas_price: None,
+                    },
+                    deposit_nonce: None,
+                    deposit_receipt_version: None,
+                })
+                .collect(),
+        )
+    } else {
+        BlockTransactions::Hashes(block.body.transactions.iter().map(|tx| tx.tx_hash()).collect())
+    };
+
+    Block {
+        header: RpcHeader {
+            hash,
+            inner: block.header.clone(),
+            total_difficulty: Some(U256::ZERO),
+            size: None,
+        },
+        uncles: vec![],
+        transactions,
+        withdrawals: block.body.withdrawals.clone(),
+    }
+}
+
+/// Returns an `INVALID` payload status.
+fn invalid(latest_valid_hash: Option<B256>, error: &str) -> PayloadStatus {
+    PayloadStatus {
+        status: PayloadStatusEnum::Invalid { validation_error: error.to_string() },
+        latest_valid_hash,
+    }
+}
+
+/// Returns a `SYNCING` payload status.
+const fn syncing() -> PayloadStatus {
+    PayloadStatus { status: PayloadStatusEnum::Syncing, latest_valid_hash: None }
+}
+
+/// Returns a `SYNCING` forkchoice update response.
+const fn syncing_forkchoice() -> ForkchoiceUpdated {
+    ForkchoiceUpdated { payload_status: syncing(), payload_id: None }
+}
+
+/// Returns the error for a payload requested from the wrong `engine_getPayload` version.
+fn unsupported_fork() -> ErrorObjectOwned {
+    ErrorObject::owned(UNSUPPORTED_FORK_CODE, "Unsupported fork", None::<()>)
+}
+
+/// Returns an empty blobs bundle. OP Stack payloads never carry blobs.
+const fn empty_blobs_bundle() -> BlobsBundleV1 {
+    BlobsBundleV1 { commitments: vec![], proofs: vec![], blobs: vec![] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HyperAuthClient, OpEngineClient};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_provider::{Provider, RootProvider};
+    use alloy_rpc_types_engine::{JwtSecret, PayloadAttributes};
+    use alloy_transport_http::Http;
+    use kona_genesis::{L1ChainConfig, SystemConfig};
+    use kona_protocol::{L1BlockInfoTx, L2BlockInfo};
+    use op_alloy_network::Optimism;
+    use op_alloy_provider::ext::engine::OpEngineApi;
+
+    type Client = RootProvider<Optimism>;
+
+    fn attributes(cfg: &RollupConfig, timestamp: u64) -> OpPayloadAttributes {
+        let (_, l1_info) = L1BlockInfoTx::try_new_with_deposit_tx(
+            cfg,
+            &L1ChainConfig::default(),
+            &SystemConfig::default(),
+            0,
+            &Header::default(),
+            timestamp,
+        )
+        .unwrap();
+        OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Default::default(),
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: Some(vec![l1_info.encoded_2718().into()]),
+            no_tx_pool: Some(true),
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+            min_base_fee: None,
+        }
+    }
+
+    fn forkchoice(head: B256) -> ForkchoiceState {
+        ForkchoiceState { head_block_hash: head, safe_block_hash: head, finalized_block_hash: head }
+    }
+
+    async fn spawn() -> (Arc<RollupConfig>, MockEngineServerHandle, Client) {
+        let mut cfg = RollupConfig { block_time: 2, ..Default::default() };
+        let server = MockEngineServer::new(Arc::new(cfg.clone()));
+        cfg.genesis.l2.hash = server.genesis_hash();
+
+        let handle = server.spawn().await.unwrap();
+        let client = OpEngineClient::<RootProvider, Client>::rpc_client::<Optimism>(
+            handle.url(),
+            JwtSecret::random(),
+        );
+        (Arc::new(cfg), handle, client)
+    }
+
+    /// Builds a payload on top of the head, and returns it.
+    async fn build(cfg: &RollupConfig, client: &Client, head: B256) -> ExecutionPayloadV1 {
+        let parent = client.get_block_by_hash(head).await.unwrap().unwrap();
+        let attributes = attributes(cfg, parent.header.timestamp + cfg.block_time);
+        let updated =
+            <Client as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v2(
+                client,
+                forkchoice(head),
+                Some(attributes),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.payload_status.status, PayloadStatusEnum::Valid);
+
+        let envelope = <Client as OpEngineApi<Optimism, Http<HyperAuthClient>>>::get_payload_v2(
+            client,
+            updated.payload_id.unwrap(),
+        )
+        .await
+        .unwrap();
+        let ExecutionPayloadFieldV2::V1(payload) = envelope.execution_payload else {
+            panic!("Expected a pre-Canyon payload");
+        };
+        payload
+    }
+
+    async fn import(client: &Client, payload: ExecutionPayloadV1) -> PayloadStatusEnum {
+        <Client as OpEngineApi<Optimism, Http<HyperAuthClient>>>::new_payload_v2(
+            client,
+            ExecutionPayloadInputV2 { execution_payload: payload, withdrawals: None },
+        )
+        .await
+        .unwrap()
+        .status
+    }
+
+    #[tokio::test]
+    async fn test_builds_and_imports_payloads() {
+        let (cfg, handle, client) = spawn().await;
+        let genesis = cfg.genesis.l2.hash;
+
+        let payload = build(&cfg, &client, genesis).await;
+        assert_eq!(payload.parent_hash, genesis);
+        assert_eq!(payload.block_number, 1);
+        assert_eq!(import(&client, payload.clone()).await, PayloadStatusEnum::Valid);
+
+        // The block is only canonical once the forkchoice moves onto it.
+        assert!(handle.block_by_number(1).await.is_none());
+        <Client as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v2(
+            &client,
+            forkchoice(payload.block_hash),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(handle.forkchoice().await, forkchoice(payload.block_hash));
+
+        // The engine reads the new head back the same way it does from op-geth.
+        let head = client.get_block_by_number(BlockNumberOrTag::Latest).full().await.unwrap();
+        let head =
+            L2BlockInfo::from_block_and_genesis(&head.unwrap().into_consensus(), &cfg.genesis)
+                .unwrap();
+        assert_eq!(head.block_info.hash, payload.block_hash);
+        assert_eq!(head.block_info.number, 1);
+        assert_eq!(head.block_info.parent_hash, genesis);
+
+        handle.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_scripted_responses() {
+        let (cfg, handle, client) = spawn().await;
+        let genesis = cfg.genesis.l2.hash;
+        let payload = build(&cfg, &client, genesis).await;
+
+        handle.set_new_payload_response(MockEngineResponse::Invalid).await;
+        assert!(matches!(
+            import(&client, payload.clone()).await,
+            PayloadStatusEnum::Invalid { .. }
+        ));
+
+        handle.set_new_payload_response(MockEngineResponse::Syncing).await;
+        assert_eq!(import(&client, payload.clone()).await, PayloadStatusEnum::Syncing);
+
+        handle.set_forkchoice_response(MockEngineResponse::Syncing).await;
+        let updated =
+            <Client as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v2(
+                &client,
+                forkchoice(genesis),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.payload_status.status, PayloadStatusEnum::Syncing);
+
+        handle.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_unknown_head_is_syncing() {
+        let (_, handle, client) = spawn().await;
+
+        let updated =
+            <Client as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v2(
+                &client,
+                forkchoice(B256::repeat_byte(0xff)),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.payload_status.status, PayloadStatusEnum::Syncing);
+        assert!(updated.payload_id.is_none());
+
+        handle.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_rejects_tampered_payloads() {
+        let (cfg, handle, client) = spawn().await;
+        let mut payload = build(&cfg, &client, cfg.genesis.l2.hash).await;
+        payload.gas_used += 1;
+
+        assert!(matches!(import(&client, payload).await, PayloadStatusEnum::Invalid { .. }));
+        handle.stop().await;
+    }
+}
@@ -8,6 +8,7 @@ use kona_protocol::{BlockInfo, L2BlockInfo};
 pub struct TestEngineStateBuilder {
     unsafe_head: L2BlockInfo,
     cross_unsafe_head: Option<L2BlockInfo>,
+    pending_safe_head: Option<L2BlockInfo>,
     local_safe_head: Option<L2BlockInfo>,
     safe_head: Option<L2BlockInfo>,
     finalized_head: Option<L2BlockInfo>,
@@ -32,6 +33,7 @@ impl TestEngineStateBuilder {
         Self {
             unsafe_head: genesis,
             cross_unsafe_head: None,
+            pending_safe_head: None,
             local_safe_head: None,
             safe_head: None,
             finalized_head: None,
@@ -52,6 +54,13 @@ impl TestEngineStateBuilder {
         self
     }
 
+    /// Sets the pending safe head
+    #[allow(dead_code)]
+    pub const fn with_pending_safe_head(mut self, block: L2BlockInfo) -> Self {
+        self.pending_safe_head = Some(block);
+        self
+    }
+
     /// Sets the safe head
     pub const fn with_safe_head(mut self, block: L2BlockInfo) -> Self {
         self.safe_head = Some(block);
@@ -76,11 +85,13 @@ impl TestEngineStateBuilder {
         let mut state = EngineState::default();
 
         // Set unsafe head (required)
+        let safe_head = self.safe_head.unwrap_or(self.unsafe_head);
         state.sync_state = state.sync_state.apply_update(EngineSyncStateUpdate {
             unsafe_head: Some(self.unsafe_head),
             cross_unsafe_head: Some(self.cross_unsafe_head.unwrap_or(self.unsafe_head)),
+            pending_safe_head: Some(self.pending_safe_head.unwrap_or(safe_head)),
             local_safe_head: Some(self.local_safe_head.unwrap_or(self.unsafe_head)),
-            safe_head: Some(self.safe_head.unwrap_or(self.unsafe_head)),
+            safe_head: Some(safe_head),
             finalized_head: Some(self.finalized_head.unwrap_or(self.unsafe_head)),
         });
 
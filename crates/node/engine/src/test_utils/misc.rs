@@ -1,6 +1,10 @@
+use alloy_consensus::{Sealed, transaction::Recovered};
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
-use kona_protocol::{BlockInfo, L2BlockInfo};
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_eth::{Block, BlockTransactions};
+use kona_protocol::{BlockInfo, L1BlockInfoTx, L2BlockInfo};
+use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
+use op_alloy_rpc_types::Transaction as OpTransaction;
 
 /// Helper to create a test L2BlockInfo at a specific block number
 pub fn test_block_info(number: u64) -> L2BlockInfo {
@@ -15,3 +19,35 @@ pub fn test_block_info(number: u64) -> L2BlockInfo {
         seq_num: 0,
     }
 }
+
+/// Helper to create the L1 info deposit that test blocks start with
+pub fn test_l1_info_deposit() -> OpTxEnvelope {
+    OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+        input: L1BlockInfoTx::Bedrock(Default::default()).encode_calldata(),
+        ..Default::default()
+    }))
+}
+
+/// Helper to create an unsafe block at a specific block number, extending `parent_hash` and
+/// holding only the L1 info deposit
+pub fn test_unsafe_block(number: u64, parent_hash: B256) -> Block<OpTransaction> {
+    let mut block = Block::<OpTransaction> {
+        transactions: BlockTransactions::Full(vec![OpTransaction {
+            inner: alloy_rpc_types_eth::Transaction {
+                inner: Recovered::new_unchecked(test_l1_info_deposit(), Address::ZERO),
+                block_hash: None,
+                block_number: Some(number),
+                transaction_index: Some(0),
+                effective_gas_price: None,
+            },
+            deposit_nonce: None,
+            deposit_receipt_version: None,
+        }]),
+        ..Default::default()
+    };
+    block.header.inner.number = number;
+    block.header.inner.parent_hash = parent_hash;
+    block.header.inner.timestamp = number * 2;
+    block.header.hash = block.header.inner.hash_slow();
+    block
+}
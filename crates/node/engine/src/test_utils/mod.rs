@@ -3,14 +3,18 @@ pub use attributes::TestAttributesBuilder;
 
 mod engine_client;
 pub use engine_client::{
-    MockEngineClient, MockEngineClientBuilder, MockEngineStorage, test_engine_client_builder,
+    MockEngineCall, MockEngineClient, MockEngineClientBuilder, MockEngineStorage,
+    test_engine_client_builder,
 };
 
+mod engine_server;
+pub use engine_server::{MockEngineResponse, MockEngineServer, MockEngineServerHandle};
+
 mod engine_state;
 pub use engine_state::TestEngineStateBuilder;
 
 mod misc;
-pub use misc::test_block_info;
+pub use misc::{test_block_info, test_l1_info_deposit, test_unsafe_block};
 
 mod provider;
 pub use provider::{MockL1Provider, MockL2Provider};
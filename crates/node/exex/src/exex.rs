@@ -0,0 +1,112 @@
+//! The [`L1ExEx`] execution extension.
+
+use crate::ExExL1Source;
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use futures::TryStreamExt;
+use kona_protocol::BlockInfo;
+use reth_exex::{ExExContext, ExExEvent, ExExNotification};
+use reth_node_api::FullNodeComponents;
+use reth_provider::{BlockIdReader, HeaderProvider};
+use tokio::sync::watch;
+
+/// A reth execution extension that publishes the canonical L1 head and the finalized L1 block of
+/// the node it is installed on.
+///
+/// The published blocks are consumed by the rollup node through the [`ExExL1Source`] returned by
+/// [`L1ExEx::new`]. Reorgs and reverts of the L1 chain are published as soon as reth commits them,
+/// without waiting for the next poll of the L1 RPC.
+#[derive(Debug)]
+pub struct L1ExEx<Node: FullNodeComponents> {
+    /// The execution extension context.
+    ctx: ExExContext<Node>,
+    /// Publishes the canonical L1 head.
+    head_tx: watch::Sender<Option<BlockInfo>>,
+    /// Publishes the finalized L1 block.
+    finalized_tx: watch::Sender<Option<BlockInfo>>,
+}
+
+impl<Node: FullNodeComponents> L1ExEx<Node> {
+    /// Creates a new [`L1ExEx`], along with the [`ExExL1Source`] receiving its updates and reading
+    /// L1 data from the provider of the node.
+    pub fn new(ctx: ExExContext<Node>) -> (Self, ExExL1Source<Node::Provider>) {
+        let (head_tx, head_rx) = watch::channel(None);
+        let (finalized_tx, finalized_rx) = watch::channel(None);
+        let source = ExExL1Source::new(head_rx, finalized_rx, ctx.provider().clone());
+        (Self { ctx, head_tx, finalized_tx }, source)
+    }
+
+    /// Runs the execution extension until the notification stream ends.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        while let Some(notification) = self.ctx.notifications.try_next().await? {
+            let head = match &notification {
+                ExExNotification::ChainCommitted { new } => {
+                    let tip = new.tip();
+                    Some(block_info(tip.hash(), tip.header()))
+                }
+                ExExNotification::ChainReorged { old, new } => {
+                    let tip = new.tip();
+                    warn!(
+                        target: "exex",
+                        old_head = %old.tip().hash(),
+                        new_head = %tip.hash(),
+                        depth = old.len(),
+                        "L1 chain reorged"
+                    );
+                    Some(block_info(tip.hash(), tip.header()))
+                }
+                ExExNotification::ChainReverted { old } => {
+                    let parent = old.first().header().number().saturating_sub(1);
+                    warn!(
+                        target: "exex",
+                        old_head = %old.tip().hash(),
+                        new_head = parent,
+                        depth = old.len(),
+                        "L1 chain reverted"
+                    );
+                    self.ctx
+                        .provider()
+                        .sealed_header(parent)?
+                        .map(|header| block_info(header.hash(), header.header()))
+                }
+            };
+
+            if let Some(head) = head {
+                debug!(target: "exex", number = head.number, hash = %head.hash, "New L1 head");
+                self.head_tx.send_replace(Some(head));
+            }
+            self.update_finalized()?;
+
+            if let Some(committed) = notification.committed_chain() {
+                let tip = committed.tip();
+                self.ctx.events.send(ExExEvent::FinishedHeight(BlockNumHash::new(
+                    tip.header().number(),
+                    tip.hash(),
+                )))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Publishes the finalized L1 block of the node, if it changed.
+    fn update_finalized(&self) -> eyre::Result<()> {
+        let Some(finalized) = self.ctx.provider().finalized_block_num_hash()? else {
+            return Ok(());
+        };
+        if self.finalized_tx.borrow().is_some_and(|block| block.hash == finalized.hash) {
+            return Ok(());
+        }
+
+        if let Some(header) = self.ctx.provider().sealed_header(finalized.number)? {
+            self.finalized_tx.send_replace(Some(block_info(header.hash(), header.header())));
+        }
+        Ok(())
+    }
+}
+
+/// Builds the [`BlockInfo`] of the block with the given hash and header.
+fn block_info(hash: B256, header: &impl BlockHeader) -> BlockInfo {
+    BlockInfo::new(hash, header.number(), header.parent_hash(), header.timestamp())
+}
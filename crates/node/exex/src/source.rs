@@ -0,0 +1,163 @@
+//! The [`ExExL1Source`], an [`L1BlockSource`] fed by the [`L1ExEx`].
+//!
+//! [`L1ExEx`]: crate::L1ExEx
+
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_primitives::B256;
+use futures::{StreamExt, future, stream::BoxStream};
+use kona_node_service::L1BlockSource;
+use kona_protocol::BlockInfo;
+use reth_provider::{BlockReader, ProviderResult};
+use std::fmt::Debug;
+use tokio::sync::watch;
+use tokio_stream::wrappers::WatchStream;
+
+/// An [`L1BlockSource`] yielding the L1 blocks published by the [`L1ExEx`], and serving the
+/// headers, receipts and transactions read by the derivation pipeline from the database of the
+/// reth node.
+///
+/// Blocks missing from the database, e.g. while the node is syncing, are fetched from the L1 RPC.
+///
+/// [`L1ExEx`]: crate::L1ExEx
+#[derive(Debug, Clone)]
+pub struct ExExL1Source<P> {
+    /// The latest canonical L1 head.
+    head: watch::Receiver<Option<BlockInfo>>,
+    /// The latest finalized L1 block.
+    finalized: watch::Receiver<Option<BlockInfo>>,
+    /// The provider of the reth node.
+    provider: P,
+}
+
+impl<P> ExExL1Source<P> {
+    /// Creates a new [`ExExL1Source`] from the channels the [`L1ExEx`] publishes to, reading L1
+    /// data from the given reth provider.
+    ///
+    /// [`L1ExEx`]: crate::L1ExEx
+    pub(crate) const fn new(
+        head: watch::Receiver<Option<BlockInfo>>,
+        finalized: watch::Receiver<Option<BlockInfo>>,
+        provider: P,
+    ) -> Self {
+        Self { head, finalized, provider }
+    }
+
+    /// Returns a stream yielding the current block of the channel, if any, and every update.
+    fn stream(rx: &watch::Receiver<Option<BlockInfo>>) -> BoxStream<'static, BlockInfo> {
+        WatchStream::new(rx.clone()).filter_map(future::ready).boxed()
+    }
+}
+
+impl<P> L1BlockSource for ExExL1Source<P>
+where
+    P: BlockReader<
+            Block = reth_ethereum_primitives::Block,
+            Header = Header,
+            Receipt = reth_ethereum_primitives::Receipt,
+        > + Debug
+        + Send
+        + Sync,
+{
+    fn head_stream(&self) -> BoxStream<'static, BlockInfo> {
+        Self::stream(&self.head)
+    }
+
+    fn finalized_stream(&self) -> BoxStream<'static, BlockInfo> {
+        Self::stream(&self.finalized)
+    }
+
+    /// Returns all the transactions of the block, including those sent to the batch inbox.
+    fn batch_inbox_transactions(&self, block_hash: B256) -> Option<(BlockInfo, Vec<TxEnvelope>)> {
+        let block = read("block", self.provider.block_by_hash(block_hash))?;
+        let block_info = BlockInfo::new(
+            block_hash,
+            block.header.number,
+            block.header.parent_hash,
+            block.header.timestamp,
+        );
+        let transactions =
+            block.body.transactions.into_iter().map(|tx| tx.map_eip4844(Into::into)).collect();
+        Some((block_info, transactions))
+    }
+
+    fn header(&self, block_hash: B256) -> Option<Header> {
+        read("header", self.provider.header(&block_hash))
+    }
+
+    fn block_info_by_number(&self, number: u64) -> Option<BlockInfo> {
+        let header = read("header", self.provider.sealed_header(number))?;
+        Some(BlockInfo::new(header.hash(), number, header.parent_hash, header.timestamp))
+    }
+
+    fn receipts(&self, block_hash: B256) -> Option<Vec<Receipt>> {
+        let receipts = read("receipts", self.provider.receipts_by_block(block_hash.into()))?;
+        Some(receipts.into_iter().map(into_consensus_receipt).collect())
+    }
+}
+
+/// Returns the value read from the reth database, if any. Errors are logged, so that the data is
+/// fetched from the L1 RPC instead.
+fn read<T>(what: &'static str, result: ProviderResult<Option<T>>) -> Option<T> {
+    result
+        .inspect_err(|e| warn!(target: "exex", what, "Failed to read from the database: {e}"))
+        .ok()
+        .flatten()
+}
+
+/// Converts a reth [`reth_ethereum_primitives::Receipt`] into a consensus [`Receipt`].
+fn into_consensus_receipt(receipt: reth_ethereum_primitives::Receipt) -> Receipt {
+    Receipt {
+        status: receipt.success.into(),
+        cumulative_gas_used: receipt.cumulative_gas_used,
+        logs: receipt.logs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxReceipt;
+    use alloy_primitives::{Address, Log};
+
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo { number, hash: B256::with_last_byte(number as u8), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_streams_yield_published_blocks() {
+        let (head_tx, head_rx) = watch::channel(None);
+        let (finalized_tx, finalized_rx) = watch::channel(Some(block(1)));
+        let source = ExExL1Source::new(head_rx, finalized_rx, ());
+
+        let mut head = ExExL1Source::<()>::stream(&source.head);
+        let mut finalized = ExExL1Source::<()>::stream(&source.finalized);
+        assert_eq!(finalized.next().await, Some(block(1)));
+
+        head_tx.send_replace(Some(block(10)));
+        assert_eq!(head.next().await, Some(block(10)));
+
+        // A reorg publishes a new head at a lower height.
+        head_tx.send_replace(Some(block(9)));
+        assert_eq!(head.next().await, Some(block(9)));
+
+        finalized_tx.send_replace(Some(block(2)));
+        assert_eq!(finalized.next().await, Some(block(2)));
+    }
+
+    #[test]
+    fn test_into_consensus_receipt() {
+        let log =
+            Log::new_unchecked(Address::repeat_byte(0xAA), vec![B256::ZERO], Default::default());
+        let receipt = reth_ethereum_primitives::Receipt {
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![log.clone()],
+            ..Default::default()
+        };
+
+        let receipt = into_consensus_receipt(receipt);
+        assert!(receipt.status());
+        assert_eq!(receipt.cumulative_gas_used, 21_000);
+        assert_eq!(receipt.logs, vec![log]);
+    }
+}
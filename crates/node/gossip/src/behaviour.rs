@@ -43,8 +43,11 @@ pub struct Behaviour {
 impl Behaviour {
     /// Configures the swarm behaviors, subscribes to the gossip topics, and returns a new
     /// [`Behaviour`].
+    ///
+    /// The L2 chain id is advertised to peers in the identify protocol version.
     pub fn new(
         public_key: libp2p::identity::PublicKey,
+        chain_id: u64,
         cfg: Config,
         handlers: &[Box<dyn Handler>],
     ) -> Result<Self, BehaviourError> {
@@ -54,7 +57,7 @@ impl Behaviour {
             .map_err(|_| BehaviourError::GossipsubCreationFailed)?;
 
         let identify = libp2p::identify::Behaviour::new(
-            libp2p::identify::Config::new("".to_string(), public_key)
+            libp2p::identify::Config::new(crate::opstack_protocol_version(chain_id), public_key)
                 .with_agent_version("kona".to_string()),
         );
 
@@ -111,7 +114,7 @@ mod tests {
         let key = libp2p::identity::Keypair::generate_secp256k1();
         let cfg = config::default_config();
         let handlers = vec![];
-        let _ = Behaviour::new(key.public(), cfg, &handlers).unwrap();
+        let _ = Behaviour::new(key.public(), 10, cfg, &handlers).unwrap();
     }
 
     #[test]
@@ -124,7 +127,7 @@ mod tests {
             recv,
         );
         let handlers: Vec<Box<dyn Handler>> = vec![Box::new(block_handler)];
-        let behaviour = Behaviour::new(key.public(), cfg, &handlers).unwrap();
+        let behaviour = Behaviour::new(key.public(), 10, cfg, &handlers).unwrap();
         let mut topics = behaviour.gossipsub.topics().cloned().collect::<Vec<TopicHash>>();
         topics.sort();
         assert_eq!(topics, op_mainnet_topics());
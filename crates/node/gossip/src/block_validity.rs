@@ -2,13 +2,12 @@
 use std::time::Instant;
 use std::time::SystemTime;
 
-use alloy_consensus::Block;
-use alloy_eips::eip7685::EMPTY_REQUESTS_HASH;
 use alloy_primitives::{Address, B256};
 use alloy_rpc_types_engine::{ExecutionPayloadV3, PayloadError};
+use derive_more::{Display, FromStr};
 use kona_genesis::RollupConfig;
+use kona_protocol::compute_block_hash;
 use libp2p::gossipsub::MessageAcceptance;
-use op_alloy_consensus::OpTxEnvelope;
 use op_alloy_rpc_types_engine::{
     OpExecutionPayload, OpExecutionPayloadV4, OpNetworkPayloadEnvelope, OpPayloadError,
 };
@@ -17,6 +16,27 @@ use super::BlockHandler;
 #[cfg(feature = "metrics")]
 use crate::Metrics;
 
+/// How strictly blocks received over gossip are validated before they are forwarded to the engine.
+///
+/// Recomputing the block hash of a payload is expensive for high-throughput chains. Nodes that only
+/// relay blocks can skip it, leaving the payload to be validated by the execution layer on
+/// insertion.
+#[derive(Debug, FromStr, Display, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadValidation {
+    /// The block hash is recomputed from the payload, and the signature is checked.
+    #[default]
+    Strict,
+    /// Only the signature is checked. The block hash is trusted as received.
+    Lenient,
+}
+
+impl PayloadValidation {
+    /// Returns `true` if the block hash is recomputed from the payload.
+    pub const fn recomputes_block_hash(&self) -> bool {
+        matches!(self, Self::Strict)
+    }
+}
+
 /// Error that can occur when validating a block.
 #[derive(Debug, thiserror::Error)]
 pub enum BlockInvalidError {
@@ -207,17 +227,17 @@ impl BlockHandler {
             });
         }
 
-        // CHECK: Ensure the block hash is valid.
-        let expected = envelope.payload.block_hash();
-        let mut block: Block<OpTxEnvelope> = envelope.payload.clone().try_into_block()?;
-        block.header.parent_beacon_block_root = envelope.parent_beacon_block_root;
-        // If isthmus is active, set the requests hash to the empty hash.
-        if self.rollup_config.is_isthmus_active(envelope.payload.timestamp()) {
-            block.header.requests_hash = Some(EMPTY_REQUESTS_HASH);
-        }
-        let received = block.header.hash_slow();
-        if received != expected {
-            return Err(BlockInvalidError::BlockHash { expected, received });
+        // CHECK: Ensure the block hash is valid, unless the payload validation is lenient.
+        if self.validation.recomputes_block_hash() {
+            let expected = envelope.payload.block_hash();
+            let received = compute_block_hash(
+                &envelope.payload,
+                envelope.parent_beacon_block_root,
+                &self.rollup_config,
+            )?;
+            if received != expected {
+                return Err(BlockInvalidError::BlockHash { expected, received });
+            }
         }
 
         // CHECK: The payload is valid for the specific version of this block.
@@ -552,6 +572,35 @@ pub(crate) mod tests {
         assert!(matches!(handler.block_valid(&envelope), Err(BlockInvalidError::BlockHash { .. })));
     }
 
+    /// Ensures a block with an invalid hash is accepted when the payload validation is lenient
+    #[test]
+    fn test_block_invalid_hash_lenient() {
+        let block = v1_valid_block();
+
+        let mut v1 = ExecutionPayloadV1::from_block_slow(&block);
+
+        v1.block_hash = B256::ZERO;
+
+        let payload = OpExecutionPayload::V1(v1);
+        let envelope = OpNetworkPayloadEnvelope {
+            payload,
+            signature: Signature::test_signature(),
+            payload_hash: PayloadHash(B256::ZERO),
+            parent_beacon_block_root: None,
+        };
+
+        let msg = envelope.payload_hash.signature_message(10);
+        let signer = envelope.signature.recover_address_from_prehash(&msg).unwrap();
+        let (_, unsafe_signer) = tokio::sync::watch::channel(signer);
+        let mut handler = BlockHandler::new(
+            RollupConfig { l2_chain_id: Chain::optimism_mainnet(), ..Default::default() },
+            unsafe_signer,
+        )
+        .with_validation(PayloadValidation::Lenient);
+
+        assert!(handler.block_valid(&envelope).is_ok());
+    }
+
     #[test]
     fn test_cannot_validate_same_block_twice() {
         let block = v1_valid_block();
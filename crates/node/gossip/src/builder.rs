@@ -10,7 +10,10 @@ use libp2p::{
 use std::time::Duration;
 use tokio::sync::watch::{self};
 
-use crate::{Behaviour, BlockHandler, GaterConfig, GossipDriver, GossipDriverBuilderError};
+use crate::{
+    Behaviour, BlockHandler, DEFAULT_RECENT_PAYLOADS_CAPACITY, GaterConfig, GossipDriver,
+    GossipDriverBuilderError, LatencyPreference, PayloadValidation, PeerTargets, RecentPayloads,
+};
 
 /// A builder for the [`GossipDriver`].
 #[derive(Debug)]
@@ -21,6 +24,8 @@ pub struct GossipDriverBuilder {
     keypair: Keypair,
     /// The [`Multiaddr`] for the gossip driver to listen on.
     gossip_addr: Multiaddr,
+    /// The QUIC [`Multiaddr`] for the gossip driver to listen on, if any.
+    quic_addr: Option<Multiaddr>,
     /// Unsafe block signer [`Address`].
     signer: Address,
     /// The idle connection timeout as a [`Duration`].
@@ -34,8 +39,16 @@ pub struct GossipDriverBuilder {
     peer_monitoring: Option<PeerMonitoring>,
     /// The configuration for the connection gater.
     gater_config: Option<GaterConfig>,
+    /// The target number of connected peers.
+    peer_targets: Option<PeerTargets>,
+    /// The preference for low-latency peers.
+    latency_preference: Option<LatencyPreference>,
     /// Topic scoring. Disabled by default.
     topic_scoring: bool,
+    /// How strictly blocks received over gossip are validated.
+    payload_validation: PayloadValidation,
+    /// The number of recent unsafe payloads kept to serve late-joining peers.
+    recent_payloads_capacity: usize,
 }
 
 impl GossipDriverBuilder {
@@ -50,22 +63,53 @@ impl GossipDriverBuilder {
             timeout: None,
             keypair,
             gossip_addr,
+            quic_addr: None,
             signer,
             scoring: None,
             config: None,
             peer_monitoring: None,
             gater_config: None,
+            peer_targets: None,
+            latency_preference: None,
             rollup_config,
             topic_scoring: false,
+            payload_validation: PayloadValidation::Strict,
+            recent_payloads_capacity: DEFAULT_RECENT_PAYLOADS_CAPACITY,
         }
     }
 
+    /// Sets the number of recent unsafe payloads kept to serve late-joining peers.
+    /// Defaults to [`DEFAULT_RECENT_PAYLOADS_CAPACITY`].
+    pub const fn with_recent_payloads_capacity(mut self, capacity: usize) -> Self {
+        self.recent_payloads_capacity = capacity;
+        self
+    }
+
+    /// Sets the [`PayloadValidation`] policy for blocks received over gossip.
+    /// Blocks are strictly validated by default.
+    pub const fn with_payload_validation(mut self, validation: PayloadValidation) -> Self {
+        self.payload_validation = validation;
+        self
+    }
+
     /// Sets the configuration for the connection gater.
     pub const fn with_gater_config(mut self, config: GaterConfig) -> Self {
         self.gater_config = Some(config);
         self
     }
 
+    /// Sets the [`PeerTargets`] for the gossip driver.
+    pub const fn with_peer_targets(mut self, targets: PeerTargets) -> Self {
+        self.peer_targets = Some(targets);
+        self
+    }
+
+    /// Sets the [`LatencyPreference`] for the gossip driver.
+    pub const fn with_latency_preference(mut self, preference: LatencyPreference) -> Self {
+        self.latency_preference = Some(preference);
+        self
+    }
+
     /// Sets the [`RollupConfig`] for the network.
     /// This is used to determine the topic to publish to.
     pub fn with_rollup_config(mut self, rollup_config: RollupConfig) -> Self {
@@ -116,6 +160,13 @@ impl GossipDriverBuilder {
         self
     }
 
+    /// Sets the QUIC [`Multiaddr`] for the gossip driver to listen on.
+    /// QUIC connections are only accepted if set.
+    pub fn with_quic_address(mut self, addr: Option<Multiaddr>) -> Self {
+        self.quic_addr = addr;
+        self
+    }
+
     /// Sets the [`Config`] for the [`Behaviour`].
     pub fn with_config(mut self, config: Config) -> Self {
         self.config = Some(config);
@@ -141,7 +192,8 @@ impl GossipDriverBuilder {
         let (signer_tx, signer_rx) = watch::channel(signer_recv);
 
         // Block Handler setup
-        let handler = BlockHandler::new(rollup_config, signer_rx);
+        let handler =
+            BlockHandler::new(rollup_config, signer_rx).with_validation(self.payload_validation);
 
         // Construct the gossip behaviour
         let config = self.config.unwrap_or(crate::default_config());
@@ -162,7 +214,12 @@ impl GossipDriverBuilder {
             config.validation_mode(),
             config.max_transmit_size()
         );
-        let mut behaviour = Behaviour::new(keypair.public(), config, &[Box::new(handler.clone())])?;
+        let mut behaviour = Behaviour::new(
+            keypair.public(),
+            l2_chain_id.id(),
+            config,
+            &[Box::new(handler.clone())],
+        )?;
 
         // If peer scoring is configured, set it on the behaviour.
         match self.scoring {
@@ -192,8 +249,9 @@ impl GossipDriverBuilder {
             .accept(sync_protocol_name)
             .map_err(|_| GossipDriverBuilderError::SyncReqRespAlreadyAccepted)?;
 
-        // Build the swarm with DNS+TCP transport.
-        // Note: with_dns() must be called after with_tcp() to wrap TCP with DNS resolution.
+        // Build the swarm with DNS+TCP and QUIC transports.
+        // Note: with_dns() must be called after with_tcp() and with_quic() to wrap both
+        // transports with DNS resolution.
         debug!(target: "gossip", "Building Swarm with Peer ID: {}", keypair.public().to_peer_id());
         let swarm = SwarmBuilder::with_existing_identity(keypair)
             .with_tokio()
@@ -206,6 +264,7 @@ impl GossipDriverBuilder {
                 YamuxConfig::default,
             )
             .map_err(|_| GossipDriverBuilderError::TcpError)?
+            .with_quic()
             .with_dns()
             .map_err(|_| GossipDriverBuilderError::TcpError)?
             .with_behaviour(|_| behaviour)
@@ -216,6 +275,12 @@ impl GossipDriverBuilder {
         let gater_config = self.gater_config.take().unwrap_or_default();
         let gate = crate::ConnectionGater::new(gater_config);
 
-        Ok((GossipDriver::new(swarm, addr, handler, sync_handler, sync_protocol, gate), signer_tx))
+        let mut driver = GossipDriver::new(swarm, addr, handler, sync_handler, sync_protocol, gate);
+        driver.quic_addr = self.quic_addr;
+        driver.peer_targets = self.peer_targets.unwrap_or_default();
+        driver.latency_preference = self.latency_preference.unwrap_or_default();
+        driver.recent_payloads = RecentPayloads::new(self.recent_payloads_capacity);
+
+        Ok((driver, signer_tx))
     }
 }
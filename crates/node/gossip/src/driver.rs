@@ -1,31 +1,42 @@
 //! Consensus-layer gossipsub driver for Optimism.
 
-use alloy_primitives::{Address, hex};
+use alloy_primitives::Address;
 use derive_more::Debug;
 use discv5::Enr;
 use futures::{AsyncReadExt, AsyncWriteExt, stream::StreamExt};
 use kona_genesis::RollupConfig;
-use kona_peers::{EnrValidation, PeerMonitoring, enr_to_multiaddr};
+use kona_peers::{EnrValidation, PeerMonitoring, enr_to_multiaddr, enr_to_quic_multiaddr};
 use libp2p::{
     Multiaddr, PeerId, Swarm, TransportError,
-    gossipsub::{IdentTopic, MessageId},
+    core::ConnectedPoint,
+    gossipsub::{IdentTopic, MessageAcceptance, MessageId},
     swarm::SwarmEvent,
 };
 use libp2p_identity::Keypair;
 use libp2p_stream::IncomingStreams;
 use op_alloy_rpc_types_engine::OpNetworkPayloadEnvelope;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::Arc,
     time::{Duration, Instant},
 };
 use tokio::sync::Mutex;
 
 use crate::{
-    Behaviour, BlockHandler, ConnectionGate, ConnectionGater, Event, GossipDriverBuilder, Handler,
-    PublishError,
+    Behaviour, BlockHandler, ConnectionGate, ConnectionGater, Event, GossipDriverBuilder,
+    GossipMessageTrace, Handler, LatencyPreference, MessageTraces, PeerTargets, PublishError,
+    RecentPayloads, recent::payload_by_number_response, smoothed_latency,
 };
 
+/// The `payload_by_number` result code of a served payload.
+const SYNC_RESULT_SUCCESS: u8 = 0;
+
+/// The `payload_by_number` result code of a payload that isn't available.
+const SYNC_RESULT_NOT_FOUND: u8 = 1;
+
+/// The `payload_by_number` result code of a malformed request.
+const SYNC_RESULT_INVALID_REQUEST: u8 = 2;
+
 /// A driver for a [`Swarm`] instance.
 ///
 /// Connects the swarm to the given [`Multiaddr`]
@@ -37,6 +48,9 @@ pub struct GossipDriver<G: ConnectionGate> {
     pub swarm: Swarm<Behaviour>,
     /// A [`Multiaddr`] to listen on.
     pub addr: Multiaddr,
+    /// A QUIC [`Multiaddr`] to listen on. QUIC connections are only accepted and preferred when
+    /// dialing if set.
+    pub quic_addr: Option<Multiaddr>,
     /// The [`BlockHandler`].
     pub handler: BlockHandler,
     /// A [`libp2p_stream::Control`] instance. Can be used to control the sync request/response
@@ -57,10 +71,21 @@ pub struct GossipDriver<G: ConnectionGate> {
     pub peer_monitoring: Option<PeerMonitoring>,
     /// Tracks connection start time for peers
     pub peer_connection_start: HashMap<PeerId, Instant>,
+    /// Tracks the endpoint of the first established connection to each connected peer, used to
+    /// report the connection direction and transport.
+    pub peer_endpoints: HashMap<PeerId, ConnectedPoint>,
     /// The connection gate.
     pub connection_gate: G,
-    /// Tracks ping times for peers.
+    /// Tracks the moving average of the ping latency of connected peers.
     pub ping: Arc<Mutex<HashMap<PeerId, Duration>>>,
+    /// The target number of connected peers.
+    pub peer_targets: PeerTargets,
+    /// The preference for low-latency peers when dialing and pruning.
+    pub latency_preference: LatencyPreference,
+    /// The most recent gossip messages received or published.
+    pub message_traces: MessageTraces,
+    /// The most recent unsafe payloads published or validated, served to late-joining peers.
+    pub recent_payloads: RecentPayloads,
 }
 
 impl<G> GossipDriver<G>
@@ -89,14 +114,20 @@ where
         Self {
             swarm,
             addr,
+            quic_addr: None,
             handler,
             peerstore: Default::default(),
             peer_monitoring: None,
             peer_connection_start: Default::default(),
+            peer_endpoints: Default::default(),
             sync_handler,
             sync_protocol: Some(sync_protocol),
             connection_gate: gate,
             ping: Arc::new(Mutex::new(Default::default())),
+            peer_targets: Default::default(),
+            latency_preference: Default::default(),
+            message_traces: Default::default(),
+            recent_payloads: Default::default(),
         }
     }
 
@@ -121,27 +152,68 @@ where
             return Ok(None);
         };
         let topic = selector(&self.handler);
+        let result = self.gossip(topic, payload.clone());
+        self.recent_payloads.insert(payload, result.is_ok());
+        Ok(Some(result?))
+    }
+
+    /// Encodes and publishes a payload on the given topic, recording its trace.
+    fn gossip(
+        &mut self,
+        topic: IdentTopic,
+        payload: OpNetworkPayloadEnvelope,
+    ) -> Result<MessageId, PublishError> {
         let topic_hash = topic.hash();
+        let trace = GossipMessageTrace::published(&topic_hash, &payload);
         let data = self.handler.encode(topic, payload)?;
-        let id = self.swarm.behaviour_mut().gossipsub.publish(topic_hash, data)?;
+        let result = self.swarm.behaviour_mut().gossipsub.publish(topic_hash, data);
+        self.message_traces.push(trace.with_publish_result(result.as_ref()));
+        let id = result?;
         kona_macros::inc!(gauge, crate::Metrics::UNSAFE_BLOCK_PUBLISHED);
-        Ok(Some(id))
+        Ok(id)
+    }
+
+    /// Re-publishes the recent payloads that were published while no peer could receive them.
+    ///
+    /// This is called whenever a peer subscribes to the block topics, so that blocks published
+    /// while the node had no mesh peers still reach the network.
+    fn republish_recent_payloads(&mut self) {
+        for payload in self.recent_payloads.ungossiped() {
+            let number = payload.payload.block_number();
+            let topic = self.handler.topic(payload.payload.timestamp());
+            match self.gossip(topic, payload) {
+                Ok(_) => {
+                    debug!(target: "gossip", number, "Re-published unsafe block");
+                    self.recent_payloads.mark_gossiped(number);
+                }
+                // The mesh still can't receive the block, it is retried on the next subscription.
+                Err(PublishError::PublishError(
+                    libp2p::gossipsub::PublishError::InsufficientPeers,
+                )) => {}
+                Err(err) => {
+                    debug!(target: "gossip", number, ?err, "Failed to re-publish unsafe block");
+                    self.recent_payloads.mark_gossiped(number);
+                }
+            }
+        }
     }
 
     /// Handles the sync request/response protocol.
     ///
-    /// This is a mock handler that supports the `payload_by_number` protocol.
-    /// It always returns: not found (1), version (0). `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
+    /// Serves the `payload_by_number` protocol from the window of [`RecentPayloads`], so that
+    /// peers joining mid-epoch can fetch the recent unsafe blocks they missed. Payloads outside
+    /// the window are answered with: not found (1), version (0).
+    /// `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
     ///
     /// ## Note
     ///
-    /// This is used to ensure op-nodes are not penalizing kona-nodes for not supporting it.
     /// This feature is being deprecated by the op-node team. Once it is fully removed from the
     /// op-node's implementation we will remove this handler.
     pub(super) fn sync_protocol_handler(&mut self) {
         let Some(mut sync_protocol) = self.sync_protocol.take() else {
             return;
         };
+        let recent_payloads = self.recent_payloads.clone();
 
         // Spawn a new task to handle the sync request/response protocol.
         tokio::spawn(async move {
@@ -153,6 +225,7 @@ where
 
                 info!(target: "gossip", "Received a sync request from {peer_id}, spawning a new task to handle it");
 
+                let recent_payloads = recent_payloads.clone();
                 tokio::spawn(async move {
                     let mut buffer = Vec::new();
                     let Ok(bytes_received) = inbound_stream.read_to_end(&mut buffer).await else {
@@ -162,18 +235,32 @@ where
 
                     debug!(target: "gossip", bytes_received = bytes_received, peer_id = ?peer_id, payload = ?buffer, "Received inbound sync request");
 
-                    // We return: not found (1), version (0). `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
+                    // Request format: <num> = a little-endian uint64 block number.
                     // Response format: <response> = <res><version><payload>
-                    // No payload is returned.
-                    const OUTPUT: [u8; 2] = hex!("0100");
+                    let output = match <[u8; 8]>::try_from(buffer.as_slice()) {
+                        Ok(number) => match recent_payloads.get(u64::from_le_bytes(number)) {
+                            Some(envelope) => payload_by_number_response(&envelope)
+                                .unwrap_or_else(|err| {
+                                    warn!(target: "gossip", ?err, "Failed to encode the sync response");
+                                    vec![SYNC_RESULT_NOT_FOUND, 0]
+                                }),
+                            None => vec![SYNC_RESULT_NOT_FOUND, 0],
+                        },
+                        Err(_) => vec![SYNC_RESULT_INVALID_REQUEST, 0],
+                    };
 
-                    // We only write that we're not supporting the sync request.
-                    if let Err(e) = inbound_stream.write_all(&OUTPUT).await {
+                    if let Err(e) = inbound_stream.write_all(&output).await {
                         error!(target: "gossip", err = ?e, "Failed to write the sync response to {peer_id}");
                         return;
                     };
 
-                    debug!(target: "gossip", bytes_sent = OUTPUT.len(), peer_id = ?peer_id, "Sent outbound sync response");
+                    debug!(
+                        target: "gossip",
+                        bytes_sent = output.len(),
+                        served = output[0] == SYNC_RESULT_SUCCESS,
+                        peer_id = ?peer_id,
+                        "Sent outbound sync response"
+                    );
                 });
             }
         });
@@ -189,6 +276,14 @@ where
         // Start the sync request/response protocol handler.
         self.sync_protocol_handler();
 
+        if let Some(quic_addr) = self.quic_addr.clone() {
+            if let Err(err) = self.swarm.listen_on(quic_addr.clone()) {
+                error!(target: "gossip", "Fail to listen on {quic_addr}: {err}");
+                return Err(err);
+            }
+            info!(target: "gossip", "Swarm accepting QUIC connections on: {quic_addr}");
+        }
+
         match self.swarm.listen_on(self.addr.clone()) {
             Ok(id) => loop {
                 if let SwarmEvent::NewListenAddr { address, listener_id } =
@@ -230,14 +325,91 @@ where
         self.swarm.connected_peers().count()
     }
 
+    /// Updates the [`PeerTargets`].
+    ///
+    /// The new targets only take effect on the next call to [`Self::prune_peers`] or
+    /// [`Self::dial`].
+    pub fn set_peer_targets(&mut self, targets: PeerTargets) {
+        info!(target: "gossip", lo = targets.lo, hi = targets.hi, "Updated peer targets");
+        self.peer_targets = targets;
+    }
+
+    /// Disconnects connected peers above the high watermark of the [`PeerTargets`].
+    ///
+    /// Peers are pruned lowest gossipsub score first. Protected peers, peers that connected
+    /// within the grace period, and peers holding the low-latency slots of the
+    /// [`LatencyPreference`] are never pruned. Returns the pruned peers.
+    pub fn prune_peers(&mut self) -> Vec<PeerId> {
+        let excess = self.peer_targets.excess(self.connected_peers());
+        if excess == 0 {
+            return Vec::new();
+        }
+
+        let mut protected =
+            self.connection_gate.list_protected_peers().into_iter().collect::<HashSet<_>>();
+        if self.latency_preference.is_enabled() {
+            // The latencies are only locked briefly by ping events and RPC requests. If they are
+            // locked, the low-latency slots are ignored until the next pruning round.
+            if let Ok(latencies) = self.ping.try_lock() {
+                protected.extend(self.latency_preference.preferred_peers(
+                    &self.peer_targets,
+                    latencies.iter().map(|(id, latency)| (*id, *latency)),
+                ));
+            }
+        }
+        let mut candidates = self
+            .swarm
+            .connected_peers()
+            .filter(|peer_id| !protected.contains(peer_id))
+            .filter(|peer_id| {
+                self.peer_connection_start
+                    .get(peer_id)
+                    .is_none_or(|start| start.elapsed() >= self.peer_targets.grace)
+            })
+            .map(|peer_id| {
+                let score =
+                    self.swarm.behaviour().gossipsub.peer_score(peer_id).unwrap_or_default();
+                (*peer_id, score)
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        candidates
+            .into_iter()
+            .take(excess)
+            .filter_map(|(peer_id, _)| {
+                self.swarm.disconnect_peer_id(peer_id).ok()?;
+                debug!(target: "gossip", ?peer_id, "Pruned peer above the high watermark");
+                kona_macros::inc!(
+                    gauge,
+                    crate::Metrics::GOSSIPSUB_CONNECTION,
+                    "type" => "pruned",
+                    "peer" => peer_id.to_string(),
+                );
+                Some(peer_id)
+            })
+            .collect()
+    }
+
     /// Dials the given [`Enr`].
+    ///
+    /// The [`Enr`] is not dialed if the number of connected peers has reached the high watermark
+    /// of the [`PeerTargets`], plus the extra dials of the [`LatencyPreference`].
     pub fn dial(&mut self, enr: Enr) {
+        let connected =
+            self.connected_peers().saturating_sub(self.latency_preference.extra_dials());
+        if !self.peer_targets.can_dial(connected) {
+            trace!(target: "gossip", hi = self.peer_targets.hi, "Peer count at the high watermark, not dialing");
+            return;
+        }
         let validation = EnrValidation::validate(&enr, self.handler.rollup_config.l2_chain_id.id());
         if validation.is_invalid() {
             trace!(target: "gossip", "Invalid OP Stack ENR for chain id {}: {}", self.handler.rollup_config.l2_chain_id.id(), validation);
             return;
         }
-        let Some(multiaddr) = enr_to_multiaddr(&enr) else {
+        // Prefer QUIC for peers advertising it, since it saves round trips on connection setup.
+        let quic_multiaddr = self.quic_addr.as_ref().and_then(|_| enr_to_quic_multiaddr(&enr));
+        let Some(multiaddr) = quic_multiaddr.or_else(|| enr_to_multiaddr(&enr)) else {
             debug!(target: "gossip", "Failed to extract tcp socket from enr: {:?}", enr);
             kona_macros::inc!(gauge, crate::Metrics::DIAL_PEER_ERROR, "type" => "invalid_enr");
             return;
@@ -314,7 +486,9 @@ where
                 let pings = Arc::clone(&self.ping);
                 tokio::spawn(async move {
                     if let Ok(time) = result {
-                        pings.lock().await.insert(peer, time);
+                        let mut pings = pings.lock().await;
+                        let latency = smoothed_latency(pings.get(&peer).copied(), time);
+                        pings.insert(peer, latency);
                     }
                 });
             }
@@ -332,6 +506,20 @@ where
         match event {
             libp2p::identify::Event::Received { connection_id, peer_id, info } => {
                 debug!(target: "gossip", ?connection_id, ?peer_id, ?info, "Received identify info from peer");
+
+                // Drop peers of other chains before they enter the mesh.
+                let chain_id = self.handler.rollup_config.l2_chain_id.id();
+                if let Err(rejection) = crate::validate_handshake(&info, chain_id) {
+                    warn!(target: "gossip", ?peer_id, %rejection, "Rejected peer handshake");
+                    kona_macros::inc!(
+                        counter,
+                        crate::Metrics::HANDSHAKE_REJECTED,
+                        "reason" => rejection.reason()
+                    );
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return;
+                }
+
                 self.peerstore.insert(peer_id, info);
             }
             libp2p::identify::Event::Sent { connection_id, peer_id } => {
@@ -360,7 +548,21 @@ where
                 trace!(target: "gossip", "Received message with topic: {}", message.topic);
                 kona_macros::inc!(gauge, crate::Metrics::GOSSIP_EVENT, "type" => "message", "topic" => message.topic.to_string());
                 if self.handler.topics().contains(&message.topic) {
+                    let topic = message.topic.clone();
+                    let start = Instant::now();
                     let (status, payload) = self.handler.handle(message);
+                    self.message_traces.push(GossipMessageTrace::received(
+                        &topic,
+                        &src,
+                        &id,
+                        &status,
+                        payload.as_ref(),
+                        start.elapsed(),
+                    ));
+                    if let (MessageAcceptance::Accept, Some(payload)) = (&status, &payload) {
+                        // Accepted messages are propagated by gossipsub itself.
+                        self.recent_payloads.insert(payload.clone(), true);
+                    }
                     _ = self
                         .swarm
                         .behaviour_mut()
@@ -372,6 +574,9 @@ where
             libp2p::gossipsub::Event::Subscribed { peer_id, topic } => {
                 trace!(target: "gossip", "Peer: {:?} subscribed to topic: {:?}", peer_id, topic);
                 kona_macros::inc!(gauge, crate::Metrics::GOSSIP_EVENT, "type" => "subscribed", "topic" => topic.to_string());
+                if self.handler.topics().contains(&topic) {
+                    self.republish_recent_payloads();
+                }
             }
             libp2p::gossipsub::Event::Unsubscribed { peer_id, topic } => {
                 trace!(target: "gossip", "Peer: {:?} unsubscribed from topic: {:?}", peer_id, topic);
@@ -395,7 +600,7 @@ where
             SwarmEvent::Behaviour(behavior_event) => {
                 return self.handle_gossip_event(behavior_event)
             }
-            SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+            SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
                 let peer_count = self.swarm.connected_peers().count();
                 info!(target: "gossip", "Connection established: {:?} | Peer Count: {}", peer_id, peer_count);
                 kona_macros::inc!(
@@ -407,6 +612,7 @@ where
                 kona_macros::set!(gauge, crate::Metrics::GOSSIP_PEER_COUNT, peer_count as f64);
 
                 self.peer_connection_start.insert(peer_id, Instant::now());
+                self.peer_endpoints.entry(peer_id).or_insert(endpoint);
             }
             SwarmEvent::OutgoingConnectionError { peer_id: _peer_id, error, .. } => {
                 debug!(target: "gossip", "Outgoing connection error: {:?}", error);
@@ -432,7 +638,7 @@ where
                     "connection_id" => _connection_id.to_string()
                 );
             }
-            SwarmEvent::ConnectionClosed { peer_id, cause, .. } => {
+            SwarmEvent::ConnectionClosed { peer_id, cause, num_established, .. } => {
                 let peer_count = self.swarm.connected_peers().count();
                 warn!(target: "gossip", ?peer_id, ?cause, peer_count, "Connection closed");
                 kona_macros::inc!(
@@ -443,6 +649,10 @@ where
                 );
                 kona_macros::set!(gauge, crate::Metrics::GOSSIP_PEER_COUNT, peer_count as f64);
 
+                if num_established == 0 {
+                    self.peer_endpoints.remove(&peer_id);
+                }
+
                 // Record the total connection duration.
                 if let Some(start_time) = self.peer_connection_start.remove(&peer_id) {
                     let _peer_duration = start_time.elapsed();
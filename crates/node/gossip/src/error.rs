@@ -46,6 +46,13 @@ pub enum HandlerEncodeError {
     /// is not recognized or that the node is not subscribed to.
     #[error("Unknown topic: {0}")]
     UnknownTopic(libp2p::gossipsub::TopicHash),
+
+    /// Failed to re-compress an encoded payload.
+    ///
+    /// This error occurs when converting a gossiped payload into the snappy framed
+    /// format of a `payload_by_number` response.
+    #[error("Failed to compress payload: {0}")]
+    CompressionError(#[from] std::io::Error),
 }
 
 /// An error type for the [`crate::GossipDriverBuilder`].
@@ -1,6 +1,6 @@
 //! Block Handler
 
-use crate::HandlerEncodeError;
+use crate::{BlockTopic, HandlerEncodeError, PayloadValidation};
 use alloy_primitives::{Address, B256};
 use kona_genesis::RollupConfig;
 use libp2p::gossipsub::{IdentTopic, Message, MessageAcceptance, TopicHash};
@@ -40,6 +40,8 @@ pub struct BlockHandler {
     /// A map of seen block height to block hash set.
     /// This map is pruned when it contains more than [`Self::SEEN_HASH_CACHE_SIZE`] entries.
     pub seen_hashes: BTreeMap<u64, HashSet<B256>>,
+    /// How strictly received blocks are validated.
+    pub validation: PayloadValidation,
 }
 
 impl Handler for BlockHandler {
@@ -94,14 +96,21 @@ impl BlockHandler {
         Self {
             rollup_config,
             signer_recv,
-            blocks_v1_topic: IdentTopic::new(format!("/optimism/{chain_id}/0/blocks")),
-            blocks_v2_topic: IdentTopic::new(format!("/optimism/{chain_id}/1/blocks")),
-            blocks_v3_topic: IdentTopic::new(format!("/optimism/{chain_id}/2/blocks")),
-            blocks_v4_topic: IdentTopic::new(format!("/optimism/{chain_id}/3/blocks")),
+            blocks_v1_topic: BlockTopic::V1.topic(chain_id),
+            blocks_v2_topic: BlockTopic::V2.topic(chain_id),
+            blocks_v3_topic: BlockTopic::V3.topic(chain_id),
+            blocks_v4_topic: BlockTopic::V4.topic(chain_id),
             seen_hashes: BTreeMap::new(),
+            validation: PayloadValidation::Strict,
         }
     }
 
+    /// Sets the [`PayloadValidation`] policy of the [`BlockHandler`].
+    pub const fn with_validation(mut self, validation: PayloadValidation) -> Self {
+        self.validation = validation;
+        self
+    }
+
     /// Returns the topic using the specified timestamp and optional [`RollupConfig`].
     ///
     /// Reference: <https://github.com/ethereum-optimism/optimism/blob/0bc5fe8d16155dc68bcdf1fa5733abc58689a618/op-node/p2p/gossip.go#L604C1-L612C3>
@@ -0,0 +1,149 @@
+//! Validation of the chain information peers advertise in the identify handshake.
+
+use libp2p::identify::Info;
+use thiserror::Error;
+
+/// The prefix of the identify protocol version advertised by OP Stack nodes, followed by the L2
+/// chain id.
+const OPSTACK_PROTOCOL_VERSION_PREFIX: &str = "/opstack/";
+
+/// The prefix of the `payload_by_number` request/response protocol, followed by the L2 chain id.
+const PAYLOAD_BY_NUMBER_PROTOCOL_PREFIX: &str = "/opstack/req/payload_by_number/";
+
+/// The prefix of the gossipsub protocols.
+const GOSSIPSUB_PROTOCOL_PREFIX: &str = "/meshsub/";
+
+/// Returns the identify protocol version advertised by the node for the given L2 chain id.
+pub fn opstack_protocol_version(chain_id: u64) -> String {
+    format!("{OPSTACK_PROTOCOL_VERSION_PREFIX}{chain_id}")
+}
+
+/// The reason a peer's identify handshake was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum HandshakeRejection {
+    /// The peer advertises a different L2 chain id.
+    #[error("Peer advertises chain id {advertised}, expected {expected}")]
+    ChainIdMismatch {
+        /// The L2 chain id of the node.
+        expected: u64,
+        /// The L2 chain id advertised by the peer.
+        advertised: u64,
+    },
+    /// The peer doesn't support gossipsub.
+    #[error("Peer does not support gossipsub")]
+    MissingGossipsub,
+}
+
+impl HandshakeRejection {
+    /// Returns the label of the rejection reason in the metrics.
+    pub const fn reason(&self) -> &'static str {
+        match self {
+            Self::ChainIdMismatch { .. } => "chain_id_mismatch",
+            Self::MissingGossipsub => "missing_gossipsub",
+        }
+    }
+}
+
+/// Cross-checks the chain information a peer advertises in the identify handshake against the
+/// L2 chain id of the node.
+///
+/// The chain id is read from the `/opstack/<chain_id>` protocol version advertised by kona nodes,
+/// and from the `payload_by_number` protocol supported by all OP Stack nodes. Peers that advertise
+/// neither, such as op-nodes with the sync protocol disabled, are accepted as long as they support
+/// gossipsub.
+pub fn validate_handshake(info: &Info, chain_id: u64) -> Result<(), HandshakeRejection> {
+    let mismatch =
+        |advertised| HandshakeRejection::ChainIdMismatch { expected: chain_id, advertised };
+
+    if let Some(advertised) = info
+        .protocol_version
+        .strip_prefix(OPSTACK_PROTOCOL_VERSION_PREFIX)
+        .and_then(|id| id.parse::<u64>().ok()) &&
+        advertised != chain_id
+    {
+        return Err(mismatch(advertised));
+    }
+
+    let sync_chain_ids = info
+        .protocols
+        .iter()
+        .filter_map(|protocol| {
+            protocol.as_ref().strip_prefix(PAYLOAD_BY_NUMBER_PROTOCOL_PREFIX)?.split('/').next()
+        })
+        .filter_map(|id| id.parse::<u64>().ok())
+        .collect::<Vec<_>>();
+    if let Some(advertised) = sync_chain_ids.first() &&
+        !sync_chain_ids.contains(&chain_id)
+    {
+        return Err(mismatch(*advertised));
+    }
+
+    if !info
+        .protocols
+        .iter()
+        .any(|protocol| protocol.as_ref().starts_with(GOSSIPSUB_PROTOCOL_PREFIX))
+    {
+        return Err(HandshakeRejection::MissingGossipsub);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::{StreamProtocol, identity::Keypair};
+
+    fn info(protocol_version: &str, protocols: &[&'static str]) -> Info {
+        Info {
+            public_key: Keypair::generate_secp256k1().public(),
+            protocol_version: protocol_version.to_string(),
+            agent_version: "kona".to_string(),
+            listen_addrs: vec![],
+            protocols: protocols.iter().copied().map(StreamProtocol::new).collect(),
+            observed_addr: "/ip4/127.0.0.1/tcp/9222".parse().unwrap(),
+            signed_peer_record: None,
+        }
+    }
+
+    #[test]
+    fn test_accepts_matching_peer() {
+        let info = info(
+            &opstack_protocol_version(10),
+            &["/meshsub/1.1.0", "/opstack/req/payload_by_number/10/0/"],
+        );
+        assert_eq!(validate_handshake(&info, 10), Ok(()));
+    }
+
+    #[test]
+    fn test_accepts_peer_without_chain_information() {
+        let info = info("", &["/meshsub/1.1.0", "/ipfs/id/1.0.0"]);
+        assert_eq!(validate_handshake(&info, 10), Ok(()));
+    }
+
+    #[test]
+    fn test_rejects_protocol_version_mismatch() {
+        let info = info(&opstack_protocol_version(8453), &["/meshsub/1.1.0"]);
+        assert_eq!(
+            validate_handshake(&info, 10),
+            Err(HandshakeRejection::ChainIdMismatch { expected: 10, advertised: 8453 })
+        );
+    }
+
+    #[test]
+    fn test_rejects_sync_protocol_mismatch() {
+        let info = info("", &["/meshsub/1.1.0", "/opstack/req/payload_by_number/8453/0/"]);
+        let rejection = validate_handshake(&info, 10).unwrap_err();
+        assert_eq!(
+            rejection,
+            HandshakeRejection::ChainIdMismatch { expected: 10, advertised: 8453 }
+        );
+        assert_eq!(rejection.reason(), "chain_id_mismatch");
+    }
+
+    #[test]
+    fn test_rejects_missing_gossipsub() {
+        let info = info("", &["/ipfs/id/1.0.0"]);
+        assert_eq!(validate_handshake(&info, 10), Err(HandshakeRejection::MissingGossipsub));
+    }
+}
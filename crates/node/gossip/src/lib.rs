@@ -10,7 +10,10 @@
 //! - [`Behaviour`]: Custom libp2p behavior combining GossipSub, Ping, and Identify
 //! - [`BlockHandler`]: Validates and processes incoming block payloads
 //! - [`ConnectionGater`]: Sophisticated connection management and rate limiting
+//! - [`PeerTargets`]: Runtime-adjustable low and high watermarks for connected peers
 //! - [`P2pRpcRequest`]: RPC interface for network administration
+//! - [`MessageTraces`]: Bounded record of recently received and published gossip messages
+//! - [`RecentPayloads`]: Window of recent unsafe payloads served to late-joining peers
 //! - [`Metrics`]: Metrics collection for monitoring and observability
 
 #![doc(html_logo_url = "https://raw.githubusercontent.com/op-rs/kona/main/assets/kona-logo.png")]
@@ -30,7 +33,7 @@ pub use metrics::Metrics;
 mod rpc;
 pub use rpc::{
     Connectedness, Direction, GossipScores, P2pRpcRequest, PeerCount, PeerDump, PeerInfo,
-    PeerScores, PeerStats, ReqRespScores, TopicScores,
+    PeerScores, PeerStats, ReqRespScores, TopicScores, TracedP2pRpcRequest,
 };
 
 mod behaviour;
@@ -44,9 +47,20 @@ pub use config::{
     default_config, default_config_builder,
 };
 
+mod handshake;
+pub use handshake::{HandshakeRejection, opstack_protocol_version, validate_handshake};
+
+mod mesh;
+pub use mesh::{BlockTopic, TopicMesh, TopicMeshError};
+
 mod gate;
 pub use gate::ConnectionGate; // trait
 
+mod targets;
+pub use targets::{
+    LatencyPreference, LatencyPreferenceError, PeerTargets, PeerTargetsError, smoothed_latency,
+};
+
 mod gater;
 pub use gater::{
     ConnectionGater, // implementation
@@ -69,8 +83,16 @@ pub use handler::{BlockHandler, Handler};
 mod driver;
 pub use driver::GossipDriver;
 
+mod trace;
+pub use trace::{
+    DEFAULT_MESSAGE_TRACE_CAPACITY, GossipMessageTrace, MessageOutcome, MessageTraces,
+};
+
+mod recent;
+pub use recent::{DEFAULT_RECENT_PAYLOADS_CAPACITY, RecentPayloads};
+
 mod block_validity;
-pub use block_validity::BlockInvalidError;
+pub use block_validity::{BlockInvalidError, PayloadValidation};
 
 #[cfg(test)]
 pub(crate) use block_validity::tests::*;
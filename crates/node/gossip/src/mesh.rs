@@ -0,0 +1,192 @@
+//! Per-topic gossip mesh parameters.
+
+use libp2p::gossipsub::{ConfigBuilder, IdentTopic, TopicMeshConfig};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// The block gossip topics, one per execution payload version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockTopic {
+    /// The topic for pre Canyon/Shanghai blocks.
+    V1,
+    /// The topic for Canyon/Delta blocks.
+    V2,
+    /// The topic for Ecotone V3 blocks.
+    V3,
+    /// The topic for V4 blocks.
+    V4,
+}
+
+impl BlockTopic {
+    /// All the block topics.
+    pub const ALL: [Self; 4] = [Self::V1, Self::V2, Self::V3, Self::V4];
+
+    /// Returns the libp2p topic for the given chain id.
+    pub fn topic(&self, chain_id: u64) -> IdentTopic {
+        let index = match self {
+            Self::V1 => 0,
+            Self::V2 => 1,
+            Self::V3 => 2,
+            Self::V4 => 3,
+        };
+        IdentTopic::new(format!("/optimism/{chain_id}/{index}/blocks"))
+    }
+}
+
+impl fmt::Display for BlockTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 => write!(f, "v1"),
+            Self::V2 => write!(f, "v2"),
+            Self::V3 => write!(f, "v3"),
+            Self::V4 => write!(f, "v4"),
+        }
+    }
+}
+
+impl FromStr for BlockTopic {
+    type Err = TopicMeshError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "v1" => Ok(Self::V1),
+            "v2" => Ok(Self::V2),
+            "v3" => Ok(Self::V3),
+            "v4" => Ok(Self::V4),
+            _ => Err(TopicMeshError::UnknownTopic(s.to_string())),
+        }
+    }
+}
+
+/// Mesh degree parameters of a [`BlockTopic`], overriding the global gossipsub `D`, `D_lo` and
+/// `D_hi` for that topic.
+///
+/// Chains with sub-second blocks gossip many more messages than the defaults are tuned for, and
+/// usually benefit from a denser mesh on the active block topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopicMesh {
+    /// The topic the parameters apply to.
+    pub topic: BlockTopic,
+    /// The target mesh degree.
+    pub d: usize,
+    /// The mesh degree below which peers are grafted.
+    pub d_lo: usize,
+    /// The mesh degree above which peers are pruned.
+    pub d_hi: usize,
+}
+
+/// An error returned when constructing an invalid [`TopicMesh`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TopicMeshError {
+    /// The topic mesh isn't formatted as `<topic>=<d>,<d_lo>,<d_hi>`.
+    #[error("Invalid topic mesh {0}, expected <topic>=<d>,<d_lo>,<d_hi>")]
+    InvalidFormat(String),
+    /// The topic isn't a block topic.
+    #[error("Unknown block topic {0}, expected one of v1, v2, v3 or v4")]
+    UnknownTopic(String),
+    /// The mesh degrees aren't ordered as `0 < d_lo <= d <= d_hi`.
+    #[error("Invalid mesh degrees d={d}, d_lo={d_lo}, d_hi={d_hi}, expected 0 < d_lo <= d <= d_hi")]
+    InvalidDegrees {
+        /// The target mesh degree.
+        d: usize,
+        /// The low mesh degree.
+        d_lo: usize,
+        /// The high mesh degree.
+        d_hi: usize,
+    },
+}
+
+impl TopicMesh {
+    /// The minimum number of outbound peers kept in the mesh, matching the gossipsub default.
+    const MESH_OUTBOUND_MIN: usize = 2;
+
+    /// Creates a new [`TopicMesh`], checking that `0 < d_lo <= d <= d_hi`.
+    pub const fn new(
+        topic: BlockTopic,
+        d: usize,
+        d_lo: usize,
+        d_hi: usize,
+    ) -> Result<Self, TopicMeshError> {
+        if d_lo == 0 || d_lo > d || d > d_hi {
+            return Err(TopicMeshError::InvalidDegrees { d, d_lo, d_hi });
+        }
+        Ok(Self { topic, d, d_lo, d_hi })
+    }
+
+    /// Sets the mesh parameters of the topic on the gossipsub [`ConfigBuilder`].
+    pub fn apply(&self, builder: &mut ConfigBuilder, chain_id: u64) {
+        // The outbound quota must not exceed the low degree, nor half the target degree.
+        let mesh_outbound_min = Self::MESH_OUTBOUND_MIN.min(self.d_lo).min(self.d / 2);
+        builder.set_topic_config(
+            self.topic.topic(chain_id).hash(),
+            TopicMeshConfig {
+                mesh_n: self.d,
+                mesh_n_low: self.d_lo,
+                mesh_n_high: self.d_hi,
+                mesh_outbound_min,
+            },
+        );
+    }
+}
+
+impl FromStr for TopicMesh {
+    type Err = TopicMeshError;
+
+    /// Parses a [`TopicMesh`] formatted as `<topic>=<d>,<d_lo>,<d_hi>`, e.g. `v4=16,12,24`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TopicMeshError::InvalidFormat(s.to_string());
+        let (topic, degrees) = s.split_once('=').ok_or_else(invalid)?;
+        let degrees = degrees
+            .split(',')
+            .map(|degree| degree.trim().parse::<usize>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [d, d_lo, d_hi] = degrees[..] else {
+            return Err(invalid());
+        };
+        Self::new(topic.trim().parse()?, d, d_lo, d_hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_topic_names() {
+        assert_eq!(BlockTopic::V1.topic(10).to_string(), "/optimism/10/0/blocks");
+        assert_eq!(BlockTopic::V4.topic(10).to_string(), "/optimism/10/3/blocks");
+        for topic in BlockTopic::ALL {
+            assert_eq!(topic.to_string().parse::<BlockTopic>().unwrap(), topic);
+        }
+    }
+
+    #[test]
+    fn test_parse_topic_mesh() {
+        assert_eq!(
+            "v4=16,12,24".parse::<TopicMesh>().unwrap(),
+            TopicMesh { topic: BlockTopic::V4, d: 16, d_lo: 12, d_hi: 24 }
+        );
+        assert!(matches!("v5=16,12,24".parse::<TopicMesh>(), Err(TopicMeshError::UnknownTopic(_))));
+        assert!(matches!("v4=16,12".parse::<TopicMesh>(), Err(TopicMeshError::InvalidFormat(_))));
+        assert!(matches!("v4".parse::<TopicMesh>(), Err(TopicMeshError::InvalidFormat(_))));
+        assert_eq!(
+            "v4=8,12,24".parse::<TopicMesh>(),
+            Err(TopicMeshError::InvalidDegrees { d: 8, d_lo: 12, d_hi: 24 })
+        );
+    }
+
+    #[test]
+    fn test_apply_topic_mesh() {
+        let mut builder = crate::default_config_builder();
+        TopicMesh::new(BlockTopic::V4, 16, 12, 24).unwrap().apply(&mut builder, 10);
+        let config = builder.build().unwrap();
+
+        let topic = BlockTopic::V4.topic(10).hash();
+        assert_eq!(config.mesh_n_for_topic(&topic), 16);
+        assert_eq!(config.mesh_n_low_for_topic(&topic), 12);
+        assert_eq!(config.mesh_n_high_for_topic(&topic), 24);
+
+        let other = BlockTopic::V3.topic(10).hash();
+        assert_eq!(config.mesh_n_for_topic(&other), crate::DEFAULT_MESH_D);
+    }
+}
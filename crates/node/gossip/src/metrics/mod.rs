@@ -55,6 +55,9 @@ impl Metrics {
     /// Identifier for the counter that tracks block version distribution.
     pub const BLOCK_VERSION: &str = "kona_node_block_version";
 
+    /// Identifier for the counter that tracks peer handshakes rejected by reason.
+    pub const HANDSHAKE_REJECTED: &str = "kona_node_handshake_rejected";
+
     /// Initializes metrics for the Gossip stack.
     ///
     /// This does two things:
@@ -116,6 +119,10 @@ impl Metrics {
             "Duration of block validation in seconds"
         );
         metrics::describe_counter!(Self::BLOCK_VERSION, "Distribution of block versions");
+        metrics::describe_counter!(
+            Self::HANDSHAKE_REJECTED,
+            "Number of peer handshakes rejected by reason"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -163,6 +170,7 @@ impl Metrics {
         kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "outgoing_error", 0);
         kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "incoming_error", 0);
         kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "closed", 0);
+        kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "pruned", 0);
 
         // Gossipsub Events
         kona_macros::set!(gauge, Self::GOSSIPSUB_EVENT, "type", "subscribed", 0);
@@ -174,6 +182,10 @@ impl Metrics {
         // Banned Peers
         kona_macros::set!(gauge, Self::BANNED_PEERS, 0);
 
+        // Rejected handshakes by reason
+        kona_macros::set!(counter, Self::HANDSHAKE_REJECTED, "reason", "chain_id_mismatch", 0);
+        kona_macros::set!(counter, Self::HANDSHAKE_REJECTED, "reason", "missing_gossipsub", 0);
+
         // Block validation metrics
         kona_macros::set!(counter, Self::BLOCK_VALIDATION_TOTAL, 0);
         kona_macros::set!(counter, Self::BLOCK_VALIDATION_SUCCESS, 0);
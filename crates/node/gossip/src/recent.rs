@@ -0,0 +1,202 @@
+//! A bounded window of recent unsafe payloads, served to late-joining peers.
+
+use crate::HandlerEncodeError;
+use op_alloy_rpc_types_engine::{OpExecutionPayload, OpNetworkPayloadEnvelope};
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// The default number of unsafe payloads kept by [`RecentPayloads`].
+pub const DEFAULT_RECENT_PAYLOADS_CAPACITY: usize = 64;
+
+/// The length of the signature prefixing a gossiped payload.
+const SIGNATURE_LENGTH: usize = 65;
+
+/// A recent unsafe payload.
+#[derive(Debug, Clone)]
+struct RecentPayload {
+    /// The payload envelope.
+    envelope: OpNetworkPayloadEnvelope,
+    /// Whether the payload was propagated over gossip. Published payloads that found no peers to
+    /// propagate to are re-published once a peer subscribes to the block topics.
+    gossiped: bool,
+}
+
+/// The most recent unsafe payloads published or validated by the node, keyed by block number.
+///
+/// Peers joining mid-epoch request the payloads they missed through the `payload_by_number`
+/// request/response protocol, which is served from this window. Once full, the lowest block is
+/// dropped for each new one. The window is cheaply cloneable and shared with the sync protocol
+/// handler.
+#[derive(Debug, Clone)]
+pub struct RecentPayloads {
+    /// The payloads, keyed by block number.
+    payloads: Arc<Mutex<BTreeMap<u64, RecentPayload>>>,
+    /// The maximum number of payloads kept.
+    capacity: usize,
+}
+
+impl Default for RecentPayloads {
+    fn default() -> Self {
+        Self::new(DEFAULT_RECENT_PAYLOADS_CAPACITY)
+    }
+}
+
+impl RecentPayloads {
+    /// Creates an empty window keeping at most `capacity` payloads.
+    pub fn new(capacity: usize) -> Self {
+        Self { payloads: Default::default(), capacity }
+    }
+
+    /// Records a payload, replacing any payload at the same height and dropping the lowest block
+    /// if the window is full. `gossiped` is `false` if the payload still needs to be propagated.
+    pub fn insert(&self, envelope: OpNetworkPayloadEnvelope, gossiped: bool) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut payloads = self.payloads.lock().unwrap_or_else(PoisonError::into_inner);
+        payloads.insert(envelope.payload.block_number(), RecentPayload { envelope, gossiped });
+        while payloads.len() > self.capacity {
+            payloads.pop_first();
+        }
+    }
+
+    /// Returns the payload at the given height, if it is in the window.
+    pub fn get(&self, number: u64) -> Option<OpNetworkPayloadEnvelope> {
+        let payloads = self.payloads.lock().unwrap_or_else(PoisonError::into_inner);
+        payloads.get(&number).map(|payload| payload.envelope.clone())
+    }
+
+    /// Returns the payloads that still need to be propagated, lowest block first.
+    pub fn ungossiped(&self) -> Vec<OpNetworkPayloadEnvelope> {
+        let payloads = self.payloads.lock().unwrap_or_else(PoisonError::into_inner);
+        payloads
+            .values()
+            .filter(|payload| !payload.gossiped)
+            .map(|payload| payload.envelope.clone())
+            .collect()
+    }
+
+    /// Marks the payload at the given height as propagated.
+    pub fn mark_gossiped(&self, number: u64) {
+        let mut payloads = self.payloads.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(payload) = payloads.get_mut(&number) {
+            payload.gossiped = true;
+        }
+    }
+
+    /// Returns the number of payloads in the window.
+    pub fn len(&self) -> usize {
+        self.payloads.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns `true` if the window holds no payloads.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Encodes a successful `payload_by_number` response carrying the given payload.
+///
+/// The response is the success result code, the little-endian `u32` response version, and the
+/// snappy framed SSZ encoding of the payload. Payloads carrying a parent beacon block root are
+/// encoded as version `1` execution payload envelopes, and earlier payloads as version `0`
+/// execution payloads.
+///
+/// See `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
+pub(crate) fn payload_by_number_response(
+    envelope: &OpNetworkPayloadEnvelope,
+) -> Result<Vec<u8>, HandlerEncodeError> {
+    let (version, gossiped) = match envelope.payload {
+        OpExecutionPayload::V1(_) => (0u32, envelope.encode_v1()?),
+        OpExecutionPayload::V2(_) => (0, envelope.encode_v2()?),
+        OpExecutionPayload::V3(_) => (1, envelope.encode_v3()?),
+        OpExecutionPayload::V4(_) => (1, envelope.encode_v4()?),
+    };
+
+    // Gossiped payloads are the snappy compressed signature, followed by the SSZ encoding of the
+    // payload, or of the envelope once it carries a parent beacon block root.
+    let decompressed =
+        snap::raw::Decoder::new().decompress_vec(&gossiped).map_err(std::io::Error::from)?;
+    let ssz = decompressed.get(SIGNATURE_LENGTH..).unwrap_or_default();
+
+    let mut response = vec![0];
+    response.extend_from_slice(&version.to_le_bytes());
+    snap::read::FrameEncoder::new(ssz).read_to_end(&mut response)?;
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v3_valid_block;
+    use alloy_primitives::{B256, Signature};
+    use alloy_rpc_types_engine::ExecutionPayloadV3;
+    use op_alloy_rpc_types_engine::PayloadHash;
+
+    fn envelope(number: u64) -> OpNetworkPayloadEnvelope {
+        let mut block = v3_valid_block();
+        block.header.number = number;
+        OpNetworkPayloadEnvelope {
+            payload: OpExecutionPayload::V3(ExecutionPayloadV3::from_block_slow(&block)),
+            signature: Signature::test_signature(),
+            payload_hash: PayloadHash(B256::ZERO),
+            parent_beacon_block_root: Some(B256::repeat_byte(1)),
+        }
+    }
+
+    #[test]
+    fn test_recent_payloads_bounded() {
+        let recent = RecentPayloads::new(2);
+        recent.insert(envelope(1), true);
+        recent.insert(envelope(3), true);
+        recent.insert(envelope(2), true);
+
+        assert_eq!(recent.len(), 2);
+        assert!(recent.get(1).is_none());
+        assert_eq!(recent.get(2).unwrap().payload.block_number(), 2);
+        assert_eq!(recent.get(3).unwrap().payload.block_number(), 3);
+    }
+
+    #[test]
+    fn test_recent_payloads_disabled() {
+        let recent = RecentPayloads::new(0);
+        recent.insert(envelope(1), true);
+        assert!(recent.is_empty());
+    }
+
+    #[test]
+    fn test_recent_payloads_ungossiped() {
+        let recent = RecentPayloads::default();
+        recent.insert(envelope(1), true);
+        recent.insert(envelope(2), false);
+        recent.insert(envelope(3), false);
+
+        let numbers =
+            recent.ungossiped().iter().map(|e| e.payload.block_number()).collect::<Vec<_>>();
+        assert_eq!(numbers, [2, 3]);
+
+        recent.mark_gossiped(2);
+        let numbers =
+            recent.ungossiped().iter().map(|e| e.payload.block_number()).collect::<Vec<_>>();
+        assert_eq!(numbers, [3]);
+    }
+
+    #[test]
+    fn test_payload_by_number_response() {
+        let envelope = envelope(1);
+        let response = payload_by_number_response(&envelope).unwrap();
+        assert_eq!(response[..5], [0, 1, 0, 0, 0]);
+
+        let mut ssz = Vec::new();
+        snap::read::FrameDecoder::new(&response[5..]).read_to_end(&mut ssz).unwrap();
+
+        // The envelope is the parent beacon block root, followed by the payload.
+        assert_eq!(ssz[..32], *envelope.parent_beacon_block_root.unwrap());
+        let gossiped =
+            snap::raw::Decoder::new().decompress_vec(&envelope.encode_v3().unwrap()).unwrap();
+        assert_eq!(ssz, gossiped[SIGNATURE_LENGTH..]);
+    }
+}
@@ -31,6 +31,9 @@
 //! - Network address filtering
 //! - Discovery table inspection
 //!
+//! Requests are sent to the network actor as [`TracedP2pRpcRequest`]s, which carry the tracing
+//! span of the RPC call they originate from.
+//!
 //! ## Usage
 //!
 //! The RPC interface is designed to be compatible with existing OP Stack tooling
@@ -44,7 +47,7 @@
 //! appropriate authentication and access controls in production deployments.
 
 mod request;
-pub use request::P2pRpcRequest;
+pub use request::{P2pRpcRequest, TracedP2pRpcRequest};
 
 mod types;
 pub use types::{
@@ -2,7 +2,7 @@
 
 use std::{net::IpAddr, num::TryFromIntError, sync::Arc};
 
-use crate::{GossipDriver, GossipScores};
+use crate::{GossipDriver, GossipMessageTrace, GossipScores};
 use alloy_primitives::map::{HashMap, HashSet};
 use discv5::{
     enr::{NodeId, k256::ecdsa},
@@ -13,6 +13,7 @@ use kona_disc::Discv5Handler;
 use kona_peers::OpStackEnr;
 use libp2p::{Multiaddr, PeerId, gossipsub::TopicHash};
 use tokio::sync::oneshot::Sender;
+use tracing::{Instrument, Span};
 
 use super::{
     PeerDump, PeerStats,
@@ -103,6 +104,48 @@ pub enum P2pRpcRequest {
     /// This information can be used to briefly monitor the current state of the p2p network for a
     /// given peer.
     PeerStats(Sender<PeerStats>),
+    /// Returns the most recent gossip messages received or published by the node, oldest first.
+    RecentMessages {
+        /// The output channel to send the traces to.
+        out: Sender<Vec<GossipMessageTrace>>,
+        /// The maximum number of traces to return. All buffered traces are returned if unset.
+        limit: Option<usize>,
+    },
+}
+
+/// A [`P2pRpcRequest`] along with the tracing span of the RPC call it originates from.
+///
+/// The request is handled within the span, so the logs of the network actor processing it carry
+/// the request ID of the RPC call.
+#[derive(Debug)]
+pub struct TracedP2pRpcRequest {
+    /// The request.
+    pub request: P2pRpcRequest,
+    /// The span the request is handled in.
+    pub span: Span,
+}
+
+impl From<P2pRpcRequest> for TracedP2pRpcRequest {
+    /// Wraps the request in the current span.
+    fn from(request: P2pRpcRequest) -> Self {
+        Self { request, span: Span::current() }
+    }
+}
+
+impl TracedP2pRpcRequest {
+    /// Handles the request within its span.
+    pub fn handle<G: ConnectionGate>(self, gossip: &mut GossipDriver<G>, disc: &Discv5Handler) {
+        let _entered = self.span.enter();
+        self.request.handle(gossip, disc);
+    }
+}
+
+/// Spawns a task answering a request, which logs within the span of the request.
+fn spawn_in_current_span<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(future.in_current_span());
 }
 
 impl P2pRpcRequest {
@@ -127,6 +170,17 @@ impl P2pRpcRequest {
             Self::BlockSubnet { address } => Self::block_subnet(address, gossip),
             Self::UnblockSubnet { address } => Self::unblock_subnet(address, gossip),
             Self::ListBlockedSubnets(s) => Self::list_blocked_subnets(s, gossip),
+            Self::RecentMessages { out, limit } => Self::recent_messages(out, limit, gossip),
+        }
+    }
+
+    fn recent_messages<G: ConnectionGate>(
+        s: Sender<Vec<GossipMessageTrace>>,
+        limit: Option<usize>,
+        gossip: &GossipDriver<G>,
+    ) {
+        if let Err(e) = s.send(gossip.message_traces.recent(limit)) {
+            warn!(target: "p2p::rpc", "Failed to send recent messages through response channel: {:?}", e);
         }
     }
 
@@ -208,7 +262,7 @@ impl P2pRpcRequest {
 
     fn handle_discovery_table(sender: Sender<Vec<String>>, disc: &Discv5Handler) {
         let enrs = disc.table_enrs();
-        tokio::spawn(async move {
+        spawn_in_current_span(async move {
             let dt = match enrs.await {
                 Ok(dt) => dt.into_iter().map(|e| e.to_string()).collect(),
 
@@ -268,6 +322,23 @@ impl P2pRpcRequest {
             })
             .collect::<HashMap<PeerId, Connectedness>>();
 
+        // The direction, transport and duration of the connections to the connected peers.
+        let peer_connections = gossip
+            .peer_endpoints
+            .iter()
+            .filter(|(id, _)| actually_connected.contains(*id))
+            .map(|(id, endpoint)| {
+                let direction =
+                    if endpoint.is_dialer() { Direction::Outbound } else { Direction::Inbound };
+                let duration = gossip
+                    .peer_connection_start
+                    .get(id)
+                    .map(|start| start.elapsed().as_secs())
+                    .unwrap_or_default();
+                (*id, (direction, transport(endpoint.get_remote_address()), duration))
+            })
+            .collect::<HashMap<_, _>>();
+
         // Clone the ping map
         let pings = Arc::clone(&gossip.ping);
 
@@ -346,7 +417,7 @@ impl P2pRpcRequest {
 
         let disc_table_infos = disc.table_infos();
 
-        tokio::spawn(async move {
+        spawn_in_current_span(async move {
             let Ok(table_infos) = disc_table_infos.await else {
                 error!(target: "p2p::rpc", "Failed to get table infos. The connection to the gossip driver is closed.");
                 return;
@@ -390,15 +461,26 @@ impl P2pRpcRequest {
                     let opstack_enr =
                         maybe_enr.clone().and_then(|enr| OpStackEnr::try_from(&enr).ok());
 
-                    let direction = maybe_status
-                        .map(|status| {
-                            if status.is_incoming() {
-                                Direction::Inbound
-                            } else {
-                                Direction::Outbound
-                            }
+                    // The direction and transport of the connection, if the peer is connected.
+                    // Peers that aren't connected fall back to the discovery table status.
+                    let (direction, transport, connected_duration) = peer_connections
+                        .get(peer_id)
+                        .cloned()
+                        .map(|(direction, transport, duration)| {
+                            (direction, Some(transport.to_string()), Some(duration))
                         })
-                        .unwrap_or_default();
+                        .unwrap_or_else(|| {
+                            let direction = maybe_status
+                                .map(|status| {
+                                    if status.is_incoming() {
+                                        Direction::Inbound
+                                    } else {
+                                        Direction::Outbound
+                                    }
+                                })
+                                .unwrap_or_default();
+                            (direction, None, None)
+                        });
 
                     let PeerMetadata { protocols, addresses, user_agent, protocol_version, score } =
                         peer_metadata.remove(peer_id).unwrap_or_default();
@@ -406,7 +488,7 @@ impl P2pRpcRequest {
                     let peer_connectedness =
                         connectedness.get(peer_id).copied().unwrap_or(Connectedness::NotConnected);
 
-                    let latency = pings.get(peer_id).map(|d| d.as_secs()).unwrap_or(0);
+                    let latency = pings.get(peer_id).map(|d| d.as_nanos() as u64).unwrap_or(0);
 
                     let node_id = format!("{:?}", &id);
                     (
@@ -421,6 +503,8 @@ impl P2pRpcRequest {
                             protocols,
                             connectedness: peer_connectedness,
                             direction,
+                            transport,
+                            connected_duration,
                             // Note: we use the chain id from the ENR if it exists, otherwise we
                             // use 0 to be consistent with op-node's behavior (`<https://github.com/ethereum-optimism/optimism/blob/6a8b2349c29c2a14f948fcb8aefb90526130acec/op-service/apis/p2p.go#L55>`).
                             chain_id: opstack_enr.map(|enr| enr.chain_id).unwrap_or(0),
@@ -494,7 +578,7 @@ impl P2pRpcRequest {
             &mut gossip.swarm.external_addresses().map(|a| a.to_string()).collect::<Vec<String>>(),
         );
 
-        tokio::spawn(async move {
+        spawn_in_current_span(async move {
             let enr = match local_enr.await {
                 Ok(enr) => enr,
                 Err(e) => {
@@ -512,7 +596,7 @@ impl P2pRpcRequest {
                 peer_id: peer_id.to_string(),
                 node_id,
                 user_agent: "kona".to_string(),
-                protocol_version: String::new(),
+                protocol_version: crate::opstack_protocol_version(chain_id),
                 enr: Some(enr.to_string()),
                 addresses,
                 protocols: Some(vec![
@@ -527,6 +611,8 @@ impl P2pRpcRequest {
                 ]),
                 connectedness: Connectedness::Connected,
                 direction: Direction::Inbound,
+                transport: None,
+                connected_duration: None,
                 protected: false,
                 chain_id,
                 latency: 0,
@@ -573,7 +659,7 @@ impl P2pRpcRequest {
         let v3_topic_hash = gossip.handler.blocks_v3_topic.hash();
         let v4_topic_hash = gossip.handler.blocks_v4_topic.hash();
 
-        tokio::spawn(async move {
+        spawn_in_current_span(async move {
             let Ok(table) = table_info.await else {
                 error!(target: "p2p::rpc", "failed to get discovery table size. The sender has been dropped. The discv5 service may not be running anymore.");
                 return;
@@ -646,7 +732,7 @@ impl P2pRpcRequest {
     ) {
         let pc_req = disc.peer_count();
         let gossip_pc = gossip.connected_peers();
-        tokio::spawn(async move {
+        spawn_in_current_span(async move {
             let pc = match pc_req.await {
                 Ok(pc) => Some(pc),
                 Err(e) => {
@@ -660,3 +746,27 @@ impl P2pRpcRequest {
         });
     }
 }
+
+/// Returns the transport of a connection from its remote address, matching the transport names
+/// reported by the op-node.
+fn transport(addr: &Multiaddr) -> &'static str {
+    if addr.iter().any(|protocol| matches!(protocol, Protocol::QuicV1 | Protocol::Quic)) {
+        "quic"
+    } else {
+        "tcp"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transport() {
+        let tcp: Multiaddr = "/ip4/127.0.0.1/tcp/9222".parse().unwrap();
+        assert_eq!(transport(&tcp), "tcp");
+
+        let quic: Multiaddr = "/ip4/127.0.0.1/udp/9222/quic-v1".parse().unwrap();
+        assert_eq!(transport(&quic), "quic");
+    }
+}
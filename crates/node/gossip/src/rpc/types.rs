@@ -39,6 +39,14 @@ pub struct PeerInfo {
     /// 1: "Inbound" (if the peer contacted us)
     /// 2: "Outbound" (if we connected to them)
     pub direction: Direction,
+    /// The transport of the connection to the peer, either `tcp` or `quic`.
+    /// Only set for connected peers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    /// The number of seconds the peer has been connected for.
+    /// Only set for connected peers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connected_duration: Option<u64>,
     /// Whether the peer is protected.
     pub protected: bool,
     /// The chain id.
@@ -377,6 +385,8 @@ mod tests {
             protocols: Some([String::from("eth"), String::from("p2p")].to_vec()),
             connectedness: Connectedness::Connected,
             direction: Direction::Outbound,
+            transport: Some(String::from("tcp")),
+            connected_duration: Some(42),
             protected: true,
             chain_id: 1,
             latency: 100,
@@ -415,6 +425,8 @@ mod tests {
         assert_eq!(peer_info.protocols, deserialized.protocols);
         assert_eq!(peer_info.connectedness, deserialized.connectedness);
         assert_eq!(peer_info.direction, deserialized.direction);
+        assert_eq!(peer_info.transport, deserialized.transport);
+        assert_eq!(peer_info.connected_duration, deserialized.connected_duration);
         assert_eq!(peer_info.protected, deserialized.protected);
         assert_eq!(peer_info.chain_id, deserialized.chain_id);
         assert_eq!(peer_info.latency, deserialized.latency);
@@ -0,0 +1,192 @@
+//! Target peer counts for the gossip swarm.
+
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, time::Duration};
+use thiserror::Error;
+
+/// The target number of connected gossip peers.
+///
+/// The gossip driver actively searches for new peers while below the low watermark, and stops
+/// dialing peers returned by discovery once the high watermark is reached. Peers connected above
+/// the high watermark are pruned, lowest score first, once their grace period has elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerTargets {
+    /// Low-tide peer count. The node actively searches for new peers if below this amount.
+    pub lo: usize,
+    /// High-tide peer count. The node starts pruning peers after reaching this amount.
+    pub hi: usize,
+    /// Grace period to keep a newly connected peer around before it may be pruned.
+    #[serde(skip)]
+    pub grace: Duration,
+}
+
+/// An error returned when constructing invalid [`PeerTargets`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PeerTargetsError {
+    /// The low watermark is above the high watermark.
+    #[error("Low peer watermark {lo} is above the high peer watermark {hi}")]
+    LoAboveHi {
+        /// The low watermark.
+        lo: usize,
+        /// The high watermark.
+        hi: usize,
+    },
+}
+
+impl Default for PeerTargets {
+    fn default() -> Self {
+        Self { lo: 20, hi: 30, grace: Duration::from_secs(30) }
+    }
+}
+
+impl PeerTargets {
+    /// Creates new [`PeerTargets`], checking that the low watermark is not above the high
+    /// watermark.
+    pub const fn new(lo: usize, hi: usize, grace: Duration) -> Result<Self, PeerTargetsError> {
+        if lo > hi {
+            return Err(PeerTargetsError::LoAboveHi { lo, hi });
+        }
+        Ok(Self { lo, hi, grace })
+    }
+
+    /// Returns `true` if the given peer count is below the low watermark.
+    pub const fn needs_peers(&self, connected: usize) -> bool {
+        connected < self.lo
+    }
+
+    /// Returns `true` if another peer can be dialed without exceeding the high watermark.
+    pub const fn can_dial(&self, connected: usize) -> bool {
+        connected < self.hi
+    }
+
+    /// Returns the number of peers to prune to get back down to the high watermark.
+    pub const fn excess(&self, connected: usize) -> usize {
+        connected.saturating_sub(self.hi)
+    }
+}
+
+/// The weight of a new ping sample in the moving average of a peer's latency.
+const LATENCY_SAMPLE_WEIGHT: f64 = 0.3;
+
+/// Returns the moving average of a peer's latency after a new ping sample.
+pub fn smoothed_latency(previous: Option<Duration>, sample: Duration) -> Duration {
+    previous.map_or(sample, |previous| {
+        previous.mul_f64(1.0 - LATENCY_SAMPLE_WEIGHT) + sample.mul_f64(LATENCY_SAMPLE_WEIGHT)
+    })
+}
+
+/// A preference for low-latency peers when managing the gossip connection slots.
+///
+/// A share of the high watermark is reserved for the connected peers with the lowest ping
+/// latency: they are never pruned. While the preference is enabled, the driver keeps dialing
+/// one peer above the high watermark so that new peers get measured, and pruning then
+/// disconnects the lowest scoring peer outside of the reserved slots. Over time, the reserved
+/// slots fill up with the closest peers, which improves unsafe block propagation times.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct LatencyPreference {
+    /// The share of the high watermark reserved for the lowest-latency peers, in `[0, 1]`.
+    /// `0` disables the preference.
+    pub share: f64,
+}
+
+/// An error returned when constructing an invalid [`LatencyPreference`].
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+#[error("Low-latency peer share {0} must be between 0 and 1")]
+pub struct LatencyPreferenceError(pub f64);
+
+impl LatencyPreference {
+    /// Creates a new [`LatencyPreference`], checking that the share is in `[0, 1]`.
+    pub fn new(share: f64) -> Result<Self, LatencyPreferenceError> {
+        if !(0.0..=1.0).contains(&share) {
+            return Err(LatencyPreferenceError(share));
+        }
+        Ok(Self { share })
+    }
+
+    /// Returns `true` if the preference is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.share > 0.0
+    }
+
+    /// Returns the number of connection slots reserved for the lowest-latency peers.
+    pub fn reserved_slots(&self, targets: &PeerTargets) -> usize {
+        (targets.hi as f64 * self.share).floor() as usize
+    }
+
+    /// Returns the number of peers that may be dialed above the high watermark.
+    pub fn extra_dials(&self) -> usize {
+        usize::from(self.is_enabled())
+    }
+
+    /// Returns the peers holding the reserved slots: the lowest-latency peers among `latencies`.
+    pub fn preferred_peers(
+        &self,
+        targets: &PeerTargets,
+        latencies: impl IntoIterator<Item = (PeerId, Duration)>,
+    ) -> HashSet<PeerId> {
+        let mut latencies = latencies.into_iter().collect::<Vec<_>>();
+        latencies.sort_by_key(|(_, latency)| *latency);
+        latencies.into_iter().take(self.reserved_slots(targets)).map(|(id, _)| id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_targets_rejects_lo_above_hi() {
+        assert_eq!(
+            PeerTargets::new(31, 30, Duration::ZERO),
+            Err(PeerTargetsError::LoAboveHi { lo: 31, hi: 30 })
+        );
+        assert!(PeerTargets::new(30, 30, Duration::ZERO).is_ok());
+    }
+
+    #[test]
+    fn test_peer_targets_watermarks() {
+        let targets = PeerTargets::new(2, 4, Duration::ZERO).unwrap();
+        assert!(targets.needs_peers(1));
+        assert!(!targets.needs_peers(2));
+        assert!(targets.can_dial(3));
+        assert!(!targets.can_dial(4));
+        assert_eq!(targets.excess(3), 0);
+        assert_eq!(targets.excess(7), 3);
+    }
+
+    #[test]
+    fn test_latency_preference_bounds() {
+        assert_eq!(LatencyPreference::new(1.5), Err(LatencyPreferenceError(1.5)));
+        assert_eq!(LatencyPreference::new(-0.1), Err(LatencyPreferenceError(-0.1)));
+        assert!(!LatencyPreference::default().is_enabled());
+        assert_eq!(LatencyPreference::default().extra_dials(), 0);
+    }
+
+    #[test]
+    fn test_latency_preference_preferred_peers() {
+        let targets = PeerTargets::new(2, 10, Duration::ZERO).unwrap();
+        let preference = LatencyPreference::new(0.25).unwrap();
+        assert_eq!(preference.reserved_slots(&targets), 2);
+        assert_eq!(preference.extra_dials(), 1);
+
+        let peers = (0..4).map(|_| PeerId::random()).collect::<Vec<_>>();
+        let latencies = [300, 20, 150, 40]
+            .into_iter()
+            .zip(&peers)
+            .map(|(ms, id)| (*id, Duration::from_millis(ms)));
+        assert_eq!(
+            preference.preferred_peers(&targets, latencies),
+            HashSet::from([peers[1], peers[3]])
+        );
+    }
+
+    #[test]
+    fn test_smoothed_latency() {
+        let sample = Duration::from_millis(100);
+        assert_eq!(smoothed_latency(None, sample), sample);
+        let smoothed = smoothed_latency(Some(Duration::from_millis(200)), sample);
+        assert!(smoothed.abs_diff(Duration::from_millis(170)) < Duration::from_micros(1));
+    }
+}
@@ -0,0 +1,247 @@
+//! A bounded in-memory record of recent gossip messages.
+
+use alloy_primitives::B256;
+use libp2p::{
+    PeerId,
+    gossipsub::{MessageAcceptance, MessageId, PublishError, TopicHash},
+};
+use op_alloy_rpc_types_engine::OpNetworkPayloadEnvelope;
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime},
+};
+
+/// The default number of gossip messages kept by [`MessageTraces`].
+pub const DEFAULT_MESSAGE_TRACE_CAPACITY: usize = 256;
+
+/// The outcome of a traced gossip message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MessageOutcome {
+    /// The received message was valid, and propagated to the mesh.
+    Accepted,
+    /// The received message was dropped without penalizing the peer.
+    Ignored,
+    /// The received message was invalid, and the peer was penalized.
+    Rejected,
+    /// The message was published by the node.
+    Published,
+    /// The node failed to publish the message.
+    PublishFailed,
+}
+
+impl From<&MessageAcceptance> for MessageOutcome {
+    fn from(acceptance: &MessageAcceptance) -> Self {
+        match acceptance {
+            MessageAcceptance::Accept => Self::Accepted,
+            MessageAcceptance::Ignore => Self::Ignored,
+            MessageAcceptance::Reject => Self::Rejected,
+        }
+    }
+}
+
+/// A gossip message received or published by the node.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GossipMessageTrace {
+    /// The unix timestamp at which the message was received or published, in milliseconds.
+    pub timestamp: u64,
+    /// The topic of the message.
+    pub topic: String,
+    /// The peer that propagated the message to the node. Unset for published messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer: Option<String>,
+    /// The gossipsub message id. Unset if the message failed to publish.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// The outcome of the message.
+    pub outcome: MessageOutcome,
+    /// The number of the block carried by the message, if it was decoded and valid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// The hash of the block carried by the message, if it was decoded and valid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<B256>,
+    /// The time elapsed between the block timestamp and the message, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// The time spent validating a received message, in microseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_us: Option<u64>,
+    /// The error of a failed publish.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl GossipMessageTrace {
+    /// Creates a trace of a message received from `peer`.
+    pub fn received(
+        topic: &TopicHash,
+        peer: &PeerId,
+        id: &MessageId,
+        acceptance: &MessageAcceptance,
+        payload: Option<&OpNetworkPayloadEnvelope>,
+        validation: Duration,
+    ) -> Self {
+        Self {
+            peer: Some(peer.to_string()),
+            message_id: Some(id.to_string()),
+            validation_us: Some(validation.as_micros() as u64),
+            ..Self::new(topic, acceptance.into(), payload)
+        }
+    }
+
+    /// Creates a trace of a message published by the node. The outcome is updated with
+    /// [`Self::with_publish_result`] once the message is published.
+    pub fn published(topic: &TopicHash, payload: &OpNetworkPayloadEnvelope) -> Self {
+        Self::new(topic, MessageOutcome::Published, Some(payload))
+    }
+
+    /// Sets the outcome of a published message.
+    pub fn with_publish_result(mut self, result: Result<&MessageId, &PublishError>) -> Self {
+        match result {
+            Ok(id) => self.message_id = Some(id.to_string()),
+            Err(e) => {
+                self.outcome = MessageOutcome::PublishFailed;
+                self.error = Some(e.to_string());
+            }
+        }
+        self
+    }
+
+    fn new(
+        topic: &TopicHash,
+        outcome: MessageOutcome,
+        payload: Option<&OpNetworkPayloadEnvelope>,
+    ) -> Self {
+        let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        let latency_ms = payload.and_then(|envelope| {
+            now.checked_sub(Duration::from_secs(envelope.payload.timestamp()))
+                .map(|latency| latency.as_millis() as u64)
+        });
+        Self {
+            timestamp: now.as_millis() as u64,
+            topic: topic.to_string(),
+            peer: None,
+            message_id: None,
+            outcome,
+            block_number: payload.map(|envelope| envelope.payload.block_number()),
+            block_hash: payload.map(|envelope| envelope.payload.block_hash()),
+            latency_ms,
+            validation_us: None,
+            error: None,
+        }
+    }
+}
+
+/// A ring buffer of the most recent [`GossipMessageTrace`]s.
+///
+/// Once full, the oldest trace is dropped for each new one.
+#[derive(Debug, Clone)]
+pub struct MessageTraces {
+    /// The traces, oldest first.
+    traces: VecDeque<GossipMessageTrace>,
+    /// The maximum number of traces kept.
+    capacity: usize,
+}
+
+impl Default for MessageTraces {
+    fn default() -> Self {
+        Self::new(DEFAULT_MESSAGE_TRACE_CAPACITY)
+    }
+}
+
+impl MessageTraces {
+    /// Creates an empty buffer keeping at most `capacity` traces.
+    pub fn new(capacity: usize) -> Self {
+        Self { traces: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    /// Records a trace, dropping the oldest one if the buffer is full.
+    pub fn push(&mut self, trace: GossipMessageTrace) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.traces.len() == self.capacity {
+            self.traces.pop_front();
+        }
+        self.traces.push_back(trace);
+    }
+
+    /// Returns up to `limit` of the most recent traces, oldest first. All traces are returned if
+    /// `limit` is [`None`].
+    pub fn recent(&self, limit: Option<usize>) -> Vec<GossipMessageTrace> {
+        let skip = limit.map_or(0, |limit| self.traces.len().saturating_sub(limit));
+        self.traces.iter().skip(skip).cloned().collect()
+    }
+
+    /// Returns the number of traces in the buffer.
+    pub fn len(&self) -> usize {
+        self.traces.len()
+    }
+
+    /// Returns `true` if the buffer holds no traces.
+    pub fn is_empty(&self) -> bool {
+        self.traces.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(topic: &str) -> GossipMessageTrace {
+        GossipMessageTrace::received(
+            &TopicHash::from_raw(topic),
+            &PeerId::random(),
+            &MessageId::from("id"),
+            &MessageAcceptance::Accept,
+            None,
+            Duration::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_message_traces_bounded() {
+        let mut traces = MessageTraces::new(2);
+        traces.push(trace("a"));
+        traces.push(trace("b"));
+        traces.push(trace("c"));
+
+        assert_eq!(traces.len(), 2);
+        let topics: Vec<_> = traces.recent(None).into_iter().map(|t| t.topic).collect();
+        assert_eq!(topics, ["b", "c"]);
+        let topics: Vec<_> = traces.recent(Some(1)).into_iter().map(|t| t.topic).collect();
+        assert_eq!(topics, ["c"]);
+    }
+
+    #[test]
+    fn test_message_traces_disabled() {
+        let mut traces = MessageTraces::new(0);
+        traces.push(trace("a"));
+        assert!(traces.is_empty());
+    }
+
+    #[test]
+    fn test_publish_failed_trace() {
+        let trace = trace("a").with_publish_result(Err(&PublishError::InsufficientPeers));
+        assert_eq!(trace.outcome, MessageOutcome::PublishFailed);
+        assert!(trace.error.is_some());
+    }
+
+    #[test]
+    fn test_received_trace() {
+        let trace = GossipMessageTrace::received(
+            &TopicHash::from_raw("/optimism/10/3/blocks"),
+            &PeerId::random(),
+            &MessageId::from("id"),
+            &MessageAcceptance::Reject,
+            None,
+            Duration::from_micros(150),
+        );
+        assert_eq!(trace.outcome, MessageOutcome::Rejected);
+        assert_eq!(trace.validation_us, Some(150));
+        assert!(trace.block_number.is_none());
+        assert!(trace.latency_ms.is_none());
+    }
+}
@@ -38,7 +38,8 @@ pub use record::{NodeRecord, NodeRecordParseError};
 
 mod utils;
 pub use utils::{
-    PeerIdConversionError, enr_to_multiaddr, local_id_to_p2p_id, peer_id_to_secp256k1_pubkey,
+    ENR_QUIC_KEY, ENR_QUIC6_KEY, PeerIdConversionError, enr_to_multiaddr, enr_to_quic_multiaddr,
+    local_id_to_p2p_id, peer_id_to_secp256k1_pubkey,
 };
 
 mod monitoring;
@@ -9,6 +9,12 @@ use libp2p::Multiaddr;
 
 use super::PeerId;
 
+/// The ENR key advertising the IPv4 QUIC port of a node.
+pub const ENR_QUIC_KEY: &str = "quic";
+
+/// The ENR key advertising the IPv6 QUIC port of a node.
+pub const ENR_QUIC6_KEY: &str = "quic6";
+
 /// Converts an [`Enr`] into a [`Multiaddr`].
 pub fn enr_to_multiaddr(enr: &Enr) -> Option<Multiaddr> {
     let mut addr = if let Some(socket) = enr.tcp4_socket() {
@@ -23,6 +29,38 @@ pub fn enr_to_multiaddr(enr: &Enr) -> Option<Multiaddr> {
         return None;
     };
 
+    addr.push(Protocol::P2p(enr_peer_id(enr)?));
+
+    Some(addr)
+}
+
+/// Converts an [`Enr`] into a QUIC [`Multiaddr`].
+///
+/// Returns `None` if the [`Enr`] doesn't advertise a QUIC port under the [`ENR_QUIC_KEY`] or
+/// [`ENR_QUIC6_KEY`] keys.
+pub fn enr_to_quic_multiaddr(enr: &Enr) -> Option<Multiaddr> {
+    let quic4 = enr.ip4().zip(enr.get_decodable::<u16>(ENR_QUIC_KEY).and_then(Result::ok));
+    let quic6 = enr.ip6().zip(enr.get_decodable::<u16>(ENR_QUIC6_KEY).and_then(Result::ok));
+    let mut addr = if let Some((ip, port)) = quic4 {
+        let mut addr = Multiaddr::from(ip);
+        addr.push(Protocol::Udp(port));
+        addr
+    } else if let Some((ip, port)) = quic6 {
+        let mut addr = Multiaddr::from(ip);
+        addr.push(Protocol::Udp(port));
+        addr
+    } else {
+        return None;
+    };
+    addr.push(Protocol::QuicV1);
+
+    addr.push(Protocol::P2p(enr_peer_id(enr)?));
+
+    Some(addr)
+}
+
+/// Returns the libp2p [`PeerId`](libp2p::PeerId) of the node advertised by an [`Enr`].
+fn enr_peer_id(enr: &Enr) -> Option<libp2p::PeerId> {
     let CombinedPublicKey::Secp256k1(pub_key) = enr.public_key() else {
         return None;
     };
@@ -30,9 +68,7 @@ pub fn enr_to_multiaddr(enr: &Enr) -> Option<Multiaddr> {
     let pub_key = libp2p_identity::secp256k1::PublicKey::try_from_bytes(&pub_key.encode()).ok()?;
     let pub_key = libp2p_identity::PublicKey::from(pub_key);
 
-    addr.push(Protocol::P2p(libp2p::PeerId::from_public_key(&pub_key)));
-
-    Some(addr)
+    Some(libp2p::PeerId::from_public_key(&pub_key))
 }
 
 /// Converts an uncompressed [`PeerId`] to a [`secp256k1::PublicKey`] by prepending the [`PeerId`]
@@ -125,6 +161,32 @@ mod tests {
         assert_eq!(received_p2p_id, Some(peer_id));
     }
 
+    #[test]
+    fn test_resolve_quic_multiaddr() {
+        let ip = Ipv4Addr::new(132, 145, 16, 10);
+        let private_key = CombinedKey::generate_secp256k1();
+
+        let enr = Enr::builder().ip4(ip).tcp4(9000).udp4(9001).build(&private_key).unwrap();
+        assert_eq!(enr_to_quic_multiaddr(&enr), None);
+
+        let enr = Enr::builder()
+            .ip4(ip)
+            .tcp4(9000)
+            .udp4(9001)
+            .add_value(ENR_QUIC_KEY, &9002u16)
+            .build(&private_key)
+            .unwrap();
+        let peer_id = enr_to_multiaddr(&enr)
+            .and_then(|addr| addr.iter().last())
+            .expect("The ENR should have a TCP multiaddr");
+
+        let multiaddr = enr_to_quic_multiaddr(&enr).unwrap();
+        assert_eq!(
+            multiaddr.iter().collect::<Vec<_>>(),
+            [Protocol::Ip4(ip), Protocol::Udp(9002), Protocol::QuicV1, peer_id]
+        );
+    }
+
     #[test]
     fn test_resolve_multiaddr_ipv6() {
         let ip = Ipv6Addr::new(0x2001, 0xdb8, 0x0a, 0x11, 0x1e, 0x8a, 0x2e, 0x3a);
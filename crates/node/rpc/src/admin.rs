@@ -1,6 +1,9 @@
 //! Admin RPC Module
 
-use crate::AdminApiServer;
+use crate::{
+    AdminApiServer, DerivationHalt, DerivationHaltSwitch, RuntimeFlag, RuntimeFlagStatus,
+    RuntimeFlags,
+};
 use alloy_primitives::B256;
 use async_trait::async_trait;
 use core::fmt::Debug;
@@ -8,6 +11,8 @@ use jsonrpsee::{
     core::RpcResult,
     types::{ErrorCode, ErrorObject},
 };
+use kona_gossip::{PeerTargets, PeerTargetsError};
+use kona_protocol::{BlockInfo, L2BlockInfo};
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
 use rollup_boost::{
     ExecutionMode, GetExecutionModeResponse, SetExecutionModeRequest, SetExecutionModeResponse,
@@ -23,6 +28,18 @@ pub enum NetworkAdminQuery {
         /// The payload to post.
         payload: OpExecutionPayloadEnvelope,
     },
+    /// An admin rpc request to set the target peer counts.
+    SetPeerTargets {
+        /// The low watermark.
+        lo: usize,
+        /// The high watermark.
+        hi: usize,
+    },
+    /// An admin rpc request to get the target peer counts.
+    GetPeerTargets {
+        /// The sender to send the peer targets to.
+        sender: oneshot::Sender<PeerTargets>,
+    },
 }
 
 /// The query types to the rollup boost component of the engine actor.
@@ -41,8 +58,24 @@ pub enum RollupBoostAdminQuery {
     },
 }
 
+/// The query types to the engine actor for the admin api.
+#[derive(Debug)]
+pub enum EngineAdminQuery {
+    /// An admin rpc request to reset the derivation pipeline, optionally pinning the L2 safe head
+    /// and L1 origin it restarts from.
+    ResetDerivationPipeline {
+        /// The L2 safe head to restart derivation from.
+        l2_safe_head: Option<L2BlockInfo>,
+        /// The L1 origin to restart derivation from.
+        l1_origin: Option<BlockInfo>,
+        /// The sender to send the result of the reset to.
+        sender: oneshot::Sender<Result<(), String>>,
+    },
+}
+
 type NetworkAdminQuerySender = tokio::sync::mpsc::Sender<NetworkAdminQuery>;
 type RollupBoostAdminQuerySender = tokio::sync::mpsc::Sender<RollupBoostAdminQuery>;
+type EngineAdminQuerySender = tokio::sync::mpsc::Sender<EngineAdminQuery>;
 
 /// The admin rpc server.
 #[derive(Debug)]
@@ -54,6 +87,12 @@ pub struct AdminRpc<SequencerAdminAPIClient> {
     /// The sender to the rollup boost component of the engine actor.
     /// Only set when rollup boost is enabled.
     pub rollup_boost_sender: Option<RollupBoostAdminQuerySender>,
+    /// The registry of the runtime flags of the node.
+    pub runtime_flags: RuntimeFlags,
+    /// The switch halting derivation, acknowledged through the admin RPC.
+    pub derivation_halt: DerivationHaltSwitch,
+    /// The sender to the engine actor, resetting the derivation pipeline in any node mode.
+    pub engine_sender: Option<EngineAdminQuerySender>,
 }
 
 impl<S: SequencerAdminAPIClient> AdminRpc<S> {
@@ -66,6 +105,8 @@ impl<S: SequencerAdminAPIClient> AdminRpc<S> {
     /// - `network_sender`: The sender to the network actor.
     /// - `rollup_boost_sender`: Sender of admin queries to the rollup boost component of the engine
     ///   actor.
+    /// - `runtime_flags`: The registry of the runtime flags of the node.
+    /// - `derivation_halt`: The switch halting derivation.
     ///
     /// # Returns
     ///
@@ -74,8 +115,24 @@ impl<S: SequencerAdminAPIClient> AdminRpc<S> {
         sequencer_admin_client: Option<S>,
         network_sender: NetworkAdminQuerySender,
         rollup_boost_sender: Option<RollupBoostAdminQuerySender>,
+        runtime_flags: RuntimeFlags,
+        derivation_halt: DerivationHaltSwitch,
     ) -> Self {
-        Self { sequencer_admin_client, network_sender, rollup_boost_sender }
+        Self {
+            sequencer_admin_client,
+            network_sender,
+            rollup_boost_sender,
+            runtime_flags,
+            derivation_halt,
+            engine_sender: None,
+        }
+    }
+
+    /// Sets the sender to the engine actor, through which the derivation pipeline is reset
+    /// without going through the sequencer.
+    pub fn with_engine_sender(mut self, engine_sender: EngineAdminQuerySender) -> Self {
+        self.engine_sender = Some(engine_sender);
+        self
     }
 }
 
@@ -92,6 +149,70 @@ impl<S: SequencerAdminAPIClient + 'static> AdminApiServer for AdminRpc<S> {
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
 
+    async fn admin_set_peer_targets(&self, lo: usize, hi: usize) -> RpcResult<()> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "admin_setPeerTargets");
+        if lo > hi {
+            return Err(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                PeerTargetsError::LoAboveHi { lo, hi }.to_string(),
+                None::<()>,
+            ));
+        }
+
+        self.network_sender
+            .send(NetworkAdminQuery::SetPeerTargets { lo, hi })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+    }
+
+    async fn admin_peer_targets(&self) -> RpcResult<PeerTargets> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "admin_peerTargets");
+        let (sender, rx) = oneshot::channel();
+
+        self.network_sender
+            .send(NetworkAdminQuery::GetPeerTargets { sender })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+    }
+
+    async fn admin_set_runtime_flag(&self, flag: RuntimeFlag, enabled: bool) -> RpcResult<()> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "admin_setRuntimeFlag");
+        if self.runtime_flags.set(flag, enabled) {
+            warn!(target: "rpc", ?flag, enabled, "Runtime flag changed");
+        }
+        Ok(())
+    }
+
+    async fn admin_runtime_flags(&self) -> RpcResult<Vec<RuntimeFlagStatus>> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "admin_runtimeFlags");
+        Ok(self.runtime_flags.status())
+    }
+
+    async fn admin_derivation_halt(&self) -> RpcResult<Option<DerivationHalt>> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "admin_derivationHalt");
+        Ok(self.derivation_halt.halted())
+    }
+
+    async fn admin_acknowledge_derivation_halt(&self) -> RpcResult<Option<DerivationHalt>> {
+        kona_macros::inc!(
+            gauge,
+            kona_gossip::Metrics::RPC_CALLS,
+            "method" => "admin_acknowledgeDerivationHalt"
+        );
+        let acknowledged = self.derivation_halt.acknowledge();
+        if let Some(halt) = &acknowledged {
+            warn!(
+                target: "rpc",
+                reason = %halt.reason,
+                block_number = halt.block_number,
+                "Derivation halt acknowledged, resuming derivation"
+            );
+        }
+        Ok(acknowledged)
+    }
+
     async fn admin_sequencer_active(&self) -> RpcResult<bool> {
         // If the sequencer is not enabled (mode runs in validator mode), return an error.
         let Some(ref sequencer_client) = self.sequencer_admin_client else {
@@ -210,14 +331,39 @@ impl<S: SequencerAdminAPIClient + 'static> AdminApiServer for AdminRpc<S> {
             .map(|execution_mode| GetExecutionModeResponse { execution_mode })
     }
 
-    async fn admin_reset_derivation_pipeline(&self) -> RpcResult<()> {
-        // If the sequencer is not enabled (mode runs in validator mode), return an error.
+    async fn admin_reset_derivation_pipeline(
+        &self,
+        l2_safe_head: Option<L2BlockInfo>,
+        l1_origin: Option<BlockInfo>,
+    ) -> RpcResult<()> {
+        // The engine is rewound to the pinned L2 safe head, and derivation restarts from the
+        // pinned L1 origin, so the anchors are only meaningful together.
+        if l2_safe_head.is_some() != l1_origin.is_some() {
+            return Err(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                "l2SafeHead and l1Origin must be set together",
+                None::<()>,
+            ));
+        }
+
+        if let Some(ref engine_sender) = self.engine_sender {
+            let (sender, rx) = oneshot::channel();
+            engine_sender
+                .send(EngineAdminQuery::ResetDerivationPipeline { l2_safe_head, l1_origin, sender })
+                .await
+                .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+            return rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?.map_err(
+                |err| ErrorObject::owned(ErrorCode::InternalError.code(), err, None::<()>),
+            );
+        }
+
+        // Without an engine sender, the reset goes through the sequencer, if it is enabled.
         let Some(ref sequencer_client) = self.sequencer_admin_client else {
             return Err(ErrorObject::from(ErrorCode::MethodNotFound));
         };
 
         sequencer_client
-            .reset_derivation_pipeline()
+            .reset_derivation_pipeline(l2_safe_head, l1_origin)
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -247,8 +393,13 @@ pub trait SequencerAdminAPIClient: Send + Sync + Debug {
     /// Override the leader.
     async fn override_leader(&self) -> Result<(), SequencerAdminAPIError>;
 
-    /// Reset the derivation pipeline.
-    async fn reset_derivation_pipeline(&self) -> Result<(), SequencerAdminAPIError>;
+    /// Reset the derivation pipeline, optionally pinning the L2 safe head and L1 origin it restarts
+    /// from.
+    async fn reset_derivation_pipeline(
+        &self,
+        l2_safe_head: Option<L2BlockInfo>,
+        l1_origin: Option<BlockInfo>,
+    ) -> Result<(), SequencerAdminAPIError>;
 }
 
 /// Errors that can occur when using the sequencer admin API.
@@ -0,0 +1,64 @@
+//! Build information of the node, served by `kona_buildInfo`.
+
+use kona_genesis::HardForkConfig;
+use serde::{Deserialize, Serialize};
+
+/// The build information of the node, assembled at compile time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    /// The semantic version of the node, suffixed with `-dev` for untagged or dirty builds.
+    pub version: String,
+    /// The git commit the node was built from.
+    pub commit: String,
+    /// The cargo profile the node was built with.
+    pub build_profile: String,
+    /// The cargo features enabled in the build.
+    pub features: Vec<String>,
+    /// The hardforks supported by the node.
+    pub hardforks: Vec<String>,
+}
+
+impl BuildInfo {
+    /// Creates a new [`BuildInfo`], with the features given as a comma separated list.
+    ///
+    /// The supported hardforks are those known to [`HardForkConfig`].
+    pub fn new(version: &str, commit: &str, build_profile: &str, features: &str) -> Self {
+        Self {
+            version: version.to_string(),
+            commit: commit.to_string(),
+            build_profile: build_profile.to_string(),
+            features: features
+                .split(',')
+                .map(str::trim)
+                .filter(|feature| !feature.is_empty())
+                .map(String::from)
+                .collect(),
+            hardforks: HardForkConfig::default().iter().map(|(name, _)| name.to_string()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_new() {
+        let info = BuildInfo::new("1.0.0-dev", "defa64b2", "maxperf", "asm-keccak,jemalloc");
+        assert_eq!(info.features, ["asm-keccak", "jemalloc"]);
+        assert_eq!(info.hardforks.first().map(String::as_str), Some("Regolith"));
+        assert!(info.hardforks.iter().any(|fork| fork == "Jovian"));
+
+        let info = BuildInfo::new("1.0.0", "defa64b2", "release", "");
+        assert!(info.features.is_empty());
+    }
+
+    #[test]
+    fn test_build_info_serde() {
+        let info = BuildInfo::new("1.0.0", "defa64b2", "release", "asm-keccak");
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["buildProfile"], "release");
+        assert_eq!(json["features"][0], "asm-keccak");
+    }
+}
@@ -1,6 +1,12 @@
 //! Contains the RPC Configuration.
 
-use std::{net::SocketAddr, path::PathBuf};
+use crate::BuildInfo;
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
 
 /// The RPC configuration.
 #[derive(Debug, Clone)]
@@ -18,6 +24,72 @@ pub struct RpcBuilder {
     pub ws_enabled: bool,
     /// Enable development RPC endpoints
     pub dev_enabled: bool,
+    /// Terminate TLS on the RPC server with the given certificates. Plaintext if not set.
+    pub tls: Option<RpcTlsConfig>,
+    /// The disabled and rate limited methods of the p2p and admin namespaces.
+    pub method_policy: RpcMethodPolicy,
+    /// The build information of the node, served by `kona_buildInfo`.
+    pub build_info: Option<BuildInfo>,
+}
+
+/// Restrictions on the methods of the p2p and admin namespaces of the RPC server, some of which
+/// are expensive enough to degrade the node when called too often.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcMethodPolicy {
+    /// The methods that are not served, as if they did not exist.
+    pub disabled: HashSet<String>,
+    /// The maximum number of calls per second of rate limited methods, across all clients.
+    pub rate_limits: HashMap<String, NonZeroU32>,
+}
+
+impl RpcMethodPolicy {
+    /// The namespaces whose methods can be disabled or rate limited.
+    pub const NAMESPACES: [&str; 2] = ["opp2p", "admin"];
+
+    /// Returns whether the method belongs to one of the [`Self::NAMESPACES`].
+    pub fn is_restrictable(method: &str) -> bool {
+        method.split_once('_').is_some_and(|(namespace, name)| {
+            !name.is_empty() && Self::NAMESPACES.contains(&namespace)
+        })
+    }
+
+    /// Returns whether the policy places no restriction on any method.
+    pub fn is_empty(&self) -> bool {
+        self.disabled.is_empty() && self.rate_limits.is_empty()
+    }
+}
+
+/// A certificate chain and its private key, stored in PEM files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsCertKeyPaths {
+    /// Path to the certificate chain in PEM format.
+    pub cert: PathBuf,
+    /// Path to the private key in PEM format.
+    pub key: PathBuf,
+}
+
+/// The TLS configuration of the RPC server.
+///
+/// The certificate files are watched, and reloaded without restarting the server when they change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcTlsConfig {
+    /// The certificate served to clients that don't send a server name indication, or whose
+    /// server name has no certificate in [`Self::sni`].
+    pub default: TlsCertKeyPaths,
+    /// Certificates selected by the server name indication of the client.
+    ///
+    /// Server names are matched case-insensitively. A name starting with `*.` matches any single
+    /// label in its place.
+    pub sni: Vec<(String, TlsCertKeyPaths)>,
+}
+
+impl RpcTlsConfig {
+    /// Returns the paths of all the certificate and key files of the configuration.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(&self.default)
+            .chain(self.sni.iter().map(|(_, paths)| paths))
+            .flat_map(|paths| [paths.cert.as_path(), paths.key.as_path()])
+    }
 }
 
 impl RpcBuilder {
@@ -36,6 +108,16 @@ impl RpcBuilder {
         self.socket
     }
 
+    /// Returns the TLS configuration of the RPC server, if it terminates TLS.
+    pub const fn tls(&self) -> Option<&RpcTlsConfig> {
+        self.tls.as_ref()
+    }
+
+    /// Returns the disabled and rate limited methods of the p2p and admin namespaces.
+    pub const fn method_policy(&self) -> &RpcMethodPolicy {
+        &self.method_policy
+    }
+
     /// Returns the number of times the RPC server will attempt to restart if it stops.
     pub const fn restart_count(&self) -> u32 {
         if self.no_restart { 0 } else { 3 }
@@ -46,3 +128,17 @@ impl RpcBuilder {
         Self { socket: addr, ..self }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_restrictable() {
+        assert!(RpcMethodPolicy::is_restrictable("opp2p_discoveryTable"));
+        assert!(RpcMethodPolicy::is_restrictable("admin_startSequencer"));
+        assert!(!RpcMethodPolicy::is_restrictable("optimism_syncStatus"));
+        assert!(!RpcMethodPolicy::is_restrictable("opp2p_"));
+        assert!(!RpcMethodPolicy::is_restrictable("opp2p"));
+    }
+}
@@ -0,0 +1,43 @@
+//! Response to the node counters request.
+
+/// The cumulative counters of the node, persisted across restarts.
+///
+/// Served by `rollup_nodeCounters`, when the node records them in its database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeCountersResponse {
+    /// The number of L2 blocks the safe head advanced by.
+    pub derived_blocks: u64,
+    /// The number of reorgs of the unsafe L2 chain handled by the engine.
+    pub l2_reorgs: u64,
+    /// The number of invalid payloads replaced by deposits-only blocks.
+    pub deposit_only_blocks: u64,
+    /// The number of derivation pipeline resets.
+    pub pipeline_resets: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_counters_response_serde() {
+        let response = NodeCountersResponse {
+            derived_blocks: 120,
+            l2_reorgs: 2,
+            deposit_only_blocks: 1,
+            pipeline_resets: 3,
+        };
+        let json = serde_json::to_value(response).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "derivedBlocks": 120,
+                "l2Reorgs": 2,
+                "depositOnlyBlocks": 1,
+                "pipelineResets": 3,
+            })
+        );
+        assert_eq!(serde_json::from_value::<NodeCountersResponse>(json).unwrap(), response);
+    }
+}
@@ -0,0 +1,152 @@
+//! The derivation halt switch, tripped by the engine and acknowledged through the admin RPC.
+
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Why derivation was halted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum DerivationHaltReason {
+    /// The execution layer rejected a streak of consecutive derived payloads as invalid.
+    InvalidPayloadStreak {
+        /// The number of consecutive invalid derived payloads.
+        count: u64,
+    },
+    /// Too many invalid derived payloads were replaced by deposits-only blocks within a window
+    /// of L2 blocks.
+    DepositOnlyBlocks {
+        /// The number of deposits-only replacements within the window.
+        count: u64,
+        /// The number of L2 blocks in the window.
+        window: u64,
+    },
+}
+
+impl core::fmt::Display for DerivationHaltReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidPayloadStreak { count } => {
+                write!(f, "{count} consecutive invalid derived payloads")
+            }
+            Self::DepositOnlyBlocks { count, window } => {
+                write!(f, "{count} deposits-only blocks within {window} L2 blocks")
+            }
+        }
+    }
+}
+
+/// A halt of derivation, returned by `admin_derivationHalt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationHalt {
+    /// Why derivation was halted.
+    pub reason: DerivationHaltReason,
+    /// The number of the L2 block whose derived payload tripped the halt.
+    pub block_number: u64,
+    /// The unix timestamp at which derivation was halted, in seconds.
+    pub halted_at: u64,
+}
+
+/// The switch halting derivation once the engine detects a runaway divergence from the chain.
+///
+/// Clones share the same switch. The engine [`Self::halt`]s derivation, which stays halted until
+/// an operator [`Self::acknowledge`]s the halt through `admin_acknowledgeDerivationHalt`. Like the
+/// runtime flags, the halt is not persisted, and derivation is not halted when the node starts.
+#[derive(Debug, Clone)]
+pub struct DerivationHaltSwitch(Arc<watch::Sender<Option<DerivationHalt>>>);
+
+impl Default for DerivationHaltSwitch {
+    fn default() -> Self {
+        Self(Arc::new(watch::Sender::new(None)))
+    }
+}
+
+impl DerivationHaltSwitch {
+    /// Returns the current halt, if derivation is halted.
+    pub fn halted(&self) -> Option<DerivationHalt> {
+        *self.0.borrow()
+    }
+
+    /// Returns whether derivation is halted.
+    pub fn is_halted(&self) -> bool {
+        self.0.borrow().is_some()
+    }
+
+    /// Halts derivation. Returns whether derivation was not already halted, in which case the
+    /// existing halt is kept.
+    pub fn halt(&self, halt: DerivationHalt) -> bool {
+        self.0.send_if_modified(|current| {
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(halt);
+            true
+        })
+    }
+
+    /// Acknowledges the halt, resuming derivation. Returns the acknowledged halt, if derivation
+    /// was halted.
+    pub fn acknowledge(&self) -> Option<DerivationHalt> {
+        let mut acknowledged = None;
+        self.0.send_if_modified(|current| {
+            acknowledged = current.take();
+            acknowledged.is_some()
+        });
+        acknowledged
+    }
+
+    /// Subscribes to the changes of the halt.
+    pub fn subscribe(&self) -> watch::Receiver<Option<DerivationHalt>> {
+        self.0.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALT: DerivationHalt = DerivationHalt {
+        reason: DerivationHaltReason::InvalidPayloadStreak { count: 3 },
+        block_number: 42,
+        halted_at: 1_700_000_000,
+    };
+
+    #[test]
+    fn test_derivation_halt_acknowledged() {
+        let switch = DerivationHaltSwitch::default();
+        let clone = switch.clone();
+        let mut rx = switch.subscribe();
+
+        assert!(!clone.is_halted());
+        assert_eq!(switch.acknowledge(), None);
+        assert!(!rx.has_changed().unwrap());
+
+        assert!(switch.halt(HALT));
+        assert!(!switch.halt(DerivationHalt { block_number: 43, ..HALT }));
+        assert_eq!(clone.halted(), Some(HALT));
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(*rx.borrow_and_update(), Some(HALT));
+
+        assert_eq!(clone.acknowledge(), Some(HALT));
+        assert!(!switch.is_halted());
+        assert!(rx.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_derivation_halt_serde() {
+        let halt = DerivationHalt {
+            reason: DerivationHaltReason::DepositOnlyBlocks { count: 2, window: 100 },
+            ..HALT
+        };
+        let json = serde_json::to_value(halt).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "reason": { "kind": "depositOnlyBlocks", "count": 2, "window": 100 },
+                "blockNumber": 42,
+                "haltedAt": 1_700_000_000,
+            })
+        );
+        assert_eq!(serde_json::from_value::<DerivationHalt>(json).unwrap(), halt);
+    }
+}
@@ -0,0 +1,63 @@
+//! Subscription endpoint streaming the events of the derivation pipeline.
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    PendingSubscriptionSink,
+    core::{SubscriptionResult, to_json_raw_value},
+};
+use kona_derive::PipelineEvent;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::DerivationEventsApiServer;
+
+/// An RPC server that streams the [`PipelineEvent`]s of the derivation pipeline.
+#[derive(Debug)]
+pub struct DerivationEventsRpc {
+    /// The sender the derivation pipeline broadcasts its events on.
+    events: broadcast::Sender<PipelineEvent>,
+}
+
+impl DerivationEventsRpc {
+    /// Constructs a new [`DerivationEventsRpc`] instance.
+    pub const fn new(events: broadcast::Sender<PipelineEvent>) -> Self {
+        Self { events }
+    }
+}
+
+#[async_trait]
+impl DerivationEventsApiServer for DerivationEventsRpc {
+    async fn kona_subscribe_derivation_events(
+        &self,
+        sink: PendingSubscriptionSink,
+    ) -> SubscriptionResult {
+        let mut events = self.events.subscribe();
+        let sink = sink.accept().await?;
+
+        loop {
+            let event = tokio::select! {
+                _ = sink.closed() => break,
+                event = events.recv() => event,
+            };
+            let event = match event {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(target: "rpc::events", skipped, "Derivation event subscriber lagged behind, skipping events.");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            let message = to_json_raw_value(&event).map_err(|_| {
+                jsonrpsee::core::SubscriptionError::from(
+                    "Internal error. Impossible to convert pipeline event to json",
+                )
+            })?;
+            if sink.send(message).await.is_err() {
+                break;
+            }
+        }
+
+        debug!(target: "rpc::events", "Subscription to derivation events has been closed.");
+        Ok(())
+    }
+}
@@ -3,10 +3,14 @@ use jsonrpsee::{
     core::RpcResult,
     types::{ErrorCode, ErrorObject},
 };
+use kona_supervisor_rpc::SupervisorHealth;
 use rollup_boost::Health;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, watch};
 
-use crate::jsonrpsee::{HealthzApiServer, RollupBoostHealthzApiServer};
+use crate::{
+    DerivationHalt, DerivationHaltSwitch,
+    jsonrpsee::{HealthzApiServer, RollupBoostHealthzApiServer},
+};
 
 /// Key for the rollup boost health status.
 /// +----------------+-------------------------------+--------------------------------------+-------------------------------+
@@ -50,11 +54,35 @@ impl From<Health> for RollupBoostHealth {
     }
 }
 
+/// The health of the node, reported by the healthcheck.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// The node is healthy.
+    #[default]
+    Ok,
+    /// The supervisor is unavailable, and interop messages are validated by the fallback policy
+    /// of its circuit breaker.
+    Degraded,
+    /// The node halted derivation, and needs an operator to acknowledge the halt.
+    Critical,
+}
+
 /// A healthcheck response for the RPC server.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HealthzResponse {
     /// The application version.
     pub version: String,
+    /// The health of the node.
+    #[serde(default)]
+    pub status: HealthStatus,
+    /// The current halt of derivation, if derivation is halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_halt: Option<DerivationHalt>,
+    /// The health of the supervisor interop messages are validated against, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supervisor_health: Option<SupervisorHealth>,
 }
 
 /// A healthcheck response for the rollup boost health.
@@ -76,19 +104,55 @@ pub struct RollupBoostHealthQuery {
 pub struct HealthzRpc {
     /// The rollup boost health.
     pub rollup_boost_health: mpsc::Sender<RollupBoostHealthQuery>,
+    /// The switch halting derivation. The node is critical while derivation is halted.
+    pub derivation_halt: DerivationHaltSwitch,
+    /// The health of the supervisor interop messages are validated against. The node is
+    /// degraded while the supervisor is not healthy.
+    pub supervisor_health: Option<watch::Receiver<SupervisorHealth>>,
 }
 
 impl HealthzRpc {
     /// Constructs a new [`HealthzRpc`] given the rollup boost health sender.
-    pub const fn new(rollup_boost_health: mpsc::Sender<RollupBoostHealthQuery>) -> Self {
-        Self { rollup_boost_health }
+    pub fn new(rollup_boost_health: mpsc::Sender<RollupBoostHealthQuery>) -> Self {
+        Self {
+            rollup_boost_health,
+            derivation_halt: DerivationHaltSwitch::default(),
+            supervisor_health: None,
+        }
+    }
+
+    /// Sets the switch halting derivation, reported by the healthcheck.
+    pub fn with_derivation_halt(self, derivation_halt: DerivationHaltSwitch) -> Self {
+        Self { derivation_halt, ..self }
+    }
+
+    /// Sets the health of the supervisor, reported by the healthcheck.
+    pub fn with_supervisor_health(
+        self,
+        supervisor_health: Option<watch::Receiver<SupervisorHealth>>,
+    ) -> Self {
+        Self { supervisor_health, ..self }
     }
 }
 
 #[async_trait]
 impl HealthzApiServer for HealthzRpc {
     async fn healthz(&self) -> RpcResult<HealthzResponse> {
-        Ok(HealthzResponse { version: env!("CARGO_PKG_VERSION").to_string() })
+        let derivation_halt = self.derivation_halt.halted();
+        let supervisor_health = self.supervisor_health.as_ref().map(|health| *health.borrow());
+        let status = if derivation_halt.is_some() {
+            HealthStatus::Critical
+        } else if supervisor_health.is_some_and(|health| !health.is_healthy()) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        };
+        Ok(HealthzResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            status,
+            derivation_halt,
+            supervisor_health,
+        })
     }
 }
 
@@ -108,3 +172,32 @@ impl RollupBoostHealthzApiServer for HealthzRpc {
         Ok(RollupBoostHealthzResponse { rollup_boost_health })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_healthz_reports_supervisor_health() {
+        let (health_tx, health_rx) = watch::channel(SupervisorHealth::Healthy);
+        let (rollup_boost_health, _) = mpsc::channel(1);
+        let rpc = HealthzRpc::new(rollup_boost_health).with_supervisor_health(Some(health_rx));
+
+        let response = rpc.healthz().await.unwrap();
+        assert_eq!(response.status, HealthStatus::Ok);
+        assert_eq!(response.supervisor_health, Some(SupervisorHealth::Healthy));
+
+        health_tx.send_replace(SupervisorHealth::Unavailable);
+        let response = rpc.healthz().await.unwrap();
+        assert_eq!(response.status, HealthStatus::Degraded);
+        assert_eq!(response.supervisor_health, Some(SupervisorHealth::Unavailable));
+    }
+
+    #[tokio::test]
+    async fn test_healthz_without_supervisor() {
+        let (rollup_boost_health, _) = mpsc::channel(1);
+        let response = HealthzRpc::new(rollup_boost_health).healthz().await.unwrap();
+        assert_eq!(response.status, HealthStatus::Ok);
+        assert_eq!(response.supervisor_health, None);
+    }
+}
@@ -1,7 +1,9 @@
 //! The Optimism RPC API using `jsonrpsee`
 
 use crate::{
-    OutputResponse, SafeHeadResponse,
+    BuildInfo, DaStats, DerivationHalt, DerivationLatency, DerivationOriginStats,
+    L1ProvenanceResponse, NodeCountersResponse, OutputResponse, RuntimeFlag, RuntimeFlagStatus,
+    SafeHeadResponse,
     health::{HealthzResponse, RollupBoostHealthzResponse},
 };
 use alloy_eips::BlockNumberOrTag;
@@ -12,9 +14,11 @@ use jsonrpsee::{
     core::{RpcResult, SubscriptionResult},
     proc_macros::rpc,
 };
+use kona_engine::{ConsolidationMismatch, DepositOnlyBlock, FeeParams};
 use kona_genesis::RollupConfig;
-use kona_gossip::{PeerCount, PeerDump, PeerInfo, PeerStats};
-use kona_protocol::SyncStatus;
+use kona_gossip::{GossipMessageTrace, PeerCount, PeerDump, PeerInfo, PeerStats, PeerTargets};
+use kona_interop::DependencySet;
+use kona_protocol::{BlockInfo, L2BlockInfo, SyncStatus};
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
 use rollup_boost::{GetExecutionModeResponse, SetExecutionModeRequest, SetExecutionModeResponse};
 
@@ -56,6 +60,39 @@ pub trait RollupNodeApi {
     async fn op_version(&self) -> RpcResult<String>;
 }
 
+/// The rollup namespace exposes the derivation events of the rollup node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "rollup"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "rollup"))]
+pub trait RollupEventsApi {
+    /// Get the recent invalid payloads replaced by deposits-only blocks, oldest first.
+    #[method(name = "depositOnlyBlocks")]
+    async fn rollup_deposit_only_blocks(&self) -> RpcResult<Vec<DepositOnlyBlock>>;
+
+    /// Get the latency of derivation through each hop of the node, over the recent safe blocks.
+    #[method(name = "derivationLatency")]
+    async fn rollup_derivation_latency(&self) -> RpcResult<DerivationLatency>;
+
+    /// Get the L1 batcher transactions an L2 block was derived from.
+    #[method(name = "l1ProvenanceForBlock")]
+    async fn rollup_l1_provenance_for_block(
+        &self,
+        l2_block: u64,
+    ) -> RpcResult<L1ProvenanceResponse>;
+
+    /// Get the cumulative counters of the node, persisted across restarts.
+    #[method(name = "nodeCounters")]
+    async fn rollup_node_counters(&self) -> RpcResult<NodeCountersResponse>;
+
+    /// Get the fee parameters in effect at the unsafe L2 head, derived from its system config and
+    /// L1 info deposit.
+    #[method(name = "feeParams")]
+    async fn rollup_fee_params(&self) -> RpcResult<FeeParams>;
+
+    /// Get the interop dependency set of the node.
+    #[method(name = "dependencySet")]
+    async fn rollup_dependency_set(&self) -> RpcResult<DependencySet>;
+}
+
 /// The opp2p namespace handles peer interactions.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "opp2p"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "opp2p"))]
@@ -133,6 +170,53 @@ pub trait OpP2PApi {
     async fn opp2p_disconnect_peer(&self, peer: String) -> RpcResult<()>;
 }
 
+/// The debug namespace exposes p2p diagnostics.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugP2PApi {
+    /// Returns the most recent gossip messages received or published by the node, oldest first.
+    /// If `limit` is set, only the `limit` most recent messages are returned.
+    #[method(name = "p2pRecentMessages")]
+    async fn debug_p2p_recent_messages(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<GossipMessageTrace>>;
+}
+
+/// The debug namespace exposes the batch data consumed by the derivation pipeline.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugDerivationApi {
+    /// Returns the frames and batches consumed from the most recent L1 origins of the derivation
+    /// pipeline, oldest first. If `limit` is set, only the `limit` most recent origins are
+    /// returned.
+    #[method(name = "derivationOrigins")]
+    async fn debug_derivation_origins(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<DerivationOriginStats>>;
+}
+
+/// The debug namespace exposes the consolidation of derived attributes by the engine.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugEngineApi {
+    /// Returns the last mismatch between derived attributes and the unsafe block they were
+    /// consolidated against, with every field that differs, or `None` if none was recorded.
+    #[method(name = "lastConsolidationMismatch")]
+    async fn debug_last_consolidation_mismatch(&self) -> RpcResult<Option<ConsolidationMismatch>>;
+}
+
+/// The rollup namespace exposes the data availability usage of the batcher.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "rollup"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "rollup"))]
+pub trait DaStatsApi {
+    /// Returns the calldata and blob usage of the batcher over the L1 blocks from `from_block` to
+    /// `to_block` inclusive, among the most recent L1 origins of the derivation pipeline.
+    #[method(name = "daStats")]
+    async fn rollup_da_stats(&self, from_block: u64, to_block: u64) -> RpcResult<DaStats>;
+}
+
 /// Websockets API for the node.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "ws"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "ws"))]
@@ -151,6 +235,26 @@ pub trait Ws {
     async fn ws_unsafe_head_updates(&self) -> SubscriptionResult;
 }
 
+/// The kona namespace streams the internal events of the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "kona"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "kona"))]
+#[async_trait]
+pub trait DerivationEventsApi {
+    /// Subscribes to the stream of events emitted by the derivation pipeline.
+    #[subscription(name = "subscribeDerivationEvents", item = kona_derive::PipelineEvent)]
+    async fn kona_subscribe_derivation_events(&self) -> SubscriptionResult;
+}
+
+/// The kona namespace reports the build of the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "kona"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "kona"))]
+#[async_trait]
+pub trait BuildInfoApi {
+    /// Returns the version, commit, build profile, features and supported hardforks of the node.
+    #[method(name = "buildInfo")]
+    async fn kona_build_info(&self) -> RpcResult<BuildInfo>;
+}
+
 /// Development RPC API for engine state introspection.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "dev"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "dev"))]
@@ -204,8 +308,47 @@ pub trait AdminApi {
     async fn admin_override_leader(&self) -> RpcResult<()>;
 
     /// Resets the derivation pipeline.
+    ///
+    /// By default, the pipeline is reset to the anchors found by resetting the engine forkchoice.
+    /// The L2 safe head and the L1 origin the pipeline restarts from can be pinned together
+    /// instead, to force re-derivation from an earlier point. The engine is then rewound to the
+    /// pinned L2 safe head, which must be canonical and not behind the finalized head. The pinned
+    /// L1 origin must be canonical and not ahead of the L1 origin of the safe head.
     #[method(name = "resetDerivationPipeline")]
-    async fn admin_reset_derivation_pipeline(&self) -> RpcResult<()>;
+    async fn admin_reset_derivation_pipeline(
+        &self,
+        l2_safe_head: Option<L2BlockInfo>,
+        l1_origin: Option<BlockInfo>,
+    ) -> RpcResult<()>;
+
+    /// Sets the low and high watermarks of connected gossip peers.
+    ///
+    /// Peers above the high watermark are pruned, and new peers are dialed while below the low
+    /// watermark.
+    #[method(name = "setPeerTargets")]
+    async fn admin_set_peer_targets(&self, lo: usize, hi: usize) -> RpcResult<()>;
+
+    /// Gets the low and high watermarks of connected gossip peers.
+    #[method(name = "peerTargets")]
+    async fn admin_peer_targets(&self) -> RpcResult<PeerTargets>;
+
+    /// Sets or unsets a runtime flag of the node.
+    #[method(name = "setRuntimeFlag")]
+    async fn admin_set_runtime_flag(&self, flag: RuntimeFlag, enabled: bool) -> RpcResult<()>;
+
+    /// Gets the state of every runtime flag of the node.
+    #[method(name = "runtimeFlags")]
+    async fn admin_runtime_flags(&self) -> RpcResult<Vec<RuntimeFlagStatus>>;
+
+    /// Gets the current halt of derivation, if the engine halted it after detecting a runaway
+    /// divergence from the chain.
+    #[method(name = "derivationHalt")]
+    async fn admin_derivation_halt(&self) -> RpcResult<Option<DerivationHalt>>;
+
+    /// Acknowledges the current halt of derivation, resuming derivation. Returns the acknowledged
+    /// halt, if derivation was halted.
+    #[method(name = "acknowledgeDerivationHalt")]
+    async fn admin_acknowledge_derivation_halt(&self) -> RpcResult<Option<DerivationHalt>>;
 
     /// Sets the rollup boost execution mode.
     #[method(name = "setExecutionMode")]
@@ -1,3 +1,5 @@
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::TransactionReceipt;
 use kona_genesis::RollupConfig;
 use kona_protocol::BlockInfo;
 use tokio::sync::oneshot::Sender;
@@ -30,6 +32,19 @@ pub struct L1State {
     pub finalized_l1: Option<BlockInfo>,
 }
 
+/// The availability of the blobs of a beacon slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobAvailability {
+    /// The blobs of the slot can be retrieved from the beacon node.
+    Available,
+    /// The beacon node serves no blobs for the slot. Either the slot has no blobs, or they were
+    /// pruned past the data availability window.
+    Unavailable,
+    /// The availability could not be determined, because the beacon node could not be queried,
+    /// or the L1 watcher has no beacon node.
+    Unknown,
+}
+
 /// A sender for L1 watcher queries.
 pub type L1WatcherQuerySender = tokio::sync::mpsc::Sender<L1WatcherQueries>;
 
@@ -40,4 +55,10 @@ pub enum L1WatcherQueries {
     Config(Sender<RollupConfig>),
     /// Get a complete view of the L1 state.
     L1State(Sender<L1State>),
+    /// Get the receipts of the L1 block with the given hash. Answers [`None`] if the block is
+    /// unknown to the L1 provider, or its receipts could not be fetched.
+    Receipts(B256, Sender<Option<Vec<TransactionReceipt>>>),
+    /// Check whether the blobs of the given beacon slot can still be retrieved, so that their
+    /// availability can be checked before the derivation pipeline needs them.
+    BlobsAvailable(u64, Sender<BlobAvailability>),
 }
@@ -0,0 +1,83 @@
+//! Response to the derivation latency request.
+
+use core::time::Duration;
+
+/// Summarizes the time taken by derived payload attributes through each hop of the node, from the
+/// L1 block they were derived from being observed, to the safe head being updated.
+///
+/// Served by `rollup_derivationLatency`, over the most recent safe blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationLatency {
+    /// The number of safe blocks the latencies are summarized over.
+    pub samples: usize,
+    /// From the L1 block being observed, to the batch being decoded into payload attributes.
+    pub l1_to_decoded: LatencySummary,
+    /// From the payload attributes being decoded, to them being sent to the engine.
+    pub decoded_to_sent: LatencySummary,
+    /// From the payload attributes being sent to the engine, to the engine executing them.
+    pub sent_to_executed: LatencySummary,
+    /// From the engine executing the payload attributes, to the safe head being updated.
+    pub executed_to_safe: LatencySummary,
+    /// From the L1 block being observed, to the safe head being updated.
+    pub end_to_end: LatencySummary,
+}
+
+/// The distribution of the latency of a derivation hop, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    /// The mean latency.
+    pub mean_ms: f64,
+    /// The median latency.
+    pub p50_ms: f64,
+    /// The 90th percentile latency.
+    pub p90_ms: f64,
+    /// The 99th percentile latency.
+    pub p99_ms: f64,
+    /// The maximum latency.
+    pub max_ms: f64,
+}
+
+impl LatencySummary {
+    /// Summarizes the given latencies. Returns an all-zero summary if there are none.
+    pub fn from_durations(durations: impl IntoIterator<Item = Duration>) -> Self {
+        let mut millis = durations
+            .into_iter()
+            .map(|duration| duration.as_nanos() as f64 / 1e6)
+            .collect::<Vec<_>>();
+        if millis.is_empty() {
+            return Self::default();
+        }
+        millis.sort_by(f64::total_cmp);
+
+        let percentile = |p: f64| millis[((millis.len() - 1) as f64 * p).round() as usize];
+        Self {
+            mean_ms: millis.iter().sum::<f64>() / millis.len() as f64,
+            p50_ms: percentile(0.5),
+            p90_ms: percentile(0.9),
+            p99_ms: percentile(0.99),
+            max_ms: millis[millis.len() - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_summary_from_durations() {
+        let summary = LatencySummary::from_durations((1..=100).map(Duration::from_millis));
+        assert_eq!(summary.mean_ms, 50.5);
+        assert_eq!(summary.p50_ms, 51.0);
+        assert_eq!(summary.p90_ms, 90.0);
+        assert_eq!(summary.p99_ms, 99.0);
+        assert_eq!(summary.max_ms, 100.0);
+    }
+
+    #[test]
+    fn test_latency_summary_empty() {
+        assert_eq!(LatencySummary::from_durations([]), LatencySummary::default());
+    }
+}
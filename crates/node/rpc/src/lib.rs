@@ -11,16 +11,31 @@ extern crate tracing;
 
 mod admin;
 pub use admin::{
-    AdminRpc, NetworkAdminQuery, RollupBoostAdminQuery, SequencerAdminAPIClient,
+    AdminRpc, EngineAdminQuery, NetworkAdminQuery, RollupBoostAdminQuery, SequencerAdminAPIClient,
     SequencerAdminAPIError, StopSequencerError,
 };
 
+mod runtime_flags;
+pub use runtime_flags::{RuntimeFlag, RuntimeFlagStatus, RuntimeFlags};
+
+mod derivation_halt;
+pub use derivation_halt::{DerivationHalt, DerivationHaltReason, DerivationHaltSwitch};
+
+mod build_info;
+pub use build_info::BuildInfo;
+
 mod config;
-pub use config::RpcBuilder;
+pub use config::{RpcBuilder, RpcMethodPolicy, RpcTlsConfig, TlsCertKeyPaths};
 
 mod net;
 pub use net::P2pRpc;
 
+mod request_id;
+pub use request_id::{RequestId, RequestIdService};
+
+mod method_policy;
+pub use method_policy::{MethodPolicyService, MethodPolicyState};
+
 mod p2p;
 
 mod response;
@@ -29,26 +44,53 @@ pub use response::SafeHeadResponse;
 mod output;
 pub use output::OutputResponse;
 
+mod latency;
+pub use latency::{DerivationLatency, LatencySummary};
+
+mod provenance;
+pub use provenance::{L1BatchSource, L1ProvenanceResponse};
+
+mod counters;
+pub use counters::NodeCountersResponse;
+
+mod origins;
+pub use origins::{DaStats, DerivationOriginStats, DerivationOriginsRpc};
+
 mod dev;
 pub use dev::DevEngineRpc;
 
 mod jsonrpsee;
+#[cfg(feature = "client")]
+pub use jsonrpsee::{
+    AdminApiClient, BuildInfoApiClient, DaStatsApiClient, DebugDerivationApiClient,
+    DebugEngineApiClient, DebugP2PApiClient, DerivationEventsApiClient, DevEngineApiClient,
+    HealthzApiClient, OpP2PApiClient, RollupBoostHealthzApiClient, RollupEventsApiClient,
+    RollupNodeApiClient, WsClient,
+};
 pub use jsonrpsee::{
-    AdminApiServer, DevEngineApiServer, HealthzApiServer, MinerApiExtServer, OpAdminApiServer,
-    OpP2PApiServer, RollupBoostHealthzApiServer, RollupNodeApiServer, WsServer,
+    AdminApiServer, BuildInfoApiServer, DaStatsApiServer, DebugDerivationApiServer,
+    DebugEngineApiServer, DebugP2PApiServer, DerivationEventsApiServer, DevEngineApiServer,
+    HealthzApiServer, MinerApiExtServer, OpAdminApiServer, OpP2PApiServer,
+    RollupBoostHealthzApiServer, RollupEventsApiServer, RollupNodeApiServer, WsServer,
 };
 
 mod rollup;
-pub use rollup::RollupRpc;
+pub use rollup::{
+    L1ProvenanceDb, L1ProvenanceDbError, NodeCountersDb, NodeCountersDbError, RollupRpc,
+    SafeHeadDb, SafeHeadDbError,
+};
 
 mod l1_watcher;
-pub use l1_watcher::{L1State, L1WatcherQueries, L1WatcherQuerySender};
+pub use l1_watcher::{BlobAvailability, L1State, L1WatcherQueries, L1WatcherQuerySender};
 
 mod ws;
 pub use ws::WsRPC;
 
+mod events;
+pub use events::DerivationEventsRpc;
+
 mod health;
 pub use health::{
-    HealthzResponse, HealthzRpc, RollupBoostHealth, RollupBoostHealthQuery,
+    HealthStatus, HealthzResponse, HealthzRpc, RollupBoostHealth, RollupBoostHealthQuery,
     RollupBoostHealthzResponse,
 };
@@ -0,0 +1,200 @@
+//! Enforcement of the [`RpcMethodPolicy`] of the RPC server.
+
+use crate::RpcMethodPolicy;
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification, RpcServiceT},
+    server::MethodResponse,
+    types::{ErrorCode, ErrorObject, ErrorObjectOwned, Request},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU32,
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
+
+/// The error code of calls rejected by a rate limit, as defined by [EIP-1474].
+///
+/// [EIP-1474]: https://eips.ethereum.org/EIPS/eip-1474
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The state enforcing an [`RpcMethodPolicy`], shared by all the connections of the server.
+#[derive(Debug)]
+pub struct MethodPolicyState {
+    /// The methods that are not served.
+    disabled: HashSet<String>,
+    /// The rate limiters of the rate limited methods.
+    rate_limiters: HashMap<String, Mutex<RateLimiter>>,
+}
+
+impl MethodPolicyState {
+    /// Creates a new [`MethodPolicyState`] enforcing the given [`RpcMethodPolicy`].
+    pub fn new(policy: &RpcMethodPolicy) -> Self {
+        let rate_limiters = policy
+            .rate_limits
+            .iter()
+            .map(|(method, limit)| (method.clone(), Mutex::new(RateLimiter::new(*limit))))
+            .collect();
+        Self { disabled: policy.disabled.clone(), rate_limiters }
+    }
+
+    /// Checks whether a call to the method is allowed, consuming from its rate limit if it is.
+    fn check(&self, method: &str) -> Result<(), ErrorObjectOwned> {
+        if self.disabled.contains(method) {
+            debug!(target: "rpc", method, "Rejected call to disabled method");
+            return Err(ErrorCode::MethodNotFound.into());
+        }
+
+        let Some(limiter) = self.rate_limiters.get(method) else { return Ok(()) };
+        if limiter.lock().unwrap_or_else(PoisonError::into_inner).try_acquire(Instant::now()) {
+            Ok(())
+        } else {
+            debug!(target: "rpc", method, "Rejected rate limited call");
+            Err(ErrorObject::owned(LIMIT_EXCEEDED_CODE, "Rate limit exceeded", None::<()>))
+        }
+    }
+}
+
+/// A token bucket allowing a number of calls per second, with bursts of up to that number.
+#[derive(Debug)]
+struct RateLimiter {
+    /// The number of calls allowed per second, and the size of the bucket.
+    rate: f64,
+    /// The number of calls currently allowed.
+    tokens: f64,
+    /// When the bucket was last refilled.
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a new, full [`RateLimiter`].
+    fn new(limit: NonZeroU32) -> Self {
+        let rate = f64::from(limit.get());
+        Self { rate, tokens: rate, refilled_at: Instant::now() }
+    }
+
+    /// Refills the bucket up to `now`, and takes a token from it if one is left.
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = elapsed.mul_add(self.rate, self.tokens).min(self.rate);
+        self.refilled_at = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// An RPC middleware rejecting the calls to the disabled methods of an [`RpcMethodPolicy`], and
+/// the calls exceeding the rate limits of its rate limited methods.
+///
+/// Disabled methods are rejected as if they did not exist. The rejected calls of a batch are
+/// answered with an error under their own request ID, and its other calls are served.
+#[derive(Debug, Clone)]
+pub struct MethodPolicyService<S> {
+    /// The inner service.
+    service: S,
+    /// The state enforcing the policy.
+    state: Arc<MethodPolicyState>,
+}
+
+impl<S> MethodPolicyService<S> {
+    /// Creates a new [`MethodPolicyService`] wrapping the given service.
+    pub const fn new(service: S, state: Arc<MethodPolicyState>) -> Self {
+        Self { service, state }
+    }
+}
+
+impl<S> RpcServiceT for MethodPolicyService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        request: Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let service = self.service.clone();
+        let state = Arc::clone(&self.state);
+        async move {
+            match state.check(request.method_name()) {
+                Ok(()) => service.call(request).await,
+                Err(err) => MethodResponse::error(request.id, err),
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut batch: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        for entry in batch.iter_mut() {
+            let Ok(BatchEntry::Call(request)) = entry else { continue };
+            if let Err(err) = self.state.check(request.method_name()) {
+                let id = request.id.clone();
+                *entry = Err(BatchEntryErr::new(id, err));
+            }
+        }
+        self.service.batch(batch)
+    }
+
+    fn notification<'a>(
+        &self,
+        notification: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.service.notification(notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_rate_limiter_refills() {
+        let mut limiter = RateLimiter::new(NonZeroU32::new(2).unwrap());
+        let start = limiter.refilled_at;
+
+        assert!(limiter.try_acquire(start));
+        assert!(limiter.try_acquire(start));
+        assert!(!limiter.try_acquire(start));
+
+        assert!(limiter.try_acquire(start + Duration::from_millis(500)));
+        assert!(!limiter.try_acquire(start + Duration::from_millis(500)));
+
+        // The bucket does not fill beyond the rate.
+        let later = start + Duration::from_secs(10);
+        assert!(limiter.try_acquire(later));
+        assert!(limiter.try_acquire(later));
+        assert!(!limiter.try_acquire(later));
+    }
+
+    #[test]
+    fn test_check_policy() {
+        let policy = RpcMethodPolicy {
+            disabled: HashSet::from(["opp2p_discoveryTable".to_string()]),
+            rate_limits: HashMap::from([("opp2p_peers".to_string(), NonZeroU32::new(1).unwrap())]),
+        };
+        let state = MethodPolicyState::new(&policy);
+
+        let err = state.check("opp2p_discoveryTable").unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MethodNotFound.code());
+
+        assert!(state.check("opp2p_peers").is_ok());
+        let err = state.check("opp2p_peers").unwrap_err();
+        assert_eq!(err.code(), LIMIT_EXCEEDED_CODE);
+
+        assert!(state.check("opp2p_self").is_ok());
+        assert!(state.check("opp2p_self").is_ok());
+    }
+}
@@ -1,16 +1,16 @@
 //! Network types
 
-use kona_gossip::P2pRpcRequest;
+use kona_gossip::TracedP2pRpcRequest;
 
-/// A type alias for the sender of a [`P2pRpcRequest`].
-type P2pReqSender = tokio::sync::mpsc::Sender<P2pRpcRequest>;
+/// A type alias for the sender of a [`TracedP2pRpcRequest`].
+type P2pReqSender = tokio::sync::mpsc::Sender<TracedP2pRpcRequest>;
 
 /// P2pRpc
 ///
-/// This is a server implementation of [`crate::OpP2PApiServer`].
-#[derive(Debug)]
+/// This is a server implementation of [`crate::OpP2PApiServer`] and [`crate::DebugP2PApiServer`].
+#[derive(Debug, Clone)]
 pub struct P2pRpc {
-    /// The channel to send [`P2pRpcRequest`]s.
+    /// The channel to send [`TracedP2pRpcRequest`]s.
     pub sender: P2pReqSender,
 }
 
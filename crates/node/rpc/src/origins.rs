@@ -0,0 +1,189 @@
+//! Endpoints serving the batch data consumed by the derivation pipeline from each L1 origin.
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{ErrorCode, ErrorObject},
+};
+use kona_protocol::BlockInfo;
+use std::collections::VecDeque;
+use tokio::sync::watch;
+
+use crate::{DaStatsApiServer, DebugDerivationApiServer};
+
+/// The batch data the derivation pipeline consumed from an L1 origin, recorded once the pipeline
+/// advanced past it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationOriginStats {
+    /// The L1 origin.
+    pub origin: BlockInfo,
+    /// The number of bytes of batcher data read from the calldata of the batcher transactions
+    /// of the origin.
+    pub calldata_bytes: u64,
+    /// The number of bytes of batcher data read from the blobs of the batcher transactions of the
+    /// origin.
+    pub blob_bytes: u64,
+    /// The number of frames read from the batcher transactions of the origin.
+    pub frames: u64,
+    /// The number of channels opened while the pipeline was at the origin.
+    pub channels: u64,
+    /// The number of batches decoded while the pipeline was at the origin.
+    pub batches: u64,
+    /// The number of batches dropped while the pipeline was at the origin.
+    pub dropped_batches: u64,
+}
+
+/// The data availability usage of the batcher over a range of L1 blocks, summed from the
+/// [`DerivationOriginStats`] of the blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaStats {
+    /// The first L1 block of the range with recorded stats.
+    pub from_block: u64,
+    /// The last L1 block of the range with recorded stats.
+    pub to_block: u64,
+    /// The number of L1 blocks of the range with recorded stats.
+    pub l1_blocks: u64,
+    /// The number of L1 blocks of the range the batcher posted data in.
+    pub batcher_blocks: u64,
+    /// The number of bytes of batcher data posted as calldata.
+    pub calldata_bytes: u64,
+    /// The number of bytes of batcher data posted as blobs.
+    pub blob_bytes: u64,
+    /// The number of frames posted.
+    pub frames: u64,
+    /// The number of channels opened.
+    pub channels: u64,
+}
+
+impl DaStats {
+    /// Sums the stats of the given L1 origins, in ascending order. Returns [`None`] if there are
+    /// none.
+    pub fn summarize<'a>(
+        origins: impl IntoIterator<Item = &'a DerivationOriginStats>,
+    ) -> Option<Self> {
+        origins.into_iter().fold(None, |summary, stats| {
+            let mut summary =
+                summary.unwrap_or(Self { from_block: stats.origin.number, ..Default::default() });
+            summary.to_block = stats.origin.number;
+            summary.l1_blocks += 1;
+            summary.batcher_blocks += u64::from(stats.calldata_bytes + stats.blob_bytes > 0);
+            summary.calldata_bytes += stats.calldata_bytes;
+            summary.blob_bytes += stats.blob_bytes;
+            summary.frames += stats.frames;
+            summary.channels += stats.channels;
+            Some(summary)
+        })
+    }
+}
+
+/// An RPC server serving the [`DerivationOriginStats`] of the most recent L1 origins, and the
+/// [`DaStats`] summarizing them.
+#[derive(Debug, Clone)]
+pub struct DerivationOriginsRpc {
+    /// The receiver of the stats of the most recent L1 origins, oldest first.
+    origins: watch::Receiver<VecDeque<DerivationOriginStats>>,
+}
+
+impl DerivationOriginsRpc {
+    /// Constructs a new [`DerivationOriginsRpc`] instance.
+    pub const fn new(origins: watch::Receiver<VecDeque<DerivationOriginStats>>) -> Self {
+        Self { origins }
+    }
+}
+
+#[async_trait]
+impl DebugDerivationApiServer for DerivationOriginsRpc {
+    async fn debug_derivation_origins(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<DerivationOriginStats>> {
+        let origins = self.origins.borrow();
+        let skip = limit.map_or(0, |limit| origins.len().saturating_sub(limit));
+        Ok(origins.iter().skip(skip).copied().collect())
+    }
+}
+
+#[async_trait]
+impl DaStatsApiServer for DerivationOriginsRpc {
+    async fn rollup_da_stats(&self, from_block: u64, to_block: u64) -> RpcResult<DaStats> {
+        if from_block > to_block {
+            return Err(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                format!("Invalid L1 block range {from_block}..={to_block}"),
+                None::<()>,
+            ));
+        }
+
+        let origins = self.origins.borrow();
+        DaStats::summarize(
+            origins.iter().filter(|stats| (from_block..=to_block).contains(&stats.origin.number)),
+        )
+        .ok_or_else(|| {
+            ErrorObject::owned(
+                -32000,
+                format!("No DA stats recorded for L1 blocks {from_block}..={to_block}"),
+                None::<()>,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_derivation_origin_stats_serde() {
+        let stats = DerivationOriginStats {
+            origin: BlockInfo { number: 7, hash: B256::with_last_byte(7), ..Default::default() },
+            calldata_bytes: 0,
+            blob_bytes: 131072,
+            frames: 4,
+            channels: 1,
+            batches: 2,
+            dropped_batches: 1,
+        };
+        let json = serde_json::to_value(stats).unwrap();
+        assert_eq!(json["frames"], 4);
+        assert_eq!(json["batches"], 2);
+        assert_eq!(json["droppedBatches"], 1);
+        assert_eq!(json["blobBytes"], 131072);
+        assert_eq!(serde_json::from_value::<DerivationOriginStats>(json).unwrap(), stats);
+    }
+
+    fn stats(number: u64, calldata_bytes: u64, blob_bytes: u64) -> DerivationOriginStats {
+        DerivationOriginStats {
+            origin: BlockInfo { number, ..Default::default() },
+            calldata_bytes,
+            blob_bytes,
+            frames: u64::from(calldata_bytes + blob_bytes > 0),
+            channels: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_da_stats_summarize() {
+        let origins =
+            [stats(10, 1000, 0), stats(11, 0, 0), stats(12, 0, 131072), stats(13, 500, 0)];
+
+        let summary = DaStats::summarize(&origins[1..]).unwrap();
+        assert_eq!(
+            summary,
+            DaStats {
+                from_block: 11,
+                to_block: 13,
+                l1_blocks: 3,
+                batcher_blocks: 2,
+                calldata_bytes: 500,
+                blob_bytes: 131072,
+                frames: 2,
+                channels: 3,
+            }
+        );
+        assert_eq!(DaStats::summarize(&[]), None);
+    }
+}
@@ -12,10 +12,10 @@ use jsonrpsee::{
     core::RpcResult,
     types::{ErrorCode, ErrorObject},
 };
-use kona_gossip::{P2pRpcRequest, PeerCount, PeerDump, PeerInfo, PeerStats};
+use kona_gossip::{GossipMessageTrace, P2pRpcRequest, PeerCount, PeerDump, PeerInfo, PeerStats};
 use std::{net::IpAddr, str::FromStr, time::Duration};
 
-use crate::{OpP2PApiServer, net::P2pRpc};
+use crate::{DebugP2PApiServer, OpP2PApiServer, net::P2pRpc};
 
 #[async_trait]
 impl OpP2PApiServer for P2pRpc {
@@ -23,7 +23,7 @@ impl OpP2PApiServer for P2pRpc {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_self");
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::PeerInfo(tx))
+            .send(P2pRpcRequest::PeerInfo(tx).into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -34,7 +34,7 @@ impl OpP2PApiServer for P2pRpc {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_peerCount");
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::PeerCount(tx))
+            .send(P2pRpcRequest::PeerCount(tx).into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -48,7 +48,7 @@ impl OpP2PApiServer for P2pRpc {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_peers");
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::Peers { out: tx, connected })
+            .send(P2pRpcRequest::Peers { out: tx, connected }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -60,7 +60,7 @@ impl OpP2PApiServer for P2pRpc {
     async fn opp2p_peer_stats(&self) -> RpcResult<PeerStats> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::PeerStats(tx))
+            .send(P2pRpcRequest::PeerStats(tx).into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -73,7 +73,7 @@ impl OpP2PApiServer for P2pRpc {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_discoveryTable");
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::DiscoveryTable(tx))
+            .send(P2pRpcRequest::DiscoveryTable(tx).into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -85,7 +85,7 @@ impl OpP2PApiServer for P2pRpc {
         let id = libp2p::PeerId::from_str(&peer_id)
             .map_err(|_| ErrorObject::from(ErrorCode::InvalidParams))?;
         self.sender
-            .send(P2pRpcRequest::BlockPeer { id })
+            .send(P2pRpcRequest::BlockPeer { id }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -95,7 +95,7 @@ impl OpP2PApiServer for P2pRpc {
         let id = libp2p::PeerId::from_str(&peer_id)
             .map_err(|_| ErrorObject::from(ErrorCode::InvalidParams))?;
         self.sender
-            .send(P2pRpcRequest::UnblockPeer { id })
+            .send(P2pRpcRequest::UnblockPeer { id }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -104,7 +104,7 @@ impl OpP2PApiServer for P2pRpc {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_listBlockedPeers");
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::ListBlockedPeers(tx))
+            .send(P2pRpcRequest::ListBlockedPeers(tx).into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -116,7 +116,7 @@ impl OpP2PApiServer for P2pRpc {
     async fn opp2p_block_addr(&self, address: IpAddr) -> RpcResult<()> {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_blockAddr");
         self.sender
-            .send(P2pRpcRequest::BlockAddr { address })
+            .send(P2pRpcRequest::BlockAddr { address }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -124,7 +124,7 @@ impl OpP2PApiServer for P2pRpc {
     async fn opp2p_unblock_addr(&self, address: IpAddr) -> RpcResult<()> {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_unblockAddr");
         self.sender
-            .send(P2pRpcRequest::UnblockAddr { address })
+            .send(P2pRpcRequest::UnblockAddr { address }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -133,7 +133,7 @@ impl OpP2PApiServer for P2pRpc {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_listBlockedAddrs");
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::ListBlockedAddrs(tx))
+            .send(P2pRpcRequest::ListBlockedAddrs(tx).into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -143,7 +143,7 @@ impl OpP2PApiServer for P2pRpc {
     async fn opp2p_block_subnet(&self, subnet: IpNet) -> RpcResult<()> {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_blockSubnet");
         self.sender
-            .send(P2pRpcRequest::BlockSubnet { address: subnet })
+            .send(P2pRpcRequest::BlockSubnet { address: subnet }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -152,7 +152,7 @@ impl OpP2PApiServer for P2pRpc {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_unblockSubnet");
 
         self.sender
-            .send(P2pRpcRequest::UnblockSubnet { address: subnet })
+            .send(P2pRpcRequest::UnblockSubnet { address: subnet }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -165,7 +165,7 @@ impl OpP2PApiServer for P2pRpc {
         );
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(P2pRpcRequest::ListBlockedSubnets(tx))
+            .send(P2pRpcRequest::ListBlockedSubnets(tx).into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -177,7 +177,7 @@ impl OpP2PApiServer for P2pRpc {
         let peer_id = libp2p::PeerId::from_str(&id)
             .map_err(|_| ErrorObject::from(ErrorCode::InvalidParams))?;
         self.sender
-            .send(P2pRpcRequest::ProtectPeer { peer_id })
+            .send(P2pRpcRequest::ProtectPeer { peer_id }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -187,7 +187,7 @@ impl OpP2PApiServer for P2pRpc {
         let peer_id = libp2p::PeerId::from_str(&id)
             .map_err(|_| ErrorObject::from(ErrorCode::InvalidParams))?;
         self.sender
-            .send(P2pRpcRequest::UnprotectPeer { peer_id })
+            .send(P2pRpcRequest::UnprotectPeer { peer_id }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
@@ -213,13 +213,15 @@ impl OpP2PApiServer for P2pRpc {
                 )
             })?;
 
-        self.sender.send(P2pRpcRequest::ConnectPeer { address: ma }).await.map_err(|_| {
-            ErrorObject::borrowed(
-                ErrorCode::InternalError.code(),
-                "Failed to send connect peer request",
-                None,
-            )
-        })?;
+        self.sender.send(P2pRpcRequest::ConnectPeer { address: ma }.into()).await.map_err(
+            |_| {
+                ErrorObject::borrowed(
+                    ErrorCode::InternalError.code(),
+                    "Failed to send connect peer request",
+                    None,
+                )
+            },
+        )?;
 
         // We need to wait until both peers are connected to each other to return from this method.
         // We try with an exponential backoff and return an error if we fail to connect to the peer.
@@ -227,7 +229,7 @@ impl OpP2PApiServer for P2pRpc {
             let (tx, rx) = tokio::sync::oneshot::channel();
 
             self.sender
-                .send(P2pRpcRequest::Peers { out: tx, connected: true })
+                .send(P2pRpcRequest::Peers { out: tx, connected: true }.into())
                 .await
                 .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -263,7 +265,7 @@ impl OpP2PApiServer for P2pRpc {
         };
 
         self.sender
-            .send(P2pRpcRequest::DisconnectPeer { peer_id })
+            .send(P2pRpcRequest::DisconnectPeer { peer_id }.into())
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -274,7 +276,7 @@ impl OpP2PApiServer for P2pRpc {
             let (tx, rx) = tokio::sync::oneshot::channel();
 
             self.sender
-                .send(P2pRpcRequest::Peers { out: tx, connected: true })
+                .send(P2pRpcRequest::Peers { out: tx, connected: true }.into())
                 .await
                 .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
@@ -300,6 +302,23 @@ impl OpP2PApiServer for P2pRpc {
     }
 }
 
+#[async_trait]
+impl DebugP2PApiServer for P2pRpc {
+    async fn debug_p2p_recent_messages(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<GossipMessageTrace>> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "debug_p2pRecentMessages");
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.sender
+            .send(P2pRpcRequest::RecentMessages { out: tx, limit }.into())
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -0,0 +1,64 @@
+//! Response to the L1 provenance request.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{B128, B256};
+
+/// The L1 data an L2 block was derived from.
+///
+/// Served by `rollup_l1ProvenanceForBlock`, for the L2 blocks derived by the node.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1ProvenanceResponse {
+    /// The number of the L2 block.
+    pub l2_block: u64,
+    /// The ID of the channel carrying the batch of the L2 block, if known.
+    pub channel_id: Option<B128>,
+    /// The L1 block at which the batch was decoded.
+    pub l1_origin: BlockNumHash,
+    /// The batcher transactions carrying the frames of the channel, in the order they were read.
+    pub sources: Vec<L1BatchSource>,
+}
+
+/// A batcher transaction carrying some of the frames of a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchSource {
+    /// The L1 block the transaction was included in.
+    pub l1_block: BlockNumHash,
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The versioned hash of the blob carrying the frames, if they were posted as a blob.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_hash: Option<B256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l1_provenance_response_serde() {
+        let response = L1ProvenanceResponse {
+            l2_block: 10,
+            channel_id: Some(B128::with_last_byte(1)),
+            l1_origin: BlockNumHash::new(5, B256::with_last_byte(5)),
+            sources: vec![
+                L1BatchSource {
+                    l1_block: BlockNumHash::new(4, B256::with_last_byte(4)),
+                    tx_hash: B256::with_last_byte(2),
+                    blob_hash: None,
+                },
+                L1BatchSource {
+                    l1_block: BlockNumHash::new(5, B256::with_last_byte(5)),
+                    tx_hash: B256::with_last_byte(3),
+                    blob_hash: Some(B256::with_last_byte(6)),
+                },
+            ],
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["l2Block"], 10);
+        assert!(json["sources"][0].get("blobHash").is_none());
+        assert_eq!(serde_json::from_value::<L1ProvenanceResponse>(json).unwrap(), response);
+    }
+}
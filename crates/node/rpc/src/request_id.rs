@@ -0,0 +1,102 @@
+//! Request IDs correlating RPC calls with the logs of the actors processing them.
+
+use jsonrpsee::{
+    core::middleware::{Batch, Notification, RpcServiceT},
+    types::Request,
+};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+use tracing::Instrument;
+
+/// The next [`RequestId`] to assign.
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The ID of an RPC call, unique for the lifetime of the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub struct RequestId(u64);
+
+impl RequestId {
+    /// Returns a new [`RequestId`].
+    pub fn next() -> Self {
+        Self(NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// An RPC middleware assigning a [`RequestId`] to each call, and processing the call in an
+/// `rpc_call` span with `request_id` and `method` fields.
+///
+/// Requests forwarded to the actors of the node carry the span of the call, so the actors log
+/// within it. Batches are processed in a single span, with `batch` as their method.
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    /// The inner service.
+    service: S,
+}
+
+impl<S> RequestIdService<S> {
+    /// Creates a new [`RequestIdService`] wrapping the given service.
+    pub const fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S> RpcServiceT for RequestIdService<S>
+where
+    S: RpcServiceT + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        request: Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let span = info_span!(
+            target: "rpc",
+            "rpc_call",
+            request_id = %RequestId::next(),
+            method = %request.method_name(),
+        );
+        let service = self.service.clone();
+        async move {
+            let start = Instant::now();
+            let response = service.call(request).await;
+            debug!(target: "rpc", elapsed = ?start.elapsed(), "RPC call processed");
+            response
+        }
+        .instrument(span)
+    }
+
+    fn batch<'a>(&self, batch: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let span = info_span!(
+            target: "rpc",
+            "rpc_call",
+            request_id = %RequestId::next(),
+            method = "batch",
+        );
+        self.service.batch(batch).instrument(span)
+    }
+
+    fn notification<'a>(
+        &self,
+        notification: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.service.notification(notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_ids_are_unique() {
+        let first = RequestId::next();
+        let second = RequestId::next();
+        assert_ne!(first, second);
+        assert!(second.0 > first.0);
+    }
+}
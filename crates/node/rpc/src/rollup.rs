@@ -8,24 +8,95 @@ use jsonrpsee::{
     core::RpcResult,
     types::{ErrorCode, ErrorObject},
 };
-use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
+use kona_engine::{
+    ConsolidationMismatch, DepositOnlyBlock, EngineQueries, EngineQuerySender, EngineState,
+    FeeParams,
+};
 use kona_genesis::RollupConfig;
-use kona_protocol::SyncStatus;
+use kona_interop::DependencySet;
+use kona_protocol::{SyncModeSelection, SyncStatus};
+use std::{fmt::Debug, sync::Arc};
+use tokio::sync::watch;
 
 use crate::{
-    L1State, L1WatcherQueries, OutputResponse, RollupNodeApiServer, SafeHeadResponse,
-    l1_watcher::L1WatcherQuerySender,
+    BuildInfo, BuildInfoApiServer, DebugEngineApiServer, DerivationLatency, L1ProvenanceResponse,
+    L1State, L1WatcherQueries, NodeCountersResponse, OutputResponse, RollupEventsApiServer,
+    RollupNodeApiServer, SafeHeadResponse, l1_watcher::L1WatcherQuerySender,
 };
 
+/// An error reading the [`SafeHeadDb`].
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to read the safe head database: {0}")]
+pub struct SafeHeadDbError(pub String);
+
+/// A record of the safe head at each L1 block, serving `optimism_safeHeadAtL1Block`.
+pub trait SafeHeadDb: Debug + Send + Sync {
+    /// Returns the safe head recorded at the highest L1 block that is not greater than
+    /// `l1_block`.
+    fn safe_head_at_l1_block(
+        &self,
+        l1_block: u64,
+    ) -> Result<Option<SafeHeadResponse>, SafeHeadDbError>;
+}
+
+/// An error reading the [`L1ProvenanceDb`].
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to read the L1 provenance database: {0}")]
+pub struct L1ProvenanceDbError(pub String);
+
+/// A record of the L1 data each derived L2 block came from, serving
+/// `rollup_l1ProvenanceForBlock`.
+pub trait L1ProvenanceDb: Debug + Send + Sync {
+    /// Returns the L1 provenance recorded for the L2 block `l2_block`.
+    fn l1_provenance(
+        &self,
+        l2_block: u64,
+    ) -> Result<Option<L1ProvenanceResponse>, L1ProvenanceDbError>;
+}
+
+/// An error reading the [`NodeCountersDb`].
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to read the node counters: {0}")]
+pub struct NodeCountersDbError(pub String);
+
+/// A record of the cumulative counters of the node, serving `rollup_nodeCounters`.
+pub trait NodeCountersDb: Debug + Send + Sync {
+    /// Returns the current value of the counters.
+    fn node_counters(&self) -> Result<NodeCountersResponse, NodeCountersDbError>;
+}
+
 /// RollupRpc
 ///
-/// This is a server implementation of [`crate::RollupNodeApiServer`].
-#[derive(Debug)]
+/// This is a server implementation of [`crate::RollupNodeApiServer`] and
+/// [`crate::RollupEventsApiServer`].
+#[derive(Debug, Clone)]
 pub struct RollupRpc {
     /// The channel to send [`kona_engine::EngineQueries`]s.
     pub engine_sender: EngineQuerySender,
     /// The channel to send [`crate::L1WatcherQueries`]s.
     pub l1_watcher_sender: L1WatcherQuerySender,
+    /// The record of the safe head at each L1 block. `optimism_safeHeadAtL1Block` is not
+    /// supported if unset.
+    pub safe_head_db: Option<Arc<dyn SafeHeadDb>>,
+    /// The latest summary of the derivation latency. `rollup_derivationLatency` is not supported
+    /// if unset.
+    pub derivation_latency: Option<watch::Receiver<DerivationLatency>>,
+    /// The record of the L1 data each derived L2 block came from.
+    /// `rollup_l1ProvenanceForBlock` is not supported if unset.
+    pub l1_provenance_db: Option<Arc<dyn L1ProvenanceDb>>,
+    /// The record of the cumulative counters of the node. `rollup_nodeCounters` is not supported
+    /// if unset.
+    pub node_counters_db: Option<Arc<dyn NodeCountersDb>>,
+    /// The current sync strategy of the node, reported in the sync status.
+    pub sync_mode: Option<watch::Receiver<Option<SyncModeSelection>>>,
+    /// The build information of the node. `kona_buildInfo` is not supported if unset, and
+    /// `optimism_version` reports the version of this crate.
+    pub build_info: Option<Arc<BuildInfo>>,
+    /// The interop dependency set of the node. `rollup_dependencySet` is not supported if unset.
+    pub dependency_set: Option<Arc<DependencySet>>,
+    /// The rollup config of the node. The retention horizon is omitted from the sync status if
+    /// unset.
+    pub rollup_config: Option<Arc<RollupConfig>>,
 }
 
 impl RollupRpc {
@@ -37,12 +108,102 @@ impl RollupRpc {
         engine_sender: EngineQuerySender,
         l1_watcher_sender: L1WatcherQuerySender,
     ) -> Self {
-        Self { engine_sender, l1_watcher_sender }
+        Self {
+            engine_sender,
+            l1_watcher_sender,
+            safe_head_db: None,
+            derivation_latency: None,
+            l1_provenance_db: None,
+            node_counters_db: None,
+            sync_mode: None,
+            build_info: None,
+            dependency_set: None,
+            rollup_config: None,
+        }
+    }
+
+    /// Serves `optimism_safeHeadAtL1Block` from the given [`SafeHeadDb`].
+    pub fn with_safe_head_db(mut self, safe_head_db: Arc<dyn SafeHeadDb>) -> Self {
+        self.safe_head_db = Some(safe_head_db);
+        self
+    }
+
+    /// Serves `rollup_derivationLatency` from the given summary receiver.
+    pub fn with_derivation_latency(
+        mut self,
+        derivation_latency: watch::Receiver<DerivationLatency>,
+    ) -> Self {
+        self.derivation_latency = Some(derivation_latency);
+        self
+    }
+
+    /// Serves `rollup_l1ProvenanceForBlock` from the given [`L1ProvenanceDb`].
+    pub fn with_l1_provenance_db(mut self, l1_provenance_db: Arc<dyn L1ProvenanceDb>) -> Self {
+        self.l1_provenance_db = Some(l1_provenance_db);
+        self
+    }
+
+    /// Serves `rollup_nodeCounters` from the given [`NodeCountersDb`].
+    pub fn with_node_counters_db(mut self, node_counters_db: Arc<dyn NodeCountersDb>) -> Self {
+        self.node_counters_db = Some(node_counters_db);
+        self
+    }
+
+    /// Reports the current sync strategy of the node in the sync status, from the given receiver.
+    pub fn with_sync_mode(mut self, sync_mode: watch::Receiver<Option<SyncModeSelection>>) -> Self {
+        self.sync_mode = Some(sync_mode);
+        self
+    }
+
+    /// Reports the given build information in `kona_buildInfo` and `optimism_version`.
+    pub fn with_build_info(mut self, build_info: BuildInfo) -> Self {
+        self.build_info = Some(Arc::new(build_info));
+        self
+    }
+
+    /// Serves `rollup_dependencySet` from the given [`DependencySet`].
+    pub fn with_dependency_set(mut self, dependency_set: Arc<DependencySet>) -> Self {
+        self.dependency_set = Some(dependency_set);
+        self
+    }
+
+    /// Reports the derivation retention horizon in the sync status, computed with the given
+    /// rollup config.
+    pub fn with_rollup_config(mut self, rollup_config: Arc<RollupConfig>) -> Self {
+        self.rollup_config = Some(rollup_config);
+        self
+    }
+
+    /// Resolves an L1 block number or tag against the L1 state of the node.
+    async fn l1_block_number(&self, block: BlockNumberOrTag) -> RpcResult<u64> {
+        let tag = match block {
+            BlockNumberOrTag::Number(number) => return Ok(number),
+            BlockNumberOrTag::Earliest => return Ok(0),
+            tag => tag,
+        };
+
+        let (l1_state_send, l1_state_recv) = tokio::sync::oneshot::channel();
+        self.l1_watcher_sender
+            .send(L1WatcherQueries::L1State(l1_state_send))
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+        let l1_state =
+            l1_state_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        let block = match tag {
+            BlockNumberOrTag::Safe => l1_state.safe_l1,
+            BlockNumberOrTag::Finalized => l1_state.finalized_l1,
+            _ => l1_state.head_l1,
+        };
+        block.map(|block| block.number).ok_or_else(|| {
+            ErrorObject::owned(-32000, format!("L1 {tag} block unknown"), None::<()>)
+        })
     }
 
     // Important note: we zero-out the fields that can't be derived yet to follow op-node's
     // behaviour.
     fn sync_status_from_actor_queries(
+        &self,
         l1_sync_status: L1State,
         l2_sync_status: EngineState,
     ) -> SyncStatus {
@@ -57,6 +218,11 @@ impl RollupRpc {
             local_safe_l2: l2_sync_status.sync_state.local_safe_head(),
             safe_l2: l2_sync_status.sync_state.safe_head(),
             finalized_l2: l2_sync_status.sync_state.finalized_head(),
+            pending_safe_l2: l2_sync_status.sync_state.pending_safe_head(),
+            sync_mode: self.sync_mode.as_ref().and_then(|sync_mode| sync_mode.borrow().clone()),
+            retention_horizon: self.rollup_config.as_ref().map(|cfg| {
+                kona_engine::retention_horizon(cfg, &l2_sync_status.sync_state.finalized_head())
+            }),
         }
     }
 }
@@ -88,19 +254,34 @@ impl RollupNodeApiServer for RollupRpc {
             }
         )?;
 
-        let sync_status = Self::sync_status_from_actor_queries(l1_sync_status, l2_sync_status);
+        let sync_status = self.sync_status_from_actor_queries(l1_sync_status, l2_sync_status);
 
         Ok(OutputResponse::from_v0(output_root, sync_status, l2_block_info))
     }
 
-    /// This RPC endpoint is not supported. It is not necessary to track the safe head for every L1
-    /// block post-interop anymore so we can remove this method from the rpc interface.
+    /// This RPC endpoint is only supported when the node records the safe head at each L1 block.
     async fn op_safe_head_at_l1_block(
         &self,
-        _block_num: BlockNumberOrTag,
+        block_num: BlockNumberOrTag,
     ) -> RpcResult<SafeHeadResponse> {
         kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_safeHeadAtL1Block");
-        return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        let Some(safe_head_db) = &self.safe_head_db else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+
+        let l1_block = self.l1_block_number(block_num).await?;
+        safe_head_db
+            .safe_head_at_l1_block(l1_block)
+            .map_err(|e| {
+                ErrorObject::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+            })?
+            .ok_or_else(|| {
+                ErrorObject::owned(
+                    -32000,
+                    format!("No safe head recorded at or before L1 block {l1_block}"),
+                    None::<()>,
+                )
+            })
     }
 
     async fn op_sync_status(&self) -> RpcResult<SyncStatus> {
@@ -127,7 +308,7 @@ impl RollupNodeApiServer for RollupRpc {
         )
         .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
-        return Ok(Self::sync_status_from_actor_queries(l1_sync_status, l2_sync_status));
+        return Ok(self.sync_status_from_actor_queries(l1_sync_status, l2_sync_status));
     }
 
     async fn op_rollup_config(&self) -> RpcResult<RollupConfig> {
@@ -147,6 +328,118 @@ impl RollupNodeApiServer for RollupRpc {
 
         const RPC_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-        return Ok(RPC_VERSION.to_string());
+        Ok(self.build_info.as_ref().map_or(RPC_VERSION, |info| &info.version).to_string())
+    }
+}
+
+#[async_trait]
+impl BuildInfoApiServer for RollupRpc {
+    async fn kona_build_info(&self) -> RpcResult<BuildInfo> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "kona_buildInfo");
+
+        let Some(build_info) = &self.build_info else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+        Ok(BuildInfo::clone(build_info))
+    }
+}
+
+#[async_trait]
+impl DebugEngineApiServer for RollupRpc {
+    async fn debug_last_consolidation_mismatch(&self) -> RpcResult<Option<ConsolidationMismatch>> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "debug_lastConsolidationMismatch");
+
+        let (mismatch_send, mismatch_recv) = tokio::sync::oneshot::channel();
+        self.engine_sender
+            .send(EngineQueries::LastConsolidationMismatch(mismatch_send))
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        mismatch_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+    }
+}
+
+#[async_trait]
+impl RollupEventsApiServer for RollupRpc {
+    async fn rollup_deposit_only_blocks(&self) -> RpcResult<Vec<DepositOnlyBlock>> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "rollup_depositOnlyBlocks");
+
+        let (deposit_only_blocks_send, deposit_only_blocks_recv) = tokio::sync::oneshot::channel();
+        self.engine_sender
+            .send(EngineQueries::DepositOnlyBlocks(deposit_only_blocks_send))
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        deposit_only_blocks_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+    }
+    async fn rollup_derivation_latency(&self) -> RpcResult<DerivationLatency> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "rollup_derivationLatency");
+
+        let Some(derivation_latency) = &self.derivation_latency else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+        Ok(*derivation_latency.borrow())
+    }
+
+    /// This RPC endpoint is only supported when the node records the L1 provenance of the blocks
+    /// it derives.
+    async fn rollup_l1_provenance_for_block(
+        &self,
+        l2_block: u64,
+    ) -> RpcResult<L1ProvenanceResponse> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "rollup_l1ProvenanceForBlock");
+
+        let Some(l1_provenance_db) = &self.l1_provenance_db else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+        l1_provenance_db
+            .l1_provenance(l2_block)
+            .map_err(|e| {
+                ErrorObject::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+            })?
+            .ok_or_else(|| {
+                ErrorObject::owned(
+                    -32000,
+                    format!("No L1 provenance recorded for L2 block {l2_block}"),
+                    None::<()>,
+                )
+            })
+    }
+
+    /// This RPC endpoint is only supported when the node records its counters in its database.
+    async fn rollup_node_counters(&self) -> RpcResult<NodeCountersResponse> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "rollup_nodeCounters");
+
+        let Some(node_counters_db) = &self.node_counters_db else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+        node_counters_db.node_counters().map_err(|e| {
+            ErrorObject::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+        })
+    }
+
+    async fn rollup_fee_params(&self) -> RpcResult<FeeParams> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "rollup_feeParams");
+
+        let (fee_params_send, fee_params_recv) = tokio::sync::oneshot::channel();
+        self.engine_sender
+            .send(EngineQueries::FeeParams {
+                block: BlockNumberOrTag::Latest,
+                sender: fee_params_send,
+            })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        fee_params_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+    }
+
+    /// This RPC endpoint is only supported when the node is configured with a dependency set.
+    async fn rollup_dependency_set(&self) -> RpcResult<DependencySet> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "rollup_dependencySet");
+
+        let Some(dependency_set) = &self.dependency_set else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+        Ok(DependencySet::clone(dependency_set))
     }
 }
@@ -0,0 +1,139 @@
+//! Runtime flags, toggled through the admin RPC.
+
+use std::{collections::BTreeSet, sync::Arc};
+use tokio::sync::watch;
+
+/// A flag changing the behaviour of the node at runtime, set with `admin_setRuntimeFlag`.
+///
+/// Runtime flags give operators levers to respond to incidents without redeploying the node. They
+/// are not persisted, and are all unset when the node starts.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeFlag {
+    /// Stops publishing the unsafe blocks built by the sequencer to the gossip network.
+    DisableGossipPublish,
+    /// Stops stepping the derivation pipeline. Derivation resumes from where it stopped once the
+    /// flag is unset.
+    PauseDerivation,
+    /// Logs every forkchoice change of the engine at the `info` level.
+    VerboseEngineLogging,
+}
+
+impl RuntimeFlag {
+    /// All runtime flags.
+    pub const ALL: [Self; 3] =
+        [Self::DisableGossipPublish, Self::PauseDerivation, Self::VerboseEngineLogging];
+
+    /// Returns a description of the effect of the flag.
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::DisableGossipPublish => "Stops publishing sequenced unsafe blocks to gossip",
+            Self::PauseDerivation => "Stops stepping the derivation pipeline",
+            Self::VerboseEngineLogging => "Logs every forkchoice change of the engine at info",
+        }
+    }
+}
+
+/// The state of a [`RuntimeFlag`], returned by `admin_runtimeFlags`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeFlagStatus {
+    /// The flag.
+    pub flag: RuntimeFlag,
+    /// Whether the flag is set.
+    pub enabled: bool,
+    /// A description of the effect of the flag.
+    pub description: String,
+}
+
+/// The registry of the [`RuntimeFlag`]s set on the node.
+///
+/// Clones share the same registry. Actors either check a flag when they act on it, or
+/// [`Self::subscribe`] to react to its changes.
+#[derive(Debug, Clone)]
+pub struct RuntimeFlags(Arc<watch::Sender<BTreeSet<RuntimeFlag>>>);
+
+impl Default for RuntimeFlags {
+    fn default() -> Self {
+        Self(Arc::new(watch::Sender::new(BTreeSet::new())))
+    }
+}
+
+impl RuntimeFlags {
+    /// Returns whether the flag is set.
+    pub fn is_enabled(&self, flag: RuntimeFlag) -> bool {
+        self.0.borrow().contains(&flag)
+    }
+
+    /// Sets or unsets the flag. Returns whether the flag changed.
+    pub fn set(&self, flag: RuntimeFlag, enabled: bool) -> bool {
+        self.0.send_if_modified(
+            |flags| if enabled { flags.insert(flag) } else { flags.remove(&flag) },
+        )
+    }
+
+    /// Returns the state of every [`RuntimeFlag`].
+    pub fn status(&self) -> Vec<RuntimeFlagStatus> {
+        let flags = self.0.borrow();
+        RuntimeFlag::ALL
+            .into_iter()
+            .map(|flag| RuntimeFlagStatus {
+                flag,
+                enabled: flags.contains(&flag),
+                description: flag.description().to_string(),
+            })
+            .collect()
+    }
+
+    /// Subscribes to the changes of the set flags.
+    pub fn subscribe(&self) -> watch::Receiver<BTreeSet<RuntimeFlag>> {
+        self.0.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_flags_shared_between_clones() {
+        let flags = RuntimeFlags::default();
+        let clone = flags.clone();
+        let mut rx = flags.subscribe();
+
+        assert!(!clone.is_enabled(RuntimeFlag::PauseDerivation));
+        assert!(flags.set(RuntimeFlag::PauseDerivation, true));
+        assert!(!flags.set(RuntimeFlag::PauseDerivation, true));
+        assert!(clone.is_enabled(RuntimeFlag::PauseDerivation));
+        assert!(rx.has_changed().unwrap());
+        assert!(rx.borrow_and_update().contains(&RuntimeFlag::PauseDerivation));
+
+        assert!(clone.set(RuntimeFlag::PauseDerivation, false));
+        assert!(!flags.is_enabled(RuntimeFlag::PauseDerivation));
+    }
+
+    #[test]
+    fn test_runtime_flags_status() {
+        let flags = RuntimeFlags::default();
+        flags.set(RuntimeFlag::DisableGossipPublish, true);
+
+        let status = flags.status();
+        assert_eq!(status.len(), RuntimeFlag::ALL.len());
+        assert!(status.iter().all(|s| s.enabled == (s.flag == RuntimeFlag::DisableGossipPublish)));
+    }
+
+    #[test]
+    fn test_runtime_flag_serde() {
+        assert_eq!(
+            serde_json::to_string(&RuntimeFlag::PauseDerivation).unwrap(),
+            "\"pause_derivation\""
+        );
+        assert_eq!(
+            serde_json::from_str::<RuntimeFlag>("\"verbose_engine_logging\"").unwrap(),
+            RuntimeFlag::VerboseEngineLogging
+        );
+        assert!(serde_json::from_str::<RuntimeFlag>("\"unknown\"").is_err());
+    }
+}
@@ -0,0 +1,282 @@
+//! [`NodeActor`] implementation storing every derived attributes payload.
+
+use crate::{
+    CancellableContext, NodeActor,
+    actors::archive::{
+        ArchiveActorError, ArchiveConfig, ArchiveHead, ArchiveTarget, ArchivedAttributes, S3Target,
+        s3, source::read_head,
+    },
+};
+use async_trait::async_trait;
+use kona_protocol::OpAttributesWithParent;
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{select, sync::mpsc, task::JoinSet};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// The timeout of the requests uploading attributes to a bucket.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of attributes being stored concurrently.
+const MAX_CONCURRENT_WRITES: usize = 16;
+
+/// The [`ArchiveActor`] stores every payload attributes derived from L1 as an
+/// [`ArchivedAttributes`] record, along with their L1 provenance, in a local directory or an
+/// S3-compatible bucket.
+///
+/// This lets downstream analytics consume the derived attributes, and lets the attributes be
+/// served again to other nodes. The attributes are received from the derivation actor over a
+/// bounded channel, so derivation waits for a slow target instead of skipping attributes.
+/// Attributes that fail to be stored are logged and skipped. Once attributes are stored, the
+/// [`ArchiveHead`] is raised to their block, so that readers can tell the end of the archive apart
+/// from attributes that failed to be stored.
+#[derive(Debug)]
+pub struct ArchiveActor {
+    /// The archive configuration.
+    config: ArchiveConfig,
+    /// The client used to upload attributes to a bucket.
+    client: reqwest::Client,
+}
+
+/// The communication context used by the [`ArchiveActor`].
+#[derive(Debug)]
+pub struct ArchiveContext {
+    /// The receiver of the attributes derived by the derivation actor.
+    pub attributes: mpsc::Receiver<Arc<OpAttributesWithParent>>,
+    /// The cancellation token, shared between all tasks.
+    pub cancellation: CancellationToken,
+}
+
+impl CancellableContext for ArchiveContext {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+impl ArchiveActor {
+    /// Creates a new [`ArchiveActor`].
+    pub fn new(config: ArchiveConfig) -> Self {
+        Self { config, client: reqwest::Client::new() }
+    }
+
+    /// Stores the `record` in the configured target.
+    async fn store(
+        target: ArchiveTarget,
+        client: reqwest::Client,
+        record: ArchivedAttributes,
+    ) -> Result<u64, ArchiveActorError> {
+        put(&target, &client, &record.key(), serde_json::to_vec(&record)?).await?;
+        Ok(record.block_number)
+    }
+
+    /// Logs the outcome of storing a record, and raises the `head` of the archive to the block of
+    /// the stored record.
+    async fn stored(
+        &self,
+        result: Result<Result<u64, ArchiveActorError>, tokio::task::JoinError>,
+        head: &mut Option<u64>,
+    ) {
+        let Some(block_number) = log_stored(result) else { return };
+        if head.is_some_and(|head| head >= block_number) {
+            return;
+        }
+
+        let body = match serde_json::to_vec(&ArchiveHead::new(block_number)) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(target: "archive", error = %e, "Failed to serialize the archive head");
+                return;
+            }
+        };
+        match put(&self.config.target, &self.client, ArchiveHead::KEY, body).await {
+            Ok(()) => *head = Some(block_number),
+            Err(e) => {
+                warn!(
+                    target: "archive",
+                    block_number,
+                    error = %e,
+                    "Failed to store the archive head"
+                );
+                kona_macros::inc!(counter, crate::Metrics::ARCHIVE_FAILURES, "reason" => "head");
+            }
+        }
+    }
+}
+
+/// Writes `body` as the object at `key` in the `target`.
+async fn put(
+    target: &ArchiveTarget,
+    client: &reqwest::Client,
+    key: &str,
+    body: Vec<u8>,
+) -> Result<(), ArchiveActorError> {
+    match target {
+        ArchiveTarget::Directory(dir) => write_file(&dir.join(key), &body).await?,
+        ArchiveTarget::S3(target) => upload(client, target, key, body).await?,
+    }
+    Ok(())
+}
+
+/// Uploads `body` as the object at `key` in the bucket of the `target`.
+async fn upload(
+    client: &reqwest::Client,
+    target: &S3Target,
+    key: &str,
+    body: Vec<u8>,
+) -> Result<(), ArchiveActorError> {
+    let url = target.url.join(key)?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let headers = s3::sign(target, "PUT", &url, &body, timestamp);
+
+    client
+        .put(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(reqwest::header::AUTHORIZATION, headers.authorization)
+        .header("x-amz-content-sha256", headers.content_sha256)
+        .header("x-amz-date", headers.date)
+        .timeout(HTTP_TIMEOUT)
+        .body(body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Replaces the contents of the file at `path`, creating its directory if needed, by writing them
+/// to a sibling temporary file that is then renamed over it.
+pub(super) async fn write_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let tmp_path = tmp_path(path);
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(&tmp_path, path).await
+}
+
+/// Returns the path of the temporary file a record is written to before replacing `path`.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+/// Logs the outcome of storing a record, returning the block of the record if it was stored.
+fn log_stored(
+    result: Result<Result<u64, ArchiveActorError>, tokio::task::JoinError>,
+) -> Option<u64> {
+    match result {
+        Ok(Ok(block_number)) => {
+            trace!(target: "archive", block_number, "Archived derived attributes");
+            kona_macros::inc!(counter, crate::Metrics::ARCHIVED_ATTRIBUTES);
+            Some(block_number)
+        }
+        Ok(Err(e)) => {
+            warn!(target: "archive", error = %e, "Failed to archive derived attributes");
+            kona_macros::inc!(counter, crate::Metrics::ARCHIVE_FAILURES, "reason" => "store");
+            None
+        }
+        Err(e) => {
+            warn!(target: "archive", error = %e, "Archive task failed");
+            kona_macros::inc!(counter, crate::Metrics::ARCHIVE_FAILURES, "reason" => "store");
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl NodeActor for ArchiveActor {
+    type Error = ArchiveActorError;
+    type StartData = ArchiveContext;
+
+    async fn start(self, mut ctx: Self::StartData) -> Result<(), Self::Error> {
+        let mut writes = JoinSet::new();
+
+        // Resume from the head of an existing archive, so that it is never lowered.
+        let mut head = read_head(&self.config.target, &self.client).await.unwrap_or_else(|e| {
+            warn!(target: "archive", error = %e, "Failed to read the archive head");
+            None
+        });
+
+        loop {
+            // Wait for a write to complete before receiving more attributes, so that a slow
+            // target holds back derivation instead of growing the pending writes without bound.
+            while writes.len() >= MAX_CONCURRENT_WRITES {
+                if let Some(result) = writes.join_next().await {
+                    self.stored(result, &mut head).await;
+                }
+            }
+
+            select! {
+                _ = ctx.cancellation.cancelled() => {
+                    info!(target: "archive", "Received shutdown signal. Exiting archive task.");
+                    while let Some(result) = writes.join_next().await {
+                        self.stored(result, &mut head).await;
+                    }
+                    return Ok(());
+                }
+                Some(result) = writes.join_next() => self.stored(result, &mut head).await,
+                attributes = ctx.attributes.recv() => {
+                    let Some(attributes) = attributes else {
+                        error!(target: "archive", "Derived attributes channel closed unexpectedly");
+                        return Err(ArchiveActorError::ChannelClosed);
+                    };
+                    let record = ArchivedAttributes::from(attributes.as_ref());
+                    writes.spawn(Self::store(
+                        self.config.target.clone(),
+                        self.client.clone(),
+                        record,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_protocol::L2BlockInfo;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    #[tokio::test]
+    async fn test_archive_to_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let actor = ArchiveActor::new(ArchiveConfig {
+            target: ArchiveTarget::Directory(dir.path().to_path_buf()),
+        });
+
+        let (attributes_tx, attributes_rx) = mpsc::channel(8);
+        let cancellation = CancellationToken::new();
+        let task = tokio::spawn(actor.start(ArchiveContext {
+            attributes: attributes_rx,
+            cancellation: cancellation.clone(),
+        }));
+
+        let attributes = OpAttributesWithParent::new(
+            OpPayloadAttributes::default(),
+            L2BlockInfo::default(),
+            None,
+            false,
+        );
+        attributes_tx.send(Arc::new(attributes.clone())).await.unwrap();
+
+        let path = dir.path().join("attributes/000000000001.json");
+        while !path.exists() {
+            tokio::task::yield_now().await;
+        }
+        cancellation.cancel();
+        task.await.unwrap().unwrap();
+
+        let record: ArchivedAttributes =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(OpAttributesWithParent::from(record), attributes);
+        assert!(!tmp_path(&path).exists());
+
+        let target = ArchiveTarget::Directory(dir.path().to_path_buf());
+        assert_eq!(read_head(&target, &reqwest::Client::new()).await.unwrap(), Some(1));
+    }
+}
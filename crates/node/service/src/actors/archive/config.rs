@@ -0,0 +1,56 @@
+//! Configuration for the [`ArchiveActor`].
+//!
+//! [`ArchiveActor`]: super::ArchiveActor
+
+use std::{fmt, path::PathBuf};
+use url::Url;
+
+/// Where the [`ArchiveActor`] stores the derived attributes.
+///
+/// [`ArchiveActor`]: super::ArchiveActor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchiveTarget {
+    /// The attributes are written to files in a local directory.
+    Directory(PathBuf),
+    /// The attributes are uploaded to an S3-compatible bucket.
+    S3(S3Target),
+}
+
+/// An S3-compatible bucket the [`ArchiveActor`] uploads the derived attributes to.
+///
+/// Objects are addressed in path style, under [`Self::url`], and uploaded with requests signed
+/// with AWS Signature Version 4.
+///
+/// [`ArchiveActor`]: super::ArchiveActor
+#[derive(Clone, PartialEq, Eq)]
+pub struct S3Target {
+    /// The URL of the bucket, including the optional prefix of the objects, e.g.
+    /// `https://s3.us-east-1.amazonaws.com/my-bucket/kona/`.
+    pub url: Url,
+    /// The region of the bucket.
+    pub region: String,
+    /// The access key ID the uploads are signed with.
+    pub access_key_id: String,
+    /// The secret access key the uploads are signed with.
+    pub secret_access_key: String,
+}
+
+impl fmt::Debug for S3Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3Target")
+            .field("url", &self.url)
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Configuration for the [`ArchiveActor`].
+///
+/// [`ArchiveActor`]: super::ArchiveActor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveConfig {
+    /// Where the attributes are stored.
+    pub target: ArchiveTarget,
+}
@@ -0,0 +1,19 @@
+/// An error produced by the [`crate::ArchiveActor`], or by the [`crate::ArchiveAttributesSource`].
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveActorError {
+    /// The channel of the derived attributes was unexpectedly closed.
+    #[error("Channel closed unexpectedly")]
+    ChannelClosed,
+    /// The archived attributes could not be serialized or deserialized.
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+    /// The archived attributes could not be written to or read from their directory.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The archived attributes could not be uploaded to or downloaded from their bucket.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The URL of the archived attributes in their bucket is invalid.
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+}
@@ -0,0 +1,19 @@
+//! The `ArchiveActor` and its components.
+
+mod config;
+pub use config::{ArchiveConfig, ArchiveTarget, S3Target};
+
+mod record;
+use record::ArchiveHead;
+pub use record::ArchivedAttributes;
+
+mod s3;
+
+mod source;
+pub use source::ArchiveAttributesSource;
+
+mod actor;
+pub use actor::{ArchiveActor, ArchiveContext};
+
+mod error;
+pub use error::ArchiveActorError;
@@ -0,0 +1,123 @@
+//! The format of the derived attributes stored by the [`ArchiveActor`].
+//!
+//! [`ArchiveActor`]: super::ArchiveActor
+
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use serde::{Deserialize, Serialize};
+
+/// Derived payload attributes, along with their L1 provenance, as stored by the
+/// [`ArchiveActor`].
+///
+/// Each record is stored as a JSON object under [`Self::key`]. Attributes derived again for the
+/// same block, after a reorg or a reset, replace the previous record.
+///
+/// [`ArchiveActor`]: super::ArchiveActor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedAttributes {
+    /// The version of the record format, [`Self::VERSION`].
+    pub version: u8,
+    /// The number of the L2 block built from the attributes.
+    pub block_number: u64,
+    /// The L2 block the attributes build on.
+    pub parent: L2BlockInfo,
+    /// The L1 block the attributes were derived from.
+    pub l1_origin: Option<BlockInfo>,
+    /// Whether the attributes are the last of their span batch.
+    pub is_last_in_span: bool,
+    /// The payload attributes.
+    pub attributes: OpPayloadAttributes,
+}
+
+impl ArchivedAttributes {
+    /// The current version of the record format.
+    pub const VERSION: u8 = 1;
+
+    /// Returns the key the record is stored under, relative to the archive directory or bucket.
+    pub fn key(&self) -> String {
+        Self::block_key(self.block_number)
+    }
+
+    /// Returns the key the record of the given L2 block is stored under.
+    ///
+    /// The block number is zero-padded so that the keys sort in block order.
+    pub fn block_key(block_number: u64) -> String {
+        format!("attributes/{block_number:012}.json")
+    }
+}
+
+/// The highest L2 block whose attributes were stored by the [`ArchiveActor`], stored as a JSON
+/// object under [`Self::KEY`].
+///
+/// The head tells the end of the archive apart from attributes missing below it, which the
+/// [`ArchiveAttributesSource`] refuses to skip over.
+///
+/// [`ArchiveActor`]: super::ArchiveActor
+/// [`ArchiveAttributesSource`]: super::ArchiveAttributesSource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ArchiveHead {
+    /// The version of the record format, [`ArchivedAttributes::VERSION`].
+    pub(super) version: u8,
+    /// The number of the highest L2 block whose attributes were stored.
+    pub(super) block_number: u64,
+}
+
+impl ArchiveHead {
+    /// The key the head is stored under, relative to the archive directory or bucket.
+    pub(super) const KEY: &str = "head.json";
+
+    /// Creates a new [`ArchiveHead`] at the given L2 block.
+    pub(super) const fn new(block_number: u64) -> Self {
+        Self { version: ArchivedAttributes::VERSION, block_number }
+    }
+}
+
+impl From<&OpAttributesWithParent> for ArchivedAttributes {
+    fn from(attributes: &OpAttributesWithParent) -> Self {
+        Self {
+            version: Self::VERSION,
+            block_number: attributes.block_number(),
+            parent: attributes.parent,
+            l1_origin: attributes.derived_from,
+            is_last_in_span: attributes.is_last_in_span,
+            attributes: attributes.attributes.clone(),
+        }
+    }
+}
+
+impl From<ArchivedAttributes> for OpAttributesWithParent {
+    fn from(record: ArchivedAttributes) -> Self {
+        Self::new(record.attributes, record.parent, record.l1_origin, record.is_last_in_span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archived_attributes_roundtrip() {
+        let attributes = OpAttributesWithParent::new(
+            OpPayloadAttributes { gas_limit: Some(30_000_000), ..Default::default() },
+            L2BlockInfo {
+                block_info: BlockInfo { number: 41, ..Default::default() },
+                ..Default::default()
+            },
+            Some(BlockInfo { number: 7, ..Default::default() }),
+            true,
+        );
+
+        let record = ArchivedAttributes::from(&attributes);
+        assert_eq!(record.version, ArchivedAttributes::VERSION);
+        assert_eq!(record.key(), "attributes/000000000042.json");
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["blockNumber"], 42);
+        assert_eq!(json["l1Origin"]["number"], 7);
+
+        let decoded = serde_json::from_value::<ArchivedAttributes>(json).unwrap();
+        assert_eq!(OpAttributesWithParent::from(decoded), attributes);
+    }
+}
@@ -0,0 +1,320 @@
+//! A source of payload attributes reading the records stored by the [`ArchiveActor`].
+//!
+//! [`ArchiveActor`]: super::ArchiveActor
+
+use crate::{
+    DerivationState, PipelineBuilder,
+    actors::archive::{
+        ArchiveActorError, ArchiveHead, ArchiveTarget, ArchivedAttributes, S3Target, s3,
+    },
+};
+use async_trait::async_trait;
+use kona_derive::{
+    ActivationSignal, OriginProvider, Pipeline, PipelineError, PipelineErrorKind, PipelineResult,
+    ResetSignal, Signal, SignalReceiver, StepResult,
+};
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The timeout of the requests downloading attributes from a bucket.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A [`Pipeline`] reading the payload attributes of each L2 block from the [`ArchivedAttributes`]
+/// records stored by the [`ArchiveActor`], instead of deriving them from L1.
+///
+/// The derivation actor steps the source like the derivation pipeline, so the attributes are fed
+/// to the engine as if they were derived, one block at a time on top of the safe head. This makes
+/// it possible to replay a chain without reading its batches from L1, for fast replays, disaster
+/// recovery and CI pipelines. The source yields once it steps past the [`ArchiveHead`], the
+/// highest block stored. It fails if the attributes of a block below the head are missing, or if
+/// the archived attributes do not build on the safe head of the engine.
+///
+/// [`ArchiveActor`]: super::ArchiveActor
+#[derive(Debug)]
+pub struct ArchiveAttributesSource {
+    /// Where the attributes are read from.
+    target: ArchiveTarget,
+    /// The rollup config.
+    rollup_config: Arc<RollupConfig>,
+    /// The client used to download attributes from a bucket.
+    client: reqwest::Client,
+    /// The L1 origin of the last attributes read.
+    origin: Option<BlockInfo>,
+    /// The prepared attributes.
+    prepared: Option<OpAttributesWithParent>,
+}
+
+impl ArchiveAttributesSource {
+    /// Creates a new [`ArchiveAttributesSource`] reading from the given target.
+    pub fn new(target: ArchiveTarget, rollup_config: Arc<RollupConfig>) -> Self {
+        Self { target, rollup_config, client: reqwest::Client::new(), origin: None, prepared: None }
+    }
+
+    /// Reads the archived attributes of the given L2 block, if they were stored.
+    async fn read(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<ArchivedAttributes>, ArchiveActorError> {
+        let key = ArchivedAttributes::block_key(block_number);
+        let Some(body) = get(&self.target, &self.client, &key).await? else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_slice(&body)?))
+    }
+
+    /// Returns the result of a step that found no attributes for the given L2 block. Past the
+    /// [`ArchiveHead`], the source waits for more attributes to be stored. Below it, the
+    /// attributes are missing from the archive, and the replay stops.
+    async fn missing(&self, block_number: u64) -> StepResult {
+        match read_head(&self.target, &self.client).await {
+            Ok(Some(head)) if head >= block_number => StepResult::StepFailed(
+                PipelineError::Provider(format!(
+                    "The attributes of block {block_number} are missing from the archive, which \
+                     holds attributes up to block {head}"
+                ))
+                .crit(),
+            ),
+            Ok(_) => {
+                debug!(target: "archive", block_number, "Reached the end of the archive");
+                StepResult::StepFailed(PipelineError::Eof.temp())
+            }
+            Err(e) => {
+                warn!(target: "archive", error = %e, "Failed to read the archive head");
+                StepResult::StepFailed(PipelineError::Provider(e.to_string()).temp())
+            }
+        }
+    }
+}
+
+/// Reads the number of the highest L2 block stored in the archive, if the [`ArchiveHead`] was
+/// stored.
+pub(super) async fn read_head(
+    target: &ArchiveTarget,
+    client: &reqwest::Client,
+) -> Result<Option<u64>, ArchiveActorError> {
+    let Some(body) = get(target, client, ArchiveHead::KEY).await? else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_slice::<ArchiveHead>(&body)?.block_number))
+}
+
+/// Reads the object at `key` in the `target`, if it exists.
+async fn get(
+    target: &ArchiveTarget,
+    client: &reqwest::Client,
+    key: &str,
+) -> Result<Option<Vec<u8>>, ArchiveActorError> {
+    match target {
+        ArchiveTarget::Directory(dir) => match tokio::fs::read(dir.join(key)).await {
+            Ok(body) => Ok(Some(body)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        },
+        ArchiveTarget::S3(target) => download(client, target, key).await,
+    }
+}
+
+/// Downloads the object at `key` in the bucket of the `target`, if it exists.
+async fn download(
+    client: &reqwest::Client,
+    target: &S3Target,
+    key: &str,
+) -> Result<Option<Vec<u8>>, ArchiveActorError> {
+    let url = target.url.join(key)?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let headers = s3::sign(target, "GET", &url, &[], timestamp);
+
+    let response = client
+        .get(url)
+        .header(reqwest::header::AUTHORIZATION, headers.authorization)
+        .header("x-amz-content-sha256", headers.content_sha256)
+        .header("x-amz-date", headers.date)
+        .timeout(HTTP_TIMEOUT)
+        .send()
+        .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+}
+
+impl Iterator for ArchiveAttributesSource {
+    type Item = OpAttributesWithParent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.prepared.take()
+    }
+}
+
+impl OriginProvider for ArchiveAttributesSource {
+    fn origin(&self) -> Option<BlockInfo> {
+        self.origin
+    }
+}
+
+#[async_trait]
+impl Pipeline for ArchiveAttributesSource {
+    fn peek(&self) -> Option<&OpAttributesWithParent> {
+        self.prepared.as_ref()
+    }
+
+    async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+        if self.prepared.is_some() {
+            return StepResult::PreparedAttributes;
+        }
+
+        let block_number = cursor.block_info.number + 1;
+        let record = match self.read(block_number).await {
+            Ok(Some(record)) => record,
+            Ok(None) => return self.missing(block_number).await,
+            Err(e) => {
+                warn!(
+                    target: "archive",
+                    block_number,
+                    error = %e,
+                    "Failed to read archived attributes"
+                );
+                return StepResult::StepFailed(PipelineError::Provider(e.to_string()).temp());
+            }
+        };
+
+        if record.version != ArchivedAttributes::VERSION {
+            return StepResult::StepFailed(
+                PipelineError::Provider(format!(
+                    "Unsupported version {} of the archived attributes of block {block_number}",
+                    record.version
+                ))
+                .crit(),
+            );
+        }
+        if record.parent.block_info.hash != cursor.block_info.hash {
+            return StepResult::StepFailed(
+                PipelineError::Provider(format!(
+                    "The archived attributes of block {block_number} build on {}, not on the \
+                     safe head {}",
+                    record.parent.block_info.hash, cursor.block_info.hash
+                ))
+                .crit(),
+            );
+        }
+
+        self.origin = record.l1_origin.or(self.origin);
+        self.prepared = Some(record.into());
+        StepResult::PreparedAttributes
+    }
+
+    fn rollup_config(&self) -> &RollupConfig {
+        &self.rollup_config
+    }
+
+    async fn system_config_by_number(&mut self, _: u64) -> Result<SystemConfig, PipelineErrorKind> {
+        // The source never requests a reset, which is the only use of the system config.
+        Err(PipelineError::Provider("System configs are not archived".to_string()).crit())
+    }
+}
+
+#[async_trait]
+impl SignalReceiver for ArchiveAttributesSource {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+        match signal {
+            Signal::Reset(ResetSignal { l1_origin, .. }) |
+            Signal::Activation(ActivationSignal { l1_origin, .. }) => {
+                self.origin = Some(l1_origin);
+                self.prepared = None;
+            }
+            Signal::FlushChannel | Signal::FlushChannelById(_) => self.prepared = None,
+            Signal::ProvideBlock(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PipelineBuilder for ArchiveAttributesSource {
+    type Pipeline = Self;
+
+    async fn build(self) -> DerivationState<Self> {
+        DerivationState::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::archive::actor::write_file;
+    use alloy_primitives::B256;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    /// Returns the info of the L2 block with the given number and hash.
+    fn block(number: u64, hash: u8) -> L2BlockInfo {
+        L2BlockInfo {
+            block_info: BlockInfo { number, hash: B256::repeat_byte(hash), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_source_steps_on_safe_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let attributes = OpAttributesWithParent::new(
+            OpPayloadAttributes { gas_limit: Some(30_000_000), ..Default::default() },
+            block(1, 1),
+            Some(BlockInfo { number: 9, ..Default::default() }),
+            true,
+        );
+        let record = ArchivedAttributes::from(&attributes);
+        write_file(&dir.path().join(record.key()), &serde_json::to_vec(&record).unwrap())
+            .await
+            .unwrap();
+
+        let mut source = ArchiveAttributesSource::new(
+            ArchiveTarget::Directory(dir.path().to_path_buf()),
+            Arc::new(RollupConfig::default()),
+        );
+
+        // The archive holds no attributes for block 1.
+        assert_eq!(
+            source.step(block(0, 0)).await,
+            StepResult::StepFailed(PipelineError::Eof.temp())
+        );
+
+        // The attributes of block 2 do not build on another block 1.
+        assert!(matches!(
+            source.step(block(1, 2)).await,
+            StepResult::StepFailed(PipelineErrorKind::Critical(_))
+        ));
+
+        assert_eq!(source.step(block(1, 1)).await, StepResult::PreparedAttributes);
+        assert_eq!(source.origin().map(|origin| origin.number), Some(9));
+        assert_eq!(source.next(), Some(attributes));
+        assert_eq!(source.next(), None);
+    }
+
+    #[tokio::test]
+    async fn test_archive_source_fails_on_missing_attributes() {
+        let dir = tempfile::tempdir().unwrap();
+        let head = serde_json::to_vec(&ArchiveHead::new(2)).unwrap();
+        write_file(&dir.path().join(ArchiveHead::KEY), &head).await.unwrap();
+
+        let mut source = ArchiveAttributesSource::new(
+            ArchiveTarget::Directory(dir.path().to_path_buf()),
+            Arc::new(RollupConfig::default()),
+        );
+
+        // The attributes of block 1 are below the head of the archive, so they are missing.
+        assert!(matches!(
+            source.step(block(0, 0)).await,
+            StepResult::StepFailed(PipelineErrorKind::Critical(_))
+        ));
+
+        // The attributes of block 3 are past the head of the archive, so they are yet to come.
+        assert_eq!(
+            source.step(block(2, 2)).await,
+            StepResult::StepFailed(PipelineError::Eof.temp())
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! [`NodeActor`] implementation for a batch submitter.
+
+use crate::{
+    CancellableContext, NodeActor,
+    actors::batcher::{
+        BatcherActorError, BatcherConfig, ChannelBuilder, ChannelData, DataAvailabilityType, da,
+    },
+};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_network::TransactionBuilder4844;
+use alloy_provider::{PendingTransactionError, Provider, RootProvider, WatchTxError};
+use alloy_rpc_types_eth::TransactionRequest;
+use async_trait::async_trait;
+use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
+use kona_genesis::RollupConfig;
+use kona_protocol::{L2BlockInfo, SingleBatch};
+use op_alloy_network::Optimism;
+use std::sync::Arc;
+use tokio::{
+    select,
+    sync::{oneshot, watch},
+};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// The maximum number of times a batch transaction is resubmitted with bumped fees before the
+/// submission is abandoned.
+const MAX_RESUBMISSIONS: usize = 10;
+
+/// The [`BatcherActor`] reads unsafe L2 blocks from the execution client, batches them into
+/// channels, and submits the channel frames to the batch inbox on L1.
+///
+/// Blocks are batched from the safe head onwards. Once a channel is submitted, the batcher keeps
+/// building the next one, and resets to the safe head if the submitted channel isn't derived
+/// within the channel timeout.
+#[derive(Debug)]
+pub struct BatcherActor<P: Provider> {
+    /// The batcher configuration.
+    config: BatcherConfig,
+    /// The rollup config.
+    rollup_config: Arc<RollupConfig>,
+    /// The L1 provider, with a wallet for the batcher signer.
+    l1_provider: P,
+    /// The L2 execution client provider, used to fetch the blocks to batch.
+    l2_provider: RootProvider<Optimism>,
+    /// The sender for queries to the engine, used to track the safe and unsafe heads.
+    engine_query_tx: EngineQuerySender,
+    /// The cancellation token, shared between all tasks.
+    cancellation: CancellationToken,
+    /// The open channel.
+    channel: ChannelBuilder,
+    /// The latest L2 block added to a channel, if any.
+    last_queued: Option<L2BlockInfo>,
+    /// The last L2 block of the latest submitted channel, and the L1 block number the channel
+    /// was included in, until the safe head reaches that block.
+    pending: Option<(L2BlockInfo, u64)>,
+    /// The estimated size, in bytes, of the batched data pending submission to L1.
+    backlog: watch::Sender<u64>,
+}
+
+impl<P: Provider> BatcherActor<P> {
+    /// Creates a new [`BatcherActor`].
+    pub fn new(
+        config: BatcherConfig,
+        rollup_config: Arc<RollupConfig>,
+        l1_provider: P,
+        l2_provider: RootProvider<Optimism>,
+        engine_query_tx: EngineQuerySender,
+        cancellation: CancellationToken,
+    ) -> Self {
+        let max_frame_size = match config.data_availability {
+            DataAvailabilityType::Calldata => da::MAX_CALLDATA_FRAME_SIZE,
+            DataAvailabilityType::Blobs | DataAvailabilityType::Auto => da::MAX_BLOB_FRAME_SIZE,
+        };
+        let channel = ChannelBuilder::new(
+            rollup_config.clone(),
+            config.target_num_frames.max(1) * max_frame_size,
+            config.approx_compression_ratio,
+            config.max_channel_duration,
+        );
+        Self {
+            config,
+            rollup_config,
+            l1_provider,
+            l2_provider,
+            engine_query_tx,
+            cancellation,
+            channel,
+            last_queued: None,
+            pending: None,
+            backlog: watch::Sender::new(0),
+        }
+    }
+
+    /// Returns a receiver of the DA backlog: the estimated size, in bytes, of the batched data
+    /// pending submission to L1.
+    pub fn backlog(&self) -> watch::Receiver<u64> {
+        self.backlog.subscribe()
+    }
+
+    /// Reports the DA backlog, made of the open channel and `submitting` bytes of channel data
+    /// being submitted.
+    fn report_backlog(&self, submitting: usize) {
+        let backlog = (self.channel.estimated_size() + submitting) as u64;
+        self.backlog.send_replace(backlog);
+        kona_macros::set!(gauge, crate::Metrics::BATCHER_DA_BACKLOG, backlog as f64);
+    }
+
+    /// Discards the open channel and any pending submission, so that batching restarts from the
+    /// safe head.
+    fn reset(&mut self) {
+        self.channel.clear();
+        self.last_queued = None;
+        self.pending = None;
+        self.report_backlog(0);
+    }
+
+    /// Subscribes to the engine state.
+    async fn engine_state(&self) -> Result<watch::Receiver<EngineState>, BatcherActorError> {
+        let (sender, receiver) = oneshot::channel();
+        self.engine_query_tx
+            .send(EngineQueries::StateReceiver(sender))
+            .await
+            .map_err(|_| BatcherActorError::ChannelClosed)?;
+        receiver.await.map_err(|_| BatcherActorError::ChannelClosed)
+    }
+
+    /// Batches the unsafe blocks that haven't been queued yet, and submits the open channel once
+    /// it is ready.
+    async fn step(
+        &mut self,
+        engine_state: &watch::Receiver<EngineState>,
+    ) -> Result<(), BatcherActorError> {
+        let (safe_head, unsafe_head) = {
+            let state = engine_state.borrow();
+            (state.sync_state.safe_head(), state.sync_state.unsafe_head())
+        };
+        let l1_head = self.l1_provider.get_block_number().await?;
+
+        if let Some((last_block, inclusion_block)) = self.pending {
+            let channel_timeout =
+                self.rollup_config.channel_timeout(last_block.block_info.timestamp);
+            if safe_head.block_info.number >= last_block.block_info.number {
+                self.pending = None;
+            } else if l1_head > inclusion_block + channel_timeout {
+                warn!(
+                    target: "batcher",
+                    last_block = last_block.block_info.number,
+                    inclusion_block,
+                    "Submitted channel was not derived within the channel timeout, resetting to the safe head"
+                );
+                self.reset();
+            }
+        }
+
+        let mut parent = match self.last_queued {
+            Some(block) if block.block_info.number >= safe_head.block_info.number => block,
+            _ => {
+                self.channel.clear();
+                safe_head
+            }
+        };
+
+        for number in parent.block_info.number + 1..=unsafe_head.block_info.number {
+            let (block, batch) = self.load_block(number).await?;
+            if block.block_info.parent_hash != parent.block_info.hash {
+                warn!(
+                    target: "batcher",
+                    number,
+                    "L2 reorg detected, resetting to the safe head"
+                );
+                self.reset();
+                return Ok(());
+            }
+
+            self.channel.add_block(block, batch, l1_head);
+            self.last_queued = Some(block);
+            parent = block;
+            self.report_backlog(0);
+
+            if self.channel.is_full() {
+                self.submit_channel().await?;
+            }
+        }
+
+        if self.channel.is_ready(l1_head) {
+            self.submit_channel().await?;
+        }
+        Ok(())
+    }
+
+    /// Fetches an L2 block, and builds its [`SingleBatch`].
+    async fn load_block(
+        &self,
+        number: u64,
+    ) -> Result<(L2BlockInfo, SingleBatch), BatcherActorError> {
+        let block = self
+            .l2_provider
+            .get_block_by_number(number.into())
+            .full()
+            .await?
+            .ok_or(BatcherActorError::BlockNotFound(number))?
+            .into_consensus()
+            .map_transactions(|tx| tx.inner.inner.into_inner());
+        let info = L2BlockInfo::from_block_and_genesis(&block, &self.rollup_config.genesis)?;
+
+        let batch = SingleBatch {
+            parent_hash: info.block_info.parent_hash,
+            epoch_num: info.l1_origin.number,
+            epoch_hash: info.l1_origin.hash,
+            timestamp: info.block_info.timestamp,
+            transactions: block
+                .body
+                .transactions
+                .iter()
+                .filter(|tx| !tx.is_deposit())
+                .map(|tx| tx.encoded_2718().into())
+                .collect(),
+        };
+        Ok((info, batch))
+    }
+
+    /// Closes the open channel and submits its frames to L1.
+    async fn submit_channel(&mut self) -> Result<(), BatcherActorError> {
+        let Some(channel) = self.channel.close()? else {
+            return Ok(());
+        };
+        self.report_backlog(channel.data.len());
+
+        let inclusion_block = if self.use_blobs(&channel).await? {
+            let frames_per_tx = self.config.target_num_frames.clamp(1, da::MAX_BLOBS_PER_TX);
+            let mut inclusion_block = 0;
+            for frames in channel.frames(da::MAX_BLOB_FRAME_SIZE)?.chunks(frames_per_tx) {
+                let sidecar = da::sidecar(da::blobs(frames)?)?;
+                let request = TransactionRequest::default().with_blob_sidecar(sidecar);
+                inclusion_block = self.send(request, true).await?;
+            }
+            inclusion_block
+        } else {
+            let mut inclusion_block = 0;
+            for frame in channel.frames(da::MAX_CALLDATA_FRAME_SIZE)? {
+                let request = TransactionRequest::default().input(da::calldata(&frame).into());
+                inclusion_block = self.send(request, false).await?;
+            }
+            inclusion_block
+        };
+
+        info!(
+            target: "batcher",
+            last_block = channel.last_block.block_info.number,
+            size = channel.data.len(),
+            inclusion_block,
+            "Submitted channel"
+        );
+        kona_macros::set!(
+            gauge,
+            crate::Metrics::BATCHER_L2_BLOCK,
+            channel.last_block.block_info.number as f64
+        );
+        self.pending = Some((channel.last_block, inclusion_block));
+        self.report_backlog(0);
+        Ok(())
+    }
+
+    /// Returns `true` if the channel should be submitted in blobs.
+    async fn use_blobs(&self, channel: &ChannelData) -> Result<bool, BatcherActorError> {
+        match self.config.data_availability {
+            DataAvailabilityType::Calldata => Ok(false),
+            DataAvailabilityType::Blobs => Ok(true),
+            DataAvailabilityType::Auto => {
+                // Blobs are only derived from after Ecotone.
+                if !self.rollup_config.is_ecotone_active(channel.last_block.block_info.timestamp) {
+                    return Ok(false);
+                }
+                let base_fee = self.l1_provider.get_gas_price().await?;
+                let blob_base_fee = self.l1_provider.get_blob_base_fee().await?;
+                Ok(da::blobs_cheaper(channel.data.len(), base_fee, blob_base_fee))
+            }
+        }
+    }
+
+    /// Sends a batch transaction to the batch inbox and waits for its inclusion, resubmitting it
+    /// with bumped fees whenever it isn't included within the resubmission timeout.
+    ///
+    /// Returns the L1 block number the transaction was included in.
+    async fn send(
+        &self,
+        request: TransactionRequest,
+        blob: bool,
+    ) -> Result<u64, BatcherActorError> {
+        let from = self.config.signer.address();
+        let nonce = self.l1_provider.get_transaction_count(from).pending().await?;
+        let fees = self.l1_provider.estimate_eip1559_fees().await?;
+        let (mut max_fee, mut priority_fee) = (fees.max_fee_per_gas, fees.max_priority_fee_per_gas);
+        let mut blob_fee = if blob {
+            Some(self.l1_provider.get_blob_base_fee().await?.saturating_mul(2).max(1))
+        } else {
+            None
+        };
+        let request = request.from(from).to(self.rollup_config.batch_inbox_address).nonce(nonce);
+
+        for _ in 0..=MAX_RESUBMISSIONS {
+            let mut attempt =
+                request.clone().max_fee_per_gas(max_fee).max_priority_fee_per_gas(priority_fee);
+            if let Some(blob_fee) = blob_fee {
+                attempt = attempt.max_fee_per_blob_gas(blob_fee);
+            }
+
+            let pending = self.l1_provider.send_transaction(attempt).await?;
+            match pending.with_timeout(Some(self.config.resubmission_timeout)).get_receipt().await {
+                Ok(receipt) if !receipt.status() => {
+                    return Err(BatcherActorError::Reverted(receipt.transaction_hash));
+                }
+                Ok(receipt) => return Ok(receipt.block_number.unwrap_or_default()),
+                Err(PendingTransactionError::TxWatcher(WatchTxError::Timeout)) => {
+                    debug!(target: "batcher", nonce, "Batch transaction not included, bumping fees");
+                    // Replacements must bump fees by at least 10%, and blob fees by 100%.
+                    max_fee = bump_fee(max_fee);
+                    priority_fee = bump_fee(priority_fee);
+                    blob_fee = blob_fee.map(|fee| fee.saturating_mul(2));
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(BatcherActorError::NotIncluded(nonce))
+    }
+}
+
+/// Bumps a fee by the minimum replacement increase of 10%, rounded up.
+const fn bump_fee(fee: u128) -> u128 {
+    fee.saturating_add(fee.div_ceil(10))
+}
+
+#[async_trait]
+impl<P: Provider + 'static> NodeActor for BatcherActor<P> {
+    type Error = BatcherActorError;
+    type StartData = ();
+
+    async fn start(mut self, _: Self::StartData) -> Result<(), Self::Error> {
+        let engine_state = self.engine_state().await?;
+        let mut ticker = tokio::time::interval(self.config.poll_interval);
+
+        loop {
+            select! {
+                _ = self.cancellation.cancelled() => {
+                    info!(target: "batcher", "Received shutdown signal. Exiting batcher task.");
+                    return Ok(());
+                }
+                _ = ticker.tick() => match self.step(&engine_state).await {
+                    Ok(()) => {}
+                    Err(BatcherActorError::ChannelClosed) => {
+                        error!(target: "batcher", "Engine query channel closed unexpectedly");
+                        return Err(BatcherActorError::ChannelClosed);
+                    }
+                    Err(e) => {
+                        warn!(target: "batcher", error = %e, "Failed to submit batches, resetting to the safe head");
+                        kona_macros::inc!(counter, crate::Metrics::BATCHER_FAILURES);
+                        self.reset();
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<P: Provider> CancellableContext for BatcherActor<P> {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_fee() {
+        assert_eq!(bump_fee(0), 0);
+        assert_eq!(bump_fee(1), 2);
+        assert_eq!(bump_fee(100), 110);
+        assert_eq!(bump_fee(105), 116);
+        assert_eq!(bump_fee(u128::MAX), u128::MAX);
+    }
+}
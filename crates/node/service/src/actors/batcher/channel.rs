@@ -0,0 +1,295 @@
+//! Builds channels of L2 batches and splits them into frames.
+
+use crate::actors::batcher::BatcherActorError;
+use alloy_primitives::{Bytes, keccak256};
+use alloy_rlp::Encodable;
+use kona_comp::{ChannelCompressor, CompressorResult, CompressorWriter, VariantCompressor};
+use kona_genesis::RollupConfig;
+use kona_protocol::{
+    Batch, BatchReader, CHANNEL_ID_LENGTH, ChannelId, Frame, L2BlockInfo, SingleBatch,
+};
+use std::sync::Arc;
+
+/// The encoding overhead of a version 0 frame: the channel ID, frame number, data length and
+/// `is_last` flag.
+const FRAME_V0_OVERHEAD: usize = CHANNEL_ID_LENGTH + 2 + 4 + 1;
+
+/// The [`ChannelBuilder`] accumulates L2 blocks into a channel, until the channel is full or has
+/// been open for too long.
+///
+/// Batches are buffered uncompressed, and the compressed size of the channel is estimated with
+/// an approximate compression ratio. The channel is only compressed once, when it is closed.
+#[derive(Debug)]
+pub struct ChannelBuilder {
+    /// The rollup config.
+    rollup_config: Arc<RollupConfig>,
+    /// The target compressed size of a channel.
+    target_size: usize,
+    /// The approximate compression ratio of the channel data.
+    approx_compression_ratio: f64,
+    /// The maximum number of L1 blocks a channel may stay open for. Disabled if `0`.
+    max_channel_duration: u64,
+    /// The RLP encoded batches of the open channel.
+    rlp: Vec<u8>,
+    /// The first L2 block in the open channel.
+    first_block: Option<L2BlockInfo>,
+    /// The last L2 block in the open channel.
+    last_block: Option<L2BlockInfo>,
+    /// The L1 head block number when the open channel received its first block.
+    opened_at: u64,
+}
+
+/// The compressed data of a closed channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelData {
+    /// The channel ID.
+    pub id: ChannelId,
+    /// The compressed channel data.
+    pub data: Vec<u8>,
+    /// The last L2 block in the channel.
+    pub last_block: L2BlockInfo,
+}
+
+impl ChannelData {
+    /// Splits the channel data into frames with an encoded size of at most `max_frame_size`.
+    ///
+    /// Returns an error if the channel needs more frames than a frame number can address.
+    pub fn frames(&self, max_frame_size: usize) -> Result<Vec<Frame>, BatcherActorError> {
+        let chunk_size = max_frame_size.saturating_sub(FRAME_V0_OVERHEAD).max(1);
+        let chunks = self.data.chunks(chunk_size);
+        let count = chunks.len();
+        chunks
+            .enumerate()
+            .map(|(number, chunk)| {
+                let frame_number =
+                    u16::try_from(number).map_err(|_| BatcherActorError::TooManyFrames(count))?;
+                Ok(Frame::new(self.id, frame_number, chunk.to_vec(), number + 1 == count))
+            })
+            .collect()
+    }
+}
+
+impl ChannelBuilder {
+    /// Creates a new [`ChannelBuilder`].
+    pub const fn new(
+        rollup_config: Arc<RollupConfig>,
+        target_size: usize,
+        approx_compression_ratio: f64,
+        max_channel_duration: u64,
+    ) -> Self {
+        Self {
+            rollup_config,
+            target_size,
+            approx_compression_ratio,
+            max_channel_duration,
+            rlp: Vec::new(),
+            first_block: None,
+            last_block: None,
+            opened_at: 0,
+        }
+    }
+
+    /// Returns `true` if the open channel holds no blocks.
+    pub const fn is_empty(&self) -> bool {
+        self.first_block.is_none()
+    }
+
+    /// Returns the last L2 block in the open channel, if any.
+    pub const fn last_block(&self) -> Option<L2BlockInfo> {
+        self.last_block
+    }
+
+    /// Returns the estimated compressed size of the open channel.
+    pub fn estimated_size(&self) -> usize {
+        (self.rlp.len() as f64 * self.approx_compression_ratio) as usize
+    }
+
+    /// Adds an L2 block to the open channel. `l1_head` is the current L1 head block number.
+    pub fn add_block(&mut self, block: L2BlockInfo, batch: SingleBatch, l1_head: u64) {
+        let mut encoded = Vec::new();
+        // Encoding a single batch is infallible.
+        let _ = Batch::Single(batch).encode(&mut encoded);
+        Bytes::from(encoded).encode(&mut self.rlp);
+
+        if self.first_block.is_none() {
+            self.first_block = Some(block);
+            self.opened_at = l1_head;
+        }
+        self.last_block = Some(block);
+    }
+
+    /// Returns `true` if the estimated compressed size of the open channel has reached the target
+    /// size, or if its input is about to exceed the maximum RLP bytes per channel.
+    pub fn is_full(&self) -> bool {
+        let Some(first) = self.first_block else {
+            return false;
+        };
+        let max_rlp_bytes =
+            self.rollup_config.max_rlp_bytes_per_channel(first.block_info.timestamp) as usize;
+        self.estimated_size() >= self.target_size || self.rlp.len() >= max_rlp_bytes / 2
+    }
+
+    /// Returns `true` if the open channel should be submitted: it is either full, or has been
+    /// open for at least the maximum channel duration.
+    pub fn is_ready(&self, l1_head: u64) -> bool {
+        !self.is_empty() &&
+            (self.is_full() ||
+                (self.max_channel_duration > 0 &&
+                    l1_head >= self.opened_at + self.max_channel_duration))
+    }
+
+    /// Discards the open channel.
+    pub fn clear(&mut self) {
+        self.rlp.clear();
+        self.first_block = None;
+        self.last_block = None;
+    }
+
+    /// Compresses and closes the open channel, returning its data. Returns [`None`] if the
+    /// channel is empty.
+    ///
+    /// Channels of blocks after Fjord are compressed with brotli, and prefixed with the brotli
+    /// channel version byte. Earlier channels are compressed with zlib.
+    pub fn close(&mut self) -> CompressorResult<Option<ChannelData>> {
+        let (Some(first), Some(last)) = (self.first_block, self.last_block) else {
+            return Ok(None);
+        };
+
+        let mut compressor =
+            VariantCompressor::from_timestamp(&self.rollup_config, first.block_info.timestamp);
+        compressor.write(&self.rlp)?;
+        compressor.close()?;
+
+        let mut data = Vec::with_capacity(compressor.len() + 1);
+        if matches!(compressor, VariantCompressor::Brotli(_)) {
+            data.push(BatchReader::CHANNEL_VERSION_BROTLI);
+        }
+        data.extend_from_slice(&compressor.get_compressed());
+
+        // The channel ID only needs to be unique, so it is derived from the blocks it contains.
+        let mut id = ChannelId::default();
+        id.copy_from_slice(
+            &keccak256([first.block_info.hash, last.block_info.hash].concat())[..CHANNEL_ID_LENGTH],
+        );
+
+        self.clear();
+        Ok(Some(ChannelData { id, data, last_block: last }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use kona_genesis::HardForkConfig;
+    use kona_protocol::{BlockInfo, Channel};
+
+    fn block(number: u64, timestamp: u64) -> (L2BlockInfo, SingleBatch) {
+        let info = L2BlockInfo {
+            block_info: BlockInfo {
+                number,
+                hash: B256::with_last_byte(number as u8),
+                parent_hash: B256::with_last_byte(number as u8 - 1),
+                timestamp,
+            },
+            ..Default::default()
+        };
+        let batch = SingleBatch {
+            parent_hash: info.block_info.parent_hash,
+            epoch_num: 1,
+            epoch_hash: B256::repeat_byte(0x11),
+            timestamp,
+            transactions: vec![Bytes::from(vec![0x02; 100 * number as usize])],
+        };
+        (info, batch)
+    }
+
+    fn builder(config: RollupConfig) -> ChannelBuilder {
+        ChannelBuilder::new(Arc::new(config), 1_000, 0.6, 2)
+    }
+
+    fn decode_channel(config: &RollupConfig, frames: Vec<Frame>) -> Vec<Batch> {
+        let mut channel = Channel::new(frames[0].id, BlockInfo::default());
+        for frame in frames {
+            channel.add_frame(frame, BlockInfo::default()).unwrap();
+        }
+        assert!(channel.is_ready());
+
+        let mut reader = BatchReader::new(
+            channel.frame_data().unwrap(),
+            config.max_rlp_bytes_per_channel(0) as usize,
+        );
+        core::iter::from_fn(|| reader.next_batch(config)).collect()
+    }
+
+    #[test]
+    fn test_channel_roundtrip() {
+        for fjord_time in [None, Some(0)] {
+            let config = RollupConfig {
+                hardforks: HardForkConfig { fjord_time, ..Default::default() },
+                ..Default::default()
+            };
+            let mut builder = builder(config.clone());
+            let blocks = [block(1, 2), block(2, 4), block(3, 6)];
+            for (info, batch) in blocks.clone() {
+                builder.add_block(info, batch, 0);
+            }
+
+            let channel = builder.close().unwrap().unwrap();
+            assert!(builder.is_empty());
+            assert_eq!(channel.last_block, blocks[2].0);
+            assert_eq!(
+                channel.data.first() == Some(&BatchReader::CHANNEL_VERSION_BROTLI),
+                fjord_time.is_some()
+            );
+
+            let frames = channel.frames(FRAME_V0_OVERHEAD + 16).unwrap();
+            assert!(frames.len() > 1);
+            assert!(frames.iter().all(|frame| frame.encode().len() <= FRAME_V0_OVERHEAD + 16));
+            assert!(frames.iter().rev().skip(1).all(|frame| !frame.is_last));
+            assert!(frames.last().unwrap().is_last);
+
+            let batches = decode_channel(&config, frames);
+            let expected: Vec<_> = blocks.into_iter().map(|(_, b)| Batch::Single(b)).collect();
+            assert_eq!(batches, expected);
+        }
+    }
+
+    #[test]
+    fn test_channel_readiness() {
+        let mut builder = builder(RollupConfig::default());
+        assert!(!builder.is_ready(100));
+
+        let (info, batch) = block(1, 2);
+        builder.add_block(info, batch, 10);
+        assert!(!builder.is_full());
+        assert!(!builder.is_ready(11));
+        // The channel has been open for the maximum channel duration.
+        assert!(builder.is_ready(12));
+
+        // 2,000 bytes of input at a 0.6 compression ratio exceed the 1,000 byte target.
+        for number in 2..=5 {
+            let (info, batch) = block(number, number * 2);
+            builder.add_block(info, batch, 10);
+        }
+        assert!(builder.is_full());
+        assert!(builder.is_ready(10));
+    }
+
+    #[test]
+    fn test_channel_too_many_frames() {
+        let mut channel = ChannelData {
+            id: ChannelId::default(),
+            data: vec![0; u16::MAX as usize + 1],
+            last_block: L2BlockInfo::default(),
+        };
+        let frames = channel.frames(FRAME_V0_OVERHEAD + 1).unwrap();
+        assert_eq!(frames.last().unwrap().number, u16::MAX);
+
+        channel.data.push(0);
+        assert!(matches!(
+            channel.frames(FRAME_V0_OVERHEAD + 1),
+            Err(BatcherActorError::TooManyFrames(count)) if count == u16::MAX as usize + 2
+        ));
+    }
+}
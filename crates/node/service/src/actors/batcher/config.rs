@@ -0,0 +1,41 @@
+//! Configuration for the [`BatcherActor`].
+//!
+//! [`BatcherActor`]: super::BatcherActor
+
+use alloy_signer_local::PrivateKeySigner;
+use std::time::Duration;
+
+/// The data availability type used to submit batches to L1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum DataAvailabilityType {
+    /// Submit frames as transaction calldata.
+    #[default]
+    Calldata,
+    /// Submit frames as EIP-4844 blobs.
+    Blobs,
+    /// Submit each channel with whichever of calldata or blobs is cheaper at the current L1 fees.
+    Auto,
+}
+
+/// Configuration for the [`BatcherActor`].
+///
+/// [`BatcherActor`]: super::BatcherActor
+#[derive(Debug, Clone)]
+pub struct BatcherConfig {
+    /// The signer of the batch transactions.
+    pub signer: PrivateKeySigner,
+    /// The data availability type.
+    pub data_availability: DataAvailabilityType,
+    /// The maximum number of L1 blocks a channel may stay open for. Disabled if `0`.
+    pub max_channel_duration: u64,
+    /// The target number of frames per channel, and per blob transaction.
+    pub target_num_frames: usize,
+    /// The approximate compression ratio of the channel data, used to estimate the compressed
+    /// size of an open channel.
+    pub approx_compression_ratio: f64,
+    /// The interval at which the batcher checks for new L2 blocks.
+    pub poll_interval: Duration,
+    /// The time to wait for a transaction to be included before resubmitting it with higher fees.
+    pub resubmission_timeout: Duration,
+}
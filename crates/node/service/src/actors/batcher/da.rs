@@ -0,0 +1,89 @@
+//! Data availability helpers for batch transactions.
+
+use crate::actors::batcher::BatcherActorError;
+use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob, BlobTransactionSidecar};
+use alloy_primitives::Bytes;
+use kona_protocol::{BLOB_MAX_DATA_SIZE, DERIVATION_VERSION_0, Frame, encode_blob};
+
+/// The maximum encoded size of a frame submitted as calldata.
+///
+/// Matches the default of the reference batcher, and keeps batch transactions well below the
+/// transaction pool size limit.
+pub(super) const MAX_CALLDATA_FRAME_SIZE: usize = 120_000 - 1;
+
+/// The maximum encoded size of a frame submitted in a blob. One byte of the blob data is taken by
+/// the derivation version.
+pub(super) const MAX_BLOB_FRAME_SIZE: usize = BLOB_MAX_DATA_SIZE - 1;
+
+/// The maximum number of blobs in a single blob transaction.
+pub(super) const MAX_BLOBS_PER_TX: usize = 6;
+
+/// The intrinsic gas of a transaction.
+const TX_GAS: u128 = 21_000;
+
+/// The gas per byte of calldata under the EIP-7623 floor cost.
+const FLOOR_GAS_PER_BYTE: u128 = 40;
+
+/// Returns the transaction data carrying `frame`: the derivation version followed by the frame.
+pub(super) fn frame_data(frame: &Frame) -> Vec<u8> {
+    let mut data = vec![DERIVATION_VERSION_0];
+    data.extend_from_slice(&frame.encode());
+    data
+}
+
+/// Returns the calldata of a batch transaction carrying `frame`.
+pub(super) fn calldata(frame: &Frame) -> Bytes {
+    frame_data(frame).into()
+}
+
+/// Encodes each frame into its own blob.
+pub(super) fn blobs(frames: &[Frame]) -> Result<Vec<Blob>, BatcherActorError> {
+    frames.iter().map(|frame| Ok(encode_blob(&frame_data(frame))?)).collect()
+}
+
+/// Returns `true` if submitting `data_len` bytes of channel data in blobs is cheaper than
+/// submitting it as calldata, given the current L1 base fee and blob base fee.
+pub(super) fn blobs_cheaper(data_len: usize, base_fee: u128, blob_base_fee: u128) -> bool {
+    let calldata_frames = data_len.div_ceil(MAX_CALLDATA_FRAME_SIZE).max(1) as u128;
+    let calldata_cost =
+        (calldata_frames * TX_GAS + data_len as u128 * FLOOR_GAS_PER_BYTE) * base_fee;
+
+    let num_blobs = data_len.div_ceil(MAX_BLOB_FRAME_SIZE).max(1);
+    let blob_txs = num_blobs.div_ceil(MAX_BLOBS_PER_TX) as u128;
+    let blob_cost =
+        blob_txs * TX_GAS * base_fee + num_blobs as u128 * BYTES_PER_BLOB as u128 * blob_base_fee;
+
+    blob_cost < calldata_cost
+}
+
+/// Builds the sidecar of a blob transaction carrying `blobs`.
+pub(super) fn sidecar(blobs: Vec<Blob>) -> Result<BlobTransactionSidecar, BatcherActorError> {
+    BlobTransactionSidecar::try_from_blobs(blobs).map_err(|e| BatcherActorError::Kzg(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_protocol::decode_blob;
+
+    #[test]
+    fn test_frame_blob_roundtrip() {
+        let frame = Frame::new([0xAA; 16], 3, vec![0x42; 1_000], true);
+        let blobs = blobs(core::slice::from_ref(&frame)).unwrap();
+        assert_eq!(blobs.len(), 1);
+
+        let data = decode_blob(&blobs[0]).unwrap();
+        assert_eq!(data[0], DERIVATION_VERSION_0);
+        assert_eq!(Frame::decode(&data[1..]).unwrap().1, frame);
+    }
+
+    #[test]
+    fn test_blobs_cheaper() {
+        // Blob gas is far cheaper than calldata at equal base fees.
+        assert!(blobs_cheaper(100_000, 10, 10));
+        // A tiny channel doesn't justify paying for a whole blob.
+        assert!(!blobs_cheaper(100, 10, 10));
+        // Calldata wins once the blob base fee spikes.
+        assert!(!blobs_cheaper(100_000, 10, 1_000));
+    }
+}
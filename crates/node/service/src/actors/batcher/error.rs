@@ -0,0 +1,43 @@
+use alloy_primitives::B256;
+use alloy_provider::PendingTransactionError;
+use alloy_transport::{RpcError, TransportErrorKind};
+use kona_comp::CompressorError;
+use kona_protocol::{BlobEncodingError, FromBlockError};
+
+/// An error produced by the [`crate::BatcherActor`].
+#[derive(Debug, thiserror::Error)]
+pub enum BatcherActorError {
+    /// A channel was unexpectedly closed.
+    #[error("Channel closed unexpectedly")]
+    ChannelClosed,
+    /// An RPC request failed.
+    #[error(transparent)]
+    Rpc(#[from] RpcError<TransportErrorKind>),
+    /// A batch transaction could not be confirmed.
+    #[error(transparent)]
+    PendingTransaction(#[from] PendingTransactionError),
+    /// The L2 block was not found.
+    #[error("L2 block {0} not found")]
+    BlockNotFound(u64),
+    /// The L2 block info could not be derived from the L2 block.
+    #[error(transparent)]
+    BlockInfo(#[from] FromBlockError),
+    /// The channel data could not be compressed.
+    #[error("Failed to compress the channel: {0}")]
+    Compression(#[from] CompressorError),
+    /// A frame could not be encoded into a blob.
+    #[error(transparent)]
+    BlobEncoding(#[from] BlobEncodingError),
+    /// The KZG commitments of the blobs could not be computed.
+    #[error("Failed to compute the blob KZG commitments: {0}")]
+    Kzg(String),
+    /// The channel needs more frames than a frame number can address.
+    #[error("Channel needs {0} frames, more than a frame number can address")]
+    TooManyFrames(usize),
+    /// A batch transaction reverted.
+    #[error("Batch transaction {0} reverted")]
+    Reverted(B256),
+    /// A batch transaction was not included after the maximum number of resubmissions.
+    #[error("Batch transaction with nonce {0} was not included after resubmissions")]
+    NotIncluded(u64),
+}
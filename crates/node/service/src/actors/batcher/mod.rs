@@ -0,0 +1,15 @@
+//! The `BatcherActor` and its components.
+
+mod config;
+pub use config::{BatcherConfig, DataAvailabilityType};
+
+mod channel;
+pub use channel::{ChannelBuilder, ChannelData};
+
+mod da;
+
+mod actor;
+pub use actor::BatcherActor;
+
+mod error;
+pub use error::BatcherActorError;
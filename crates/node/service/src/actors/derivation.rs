@@ -1,16 +1,16 @@
 //! [NodeActor] implementation for the derivation sub-routine.
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, fmt, sync::Arc, time::Instant};
 
 use crate::{
-    InteropMode, Metrics, NodeActor,
-    actors::{CancellableContext, engine::ResetRequest},
+    DerivationTimings, InteropMode, MeteredSender, Metrics, NodeActor,
+    actors::{CancellableContext, L1BlockSource, L1BlockSourceBlocks, engine::ResetRequest},
 };
 use alloy_provider::RootProvider;
 use async_trait::async_trait;
 use kona_derive::{
-    ActivationSignal, Pipeline, PipelineError, PipelineErrorKind, ResetError, ResetSignal, Signal,
-    SignalReceiver, StepResult,
+    ActivationSignal, AltDaProvider, CommitmentType, Pipeline, PipelineError, PipelineErrorKind,
+    PipelineEvents, PipelineMemory, ResetError, ResetSignal, Signal, SignalReceiver, StepResult,
 };
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
@@ -18,6 +18,7 @@ use kona_providers_alloy::{
     AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider,
     OnlinePipeline,
 };
+use kona_rpc::{DerivationHaltSwitch, RuntimeFlag, RuntimeFlags};
 use op_alloy_network::Optimism;
 use thiserror::Error;
 use tokio::{
@@ -25,6 +26,7 @@ use tokio::{
     sync::{mpsc, oneshot, watch},
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+use tracing::Span;
 
 /// The [NodeActor] for the derivation sub-routine.
 ///
@@ -81,6 +83,27 @@ where
     /// A flag indicating whether or not derivation is waiting for a signal. When waiting for a
     /// signal, derivation cannot process any incoming events.
     pub waiting_for_signal: bool,
+    /// The L1 block that attributes are currently being derived from, along with its [`Span`].
+    /// All attributes derived from the same L1 block share this span as their parent.
+    pub origin_span: Option<(BlockInfo, Span)>,
+    /// The instants at which L1 heads were first observed, keyed by L1 block number. Heads below
+    /// the current derivation origin are pruned.
+    pub l1_observed: BTreeMap<u64, Instant>,
+    /// The first L1 head observed. L1 blocks up to it were already known when the node started,
+    /// so their observation instant is unknown.
+    pub first_l1_head: Option<u64>,
+}
+
+/// Payload attributes produced by the [DerivationActor], along with the [`Span`] that traces
+/// their processing from the L1 block they were derived from through to the engine.
+#[derive(Debug, Clone)]
+pub struct DerivedAttributes {
+    /// The derived [`OpAttributesWithParent`].
+    pub attributes: OpAttributesWithParent,
+    /// The span to execute the attributes in.
+    pub span: Span,
+    /// The instants at which the attributes passed each hop of the derivation actor.
+    pub timings: DerivationTimings,
 }
 
 /// The size of the cache used in the derivation pipeline's providers.
@@ -96,6 +119,36 @@ pub trait PipelineBuilder: Send + Sync + 'static {
     async fn build(self) -> DerivationState<Self::Pipeline>;
 }
 
+/// Builds an [`AltDaProvider`] for each derivation pipeline.
+type AltDaProviderFactory = Arc<dyn Fn() -> Box<dyn AltDaProvider> + Send + Sync>;
+
+/// The [`AltDaProvider`]s the derivation pipeline resolves alt-DA commitments with, by
+/// [`CommitmentType`].
+#[derive(Clone, Default)]
+pub struct AltDaProviders(BTreeMap<CommitmentType, AltDaProviderFactory>);
+
+impl fmt::Debug for AltDaProviders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+impl AltDaProviders {
+    /// Registers the provider of the input data of the given commitment type, replacing the
+    /// provider previously registered for it. Each pipeline gets its own clone of the provider.
+    pub fn register<P>(&mut self, commitment_type: CommitmentType, provider: P)
+    where
+        P: AltDaProvider + Clone + 'static,
+    {
+        self.0.insert(commitment_type, Arc::new(move || Box::new(provider.clone())));
+    }
+
+    /// Builds the registered providers.
+    pub fn build(&self) -> BTreeMap<CommitmentType, Box<dyn AltDaProvider>> {
+        self.0.iter().map(|(commitment_type, factory)| (*commitment_type, factory())).collect()
+    }
+}
+
 /// The configuration necessary to build the derivation actor.
 #[derive(Debug)]
 pub struct DerivationBuilder {
@@ -115,6 +168,15 @@ pub struct DerivationBuilder {
     pub l1_config: Arc<L1ChainConfig>,
     /// The interop mode.
     pub interop_mode: InteropMode,
+    /// The handle the derivation pipeline emits its events to.
+    pub pipeline_events: PipelineEvents,
+    /// The handle the buffers of the derivation pipeline are accounted against.
+    pub pipeline_memory: PipelineMemory,
+    /// The providers alt-DA commitments are resolved with.
+    pub alt_da_providers: AltDaProviders,
+    /// The [`L1BlockSource`] of the node, if any, queried for the L1 blocks, headers and receipts
+    /// read by the pipeline before falling back to the L1 provider.
+    pub l1_block_source: Option<Arc<dyn L1BlockSource>>,
 }
 
 #[async_trait]
@@ -123,11 +185,15 @@ impl PipelineBuilder for DerivationBuilder {
 
     async fn build(self) -> DerivationState<OnlinePipeline> {
         // Create the caching L1/L2 EL providers for derivation.
-        let l1_derivation_provider = AlloyChainProvider::new_with_trust(
+        let mut l1_derivation_provider = AlloyChainProvider::new_with_trust(
             self.l1_provider.clone(),
             DERIVATION_PROVIDER_CACHE_SIZE,
             self.l1_trust_rpc,
         );
+        if let Some(source) = &self.l1_block_source {
+            l1_derivation_provider = l1_derivation_provider
+                .with_prefetched_blocks(Arc::new(L1BlockSourceBlocks(Arc::clone(source))));
+        }
         let l2_derivation_provider = AlloyL2ChainProvider::new_with_trust(
             self.l2_provider.clone(),
             self.rollup_config.clone(),
@@ -142,6 +208,9 @@ impl PipelineBuilder for DerivationBuilder {
                 OnlineBlobProvider::init(self.l1_beacon.clone()).await,
                 l1_derivation_provider,
                 l2_derivation_provider,
+                self.alt_da_providers.build(),
+                self.pipeline_events.clone(),
+                self.pipeline_memory.clone(),
             ),
             InteropMode::Indexed => OnlinePipeline::new_indexed(
                 self.rollup_config.clone(),
@@ -149,6 +218,9 @@ impl PipelineBuilder for DerivationBuilder {
                 OnlineBlobProvider::init(self.l1_beacon.clone()).await,
                 l1_derivation_provider,
                 l2_derivation_provider,
+                self.alt_da_providers.build(),
+                self.pipeline_events.clone(),
+                self.pipeline_memory.clone(),
             ),
         };
 
@@ -179,11 +251,22 @@ pub struct DerivationInboundChannels {
 pub struct DerivationContext {
     /// The cancellation token, shared between all tasks.
     pub cancellation: CancellationToken,
-    /// Sends the derived [`OpAttributesWithParent`]s produced by the actor.
-    pub derived_attributes_tx: mpsc::Sender<OpAttributesWithParent>,
+    /// Sends the [`DerivedAttributes`] produced by the actor.
+    pub derived_attributes_tx: MeteredSender<DerivedAttributes>,
     /// The reset request sender, used to handle [`PipelineErrorKind::Reset`] events and forward
     /// them to the engine.
     pub reset_request_tx: mpsc::Sender<ResetRequest>,
+    /// Sends the derived payload attributes to the [`ArchiveActor`], if the archive is enabled.
+    /// Derivation waits for the archive to keep up, so that no attributes go unarchived.
+    ///
+    /// [`ArchiveActor`]: crate::ArchiveActor
+    pub archive_tx: Option<mpsc::Sender<Arc<OpAttributesWithParent>>>,
+    /// The registry of the runtime flags of the node. Derivation is paused while
+    /// [`RuntimeFlag::PauseDerivation`] is set.
+    pub runtime_flags: RuntimeFlags,
+    /// The switch the engine halts derivation with. Derivation stays paused until the halt is
+    /// acknowledged.
+    pub derivation_halt: DerivationHaltSwitch,
 }
 
 impl CancellableContext for DerivationContext {
@@ -198,7 +281,69 @@ where
 {
     /// Creates a new instance of the [DerivationState].
     pub const fn new(pipeline: P) -> Self {
-        Self { pipeline, derivation_idle: true, waiting_for_signal: false }
+        Self {
+            pipeline,
+            derivation_idle: true,
+            waiting_for_signal: false,
+            origin_span: None,
+            l1_observed: BTreeMap::new(),
+            first_l1_head: None,
+        }
+    }
+
+    /// Records the instant at which the given L1 head was first observed.
+    fn observe_l1_head(&mut self, head: BlockInfo) {
+        if self.first_l1_head.get_or_insert(head.number) == &head.number {
+            return;
+        }
+        self.l1_observed.entry(head.number).or_insert_with(Instant::now);
+    }
+
+    /// Returns the [`DerivationTimings`] of attributes decoded now, pruning the observations of
+    /// the L1 heads below their origin.
+    fn attributes_timings(&mut self, attributes: &OpAttributesWithParent) -> DerivationTimings {
+        let decoded = Instant::now();
+        let Some(origin) = attributes.derived_from else {
+            return DerivationTimings { l1_observed: None, decoded };
+        };
+
+        self.l1_observed = self.l1_observed.split_off(&origin.number);
+        // The origin was known by the time the first head at or above it was observed.
+        let l1_observed = self
+            .first_l1_head
+            .is_some_and(|first| origin.number > first)
+            .then(|| self.l1_observed.values().next().copied())
+            .flatten();
+        DerivationTimings { l1_observed, decoded }
+    }
+
+    /// Returns the [`Span`] for the given attributes, as a child of the span of the L1 block they
+    /// were derived from. A new L1 block span is opened whenever the derivation origin changes.
+    fn attributes_span(&mut self, attributes: &OpAttributesWithParent) -> Span {
+        let Some(origin) = attributes.derived_from else {
+            return Span::none();
+        };
+
+        let origin_span = match &self.origin_span {
+            Some((block, span)) if *block == origin => span.clone(),
+            _ => {
+                let span = info_span!(
+                    target: "derivation",
+                    "l1_block",
+                    l1_number = origin.number,
+                    l1_hash = %origin.hash,
+                );
+                self.origin_span = Some((origin, span.clone()));
+                span
+            }
+        };
+
+        info_span!(
+            target: "derivation",
+            parent: &origin_span,
+            "derived_attributes",
+            l2_number = attributes.block_number(),
+        )
     }
 
     /// Handles a [`Signal`] received over the derivation signal receiver channel.
@@ -291,7 +436,7 @@ where
                                     .rollup_config()
                                     .is_interop_active(l2_safe_head.block_info.timestamp)
                                 {
-                                    reset_request_tx.send(ResetRequest{result_tx: None}).await.map_err(|e| {
+                                    reset_request_tx.send(ResetRequest{result_tx: None, l2_safe_head: None, l1_origin: None}).await.map_err(|e| {
                                         error!(target: "derivation", ?e, "Failed to send reset request");
                                         DerivationError::Sender(Box::new(e))
                                     })?;
@@ -333,8 +478,9 @@ where
         msg: InboundDerivationMessage,
         engine_l2_safe_head: &mut watch::Receiver<L2BlockInfo>,
         el_sync_complete_rx: &oneshot::Receiver<()>,
-        derived_attributes_tx: &mpsc::Sender<OpAttributesWithParent>,
+        derived_attributes_tx: &MeteredSender<DerivedAttributes>,
         reset_request_tx: &mpsc::Sender<ResetRequest>,
+        archive_tx: Option<&mpsc::Sender<Arc<OpAttributesWithParent>>>,
     ) -> Result<(), DerivationError> {
         // Only attempt derivation once the engine finishes syncing.
         if !el_sync_complete_rx.is_terminated() {
@@ -384,15 +530,26 @@ where
                 }
             };
 
+        let timings = self.attributes_timings(&payload_attrs);
+
         // Mark derivation as busy.
         self.derivation_idle = false;
 
         // Mark the L2 safe head as seen.
         engine_l2_safe_head.borrow_and_update();
 
+        // Archive the payload attributes before they are processed.
+        if let Some(archive_tx) = archive_tx {
+            archive_tx
+                .send(Arc::new(payload_attrs.clone()))
+                .await
+                .map_err(|e| DerivationError::Sender(Box::new(e)))?;
+        }
+
         // Send payload attributes out for processing.
+        let span = self.attributes_span(&payload_attrs);
         derived_attributes_tx
-            .send(payload_attrs)
+            .send(DerivedAttributes { attributes: payload_attrs, span, timings })
             .await
             .map_err(|e| DerivationError::Sender(Box::new(e)))?;
 
@@ -444,12 +601,28 @@ where
         DerivationContext {
             derived_attributes_tx,
             reset_request_tx,
+            archive_tx,
+            runtime_flags,
+            derivation_halt,
             cancellation,
         }: Self::StartData,
     ) -> Result<(), Self::Error> {
         let mut state = self.state.build().await;
 
+        // While the engine falls behind on the derived attributes, head updates are left pending,
+        // and are only processed once it catches up.
+        let mut backpressure = derived_attributes_tx.monitor().subscribe_backpressure();
+
+        // While derivation is paused by a runtime flag, or halted by the engine, head updates are
+        // left pending as well.
+        let mut runtime_flags_rx = runtime_flags.subscribe();
+        let mut derivation_halt_rx = derivation_halt.subscribe();
+
         loop {
+            let backpressured = *backpressure.borrow();
+            let flag_paused = runtime_flags.is_enabled(RuntimeFlag::PauseDerivation);
+            let halted = derivation_halt.is_halted();
+            let paused = flag_paused || halted;
             select! {
                 biased;
 
@@ -473,7 +646,38 @@ where
                     state.signal(signal).await;
                     state.waiting_for_signal = false;
                 }
-                msg = self.l1_head_updates.changed() => {
+                Ok(()) = backpressure.changed() => {
+                    if *backpressure.borrow_and_update() {
+                        info!(target: "derivation", "Engine is falling behind, pausing derivation");
+                    } else {
+                        info!(target: "derivation", "Engine caught up, resuming derivation");
+                    }
+                }
+                Ok(()) = runtime_flags_rx.changed() => {
+                    runtime_flags_rx.borrow_and_update();
+                    let now_paused = runtime_flags.is_enabled(RuntimeFlag::PauseDerivation);
+                    if now_paused && !flag_paused {
+                        warn!(target: "derivation", "Derivation paused by runtime flag");
+                    } else if !now_paused && flag_paused {
+                        info!(target: "derivation", "Derivation resumed by runtime flag");
+                    }
+                }
+                Ok(()) = derivation_halt_rx.changed() => {
+                    match *derivation_halt_rx.borrow_and_update() {
+                        Some(halt) if !halted => {
+                            error!(
+                                target: "derivation",
+                                reason = %halt.reason,
+                                "Derivation halted by the engine"
+                            );
+                        }
+                        None if halted => {
+                            info!(target: "derivation", "Derivation halt acknowledged, resuming");
+                        }
+                        _ => {}
+                    }
+                }
+                msg = self.l1_head_updates.changed(), if !backpressured && !paused => {
                     if let Err(err) = msg {
                         error!(
                             target: "derivation",
@@ -483,15 +687,18 @@ where
                         return Ok(());
                     }
 
-                    state.process(InboundDerivationMessage::NewDataAvailable, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx).await?;
+                    if let Some(head) = *self.l1_head_updates.borrow() {
+                        state.observe_l1_head(head);
+                    }
+                    state.process(InboundDerivationMessage::NewDataAvailable, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx, archive_tx.as_ref()).await?;
                 }
-                _ = self.engine_l2_safe_head.changed() => {
-                    state.process(InboundDerivationMessage::SafeHeadUpdated, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx).await?;
+                _ = self.engine_l2_safe_head.changed(), if !backpressured && !paused => {
+                    state.process(InboundDerivationMessage::SafeHeadUpdated, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx, archive_tx.as_ref()).await?;
                 }
-                _ = &mut self.el_sync_complete_rx, if !self.el_sync_complete_rx.is_terminated() => {
+                _ = &mut self.el_sync_complete_rx, if !self.el_sync_complete_rx.is_terminated() && !paused => {
                     info!(target: "derivation", "Engine finished syncing, starting derivation.");
                     // Optimistically process the first message.
-                    state.process(InboundDerivationMessage::NewDataAvailable, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx).await?;
+                    state.process(InboundDerivationMessage::NewDataAvailable, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx, archive_tx.as_ref()).await?;
                 }
             }
         }
@@ -519,7 +726,7 @@ pub enum DerivationError {
     Yield,
     /// An error originating from the broadcast sender.
     #[error("Failed to send event to broadcast sender: {0}")]
-    Sender(Box<dyn std::error::Error>),
+    Sender(Box<dyn std::error::Error + Send + Sync>),
     /// An error from the signal receiver.
     #[error("Failed to receive signal")]
     SignalReceiveFailed,
@@ -1,26 +1,42 @@
 //! The [`EngineActor`].
 
-use super::{BlockEngineResult, EngineError, L2Finalizer};
-use crate::{BlockEngineError, NodeActor, NodeMode, actors::CancellableContext};
+use super::{
+    AutoSyncConfig, BlockEngineResult, CheckpointConfig, DerivationHaltConfig,
+    DerivationLatencyTracker, EngineActorClient, EngineError, L2Finalizer,
+    halt::DerivationHaltDetector,
+};
+use crate::{
+    BlockEngineError, ChannelAlarms, DerivationCheckpoint, DerivedAttributes, MeteredReceiver,
+    MeteredSender, NodeActor, NodeCounter, NodeDb, NodeEvent, NodeMode, QueueMonitor,
+    SafeHeadRecord, Watermarks,
+    actors::{CancellableContext, extension::publish},
+    metered_channel, watermark_channel,
+};
 use alloy_provider::RootProvider;
 use alloy_rpc_types_engine::{JwtSecret, PayloadId};
 use async_trait::async_trait;
 use futures::{FutureExt, future::OptionFuture};
 use kona_derive::{ResetSignal, Signal};
 use kona_engine::{
-    BuildTask, ConsolidateTask, Engine, EngineClient, EngineClientBuilder,
-    EngineClientBuilderError, EngineQueries, EngineState as InnerEngineState, EngineTask,
-    EngineTaskError, EngineTaskErrorSeverity, InsertTask, OpEngineClient, RollupBoostServer,
-    RollupBoostServerArgs, SealTask, SealTaskError,
+    BuildTask, ConsolidateTask, ConsolidationMismatch, Engine, EngineClient, EngineClientBuilder,
+    EngineClientBuilderError, EngineQueries, EngineResetError, EngineState as InnerEngineState,
+    EngineSyncState, EngineTask, EngineTaskError, EngineTaskErrorSeverity, EngineTaskErrors,
+    ForkchoiceBatching, InsertTask, OpEngineClient, RollupBoostServer, RollupBoostServerArgs,
+    SealTask, SealTaskError,
 };
 use kona_genesis::RollupConfig;
-use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
-use kona_rpc::{RollupBoostAdminQuery, RollupBoostHealthQuery};
+use kona_protocol::{
+    BlockInfo, L2BlockInfo, OpAttributesWithParent, SyncModeSelection, SyncStrategy,
+};
+use kona_rpc::{
+    DerivationHaltSwitch, DerivationLatency, EngineAdminQuery, RollupBoostAdminQuery,
+    RollupBoostHealth, RollupBoostHealthQuery, RuntimeFlag, RuntimeFlags,
+};
 use op_alloy_network::Optimism;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
 use std::{fmt::Debug, sync::Arc, time::Duration};
 use tokio::{
-    sync::{mpsc, oneshot, watch},
+    sync::{broadcast, mpsc, oneshot, watch},
     task::JoinHandle,
 };
 use tokio_util::{
@@ -46,6 +62,12 @@ pub struct BuildRequest {
 pub struct ResetRequest {
     /// response will be sent to this channel, if `Some`.
     pub result_tx: Option<mpsc::Sender<BlockEngineResult<()>>>,
+    /// The L2 safe head to rewind the engine to, instead of the one found by the reset. Only
+    /// used if the L1 origin is pinned as well.
+    pub l2_safe_head: Option<L2BlockInfo>,
+    /// The L1 origin to restart derivation from, instead of the one found by the reset. Only used
+    /// if the L2 safe head is pinned as well.
+    pub l1_origin: Option<BlockInfo>,
 }
 
 /// A request to seal and canonicalize a payload.
@@ -63,10 +85,15 @@ pub struct SealRequest {
 /// The [`EngineActor`] is responsible for managing the operations sent to the execution layer's
 /// Engine API. To accomplish this, it uses the [`Engine`] task queue to order Engine API
 /// interactions based off of the [`Ord`] implementation of [`EngineTask`].
+///
+/// The actor drives the execution layer through an [`EngineActorClient`], an [`OpEngineClient`]
+/// built from the [`EngineConfig`] by default.
 #[derive(Debug)]
-pub struct EngineActor {
-    /// A channel to receive [`OpAttributesWithParent`] from the derivation actor.
-    attributes_rx: mpsc::Receiver<OpAttributesWithParent>,
+pub struct EngineActor<
+    EngineClient_: EngineActorClient = OpEngineClient<RootProvider, RootProvider<Optimism>>,
+> {
+    /// A channel to receive [`DerivedAttributes`] from the derivation actor.
+    attributes_rx: MeteredReceiver<DerivedAttributes>,
     /// The [`EngineConfig`] used to build the actor.
     builder: EngineConfig,
     /// A channel to receive build requests.
@@ -78,10 +105,14 @@ pub struct EngineActor {
     build_request_rx: Option<mpsc::Receiver<BuildRequest>>,
     /// The [`L2Finalizer`], used to finalize L2 blocks.
     finalizer: L2Finalizer,
+    /// The [`DerivationLatencyTracker`], tracking derived attributes until they are safe.
+    latency: DerivationLatencyTracker,
     /// Handler for inbound queries to the engine.
     inbound_queries: mpsc::Receiver<EngineQueries>,
     /// A channel to receive reset requests.
     reset_request_rx: mpsc::Receiver<ResetRequest>,
+    /// A channel to receive admin queries resetting the derivation pipeline.
+    engine_admin_query_rx: mpsc::Receiver<EngineAdminQuery>,
     /// Shared admin query handle (from rollup-boost), exposed for RPC wiring.
     /// Only set when rollup boost is enabled.
     pub rollup_boost_admin_query_rx: mpsc::Receiver<RollupBoostAdminQuery>,
@@ -95,19 +126,26 @@ pub struct EngineActor {
     /// mode.
     seal_request_rx: Option<mpsc::Receiver<SealRequest>>,
     /// A channel to receive [`OpExecutionPayloadEnvelope`] from the network actor.
-    unsafe_block_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
+    unsafe_block_rx: MeteredReceiver<OpExecutionPayloadEnvelope>,
     /// A channel to use to relay the current unsafe head.
     /// ## Note
     /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
     /// mode.
     unsafe_head_tx: Option<watch::Sender<L2BlockInfo>>,
+    /// The engine client to use instead of building one from the [`EngineConfig`], if any.
+    client: Option<Arc<EngineClient_>>,
+    /// The event bus of the node, on which the engine state, the derived attributes and the
+    /// unsafe payloads are published, if any extension subscribes to it.
+    node_events: Option<broadcast::Sender<NodeEvent>>,
+    /// A channel to relay the current sync strategy.
+    sync_mode_tx: watch::Sender<Option<SyncModeSelection>>,
 }
 
 /// The outbound data for the [`EngineActor`].
 #[derive(Debug)]
 pub struct EngineInboundData {
-    /// A channel to send [`OpAttributesWithParent`] to the engine actor.
-    pub attributes_tx: mpsc::Sender<OpAttributesWithParent>,
+    /// A channel to send [`DerivedAttributes`] to the engine actor.
+    pub attributes_tx: MeteredSender<DerivedAttributes>,
     /// A channel to use to send [`BuildRequest`] payloads to the engine actor.
     ///
     /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
@@ -119,6 +157,8 @@ pub struct EngineInboundData {
     pub inbound_queries_tx: mpsc::Sender<EngineQueries>,
     /// A channel to send reset requests.
     pub reset_request_tx: mpsc::Sender<ResetRequest>,
+    /// A channel to send admin queries resetting the derivation pipeline to the engine actor.
+    pub engine_admin_query_tx: mpsc::Sender<EngineAdminQuery>,
     /// A channel to send rollup boost admin queries to the engine actor.
     pub rollup_boost_admin_query_tx: mpsc::Sender<RollupBoostAdminQuery>,
     /// A channel to send rollup boost health queries to the engine actor.
@@ -135,12 +175,17 @@ pub struct EngineInboundData {
     /// actor through that channel. Instead, it should use the `build_request_tx` channel to
     /// trigger [`BuildTask`] tasks which should insert the block newly built to the engine
     /// state upon completion.
-    pub unsafe_block_tx: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    pub unsafe_block_tx: MeteredSender<OpExecutionPayloadEnvelope>,
     /// A receiver to use to view the latest unsafe head [`L2BlockInfo`] and await its changes.
     ///
     /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
     /// mode.
     pub unsafe_head_rx: Option<watch::Receiver<L2BlockInfo>>,
+    /// A receiver of the latest [`DerivationLatency`] summary.
+    pub derivation_latency_rx: watch::Receiver<DerivationLatency>,
+    /// A receiver of the current sync strategy of the engine actor, selected on startup and
+    /// updated when EL sync is restarted.
+    pub sync_mode_rx: watch::Receiver<Option<SyncModeSelection>>,
 }
 
 /// Configuration for the Engine Actor.
@@ -173,18 +218,46 @@ pub struct EngineConfig {
 
     /// The rollup boost arguments.
     pub rollup_boost: RollupBoostServerArgs,
+
+    /// The thresholds past which the engine's inbound channels and task queue raise alarms.
+    pub channel_alarms: ChannelAlarms,
+
+    /// The watermarks of the channel of derived attributes. Derivation pauses once the engine
+    /// falls behind by the high watermark, and resumes once it catches up to the low watermark.
+    pub attributes_watermarks: Watermarks,
+
+    /// The trusted rollup node to checkpoint sync from. The node syncs from the L2 genesis, or
+    /// from the execution layer's finalized block, if unset.
+    pub checkpoint: Option<CheckpointConfig>,
+
+    /// The configuration of the automatic selection between EL and CL sync on startup. If set,
+    /// the node only syncs to the checkpoint if the selection picks EL sync.
+    pub auto_sync: Option<AutoSyncConfig>,
+
+    /// The number of L2 blocks a gossiped unsafe payload may be ahead of the safe head before the
+    /// engine restarts EL sync towards it, instead of deriving the gap from L1. Disabled if unset.
+    pub unsafe_gap_limit: Option<u64>,
+
+    /// The batching of the forkchoice updates of consecutive inserted or consolidated blocks. A
+    /// forkchoice update is sent for every block if unset.
+    pub forkchoice_batching: Option<ForkchoiceBatching>,
+
+    /// The thresholds of invalid derived payloads past which the engine halts derivation. The
+    /// engine never halts derivation if unset.
+    pub derivation_halt: Option<DerivationHaltConfig>,
+
+    /// The database recording the safe head at each L1 block, the derivation checkpoints and the
+    /// unsafe payloads. Nothing is recorded if unset.
+    pub db: Option<NodeDb>,
 }
 
 impl EngineConfig {
-    /// Launches the [`Engine`]. Returns the [`Engine`] and a channel to receive engine state
-    /// updates.
-    fn build_state(
-        self,
-    ) -> Result<
-        EngineActorState<OpEngineClient<RootProvider, RootProvider<Optimism>>>,
-        EngineClientBuilderError,
-    > {
-        let client = EngineClientBuilder {
+    /// Builds the [`OpEngineClient`] described by the configuration.
+    pub fn build_client(
+        &self,
+    ) -> Result<OpEngineClient<RootProvider, RootProvider<Optimism>>, EngineClientBuilderError>
+    {
+        EngineClientBuilder {
             builder: self.builder_url.clone(),
             builder_jwt: self.builder_jwt_secret,
             builder_timeout: self.builder_timeout,
@@ -195,17 +268,63 @@ impl EngineConfig {
             cfg: self.config.clone(),
             rollup_boost: self.rollup_boost.clone(),
         }
-        .build()?
-        .into();
+        .build()
+    }
+
+    /// Launches the [`Engine`]. Returns the [`Engine`] and a channel to receive engine state
+    /// updates.
+    ///
+    /// If no `client` is given, the engine client is built from the configuration.
+    fn build_state<EngineClient_: EngineActorClient>(
+        self,
+        client: Option<Arc<EngineClient_>>,
+        sync_mode_tx: watch::Sender<Option<SyncModeSelection>>,
+        runtime_flags: RuntimeFlags,
+        derivation_halt: DerivationHaltSwitch,
+    ) -> Result<EngineActorState<EngineClient_>, EngineError> {
+        let client = match client {
+            Some(client) => client,
+            None => EngineClient_::build(&self)?.into(),
+        };
 
         let state = InnerEngineState::default();
         let (engine_state_send, _) = tokio::sync::watch::channel(state);
         let (engine_queue_length_send, _) = tokio::sync::watch::channel(0);
+        let mut engine = Engine::new(state, engine_state_send, engine_queue_length_send);
+        if let Some(batching) = self.forkchoice_batching {
+            engine = engine.with_forkchoice_batching(batching);
+        }
+
+        // Restore the last consolidation mismatch, so that it outlives restarts.
+        let last_mismatch = self.db.as_ref().and_then(|db| {
+            db.last_consolidation_mismatch()
+                .inspect_err(|err| {
+                    warn!(target: "engine", ?err, "Failed to read the last consolidation mismatch")
+                })
+                .ok()
+                .flatten()
+        });
+        if let Some(mismatch) = last_mismatch {
+            engine = engine.with_consolidation_mismatch(mismatch);
+        }
+        let consolidation_mismatch_rx = engine.consolidation_mismatch_subscribe();
+        let derivation_halt =
+            self.derivation_halt.map(|config| DerivationHaltDetector::new(config, derivation_halt));
 
         Ok(EngineActorState {
             rollup: self.config,
             client,
-            engine: Engine::new(state, engine_state_send, engine_queue_length_send),
+            engine,
+            checkpoint: None,
+            unsafe_gap_limit: self.unsafe_gap_limit,
+            el_sync_fallback: false,
+            deferred_consolidations: Vec::new(),
+            sync_mode_tx,
+            db: self.db,
+            last_safe_head_record: None,
+            consolidation_mismatch_rx,
+            runtime_flags,
+            derivation_halt,
         })
     }
 }
@@ -215,10 +334,35 @@ impl EngineConfig {
 pub(super) struct EngineActorState<EngineClient_: EngineClient> {
     /// The [`RollupConfig`] used to build tasks.
     pub(super) rollup: Arc<RollupConfig>,
-    /// An [`OpEngineClient`] used for creating engine tasks.
+    /// The engine client used for creating engine tasks.
     pub(super) client: Arc<EngineClient_>,
     /// The [`Engine`] task queue.
     pub(super) engine: Engine<EngineClient_>,
+    /// The checkpoint the execution layer is syncing to, if the node is checkpoint syncing. Taken
+    /// once the execution layer has finished syncing.
+    pub(super) checkpoint: Option<L2BlockInfo>,
+    /// The number of L2 blocks an unsafe payload may be ahead of the safe head before EL sync is
+    /// restarted towards it.
+    unsafe_gap_limit: Option<u64>,
+    /// Whether EL sync was restarted because the unsafe head got too far ahead of the safe head.
+    /// Cleared once the execution layer has finished syncing.
+    pub(super) el_sync_fallback: bool,
+    /// The consolidations of derived attributes deferred while EL sync is restarted, enqueued
+    /// once the execution layer holds the unsafe blocks they consolidate.
+    deferred_consolidations: Vec<ConsolidateTask<EngineClient_>>,
+    /// A channel to relay the current sync strategy.
+    sync_mode_tx: watch::Sender<Option<SyncModeSelection>>,
+    /// The node database, if enabled.
+    pub(super) db: Option<NodeDb>,
+    /// The last safe head recorded in the node database.
+    last_safe_head_record: Option<SafeHeadRecord>,
+    /// A receiver of the last consolidation mismatch of the [`Engine`], recorded in the node
+    /// database when it changes.
+    consolidation_mismatch_rx: watch::Receiver<Option<ConsolidationMismatch>>,
+    /// The registry of the runtime flags of the node.
+    runtime_flags: RuntimeFlags,
+    /// Halts derivation once too many derived payloads are rejected as invalid, if configured.
+    derivation_halt: Option<DerivationHaltDetector>,
 }
 
 /// The communication context used by the engine actor.
@@ -235,6 +379,11 @@ pub struct EngineContext {
     pub sync_complete_tx: oneshot::Sender<()>,
     /// A way for the engine actor to send a [`Signal`] back to the derivation actor.
     pub derivation_signal_tx: mpsc::Sender<Signal>,
+    /// The registry of the runtime flags of the node.
+    pub runtime_flags: RuntimeFlags,
+    /// The switch the engine halts derivation with, once too many derived payloads are rejected
+    /// as invalid.
+    pub derivation_halt: DerivationHaltSwitch,
 }
 
 impl CancellableContext for EngineContext {
@@ -252,14 +401,17 @@ struct SequencerChannels {
     unsafe_head_tx: Option<watch::Sender<L2BlockInfo>>,
 }
 
-impl EngineActor {
+impl<EngineClient_: EngineActorClient> EngineActor<EngineClient_> {
     /// Constructs a new [`EngineActor`] from the params.
     pub fn new(config: EngineConfig) -> (EngineInboundData, Self) {
         let (finalized_l1_block_tx, finalized_l1_block_rx) = watch::channel(None);
         let (inbound_queries_tx, inbound_queries_rx) = mpsc::channel(1024);
-        let (attributes_tx, attributes_rx) = mpsc::channel(1024);
-        let (unsafe_block_tx, unsafe_block_rx) = mpsc::channel(1024);
+        let (attributes_tx, attributes_rx) =
+            watermark_channel("attributes", config.attributes_watermarks, config.channel_alarms);
+        let (unsafe_block_tx, unsafe_block_rx) =
+            metered_channel("unsafe_blocks", 1024, config.channel_alarms);
         let (reset_request_tx, reset_request_rx) = mpsc::channel(1024);
+        let (engine_admin_query_tx, engine_admin_query_rx) = mpsc::channel(1024);
 
         let sequencer_channels = if config.mode.is_sequencer() {
             let (build_request_tx, build_request_rx) = mpsc::channel(1024);
@@ -287,6 +439,8 @@ impl EngineActor {
 
         let (rollup_boost_admin_query_tx, rollup_boost_admin_query_rx) = mpsc::channel(1024);
         let (rollup_boost_health_query_tx, rollup_boost_health_query_rx) = mpsc::channel(1024);
+        let (latency, derivation_latency_rx) = DerivationLatencyTracker::new();
+        let (sync_mode_tx, sync_mode_rx) = watch::channel(None);
 
         let actor = Self {
             builder: config,
@@ -294,12 +448,17 @@ impl EngineActor {
             unsafe_block_rx,
             unsafe_head_tx: sequencer_channels.unsafe_head_tx,
             reset_request_rx,
+            engine_admin_query_rx,
             inbound_queries: inbound_queries_rx,
             build_request_rx: sequencer_channels.build_request_rx,
             seal_request_rx: sequencer_channels.seal_request_rx,
             finalizer: L2Finalizer::new(finalized_l1_block_rx),
+            latency,
             rollup_boost_admin_query_rx,
             rollup_boost_health_query_rx,
+            client: None,
+            node_events: None,
+            sync_mode_tx,
         };
 
         let outbound_data = EngineInboundData {
@@ -308,15 +467,42 @@ impl EngineActor {
             finalized_l1_block_tx,
             inbound_queries_tx,
             reset_request_tx,
+            engine_admin_query_tx,
             rollup_boost_admin_query_tx,
             rollup_boost_health_query_tx,
             seal_request_tx: sequencer_channels.seal_request_tx,
             unsafe_block_tx,
             unsafe_head_rx: sequencer_channels.unsafe_head_rx,
+            derivation_latency_rx,
+            sync_mode_rx,
         };
 
         (outbound_data, actor)
     }
+
+    /// Sets the engine client used by the actor, instead of building one from the
+    /// [`EngineConfig`].
+    pub fn with_client(self, client: Arc<EngineClient_>) -> Self {
+        Self { client: Some(client), ..self }
+    }
+
+    /// Sets the event bus of the node, on which the actor publishes the engine state, the derived
+    /// attributes and the unsafe payloads it receives.
+    pub(crate) fn with_node_events(self, node_events: broadcast::Sender<NodeEvent>) -> Self {
+        Self { node_events: Some(node_events), ..self }
+    }
+
+    /// Checks the result of a requested reset. Invalid pinned anchors are rejected without
+    /// touching the engine, so they are reported to the requester rather than treated as fatal.
+    fn check_reset_result(reset_res: Result<(), EngineError>) -> Result<(), EngineError> {
+        match reset_res {
+            Err(EngineError::EngineReset(EngineResetError::Anchors(err))) => {
+                warn!(target: "engine", %err, "Rejected the pinned reset anchors");
+                Ok(())
+            }
+            res => res,
+        }
+    }
 }
 
 impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
@@ -326,10 +512,12 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
         mut inbound_query_channel: tokio::sync::mpsc::Receiver<EngineQueries>,
         mut rollup_boost_admin_query_rx: tokio::sync::mpsc::Receiver<RollupBoostAdminQuery>,
         mut rollup_boost_health_query_rx: tokio::sync::mpsc::Receiver<RollupBoostHealthQuery>,
-        rollup_boost: Arc<RollupBoostServer>,
+        rollup_boost: Option<Arc<RollupBoostServer>>,
     ) -> JoinHandle<Result<(), EngineError>> {
         let state_recv = self.engine.state_subscribe();
         let queue_length_recv = self.engine.queue_length_subscribe();
+        let deposit_only_blocks_recv = self.engine.deposit_only_blocks_subscribe();
+        let consolidation_mismatch_recv = self.engine.consolidation_mismatch_subscribe();
         let engine_client = self.client.clone();
         let rollup_config = self.rollup.clone();
 
@@ -346,7 +534,14 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
                             trace!(target: "engine", ?req, "Received engine query.");
 
                             if let Err(e) = req
-                                .handle(&state_recv, &queue_length_recv, &engine_client, &rollup_config)
+                                .handle(
+                                    &state_recv,
+                                    &queue_length_recv,
+                                    &deposit_only_blocks_recv,
+                                    &consolidation_mismatch_recv,
+                                    &engine_client,
+                                    &rollup_config,
+                                )
                                 .await
                             {
                                 warn!(target: "engine", err = ?e, "Failed to handle engine query.");
@@ -361,6 +556,15 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
                             continue;
                         };
 
+                        // Dropping the query answers it with an error.
+                        let Some(rollup_boost) = &rollup_boost else {
+                            warn!(
+                                target: "engine",
+                                "Received a rollup boost query, but rollup boost is not in use"
+                            );
+                            continue;
+                        };
+
                         match admin_query {
                             RollupBoostAdminQuery::SetExecutionMode { execution_mode } => {
                                 rollup_boost.server.set_execution_mode(execution_mode);
@@ -379,24 +583,101 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
                             return Err(EngineError::ChannelClosed);
                         };
 
-                        let health = rollup_boost.get_health();
-                        health_query.sender.send(health.into()).unwrap();
+                        // Without rollup boost, the engine client has no builder to be unhealthy.
+                        let health = rollup_boost
+                            .as_ref()
+                            .map_or(RollupBoostHealth::Healthy, |rollup_boost| {
+                                rollup_boost.get_health().into()
+                            });
+                        health_query.sender.send(health).unwrap();
                     }
                 }
             }
         })
     }
 
+    /// Selects the sync strategy of the node, and points the execution layer at the checkpoint
+    /// fetched from the trusted rollup node if EL sync is selected.
+    ///
+    /// Without an [`AutoSyncConfig`], the node syncs to the configured checkpoint unless the
+    /// execution layer has already finalized it.
+    async fn select_sync_mode(
+        &mut self,
+        checkpoint: Option<&CheckpointConfig>,
+        auto_sync: Option<AutoSyncConfig>,
+    ) -> Result<SyncModeSelection, EngineError> {
+        let (block, selection) = match (auto_sync, checkpoint) {
+            (Some(auto_sync), checkpoint) => {
+                auto_sync.select(&self.rollup, self.client.as_ref(), checkpoint).await?
+            }
+            (None, Some(checkpoint)) => {
+                match checkpoint.fetch(&self.rollup, self.client.as_ref()).await? {
+                    Some(block) => (
+                        Some(block),
+                        SyncModeSelection {
+                            strategy: SyncStrategy::ExecutionLayer,
+                            reason: "checkpoint sync is configured".to_string(),
+                        },
+                    ),
+                    None => (
+                        None,
+                        SyncModeSelection {
+                            strategy: SyncStrategy::ConsensusLayer,
+                            reason: "the execution layer is past the checkpoint".to_string(),
+                        },
+                    ),
+                }
+            }
+            (None, None) => (
+                None,
+                SyncModeSelection {
+                    strategy: SyncStrategy::ConsensusLayer,
+                    reason: "consensus-layer sync is configured".to_string(),
+                },
+            ),
+        };
+
+        if let Some(block) = block {
+            self.engine.checkpoint(self.client.clone(), self.rollup.clone(), block).await?;
+            self.checkpoint = Some(block);
+        }
+        Ok(selection)
+    }
+
     /// Resets the inner [`Engine`] and propagates the reset to the derivation actor.
+    ///
+    /// If both the L2 safe head and L1 origin are pinned, the engine is rewound to the pinned safe
+    /// head once the anchors are validated, and the derivation pipeline restarts from them.
+    /// Otherwise, the pipeline restarts from the anchors found by the engine reset.
     pub(super) async fn reset(
         &mut self,
         derivation_signal_tx: &mpsc::Sender<Signal>,
         engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>,
         finalizer: &mut L2Finalizer,
+        pinned_l2_safe_head: Option<L2BlockInfo>,
+        pinned_l1_origin: Option<BlockInfo>,
     ) -> Result<(), EngineError> {
-        // Reset the engine.
+        // Reset the engine, rewinding it to the pinned anchors if they are provided.
         let (l2_safe_head, l1_origin, system_config) =
-            self.engine.reset(self.client.clone(), self.rollup.clone()).await?;
+            match pinned_l2_safe_head.zip(pinned_l1_origin) {
+                Some((pinned_l2_safe_head, pinned_l1_origin)) => {
+                    info!(
+                        target: "engine",
+                        l2_safe_head = ?pinned_l2_safe_head,
+                        l1_origin = ?pinned_l1_origin,
+                        "Resetting to pinned anchors"
+                    );
+                    self.engine
+                        .reset_to(
+                            self.client.clone(),
+                            self.rollup.clone(),
+                            pinned_l2_safe_head,
+                            pinned_l1_origin,
+                        )
+                        .await?
+                }
+                None => self.engine.reset(self.client.clone(), self.rollup.clone()).await?,
+            };
 
         // Attempt to update the safe head following the reset.
         // IMPORTANT NOTE: We need to update the safe head BEFORE sending the reset signal to the
@@ -405,6 +686,13 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
         // condition where the derivation actor receives the pre-reset safe head.
         self.maybe_update_safe_head(engine_l2_safe_head_tx);
 
+        if let Some(db) = &self.db {
+            let checkpoint = DerivationCheckpoint { l2_safe_head, l1_origin, system_config };
+            if let Err(err) = db.record_checkpoint(checkpoint) {
+                warn!(target: "engine", ?err, "Failed to record the derivation checkpoint");
+            }
+        }
+
         // Signal the derivation actor to reset.
         let signal = ResetSignal { l2_safe_head, l1_origin, system_config: Some(system_config) };
         match derivation_signal_tx.send(signal.signal()).await {
@@ -429,11 +717,21 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
         engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>,
         finalizer: &mut L2Finalizer,
     ) -> Result<(), EngineError> {
-        match self.engine.drain().await {
+        let sync_state = self.engine.state().sync_state;
+        let drain_result = self.engine.drain().await;
+        self.count_head_changes(sync_state.safe_head(), sync_state.unsafe_head());
+        self.record_consolidation_mismatch();
+        self.log_forkchoice_changes(&sync_state);
+        self.check_derivation_halt(&drain_result, sync_state.safe_head());
+
+        match drain_result {
             Ok(_) => {
                 trace!(target: "engine", "[ENGINE] tasks drained");
             }
             Err(err) => {
+                if err.deposit_only_block().is_some() {
+                    self.increment_counter(NodeCounter::DepositOnlyBlocks, 1);
+                }
                 match err.severity() {
                     EngineTaskErrorSeverity::Critical => {
                         error!(target: "engine", ?err, "Critical error draining engine tasks");
@@ -441,15 +739,28 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
                     }
                     EngineTaskErrorSeverity::Reset => {
                         warn!(target: "engine", ?err, "Received reset request");
-                        self.reset(derivation_signal_tx, engine_l2_safe_head_tx, finalizer).await?;
+                        self.reset(
+                            derivation_signal_tx,
+                            engine_l2_safe_head_tx,
+                            finalizer,
+                            None,
+                            None,
+                        )
+                        .await?;
+                        self.increment_counter(NodeCounter::PipelineResets, 1);
                     }
                     EngineTaskErrorSeverity::Flush => {
                         // This error is encountered when the payload is marked INVALID
                         // by the engine api. Post-holocene, the payload is replaced by
                         // a "deposits-only" block and re-executed. At the same time,
-                        // the channel and any remaining buffered batches are flushed.
+                        // the channel and any remaining buffered batches are flushed,
+                        // targeting the channel of the payload's batch if it is known.
                         warn!(target: "engine", ?err, "Invalid payload, Flushing derivation pipeline.");
-                        match derivation_signal_tx.send(Signal::FlushChannel).await {
+                        let signal = err
+                            .deposit_only_block()
+                            .and_then(|block| block.channel_id)
+                            .map_or(Signal::FlushChannel, Signal::FlushChannelById);
+                        match derivation_signal_tx.send(signal).await {
                             Ok(_) => {
                                 debug!(target: "engine", "Sent flush signal to derivation actor")
                             }
@@ -467,6 +778,11 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
         }
 
         self.maybe_update_safe_head(engine_l2_safe_head_tx);
+        self.maybe_record_safe_head(finalizer);
+
+        // The safe head may have caught up with the finalized L1 chain.
+        finalizer.try_finalize_next(self).await;
+
         self.check_el_sync(
             derivation_signal_tx,
             engine_l2_safe_head_tx,
@@ -487,28 +803,243 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
         finalizer: &mut L2Finalizer,
     ) -> Result<(), EngineError> {
         if self.engine.state().el_sync_finished {
+            if std::mem::take(&mut self.el_sync_fallback) {
+                return self.finish_el_sync_fallback().await;
+            }
+
             let Some(sync_complete_tx) = std::mem::take(sync_complete_tx) else {
                 return Ok(());
             };
 
             // Only reset the engine if the sync state does not already know about a finalized
-            // block.
-            if self.engine.state().sync_state.finalized_head() != L2BlockInfo::default() {
+            // block, or if the finalized block is the checkpoint. Derivation then starts from the
+            // checkpoint.
+            if self.checkpoint.take().is_none() &&
+                self.engine.state().sync_state.finalized_head() != L2BlockInfo::default()
+            {
                 return Ok(());
             }
 
             // If the sync status is finished, we can reset the engine and start derivation.
             info!(target: "engine", "Performing initial engine reset");
-            self.reset(derivation_signal_tx, engine_l2_safe_head_tx, finalizer).await?;
+            self.reset(derivation_signal_tx, engine_l2_safe_head_tx, finalizer, None, None).await?;
             sync_complete_tx.send(()).ok();
         }
 
         Ok(())
     }
 
+    /// Enqueues the consolidation of derived attributes, or defers it while EL sync is restarted.
+    fn enqueue_consolidation(&mut self, task: ConsolidateTask<EngineClient_>) {
+        if self.el_sync_fallback {
+            trace!(target: "engine", "Deferring derived attributes while the EL syncs");
+            self.deferred_consolidations.push(task);
+            return;
+        }
+        self.engine.enqueue(EngineTask::Consolidate(Box::new(task)));
+    }
+
+    /// Restarts EL sync towards the unsafe payload `number` if it is more than the unsafe gap limit
+    /// ahead of the safe head.
+    fn maybe_restart_el_sync(&mut self, number: u64) {
+        let Some(limit) = self.unsafe_gap_limit else {
+            return;
+        };
+        let state = self.engine.state();
+        if !state.el_sync_finished || self.el_sync_fallback {
+            return;
+        }
+
+        let safe_head = state.sync_state.safe_head().block_info.number;
+        let gap = number.saturating_sub(safe_head);
+        if gap <= limit {
+            return;
+        }
+
+        warn!(
+            target: "engine",
+            unsafe_block = number,
+            safe_head,
+            gap,
+            limit,
+            "Unsafe head too far ahead of the safe head, restarting execution layer sync"
+        );
+        self.deferred_consolidations.extend(self.engine.restart_el_sync());
+        self.el_sync_fallback = true;
+        self.sync_mode_tx.send_replace(Some(SyncModeSelection {
+            strategy: SyncStrategy::ExecutionLayer,
+            reason: format!(
+                "the unsafe head is {gap} blocks ahead of the safe head, more than {limit}"
+            ),
+        }));
+    }
+
+    /// Completes the EL sync restarted by [`Self::maybe_restart_el_sync`]. The forkchoice moves
+    /// to the synced unsafe head, keeping the safe and finalized heads, and the deferred
+    /// consolidations resume so that derivation verifies the synced unsafe blocks.
+    async fn finish_el_sync_fallback(&mut self) -> Result<(), EngineError> {
+        let sync_state = self.engine.state().sync_state;
+        let unsafe_head = sync_state.unsafe_head();
+        info!(
+            target: "engine",
+            number = unsafe_head.block_info.number,
+            hash = %unsafe_head.block_info.hash,
+            safe_head = sync_state.safe_head().block_info.number,
+            "Execution layer synced to the unsafe head, resuming derivation"
+        );
+
+        self.engine.sync_unsafe_head(self.client.clone(), self.rollup.clone(), unsafe_head).await?;
+        for task in std::mem::take(&mut self.deferred_consolidations) {
+            self.engine.enqueue(EngineTask::Consolidate(Box::new(task)));
+        }
+        self.sync_mode_tx.send_replace(Some(SyncModeSelection {
+            strategy: SyncStrategy::ConsensusLayer,
+            reason: "the execution layer finished syncing to the unsafe head".to_string(),
+        }));
+        Ok(())
+    }
+
+    /// Records the safe head at the L1 block it was derived from in the node database, if it
+    /// changed since the last record.
+    fn maybe_record_safe_head(&mut self, finalizer: &L2Finalizer) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        let safe_head = self.engine.state().sync_state.safe_head();
+        let Some(l1_block) = finalizer.derived_from(safe_head.block_info.number) else {
+            return;
+        };
+
+        let record =
+            SafeHeadRecord { l1_block: l1_block.id(), safe_head: safe_head.block_info.id() };
+        if self.last_safe_head_record == Some(record) {
+            return;
+        }
+        match db.record_safe_head(record) {
+            Ok(()) => self.last_safe_head_record = Some(record),
+            Err(err) => warn!(target: "engine", ?err, "Failed to record the safe head"),
+        }
+    }
+
+    /// Counts the L2 blocks derived and the unsafe reorgs handled since the engine was at the given
+    /// safe and unsafe heads.
+    fn count_head_changes(&self, safe_head: L2BlockInfo, unsafe_head: L2BlockInfo) {
+        let state = self.engine.state();
+
+        // The safe head jumps to the execution layer's chain when EL sync completes.
+        let derived = state
+            .sync_state
+            .safe_head()
+            .block_info
+            .number
+            .saturating_sub(safe_head.block_info.number);
+        if state.el_sync_finished && derived > 0 {
+            self.increment_counter(NodeCounter::DerivedBlocks, derived);
+        }
+
+        let (old, new) = (unsafe_head.block_info, state.sync_state.unsafe_head().block_info);
+        let reorged = if new.number == old.number + 1 {
+            new.parent_hash != old.hash
+        } else {
+            new.number <= old.number && new.hash != old.hash
+        };
+        if reorged && unsafe_head != L2BlockInfo::default() {
+            self.increment_counter(NodeCounter::L2Reorgs, 1);
+        }
+    }
+
+    /// Logs the changes of the forkchoice since `previous` at the `info` level, if
+    /// [`RuntimeFlag::VerboseEngineLogging`] is set.
+    fn log_forkchoice_changes(&self, previous: &EngineSyncState) {
+        if !self.runtime_flags.is_enabled(RuntimeFlag::VerboseEngineLogging) {
+            return;
+        }
+
+        let current = self.engine.state().sync_state;
+        if current == *previous {
+            return;
+        }
+        info!(
+            target: "engine",
+            unsafe_head = %current.unsafe_head().block_info.number,
+            cross_unsafe_head = %current.cross_unsafe_head().block_info.number,
+            pending_safe_head = %current.pending_safe_head().block_info.number,
+            safe_head = %current.safe_head().block_info.number,
+            finalized_head = %current.finalized_head().block_info.number,
+            unsafe_hash = %current.unsafe_head().block_info.hash,
+            "Forkchoice changed"
+        );
+    }
+
+    /// Records the derived payloads the drain of the [`Engine`] accepted or rejected as invalid,
+    /// halting derivation once too many of them were rejected.
+    fn check_derivation_halt(
+        &mut self,
+        drain_result: &Result<(), EngineTaskErrors>,
+        prev_safe_head: L2BlockInfo,
+    ) {
+        let Some(detector) = self.derivation_halt.as_mut() else {
+            return;
+        };
+
+        let err = match drain_result {
+            Err(err) if err.is_invalid_derived_payload() => err,
+            _ => {
+                if self.engine.state().sync_state.safe_head().block_info.number >
+                    prev_safe_head.block_info.number
+                {
+                    detector.record_valid();
+                }
+                return;
+            }
+        };
+
+        let deposit_only = err.deposit_only_block();
+        let block_number =
+            deposit_only.map_or(prev_safe_head.block_info.number + 1, |block| block.block_number);
+        let Some(halt) = detector.record_invalid(block_number, deposit_only.is_some()) else {
+            return;
+        };
+        error!(
+            target: "engine",
+            reason = %halt.reason,
+            block_number,
+            "Halting derivation until the halt is acknowledged through the admin RPC"
+        );
+        kona_macros::inc!(counter, crate::Metrics::DERIVATION_HALTS);
+    }
+
+    /// Records the last consolidation mismatch of the [`Engine`] in the node database, if enabled
+    /// and if it changed.
+    fn record_consolidation_mismatch(&mut self) {
+        if !self.consolidation_mismatch_rx.has_changed().unwrap_or_default() {
+            return;
+        }
+        let mismatch = self.consolidation_mismatch_rx.borrow_and_update().clone();
+        let (Some(db), Some(mismatch)) = (&self.db, mismatch) else {
+            return;
+        };
+        if let Err(err) = db.record_consolidation_mismatch(&mismatch) {
+            warn!(target: "engine", ?err, "Failed to record the consolidation mismatch");
+        }
+    }
+
+    /// Adds `by` to a counter in the node database, if enabled.
+    fn increment_counter(&self, counter: NodeCounter, by: u64) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        if let Err(err) = db.increment_counter(counter, by) {
+            warn!(target: "engine", ?err, %counter, "Failed to increment the counter");
+        }
+    }
+
     /// Attempts to update the safe head via the watch channel.
+    ///
+    /// The pending safe head is sent, since derivation builds on top of blocks of a span batch
+    /// that has not been fully validated yet.
     fn maybe_update_safe_head(&self, engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>) {
-        let state_safe_head = self.engine.state().sync_state.safe_head();
+        let state_safe_head = self.engine.state().sync_state.pending_safe_head();
         let update = |head: &mut L2BlockInfo| {
             if head != &state_safe_head {
                 *head = state_safe_head;
@@ -522,7 +1053,7 @@ impl<EngineClient_: EngineClient + 'static> EngineActorState<EngineClient_> {
 }
 
 #[async_trait]
-impl NodeActor for EngineActor {
+impl<EngineClient_: EngineActorClient> NodeActor for EngineActor<EngineClient_> {
     type Error = EngineError;
     type StartData = EngineContext;
 
@@ -533,9 +1064,38 @@ impl NodeActor for EngineActor {
             engine_l2_safe_head_tx,
             sync_complete_tx,
             derivation_signal_tx,
+            runtime_flags,
+            derivation_halt,
         }: Self::StartData,
     ) -> Result<(), Self::Error> {
-        let mut state = self.builder.build_state()?;
+        let queue_monitor = QueueMonitor::new("engine_tasks", self.builder.channel_alarms);
+        let checkpoint = self.builder.checkpoint.clone();
+        let auto_sync = self.builder.auto_sync;
+        let mut state = self.builder.build_state(
+            self.client,
+            self.sync_mode_tx,
+            runtime_flags,
+            derivation_halt,
+        )?;
+        let queue_length = state.engine.queue_length_subscribe();
+
+        let selection = state.select_sync_mode(checkpoint.as_ref(), auto_sync).await?;
+        info!(
+            target: "engine",
+            strategy = %selection.strategy,
+            reason = %selection.reason,
+            "Selected sync strategy"
+        );
+        state.sync_mode_tx.send_replace(Some(selection));
+
+        if let Some(events) = self.node_events.clone() {
+            tokio::spawn(publish(
+                state.engine.state_subscribe(),
+                events,
+                NodeEvent::EngineState,
+                cancellation.clone(),
+            ));
+        }
 
         // Start the engine query server in a separate task to avoid blocking the main task.
         let handle = state
@@ -543,7 +1103,7 @@ impl NodeActor for EngineActor {
                 self.inbound_queries,
                 self.rollup_boost_admin_query_rx,
                 self.rollup_boost_health_query_rx,
-                state.client.rollup_boost.clone(),
+                state.client.rollup_boost(),
             )
             .with_cancellation_token(&cancellation)
             .then(async |result| {
@@ -576,6 +1136,8 @@ impl NodeActor for EngineActor {
         let mut sync_complete_tx = Some(sync_complete_tx);
 
         loop {
+            queue_monitor.record_depth(*queue_length.borrow());
+
             tokio::select! {
                 _ = cancellation.cancelled() => {
                     warn!(target: "engine", "EngineActor received shutdown signal. Aborting engine query task.");
@@ -600,6 +1162,8 @@ impl NodeActor for EngineActor {
                             return Err(err);
                         }
 
+                        self.latency.update(&state.engine.state().sync_state);
+
                         // If the unsafe head has updated, propagate it to the outbound channels.
                         if let Some(unsafe_head_tx) = self.unsafe_head_tx.as_mut() {
                             unsafe_head_tx.send_if_modified(|val| {
@@ -619,7 +1183,7 @@ impl NodeActor for EngineActor {
                     return Ok(());
                 }
                 reset = self.reset_request_rx.recv() => {
-                    let Some(ResetRequest{result_tx: result_tx_option}) = reset else {
+                    let Some(ResetRequest{result_tx: result_tx_option, l2_safe_head, l1_origin}) = reset else {
                         error!(target: "engine", "Reset request receiver closed unexpectedly");
                         cancellation.cancel();
                         return Err(EngineError::ChannelClosed);
@@ -628,8 +1192,11 @@ impl NodeActor for EngineActor {
                     warn!(target: "engine", "Received reset request");
 
                     let reset_res = state
-                        .reset(&derivation_signal_tx, &engine_l2_safe_head_tx, &mut self.finalizer)
+                        .reset(&derivation_signal_tx, &engine_l2_safe_head_tx, &mut self.finalizer, l2_safe_head, l1_origin)
                         .await;
+                    if reset_res.is_ok() {
+                        state.increment_counter(NodeCounter::PipelineResets, 1);
+                    }
 
                     // Send the result if there is a channel on which to do so.
                     if let Some(tx) = result_tx_option {
@@ -639,7 +1206,34 @@ impl NodeActor for EngineActor {
                         }
                     }
 
-                    reset_res?;
+                    Self::check_reset_result(reset_res)?;
+                }
+                Some(query) = self.engine_admin_query_rx.recv() => {
+                    let EngineAdminQuery::ResetDerivationPipeline {
+                        l2_safe_head,
+                        l1_origin,
+                        sender,
+                    } = query;
+                    warn!(target: "engine", "Received reset request through the admin api");
+
+                    let reset_res = state
+                        .reset(
+                            &derivation_signal_tx,
+                            &engine_l2_safe_head_tx,
+                            &mut self.finalizer,
+                            l2_safe_head,
+                            l1_origin,
+                        )
+                        .await;
+                    if reset_res.is_ok() {
+                        state.increment_counter(NodeCounter::PipelineResets, 1);
+                    }
+                    let response = reset_res.as_ref().map(|_| ()).map_err(|e| e.to_string());
+                    if sender.send(response).is_err() {
+                        warn!(target: "engine", "Sending reset response failed");
+                    }
+
+                    Self::check_reset_result(reset_res)?;
                 }
                 Some(req) = OptionFuture::from(self.seal_request_rx.as_mut().map(|rx| rx.recv())), if self.seal_request_rx.is_some() => {
                     let Some(SealRequest{payload_id, attributes, result_tx}) = req else {
@@ -680,6 +1274,13 @@ impl NodeActor for EngineActor {
                         cancellation.cancel();
                         return Err(EngineError::ChannelClosed);
                     };
+                    if let Some(Err(err)) = state.db.as_ref().map(|db| db.cache_unsafe_payload(&envelope)) {
+                        warn!(target: "engine", ?err, "Failed to cache the unsafe payload");
+                    }
+                    if let Some(events) = &self.node_events {
+                        let _ = events.send(NodeEvent::UnsafePayload(Arc::new(envelope.clone())));
+                    }
+                    state.maybe_restart_el_sync(envelope.execution_payload.block_number());
                     let task = EngineTask::Insert(Box::new(InsertTask::new(
                         state.client.clone(),
                         state.rollup.clone(),
@@ -689,20 +1290,24 @@ impl NodeActor for EngineActor {
                     state.engine.enqueue(task);
                 }
                 attributes = self.attributes_rx.recv() => {
-                    let Some(attributes) = attributes else {
+                    let Some(DerivedAttributes { attributes, span, timings }) = attributes else {
                         error!(target: "engine", "Attributes receiver closed unexpectedly");
                         cancellation.cancel();
                         return Err(EngineError::ChannelClosed);
                     };
                     self.finalizer.enqueue_for_finalization(&attributes);
+                    self.latency.attributes_sent(attributes.block_number(), timings);
+                    if let Some(events) = &self.node_events {
+                        let _ = events.send(NodeEvent::DerivedAttributes(Arc::new(attributes.clone())));
+                    }
 
-                    let task = EngineTask::Consolidate(Box::new(ConsolidateTask::new(
+                    state.enqueue_consolidation(ConsolidateTask::new(
                         state.client.clone(),
                         state.rollup.clone(),
                         attributes,
                         true,
-                    )));
-                    state.engine.enqueue(task);
+                        span,
+                    ));
                 }
                 msg = self.finalizer.new_finalized_block() => {
                     if let Err(err) = msg {
@@ -718,3 +1323,138 @@ impl NodeActor for EngineActor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::{ForkchoiceUpdated, PayloadStatus, PayloadStatusEnum};
+    use kona_engine::test_utils::{
+        MockEngineClient, TestAttributesBuilder, TestEngineStateBuilder, test_block_info,
+        test_engine_client_builder,
+    };
+    use tracing::Span;
+
+    fn state(
+        engine_state: InnerEngineState,
+        unsafe_gap_limit: u64,
+    ) -> EngineActorState<MockEngineClient> {
+        let client = Arc::new(
+            test_engine_client_builder()
+                .with_fork_choice_updated_v3_response(ForkchoiceUpdated {
+                    payload_status: PayloadStatus {
+                        status: PayloadStatusEnum::Valid,
+                        latest_valid_hash: None,
+                    },
+                    payload_id: None,
+                })
+                .build(),
+        );
+        let engine = Engine::new(engine_state, watch::channel(engine_state).0, watch::channel(0).0);
+        EngineActorState {
+            rollup: Arc::new(RollupConfig::default()),
+            client,
+            consolidation_mismatch_rx: engine.consolidation_mismatch_subscribe(),
+            engine,
+            checkpoint: None,
+            unsafe_gap_limit: Some(unsafe_gap_limit),
+            el_sync_fallback: false,
+            deferred_consolidations: Vec::new(),
+            sync_mode_tx: watch::channel(None).0,
+            db: None,
+            last_safe_head_record: None,
+            runtime_flags: RuntimeFlags::default(),
+            derivation_halt: None,
+        }
+    }
+
+    fn consolidation(
+        state: &EngineActorState<MockEngineClient>,
+        parent: L2BlockInfo,
+    ) -> ConsolidateTask<MockEngineClient> {
+        ConsolidateTask::new(
+            state.client.clone(),
+            state.rollup.clone(),
+            TestAttributesBuilder::new().with_parent(parent).build(),
+            true,
+            Span::none(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_el_sync_fallback_keeps_safe_head_and_attributes() {
+        let safe_head = test_block_info(10);
+        let engine_state = TestEngineStateBuilder::new()
+            .with_unsafe_head(test_block_info(12))
+            .with_safe_head(safe_head)
+            .with_finalized_head(safe_head)
+            .build();
+        let mut state = state(engine_state, 5);
+        let queue_length = state.engine.queue_length_subscribe();
+
+        state.enqueue_consolidation(consolidation(&state, safe_head));
+        assert_eq!(*queue_length.borrow(), 1);
+
+        // A gossiped block too far ahead of the safe head restarts EL sync, deferring the queued
+        // consolidation along with the attributes derived while the execution layer syncs.
+        state.maybe_restart_el_sync(20);
+        assert!(state.el_sync_fallback);
+        assert!(!state.engine.state().el_sync_finished);
+        state.enqueue_consolidation(consolidation(&state, test_block_info(11)));
+        assert_eq!(*queue_length.borrow(), 0);
+        assert_eq!(state.deferred_consolidations.len(), 2);
+
+        // The execution layer reports the gossiped block as valid.
+        let unsafe_head = test_block_info(20);
+        state
+            .engine
+            .sync_unsafe_head(state.client.clone(), state.rollup.clone(), unsafe_head)
+            .await
+            .unwrap();
+        assert!(state.engine.state().el_sync_finished);
+
+        let (derivation_signal_tx, mut derivation_signal_rx) = mpsc::channel(1);
+        let (engine_l2_safe_head_tx, _) = watch::channel(L2BlockInfo::default());
+        let mut finalizer = L2Finalizer::new(watch::channel(None).1);
+        state
+            .check_el_sync(
+                &derivation_signal_tx,
+                &engine_l2_safe_head_tx,
+                &mut None,
+                &mut finalizer,
+            )
+            .await
+            .unwrap();
+
+        // Only the unsafe head moved, and derivation verifies the synced blocks from the safe head
+        // without a reset.
+        let sync_state = state.engine.state().sync_state;
+        assert_eq!(sync_state.unsafe_head(), unsafe_head);
+        assert_eq!(sync_state.safe_head(), safe_head);
+        assert_eq!(sync_state.finalized_head(), safe_head);
+        assert!(!state.el_sync_fallback);
+        assert!(state.deferred_consolidations.is_empty());
+        assert_eq!(*queue_length.borrow(), 2);
+        assert!(derivation_signal_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_task_without_rollup_boost() {
+        let state = state(TestEngineStateBuilder::new().build(), 5);
+        let (_queries_tx, queries_rx) = mpsc::channel(1);
+        let (admin_tx, admin_rx) = mpsc::channel(1);
+        let (health_tx, health_rx) = mpsc::channel(1);
+        let handle = state.start_query_task(queries_rx, admin_rx, health_rx, None);
+
+        // Without rollup boost, the engine client has no builder to be unhealthy.
+        let (sender, health) = oneshot::channel();
+        health_tx.send(RollupBoostHealthQuery { sender }).await.unwrap();
+        assert!(matches!(health.await.unwrap(), RollupBoostHealth::Healthy));
+
+        // Admin queries are answered with an error.
+        let (sender, execution_mode) = oneshot::channel();
+        admin_tx.send(RollupBoostAdminQuery::GetExecutionMode { sender }).await.unwrap();
+        assert!(execution_mode.await.is_err());
+
+        handle.abort();
+    }
+}
@@ -3,7 +3,7 @@ use alloy_rpc_types_engine::PayloadId;
 use async_trait::async_trait;
 use derive_more::Constructor;
 use kona_engine::{BuildTaskError, SealTaskError};
-use kona_protocol::{L2BlockInfo, OpAttributesWithParent};
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
 use std::fmt::Debug;
 use thiserror::Error;
@@ -19,6 +19,14 @@ pub trait BlockBuildingClient: Debug + Send + Sync {
     /// error in performing the reset.
     async fn reset_engine_forkchoice(&self) -> BlockEngineResult<()>;
 
+    /// Resets the engine's forkchoice and the derivation pipeline, pinning the L2 safe head and
+    /// L1 origin the pipeline restarts from if they are provided.
+    async fn reset_derivation_pipeline(
+        &self,
+        l2_safe_head: Option<L2BlockInfo>,
+        l1_origin: Option<BlockInfo>,
+    ) -> BlockEngineResult<()>;
+
     /// Starts building a block with the provided attributes.
     ///
     /// Returns a `PayloadId` that can be used to seal the block later.
@@ -68,10 +76,18 @@ impl BlockBuildingClient for QueuedBlockBuildingClient {
     }
 
     async fn reset_engine_forkchoice(&self) -> BlockEngineResult<()> {
+        self.reset_derivation_pipeline(None, None).await
+    }
+
+    async fn reset_derivation_pipeline(
+        &self,
+        l2_safe_head: Option<L2BlockInfo>,
+        l1_origin: Option<BlockInfo>,
+    ) -> BlockEngineResult<()> {
         let (result_tx, mut result_rx) = mpsc::channel(1);
 
         self.reset_request_tx
-            .send(ResetRequest { result_tx: Some(result_tx) })
+            .send(ResetRequest { result_tx: Some(result_tx), l2_safe_head, l1_origin })
             .await
             .map_err(|_| BlockEngineError::RequestError("request channel closed.".to_string()))?;
 
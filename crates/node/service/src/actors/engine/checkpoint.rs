@@ -0,0 +1,180 @@
+//! Checkpoint sync: starting the node from an L2 block of a trusted rollup node.
+
+use alloy_provider::{Provider, RootProvider};
+use alloy_transport::TransportError;
+use kona_engine::{EngineClient, L2ForkchoiceState, SyncStartError};
+use kona_genesis::RollupConfig;
+use kona_protocol::{L2BlockInfo, SyncModeSelection, SyncStatus, SyncStrategy};
+use url::Url;
+
+/// The L2 block of the trusted rollup node used as the checkpoint.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum CheckpointBlock {
+    /// The safe L2 block. More recent, but may still be reorged by an L1 reorg.
+    Safe,
+    /// The finalized L2 block.
+    #[default]
+    Finalized,
+}
+
+/// Configuration for checkpoint sync.
+///
+/// Instead of deriving the L2 chain from its genesis, the node fetches a recent L2 block from a
+/// trusted rollup node and points the execution layer's forkchoice at it. The execution client
+/// syncs the chain up to the checkpoint from its own peers, after which derivation starts from
+/// the checkpoint. This requires an execution client that can sync from a forkchoice update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointConfig {
+    /// The RPC url of the trusted rollup node.
+    pub url: Url,
+    /// The L2 block of the trusted rollup node used as the checkpoint.
+    pub block: CheckpointBlock,
+}
+
+/// Configuration for the automatic selection of the sync strategy, made on startup.
+///
+/// The node derives the L2 chain from L1 (CL sync) if the execution layer already holds a
+/// finalized chain, and lets the execution layer sync from its peers up to a checkpoint (EL sync)
+/// if it is far enough behind the trusted rollup node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoSyncConfig {
+    /// The minimum number of L2 blocks the execution layer must be behind the checkpoint for EL
+    /// sync to be selected.
+    pub min_el_sync_distance: u64,
+}
+
+/// An error that occurred while fetching a checkpoint.
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    /// The sync status of the trusted rollup node could not be fetched.
+    #[error("Failed to fetch the sync status of the trusted rollup node: {0}")]
+    SyncStatus(TransportError),
+    /// The checkpoint is below the L2 genesis.
+    #[error("Checkpoint block #{0} is below the L2 genesis")]
+    BeforeGenesis(u64),
+    /// The L1 origin of the checkpoint is not in the canonical L1 chain.
+    #[error("L1 origin #{number} ({hash}) of the checkpoint is not canonical")]
+    NonCanonicalOrigin {
+        /// The number of the L1 origin.
+        number: u64,
+        /// The hash of the L1 origin.
+        hash: alloy_primitives::B256,
+    },
+    /// The L1 or L2 chain could not be queried.
+    #[error(transparent)]
+    SyncStart(#[from] SyncStartError),
+}
+
+impl CheckpointConfig {
+    /// Fetches the checkpoint from the trusted rollup node.
+    ///
+    /// Returns [`None`] if the execution layer has already finalized the checkpoint, in which case
+    /// the node syncs as usual.
+    pub async fn fetch<EngineClient_: EngineClient>(
+        &self,
+        cfg: &RollupConfig,
+        engine_client: &EngineClient_,
+    ) -> Result<Option<L2BlockInfo>, CheckpointError> {
+        let status: SyncStatus =
+            RootProvider::<alloy_network::Ethereum>::new_http(self.url.clone())
+                .raw_request("optimism_syncStatus".into(), ())
+                .await
+                .map_err(CheckpointError::SyncStatus)?;
+        let checkpoint = match self.block {
+            CheckpointBlock::Safe => status.safe_l2,
+            CheckpointBlock::Finalized => status.finalized_l2,
+        };
+
+        if checkpoint.block_info.number < cfg.genesis.l2.number {
+            return Err(CheckpointError::BeforeGenesis(checkpoint.block_info.number));
+        }
+
+        let current = L2ForkchoiceState::current(cfg, engine_client).await?;
+        if current.finalized.block_info.number >= checkpoint.block_info.number {
+            info!(
+                target: "engine",
+                checkpoint = %checkpoint.block_info.number,
+                finalized = %current.finalized.block_info.number,
+                "Execution layer is past the checkpoint, syncing as usual"
+            );
+            return Ok(None);
+        }
+
+        // The trusted rollup node must follow the same L1 chain.
+        let origin = checkpoint.l1_origin;
+        if engine_client
+            .get_l1_block(origin.hash.into())
+            .await
+            .map_err(SyncStartError::RpcError)?
+            .is_none()
+        {
+            return Err(CheckpointError::NonCanonicalOrigin {
+                number: origin.number,
+                hash: origin.hash,
+            });
+        }
+
+        info!(
+            target: "engine",
+            number = %checkpoint.block_info.number,
+            hash = %checkpoint.block_info.hash,
+            block = %self.block,
+            url = %self.url,
+            "Fetched checkpoint from the trusted rollup node"
+        );
+        Ok(Some(checkpoint))
+    }
+}
+
+impl AutoSyncConfig {
+    /// Selects the sync strategy from the state of the execution layer, and its distance to the
+    /// checkpoint of the trusted rollup node, if any.
+    ///
+    /// Returns the checkpoint to sync the execution layer to if EL sync is selected, along with
+    /// the selection.
+    pub async fn select<EngineClient_: EngineClient>(
+        &self,
+        cfg: &RollupConfig,
+        engine_client: &EngineClient_,
+        checkpoint: Option<&CheckpointConfig>,
+    ) -> Result<(Option<L2BlockInfo>, SyncModeSelection), CheckpointError> {
+        let cl_sync = |reason: String| {
+            Ok((None, SyncModeSelection { strategy: SyncStrategy::ConsensusLayer, reason }))
+        };
+
+        let current = L2ForkchoiceState::current(cfg, engine_client).await?;
+        let finalized = current.finalized.block_info.number;
+        if finalized > cfg.genesis.l2.number {
+            return cl_sync(format!("the execution layer has finalized block #{finalized}"));
+        }
+
+        let Some(checkpoint) = checkpoint else {
+            return cl_sync("no trusted rollup node to sync the execution layer to".to_string());
+        };
+        let block = match checkpoint.fetch(cfg, engine_client).await {
+            Ok(Some(block)) => block,
+            Ok(None) => return cl_sync("the execution layer is past the checkpoint".to_string()),
+            Err(e) => {
+                warn!(target: "engine", %e, "Failed to fetch the checkpoint, selecting CL sync");
+                return cl_sync(format!("failed to fetch the checkpoint: {e}"));
+            }
+        };
+
+        let head = current.un_safe.block_info.number;
+        let distance = block.block_info.number.saturating_sub(head);
+        if distance < self.min_el_sync_distance {
+            return cl_sync(format!(
+                "the execution layer is {distance} blocks behind the checkpoint, less than {}",
+                self.min_el_sync_distance
+            ));
+        }
+
+        let reason = if head == cfg.genesis.l2.number {
+            format!("the execution layer is empty, {distance} blocks behind the checkpoint")
+        } else {
+            format!("the execution layer is {distance} blocks behind the checkpoint")
+        };
+        Ok((Some(block), SyncModeSelection { strategy: SyncStrategy::ExecutionLayer, reason }))
+    }
+}
@@ -0,0 +1,48 @@
+//! The [`EngineClient`]s the [`EngineActor`] drives the execution layer with.
+//!
+//! [`EngineActor`]: super::EngineActor
+
+use super::{EngineConfig, EngineError};
+use alloy_provider::RootProvider;
+use kona_engine::{EngineClient, OpEngineClient, RollupBoostServer};
+use op_alloy_network::Optimism;
+use std::sync::Arc;
+
+/// An [`EngineClient`] the [`EngineActor`] drives the execution layer with.
+///
+/// [`EngineActor`]: super::EngineActor
+pub trait EngineActorClient: EngineClient + Sized + 'static {
+    /// Builds the client described by the [`EngineConfig`], if none was given to the actor.
+    fn build(config: &EngineConfig) -> Result<Self, EngineError>;
+
+    /// Returns the rollup boost server the client sends its Engine API calls through, if any.
+    fn rollup_boost(&self) -> Option<Arc<RollupBoostServer>>;
+}
+
+impl EngineActorClient for OpEngineClient<RootProvider, RootProvider<Optimism>> {
+    fn build(config: &EngineConfig) -> Result<Self, EngineError> {
+        Ok(config.build_client()?)
+    }
+
+    fn rollup_boost(&self) -> Option<Arc<RollupBoostServer>> {
+        Some(Arc::clone(&self.rollup_boost))
+    }
+}
+
+/// The [`InProcessEngineClient`] executes blocks on top of the state it was created with, so it
+/// cannot be built from the [`EngineConfig`] and must be given to the actor.
+///
+/// [`InProcessEngineClient`]: kona_engine::InProcessEngineClient
+#[cfg(feature = "in-process")]
+impl<L1Provider> EngineActorClient for kona_engine::InProcessEngineClient<L1Provider>
+where
+    L1Provider: alloy_provider::Provider + 'static,
+{
+    fn build(_: &EngineConfig) -> Result<Self, EngineError> {
+        Err(EngineError::MissingEngineClient)
+    }
+
+    fn rollup_boost(&self) -> Option<Arc<RollupBoostServer>> {
+        None
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! [`EngineActor`]: super::EngineActor
 
-use kona_engine::{EngineClientBuilderError, EngineResetError, EngineTaskErrors};
+use super::CheckpointError;
+use kona_engine::{
+    EngineClientBuilderError, EngineResetError, EngineTaskErrors, SynchronizeTaskError,
+};
 
 /// An error from the [`EngineActor`].
 ///
@@ -18,7 +21,16 @@ pub enum EngineError {
     /// Engine client builder error.
     #[error(transparent)]
     EngineClientBuilder(#[from] EngineClientBuilderError),
+    /// The engine client cannot be built from the configuration, and was not given to the actor.
+    #[error("the engine client must be given to the engine actor")]
+    MissingEngineClient,
     /// Engine task error.
     #[error(transparent)]
     EngineTask(#[from] EngineTaskErrors),
+    /// Checkpoint sync error.
+    #[error(transparent)]
+    Checkpoint(#[from] CheckpointError),
+    /// Forkchoice update to the checkpoint error.
+    #[error(transparent)]
+    CheckpointForkchoice(#[from] SynchronizeTaskError),
 }
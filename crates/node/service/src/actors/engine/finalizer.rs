@@ -13,18 +13,29 @@ type L1BlockNumber = u64;
 /// An internal type alias for L2 block numbers.
 type L2BlockNumber = u64;
 
+/// The range of L2 blocks derived from a single L1 origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DerivedRange {
+    /// The L1 block that the L2 blocks were derived from.
+    l1_origin: BlockInfo,
+    /// The lowest L2 block number derived from the L1 origin.
+    first: L2BlockNumber,
+    /// The highest L2 block number derived from the L1 origin.
+    last: L2BlockNumber,
+}
+
 /// The [`L2Finalizer`] is responsible for finalizing L2 blocks derived from finalized L1 blocks.
-/// It maintains a queue of derived L2 blocks that are awaiting finalization, and finalizes them
-/// as new finalized L1 blocks are received.
+///
+/// It maps each L1 origin to the range of L2 blocks derived from it. When a new finalized L1 block
+/// is observed, the highest L2 block that was fully derived from finalized L1 data, and that has
+/// already been promoted to safe, is finalized with a forkchoice update.
 #[derive(Debug)]
 pub struct L2Finalizer {
     /// A channel that receives new finalized L1 blocks intermittently.
     finalized_l1_block_rx: watch::Receiver<Option<BlockInfo>>,
-    /// A map of `L1 block number -> highest derived L2 block number` within the L1 epoch, used to
-    /// track derived [`OpAttributesWithParent`] awaiting finalization. When a new finalized L1
-    /// block is received, the highest L2 block whose inputs are contained within the finalized
-    /// L1 chain is finalized.
-    awaiting_finalization: BTreeMap<L1BlockNumber, L2BlockNumber>,
+    /// A map of `L1 block number -> derived L2 block range`, used to track derived
+    /// [`OpAttributesWithParent`] awaiting finalization.
+    awaiting_finalization: BTreeMap<L1BlockNumber, DerivedRange>,
 }
 
 impl L2Finalizer {
@@ -36,15 +47,46 @@ impl L2Finalizer {
     /// Enqueues a derived [`OpAttributesWithParent`] for finalization. When a new finalized L1
     /// block is observed that is `>=` the height of [`OpAttributesWithParent::derived_from`], the
     /// L2 block associated with the payload attributes will be finalized.
+    ///
+    /// If the attributes were derived from an L1 block that conflicts with a tracked origin at the
+    /// same height, the L1 chain has reorged: the stale origin and all tracked origins above it
+    /// are discarded.
     pub fn enqueue_for_finalization(&mut self, attributes: &OpAttributesWithParent) {
+        let l1_origin = *attributes
+            .derived_from()
+            .expect("Fatal: Cannot enqueue attributes for finalization that weren't derived");
+        let block_number = attributes.block_number();
+
+        if self
+            .awaiting_finalization
+            .get(&l1_origin.number)
+            .is_some_and(|range| range.l1_origin.hash != l1_origin.hash)
+        {
+            warn!(
+                target: "engine",
+                number = l1_origin.number,
+                hash = %l1_origin.hash,
+                "L1 origin reorged, discarding stale blocks awaiting finalization"
+            );
+            self.awaiting_finalization.retain(|&number, _| number < l1_origin.number);
+        }
+
         self.awaiting_finalization
-            .entry(
-                attributes.derived_from.map(|b| b.number).expect(
-                    "Fatal: Cannot enqueue attributes for finalization that weren't derived",
-                ),
-            )
-            .and_modify(|n| *n = (*n).max(attributes.block_number()))
-            .or_insert(attributes.block_number());
+            .entry(l1_origin.number)
+            .and_modify(|range| {
+                range.first = range.first.min(block_number);
+                range.last = range.last.max(block_number);
+            })
+            .or_insert(DerivedRange { l1_origin, first: block_number, last: block_number });
+    }
+
+    /// Returns the L1 block that the L2 block `number` was derived from, if it is awaiting
+    /// finalization.
+    pub fn derived_from(&self, number: L2BlockNumber) -> Option<BlockInfo> {
+        self.awaiting_finalization
+            .values()
+            .find(|range| (range.first..=range.last).contains(&number))
+            .map(|range| range.l1_origin)
     }
 
     /// Clears the finalization queue.
@@ -57,8 +99,22 @@ impl L2Finalizer {
         self.finalized_l1_block_rx.changed().await
     }
 
-    /// Attempts to finalize any L2 blocks that the finalizer knows about and are contained within
-    /// the new finalized L1 chain.
+    /// Returns the highest L2 block number that is fully derived from L1 blocks contained within
+    /// the finalized L1 chain, and that is no higher than the `safe_head`.
+    pub fn finalizable(
+        &self,
+        finalized_l1: &BlockInfo,
+        safe_head: L2BlockNumber,
+    ) -> Option<L2BlockNumber> {
+        self.awaiting_finalization
+            .range(..=finalized_l1.number)
+            .map(|(_, range)| range.last)
+            .take_while(|last| *last <= safe_head)
+            .last()
+    }
+
+    /// Attempts to finalize any L2 blocks that the finalizer knows about, that are contained
+    /// within the finalized L1 chain and that have been promoted to safe.
     pub(super) async fn try_finalize_next<EngineClient_: EngineClient>(
         &mut self,
         engine_state: &mut EngineActorState<EngineClient_>,
@@ -68,21 +124,124 @@ impl L2Finalizer {
             return;
         };
 
-        // Find the highest safe L2 block that is contained within the finalized chain,
-        // that the finalizer is aware of.
-        let highest_safe = self.awaiting_finalization.range(..=new_finalized_l1.number).next_back();
-
-        // If the highest safe block is found, enqueue a finalization task and drain the
-        // queue of all L1 blocks not contained in the finalized L1 chain.
-        if let Some((_, highest_safe_number)) = highest_safe {
-            let task = EngineTask::Finalize(Box::new(FinalizeTask::new(
-                engine_state.client.clone(),
-                engine_state.rollup.clone(),
-                *highest_safe_number,
-            )));
-            engine_state.engine.enqueue(task);
-
-            self.awaiting_finalization.retain(|&number, _| number > new_finalized_l1.number);
+        let sync_state = engine_state.engine.state().sync_state;
+        let Some(highest_finalizable) =
+            self.finalizable(&new_finalized_l1, sync_state.safe_head().block_info.number)
+        else {
+            return;
+        };
+
+        // Drain all L1 origins whose derived blocks are covered by the finalization.
+        self.awaiting_finalization.retain(|&number, range| {
+            let keep = number > new_finalized_l1.number || range.last > highest_finalizable;
+            if !keep {
+                debug!(
+                    target: "engine",
+                    l1_origin = number,
+                    first = range.first,
+                    last = range.last,
+                    "Derived L2 range covered by finalized L1 chain"
+                );
+            }
+            keep
+        });
+
+        // Skip blocks that are already finalized.
+        if highest_finalizable <= sync_state.finalized_head().block_info.number {
+            return;
+        }
+
+        let task = EngineTask::Finalize(Box::new(FinalizeTask::new(
+            engine_state.client.clone(),
+            engine_state.rollup.clone(),
+            highest_finalizable,
+        )));
+        engine_state.engine.enqueue(task);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use kona_protocol::L2BlockInfo;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    fn l1_block(number: u64, hash: u8) -> BlockInfo {
+        BlockInfo { number, hash: B256::repeat_byte(hash), ..Default::default() }
+    }
+
+    fn attributes(number: u64, origin: BlockInfo) -> OpAttributesWithParent {
+        let parent = L2BlockInfo {
+            block_info: BlockInfo { number: number - 1, ..Default::default() },
+            ..Default::default()
+        };
+        OpAttributesWithParent::new(OpPayloadAttributes::default(), parent, Some(origin), true)
+    }
+
+    fn finalizer() -> L2Finalizer {
+        let (_, rx) = watch::channel(None);
+        L2Finalizer::new(rx)
+    }
+
+    #[test]
+    fn test_tracks_derived_ranges() {
+        let mut finalizer = finalizer();
+        for number in 1..=3 {
+            finalizer.enqueue_for_finalization(&attributes(number, l1_block(10, 0xAA)));
         }
+        finalizer.enqueue_for_finalization(&attributes(4, l1_block(11, 0xBB)));
+
+        assert_eq!(
+            finalizer.awaiting_finalization[&10],
+            DerivedRange { l1_origin: l1_block(10, 0xAA), first: 1, last: 3 }
+        );
+        assert_eq!(
+            finalizer.awaiting_finalization[&11],
+            DerivedRange { l1_origin: l1_block(11, 0xBB), first: 4, last: 4 }
+        );
+        assert_eq!(finalizer.derived_from(2), Some(l1_block(10, 0xAA)));
+        assert_eq!(finalizer.derived_from(4), Some(l1_block(11, 0xBB)));
+        assert_eq!(finalizer.derived_from(5), None);
+    }
+
+    #[test]
+    fn test_finalizable_capped_by_finalized_l1() {
+        let mut finalizer = finalizer();
+        finalizer.enqueue_for_finalization(&attributes(1, l1_block(10, 0xAA)));
+        finalizer.enqueue_for_finalization(&attributes(2, l1_block(11, 0xBB)));
+
+        assert_eq!(finalizer.finalizable(&l1_block(9, 0x00), 2), None);
+        assert_eq!(finalizer.finalizable(&l1_block(10, 0xAA), 2), Some(1));
+        assert_eq!(finalizer.finalizable(&l1_block(12, 0xCC), 2), Some(2));
+    }
+
+    #[test]
+    fn test_finalizable_capped_by_safe_head() {
+        let mut finalizer = finalizer();
+        finalizer.enqueue_for_finalization(&attributes(1, l1_block(10, 0xAA)));
+        finalizer.enqueue_for_finalization(&attributes(2, l1_block(10, 0xAA)));
+        finalizer.enqueue_for_finalization(&attributes(3, l1_block(11, 0xBB)));
+
+        // The L1 origin's range is not fully safe yet.
+        assert_eq!(finalizer.finalizable(&l1_block(11, 0xBB), 1), None);
+        assert_eq!(finalizer.finalizable(&l1_block(11, 0xBB), 2), Some(2));
+        assert_eq!(finalizer.finalizable(&l1_block(11, 0xBB), 3), Some(3));
+    }
+
+    #[test]
+    fn test_reorged_origin_discards_stale_ranges() {
+        let mut finalizer = finalizer();
+        finalizer.enqueue_for_finalization(&attributes(1, l1_block(10, 0xAA)));
+        finalizer.enqueue_for_finalization(&attributes(2, l1_block(11, 0xBB)));
+        finalizer.enqueue_for_finalization(&attributes(3, l1_block(12, 0xCC)));
+
+        finalizer.enqueue_for_finalization(&attributes(2, l1_block(11, 0xDD)));
+
+        assert_eq!(finalizer.awaiting_finalization.len(), 2);
+        assert_eq!(
+            finalizer.awaiting_finalization[&11],
+            DerivedRange { l1_origin: l1_block(11, 0xDD), first: 2, last: 2 }
+        );
     }
 }
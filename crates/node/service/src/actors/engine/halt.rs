@@ -0,0 +1,148 @@
+//! Halts derivation once the engine detects a runaway divergence from the chain.
+
+use kona_rpc::{DerivationHalt, DerivationHaltReason, DerivationHaltSwitch};
+use std::{
+    collections::VecDeque,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The thresholds of invalid derived payloads past which the engine halts derivation.
+///
+/// A streak of payloads rejected by the execution layer, or frequent deposits-only replacements,
+/// usually point at a consensus bug in the node or the execution layer rather than at invalid
+/// batches. Halting derivation keeps a verifier from diverging further from the chain until an
+/// operator acknowledges the halt through `admin_acknowledgeDerivationHalt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerivationHaltConfig {
+    /// The number of consecutive derived payloads rejected as invalid after which derivation
+    /// halts. Disabled if unset.
+    pub invalid_payload_streak: Option<u64>,
+    /// The number of invalid derived payloads replaced by deposits-only blocks within
+    /// [`Self::deposit_only_window`] L2 blocks after which derivation halts. Disabled if unset.
+    pub deposit_only_blocks: Option<u64>,
+    /// The number of L2 blocks the deposits-only replacements are counted over.
+    pub deposit_only_window: u64,
+}
+
+/// Tracks the invalid derived payloads of the engine, and halts derivation once they cross the
+/// thresholds of the [`DerivationHaltConfig`].
+#[derive(Debug)]
+pub(super) struct DerivationHaltDetector {
+    /// The thresholds past which derivation halts.
+    config: DerivationHaltConfig,
+    /// The switch halting derivation.
+    switch: DerivationHaltSwitch,
+    /// The number of consecutive invalid derived payloads.
+    invalid_streak: u64,
+    /// The numbers of the L2 blocks replaced by deposits-only blocks within the window, oldest
+    /// first.
+    deposit_only_blocks: VecDeque<u64>,
+}
+
+impl DerivationHaltDetector {
+    /// Creates a new [`DerivationHaltDetector`] tripping the given switch.
+    pub(super) const fn new(config: DerivationHaltConfig, switch: DerivationHaltSwitch) -> Self {
+        Self { config, switch, invalid_streak: 0, deposit_only_blocks: VecDeque::new() }
+    }
+
+    /// Records that the derived payload of the L2 block `block_number` was rejected as invalid,
+    /// and replaced by a deposits-only block if `deposit_only` is set.
+    ///
+    /// Returns the [`DerivationHalt`] if the payload halted derivation. The counts start over once
+    /// derivation halts, so that the halt is not tripped again right after it is acknowledged.
+    pub(super) fn record_invalid(
+        &mut self,
+        block_number: u64,
+        deposit_only: bool,
+    ) -> Option<DerivationHalt> {
+        self.invalid_streak += 1;
+        if deposit_only {
+            self.deposit_only_blocks.push_back(block_number);
+        }
+        let window = self.config.deposit_only_window;
+        while self
+            .deposit_only_blocks
+            .front()
+            .is_some_and(|oldest| oldest.saturating_add(window) <= block_number)
+        {
+            self.deposit_only_blocks.pop_front();
+        }
+
+        let deposit_only_count = self.deposit_only_blocks.len() as u64;
+        let reason = if self.config.invalid_payload_streak.is_some_and(|m| self.invalid_streak >= m)
+        {
+            DerivationHaltReason::InvalidPayloadStreak { count: self.invalid_streak }
+        } else if self.config.deposit_only_blocks.is_some_and(|n| deposit_only_count >= n) {
+            DerivationHaltReason::DepositOnlyBlocks { count: deposit_only_count, window }
+        } else {
+            return None;
+        };
+
+        self.invalid_streak = 0;
+        self.deposit_only_blocks.clear();
+
+        let halted_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let halt = DerivationHalt { reason, block_number, halted_at };
+        self.switch.halt(halt).then_some(halt)
+    }
+
+    /// Records that a derived payload was accepted by the execution layer, ending the streak of
+    /// invalid payloads.
+    pub(super) const fn record_valid(&mut self) {
+        self.invalid_streak = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_halt_on_invalid_payload_streak() {
+        let switch = DerivationHaltSwitch::default();
+        let config = DerivationHaltConfig {
+            invalid_payload_streak: Some(3),
+            deposit_only_blocks: None,
+            deposit_only_window: 0,
+        };
+        let mut detector = DerivationHaltDetector::new(config, switch.clone());
+
+        assert!(detector.record_invalid(10, false).is_none());
+        assert!(detector.record_invalid(10, false).is_none());
+        detector.record_valid();
+        assert!(detector.record_invalid(11, false).is_none());
+        assert!(detector.record_invalid(11, false).is_none());
+
+        let halt = detector.record_invalid(11, true).unwrap();
+        assert_eq!(halt.reason, DerivationHaltReason::InvalidPayloadStreak { count: 3 });
+        assert_eq!(halt.block_number, 11);
+        assert_eq!(switch.halted(), Some(halt));
+
+        // The streak starts over once derivation halted.
+        switch.acknowledge();
+        assert!(detector.record_invalid(12, false).is_none());
+        assert!(!switch.is_halted());
+    }
+
+    #[test]
+    fn test_halt_on_deposit_only_blocks_within_window() {
+        let switch = DerivationHaltSwitch::default();
+        let config = DerivationHaltConfig {
+            invalid_payload_streak: None,
+            deposit_only_blocks: Some(3),
+            deposit_only_window: 100,
+        };
+        let mut detector = DerivationHaltDetector::new(config, switch.clone());
+
+        assert!(detector.record_invalid(10, true).is_none());
+        detector.record_valid();
+        assert!(detector.record_invalid(50, true).is_none());
+        // The replacement of block 10 falls out of the window.
+        assert!(detector.record_invalid(110, true).is_none());
+        assert!(!switch.is_halted());
+
+        let halt = detector.record_invalid(149, true).unwrap();
+        assert_eq!(halt.reason, DerivationHaltReason::DepositOnlyBlocks { count: 3, window: 100 });
+        assert_eq!(switch.halted(), Some(halt));
+    }
+}
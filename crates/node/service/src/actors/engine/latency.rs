@@ -0,0 +1,212 @@
+//! Tracks the latency of derived payload attributes through the node.
+
+use kona_engine::EngineSyncState;
+use kona_rpc::{DerivationLatency, LatencySummary};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+/// The instants at which derived payload attributes passed each hop of the derivation actor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerivationTimings {
+    /// When the L1 block the attributes were derived from was first observed. `None` if the block
+    /// was already known when the node started.
+    pub l1_observed: Option<Instant>,
+    /// When the batch was decoded into the attributes.
+    pub decoded: Instant,
+}
+
+/// Derived payload attributes that have not reached the safe chain yet.
+#[derive(Debug, Clone, Copy)]
+struct PendingAttributes {
+    /// The instants at which the attributes passed the derivation actor.
+    timings: DerivationTimings,
+    /// When the attributes were delivered to the engine actor.
+    sent: Instant,
+    /// When the engine executed the attributes, advancing the pending safe head.
+    executed: Option<Instant>,
+}
+
+/// The latency of a safe block through each hop.
+#[derive(Debug, Clone, Copy)]
+struct HopLatencies {
+    l1_to_decoded: Option<Duration>,
+    decoded_to_sent: Duration,
+    sent_to_executed: Duration,
+    executed_to_safe: Duration,
+    end_to_end: Option<Duration>,
+}
+
+/// Tracks derived payload attributes from the L1 block they were derived from being observed, to
+/// the safe head being updated.
+///
+/// Each hop is recorded in the [`crate::Metrics::DERIVATION_LATENCY`] histogram, and the latencies
+/// of the most recent safe blocks are summarized in a [`DerivationLatency`] served over
+/// `rollup_derivationLatency`.
+#[derive(Debug)]
+pub struct DerivationLatencyTracker {
+    /// The attributes awaiting the safe head, keyed by L2 block number.
+    pending: BTreeMap<u64, PendingAttributes>,
+    /// The latencies of the most recent safe blocks.
+    samples: VecDeque<HopLatencies>,
+    /// Publishes the summary of the latencies.
+    summary: watch::Sender<DerivationLatency>,
+}
+
+impl DerivationLatencyTracker {
+    /// The number of safe blocks the latencies are summarized over.
+    pub const WINDOW: usize = 256;
+
+    /// The maximum number of attributes awaiting the safe head. Attributes are re-derived after a
+    /// reset, so this only bounds the memory held by attributes that are never re-derived.
+    const MAX_PENDING: usize = 4096;
+
+    /// Creates a new [`DerivationLatencyTracker`], along with a receiver of its summaries.
+    pub fn new() -> (Self, watch::Receiver<DerivationLatency>) {
+        let (summary, summary_rx) = watch::channel(DerivationLatency::default());
+        (Self { pending: BTreeMap::new(), samples: VecDeque::new(), summary }, summary_rx)
+    }
+
+    /// Records the delivery of the attributes for the given L2 block to the engine actor.
+    pub fn attributes_sent(&mut self, block_number: u64, timings: DerivationTimings) {
+        self.pending.insert(
+            block_number,
+            PendingAttributes { timings, sent: Instant::now(), executed: None },
+        );
+        if self.pending.len() > Self::MAX_PENDING {
+            self.pending.pop_first();
+        }
+    }
+
+    /// Advances the tracked attributes to the given engine sync state, completing those that
+    /// reached the safe chain.
+    pub fn update(&mut self, sync_state: &EngineSyncState) {
+        let now = Instant::now();
+
+        let pending_safe = sync_state.pending_safe_head().block_info.number;
+        for attributes in self.pending.range_mut(..=pending_safe).map(|(_, attributes)| attributes)
+        {
+            attributes.executed.get_or_insert(now);
+        }
+
+        let safe = sync_state.safe_head().block_info.number;
+        let remaining = self.pending.split_off(&safe.saturating_add(1));
+        let completed = std::mem::replace(&mut self.pending, remaining);
+        if completed.is_empty() {
+            return;
+        }
+
+        for attributes in completed.into_values() {
+            let executed = attributes.executed.unwrap_or(now);
+            let latencies = HopLatencies {
+                l1_to_decoded: attributes
+                    .timings
+                    .l1_observed
+                    .map(|observed| attributes.timings.decoded.saturating_duration_since(observed)),
+                decoded_to_sent: attributes
+                    .sent
+                    .saturating_duration_since(attributes.timings.decoded),
+                sent_to_executed: executed.saturating_duration_since(attributes.sent),
+                executed_to_safe: now.saturating_duration_since(executed),
+                end_to_end: attributes
+                    .timings
+                    .l1_observed
+                    .map(|observed| now.saturating_duration_since(observed)),
+            };
+            Self::record_metrics(&latencies);
+
+            if self.samples.len() == Self::WINDOW {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(latencies);
+        }
+
+        self.summary.send_replace(self.summarize());
+    }
+
+    /// Summarizes the latencies of the most recent safe blocks.
+    fn summarize(&self) -> DerivationLatency {
+        let summary = |hop: fn(&HopLatencies) -> Option<Duration>| {
+            LatencySummary::from_durations(self.samples.iter().filter_map(hop))
+        };
+
+        DerivationLatency {
+            samples: self.samples.len(),
+            l1_to_decoded: summary(|latencies| latencies.l1_to_decoded),
+            decoded_to_sent: summary(|latencies| Some(latencies.decoded_to_sent)),
+            sent_to_executed: summary(|latencies| Some(latencies.sent_to_executed)),
+            executed_to_safe: summary(|latencies| Some(latencies.executed_to_safe)),
+            end_to_end: summary(|latencies| latencies.end_to_end),
+        }
+    }
+
+    /// Records the latencies of a safe block in the [`crate::Metrics::DERIVATION_LATENCY`]
+    /// histogram.
+    fn record_metrics(_latencies: &HopLatencies) {
+        // no-op if disabled.
+        #[cfg(feature = "metrics")]
+        {
+            let hops = [
+                (crate::Metrics::L1_TO_DECODED_LABEL, _latencies.l1_to_decoded),
+                (crate::Metrics::DECODED_TO_SENT_LABEL, Some(_latencies.decoded_to_sent)),
+                (crate::Metrics::SENT_TO_EXECUTED_LABEL, Some(_latencies.sent_to_executed)),
+                (crate::Metrics::EXECUTED_TO_SAFE_LABEL, Some(_latencies.executed_to_safe)),
+                (crate::Metrics::END_TO_END_LABEL, _latencies.end_to_end),
+            ];
+            for (hop, latency) in hops {
+                if let Some(latency) = latency {
+                    kona_macros::record!(
+                        histogram,
+                        crate::Metrics::DERIVATION_LATENCY,
+                        "hop",
+                        hop,
+                        latency.as_secs_f64()
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_engine::EngineSyncStateUpdate;
+    use kona_protocol::L2BlockInfo;
+
+    fn sync_state(pending_safe: u64, safe: u64) -> EngineSyncState {
+        let block = |number| {
+            let mut block = L2BlockInfo::default();
+            block.block_info.number = number;
+            block
+        };
+        EngineSyncState::default().apply_update(EngineSyncStateUpdate {
+            pending_safe_head: Some(block(pending_safe)),
+            safe_head: Some(block(safe)),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_completes_attributes_once_safe() {
+        let (mut tracker, summary) = DerivationLatencyTracker::new();
+        let timings =
+            DerivationTimings { l1_observed: Some(Instant::now()), decoded: Instant::now() };
+        tracker.attributes_sent(1, timings);
+        tracker.attributes_sent(2, DerivationTimings { l1_observed: None, ..timings });
+
+        // Executed, but not safe yet.
+        tracker.update(&sync_state(2, 0));
+        assert_eq!(summary.borrow().samples, 0);
+        assert!(tracker.pending.values().all(|attributes| attributes.executed.is_some()));
+
+        tracker.update(&sync_state(2, 2));
+        let summary = *summary.borrow();
+        assert_eq!(summary.samples, 2);
+        assert!(tracker.pending.is_empty());
+        // Only the first block's L1 block was observed by the node.
+        assert!(summary.end_to_end.max_ms >= summary.executed_to_safe.max_ms);
+    }
+}
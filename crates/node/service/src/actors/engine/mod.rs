@@ -9,11 +9,23 @@ pub use actor::{
 mod error;
 pub use error::EngineError;
 
+mod client;
+pub use client::EngineActorClient;
+
+mod checkpoint;
+pub use checkpoint::{AutoSyncConfig, CheckpointBlock, CheckpointConfig, CheckpointError};
+
 mod api;
 pub use api::{
     BlockBuildingClient, BlockEngineError, BlockEngineResult, QueuedBlockBuildingClient,
 };
 
+mod halt;
+pub use halt::DerivationHaltConfig;
+
+mod latency;
+pub use latency::{DerivationLatencyTracker, DerivationTimings};
+
 mod finalizer;
 
 pub use finalizer::L2Finalizer;
@@ -0,0 +1,263 @@
+//! Extensions running additional [`NodeActor`]s alongside the actors of the node.
+
+use crate::{NodeActor, actors::CancellableContext};
+use async_trait::async_trait;
+use futures::{FutureExt, future::BoxFuture};
+use kona_engine::{EngineQueries, EngineState};
+use kona_protocol::{BlockInfo, OpAttributesWithParent};
+use kona_rpc::DerivationOriginStats;
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::{fmt::Debug, sync::Arc};
+use tokio::{
+    sync::{broadcast, mpsc, watch},
+    task::JoinSet,
+};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// An event published on the event bus of the node, to which [`NodeExtension`]s subscribe.
+#[derive(Debug, Clone)]
+pub enum NodeEvent {
+    /// The L1 head observed by the node changed.
+    L1Head(BlockInfo),
+    /// The state of the engine, holding the unsafe, safe and finalized L2 heads, changed.
+    EngineState(EngineState),
+    /// The engine received payload attributes derived from L1.
+    DerivedAttributes(Arc<OpAttributesWithParent>),
+    /// The engine received an unsafe payload, gossiped by the network.
+    UnsafePayload(Arc<OpExecutionPayloadEnvelope>),
+    /// The derivation pipeline advanced past an L1 origin, having consumed the given frames and
+    /// batches from it.
+    DerivationOrigin(DerivationOriginStats),
+}
+
+/// The communication context used by a [`NodeExtension`].
+#[derive(Debug)]
+pub struct ExtensionContext {
+    /// The receiver of the events published by the node.
+    pub events: broadcast::Receiver<NodeEvent>,
+    /// The sender of queries to the engine.
+    pub engine_queries: mpsc::Sender<EngineQueries>,
+    /// The cancellation token, shared between all tasks.
+    pub cancellation: CancellationToken,
+}
+
+impl CancellableContext for ExtensionContext {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+/// An additional actor run by the node, registered with [`RollupNodeBuilder::with_extension`].
+///
+/// The trait is implemented for every cloneable [`NodeActor`] started with an
+/// [`ExtensionContext`]. The actor is cloned and started every time the node starts, and is
+/// stopped with the node. If the actor fails, the node is shut down.
+///
+/// [`RollupNodeBuilder::with_extension`]: crate::RollupNodeBuilder::with_extension
+pub trait NodeExtension: Debug + Send + Sync + 'static {
+    /// Starts the extension with the given context.
+    fn launch(&self, context: ExtensionContext) -> BoxFuture<'static, Result<(), String>>;
+}
+
+impl<A> NodeExtension for A
+where
+    A: NodeActor<StartData = ExtensionContext> + Clone + Debug + Sync,
+{
+    fn launch(&self, context: ExtensionContext) -> BoxFuture<'static, Result<(), String>> {
+        let actor = self.clone();
+        async move { actor.start(context).await.map_err(|e| format!("{e:?}")) }.boxed()
+    }
+}
+
+/// The actor running the [`NodeExtension`]s of the node, and publishing the L1 head on the event
+/// bus.
+#[derive(Debug)]
+pub(crate) struct ExtensionsActor {
+    /// The extensions to run.
+    extensions: Vec<Arc<dyn NodeExtension>>,
+}
+
+impl ExtensionsActor {
+    /// Creates a new [`ExtensionsActor`] running the given extensions.
+    pub(crate) const fn new(extensions: Vec<Arc<dyn NodeExtension>>) -> Self {
+        Self { extensions }
+    }
+}
+
+/// The communication context used by the [`ExtensionsActor`].
+#[derive(Debug)]
+pub(crate) struct ExtensionsContext {
+    /// The sender of the event bus of the node.
+    pub(crate) events: broadcast::Sender<NodeEvent>,
+    /// The receiver of the L1 head observed by the node.
+    pub(crate) l1_head: watch::Receiver<Option<BlockInfo>>,
+    /// The sender of queries to the engine.
+    pub(crate) engine_queries: mpsc::Sender<EngineQueries>,
+    /// The cancellation token, shared between all tasks.
+    pub(crate) cancellation: CancellationToken,
+}
+
+impl CancellableContext for ExtensionsContext {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+#[async_trait]
+impl NodeActor for ExtensionsActor {
+    type Error = String;
+    type StartData = ExtensionsContext;
+
+    async fn start(
+        self,
+        ExtensionsContext { events, mut l1_head, engine_queries, cancellation }: Self::StartData,
+    ) -> Result<(), Self::Error> {
+        let mut extensions = JoinSet::new();
+        for extension in &self.extensions {
+            extensions.spawn(extension.launch(ExtensionContext {
+                events: events.subscribe(),
+                engine_queries: engine_queries.clone(),
+                cancellation: cancellation.clone(),
+            }));
+        }
+
+        loop {
+            tokio::select! {
+                _ = cancellation.cancelled() => break,
+                changed = l1_head.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    if let Some(head) = *l1_head.borrow_and_update() {
+                        // Events are dropped while there are no subscribers.
+                        let _ = events.send(NodeEvent::L1Head(head));
+                    }
+                }
+                Some(result) = extensions.join_next() => match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => {
+                        error!(target: "extensions", "Node extension failed: {e}");
+                        return Err(e);
+                    }
+                    Err(e) => return Err(format!("Node extension join error: {e}")),
+                },
+            }
+        }
+
+        // Let the extensions observe the cancellation and stop.
+        while let Some(result) = extensions.join_next().await {
+            if let Ok(Err(e)) = result {
+                warn!(target: "extensions", "Node extension failed while stopping: {e}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Publishes the values of a watch channel on the event bus, until the channel is closed or the
+/// node is cancelled.
+pub(crate) async fn publish<T: Clone>(
+    mut from: watch::Receiver<T>,
+    events: broadcast::Sender<NodeEvent>,
+    to_event: fn(T) -> NodeEvent,
+    cancellation: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = cancellation.cancelled() => return,
+            changed = from.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                let value = from.borrow_and_update().clone();
+                // Events are dropped while there are no subscribers.
+                let _ = events.send(to_event(value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct CountHeads(mpsc::Sender<u64>);
+
+    #[async_trait]
+    impl NodeActor for CountHeads {
+        type Error = String;
+        type StartData = ExtensionContext;
+
+        async fn start(self, mut context: ExtensionContext) -> Result<(), Self::Error> {
+            loop {
+                tokio::select! {
+                    _ = context.cancellation.cancelled() => return Ok(()),
+                    event = context.events.recv() => {
+                        if let Ok(NodeEvent::L1Head(head)) = event {
+                            self.0.send(head.number).await.map_err(|e| e.to_string())?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extensions_receive_l1_heads() {
+        let (heads_tx, mut heads_rx) = mpsc::channel(8);
+        let actor =
+            ExtensionsActor::new(vec![Arc::new(CountHeads(heads_tx)) as Arc<dyn NodeExtension>]);
+
+        let (events, _) = broadcast::channel(8);
+        let (l1_head_tx, l1_head) = watch::channel(None);
+        let (engine_queries, _engine_queries_rx) = mpsc::channel(1);
+        let cancellation = CancellationToken::new();
+        let task = tokio::spawn(actor.start(ExtensionsContext {
+            events: events.clone(),
+            l1_head,
+            engine_queries,
+            cancellation: cancellation.clone(),
+        }));
+
+        // Wait for the extension to subscribe to the event bus.
+        while events.receiver_count() == 0 {
+            tokio::task::yield_now().await;
+        }
+        l1_head_tx.send_replace(Some(BlockInfo { number: 7, ..Default::default() }));
+        assert_eq!(heads_rx.recv().await, Some(7));
+
+        cancellation.cancel();
+        assert_eq!(task.await.unwrap(), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_failed_extension_stops_the_actor() {
+        #[derive(Debug, Clone)]
+        struct Fail;
+
+        #[async_trait]
+        impl NodeActor for Fail {
+            type Error = &'static str;
+            type StartData = ExtensionContext;
+
+            async fn start(self, _: ExtensionContext) -> Result<(), Self::Error> {
+                Err("boom")
+            }
+        }
+
+        let actor = ExtensionsActor::new(vec![Arc::new(Fail) as Arc<dyn NodeExtension>]);
+        let (events, _) = broadcast::channel(8);
+        let (_l1_head_tx, l1_head) = watch::channel(None);
+        let (engine_queries, _engine_queries_rx) = mpsc::channel(1);
+        let result = actor
+            .start(ExtensionsContext {
+                events,
+                l1_head,
+                engine_queries,
+                cancellation: CancellationToken::new(),
+            })
+            .await;
+        assert_eq!(result, Err("\"boom\"".to_string()));
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     NodeActor,
-    actors::{CancellableContext, l1_watcher::error::L1WatcherActorError},
+    actors::{CancellableContext, L1BlockSource, l1_watcher::error::L1WatcherActorError},
 };
 use alloy_eips::BlockId;
 use alloy_primitives::Address;
@@ -12,7 +12,8 @@ use async_trait::async_trait;
 use futures::{Stream, StreamExt};
 use kona_genesis::{RollupConfig, SystemConfigLog, SystemConfigUpdate, UnsafeBlockSignerUpdate};
 use kona_protocol::BlockInfo;
-use kona_rpc::{L1State, L1WatcherQueries};
+use kona_providers_alloy::OnlineBeaconClient;
+use kona_rpc::{BlobAvailability, L1State, L1WatcherQueries};
 use std::sync::Arc;
 use tokio::{
     select,
@@ -49,6 +50,11 @@ where
     head_stream: BS,
     /// A stream over the finalized block accepted as canonical.
     finalized_stream: BS,
+    /// The [`L1BlockSource`] the streams come from, if any, queried for the system config logs
+    /// before falling back to the L1 provider.
+    block_source: Option<Arc<dyn L1BlockSource>>,
+    /// The L1 beacon client, queried for the availability of blobs.
+    beacon_client: Option<OnlineBeaconClient>,
 }
 impl<BS, L1P> L1WatcherActor<BS, L1P>
 where
@@ -78,8 +84,20 @@ where
             cancellation,
             head_stream,
             finalized_stream,
+            block_source: None,
+            beacon_client: None,
         }
     }
+
+    /// Sets the [`L1BlockSource`] the head and finalized streams come from.
+    pub fn with_block_source(self, block_source: Arc<dyn L1BlockSource>) -> Self {
+        Self { block_source: Some(block_source), ..self }
+    }
+
+    /// Sets the L1 beacon client queried for the availability of blobs.
+    pub fn with_beacon_client(self, beacon_client: OnlineBeaconClient) -> Self {
+        Self { beacon_client: Some(beacon_client), ..self }
+    }
 }
 
 #[async_trait]
@@ -119,8 +137,13 @@ where
                         // Build the [`SystemConfigUpdate`] from the log.
                         // If the update is an Unsafe block signer update, send the address
                         // to the block signer sender.
-                        let filter_address =  self.rollup_config.l1_system_config_address;
-                        let logs = self.l1_provider .get_logs(&alloy_rpc_types_eth::Filter::new().address(filter_address).select(head_block_info.hash)).await?;
+                        let logs = match self.block_source.as_ref().and_then(|source| source.system_config_logs(head_block_info.hash)) {
+                            Some(logs) => logs,
+                            None => {
+                                let filter_address = self.rollup_config.l1_system_config_address;
+                                self.l1_provider.get_logs(&alloy_rpc_types_eth::Filter::new().address(filter_address).select(head_block_info.hash)).await?
+                            }
+                        };
                         let ecotone_active = self.rollup_config.is_ecotone_active(head_block_info.timestamp);
                         for log in logs {
                             let sys_cfg_log = SystemConfigLog::new(log.into(), ecotone_active);
@@ -189,6 +212,34 @@ where
                                 warn!(target: "l1_watcher", error = ?e, "Failed to send L1 state to the query sender");
                             }
                         }
+                        L1WatcherQueries::Receipts(hash, sender) => {
+                            let receipts = match self.l1_provider.get_block_receipts(BlockId::hash(hash)).await {
+                                Ok(receipts) => receipts,
+                                Err(e) => {
+                                    warn!(target: "l1_watcher", error = ?e, %hash, "failed to query l1 provider for block receipts");
+                                    None
+                                }
+                            };
+                            if sender.send(receipts).is_err() {
+                                warn!(target: "l1_watcher", "Failed to send L1 receipts to the query sender");
+                            }
+                        }
+                        L1WatcherQueries::BlobsAvailable(slot, sender) => {
+                            let availability = match &self.beacon_client {
+                                Some(beacon_client) => match beacon_client.blobs_available(slot).await {
+                                    Ok(true) => BlobAvailability::Available,
+                                    Ok(false) => BlobAvailability::Unavailable,
+                                    Err(e) => {
+                                        warn!(target: "l1_watcher", error = ?e, slot, "failed to query beacon client for blob availability");
+                                        BlobAvailability::Unknown
+                                    }
+                                },
+                                None => BlobAvailability::Unknown,
+                            };
+                            if sender.send(availability).is_err() {
+                                warn!(target: "l1_watcher", "Failed to send blob availability to the query sender");
+                            }
+                        }
                     }
                 },
                 None => {
@@ -4,5 +4,15 @@ pub use actor::L1WatcherActor;
 mod blockstream;
 pub use blockstream::BlockStream;
 
+mod source;
+pub use source::L1BlockSource;
+pub(crate) use source::L1BlockSourceBlocks;
+
+mod ws;
+pub use ws::WsL1BlockSource;
+
+mod shared;
+pub use shared::{SharedL1Source, SharedL1Watcher};
+
 mod error;
 pub use error::L1WatcherActorError;
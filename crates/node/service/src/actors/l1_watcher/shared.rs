@@ -0,0 +1,399 @@
+//! The [`SharedL1Watcher`], polling the L1 chain once for several rollup nodes.
+
+use crate::actors::{BlockStream, L1BlockSource, L1WatcherActorError};
+use alloy_consensus::{Transaction, TxEnvelope};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::{Filter, Log};
+use futures::{StreamExt, future, stream::BoxStream};
+use kona_genesis::{BatchInboxActivation, RollupConfig};
+use kona_protocol::BlockInfo;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+use tokio::{select, sync::watch};
+use tokio_stream::wrappers::WatchStream;
+use tokio_util::sync::CancellationToken;
+
+/// The number of recent L1 heads whose batch inbox transactions are held for derivation, which
+/// may lag the head.
+const RECENT_BLOCKS: usize = 64;
+
+/// An L1 head, with the system config logs emitted in it by the subscribed chains.
+#[derive(Debug, Clone)]
+struct SharedL1Head {
+    /// The L1 head block.
+    block: BlockInfo,
+    /// The logs emitted in the block by the system config contracts of the subscribed chains, if
+    /// they could be fetched.
+    logs: Option<Arc<Vec<Log>>>,
+}
+
+/// A recent L1 head, with the transactions sent to the batch inboxes of the subscribed chains.
+#[derive(Debug)]
+struct SharedL1Block {
+    /// The L1 block.
+    block: BlockInfo,
+    /// The transactions of the block sent to the batch inbox of any subscribed chain.
+    transactions: Vec<TxEnvelope>,
+}
+
+/// The recent L1 heads held by a [`SharedL1Watcher`], oldest first.
+type RecentBlocks = Arc<Mutex<VecDeque<SharedL1Block>>>;
+
+/// The batch inbox of a chain subscribed to a [`SharedL1Watcher`].
+#[derive(Debug, Clone)]
+struct BatchInbox {
+    /// The batch inbox address of the rollup config.
+    address: Address,
+    /// The rotations of the batch inbox address.
+    schedule: Vec<BatchInboxActivation>,
+}
+
+impl BatchInbox {
+    /// Returns the batch inbox address in effect at the given L1 block number.
+    fn address_at(&self, l1_block: u64) -> Address {
+        BatchInboxActivation::active_at(&self.schedule, l1_block)
+            .map_or(self.address, |entry| entry.inbox_address)
+    }
+}
+
+/// An L1 watcher shared between several rollup nodes following the same L1 chain.
+///
+/// Running several chains of a superchain in the same process would otherwise poll the same L1
+/// RPC once per chain. The [`SharedL1Watcher`] polls the L1 head and finalized blocks once, fetches
+/// the system config logs of every subscribed chain with a single `eth_getLogs` request per head,
+/// and the transactions of each head with a single `eth_getBlockByHash` request. Both are fanned
+/// out to a [`SharedL1Source`] per chain, which only exposes the logs of its own chain's system
+/// config contract and the transactions sent to its own batch inbox. The transactions of the last
+/// [`RECENT_BLOCKS`] heads are held, and served to the derivation pipeline of each chain.
+///
+/// Chains subscribe with [`SharedL1Watcher::subscribe`] before the watcher is started with
+/// [`SharedL1Watcher::start`], and use their [`SharedL1Source`] as the [`L1BlockSource`] of their
+/// node, e.g. with [`RollupNodeBuilder::with_shared_l1_watcher`].
+///
+/// [`RollupNodeBuilder::with_shared_l1_watcher`]: crate::RollupNodeBuilder::with_shared_l1_watcher
+#[derive(Debug)]
+pub struct SharedL1Watcher<L1P: Provider> {
+    /// The L1 provider.
+    l1_provider: L1P,
+    /// The interval at which the L1 head is polled.
+    head_poll_interval: Duration,
+    /// The interval at which the L1 finalized block is polled.
+    finalized_poll_interval: Duration,
+    /// The system config addresses of the subscribed chains.
+    system_config_addresses: Vec<Address>,
+    /// The batch inboxes of the subscribed chains.
+    batch_inboxes: Vec<BatchInbox>,
+    /// The latest L1 head.
+    head: watch::Sender<Option<SharedL1Head>>,
+    /// The recent L1 heads, with their batch inbox transactions.
+    blocks: RecentBlocks,
+    /// The latest L1 finalized block.
+    finalized: watch::Sender<Option<BlockInfo>>,
+}
+
+impl<L1P: Provider + Clone> SharedL1Watcher<L1P> {
+    /// Creates a new [`SharedL1Watcher`] polling the given L1 provider.
+    pub fn new(
+        l1_provider: L1P,
+        head_poll_interval: Duration,
+        finalized_poll_interval: Duration,
+    ) -> Self {
+        Self {
+            l1_provider,
+            head_poll_interval,
+            finalized_poll_interval,
+            system_config_addresses: Vec::new(),
+            batch_inboxes: Vec::new(),
+            head: watch::channel(None).0,
+            blocks: RecentBlocks::default(),
+            finalized: watch::channel(None).0,
+        }
+    }
+
+    /// Subscribes the chain of the given rollup config to the watcher, returning the
+    /// [`L1BlockSource`] of its node.
+    ///
+    /// The source only exposes the logs emitted by the chain's system config contract, and the
+    /// transactions sent to the chain's batch inbox.
+    pub fn subscribe(&mut self, config: &RollupConfig) -> SharedL1Source {
+        let system_config_address = config.l1_system_config_address;
+        if !self.system_config_addresses.contains(&system_config_address) {
+            self.system_config_addresses.push(system_config_address);
+        }
+        let batch_inbox = BatchInbox {
+            address: config.batch_inbox_address,
+            schedule: config.batch_inbox_schedule.clone(),
+        };
+        self.batch_inboxes.push(batch_inbox.clone());
+        SharedL1Source {
+            system_config_address,
+            batch_inbox,
+            head: self.head.subscribe(),
+            finalized: self.finalized.subscribe(),
+            blocks: Arc::clone(&self.blocks),
+        }
+    }
+
+    /// Polls the L1 chain and fans the updates out to the subscribed chains, until cancelled.
+    pub async fn start(
+        self,
+        cancellation: CancellationToken,
+    ) -> Result<(), L1WatcherActorError<BlockInfo>> {
+        let mut head_stream = BlockStream::new_as_stream(
+            self.l1_provider.clone(),
+            BlockNumberOrTag::Latest,
+            self.head_poll_interval,
+        )
+        .map_err(|_| L1WatcherActorError::StreamEnded)?;
+        let mut finalized_stream = BlockStream::new_as_stream(
+            self.l1_provider.clone(),
+            BlockNumberOrTag::Finalized,
+            self.finalized_poll_interval,
+        )
+        .map_err(|_| L1WatcherActorError::StreamEnded)?;
+
+        loop {
+            select! {
+                _ = cancellation.cancelled() => {
+                    info!(target: "l1_watcher", "Received shutdown signal. Exiting shared L1 watcher task.");
+                    return Ok(());
+                }
+                head = head_stream.next() => {
+                    let Some(block) = head else {
+                        return Err(L1WatcherActorError::StreamEnded);
+                    };
+                    let logs = self.system_config_logs(block.hash).await.map(Arc::new);
+                    self.fetch_batch_inbox_transactions(block).await;
+                    self.head.send_replace(Some(SharedL1Head { block, logs }));
+                }
+                finalized = finalized_stream.next() => {
+                    let Some(block) = finalized else {
+                        return Err(L1WatcherActorError::StreamEnded);
+                    };
+                    self.finalized.send_replace(Some(block));
+                }
+            }
+        }
+    }
+
+    /// Fetches the logs emitted in the given block by the system config contracts of all
+    /// subscribed chains.
+    async fn system_config_logs(&self, block_hash: B256) -> Option<Vec<Log>> {
+        if self.system_config_addresses.is_empty() {
+            return Some(Vec::new());
+        }
+        let filter = Filter::new().address(self.system_config_addresses.clone()).select(block_hash);
+        match self.l1_provider.get_logs(&filter).await {
+            Ok(logs) => Some(logs),
+            Err(e) => {
+                // The subscribed chains fall back to fetching their own logs.
+                warn!(target: "l1_watcher", error = ?e, "Failed to fetch system config logs");
+                None
+            }
+        }
+    }
+
+    /// Fetches the given head, and holds its transactions sent to the batch inbox of any
+    /// subscribed chain. The subscribed chains fetch the block themselves if this fails.
+    async fn fetch_batch_inbox_transactions(&self, block: BlockInfo) {
+        if self.batch_inboxes.is_empty() {
+            return;
+        }
+        let fetched = match self.l1_provider.get_block_by_hash(block.hash).full().await {
+            Ok(Some(fetched)) => {
+                fetched.into_consensus().map_transactions(|tx| tx.inner.into_inner())
+            }
+            Ok(None) => {
+                warn!(target: "l1_watcher", hash = %block.hash, "L1 head not found");
+                return;
+            }
+            Err(e) => {
+                warn!(target: "l1_watcher", error = ?e, "Failed to fetch the L1 head");
+                return;
+            }
+        };
+        if fetched.header.hash_slow() != block.hash {
+            warn!(target: "l1_watcher", hash = %block.hash, "L1 head does not match its hash");
+            return;
+        }
+
+        let inboxes = self
+            .batch_inboxes
+            .iter()
+            .map(|inbox| inbox.address_at(block.number))
+            .collect::<Vec<_>>();
+        let transactions = fetched
+            .body
+            .transactions
+            .into_iter()
+            .filter(|tx| tx.to().is_some_and(|to| inboxes.contains(&to)))
+            .collect();
+
+        let mut blocks = self.blocks.lock().unwrap_or_else(PoisonError::into_inner);
+        if blocks.len() == RECENT_BLOCKS {
+            blocks.pop_front();
+        }
+        blocks.push_back(SharedL1Block { block, transactions });
+    }
+}
+
+/// The [`L1BlockSource`] of a chain subscribed to a [`SharedL1Watcher`].
+#[derive(Debug, Clone)]
+pub struct SharedL1Source {
+    /// The address of the chain's system config contract.
+    system_config_address: Address,
+    /// The chain's batch inbox.
+    batch_inbox: BatchInbox,
+    /// The latest L1 head.
+    head: watch::Receiver<Option<SharedL1Head>>,
+    /// The latest L1 finalized block.
+    finalized: watch::Receiver<Option<BlockInfo>>,
+    /// The recent L1 heads, with the batch inbox transactions of all subscribed chains.
+    blocks: RecentBlocks,
+}
+
+impl L1BlockSource for SharedL1Source {
+    fn head_stream(&self) -> BoxStream<'static, BlockInfo> {
+        WatchStream::new(self.head.clone())
+            .filter_map(|head| future::ready(head.map(|head| head.block)))
+            .boxed()
+    }
+
+    fn finalized_stream(&self) -> BoxStream<'static, BlockInfo> {
+        WatchStream::new(self.finalized.clone()).filter_map(future::ready).boxed()
+    }
+
+    fn system_config_logs(&self, block_hash: B256) -> Option<Vec<Log>> {
+        let head = self.head.borrow();
+        let head = head.as_ref().filter(|head| head.block.hash == block_hash)?;
+        let logs = head.logs.as_ref()?;
+        Some(
+            logs.iter()
+                .filter(|log| log.address() == self.system_config_address)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn batch_inbox_transactions(&self, block_hash: B256) -> Option<(BlockInfo, Vec<TxEnvelope>)> {
+        let blocks = self.blocks.lock().unwrap_or_else(PoisonError::into_inner);
+        let block = blocks.iter().rev().find(|block| block.block.hash == block_hash)?;
+        let inbox = self.batch_inbox.address_at(block.block.number);
+        Some((
+            block.block,
+            block.transactions.iter().filter(|tx| tx.to() == Some(inbox)).cloned().collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxLegacy};
+    use alloy_primitives::{LogData, Signature, TxKind};
+    use alloy_provider::RootProvider;
+
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo { number, hash: B256::with_last_byte(number as u8), ..Default::default() }
+    }
+
+    fn log(address: Address) -> Log {
+        Log {
+            inner: alloy_primitives::Log { address, data: LogData::empty() },
+            ..Default::default()
+        }
+    }
+
+    fn tx(to: Address) -> TxEnvelope {
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy { to: TxKind::Call(to), ..Default::default() },
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    }
+
+    fn config(system_config_address: Address, batch_inbox_address: Address) -> RollupConfig {
+        RollupConfig {
+            l1_system_config_address: system_config_address,
+            batch_inbox_address,
+            ..Default::default()
+        }
+    }
+
+    fn watcher() -> SharedL1Watcher<RootProvider> {
+        let provider = RootProvider::new_http("http://localhost:8545".parse().unwrap());
+        SharedL1Watcher::new(provider, Duration::from_secs(4), Duration::from_secs(60))
+    }
+
+    #[tokio::test]
+    async fn test_sources_filter_logs_per_chain() {
+        let mut watcher = watcher();
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let source_a = watcher.subscribe(&config(a, Address::with_last_byte(0xa)));
+        let source_b = watcher.subscribe(&config(b, Address::with_last_byte(0xb)));
+
+        let mut heads_a = source_a.head_stream();
+        let mut heads_b = source_b.head_stream();
+        let logs = vec![log(a), log(b), log(b)];
+        watcher.head.send_replace(Some(SharedL1Head { block: block(1), logs: Some(logs.into()) }));
+
+        assert_eq!(heads_a.next().await, Some(block(1)));
+        assert_eq!(heads_b.next().await, Some(block(1)));
+        assert_eq!(source_a.system_config_logs(block(1).hash).unwrap().len(), 1);
+        assert_eq!(source_b.system_config_logs(block(1).hash).unwrap().len(), 2);
+
+        // Logs of a block other than the latest head are not at hand.
+        assert!(source_a.system_config_logs(block(2).hash).is_none());
+
+        // Logs that could not be fetched are left to the chains to fetch.
+        watcher.head.send_replace(Some(SharedL1Head { block: block(2), logs: None }));
+        assert!(source_a.system_config_logs(block(2).hash).is_none());
+
+        watcher.finalized.send_replace(Some(block(1)));
+        assert_eq!(source_b.finalized_stream().next().await, Some(block(1)));
+    }
+
+    #[test]
+    fn test_sources_filter_batch_inbox_transactions_per_chain() {
+        let mut watcher = watcher();
+        let (inbox_a, inbox_b, rotated_b) = (
+            Address::with_last_byte(0xa),
+            Address::with_last_byte(0xb),
+            Address::with_last_byte(0xc),
+        );
+        let source_a = watcher.subscribe(&config(Address::with_last_byte(1), inbox_a));
+        let source_b = watcher.subscribe(&RollupConfig {
+            batch_inbox_schedule: vec![BatchInboxActivation {
+                l1_block: 2,
+                inbox_address: rotated_b,
+                batcher_address: None,
+            }],
+            ..config(Address::with_last_byte(2), inbox_b)
+        });
+
+        let transactions = vec![tx(inbox_a), tx(inbox_b), tx(rotated_b), tx(inbox_a)];
+        watcher.blocks.lock().unwrap().extend([
+            SharedL1Block { block: block(1), transactions: transactions.clone() },
+            SharedL1Block { block: block(2), transactions },
+        ]);
+
+        let (info, txs) = source_a.batch_inbox_transactions(block(1).hash).unwrap();
+        assert_eq!(info, block(1));
+        assert_eq!(txs, vec![tx(inbox_a), tx(inbox_a)]);
+        assert_eq!(source_b.batch_inbox_transactions(block(1).hash).unwrap().1, vec![tx(inbox_b)]);
+
+        // The inbox of the second chain rotates at the second block.
+        assert_eq!(
+            source_b.batch_inbox_transactions(block(2).hash).unwrap().1,
+            vec![tx(rotated_b)]
+        );
+
+        // Blocks that are not held are left to the chains to fetch.
+        assert!(source_a.batch_inbox_transactions(block(3).hash).is_none());
+    }
+}
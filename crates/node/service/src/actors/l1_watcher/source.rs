@@ -0,0 +1,90 @@
+//! The [`L1BlockSource`] trait.
+
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::Log;
+use futures::stream::BoxStream;
+use kona_protocol::BlockInfo;
+use kona_providers_alloy::PrefetchedL1Blocks;
+use std::{fmt::Debug, sync::Arc};
+
+/// A source of L1 head and finalized block updates for the [`L1WatcherActor`].
+///
+/// By default, the rollup node polls the L1 RPC for new blocks with a [`BlockStream`]. An
+/// [`L1BlockSource`] lets the node be driven by another source instead, such as an execution
+/// extension running in the same process as the L1 execution client.
+///
+/// [`L1WatcherActor`]: super::L1WatcherActor
+/// [`BlockStream`]: super::BlockStream
+pub trait L1BlockSource: Debug + Send + Sync {
+    /// Returns a stream of new L1 head blocks.
+    ///
+    /// If the L1 chain reorgs, the stream yields the new head of the canonical chain.
+    fn head_stream(&self) -> BoxStream<'static, BlockInfo>;
+
+    /// Returns a stream of new finalized L1 blocks.
+    fn finalized_stream(&self) -> BoxStream<'static, BlockInfo>;
+
+    /// Returns the logs emitted by the chain's system config contract in the given L1 block, if
+    /// the source already holds them.
+    ///
+    /// If [`None`] is returned, the [`L1WatcherActor`] fetches the logs from the L1 RPC.
+    ///
+    /// [`L1WatcherActor`]: super::L1WatcherActor
+    fn system_config_logs(&self, _block_hash: B256) -> Option<Vec<Log>> {
+        None
+    }
+
+    /// Returns the info of the given L1 block and its transactions sent to the chain's batch
+    /// inbox, if the source already holds them.
+    ///
+    /// If [`None`] is returned, the derivation pipeline fetches the block from the L1 RPC.
+    fn batch_inbox_transactions(&self, _block_hash: B256) -> Option<(BlockInfo, Vec<TxEnvelope>)> {
+        None
+    }
+
+    /// Returns the header of the given L1 block, if the source already holds it.
+    ///
+    /// If [`None`] is returned, the derivation pipeline fetches the header from the L1 RPC.
+    fn header(&self, _block_hash: B256) -> Option<Header> {
+        None
+    }
+
+    /// Returns the info of the canonical L1 block with the given number, if the source already
+    /// holds it.
+    ///
+    /// If [`None`] is returned, the derivation pipeline fetches the block from the L1 RPC.
+    fn block_info_by_number(&self, _number: u64) -> Option<BlockInfo> {
+        None
+    }
+
+    /// Returns the receipts of the given L1 block, if the source already holds them.
+    ///
+    /// If [`None`] is returned, the derivation pipeline fetches the receipts from the L1 RPC.
+    fn receipts(&self, _block_hash: B256) -> Option<Vec<Receipt>> {
+        None
+    }
+}
+
+/// Serves the L1 blocks held by an [`L1BlockSource`] to the L1 provider of the derivation
+/// pipeline.
+#[derive(Debug)]
+pub(crate) struct L1BlockSourceBlocks(pub(crate) Arc<dyn L1BlockSource>);
+
+impl PrefetchedL1Blocks for L1BlockSourceBlocks {
+    fn batch_inbox_transactions(&self, hash: B256) -> Option<(BlockInfo, Vec<TxEnvelope>)> {
+        self.0.batch_inbox_transactions(hash)
+    }
+
+    fn header(&self, hash: B256) -> Option<Header> {
+        self.0.header(hash)
+    }
+
+    fn block_info_by_number(&self, number: u64) -> Option<BlockInfo> {
+        self.0.block_info_by_number(number)
+    }
+
+    fn receipts(&self, hash: B256) -> Option<Vec<Receipt>> {
+        self.0.receipts(hash)
+    }
+}
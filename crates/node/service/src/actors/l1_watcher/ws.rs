@@ -0,0 +1,200 @@
+//! An [`L1BlockSource`] streaming L1 heads from a WebSocket `newHeads` subscription.
+
+use super::{BlockStream, L1BlockSource};
+use crate::service::{FINALIZED_STREAM_POLL_INTERVAL, HEAD_STREAM_POLL_INTERVAL};
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{Provider, ProviderBuilder, RootProvider, WsConnect};
+use alloy_rpc_types_eth::Header;
+use alloy_transport::TransportResult;
+use async_stream::stream;
+use futures::{Stream, StreamExt, stream::BoxStream};
+use kona_protocol::BlockInfo;
+use std::{ops::Range, time::Duration};
+use tokio::time::Instant;
+use url::Url;
+
+/// The delay before resubscribing after the first failure of the subscription.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The maximum delay between resubscription attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The maximum number of missed heads fetched to fill a gap in the subscription. Only the new
+/// head is yielded past this gap.
+const MAX_GAP: u64 = 256;
+
+/// An [`L1BlockSource`] streaming L1 heads from a WebSocket `newHeads` subscription, rather than
+/// polling the L1 RPC for them.
+///
+/// When the subscription fails or ends, the heads are polled over HTTP until the source
+/// resubscribes, with an exponential backoff between the attempts. Heads missed while the
+/// subscription was down are fetched by number, so that every head between the last yielded one
+/// and the new one is yielded in order.
+///
+/// There is no subscription to finalized blocks, so these are always polled over HTTP.
+#[derive(Debug, Clone)]
+pub struct WsL1BlockSource {
+    /// The URL of the WebSocket endpoint of the L1 RPC.
+    ws_url: Url,
+    /// The HTTP provider of the L1 RPC, used to fetch missed heads and while the subscription is
+    /// down.
+    http: RootProvider,
+}
+
+impl WsL1BlockSource {
+    /// Creates a new [`WsL1BlockSource`] subscribing to the given WebSocket endpoint, and falling
+    /// back to the given HTTP endpoint of the L1 RPC.
+    pub fn new(ws_url: Url, http_url: Url) -> Self {
+        Self { ws_url, http: RootProvider::new_http(http_url) }
+    }
+
+    /// Subscribes to new L1 heads, returning the subscription along with the provider, which
+    /// must be kept alive for as long as the subscription is used.
+    async fn subscribe(
+        &self,
+    ) -> TransportResult<(impl Provider, impl Stream<Item = Header> + Unpin + Send)> {
+        let provider =
+            ProviderBuilder::new().connect_ws(WsConnect::new(self.ws_url.as_str())).await?;
+        let subscription = provider.subscribe_blocks().await?;
+        Ok((provider, Box::pin(subscription.into_stream())))
+    }
+
+    /// Polls the latest L1 head over HTTP.
+    async fn poll(&self) -> Option<BlockInfo> {
+        match self.http.get_block_by_number(BlockNumberOrTag::Latest).await {
+            Ok(block) => block.map(|block| header_info(&block.header)),
+            Err(err) => {
+                warn!(target: "l1_watcher", %err, "Failed to poll the L1 head");
+                None
+            }
+        }
+    }
+
+    /// Returns the heads to yield for a new head: the heads missed since the last yielded head,
+    /// followed by the new head.
+    async fn catch_up(&self, last: Option<BlockInfo>, head: BlockInfo) -> Vec<BlockInfo> {
+        if last == Some(head) {
+            return Vec::new();
+        }
+
+        let Some(missed) = last.and_then(|last| missed_heads(last, head)) else {
+            return vec![head];
+        };
+        if missed.end - missed.start > MAX_GAP {
+            warn!(target: "l1_watcher", ?missed, "Too many missed L1 heads, skipping to the new head");
+            return vec![head];
+        }
+
+        debug!(target: "l1_watcher", ?missed, "Fetching missed L1 heads");
+        let mut heads = Vec::new();
+        for number in missed {
+            match self.http.get_block_by_number(number.into()).await {
+                Ok(Some(block)) => heads.push(header_info(&block.header)),
+                Ok(None) => {
+                    warn!(target: "l1_watcher", number, "Missed L1 head not found");
+                    break;
+                }
+                Err(err) => {
+                    warn!(target: "l1_watcher", number, %err, "Failed to fetch missed L1 head");
+                    break;
+                }
+            }
+        }
+        heads.push(head);
+        heads
+    }
+}
+
+impl L1BlockSource for WsL1BlockSource {
+    fn head_stream(&self) -> BoxStream<'static, BlockInfo> {
+        let source = self.clone();
+        Box::pin(stream! {
+            let mut last = None;
+            let mut backoff = INITIAL_BACKOFF;
+
+            loop {
+                match source.subscribe().await {
+                    Ok((_provider, mut headers)) => {
+                        info!(target: "l1_watcher", url = %source.ws_url, "Subscribed to L1 heads");
+                        backoff = INITIAL_BACKOFF;
+                        while let Some(header) = headers.next().await {
+                            for head in source.catch_up(last, header_info(&header)).await {
+                                last = Some(head);
+                                yield head;
+                            }
+                        }
+                        warn!(target: "l1_watcher", "L1 head subscription ended");
+                    }
+                    Err(err) => {
+                        warn!(target: "l1_watcher", %err, ?backoff, "Failed to subscribe to L1 heads");
+                    }
+                }
+
+                // Poll over HTTP until the next attempt to resubscribe.
+                let resubscribe_at = Instant::now() + backoff;
+                loop {
+                    let now = Instant::now();
+                    if now >= resubscribe_at {
+                        break;
+                    }
+                    let poll_interval = Duration::from_secs(HEAD_STREAM_POLL_INTERVAL);
+                    tokio::time::sleep(poll_interval.min(resubscribe_at - now)).await;
+                    let Some(head) = source.poll().await else { continue };
+                    for head in source.catch_up(last, head).await {
+                        last = Some(head);
+                        yield head;
+                    }
+                }
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        })
+    }
+
+    fn finalized_stream(&self) -> BoxStream<'static, BlockInfo> {
+        BlockStream::new_as_stream(
+            self.http.clone(),
+            BlockNumberOrTag::Finalized,
+            Duration::from_secs(FINALIZED_STREAM_POLL_INTERVAL),
+        )
+        .expect("Finalized is a block tag")
+        .boxed()
+    }
+}
+
+/// Returns the [`BlockInfo`] of an RPC block header.
+const fn header_info(header: &Header) -> BlockInfo {
+    BlockInfo::new(
+        header.hash,
+        header.inner.number,
+        header.inner.parent_hash,
+        header.inner.timestamp,
+    )
+}
+
+/// Returns the numbers of the heads missed between the last yielded head and the new one, if any.
+///
+/// A new head at or below the last one replaces it after a reorg, and misses no heads.
+fn missed_heads(last: BlockInfo, head: BlockInfo) -> Option<Range<u64>> {
+    let missed = last.number + 1..head.number;
+    (!missed.is_empty()).then_some(missed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo::new(B256::with_last_byte(number as u8), number, B256::ZERO, number * 12)
+    }
+
+    #[test]
+    fn test_missed_heads() {
+        assert_eq!(missed_heads(block(10), block(11)), None);
+        assert_eq!(missed_heads(block(10), block(14)), Some(11..14));
+
+        // Reorgs replace the last head.
+        assert_eq!(missed_heads(block(10), block(10)), None);
+        assert_eq!(missed_heads(block(10), block(8)), None);
+    }
+}
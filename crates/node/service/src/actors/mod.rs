@@ -7,22 +7,35 @@ pub use traits::{CancellableContext, NodeActor};
 
 mod engine;
 pub use engine::{
-    BlockBuildingClient, BlockEngineError, BlockEngineResult, BuildRequest, EngineActor,
-    EngineConfig, EngineContext, EngineError, EngineInboundData, L2Finalizer,
-    QueuedBlockBuildingClient, ResetRequest, SealRequest,
+    AutoSyncConfig, BlockBuildingClient, BlockEngineError, BlockEngineResult, BuildRequest,
+    CheckpointBlock, CheckpointConfig, CheckpointError, DerivationHaltConfig,
+    DerivationLatencyTracker, DerivationTimings, EngineActor, EngineActorClient, EngineConfig,
+    EngineContext, EngineError, EngineInboundData, L2Finalizer, QueuedBlockBuildingClient,
+    ResetRequest, SealRequest,
 };
 
+mod origins;
+pub use origins::DerivationOriginTracker;
+
+pub(crate) mod extension;
+pub use extension::{ExtensionContext, NodeEvent, NodeExtension};
+
 mod rpc;
-pub use rpc::{RpcActor, RpcActorError, RpcContext};
+pub use rpc::{RpcActor, RpcActorError, RpcContext, RpcTlsError};
 
 mod derivation;
 pub use derivation::{
-    DerivationActor, DerivationBuilder, DerivationContext, DerivationError,
-    DerivationInboundChannels, DerivationState, InboundDerivationMessage, PipelineBuilder,
+    AltDaProviders, DerivationActor, DerivationBuilder, DerivationContext, DerivationError,
+    DerivationInboundChannels, DerivationState, DerivedAttributes, InboundDerivationMessage,
+    PipelineBuilder,
 };
 
 mod l1_watcher;
-pub use l1_watcher::{BlockStream, L1WatcherActor, L1WatcherActorError};
+pub(crate) use l1_watcher::L1BlockSourceBlocks;
+pub use l1_watcher::{
+    BlockStream, L1BlockSource, L1WatcherActor, L1WatcherActorError, SharedL1Source,
+    SharedL1Watcher, WsL1BlockSource,
+};
 
 mod network;
 pub use network::{
@@ -31,11 +44,39 @@ pub use network::{
     QueuedUnsafePayloadGossipClient, UnsafePayloadGossipClient, UnsafePayloadGossipClientError,
 };
 
+mod batcher;
+pub use batcher::{
+    BatcherActor, BatcherActorError, BatcherConfig, ChannelBuilder, ChannelData,
+    DataAvailabilityType,
+};
+
+mod proposer;
+pub use proposer::{ProposalTarget, ProposerActor, ProposerActorError, ProposerConfig};
+
+mod pruning_hint;
+pub use pruning_hint::{
+    PruningHint, PruningHintActor, PruningHintActorError, PruningHintConfig, PruningHintContext,
+};
+
+mod archive;
+pub use archive::{
+    ArchiveActor, ArchiveActorError, ArchiveAttributesSource, ArchiveConfig, ArchiveContext,
+    ArchiveTarget, ArchivedAttributes, S3Target,
+};
+
+mod snapshot;
+pub use snapshot::{
+    NodeSnapshot, SnapshotActor, SnapshotActorError, SnapshotConfig, SnapshotContext,
+    SnapshotTarget,
+};
+
 mod sequencer;
 pub use sequencer::{
-    Conductor, ConductorClient, ConductorError, DelayedL1OriginSelectorProvider, L1OriginSelector,
-    L1OriginSelectorError, L1OriginSelectorProvider, OriginSelector, QueuedSequencerAdminAPIClient,
-    SequencerActor, SequencerActorError, SequencerAdminQuery, SequencerConfig,
+    Conductor, ConductorClient, ConductorError, DaLimits, DaLimitsClient, DaThrottle,
+    DaThrottleConfig, DaThrottlePolicy, DelayedL1OriginSelectorProvider, L1OriginSelector,
+    L1OriginSelectorError, L1OriginSelectorProvider, LinearDaThrottle, OriginSelector,
+    PayloadBuildConfig, QueuedSequencerAdminAPIClient, SequencerActor, SequencerActorError,
+    SequencerAdminQuery, SequencerConfig,
 };
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 use alloy_primitives::Address;
 use async_trait::async_trait;
-use kona_gossip::P2pRpcRequest;
-use kona_rpc::NetworkAdminQuery;
+use kona_gossip::{PeerTargets, TracedP2pRpcRequest};
+use kona_rpc::{NetworkAdminQuery, RuntimeFlag, RuntimeFlags};
 use kona_sources::BlockSignerError;
 use libp2p::TransportError;
 use op_alloy_rpc_types_engine::{OpExecutionPayloadEnvelope, OpNetworkPayloadEnvelope};
@@ -10,7 +10,7 @@ use tokio::{self, select, sync::mpsc};
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 
 use crate::{
-    CancellableContext, NodeActor,
+    CancellableContext, MeteredSender, NodeActor,
     actors::network::{
         builder::NetworkBuilder, driver::NetworkDriverError, error::NetworkBuilderError,
     },
@@ -50,7 +50,7 @@ pub struct NetworkActor {
     /// A channel to receive the unsafe block signer address.
     pub(super) signer: mpsc::Receiver<Address>,
     /// Handler for p2p RPC Requests.
-    pub(super) p2p_rpc: mpsc::Receiver<P2pRpcRequest>,
+    pub(super) p2p_rpc: mpsc::Receiver<TracedP2pRpcRequest>,
     /// A channel to receive admin rpc requests.
     pub(super) admin_rpc: mpsc::Receiver<NetworkAdminQuery>,
     /// A channel to receive unsafe blocks and send them through the gossip layer.
@@ -63,7 +63,7 @@ pub struct NetworkInboundData {
     /// A channel to send the unsafe block signer address to the network actor.
     pub signer: mpsc::Sender<Address>,
     /// Handler for p2p RPC Requests sent to the network actor.
-    pub p2p_rpc: mpsc::Sender<P2pRpcRequest>,
+    pub p2p_rpc: mpsc::Sender<TracedP2pRpcRequest>,
     /// Handler for admin RPC Requests.
     pub admin_rpc: mpsc::Sender<NetworkAdminQuery>,
     /// A channel to send unsafe blocks to the network actor.
@@ -100,7 +100,9 @@ impl NetworkActor {
 #[derive(Debug)]
 pub struct NetworkContext {
     /// The channel used by the sequencer actor for sending unsafe blocks to the network.
-    pub blocks: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    pub blocks: MeteredSender<OpExecutionPayloadEnvelope>,
+    /// The registry of the runtime flags of the node.
+    pub runtime_flags: RuntimeFlags,
     /// Cancels the network actor.
     pub cancellation: CancellationToken,
 }
@@ -144,12 +146,13 @@ impl NodeActor for NetworkActor {
 
     async fn start(
         mut self,
-        NetworkContext { blocks, cancellation }: Self::StartData,
+        NetworkContext { blocks, runtime_flags, cancellation }: Self::StartData,
     ) -> Result<(), Self::Error> {
         let mut handler = self.builder.build()?.start().await?;
 
-        // New unsafe block channel.
-        let (unsafe_block_tx, mut unsafe_block_rx) = tokio::sync::mpsc::unbounded_channel();
+        // New unsafe block channel, sharing the alarm thresholds of the engine's inbound channel.
+        let (unsafe_block_tx, mut unsafe_block_rx) =
+            crate::metered_unbounded_channel("gossip_payloads", blocks.monitor().alarms());
 
         loop {
             select! {
@@ -187,6 +190,14 @@ impl NodeActor for NetworkActor {
                     }
                 }
                 Some(block) = self.publish_rx.recv(), if !self.publish_rx.is_closed() => {
+                    if runtime_flags.is_enabled(RuntimeFlag::DisableGossipPublish) {
+                        warn!(
+                            target: "network",
+                            "Gossip publishing is disabled, dropping unsafe payload"
+                        );
+                        continue;
+                    }
+
                     let timestamp = block.execution_payload.timestamp();
                     let selector = |handler: &kona_gossip::BlockHandler| {
                         handler.topic(timestamp)
@@ -237,10 +248,29 @@ impl NodeActor for NetworkActor {
                 _ = handler.peer_score_inspector.tick(), if handler.gossip.peer_monitoring.as_ref().is_some() => {
                     handler.handle_peer_monitoring().await;
                 },
-                Some(NetworkAdminQuery::PostUnsafePayload { payload }) = self.admin_rpc.recv(), if !self.admin_rpc.is_closed() => {
-                    debug!(target: "node::p2p", "Broadcasting unsafe payload from admin api");
-                    if unsafe_block_tx.send(payload).is_err() {
-                        warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
+                _ = handler.peer_count_inspector.tick() => {
+                    handler.rebalance_peers().await;
+                },
+                Some(query) = self.admin_rpc.recv(), if !self.admin_rpc.is_closed() => match query {
+                    NetworkAdminQuery::PostUnsafePayload { payload } => {
+                        debug!(target: "node::p2p", "Broadcasting unsafe payload from admin api");
+                        if unsafe_block_tx.send(payload).is_err() {
+                            warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
+                        }
+                    }
+                    NetworkAdminQuery::SetPeerTargets { lo, hi } => {
+                        match PeerTargets::new(lo, hi, handler.gossip.peer_targets.grace) {
+                            Ok(targets) => {
+                                handler.gossip.set_peer_targets(targets);
+                                handler.rebalance_peers().await;
+                            }
+                            Err(e) => warn!(target: "node::p2p", "Invalid peer targets from admin api: {e}"),
+                        }
+                    }
+                    NetworkAdminQuery::GetPeerTargets { sender } => {
+                        if sender.send(handler.gossip.peer_targets).is_err() {
+                            warn!(target: "node::p2p", "Failed to send peer targets through response channel");
+                        }
                     }
                 },
                 Some(req) = self.p2p_rpc.recv(), if !self.p2p_rpc.is_closed() => {
@@ -4,7 +4,9 @@ use alloy_primitives::Address;
 use discv5::Config as Discv5Config;
 use kona_disc::{Discv5Builder, LocalNode};
 use kona_genesis::RollupConfig;
-use kona_gossip::{GaterConfig, GossipDriverBuilder};
+use kona_gossip::{
+    GaterConfig, GossipDriverBuilder, LatencyPreference, PayloadValidation, PeerTargets,
+};
 use kona_peers::{BootNodes, BootStoreFile, PeerMonitoring, PeerScoreLevel};
 use kona_sources::BlockSigner;
 use libp2p::{Multiaddr, identity::Keypair};
@@ -43,6 +45,7 @@ impl From<NetworkConfig> for NetworkBuilder {
             config.gossip_signer,
         )
         .with_enr_update(config.enr_update)
+        .with_gossip_quic_address(config.gossip_quic_address)
         .with_discovery_randomize(config.discovery_randomize)
         .with_bootstore(config.bootstore)
         .with_bootnodes(config.bootnodes)
@@ -52,6 +55,10 @@ impl From<NetworkConfig> for NetworkBuilder {
         .with_peer_monitoring(config.monitor_peers)
         .with_topic_scoring(config.topic_scoring)
         .with_gater_config(config.gater_config)
+        .with_peer_targets(config.peer_targets)
+        .with_latency_preference(config.latency_preference)
+        .with_payload_validation(config.payload_validation)
+        .with_recent_payloads_capacity(config.recent_payloads)
     }
 }
 
@@ -123,6 +130,27 @@ impl NetworkBuilder {
         Self { gossip: self.gossip.with_topic_scoring(topic_scoring), ..self }
     }
 
+    /// Sets the [`PeerTargets`] for the [`GossipDriverBuilder`].
+    pub fn with_peer_targets(self, targets: PeerTargets) -> Self {
+        Self { gossip: self.gossip.with_peer_targets(targets), ..self }
+    }
+
+    /// Sets the [`LatencyPreference`] for the [`GossipDriverBuilder`].
+    pub fn with_latency_preference(self, preference: LatencyPreference) -> Self {
+        Self { gossip: self.gossip.with_latency_preference(preference), ..self }
+    }
+
+    /// Sets the [`PayloadValidation`] policy for the [`GossipDriverBuilder`].
+    pub fn with_payload_validation(self, validation: PayloadValidation) -> Self {
+        Self { gossip: self.gossip.with_payload_validation(validation), ..self }
+    }
+
+    /// Sets the number of recent unsafe payloads the [`GossipDriverBuilder`] keeps to serve
+    /// late-joining peers.
+    pub fn with_recent_payloads_capacity(self, capacity: usize) -> Self {
+        Self { gossip: self.gossip.with_recent_payloads_capacity(capacity), ..self }
+    }
+
     /// Sets the peer monitoring for the [`GossipDriverBuilder`].
     pub fn with_peer_monitoring(self, peer_monitoring: Option<PeerMonitoring>) -> Self {
         Self { gossip: self.gossip.with_peer_monitoring(peer_monitoring), ..self }
@@ -153,6 +181,11 @@ impl NetworkBuilder {
         Self { gossip: self.gossip.with_address(addr), ..self }
     }
 
+    /// Sets the QUIC gossip address for the [`GossipDriverBuilder`].
+    pub fn with_gossip_quic_address(self, addr: Option<Multiaddr>) -> Self {
+        Self { gossip: self.gossip.with_quic_address(addr), ..self }
+    }
+
     /// Sets the timeout for the [`GossipDriverBuilder`].
     pub fn with_timeout(self, timeout: Duration) -> Self {
         Self { gossip: self.gossip.with_timeout(timeout), ..self }
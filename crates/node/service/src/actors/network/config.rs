@@ -3,7 +3,10 @@
 use alloy_primitives::Address;
 use kona_disc::LocalNode;
 use kona_genesis::RollupConfig;
-use kona_gossip::GaterConfig;
+use kona_gossip::{
+    DEFAULT_RECENT_PAYLOADS_CAPACITY, GaterConfig, LatencyPreference, PayloadValidation,
+    PeerTargets,
+};
 use kona_peers::{BootNodes, BootStoreFile, PeerMonitoring, PeerScoreLevel};
 use kona_sources::BlockSigner;
 use libp2p::{Multiaddr, identity::Keypair};
@@ -25,6 +28,8 @@ pub struct NetworkConfig {
     pub enr_update: bool,
     /// The gossip address.
     pub gossip_address: libp2p::Multiaddr,
+    /// The QUIC gossip address. QUIC connections are only accepted if set.
+    pub gossip_quic_address: Option<libp2p::Multiaddr>,
     /// The unsafe block signer.
     pub unsafe_block_signer: Address,
     /// The keypair.
@@ -41,6 +46,14 @@ pub struct NetworkConfig {
     pub bootstore: Option<BootStoreFile>,
     /// The configuration for the connection gater.
     pub gater_config: GaterConfig,
+    /// The target number of connected gossip peers.
+    pub peer_targets: PeerTargets,
+    /// The preference for low-latency gossip peers.
+    pub latency_preference: LatencyPreference,
+    /// How strictly blocks received over gossip are validated.
+    pub payload_validation: PayloadValidation,
+    /// The number of recent unsafe payloads kept to serve late-joining peers.
+    pub recent_payloads: usize,
     /// An optional list of bootnode ENRs to start the node with.
     pub bootnodes: BootNodes,
     /// The [`RollupConfig`].
@@ -84,12 +97,17 @@ impl NetworkConfig {
             discovery_interval: Self::DEFAULT_DISCOVERY_INTERVAL,
             discovery_randomize: Self::DEFAULT_DISCOVERY_RANDOMIZE,
             gossip_address,
+            gossip_quic_address: None,
             unsafe_block_signer,
             enr_update: true,
             keypair: Keypair::generate_secp256k1(),
             bootnodes: Default::default(),
             bootstore: Default::default(),
             gater_config: Default::default(),
+            peer_targets: Default::default(),
+            latency_preference: Default::default(),
+            payload_validation: Default::default(),
+            recent_payloads: DEFAULT_RECENT_PAYLOADS_CAPACITY,
             gossip_config: Default::default(),
             scoring: Default::default(),
             topic_scoring: Default::default(),
@@ -81,6 +81,9 @@ impl NetworkDriver {
         // We are checking the peer scores every [`PEER_SCORE_INSPECT_FREQUENCY`] seconds.
         let peer_score_inspector = tokio::time::interval(*PEER_SCORE_INSPECT_FREQUENCY);
 
+        // We are checking the connected peer count against the peer targets just as often.
+        let peer_count_inspector = tokio::time::interval(*PEER_SCORE_INSPECT_FREQUENCY);
+
         // Start the block signer if it is configured.
         let signer =
             OptionFuture::from(self.signer.map(async |s| s.start().await)).await.transpose()?;
@@ -91,6 +94,7 @@ impl NetworkDriver {
             enr_receiver,
             unsafe_block_signer_sender: self.unsafe_block_signer_sender,
             peer_score_inspector,
+            peer_count_inspector,
             signer,
         })
     }
@@ -20,11 +20,44 @@ pub struct NetworkHandler {
     pub unsafe_block_signer_sender: watch::Sender<Address>,
     /// The peer score inspector. Is used to ban peers that are below a given threshold.
     pub peer_score_inspector: tokio::time::Interval,
+    /// The peer count inspector. Is used to keep the number of connected peers within the
+    /// [`kona_gossip::PeerTargets`].
+    pub peer_count_inspector: tokio::time::Interval,
     /// A handler for the block signer.
     pub signer: Option<BlockSignerHandler>,
 }
 
 impl NetworkHandler {
+    /// Brings the number of connected gossip peers back within the [`kona_gossip::PeerTargets`].
+    ///
+    /// Peers above the high watermark are pruned. While below the low watermark, peers from the
+    /// discovery table are dialed.
+    pub(super) async fn rebalance_peers(&mut self) {
+        let pruned = self.gossip.prune_peers();
+        if !pruned.is_empty() {
+            info!(target: "network", count = pruned.len(), "Pruned peers above the high watermark");
+        }
+
+        let connected = self.gossip.connected_peers();
+        let targets = self.gossip.peer_targets;
+        if !targets.needs_peers(connected) {
+            return;
+        }
+
+        let enrs = match self.discovery.table_enrs().await {
+            Ok(enrs) => enrs,
+            Err(e) => {
+                warn!(target: "network", err = ?e, "Failed to fetch ENRs from the discovery table");
+                return;
+            }
+        };
+
+        debug!(target: "network", connected, lo = targets.lo, "Below the low watermark, dialing peers from the discovery table");
+        for enr in enrs.into_iter().take(targets.hi.saturating_sub(connected)) {
+            self.gossip.dial(enr);
+        }
+    }
+
     pub(super) async fn handle_peer_monitoring(&mut self) {
         // Inspect peer scores and ban peers that are below the threshold.
         let Some(ban_peers) = self.gossip.peer_monitoring.as_ref() else {
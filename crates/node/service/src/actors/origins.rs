@@ -0,0 +1,219 @@
+//! Tracks the batch data the derivation pipeline consumes from each L1 origin.
+
+use crate::NodeEvent;
+use kona_derive::{PipelineEvent, PipelineEventSink};
+use kona_protocol::BlockInfo;
+use kona_rpc::DerivationOriginStats;
+use std::{collections::VecDeque, sync::Mutex};
+use tokio::sync::{broadcast, watch};
+
+/// A [`PipelineEventSink`] counting the batcher data, frames, channels and batches the derivation
+/// pipeline consumes from each L1 origin.
+///
+/// Once the pipeline advances past an origin, its [`DerivationOriginStats`] are published as a
+/// [`NodeEvent::DerivationOrigin`], recorded in the [`crate::Metrics::DERIVATION_ORIGIN_CONSUMED`]
+/// counter, and kept among the stats of the most recent origins served over
+/// `debug_derivationOrigins` and summarized by `rollup_daStats`. This lets batcher operators
+/// confirm that their data was consumed, and compare their calldata and blob usage.
+#[derive(Debug)]
+pub struct DerivationOriginTracker {
+    /// The stats of the origin the pipeline is at, if any.
+    current: Mutex<Option<DerivationOriginStats>>,
+    /// Publishes the stats of the most recent origins, oldest first.
+    origins: watch::Sender<VecDeque<DerivationOriginStats>>,
+    /// The event bus of the node, on which the stats of each origin are published.
+    node_events: Option<broadcast::Sender<NodeEvent>>,
+}
+
+impl DerivationOriginTracker {
+    /// The number of L1 origins the stats are kept for.
+    pub const HISTORY: usize = 256;
+
+    /// Creates a new [`DerivationOriginTracker`], along with a receiver of the stats of the most
+    /// recent origins.
+    pub fn new() -> (Self, watch::Receiver<VecDeque<DerivationOriginStats>>) {
+        let (origins, origins_rx) = watch::channel(VecDeque::new());
+        (Self { current: Mutex::new(None), origins, node_events: None }, origins_rx)
+    }
+
+    /// Publishes the stats of each origin on the given event bus of the node.
+    pub(crate) fn with_node_events(self, node_events: broadcast::Sender<NodeEvent>) -> Self {
+        Self { node_events: Some(node_events), ..self }
+    }
+
+    /// Records the stats of an origin the pipeline advanced past.
+    fn complete(&self, stats: DerivationOriginStats) {
+        debug!(
+            target: "derivation",
+            l1_block = stats.origin.number,
+            calldata_bytes = stats.calldata_bytes,
+            blob_bytes = stats.blob_bytes,
+            frames = stats.frames,
+            channels = stats.channels,
+            batches = stats.batches,
+            dropped_batches = stats.dropped_batches,
+            "Consumed L1 origin"
+        );
+
+        #[cfg(feature = "metrics")]
+        {
+            metrics::counter!(crate::Metrics::DERIVATION_ORIGIN_CONSUMED, "type" => "calldata_bytes")
+                .increment(stats.calldata_bytes);
+            metrics::counter!(crate::Metrics::DERIVATION_ORIGIN_CONSUMED, "type" => "blob_bytes")
+                .increment(stats.blob_bytes);
+            metrics::counter!(crate::Metrics::DERIVATION_ORIGIN_CONSUMED, "type" => "frames")
+                .increment(stats.frames);
+            metrics::counter!(crate::Metrics::DERIVATION_ORIGIN_CONSUMED, "type" => "channels")
+                .increment(stats.channels);
+            metrics::counter!(crate::Metrics::DERIVATION_ORIGIN_CONSUMED, "type" => "batches")
+                .increment(stats.batches);
+            metrics::counter!(crate::Metrics::DERIVATION_ORIGIN_CONSUMED, "type" => "dropped_batches")
+                .increment(stats.dropped_batches);
+        }
+
+        self.origins.send_modify(|origins| {
+            if origins.len() == Self::HISTORY {
+                origins.pop_front();
+            }
+            origins.push_back(stats);
+        });
+
+        if let Some(node_events) = &self.node_events {
+            // Events are dropped while there are no subscribers.
+            let _ = node_events.send(NodeEvent::DerivationOrigin(stats));
+        }
+    }
+
+    /// Updates the stats of the origin the pipeline is at, completing the previous origin if the
+    /// pipeline advanced past it.
+    fn update(&self, origin: BlockInfo, update: impl FnOnce(&mut DerivationOriginStats)) {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        let completed = match current.as_mut() {
+            Some(stats) if stats.origin == origin => None,
+            _ => current.replace(DerivationOriginStats { origin, ..Default::default() }),
+        };
+        if let Some(stats) = current.as_mut() {
+            update(stats);
+        }
+        drop(current);
+
+        if let Some(completed) = completed {
+            self.complete(completed);
+        }
+    }
+}
+
+impl PipelineEventSink for DerivationOriginTracker {
+    fn emit(&self, event: PipelineEvent) {
+        match event {
+            PipelineEvent::OriginAdvanced { origin } => self.update(origin, |_| {}),
+            // Data read from transactions that are not known to carry blobs is counted as
+            // calldata.
+            PipelineEvent::DataRetrieved { bytes, tx, origin } => {
+                self.update(origin, |stats| match tx.and_then(|tx| tx.blob_hash) {
+                    Some(_) => stats.blob_bytes += bytes,
+                    None => stats.calldata_bytes += bytes,
+                })
+            }
+            PipelineEvent::ChannelOpened { origin, .. } => {
+                self.update(origin, |stats| stats.channels += 1)
+            }
+            PipelineEvent::FrameRetrieved { origin, .. } => {
+                self.update(origin, |stats| stats.frames += 1)
+            }
+            PipelineEvent::BatchDecoded { origin, .. } => {
+                self.update(origin, |stats| stats.batches += 1)
+            }
+            PipelineEvent::BatchDropped { origin, .. } => {
+                self.update(origin, |stats| stats.dropped_batches += 1)
+            }
+            // The origin the pipeline was at may be consumed again after the reset.
+            PipelineEvent::Reset { .. } => {
+                *self.current.lock().unwrap_or_else(|e| e.into_inner()) = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use kona_derive::BatcherTxRef;
+
+    fn origin(number: u64) -> BlockInfo {
+        BlockInfo { number, ..Default::default() }
+    }
+
+    fn frame(origin: BlockInfo) -> PipelineEvent {
+        PipelineEvent::FrameRetrieved {
+            channel_id: Default::default(),
+            frame_number: 0,
+            tx: None,
+            origin,
+        }
+    }
+
+    #[test]
+    fn test_origin_stats_completed_on_advance() {
+        let (tracker, origins) = DerivationOriginTracker::new();
+        tracker.emit(PipelineEvent::OriginAdvanced { origin: origin(1) });
+        tracker.emit(frame(origin(1)));
+        tracker.emit(frame(origin(1)));
+        tracker.emit(PipelineEvent::BatchDecoded {
+            channel_id: None,
+            first_timestamp: 0,
+            last_timestamp: 0,
+            origin: origin(1),
+        });
+        assert!(origins.borrow().is_empty());
+
+        tracker.emit(PipelineEvent::OriginAdvanced { origin: origin(2) });
+        assert_eq!(
+            *origins.borrow(),
+            [DerivationOriginStats {
+                origin: origin(1),
+                frames: 2,
+                batches: 1,
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_origin_stats_da_usage() {
+        let (tracker, origins) = DerivationOriginTracker::new();
+        let blob = BatcherTxRef { tx_hash: B256::with_last_byte(1), blob_hash: Some(B256::ZERO) };
+        let calldata = BatcherTxRef { tx_hash: B256::with_last_byte(2), blob_hash: None };
+        tracker.emit(PipelineEvent::DataRetrieved {
+            bytes: 130_000,
+            tx: Some(blob),
+            origin: origin(1),
+        });
+        tracker.emit(PipelineEvent::DataRetrieved {
+            bytes: 900,
+            tx: Some(calldata),
+            origin: origin(1),
+        });
+        tracker.emit(PipelineEvent::ChannelOpened { id: Default::default(), origin: origin(1) });
+        tracker.emit(PipelineEvent::OriginAdvanced { origin: origin(2) });
+
+        let stats = origins.borrow()[0];
+        assert_eq!((stats.blob_bytes, stats.calldata_bytes, stats.channels), (130_000, 900, 1));
+    }
+
+    #[test]
+    fn test_origin_stats_discarded_on_reset() {
+        let (tracker, origins) = DerivationOriginTracker::new();
+        tracker.emit(frame(origin(1)));
+        tracker
+            .emit(PipelineEvent::Reset { l2_safe_head: Default::default(), l1_origin: origin(1) });
+        tracker.emit(PipelineEvent::OriginAdvanced { origin: origin(1) });
+        tracker.emit(PipelineEvent::OriginAdvanced { origin: origin(2) });
+        assert_eq!(
+            *origins.borrow(),
+            [DerivationOriginStats { origin: origin(1), ..Default::default() }]
+        );
+    }
+}
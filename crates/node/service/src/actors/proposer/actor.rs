@@ -0,0 +1,278 @@
+//! [`NodeActor`] implementation for an output root proposer.
+
+use crate::{
+    CancellableContext, NodeActor,
+    actors::proposer::{
+        ProposalTarget, ProposerActorError, ProposerConfig,
+        contracts::{DisputeGameFactory, L2OutputOracle},
+    },
+};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, B256, Bytes, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::TransactionRequest;
+use alloy_sol_types::SolCall;
+use async_trait::async_trait;
+use kona_engine::{EngineQueries, EngineQuerySender};
+use kona_protocol::{L2BlockInfo, OutputRoot};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::{select, sync::oneshot};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// The [`ProposerActor`] periodically computes the output root at the safe or finalized L2 head,
+/// and proposes it to the `L2OutputOracle` or `DisputeGameFactory` on L1.
+#[derive(Debug)]
+pub struct ProposerActor<P: Provider> {
+    /// The proposer configuration.
+    config: ProposerConfig,
+    /// The L1 provider, with a wallet for the proposer signer.
+    l1_provider: P,
+    /// The sender for queries to the engine, used to compute output roots.
+    engine_query_tx: EngineQuerySender,
+    /// The cancellation token, shared between all tasks.
+    cancellation: CancellationToken,
+    /// The unix timestamp of the latest dispute game created by the proposer, if any.
+    last_game_timestamp: Option<u64>,
+}
+
+impl<P: Provider> ProposerActor<P> {
+    /// Creates a new [`ProposerActor`].
+    pub const fn new(
+        config: ProposerConfig,
+        l1_provider: P,
+        engine_query_tx: EngineQuerySender,
+        cancellation: CancellationToken,
+    ) -> Self {
+        Self { config, l1_provider, engine_query_tx, cancellation, last_game_timestamp: None }
+    }
+
+    /// The L2 block label that outputs are proposed at.
+    const fn proposal_head(&self) -> BlockNumberOrTag {
+        if self.config.allow_non_finalized {
+            BlockNumberOrTag::Safe
+        } else {
+            BlockNumberOrTag::Finalized
+        }
+    }
+
+    /// Submits an output proposal if one is due.
+    async fn try_propose(&mut self) -> Result<(), ProposerActorError> {
+        match self.config.target {
+            ProposalTarget::L2OutputOracle(oracle) => self.propose_to_oracle(oracle).await,
+            ProposalTarget::DisputeGameFactory { address, game_type, proposal_interval } => {
+                self.propose_to_factory(address, game_type, proposal_interval).await
+            }
+        }
+    }
+
+    /// Proposes the next output expected by the `L2OutputOracle`, once the proposal head has
+    /// reached it.
+    async fn propose_to_oracle(&self, oracle: Address) -> Result<(), ProposerActorError> {
+        let next_block: u64 =
+            self.call(oracle, L2OutputOracle::nextBlockNumberCall {}).await?.saturating_to();
+        let (head, _) = self.output_at(self.proposal_head()).await?;
+        if head.block_info.number < next_block {
+            debug!(
+                target: "proposer",
+                head = head.block_info.number,
+                next_block,
+                "Proposal head has not reached the next output block"
+            );
+            return Ok(());
+        }
+
+        let (block, output_root) = self.output_at(BlockNumberOrTag::Number(next_block)).await?;
+        let l1_head = self
+            .l1_provider
+            .get_block(BlockId::latest())
+            .await?
+            .ok_or(ProposerActorError::L1HeadNotFound)?;
+        let call = L2OutputOracle::proposeL2OutputCall {
+            outputRoot: output_root.hash(),
+            l2BlockNumber: U256::from(block.block_info.number),
+            l1BlockHash: l1_head.header.hash,
+            l1BlockNumber: U256::from(l1_head.header.inner.number),
+        };
+        self.submit(oracle, call, U256::ZERO, block, output_root).await
+    }
+
+    /// Creates a dispute game for the output at the proposal head, if the proposal interval has
+    /// elapsed since the latest game of the configured type.
+    async fn propose_to_factory(
+        &mut self,
+        factory: Address,
+        game_type: u32,
+        proposal_interval: Duration,
+    ) -> Result<(), ProposerActorError> {
+        if self.last_game_timestamp.is_none() {
+            self.last_game_timestamp = self.latest_game_timestamp(factory, game_type).await?;
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if !proposal_due(self.last_game_timestamp, now, proposal_interval) {
+            return Ok(());
+        }
+
+        let (block, output_root) = self.output_at(self.proposal_head()).await?;
+        let bond =
+            self.call(factory, DisputeGameFactory::initBondsCall { gameType: game_type }).await?;
+        let call = DisputeGameFactory::createCall {
+            gameType: game_type,
+            rootClaim: output_root.hash(),
+            extraData: game_extra_data(block.block_info.number),
+        };
+        self.submit(factory, call, bond, block, output_root).await?;
+        self.last_game_timestamp = Some(now);
+        Ok(())
+    }
+
+    /// Returns the creation timestamp of the latest dispute game of the given type, if any.
+    async fn latest_game_timestamp(
+        &self,
+        factory: Address,
+        game_type: u32,
+    ) -> Result<Option<u64>, ProposerActorError> {
+        let game_count = self.call(factory, DisputeGameFactory::gameCountCall {}).await?;
+        if game_count.is_zero() {
+            return Ok(None);
+        }
+
+        let games = self
+            .call(
+                factory,
+                DisputeGameFactory::findLatestGamesCall {
+                    gameType: game_type,
+                    start: game_count - U256::from(1),
+                    n: U256::from(1),
+                },
+            )
+            .await?;
+        Ok(games.first().map(|game| game.timestamp))
+    }
+
+    /// Queries the engine for the output root at the given block.
+    async fn output_at(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> Result<(L2BlockInfo, OutputRoot), ProposerActorError> {
+        let (sender, receiver) = oneshot::channel();
+        self.engine_query_tx
+            .send(EngineQueries::OutputAtBlock { block, sender })
+            .await
+            .map_err(|_| ProposerActorError::ChannelClosed)?;
+        let (block_info, output_root, _) =
+            receiver.await.map_err(|_| ProposerActorError::OutputUnavailable(block))?;
+        Ok((block_info, output_root))
+    }
+
+    /// Executes a read-only call against an L1 contract.
+    async fn call<C: SolCall>(
+        &self,
+        to: Address,
+        call: C,
+    ) -> Result<C::Return, ProposerActorError> {
+        let request =
+            TransactionRequest::default().to(to).input(Bytes::from(call.abi_encode()).into());
+        let output = self.l1_provider.call(request).await?;
+        Ok(C::abi_decode_returns(&output)?)
+    }
+
+    /// Sends a proposal transaction and waits for its receipt.
+    async fn submit<C: SolCall>(
+        &self,
+        to: Address,
+        call: C,
+        value: U256,
+        block: L2BlockInfo,
+        output_root: OutputRoot,
+    ) -> Result<(), ProposerActorError> {
+        let request = TransactionRequest::default()
+            .to(to)
+            .input(Bytes::from(call.abi_encode()).into())
+            .value(value);
+        let receipt = self.l1_provider.send_transaction(request).await?.get_receipt().await?;
+        if !receipt.status() {
+            return Err(ProposerActorError::Reverted(receipt.transaction_hash));
+        }
+
+        info!(
+            target: "proposer",
+            l2_block = block.block_info.number,
+            output_root = %output_root.hash(),
+            tx_hash = %receipt.transaction_hash,
+            "Proposed output root"
+        );
+        kona_macros::set!(gauge, crate::Metrics::PROPOSER_L2_BLOCK, block.block_info.number as f64);
+        Ok(())
+    }
+}
+
+/// Returns `true` if a new dispute game is due, given the timestamp of the latest game.
+fn proposal_due(last_game_timestamp: Option<u64>, now: u64, proposal_interval: Duration) -> bool {
+    last_game_timestamp.is_none_or(|last| now.saturating_sub(last) >= proposal_interval.as_secs())
+}
+
+/// Returns the extra data of a dispute game proposing the output at `l2_block_number`.
+fn game_extra_data(l2_block_number: u64) -> Bytes {
+    B256::from(U256::from(l2_block_number)).into()
+}
+
+#[async_trait]
+impl<P: Provider + 'static> NodeActor for ProposerActor<P> {
+    type Error = ProposerActorError;
+    type StartData = ();
+
+    async fn start(mut self, _: Self::StartData) -> Result<(), Self::Error> {
+        let mut ticker = tokio::time::interval(self.config.poll_interval);
+
+        loop {
+            select! {
+                _ = self.cancellation.cancelled() => {
+                    info!(target: "proposer", "Received shutdown signal. Exiting proposer task.");
+                    return Ok(());
+                }
+                _ = ticker.tick() => match self.try_propose().await {
+                    Ok(()) => {}
+                    Err(ProposerActorError::ChannelClosed) => {
+                        error!(target: "proposer", "Engine query channel closed unexpectedly");
+                        return Err(ProposerActorError::ChannelClosed);
+                    }
+                    Err(e) => {
+                        warn!(target: "proposer", error = %e, "Failed to submit output proposal");
+                        kona_macros::inc!(counter, crate::Metrics::PROPOSER_FAILURES);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<P: Provider> CancellableContext for ProposerActor<P> {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proposal_due() {
+        let interval = Duration::from_secs(600);
+
+        assert!(proposal_due(None, 1_000, interval));
+        assert!(!proposal_due(Some(1_000), 1_599, interval));
+        assert!(proposal_due(Some(1_000), 1_600, interval));
+        // A game timestamp ahead of the local clock never underflows.
+        assert!(!proposal_due(Some(2_000), 1_000, interval));
+    }
+
+    #[test]
+    fn test_game_extra_data() {
+        let extra_data = game_extra_data(0x1234);
+
+        assert_eq!(extra_data.len(), 32);
+        assert_eq!(U256::from_be_slice(&extra_data), U256::from(0x1234));
+    }
+}
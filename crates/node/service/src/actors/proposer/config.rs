@@ -0,0 +1,40 @@
+//! Configuration for the [`ProposerActor`].
+//!
+//! [`ProposerActor`]: super::ProposerActor
+
+use alloy_primitives::Address;
+use alloy_signer_local::PrivateKeySigner;
+use std::time::Duration;
+
+/// The L1 contract that output proposals are submitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalTarget {
+    /// The legacy `L2OutputOracle`. Outputs are proposed at every submission interval, as
+    /// reported by the oracle's `nextBlockNumber`.
+    L2OutputOracle(Address),
+    /// The `DisputeGameFactory`. A dispute game is created with the latest output root once per
+    /// proposal interval.
+    DisputeGameFactory {
+        /// The address of the `DisputeGameFactory` proxy.
+        address: Address,
+        /// The type of dispute game to create.
+        game_type: u32,
+        /// The minimum time between two proposals.
+        proposal_interval: Duration,
+    },
+}
+
+/// Configuration for the [`ProposerActor`].
+///
+/// [`ProposerActor`]: super::ProposerActor
+#[derive(Debug, Clone)]
+pub struct ProposerConfig {
+    /// The signer of the proposal transactions.
+    pub signer: PrivateKeySigner,
+    /// The L1 contract that proposals are submitted to.
+    pub target: ProposalTarget,
+    /// The interval at which the proposer checks whether a new proposal is due.
+    pub poll_interval: Duration,
+    /// Whether to propose outputs at the safe head rather than the finalized head.
+    pub allow_non_finalized: bool,
+}
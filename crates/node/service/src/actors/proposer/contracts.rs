@@ -0,0 +1,50 @@
+//! ABI bindings for the L1 contracts the proposer submits to.
+
+use alloy_sol_types::sol;
+
+sol! {
+    /// The legacy `L2OutputOracle` contract.
+    interface L2OutputOracle {
+        /// Returns the L2 block number of the next output to be proposed.
+        function nextBlockNumber() external view returns (uint256);
+
+        /// Proposes an output root for the given L2 block, checkpointed against an L1 block.
+        function proposeL2Output(
+            bytes32 outputRoot,
+            uint256 l2BlockNumber,
+            bytes32 l1BlockHash,
+            uint256 l1BlockNumber
+        ) external payable;
+    }
+
+    /// A dispute game returned by `DisputeGameFactory.findLatestGames`.
+    struct GameSearchResult {
+        uint256 index;
+        bytes32 metadata;
+        uint64 timestamp;
+        bytes32 rootClaim;
+        bytes extraData;
+    }
+
+    /// The `DisputeGameFactory` contract.
+    interface DisputeGameFactory {
+        /// Returns the total number of dispute games created.
+        function gameCount() external view returns (uint256);
+
+        /// Returns up to `n` of the latest games of the given type, searching backwards from
+        /// index `start`.
+        function findLatestGames(uint32 gameType, uint256 start, uint256 n)
+            external
+            view
+            returns (GameSearchResult[] memory);
+
+        /// Returns the bond required to create a game of the given type.
+        function initBonds(uint32 gameType) external view returns (uint256);
+
+        /// Creates a dispute game claiming `rootClaim`.
+        function create(uint32 gameType, bytes32 rootClaim, bytes calldata extraData)
+            external
+            payable
+            returns (address);
+    }
+}
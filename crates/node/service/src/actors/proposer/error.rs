@@ -0,0 +1,30 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::B256;
+use alloy_provider::PendingTransactionError;
+use alloy_transport::{RpcError, TransportErrorKind};
+
+/// An error produced by the [`crate::ProposerActor`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProposerActorError {
+    /// A channel was unexpectedly closed.
+    #[error("Channel closed unexpectedly")]
+    ChannelClosed,
+    /// The engine could not compute the output root at the requested block.
+    #[error("Failed to compute the output root at block {0}")]
+    OutputUnavailable(BlockNumberOrTag),
+    /// The L1 head block could not be found.
+    #[error("L1 head block not found")]
+    L1HeadNotFound,
+    /// An L1 RPC request failed.
+    #[error(transparent)]
+    Rpc(#[from] RpcError<TransportErrorKind>),
+    /// The proposal transaction could not be confirmed.
+    #[error(transparent)]
+    PendingTransaction(#[from] PendingTransactionError),
+    /// An L1 contract call returned data that could not be decoded.
+    #[error(transparent)]
+    Abi(#[from] alloy_sol_types::Error),
+    /// The proposal transaction reverted.
+    #[error("Proposal transaction {0} reverted")]
+    Reverted(B256),
+}
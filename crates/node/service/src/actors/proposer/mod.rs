@@ -0,0 +1,12 @@
+//! The `ProposerActor` and its components.
+
+mod config;
+pub use config::{ProposalTarget, ProposerConfig};
+
+mod contracts;
+
+mod actor;
+pub use actor::ProposerActor;
+
+mod error;
+pub use error::ProposerActorError;
@@ -0,0 +1,131 @@
+//! [`NodeActor`] implementation sending periodic pruning hints to the execution layer.
+
+use crate::{
+    CancellableContext, NodeActor,
+    actors::pruning_hint::{PruningHint, PruningHintActorError, PruningHintConfig},
+};
+use async_trait::async_trait;
+use kona_engine::{EngineQueries, EngineQuerySender, retention_horizon};
+use kona_genesis::RollupConfig;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{select, sync::oneshot};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// The timeout of the requests sending pruning hints.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The [`PruningHintActor`] periodically sends a [`PruningHint`] holding the lowest L2 block the
+/// node may still need for derivation resets to an HTTP endpoint.
+///
+/// This lets a co-located execution layer prune its L2 chain state below that height without
+/// breaking the node's ability to recover from a reset.
+#[derive(Debug)]
+pub struct PruningHintActor {
+    /// The pruning hint configuration.
+    config: PruningHintConfig,
+    /// The rollup config, used to compute the retention horizon.
+    rollup_config: Arc<RollupConfig>,
+    /// The client used to send the pruning hints.
+    client: reqwest::Client,
+}
+
+/// The communication context used by the [`PruningHintActor`].
+#[derive(Debug)]
+pub struct PruningHintContext {
+    /// The sender of queries to the engine.
+    pub engine_queries: EngineQuerySender,
+    /// The cancellation token, shared between all tasks.
+    pub cancellation: CancellationToken,
+}
+
+impl CancellableContext for PruningHintContext {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+impl PruningHintActor {
+    /// Creates a new [`PruningHintActor`].
+    pub fn new(config: PruningHintConfig, rollup_config: Arc<RollupConfig>) -> Self {
+        Self { config, rollup_config, client: reqwest::Client::new() }
+    }
+
+    /// Computes a [`PruningHint`] from the finalized L2 head of the engine.
+    async fn hint(&self, ctx: &PruningHintContext) -> Result<PruningHint, PruningHintActorError> {
+        let (state_tx, state_rx) = oneshot::channel();
+        ctx.engine_queries
+            .send(EngineQueries::State(state_tx))
+            .await
+            .map_err(|_| PruningHintActorError::ChannelClosed)?;
+        let state = state_rx.await.map_err(|_| PruningHintActorError::ChannelClosed)?;
+
+        let finalized_l2 = state.sync_state.finalized_head();
+        Ok(PruningHint {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            retention_horizon: retention_horizon(&self.rollup_config, &finalized_l2),
+            finalized_l2,
+        })
+    }
+
+    /// Computes a pruning hint and sends it to the configured endpoint.
+    async fn send(&self, ctx: &PruningHintContext) -> Result<(), PruningHintActorError> {
+        let hint = self.hint(ctx).await?;
+        self.client
+            .post(self.config.url.as_str())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .timeout(HTTP_TIMEOUT)
+            .body(serde_json::to_vec(&hint)?)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        debug!(
+            target: "pruning_hint",
+            retention_horizon = hint.retention_horizon,
+            finalized = hint.finalized_l2.block_info.number,
+            "Sent pruning hint"
+        );
+        kona_macros::set!(
+            gauge,
+            crate::Metrics::PRUNING_HINT_HORIZON,
+            hint.retention_horizon as f64
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NodeActor for PruningHintActor {
+    type Error = PruningHintActorError;
+    type StartData = PruningHintContext;
+
+    async fn start(self, ctx: Self::StartData) -> Result<(), Self::Error> {
+        let mut ticker = tokio::time::interval(self.config.interval);
+
+        loop {
+            select! {
+                _ = ctx.cancellation.cancelled() => {
+                    info!(
+                        target: "pruning_hint",
+                        "Received shutdown signal. Exiting pruning hint task."
+                    );
+                    return Ok(());
+                }
+                _ = ticker.tick() => match self.send(&ctx).await {
+                    Ok(()) => {}
+                    Err(PruningHintActorError::ChannelClosed) => {
+                        error!(target: "pruning_hint", "Engine query channel closed unexpectedly");
+                        return Err(PruningHintActorError::ChannelClosed);
+                    }
+                    Err(e) => {
+                        warn!(target: "pruning_hint", error = %e, "Failed to send pruning hint");
+                        kona_macros::inc!(counter, crate::Metrics::PRUNING_HINT_FAILURES);
+                    }
+                },
+            }
+        }
+    }
+}
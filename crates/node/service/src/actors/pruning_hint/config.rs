@@ -0,0 +1,17 @@
+//! Configuration for the [`PruningHintActor`].
+//!
+//! [`PruningHintActor`]: super::PruningHintActor
+
+use std::time::Duration;
+use url::Url;
+
+/// Configuration for the [`PruningHintActor`].
+///
+/// [`PruningHintActor`]: super::PruningHintActor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruningHintConfig {
+    /// The HTTP endpoint the pruning hints are sent to, as the JSON body of a `POST` request.
+    pub url: Url,
+    /// The interval at which pruning hints are sent.
+    pub interval: Duration,
+}
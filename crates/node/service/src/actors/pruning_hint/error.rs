@@ -0,0 +1,13 @@
+/// An error produced by the [`crate::PruningHintActor`].
+#[derive(Debug, thiserror::Error)]
+pub enum PruningHintActorError {
+    /// A channel was unexpectedly closed.
+    #[error("Channel closed unexpectedly")]
+    ChannelClosed,
+    /// The pruning hint could not be serialized.
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+    /// The pruning hint could not be sent to its HTTP endpoint.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
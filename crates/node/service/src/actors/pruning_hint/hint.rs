@@ -0,0 +1,21 @@
+//! The pruning hint sent by the [`PruningHintActor`].
+//!
+//! [`PruningHintActor`]: super::PruningHintActor
+
+use kona_protocol::L2BlockInfo;
+use serde::Serialize;
+
+/// A hint of the L2 blocks the node no longer needs, sent as JSON by the [`PruningHintActor`].
+///
+/// [`PruningHintActor`]: super::PruningHintActor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruningHint {
+    /// The unix timestamp at which the hint was computed, in seconds.
+    pub timestamp: u64,
+    /// The number of the lowest L2 block the node may still need to derive from after a reset.
+    /// The execution layer can safely prune the L2 chain state below it.
+    pub retention_horizon: u64,
+    /// The finalized L2 head the retention horizon was computed from.
+    pub finalized_l2: L2BlockInfo,
+}
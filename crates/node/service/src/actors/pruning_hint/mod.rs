@@ -0,0 +1,13 @@
+//! The `PruningHintActor` and its components.
+
+mod config;
+pub use config::PruningHintConfig;
+
+mod hint;
+pub use hint::PruningHint;
+
+mod actor;
+pub use actor::{PruningHintActor, PruningHintContext};
+
+mod error;
+pub use error::PruningHintActorError;
@@ -0,0 +1,437 @@
+//! RPC Server Actor
+
+use super::tls::RpcTlsAcceptor;
+use crate::{NodeActor, actors::CancellableContext};
+use async_trait::async_trait;
+use kona_derive::PipelineEvent;
+use kona_gossip::TracedP2pRpcRequest;
+use kona_rpc::{
+    AdminApiServer, AdminRpc, BuildInfoApiServer, DaStatsApiServer, DebugDerivationApiServer,
+    DebugEngineApiServer, DebugP2PApiServer, DerivationEventsApiServer, DerivationEventsRpc,
+    DerivationOriginsRpc, DevEngineApiServer, DevEngineRpc, EngineAdminQuery, HealthzApiServer,
+    HealthzRpc, NetworkAdminQuery, OpP2PApiServer, RollupBoostAdminQuery, RollupBoostHealthQuery,
+    RollupBoostHealthzApiServer, RollupEventsApiServer, RollupNodeApiServer,
+    SequencerAdminAPIClient, WsRPC, WsServer,
+};
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use jsonrpsee::{
+    RpcModule,
+    core::RegisterMethodError,
+    server::{
+        RpcServiceBuilder, Server, ServerHandle, middleware::http::ProxyGetRequestLayer,
+        serve_with_graceful_shutdown, stop_channel,
+    },
+};
+use kona_engine::EngineQueries;
+use kona_genesis::RollupConfig;
+use kona_interop::DependencySet;
+use kona_protocol::SyncModeSelection;
+use kona_rpc::{
+    DerivationHaltSwitch, DerivationLatency, DerivationOriginStats, L1ProvenanceDb,
+    L1WatcherQueries, MethodPolicyService, MethodPolicyState, NodeCountersDb, P2pRpc,
+    RequestIdService, RollupRpc, RpcBuilder, RuntimeFlags, SafeHeadDb,
+};
+use kona_supervisor_rpc::SupervisorHealth;
+use tokio::{
+    net::TcpListener,
+    sync::{broadcast, mpsc, watch},
+};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// The time a client has to complete the TLS handshake after connecting.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The delay before accepting connections again after failing to accept one, e.g. when the
+/// process runs out of file descriptors.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// An error returned by the [`RpcActor`].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcActorError {
+    /// Failed to register the healthz endpoint.
+    #[error("Failed to register the healthz endpoint")]
+    RegisterHealthz(#[from] RegisterMethodError),
+    /// Failed to launch the RPC server.
+    #[error(transparent)]
+    LaunchFailed(#[from] std::io::Error),
+    /// The [`RpcActor`]'s RPC server stopped unexpectedly.
+    #[error("RPC server stopped unexpectedly")]
+    ServerStopped,
+    /// Failed to stop the RPC server.
+    #[error("Failed to stop the RPC server")]
+    StopFailed,
+}
+
+/// An actor that handles the RPC server for the rollup node.
+#[derive(Debug)]
+pub struct RpcActor<S: SequencerAdminAPIClient> {
+    /// A launcher for the rpc.
+    config: RpcBuilder,
+    /// Additional modules served alongside the node's own.
+    modules: Option<RpcModule<()>>,
+
+    phantom: std::marker::PhantomData<S>,
+}
+
+impl<S: SequencerAdminAPIClient> RpcActor<S> {
+    /// Constructs a new [`RpcActor`] given the [`RpcBuilder`].
+    pub const fn new(config: RpcBuilder) -> Self {
+        Self { config, modules: None, phantom: std::marker::PhantomData }
+    }
+
+    /// Sets additional modules to serve alongside the node's own.
+    pub fn with_modules(self, modules: Option<RpcModule<()>>) -> Self {
+        Self { modules, ..self }
+    }
+}
+
+/// The communication context used by the RPC actor.
+#[derive(Debug)]
+pub struct RpcContext<SequencerAdminApiClient> {
+    /// The network p2p rpc sender.
+    pub p2p_network: mpsc::Sender<TracedP2pRpcRequest>,
+    /// The network admin rpc sender.
+    pub network_admin: mpsc::Sender<NetworkAdminQuery>,
+    /// The sequencer admin rpc sender.
+    pub sequencer_admin: Option<SequencerAdminApiClient>,
+    /// The l1 watcher queries sender.
+    pub l1_watcher_queries: mpsc::Sender<L1WatcherQueries>,
+    /// The engine query sender.
+    pub engine_query: mpsc::Sender<EngineQueries>,
+    /// The engine admin rpc sender.
+    pub engine_admin: mpsc::Sender<EngineAdminQuery>,
+    /// The cancellation token, shared between all tasks.
+    pub cancellation: CancellationToken,
+    /// The rollup boost admin rpc sender.
+    pub rollup_boost_admin: mpsc::Sender<RollupBoostAdminQuery>,
+    /// The rollup boost health rpc sender.
+    pub rollup_boost_health: mpsc::Sender<RollupBoostHealthQuery>,
+    /// The record of the safe head at each L1 block, if the node keeps one.
+    pub safe_head_db: Option<Arc<dyn SafeHeadDb>>,
+    /// The record of the L1 data each derived L2 block came from, if the node keeps one.
+    pub l1_provenance_db: Option<Arc<dyn L1ProvenanceDb>>,
+    /// The record of the cumulative counters of the node, if the node keeps one.
+    pub node_counters_db: Option<Arc<dyn NodeCountersDb>>,
+    /// A receiver of the latest summary of the derivation latency.
+    pub derivation_latency: watch::Receiver<DerivationLatency>,
+    /// A receiver of the current sync strategy of the engine.
+    pub sync_mode: watch::Receiver<Option<SyncModeSelection>>,
+    /// A receiver of the batch data consumed from the most recent L1 origins of the derivation
+    /// pipeline.
+    pub derivation_origins: watch::Receiver<VecDeque<DerivationOriginStats>>,
+    /// The sender the derivation pipeline broadcasts its events on.
+    pub pipeline_events: broadcast::Sender<PipelineEvent>,
+    /// The interop dependency set of the chain, if it schedules Interop.
+    pub dependency_set: Option<Arc<DependencySet>>,
+    /// The rollup config of the chain.
+    pub rollup_config: Arc<RollupConfig>,
+    /// The registry of the runtime flags of the node.
+    pub runtime_flags: RuntimeFlags,
+    /// The switch halting derivation, reported by the healthcheck and acknowledged through the
+    /// admin RPC.
+    pub derivation_halt: DerivationHaltSwitch,
+    /// The health of the supervisor interop messages are validated against, reported by the
+    /// healthcheck.
+    pub supervisor_health: Option<watch::Receiver<SupervisorHealth>>,
+}
+
+impl<S: SequencerAdminAPIClient> CancellableContext for RpcContext<S> {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+/// Launches the jsonrpsee [`Server`], terminating TLS if the [`RpcBuilder`] has a TLS
+/// configuration.
+///
+/// If the RPC server is disabled, this will return `Ok(None)`.
+///
+/// ## Errors
+///
+/// - [`std::io::Error`] if the server fails to start, or its certificates fail to load.
+async fn launch(
+    config: &RpcBuilder,
+    module: RpcModule<()>,
+) -> Result<ServerHandle, std::io::Error> {
+    let middleware = tower::ServiceBuilder::new()
+        .layer(
+            ProxyGetRequestLayer::new([
+                ("/healthz", "healthz"),
+                ("/kona-rollup-boost/healthz", "kona-rollup-boost_healthz"),
+            ])
+            .expect("Critical: Failed to build GET method proxy"),
+        )
+        .timeout(Duration::from_secs(2));
+    let policy = Arc::new(MethodPolicyState::new(config.method_policy()));
+    let builder = Server::builder().set_http_middleware(middleware).set_rpc_middleware(
+        RpcServiceBuilder::new()
+            .layer_fn(RequestIdService::new)
+            .layer_fn(move |service| MethodPolicyService::new(service, Arc::clone(&policy))),
+    );
+
+    let Some(tls) = config.tls() else {
+        let server = builder.build(config.socket).await?;
+
+        if let Ok(addr) = server.local_addr() {
+            info!(target: "rpc", addr = ?addr, "RPC server bound to address");
+        } else {
+            error!(target: "rpc", "Failed to get local address for RPC server");
+        }
+
+        return Ok(server.start(module));
+    };
+
+    let acceptor = RpcTlsAcceptor::new(tls).map_err(std::io::Error::other)?;
+    let listener = TcpListener::bind(config.socket).await?;
+    info!(target: "rpc", addr = ?listener.local_addr()?, "RPC server bound to address with TLS");
+
+    // Serve the connections with the low-level API of jsonrpsee, so that the TLS handshake happens
+    // before the connection is handed to the server. The server stops once all the clones of the
+    // stop handle are dropped.
+    let (stop_handle, server_handle) = stop_channel();
+    let service = builder.to_service_builder().build(module, stop_handle.clone());
+    tokio::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        warn!(target: "rpc", ?err, "Failed to accept RPC connection");
+                        tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                        continue;
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+
+            let acceptor = acceptor.clone();
+            let service = service.clone();
+            let stop_handle = stop_handle.clone();
+            tokio::spawn(async move {
+                let stream = match tokio::time::timeout(
+                    TLS_HANDSHAKE_TIMEOUT,
+                    acceptor.accept(stream),
+                )
+                .await
+                {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(err)) => {
+                        debug!(target: "rpc", ?err, "TLS handshake failed");
+                        return;
+                    }
+                    Err(_) => {
+                        debug!(target: "rpc", "TLS handshake timed out");
+                        return;
+                    }
+                };
+                if let Err(err) =
+                    serve_with_graceful_shutdown(stream, service, stop_handle.shutdown()).await
+                {
+                    debug!(target: "rpc", ?err, "RPC connection closed with an error");
+                }
+            });
+        }
+    });
+
+    Ok(server_handle)
+}
+
+#[async_trait]
+impl<S: SequencerAdminAPIClient + 'static> NodeActor for RpcActor<S> {
+    type Error = RpcActorError;
+    type StartData = RpcContext<S>;
+
+    async fn start(
+        mut self,
+        RpcContext {
+            cancellation,
+            p2p_network,
+            l1_watcher_queries,
+            engine_query,
+            engine_admin,
+            network_admin,
+            sequencer_admin,
+            rollup_boost_admin,
+            rollup_boost_health,
+            safe_head_db,
+            l1_provenance_db,
+            node_counters_db,
+            derivation_latency,
+            sync_mode,
+            derivation_origins,
+            pipeline_events,
+            dependency_set,
+            rollup_config,
+            runtime_flags,
+            derivation_halt,
+            supervisor_health,
+        }: Self::StartData,
+    ) -> Result<(), Self::Error> {
+        let mut modules = RpcModule::new(());
+
+        let healthz_rpc = HealthzRpc::new(rollup_boost_health)
+            .with_derivation_halt(derivation_halt.clone())
+            .with_supervisor_health(supervisor_health);
+        modules.merge(HealthzApiServer::into_rpc(healthz_rpc.clone()))?;
+        modules.merge(RollupBoostHealthzApiServer::into_rpc(healthz_rpc))?;
+
+        // Build the p2p rpc module.
+        let p2p_rpc = P2pRpc::new(p2p_network);
+        modules.merge(DebugP2PApiServer::into_rpc(p2p_rpc.clone()))?;
+        modules.merge(OpP2PApiServer::into_rpc(p2p_rpc))?;
+
+        // Build the admin rpc module.
+        modules.merge(
+            AdminRpc::new(
+                sequencer_admin,
+                network_admin,
+                Some(rollup_boost_admin),
+                runtime_flags,
+                derivation_halt,
+            )
+            .with_engine_sender(engine_admin)
+            .into_rpc(),
+        )?;
+
+        // Create context for communication between actors.
+        let mut rollup_rpc = RollupRpc::new(engine_query.clone(), l1_watcher_queries)
+            .with_derivation_latency(derivation_latency)
+            .with_sync_mode(sync_mode)
+            .with_rollup_config(rollup_config);
+        if let Some(safe_head_db) = safe_head_db {
+            rollup_rpc = rollup_rpc.with_safe_head_db(safe_head_db);
+        }
+        if let Some(l1_provenance_db) = l1_provenance_db {
+            rollup_rpc = rollup_rpc.with_l1_provenance_db(l1_provenance_db);
+        }
+        if let Some(node_counters_db) = node_counters_db {
+            rollup_rpc = rollup_rpc.with_node_counters_db(node_counters_db);
+        }
+        if let Some(build_info) = self.config.build_info.clone() {
+            rollup_rpc = rollup_rpc.with_build_info(build_info);
+        }
+        if let Some(dependency_set) = dependency_set {
+            rollup_rpc = rollup_rpc.with_dependency_set(dependency_set);
+        }
+        modules.merge(RollupEventsApiServer::into_rpc(rollup_rpc.clone()))?;
+        modules.merge(BuildInfoApiServer::into_rpc(rollup_rpc.clone()))?;
+        modules.merge(DebugEngineApiServer::into_rpc(rollup_rpc.clone()))?;
+        modules.merge(RollupNodeApiServer::into_rpc(rollup_rpc))?;
+        let origins_rpc = DerivationOriginsRpc::new(derivation_origins);
+        modules.merge(DebugDerivationApiServer::into_rpc(origins_rpc.clone()))?;
+        modules.merge(DaStatsApiServer::into_rpc(origins_rpc))?;
+
+        // Add development RPC module for engine state introspection if enabled
+        if self.config.dev_enabled() {
+            let dev_rpc = DevEngineRpc::new(engine_query.clone());
+            modules.merge(dev_rpc.into_rpc())?;
+        }
+
+        if self.config.ws_enabled() {
+            modules.merge(WsRPC::new(engine_query).into_rpc())?;
+            modules.merge(DerivationEventsRpc::new(pipeline_events).into_rpc())?;
+        }
+
+        if let Some(extra) = self.modules.take() {
+            modules.merge(extra)?;
+        }
+
+        let restarts = self.config.restart_count();
+
+        let mut handle = launch(&self.config, modules.clone()).await?;
+
+        for _ in 0..=restarts {
+            tokio::select! {
+                _ = handle.clone().stopped() => {
+                    match launch(&self.config, modules.clone()).await {
+                        Ok(h) => handle = h,
+                        Err(err) => {
+                            error!(target: "rpc", ?err, "Failed to launch rpc server");
+                            cancellation.cancel();
+                            return Err(RpcActorError::ServerStopped);
+                        }
+                    }
+                }
+                _ = cancellation.cancelled() => {
+                    // The cancellation token has been triggered, so we should stop the server.
+                    handle.stop().map_err(|_| RpcActorError::StopFailed)?;
+                    // Since the RPC Server didn't originate the error, we should return Ok.
+                    return Ok(());
+                }
+            }
+        }
+
+        // Stop the node if there has already been 3 rpc restarts.
+        cancellation.cancel();
+        return Err(RpcActorError::ServerStopped);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+    use kona_rpc::{RpcTlsConfig, TlsCertKeyPaths};
+
+    #[tokio::test]
+    async fn test_launch_no_modules() {
+        let launcher = RpcBuilder {
+            socket: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            no_restart: false,
+            enable_admin: false,
+            admin_persistence: None,
+            ws_enabled: false,
+            dev_enabled: false,
+            tls: None,
+            method_policy: Default::default(),
+            build_info: None,
+        };
+        let result = launch(&launcher, RpcModule::new(())).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_launch_with_modules() {
+        let launcher = RpcBuilder {
+            socket: SocketAddr::from(([127, 0, 0, 1], 8081)),
+            no_restart: false,
+            enable_admin: false,
+            admin_persistence: None,
+            ws_enabled: false,
+            dev_enabled: false,
+            tls: None,
+            method_policy: Default::default(),
+            build_info: None,
+        };
+        let mut modules = RpcModule::new(());
+
+        modules.merge(RpcModule::new(())).expect("module merge");
+        modules.merge(RpcModule::new(())).expect("module merge");
+        modules.merge(RpcModule::new(())).expect("module merge");
+
+        let result = launch(&launcher, modules).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_launch_tls_missing_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths =
+            TlsCertKeyPaths { cert: dir.path().join("cert.pem"), key: dir.path().join("key.pem") };
+        let launcher = RpcBuilder {
+            socket: SocketAddr::from(([127, 0, 0, 1], 0)),
+            no_restart: false,
+            enable_admin: false,
+            admin_persistence: None,
+            ws_enabled: false,
+            dev_enabled: false,
+            tls: Some(RpcTlsConfig { default: paths, sni: vec![] }),
+            method_policy: Default::default(),
+            build_info: None,
+        };
+        let result = launch(&launcher, RpcModule::new(())).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,7 @@
+//! The RPC server of the rollup node.
+
+mod actor;
+pub use actor::{RpcActor, RpcActorError, RpcContext};
+
+mod tls;
+pub use tls::RpcTlsError;
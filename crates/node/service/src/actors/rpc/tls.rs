@@ -0,0 +1,296 @@
+//! TLS termination for the RPC server.
+
+use kona_rpc::{RpcTlsConfig, TlsCertKeyPaths};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rustls::{
+    ServerConfig,
+    crypto::CryptoProvider,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+};
+use tokio::net::TcpStream;
+use tokio_rustls::{Accept, TlsAcceptor};
+
+/// PEM parsing error type alias
+type PemError = rustls::pki_types::pem::Error;
+
+/// An error loading the certificates of the RPC server.
+#[derive(Debug, thiserror::Error)]
+pub enum RpcTlsError {
+    /// The certificate file could not be parsed.
+    #[error("Invalid certificate {}: {1}", .0.display())]
+    InvalidCertificate(PathBuf, PemError),
+    /// The certificate file contains no certificate.
+    #[error("No certificate found in {}", .0.display())]
+    MissingCertificate(PathBuf),
+    /// The private key file could not be parsed.
+    #[error("Invalid private key {}: {1}", .0.display())]
+    InvalidPrivateKey(PathBuf, PemError),
+    /// The crypto provider does not support the TLS protocol versions of the server.
+    #[error("Unsupported TLS configuration: {0}")]
+    Config(rustls::Error),
+    /// The private key is not supported by the crypto provider.
+    #[error("Unsupported private key {}: {1}", .0.display())]
+    UnsupportedPrivateKey(PathBuf, rustls::Error),
+    /// Failed to watch the certificate files for changes.
+    #[error("Failed to watch the certificate files: {0}")]
+    Watch(#[from] notify::Error),
+}
+
+/// Accepts TLS connections to the RPC server.
+///
+/// The certificate files are watched, and the certificates are reloaded when they change, so
+/// renewed certificates are served without restarting the server. If the new certificates can't
+/// be loaded, the previous ones keep being served.
+#[derive(Debug, Clone)]
+pub(super) struct RpcTlsAcceptor {
+    /// The TLS configuration of the server, resolving certificates through a [`CertResolver`].
+    config: Arc<ServerConfig>,
+    /// The watcher of the certificate files, stopped when the last acceptor is dropped.
+    _watcher: Arc<RecommendedWatcher>,
+}
+
+impl RpcTlsAcceptor {
+    /// Loads the certificates of the [`RpcTlsConfig`] and starts watching their files.
+    pub(super) fn new(tls: &RpcTlsConfig) -> Result<Self, RpcTlsError> {
+        // The crypto provider is set explicitly, as the process-wide default can't be determined
+        // when several providers are enabled in the dependency graph.
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .map_err(RpcTlsError::Config)?;
+        let resolver = Arc::new(CertResolver::new(tls.clone(), provider)?);
+
+        let mut config = builder.with_no_client_auth().with_cert_resolver(resolver.clone());
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        // Watch the parent directories rather than the files, since certificates are usually
+        // renewed by replacing the files, which drops the watches on the previous ones. The paths
+        // are made absolute to match the paths of the watcher events.
+        let paths: BTreeSet<PathBuf> = tls
+            .paths()
+            .map(|path| std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+            .collect();
+        let dirs: BTreeSet<PathBuf> =
+            paths.iter().filter_map(|path| path.parent()).map(Path::to_path_buf).collect();
+        let mut watcher =
+            notify::recommended_watcher(move |res: Result<Event, notify::Error>| match res {
+                Ok(event @ Event { kind: EventKind::Create(_) | EventKind::Modify(_), .. })
+                    if event.paths.iter().any(|path| paths.contains(path)) =>
+                {
+                    resolver.reload();
+                }
+                Ok(event) => {
+                    trace!(target: "rpc::tls", ?event, "Ignoring certificate watcher event");
+                }
+                Err(err) => {
+                    error!(target: "rpc::tls", %err, "Failed to receive certificate watcher event");
+                }
+            })?;
+        for dir in dirs {
+            watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self { config: Arc::new(config), _watcher: Arc::new(watcher) })
+    }
+
+    /// Performs the TLS handshake of an incoming connection.
+    pub(super) fn accept(&self, stream: TcpStream) -> Accept<TcpStream> {
+        TlsAcceptor::from(Arc::clone(&self.config)).accept(stream)
+    }
+}
+
+/// Resolves the certificate served to a client from its server name indication.
+#[derive(Debug)]
+struct CertResolver {
+    /// The paths of the certificates.
+    tls: RpcTlsConfig,
+    /// The crypto provider loading the private keys.
+    provider: Arc<CryptoProvider>,
+    /// The currently loaded certificates.
+    certificates: RwLock<Certificates>,
+}
+
+impl CertResolver {
+    /// Creates a new [`CertResolver`], loading the certificates of the [`RpcTlsConfig`].
+    fn new(tls: RpcTlsConfig, provider: Arc<CryptoProvider>) -> Result<Self, RpcTlsError> {
+        let certificates = RwLock::new(Certificates::load(&tls, &provider)?);
+        Ok(Self { tls, provider, certificates })
+    }
+
+    /// Reloads the certificates from their files, keeping the current ones if that fails.
+    fn reload(&self) {
+        match Certificates::load(&self.tls, &self.provider) {
+            Ok(certificates) => {
+                *self.certificates.write().unwrap_or_else(PoisonError::into_inner) = certificates;
+                info!(target: "rpc::tls", "Reloaded RPC server certificates");
+            }
+            Err(err) => {
+                error!(target: "rpc::tls", %err, "Failed to reload RPC server certificates");
+            }
+        }
+    }
+}
+
+impl ResolvesServerCert for CertResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let certificates = self.certificates.read().unwrap_or_else(PoisonError::into_inner);
+        let certificate = client_hello
+            .server_name()
+            .and_then(|server_name| lookup(&certificates.by_name, server_name))
+            .unwrap_or(&certificates.default);
+        Some(Arc::clone(certificate))
+    }
+}
+
+/// The loaded certificates of the RPC server.
+#[derive(Debug)]
+struct Certificates {
+    /// The certificate served when no other one matches the server name of the client.
+    default: Arc<CertifiedKey>,
+    /// The certificates selected by server name, keyed by lowercase server name.
+    by_name: HashMap<String, Arc<CertifiedKey>>,
+}
+
+impl Certificates {
+    /// Loads all the certificates of the [`RpcTlsConfig`].
+    fn load(tls: &RpcTlsConfig, provider: &CryptoProvider) -> Result<Self, RpcTlsError> {
+        let default = Arc::new(load_certified_key(&tls.default, provider)?);
+        let by_name = tls
+            .sni
+            .iter()
+            .map(|(name, paths)| {
+                Ok((name.to_ascii_lowercase(), Arc::new(load_certified_key(paths, provider)?)))
+            })
+            .collect::<Result<_, RpcTlsError>>()?;
+        Ok(Self { default, by_name })
+    }
+}
+
+/// Loads a certificate chain and its private key from their PEM files.
+fn load_certified_key(
+    paths: &TlsCertKeyPaths,
+    provider: &CryptoProvider,
+) -> Result<CertifiedKey, RpcTlsError> {
+    let certs = CertificateDer::pem_file_iter(&paths.cert)
+        .map_err(|err| RpcTlsError::InvalidCertificate(paths.cert.clone(), err))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| RpcTlsError::InvalidCertificate(paths.cert.clone(), err))?;
+    if certs.is_empty() {
+        return Err(RpcTlsError::MissingCertificate(paths.cert.clone()));
+    }
+
+    let key = PrivateKeyDer::from_pem_file(&paths.key)
+        .map_err(|err| RpcTlsError::InvalidPrivateKey(paths.key.clone(), err))?;
+    let key = provider
+        .key_provider
+        .load_private_key(key)
+        .map_err(|err| RpcTlsError::UnsupportedPrivateKey(paths.key.clone(), err))?;
+
+    Ok(CertifiedKey::new(certs, key))
+}
+
+/// Looks up the entry of a server name, falling back to the wildcard entry of its parent domain.
+fn lookup<'a, T>(by_name: &'a HashMap<String, T>, server_name: &str) -> Option<&'a T> {
+    let server_name = server_name.to_ascii_lowercase();
+    by_name.get(&server_name).or_else(|| {
+        let (_, parent) = server_name.split_once('.')?;
+        by_name.get(&format!("*.{parent}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustls::{ClientConfig, RootCertStore, pki_types::ServerName};
+    use tokio::net::TcpListener;
+    use tokio_rustls::TlsConnector;
+
+    /// The directory of the test certificates.
+    const TESTDATA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/tls");
+
+    /// Returns the paths of the test certificate of the given server name.
+    fn paths(name: &str) -> TlsCertKeyPaths {
+        TlsCertKeyPaths {
+            cert: format!("{TESTDATA}/{name}.crt").into(),
+            key: format!("{TESTDATA}/{name}.key").into(),
+        }
+    }
+
+    /// Connects to the acceptor with the given server name, trusting all the test certificates.
+    /// Returns the certificate served, and the negotiated ALPN protocol.
+    async fn handshake(
+        acceptor: &RpcTlsAcceptor,
+        server_name: &str,
+    ) -> (CertificateDer<'static>, Option<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = acceptor.clone();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            acceptor.accept(stream).await.unwrap();
+        });
+
+        let mut roots = RootCertStore::empty();
+        for name in ["localhost", "rpc.example.com"] {
+            roots.add(CertificateDer::from_pem_file(paths(name).cert).unwrap()).unwrap();
+        }
+        let mut config =
+            ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .unwrap()
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+        config.alpn_protocols = vec![b"h2".to_vec()];
+
+        let server_name = ServerName::try_from(server_name.to_string()).unwrap();
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let stream =
+            TlsConnector::from(Arc::new(config)).connect(server_name, stream).await.unwrap();
+        server.await.unwrap();
+
+        let (_, connection) = stream.get_ref();
+        let certificate = connection.peer_certificates().unwrap()[0].clone().into_owned();
+        (certificate, connection.alpn_protocol().map(<[u8]>::to_vec))
+    }
+
+    #[tokio::test]
+    async fn test_handshake_selects_certificate_by_server_name() {
+        let acceptor = RpcTlsAcceptor::new(&RpcTlsConfig {
+            default: paths("localhost"),
+            sni: vec![("rpc.example.com".to_string(), paths("rpc.example.com"))],
+        })
+        .unwrap();
+        let certificate = |name| CertificateDer::from_pem_file(paths(name).cert).unwrap();
+
+        let (served, alpn) = handshake(&acceptor, "localhost").await;
+        assert_eq!(served, certificate("localhost"));
+        assert_eq!(alpn.as_deref(), Some(&b"h2"[..]));
+
+        let (served, _) = handshake(&acceptor, "rpc.example.com").await;
+        assert_eq!(served, certificate("rpc.example.com"));
+    }
+
+    #[test]
+    fn test_lookup_server_name() {
+        let by_name = HashMap::from([
+            ("rpc.example.com".to_string(), 1),
+            ("*.example.com".to_string(), 2),
+            ("*.example.org".to_string(), 3),
+        ]);
+
+        assert_eq!(lookup(&by_name, "rpc.example.com"), Some(&1));
+        assert_eq!(lookup(&by_name, "RPC.Example.com"), Some(&1));
+        assert_eq!(lookup(&by_name, "ws.example.com"), Some(&2));
+        assert_eq!(lookup(&by_name, "rpc.example.org"), Some(&3));
+        assert_eq!(lookup(&by_name, "a.rpc.example.org"), None);
+        assert_eq!(lookup(&by_name, "example.com"), None);
+        assert_eq!(lookup(&by_name, "example.net"), None);
+    }
+}
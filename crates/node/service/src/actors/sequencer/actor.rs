@@ -8,12 +8,15 @@ use crate::{
         sequencer::{
             admin_api_client::SequencerAdminQuery,
             conductor::Conductor,
+            config::PayloadBuildConfig,
             error::SequencerActorError,
             metrics::{
                 update_attributes_build_duration_metrics, update_block_build_duration_metrics,
                 update_conductor_commitment_duration_metrics, update_seal_duration_metrics,
+                update_sealed_payload_metrics,
             },
             origin_selector::OriginSelector,
+            throttle::DaThrottle,
         },
     },
 };
@@ -23,6 +26,7 @@ use kona_derive::{AttributesBuilder, PipelineErrorKind};
 use kona_engine::{InsertTaskError, SealTaskError, SynchronizeTaskError};
 use kona_genesis::RollupConfig;
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use op_alloy_consensus::OpTxType;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use std::{
     sync::Arc,
@@ -38,6 +42,8 @@ pub(super) struct UnsealedPayloadHandle {
     pub payload_id: PayloadId,
     /// The [`OpAttributesWithParent`] used to start block building.
     pub attributes_with_parent: OpAttributesWithParent,
+    /// When block building was started.
+    pub build_started: Instant,
 }
 
 /// The return payload of the `seal_last_and_start_next` function. This allows the sequencer
@@ -48,6 +54,8 @@ struct SealLastStartNextResult {
     pub unsealed_payload_handle: Option<UnsealedPayloadHandle>,
     /// How long it took to execute the seal operation.
     pub seal_duration: Duration,
+    /// The gas utilization of the sealed block, in percent of its gas limit, if one was sealed.
+    pub sealed_gas_utilization: Option<u8>,
 }
 
 /// The [`SequencerActor`] is responsible for building L2 blocks on top of the current unsafe head
@@ -77,12 +85,16 @@ pub struct SequencerActor<
     pub cancellation_token: CancellationToken,
     /// The optional conductor RPC client.
     pub conductor: Option<Conductor_>,
+    /// Throttles the DA usage of sequenced blocks based on the DA backlog, if enabled.
+    pub da_throttle: Option<DaThrottle>,
     /// Whether the sequencer is active.
     pub is_active: bool,
     /// Whether the sequencer is in recovery mode.
     pub in_recovery_mode: bool,
     /// The struct used to determine the next L1 origin.
     pub origin_selector: OriginSelector_,
+    /// The tuning of the payloads built by the sequencer.
+    pub payload_build_config: PayloadBuildConfig,
     /// The rollup configuration.
     pub rollup_config: Arc<RollupConfig>,
     /// A client to asynchronously sign and gossip built payloads to the network actor.
@@ -119,26 +131,32 @@ where
         &mut self,
         payload_to_seal: Option<&UnsealedPayloadHandle>,
     ) -> Result<SealLastStartNextResult, SequencerActorError> {
-        let seal_duration = match payload_to_seal {
+        let (seal_duration, sealed_gas_utilization) = match payload_to_seal {
             Some(to_seal) => {
                 let seal_start = Instant::now();
-                self.seal_and_commit_payload_if_applicable(to_seal).await?;
-                seal_start.elapsed()
+                let gas_utilization = self.seal_and_commit_payload_if_applicable(to_seal).await?;
+                (seal_start.elapsed(), Some(gas_utilization))
             }
-            None => Duration::default(),
+            None => (Duration::default(), None),
         };
 
         let unsealed_payload_handle = self.build_unsealed_payload().await?;
 
-        Ok(SealLastStartNextResult { unsealed_payload_handle, seal_duration })
+        Ok(SealLastStartNextResult {
+            unsealed_payload_handle,
+            seal_duration,
+            sealed_gas_utilization,
+        })
     }
 
     /// Sends a seal request to seal the provided [`UnsealedPayloadHandle`], committing and
     /// gossiping the resulting block, if one is built.
+    ///
+    /// Returns the gas utilization of the sealed block, in percent of its gas limit.
     async fn seal_and_commit_payload_if_applicable(
         &mut self,
         unsealed_payload_handle: &UnsealedPayloadHandle,
-    ) -> Result<(), SequencerActorError> {
+    ) -> Result<u8, SequencerActorError> {
         let seal_request_start = Instant::now();
 
         // Send the seal request to the engine to seal the unsealed block.
@@ -152,6 +170,32 @@ where
 
         update_seal_duration_metrics(seal_request_start.elapsed());
 
+        let block = payload.execution_payload.as_v1();
+        let deposits = block
+            .transactions
+            .iter()
+            .filter(|tx| tx.first() == Some(&(OpTxType::Deposit as u8)))
+            .count();
+        let gas_utilization = block
+            .gas_used
+            .saturating_mul(100)
+            .checked_div(block.gas_limit)
+            .map_or(0, |utilization| utilization.min(100) as u8);
+        update_sealed_payload_metrics(
+            unsealed_payload_handle.build_started.elapsed(),
+            deposits,
+            block.transactions.len() - deposits,
+            gas_utilization,
+        );
+        debug!(
+            target: "sequencer",
+            number = block.block_number,
+            transactions = block.transactions.len(),
+            deposits,
+            gas_utilization,
+            "Sealed block"
+        );
+
         // If the conductor is available, commit the payload to it.
         if let Some(conductor) = &self.conductor {
             let _conductor_commitment_start = Instant::now();
@@ -162,10 +206,8 @@ where
             update_conductor_commitment_duration_metrics(_conductor_commitment_start.elapsed());
         }
 
-        self.unsafe_payload_gossip_client
-            .schedule_execution_payload_gossip(payload)
-            .await
-            .map_err(Into::into)
+        self.unsafe_payload_gossip_client.schedule_execution_payload_gossip(payload).await?;
+        Ok(gas_utilization)
     }
 
     /// Starts building an L2 block by creating and populating payload attributes referencing the
@@ -198,6 +240,11 @@ where
 
         update_attributes_build_duration_metrics(attributes_build_start.elapsed());
 
+        // Limit the DA usage of the block according to the DA backlog.
+        if let Some(da_throttle) = &mut self.da_throttle {
+            da_throttle.apply().await;
+        }
+
         // Send the built attributes to the engine to be built.
         let build_request_start = Instant::now();
 
@@ -206,7 +253,11 @@ where
 
         update_block_build_duration_metrics(build_request_start.elapsed());
 
-        Ok(Some(UnsealedPayloadHandle { payload_id, attributes_with_parent }))
+        Ok(Some(UnsealedPayloadHandle {
+            payload_id,
+            attributes_with_parent,
+            build_started: build_request_start,
+        }))
     }
 
     /// Determines and validates the L1 origin block for the provided L2 unsafe head.
@@ -288,14 +339,59 @@ where
         Ok(Some(attrs_with_parent))
     }
 
+    /// Returns the time at which to seal the provided [`UnsealedPayloadHandle`], given how long
+    /// the last seal took and the gas utilization of the last sealed block.
+    ///
+    /// Payloads are sealed at their slot, less the time it takes to seal them, or once their
+    /// [`PayloadBuildConfig::deadline`] has passed since the later of `now` and the start of
+    /// their parent's slot, whichever comes first.
+    pub(super) fn seal_time(
+        &self,
+        payload: &UnsealedPayloadHandle,
+        last_seal_duration: Duration,
+        last_gas_utilization: Option<u8>,
+        now: SystemTime,
+    ) -> SystemTime {
+        let parent_time = UNIX_EPOCH +
+            Duration::from_secs(payload.attributes_with_parent.parent().block_info.timestamp);
+        let slot_time =
+            parent_time + Duration::from_secs(self.rollup_config.block_time) - last_seal_duration;
+
+        match self.payload_build_config.deadline(last_gas_utilization) {
+            Some(deadline) => slot_time.min(now.max(parent_time) + deadline),
+            None => slot_time,
+        }
+    }
+
     /// Determines, for the provided L1 origin block and payload attributes being constructed, if
     /// transaction pool transactions should be enabled.
-    fn should_use_tx_pool(&self, l1_origin: BlockInfo, attributes: &OpPayloadAttributes) -> bool {
+    pub(super) fn should_use_tx_pool(
+        &self,
+        l1_origin: BlockInfo,
+        attributes: &OpPayloadAttributes,
+    ) -> bool {
         if self.in_recovery_mode {
             warn!(target: "sequencer", "Sequencer is in recovery mode, producing empty block");
             return false;
         }
 
+        // If the block carries more deposits than allowed alongside pool transactions, it is
+        // built with its deposits only.
+        if let Some(max_deposits) = self.payload_build_config.max_deposits_per_block {
+            let deposits = attributes.transactions.as_ref().map_or(0, |txs| {
+                txs.iter().filter(|tx| tx.first() == Some(&(OpTxType::Deposit as u8))).count()
+            });
+            if deposits > max_deposits {
+                info!(
+                    target: "sequencer",
+                    deposits,
+                    max_deposits,
+                    "Block exceeds the maximum number of deposits, producing deposit-only block"
+                );
+                return false;
+            }
+        }
+
         // If the next L2 block is beyond the sequencer drift threshold, we must produce an empty
         // block.
         if attributes.payload_attributes.timestamp >
@@ -400,6 +496,7 @@ where
 
         let mut next_payload_to_seal: Option<UnsealedPayloadHandle> = None;
         let mut last_seal_duration = Duration::from_secs(0);
+        let mut last_gas_utilization = None;
         loop {
             select! {
                 // We are using a biased select here to ensure that the admin queries are given priority over the block building task.
@@ -429,6 +526,7 @@ where
                         Ok(res) => {
                             next_payload_to_seal = res.unsealed_payload_handle;
                             last_seal_duration = res.seal_duration;
+                            last_gas_utilization = res.sealed_gas_utilization.or(last_gas_utilization);
                         },
                         Err(SequencerActorError::BlockEngine(BlockEngineError::SealError(err))) => {
                             if is_seal_task_err_fatal(&err) {
@@ -447,10 +545,9 @@ where
                     }
 
                     if let Some(ref payload) = next_payload_to_seal {
-                        let next_block_seconds = payload.attributes_with_parent.parent().block_info.timestamp.saturating_add(self.rollup_config.block_time);
-                        // next block time is last + block_time - time it takes to seal.
-                        let next_block_time = UNIX_EPOCH + Duration::from_secs(next_block_seconds) - last_seal_duration;
-                        match next_block_time.duration_since(SystemTime::now()) {
+                        let now = SystemTime::now();
+                        let next_block_time = self.seal_time(payload, last_seal_duration, last_gas_utilization, now);
+                        match next_block_time.duration_since(now) {
                             Ok(duration) => build_ticker.reset_after(duration),
                             Err(_) => build_ticker.reset_immediately(),
                         };
@@ -512,7 +609,7 @@ fn is_seal_task_err_fatal(err: &SealTaskError) -> bool {
         SealTaskError::GetPayloadFailed(_) => false,
         SealTaskError::DepositOnlyPayloadFailed => true,
         SealTaskError::DepositOnlyPayloadReattemptFailed => true,
-        SealTaskError::HoloceneInvalidFlush => false,
+        SealTaskError::HoloceneInvalidFlush(_) => false,
         SealTaskError::FromBlock(_) => true,
         SealTaskError::MpscSend(_) => true,
         SealTaskError::ClockWentBackwards => true,
@@ -4,6 +4,7 @@
 use alloy_primitives::B256;
 use async_trait::async_trait;
 use derive_more::Constructor;
+use kona_protocol::{BlockInfo, L2BlockInfo};
 use kona_rpc::{SequencerAdminAPIClient, SequencerAdminAPIError};
 use tokio::sync::{mpsc, oneshot};
 
@@ -32,8 +33,13 @@ pub enum SequencerAdminQuery {
     SetRecoveryMode(bool, oneshot::Sender<Result<(), SequencerAdminAPIError>>),
     /// A query to override the leader.
     OverrideLeader(oneshot::Sender<Result<(), SequencerAdminAPIError>>),
-    /// A query to reset the derivation pipeline.
-    ResetDerivationPipeline(oneshot::Sender<Result<(), SequencerAdminAPIError>>),
+    /// A query to reset the derivation pipeline, optionally pinning the L2 safe head and L1 origin
+    /// it restarts from.
+    ResetDerivationPipeline(
+        Option<L2BlockInfo>,
+        Option<BlockInfo>,
+        oneshot::Sender<Result<(), SequencerAdminAPIError>>,
+    ),
 }
 
 #[async_trait]
@@ -115,12 +121,19 @@ impl SequencerAdminAPIClient for QueuedSequencerAdminAPIClient {
         })?
     }
 
-    async fn reset_derivation_pipeline(&self) -> Result<(), SequencerAdminAPIError> {
+    async fn reset_derivation_pipeline(
+        &self,
+        l2_safe_head: Option<L2BlockInfo>,
+        l1_origin: Option<BlockInfo>,
+    ) -> Result<(), SequencerAdminAPIError> {
         let (tx, rx) = oneshot::channel();
 
-        self.request_tx.send(SequencerAdminQuery::ResetDerivationPipeline(tx)).await.map_err(
-            |_| SequencerAdminAPIError::RequestError("request channel closed".to_string()),
-        )?;
+        self.request_tx
+            .send(SequencerAdminQuery::ResetDerivationPipeline(l2_safe_head, l1_origin, tx))
+            .await
+            .map_err(|_| {
+                SequencerAdminAPIError::RequestError("request channel closed".to_string())
+            })?;
         rx.await.map_err(|_| {
             SequencerAdminAPIError::ResponseError("response channel closed".to_string())
         })?
@@ -4,6 +4,7 @@ use crate::{
 };
 use alloy_primitives::B256;
 use kona_derive::AttributesBuilder;
+use kona_protocol::{BlockInfo, L2BlockInfo};
 use kona_rpc::{SequencerAdminAPIError, StopSequencerError};
 
 /// Handler for the Sequencer Admin API.
@@ -67,8 +68,8 @@ where
                     warn!(target: "sequencer", "Failed to send response for override_leader query");
                 }
             }
-            SequencerAdminQuery::ResetDerivationPipeline(tx) => {
-                if tx.send(self.reset_derivation_pipeline().await).is_err() {
+            SequencerAdminQuery::ResetDerivationPipeline(l2_safe_head, l1_origin, tx) => {
+                if tx.send(self.reset_derivation_pipeline(l2_safe_head, l1_origin).await).is_err() {
                     warn!(target: "sequencer", "Failed to send response for reset_derivation_pipeline query");
                 }
             }
@@ -153,11 +154,17 @@ where
         Ok(())
     }
 
-    pub(super) async fn reset_derivation_pipeline(&mut self) -> Result<(), SequencerAdminAPIError> {
-        info!(target: "sequencer", "Resetting derivation pipeline");
-        self.block_building_client.reset_engine_forkchoice().await.map_err(|e| {
-            error!(target: "sequencer", err=?e, "Failed to reset engine forkchoice");
-            SequencerAdminAPIError::RequestError(format!("Failed to reset engine: {e}"))
-        })
+    pub(super) async fn reset_derivation_pipeline(
+        &mut self,
+        l2_safe_head: Option<L2BlockInfo>,
+        l1_origin: Option<BlockInfo>,
+    ) -> Result<(), SequencerAdminAPIError> {
+        info!(target: "sequencer", ?l2_safe_head, ?l1_origin, "Resetting derivation pipeline");
+        self.block_building_client.reset_derivation_pipeline(l2_safe_head, l1_origin).await.map_err(
+            |e| {
+                error!(target: "sequencer", err=?e, "Failed to reset engine forkchoice");
+                SequencerAdminAPIError::RequestError(format!("Failed to reset engine: {e}"))
+            },
+        )
     }
 }
@@ -2,6 +2,8 @@
 //!
 //! [`SequencerActor`]: super::SequencerActor
 
+use crate::DaThrottleConfig;
+use std::time::Duration;
 use url::Url;
 
 /// Configuration for the [`SequencerActor`].
@@ -17,4 +19,38 @@ pub struct SequencerConfig {
     pub conductor_rpc_url: Option<Url>,
     /// The confirmation delay for the sequencer.
     pub l1_conf_delay: u64,
+    /// The tuning of the payloads built by the sequencer.
+    pub payload_build: PayloadBuildConfig,
+    /// The DA throttling of sequenced blocks. If [`None`], blocks aren't throttled.
+    pub da_throttle: Option<DaThrottleConfig>,
+}
+
+/// Tuning of the payloads built by the [`SequencerActor`], trading block latency against
+/// fullness.
+///
+/// [`SequencerActor`]: super::SequencerActor
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadBuildConfig {
+    /// The maximum time to build a payload for before sealing it. Payloads are never sealed later
+    /// than their slot. If [`None`], payloads are built until their slot.
+    pub build_deadline: Option<Duration>,
+    /// The gas utilization of the previous block, in percent of its gas limit, below which the
+    /// [`Self::build_deadline`] is lifted, giving the next payload its full slot to fill up.
+    pub target_gas_utilization: Option<u8>,
+    /// The maximum number of deposits in a block that may also include transactions from the
+    /// transaction pool. Blocks with more deposits are built with their deposits only.
+    ///
+    /// Deposits are mandated by the derivation rules, so they are never dropped.
+    pub max_deposits_per_block: Option<usize>,
+}
+
+impl PayloadBuildConfig {
+    /// Returns the build deadline of the next payload, given the gas utilization of the
+    /// previously sealed block, in percent of its gas limit.
+    pub const fn deadline(&self, last_gas_utilization: Option<u8>) -> Option<Duration> {
+        match (self.target_gas_utilization, last_gas_utilization) {
+            (Some(target), Some(utilization)) if utilization < target => None,
+            _ => self.build_deadline,
+        }
+    }
 }
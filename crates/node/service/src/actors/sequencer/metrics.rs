@@ -68,3 +68,30 @@ pub(super) fn update_seal_duration_metrics(duration: Duration) {
     // Log the block building seal task duration, if metrics are enabled.
     kona_macros::set!(gauge, crate::Metrics::SEQUENCER_BLOCK_BUILDING_SEAL_TASK_DURATION, duration);
 }
+
+#[inline]
+pub(super) fn update_sealed_payload_metrics(
+    build_duration: Duration,
+    deposits: usize,
+    pool_transactions: usize,
+    gas_utilization: u8,
+) {
+    kona_macros::set!(gauge, crate::Metrics::SEQUENCER_PAYLOAD_BUILD_DURATION, build_duration);
+    kona_macros::set!(
+        gauge,
+        crate::Metrics::SEQUENCER_BLOCK_TRANSACTIONS,
+        "deposit",
+        deposits as f64
+    );
+    kona_macros::set!(
+        gauge,
+        crate::Metrics::SEQUENCER_BLOCK_TRANSACTIONS,
+        "pool",
+        pool_transactions as f64
+    );
+    kona_macros::set!(
+        gauge,
+        crate::Metrics::SEQUENCER_BLOCK_GAS_UTILIZATION,
+        gas_utilization as f64
+    );
+}
@@ -1,7 +1,12 @@
 //! The `SequencerActor` and its components.
 
 mod config;
-pub use config::SequencerConfig;
+pub use config::{PayloadBuildConfig, SequencerConfig};
+
+mod throttle;
+pub use throttle::{
+    DaLimits, DaLimitsClient, DaThrottle, DaThrottleConfig, DaThrottlePolicy, LinearDaThrottle,
+};
 
 mod origin_selector;
 pub use origin_selector::{
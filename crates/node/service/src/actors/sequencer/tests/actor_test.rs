@@ -1,13 +1,22 @@
 #[cfg(test)]
 use crate::{
-    SequencerActorError,
+    PayloadBuildConfig, SequencerActorError,
     actors::{
-        MockBlockBuildingClient, MockOriginSelector, sequencer::tests::test_util::test_actor,
+        MockBlockBuildingClient, MockOriginSelector,
+        sequencer::{actor::UnsealedPayloadHandle, tests::test_util::test_actor},
     },
 };
+use alloy_rpc_types_engine::PayloadId;
 use kona_derive::{BuilderError, PipelineErrorKind, test_utils::TestAttributesBuilder};
-use kona_protocol::{BlockInfo, L2BlockInfo};
+use kona_genesis::RollupConfig;
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use op_alloy_consensus::OpTxType;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use rstest::rstest;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 #[rstest]
 #[case::temp(PipelineErrorKind::Temporary(BuilderError::Custom("".into()).into()), false)]
@@ -50,3 +59,76 @@ async fn test_build_unsealed_payload_prepare_payload_attributes_error(
         assert!(result.is_ok());
     }
 }
+
+fn unsealed_payload(parent_timestamp: u64) -> UnsealedPayloadHandle {
+    let mut parent = L2BlockInfo::default();
+    parent.block_info.timestamp = parent_timestamp;
+    UnsealedPayloadHandle {
+        payload_id: PayloadId::default(),
+        attributes_with_parent: OpAttributesWithParent::new(
+            OpPayloadAttributes::default(),
+            parent,
+            None,
+            false,
+        ),
+        build_started: Instant::now(),
+    }
+}
+
+#[rstest]
+#[case::no_deadline(None, Some(100), 12)]
+#[case::deadline(Some(Duration::from_secs(1)), None, 11)]
+#[case::deadline_past_slot(Some(Duration::from_secs(5)), None, 12)]
+#[case::deadline_below_target(Some(Duration::from_secs(1)), Some(40), 12)]
+#[case::deadline_above_target(Some(Duration::from_secs(1)), Some(90), 11)]
+fn test_seal_time(
+    #[case] build_deadline: Option<Duration>,
+    #[case] last_gas_utilization: Option<u8>,
+    #[case] expected_seconds: u64,
+) {
+    let mut actor = test_actor();
+    actor.rollup_config = Arc::new(RollupConfig { block_time: 2, ..Default::default() });
+    actor.payload_build_config = PayloadBuildConfig {
+        build_deadline,
+        target_gas_utilization: Some(80),
+        max_deposits_per_block: None,
+    };
+
+    let payload = unsealed_payload(10);
+    let now = UNIX_EPOCH + Duration::from_secs(10);
+    let seal_time = actor.seal_time(&payload, Duration::ZERO, last_gas_utilization, now);
+    assert_eq!(seal_time, UNIX_EPOCH + Duration::from_secs(expected_seconds));
+}
+
+#[test]
+fn test_seal_time_subtracts_seal_duration() {
+    let mut actor = test_actor();
+    actor.rollup_config = Arc::new(RollupConfig { block_time: 2, ..Default::default() });
+
+    let payload = unsealed_payload(10);
+    let seal_time =
+        actor.seal_time(&payload, Duration::from_millis(500), None, SystemTime::UNIX_EPOCH);
+    assert_eq!(seal_time, UNIX_EPOCH + Duration::from_millis(11_500));
+}
+
+#[rstest]
+#[case::unlimited(None, true)]
+#[case::within_limit(Some(2), true)]
+#[case::above_limit(Some(1), false)]
+fn test_should_use_tx_pool_max_deposits(
+    #[case] max_deposits_per_block: Option<usize>,
+    #[case] expected: bool,
+) {
+    let mut actor = test_actor();
+    actor.payload_build_config.max_deposits_per_block = max_deposits_per_block;
+
+    let attributes = OpPayloadAttributes {
+        transactions: Some(vec![
+            vec![OpTxType::Deposit as u8, 0x0].into(),
+            vec![OpTxType::Deposit as u8, 0x1].into(),
+            vec![OpTxType::Eip1559 as u8, 0x2].into(),
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(actor.should_use_tx_pool(BlockInfo::default(), &attributes), expected);
+}
@@ -294,18 +294,34 @@ async fn test_override_leader(
 #[rstest]
 #[tokio::test]
 async fn test_reset_derivation_pipeline_success(#[values(true, false)] via_channel: bool) {
+    let l2_safe_head = L2BlockInfo {
+        block_info: BlockInfo { number: 10, ..Default::default() },
+        ..Default::default()
+    };
+    let l1_origin = BlockInfo { number: 5, ..Default::default() };
+
     let mut client = MockBlockBuildingClient::new();
-    client.expect_reset_engine_forkchoice().times(1).return_once(|| Ok(()));
+    client
+        .expect_reset_derivation_pipeline()
+        .withf(move |l2, l1| *l2 == Some(l2_safe_head) && *l1 == Some(l1_origin))
+        .times(1)
+        .return_once(|_, _| Ok(()));
 
     let mut actor = test_actor();
     actor.block_building_client = client;
 
     let result = async {
         match via_channel {
-            false => actor.reset_derivation_pipeline().await,
+            false => actor.reset_derivation_pipeline(Some(l2_safe_head), Some(l1_origin)).await,
             true => {
                 let (tx, rx) = oneshot::channel();
-                actor.handle_admin_query(SequencerAdminQuery::ResetDerivationPipeline(tx)).await;
+                actor
+                    .handle_admin_query(SequencerAdminQuery::ResetDerivationPipeline(
+                        Some(l2_safe_head),
+                        Some(l1_origin),
+                        tx,
+                    ))
+                    .await;
                 rx.await.unwrap()
             }
         }
@@ -320,19 +336,23 @@ async fn test_reset_derivation_pipeline_success(#[values(true, false)] via_chann
 async fn test_reset_derivation_pipeline_error(#[values(true, false)] via_channel: bool) {
     let mut client = MockBlockBuildingClient::new();
     client
-        .expect_reset_engine_forkchoice()
+        .expect_reset_derivation_pipeline()
         .times(1)
-        .return_once(|| Err(BlockEngineError::RequestError("reset failed".to_string())));
+        .return_once(|_, _| Err(BlockEngineError::RequestError("reset failed".to_string())));
 
     let mut actor = test_actor();
     actor.block_building_client = client;
 
     let result = async {
         match via_channel {
-            false => actor.reset_derivation_pipeline().await,
+            false => actor.reset_derivation_pipeline(None, None).await,
             true => {
                 let (tx, rx) = oneshot::channel();
-                actor.handle_admin_query(SequencerAdminQuery::ResetDerivationPipeline(tx)).await;
+                actor
+                    .handle_admin_query(SequencerAdminQuery::ResetDerivationPipeline(
+                        None, None, tx,
+                    ))
+                    .await;
                 rx.await.unwrap()
             }
         }
@@ -355,7 +375,7 @@ async fn test_handle_admin_query_resilient_to_dropped_receiver() {
     };
     let mut client = MockBlockBuildingClient::new();
     client.expect_get_unsafe_head().times(1).returning(move || Ok(unsafe_head));
-    client.expect_reset_engine_forkchoice().times(1).returning(|| Ok(()));
+    client.expect_reset_derivation_pipeline().times(1).returning(|_, _| Ok(()));
 
     let mut actor = test_actor();
     actor.conductor = Some(conductor);
@@ -400,7 +420,7 @@ async fn test_handle_admin_query_resilient_to_dropped_receiver() {
     {
         // immediately drop receiver
         let (tx, _rx) = oneshot::channel();
-        queries.push(SequencerAdminQuery::ResetDerivationPipeline(tx));
+        queries.push(SequencerAdminQuery::ResetDerivationPipeline(None, None, tx));
     }
 
     // None of these should fail even if the receiver is dropped
@@ -27,9 +27,11 @@ pub(crate) fn test_actor() -> SequencerActor<
         block_building_client: MockBlockBuildingClient::new(),
         cancellation_token: CancellationToken::new(),
         conductor: None,
+        da_throttle: None,
         is_active: true,
         in_recovery_mode: false,
         origin_selector: MockOriginSelector::new(),
+        payload_build_config: Default::default(),
         rollup_config: Arc::new(RollupConfig::default()),
         unsafe_payload_gossip_client: MockUnsafePayloadGossipClient::new(),
     }
@@ -0,0 +1,230 @@
+//! Throttling of the data availability usage of sequenced blocks.
+//!
+//! When L1 fees spike, the batcher submits less data than the sequencer produces, and the data
+//! pending submission grows. The [`DaThrottle`] feeds this backlog back into block building: before
+//! each payload is started, a [`DaThrottlePolicy`] turns the backlog into [`DaLimits`], which are
+//! applied to the execution client's block builder.
+
+use alloy_network::Network;
+use alloy_primitives::U64;
+use alloy_provider::{Provider, RootProvider};
+use alloy_transport::{RpcError, TransportErrorKind};
+use async_trait::async_trait;
+use std::fmt::Debug;
+use tokio::sync::watch;
+
+/// Limits on the data availability usage of a block, in estimated compressed bytes.
+///
+/// A limit of `0` disables it, matching the semantics of the `miner_setMaxDASize` RPC.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DaLimits {
+    /// The maximum DA size of a single transaction included in the block.
+    pub max_tx_size: u64,
+    /// The maximum DA size of the block.
+    pub max_block_size: u64,
+}
+
+impl DaLimits {
+    /// Limits that don't throttle block building.
+    pub const UNLIMITED: Self = Self { max_tx_size: 0, max_block_size: 0 };
+}
+
+/// A policy deciding the [`DaLimits`] of the next block from the DA backlog, in bytes pending
+/// submission to L1.
+pub trait DaThrottlePolicy: Debug + Send + Sync {
+    /// Returns the [`DaLimits`] to build the next block with.
+    fn limits(&self, backlog: u64) -> DaLimits;
+}
+
+/// Configuration of the [`LinearDaThrottle`] policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DaThrottleConfig {
+    /// The backlog, in bytes, above which blocks are throttled.
+    pub threshold: u64,
+    /// The maximum DA size of a transaction in a throttled block.
+    pub max_tx_size: u64,
+    /// The maximum DA size of a block once the backlog reaches twice the threshold.
+    pub block_size_lower_limit: u64,
+    /// The maximum DA size of a block as the backlog crosses the threshold.
+    pub block_size_upper_limit: u64,
+}
+
+/// A [`DaThrottlePolicy`] that doesn't throttle blocks while the backlog is at most the
+/// threshold, and then tightens the block size limit linearly, from the upper limit at the
+/// threshold down to the lower limit at twice the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinearDaThrottle {
+    /// The policy configuration.
+    pub config: DaThrottleConfig,
+}
+
+impl DaThrottlePolicy for LinearDaThrottle {
+    fn limits(&self, backlog: u64) -> DaLimits {
+        let DaThrottleConfig {
+            threshold,
+            max_tx_size,
+            block_size_lower_limit,
+            block_size_upper_limit,
+        } = self.config;
+        if backlog <= threshold {
+            return DaLimits::UNLIMITED;
+        }
+
+        let excess = (backlog - threshold).min(threshold.max(1)) as u128;
+        let range = block_size_upper_limit.saturating_sub(block_size_lower_limit) as u128;
+        let reduction = (range * excess / threshold.max(1) as u128) as u64;
+        DaLimits { max_tx_size, max_block_size: block_size_upper_limit - reduction }
+    }
+}
+
+/// A client applying [`DaLimits`] to the execution client's block builder.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DaLimitsClient: Debug + Send + Sync {
+    /// Sets the [`DaLimits`] of the blocks built from now on. Returns `false` if the execution
+    /// client doesn't support DA throttling.
+    async fn set_max_da_size(&self, limits: DaLimits)
+    -> Result<bool, RpcError<TransportErrorKind>>;
+}
+
+#[async_trait]
+impl<N: Network> DaLimitsClient for RootProvider<N> {
+    async fn set_max_da_size(
+        &self,
+        limits: DaLimits,
+    ) -> Result<bool, RpcError<TransportErrorKind>> {
+        self.raw_request(
+            "miner_setMaxDASize".into(),
+            (U64::from(limits.max_tx_size), U64::from(limits.max_block_size)),
+        )
+        .await
+    }
+}
+
+/// Throttles the DA usage of sequenced blocks based on the DA backlog.
+#[derive(Debug)]
+pub struct DaThrottle {
+    /// The policy turning the backlog into [`DaLimits`].
+    policy: Box<dyn DaThrottlePolicy>,
+    /// The client applying the [`DaLimits`] to the execution client.
+    client: Box<dyn DaLimitsClient>,
+    /// The DA backlog, in bytes pending submission to L1.
+    backlog: watch::Receiver<u64>,
+    /// The [`DaLimits`] last applied to the execution client, if any.
+    applied: Option<DaLimits>,
+}
+
+impl DaThrottle {
+    /// Creates a new [`DaThrottle`].
+    pub fn new(
+        policy: impl DaThrottlePolicy + 'static,
+        client: impl DaLimitsClient + 'static,
+        backlog: watch::Receiver<u64>,
+    ) -> Self {
+        Self { policy: Box::new(policy), client: Box::new(client), backlog, applied: None }
+    }
+
+    /// Applies the [`DaLimits`] for the current backlog to the execution client, if they changed
+    /// since they were last applied.
+    ///
+    /// Failures are logged rather than returned, since throttling must never stall the sequencer.
+    /// The limits are retried before the next block.
+    pub async fn apply(&mut self) {
+        let backlog = *self.backlog.borrow();
+        let limits = self.policy.limits(backlog);
+        kona_macros::set!(gauge, crate::Metrics::SEQUENCER_DA_THROTTLE, "backlog", backlog as f64);
+        if self.applied == Some(limits) {
+            return;
+        }
+
+        match self.client.set_max_da_size(limits).await {
+            Ok(true) => {
+                info!(
+                    target: "sequencer",
+                    backlog,
+                    max_tx_size = limits.max_tx_size,
+                    max_block_size = limits.max_block_size,
+                    "Updated DA throttling limits"
+                );
+                kona_macros::set!(
+                    gauge,
+                    crate::Metrics::SEQUENCER_DA_THROTTLE,
+                    "max_block_size",
+                    limits.max_block_size as f64
+                );
+                kona_macros::set!(
+                    gauge,
+                    crate::Metrics::SEQUENCER_DA_THROTTLE,
+                    "max_tx_size",
+                    limits.max_tx_size as f64
+                );
+                self.applied = Some(limits);
+            }
+            Ok(false) => {
+                warn!(target: "sequencer", "Execution client does not support DA throttling");
+                self.applied = Some(limits);
+            }
+            Err(err) => {
+                warn!(target: "sequencer", ?err, "Failed to update DA throttling limits");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: DaThrottleConfig = DaThrottleConfig {
+        threshold: 1_000,
+        max_tx_size: 300,
+        block_size_lower_limit: 2_000,
+        block_size_upper_limit: 130_000,
+    };
+
+    #[test]
+    fn test_linear_da_throttle() {
+        let policy = LinearDaThrottle { config: CONFIG };
+        assert_eq!(policy.limits(0), DaLimits::UNLIMITED);
+        assert_eq!(policy.limits(1_000), DaLimits::UNLIMITED);
+        assert_eq!(policy.limits(1_500), DaLimits { max_tx_size: 300, max_block_size: 66_000 });
+        assert_eq!(policy.limits(2_000), DaLimits { max_tx_size: 300, max_block_size: 2_000 });
+        assert_eq!(policy.limits(u64::MAX), DaLimits { max_tx_size: 300, max_block_size: 2_000 });
+    }
+
+    #[tokio::test]
+    async fn test_da_throttle_applies_changed_limits() {
+        let (backlog_tx, backlog_rx) = watch::channel(0);
+        let mut client = MockDaLimitsClient::new();
+        client.expect_set_max_da_size().times(2).returning(|_| Ok(true));
+
+        let mut throttle = DaThrottle::new(LinearDaThrottle { config: CONFIG }, client, backlog_rx);
+        throttle.apply().await;
+        assert_eq!(throttle.applied, Some(DaLimits::UNLIMITED));
+
+        // Unchanged limits aren't applied again.
+        backlog_tx.send(500).unwrap();
+        throttle.apply().await;
+
+        backlog_tx.send(2_000).unwrap();
+        throttle.apply().await;
+        assert_eq!(throttle.applied, Some(DaLimits { max_tx_size: 300, max_block_size: 2_000 }));
+    }
+
+    #[tokio::test]
+    async fn test_da_throttle_retries_failed_limits() {
+        let (_backlog_tx, backlog_rx) = watch::channel(2_000);
+        let mut client = MockDaLimitsClient::new();
+        client
+            .expect_set_max_da_size()
+            .times(1)
+            .returning(|_| Err(TransportErrorKind::backend_gone()));
+        client.expect_set_max_da_size().times(1).returning(|_| Ok(true));
+
+        let mut throttle = DaThrottle::new(LinearDaThrottle { config: CONFIG }, client, backlog_rx);
+        throttle.apply().await;
+        assert_eq!(throttle.applied, None);
+        throttle.apply().await;
+        assert!(throttle.applied.is_some());
+    }
+}
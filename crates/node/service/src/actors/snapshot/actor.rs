@@ -0,0 +1,201 @@
+//! [`NodeActor`] implementation exporting periodic snapshots of the node state.
+
+use crate::{
+    CancellableContext, NodeActor,
+    actors::snapshot::{NodeSnapshot, SnapshotActorError, SnapshotConfig, SnapshotTarget},
+};
+use async_trait::async_trait;
+use kona_engine::{EngineQueries, EngineQuerySender};
+use kona_gossip::{P2pRpcRequest, TracedP2pRpcRequest};
+use kona_protocol::{BlockInfo, SyncModeSelection};
+use kona_rpc::DerivationOriginStats;
+use std::{
+    collections::VecDeque,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    select,
+    sync::{mpsc, oneshot, watch},
+};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// The timeout of the requests pushing snapshots to an HTTP endpoint.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The [`SnapshotActor`] periodically collects a [`NodeSnapshot`] of the heads, sync status, peer
+/// counts, derivation origin and engine queue depth of the node, and exports it as JSON to a file
+/// or an HTTP endpoint.
+///
+/// This lets fleets scrape the state of their nodes without running Prometheus.
+#[derive(Debug)]
+pub struct SnapshotActor {
+    /// The snapshot configuration.
+    config: SnapshotConfig,
+    /// The client used to push snapshots to an HTTP endpoint.
+    client: reqwest::Client,
+}
+
+/// The communication context used by the [`SnapshotActor`].
+#[derive(Debug)]
+pub struct SnapshotContext {
+    /// The sender of queries to the engine.
+    pub engine_queries: EngineQuerySender,
+    /// The sender of queries to the p2p network.
+    pub p2p_network: mpsc::Sender<TracedP2pRpcRequest>,
+    /// The receiver of the L1 head observed by the node.
+    pub l1_head: watch::Receiver<Option<BlockInfo>>,
+    /// The receiver of the sync strategy selected at startup.
+    pub sync_mode: watch::Receiver<Option<SyncModeSelection>>,
+    /// The receiver of the stats of the most recent L1 origins of the derivation pipeline.
+    pub derivation_origins: watch::Receiver<VecDeque<DerivationOriginStats>>,
+    /// The cancellation token, shared between all tasks.
+    pub cancellation: CancellationToken,
+}
+
+impl CancellableContext for SnapshotContext {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+impl SnapshotContext {
+    /// Collects a [`NodeSnapshot`] from the engine, the p2p network and the watch channels of the
+    /// node.
+    async fn snapshot(&self) -> Result<NodeSnapshot, SnapshotActorError> {
+        let (state_tx, state_rx) = oneshot::channel();
+        self.engine_queries
+            .send(EngineQueries::State(state_tx))
+            .await
+            .map_err(|_| SnapshotActorError::ChannelClosed)?;
+        let state = state_rx.await.map_err(|_| SnapshotActorError::ChannelClosed)?;
+
+        let (queue_tx, queue_rx) = oneshot::channel();
+        self.engine_queries
+            .send(EngineQueries::TaskQueueLength(queue_tx))
+            .await
+            .map_err(|_| SnapshotActorError::ChannelClosed)?;
+        let engine_queue_length = queue_rx.await.map_err(|_| SnapshotActorError::ChannelClosed)?;
+
+        let (peers_tx, peers_rx) = oneshot::channel();
+        self.p2p_network
+            .send(P2pRpcRequest::PeerCount(peers_tx).into())
+            .await
+            .map_err(|_| SnapshotActorError::ChannelClosed)?;
+        let (discovered_peers, connected_peers) =
+            peers_rx.await.map_err(|_| SnapshotActorError::ChannelClosed)?;
+
+        Ok(NodeSnapshot {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            l1_head: *self.l1_head.borrow(),
+            unsafe_l2: state.sync_state.unsafe_head(),
+            cross_unsafe_l2: state.sync_state.cross_unsafe_head(),
+            local_safe_l2: state.sync_state.local_safe_head(),
+            safe_l2: state.sync_state.safe_head(),
+            finalized_l2: state.sync_state.finalized_head(),
+            el_sync_finished: state.el_sync_finished,
+            sync_mode: self.sync_mode.borrow().clone(),
+            derivation_origin: self.derivation_origins.borrow().back().map(|stats| stats.origin),
+            connected_peers,
+            discovered_peers,
+            engine_queue_length,
+        })
+    }
+}
+
+impl SnapshotActor {
+    /// Creates a new [`SnapshotActor`].
+    pub fn new(config: SnapshotConfig) -> Self {
+        Self { config, client: reqwest::Client::new() }
+    }
+
+    /// Collects a snapshot of the node state and exports it to the configured target.
+    async fn export(&self, ctx: &SnapshotContext) -> Result<(), SnapshotActorError> {
+        let body = serde_json::to_vec(&ctx.snapshot().await?)?;
+        match &self.config.target {
+            SnapshotTarget::File(path) => write_file(path, &body).await?,
+            SnapshotTarget::Http(url) => {
+                self.client
+                    .post(url.as_str())
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .timeout(HTTP_TIMEOUT)
+                    .body(body)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the contents of the file at `path`, by writing them to a sibling temporary file that
+/// is then renamed over it.
+async fn write_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let tmp_path = tmp_path(path);
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(&tmp_path, path).await
+}
+
+/// Returns the path of the temporary file a snapshot is written to before replacing `path`.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+#[async_trait]
+impl NodeActor for SnapshotActor {
+    type Error = SnapshotActorError;
+    type StartData = SnapshotContext;
+
+    async fn start(self, ctx: Self::StartData) -> Result<(), Self::Error> {
+        let mut ticker = tokio::time::interval(self.config.interval);
+
+        loop {
+            select! {
+                _ = ctx.cancellation.cancelled() => {
+                    info!(target: "snapshot", "Received shutdown signal. Exiting snapshot task.");
+                    return Ok(());
+                }
+                _ = ticker.tick() => match self.export(&ctx).await {
+                    Ok(()) => {}
+                    Err(SnapshotActorError::ChannelClosed) => {
+                        error!(target: "snapshot", "Node query channel closed unexpectedly");
+                        return Err(SnapshotActorError::ChannelClosed);
+                    }
+                    Err(e) => {
+                        warn!(target: "snapshot", error = %e, "Failed to export node snapshot");
+                        kona_macros::inc!(counter, crate::Metrics::SNAPSHOT_FAILURES);
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tmp_path() {
+        assert_eq!(
+            tmp_path(Path::new("/var/lib/kona/snapshot.json")),
+            PathBuf::from("/var/lib/kona/snapshot.json.tmp")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_file_replaces_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+
+        write_file(&path, b"{\"timestamp\":1}").await.unwrap();
+        write_file(&path, b"{\"timestamp\":2}").await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"timestamp\":2}");
+        assert!(!tmp_path(&path).exists());
+    }
+}
@@ -0,0 +1,29 @@
+//! Configuration for the [`SnapshotActor`].
+//!
+//! [`SnapshotActor`]: super::SnapshotActor
+
+use std::{path::PathBuf, time::Duration};
+use url::Url;
+
+/// Where the [`SnapshotActor`] exports the node state snapshots.
+///
+/// [`SnapshotActor`]: super::SnapshotActor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotTarget {
+    /// The snapshot is written to a file, replacing the previous one. The file is replaced
+    /// atomically, so readers never observe a partially written snapshot.
+    File(PathBuf),
+    /// The snapshot is sent as the JSON body of a `POST` request to an HTTP endpoint.
+    Http(Url),
+}
+
+/// Configuration for the [`SnapshotActor`].
+///
+/// [`SnapshotActor`]: super::SnapshotActor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotConfig {
+    /// Where the snapshots are exported.
+    pub target: SnapshotTarget,
+    /// The interval at which snapshots are taken.
+    pub interval: Duration,
+}
@@ -0,0 +1,16 @@
+/// An error produced by the [`crate::SnapshotActor`].
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotActorError {
+    /// A channel was unexpectedly closed.
+    #[error("Channel closed unexpectedly")]
+    ChannelClosed,
+    /// The snapshot could not be serialized.
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+    /// The snapshot could not be written to its file.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The snapshot could not be sent to its HTTP endpoint.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
@@ -0,0 +1,13 @@
+//! The `SnapshotActor` and its components.
+
+mod config;
+pub use config::{SnapshotConfig, SnapshotTarget};
+
+mod state;
+pub use state::NodeSnapshot;
+
+mod actor;
+pub use actor::{SnapshotActor, SnapshotContext};
+
+mod error;
+pub use error::SnapshotActorError;
@@ -0,0 +1,40 @@
+//! The node state exported by the [`SnapshotActor`].
+//!
+//! [`SnapshotActor`]: super::SnapshotActor
+
+use kona_protocol::{BlockInfo, L2BlockInfo, SyncModeSelection};
+use serde::Serialize;
+
+/// A compact snapshot of the state of the node, exported as JSON by the [`SnapshotActor`].
+///
+/// [`SnapshotActor`]: super::SnapshotActor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSnapshot {
+    /// The unix timestamp at which the snapshot was taken, in seconds.
+    pub timestamp: u64,
+    /// The L1 head observed by the node, if any.
+    pub l1_head: Option<BlockInfo>,
+    /// The unsafe L2 head.
+    pub unsafe_l2: L2BlockInfo,
+    /// The cross-verified unsafe L2 head.
+    pub cross_unsafe_l2: L2BlockInfo,
+    /// The local safe L2 head.
+    pub local_safe_l2: L2BlockInfo,
+    /// The safe L2 head.
+    pub safe_l2: L2BlockInfo,
+    /// The finalized L2 head.
+    pub finalized_l2: L2BlockInfo,
+    /// Whether the execution layer has finished syncing.
+    pub el_sync_finished: bool,
+    /// The sync strategy selected at startup, if any.
+    pub sync_mode: Option<SyncModeSelection>,
+    /// The L1 origin of the derivation pipeline, if it has advanced past any.
+    pub derivation_origin: Option<BlockInfo>,
+    /// The number of peers connected to the gossip network.
+    pub connected_peers: usize,
+    /// The number of peers in the discovery table, if discovery is enabled.
+    pub discovered_peers: Option<usize>,
+    /// The number of tasks queued in the engine.
+    pub engine_queue_length: usize,
+}
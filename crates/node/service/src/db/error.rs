@@ -0,0 +1,29 @@
+//! Error type for the [`NodeDb`].
+//!
+//! [`NodeDb`]: super::NodeDb
+
+use super::Table;
+
+/// An error from the [`NodeDb`].
+///
+/// [`NodeDb`]: super::NodeDb
+#[derive(Debug, thiserror::Error)]
+pub enum NodeDbError {
+    /// The underlying RocksDB instance failed.
+    #[error("Database error: {0}")]
+    Rocks(#[from] rocksdb::Error),
+    /// A table is missing from the database.
+    #[error("Table {0} is missing from the database")]
+    MissingTable(Table),
+    /// A key of a table is not a big endian block number.
+    #[error("Invalid key of {len} bytes in table {table}")]
+    InvalidKey {
+        /// The table holding the key.
+        table: Table,
+        /// The length of the key.
+        len: usize,
+    },
+    /// A record could not be encoded or decoded.
+    #[error("Invalid record: {0}")]
+    Codec(#[from] serde_json::Error),
+}
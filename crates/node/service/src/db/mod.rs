@@ -0,0 +1,29 @@
+//! The embedded database of the node.
+//!
+//! The [`NodeDb`] keeps records that are expensive or impossible to rebuild from the L1 and L2
+//! chains alone: the safe head at each L1 block, the anchors the derivation pipeline was reset
+//! to, a cache of the unsafe payloads received from the network, the L1 batcher transactions
+//! each derived L2 block came from, and the derived attributes that failed to consolidate against
+//! the unsafe chain. Each table is pruned according to the [`PruningConfig`].
+//!
+//! The database also persists the cumulative [`NodeCounter`]s of the node across restarts.
+
+mod error;
+pub use error::NodeDbError;
+
+mod store;
+pub use store::{MemoryNodeStore, NodeStore, Table};
+
+mod rocks;
+pub use rocks::RocksNodeStore;
+
+mod records;
+pub use records::{
+    BatchSourceRecord, DerivationCheckpoint, L1ProvenanceRecord, NodeCounter, SafeHeadRecord,
+};
+
+mod node_db;
+pub use node_db::{DbIssue, NodeDb, PruningConfig, TableStats};
+
+mod provenance;
+pub use provenance::L1ProvenanceRecorder;
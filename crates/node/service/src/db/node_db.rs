@@ -0,0 +1,532 @@
+//! The [`NodeDb`].
+
+use super::{
+    DerivationCheckpoint, L1ProvenanceRecord, MemoryNodeStore, NodeCounter, NodeDbError, NodeStore,
+    RocksNodeStore, SafeHeadRecord, Table,
+};
+use kona_engine::ConsolidationMismatch;
+use kona_rpc::{
+    L1BatchSource, L1ProvenanceDb, L1ProvenanceDbError, L1ProvenanceResponse, NodeCountersDb,
+    NodeCountersDbError, NodeCountersResponse, SafeHeadDb, SafeHeadDbError, SafeHeadResponse,
+};
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{fmt, path::Path, sync::Arc};
+use strum::IntoEnumIterator;
+
+/// The interval, in block numbers, at which tables are pruned as new records are written.
+const PRUNE_INTERVAL: u64 = 64;
+
+/// The number of blocks of history retained in each table of the [`NodeDb`].
+///
+/// Entries more than the horizon below the highest key of their table are pruned. A table
+/// without a horizon is never pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruningConfig {
+    /// The number of L1 blocks of safe head records retained.
+    pub safe_heads: Option<u64>,
+    /// The number of L2 blocks of derivation checkpoints retained.
+    pub derivation_checkpoints: Option<u64>,
+    /// The number of L2 blocks of unsafe payloads retained.
+    pub unsafe_payloads: Option<u64>,
+    /// The number of L2 blocks of L1 provenance records retained.
+    pub l1_provenance: Option<u64>,
+}
+
+impl Default for PruningConfig {
+    fn default() -> Self {
+        Self {
+            safe_heads: None,
+            derivation_checkpoints: None,
+            unsafe_payloads: Some(7_200),
+            l1_provenance: None,
+        }
+    }
+}
+
+impl PruningConfig {
+    /// Returns the horizon of `table`.
+    pub const fn horizon(&self, table: Table) -> Option<u64> {
+        match table {
+            Table::SafeHeads => self.safe_heads,
+            Table::DerivationCheckpoints => self.derivation_checkpoints,
+            Table::UnsafePayloads => self.unsafe_payloads,
+            Table::L1Provenance => self.l1_provenance,
+            Table::ConsolidationMismatches | Table::Counters => None,
+        }
+    }
+}
+
+/// Statistics of a [`Table`] of the [`NodeDb`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableStats {
+    /// The table.
+    pub table: Table,
+    /// The number of entries.
+    pub entries: u64,
+    /// The total size of the values, in bytes.
+    pub size: u64,
+    /// The lowest key.
+    pub first: Option<u64>,
+    /// The highest key.
+    pub last: Option<u64>,
+}
+
+impl fmt::Display for TableStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} entries, {} bytes", self.table, self.entries, self.size)?;
+        if let (Some(first), Some(last)) = (self.first, self.last) {
+            write!(f, ", blocks {first}..={last}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An inconsistent entry found by [`NodeDb::verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbIssue {
+    /// The table holding the entry.
+    pub table: Table,
+    /// The key of the entry.
+    pub key: u64,
+    /// The inconsistency.
+    pub reason: String,
+}
+
+impl fmt::Display for DbIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} #{}: {}", self.table, self.key, self.reason)
+    }
+}
+
+/// The embedded database of the node, storing typed records in a [`NodeStore`].
+#[derive(Debug, Clone)]
+pub struct NodeDb {
+    /// The underlying store.
+    store: Arc<dyn NodeStore>,
+    /// The pruning horizons of the tables.
+    pruning: PruningConfig,
+}
+
+impl NodeDb {
+    /// Creates a new [`NodeDb`] over the given store.
+    pub fn new(store: Arc<dyn NodeStore>, pruning: PruningConfig) -> Self {
+        Self { store, pruning }
+    }
+
+    /// Opens the [`NodeDb`] at `path`, creating it if it does not exist.
+    pub fn open(path: &Path, pruning: PruningConfig) -> Result<Self, NodeDbError> {
+        Ok(Self::new(Arc::new(RocksNodeStore::open(path)?), pruning))
+    }
+
+    /// Creates an empty in-memory [`NodeDb`].
+    pub fn in_memory(pruning: PruningConfig) -> Self {
+        Self::new(Arc::new(MemoryNodeStore::new()), pruning)
+    }
+
+    /// Returns the pruning horizons of the tables.
+    pub const fn pruning(&self) -> &PruningConfig {
+        &self.pruning
+    }
+
+    /// Records the safe head once the derivation pipeline has consumed an L1 block.
+    pub fn record_safe_head(&self, record: SafeHeadRecord) -> Result<(), NodeDbError> {
+        self.put(Table::SafeHeads, record.l1_block.number, &record)
+    }
+
+    /// Returns the safe head recorded at the highest L1 block that is not greater than
+    /// `l1_block`.
+    pub fn safe_head_at_l1_block(
+        &self,
+        l1_block: u64,
+    ) -> Result<Option<SafeHeadRecord>, NodeDbError> {
+        self.store
+            .get_at_or_below(Table::SafeHeads, l1_block)?
+            .map(|(_, value)| Ok(serde_json::from_slice(&value)?))
+            .transpose()
+    }
+
+    /// Records an anchor the derivation pipeline was reset to.
+    pub fn record_checkpoint(&self, checkpoint: DerivationCheckpoint) -> Result<(), NodeDbError> {
+        self.put(
+            Table::DerivationCheckpoints,
+            checkpoint.l2_safe_head.block_info.number,
+            &checkpoint,
+        )
+    }
+
+    /// Returns the most recent anchor the derivation pipeline was reset to.
+    pub fn latest_checkpoint(&self) -> Result<Option<DerivationCheckpoint>, NodeDbError> {
+        self.store
+            .last(Table::DerivationCheckpoints)?
+            .map(|(_, value)| Ok(serde_json::from_slice(&value)?))
+            .transpose()
+    }
+
+    /// Caches an unsafe payload received from the network.
+    pub fn cache_unsafe_payload(
+        &self,
+        envelope: &OpExecutionPayloadEnvelope,
+    ) -> Result<(), NodeDbError> {
+        self.put(Table::UnsafePayloads, envelope.execution_payload.block_number(), envelope)
+    }
+
+    /// Returns the cached unsafe payload of the L2 block `number`.
+    pub fn unsafe_payload(
+        &self,
+        number: u64,
+    ) -> Result<Option<OpExecutionPayloadEnvelope>, NodeDbError> {
+        self.get(Table::UnsafePayloads, number)
+    }
+
+    /// Records the L1 data a derived L2 block came from.
+    pub fn record_l1_provenance(&self, record: &L1ProvenanceRecord) -> Result<(), NodeDbError> {
+        self.put(Table::L1Provenance, record.l2_block, record)
+    }
+
+    /// Returns the L1 data the L2 block `number` was derived from.
+    pub fn l1_provenance(&self, number: u64) -> Result<Option<L1ProvenanceRecord>, NodeDbError> {
+        self.get(Table::L1Provenance, number)
+    }
+
+    /// Records a mismatch between derived attributes and the unsafe block they were consolidated
+    /// against.
+    pub fn record_consolidation_mismatch(
+        &self,
+        mismatch: &ConsolidationMismatch,
+    ) -> Result<(), NodeDbError> {
+        self.put(Table::ConsolidationMismatches, mismatch.block_number, mismatch)
+    }
+
+    /// Returns the recorded consolidation mismatch at the highest L2 block.
+    pub fn last_consolidation_mismatch(
+        &self,
+    ) -> Result<Option<ConsolidationMismatch>, NodeDbError> {
+        self.store
+            .last(Table::ConsolidationMismatches)?
+            .map(|(_, value)| Ok(serde_json::from_slice(&value)?))
+            .transpose()
+    }
+
+    /// Adds `by` to a counter. Returns the new value of the counter.
+    pub fn increment_counter(&self, counter: NodeCounter, by: u64) -> Result<u64, NodeDbError> {
+        let value = self.counter(counter)?.saturating_add(by);
+        self.put(Table::Counters, counter.key(), &value)?;
+        Ok(value)
+    }
+
+    /// Returns the value of a counter, which is zero if it was never incremented.
+    pub fn counter(&self, counter: NodeCounter) -> Result<u64, NodeDbError> {
+        Ok(self.get(Table::Counters, counter.key())?.unwrap_or_default())
+    }
+
+    /// Prunes every table down to its horizon. Returns the number of deleted entries per table.
+    pub fn prune(&self) -> Result<Vec<(Table, usize)>, NodeDbError> {
+        Table::iter().map(|table| Ok((table, self.prune_table(table)?))).collect()
+    }
+
+    /// Returns the statistics of every table.
+    pub fn stats(&self) -> Result<Vec<TableStats>, NodeDbError> {
+        Table::iter()
+            .map(|table| {
+                let mut stats = TableStats { table, entries: 0, size: 0, first: None, last: None };
+                self.store.for_each(table, &mut |key, value| {
+                    stats.entries += 1;
+                    stats.size += value.len() as u64;
+                    stats.first = stats.first.or(Some(key));
+                    stats.last = Some(key);
+                })?;
+                Ok(stats)
+            })
+            .collect()
+    }
+
+    /// Checks that every record decodes, matches its key, and that the safe head never moves
+    /// backwards as the L1 chain advances. Returns the inconsistent entries.
+    pub fn verify(&self) -> Result<Vec<DbIssue>, NodeDbError> {
+        let mut issues = Vec::new();
+
+        let mut previous_safe_head: Option<u64> = None;
+        self.verify_table::<SafeHeadRecord>(&mut issues, Table::SafeHeads, |key, record| {
+            if record.l1_block.number != key {
+                return Err(format!("record of L1 block #{}", record.l1_block.number));
+            }
+            let safe_head = record.safe_head.number;
+            if previous_safe_head.replace(safe_head).is_some_and(|previous| previous > safe_head) {
+                return Err(format!("safe head #{safe_head} is behind the previous L1 block's"));
+            }
+            Ok(())
+        })?;
+
+        self.verify_table::<DerivationCheckpoint>(
+            &mut issues,
+            Table::DerivationCheckpoints,
+            |key, checkpoint| {
+                let number = checkpoint.l2_safe_head.block_info.number;
+                if number != key {
+                    return Err(format!("checkpoint of L2 block #{number}"));
+                }
+                Ok(())
+            },
+        )?;
+
+        self.verify_table::<OpExecutionPayloadEnvelope>(
+            &mut issues,
+            Table::UnsafePayloads,
+            |key, envelope| {
+                let number = envelope.execution_payload.block_number();
+                if number != key {
+                    return Err(format!("payload of L2 block #{number}"));
+                }
+                Ok(())
+            },
+        )?;
+
+        self.verify_table::<L1ProvenanceRecord>(
+            &mut issues,
+            Table::L1Provenance,
+            |key, record| {
+                if record.l2_block != key {
+                    return Err(format!("provenance of L2 block #{}", record.l2_block));
+                }
+                Ok(())
+            },
+        )?;
+
+        self.verify_table::<ConsolidationMismatch>(
+            &mut issues,
+            Table::ConsolidationMismatches,
+            |key, mismatch| {
+                if mismatch.block_number != key {
+                    return Err(format!("mismatch of L2 block #{}", mismatch.block_number));
+                }
+                Ok(())
+            },
+        )?;
+
+        self.verify_table::<u64>(&mut issues, Table::Counters, |key, _| {
+            if NodeCounter::iter().all(|counter| counter.key() != key) {
+                return Err("unknown counter".to_string());
+            }
+            Ok(())
+        })?;
+
+        Ok(issues)
+    }
+
+    /// Decodes every record of `table`, and runs `check` on each of them.
+    fn verify_table<T: DeserializeOwned>(
+        &self,
+        issues: &mut Vec<DbIssue>,
+        table: Table,
+        mut check: impl FnMut(u64, T) -> Result<(), String>,
+    ) -> Result<(), NodeDbError> {
+        self.store.for_each(table, &mut |key, value| {
+            let result = serde_json::from_slice(value)
+                .map_err(|e| format!("undecodable record: {e}"))
+                .and_then(|record| check(key, record));
+            if let Err(reason) = result {
+                issues.push(DbIssue { table, key, reason });
+            }
+        })
+    }
+
+    fn get<T: DeserializeOwned>(&self, table: Table, key: u64) -> Result<Option<T>, NodeDbError> {
+        self.store.get(table, key)?.map(|value| Ok(serde_json::from_slice(&value)?)).transpose()
+    }
+
+    /// Writes a record, pruning the table every [`PRUNE_INTERVAL`] blocks.
+    fn put<T: Serialize>(&self, table: Table, key: u64, record: &T) -> Result<(), NodeDbError> {
+        self.store.put(table, key, &serde_json::to_vec(record)?)?;
+        if key % PRUNE_INTERVAL == 0 || table == Table::DerivationCheckpoints {
+            self.prune_table(table)?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the entries of `table` more than its horizon below its highest key.
+    fn prune_table(&self, table: Table) -> Result<usize, NodeDbError> {
+        let Some(horizon) = self.pruning.horizon(table) else {
+            return Ok(0);
+        };
+        let Some((last, _)) = self.store.last(table)? else {
+            return Ok(0);
+        };
+        let deleted = self.store.delete_below(table, last.saturating_sub(horizon))?;
+        if deleted > 0 {
+            debug!(target: "db", %table, deleted, "Pruned table");
+        }
+        Ok(deleted)
+    }
+}
+
+impl SafeHeadDb for NodeDb {
+    fn safe_head_at_l1_block(
+        &self,
+        l1_block: u64,
+    ) -> Result<Option<SafeHeadResponse>, SafeHeadDbError> {
+        let record = Self::safe_head_at_l1_block(self, l1_block)
+            .map_err(|e| SafeHeadDbError(e.to_string()))?;
+        Ok(record.map(|record| SafeHeadResponse {
+            l1_block: record.l1_block,
+            safe_head: record.safe_head,
+        }))
+    }
+}
+
+impl L1ProvenanceDb for NodeDb {
+    fn l1_provenance(
+        &self,
+        l2_block: u64,
+    ) -> Result<Option<L1ProvenanceResponse>, L1ProvenanceDbError> {
+        let record =
+            Self::l1_provenance(self, l2_block).map_err(|e| L1ProvenanceDbError(e.to_string()))?;
+        Ok(record.map(|record| L1ProvenanceResponse {
+            l2_block: record.l2_block,
+            channel_id: record.channel_id,
+            l1_origin: record.l1_origin,
+            sources: record
+                .sources
+                .into_iter()
+                .map(|source| L1BatchSource {
+                    l1_block: source.l1_block,
+                    tx_hash: source.tx_hash,
+                    blob_hash: source.blob_hash,
+                })
+                .collect(),
+        }))
+    }
+}
+
+impl NodeCountersDb for NodeDb {
+    fn node_counters(&self) -> Result<NodeCountersResponse, NodeCountersDbError> {
+        let counter =
+            |counter| self.counter(counter).map_err(|e| NodeCountersDbError(e.to_string()));
+        Ok(NodeCountersResponse {
+            derived_blocks: counter(NodeCounter::DerivedBlocks)?,
+            l2_reorgs: counter(NodeCounter::L2Reorgs)?,
+            deposit_only_blocks: counter(NodeCounter::DepositOnlyBlocks)?,
+            pipeline_resets: counter(NodeCounter::PipelineResets)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
+    use kona_protocol::{BlockInfo, L2BlockInfo};
+
+    fn safe_head(l1: u64, l2: u64) -> SafeHeadRecord {
+        SafeHeadRecord {
+            l1_block: BlockNumHash::new(l1, B256::with_last_byte(l1 as u8)),
+            safe_head: BlockNumHash::new(l2, B256::with_last_byte(l2 as u8)),
+        }
+    }
+
+    #[test]
+    fn test_safe_head_at_l1_block() {
+        let db = NodeDb::in_memory(PruningConfig::default());
+        db.record_safe_head(safe_head(10, 100)).unwrap();
+        db.record_safe_head(safe_head(12, 110)).unwrap();
+
+        assert_eq!(db.safe_head_at_l1_block(9).unwrap(), None);
+        assert_eq!(db.safe_head_at_l1_block(10).unwrap(), Some(safe_head(10, 100)));
+        assert_eq!(db.safe_head_at_l1_block(11).unwrap(), Some(safe_head(10, 100)));
+        assert_eq!(db.safe_head_at_l1_block(20).unwrap(), Some(safe_head(12, 110)));
+    }
+
+    #[test]
+    fn test_latest_checkpoint() {
+        let db = NodeDb::in_memory(PruningConfig::default());
+        assert_eq!(db.latest_checkpoint().unwrap(), None);
+
+        let checkpoint = |number| DerivationCheckpoint {
+            l2_safe_head: L2BlockInfo {
+                block_info: BlockInfo { number, ..Default::default() },
+                ..Default::default()
+            },
+            l1_origin: BlockInfo::default(),
+            system_config: Default::default(),
+        };
+        db.record_checkpoint(checkpoint(5)).unwrap();
+        db.record_checkpoint(checkpoint(3)).unwrap();
+        assert_eq!(db.latest_checkpoint().unwrap(), Some(checkpoint(5)));
+    }
+
+    #[test]
+    fn test_last_consolidation_mismatch() {
+        let db = NodeDb::in_memory(PruningConfig::default());
+        assert_eq!(db.last_consolidation_mismatch().unwrap(), None);
+
+        let mismatch = |block_number| ConsolidationMismatch {
+            block_number,
+            block_hash: B256::repeat_byte(block_number as u8),
+            reason: "GasLimit(1, 2)".to_string(),
+            fields: Vec::new(),
+        };
+        db.record_consolidation_mismatch(&mismatch(8)).unwrap();
+        db.record_consolidation_mismatch(&mismatch(4)).unwrap();
+        assert_eq!(db.last_consolidation_mismatch().unwrap(), Some(mismatch(8)));
+        assert!(db.verify().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prune_to_horizon() {
+        let pruning = PruningConfig { safe_heads: Some(2), ..Default::default() };
+        let db = NodeDb::in_memory(pruning);
+        for l1 in 1..=5 {
+            db.record_safe_head(safe_head(l1, l1 * 10)).unwrap();
+        }
+
+        assert_eq!(
+            db.prune().unwrap(),
+            [
+                (Table::SafeHeads, 2),
+                (Table::DerivationCheckpoints, 0),
+                (Table::UnsafePayloads, 0),
+                (Table::L1Provenance, 0),
+                (Table::ConsolidationMismatches, 0),
+                (Table::Counters, 0)
+            ]
+        );
+        let stats = db.stats().unwrap();
+        assert_eq!(stats[0].entries, 3);
+        assert_eq!(stats[0].first, Some(3));
+        assert_eq!(stats[0].last, Some(5));
+    }
+
+    #[test]
+    fn test_counters_persist_across_restarts() {
+        let store = Arc::new(MemoryNodeStore::new());
+        let db = NodeDb::new(store.clone(), PruningConfig::default());
+        assert_eq!(db.counter(NodeCounter::PipelineResets).unwrap(), 0);
+        assert_eq!(db.increment_counter(NodeCounter::PipelineResets, 1).unwrap(), 1);
+        assert_eq!(db.increment_counter(NodeCounter::DerivedBlocks, 12).unwrap(), 12);
+
+        let db = NodeDb::new(store, PruningConfig::default());
+        assert_eq!(db.increment_counter(NodeCounter::DerivedBlocks, 3).unwrap(), 15);
+        assert_eq!(
+            db.node_counters().unwrap(),
+            NodeCountersResponse { derived_blocks: 15, pipeline_resets: 1, ..Default::default() }
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let store = Arc::new(MemoryNodeStore::new());
+        let db = NodeDb::new(store.clone(), PruningConfig::default());
+        db.record_safe_head(safe_head(1, 10)).unwrap();
+        db.record_safe_head(safe_head(2, 8)).unwrap();
+        store.put(Table::SafeHeads, 3, &serde_json::to_vec(&safe_head(4, 20)).unwrap()).unwrap();
+        store.put(Table::UnsafePayloads, 1, b"garbage").unwrap();
+
+        let issues = db.verify().unwrap();
+        let keys: Vec<_> = issues.iter().map(|issue| (issue.table, issue.key)).collect();
+        assert_eq!(
+            keys,
+            [(Table::SafeHeads, 2), (Table::SafeHeads, 3), (Table::UnsafePayloads, 1)]
+        );
+    }
+}
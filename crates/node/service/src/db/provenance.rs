@@ -0,0 +1,238 @@
+//! The [`L1ProvenanceRecorder`].
+
+use super::{BatchSourceRecord, L1ProvenanceRecord, NodeDb};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B128;
+use kona_derive::{ChannelCloseReason, PipelineEvent, PipelineEventSink};
+use kona_genesis::RollupConfig;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// The maximum number of decoded batches awaiting acceptance tracked by the
+/// [`L1ProvenanceRecorder`].
+const MAX_PENDING_BATCHES: usize = 1024;
+
+/// A batch decoded from a channel, awaiting acceptance.
+#[derive(Debug)]
+struct PendingBatch {
+    /// The timestamp of the first L2 block of the batch.
+    first_timestamp: u64,
+    /// The timestamp of the last L2 block of the batch.
+    last_timestamp: u64,
+    /// The ID of the channel the batch was decoded from, if known.
+    channel_id: Option<B128>,
+    /// The L1 block at which the batch was decoded.
+    l1_origin: BlockNumHash,
+    /// The batcher transactions carrying the frames of the channel.
+    sources: Vec<BatchSourceRecord>,
+}
+
+/// The state of the [`L1ProvenanceRecorder`].
+#[derive(Debug, Default)]
+struct RecorderState {
+    /// The batcher transactions carrying the frames of the channels being assembled or read.
+    channels: HashMap<B128, Vec<BatchSourceRecord>>,
+    /// The channel most recently forwarded to the channel reader.
+    last_ready: Option<B128>,
+    /// The batches decoded but not yet accepted, oldest first.
+    pending: VecDeque<PendingBatch>,
+}
+
+/// A [`PipelineEventSink`] recording the L1 batcher transactions each derived L2 block came from
+/// in the [`NodeDb`].
+///
+/// The recorder follows the frames of each channel back to the batcher transactions that carried
+/// them, the batches decoded from each channel, and finally the batches accepted by the pipeline.
+/// Once a batch is accepted, the L2 blocks it derives are recorded against the batcher
+/// transactions of its channel.
+#[derive(Debug)]
+pub struct L1ProvenanceRecorder {
+    /// The node database.
+    db: NodeDb,
+    /// The rollup config.
+    rollup_config: Arc<RollupConfig>,
+    /// The state of the recorder.
+    state: Mutex<RecorderState>,
+}
+
+impl L1ProvenanceRecorder {
+    /// Creates a new [`L1ProvenanceRecorder`] writing to the given [`NodeDb`].
+    pub fn new(db: NodeDb, rollup_config: Arc<RollupConfig>) -> Self {
+        Self { db, rollup_config, state: Mutex::default() }
+    }
+
+    /// Returns the number of the L2 block at `timestamp`.
+    fn block_number(&self, timestamp: u64) -> u64 {
+        self.rollup_config.genesis.l2.number +
+            self.rollup_config.block_number_from_timestamp(timestamp)
+    }
+
+    /// Records the L2 blocks from `first_timestamp` to `last_timestamp` against `batch`.
+    fn record(&self, batch: &PendingBatch, first_timestamp: u64, last_timestamp: u64) {
+        let block_time = self.rollup_config.block_time.max(1);
+        for timestamp in (first_timestamp..=last_timestamp).step_by(block_time as usize) {
+            let record = L1ProvenanceRecord {
+                l2_block: self.block_number(timestamp),
+                channel_id: batch.channel_id,
+                l1_origin: batch.l1_origin,
+                sources: batch.sources.clone(),
+            };
+            if let Err(err) = self.db.record_l1_provenance(&record) {
+                warn!(target: "db", ?err, "Failed to record the L1 provenance");
+                return;
+            }
+        }
+    }
+}
+
+impl PipelineEventSink for L1ProvenanceRecorder {
+    fn emit(&self, event: PipelineEvent) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match event {
+            PipelineEvent::FrameRetrieved { channel_id, tx: Some(tx), origin, .. } => {
+                let source = BatchSourceRecord {
+                    l1_block: origin.id(),
+                    tx_hash: tx.tx_hash,
+                    blob_hash: tx.blob_hash,
+                };
+                let sources = state.channels.entry(channel_id).or_default();
+                if !sources.contains(&source) {
+                    sources.push(source);
+                }
+            }
+            PipelineEvent::ChannelClosed { id, reason: ChannelCloseReason::Ready, .. } => {
+                // The channel reader reads a channel to completion before pulling the next one.
+                if let Some(previous) = state.last_ready.replace(id) &&
+                    previous != id
+                {
+                    state.channels.remove(&previous);
+                }
+            }
+            PipelineEvent::ChannelClosed { id, .. } => {
+                state.channels.remove(&id);
+            }
+            PipelineEvent::BatchDecoded { channel_id, first_timestamp, last_timestamp, origin } => {
+                let sources =
+                    channel_id.and_then(|id| state.channels.get(&id).cloned()).unwrap_or_default();
+                if state.pending.len() == MAX_PENDING_BATCHES {
+                    state.pending.pop_front();
+                }
+                state.pending.push_back(PendingBatch {
+                    first_timestamp,
+                    last_timestamp,
+                    channel_id,
+                    l1_origin: origin.id(),
+                    sources,
+                });
+            }
+            PipelineEvent::BatchAccepted { timestamp, origin } => {
+                while state.pending.front().is_some_and(|batch| batch.last_timestamp < timestamp) {
+                    state.pending.pop_front();
+                }
+                let Some(batch) = state.pending.iter().find(|batch| {
+                    (batch.first_timestamp..=batch.last_timestamp).contains(&timestamp)
+                }) else {
+                    return;
+                };
+                // Before Holocene, span batches are accepted as a whole. From Holocene onwards,
+                // each of their blocks is accepted on its own.
+                let last_timestamp = if self.rollup_config.is_holocene_active(origin.timestamp) {
+                    timestamp
+                } else {
+                    batch.last_timestamp
+                };
+                self.record(batch, timestamp, last_timestamp);
+            }
+            PipelineEvent::Reset { .. } => *state = RecorderState::default(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PruningConfig;
+    use alloy_primitives::B256;
+    use kona_derive::BatcherTxRef;
+    use kona_protocol::BlockInfo;
+
+    fn origin(number: u64) -> BlockInfo {
+        BlockInfo { number, hash: B256::with_last_byte(number as u8), ..Default::default() }
+    }
+
+    fn tx(byte: u8) -> BatcherTxRef {
+        BatcherTxRef { tx_hash: B256::with_last_byte(byte), blob_hash: None }
+    }
+
+    fn source(l1_block: u64, byte: u8) -> BatchSourceRecord {
+        BatchSourceRecord {
+            l1_block: origin(l1_block).id(),
+            tx_hash: B256::with_last_byte(byte),
+            blob_hash: None,
+        }
+    }
+
+    #[test]
+    fn test_records_accepted_span_batch() {
+        let db = NodeDb::in_memory(PruningConfig::default());
+        let rollup_config = RollupConfig { block_time: 2, ..Default::default() };
+        let recorder = L1ProvenanceRecorder::new(db.clone(), Arc::new(rollup_config));
+        let channel_id = B128::with_last_byte(1);
+
+        for (frame_number, (l1_block, byte)) in [(1, 1), (1, 1), (2, 2)].into_iter().enumerate() {
+            recorder.emit(PipelineEvent::FrameRetrieved {
+                channel_id,
+                frame_number: frame_number as u16,
+                tx: Some(tx(byte)),
+                origin: origin(l1_block),
+            });
+        }
+        recorder.emit(PipelineEvent::ChannelClosed {
+            id: channel_id,
+            origin: origin(2),
+            reason: ChannelCloseReason::Ready,
+        });
+        recorder.emit(PipelineEvent::BatchDecoded {
+            channel_id: Some(channel_id),
+            first_timestamp: 2,
+            last_timestamp: 6,
+            origin: origin(2),
+        });
+        assert_eq!(db.l1_provenance(1).unwrap(), None);
+
+        recorder.emit(PipelineEvent::BatchAccepted { timestamp: 2, origin: origin(2) });
+        for l2_block in 1..=3 {
+            let record = db.l1_provenance(l2_block).unwrap().unwrap();
+            assert_eq!(record.channel_id, Some(channel_id));
+            assert_eq!(record.l1_origin, origin(2).id());
+            assert_eq!(record.sources, [source(1, 1), source(2, 2)]);
+        }
+        assert_eq!(db.l1_provenance(4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_ignores_dropped_channels() {
+        let db = NodeDb::in_memory(PruningConfig::default());
+        let recorder = L1ProvenanceRecorder::new(db.clone(), Arc::new(RollupConfig::default()));
+        let channel_id = B128::with_last_byte(1);
+
+        recorder.emit(PipelineEvent::FrameRetrieved {
+            channel_id,
+            frame_number: 0,
+            tx: Some(tx(1)),
+            origin: origin(1),
+        });
+        recorder.emit(PipelineEvent::ChannelClosed {
+            id: channel_id,
+            origin: origin(2),
+            reason: ChannelCloseReason::TimedOut,
+        });
+        recorder.emit(PipelineEvent::BatchAccepted { timestamp: 0, origin: origin(2) });
+
+        assert!(recorder.state.lock().unwrap().channels.is_empty());
+        assert_eq!(db.l1_provenance(0).unwrap(), None);
+    }
+}
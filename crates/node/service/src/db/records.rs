@@ -0,0 +1,83 @@
+//! Records stored in the [`NodeDb`].
+//!
+//! [`NodeDb`]: super::NodeDb
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{B128, B256};
+use kona_genesis::SystemConfig;
+use kona_protocol::{BlockInfo, L2BlockInfo};
+
+/// A cumulative counter of the node, persisted in the [`NodeDb`] across restarts.
+///
+/// [`NodeDb`]: super::NodeDb
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::AsRefStr, strum::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum NodeCounter {
+    /// The number of L2 blocks the safe head advanced by.
+    DerivedBlocks,
+    /// The number of reorgs of the unsafe L2 chain handled by the engine.
+    L2Reorgs,
+    /// The number of invalid payloads replaced by deposits-only blocks.
+    DepositOnlyBlocks,
+    /// The number of derivation pipeline resets.
+    PipelineResets,
+}
+
+impl NodeCounter {
+    /// Returns the key of the counter in the [`Table::Counters`] table.
+    ///
+    /// [`Table::Counters`]: super::Table::Counters
+    pub const fn key(self) -> u64 {
+        self as u64
+    }
+}
+
+/// The safe head of the node once the derivation pipeline has consumed an L1 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeHeadRecord {
+    /// The L1 block.
+    pub l1_block: BlockNumHash,
+    /// The highest safe L2 block derived from L1 data up to and including the L1 block.
+    pub safe_head: BlockNumHash,
+}
+
+/// An anchor the derivation pipeline was reset to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationCheckpoint {
+    /// The L2 safe head the pipeline was reset to.
+    pub l2_safe_head: L2BlockInfo,
+    /// The L1 block the pipeline resumed deriving from.
+    pub l1_origin: BlockInfo,
+    /// The system config at the L2 safe head.
+    pub system_config: SystemConfig,
+}
+
+/// The L1 data an L2 block was derived from.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1ProvenanceRecord {
+    /// The number of the L2 block.
+    pub l2_block: u64,
+    /// The ID of the channel carrying the batch of the L2 block, if known.
+    pub channel_id: Option<B128>,
+    /// The L1 block at which the batch was decoded.
+    pub l1_origin: BlockNumHash,
+    /// The batcher transactions carrying the frames of the channel, in the order they were read.
+    pub sources: Vec<BatchSourceRecord>,
+}
+
+/// A batcher transaction carrying some of the frames of a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSourceRecord {
+    /// The L1 block the transaction was included in.
+    pub l1_block: BlockNumHash,
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The versioned hash of the blob carrying the frames, if they were posted as a blob.
+    pub blob_hash: Option<B256>,
+}
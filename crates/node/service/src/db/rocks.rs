@@ -0,0 +1,83 @@
+//! A [`NodeStore`] backed by [rocksdb].
+
+use super::{NodeDbError, NodeStore, Table};
+use rocksdb::{ColumnFamily, DB, Direction, IteratorMode, Options};
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+/// A [`NodeStore`] persisting its tables on disk with [rocksdb]. Each [`Table`] is stored in its
+/// own column family, keyed by big endian block number.
+#[derive(Debug)]
+pub struct RocksNodeStore {
+    db: DB,
+}
+
+impl RocksNodeStore {
+    /// Opens the database at `path`, creating it if it does not exist.
+    pub fn open(path: &Path) -> Result<Self, NodeDbError> {
+        let mut options = Options::default();
+        options.set_compression_type(rocksdb::DBCompressionType::Snappy);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db =
+            DB::open_cf(&options, path, Table::iter().map(|table| table.as_ref().to_string()))?;
+        Ok(Self { db })
+    }
+
+    fn cf(&self, table: Table) -> Result<&ColumnFamily, NodeDbError> {
+        self.db.cf_handle(table.as_ref()).ok_or(NodeDbError::MissingTable(table))
+    }
+
+    fn decode_key(table: Table, key: &[u8]) -> Result<u64, NodeDbError> {
+        let key: [u8; 8] =
+            key.try_into().map_err(|_| NodeDbError::InvalidKey { table, len: key.len() })?;
+        Ok(u64::from_be_bytes(key))
+    }
+}
+
+impl NodeStore for RocksNodeStore {
+    fn get(&self, table: Table, key: u64) -> Result<Option<Vec<u8>>, NodeDbError> {
+        Ok(self.db.get_cf(self.cf(table)?, key.to_be_bytes())?)
+    }
+
+    fn get_at_or_below(
+        &self,
+        table: Table,
+        key: u64,
+    ) -> Result<Option<(u64, Vec<u8>)>, NodeDbError> {
+        let key = key.to_be_bytes();
+        let mut iter =
+            self.db.iterator_cf(self.cf(table)?, IteratorMode::From(&key, Direction::Reverse));
+        iter.next()
+            .transpose()?
+            .map(|(key, value)| Ok((Self::decode_key(table, &key)?, value.into_vec())))
+            .transpose()
+    }
+
+    fn put(&self, table: Table, key: u64, value: &[u8]) -> Result<(), NodeDbError> {
+        Ok(self.db.put_cf(self.cf(table)?, key.to_be_bytes(), value)?)
+    }
+
+    fn delete_below(&self, table: Table, key: u64) -> Result<usize, NodeDbError> {
+        let cf = self.cf(table)?;
+        let mut deleted = 0;
+        for entry in self.db.iterator_cf(cf, IteratorMode::Start) {
+            let (entry_key, _) = entry?;
+            if Self::decode_key(table, &entry_key)? >= key {
+                break;
+            }
+            deleted += 1;
+        }
+        self.db.delete_range_cf(cf, 0u64.to_be_bytes(), key.to_be_bytes())?;
+        Ok(deleted)
+    }
+
+    fn for_each(&self, table: Table, visit: &mut dyn FnMut(u64, &[u8])) -> Result<(), NodeDbError> {
+        for entry in self.db.iterator_cf(self.cf(table)?, IteratorMode::Start) {
+            let (key, value) = entry?;
+            visit(Self::decode_key(table, &key)?, &value);
+        }
+        Ok(())
+    }
+}
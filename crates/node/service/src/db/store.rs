@@ -0,0 +1,129 @@
+//! The [`NodeStore`] trait, and an in-memory implementation.
+
+use super::NodeDbError;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    sync::Mutex,
+};
+
+/// A table of the [`NodeDb`]. Every table but [`Table::Counters`] is keyed by block number.
+///
+/// [`NodeDb`]: super::NodeDb
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    strum::Display,
+    strum::AsRefStr,
+    strum::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum Table {
+    /// The safe head at each L1 block, keyed by L1 block number.
+    SafeHeads,
+    /// The anchors the derivation pipeline was reset to, keyed by L2 block number.
+    DerivationCheckpoints,
+    /// The unsafe payloads received from the network, keyed by L2 block number.
+    UnsafePayloads,
+    /// The L1 data each derived L2 block came from, keyed by L2 block number.
+    L1Provenance,
+    /// The derived attributes that did not match the unsafe block they were consolidated
+    /// against, keyed by L2 block number.
+    ConsolidationMismatches,
+    /// The cumulative counters of the node, keyed by [`NodeCounter::key`].
+    ///
+    /// [`NodeCounter::key`]: super::NodeCounter::key
+    Counters,
+}
+
+/// An ordered key-value store backing the [`NodeDb`].
+///
+/// Entries are ordered by key within each [`Table`]. Values are opaque to the store.
+///
+/// [`NodeDb`]: super::NodeDb
+pub trait NodeStore: Debug + Send + Sync {
+    /// Returns the value at `key`.
+    fn get(&self, table: Table, key: u64) -> Result<Option<Vec<u8>>, NodeDbError>;
+
+    /// Returns the entry with the highest key that is not greater than `key`.
+    fn get_at_or_below(
+        &self,
+        table: Table,
+        key: u64,
+    ) -> Result<Option<(u64, Vec<u8>)>, NodeDbError>;
+
+    /// Returns the entry with the highest key.
+    fn last(&self, table: Table) -> Result<Option<(u64, Vec<u8>)>, NodeDbError> {
+        self.get_at_or_below(table, u64::MAX)
+    }
+
+    /// Sets the value at `key`, overwriting any existing value.
+    fn put(&self, table: Table, key: u64, value: &[u8]) -> Result<(), NodeDbError>;
+
+    /// Deletes all entries with a key lower than `key`. Returns the number of deleted entries.
+    fn delete_below(&self, table: Table, key: u64) -> Result<usize, NodeDbError>;
+
+    /// Visits every entry of the table in key order.
+    fn for_each(&self, table: Table, visit: &mut dyn FnMut(u64, &[u8])) -> Result<(), NodeDbError>;
+}
+
+/// A [`NodeStore`] holding its tables in memory.
+#[derive(Debug, Default)]
+pub struct MemoryNodeStore {
+    tables: Mutex<HashMap<Table, BTreeMap<u64, Vec<u8>>>>,
+}
+
+impl MemoryNodeStore {
+    /// Creates an empty [`MemoryNodeStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_table<T>(&self, table: Table, f: impl FnOnce(&mut BTreeMap<u64, Vec<u8>>) -> T) -> T {
+        let mut tables = self.tables.lock().unwrap_or_else(|e| e.into_inner());
+        f(tables.entry(table).or_default())
+    }
+}
+
+impl NodeStore for MemoryNodeStore {
+    fn get(&self, table: Table, key: u64) -> Result<Option<Vec<u8>>, NodeDbError> {
+        Ok(self.with_table(table, |entries| entries.get(&key).cloned()))
+    }
+
+    fn get_at_or_below(
+        &self,
+        table: Table,
+        key: u64,
+    ) -> Result<Option<(u64, Vec<u8>)>, NodeDbError> {
+        Ok(self.with_table(table, |entries| {
+            entries.range(..=key).next_back().map(|(key, value)| (*key, value.clone()))
+        }))
+    }
+
+    fn put(&self, table: Table, key: u64, value: &[u8]) -> Result<(), NodeDbError> {
+        self.with_table(table, |entries| entries.insert(key, value.to_vec()));
+        Ok(())
+    }
+
+    fn delete_below(&self, table: Table, key: u64) -> Result<usize, NodeDbError> {
+        Ok(self.with_table(table, |entries| {
+            let retained = entries.split_off(&key);
+            let deleted = entries.len();
+            *entries = retained;
+            deleted
+        }))
+    }
+
+    fn for_each(&self, table: Table, visit: &mut dyn FnMut(u64, &[u8])) -> Result<(), NodeDbError> {
+        self.with_table(table, |entries| {
+            entries.iter().for_each(|(key, value)| visit(*key, value));
+        });
+        Ok(())
+    }
+}
@@ -11,28 +11,53 @@ extern crate tracing;
 
 mod service;
 pub use service::{
-    InteropMode, L1Config, L1ConfigBuilder, NodeMode, RollupNode, RollupNodeBuilder,
+    InteropMode, L1Config, L1ConfigBuilder, NodeMode, NodeSignal, NodeSignals, RollupNode,
+    RollupNodeBuilder, RollupNodeHandle,
 };
 
 mod actors;
 pub use actors::{
-    BlockBuildingClient, BlockEngineError, BlockEngineResult, BlockStream, BuildRequest,
-    CancellableContext, Conductor, ConductorClient, ConductorError,
+    AltDaProviders, ArchiveActor, ArchiveActorError, ArchiveAttributesSource, ArchiveConfig,
+    ArchiveContext, ArchiveTarget, ArchivedAttributes, AutoSyncConfig, BatcherActor,
+    BatcherActorError, BatcherConfig, BlockBuildingClient, BlockEngineError, BlockEngineResult,
+    BlockStream, BuildRequest, CancellableContext, ChannelBuilder, ChannelData, CheckpointBlock,
+    CheckpointConfig, CheckpointError, Conductor, ConductorClient, ConductorError, DaLimits,
+    DaLimitsClient, DaThrottle, DaThrottleConfig, DaThrottlePolicy, DataAvailabilityType,
     DelayedL1OriginSelectorProvider, DerivationActor, DerivationBuilder, DerivationContext,
-    DerivationError, DerivationInboundChannels, DerivationState, EngineActor, EngineConfig,
-    EngineContext, EngineError, EngineInboundData, InboundDerivationMessage, L1OriginSelector,
+    DerivationError, DerivationHaltConfig, DerivationInboundChannels, DerivationLatencyTracker,
+    DerivationOriginTracker, DerivationState, DerivationTimings, DerivedAttributes, EngineActor,
+    EngineActorClient, EngineConfig, EngineContext, EngineError, EngineInboundData,
+    ExtensionContext, InboundDerivationMessage, L1BlockSource, L1OriginSelector,
     L1OriginSelectorError, L1OriginSelectorProvider, L1WatcherActor, L1WatcherActorError,
-    L2Finalizer, NetworkActor, NetworkActorError, NetworkBuilder, NetworkBuilderError,
-    NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError, NetworkHandler,
-    NetworkInboundData, NodeActor, OriginSelector, PipelineBuilder, QueuedBlockBuildingClient,
+    L2Finalizer, LinearDaThrottle, NetworkActor, NetworkActorError, NetworkBuilder,
+    NetworkBuilderError, NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError,
+    NetworkHandler, NetworkInboundData, NodeActor, NodeEvent, NodeExtension, NodeSnapshot,
+    OriginSelector, PayloadBuildConfig, PipelineBuilder, ProposalTarget, ProposerActor,
+    ProposerActorError, ProposerConfig, PruningHint, PruningHintActor, PruningHintActorError,
+    PruningHintConfig, PruningHintContext, QueuedBlockBuildingClient,
     QueuedSequencerAdminAPIClient, QueuedUnsafePayloadGossipClient, ResetRequest, RpcActor,
-    RpcActorError, RpcContext, SealRequest, SequencerActor, SequencerActorError,
-    SequencerAdminQuery, SequencerConfig, UnsafePayloadGossipClient,
-    UnsafePayloadGossipClientError,
+    RpcActorError, RpcContext, RpcTlsError, S3Target, SealRequest, SequencerActor,
+    SequencerActorError, SequencerAdminQuery, SequencerConfig, SharedL1Source, SharedL1Watcher,
+    SnapshotActor, SnapshotActorError, SnapshotConfig, SnapshotContext, SnapshotTarget,
+    UnsafePayloadGossipClient, UnsafePayloadGossipClientError, WsL1BlockSource,
+};
+
+mod db;
+pub use db::{
+    BatchSourceRecord, DbIssue, DerivationCheckpoint, L1ProvenanceRecord, L1ProvenanceRecorder,
+    MemoryNodeStore, NodeCounter, NodeDb, NodeDbError, NodeStore, PruningConfig, RocksNodeStore,
+    SafeHeadRecord, Table, TableStats,
 };
 
 mod metrics;
-pub use metrics::Metrics;
+pub use metrics::{
+    ChannelAlarms, MeteredReceiver, MeteredSender, MeteredUnboundedReceiver,
+    MeteredUnboundedSender, Metrics, QueueMonitor, Watermarks, metered_channel,
+    metered_unbounded_channel, watermark_channel,
+};
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 #[cfg(test)]
 pub use actors::{
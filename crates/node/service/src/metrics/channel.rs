@@ -0,0 +1,428 @@
+//! Metered channels between the node actors.
+//!
+//! Queue growth between actors is otherwise silent, so each metered channel reports its depth and
+//! the age of its oldest message, and logs a warning once either crosses the configured
+//! [`ChannelAlarms`] thresholds.
+//!
+//! Channels created with [`watermark_channel`] additionally signal backpressure to their senders
+//! once their depth reaches the high [`Watermarks`], until it drains back to the low watermark.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{
+    mpsc::{
+        self,
+        error::{SendError, TrySendError},
+    },
+    watch,
+};
+
+/// Thresholds past which a metered channel logs a warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelAlarms {
+    /// The number of queued messages past which the channel is considered backed up.
+    pub max_depth: usize,
+    /// The age of the oldest queued message past which the channel is considered stalled.
+    pub max_age: Duration,
+}
+
+impl Default for ChannelAlarms {
+    fn default() -> Self {
+        Self { max_depth: 256, max_age: Duration::from_secs(12) }
+    }
+}
+
+/// The depths between which a [`watermark_channel`] signals backpressure to its senders.
+///
+/// Backpressure is applied once the depth of the channel reaches the high watermark, and released
+/// once it drains back to the low watermark, so that senders resume in batches rather than one
+/// message at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watermarks {
+    /// The depth at which backpressure is applied. This is also the capacity of the channel.
+    pub high: usize,
+    /// The depth at which backpressure is released. Must be lower than the high watermark.
+    pub low: usize,
+}
+
+impl Default for Watermarks {
+    fn default() -> Self {
+        Self { high: 64, low: 16 }
+    }
+}
+
+/// Tracks the depth of a named queue and raises alarms when it crosses the [`ChannelAlarms`].
+///
+/// The monitor is cheap to clone, and all clones share the same depth counter.
+#[derive(Debug, Clone)]
+pub struct QueueMonitor {
+    /// The name of the queue, used as the `channel` metric label.
+    name: &'static str,
+    /// The alarm thresholds.
+    alarms: ChannelAlarms,
+    /// The shared state of the queue.
+    state: Arc<QueueState>,
+}
+
+/// The shared state of a [`QueueMonitor`].
+#[derive(Debug)]
+struct QueueState {
+    /// The number of queued messages.
+    depth: AtomicUsize,
+    /// Whether the depth alarm is currently raised.
+    depth_alarm: AtomicBool,
+    /// Whether the age alarm is currently raised.
+    age_alarm: AtomicBool,
+    /// The watermarks of the queue, if it signals backpressure.
+    watermarks: Option<Watermarks>,
+    /// Whether backpressure is currently applied.
+    backpressure: watch::Sender<bool>,
+}
+
+impl QueueState {
+    /// Creates a new [`QueueState`], signaling backpressure at the given watermarks.
+    fn new(watermarks: Option<Watermarks>) -> Self {
+        Self {
+            depth: Default::default(),
+            depth_alarm: Default::default(),
+            age_alarm: Default::default(),
+            watermarks,
+            backpressure: watch::channel(false).0,
+        }
+    }
+}
+
+impl QueueMonitor {
+    /// Creates a new [`QueueMonitor`] for the queue with the given name.
+    pub fn new(name: &'static str, alarms: ChannelAlarms) -> Self {
+        Self { name, alarms, state: Arc::new(QueueState::new(None)) }
+    }
+
+    /// Creates a new [`QueueMonitor`] for the queue with the given name, signaling backpressure
+    /// at the given [`Watermarks`].
+    fn with_watermarks(name: &'static str, alarms: ChannelAlarms, watermarks: Watermarks) -> Self {
+        Self { name, alarms, state: Arc::new(QueueState::new(Some(watermarks))) }
+    }
+
+    /// Returns the [`ChannelAlarms`] of the monitor.
+    pub const fn alarms(&self) -> ChannelAlarms {
+        self.alarms
+    }
+
+    /// Returns the number of queued messages.
+    pub fn depth(&self) -> usize {
+        self.state.depth.load(Ordering::Relaxed)
+    }
+
+    /// Records the number of queued messages, raising or clearing the depth alarm.
+    pub fn record_depth(&self, depth: usize) {
+        self.state.depth.store(depth, Ordering::Relaxed);
+        kona_macros::set!(gauge, super::Metrics::CHANNEL_DEPTH, "channel", self.name, depth as f64);
+
+        let exceeded = depth > self.alarms.max_depth;
+        if self.state.depth_alarm.swap(exceeded, Ordering::Relaxed) == exceeded {
+            return;
+        }
+        if exceeded {
+            warn!(target: "node::channel", channel = self.name, depth, max_depth = self.alarms.max_depth, "Queue is backing up");
+            kona_macros::inc!(counter, super::Metrics::CHANNEL_ALARMS, "channel" => self.name, "type" => "depth");
+        } else {
+            info!(target: "node::channel", channel = self.name, depth, "Queue depth recovered");
+        }
+    }
+
+    /// Records the age of the oldest queued message, raising or clearing the age alarm.
+    pub fn record_age(&self, age: Duration) {
+        kona_macros::set!(
+            gauge,
+            super::Metrics::CHANNEL_OLDEST_MESSAGE_AGE,
+            "channel",
+            self.name,
+            age.as_secs_f64()
+        );
+
+        let exceeded = age > self.alarms.max_age;
+        if self.state.age_alarm.swap(exceeded, Ordering::Relaxed) == exceeded {
+            return;
+        }
+        if exceeded {
+            warn!(target: "node::channel", channel = self.name, ?age, max_age = ?self.alarms.max_age, "Queue is stalling");
+            kona_macros::inc!(counter, super::Metrics::CHANNEL_ALARMS, "channel" => self.name, "type" => "age");
+        } else {
+            info!(target: "node::channel", channel = self.name, ?age, "Queue latency recovered");
+        }
+    }
+
+    /// Returns `true` if the queue is backpressured, that is its depth reached the high watermark
+    /// and hasn't drained back to the low watermark yet.
+    pub fn is_backpressured(&self) -> bool {
+        *self.state.backpressure.borrow()
+    }
+
+    /// Returns a receiver of the backpressure state of the queue. The state never changes for
+    /// queues without [`Watermarks`].
+    pub fn subscribe_backpressure(&self) -> watch::Receiver<bool> {
+        self.state.backpressure.subscribe()
+    }
+
+    /// Applies or releases backpressure once the depth crosses the watermarks.
+    fn update_backpressure(&self, depth: usize) {
+        let Some(watermarks) = self.state.watermarks else { return };
+        self.state.backpressure.send_if_modified(|backpressured| {
+            let next = if *backpressured { depth > watermarks.low } else { depth >= watermarks.high };
+            if next == *backpressured {
+                return false;
+            }
+            if next {
+                debug!(target: "node::channel", channel = self.name, depth, "Applying backpressure");
+            } else {
+                debug!(target: "node::channel", channel = self.name, depth, "Releasing backpressure");
+            }
+            *backpressured = next;
+            true
+        });
+    }
+
+    /// Records a message that was dropped before it could be queued.
+    pub fn record_dropped(&self, reason: &'static str) {
+        warn!(target: "node::channel", channel = self.name, reason, "Dropped message");
+        kona_macros::inc!(counter, super::Metrics::CHANNEL_DROPPED, "channel" => self.name, "reason" => reason);
+    }
+
+    /// Records a message being pushed onto the queue.
+    fn pushed(&self) {
+        let depth = self.state.depth.fetch_add(1, Ordering::Relaxed) + 1;
+        self.record_depth(depth);
+        self.update_backpressure(depth);
+    }
+
+    /// Records a message being popped from the queue after waiting for the given duration.
+    fn popped(&self, age: Duration) {
+        let depth = self.state.depth.fetch_sub(1, Ordering::Relaxed).saturating_sub(1);
+        self.record_depth(depth);
+        self.update_backpressure(depth);
+        self.record_age(age);
+    }
+}
+
+/// Creates a bounded, metered channel with the given name and capacity.
+pub fn metered_channel<T>(
+    name: &'static str,
+    capacity: usize,
+    alarms: ChannelAlarms,
+) -> (MeteredSender<T>, MeteredReceiver<T>) {
+    let (tx, rx) = mpsc::channel(capacity);
+    let monitor = QueueMonitor::new(name, alarms);
+    (MeteredSender { inner: tx, monitor: monitor.clone() }, MeteredReceiver { inner: rx, monitor })
+}
+
+/// Creates a bounded, metered channel with the given name, signaling backpressure to its senders
+/// at the given [`Watermarks`]. The capacity of the channel is its high watermark.
+pub fn watermark_channel<T>(
+    name: &'static str,
+    watermarks: Watermarks,
+    alarms: ChannelAlarms,
+) -> (MeteredSender<T>, MeteredReceiver<T>) {
+    let (tx, rx) = mpsc::channel(watermarks.high);
+    let monitor = QueueMonitor::with_watermarks(name, alarms, watermarks);
+    (MeteredSender { inner: tx, monitor: monitor.clone() }, MeteredReceiver { inner: rx, monitor })
+}
+
+/// Creates an unbounded, metered channel with the given name.
+pub fn metered_unbounded_channel<T>(
+    name: &'static str,
+    alarms: ChannelAlarms,
+) -> (MeteredUnboundedSender<T>, MeteredUnboundedReceiver<T>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let monitor = QueueMonitor::new(name, alarms);
+    (
+        MeteredUnboundedSender { inner: tx, monitor: monitor.clone() },
+        MeteredUnboundedReceiver { inner: rx, monitor },
+    )
+}
+
+/// The sending half of a [`metered_channel`].
+#[derive(Debug)]
+pub struct MeteredSender<T> {
+    inner: mpsc::Sender<(Instant, T)>,
+    monitor: QueueMonitor,
+}
+
+impl<T> Clone for MeteredSender<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), monitor: self.monitor.clone() }
+    }
+}
+
+impl<T> MeteredSender<T> {
+    /// Returns the [`QueueMonitor`] of the channel.
+    pub const fn monitor(&self) -> &QueueMonitor {
+        &self.monitor
+    }
+
+    /// Sends a value, waiting until there is capacity.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.monitor.pushed();
+        self.inner.send((Instant::now(), value)).await.map_err(|SendError((_, value))| {
+            self.monitor.popped(Duration::ZERO);
+            self.monitor.record_dropped("closed");
+            SendError(value)
+        })
+    }
+
+    /// Attempts to immediately send a value.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.monitor.pushed();
+        self.inner.try_send((Instant::now(), value)).map_err(|e| {
+            self.monitor.popped(Duration::ZERO);
+            match e {
+                TrySendError::Full((_, value)) => {
+                    self.monitor.record_dropped("full");
+                    TrySendError::Full(value)
+                }
+                TrySendError::Closed((_, value)) => {
+                    self.monitor.record_dropped("closed");
+                    TrySendError::Closed(value)
+                }
+            }
+        })
+    }
+}
+
+/// The receiving half of a [`metered_channel`].
+#[derive(Debug)]
+pub struct MeteredReceiver<T> {
+    inner: mpsc::Receiver<(Instant, T)>,
+    monitor: QueueMonitor,
+}
+
+impl<T> MeteredReceiver<T> {
+    /// Receives the next value, recording how long it was queued for.
+    pub async fn recv(&mut self) -> Option<T> {
+        let (sent_at, value) = self.inner.recv().await?;
+        self.monitor.popped(sent_at.elapsed());
+        Some(value)
+    }
+
+    /// Returns `true` if all senders have been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+/// The sending half of a [`metered_unbounded_channel`].
+#[derive(Debug)]
+pub struct MeteredUnboundedSender<T> {
+    inner: mpsc::UnboundedSender<(Instant, T)>,
+    monitor: QueueMonitor,
+}
+
+impl<T> Clone for MeteredUnboundedSender<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), monitor: self.monitor.clone() }
+    }
+}
+
+impl<T> MeteredUnboundedSender<T> {
+    /// Sends a value without waiting.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.monitor.pushed();
+        self.inner.send((Instant::now(), value)).map_err(|SendError((_, value))| {
+            self.monitor.popped(Duration::ZERO);
+            self.monitor.record_dropped("closed");
+            SendError(value)
+        })
+    }
+}
+
+/// The receiving half of a [`metered_unbounded_channel`].
+#[derive(Debug)]
+pub struct MeteredUnboundedReceiver<T> {
+    inner: mpsc::UnboundedReceiver<(Instant, T)>,
+    monitor: QueueMonitor,
+}
+
+impl<T> MeteredUnboundedReceiver<T> {
+    /// Receives the next value, recording how long it was queued for.
+    pub async fn recv(&mut self) -> Option<T> {
+        let (sent_at, value) = self.inner.recv().await?;
+        self.monitor.popped(sent_at.elapsed());
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metered_channel_tracks_depth() {
+        let (tx, mut rx) = metered_channel("test", 4, ChannelAlarms::default());
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert_eq!(tx.monitor().depth(), 2);
+
+        assert_eq!(rx.recv().await, Some(1));
+        assert_eq!(tx.monitor().depth(), 1);
+        assert_eq!(rx.recv().await, Some(2));
+        assert_eq!(tx.monitor().depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_metered_channel_dropped_messages_do_not_count() {
+        let (tx, rx) = metered_channel("test", 1, ChannelAlarms::default());
+        tx.try_send(1).unwrap();
+        assert!(matches!(tx.try_send(2), Err(TrySendError::Full(2))));
+        assert_eq!(tx.monitor().depth(), 1);
+
+        drop(rx);
+        assert!(tx.send(3).await.is_err());
+        assert_eq!(tx.monitor().depth(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_watermark_channel_backpressure() {
+        let watermarks = Watermarks { high: 3, low: 1 };
+        let (tx, mut rx) = watermark_channel("test", watermarks, ChannelAlarms::default());
+        let mut backpressure = tx.monitor().subscribe_backpressure();
+
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert!(!tx.monitor().is_backpressured());
+        tx.send(3).await.unwrap();
+        assert!(tx.monitor().is_backpressured());
+        assert!(backpressure.has_changed().unwrap());
+        assert!(*backpressure.borrow_and_update());
+
+        // Backpressure is only released once the channel drains to the low watermark.
+        rx.recv().await.unwrap();
+        assert!(tx.monitor().is_backpressured());
+        rx.recv().await.unwrap();
+        assert!(!tx.monitor().is_backpressured());
+        assert!(!*backpressure.borrow_and_update());
+
+        tx.send(4).await.unwrap();
+        assert!(!tx.monitor().is_backpressured());
+    }
+
+    #[test]
+    fn test_queue_monitor_alarm_transitions() {
+        let alarms = ChannelAlarms { max_depth: 1, max_age: Duration::from_secs(1) };
+        let monitor = QueueMonitor::new("test", alarms);
+
+        monitor.record_depth(2);
+        assert!(monitor.state.depth_alarm.load(Ordering::Relaxed));
+        monitor.record_depth(1);
+        assert!(!monitor.state.depth_alarm.load(Ordering::Relaxed));
+
+        monitor.record_age(Duration::from_secs(2));
+        assert!(monitor.state.age_alarm.load(Ordering::Relaxed));
+        monitor.record_age(Duration::ZERO);
+        assert!(!monitor.state.age_alarm.load(Ordering::Relaxed));
+    }
+}
@@ -1,5 +1,12 @@
 //! Metrics for the node service
 
+mod channel;
+pub use channel::{
+    ChannelAlarms, MeteredReceiver, MeteredSender, MeteredUnboundedReceiver,
+    MeteredUnboundedSender, QueueMonitor, Watermarks, metered_channel, metered_unbounded_channel,
+    watermark_channel,
+};
+
 /// Container for metrics.
 #[derive(Debug, Clone)]
 pub struct Metrics;
@@ -14,6 +21,25 @@ impl Metrics {
     /// Identifier for the counter of critical derivation errors (strictly for alerting.)
     pub const DERIVATION_CRITICAL_ERROR: &str = "kona_node_derivation_critical_errors";
 
+    /// Identifier for the counter of the batcher data, frames, channels and batches consumed from
+    /// L1 origins the derivation pipeline advanced past.
+    pub const DERIVATION_ORIGIN_CONSUMED: &str = "kona_node_derivation_origin_consumed";
+
+    /// Identifier for the histogram that tracks the latency of derived payload attributes through
+    /// each hop of the node.
+    pub const DERIVATION_LATENCY: &str = "kona_node_derivation_latency_seconds";
+
+    /// From the L1 block being observed, to the batch being decoded.
+    pub const L1_TO_DECODED_LABEL: &str = "l1_to_decoded";
+    /// From the batch being decoded, to the attributes being delivered to the engine.
+    pub const DECODED_TO_SENT_LABEL: &str = "decoded_to_sent";
+    /// From the attributes being delivered to the engine, to the engine executing them.
+    pub const SENT_TO_EXECUTED_LABEL: &str = "sent_to_executed";
+    /// From the engine executing the attributes, to the safe head being updated.
+    pub const EXECUTED_TO_SAFE_LABEL: &str = "executed_to_safe";
+    /// From the L1 block being observed, to the safe head being updated.
+    pub const END_TO_END_LABEL: &str = "end_to_end";
+
     /// Identifier for the counter that tracks sequencer state flags.
     pub const SEQUENCER_STATE: &str = "kona_node_sequencer_state";
 
@@ -33,6 +59,66 @@ impl Metrics {
     pub const SEQUENCER_CONDUCTOR_COMMITMENT_DURATION: &str =
         "kona_node_sequencer_conductor_commitment_duration";
 
+    /// Gauge for the time the sequencer's last sealed payload was built for.
+    pub const SEQUENCER_PAYLOAD_BUILD_DURATION: &str = "kona_node_sequencer_payload_build_duration";
+
+    /// Gauge for the number of transactions included in the sequencer's last sealed block.
+    pub const SEQUENCER_BLOCK_TRANSACTIONS: &str = "kona_node_sequencer_block_transactions";
+
+    /// Gauge for the gas utilization of the sequencer's last sealed block, in percent.
+    pub const SEQUENCER_BLOCK_GAS_UTILIZATION: &str = "kona_node_sequencer_block_gas_utilization";
+
+    /// Gauge for the sequencer's DA backlog and the DA throttling limits applied for it.
+    pub const SEQUENCER_DA_THROTTLE: &str = "kona_node_sequencer_da_throttle";
+
+    /// Identifier for the gauge that tracks the number of messages queued in a channel.
+    pub const CHANNEL_DEPTH: &str = "kona_node_channel_depth";
+
+    /// Identifier for the gauge that tracks the age of the oldest message queued in a channel.
+    pub const CHANNEL_OLDEST_MESSAGE_AGE: &str = "kona_node_channel_oldest_message_age_seconds";
+
+    /// Identifier for the counter of messages dropped before they could be queued in a channel.
+    pub const CHANNEL_DROPPED: &str = "kona_node_channel_dropped_messages";
+
+    /// Identifier for the counter of raised channel depth and age alarms.
+    pub const CHANNEL_ALARMS: &str = "kona_node_channel_alarms";
+
+    /// Identifier for the gauge that tracks the L2 block number of the latest output proposal.
+    pub const PROPOSER_L2_BLOCK: &str = "kona_node_proposer_l2_block";
+
+    /// Identifier for the counter that tracks the number of failed output proposals.
+    pub const PROPOSER_FAILURES: &str = "kona_node_proposer_failures";
+
+    /// Identifier for the gauge that tracks the last L2 block of the latest submitted channel.
+    pub const BATCHER_L2_BLOCK: &str = "kona_node_batcher_l2_block";
+
+    /// Identifier for the counter that tracks the number of failed batch submissions.
+    pub const BATCHER_FAILURES: &str = "kona_node_batcher_failures";
+
+    /// Identifier for the gauge that tracks the estimated size of the data pending submission.
+    pub const BATCHER_DA_BACKLOG: &str = "kona_node_batcher_da_backlog";
+
+    /// Identifier for the counter that tracks the number of node state snapshots that failed to
+    /// be exported.
+    pub const SNAPSHOT_FAILURES: &str = "kona_node_snapshot_failures";
+
+    /// Identifier for the counter that tracks the number of derived attributes archived.
+    pub const ARCHIVED_ATTRIBUTES: &str = "kona_node_archived_attributes";
+
+    /// Identifier for the counter that tracks the number of derived attributes that failed to be
+    /// archived, labeled by the reason of the failure.
+    pub const ARCHIVE_FAILURES: &str = "kona_node_archive_failures";
+
+    /// Identifier for the counter that tracks the number of times the engine halted derivation
+    /// after too many derived payloads were rejected as invalid.
+    pub const DERIVATION_HALTS: &str = "kona_node_derivation_halts";
+
+    /// Identifier for the gauge that tracks the retention horizon last sent in a pruning hint.
+    pub const PRUNING_HINT_HORIZON: &str = "kona_node_pruning_hint_horizon";
+
+    /// Identifier for the counter that tracks the number of pruning hints that failed to be sent.
+    pub const PRUNING_HINT_FAILURES: &str = "kona_node_pruning_hint_failures";
+
     /// Initializes metrics for the node service.
     ///
     /// This does two things:
@@ -59,6 +145,20 @@ impl Metrics {
             "Critical errors in the derivation pipeline"
         );
 
+        // Derivation origin consumed
+        metrics::describe_counter!(
+            Self::DERIVATION_ORIGIN_CONSUMED,
+            metrics::Unit::Count,
+            "Batcher data, frames, channels and batches consumed from L1 origins by the derivation pipeline"
+        );
+
+        // Derivation latency
+        metrics::describe_histogram!(
+            Self::DERIVATION_LATENCY,
+            metrics::Unit::Seconds,
+            "Latency of derived payload attributes through each hop of the node"
+        );
+
         // Sequencer state
         metrics::describe_counter!(Self::SEQUENCER_STATE, "Tracks sequencer state flags");
 
@@ -85,6 +185,124 @@ impl Metrics {
             Self::SEQUENCER_CONDUCTOR_COMMITMENT_DURATION,
             "Duration of the sequencer conductor commitment"
         );
+
+        // Sequencer payload build duration
+        metrics::describe_gauge!(
+            Self::SEQUENCER_PAYLOAD_BUILD_DURATION,
+            "Time the last sealed sequencer payload was built for"
+        );
+
+        // Sequencer block transactions
+        metrics::describe_gauge!(
+            Self::SEQUENCER_BLOCK_TRANSACTIONS,
+            "Number of deposit and pool transactions in the last sealed sequencer block"
+        );
+
+        // Sequencer block gas utilization
+        metrics::describe_gauge!(
+            Self::SEQUENCER_BLOCK_GAS_UTILIZATION,
+            "Gas used by the last sealed sequencer block, in percent of its gas limit"
+        );
+
+        // Sequencer DA throttling
+        metrics::describe_gauge!(
+            Self::SEQUENCER_DA_THROTTLE,
+            "DA backlog in bytes and the DA size limits applied to sequenced blocks"
+        );
+
+        // Channel depth
+        metrics::describe_gauge!(Self::CHANNEL_DEPTH, "Number of messages queued in a channel");
+
+        // Channel oldest message age
+        metrics::describe_gauge!(
+            Self::CHANNEL_OLDEST_MESSAGE_AGE,
+            metrics::Unit::Seconds,
+            "Age of the oldest message queued in a channel"
+        );
+
+        // Channel dropped messages
+        metrics::describe_counter!(
+            Self::CHANNEL_DROPPED,
+            metrics::Unit::Count,
+            "Messages dropped before they could be queued in a channel"
+        );
+
+        // Channel alarms
+        metrics::describe_counter!(
+            Self::CHANNEL_ALARMS,
+            metrics::Unit::Count,
+            "Channel depth and age alarms raised"
+        );
+
+        // Proposer L2 block
+        metrics::describe_gauge!(
+            Self::PROPOSER_L2_BLOCK,
+            "L2 block number of the latest output proposal"
+        );
+
+        // Proposer failures
+        metrics::describe_counter!(
+            Self::PROPOSER_FAILURES,
+            metrics::Unit::Count,
+            "Output proposals that failed to be submitted"
+        );
+
+        // Batcher L2 block
+        metrics::describe_gauge!(
+            Self::BATCHER_L2_BLOCK,
+            "Last L2 block number of the latest channel submitted by the batcher"
+        );
+
+        // Batcher failures
+        metrics::describe_counter!(
+            Self::BATCHER_FAILURES,
+            metrics::Unit::Count,
+            "Batch submissions that failed"
+        );
+
+        // Batcher DA backlog
+        metrics::describe_gauge!(
+            Self::BATCHER_DA_BACKLOG,
+            metrics::Unit::Bytes,
+            "Estimated size of the batched data pending submission to L1"
+        );
+
+        // Snapshot failures
+        metrics::describe_counter!(
+            Self::SNAPSHOT_FAILURES,
+            metrics::Unit::Count,
+            "Node state snapshots that failed to be exported"
+        );
+
+        // Archive
+        metrics::describe_counter!(
+            Self::ARCHIVED_ATTRIBUTES,
+            metrics::Unit::Count,
+            "Derived attributes archived"
+        );
+        metrics::describe_counter!(
+            Self::ARCHIVE_FAILURES,
+            metrics::Unit::Count,
+            "Derived attributes that failed to be archived"
+        );
+
+        // Derivation halts
+        metrics::describe_counter!(
+            Self::DERIVATION_HALTS,
+            metrics::Unit::Count,
+            "Derivation halts after too many invalid derived payloads"
+        );
+
+        // Pruning hints
+        metrics::describe_gauge!(
+            Self::PRUNING_HINT_HORIZON,
+            "The retention horizon last sent in a pruning hint"
+        );
+        metrics::describe_counter!(
+            Self::PRUNING_HINT_FAILURES,
+            metrics::Unit::Count,
+            "Pruning hints that failed to be sent"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -96,5 +314,25 @@ impl Metrics {
 
         // Derivation critical error
         kona_macros::set!(counter, Self::DERIVATION_CRITICAL_ERROR, 0);
+
+        // Proposer failures
+        kona_macros::set!(counter, Self::PROPOSER_FAILURES, 0);
+
+        // Batcher failures
+        kona_macros::set!(counter, Self::BATCHER_FAILURES, 0);
+
+        // Snapshot failures
+        kona_macros::set!(counter, Self::SNAPSHOT_FAILURES, 0);
+
+        // Archive
+        kona_macros::set!(counter, Self::ARCHIVED_ATTRIBUTES, 0);
+        kona_macros::set!(counter, Self::ARCHIVE_FAILURES, "reason", "store", 0);
+        kona_macros::set!(counter, Self::ARCHIVE_FAILURES, "reason", "head", 0);
+
+        // Derivation halts
+        kona_macros::set!(counter, Self::DERIVATION_HALTS, 0);
+
+        // Pruning hint failures
+        kona_macros::set!(counter, Self::PRUNING_HINT_FAILURES, 0);
     }
 }
@@ -1,24 +1,32 @@
 //! Contains the builder for the [`RollupNode`].
 
 use crate::{
-    EngineConfig, InteropMode, NetworkConfig, RollupNode, SequencerConfig, service::node::L1Config,
+    AltDaProviders, ArchiveConfig, ArchiveTarget, BatcherConfig, EngineActorClient, EngineConfig,
+    InteropMode, L1BlockSource, NetworkConfig, NodeExtension, ProposerConfig, PruningHintConfig,
+    RollupNode, SequencerConfig, SharedL1Watcher, SnapshotConfig, service::node::L1Config,
 };
 use alloy_primitives::Bytes;
-use alloy_provider::RootProvider;
+use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_client::RpcClient;
 use alloy_transport_http::{
     AuthLayer, Http, HyperClient,
     hyper_util::{client::legacy::Client, rt::TokioExecutor},
 };
 use http_body_util::Full;
+use jsonrpsee::RpcModule;
+use kona_engine::OpEngineClient;
 use op_alloy_network::Optimism;
 use std::sync::Arc;
+use tokio::sync::watch;
 use tower::ServiceBuilder;
 use url::Url;
 
+use kona_derive::{AltDaProvider, CommitmentType};
 use kona_genesis::{L1ChainConfig, RollupConfig};
+use kona_interop::DependencySet;
 use kona_providers_alloy::OnlineBeaconClient;
 use kona_rpc::RpcBuilder;
+use kona_supervisor_rpc::SupervisorHealth;
 
 /// The [`L1ConfigBuilder`] is used to construct a [`L1Config`].
 #[derive(Debug)]
@@ -37,8 +45,14 @@ pub struct L1ConfigBuilder {
 }
 
 /// The [`RollupNodeBuilder`] is used to construct a [`RollupNode`] service.
+///
+/// The node drives its execution layer through an [`OpEngineClient`] built from the
+/// [`EngineConfig`], unless another [`EngineActorClient`] is given with
+/// [`RollupNodeBuilder::with_engine_client`].
 #[derive(Debug)]
-pub struct RollupNodeBuilder {
+pub struct RollupNodeBuilder<
+    EngineClient_: EngineActorClient = OpEngineClient<RootProvider, RootProvider<Optimism>>,
+> {
     /// The rollup configuration.
     pub config: RollupConfig,
     /// The L1 chain configuration.
@@ -53,8 +67,46 @@ pub struct RollupNodeBuilder {
     pub rpc_config: Option<RpcBuilder>,
     /// The [`SequencerConfig`].
     pub sequencer_config: Option<SequencerConfig>,
+    /// The [`ProposerConfig`]. If [`Some`], enables the output root proposer.
+    pub proposer_config: Option<ProposerConfig>,
+    /// The [`BatcherConfig`]. If [`Some`], enables the batcher.
+    pub batcher_config: Option<BatcherConfig>,
+    /// The [`SnapshotConfig`]. If [`Some`], enables the node state snapshot export.
+    pub snapshot_config: Option<SnapshotConfig>,
+    /// The [`PruningHintConfig`]. If [`Some`], enables the pruning hints sent to the execution
+    /// layer.
+    pub pruning_hint_config: Option<PruningHintConfig>,
+    /// The [`ArchiveConfig`]. If [`Some`], enables the archive of the derived attributes.
+    pub archive_config: Option<ArchiveConfig>,
+    /// The [`ArchiveTarget`] to read the payload attributes from. If [`Some`], the attributes are
+    /// read from the archive instead of being derived from L1.
+    pub archive_source: Option<ArchiveTarget>,
     /// Whether to run the node in interop mode.
     pub interop_mode: InteropMode,
+    /// The interop [`DependencySet`] of the chain, if it schedules Interop.
+    pub dependency_set: Option<DependencySet>,
+    /// The [`L1BlockSource`] driving the L1 watcher. If [`None`], the L1 RPC is polled.
+    pub l1_block_source: Option<Arc<dyn L1BlockSource>>,
+    /// The maximum number of bytes buffered by the derivation pipeline. If [`None`], the buffers
+    /// are unbounded.
+    pub derivation_memory_budget: Option<usize>,
+    /// The L1 RPC provider. If [`None`], it is built from the L1 RPC URL.
+    pub l1_provider: Option<RootProvider>,
+    /// The L1 beacon client. If [`None`], it is built from the L1 beacon API URL.
+    pub l1_beacon: Option<OnlineBeaconClient>,
+    /// The L2 RPC provider. If [`None`], it is built from the L2 engine URL and JWT secret.
+    pub l2_provider: Option<RootProvider<Optimism>>,
+    /// The engine client. If [`None`], it is built from the [`EngineConfig`].
+    pub engine_client: Option<Arc<EngineClient_>>,
+    /// Additional RPC modules, served alongside the node's own if the RPC server is enabled.
+    pub rpc_modules: Option<RpcModule<()>>,
+    /// The [`NodeExtension`]s run alongside the actors of the node.
+    pub extensions: Vec<Arc<dyn NodeExtension>>,
+    /// The providers the derivation pipeline resolves alt-DA commitments with.
+    pub alt_da_providers: AltDaProviders,
+    /// The health of the supervisor interop messages are validated against, reported by the
+    /// healthcheck.
+    pub supervisor_health: Option<watch::Receiver<SupervisorHealth>>,
 }
 
 impl RollupNodeBuilder {
@@ -75,15 +127,128 @@ impl RollupNodeBuilder {
             p2p_config,
             rpc_config,
             interop_mode: InteropMode::default(),
+            dependency_set: None,
             sequencer_config: None,
+            proposer_config: None,
+            batcher_config: None,
+            snapshot_config: None,
+            pruning_hint_config: None,
+            archive_config: None,
+            archive_source: None,
+            l1_block_source: None,
+            derivation_memory_budget: None,
+            l1_provider: None,
+            l1_beacon: None,
+            l2_provider: None,
+            engine_client: None,
+            rpc_modules: None,
+            extensions: Vec::new(),
+            alt_da_providers: AltDaProviders::default(),
+            supervisor_health: None,
         }
     }
+}
 
+impl<EngineClient_: EngineActorClient> RollupNodeBuilder<EngineClient_> {
     /// Sets the [`EngineConfig`] on the [`RollupNodeBuilder`].
     pub fn with_engine_config(self, engine_config: EngineConfig) -> Self {
         Self { engine_config, ..self }
     }
 
+    /// Sets the [`NetworkConfig`] on the [`RollupNodeBuilder`].
+    pub fn with_p2p_config(self, p2p_config: NetworkConfig) -> Self {
+        Self { p2p_config, ..self }
+    }
+
+    /// Sets the [`InteropMode`] on the [`RollupNodeBuilder`].
+    pub fn with_interop_mode(self, interop_mode: InteropMode) -> Self {
+        Self { interop_mode, ..self }
+    }
+
+    /// Sets the L1 RPC provider, instead of building one from the L1 RPC URL.
+    pub fn with_l1_provider(self, l1_provider: RootProvider) -> Self {
+        Self { l1_provider: Some(l1_provider), ..self }
+    }
+
+    /// Sets the L1 beacon client, instead of building one from the L1 beacon API URL.
+    pub fn with_l1_beacon(self, l1_beacon: OnlineBeaconClient) -> Self {
+        Self { l1_beacon: Some(l1_beacon), ..self }
+    }
+
+    /// Sets the L2 RPC provider, instead of building one from the L2 engine URL.
+    pub fn with_l2_provider(self, l2_provider: RootProvider<Optimism>) -> Self {
+        Self { l2_provider: Some(l2_provider), ..self }
+    }
+
+    /// Sets the engine client, instead of building one from the [`EngineConfig`].
+    ///
+    /// Any [`EngineActorClient`] may be given, such as an in-process client executing the blocks
+    /// without an external execution layer.
+    pub fn with_engine_client<Client: EngineActorClient>(
+        self,
+        engine_client: Arc<Client>,
+    ) -> RollupNodeBuilder<Client> {
+        RollupNodeBuilder {
+            config: self.config,
+            l1_config_builder: self.l1_config_builder,
+            l2_trust_rpc: self.l2_trust_rpc,
+            engine_config: self.engine_config,
+            p2p_config: self.p2p_config,
+            rpc_config: self.rpc_config,
+            interop_mode: self.interop_mode,
+            dependency_set: self.dependency_set,
+            sequencer_config: self.sequencer_config,
+            proposer_config: self.proposer_config,
+            batcher_config: self.batcher_config,
+            snapshot_config: self.snapshot_config,
+            pruning_hint_config: self.pruning_hint_config,
+            archive_config: self.archive_config,
+            archive_source: self.archive_source,
+            l1_block_source: self.l1_block_source,
+            derivation_memory_budget: self.derivation_memory_budget,
+            l1_provider: self.l1_provider,
+            l1_beacon: self.l1_beacon,
+            l2_provider: self.l2_provider,
+            engine_client: Some(engine_client),
+            rpc_modules: self.rpc_modules,
+            extensions: self.extensions,
+            alt_da_providers: self.alt_da_providers,
+            supervisor_health: self.supervisor_health,
+        }
+    }
+
+    /// Sets additional RPC modules, served alongside the node's own if the RPC server is enabled.
+    pub fn with_rpc_modules(self, rpc_modules: RpcModule<()>) -> Self {
+        Self { rpc_modules: Some(rpc_modules), ..self }
+    }
+
+    /// Registers a [`NodeExtension`], run alongside the actors of the node and subscribed to its
+    /// events.
+    pub fn with_extension(mut self, extension: impl NodeExtension) -> Self {
+        self.extensions.push(Arc::new(extension));
+        self
+    }
+
+    /// Registers the [`AltDaProvider`] the derivation pipeline resolves the alt-DA commitments of
+    /// the given [`CommitmentType`] with.
+    pub fn with_alt_da_provider<P>(mut self, commitment_type: CommitmentType, provider: P) -> Self
+    where
+        P: AltDaProvider + Clone + 'static,
+    {
+        self.alt_da_providers.register(commitment_type, provider);
+        self
+    }
+
+    /// Reports the health of the supervisor interop messages are validated against in the
+    /// healthcheck, e.g. from the `CircuitBreakerClient` of an interop transaction validator run
+    /// alongside the node.
+    pub fn with_supervisor_health(
+        self,
+        supervisor_health: watch::Receiver<SupervisorHealth>,
+    ) -> Self {
+        Self { supervisor_health: Some(supervisor_health), ..self }
+    }
+
     /// Sets the [`RpcBuilder`] on the [`RollupNodeBuilder`].
     pub fn with_rpc_config(self, rpc_config: Option<RpcBuilder>) -> Self {
         Self { rpc_config, ..self }
@@ -94,6 +259,66 @@ impl RollupNodeBuilder {
         Self { sequencer_config: Some(sequencer_config), ..self }
     }
 
+    /// Sets the [`ProposerConfig`] on the [`RollupNodeBuilder`].
+    pub fn with_proposer_config(self, proposer_config: Option<ProposerConfig>) -> Self {
+        Self { proposer_config, ..self }
+    }
+
+    /// Sets the [`BatcherConfig`] on the [`RollupNodeBuilder`].
+    pub fn with_batcher_config(self, batcher_config: Option<BatcherConfig>) -> Self {
+        Self { batcher_config, ..self }
+    }
+
+    /// Sets the [`SnapshotConfig`] on the [`RollupNodeBuilder`].
+    pub fn with_snapshot_config(self, snapshot_config: Option<SnapshotConfig>) -> Self {
+        Self { snapshot_config, ..self }
+    }
+
+    /// Sets the [`PruningHintConfig`] on the [`RollupNodeBuilder`].
+    pub fn with_pruning_hint_config(self, pruning_hint_config: Option<PruningHintConfig>) -> Self {
+        Self { pruning_hint_config, ..self }
+    }
+
+    /// Sets the [`ArchiveConfig`] on the [`RollupNodeBuilder`].
+    pub fn with_archive_config(self, archive_config: Option<ArchiveConfig>) -> Self {
+        Self { archive_config, ..self }
+    }
+
+    /// Sets the [`ArchiveTarget`] the payload attributes are read from on the
+    /// [`RollupNodeBuilder`], instead of being derived from L1.
+    pub fn with_archive_source(self, archive_source: Option<ArchiveTarget>) -> Self {
+        Self { archive_source, ..self }
+    }
+
+    /// Sets the interop [`DependencySet`] of the chain, validated against the rollup config when
+    /// the node starts.
+    pub fn with_dependency_set(self, dependency_set: DependencySet) -> Self {
+        Self { dependency_set: Some(dependency_set), ..self }
+    }
+
+    /// Sets the [`L1BlockSource`] that drives the L1 watcher, instead of polling the L1 RPC. The
+    /// derivation pipeline reads the L1 data the source holds from it, before falling back to the
+    /// L1 RPC.
+    pub fn with_l1_block_source(self, l1_block_source: Arc<dyn L1BlockSource>) -> Self {
+        Self { l1_block_source: Some(l1_block_source), ..self }
+    }
+
+    /// Subscribes the node to the given [`SharedL1Watcher`], which then drives its L1 watcher and
+    /// serves the batch inbox transactions read by its derivation pipeline. The watcher must be
+    /// started by the caller once all the nodes sharing it subscribed.
+    pub fn with_shared_l1_watcher<L1P: Provider + Clone>(
+        self,
+        watcher: &mut SharedL1Watcher<L1P>,
+    ) -> Self {
+        let source = watcher.subscribe(&self.config);
+        self.with_l1_block_source(Arc::new(source))
+    }
+
+    /// Sets the maximum number of bytes buffered by the derivation pipeline.
+    pub fn with_derivation_memory_budget(self, derivation_memory_budget: Option<usize>) -> Self {
+        Self { derivation_memory_budget, ..self }
+    }
+
     /// Assembles the [`RollupNode`] service.
     ///
     /// ## Panics
@@ -106,29 +331,38 @@ impl RollupNodeBuilder {
     /// - The jwt secret is not set.
     /// - The P2P config is not set.
     /// - The rollup boost args are not set.
-    pub fn build(self) -> RollupNode {
-        let mut l1_beacon = OnlineBeaconClient::new_http(self.l1_config_builder.beacon.to_string());
-        if let Some(l1_slot_duration) = self.l1_config_builder.slot_duration_override {
-            l1_beacon = l1_beacon.with_l1_slot_duration_override(l1_slot_duration);
-        }
+    pub fn build(self) -> RollupNode<EngineClient_> {
+        let l1_beacon = self.l1_beacon.unwrap_or_else(|| {
+            let l1_beacon = OnlineBeaconClient::new_http(self.l1_config_builder.beacon.to_string());
+            match self.l1_config_builder.slot_duration_override {
+                Some(l1_slot_duration) => {
+                    l1_beacon.with_l1_slot_duration_override(l1_slot_duration)
+                }
+                None => l1_beacon,
+            }
+        });
 
         let l1_config = L1Config {
             chain_config: Arc::new(self.l1_config_builder.chain_config),
             trust_rpc: self.l1_config_builder.trust_rpc,
             beacon_client: l1_beacon,
-            engine_provider: RootProvider::new_http(self.l1_config_builder.rpc_url.clone()),
+            engine_provider: self
+                .l1_provider
+                .unwrap_or_else(|| RootProvider::new_http(self.l1_config_builder.rpc_url.clone())),
         };
 
-        let jwt_secret = self.engine_config.l2_jwt_secret;
-        let hyper_client = Client::builder(TokioExecutor::new()).build_http::<Full<Bytes>>();
+        let l2_provider = self.l2_provider.unwrap_or_else(|| {
+            let jwt_secret = self.engine_config.l2_jwt_secret;
+            let hyper_client = Client::builder(TokioExecutor::new()).build_http::<Full<Bytes>>();
 
-        let auth_layer = AuthLayer::new(jwt_secret);
-        let service = ServiceBuilder::new().layer(auth_layer).service(hyper_client);
+            let auth_layer = AuthLayer::new(jwt_secret);
+            let service = ServiceBuilder::new().layer(auth_layer).service(hyper_client);
 
-        let layer_transport = HyperClient::with_service(service);
-        let http_hyper = Http::with_client(layer_transport, self.engine_config.l2_url.clone());
-        let rpc_client = RpcClient::new(http_hyper, false);
-        let l2_provider = RootProvider::<Optimism>::new(rpc_client);
+            let layer_transport = HyperClient::with_service(service);
+            let http_hyper = Http::with_client(layer_transport, self.engine_config.l2_url.clone());
+            let rpc_client = RpcClient::new(http_hyper, false);
+            RootProvider::<Optimism>::new(rpc_client)
+        });
 
         let rollup_config = Arc::new(self.config);
 
@@ -139,12 +373,26 @@ impl RollupNodeBuilder {
             config: rollup_config,
             l1_config,
             interop_mode: self.interop_mode,
+            dependency_set: self.dependency_set.map(Arc::new),
             l2_provider,
             l2_trust_rpc: self.l2_trust_rpc,
             engine_config: self.engine_config,
             rpc_builder: self.rpc_config,
             p2p_config,
             sequencer_config,
+            proposer_config: self.proposer_config,
+            batcher_config: self.batcher_config,
+            snapshot_config: self.snapshot_config,
+            pruning_hint_config: self.pruning_hint_config,
+            archive_config: self.archive_config,
+            archive_source: self.archive_source,
+            l1_block_source: self.l1_block_source,
+            derivation_memory_budget: self.derivation_memory_budget,
+            engine_client: self.engine_client,
+            rpc_modules: self.rpc_modules,
+            extensions: self.extensions,
+            alt_da_providers: self.alt_da_providers,
+            supervisor_health: self.supervisor_health,
         }
     }
 }
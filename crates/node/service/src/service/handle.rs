@@ -0,0 +1,189 @@
+//! Contains the [`RollupNodeHandle`] to a [`RollupNode`] running in the background.
+//!
+//! [`RollupNode`]: crate::RollupNode
+
+use kona_engine::{EngineQueries, EngineState};
+use kona_protocol::{BlockInfo, L2BlockInfo};
+use std::time::Duration;
+use tokio::{
+    sync::{mpsc, oneshot, watch},
+    task::JoinHandle,
+};
+use tokio_util::sync::CancellationToken;
+
+/// A handle to a [`RollupNode`] running in the background, returned by [`RollupNode::launch`].
+///
+/// The handle follows the heads of the node through watch channels, and shuts the node down.
+///
+/// [`RollupNode`]: crate::RollupNode
+/// [`RollupNode::launch`]: crate::RollupNode::launch
+#[derive(Debug)]
+pub struct RollupNodeHandle {
+    /// The state of the engine, holding the L2 heads of the node.
+    pub(crate) engine_state: watch::Receiver<EngineState>,
+    /// The L1 head observed by the node.
+    pub(crate) l1_head: watch::Receiver<Option<BlockInfo>>,
+    /// The cancellation token shared by all the actors of the node.
+    pub(crate) cancellation: CancellationToken,
+    /// The task running the node.
+    pub(crate) task: JoinHandle<Result<(), String>>,
+}
+
+impl RollupNodeHandle {
+    /// Returns a receiver of the [`EngineState`], holding the unsafe, safe and finalized L2 heads.
+    pub fn engine_state(&self) -> watch::Receiver<EngineState> {
+        self.engine_state.clone()
+    }
+
+    /// Returns a receiver of the latest L1 head observed by the node.
+    pub fn l1_head(&self) -> watch::Receiver<Option<BlockInfo>> {
+        self.l1_head.clone()
+    }
+
+    /// Returns the current unsafe L2 head.
+    pub fn unsafe_head(&self) -> L2BlockInfo {
+        self.engine_state.borrow().sync_state.unsafe_head()
+    }
+
+    /// Returns the current safe L2 head.
+    pub fn safe_head(&self) -> L2BlockInfo {
+        self.engine_state.borrow().sync_state.safe_head()
+    }
+
+    /// Returns the current finalized L2 head.
+    pub fn finalized_head(&self) -> L2BlockInfo {
+        self.engine_state.borrow().sync_state.finalized_head()
+    }
+
+    /// Returns whether the node has stopped.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Waits for the node to stop on its own, returning the error of the actor that stopped it,
+    /// if any.
+    pub async fn wait(mut self) -> Result<(), String> {
+        self.stopped().await
+    }
+
+    /// Waits for the node to stop on its own, like [`RollupNodeHandle::wait`], without consuming
+    /// the handle. This method is cancel safe, but must not be called again once it returned.
+    pub async fn stopped(&mut self) -> Result<(), String> {
+        (&mut self.task).await.map_err(|e| format!("Task join error: {e}"))?
+    }
+
+    /// Shuts the node down and waits for all its actors to stop.
+    pub async fn shutdown(self) -> Result<(), String> {
+        self.cancellation.cancel();
+        self.wait().await
+    }
+
+    /// Shuts the node down, and waits up to `timeout` for its actors to finish their in-flight
+    /// work and stop. The actors still running once the timeout elapses are aborted.
+    pub async fn drain(mut self, timeout: Duration) -> Result<(), String> {
+        self.cancellation.cancel();
+        match tokio::time::timeout(timeout, &mut self.task).await {
+            Ok(result) => result.map_err(|e| format!("Task join error: {e}"))?,
+            Err(_) => {
+                self.task.abort();
+                Err(format!("Node did not drain within {}s", timeout.as_secs()))
+            }
+        }
+    }
+
+    /// Logs a report of the L1 head and the L2 heads of the node, for debugging.
+    pub fn log_debug_report(&self) {
+        let state = *self.engine_state.borrow();
+        let l1_head = *self.l1_head.borrow();
+        info!(
+            target: "rollup_node",
+            l1_head = ?l1_head.map(|head| head.number),
+            unsafe_head = state.sync_state.unsafe_head().block_info.number,
+            cross_unsafe_head = state.sync_state.cross_unsafe_head().block_info.number,
+            local_safe_head = state.sync_state.local_safe_head().block_info.number,
+            safe_head = state.sync_state.safe_head().block_info.number,
+            finalized_head = state.sync_state.finalized_head().block_info.number,
+            el_sync_finished = state.el_sync_finished,
+            stopped = self.is_finished(),
+            "Node state report"
+        );
+    }
+}
+
+/// The senders feeding the watch channels of a [`RollupNodeHandle`].
+#[derive(Debug)]
+pub(crate) struct HandleSenders {
+    /// The sender of the engine state.
+    pub(crate) engine_state: watch::Sender<EngineState>,
+    /// The sender of the L1 head.
+    pub(crate) l1_head: watch::Sender<Option<BlockInfo>>,
+}
+
+impl HandleSenders {
+    /// Spawns the tasks forwarding the L1 head and the engine state of the node to the handle,
+    /// until the node is cancelled.
+    pub(crate) fn spawn(
+        self,
+        engine_queries: mpsc::Sender<EngineQueries>,
+        l1_head: watch::Receiver<Option<BlockInfo>>,
+        cancellation: CancellationToken,
+    ) {
+        tokio::spawn(forward(l1_head, self.l1_head, cancellation.clone()));
+        tokio::spawn(async move {
+            // The engine state channel is only created once the engine actor starts, and is
+            // fetched through a query.
+            let (tx, rx) = oneshot::channel();
+            if engine_queries.send(EngineQueries::StateReceiver(tx)).await.is_err() {
+                return;
+            }
+            let Ok(engine_state) = rx.await else {
+                return;
+            };
+            forward(engine_state, self.engine_state, cancellation).await;
+        });
+    }
+}
+
+/// Forwards the values of a watch channel to another, until the source is closed or the node is
+/// cancelled.
+async fn forward<T: Clone>(
+    mut from: watch::Receiver<T>,
+    to: watch::Sender<T>,
+    cancellation: CancellationToken,
+) {
+    loop {
+        to.send_replace(from.borrow_and_update().clone());
+        tokio::select! {
+            _ = cancellation.cancelled() => return,
+            changed = from.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_forward_until_cancelled() {
+        let (from_tx, from_rx) = watch::channel(1u64);
+        let (to_tx, mut to_rx) = watch::channel(0u64);
+        let cancellation = CancellationToken::new();
+        let task = tokio::spawn(forward(from_rx, to_tx, cancellation.clone()));
+
+        to_rx.changed().await.unwrap();
+        assert_eq!(*to_rx.borrow_and_update(), 1);
+
+        from_tx.send_replace(2);
+        to_rx.changed().await.unwrap();
+        assert_eq!(*to_rx.borrow_and_update(), 2);
+
+        cancellation.cancel();
+        task.await.unwrap();
+        assert!(to_rx.changed().await.is_err());
+    }
+}
@@ -6,11 +6,21 @@
 mod builder;
 pub use builder::{L1ConfigBuilder, RollupNodeBuilder};
 
+mod handle;
+pub use handle::RollupNodeHandle;
+
+mod signals;
+pub use signals::{NodeSignal, NodeSignals};
+
 mod mode;
 pub use mode::{InteropMode, NodeMode};
 
 mod node;
+pub(crate) use node::{FINALIZED_STREAM_POLL_INTERVAL, HEAD_STREAM_POLL_INTERVAL};
 pub use node::{L1Config, RollupNode};
 
 pub(crate) mod util;
 pub(crate) use util::spawn_and_wait;
+
+mod verify;
+pub(crate) use verify::verify_chains;
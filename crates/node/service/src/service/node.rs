@@ -1,29 +1,48 @@
 //! Contains the [`RollupNode`] implementation.
 use crate::{
-    ConductorClient, DelayedL1OriginSelectorProvider, DerivationActor, DerivationBuilder,
-    DerivationContext, EngineActor, EngineConfig, EngineContext, InteropMode, L1OriginSelector,
-    L1WatcherActor, NetworkActor, NetworkBuilder, NetworkConfig, NetworkContext, NodeActor,
-    NodeMode, QueuedBlockBuildingClient, QueuedSequencerAdminAPIClient, RpcActor, RpcContext,
-    SequencerActor, SequencerConfig,
+    AltDaProviders, ArchiveActor, ArchiveAttributesSource, ArchiveConfig, ArchiveContext,
+    ArchiveTarget, BatcherActor, BatcherConfig, ConductorClient, DaThrottle,
+    DelayedL1OriginSelectorProvider, DerivationActor, DerivationBuilder, DerivationContext,
+    DerivationOriginTracker, EngineActor, EngineActorClient, EngineConfig, EngineContext,
+    InteropMode, L1BlockSource, L1OriginSelector, L1ProvenanceRecorder, L1WatcherActor,
+    LinearDaThrottle, NetworkActor, NetworkBuilder, NetworkConfig, NetworkContext, NodeActor,
+    NodeExtension, NodeMode, ProposerActor, ProposerConfig, PruningHintActor, PruningHintConfig,
+    PruningHintContext, QueuedBlockBuildingClient, QueuedSequencerAdminAPIClient, RollupNodeHandle,
+    RpcActor, RpcContext, SequencerActor, SequencerConfig, SnapshotActor, SnapshotConfig,
+    SnapshotContext,
     actors::{
         BlockStream, DerivationInboundChannels, EngineInboundData, NetworkInboundData,
         QueuedUnsafePayloadGossipClient,
+        extension::{ExtensionsActor, ExtensionsContext},
     },
+    service::{handle::HandleSenders, verify_chains},
 };
 use alloy_eips::BlockNumberOrTag;
-use alloy_provider::RootProvider;
-use kona_derive::StatefulAttributesBuilder;
+use alloy_provider::{ProviderBuilder, RootProvider};
+use futures::StreamExt;
+use jsonrpsee::RpcModule;
+use kona_derive::{
+    PipelineEvent, PipelineEventSink, PipelineEvents, PipelineMemory, StatefulAttributesBuilder,
+};
+use kona_engine::{EngineState, OpEngineClient};
 use kona_genesis::{L1ChainConfig, RollupConfig};
+use kona_interop::DependencySet;
 use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient};
-use kona_rpc::RpcBuilder;
+use kona_rpc::{
+    DerivationHaltSwitch, L1ProvenanceDb, NodeCountersDb, RpcBuilder, RuntimeFlags, SafeHeadDb,
+};
+use kona_supervisor_rpc::SupervisorHealth;
 use op_alloy_network::Optimism;
 use std::{ops::Not as _, sync::Arc, time::Duration};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio_util::sync::CancellationToken;
 
 const DERIVATION_PROVIDER_CACHE_SIZE: usize = 1024;
-const HEAD_STREAM_POLL_INTERVAL: u64 = 4;
-const FINALIZED_STREAM_POLL_INTERVAL: u64 = 60;
+pub(crate) const HEAD_STREAM_POLL_INTERVAL: u64 = 4;
+pub(crate) const FINALIZED_STREAM_POLL_INTERVAL: u64 = 60;
+const PIPELINE_EVENTS_CAPACITY: usize = 1024;
+const NODE_EVENTS_CAPACITY: usize = 1024;
+const ARCHIVE_CAPACITY: usize = 1024;
 
 /// The configuration for the L1 chain.
 #[derive(Debug, Clone)]
@@ -40,14 +59,21 @@ pub struct L1Config {
 
 /// The standard implementation of the [RollupNode] service, using the governance approved OP Stack
 /// configuration of components.
+///
+/// The node drives its execution layer through an [`EngineActorClient`], an [`OpEngineClient`]
+/// built from the [`EngineConfig`] by default.
 #[derive(Debug)]
-pub struct RollupNode {
+pub struct RollupNode<
+    EngineClient_: EngineActorClient = OpEngineClient<RootProvider, RootProvider<Optimism>>,
+> {
     /// The rollup configuration.
     pub(crate) config: Arc<RollupConfig>,
     /// The L1 configuration.
     pub(crate) l1_config: L1Config,
     /// The interop mode for the node.
     pub(crate) interop_mode: InteropMode,
+    /// The interop [`DependencySet`] of the chain, if it schedules Interop.
+    pub(crate) dependency_set: Option<Arc<DependencySet>>,
     /// The L2 EL provider.
     pub(crate) l2_provider: RootProvider<Optimism>,
     /// Whether to trust the L2 RPC.
@@ -60,16 +86,55 @@ pub struct RollupNode {
     pub(crate) p2p_config: NetworkConfig,
     /// The [`SequencerConfig`] for the node.
     pub(crate) sequencer_config: SequencerConfig,
+    /// The [`ProposerConfig`] for the node, if the output root proposer is enabled.
+    pub(crate) proposer_config: Option<ProposerConfig>,
+    /// The [`BatcherConfig`] for the node, if the batcher is enabled.
+    pub(crate) batcher_config: Option<BatcherConfig>,
+    /// The [`SnapshotConfig`] for the node, if the node state snapshot export is enabled.
+    pub(crate) snapshot_config: Option<SnapshotConfig>,
+    /// The [`PruningHintConfig`] for the node, if the pruning hints are enabled.
+    pub(crate) pruning_hint_config: Option<PruningHintConfig>,
+    /// The [`ArchiveConfig`] for the node, if the archive of the derived attributes is enabled.
+    pub(crate) archive_config: Option<ArchiveConfig>,
+    /// The [`ArchiveTarget`] the payload attributes are read from, instead of being derived from
+    /// L1. If [`None`], the attributes are derived from L1.
+    pub(crate) archive_source: Option<ArchiveTarget>,
+    /// The [`L1BlockSource`] driving the L1 watcher. If [`None`], the L1 RPC is polled.
+    pub(crate) l1_block_source: Option<Arc<dyn L1BlockSource>>,
+    /// The maximum number of bytes buffered by the derivation pipeline. If [`None`], the buffers
+    /// are unbounded.
+    pub(crate) derivation_memory_budget: Option<usize>,
+    /// The engine client. If [`None`], it is built from the [`EngineConfig`].
+    pub(crate) engine_client: Option<Arc<EngineClient_>>,
+    /// Additional RPC modules, served alongside the node's own.
+    pub(crate) rpc_modules: Option<RpcModule<()>>,
+    /// The [`NodeExtension`]s run alongside the actors of the node.
+    pub(crate) extensions: Vec<Arc<dyn NodeExtension>>,
+    /// The providers alt-DA commitments are resolved with.
+    pub(crate) alt_da_providers: AltDaProviders,
+    /// The health of the supervisor interop messages are validated against.
+    pub(crate) supervisor_health: Option<watch::Receiver<SupervisorHealth>>,
 }
 
-impl RollupNode {
+impl<EngineClient_: EngineActorClient> RollupNode<EngineClient_> {
     /// The mode of operation for the node.
     const fn mode(&self) -> NodeMode {
         self.engine_config.mode
     }
 
-    /// Returns a derivation builder for the node.
-    fn derivation_builder(&self) -> DerivationBuilder {
+    /// Returns a derivation builder for the node, broadcasting the pipeline's events on the given
+    /// sender, and counting the batch data consumed from each L1 origin with the given tracker. If
+    /// the node keeps a database, the L1 provenance of the derived blocks is recorded in it.
+    fn derivation_builder(
+        &self,
+        pipeline_events: broadcast::Sender<PipelineEvent>,
+        origins: DerivationOriginTracker,
+    ) -> DerivationBuilder {
+        let recorder = self
+            .engine_config
+            .db
+            .clone()
+            .map(|db| L1ProvenanceRecorder::new(db, self.config.clone()));
         DerivationBuilder {
             l1_provider: self.l1_config.engine_provider.clone(),
             l1_trust_rpc: self.l1_config.trust_rpc,
@@ -79,6 +144,19 @@ impl RollupNode {
             rollup_config: self.config.clone(),
             l1_config: self.l1_config.chain_config.clone(),
             interop_mode: self.interop_mode,
+            pipeline_events: PipelineEvents::new(move |event| {
+                if let Some(recorder) = &recorder {
+                    recorder.emit(event);
+                }
+                origins.emit(event);
+                // Events are dropped while there are no subscribers.
+                let _ = pipeline_events.send(event);
+            }),
+            pipeline_memory: self
+                .derivation_memory_budget
+                .map_or_else(PipelineMemory::unbounded, PipelineMemory::with_budget),
+            alt_da_providers: self.alt_da_providers.clone(),
+            l1_block_source: self.l1_block_source.clone(),
         }
     }
 
@@ -141,19 +219,83 @@ impl RollupNode {
     /// finalizes `safe` blocks that it has derived when L1 finalized block updates are
     /// received.
     pub async fn start(&self) -> Result<(), String> {
+        self.run(CancellationToken::new(), None).await
+    }
+
+    /// Launches the rollup node service in the background. See [`RollupNode::start`].
+    ///
+    /// Returns a [`RollupNodeHandle`] following the heads of the node, and shutting it down.
+    pub fn launch(self) -> RollupNodeHandle {
         // Create a global cancellation token for graceful shutdown of tasks.
         let cancellation = CancellationToken::new();
+        let (engine_state_tx, engine_state) = watch::channel(EngineState::default());
+        let (l1_head_tx, l1_head) = watch::channel(None);
+        let senders = HandleSenders { engine_state: engine_state_tx, l1_head: l1_head_tx };
+
+        let task = tokio::spawn({
+            let cancellation = cancellation.clone();
+            async move { self.run(cancellation, Some(senders)).await }
+        });
+
+        RollupNodeHandle { engine_state, l1_head, cancellation, task }
+    }
+
+    /// Runs the rollup node service until the `cancellation` token is cancelled or one of its
+    /// actors fails. If `senders` are given, the heads of the node are forwarded to them.
+    async fn run(
+        &self,
+        cancellation: CancellationToken,
+        senders: Option<HandleSenders>,
+    ) -> Result<(), String> {
+        // Refuse to start against execution layers serving another chain.
+        verify_chains(&self.config, &self.l1_config.engine_provider, &self.l2_provider)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Refuse to start with a dependency set the chain is not part of.
+        if let Some(dependency_set) = &self.dependency_set {
+            dependency_set.validate(&self.config).map_err(|e| e.to_string())?;
+            info!(
+                target: "rollup_node",
+                chains = ?dependency_set.dependencies.keys().collect::<Vec<_>>(),
+                interop_time = ?self.config.hardforks.interop_time,
+                "Loaded the interop dependency set"
+            );
+        }
+
+        // Create the event bus of the node, which extensions subscribe to.
+        let (node_events_tx, _) = broadcast::channel(NODE_EVENTS_CAPACITY);
 
         // Create the derivation actor.
-        let (
-            DerivationInboundChannels {
-                derivation_signal_tx,
-                l1_head_updates_tx,
-                engine_l2_safe_head_tx,
-                el_sync_complete_tx,
-            },
-            derivation,
-        ) = DerivationActor::new(self.derivation_builder());
+        let (pipeline_events_tx, _) = broadcast::channel(PIPELINE_EVENTS_CAPACITY);
+        let (origins, derivation_origins_rx) = DerivationOriginTracker::new();
+        // If an archive source is configured, the attributes are read from the archive instead of
+        // being derived from L1.
+        let (inbound_channels, derivation, archive_derivation) = match &self.archive_source {
+            Some(target) => {
+                info!(
+                    target: "rollup_node",
+                    ?target,
+                    "Reading payload attributes from the archive"
+                );
+                let source = ArchiveAttributesSource::new(target.clone(), self.config.clone());
+                let (inbound_channels, derivation) = DerivationActor::new(source);
+                (inbound_channels, None, Some(derivation))
+            }
+            None => {
+                let (inbound_channels, derivation) = DerivationActor::new(self.derivation_builder(
+                    pipeline_events_tx.clone(),
+                    origins.with_node_events(node_events_tx.clone()),
+                ));
+                (inbound_channels, Some(derivation), None)
+            }
+        };
+        let DerivationInboundChannels {
+            derivation_signal_tx,
+            l1_head_updates_tx,
+            engine_l2_safe_head_tx,
+            el_sync_complete_tx,
+        } = inbound_channels;
 
         // Create the engine actor.
         let (
@@ -163,14 +305,46 @@ impl RollupNode {
                 finalized_l1_block_tx,
                 inbound_queries_tx: engine_rpc,
                 reset_request_tx,
+                engine_admin_query_tx: engine_admin_rpc,
                 rollup_boost_admin_query_tx: rollup_boost_admin_rpc,
                 rollup_boost_health_query_tx: rollup_boost_health_rpc,
                 seal_request_tx,
                 unsafe_block_tx,
                 unsafe_head_rx,
+                derivation_latency_rx,
+                sync_mode_rx,
             },
             engine,
-        ) = EngineActor::new(self.engine_config());
+        ) = EngineActor::<EngineClient_>::new(self.engine_config());
+        let engine = match &self.engine_client {
+            Some(client) => engine.with_client(Arc::clone(client)),
+            None => engine,
+        };
+
+        // Create the extensions actor.
+        let extensions =
+            (!self.extensions.is_empty()).then(|| ExtensionsActor::new(self.extensions.clone()));
+
+        // Create the derived attributes archive if configured. It receives the attributes from the
+        // derivation actor over a bounded channel, so that none are dropped.
+        let (archive, archive_tx) = match self.archive_config.clone() {
+            Some(config) => {
+                let (archive_tx, archive_rx) = mpsc::channel(ARCHIVE_CAPACITY);
+                (Some((ArchiveActor::new(config), archive_rx)), Some(archive_tx))
+            }
+            None => (None, None),
+        };
+
+        // The engine only publishes its events while they have consumers.
+        let engine = if extensions.is_some() {
+            engine.with_node_events(node_events_tx.clone())
+        } else {
+            engine
+        };
+
+        if let Some(senders) = senders {
+            senders.spawn(engine_rpc.clone(), l1_head_updates_tx.subscribe(), cancellation.clone());
+        }
 
         // Create the p2p actor.
         let (
@@ -184,7 +358,9 @@ impl RollupNode {
         ) = NetworkActor::new(self.network_builder());
 
         // Create the RPC server actor.
-        let rpc = self.rpc_builder().map(RpcActor::new);
+        let rpc = self
+            .rpc_builder()
+            .map(|config| RpcActor::new(config).with_modules(self.rpc_modules.clone()));
 
         let delayed_l1_provider = DelayedL1OriginSelectorProvider::new(
             self.l1_config.engine_provider.clone(),
@@ -204,19 +380,26 @@ impl RollupNode {
         // A channel to send queries about the state of L1.
         let (l1_query_tx, l1_query_rx) = mpsc::channel(1024);
 
-        let head_stream = BlockStream::new_as_stream(
-            self.l1_config.engine_provider.clone(),
-            BlockNumberOrTag::Latest,
-            Duration::from_secs(HEAD_STREAM_POLL_INTERVAL),
-        )?;
-        let finalized_stream = BlockStream::new_as_stream(
-            self.l1_config.engine_provider.clone(),
-            BlockNumberOrTag::Finalized,
-            Duration::from_secs(FINALIZED_STREAM_POLL_INTERVAL),
-        )?;
+        let (head_stream, finalized_stream) = match &self.l1_block_source {
+            Some(source) => (source.head_stream(), source.finalized_stream()),
+            None => (
+                BlockStream::new_as_stream(
+                    self.l1_config.engine_provider.clone(),
+                    BlockNumberOrTag::Latest,
+                    Duration::from_secs(HEAD_STREAM_POLL_INTERVAL),
+                )?
+                .boxed(),
+                BlockStream::new_as_stream(
+                    self.l1_config.engine_provider.clone(),
+                    BlockNumberOrTag::Finalized,
+                    Duration::from_secs(FINALIZED_STREAM_POLL_INTERVAL),
+                )?
+                .boxed(),
+            ),
+        };
 
         // Create the [`L1WatcherActor`]. Previously known as the DA watcher actor.
-        let l1_watcher = L1WatcherActor::new(
+        let mut l1_watcher = L1WatcherActor::new(
             self.config.clone(),
             self.l1_config.engine_provider.clone(),
             l1_query_rx,
@@ -226,7 +409,26 @@ impl RollupNode {
             cancellation.clone(),
             head_stream,
             finalized_stream,
-        );
+        )
+        .with_beacon_client(self.l1_config.beacon_client.clone());
+        if let Some(source) = &self.l1_block_source {
+            l1_watcher = l1_watcher.with_block_source(Arc::clone(source));
+        }
+
+        // Create the batcher if configured.
+        let batcher = self.batcher_config.clone().map(|config| {
+            let l1_provider = ProviderBuilder::new()
+                .wallet(config.signer.clone())
+                .connect_provider(self.l1_config.engine_provider.clone());
+            BatcherActor::new(
+                config,
+                self.config.clone(),
+                l1_provider,
+                self.l2_provider.clone(),
+                engine_rpc.clone(),
+                cancellation.clone(),
+            )
+        });
 
         // Create the sequencer if needed
         let (sequencer_actor, sequencer_admin_api_tx) = if self.mode().is_sequencer() {
@@ -251,6 +453,21 @@ impl RollupNode {
             let queued_gossip_client =
                 QueuedUnsafePayloadGossipClient::new(gossip_payload_tx.clone());
 
+            // Throttle the DA usage of sequenced blocks on the backlog of the batcher.
+            let da_throttle = self.sequencer_config.da_throttle.map(|config| {
+                let backlog = batcher.as_ref().map_or_else(
+                    || {
+                        warn!(
+                            target: "sequencer",
+                            "DA throttling is enabled without a batcher, blocks will not be throttled"
+                        );
+                        watch::channel(0).1
+                    },
+                    BatcherActor::backlog,
+                );
+                DaThrottle::new(LinearDaThrottle { config }, self.l2_provider.clone(), backlog)
+            });
+
             (
                 Some(SequencerActor {
                     admin_api_rx: sequencer_admin_api_rx,
@@ -258,9 +475,11 @@ impl RollupNode {
                     block_building_client,
                     cancellation_token: cancellation.clone(),
                     conductor,
+                    da_throttle,
                     is_active: self.sequencer_config.sequencer_stopped.not(),
                     in_recovery_mode: self.sequencer_config.sequencer_recovery_mode,
                     origin_selector: delayed_origin_selector,
+                    payload_build_config: self.sequencer_config.payload_build,
                     rollup_config: self.config.clone(),
                     unsafe_payload_gossip_client: queued_gossip_client,
                 }),
@@ -270,9 +489,77 @@ impl RollupNode {
             (None, None)
         };
 
+        // Create the output root proposer if configured.
+        let proposer = self.proposer_config.clone().map(|config| {
+            let l1_provider = ProviderBuilder::new()
+                .wallet(config.signer.clone())
+                .connect_provider(self.l1_config.engine_provider.clone());
+            ProposerActor::new(config, l1_provider, engine_rpc.clone(), cancellation.clone())
+        });
+
+        // Create the node state snapshot exporter if configured.
+        let snapshot = self.snapshot_config.clone().map(SnapshotActor::new);
+
+        // The runtime flags, set through the admin RPC.
+        let runtime_flags = RuntimeFlags::default();
+
+        // The switch the engine halts derivation with, acknowledged through the admin RPC.
+        let derivation_halt = DerivationHaltSwitch::default();
+
+        // Create the pruning hint sender if configured.
+        let pruning_hint = self
+            .pruning_hint_config
+            .clone()
+            .map(|config| PruningHintActor::new(config, self.config.clone()));
+
+        // Only one of the derivation actors is created, and started with the derivation context.
+        let derivation_context = DerivationContext {
+            reset_request_tx: reset_request_tx.clone(),
+            derived_attributes_tx: attributes_tx,
+            archive_tx,
+            runtime_flags: runtime_flags.clone(),
+            derivation_halt: derivation_halt.clone(),
+            cancellation: cancellation.clone(),
+        };
+        let (derivation, archive_derivation) = match derivation {
+            Some(derivation) => (Some((derivation, derivation_context)), None),
+            None => (None, archive_derivation.map(|derivation| (derivation, derivation_context))),
+        };
+
         crate::service::spawn_and_wait!(
             cancellation,
             actors = [
+                extensions.map(|e| (
+                    e,
+                    ExtensionsContext {
+                        events: node_events_tx,
+                        l1_head: l1_head_updates_tx.subscribe(),
+                        engine_queries: engine_rpc.clone(),
+                        cancellation: cancellation.clone(),
+                    }
+                )),
+                archive.map(|(a, attributes)| (
+                    a,
+                    ArchiveContext { attributes, cancellation: cancellation.clone() }
+                )),
+                snapshot.map(|s| (
+                    s,
+                    SnapshotContext {
+                        engine_queries: engine_rpc.clone(),
+                        p2p_network: network_rpc.clone(),
+                        l1_head: l1_head_updates_tx.subscribe(),
+                        sync_mode: sync_mode_rx.clone(),
+                        derivation_origins: derivation_origins_rx.clone(),
+                        cancellation: cancellation.clone(),
+                    }
+                )),
+                pruning_hint.map(|p| (
+                    p,
+                    PruningHintContext {
+                        engine_queries: engine_rpc.clone(),
+                        cancellation: cancellation.clone(),
+                    }
+                )),
                 rpc.map(|r| (
                     r,
                     RpcContext {
@@ -282,30 +569,57 @@ impl RollupNode {
                         sequencer_admin: sequencer_admin_api_tx,
                         l1_watcher_queries: l1_query_tx,
                         engine_query: engine_rpc,
+                        engine_admin: engine_admin_rpc,
                         rollup_boost_admin: rollup_boost_admin_rpc,
                         rollup_boost_health: rollup_boost_health_rpc,
+                        safe_head_db: self
+                            .engine_config
+                            .db
+                            .clone()
+                            .map(|db| Arc::new(db) as Arc<dyn SafeHeadDb>),
+                        l1_provenance_db: self
+                            .engine_config
+                            .db
+                            .clone()
+                            .map(|db| Arc::new(db) as Arc<dyn L1ProvenanceDb>),
+                        node_counters_db: self
+                            .engine_config
+                            .db
+                            .clone()
+                            .map(|db| Arc::new(db) as Arc<dyn NodeCountersDb>),
+                        derivation_latency: derivation_latency_rx,
+                        sync_mode: sync_mode_rx,
+                        derivation_origins: derivation_origins_rx,
+                        pipeline_events: pipeline_events_tx,
+                        dependency_set: self.dependency_set.clone(),
+                        rollup_config: self.config.clone(),
+                        runtime_flags: runtime_flags.clone(),
+                        derivation_halt: derivation_halt.clone(),
+                        supervisor_health: self.supervisor_health.clone(),
                     }
                 )),
                 sequencer_actor.map(|s| (s, ())),
+                proposer.map(|p| (p, ())),
+                batcher.map(|b| (b, ())),
                 Some((
                     network,
-                    NetworkContext { blocks: unsafe_block_tx, cancellation: cancellation.clone() }
-                )),
-                Some((l1_watcher, ())),
-                Some((
-                    derivation,
-                    DerivationContext {
-                        reset_request_tx: reset_request_tx.clone(),
-                        derived_attributes_tx: attributes_tx,
+                    NetworkContext {
+                        blocks: unsafe_block_tx,
+                        runtime_flags: runtime_flags.clone(),
                         cancellation: cancellation.clone(),
                     }
                 )),
+                Some((l1_watcher, ())),
+                derivation,
+                archive_derivation,
                 Some((
                     engine,
                     EngineContext {
                         engine_l2_safe_head_tx,
                         sync_complete_tx: el_sync_complete_tx,
                         derivation_signal_tx,
+                        runtime_flags,
+                        derivation_halt,
                         cancellation: cancellation.clone(),
                     }
                 )),
@@ -0,0 +1,122 @@
+//! Process signals handled by the rollup node, for integration with service managers.
+
+use std::fmt;
+
+/// A request to the rollup node, delivered as a process signal.
+///
+/// | Signal | Unix | Windows |
+/// |--------|------|---------|
+/// | [`NodeSignal::Reload`] | `SIGHUP` | - |
+/// | [`NodeSignal::DebugReport`] | `SIGUSR1` | `CTRL_BREAK` |
+/// | [`NodeSignal::Shutdown`] | `SIGTERM`, `SIGINT` | `CTRL_C`, `CTRL_CLOSE`, `CTRL_SHUTDOWN` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeSignal {
+    /// Reload the configuration, JWT secrets and log levels of the node.
+    Reload,
+    /// Log a report of the state of the node.
+    DebugReport,
+    /// Drain the actors of the node and stop it.
+    Shutdown,
+}
+
+impl fmt::Display for NodeSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reload => write!(f, "reload"),
+            Self::DebugReport => write!(f, "debug report"),
+            Self::Shutdown => write!(f, "shutdown"),
+        }
+    }
+}
+
+/// Listens to the process signals handled by the rollup node.
+///
+/// Once created, the signals are no longer handled by the default disposition of the process, so
+/// `SIGTERM` and `SIGINT` only stop the node through [`NodeSignal::Shutdown`].
+#[derive(Debug)]
+pub struct NodeSignals {
+    #[cfg(unix)]
+    hangup: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    user_defined1: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    interrupt: tokio::signal::unix::Signal,
+    #[cfg(windows)]
+    ctrl_c: tokio::signal::windows::CtrlC,
+    #[cfg(windows)]
+    ctrl_break: tokio::signal::windows::CtrlBreak,
+    #[cfg(windows)]
+    ctrl_close: tokio::signal::windows::CtrlClose,
+    #[cfg(windows)]
+    ctrl_shutdown: tokio::signal::windows::CtrlShutdown,
+}
+
+impl NodeSignals {
+    /// Registers the signal handlers of the node.
+    ///
+    /// Must be called from within a tokio runtime.
+    #[cfg(unix)]
+    pub fn new() -> std::io::Result<Self> {
+        use tokio::signal::unix::{SignalKind, signal};
+        Ok(Self {
+            hangup: signal(SignalKind::hangup())?,
+            user_defined1: signal(SignalKind::user_defined1())?,
+            terminate: signal(SignalKind::terminate())?,
+            interrupt: signal(SignalKind::interrupt())?,
+        })
+    }
+
+    /// Registers the signal handlers of the node.
+    ///
+    /// Must be called from within a tokio runtime.
+    #[cfg(windows)]
+    pub fn new() -> std::io::Result<Self> {
+        use tokio::signal::windows::{ctrl_break, ctrl_c, ctrl_close, ctrl_shutdown};
+        Ok(Self {
+            ctrl_c: ctrl_c()?,
+            ctrl_break: ctrl_break()?,
+            ctrl_close: ctrl_close()?,
+            ctrl_shutdown: ctrl_shutdown()?,
+        })
+    }
+
+    /// Waits for the next signal. This method is cancel safe.
+    #[cfg(unix)]
+    pub async fn recv(&mut self) -> NodeSignal {
+        tokio::select! {
+            Some(()) = self.hangup.recv() => NodeSignal::Reload,
+            Some(()) = self.user_defined1.recv() => NodeSignal::DebugReport,
+            Some(()) = self.terminate.recv() => NodeSignal::Shutdown,
+            Some(()) = self.interrupt.recv() => NodeSignal::Shutdown,
+        }
+    }
+
+    /// Waits for the next signal. This method is cancel safe.
+    #[cfg(windows)]
+    pub async fn recv(&mut self) -> NodeSignal {
+        tokio::select! {
+            Some(()) = self.ctrl_break.recv() => NodeSignal::DebugReport,
+            Some(()) = self.ctrl_c.recv() => NodeSignal::Shutdown,
+            Some(()) = self.ctrl_close.recv() => NodeSignal::Shutdown,
+            Some(()) = self.ctrl_shutdown.recv() => NodeSignal::Shutdown,
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_receives_signals() {
+        let mut signals = NodeSignals::new().unwrap();
+        let pid = std::process::id().to_string();
+
+        for (name, expected) in [("-USR1", NodeSignal::DebugReport), ("-HUP", NodeSignal::Reload)] {
+            std::process::Command::new("kill").args([name, &pid]).status().unwrap();
+            assert_eq!(signals.recv().await, expected);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Verification of the chains served by the L1 and L2 execution layers against the rollup config,
+//! run before the node starts.
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use kona_genesis::RollupConfig;
+use op_alloy_network::Optimism;
+use thiserror::Error;
+
+/// An error returned when an execution layer serves a different chain than the rollup config.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub(crate) enum ChainVerificationError {
+    /// The L1 execution layer serves a different chain.
+    #[error("L1 chain ID is {actual}, but the rollup config expects {expected}")]
+    L1ChainId {
+        /// The L1 chain ID of the rollup config.
+        expected: u64,
+        /// The chain ID returned by the L1 execution layer.
+        actual: u64,
+    },
+    /// The L2 execution layer serves a different chain.
+    #[error("L2 chain ID is {actual}, but the rollup config expects {expected}")]
+    L2ChainId {
+        /// The L2 chain ID of the rollup config.
+        expected: u64,
+        /// The chain ID returned by the L2 execution layer.
+        actual: u64,
+    },
+    /// The L2 execution layer has a different genesis block.
+    #[error(
+        "L2 genesis block #{number} has hash {actual}, but the rollup config expects {expected}"
+    )]
+    L2GenesisHash {
+        /// The number of the L2 genesis block.
+        number: u64,
+        /// The L2 genesis hash of the rollup config.
+        expected: B256,
+        /// The hash of the block returned by the L2 execution layer.
+        actual: B256,
+    },
+}
+
+/// Verifies that the L1 and L2 execution layers serve the chains of the rollup config, by
+/// comparing their chain IDs and the L2 genesis block hash.
+///
+/// A mispointed endpoint is refused here, rather than surfacing later as confusing derivation or
+/// engine failures. Checks that fail to reach the execution layer are skipped with a warning, as
+/// the execution layer may still be starting up.
+pub(crate) async fn verify_chains(
+    config: &RollupConfig,
+    l1_provider: &RootProvider,
+    l2_provider: &RootProvider<Optimism>,
+) -> Result<(), ChainVerificationError> {
+    match l1_provider.get_chain_id().await {
+        Ok(actual) if actual != config.l1_chain_id => {
+            return Err(ChainVerificationError::L1ChainId { expected: config.l1_chain_id, actual });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            warn!(target: "rollup_node", "Failed to fetch the L1 chain ID, skipping check: {e}")
+        }
+    }
+
+    let expected = config.l2_chain_id.id();
+    match l2_provider.get_chain_id().await {
+        Ok(actual) if actual != expected => {
+            return Err(ChainVerificationError::L2ChainId { expected, actual });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            warn!(target: "rollup_node", "Failed to fetch the L2 chain ID, skipping check: {e}")
+        }
+    }
+
+    let genesis = config.genesis.l2;
+    match l2_provider.get_block_by_number(BlockNumberOrTag::Number(genesis.number)).await {
+        Ok(Some(block)) if block.header.hash != genesis.hash => {
+            return Err(ChainVerificationError::L2GenesisHash {
+                number: genesis.number,
+                expected: genesis.hash,
+                actual: block.header.hash,
+            });
+        }
+        Ok(Some(_)) => {}
+        // The genesis block is unavailable on execution layers started from a snapshot.
+        Ok(None) => warn!(
+            target: "rollup_node",
+            "L2 genesis block #{} not found, skipping genesis hash check", genesis.number
+        ),
+        Err(e) => warn!(
+            target: "rollup_node",
+            "Failed to fetch the L2 genesis block, skipping genesis hash check: {e}"
+        ),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U64;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::mock::{Asserter, MockTransport};
+
+    fn provider<N: alloy_network::Network>(asserter: &Asserter) -> RootProvider<N> {
+        RootProvider::new(RpcClient::new(MockTransport::new(asserter.clone()), false))
+    }
+
+    fn rollup_config() -> RollupConfig {
+        RollupConfig { l1_chain_id: 1, l2_chain_id: 10.into(), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_l1_chain_id_mismatch() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        l1.push_success(&U64::from(11155111));
+
+        let result = verify_chains(&rollup_config(), &provider(&l1), &provider(&l2)).await;
+        assert_eq!(
+            result,
+            Err(ChainVerificationError::L1ChainId { expected: 1, actual: 11155111 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_l2_chain_id_mismatch() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        l1.push_success(&U64::from(1));
+        l2.push_success(&U64::from(8453));
+
+        let result = verify_chains(&rollup_config(), &provider(&l1), &provider(&l2)).await;
+        assert_eq!(result, Err(ChainVerificationError::L2ChainId { expected: 10, actual: 8453 }));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_checks_are_skipped() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        l1.push_failure_msg("connection refused");
+        l2.push_success(&U64::from(10));
+        l2.push_success(&Option::<()>::None);
+
+        let result = verify_chains(&rollup_config(), &provider(&l1), &provider(&l2)).await;
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,165 @@
+//! A scripted engine, standing in for the [`EngineActor`] on its channels to the derivation actor.
+//!
+//! [`EngineActor`]: crate::EngineActor
+
+use super::ScriptedL1;
+use crate::{DerivedAttributes, MeteredReceiver, ResetRequest};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::keccak256;
+use kona_derive::{ResetSignal, Signal};
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
+
+/// How long the engine waits on the derivation actor, in virtual time, before giving up.
+pub const ENGINE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The response of the scripted engine to derived attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineResponse {
+    /// The payload is valid, and becomes the new safe head.
+    Valid,
+    /// The payload is invalid. The engine flushes the active channel of the derivation pipeline,
+    /// and builds a deposits-only block in its place.
+    Invalid,
+}
+
+/// Plays the engine side of the channels to the derivation actor.
+///
+/// Mirrors the [`EngineActor`]: the safe head is only published when it changes, and it is
+/// always published before the signal that follows it.
+///
+/// [`EngineActor`]: crate::EngineActor
+#[derive(Debug)]
+pub struct ScriptedEngine {
+    /// Receives the attributes derived by the derivation actor.
+    pub(crate) attributes_rx: MeteredReceiver<DerivedAttributes>,
+    /// Receives the reset requests of the derivation actor.
+    pub(crate) reset_request_rx: mpsc::Receiver<ResetRequest>,
+    /// Publishes the L2 safe head.
+    pub(crate) safe_head_tx: watch::Sender<L2BlockInfo>,
+    /// Sends signals to the derivation pipeline.
+    pub(crate) derivation_signal_tx: mpsc::Sender<Signal>,
+    /// Signals the derivation actor that EL sync has completed.
+    pub(crate) el_sync_complete_tx: Option<oneshot::Sender<()>>,
+    /// The safe L2 chain, starting at the genesis safe head.
+    pub(crate) safe_chain: Vec<L2BlockInfo>,
+}
+
+impl ScriptedEngine {
+    /// Returns the L2 safe head.
+    pub fn safe_head(&self) -> L2BlockInfo {
+        *self.safe_chain.last().expect("The safe chain has a genesis")
+    }
+
+    /// Performs the initial engine reset onto the genesis safe head, and starts derivation.
+    pub(crate) async fn complete_el_sync(&mut self, l1_origin: BlockInfo) {
+        self.publish_safe_head();
+        self.signal(
+            ResetSignal { l2_safe_head: self.safe_head(), l1_origin, system_config: None }.signal(),
+        )
+        .await;
+        if let Some(tx) = self.el_sync_complete_tx.take() {
+            tx.send(()).expect("Derivation actor stopped");
+        }
+    }
+
+    /// Waits for the next attributes derived by the derivation actor.
+    pub async fn next_attributes(&mut self) -> OpAttributesWithParent {
+        let attributes = tokio::time::timeout(ENGINE_TIMEOUT, self.attributes_rx.recv())
+            .await
+            .expect("No attributes were derived")
+            .expect("Derivation actor stopped");
+        attributes.attributes
+    }
+
+    /// Asserts that the derivation actor derives no attributes within the given duration.
+    pub async fn expect_no_attributes(&mut self, within: Duration) {
+        if let Ok(attributes) = tokio::time::timeout(within, self.attributes_rx.recv()).await {
+            panic!("Unexpected attributes were derived: {attributes:?}");
+        }
+    }
+
+    /// Executes the attributes with the given response, returning the new safe head.
+    pub async fn execute(
+        &mut self,
+        attributes: &OpAttributesWithParent,
+        response: EngineResponse,
+    ) -> L2BlockInfo {
+        if response == EngineResponse::Invalid {
+            self.signal(Signal::FlushChannel).await;
+        }
+
+        let parent = attributes.parent;
+        let origin = attributes.derived_from.expect("Derived attributes have an L1 origin");
+        let timestamp = attributes.attributes.payload_attributes.timestamp;
+        let seq_num = if parent.l1_origin.hash == origin.hash { parent.seq_num + 1 } else { 0 };
+        let deposits_only = response == EngineResponse::Invalid;
+
+        let block = L2BlockInfo::new(
+            BlockInfo::new(
+                keccak256(
+                    [
+                        parent.block_info.hash.as_slice(),
+                        &timestamp.to_be_bytes(),
+                        &[deposits_only as u8],
+                    ]
+                    .concat(),
+                ),
+                parent.block_info.number + 1,
+                parent.block_info.hash,
+                timestamp,
+            ),
+            BlockNumHash { number: origin.number, hash: origin.hash },
+            seq_num,
+        );
+        self.safe_chain.push(block);
+        self.publish_safe_head();
+        block
+    }
+
+    /// Waits for a reset request from the derivation actor, and resets the safe head to the
+    /// latest safe block whose L1 origin is still canonical.
+    pub(crate) async fn reset(&mut self, l1: &std::sync::Mutex<ScriptedL1>) -> L2BlockInfo {
+        let request = tokio::time::timeout(ENGINE_TIMEOUT, self.reset_request_rx.recv())
+            .await
+            .expect("No reset was requested")
+            .expect("Derivation actor stopped");
+
+        let l1_origin = {
+            let l1 = l1.lock().unwrap();
+            while !l1
+                .is_canonical(self.safe_head().l1_origin.number, self.safe_head().l1_origin.hash)
+            {
+                self.safe_chain.pop();
+            }
+            l1.blocks[&self.safe_head().l1_origin.number]
+        };
+
+        self.publish_safe_head();
+        self.signal(
+            ResetSignal { l2_safe_head: self.safe_head(), l1_origin, system_config: None }.signal(),
+        )
+        .await;
+
+        if let Some(tx) = request.result_tx {
+            tx.send(Ok(())).await.ok();
+        }
+        self.safe_head()
+    }
+
+    /// Publishes the safe head, if it changed.
+    fn publish_safe_head(&self) {
+        let safe_head = self.safe_head();
+        self.safe_head_tx.send_if_modified(|head| {
+            let modified = *head != safe_head;
+            *head = safe_head;
+            modified
+        });
+    }
+
+    /// Sends a signal to the derivation pipeline.
+    async fn signal(&self, signal: Signal) {
+        self.derivation_signal_tx.send(signal).await.expect("Derivation actor stopped");
+    }
+}
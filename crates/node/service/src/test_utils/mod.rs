@@ -0,0 +1,319 @@
+//! Test utilities for deterministic action tests of the node actors.
+//!
+//! The [`ActionHarness`] runs the real [`L1WatcherActor`] and [`DerivationActor`], with every
+//! external input scripted by the test:
+//! - L1 heads are pushed into the head stream of the L1 watcher, over a mocked L1 provider.
+//! - A scripted pipeline derives a scripted number of L2 blocks from each L1 block, and fails its
+//!   steps with scripted temporary, reset or critical errors.
+//! - The [`ScriptedEngine`] plays the engine actor, answering each derived payload with a scripted
+//!   [`EngineResponse`] and serving the derivation actor's reset requests.
+//! - The harness plays the archive actor, receiving the derived attributes over a bounded channel.
+//!
+//! Tests run on tokio's paused clock, so timeouts advance virtual time only once every actor is
+//! idle. Runs are reproducible, and stalls can be asserted without waiting in real time. The
+//! harness is exposed by the `test-utils` feature, so that downstream crates can test their
+//! modifications of the actors:
+//!
+//! ```ignore
+//! let mut harness = ActionHarness::new().await;
+//! harness.push_step_failure(ResetError::HoloceneActivation.reset());
+//! harness.push_l1_block(2).await;
+//!
+//! harness.reset().await;
+//! let attributes = harness.next_attributes().await;
+//! ```
+
+mod engine;
+pub use engine::{ENGINE_TIMEOUT, EngineResponse, ScriptedEngine};
+
+mod pipeline;
+pub(crate) use pipeline::{ScriptedL1, ScriptedPipeline};
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    ChannelAlarms, DerivationActor, DerivationContext, DerivationError, DerivedAttributes,
+    L1WatcherActor, L1WatcherActorError, MeteredReceiver, MeteredSender, NodeActor, Watermarks,
+    metered_channel, watermark_channel,
+};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, B256, keccak256};
+use alloy_provider::RootProvider;
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types_eth::Log;
+use alloy_transport::mock::{Asserter, MockTransport};
+use kona_derive::{PipelineErrorKind, Signal};
+use kona_genesis::RollupConfig;
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use kona_rpc::{DerivationHaltSwitch, L1WatcherQueries};
+use std::sync::{Arc, Mutex};
+use tokio::{
+    sync::{mpsc, oneshot, watch},
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
+
+/// The L1 block time of the scripted L1 chain, in seconds.
+const L1_BLOCK_TIME: u64 = 12;
+
+/// The default capacity of the channel of the archived attributes.
+const ARCHIVE_CAPACITY: usize = 64;
+
+/// Drives the L1 watcher and derivation actors with scripted events.
+#[derive(Debug)]
+pub struct ActionHarness {
+    /// The scripted L1 chain.
+    l1: Arc<Mutex<ScriptedL1>>,
+    /// A counter mixed into L1 block hashes, so that reorged blocks get new hashes.
+    l1_nonce: u64,
+    /// Feeds L1 heads to the L1 watcher.
+    l1_head_tx: mpsc::Sender<BlockInfo>,
+    /// Feeds finalized L1 blocks to the L1 watcher.
+    _l1_finalized_tx: mpsc::Sender<BlockInfo>,
+    /// Queues the responses of the mocked L1 provider.
+    l1_responses: Asserter,
+    /// Sends queries to the L1 watcher.
+    l1_query_tx: mpsc::Sender<L1WatcherQueries>,
+    /// Keeps the block signer channel of the L1 watcher open.
+    _block_signer_rx: mpsc::Receiver<Address>,
+    /// The scripted engine.
+    pub engine: ScriptedEngine,
+    /// Receives the attributes the derivation actor archives.
+    archive_rx: mpsc::Receiver<Arc<OpAttributesWithParent>>,
+    /// The switch halting derivation.
+    derivation_halt: DerivationHaltSwitch,
+    /// The cancellation token shared by the actors.
+    cancellation: CancellationToken,
+    /// The L1 watcher task.
+    l1_watcher: JoinHandle<Result<(), L1WatcherActorError<BlockInfo>>>,
+    /// The derivation task.
+    derivation: JoinHandle<Result<(), DerivationError>>,
+}
+
+impl ActionHarness {
+    /// Starts the actors on a scripted L1 chain holding only a genesis block, and performs the
+    /// initial engine reset onto the L2 genesis.
+    pub async fn new() -> Self {
+        Self::with_archive_capacity(ARCHIVE_CAPACITY).await
+    }
+
+    /// Starts the actors like [`Self::new`], archiving the derived attributes over a channel of
+    /// the given capacity.
+    pub async fn with_archive_capacity(archive_capacity: usize) -> Self {
+        let attributes_channel = metered_channel("attributes", 16, ChannelAlarms::default());
+        Self::start(archive_capacity, attributes_channel).await
+    }
+
+    /// Starts the actors like [`Self::new`], pausing derivation with the given [`Watermarks`] on
+    /// the channel of derived attributes.
+    pub async fn with_watermarks(watermarks: Watermarks) -> Self {
+        let attributes_channel =
+            watermark_channel("attributes", watermarks, ChannelAlarms::default());
+        Self::start(ARCHIVE_CAPACITY, attributes_channel).await
+    }
+
+    /// Starts the actors, sending the derived attributes over the given channel.
+    async fn start(
+        archive_capacity: usize,
+        (derived_attributes_tx, attributes_rx): (
+            MeteredSender<DerivedAttributes>,
+            MeteredReceiver<DerivedAttributes>,
+        ),
+    ) -> Self {
+        let rollup_config = Arc::new(RollupConfig { block_time: 2, ..Default::default() });
+        let cancellation = CancellationToken::new();
+
+        let l1_genesis = BlockInfo::new(keccak256("l1_genesis"), 0, B256::ZERO, 0);
+        let l1 = Arc::new(Mutex::new(ScriptedL1::default()));
+        l1.lock().unwrap().blocks.insert(0, l1_genesis);
+
+        let pipeline = ScriptedPipeline::new(rollup_config.clone(), l1.clone());
+        let (derivation_inbound, derivation) = DerivationActor::new(pipeline);
+
+        let (l1_head_tx, l1_head_rx) = mpsc::channel(16);
+        let (l1_finalized_tx, l1_finalized_rx) = mpsc::channel(16);
+        let (l1_query_tx, l1_query_rx) = mpsc::channel(16);
+        let (block_signer_tx, block_signer_rx) = mpsc::channel(16);
+        let l1_responses = Asserter::new();
+        let l1_watcher = L1WatcherActor::new(
+            rollup_config,
+            RootProvider::new(RpcClient::new(MockTransport::new(l1_responses.clone()), false)),
+            l1_query_rx,
+            derivation_inbound.l1_head_updates_tx,
+            watch::channel(None).0,
+            block_signer_tx,
+            cancellation.clone(),
+            ReceiverStream::new(l1_head_rx),
+            ReceiverStream::new(l1_finalized_rx),
+        );
+
+        let (reset_request_tx, reset_request_rx) = mpsc::channel(16);
+        let (archive_tx, archive_rx) = mpsc::channel(archive_capacity);
+        let derivation_halt = DerivationHaltSwitch::default();
+        let context = DerivationContext {
+            cancellation: cancellation.clone(),
+            derived_attributes_tx,
+            reset_request_tx,
+            archive_tx: Some(archive_tx),
+            runtime_flags: Default::default(),
+            derivation_halt: derivation_halt.clone(),
+        };
+
+        let l2_genesis = L2BlockInfo::new(
+            BlockInfo::new(keccak256("l2_genesis"), 0, B256::ZERO, 0),
+            BlockNumHash { number: l1_genesis.number, hash: l1_genesis.hash },
+            0,
+        );
+        let mut engine = ScriptedEngine {
+            attributes_rx,
+            reset_request_rx,
+            safe_head_tx: derivation_inbound.engine_l2_safe_head_tx,
+            derivation_signal_tx: derivation_inbound.derivation_signal_tx,
+            el_sync_complete_tx: Some(derivation_inbound.el_sync_complete_tx),
+            safe_chain: vec![l2_genesis],
+        };
+
+        let l1_watcher = tokio::spawn(l1_watcher.start(()));
+        let derivation = tokio::spawn(derivation.start(context));
+        engine.complete_el_sync(l1_genesis).await;
+
+        Self {
+            l1,
+            l1_nonce: 0,
+            l1_head_tx,
+            _l1_finalized_tx: l1_finalized_tx,
+            l1_responses,
+            l1_query_tx,
+            _block_signer_rx: block_signer_rx,
+            engine,
+            archive_rx,
+            derivation_halt,
+            cancellation,
+            l1_watcher,
+            derivation,
+        }
+    }
+
+    /// Extends the canonical L1 chain with a block batching the given number of L2 blocks, and
+    /// announces it as the new L1 head.
+    pub async fn push_l1_block(&mut self, batches: u64) -> BlockInfo {
+        self.l1_nonce += 1;
+        let block = {
+            let mut l1 = self.l1.lock().unwrap();
+            let parent = l1.tip();
+            let block = BlockInfo::new(
+                keccak256([parent.hash.as_slice(), &self.l1_nonce.to_be_bytes()].concat()),
+                parent.number + 1,
+                parent.hash,
+                parent.timestamp + L1_BLOCK_TIME,
+            );
+            l1.blocks.insert(block.number, block);
+            l1.batches.insert(block.hash, batches);
+            block
+        };
+
+        // The L1 watcher fetches the system config logs of every new head.
+        self.l1_responses.push_success(&Vec::<Log>::new());
+        self.l1_head_tx.send(block).await.expect("L1 watcher stopped");
+        block
+    }
+
+    /// Sends a query to the L1 watcher, and waits for its answer.
+    pub async fn query_l1_watcher<T>(
+        &self,
+        query: impl FnOnce(oneshot::Sender<T>) -> L1WatcherQueries,
+    ) -> T {
+        let (tx, rx) = oneshot::channel();
+        self.l1_query_tx.send(query(tx)).await.expect("L1 watcher stopped");
+        rx.await.expect("L1 watcher dropped the query")
+    }
+
+    /// Queues a response of the mocked L1 provider.
+    pub fn push_l1_response<R: serde::Serialize>(&self, response: &R) {
+        self.l1_responses.push_success(response);
+    }
+
+    /// Queues an error response of the mocked L1 provider.
+    pub fn push_l1_error(&self, message: &str) {
+        self.l1_responses.push_failure_msg(message);
+    }
+
+    /// Scripts the next step of the derivation pipeline to fail with the given error. Scripted
+    /// failures are taken in order, before the pipeline derives from the L1 chain again.
+    pub fn push_step_failure(&self, err: PipelineErrorKind) {
+        self.l1.lock().unwrap().failures.push_back(err);
+    }
+
+    /// Removes the given number of blocks from the tip of the canonical L1 chain. The next
+    /// pushed block forks off the new tip.
+    pub fn reorg_l1(&mut self, depth: u64) {
+        let mut l1 = self.l1.lock().unwrap();
+        for _ in 0..depth {
+            let tip = l1.tip();
+            assert_ne!(tip.number, 0, "Cannot reorg the L1 genesis");
+            l1.blocks.remove(&tip.number);
+        }
+    }
+
+    /// Waits for the next derived attributes.
+    pub async fn next_attributes(&mut self) -> OpAttributesWithParent {
+        self.engine.next_attributes().await
+    }
+
+    /// Waits for the next derived attributes, and executes them with the given response.
+    pub async fn derive_and_execute(&mut self, response: EngineResponse) -> L2BlockInfo {
+        let attributes = self.engine.next_attributes().await;
+        self.engine.execute(&attributes, response).await
+    }
+
+    /// Returns the next archived attributes, if any were archived.
+    pub fn next_archived(&mut self) -> Option<Arc<OpAttributesWithParent>> {
+        self.archive_rx.try_recv().ok()
+    }
+
+    /// Asserts that no attributes are derived within the engine timeout.
+    pub async fn expect_stall(&mut self) {
+        self.engine.expect_no_attributes(ENGINE_TIMEOUT).await;
+    }
+
+    /// Serves the next reset request of the derivation actor, returning the new safe head.
+    pub async fn reset(&mut self) -> L2BlockInfo {
+        self.engine.reset(&self.l1).await
+    }
+
+    /// Returns the signals received by the derivation pipeline, in order.
+    pub fn signals(&self) -> Vec<Signal> {
+        self.l1.lock().unwrap().signals.clone()
+    }
+
+    /// Returns the switch halting derivation, as tripped by the engine and acknowledged through
+    /// the admin RPC.
+    pub const fn derivation_halt(&self) -> &DerivationHaltSwitch {
+        &self.derivation_halt
+    }
+
+    /// Waits for the derivation actor to stop on its own, returning its result, and stops the L1
+    /// watcher.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the derivation actor is still running after the [`ENGINE_TIMEOUT`].
+    pub async fn wait(mut self) -> Result<(), DerivationError> {
+        let result = tokio::time::timeout(ENGINE_TIMEOUT, &mut self.derivation)
+            .await
+            .expect("Derivation actor is still running")
+            .expect("Derivation actor panicked");
+        self.cancellation.cancel();
+        self.l1_watcher.await.unwrap().expect("L1 watcher failed");
+        result
+    }
+
+    /// Stops the actors, asserting that neither of them failed.
+    pub async fn shutdown(self) {
+        self.cancellation.cancel();
+        self.derivation.await.unwrap().expect("Derivation actor failed");
+        self.l1_watcher.await.unwrap().expect("L1 watcher failed");
+    }
+}
@@ -0,0 +1,176 @@
+//! A scripted derivation pipeline, reading from the L1 chain of the [`ActionHarness`].
+//!
+//! [`ActionHarness`]: super::ActionHarness
+
+use crate::{DerivationState, PipelineBuilder};
+use alloy_primitives::{B256, map::HashMap};
+use alloy_rpc_types_engine::PayloadAttributes;
+use async_trait::async_trait;
+use kona_derive::{
+    ActivationSignal, OriginProvider, Pipeline, PipelineError, PipelineErrorKind, PipelineResult,
+    ResetError, ResetSignal, Signal, SignalReceiver, StepResult,
+};
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// The scripted L1 chain, shared between the harness and the [`ScriptedPipeline`].
+#[derive(Debug, Default)]
+pub(crate) struct ScriptedL1 {
+    /// The canonical L1 blocks, keyed by number.
+    pub(crate) blocks: BTreeMap<u64, BlockInfo>,
+    /// The number of L2 blocks batched in each L1 block, keyed by hash.
+    pub(crate) batches: HashMap<B256, u64>,
+    /// The signals received by the pipeline, in order.
+    pub(crate) signals: Vec<Signal>,
+    /// The errors the next steps of the pipeline fail with, in order.
+    pub(crate) failures: VecDeque<PipelineErrorKind>,
+}
+
+impl ScriptedL1 {
+    /// Returns the canonical L1 tip.
+    pub(crate) fn tip(&self) -> BlockInfo {
+        self.blocks.last_key_value().map(|(_, b)| *b).expect("The scripted L1 chain has a genesis")
+    }
+
+    /// Returns `true` if the given block is part of the canonical L1 chain.
+    pub(crate) fn is_canonical(&self, number: u64, hash: B256) -> bool {
+        self.blocks.get(&number).is_some_and(|b| b.hash == hash)
+    }
+}
+
+/// A derivation pipeline that derives a scripted number of L2 blocks from each L1 block.
+///
+/// Like the real pipeline, it advances its origin one L1 block at a time, detects L1 reorgs when
+/// the next block doesn't build on its origin, and returns an EOF once it reaches the L1 tip.
+/// Scripted failures are returned before anything else.
+#[derive(Debug)]
+pub(crate) struct ScriptedPipeline {
+    /// The rollup config.
+    rollup_config: Arc<RollupConfig>,
+    /// The scripted L1 chain.
+    l1: Arc<Mutex<ScriptedL1>>,
+    /// The current L1 origin.
+    origin: Option<BlockInfo>,
+    /// The number of L2 blocks batched in the origin that are yet to be derived.
+    pending: u64,
+    /// The prepared attributes.
+    prepared: VecDeque<OpAttributesWithParent>,
+}
+
+impl ScriptedPipeline {
+    /// Creates a new [`ScriptedPipeline`] reading from the given L1 chain.
+    pub(crate) const fn new(rollup_config: Arc<RollupConfig>, l1: Arc<Mutex<ScriptedL1>>) -> Self {
+        Self { rollup_config, l1, origin: None, pending: 0, prepared: VecDeque::new() }
+    }
+
+    /// Derives attributes for the L2 block following the cursor.
+    fn derive(&self, cursor: L2BlockInfo, origin: BlockInfo) -> OpAttributesWithParent {
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: cursor.block_info.timestamp + self.rollup_config.block_time,
+                prev_randao: origin.hash,
+                suggested_fee_recipient: Default::default(),
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: None,
+            no_tx_pool: Some(true),
+            gas_limit: None,
+            eip_1559_params: None,
+            min_base_fee: None,
+        };
+        OpAttributesWithParent::new(attributes, cursor, Some(origin), self.pending == 0)
+    }
+}
+
+impl Iterator for ScriptedPipeline {
+    type Item = OpAttributesWithParent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.prepared.pop_front()
+    }
+}
+
+impl OriginProvider for ScriptedPipeline {
+    fn origin(&self) -> Option<BlockInfo> {
+        self.origin
+    }
+}
+
+#[async_trait]
+impl Pipeline for ScriptedPipeline {
+    fn peek(&self) -> Option<&OpAttributesWithParent> {
+        self.prepared.front()
+    }
+
+    async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+        let Some(origin) = self.origin else {
+            return StepResult::StepFailed(PipelineError::MissingOrigin.crit());
+        };
+
+        let mut l1 = self.l1.lock().unwrap();
+        if let Some(err) = l1.failures.pop_front() {
+            return StepResult::StepFailed(err);
+        }
+
+        if self.pending > 0 {
+            self.pending -= 1;
+            let attributes = self.derive(cursor, origin);
+            self.prepared.push_back(attributes);
+            return StepResult::PreparedAttributes;
+        }
+
+        let Some(next) = l1.blocks.get(&(origin.number + 1)).copied() else {
+            return StepResult::OriginAdvanceErr(PipelineError::Eof.temp());
+        };
+        if next.parent_hash != origin.hash {
+            return StepResult::OriginAdvanceErr(
+                ResetError::ReorgDetected(origin.hash, next.parent_hash).reset(),
+            );
+        }
+
+        self.pending = l1.batches.get(&next.hash).copied().unwrap_or_default();
+        self.origin = Some(next);
+        StepResult::AdvancedOrigin
+    }
+
+    fn rollup_config(&self) -> &RollupConfig {
+        &self.rollup_config
+    }
+
+    async fn system_config_by_number(&mut self, _: u64) -> Result<SystemConfig, PipelineErrorKind> {
+        Ok(SystemConfig::default())
+    }
+}
+
+#[async_trait]
+impl SignalReceiver for ScriptedPipeline {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+        match signal {
+            Signal::Reset(ResetSignal { l1_origin, .. }) |
+            Signal::Activation(ActivationSignal { l1_origin, .. }) => {
+                self.origin = Some(l1_origin);
+                self.pending = 0;
+                self.prepared.clear();
+            }
+            Signal::FlushChannel | Signal::FlushChannelById(_) => self.pending = 0,
+            Signal::ProvideBlock(_) => {}
+        }
+        self.l1.lock().unwrap().signals.push(signal);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PipelineBuilder for ScriptedPipeline {
+    type Pipeline = Self;
+
+    async fn build(self) -> DerivationState<Self> {
+        DerivationState::new(self)
+    }
+}
@@ -0,0 +1,244 @@
+//! Action tests for the L1 watcher and derivation actors.
+
+use super::{ActionHarness, ENGINE_TIMEOUT, EngineResponse};
+use crate::{DerivationError, Watermarks};
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::TransactionReceipt;
+use kona_derive::{PipelineError, ResetError, Signal};
+use kona_rpc::{BlobAvailability, DerivationHalt, DerivationHaltReason, L1WatcherQueries};
+
+#[tokio::test(start_paused = true)]
+async fn test_derives_attributes_from_new_l1_heads() {
+    let mut harness = ActionHarness::new().await;
+    let genesis = harness.engine.safe_head();
+
+    let l1_block = harness.push_l1_block(2).await;
+    let first = harness.next_attributes().await;
+    assert_eq!(first.parent, genesis);
+    assert_eq!(first.derived_from, Some(l1_block));
+    assert!(!first.is_last_in_span);
+
+    let safe_head = harness.engine.execute(&first, EngineResponse::Valid).await;
+    let second = harness.next_attributes().await;
+    assert_eq!(second.parent, safe_head);
+    assert_eq!(second.derived_from, Some(l1_block));
+    assert!(second.is_last_in_span);
+
+    harness.engine.execute(&second, EngineResponse::Valid).await;
+    harness.expect_stall().await;
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_derivation_stalls_until_l1_extends() {
+    let mut harness = ActionHarness::new().await;
+    harness.expect_stall().await;
+
+    let l1_block = harness.push_l1_block(0).await;
+    harness.expect_stall().await;
+
+    harness.push_l1_block(1).await;
+    let attributes = harness.next_attributes().await;
+    assert_eq!(attributes.parent, harness.engine.safe_head());
+    assert_ne!(attributes.derived_from, Some(l1_block));
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_derivation_stalls_until_engine_executes() {
+    let mut harness = ActionHarness::new().await;
+
+    harness.push_l1_block(2).await;
+    let first = harness.next_attributes().await;
+    harness.expect_stall().await;
+
+    // New L1 data doesn't unblock derivation while the engine is still executing.
+    harness.push_l1_block(1).await;
+    harness.expect_stall().await;
+
+    let safe_head = harness.engine.execute(&first, EngineResponse::Valid).await;
+    let second = harness.next_attributes().await;
+    assert_eq!(second.parent, safe_head);
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_derived_attributes_are_archived() {
+    let mut harness = ActionHarness::new().await;
+
+    harness.push_l1_block(2).await;
+    let first = harness.next_attributes().await;
+    harness.engine.execute(&first, EngineResponse::Valid).await;
+    let second = harness.next_attributes().await;
+
+    assert_eq!(harness.next_archived().as_deref(), Some(&first));
+    assert_eq!(harness.next_archived().as_deref(), Some(&second));
+    assert_eq!(harness.next_archived(), None);
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_derivation_waits_for_the_archive() {
+    let mut harness = ActionHarness::with_archive_capacity(1).await;
+
+    harness.push_l1_block(2).await;
+    let first = harness.next_attributes().await;
+    let safe_head = harness.engine.execute(&first, EngineResponse::Valid).await;
+
+    // The archive is full, so the next attributes are held back rather than skipped.
+    harness.expect_stall().await;
+    assert_eq!(harness.next_archived().as_deref(), Some(&first));
+
+    let second = harness.next_attributes().await;
+    assert_eq!(second.parent, safe_head);
+    assert_eq!(harness.next_archived().as_deref(), Some(&second));
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_invalid_payload_flushes_channel() {
+    let mut harness = ActionHarness::new().await;
+
+    harness.push_l1_block(3).await;
+    let attributes = harness.next_attributes().await;
+    let deposits_only = harness.engine.execute(&attributes, EngineResponse::Invalid).await;
+
+    // The rest of the channel is dropped.
+    harness.expect_stall().await;
+    assert_eq!(harness.signals().last(), Some(&Signal::FlushChannel));
+
+    let l1_block = harness.push_l1_block(1).await;
+    let attributes = harness.next_attributes().await;
+    assert_eq!(attributes.parent, deposits_only);
+    assert_eq!(attributes.derived_from, Some(l1_block));
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_l1_reorg_resets_to_canonical_safe_head() {
+    let mut harness = ActionHarness::new().await;
+
+    harness.push_l1_block(1).await;
+    let kept = harness.derive_and_execute(EngineResponse::Valid).await;
+    harness.push_l1_block(1).await;
+    harness.derive_and_execute(EngineResponse::Valid).await;
+    harness.expect_stall().await;
+
+    // Replace the last L1 block. The reorg is detected once the pipeline looks past its origin.
+    harness.reorg_l1(1);
+    let forked = harness.push_l1_block(1).await;
+    harness.push_l1_block(1).await;
+
+    let safe_head = harness.reset().await;
+    assert_eq!(safe_head, kept);
+    assert!(matches!(
+        harness.signals().last(),
+        Some(Signal::Reset(reset)) if reset.l2_safe_head == kept
+    ));
+
+    let attributes = harness.next_attributes().await;
+    assert_eq!(attributes.parent, kept);
+    assert_eq!(attributes.derived_from, Some(forked));
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_l1_watcher_answers_data_availability_queries() {
+    let harness = ActionHarness::new().await;
+
+    harness.push_l1_response(&Vec::<TransactionReceipt>::new());
+    let receipts = harness.query_l1_watcher(|tx| L1WatcherQueries::Receipts(B256::ZERO, tx)).await;
+    assert_eq!(receipts, Some(vec![]));
+
+    harness.push_l1_error("unavailable");
+    let receipts = harness.query_l1_watcher(|tx| L1WatcherQueries::Receipts(B256::ZERO, tx)).await;
+    assert_eq!(receipts, None);
+
+    // The harness has no beacon node.
+    let availability = harness.query_l1_watcher(|tx| L1WatcherQueries::BlobsAvailable(1, tx)).await;
+    assert_eq!(availability, BlobAvailability::Unknown);
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_derivation_retries_temporary_errors() {
+    let mut harness = ActionHarness::new().await;
+    let genesis = harness.engine.safe_head();
+    harness.push_step_failure(PipelineError::NotEnoughData.temp());
+    harness.push_step_failure(PipelineError::NotEnoughData.temp());
+
+    harness.push_l1_block(1).await;
+    let attributes = harness.next_attributes().await;
+    assert_eq!(attributes.parent, genesis);
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reset_error_requests_reset() {
+    let mut harness = ActionHarness::new().await;
+    let genesis = harness.engine.safe_head();
+    harness.push_step_failure(ResetError::HoloceneActivation.reset());
+
+    // The pipeline isn't stepped again until the engine resets it.
+    let l1_block = harness.push_l1_block(1).await;
+    harness.expect_stall().await;
+
+    assert_eq!(harness.reset().await, genesis);
+    let attributes = harness.next_attributes().await;
+    assert_eq!(attributes.parent, genesis);
+    assert_eq!(attributes.derived_from, Some(l1_block));
+    assert_eq!(harness.signals().iter().filter(|s| matches!(s, Signal::Reset(_))).count(), 2);
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_critical_error_stops_derivation() {
+    let harness = ActionHarness::new().await;
+    harness.push_step_failure(PipelineError::MissingOrigin.crit());
+
+    harness.push_l1_block(1).await;
+    let err = harness.wait().await.unwrap_err();
+    assert!(matches!(err, DerivationError::Pipeline(_)), "{err:?}");
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_derivation_pauses_until_halt_acknowledged() {
+    let mut harness = ActionHarness::new().await;
+    let genesis = harness.engine.safe_head();
+    harness.derivation_halt().halt(DerivationHalt {
+        reason: DerivationHaltReason::InvalidPayloadStreak { count: 3 },
+        block_number: 1,
+        halted_at: 0,
+    });
+
+    harness.push_l1_block(1).await;
+    harness.expect_stall().await;
+
+    // Acknowledging the halt resumes derivation with the pending L1 head.
+    harness.derivation_halt().acknowledge();
+    assert_eq!(harness.next_attributes().await.parent, genesis);
+    harness.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_derivation_pauses_while_engine_falls_behind() {
+    let mut harness = ActionHarness::with_watermarks(Watermarks { high: 2, low: 1 }).await;
+    harness.push_l1_block(3).await;
+
+    // The engine executes two attributes without taking them off the channel, which reaches the
+    // high watermark.
+    tokio::time::sleep(ENGINE_TIMEOUT).await;
+    let first = harness.next_archived().expect("First attributes were derived");
+    harness.engine.execute(&first, EngineResponse::Valid).await;
+    tokio::time::sleep(ENGINE_TIMEOUT).await;
+    let second = harness.next_archived().expect("Second attributes were derived");
+    harness.engine.execute(&second, EngineResponse::Valid).await;
+    tokio::time::sleep(ENGINE_TIMEOUT).await;
+    assert_eq!(harness.next_archived(), None);
+
+    // Draining the channel to the low watermark resumes derivation.
+    assert_eq!(harness.next_attributes().await, *first);
+    assert_eq!(harness.next_attributes().await, *second);
+    assert_eq!(harness.next_attributes().await.parent, harness.engine.safe_head());
+    harness.shutdown().await;
+}
@@ -7,12 +7,11 @@ use discv5::{ConfigBuilder, Enr, ListenConfig};
 use alloy_primitives::Address;
 use kona_disc::LocalNode;
 use kona_genesis::RollupConfig;
-use kona_node_service::{NetworkActor, NetworkBuilder, NetworkContext, NodeActor};
+use kona_node_service::{NetworkActor, NetworkBuilder, NetworkContext, NodeActor, metered_channel};
 use kona_peers::BootNode;
 use kona_sources::BlockSigner;
 use libp2p::{Multiaddr, identity::Keypair, multiaddr::Protocol};
 use rand::RngCore;
-use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 use crate::actors::network::TestNetwork;
@@ -95,10 +94,11 @@ impl TestNetworkBuilder {
 
         let (inbound_data, actor) = NetworkActor::new(builder);
 
-        let (blocks_tx, blocks_rx) = mpsc::channel(1024);
+        let (blocks_tx, blocks_rx) = metered_channel("unsafe_blocks", 1024, Default::default());
         let cancellation = CancellationToken::new();
 
-        let context = NetworkContext { blocks: blocks_tx, cancellation };
+        let context =
+            NetworkContext { blocks: blocks_tx, runtime_flags: Default::default(), cancellation };
 
         let handle = tokio::spawn(async move { actor.start(context).await });
 
@@ -5,18 +5,15 @@ use std::{str::FromStr, time::Duration};
 use backon::{ExponentialBuilder, Retryable};
 use discv5::Enr;
 use kona_gossip::{P2pRpcRequest, PeerDump, PeerInfo};
-use kona_node_service::{NetworkActorError, NetworkInboundData};
+use kona_node_service::{MeteredReceiver, NetworkActorError, NetworkInboundData};
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
-use tokio::{
-    sync::{mpsc, oneshot},
-    task::JoinHandle,
-};
+use tokio::{sync::oneshot, task::JoinHandle};
 
 pub(crate) mod builder;
 
 pub(crate) struct TestNetwork {
     pub(super) inbound_data: NetworkInboundData,
-    pub(super) blocks_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
+    pub(super) blocks_rx: MeteredReceiver<OpExecutionPayloadEnvelope>,
     #[allow(dead_code)]
     handle: JoinHandle<Result<(), NetworkActorError>>,
 }
@@ -42,7 +39,7 @@ impl TestNetwork {
         let peer_info_request = P2pRpcRequest::PeerInfo(peer_info_tx);
         self.inbound_data
             .p2p_rpc
-            .send(peer_info_request)
+            .send(peer_info_request.into())
             .await
             .map_err(|_| TestNetworkError::P2pReceiverClosed)?;
 
@@ -56,7 +53,7 @@ impl TestNetwork {
         let peers_request = P2pRpcRequest::Peers { out: peers_tx, connected: true };
         self.inbound_data
             .p2p_rpc
-            .send(peers_request)
+            .send(peers_request.into())
             .await
             .map_err(|_| TestNetworkError::P2pReceiverClosed)?;
         let peers = peers_rx.await?;
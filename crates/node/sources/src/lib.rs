@@ -9,6 +9,7 @@
 
 mod signer;
 pub use signer::{
-    BlockSigner, BlockSignerError, BlockSignerHandler, BlockSignerStartError, CertificateError,
-    ClientCert, RemoteSigner, RemoteSignerError, RemoteSignerHandler, RemoteSignerStartError,
+    BlockSigner, BlockSignerError, BlockSignerHandler, BlockSignerStartError, BlockSigning,
+    CertificateError, ClientCert, RemoteSigner, RemoteSignerError, RemoteSignerHandler,
+    RemoteSignerStartError, Web3Signer, Web3SignerHandler,
 };
@@ -1,12 +1,17 @@
 //! Signer utilities for the Kona node.
 //!
-//! We currently support two types of block signers:
+//! We currently support three types of block signers:
 //!
 //! 1. A local block signer that is used to sign blocks with a locally available private key.
-//! 2. A remote block signer that is used to sign blocks with a remote private key.
+//! 2. A remote block signer that is used to sign blocks with an op-signer server.
+//! 3. A remote block signer that is used to sign blocks with a web3signer server.
+//!
+//! All of them implement the [`BlockSigning`] trait.
 
 use alloy_primitives::{Address, ChainId};
 use alloy_signer::{Signature, SignerSync};
+use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
 use derive_more::From;
 use op_alloy_rpc_types_engine::PayloadHash;
 use std::fmt::Debug;
@@ -14,25 +19,45 @@ use std::fmt::Debug;
 mod remote;
 pub use remote::{
     CertificateError, ClientCert, RemoteSigner, RemoteSignerError, RemoteSignerHandler,
-    RemoteSignerStartError,
+    RemoteSignerStartError, Web3Signer, Web3SignerHandler,
 };
 
+/// A started signer of unsafe block payloads, independent of where its key is held.
+#[async_trait]
+pub trait BlockSigning: Debug + Send + Sync {
+    /// Returns the address of the signer.
+    fn address(&self) -> Address;
+
+    /// Signs the hash of a block payload for the given chain. `sender_address` is the expected
+    /// address of the signer.
+    async fn sign_block(
+        &self,
+        payload_hash: PayloadHash,
+        chain_id: ChainId,
+        sender_address: Address,
+    ) -> Result<Signature, BlockSignerError>;
+}
+
 /// A builder for a block signer.
 #[derive(Debug, Clone, From)]
 pub enum BlockSigner {
     /// A local block signer that is used to sign blocks with a locally available private key.
-    Local(#[from] alloy_signer_local::PrivateKeySigner),
+    Local(#[from] PrivateKeySigner),
     /// A remote block signer that is used to sign blocks with a remote private key.
     Remote(#[from] RemoteSigner),
+    /// A remote block signer that is used to sign blocks with a web3signer private key.
+    Web3Signer(#[from] Web3Signer),
 }
 
 /// A handler for a block signer.
 #[derive(Debug)]
 pub enum BlockSignerHandler {
     /// A local block signer that is used to sign blocks with a locally available private key.
-    Local(alloy_signer_local::PrivateKeySigner),
+    Local(PrivateKeySigner),
     /// A remote block signer that is used to sign blocks with a remote private key.
     Remote(RemoteSignerHandler),
+    /// A remote block signer that is used to sign blocks with a web3signer private key.
+    Web3Signer(Web3SignerHandler),
 }
 
 /// Errors that can occur when starting a block signer.
@@ -63,11 +88,21 @@ impl BlockSigner {
         match self {
             Self::Local(signer) => Ok(BlockSignerHandler::Local(signer)),
             Self::Remote(signer) => Ok(BlockSignerHandler::Remote(signer.start().await?)),
+            Self::Web3Signer(signer) => Ok(BlockSignerHandler::Web3Signer(signer.start().await?)),
         }
     }
 }
 
 impl BlockSignerHandler {
+    /// Returns the started signer.
+    pub fn signer(&self) -> &dyn BlockSigning {
+        match self {
+            Self::Local(signer) => signer,
+            Self::Remote(signer) => signer,
+            Self::Web3Signer(signer) => signer,
+        }
+    }
+
     /// Signs a payload with the signer.
     pub async fn sign_block(
         &self,
@@ -75,15 +110,54 @@ impl BlockSignerHandler {
         chain_id: ChainId,
         sender_address: Address,
     ) -> Result<Signature, BlockSignerError> {
-        let signature = match self {
-            Self::Local(signer) => {
-                signer.sign_hash_sync(&payload_hash.signature_message(chain_id))?
-            }
-            Self::Remote(signer) => {
-                signer.sign_block_v1(payload_hash, chain_id, sender_address).await?
-            }
-        };
-
-        Ok(signature)
+        self.signer().sign_block(payload_hash, chain_id, sender_address).await
+    }
+}
+
+#[async_trait]
+impl BlockSigning for PrivateKeySigner {
+    fn address(&self) -> Address {
+        alloy_signer::Signer::address(self)
+    }
+
+    async fn sign_block(
+        &self,
+        payload_hash: PayloadHash,
+        chain_id: ChainId,
+        _: Address,
+    ) -> Result<Signature, BlockSignerError> {
+        Ok(self.sign_hash_sync(&payload_hash.signature_message(chain_id))?)
+    }
+}
+
+#[async_trait]
+impl BlockSigning for RemoteSignerHandler {
+    fn address(&self) -> Address {
+        Self::address(self)
+    }
+
+    async fn sign_block(
+        &self,
+        payload_hash: PayloadHash,
+        chain_id: ChainId,
+        sender_address: Address,
+    ) -> Result<Signature, BlockSignerError> {
+        Ok(self.sign_block_v1(payload_hash, chain_id, sender_address).await?)
+    }
+}
+
+#[async_trait]
+impl BlockSigning for Web3SignerHandler {
+    fn address(&self) -> Address {
+        Self::address(self)
+    }
+
+    async fn sign_block(
+        &self,
+        payload_hash: PayloadHash,
+        chain_id: ChainId,
+        sender_address: Address,
+    ) -> Result<Signature, BlockSignerError> {
+        Ok(self.sign_block_v1(payload_hash, chain_id, sender_address).await?)
     }
 }
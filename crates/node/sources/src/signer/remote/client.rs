@@ -47,6 +47,15 @@ pub enum RemoteSignerStartError {
     /// Failed to ping signer
     #[error("Failed to ping signer: {0}")]
     Ping(alloy_transport::TransportError),
+    /// Invalid signer endpoint
+    #[error("Invalid signer endpoint: {0}")]
+    InvalidEndpoint(#[from] url::ParseError),
+    /// Failed to check the status of a web3signer
+    #[error("Failed to check the web3signer status: {0}")]
+    Upcheck(reqwest::Error),
+    /// The signer address is not among the keys of the web3signer
+    #[error("The web3signer does not hold a key for {0}")]
+    UnknownKey(Address),
     /// HTTP client build error
     #[error("HTTP client build error: {0}")]
     HTTPClientBuild(#[from] reqwest::Error),
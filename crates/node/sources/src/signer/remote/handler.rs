@@ -57,6 +57,12 @@ pub enum RemoteSignerError {
     /// Signature error
     #[error("Signature error: {0}")]
     SignatureError(#[from] SignatureError),
+    /// Invalid signer endpoint
+    #[error("Invalid signer endpoint: {0}")]
+    InvalidEndpoint(#[from] url::ParseError),
+    /// HTTP request error
+    #[error("HTTP request error: {0}")]
+    HttpError(#[from] reqwest::Error),
     /// Invalid address
     #[error(
         "Unsafe block signer address does not match remote signer address: {unsafe_block_signer} != {remote_signer}"
@@ -70,6 +76,11 @@ pub enum RemoteSignerError {
 }
 
 impl RemoteSignerHandler {
+    /// Returns the address of the signer.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
     /// Returns true if certificate watching is enabled
     pub const fn is_certificate_watching_enabled(&self) -> bool {
         self.watcher_handle.is_some()
@@ -107,18 +118,18 @@ impl RemoteSignerHandler {
                 .map_err(RemoteSignerError::SigningRPCError)?
         };
 
-        // Parse the hex signature
-        let signature_bytes =
-            alloy_primitives::hex::decode(response.signature.trim_start_matches("0x"))
-                .map_err(RemoteSignerError::InvalidSignatureHex)?;
-
-        if signature_bytes.len() != 65 {
-            return Err(RemoteSignerError::InvalidSignatureLength(signature_bytes.len()));
-        }
+        parse_signature(&response.signature)
+    }
+}
 
-        let signature = Signature::from_raw(signature_bytes.as_slice())
-            .map_err(RemoteSignerError::SignatureError)?;
+/// Parses a hex encoded 65 byte signature returned by a remote signer.
+pub(super) fn parse_signature(signature: &str) -> Result<Signature, RemoteSignerError> {
+    let signature_bytes = alloy_primitives::hex::decode(signature.trim().trim_start_matches("0x"))
+        .map_err(RemoteSignerError::InvalidSignatureHex)?;
 
-        Ok(signature)
+    if signature_bytes.len() != 65 {
+        return Err(RemoteSignerError::InvalidSignatureLength(signature_bytes.len()));
     }
+
+    Signature::from_raw(signature_bytes.as_slice()).map_err(RemoteSignerError::SignatureError)
 }
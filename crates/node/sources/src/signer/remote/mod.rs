@@ -5,3 +5,6 @@ pub use client::{RemoteSigner, RemoteSignerStartError};
 
 mod handler;
 pub use handler::{RemoteSignerError, RemoteSignerHandler};
+
+mod web3signer;
+pub use web3signer::{Web3Signer, Web3SignerHandler};
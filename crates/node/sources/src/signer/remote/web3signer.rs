@@ -0,0 +1,160 @@
+use alloy_primitives::{Address, ChainId, hex};
+use alloy_signer::Signature;
+use op_alloy_rpc_types_engine::PayloadHash;
+use serde_json::json;
+use url::Url;
+
+use crate::{
+    RemoteSigner, RemoteSignerError, RemoteSignerStartError,
+    signer::remote::handler::parse_signature,
+};
+
+/// A remote signer backed by the [web3signer] HTTP API.
+///
+/// Web3signer holds the keys and signs the `keccak256` hash of the data it is sent, which lets
+/// it sign unsafe block payloads. The endpoint, headers and TLS configuration are the same as
+/// for the op-signer [`RemoteSigner`]. Certificates are loaded when the signer is started, and
+/// are not watched for changes.
+///
+/// [web3signer]: https://docs.web3signer.consensys.io/reference/api/rest
+#[derive(Debug, Clone)]
+pub struct Web3Signer(pub RemoteSigner);
+
+impl Web3Signer {
+    /// Starts the web3signer client, checking that the server is up and holds the key of the
+    /// configured address.
+    pub async fn start(self) -> Result<Web3SignerHandler, RemoteSignerStartError> {
+        let client = self.0.build_http_client()?;
+        let endpoint = base_url(self.0.endpoint);
+
+        client
+            .get(endpoint.join("upcheck")?)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(RemoteSignerStartError::Upcheck)?;
+
+        // Web3signer identifies secp256k1 keys by their public key.
+        let public_keys: Vec<String> = client
+            .get(endpoint.join("api/v1/eth1/publicKeys")?)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(RemoteSignerStartError::Upcheck)?
+            .json()
+            .await
+            .map_err(RemoteSignerStartError::Upcheck)?;
+        let public_key = public_keys
+            .into_iter()
+            .find(|key| public_key_address(key) == Some(self.0.address))
+            .ok_or(RemoteSignerStartError::UnknownKey(self.0.address))?;
+
+        tracing::info!(target: "signer", %endpoint, address = %self.0.address, "Connected to web3signer");
+
+        Ok(Web3SignerHandler { client, endpoint, address: self.0.address, public_key })
+    }
+}
+
+/// A handle to a started [`Web3Signer`].
+#[derive(Debug)]
+pub struct Web3SignerHandler {
+    /// The HTTP client.
+    client: reqwest::Client,
+    /// The web3signer endpoint.
+    endpoint: Url,
+    /// The address of the signer.
+    address: Address,
+    /// The public key identifying the signer key on the web3signer.
+    public_key: String,
+}
+
+impl Web3SignerHandler {
+    /// Returns the address of the signer.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signs a block payload hash. Web3signer hashes the signing preimage, which yields the same
+    /// signature as signing [`PayloadHash::signature_message`].
+    pub async fn sign_block_v1(
+        &self,
+        payload_hash: PayloadHash,
+        chain_id: ChainId,
+        sender_address: Address,
+    ) -> Result<Signature, RemoteSignerError> {
+        if sender_address != self.address {
+            return Err(RemoteSignerError::InvalidAddress {
+                unsafe_block_signer: sender_address,
+                remote_signer: self.address,
+            });
+        }
+
+        // The v1 domain is zero, followed by the chain ID and the payload hash.
+        let mut preimage = [0u8; 96];
+        preimage[56..64].copy_from_slice(&chain_id.to_be_bytes());
+        preimage[64..].copy_from_slice(payload_hash.0.as_slice());
+
+        self.sign(&preimage).await
+    }
+
+    /// Signs the `keccak256` hash of `data`.
+    pub async fn sign(&self, data: &[u8]) -> Result<Signature, RemoteSignerError> {
+        let url = self.endpoint.join(&format!("api/v1/eth1/sign/{}", self.public_key))?;
+        let signature = self
+            .client
+            .post(url)
+            .json(&json!({ "data": hex::encode_prefixed(data) }))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        parse_signature(&signature)
+    }
+}
+
+/// Appends a trailing slash to the path of `url`, so that joining a relative path to it keeps its
+/// last path segment.
+fn base_url(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url
+}
+
+/// Returns the address of a hex encoded secp256k1 public key, if it is valid.
+///
+/// Both raw 64 byte keys and uncompressed 65 byte keys are accepted.
+fn public_key_address(public_key: &str) -> Option<Address> {
+    let bytes = hex::decode(public_key).ok()?;
+    match bytes.len() {
+        64 => Some(Address::from_raw_public_key(&bytes)),
+        65 if bytes[0] == 0x04 => Some(Address::from_raw_public_key(&bytes[1..])),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_keeps_last_segment() {
+        let endpoint = base_url("http://localhost:9000/web3signer".parse().unwrap());
+        assert_eq!(
+            endpoint.join("upcheck").unwrap().as_str(),
+            "http://localhost:9000/web3signer/upcheck"
+        );
+
+        let endpoint = base_url("http://localhost:9000/web3signer/".parse().unwrap());
+        assert_eq!(
+            endpoint.join("upcheck").unwrap().as_str(),
+            "http://localhost:9000/web3signer/upcheck"
+        );
+
+        let endpoint = base_url("http://localhost:9000".parse().unwrap());
+        assert_eq!(endpoint.join("upcheck").unwrap().as_str(), "http://localhost:9000/upcheck");
+    }
+}
@@ -1,11 +1,13 @@
 //! The driver of the kona derivation pipeline.
 
-use crate::{DriverError, DriverPipeline, DriverResult, Executor, PipelineCursor, TipCursor};
+use crate::{
+    DriverError, DriverPipeline, DriverResult, Executor, OutputTrace, PipelineCursor, TipCursor,
+};
 use alloc::{sync::Arc, vec::Vec};
 use alloy_consensus::BlockBody;
 use alloy_primitives::{B256, Bytes};
 use alloy_rlp::Decodable;
-use core::fmt::Debug;
+use core::{fmt::Debug, num::NonZeroU64};
 use kona_derive::{Pipeline, PipelineError, PipelineErrorKind, Signal, SignalReceiver};
 use kona_executor::BlockBuildingOutcome;
 use kona_genesis::RollupConfig;
@@ -74,6 +76,11 @@ where
     /// from the last successfully executed block. It's used for efficiency and
     /// debugging purposes. `None` when no block has been executed yet.
     pub safe_head_artifacts: Option<(BlockBuildingOutcome, Vec<Bytes>)>,
+    /// The output roots of the derived blocks, recorded at a fixed block interval.
+    ///
+    /// `None` unless enabled with [`Self::with_output_trace`]. Used to source the trace of
+    /// dispute game bisection from the derivation.
+    pub output_trace: Option<OutputTrace>,
 }
 
 impl<E, DP, P> Driver<E, DP, P>
@@ -108,9 +115,27 @@ where
             executor,
             pipeline,
             safe_head_artifacts: None,
+            output_trace: None,
         }
     }
 
+    /// Records the output roots of the derived blocks into an [`OutputTrace`], every `interval`
+    /// blocks past the current L2 safe head.
+    ///
+    /// The trace is available in [`Self::output_trace`] as derivation advances.
+    ///
+    /// # Usage
+    /// ```rust,ignore
+    /// let mut driver = Driver::new(cursor, executor, pipeline).with_output_trace(interval);
+    /// driver.advance_to_target(&config, Some(target_block)).await?;
+    /// let trace = driver.output_trace.take();
+    /// ```
+    pub fn with_output_trace(mut self, interval: NonZeroU64) -> Self {
+        let start = self.cursor.read().l2_safe_head().block_info.number;
+        self.output_trace = Some(OutputTrace::new(start, interval));
+        self
+    }
+
     /// Waits until the executor is ready for block processing.
     ///
     /// This method blocks until the underlying executor has completed any necessary
@@ -159,6 +184,7 @@ where
     /// - **Other Critical**: Fatal pipeline errors that stop derivation
     ///
     /// ## Execution Errors  
+    /// - **Fatal**: Errors for which [`Executor::is_fatal`] holds are returned immediately
     /// - **Pre-Holocene**: Block execution failures cause block to be discarded
     /// - **Holocene+**: Failed blocks are retried as deposit-only blocks
     ///   - Strips non-deposit transactions and flushes invalidated channel
@@ -191,6 +217,7 @@ where
     /// - Executor safe head for next block building
     /// - Cached artifacts for the most recent block
     /// - Output root computation for verification
+    /// - The output trace, if enabled with [`Self::with_output_trace`]
     ///
     /// # Usage Pattern
     /// ```rust,ignore
@@ -226,9 +253,12 @@ where
                 }
             }
 
-            let mut attributes = match self.pipeline.produce_payload(tip_cursor.l2_safe_head).await
+            let (channel_id, mut attributes) = match self
+                .pipeline
+                .produce_payload(tip_cursor.l2_safe_head)
+                .await
             {
-                Ok(attrs) => attrs.take_inner(),
+                Ok(attrs) => (attrs.channel_id(), attrs.take_inner()),
                 Err(PipelineErrorKind::Critical(PipelineError::EndOfSource)) => {
                     warn!(target: "client", "Exhausted data source; Halting derivation and using current safe head.");
 
@@ -255,6 +285,10 @@ where
             self.executor.update_safe_head(tip_cursor.l2_safe_head_header.clone());
             let outcome = match self.executor.execute_payload(attributes.clone()).await {
                 Ok(outcome) => outcome,
+                Err(e) if E::is_fatal(&e) => {
+                    error!(target: "client", "Critical - Failed to execute L2 block: {e}");
+                    return Err(DriverError::Executor(e));
+                }
                 Err(e) => {
                     error!(target: "client", "Failed to execute L2 block: {}", e);
 
@@ -265,8 +299,10 @@ where
                         // Flush the current batch and channel - if a block was replaced with a
                         // deposit-only block due to execution failure, the
                         // batch and channel it is contained in is forwards
-                        // invalidated.
-                        self.pipeline.signal(Signal::FlushChannel).await?;
+                        // invalidated. Only that channel is flushed if it is known.
+                        let signal =
+                            channel_id.map_or(Signal::FlushChannel, Signal::FlushChannelById);
+                        self.pipeline.signal(signal).await?;
 
                         // Strip out all transactions that are not deposits.
                         attributes.transactions = attributes.transactions.map(|txs| {
@@ -322,6 +358,15 @@ where
                 self.executor.compute_output_root().map_err(DriverError::Executor)?,
             );
 
+            // Record the output root of the block if it falls on the output trace.
+            if let Some(trace) = self.output_trace.as_mut() {
+                trace.record(
+                    tip_cursor.l2_safe_head.block_info.number,
+                    tip_cursor.l2_safe_head.block_info.hash,
+                    tip_cursor.l2_safe_head_output_root,
+                );
+            }
+
             // Advance the derivation pipeline cursor
             drop(pipeline_cursor);
             self.cursor.write().advance(origin, tip_cursor);
@@ -102,4 +102,15 @@ pub trait Executor {
     /// via [`Self::execute_payload`]. The computed root corresponds to the state
     /// after the most recent block execution.
     fn compute_output_root(&mut self) -> Result<B256, Self::Error>;
+
+    /// Returns whether the given execution error is fatal.
+    ///
+    /// Fatal errors, such as failed allocations, are not caused by the payload itself. The
+    /// driver returns them to the caller rather than treating the payload as invalid, which
+    /// would replace the block with a deposit-only block post-Holocene.
+    ///
+    /// By default, no execution error is fatal.
+    fn is_fatal(_error: &Self::Error) -> bool {
+        false
+    }
 }
@@ -29,3 +29,6 @@ pub use cursor::PipelineCursor;
 
 mod tip;
 pub use tip::TipCursor;
+
+mod trace;
+pub use trace::{OutputCommitment, OutputTrace};
@@ -0,0 +1,128 @@
+//! Contains the output trace recorded by the derivation driver.
+//!
+//! This module provides the [`OutputTrace`], which records the output roots of the L2 blocks
+//! derived by the [`Driver`] at a fixed block interval, for use by dispute game bisection.
+//!
+//! [`Driver`]: crate::Driver
+
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use core::num::NonZeroU64;
+
+/// An output root committed to at an L2 block during derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputCommitment {
+    /// The number of the L2 block.
+    pub l2_block_number: u64,
+    /// The hash of the L2 block.
+    pub l2_block_hash: B256,
+    /// The output root of the L2 block.
+    pub output_root: B256,
+}
+
+/// The output roots of the L2 blocks derived past a starting block, recorded at a fixed block
+/// interval.
+///
+/// The trace is indexed the way output bisection games index their traces: the commitment at
+/// trace position `i` is the output root of the L2 block `start + (i + 1) * interval`, where
+/// `start` is the agreed upon L2 block the derivation started from.
+///
+/// # Usage
+/// ```rust,ignore
+/// let mut driver = Driver::new(cursor, executor, pipeline).with_output_trace(interval);
+/// driver.advance_to_target(&rollup_config, Some(target)).await?;
+/// let trace = driver.output_trace.take().unwrap_or_default();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputTrace {
+    /// The number of the L2 block the trace starts from.
+    start: u64,
+    /// The number of L2 blocks between the commitments of the trace.
+    interval: u64,
+    /// The commitments of the trace, in order.
+    commitments: Vec<OutputCommitment>,
+}
+
+impl OutputTrace {
+    /// Creates a new, empty [`OutputTrace`] starting from the given L2 block and recording a
+    /// commitment every `interval` blocks past it.
+    pub const fn new(start: u64, interval: NonZeroU64) -> Self {
+        Self { start, interval: interval.get(), commitments: Vec::new() }
+    }
+
+    /// Returns the number of the L2 block the trace starts from.
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the number of L2 blocks between the commitments of the trace.
+    pub const fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Returns the commitments of the trace, in order.
+    pub fn commitments(&self) -> &[OutputCommitment] {
+        &self.commitments
+    }
+
+    /// Consumes the trace, returning its commitments in order.
+    pub fn into_commitments(self) -> Vec<OutputCommitment> {
+        self.commitments
+    }
+
+    /// Returns the commitment at the given trace position, if it has been recorded.
+    pub fn get(&self, position: u64) -> Option<&OutputCommitment> {
+        self.commitments.get(usize::try_from(position).ok()?)
+    }
+
+    /// Returns the trace position of the given L2 block, if it falls on the trace.
+    pub const fn position(&self, l2_block_number: u64) -> Option<u64> {
+        if self.interval == 0 || l2_block_number <= self.start {
+            return None;
+        }
+        let offset = l2_block_number - self.start;
+        if offset % self.interval == 0 { Some(offset / self.interval - 1) } else { None }
+    }
+
+    /// Records the output root of a derived L2 block, if the block falls on the trace.
+    ///
+    /// Blocks must be recorded in order. Blocks that do not fall on the trace, or that have
+    /// already been recorded, are ignored.
+    pub fn record(&mut self, l2_block_number: u64, l2_block_hash: B256, output_root: B256) {
+        if self.position(l2_block_number) == Some(self.commitments.len() as u64) {
+            self.commitments.push(OutputCommitment { l2_block_number, l2_block_hash, output_root });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_trace() {
+        let mut trace = OutputTrace::new(100, NonZeroU64::new(4).unwrap());
+        for number in 99..=113 {
+            trace.record(
+                number,
+                B256::with_last_byte(number as u8),
+                B256::repeat_byte(number as u8),
+            );
+        }
+        // Recording a block twice is a no-op.
+        trace.record(112, B256::ZERO, B256::ZERO);
+
+        assert_eq!(
+            trace.commitments().iter().map(|c| c.l2_block_number).collect::<Vec<_>>(),
+            [104, 108, 112]
+        );
+        assert_eq!(trace.get(1).unwrap().output_root, B256::repeat_byte(108));
+        assert_eq!(trace.get(1).unwrap().l2_block_hash, B256::with_last_byte(108));
+        assert!(trace.get(3).is_none());
+
+        assert_eq!(trace.position(100), None);
+        assert_eq!(trace.position(101), None);
+        assert_eq!(trace.position(104), Some(0));
+        assert_eq!(trace.position(116), Some(3));
+    }
+}
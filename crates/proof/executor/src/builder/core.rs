@@ -4,9 +4,9 @@
 //! for OP Stack L2 chains that operates in a stateless manner, pulling required state
 //! data from a [TrieDB] during execution rather than maintaining full state.
 
-use crate::{ExecutorError, ExecutorResult, TrieDB, TrieDBError, TrieDBProvider};
+use crate::{ExecutorError, ExecutorResult, StateDiff, TrieDB, TrieDBError, TrieDBProvider};
 use alloc::{string::ToString, vec::Vec};
-use alloy_consensus::{Header, Sealed, crypto::RecoveryError};
+use alloy_consensus::{Header, Sealed};
 use alloy_evm::{
     EvmFactory, FromRecoveredTx, FromTxWithEncoded,
     block::{BlockExecutionResult, BlockExecutor, BlockExecutorFactory},
@@ -15,6 +15,7 @@ use alloy_op_evm::{
     OpBlockExecutionCtx, OpBlockExecutorFactory,
     block::{OpAlloyReceiptBuilder, OpTxEnv},
 };
+use alloy_primitives::Bytes;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
 use kona_mpt::TrieHinter;
@@ -97,6 +98,8 @@ where
     /// understand OP-specific transaction types, system calls, and state
     /// management required for proper L2 block execution.
     pub(crate) factory: OpBlockExecutorFactory<OpAlloyReceiptBuilder, RollupConfig, Evm>,
+    /// Whether the [`StateDiff`] of each built block is recorded.
+    pub(crate) record_state_diff: bool,
 }
 
 impl<'a, P, H, Evm> StatelessL2Builder<'a, P, H, Evm>
@@ -145,7 +148,13 @@ where
             config.clone(),
             evm_factory,
         );
-        Self { config, trie_db, factory }
+        Self { config, trie_db, factory, record_state_diff: false }
+    }
+
+    /// Records the [`StateDiff`] of each built block, returned in its [`BlockBuildingOutcome`].
+    pub const fn with_state_diff(mut self, record_state_diff: bool) -> Self {
+        self.record_state_diff = record_state_diff;
+        self
     }
 
     /// Builds and executes a new L2 block using the provided payload attributes.
@@ -263,10 +272,14 @@ where
         let executor = self.factory.create_executor(evm, ctx);
 
         // Step 3. Execute the block containing the transactions within the payload attributes.
-        let transactions = attrs
-            .recovered_transactions_with_encoded()
-            .collect::<Result<Vec<_>, RecoveryError>>()
-            .map_err(ExecutorError::Recovery)?;
+        let tx_count = attrs.transactions.as_ref().map_or(0, Vec::len);
+        let mut transactions = Vec::new();
+        transactions
+            .try_reserve_exact(tx_count)
+            .map_err(|_| ExecutorError::AllocationFailed(tx_count))?;
+        for tx in attrs.recovered_transactions_with_encoded() {
+            transactions.push(tx.map_err(ExecutorError::Recovery)?);
+        }
         let ex_result = executor.execute_block(transactions.iter())?;
 
         info!(
@@ -279,6 +292,8 @@ where
         // Step 4. Merge state transitions and seal the block.
         state.merge_transitions(BundleRetention::Reverts);
         let bundle = state.take_bundle();
+        let bytecodes = bundle.contracts.values().map(|code| code.original_bytes()).collect();
+        let state_diff = self.record_state_diff.then(|| StateDiff::new(&state.cache, &bundle));
         let header = self.seal_block(&attrs, parent_hash, &block_env, &ex_result, bundle)?;
 
         info!(
@@ -293,7 +308,14 @@ where
 
         // Update the parent block hash in the state database, preparing for the next block.
         self.trie_db.set_parent_block_header(header.clone());
-        Ok((header, ex_result).into())
+        Ok(BlockBuildingOutcome { header, execution_result: ex_result, bytecodes, state_diff })
+    }
+
+    /// Returns a reference to the [`TrieDB`] holding the state of the latest built block.
+    ///
+    /// The trie nodes touched while building blocks are held unblinded within the [`TrieDB`].
+    pub const fn trie_db(&self) -> &TrieDB<P, H> {
+        &self.trie_db
     }
 }
 
@@ -305,13 +327,17 @@ pub struct BlockBuildingOutcome {
     pub header: Sealed<Header>,
     /// The block execution result.
     pub execution_result: BlockExecutionResult<OpReceiptEnvelope>,
+    /// The bytecode of the contracts created while executing the block.
+    pub bytecodes: Vec<Bytes>,
+    /// The accounts and storage slots read and written while executing the block, if recorded.
+    pub state_diff: Option<StateDiff>,
 }
 
 impl From<(Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>)> for BlockBuildingOutcome {
     fn from(
         (header, execution_result): (Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>),
     ) -> Self {
-        Self { header, execution_result }
+        Self { header, execution_result, bytecodes: Vec::new(), state_diff: None }
     }
 }
 
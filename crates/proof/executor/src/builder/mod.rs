@@ -6,4 +6,10 @@ pub use core::{BlockBuildingOutcome, StatelessL2Builder};
 mod assemble;
 pub use assemble::compute_receipts_root;
 
+mod receipts;
+pub use receipts::{BlockReceipts, ExecutedTransaction, MessagePassed};
+
+mod state_diff;
+pub use state_diff::{AccountDiff, AccountState, StateDiff, StorageDiff};
+
 mod env;
@@ -0,0 +1,154 @@
+//! Receipts, logs and gas usage of the transactions of a built block.
+
+use super::BlockBuildingOutcome;
+use alloc::vec::Vec;
+use alloy_primitives::{B256, Bytes, Log, keccak256};
+use alloy_sol_types::{SolEvent, sol};
+use kona_protocol::Predeploys;
+
+sol! {
+    /// @notice Emitted by the `L2ToL1MessagePasser` predeploy when a withdrawal is initiated.
+    #[derive(Default, Debug, PartialEq, Eq)]
+    event MessagePassed(
+        uint256 indexed nonce,
+        address indexed sender,
+        address indexed target,
+        uint256 value,
+        uint256 gasLimit,
+        bytes data,
+        bytes32 withdrawalHash
+    );
+}
+
+/// The outcome of a transaction executed in a built block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedTransaction {
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+    /// The gas used by the block up to and including the transaction.
+    pub cumulative_gas_used: u64,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+/// The receipts of a built block, along with the outcome of each of its transactions.
+///
+/// Returned by [`BlockBuildingOutcome::receipts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockReceipts {
+    /// The receipts root of the built block.
+    pub receipts_root: B256,
+    /// The outcome of each transaction of the block, in order.
+    pub transactions: Vec<ExecutedTransaction>,
+}
+
+impl BlockReceipts {
+    /// Returns an iterator over the logs emitted by the transactions of the block, in order.
+    pub fn logs(&self) -> impl Iterator<Item = &Log> {
+        self.transactions.iter().flat_map(|tx| tx.logs.iter())
+    }
+
+    /// Returns the withdrawals initiated in the block, as the [`MessagePassed`] events emitted by
+    /// the [`Predeploys::L2_TO_L1_MESSAGE_PASSER`].
+    pub fn withdrawals(&self) -> Vec<MessagePassed> {
+        self.logs()
+            .filter(|log| log.address == Predeploys::L2_TO_L1_MESSAGE_PASSER)
+            .filter_map(|log| MessagePassed::decode_log_data(&log.data).ok())
+            .collect()
+    }
+}
+
+impl BlockBuildingOutcome {
+    /// Returns the [`BlockReceipts`] of the built block, given the EIP-2718 encoded transactions
+    /// it was built from.
+    ///
+    /// The gas used by each transaction is the difference between the cumulative gas used of its
+    /// receipt and the receipt before it.
+    pub fn receipts(&self, transactions: &[Bytes]) -> BlockReceipts {
+        let mut prev_cumulative_gas_used = 0;
+        let transactions = self
+            .execution_result
+            .receipts
+            .iter()
+            .zip(transactions)
+            .map(|(receipt, tx)| {
+                let cumulative_gas_used = receipt.cumulative_gas_used();
+                let gas_used = cumulative_gas_used.saturating_sub(prev_cumulative_gas_used);
+                prev_cumulative_gas_used = cumulative_gas_used;
+                ExecutedTransaction {
+                    tx_hash: keccak256(tx),
+                    success: receipt.status(),
+                    gas_used,
+                    cumulative_gas_used,
+                    logs: receipt.logs().to_vec(),
+                }
+            })
+            .collect();
+
+        BlockReceipts { receipts_root: self.header.receipts_root, transactions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Eip658Value, Header, Receipt, ReceiptWithBloom, Sealed};
+    use alloy_evm::block::BlockExecutionResult;
+    use alloy_primitives::{Address, LogData, U256, b256};
+    use op_alloy_consensus::OpReceiptEnvelope;
+
+    fn receipt(cumulative_gas_used: u64, logs: Vec<Log>) -> OpReceiptEnvelope {
+        OpReceiptEnvelope::Eip1559(ReceiptWithBloom::from(Receipt {
+            status: Eip658Value::Eip658(true),
+            cumulative_gas_used,
+            logs,
+        }))
+    }
+
+    #[test]
+    fn test_block_receipts() {
+        let event = MessagePassed {
+            nonce: U256::from(1),
+            sender: Address::repeat_byte(0x01),
+            target: Address::repeat_byte(0x02),
+            value: U256::from(100),
+            gasLimit: U256::from(21_000),
+            data: Bytes::new(),
+            withdrawalHash: B256::repeat_byte(0x03),
+        };
+        let withdrawal =
+            Log { address: Predeploys::L2_TO_L1_MESSAGE_PASSER, data: event.encode_log_data() };
+        let other = Log {
+            address: Address::repeat_byte(0x04),
+            data: LogData::new_unchecked(vec![MessagePassed::SIGNATURE_HASH], Bytes::new()),
+        };
+
+        let receipts_root =
+            b256!("0x1111111111111111111111111111111111111111111111111111111111111111");
+        let outcome = BlockBuildingOutcome::from((
+            Sealed::new(Header { receipts_root, ..Default::default() }),
+            BlockExecutionResult {
+                receipts: vec![receipt(50_000, vec![]), receipt(120_000, vec![withdrawal, other])],
+                requests: Default::default(),
+                gas_used: 120_000,
+                blob_gas_used: 0,
+            },
+        ));
+
+        let txs = [Bytes::from_static(&[0x7e, 0x01]), Bytes::from_static(&[0x02, 0x02])];
+        let receipts = outcome.receipts(&txs);
+        assert_eq!(receipts.receipts_root, receipts_root);
+        assert_eq!(receipts.transactions.len(), 2);
+        assert_eq!(receipts.transactions[0].tx_hash, keccak256(&txs[0]));
+        assert_eq!(receipts.transactions[0].gas_used, 50_000);
+        assert_eq!(receipts.transactions[1].gas_used, 70_000);
+        assert_eq!(receipts.transactions[1].cumulative_gas_used, 120_000);
+        assert_eq!(receipts.logs().count(), 2);
+        assert_eq!(receipts.withdrawals(), vec![event]);
+    }
+}
@@ -0,0 +1,170 @@
+//! State diffs recorded while building blocks.
+
+use alloc::collections::BTreeMap;
+use alloy_primitives::{Address, B256, U256};
+use revm::{
+    database::{BundleState, CacheState},
+    state::AccountInfo,
+};
+
+/// The state of an account, before or after a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccountState {
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The hash of the code of the account.
+    pub code_hash: B256,
+}
+
+impl From<&AccountInfo> for AccountState {
+    fn from(info: &AccountInfo) -> Self {
+        Self { balance: info.balance, nonce: info.nonce, code_hash: info.code_hash }
+    }
+}
+
+/// A storage slot read or written by a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageDiff {
+    /// The value of the slot before the block.
+    pub pre: U256,
+    /// The value of the slot after the block.
+    pub post: U256,
+}
+
+/// An account read or written by a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountDiff {
+    /// The state of the account before the block, or `None` if it did not exist.
+    pub pre: Option<AccountState>,
+    /// The state of the account after the block, or `None` if it does not exist.
+    pub post: Option<AccountState>,
+    /// The storage slots of the account read or written by the block.
+    pub storage: BTreeMap<U256, StorageDiff>,
+}
+
+impl AccountDiff {
+    /// Returns true if the block changed the account or any of its storage slots.
+    pub fn is_written(&self) -> bool {
+        self.pre != self.post || self.storage.values().any(|slot| slot.pre != slot.post)
+    }
+}
+
+/// The accounts and storage slots read and written while executing a block, ordered by address
+/// and slot.
+///
+/// Recorded when the [`StatelessL2Builder`] is built with [`StatelessL2Builder::with_state_diff`].
+/// Accounts and slots that were only read have equal `pre` and `post` values.
+///
+/// [`StatelessL2Builder`]: crate::StatelessL2Builder
+/// [`StatelessL2Builder::with_state_diff`]: crate::StatelessL2Builder::with_state_diff
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateDiff {
+    /// The accounts read or written by the block.
+    pub accounts: BTreeMap<Address, AccountDiff>,
+}
+
+impl StateDiff {
+    /// Records the state diff of a block from the [`CacheState`] it was executed with, holding
+    /// every account and slot it loaded, and the [`BundleState`] of its changes, holding their
+    /// values before the block.
+    pub(crate) fn new(cache: &CacheState, bundle: &BundleState) -> Self {
+        let mut accounts = BTreeMap::new();
+
+        for (address, cached) in &cache.accounts {
+            let post = cached.account.as_ref().map(|account| AccountState::from(&account.info));
+            let changes = bundle.state.get(address);
+            let pre = changes
+                .map_or(post, |changes| changes.original_info.as_ref().map(AccountState::from));
+
+            let mut storage = BTreeMap::new();
+            if let Some(account) = &cached.account {
+                for (slot, value) in &account.storage {
+                    storage.insert(*slot, StorageDiff { pre: *value, post: *value });
+                }
+            }
+            if let Some(changes) = changes {
+                for (slot, value) in &changes.storage {
+                    storage.insert(
+                        *slot,
+                        StorageDiff { pre: value.original_value(), post: value.present_value },
+                    );
+                }
+            }
+
+            accounts.insert(*address, AccountDiff { pre, post, storage });
+        }
+
+        Self { accounts }
+    }
+
+    /// Returns an iterator over the accounts written by the block.
+    pub fn written(&self) -> impl Iterator<Item = (&Address, &AccountDiff)> {
+        self.accounts.iter().filter(|(_, diff)| diff.is_written())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::HashMap;
+
+    #[test]
+    fn test_state_diff() {
+        let read = Address::repeat_byte(0x01);
+        let written = Address::repeat_byte(0x02);
+        let pre = AccountInfo { balance: U256::from(10), nonce: 1, ..Default::default() };
+        let post = AccountInfo { balance: U256::from(5), nonce: 2, ..Default::default() };
+
+        let mut cache = CacheState::new(false);
+        cache.insert_account_with_storage(
+            read,
+            pre.clone(),
+            HashMap::from_iter([(U256::from(1), U256::from(7))]),
+        );
+        cache.insert_account_with_storage(
+            written,
+            post.clone(),
+            HashMap::from_iter([(U256::from(1), U256::from(9))]),
+        );
+        let bundle = BundleState::builder(0..=0)
+            .state_original_account_info(written, pre.clone())
+            .state_present_account_info(written, post.clone())
+            .state_storage(
+                written,
+                HashMap::from_iter([(U256::from(1), (U256::from(8), U256::from(9)))]),
+            )
+            .build();
+
+        let diff = StateDiff::new(&cache, &bundle);
+        assert_eq!(
+            diff.accounts[&read],
+            AccountDiff {
+                pre: Some(AccountState::from(&pre)),
+                post: Some(AccountState::from(&pre)),
+                storage: BTreeMap::from([(
+                    U256::from(1),
+                    StorageDiff { pre: U256::from(7), post: U256::from(7) }
+                )]),
+            }
+        );
+        assert_eq!(
+            diff.accounts[&written],
+            AccountDiff {
+                pre: Some(AccountState::from(&pre)),
+                post: Some(AccountState::from(&post)),
+                storage: BTreeMap::from([(
+                    U256::from(1),
+                    StorageDiff { pre: U256::from(8), post: U256::from(9) }
+                )]),
+            }
+        );
+        assert_eq!(diff.written().map(|(address, _)| *address).collect::<Vec<_>>(), [written]);
+    }
+}
@@ -18,6 +18,9 @@ use revm::{
 mod traits;
 pub use traits::{NoopTrieDBProvider, TrieDBProvider};
 
+mod parallel;
+use parallel::blind_all;
+
 /// A Trie DB that caches open state in-memory.
 ///
 /// When accounts that don't already exist within the cached [`TrieNode`] are queried, the database
@@ -210,40 +213,26 @@ where
     fn update_accounts(&mut self, bundle: &BundleState) -> TrieDBResult<()> {
         // Sort the storage keys prior to applying the changeset, to ensure that the order of
         // application is deterministic between runs.
-        let mut sorted_state =
-            bundle.state().iter().map(|(k, v)| (k, keccak256(*k), v)).collect::<Vec<_>>();
+        let mut sorted_state = bundle
+            .state()
+            .iter()
+            .filter(|(_, v)| !v.status.is_not_modified())
+            .map(|(k, v)| (k, keccak256(*k), v))
+            .collect::<Vec<_>>();
         sorted_state.sort_by_key(|(_, hashed_addr, _)| *hashed_addr);
 
-        for (address, hashed_address, bundle_account) in sorted_state {
-            if bundle_account.status.is_not_modified() {
-                continue;
-            }
-
-            // Compute the path to the account in the trie.
-            let account_path = Nibbles::unpack(hashed_address.as_slice());
-
-            // If the account was destroyed, delete it from the trie.
+        // Apply the storage changes of the accounts that were not destroyed to their storage tries.
+        let mut storage_addresses = Vec::with_capacity(sorted_state.len());
+        let mut storage_tries = Vec::with_capacity(sorted_state.len());
+        for (address, _, bundle_account) in &sorted_state {
             if bundle_account.was_destroyed() {
-                self.root_node.delete(&account_path, &self.fetcher, &self.hinter)?;
-                self.storage_roots.remove(address);
                 continue;
             }
 
-            let account_info =
-                bundle_account.account_info().ok_or(TrieDBError::MissingAccountInfo)?;
-
-            let mut trie_account = TrieAccount {
-                balance: account_info.balance,
-                nonce: account_info.nonce,
-                code_hash: account_info.code_hash,
-                ..Default::default()
-            };
-
-            // Update the account's storage root
-            let acc_storage_root = self
+            let mut storage_trie = self
                 .storage_roots
-                .entry(*address)
-                .or_insert_with(|| TrieNode::new_blinded(EMPTY_ROOT_HASH));
+                .remove(*address)
+                .unwrap_or_else(|| TrieNode::new_blinded(EMPTY_ROOT_HASH));
 
             // Sort the hashed storage keys prior to applying the changeset, to ensure that the
             // order of application is deterministic between runs.
@@ -256,7 +245,7 @@ where
 
             sorted_storage.into_iter().try_for_each(|(hashed_key, value)| {
                 Self::change_storage(
-                    acc_storage_root,
+                    &mut storage_trie,
                     hashed_key,
                     value,
                     &self.fetcher,
@@ -264,9 +253,37 @@ where
                 )
             })?;
 
-            // Recompute the account storage root.
-            let root = acc_storage_root.blind();
-            trie_account.storage_root = root;
+            storage_addresses.push(**address);
+            storage_tries.push(storage_trie);
+        }
+
+        // Recompute the storage roots of the updated accounts. The storage tries are independent
+        // of each other, and are hashed concurrently when the `parallel` feature is enabled.
+        let storage_roots = blind_all(&storage_tries);
+        let storage_roots =
+            storage_addresses.iter().copied().zip(storage_roots).collect::<HashMap<_, _>>();
+        self.storage_roots.extend(storage_addresses.into_iter().zip(storage_tries));
+
+        for (address, hashed_address, bundle_account) in sorted_state {
+            // Compute the path to the account in the trie.
+            let account_path = Nibbles::unpack(hashed_address.as_slice());
+
+            // If the account was destroyed, delete it from the trie.
+            if bundle_account.was_destroyed() {
+                self.root_node.delete(&account_path, &self.fetcher, &self.hinter)?;
+                self.storage_roots.remove(address);
+                continue;
+            }
+
+            let account_info =
+                bundle_account.account_info().ok_or(TrieDBError::MissingAccountInfo)?;
+
+            let trie_account = TrieAccount {
+                balance: account_info.balance,
+                nonce: account_info.nonce,
+                code_hash: account_info.code_hash,
+                storage_root: storage_roots[address],
+            };
 
             // RLP encode the trie account for insertion.
             let mut account_buf = Vec::with_capacity(trie_account.length());
@@ -0,0 +1,64 @@
+//! Commitment computation of independent subtries.
+//!
+//! With the `parallel` feature, the subtries are hashed concurrently across the available cores.
+//! Otherwise, as on the FPVM targets, they are hashed sequentially.
+
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use kona_mpt::TrieNode;
+
+/// Computes the commitments of the given independent subtries, in order.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn blind_all(tries: &[TrieNode]) -> Vec<B256> {
+    tries.iter().map(TrieNode::blind).collect()
+}
+
+/// Computes the commitments of the given independent subtries, in order, hashing them
+/// concurrently across the available cores.
+#[cfg(feature = "parallel")]
+pub(crate) fn blind_all(tries: &[TrieNode]) -> Vec<B256> {
+    use core::num::NonZeroUsize;
+    use std::thread;
+
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get).min(tries.len());
+    if threads <= 1 {
+        return tries.iter().map(TrieNode::blind).collect();
+    }
+
+    let chunk_size = tries.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let handles = tries
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(TrieNode::blind).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, keccak256};
+    use kona_mpt::{Nibbles, NoopTrieProvider};
+
+    #[test]
+    fn test_blind_all() {
+        let tries = (0..32u8)
+            .map(|i| {
+                let mut trie = TrieNode::Empty;
+                for j in 0..=i {
+                    let path = Nibbles::unpack(keccak256([i, j]));
+                    trie.insert(&path, Bytes::from(vec![j; 32]), &NoopTrieProvider).unwrap();
+                }
+                trie
+            })
+            .collect::<Vec<_>>();
+
+        let expected = tries.iter().map(TrieNode::blind).collect::<Vec<_>>();
+        assert_eq!(blind_all(&tries), expected);
+        assert!(blind_all(&[]).is_empty());
+    }
+}
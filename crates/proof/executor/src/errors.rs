@@ -200,6 +200,17 @@ pub enum ExecutorError {
     /// - Incorrect executor lifecycle management
     #[error("Missing the executor")]
     MissingExecutor,
+    /// Failed to allocate memory while building the block.
+    ///
+    /// This error occurs when an allocation in the block building hot path
+    /// fails, and is surfaced rather than aborting so that memory-constrained
+    /// FPVM targets fail gracefully.
+    ///
+    /// # Common Causes
+    /// - Blocks with more transactions than the target's heap can hold
+    /// - Heap exhaustion from previously built blocks
+    #[error("Failed to allocate memory for {0} elements")]
+    AllocationFailed(usize),
 }
 
 /// Result type alias for operations that may fail with [`ExecutorError`].
@@ -5,7 +5,7 @@
     issue_tracker_base_url = "https://github.com/op-rs/kona/issues/"
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
-#![cfg_attr(not(any(test, feature = "test-utils")), no_std)]
+#![cfg_attr(not(any(test, feature = "test-utils", feature = "parallel")), no_std)]
 
 extern crate alloc;
 
@@ -16,7 +16,10 @@ mod db;
 pub use db::{NoopTrieDBProvider, TrieDB, TrieDBProvider};
 
 mod builder;
-pub use builder::{BlockBuildingOutcome, StatelessL2Builder, compute_receipts_root};
+pub use builder::{
+    AccountDiff, AccountState, BlockBuildingOutcome, BlockReceipts, ExecutedTransaction,
+    MessagePassed, StateDiff, StatelessL2Builder, StorageDiff, compute_receipts_root,
+};
 
 mod errors;
 pub use errors::{
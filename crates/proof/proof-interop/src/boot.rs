@@ -34,6 +34,13 @@ pub const L2_ROLLUP_CONFIG_KEY: U256 = U256::from_be_slice(&[6]);
 /// The local key ident for the l1 config.
 pub const L1_CONFIG_KEY: U256 = U256::from_be_slice(&[7]);
 
+/// The local key ident for the number of state transition steps covered by the L2 post-state
+/// claim.
+///
+/// This key is only served by the host for claims over a range of super roots, and is never
+/// provided by the fault dispute game. It is only read by [`BootInfo::load_multi_step`].
+pub const L2_CLAIMED_STEPS_KEY: U256 = U256::from_be_slice(&[8]);
+
 /// The boot information for the interop client program.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BootInfo {
@@ -47,6 +54,10 @@ pub struct BootInfo {
     pub claimed_post_state: B256,
     /// The L2 claim timestamp.
     pub claimed_l2_timestamp: u64,
+    /// The number of steps of the superchain state transition from the pre-state to the claimed
+    /// post-state. Claims of the fault dispute game cover a single step, while claims over a
+    /// range of super roots, loaded with [`BootInfo::load_multi_step`], cover several.
+    pub claimed_steps: u64,
     /// The rollup config for the L2 chain.
     pub rollup_configs: HashMap<u64, RollupConfig>,
     /// The L1 config for the L2 chain.
@@ -54,7 +65,8 @@ pub struct BootInfo {
 }
 
 impl BootInfo {
-    /// Load the boot information from the preimage oracle.
+    /// Load the boot information from the preimage oracle, reading only the local keys provided
+    /// by the fault dispute game. The claim covers a single step of the state transition.
     ///
     /// ## Takes
     /// - `oracle`: The preimage oracle reader.
@@ -175,9 +187,40 @@ impl BootInfo {
             agreed_pre_state,
             claimed_post_state: l2_post,
             claimed_l2_timestamp: l2_claim_block,
+            claimed_steps: 1,
         })
     }
 
+    /// Load the boot information from the preimage oracle, along with the number of steps of the
+    /// state transition covered by the claim.
+    ///
+    /// The number of steps is read from the host-only [`L2_CLAIMED_STEPS_KEY`], which the fault
+    /// dispute game does not provide. Programs run by the fault dispute game must use
+    /// [`BootInfo::load`] instead.
+    ///
+    /// ## Takes
+    /// - `oracle`: The preimage oracle reader.
+    ///
+    /// ## Returns
+    /// - `Ok(BootInfo)`: The boot information.
+    /// - `Err(_)`: Failed to load the boot information.
+    pub async fn load_multi_step<O>(oracle: &O) -> Result<Self, BootstrapError>
+    where
+        O: PreimageOracleClient + HintWriterClient + Clone + Send,
+    {
+        let mut boot = Self::load(oracle).await?;
+        boot.claimed_steps = u64::from_be_bytes(
+            oracle
+                .get(PreimageKey::new_local(L2_CLAIMED_STEPS_KEY.to()))
+                .await
+                .map_err(OracleProviderError::Preimage)?
+                .as_slice()
+                .try_into()
+                .map_err(OracleProviderError::SliceConversion)?,
+        );
+        Ok(boot)
+    }
+
     /// Returns the [RollupConfig] corresponding to the [PreState::active_l2_chain_id].
     pub fn active_rollup_config(&self) -> Option<RollupConfig> {
         let active_l2_chain_id = self.agreed_pre_state.active_l2_chain_id()?;
@@ -238,3 +281,94 @@ where
 
     Ok(Bytes::from(pre))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::keccak256;
+    use alloy_rlp::Encodable;
+    use async_trait::async_trait;
+    use kona_interop::{OutputRootWithChain, SuperRoot};
+    use kona_preimage::errors::PreimageOracleResult;
+
+    /// An oracle serving a fixed set of preimages.
+    #[derive(Debug, Clone)]
+    struct MockOracle(Vec<(PreimageKey, Vec<u8>)>);
+
+    impl MockOracle {
+        /// Creates an oracle serving the local keys of the fault dispute game for a claim over
+        /// a super root of OP Mainnet.
+        fn new() -> (Self, PreState) {
+            let pre_state = PreState::SuperRoot(SuperRoot::new(
+                100,
+                vec![OutputRootWithChain::new(10, B256::repeat_byte(1))],
+            ));
+            let mut raw_pre_state = Vec::new();
+            pre_state.encode(&mut raw_pre_state);
+            let pre_state_hash = keccak256(&raw_pre_state);
+
+            let local =
+                |key: U256, value: &[u8]| (PreimageKey::new_local(key.to()), value.to_vec());
+            let oracle = Self(vec![
+                local(L1_HEAD_KEY, B256::repeat_byte(2).as_slice()),
+                local(L2_AGREED_PRE_STATE_KEY, pre_state_hash.as_slice()),
+                local(L2_CLAIMED_POST_STATE_KEY, B256::repeat_byte(3).as_slice()),
+                local(L2_CLAIMED_TIMESTAMP_KEY, &101u64.to_be_bytes()),
+                (PreimageKey::new(*pre_state_hash, PreimageKeyType::Keccak256), raw_pre_state),
+            ]);
+            (oracle, pre_state)
+        }
+    }
+
+    #[async_trait]
+    impl PreimageOracleClient for MockOracle {
+        async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            self.0
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, value)| value.clone())
+                .ok_or(PreimageOracleError::KeyNotFound)
+        }
+
+        async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+            let value = self.get(key).await?;
+            if value.len() != buf.len() {
+                return Err(PreimageOracleError::BufferLengthMismatch(value.len(), buf.len()));
+            }
+            buf.copy_from_slice(&value);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl HintWriterClient for MockOracle {
+        async fn write(&self, _: &str) -> PreimageOracleResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_load_without_claimed_steps_key() {
+        let (oracle, pre_state) = MockOracle::new();
+
+        let boot = kona_proof::block_on(BootInfo::load(&oracle)).unwrap();
+        assert_eq!(boot.agreed_pre_state, pre_state);
+        assert_eq!(boot.claimed_l2_timestamp, 101);
+        assert_eq!(boot.claimed_steps, 1);
+
+        // The multi-step claims require the host-only key.
+        assert!(kona_proof::block_on(BootInfo::load_multi_step(&oracle)).is_err());
+    }
+
+    #[test]
+    fn test_load_multi_step() {
+        let (mut oracle, _) = MockOracle::new();
+        oracle
+            .0
+            .push((PreimageKey::new_local(L2_CLAIMED_STEPS_KEY.to()), 4u64.to_be_bytes().to_vec()));
+
+        let boot = kona_proof::block_on(BootInfo::load_multi_step(&oracle)).unwrap();
+        assert_eq!(boot.claimed_steps, 4);
+    }
+}
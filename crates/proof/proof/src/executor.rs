@@ -102,4 +102,9 @@ where
             |e| e.compute_output_root(),
         )
     }
+
+    /// Failed allocations are fatal, as they are not caused by the payload.
+    fn is_fatal(error: &Self::Error) -> bool {
+        matches!(error, kona_executor::ExecutorError::AllocationFailed(_))
+    }
 }
@@ -0,0 +1,166 @@
+//! Calls into the JavaScript provider objects.
+
+use core::{
+    fmt::Display,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use js_sys::{Array, Function, JSON, Promise, Reflect, Uint8Array};
+use kona_derive::{PipelineError, PipelineErrorKind};
+use serde::{Serialize, de::DeserializeOwned};
+use wasm_bindgen::{JsCast, JsError, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// An error of a JavaScript provider.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum JsProviderError {
+    /// The call of the provider method failed.
+    #[error("`{method}` failed: {message}")]
+    Call {
+        /// The name of the method.
+        method: &'static str,
+        /// The error thrown by the method.
+        message: String,
+    },
+    /// The provider method returned an unexpected value.
+    #[error("Invalid value returned by `{method}`: {message}")]
+    InvalidResponse {
+        /// The name of the method.
+        method: &'static str,
+        /// The reason the value is invalid.
+        message: String,
+    },
+}
+
+impl JsProviderError {
+    /// Creates a new [`JsProviderError::InvalidResponse`].
+    pub(crate) fn invalid(method: &'static str, message: impl Display) -> Self {
+        Self::InvalidResponse { method, message: message.to_string() }
+    }
+}
+
+impl From<JsProviderError> for PipelineErrorKind {
+    fn from(e: JsProviderError) -> Self {
+        PipelineError::Provider(e.to_string()).temp()
+    }
+}
+
+/// A JavaScript object whose async methods are called by the pipeline.
+#[derive(Debug, Clone)]
+pub(crate) struct JsObject(JsValue);
+
+// SAFETY: the crate is only compiled for `wasm32` targets, whose modules run on the single thread
+// of their JavaScript host, so JavaScript values are never shared across threads. The pipeline
+// requires its providers to be `Send` and `Sync` to support multi-threaded native runtimes.
+unsafe impl Send for JsObject {}
+unsafe impl Sync for JsObject {}
+
+impl JsObject {
+    /// Wraps the given JavaScript object.
+    pub(crate) const fn new(object: JsValue) -> Self {
+        Self(object)
+    }
+
+    /// Calls the given method of the object, awaiting the returned value if it is a promise.
+    pub(crate) fn call(&self, method: &'static str, args: Vec<JsValue>) -> JsCall {
+        let object = self.0.clone();
+        let future = async move {
+            let function: Function = Reflect::get(&object, &JsValue::from_str(method))?
+                .dyn_into()
+                .map_err(|_| JsValue::from_str("not a function"))?;
+            let value = function.apply(&object, &args.into_iter().collect::<Array>())?;
+            JsFuture::from(Promise::resolve(&value)).await
+        };
+        JsCall { method, future: Box::pin(future) }
+    }
+}
+
+/// The future of a call of a method of a [`JsObject`].
+pub(crate) struct JsCall {
+    /// The name of the method.
+    method: &'static str,
+    /// The future of the value returned by the method.
+    future: Pin<Box<dyn Future<Output = Result<JsValue, JsValue>>>>,
+}
+
+// SAFETY: see the `Send` implementation of `JsObject`.
+unsafe impl Send for JsCall {}
+
+impl core::fmt::Debug for JsCall {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("JsCall").field("method", &self.method).finish_non_exhaustive()
+    }
+}
+
+impl Future for JsCall {
+    type Output = Result<JsValue, JsProviderError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let method = this.method;
+        this.future
+            .as_mut()
+            .poll(cx)
+            .map_err(|e| JsProviderError::Call { method, message: describe(&e) })
+    }
+}
+
+/// Returns the message of a thrown JavaScript value.
+fn describe(value: &JsValue) -> String {
+    value
+        .as_string()
+        .or_else(|| value.dyn_ref::<js_sys::Error>().map(|e| String::from(e.message())))
+        .unwrap_or_else(|| format!("{value:?}"))
+}
+
+/// Reads a `Uint8Array` returned by the given method.
+pub(crate) fn bytes(method: &'static str, value: &JsValue) -> Result<Vec<u8>, JsProviderError> {
+    value
+        .dyn_ref::<Uint8Array>()
+        .map(Uint8Array::to_vec)
+        .ok_or_else(|| JsProviderError::invalid(method, "expected a Uint8Array"))
+}
+
+/// Reads an array of `Uint8Array`s returned by the given method.
+pub(crate) fn bytes_list(
+    method: &'static str,
+    value: &JsValue,
+) -> Result<Vec<Vec<u8>>, JsProviderError> {
+    value
+        .dyn_ref::<Array>()
+        .ok_or_else(|| JsProviderError::invalid(method, "expected an array"))?
+        .iter()
+        .map(|item| bytes(method, &item))
+        .collect()
+}
+
+/// Reads the given field of an object returned by the given method.
+pub(crate) fn field(
+    method: &'static str,
+    value: &JsValue,
+    name: &str,
+) -> Result<JsValue, JsProviderError> {
+    Reflect::get(value, &JsValue::from_str(name))
+        .map_err(|_| JsProviderError::invalid(method, format!("missing `{name}`")))
+}
+
+/// Deserializes a value from its JSON representation, as returned by the given method.
+pub(crate) fn from_js<T: DeserializeOwned>(
+    method: &'static str,
+    value: &JsValue,
+) -> Result<T, JsProviderError> {
+    let json =
+        JSON::stringify(value).map_err(|e| JsProviderError::invalid(method, describe(&e)))?;
+    serde_json::from_str(&String::from(json)).map_err(|e| JsProviderError::invalid(method, e))
+}
+
+/// Serializes a value to its JSON representation.
+pub(crate) fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    let json = serde_json::to_string(value).map_err(js_error)?;
+    JSON::parse(&json)
+}
+
+/// Converts an error to a JavaScript `Error`.
+pub(crate) fn js_error(e: impl Display) -> JsValue {
+    JsError::new(&e.to_string()).into()
+}
@@ -0,0 +1,17 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/op-rs/kona/main/assets/square.png",
+    html_favicon_url = "https://raw.githubusercontent.com/op-rs/kona/main/assets/favicon.ico",
+    issue_tracker_base_url = "https://github.com/op-rs/kona/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg(target_arch = "wasm32")]
+
+mod js;
+pub use js::JsProviderError;
+
+mod providers;
+pub use providers::{JsBlobProvider, JsChainProvider, JsL2ChainProvider};
+
+mod pipeline;
+pub use pipeline::WasmPipeline;
@@ -0,0 +1,194 @@
+//! Contains the [WasmPipeline], the JavaScript-facing derivation pipeline.
+
+use crate::{
+    JsBlobProvider, JsChainProvider, JsL2ChainProvider,
+    js::{from_js, js_error, to_js},
+};
+use js_sys::Promise;
+use kona_derive::{
+    DerivationPipeline, EthereumDataSource, L2ChainProvider, OriginProvider, Pipeline,
+    PipelineBuilder, PipelineErrorKind, PolledAttributesQueueStage, ResetSignal, SignalReceiver,
+    StatefulAttributesBuilder, StepResult,
+};
+use kona_genesis::{L1ChainConfig, RollupConfig};
+use kona_protocol::{BlockInfo, L2BlockInfo};
+use serde::Serialize;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+use wasm_bindgen::{JsError, JsValue, prelude::wasm_bindgen};
+use wasm_bindgen_futures::future_to_promise;
+
+/// A derivation pipeline backed by JavaScript providers.
+type JsDerivationPipeline = DerivationPipeline<
+    PolledAttributesQueueStage<
+        EthereumDataSource<JsChainProvider, JsBlobProvider>,
+        JsChainProvider,
+        JsL2ChainProvider,
+        StatefulAttributesBuilder<JsChainProvider, JsL2ChainProvider>,
+    >,
+    JsL2ChainProvider,
+>;
+
+/// The outcome of a step of the pipeline, as returned to JavaScript.
+#[derive(Debug, Serialize)]
+#[serde(tag = "result", rename_all = "camelCase", rename_all_fields = "camelCase")]
+enum StepOutcome {
+    /// Attributes were prepared, and can be read with `next`.
+    PreparedAttributes,
+    /// The L1 origin of the pipeline was advanced.
+    AdvancedOrigin,
+    /// The L1 origin of the pipeline could not be advanced.
+    OriginAdvanceErr {
+        /// The kind of the error: `temporary`, `critical` or `reset`.
+        kind: &'static str,
+        /// The error message.
+        error: String,
+    },
+    /// The step failed.
+    StepFailed {
+        /// The kind of the error: `temporary`, `critical` or `reset`.
+        kind: &'static str,
+        /// The error message.
+        error: String,
+    },
+}
+
+/// Returns the kind of the given error.
+const fn error_kind(e: &PipelineErrorKind) -> &'static str {
+    match e {
+        PipelineErrorKind::Temporary(_) => "temporary",
+        PipelineErrorKind::Critical(_) => "critical",
+        PipelineErrorKind::Reset(_) => "reset",
+    }
+}
+
+impl From<StepResult> for StepOutcome {
+    fn from(result: StepResult) -> Self {
+        match result {
+            StepResult::PreparedAttributes => Self::PreparedAttributes,
+            StepResult::AdvancedOrigin => Self::AdvancedOrigin,
+            StepResult::OriginAdvanceErr(e) => {
+                Self::OriginAdvanceErr { kind: error_kind(&e), error: e.to_string() }
+            }
+            StepResult::StepFailed(e) => {
+                Self::StepFailed { kind: error_kind(&e), error: e.to_string() }
+            }
+        }
+    }
+}
+
+/// A derivation pipeline reading chain data from JavaScript providers.
+///
+/// The pipeline must be reset with `reset` before it is stepped. A step resolves to an object
+/// whose `result` is `preparedAttributes`, `advancedOrigin`, `originAdvanceErr` or `stepFailed`,
+/// with the `kind` and `error` message of failed steps. Calls that would interleave with a pending
+/// `reset` or `step` are rejected.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct WasmPipeline {
+    /// The pipeline, taken out while a reset or step is pending.
+    pipeline: Rc<RefCell<Option<JsDerivationPipeline>>>,
+    /// The L2 provider, used to fetch the system config on resets.
+    l2_chain_provider: JsL2ChainProvider,
+    /// The rollup config.
+    rollup_config: Arc<RollupConfig>,
+}
+
+#[wasm_bindgen]
+impl WasmPipeline {
+    /// Creates a new pipeline from the JSON rollup and L1 chain configs, and the L1, L2 and blob
+    /// provider objects.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        rollup_config: JsValue,
+        l1_config: JsValue,
+        l1_provider: JsValue,
+        l2_provider: JsValue,
+        blob_provider: JsValue,
+    ) -> Result<Self, JsError> {
+        let rollup_config: Arc<RollupConfig> = Arc::new(from_js("rollupConfig", &rollup_config)?);
+        let l1_config: Arc<L1ChainConfig> = Arc::new(from_js("l1Config", &l1_config)?);
+        let chain_provider = JsChainProvider::new(l1_provider);
+        let l2_chain_provider = JsL2ChainProvider::new(l2_provider);
+
+        let attributes = StatefulAttributesBuilder::new(
+            rollup_config.clone(),
+            l1_config,
+            l2_chain_provider.clone(),
+            chain_provider.clone(),
+        );
+        let dap = EthereumDataSource::new_from_parts(
+            chain_provider.clone(),
+            JsBlobProvider::new(blob_provider),
+            &rollup_config,
+        );
+        let pipeline = PipelineBuilder::new()
+            .rollup_config(rollup_config.clone())
+            .dap_source(dap)
+            .l2_chain_provider(l2_chain_provider.clone())
+            .chain_provider(chain_provider)
+            .builder(attributes)
+            .origin(BlockInfo::default())
+            .build_polled();
+
+        Ok(Self {
+            pipeline: Rc::new(RefCell::new(Some(pipeline))),
+            l2_chain_provider,
+            rollup_config,
+        })
+    }
+
+    /// Resets the pipeline to the given L2 safe head and L1 origin.
+    pub fn reset(&self, l2_safe_head: JsValue, l1_origin: JsValue) -> Result<Promise, JsError> {
+        let l2_safe_head: L2BlockInfo = from_js("l2SafeHead", &l2_safe_head)?;
+        let l1_origin: BlockInfo = from_js("l1Origin", &l1_origin)?;
+        let mut l2_chain_provider = self.l2_chain_provider.clone();
+        let rollup_config = self.rollup_config.clone();
+        let cell = self.pipeline.clone();
+
+        Ok(future_to_promise(async move {
+            let system_config = l2_chain_provider
+                .system_config_by_number(l2_safe_head.block_info.number, rollup_config)
+                .await
+                .ok();
+            let mut pipeline = take(&cell)?;
+            let signal = ResetSignal { l2_safe_head, l1_origin, system_config }.signal();
+            let result = pipeline.signal(signal).await;
+            *cell.borrow_mut() = Some(pipeline);
+            result.map_err(js_error)?;
+            Ok(JsValue::UNDEFINED)
+        }))
+    }
+
+    /// Steps the pipeline on top of the given L2 cursor.
+    pub fn step(&self, cursor: JsValue) -> Result<Promise, JsError> {
+        let cursor: L2BlockInfo = from_js("cursor", &cursor)?;
+        let cell = self.pipeline.clone();
+
+        Ok(future_to_promise(async move {
+            let mut pipeline = take(&cell)?;
+            let result = pipeline.step(cursor).await;
+            *cell.borrow_mut() = Some(pipeline);
+            to_js(&StepOutcome::from(result))
+        }))
+    }
+
+    /// Returns the next prepared payload attributes, or `undefined` if there are none.
+    #[wasm_bindgen(js_name = next)]
+    pub fn next_attributes(&self) -> Result<JsValue, JsValue> {
+        let mut pipeline = self.pipeline.borrow_mut();
+        let pipeline = pipeline.as_mut().ok_or_else(|| js_error("pipeline is busy"))?;
+        pipeline.next().map_or(Ok(JsValue::UNDEFINED), |attributes| to_js(&attributes))
+    }
+
+    /// Returns the L1 origin of the pipeline, or `undefined` if it has none.
+    pub fn origin(&self) -> Result<JsValue, JsValue> {
+        let pipeline = self.pipeline.borrow();
+        let pipeline = pipeline.as_ref().ok_or_else(|| js_error("pipeline is busy"))?;
+        pipeline.origin().map_or(Ok(JsValue::UNDEFINED), |origin| to_js(&origin))
+    }
+}
+
+/// Takes the pipeline out of its cell for the duration of a reset or step.
+fn take(cell: &RefCell<Option<JsDerivationPipeline>>) -> Result<JsDerivationPipeline, JsValue> {
+    cell.take().ok_or_else(|| js_error("pipeline is busy"))
+}
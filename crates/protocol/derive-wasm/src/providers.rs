@@ -0,0 +1,188 @@
+//! Providers of the pipeline backed by JavaScript objects.
+
+use crate::{
+    JsProviderError,
+    js::{JsObject, bytes, bytes_list, field, from_js, to_js},
+};
+use alloy_consensus::{Blob, Header, Receipt, ReceiptEnvelope, TxEnvelope};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::B256;
+use alloy_rlp::Decodable;
+use async_trait::async_trait;
+use kona_derive::{BlobProvider, ChainProvider, L2ChainProvider};
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo};
+use op_alloy_consensus::OpBlock;
+use std::sync::Arc;
+use wasm_bindgen::JsValue;
+
+/// Converts a block number to a JavaScript number.
+fn number(number: u64) -> JsValue {
+    JsValue::from_f64(number as f64)
+}
+
+/// Converts a hash to its hex representation.
+fn hash(hash: B256) -> JsValue {
+    JsValue::from_str(&hash.to_string())
+}
+
+/// A [`ChainProvider`] backed by a JavaScript object exposing the following async methods:
+/// - `headerByHash(hash)`, returning the RLP encoded header.
+/// - `blockInfoByNumber(number)`, returning the [`BlockInfo`].
+/// - `receiptsByHash(hash)`, returning the EIP-2718 encoded receipts of the block.
+/// - `blockInfoAndTransactionsByHash(hash)`, returning the [`BlockInfo`] as `blockInfo` and the
+///   EIP-2718 encoded transactions of the block as `transactions`.
+#[derive(Debug, Clone)]
+pub struct JsChainProvider(JsObject);
+
+impl JsChainProvider {
+    /// Creates a new [`JsChainProvider`] backed by the given JavaScript object.
+    pub const fn new(object: JsValue) -> Self {
+        Self(JsObject::new(object))
+    }
+}
+
+#[async_trait]
+impl ChainProvider for JsChainProvider {
+    type Error = JsProviderError;
+
+    async fn header_by_hash(&mut self, block_hash: B256) -> Result<Header, Self::Error> {
+        const METHOD: &str = "headerByHash";
+        let value = self.0.call(METHOD, vec![hash(block_hash)]).await?;
+        Header::decode(&mut bytes(METHOD, &value)?.as_slice())
+            .map_err(|e| JsProviderError::invalid(METHOD, e))
+    }
+
+    async fn block_info_by_number(&mut self, block_number: u64) -> Result<BlockInfo, Self::Error> {
+        const METHOD: &str = "blockInfoByNumber";
+        let value = self.0.call(METHOD, vec![number(block_number)]).await?;
+        from_js(METHOD, &value)
+    }
+
+    async fn receipts_by_hash(&mut self, block_hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        const METHOD: &str = "receiptsByHash";
+        let value = self.0.call(METHOD, vec![hash(block_hash)]).await?;
+        bytes_list(METHOD, &value)?
+            .into_iter()
+            .map(|receipt| {
+                let envelope = ReceiptEnvelope::decode_2718(&mut receipt.as_slice())
+                    .map_err(|e| JsProviderError::invalid(METHOD, e))?;
+                envelope
+                    .as_receipt()
+                    .cloned()
+                    .ok_or_else(|| JsProviderError::invalid(METHOD, "unsupported receipt type"))
+            })
+            .collect()
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        block_hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        const METHOD: &str = "blockInfoAndTransactionsByHash";
+        let value = self.0.call(METHOD, vec![hash(block_hash)]).await?;
+        let info = from_js(METHOD, &field(METHOD, &value, "blockInfo")?)?;
+        let transactions = bytes_list(METHOD, &field(METHOD, &value, "transactions")?)?
+            .into_iter()
+            .map(|tx| {
+                TxEnvelope::decode_2718(&mut tx.as_slice())
+                    .map_err(|e| JsProviderError::invalid(METHOD, e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((info, transactions))
+    }
+}
+
+/// An [`L2ChainProvider`] backed by a JavaScript object exposing the following async methods:
+/// - `l2BlockInfoByNumber(number)`, returning the [`L2BlockInfo`].
+/// - `blockByNumber(number)`, returning the RLP encoded block.
+/// - `systemConfigByNumber(number)`, returning the [`SystemConfig`] of the block.
+#[derive(Debug, Clone)]
+pub struct JsL2ChainProvider(JsObject);
+
+impl JsL2ChainProvider {
+    /// Creates a new [`JsL2ChainProvider`] backed by the given JavaScript object.
+    pub const fn new(object: JsValue) -> Self {
+        Self(JsObject::new(object))
+    }
+}
+
+#[async_trait]
+impl BatchValidationProvider for JsL2ChainProvider {
+    type Error = JsProviderError;
+
+    async fn l2_block_info_by_number(
+        &mut self,
+        block_number: u64,
+    ) -> Result<L2BlockInfo, Self::Error> {
+        const METHOD: &str = "l2BlockInfoByNumber";
+        let value = self.0.call(METHOD, vec![number(block_number)]).await?;
+        from_js(METHOD, &value)
+    }
+
+    async fn block_by_number(&mut self, block_number: u64) -> Result<OpBlock, Self::Error> {
+        const METHOD: &str = "blockByNumber";
+        let value = self.0.call(METHOD, vec![number(block_number)]).await?;
+        OpBlock::decode(&mut bytes(METHOD, &value)?.as_slice())
+            .map_err(|e| JsProviderError::invalid(METHOD, e))
+    }
+}
+
+#[async_trait]
+impl L2ChainProvider for JsL2ChainProvider {
+    type Error = JsProviderError;
+
+    async fn system_config_by_number(
+        &mut self,
+        block_number: u64,
+        _: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+        const METHOD: &str = "systemConfigByNumber";
+        let value = self.0.call(METHOD, vec![number(block_number)]).await?;
+        from_js(METHOD, &value)
+    }
+}
+
+/// A [`BlobProvider`] backed by a JavaScript object exposing an async `blobs(blockInfo, hashes)`
+/// method, returning the blobs with the given `{ index, hash }` versioned hashes in order.
+///
+/// The JavaScript provider must check the blobs against their versioned hashes, as the KZG
+/// commitments of the blobs are not verified by the pipeline.
+#[derive(Debug, Clone)]
+pub struct JsBlobProvider(JsObject);
+
+impl JsBlobProvider {
+    /// Creates a new [`JsBlobProvider`] backed by the given JavaScript object.
+    pub const fn new(object: JsValue) -> Self {
+        Self(JsObject::new(object))
+    }
+}
+
+#[async_trait]
+impl BlobProvider for JsBlobProvider {
+    type Error = JsProviderError;
+
+    async fn get_and_validate_blobs(
+        &mut self,
+        block_ref: &BlockInfo,
+        blob_hashes: &[alloy_eips::eip4844::IndexedBlobHash],
+    ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        const METHOD: &str = "blobs";
+        let args = to_js(block_ref)
+            .and_then(|block_ref| Ok(vec![block_ref, to_js(&blob_hashes)?]))
+            .map_err(|_| JsProviderError::invalid(METHOD, "unserializable arguments"))?;
+        let value = self.0.call(METHOD, args).await?;
+        let blobs = bytes_list(METHOD, &value)?;
+        if blobs.len() != blob_hashes.len() {
+            return Err(JsProviderError::invalid(METHOD, "unexpected number of blobs"));
+        }
+        blobs
+            .into_iter()
+            .map(|blob| {
+                Blob::try_from(blob.as_slice())
+                    .map(Box::new)
+                    .map_err(|_| JsProviderError::invalid(METHOD, "invalid blob length"))
+            })
+            .collect()
+    }
+}
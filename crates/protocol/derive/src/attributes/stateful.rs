@@ -1,8 +1,8 @@
 //! The [`AttributesBuilder`] and it's default implementation.
 
 use crate::{
-    AttributesBuilder, BuilderError, ChainProvider, L2ChainProvider, PipelineEncodingError,
-    PipelineError, PipelineErrorKind, PipelineResult,
+    AttributesBuilder, AttributesPostProcessor, BuilderError, ChainProvider, L2ChainProvider,
+    PipelineEncodingError, PipelineError, PipelineErrorKind, PipelineResult,
 };
 use alloc::{boxed::Box, fmt::Debug, string::ToString, sync::Arc, vec, vec::Vec};
 use alloy_consensus::{Eip658Value, Receipt};
@@ -19,11 +19,15 @@ use kona_protocol::{
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
 /// A stateful implementation of the [`AttributesBuilder`].
+///
+/// The prepared attributes are passed through an [`AttributesPostProcessor`] before being
+/// returned. By default, this is the no-op `()` post-processor.
 #[derive(Debug, Default)]
-pub struct StatefulAttributesBuilder<L1P, L2P>
+pub struct StatefulAttributesBuilder<L1P, L2P, PP = ()>
 where
     L1P: ChainProvider + Debug,
     L2P: L2ChainProvider + Debug,
+    PP: AttributesPostProcessor,
 {
     /// The rollup config.
     rollup_cfg: Arc<RollupConfig>,
@@ -33,6 +37,8 @@ where
     config_fetcher: L2P,
     /// The L1 receipts fetcher.
     receipts_fetcher: L1P,
+    /// The post-processor applied to prepared attributes.
+    post_processor: PP,
 }
 
 impl<L1P, L2P> StatefulAttributesBuilder<L1P, L2P>
@@ -52,15 +58,54 @@ where
             l1_cfg,
             config_fetcher: sys_cfg_fetcher,
             receipts_fetcher: receipts,
+            post_processor: (),
+        }
+    }
+
+    /// Create a new [`StatefulAttributesBuilder`] for the L2 chain with the given chain ID,
+    /// loading its rollup config and L1 chain config from the superchain registry.
+    ///
+    /// Returns [`None`] if either config is missing from the registry.
+    #[cfg(feature = "registry")]
+    pub fn from_chain_id(l2_chain_id: u64, sys_cfg_fetcher: L2P, receipts: L1P) -> Option<Self> {
+        let rollup_cfg = kona_registry::rollup_config_by_chain_id(l2_chain_id)?;
+        let l1_cfg = kona_registry::L1_CONFIGS.get(&rollup_cfg.l1_chain_id)?;
+        Some(Self::new(
+            Arc::new(rollup_cfg.clone()),
+            Arc::new(l1_cfg.clone()),
+            sys_cfg_fetcher,
+            receipts,
+        ))
+    }
+}
+
+impl<L1P, L2P, PP> StatefulAttributesBuilder<L1P, L2P, PP>
+where
+    L1P: ChainProvider + Debug,
+    L2P: L2ChainProvider + Debug,
+    PP: AttributesPostProcessor,
+{
+    /// Sets the [`AttributesPostProcessor`] applied to prepared attributes.
+    pub fn with_post_processor<P>(self, post_processor: P) -> StatefulAttributesBuilder<L1P, L2P, P>
+    where
+        P: AttributesPostProcessor,
+    {
+        StatefulAttributesBuilder {
+            rollup_cfg: self.rollup_cfg,
+            l1_cfg: self.l1_cfg,
+            config_fetcher: self.config_fetcher,
+            receipts_fetcher: self.receipts_fetcher,
+            post_processor,
         }
     }
 }
 
 #[async_trait]
-impl<L1P, L2P> AttributesBuilder for StatefulAttributesBuilder<L1P, L2P>
+impl<L1P, L2P, PP> AttributesBuilder for StatefulAttributesBuilder<L1P, L2P, PP>
 where
     L1P: ChainProvider + Debug + Send,
     L2P: L2ChainProvider + Debug + Send,
+    PP: AttributesPostProcessor,
 {
     async fn prepare_payload_attributes(
         &mut self,
@@ -197,7 +242,7 @@ where
             parent_beacon_root = Some(l1_header.parent_beacon_block_root.unwrap_or_default());
         }
 
-        Ok(OpPayloadAttributes {
+        let mut attributes = OpPayloadAttributes {
             payload_attributes: PayloadAttributes {
                 timestamp: next_l2_time,
                 prev_randao: l1_header.mix_hash,
@@ -220,7 +265,10 @@ where
                 .is_jovian_active(next_l2_time)
                 .then(|| sys_config.min_base_fee.unwrap_or_default()), /* Default to zero if not
                                                                         * set at Jovian */
-        })
+        };
+        self.post_processor.post_process(&mut attributes, l2_parent, epoch).await?;
+
+        Ok(attributes)
     }
 }
 
@@ -551,6 +599,78 @@ mod tests {
         assert_eq!(payload.transactions.unwrap().len(), 1);
     }
 
+    /// Appends a fixed transaction to the prepared attributes.
+    #[derive(Debug)]
+    struct AppendTx(Bytes);
+
+    #[async_trait]
+    impl AttributesPostProcessor for AppendTx {
+        async fn post_process(
+            &mut self,
+            attributes: &mut OpPayloadAttributes,
+            _: L2BlockInfo,
+            _: BlockNumHash,
+        ) -> PipelineResult<()> {
+            attributes.transactions.get_or_insert_default().push(self.0.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_with_post_processor() {
+        let block_time = 10;
+        let timestamp = 100;
+        let cfg = Arc::new(RollupConfig { block_time, ..Default::default() });
+        let l1_cfg = Arc::new(L1Config::sepolia().into());
+        let l2_number = 1;
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(l2_number, SystemConfig::default());
+        let mut provider = TestChainProvider::default();
+        let header = Header { timestamp, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.insert_header(hash, header);
+        let injected = Bytes::from_static(&[0x7e, 0x01]);
+        let mut builder = StatefulAttributesBuilder::new(cfg, l1_cfg, fetcher, provider)
+            .with_post_processor(AppendTx(injected.clone()));
+        let epoch = BlockNumHash { hash, number: l2_number };
+        let l2_parent = L2BlockInfo {
+            block_info: BlockInfo {
+                hash: B256::ZERO,
+                number: l2_number,
+                timestamp,
+                parent_hash: hash,
+            },
+            l1_origin: BlockNumHash { hash, number: l2_number },
+            seq_num: 0,
+        };
+        let payload = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        let txs = payload.transactions.unwrap();
+        assert_eq!(txs.len(), 2);
+        assert_eq!(txs[1], injected);
+    }
+
+    #[test]
+    #[cfg(feature = "registry")]
+    fn test_from_chain_id() {
+        let builder = StatefulAttributesBuilder::from_chain_id(
+            10,
+            TestSystemConfigL2Fetcher::default(),
+            TestChainProvider::default(),
+        )
+        .unwrap();
+        assert_eq!(builder.rollup_cfg.l2_chain_id.id(), 10);
+        assert_eq!(builder.l1_cfg.chain_id, 1);
+
+        assert!(
+            StatefulAttributesBuilder::from_chain_id(
+                u64::MAX,
+                TestSystemConfigL2Fetcher::default(),
+                TestChainProvider::default(),
+            )
+            .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn test_prepare_payload_with_ecotone() {
         let block_time = 2;
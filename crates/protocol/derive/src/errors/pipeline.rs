@@ -270,6 +270,31 @@ pub enum PipelineError {
     /// It indicates a protocol version mismatch or configuration issue.
     #[error("Unsupported signal")]
     UnsupportedSignal,
+    /// The data buffered in the pipeline exceeds its memory budget.
+    ///
+    /// This error is returned by the [`L1Retrieval`] stage instead of pulling new L1 data while
+    /// the buffers of the pipeline hold more than the budget of its [`PipelineMemory`] handle.
+    ///
+    /// # Recovery
+    /// Retry once the downstream stages have drained their buffers. If the [`ChannelProvider`]
+    /// has no channel left to hand downstream, nothing can drain, and the error is escalated to a
+    /// critical error, as the budget is too small for the pipeline to make progress.
+    ///
+    /// [`L1Retrieval`]: crate::stages::L1Retrieval
+    /// [`ChannelProvider`]: crate::stages::ChannelProvider
+    /// [`PipelineMemory`]: crate::PipelineMemory
+    #[error("Memory budget exceeded")]
+    MemoryBudgetExceeded,
+    /// A stage failed to allocate memory for the data it read.
+    ///
+    /// This error is returned instead of aborting when an allocation fails, e.g. while
+    /// decompressing or decoding channel data on memory-constrained targets.
+    ///
+    /// # Recovery
+    /// The data itself is not invalid, so it must not be skipped. This error should be
+    /// treated as critical.
+    #[error("Failed to allocate memory for {0} elements")]
+    AllocationFailed(usize),
 }
 
 impl PipelineError {
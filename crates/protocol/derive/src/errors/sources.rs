@@ -21,6 +21,20 @@ pub enum BlobDecodingError {
     MissingData,
 }
 
+impl From<kona_protocol::BlobDecodingError> for BlobDecodingError {
+    fn from(err: kona_protocol::BlobDecodingError) -> Self {
+        match err {
+            kona_protocol::BlobDecodingError::InvalidEncodingVersion(_) => {
+                Self::InvalidEncodingVersion
+            }
+            kona_protocol::BlobDecodingError::InvalidLength(_) => Self::InvalidLength,
+            kona_protocol::BlobDecodingError::InvalidFieldElement(_) |
+            kona_protocol::BlobDecodingError::TrailingData(_) |
+            kona_protocol::BlobDecodingError::NonZeroPadding(_) => Self::InvalidFieldElement,
+        }
+    }
+}
+
 /// An error returned by the [`BlobProviderError`].
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum BlobProviderError {
@@ -62,6 +76,16 @@ mod tests {
         assert!(err.source().is_some());
     }
 
+    #[test]
+    fn test_from_protocol_blob_decoding_error() {
+        let err: BlobDecodingError = kona_protocol::BlobDecodingError::NonZeroPadding(4096).into();
+        assert_eq!(err, BlobDecodingError::InvalidFieldElement);
+
+        let err: BlobDecodingError =
+            kona_protocol::BlobDecodingError::InvalidLength(1 << 20).into();
+        assert_eq!(err, BlobDecodingError::InvalidLength);
+    }
+
     #[test]
     fn test_from_blob_provider_error() {
         let err: PipelineErrorKind = BlobProviderError::SlotDerivation.into();
@@ -9,6 +9,12 @@
 
 extern crate alloc;
 
+#[cfg(all(feature = "metrics", target_arch = "wasm32", target_os = "unknown"))]
+compile_error!(
+    "the `metrics` feature times the pipeline stages with `std::time::Instant`, which is not \
+     supported on `wasm32-unknown-unknown`"
+);
+
 #[macro_use]
 extern crate tracing;
 
@@ -28,26 +34,38 @@ pub use pipeline::{
     L1RetrievalStage, PipelineBuilder, PolledAttributesQueueStage,
 };
 
+mod providers;
+pub use providers::CachedL2ChainProvider;
+
 mod sources;
-pub use sources::{BlobData, BlobSource, CalldataSource, EthereumDataSource};
+pub use sources::{
+    ALT_DA_DERIVATION_VERSION, BlobData, BlobSource, CalldataSource, CommitmentType,
+    DispatchDataSource, EthereumDataSource,
+};
 
 mod stages;
 pub use stages::{
     AttributesQueue, BatchProvider, BatchQueue, BatchStream, BatchStreamProvider, BatchValidator,
-    ChannelAssembler, ChannelBank, ChannelProvider, ChannelReader, ChannelReaderProvider,
-    FrameQueue, FrameQueueProvider, IndexedTraversal, L1Retrieval, L1RetrievalProvider,
-    NextBatchProvider, NextFrameProvider, PollingTraversal, TraversalStage,
+    ChannelAssembler, ChannelBank, ChannelOrdering, ChannelOrderingPolicy, ChannelProvider,
+    ChannelReader, ChannelReaderProvider, FrameQueue, FrameQueueProvider, IndexedTraversal,
+    L1Retrieval, L1RetrievalProvider, NextBatchProvider, NextFrameProvider, PollingTraversal,
+    TraversalStage,
 };
 
 mod traits;
 pub use traits::{
-    AttributesBuilder, AttributesProvider, BatchValidationProviderDerive, BlobProvider,
-    ChainProvider, DataAvailabilityProvider, L2ChainProvider, NextAttributes, OriginAdvancer,
-    OriginProvider, Pipeline, ResetProvider, SignalReceiver,
+    AltDaProvider, AttributesBuilder, AttributesPostProcessor, AttributesProvider,
+    BatchValidationProviderDerive, BlobProvider, ChainProvider, DataAvailabilityProvider,
+    L2ChainProvider, NextAttributes, OriginAdvancer, OriginProvider, Pipeline, ResetProvider,
+    SignalReceiver,
 };
 
 mod types;
-pub use types::{ActivationSignal, PipelineResult, ResetSignal, Signal, StepResult};
+pub use types::{
+    ActivationSignal, BatcherTxRef, ChannelCloseReason, MemoryComponent, PipelineEvent,
+    PipelineEventSink, PipelineEvents, PipelineMemory, PipelineResult, ResetSignal, Signal,
+    StepResult,
+};
 
 mod metrics;
 pub use metrics::Metrics;
@@ -26,6 +26,12 @@ impl Metrics {
     /// Identifier for the frame queue buffer memory overhead gauge.
     pub const PIPELINE_FRAME_QUEUE_MEM: &str = "kona_derive_frame_queue_mem";
 
+    /// Identifier for the gauge that tracks frames arriving out of channel order.
+    pub const PIPELINE_FRAME_QUEUE_REORDERED: &str = "kona_derive_frame_queue_reordered_frames";
+
+    /// Identifier for the gauge that tracks frames dropped for violating the channel ordering.
+    pub const PIPELINE_FRAME_QUEUE_DROPPED: &str = "kona_derive_frame_queue_dropped_frames";
+
     /// Identifier for the number of channels held in the pipeline.
     pub const PIPELINE_CHANNEL_BUFFER: &str = "kona_derive_channel_buffer";
 
@@ -44,6 +50,13 @@ impl Metrics {
     /// Identifier for the batch stream stage batch memory overhead gauge.
     pub const PIPELINE_BATCH_MEM: &str = "kona_derive_batch_mem";
 
+    /// Identifier for the gauge that tracks the bytes buffered by each accounted pipeline
+    /// component.
+    pub const PIPELINE_MEMORY_USAGE: &str = "kona_derive_memory_usage";
+
+    /// Identifier for the gauge that tracks the memory budget of the pipeline.
+    pub const PIPELINE_MEMORY_BUDGET: &str = "kona_derive_memory_budget";
+
     /// Identifier for the size of batches read by the channel reader.
     pub const PIPELINE_READ_BATCHES: &str = "kona_derive_read_batches";
 
@@ -101,6 +114,9 @@ impl Metrics {
     /// Gauge that tracks the latest decompressed batch type.
     pub const PIPELINE_LATEST_DECOMPRESSED_BATCH_TYPE: &str =
         "kona_derive_latest_decompressed_batch_type";
+
+    /// Identifier for the counter of the lookups served by the L2 chain provider cache.
+    pub const PIPELINE_L2_CHAIN_CACHE: &str = "kona_derive_l2_chain_cache_lookups";
 }
 
 impl Metrics {
@@ -182,6 +198,14 @@ impl Metrics {
             Self::PIPELINE_FRAME_QUEUE_MEM,
             "The memory size of frames held in the frame queue"
         );
+        metrics::describe_gauge!(
+            Self::PIPELINE_FRAME_QUEUE_REORDERED,
+            "The number of frames that arrived out of channel order"
+        );
+        metrics::describe_gauge!(
+            Self::PIPELINE_FRAME_QUEUE_DROPPED,
+            "The number of frames dropped for violating the channel ordering"
+        );
         metrics::describe_gauge!(
             Self::PIPELINE_CHANNEL_BUFFER,
             "The number of channels in the channel assembler stage"
@@ -206,6 +230,14 @@ impl Metrics {
             Self::PIPELINE_BATCH_MEM,
             "The memory size of batches held in the batch stream stage"
         );
+        metrics::describe_gauge!(
+            Self::PIPELINE_MEMORY_USAGE,
+            "The number of bytes buffered by each accounted pipeline component"
+        );
+        metrics::describe_gauge!(
+            Self::PIPELINE_MEMORY_BUDGET,
+            "The maximum number of bytes buffered by the pipeline, or 0 if unbounded"
+        );
         metrics::describe_gauge!(
             Self::PIPELINE_STEPS,
             "The total number of pipeline steps on the derivation pipeline"
@@ -226,6 +258,10 @@ impl Metrics {
             Self::PIPELINE_PAYLOAD_ATTRIBUTES_BUFFER,
             "The number of payload attributes buffered in the pipeline"
         );
+        metrics::describe_counter!(
+            Self::PIPELINE_L2_CHAIN_CACHE,
+            "The number of L2 chain provider lookups that hit or missed the cache"
+        );
     }
 
     /// Initializes metrics to 0 so they can be queried immediately.
@@ -258,7 +294,19 @@ impl Metrics {
         kona_macros::set!(gauge, Self::PIPELINE_READ_BATCHES, "type", "span", 0);
 
         // Cumulative counters start at zero.
+        kona_macros::set!(
+            gauge,
+            Self::PIPELINE_FRAME_QUEUE_REORDERED,
+            "ordering",
+            "interleaved",
+            0
+        );
+        kona_macros::set!(gauge, Self::PIPELINE_FRAME_QUEUE_REORDERED, "ordering", "strict", 0);
+        kona_macros::set!(gauge, Self::PIPELINE_FRAME_QUEUE_DROPPED, "ordering", "interleaved", 0);
+        kona_macros::set!(gauge, Self::PIPELINE_FRAME_QUEUE_DROPPED, "ordering", "strict", 0);
         kona_macros::set!(gauge, Self::PIPELINE_STEPS, 0);
+        kona_macros::set!(counter, Self::PIPELINE_L2_CHAIN_CACHE, "result", "hit", 0);
+        kona_macros::set!(counter, Self::PIPELINE_L2_CHAIN_CACHE, "result", "miss", 0);
         kona_macros::set!(gauge, Self::PIPELINE_PREPARED_ATTRIBUTES, 0);
 
         // All buffers can be zeroed out since they are expected to return to zero.
@@ -266,5 +314,8 @@ impl Metrics {
         kona_macros::set!(gauge, Self::PIPELINE_CHANNEL_BUFFER, 0);
         kona_macros::set!(gauge, Self::PIPELINE_FRAME_QUEUE_BUFFER, 0);
         kona_macros::set!(gauge, Self::PIPELINE_PAYLOAD_ATTRIBUTES_BUFFER, 0);
+        kona_macros::set!(gauge, Self::PIPELINE_MEMORY_USAGE, "component", "channel_provider", 0);
+        kona_macros::set!(gauge, Self::PIPELINE_MEMORY_USAGE, "component", "batch_stream", 0);
+        kona_macros::set!(gauge, Self::PIPELINE_MEMORY_BUDGET, 0);
     }
 }
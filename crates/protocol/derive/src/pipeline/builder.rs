@@ -1,10 +1,10 @@
 //! Contains the `PipelineBuilder` object that is used to build a `DerivationPipeline`.
 
 use crate::{
-    AttributesBuilder, AttributesQueue, BatchProvider, BatchStream, ChainProvider, ChannelProvider,
-    ChannelReader, DataAvailabilityProvider, DerivationPipeline, FrameQueue,
-    IndexedAttributesQueueStage, IndexedTraversal, L1Retrieval, L2ChainProvider,
-    PolledAttributesQueueStage, PollingTraversal,
+    AttributesBuilder, AttributesQueue, BatchProvider, BatchStream, ChainProvider,
+    ChannelOrderingPolicy, ChannelProvider, ChannelReader, DataAvailabilityProvider,
+    DerivationPipeline, FrameQueue, IndexedAttributesQueueStage, IndexedTraversal, L1Retrieval,
+    L2ChainProvider, PipelineEvents, PipelineMemory, PolledAttributesQueueStage, PollingTraversal,
 };
 use alloc::sync::Arc;
 use core::fmt::Debug;
@@ -26,6 +26,9 @@ where
     builder: Option<B>,
     origin: Option<BlockInfo>,
     rollup_config: Option<Arc<RollupConfig>>,
+    ordering_policy: ChannelOrderingPolicy,
+    events: PipelineEvents,
+    memory: PipelineMemory,
 }
 
 impl<B, P, T, D> Default for PipelineBuilder<B, P, T, D>
@@ -43,6 +46,9 @@ where
             builder: None,
             origin: None,
             rollup_config: None,
+            ordering_policy: ChannelOrderingPolicy::default(),
+            events: PipelineEvents::default(),
+            memory: PipelineMemory::default(),
         }
     }
 }
@@ -95,6 +101,30 @@ where
         self
     }
 
+    /// Sets the channel ordering policy for the pipeline.
+    ///
+    /// Defaults to [`ChannelOrderingPolicy::Hardfork`].
+    pub const fn channel_ordering_policy(mut self, policy: ChannelOrderingPolicy) -> Self {
+        self.ordering_policy = policy;
+        self
+    }
+
+    /// Sets the [`PipelineEvents`] handle that the pipeline and its stages emit events to.
+    ///
+    /// By default, events are discarded.
+    pub fn events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Sets the [`PipelineMemory`] handle that the buffers of the pipeline are accounted against.
+    ///
+    /// By default, the memory usage is tracked without a budget.
+    pub fn memory(mut self, memory: PipelineMemory) -> Self {
+        self.memory = memory;
+        self
+    }
+
     /// Builds a derivation pipeline with the [`PolledAttributesQueueStage`].
     pub fn build_polled(self) -> DerivationPipeline<PolledAttributesQueueStage<D, P, T, B>, T> {
         self.into()
@@ -125,19 +155,28 @@ where
         // Compose the stage stack.
         let mut l1_traversal = PollingTraversal::new(chain_provider, Arc::clone(&rollup_config));
         l1_traversal.block = Some(builder.origin.expect("origin must be set"));
-        let l1_retrieval = L1Retrieval::new(l1_traversal, dap_source);
-        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config));
-        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue);
-        let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config));
+        let l1_retrieval =
+            L1Retrieval::new(l1_traversal, dap_source).with_memory(builder.memory.clone());
+        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config))
+            .with_ordering_policy(builder.ordering_policy)
+            .with_events(builder.events.clone());
+        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue)
+            .with_ordering_policy(builder.ordering_policy)
+            .with_events(builder.events.clone())
+            .with_memory(builder.memory.clone());
+        let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config))
+            .with_events(builder.events.clone());
         let batch_stream =
-            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone());
+            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone())
+                .with_memory(builder.memory);
         let batch_provider =
-            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone());
+            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone())
+                .with_events(builder.events.clone());
         let attributes =
             AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder);
 
         // Create the pipeline.
-        Self::new(attributes, rollup_config, l2_chain_provider)
+        Self::new(attributes, rollup_config, l2_chain_provider).with_events(builder.events)
     }
 }
 
@@ -160,18 +199,27 @@ where
         // Compose the stage stack.
         let mut l1_traversal = IndexedTraversal::new(chain_provider, Arc::clone(&rollup_config));
         l1_traversal.block = Some(builder.origin.expect("origin must be set"));
-        let l1_retrieval = L1Retrieval::new(l1_traversal, dap_source);
-        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config));
-        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue);
-        let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config));
+        let l1_retrieval =
+            L1Retrieval::new(l1_traversal, dap_source).with_memory(builder.memory.clone());
+        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config))
+            .with_ordering_policy(builder.ordering_policy)
+            .with_events(builder.events.clone());
+        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue)
+            .with_ordering_policy(builder.ordering_policy)
+            .with_events(builder.events.clone())
+            .with_memory(builder.memory.clone());
+        let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config))
+            .with_events(builder.events.clone());
         let batch_stream =
-            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone());
+            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone())
+                .with_memory(builder.memory);
         let batch_provider =
-            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone());
+            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone())
+                .with_events(builder.events.clone());
         let attributes =
             AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder);
 
         // Create the pipeline.
-        Self::new(attributes, rollup_config, l2_chain_provider)
+        Self::new(attributes, rollup_config, l2_chain_provider).with_events(builder.events)
     }
 }
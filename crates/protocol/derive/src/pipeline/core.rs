@@ -2,8 +2,8 @@
 
 use crate::{
     ActivationSignal, L2ChainProvider, NextAttributes, OriginAdvancer, OriginProvider, Pipeline,
-    PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal, SignalReceiver,
-    StepResult,
+    PipelineError, PipelineErrorKind, PipelineEvent, PipelineEvents, PipelineResult, ResetSignal,
+    Signal, SignalReceiver, StepResult,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -28,6 +28,8 @@ where
     pub rollup_config: Arc<RollupConfig>,
     /// The L2 Chain Provider used to fetch the system config on reset.
     pub l2_chain_provider: P,
+    /// The handle that origin and reset events are emitted to.
+    pub events: PipelineEvents,
 }
 
 impl<S, P> DerivationPipeline<S, P>
@@ -41,7 +43,19 @@ where
         rollup_config: Arc<RollupConfig>,
         l2_chain_provider: P,
     ) -> Self {
-        Self { attributes, prepared: VecDeque::new(), rollup_config, l2_chain_provider }
+        Self {
+            attributes,
+            prepared: VecDeque::new(),
+            rollup_config,
+            l2_chain_provider,
+            events: PipelineEvents::none(),
+        }
+    }
+
+    /// Sets the [`PipelineEvents`] handle that origin and reset events are emitted to.
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
     }
 }
 
@@ -95,6 +109,9 @@ where
         match signal {
             mut s @ Signal::Reset(ResetSignal { l2_safe_head, .. }) |
             mut s @ Signal::Activation(ActivationSignal { l2_safe_head, .. }) => {
+                if matches!(s, Signal::Reset(_)) {
+                    self.l2_chain_provider.invalidate_cache();
+                }
                 let system_config = self
                     .l2_chain_provider
                     .system_config_by_number(
@@ -116,13 +133,16 @@ where
                     }
                 }
             }
-            Signal::FlushChannel => {
+            Signal::FlushChannel | Signal::FlushChannelById(_) => {
                 self.attributes.signal(signal).await?;
             }
             Signal::ProvideBlock(_) => {
                 self.attributes.signal(signal).await?;
             }
         }
+        if let Signal::Reset(ResetSignal { l2_safe_head, l1_origin, .. }) = signal {
+            self.events.emit(PipelineEvent::Reset { l2_safe_head, l1_origin });
+        }
         kona_macros::inc!(
             gauge,
             crate::metrics::Metrics::PIPELINE_SIGNALS,
@@ -209,6 +229,9 @@ where
                     if let Err(e) = self.attributes.advance_origin().await {
                         return StepResult::OriginAdvanceErr(e);
                     }
+                    if let Some(origin) = self.origin() {
+                        self.events.emit(PipelineEvent::OriginAdvanced { origin });
+                    }
                     StepResult::AdvancedOrigin
                 }
                 PipelineErrorKind::Temporary(_) => {
@@ -253,6 +276,7 @@ mod tests {
             parent: Default::default(),
             derived_from: Default::default(),
             is_last_in_span: false,
+            channel_id: None,
         }
     }
 
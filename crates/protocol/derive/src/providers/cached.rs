@@ -0,0 +1,282 @@
+//! An [`L2ChainProvider`] memoizing the L2 chain data it fetches.
+
+use crate::{L2ChainProvider, PipelineErrorKind};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{BatchValidationProvider, L2BlockInfo};
+use op_alloy_consensus::OpBlock;
+use spin::Mutex;
+
+/// The L2 chain data memoized by a [`CachedL2ChainProvider`], keyed by block number.
+#[derive(Debug, Default)]
+struct L2ChainCache {
+    /// The maximum number of blocks kept in each map.
+    capacity: usize,
+    /// The memoized [`L2BlockInfo`]s.
+    block_infos: BTreeMap<u64, L2BlockInfo>,
+    /// The memoized [`OpBlock`]s, with their hashes.
+    blocks: BTreeMap<u64, (B256, OpBlock)>,
+    /// The memoized [`SystemConfig`]s.
+    system_configs: BTreeMap<u64, SystemConfig>,
+}
+
+impl L2ChainCache {
+    /// Drops the memoized data at and above the block with the given number if the cached hash of
+    /// the block differs from `hash`, as the L2 chain was reorged since it was memoized.
+    fn check_hash(&mut self, number: u64, hash: B256) {
+        let cached = self
+            .block_infos
+            .get(&number)
+            .map(|info| info.block_info.hash)
+            .or_else(|| self.blocks.get(&number).map(|(hash, _)| *hash));
+        if cached.is_some_and(|cached| cached != hash) {
+            self.block_infos.split_off(&number);
+            self.blocks.split_off(&number);
+            self.system_configs.split_off(&number);
+        }
+    }
+
+    /// Memoizes the [`L2BlockInfo`] of a block.
+    fn insert_block_info(&mut self, info: L2BlockInfo) {
+        let number = info.block_info.number;
+        self.check_hash(number, info.block_info.hash);
+        self.block_infos.insert(number, info);
+        Self::evict(&mut self.block_infos, self.capacity);
+    }
+
+    /// Memoizes a block.
+    fn insert_block(&mut self, number: u64, block: OpBlock) {
+        let hash = block.header.hash_slow();
+        self.check_hash(number, hash);
+        self.blocks.insert(number, (hash, block));
+        Self::evict(&mut self.blocks, self.capacity);
+    }
+
+    /// Memoizes the [`SystemConfig`] of a block.
+    fn insert_system_config(&mut self, number: u64, system_config: SystemConfig) {
+        self.system_configs.insert(number, system_config);
+        Self::evict(&mut self.system_configs, self.capacity);
+    }
+
+    /// Evicts the lowest blocks of the map until it holds at most `capacity` blocks. Batch
+    /// validation moves up the L2 chain, so the lowest blocks are the least likely to be queried
+    /// again.
+    fn evict<V>(map: &mut BTreeMap<u64, V>, capacity: usize) {
+        while map.len() > capacity {
+            map.pop_first();
+        }
+    }
+
+    /// Drops all memoized data.
+    fn clear(&mut self) {
+        self.block_infos.clear();
+        self.blocks.clear();
+        self.system_configs.clear();
+    }
+}
+
+/// An [`L2ChainProvider`] memoizing the [`L2BlockInfo`]s, blocks and [`SystemConfig`]s fetched
+/// from the inner provider.
+///
+/// The stages of the pipeline hold clones of the L2 provider, and the batch stages repeatedly
+/// query the same parent blocks and system configs while validating span batches. Clones of a
+/// [`CachedL2ChainProvider`] share the same cache, so each block is only fetched once across the
+/// pipeline.
+///
+/// The cache is keyed by block number. A block memoized with a different hash than the one
+/// fetched for its number drops the data memoized at and above it, and the whole cache is
+/// invalidated when the pipeline is reset, as the L2 chain may have been reorged.
+#[derive(Debug, Clone)]
+pub struct CachedL2ChainProvider<P> {
+    /// The inner provider.
+    inner: P,
+    /// The cache shared by the clones of the provider.
+    cache: Arc<Mutex<L2ChainCache>>,
+}
+
+impl<P> CachedL2ChainProvider<P> {
+    /// Creates a new [`CachedL2ChainProvider`] memoizing up to `capacity` blocks of each kind of
+    /// data fetched from the `inner` provider.
+    pub fn new(inner: P, capacity: usize) -> Self {
+        Self { inner, cache: Arc::new(Mutex::new(L2ChainCache { capacity, ..Default::default() })) }
+    }
+
+    /// Returns a reference to the inner provider.
+    pub const fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Drops all data memoized by the provider and its clones.
+    pub fn clear(&self) {
+        self.cache.lock().clear();
+    }
+}
+
+#[async_trait]
+impl<P> BatchValidationProvider for CachedL2ChainProvider<P>
+where
+    P: BatchValidationProvider + Send + Sync,
+{
+    type Error = P::Error;
+
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        if let Some(info) = self.cache.lock().block_infos.get(&number) {
+            kona_macros::inc!(
+                counter,
+                crate::metrics::Metrics::PIPELINE_L2_CHAIN_CACHE,
+                "result" => "hit"
+            );
+            return Ok(*info);
+        }
+        kona_macros::inc!(
+            counter,
+            crate::metrics::Metrics::PIPELINE_L2_CHAIN_CACHE,
+            "result" => "miss"
+        );
+
+        let info = self.inner.l2_block_info_by_number(number).await?;
+        self.cache.lock().insert_block_info(info);
+        Ok(info)
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        if let Some((_, block)) = self.cache.lock().blocks.get(&number) {
+            kona_macros::inc!(
+                counter,
+                crate::metrics::Metrics::PIPELINE_L2_CHAIN_CACHE,
+                "result" => "hit"
+            );
+            return Ok(block.clone());
+        }
+        kona_macros::inc!(
+            counter,
+            crate::metrics::Metrics::PIPELINE_L2_CHAIN_CACHE,
+            "result" => "miss"
+        );
+
+        let block = self.inner.block_by_number(number).await?;
+        self.cache.lock().insert_block(number, block.clone());
+        Ok(block)
+    }
+}
+
+#[async_trait]
+impl<P> L2ChainProvider for CachedL2ChainProvider<P>
+where
+    P: L2ChainProvider + Send + Sync,
+    <P as BatchValidationProvider>::Error: Into<PipelineErrorKind>,
+{
+    type Error = <P as L2ChainProvider>::Error;
+
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+        if let Some(system_config) = self.cache.lock().system_configs.get(&number) {
+            kona_macros::inc!(
+                counter,
+                crate::metrics::Metrics::PIPELINE_L2_CHAIN_CACHE,
+                "result" => "hit"
+            );
+            return Ok(*system_config);
+        }
+        kona_macros::inc!(
+            counter,
+            crate::metrics::Metrics::PIPELINE_L2_CHAIN_CACHE,
+            "result" => "miss"
+        );
+
+        let system_config = self.inner.system_config_by_number(number, rollup_config).await?;
+        self.cache.lock().insert_system_config(number, system_config);
+        Ok(system_config)
+    }
+
+    fn invalidate_cache(&mut self) {
+        self.clear();
+        self.inner.invalidate_cache();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestL2ChainProvider;
+    use alloy_consensus::Header;
+    use kona_protocol::BlockInfo;
+
+    fn block_info(number: u64, hash: u8) -> L2BlockInfo {
+        L2BlockInfo {
+            block_info: BlockInfo { number, hash: B256::repeat_byte(hash), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_provider_memoizes_across_clones() {
+        let inner = TestL2ChainProvider {
+            blocks: vec![block_info(1, 1), block_info(2, 2)],
+            system_configs: [(1, SystemConfig { gas_limit: 1, ..Default::default() })]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let mut provider = CachedL2ChainProvider::new(inner, 16);
+        let mut clone = provider.clone();
+
+        assert_eq!(provider.l2_block_info_by_number(1).await.unwrap(), block_info(1, 1));
+        let cfg = Arc::new(RollupConfig::default());
+        assert_eq!(provider.system_config_by_number(1, cfg.clone()).await.unwrap().gas_limit, 1);
+
+        // The clone is served from the shared cache, even once the inner provider forgot the data.
+        provider.inner.blocks.clear();
+        provider.inner.system_configs.clear();
+        clone.inner.blocks.clear();
+        clone.inner.system_configs.clear();
+        assert_eq!(clone.l2_block_info_by_number(1).await.unwrap(), block_info(1, 1));
+        assert_eq!(clone.system_config_by_number(1, cfg.clone()).await.unwrap().gas_limit, 1);
+        assert!(clone.l2_block_info_by_number(2).await.is_err());
+
+        // Invalidating the cache of one clone invalidates it for all clones.
+        provider.invalidate_cache();
+        assert!(clone.l2_block_info_by_number(1).await.is_err());
+        assert!(clone.system_config_by_number(1, cfg).await.is_err());
+    }
+
+    #[test]
+    fn test_cache_drops_reorged_blocks() {
+        let mut cache = L2ChainCache { capacity: 16, ..Default::default() };
+        for number in 1..=4 {
+            cache.insert_block_info(block_info(number, number as u8));
+            cache.insert_system_config(number, SystemConfig::default());
+        }
+
+        // A different block at height 3 drops the data memoized at and above it.
+        cache.insert_block_info(block_info(3, 0xFF));
+        assert_eq!(cache.block_infos.keys().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(cache.block_infos[&3], block_info(3, 0xFF));
+        assert_eq!(cache.system_configs.keys().copied().collect::<Vec<_>>(), [1, 2]);
+
+        // The same block does not.
+        cache.insert_block_info(block_info(2, 2));
+        assert_eq!(cache.block_infos.len(), 3);
+
+        // Block infos and blocks are checked against each other.
+        let block =
+            OpBlock { header: Header { number: 1, ..Default::default() }, ..Default::default() };
+        cache.insert_block(1, block);
+        assert!(cache.block_infos.is_empty());
+        assert_eq!(cache.blocks.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_evicts_lowest_blocks() {
+        let mut cache = L2ChainCache { capacity: 2, ..Default::default() };
+        for number in 1..=4 {
+            cache.insert_block_info(block_info(number, number as u8));
+        }
+        assert_eq!(cache.block_infos.keys().copied().collect::<Vec<_>>(), [3, 4]);
+    }
+}
@@ -0,0 +1,4 @@
+//! Providers wrapping the data sources of the derivation pipeline.
+
+mod cached;
+pub use cached::CachedL2ChainProvider;
@@ -1,8 +1,8 @@
 //! Blob Data Source
 
 use crate::{
-    BlobData, BlobProvider, BlobProviderError, ChainProvider, DataAvailabilityProvider,
-    PipelineError, PipelineResult,
+    BatcherTxRef, BlobData, BlobProvider, BlobProviderError, ChainProvider,
+    DataAvailabilityProvider, PipelineError, PipelineResult,
 };
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use alloy_consensus::{
@@ -11,6 +11,7 @@ use alloy_consensus::{
 use alloy_eips::eip4844::IndexedBlobHash;
 use alloy_primitives::{Address, Bytes};
 use async_trait::async_trait;
+use kona_genesis::BatchInboxActivation;
 use kona_protocol::BlockInfo;
 
 /// A data iterator that reads from a blob.
@@ -26,8 +27,12 @@ where
     pub blob_fetcher: B,
     /// The address of the batcher contract.
     pub batcher_address: Address,
+    /// Rotations of the batch inbox address, keyed by L1 activation block.
+    pub inbox_schedule: Vec<BatchInboxActivation>,
     /// Data.
     pub data: Vec<BlobData>,
+    /// The batcher transaction of the data most recently returned.
+    pub last_source: Option<BatcherTxRef>,
     /// Whether the source is open.
     pub open: bool,
 }
@@ -39,12 +44,28 @@ where
 {
     /// Creates a new blob source.
     pub const fn new(chain_provider: F, blob_fetcher: B, batcher_address: Address) -> Self {
-        Self { chain_provider, blob_fetcher, batcher_address, data: Vec::new(), open: false }
+        Self {
+            chain_provider,
+            blob_fetcher,
+            batcher_address,
+            inbox_schedule: Vec::new(),
+            data: Vec::new(),
+            last_source: None,
+            open: false,
+        }
+    }
+
+    /// Sets the batch inbox schedule, overriding the batch inbox address and batcher signer from
+    /// each entry's L1 activation block onwards.
+    pub fn with_inbox_schedule(mut self, inbox_schedule: Vec<BatchInboxActivation>) -> Self {
+        self.inbox_schedule = inbox_schedule;
+        self
     }
 
     fn extract_blob_data(
         &self,
         txs: Vec<TxEnvelope>,
+        inbox_address: Address,
         batcher_address: Address,
     ) -> (Vec<BlobData>, Vec<IndexedBlobHash>) {
         let mut index: u64 = 0;
@@ -67,8 +88,9 @@ where
                 _ => continue,
             };
             let Some(to) = tx_kind else { continue };
+            let tx_hash = *tx.tx_hash();
 
-            if to != self.batcher_address {
+            if to != inbox_address {
                 index += blob_hashes.map_or(0, |h| h.len() as u64);
                 continue;
             }
@@ -77,7 +99,11 @@ where
                 continue;
             }
             if tx.tx_type() != TxType::Eip4844 {
-                let blob_data = BlobData { data: None, calldata: Some(calldata.to_vec().into()) };
+                let blob_data = BlobData {
+                    data: None,
+                    calldata: Some(calldata.to_vec().into()),
+                    source: Some(BatcherTxRef { tx_hash, blob_hash: None }),
+                };
                 data.push(blob_data);
                 continue;
             }
@@ -99,7 +125,10 @@ where
             for hash in blob_hashes {
                 let indexed = IndexedBlobHash { hash, index };
                 hashes.push(indexed);
-                data.push(BlobData::default());
+                data.push(BlobData {
+                    source: Some(BatcherTxRef { tx_hash, blob_hash: Some(hash) }),
+                    ..Default::default()
+                });
                 index += 1;
             }
         }
@@ -128,7 +157,14 @@ where
             .await
             .map_err(|e| BlobProviderError::Backend(e.to_string()))?;
 
-        let (mut data, blob_hashes) = self.extract_blob_data(info.1, batcher_address);
+        let (inbox_address, batcher_address) = BatchInboxActivation::resolve(
+            &self.inbox_schedule,
+            block_ref.number,
+            self.batcher_address,
+            batcher_address,
+        );
+        let (mut data, blob_hashes) =
+            self.extract_blob_data(info.1, inbox_address, batcher_address);
 
         // If there are no hashes, set the calldata and return.
         if blob_hashes.is_empty() {
@@ -191,6 +227,7 @@ where
         self.load_blobs(block_ref, batcher_address).await?;
 
         let next_data = self.next_data()?;
+        self.last_source = next_data.source;
         if let Some(c) = next_data.calldata {
             return Ok(c);
         }
@@ -208,8 +245,13 @@ where
 
     fn clear(&mut self) {
         self.data.clear();
+        self.last_source = None;
         self.open = false;
     }
+
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        self.last_source
+    }
 }
 
 #[cfg(test)]
@@ -315,6 +357,15 @@ pub(crate) mod tests {
         source.load_blobs(&BlockInfo::default(), batcher_address).await.unwrap();
         assert!(source.open);
         assert!(!source.data.is_empty());
+
+        // Each blob is attributed to the transaction it was posted in.
+        let tx_hash = *valid_blob_txs()[0].tx_hash();
+        let sources = source.data.iter().map(|data| data.source.unwrap()).collect::<Vec<_>>();
+        let expected = hashes
+            .iter()
+            .map(|hash| BatcherTxRef { tx_hash, blob_hash: Some(*hash) })
+            .collect::<Vec<_>>();
+        assert_eq!(sources, expected);
     }
 
     #[tokio::test]
@@ -330,7 +381,7 @@ pub(crate) mod tests {
     async fn test_open_calldata() {
         let mut source = default_test_blob_source();
         source.open = true;
-        source.data.push(BlobData { data: None, calldata: Some(Bytes::default()) });
+        source.data.push(BlobData { calldata: Some(Bytes::default()), ..Default::default() });
 
         let data = source.next(&BlockInfo::default(), Address::ZERO).await.unwrap();
         assert_eq!(data, Bytes::default());
@@ -340,7 +391,7 @@ pub(crate) mod tests {
     async fn test_open_blob_data_decode_missing_data() {
         let mut source = default_test_blob_source();
         source.open = true;
-        source.data.push(BlobData { data: Some(Bytes::from(&[1; 32])), calldata: None });
+        source.data.push(BlobData { data: Some(Bytes::from(&[1; 32])), ..Default::default() });
 
         let err = source.next(&BlockInfo::default(), Address::ZERO).await.unwrap_err();
         assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Eof)));
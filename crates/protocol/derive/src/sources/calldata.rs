@@ -1,10 +1,11 @@
 //! CallData Source
 
-use crate::{ChainProvider, DataAvailabilityProvider, PipelineError, PipelineResult};
-use alloc::{boxed::Box, collections::VecDeque};
+use crate::{BatcherTxRef, ChainProvider, DataAvailabilityProvider, PipelineError, PipelineResult};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 use alloy_consensus::{Transaction, TxEnvelope, transaction::SignerRecoverable};
-use alloy_primitives::{Address, Bytes};
+use alloy_primitives::{Address, B256, Bytes};
 use async_trait::async_trait;
+use kona_genesis::BatchInboxActivation;
 use kona_protocol::BlockInfo;
 
 /// A data iterator that reads from calldata.
@@ -17,8 +18,14 @@ where
     pub chain_provider: CP,
     /// The batch inbox address.
     pub batch_inbox_address: Address,
+    /// Rotations of the batch inbox address, keyed by L1 activation block.
+    pub inbox_schedule: Vec<BatchInboxActivation>,
     /// Current calldata.
     pub calldata: VecDeque<Bytes>,
+    /// The hashes of the transactions carrying the current calldata.
+    pub tx_hashes: VecDeque<B256>,
+    /// The batcher transaction of the calldata most recently returned.
+    pub last_source: Option<BatcherTxRef>,
     /// Whether the calldata source is open.
     pub open: bool,
 }
@@ -26,7 +33,22 @@ where
 impl<CP: ChainProvider + Send> CalldataSource<CP> {
     /// Creates a new calldata source.
     pub const fn new(chain_provider: CP, batch_inbox_address: Address) -> Self {
-        Self { chain_provider, batch_inbox_address, calldata: VecDeque::new(), open: false }
+        Self {
+            chain_provider,
+            batch_inbox_address,
+            inbox_schedule: Vec::new(),
+            calldata: VecDeque::new(),
+            tx_hashes: VecDeque::new(),
+            last_source: None,
+            open: false,
+        }
+    }
+
+    /// Sets the batch inbox schedule, overriding the batch inbox address and batcher signer from
+    /// each entry's L1 activation block onwards.
+    pub fn with_inbox_schedule(mut self, inbox_schedule: Vec<BatchInboxActivation>) -> Self {
+        self.inbox_schedule = inbox_schedule;
+        self
     }
 
     /// Loads the calldata into the source if it is not open.
@@ -41,8 +63,14 @@ impl<CP: ChainProvider + Send> CalldataSource<CP> {
 
         let (_, txs) =
             self.chain_provider.block_info_and_transactions_by_hash(block_ref.hash).await?;
+        let (inbox_address, batcher_address) = BatchInboxActivation::resolve(
+            &self.inbox_schedule,
+            block_ref.number,
+            self.batch_inbox_address,
+            batcher_address,
+        );
 
-        self.calldata = txs
+        (self.calldata, self.tx_hashes) = txs
             .iter()
             .filter_map(|tx| {
                 let (tx_kind, data) = match tx {
@@ -53,15 +81,15 @@ impl<CP: ChainProvider + Send> CalldataSource<CP> {
                 };
                 let to = tx_kind?;
 
-                if to != self.batch_inbox_address {
+                if to != inbox_address {
                     return None;
                 }
                 if tx.recover_signer().ok()? != batcher_address {
                     return None;
                 }
-                Some(data.to_vec().into())
+                Some((data.to_vec().into(), *tx.tx_hash()))
             })
-            .collect::<VecDeque<_>>();
+            .unzip();
 
         #[cfg(feature = "metrics")]
         metrics::gauge!(
@@ -86,13 +114,22 @@ impl<CP: ChainProvider + Send> DataAvailabilityProvider for CalldataSource<CP> {
         batcher_address: Address,
     ) -> PipelineResult<Self::Item> {
         self.load_calldata(block_ref, batcher_address).await.map_err(Into::into)?;
-        self.calldata.pop_front().ok_or(PipelineError::Eof.temp())
+        let data = self.calldata.pop_front().ok_or(PipelineError::Eof.temp())?;
+        self.last_source =
+            self.tx_hashes.pop_front().map(|tx_hash| BatcherTxRef { tx_hash, blob_hash: None });
+        Ok(data)
     }
 
     fn clear(&mut self) {
         self.calldata.clear();
+        self.tx_hashes.clear();
+        self.last_source = None;
         self.open = false;
     }
+
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        self.last_source
+    }
 }
 
 #[cfg(test)]
@@ -101,7 +138,7 @@ mod tests {
     use crate::{errors::PipelineErrorKind, test_utils::TestChainProvider};
     use alloc::{vec, vec::Vec};
     use alloy_consensus::{Signed, TxEip2930, TxEip4844, TxEip4844Variant, TxEip7702, TxLegacy};
-    use alloy_primitives::{Address, Signature, TxKind, address};
+    use alloy_primitives::{Address, B256, Signature, TxKind, address};
 
     pub(crate) fn test_legacy_tx(to: Address) -> TxEnvelope {
         let sig = Signature::test_signature();
@@ -148,8 +185,10 @@ mod tests {
         let mut source = default_test_calldata_source();
         source.open = true;
         source.calldata.push_back(Bytes::default());
+        source.tx_hashes.push_back(B256::ZERO);
         source.clear();
         assert!(source.calldata.is_empty());
+        assert!(source.tx_hashes.is_empty());
         assert!(!source.open);
     }
 
@@ -220,6 +259,24 @@ mod tests {
         assert!(source.open);
     }
 
+    #[tokio::test]
+    async fn test_next_tracks_last_source() {
+        let batch_inbox_address = address!("0123456789012345678901234567890123456789");
+        let mut source = default_test_calldata_source();
+        source.batch_inbox_address = batch_inbox_address;
+        let tx = test_legacy_tx(batch_inbox_address);
+        let block_info = BlockInfo::default();
+        source.chain_provider.insert_block_with_transactions(0, block_info, vec![tx.clone()]);
+        let signer = tx.recover_signer().unwrap();
+
+        assert!(source.next(&block_info, signer).await.is_ok());
+        let expected = BatcherTxRef { tx_hash: *tx.tx_hash(), blob_hash: None };
+        assert_eq!(source.last_source(), Some(expected));
+
+        source.clear();
+        assert_eq!(source.last_source(), None);
+    }
+
     #[tokio::test]
     async fn test_load_calldata_valid_eip2930_tx() {
         let batch_inbox_address = address!("0123456789012345678901234567890123456789");
@@ -236,6 +293,36 @@ mod tests {
         assert!(source.open);
     }
 
+    #[tokio::test]
+    async fn test_load_calldata_inbox_schedule() {
+        let old_inbox = address!("0123456789012345678901234567890123456789");
+        let new_inbox = address!("9876543210987654321098765432109876543210");
+        let tx = test_legacy_tx(new_inbox);
+        let signer = tx.recover_signer().unwrap();
+        let mut source =
+            default_test_calldata_source().with_inbox_schedule(vec![BatchInboxActivation {
+                l1_block: 10,
+                inbox_address: new_inbox,
+                batcher_address: Some(signer),
+            }]);
+        source.batch_inbox_address = old_inbox;
+
+        // Before activation, the configured inbox address and batcher apply.
+        let block_info =
+            BlockInfo { number: 9, hash: B256::with_last_byte(9), ..Default::default() };
+        source.chain_provider.insert_block_with_transactions(9, block_info, vec![tx.clone()]);
+        assert!(source.load_calldata(&block_info, signer).await.is_ok());
+        assert!(source.calldata.is_empty());
+
+        // From activation onwards, the scheduled inbox and signer apply.
+        source.clear();
+        let block_info =
+            BlockInfo { number: 10, hash: B256::with_last_byte(10), ..Default::default() };
+        source.chain_provider.insert_block_with_transactions(10, block_info, vec![tx]);
+        assert!(source.load_calldata(&block_info, Address::ZERO).await.is_ok());
+        assert_eq!(source.calldata.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_load_calldata_blob_tx_ignored() {
         let batch_inbox_address = address!("0123456789012345678901234567890123456789");
@@ -0,0 +1,286 @@
+//! Contains the [DispatchDataSource], a [DataAvailabilityProvider] routing batcher transaction
+//! data to the registered provider of its commitment type.
+
+use crate::{AltDaProvider, BatcherTxRef, DataAvailabilityProvider, PipelineResult};
+use alloc::{boxed::Box, collections::BTreeMap};
+use alloy_primitives::{Address, Bytes, keccak256};
+use async_trait::async_trait;
+use kona_protocol::BlockInfo;
+
+/// The version byte of batcher transaction data carrying an alt-DA commitment, rather than
+/// frames.
+pub const ALT_DA_DERIVATION_VERSION: u8 = 1;
+
+/// The type of an alt-DA commitment, read from the byte following the version byte of the
+/// batcher transaction data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommitmentType {
+    /// A keccak256 commitment (`0x00`) to the input data, which is verified against the data
+    /// returned by its provider.
+    Keccak256,
+    /// A generic commitment (`0x01`), followed by the byte identifying the DA layer that
+    /// interprets it.
+    Generic(u8),
+}
+
+impl CommitmentType {
+    /// The type byte of keccak256 commitments.
+    pub const KECCAK256: u8 = 0;
+
+    /// The type byte of generic commitments.
+    pub const GENERIC: u8 = 1;
+
+    /// Returns the type of the given encoded commitment, which starts with its type byte.
+    pub const fn decode(commitment: &[u8]) -> Option<Self> {
+        match commitment {
+            [Self::KECCAK256, ..] => Some(Self::Keccak256),
+            [Self::GENERIC, da_layer, ..] => Some(Self::Generic(*da_layer)),
+            _ => None,
+        }
+    }
+}
+
+/// A [DataAvailabilityProvider] routing batcher transaction data to the registered provider of
+/// its commitment type.
+///
+/// The inner source reads the data of the batcher transactions from L1, as calldata or blobs
+/// depending on the active hardfork. Data starting with [`DERIVATION_VERSION_0`] holds frames,
+/// and is returned as is. Data starting with [`ALT_DA_DERIVATION_VERSION`] holds an alt-DA
+/// commitment, whose input data is fetched from the [`AltDaProvider`] registered for its
+/// [`CommitmentType`]. Commitments without a registered provider, and keccak256 commitments that
+/// do not match their input data, are skipped.
+///
+/// Providers are held as trait objects, so that new DA layers can be registered with
+/// [`Self::with_provider`] without changing the type of the pipeline.
+///
+/// [`DERIVATION_VERSION_0`]: kona_protocol::DERIVATION_VERSION_0
+#[derive(Debug)]
+pub struct DispatchDataSource<D>
+where
+    D: DataAvailabilityProvider<Item = Bytes> + Send,
+{
+    /// The source of the batcher transaction data.
+    pub inner: D,
+    /// The providers of alt-DA input data, by commitment type.
+    pub providers: BTreeMap<CommitmentType, Box<dyn AltDaProvider>>,
+    /// The commitment whose input data is being fetched, kept across temporary errors of its
+    /// provider so that the commitment is not skipped when the pipeline retries.
+    pub pending: Option<(CommitmentType, Bytes)>,
+}
+
+impl<D> DispatchDataSource<D>
+where
+    D: DataAvailabilityProvider<Item = Bytes> + Send,
+{
+    /// Creates a new [`DispatchDataSource`] over the given source, without alt-DA providers.
+    pub const fn new(inner: D) -> Self {
+        Self { inner, providers: BTreeMap::new(), pending: None }
+    }
+
+    /// Registers the provider of the input data of the given commitment type, replacing the
+    /// provider previously registered for it.
+    pub fn with_provider(
+        mut self,
+        commitment_type: CommitmentType,
+        provider: impl AltDaProvider + 'static,
+    ) -> Self {
+        self.register(commitment_type, provider);
+        self
+    }
+
+    /// Registers the provider of the input data of the given commitment type, returning the
+    /// provider previously registered for it.
+    pub fn register(
+        &mut self,
+        commitment_type: CommitmentType,
+        provider: impl AltDaProvider + 'static,
+    ) -> Option<Box<dyn AltDaProvider>> {
+        self.providers.insert(commitment_type, Box::new(provider))
+    }
+
+    /// Fetches the input data of the pending commitment. Returns [`None`] if the commitment is
+    /// skipped.
+    async fn resolve(&mut self, block_ref: &BlockInfo) -> PipelineResult<Option<Bytes>> {
+        let Some((commitment_type, commitment)) = self.pending.clone() else { return Ok(None) };
+        let Some(provider) = self.providers.get_mut(&commitment_type) else {
+            warn!(
+                target: "dispatch_source",
+                ?commitment_type,
+                "No provider registered for the alt-DA commitment, skipping"
+            );
+            self.pending = None;
+            return Ok(None);
+        };
+
+        let input = provider.get_input(&commitment, block_ref).await?;
+        self.pending = None;
+        if commitment_type == CommitmentType::Keccak256 &&
+            keccak256(&input).as_slice() != &commitment[1..]
+        {
+            warn!(
+                target: "dispatch_source",
+                %commitment,
+                "Alt-DA input does not match its keccak256 commitment, skipping"
+            );
+            return Ok(None);
+        }
+        Ok(Some(input))
+    }
+}
+
+#[async_trait]
+impl<D> DataAvailabilityProvider for DispatchDataSource<D>
+where
+    D: DataAvailabilityProvider<Item = Bytes> + Send + Sync,
+{
+    type Item = Bytes;
+
+    async fn next(
+        &mut self,
+        block_ref: &BlockInfo,
+        batcher_address: Address,
+    ) -> PipelineResult<Self::Item> {
+        loop {
+            if let Some(input) = self.resolve(block_ref).await? {
+                return Ok(input);
+            }
+
+            let data = self.inner.next(block_ref, batcher_address).await?;
+            match data.first() {
+                Some(&ALT_DA_DERIVATION_VERSION) => match CommitmentType::decode(&data[1..]) {
+                    Some(commitment_type) => {
+                        self.pending = Some((commitment_type, data.slice(1..)));
+                    }
+                    None => {
+                        warn!(target: "dispatch_source", "Invalid alt-DA commitment, skipping");
+                    }
+                },
+                // Frames, and data that is not understood, are left to the frame queue.
+                _ => return Ok(data),
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+        self.pending = None;
+        self.providers.values_mut().for_each(|provider| provider.clear());
+    }
+
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        self.inner.last_source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PipelineError, test_utils::TestDAP};
+    use alloc::{vec, vec::Vec};
+    use alloy_primitives::map::HashMap;
+    use kona_protocol::DERIVATION_VERSION_0;
+
+    /// An [`AltDaProvider`] serving the input data of known commitments.
+    #[derive(Debug, Default)]
+    struct TestAltDaProvider {
+        inputs: HashMap<Bytes, Bytes>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl AltDaProvider for TestAltDaProvider {
+        async fn get_input(&mut self, commitment: &Bytes, _: &BlockInfo) -> PipelineResult<Bytes> {
+            if core::mem::take(&mut self.fail) {
+                return Err(PipelineError::Provider("unavailable".into()).temp());
+            }
+            Ok(self.inputs.get(commitment).cloned().unwrap_or_default())
+        }
+    }
+
+    /// Returns batcher transaction data carrying the given encoded commitment.
+    fn tx_data(commitment: &[u8]) -> Bytes {
+        [&[ALT_DA_DERIVATION_VERSION], commitment].concat().into()
+    }
+
+    /// Returns the encoded keccak256 commitment to the given input.
+    fn keccak_commitment(input: &[u8]) -> Bytes {
+        [&[CommitmentType::KECCAK256], keccak256(input).as_slice()].concat().into()
+    }
+
+    async fn drain<D>(source: &mut DispatchDataSource<D>) -> Vec<Bytes>
+    where
+        D: DataAvailabilityProvider<Item = Bytes> + Send + Sync,
+    {
+        let mut items = Vec::new();
+        while let Ok(item) = source.next(&BlockInfo::default(), Address::ZERO).await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[test]
+    fn test_decode_commitment_type() {
+        assert_eq!(CommitmentType::decode(&[0, 1, 2]), Some(CommitmentType::Keccak256));
+        assert_eq!(CommitmentType::decode(&[1, 0x0c, 2]), Some(CommitmentType::Generic(0x0c)));
+        assert_eq!(CommitmentType::decode(&[1]), None);
+        assert_eq!(CommitmentType::decode(&[2, 0]), None);
+        assert_eq!(CommitmentType::decode(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_keccak_commitments() {
+        let frames = Bytes::from_static(&[DERIVATION_VERSION_0, 1, 2, 3]);
+        let valid = keccak_commitment(b"frames");
+        let invalid = keccak_commitment(b"other frames");
+        let provider = TestAltDaProvider {
+            inputs: HashMap::from_iter([
+                (valid.clone(), Bytes::from_static(b"frames")),
+                (invalid.clone(), Bytes::from_static(b"tampered")),
+            ]),
+            ..Default::default()
+        };
+
+        let inner = TestDAP::new(vec![frames.clone(), tx_data(&invalid), tx_data(&valid)]);
+        let mut source =
+            DispatchDataSource::new(inner).with_provider(CommitmentType::Keccak256, provider);
+        assert_eq!(drain(&mut source).await, [frames, Bytes::from_static(b"frames")]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_generic_commitments() {
+        let celestia = Bytes::from_static(&[CommitmentType::GENERIC, 0x0c, 0xaa]);
+        let unknown = Bytes::from_static(&[CommitmentType::GENERIC, 0x42, 0xbb]);
+        let provider = TestAltDaProvider {
+            inputs: HashMap::from_iter([(celestia.clone(), Bytes::from_static(b"blob"))]),
+            ..Default::default()
+        };
+
+        let inner = TestDAP::new(vec![tx_data(&unknown), tx_data(&celestia), tx_data(&[7])]);
+        let mut source =
+            DispatchDataSource::new(inner).with_provider(CommitmentType::Generic(0x0c), provider);
+        assert_eq!(drain(&mut source).await, [Bytes::from_static(b"blob")]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_retries_pending_commitment() {
+        let commitment = Bytes::from_static(&[CommitmentType::GENERIC, 0x0c, 0xaa]);
+        let provider = TestAltDaProvider {
+            inputs: HashMap::from_iter([(commitment.clone(), Bytes::from_static(b"blob"))]),
+            fail: true,
+        };
+
+        let inner = TestDAP::new(vec![tx_data(&commitment)]);
+        let mut source =
+            DispatchDataSource::new(inner).with_provider(CommitmentType::Generic(0x0c), provider);
+        let block_ref = BlockInfo::default();
+        assert!(source.next(&block_ref, Address::ZERO).await.is_err());
+        assert_eq!(
+            source.next(&block_ref, Address::ZERO).await.unwrap(),
+            Bytes::from_static(b"blob")
+        );
+
+        source.pending = Some((CommitmentType::Generic(0x0c), commitment));
+        source.clear();
+        assert!(source.pending.is_none());
+    }
+}
@@ -2,8 +2,8 @@
 //! [DataAvailabilityProvider] trait for the Ethereum protocol.
 
 use crate::{
-    BlobProvider, BlobSource, CalldataSource, ChainProvider, DataAvailabilityProvider,
-    PipelineResult,
+    BatcherTxRef, BlobProvider, BlobSource, CalldataSource, ChainProvider,
+    DataAvailabilityProvider, PipelineResult,
 };
 use alloc::{boxed::Box, fmt::Debug};
 use alloy_primitives::{Address, Bytes};
@@ -44,8 +44,10 @@ where
     pub fn new_from_parts(provider: C, blobs: B, cfg: &RollupConfig) -> Self {
         Self {
             ecotone_timestamp: cfg.hardforks.ecotone_time,
-            blob_source: BlobSource::new(provider.clone(), blobs, cfg.batch_inbox_address),
-            calldata_source: CalldataSource::new(provider, cfg.batch_inbox_address),
+            blob_source: BlobSource::new(provider.clone(), blobs, cfg.batch_inbox_address)
+                .with_inbox_schedule(cfg.batch_inbox_schedule.clone()),
+            calldata_source: CalldataSource::new(provider, cfg.batch_inbox_address)
+                .with_inbox_schedule(cfg.batch_inbox_schedule.clone()),
         }
     }
 }
@@ -76,6 +78,10 @@ where
         self.blob_source.clear();
         self.calldata_source.clear();
     }
+
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        self.blob_source.last_source().or(self.calldata_source.last_source())
+    }
 }
 
 #[cfg(test)]
@@ -124,7 +130,7 @@ mod tests {
         let chain = TestChainProvider::default();
         let mut blob = default_test_blob_source();
         blob.open = true;
-        blob.data.push(BlobData { data: None, calldata: Some(Bytes::default()) });
+        blob.data.push(BlobData { calldata: Some(Bytes::default()), ..Default::default() });
         let calldata = CalldataSource::new(chain.clone(), Address::ZERO);
         let cfg = RollupConfig {
             hardforks: HardForkConfig { ecotone_time: Some(0), ..Default::default() },
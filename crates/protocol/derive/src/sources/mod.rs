@@ -18,3 +18,6 @@ pub use blobs::BlobSource;
 
 mod calldata;
 pub use calldata::CalldataSource;
+
+mod dispatch;
+pub use dispatch::{ALT_DA_DERIVATION_VERSION, CommitmentType, DispatchDataSource};
@@ -12,7 +12,7 @@ use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
-use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent, SingleBatch};
+use kona_protocol::{BlockInfo, ChannelId, L2BlockInfo, OpAttributesWithParent, SingleBatch};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
 /// [`AttributesQueue`] accepts batches from the [`BatchQueue`] stage
@@ -40,6 +40,8 @@ where
     pub prev: P,
     /// Whether the current batch is the last in its span.
     pub is_last_in_span: bool,
+    /// The [`ChannelId`] of the channel the current batch was read from, if known.
+    pub channel_id: Option<ChannelId>,
     /// The current batch being processed.
     pub batch: Option<SingleBatch>,
     /// The attributes builder.
@@ -53,7 +55,7 @@ where
 {
     /// Create a new [`AttributesQueue`] stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P, builder: AB) -> Self {
-        Self { cfg, prev, is_last_in_span: false, batch: None, builder }
+        Self { cfg, prev, is_last_in_span: false, channel_id: None, batch: None, builder }
     }
 
     /// Loads a [`SingleBatch`] from the [`AttributesProvider`] if needed.
@@ -62,6 +64,7 @@ where
             let batch = self.prev.next_batch(parent).await?;
             self.batch = Some(batch);
             self.is_last_in_span = self.prev.is_last_in_span();
+            self.channel_id = self.prev.channel_id();
         }
         self.batch.as_ref().cloned().ok_or(PipelineError::Eof.temp())
     }
@@ -89,7 +92,8 @@ where
         };
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
         let populated_attributes =
-            OpAttributesWithParent::new(attributes, parent, Some(origin), self.is_last_in_span);
+            OpAttributesWithParent::new(attributes, parent, Some(origin), self.is_last_in_span)
+                .with_channel_id(self.channel_id);
         kona_macros::record!(
             histogram,
             crate::metrics::Metrics::PIPELINE_ATTRIBUTES_BUILD_DURATION,
@@ -99,6 +103,7 @@ where
         // Clear out the local state once payload attributes are prepared.
         self.batch = None;
         self.is_last_in_span = false;
+        self.channel_id = None;
         Ok(populated_attributes)
     }
 
@@ -191,9 +196,20 @@ where
                 self.prev.signal(s).await?;
                 self.batch = None;
                 self.is_last_in_span = false;
+                self.channel_id = None;
             }
             s @ Signal::FlushChannel => {
                 self.batch = None;
+                self.channel_id = None;
+                self.prev.signal(s).await?;
+            }
+            s @ Signal::FlushChannelById(id) => {
+                // Only drop the loaded batch if it was read from the targeted channel. A batch
+                // from an unknown channel is conservatively treated as a match.
+                if self.channel_id.is_none_or(|c| c == id) {
+                    self.batch = None;
+                    self.channel_id = None;
+                }
                 self.prev.signal(s).await?;
             }
             s @ Signal::ProvideBlock(_) => {
@@ -255,6 +271,36 @@ mod tests {
         assert!(attributes_queue.batch.is_none());
     }
 
+    #[tokio::test]
+    async fn test_attributes_queue_flush_channel_by_id() {
+        let mut attributes_queue = new_attributes_queue(None, None, vec![], vec![]);
+        attributes_queue.batch = Some(SingleBatch::default());
+        attributes_queue.channel_id = Some([0xAA; 16]);
+
+        // The loaded batch is preserved when another channel is flushed.
+        attributes_queue.signal(Signal::FlushChannelById([0xBB; 16])).await.unwrap();
+        assert!(attributes_queue.prev.flushed);
+        assert!(attributes_queue.batch.is_some());
+
+        attributes_queue.signal(Signal::FlushChannelById([0xAA; 16])).await.unwrap();
+        assert!(attributes_queue.batch.is_none());
+        assert!(attributes_queue.channel_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_next_attributes_carries_channel_id() {
+        let mut mock =
+            new_test_attributes_provider(Some(Default::default()), vec![Ok(Default::default())]);
+        mock.channel_id = Some([0xAA; 16]);
+        let mock_builder =
+            TestAttributesBuilder { attributes: vec![Ok(default_optimism_payload_attributes())] };
+        let mut aq = AttributesQueue::new(Arc::new(RollupConfig::default()), mock, mock_builder);
+
+        let attributes = aq.next_attributes(L2BlockInfo::default()).await.unwrap();
+        assert_eq!(attributes.channel_id(), Some([0xAA; 16]));
+        assert!(aq.channel_id.is_none());
+    }
+
     #[tokio::test]
     async fn test_attributes_queue_reset() {
         let cfg = RollupConfig::default();
@@ -404,6 +450,7 @@ mod tests {
             parent: L2BlockInfo::default(),
             derived_from: Some(BlockInfo::default()),
             is_last_in_span: true,
+            channel_id: None,
         };
         assert_eq!(attributes, populated_attributes);
         assert!(!aq.is_last_in_span);
@@ -3,13 +3,13 @@
 use super::NextBatchProvider;
 use crate::{
     AttributesProvider, BatchQueue, BatchValidator, L2ChainProvider, OriginAdvancer,
-    OriginProvider, PipelineError, PipelineResult, Signal, SignalReceiver,
+    OriginProvider, PipelineError, PipelineEvents, PipelineResult, Signal, SignalReceiver,
 };
 use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
-use kona_protocol::{BlockInfo, L2BlockInfo, SingleBatch};
+use kona_protocol::{BlockInfo, ChannelId, L2BlockInfo, SingleBatch};
 
 /// The [`BatchProvider`] stage is a mux between the [`BatchQueue`] and [`BatchValidator`] stages.
 ///
@@ -44,6 +44,8 @@ where
     ///
     /// Must be [`None`] if `prev` or `batch_queue` is [`Some`].
     pub batch_validator: Option<BatchValidator<P>>,
+    /// The handle passed on to the active stage to emit batch events to.
+    pub events: PipelineEvents,
 }
 
 impl<P, F> BatchProvider<P, F>
@@ -53,7 +55,20 @@ where
 {
     /// Creates a new [`BatchProvider`] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P, provider: F) -> Self {
-        Self { cfg, provider, prev: Some(prev), batch_queue: None, batch_validator: None }
+        Self {
+            cfg,
+            provider,
+            prev: Some(prev),
+            batch_queue: None,
+            batch_validator: None,
+            events: PipelineEvents::none(),
+        }
+    }
+
+    /// Sets the [`PipelineEvents`] handle of the [`BatchProvider`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
     }
 
     /// Attempts to update the active stage of the mux.
@@ -63,16 +78,21 @@ where
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
             if self.cfg.is_holocene_active(origin.timestamp) {
-                self.batch_validator = Some(BatchValidator::new(self.cfg.clone(), prev));
+                self.batch_validator = Some(
+                    BatchValidator::new(self.cfg.clone(), prev).with_events(self.events.clone()),
+                );
             } else {
-                self.batch_queue =
-                    Some(BatchQueue::new(self.cfg.clone(), prev, self.provider.clone()));
+                self.batch_queue = Some(
+                    BatchQueue::new(self.cfg.clone(), prev, self.provider.clone())
+                        .with_events(self.events.clone()),
+                );
             }
         } else if self.batch_queue.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
             // If the batch queue is active and Holocene is also active, transition to the batch
             // validator.
             let batch_queue = self.batch_queue.take().expect("Must have batch queue");
-            let mut bv = BatchValidator::new(self.cfg.clone(), batch_queue.prev);
+            let mut bv = BatchValidator::new(self.cfg.clone(), batch_queue.prev)
+                .with_events(self.events.clone());
             bv.l1_blocks = batch_queue.l1_blocks;
             self.batch_validator = Some(bv);
         } else if self.batch_validator.is_some() && !self.cfg.is_holocene_active(origin.timestamp) {
@@ -81,7 +101,8 @@ where
             // until Holocene re-activates.
             let batch_validator = self.batch_validator.take().expect("Must have batch validator");
             let mut bq =
-                BatchQueue::new(self.cfg.clone(), batch_validator.prev, self.provider.clone());
+                BatchQueue::new(self.cfg.clone(), batch_validator.prev, self.provider.clone())
+                    .with_events(self.events.clone());
             bq.l1_blocks = batch_validator.l1_blocks;
             self.batch_queue = Some(bq);
         }
@@ -158,6 +179,13 @@ where
         )
     }
 
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.batch_validator.as_ref().map_or_else(
+            || self.batch_queue.as_ref().and_then(|batch_queue| batch_queue.channel_id()),
+            |batch_validator| batch_validator.channel_id(),
+        )
+    }
+
     async fn next_batch(&mut self, parent: L2BlockInfo) -> PipelineResult<SingleBatch> {
         self.attempt_update()?;
 
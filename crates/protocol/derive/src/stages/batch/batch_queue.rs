@@ -4,14 +4,14 @@ use super::NextBatchProvider;
 use crate::{
     errors::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError},
     traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    types::{PipelineEvent, PipelineEvents, PipelineResult, ResetSignal, Signal},
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
 use kona_protocol::{
-    Batch, BatchValidity, BatchWithInclusionBlock, BlockInfo, L2BlockInfo, SingleBatch,
+    Batch, BatchValidity, BatchWithInclusionBlock, BlockInfo, ChannelId, L2BlockInfo, SingleBatch,
 };
 
 /// [`BatchQueue`] is responsible for ordering unordered batches
@@ -55,6 +55,8 @@ where
     pub next_spans: Vec<SingleBatch>,
     /// Used to validate the batches.
     pub fetcher: BF,
+    /// The handle that batch events are emitted to.
+    pub events: PipelineEvents,
 }
 
 impl<P, BF> BatchQueue<P, BF>
@@ -73,9 +75,16 @@ where
             batches: Default::default(),
             next_spans: Default::default(),
             fetcher,
+            events: PipelineEvents::none(),
         }
     }
 
+    /// Sets the [`PipelineEvents`] handle of the [`BatchQueue`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
+    }
+
     /// Pops the next batch from the current queued up span-batch cache.
     /// The parent is used to set the parent hash of the batch.
     /// The parent is verified when the batch is later validated.
@@ -142,6 +151,11 @@ where
                     } else {
                         self.prev.flush();
                         warn!(target: "batch_queue", "[HOLOCENE] Dropping future batch with parent: {}", parent.block_info.number);
+                        self.events.emit(PipelineEvent::BatchDropped {
+                            timestamp: batch.batch.timestamp(),
+                            origin,
+                            validity,
+                        });
                     }
                 }
                 BatchValidity::Drop => {
@@ -149,9 +163,18 @@ where
                     // stage.
                     self.prev.flush();
                     warn!(target: "batch_queue", "Dropping batch with parent: {}", parent.block_info);
+                    self.events.emit(PipelineEvent::BatchDropped {
+                        timestamp: batch.batch.timestamp(),
+                        origin,
+                        validity,
+                    });
                     continue;
                 }
                 BatchValidity::Accept => {
+                    self.events.emit(PipelineEvent::BatchAccepted {
+                        timestamp: batch.batch.timestamp(),
+                        origin,
+                    });
                     next_batch = Some(batch.clone());
                     // Don't keep the current batch in the remaining items since we are processing
                     // it now, but retain every batch we didn't get to yet.
@@ -170,6 +193,11 @@ where
                     }
 
                     warn!(target: "batch_queue", "[HOLOCENE] Dropping outdated batch with parent: {}", parent.block_info.number);
+                    self.events.emit(PipelineEvent::BatchDropped {
+                        timestamp: batch.batch.timestamp(),
+                        origin,
+                        validity,
+                    });
                     continue;
                 }
             }
@@ -416,6 +444,12 @@ where
     fn is_last_in_span(&self) -> bool {
         self.next_spans.is_empty()
     }
+
+    /// Batches are buffered across channels before they are validated, so the channel of the
+    /// current batch is not tracked.
+    fn channel_id(&self) -> Option<ChannelId> {
+        None
+    }
 }
 
 impl<P, BF> OriginProvider for BatchQueue<P, BF>
@@ -452,6 +486,11 @@ where
                 self.batches.clear();
                 self.next_spans.clear();
             }
+            s @ Signal::FlushChannelById(_) => {
+                // Buffered batches are not attributed to a channel, so they are preserved and
+                // only the targeted channel is flushed by the previous stages.
+                self.prev.signal(s).await?;
+            }
             s @ Signal::ProvideBlock(_) => {
                 self.prev.signal(s).await?;
             }
@@ -1,16 +1,16 @@
 //! This module contains the `BatchStream` stage.
 
 use crate::{
-    L2ChainProvider, NextBatchProvider, OriginAdvancer, OriginProvider, PipelineError,
-    PipelineResult, Signal, SignalReceiver,
+    L2ChainProvider, MemoryComponent, NextBatchProvider, OriginAdvancer, OriginProvider,
+    PipelineError, PipelineMemory, PipelineResult, Signal, SignalReceiver,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
 use kona_protocol::{
-    Batch, BatchValidity, BatchWithInclusionBlock, BlockInfo, L2BlockInfo, SingleBatch, SpanBatch,
-    SpanBatchError,
+    Batch, BatchValidity, BatchWithInclusionBlock, BlockInfo, ChannelId, L2BlockInfo, SingleBatch,
+    SpanBatch, SpanBatchError,
 };
 
 /// Provides [`Batch`]es for the [`BatchStream`] stage.
@@ -21,6 +21,9 @@ pub trait BatchStreamProvider {
 
     /// Drains the recent `Channel` if an invalid span batch is found post-holocene.
     fn flush(&mut self);
+
+    /// Returns the [`ChannelId`] of the channel that batches are currently read from, if known.
+    fn channel_id(&self) -> Option<ChannelId>;
 }
 
 /// [`BatchStream`] stage in the derivation pipeline.
@@ -44,11 +47,15 @@ where
     pub span: Option<SpanBatch>,
     /// A buffer of single batches derived from the [`SpanBatch`].
     pub buffer: VecDeque<SingleBatch>,
+    /// The [`ChannelId`] of the channel the staged span batch was read from, if known.
+    pub span_channel_id: Option<ChannelId>,
     /// A reference to the rollup config, used to check
     /// if the [`BatchStream`] stage should be activated.
     pub config: Arc<RollupConfig>,
     /// Used to validate the batches.
     pub fetcher: BF,
+    /// The handle that the size of the buffered batches is reported to.
+    pub memory: PipelineMemory,
 }
 
 impl<P, BF> BatchStream<P, BF>
@@ -57,8 +64,30 @@ where
     BF: L2ChainProvider + Debug,
 {
     /// Create a new [`BatchStream`] stage.
-    pub const fn new(prev: P, config: Arc<RollupConfig>, fetcher: BF) -> Self {
-        Self { prev, span: None, buffer: VecDeque::new(), config, fetcher }
+    pub fn new(prev: P, config: Arc<RollupConfig>, fetcher: BF) -> Self {
+        Self {
+            prev,
+            span: None,
+            buffer: VecDeque::new(),
+            span_channel_id: None,
+            config,
+            fetcher,
+            memory: PipelineMemory::unbounded(),
+        }
+    }
+
+    /// Sets the [`PipelineMemory`] handle of the [`BatchStream`].
+    pub fn with_memory(mut self, memory: PipelineMemory) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Returns the size of the single batches held in the buffer, including their transactions.
+    pub fn size(&self) -> usize {
+        self.buffer.iter().fold(0, |acc, batch| {
+            acc + core::mem::size_of::<SingleBatch>() +
+                batch.transactions.iter().map(|tx| tx.len()).sum::<usize>()
+        })
     }
 
     /// Returns if the [`BatchStream`] stage is active based on the
@@ -77,7 +106,9 @@ where
         trace!(target: "batch_span", "Attempting to get a SingleBatch from buffer len: {}", self.buffer.len());
 
         self.try_hydrate_buffer(parent, l1_origins)?;
-        Ok(self.buffer.pop_front())
+        let batch = self.buffer.pop_front();
+        self.memory.set(MemoryComponent::BatchStream, self.size());
+        Ok(batch)
     }
 
     /// Hydrates the buffer with single batches derived from the span batch, if there is one
@@ -111,7 +142,9 @@ where
         if self.is_active().unwrap_or(false) {
             self.prev.flush();
             self.span = None;
+            self.span_channel_id = None;
             self.buffer.clear();
+            self.memory.set(MemoryComponent::BatchStream, 0);
         }
     }
 
@@ -119,6 +152,10 @@ where
         self.buffer.len()
     }
 
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.span_channel_id.or_else(|| self.prev.channel_id())
+    }
+
     async fn next_batch(
         &mut self,
         parent: L2BlockInfo,
@@ -143,7 +180,10 @@ where
             // forwarded to the `BatchQueue` stage. Otherwise, we buffer
             // the span batch in this stage if it passes the validity checks.
             match batch_with_inclusion.batch {
-                Batch::Single(b) => return Ok(Batch::Single(b)),
+                Batch::Single(b) => {
+                    self.span_channel_id = None;
+                    return Ok(Batch::Single(b));
+                }
                 Batch::Span(b) => {
                     #[cfg(feature = "metrics")]
                     let start = std::time::Instant::now();
@@ -169,7 +209,10 @@ where
                     );
 
                     match validity {
-                        BatchValidity::Accept => self.span = Some(b),
+                        BatchValidity::Accept => {
+                            self.span = Some(b);
+                            self.span_channel_id = self.prev.channel_id();
+                        }
                         BatchValidity::Drop => {
                             // Flush the stage.
                             self.flush();
@@ -236,8 +279,16 @@ where
 {
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         self.prev.signal(signal).await?;
+        if let Signal::FlushChannelById(id) = signal {
+            // Preserve the staged span batch if it was read from a different channel.
+            if self.span_channel_id.is_some_and(|c| c != id) {
+                return Ok(());
+            }
+        }
         self.buffer.clear();
         self.span.take();
+        self.span_channel_id = None;
+        self.memory.set(MemoryComponent::BatchStream, 0);
         Ok(())
     }
 }
@@ -309,6 +360,46 @@ mod test {
         assert!(stream.span.is_none());
     }
 
+    #[tokio::test]
+    async fn test_batch_stream_flush_channel_by_id() {
+        let config = Arc::new(RollupConfig {
+            hardforks: HardForkConfig { holocene_time: Some(0), ..Default::default() },
+            ..Default::default()
+        });
+        let prev = TestBatchStreamProvider::new(vec![]);
+        let mut stream = BatchStream::new(prev, config, TestL2ChainProvider::default());
+        stream.buffer.push_back(SingleBatch::default());
+        stream.span = Some(SpanBatch::default());
+        stream.span_channel_id = Some([0xAA; 16]);
+
+        // The staged span batch is preserved when another channel is flushed.
+        stream.signal(Signal::FlushChannelById([0xBB; 16])).await.unwrap();
+        assert!(stream.prev.flushed);
+        assert!(!stream.buffer.is_empty());
+        assert!(stream.span.is_some());
+
+        stream.signal(Signal::FlushChannelById([0xAA; 16])).await.unwrap();
+        assert!(stream.buffer.is_empty());
+        assert!(stream.span.is_none());
+        assert!(stream.span_channel_id.is_none());
+    }
+
+    #[test]
+    fn test_batch_stream_channel_id() {
+        let config = Arc::new(RollupConfig::default());
+        let mut prev = TestBatchStreamProvider::new(vec![]);
+        prev.channel_id = Some([0xBB; 16]);
+        let mut stream = BatchStream::new(prev, config, TestL2ChainProvider::default());
+
+        // Batches buffered from a span batch report the channel of the span batch.
+        stream.span_channel_id = Some([0xAA; 16]);
+        assert_eq!(stream.channel_id(), Some([0xAA; 16]));
+
+        // Batches read directly from the channel reader report its current channel.
+        stream.span_channel_id = None;
+        assert_eq!(stream.channel_id(), Some([0xBB; 16]));
+    }
+
     #[tokio::test]
     async fn test_batch_stream_inactive() {
         let trace_store: TraceStorage = Default::default();
@@ -358,7 +449,9 @@ mod test {
         });
         let prev = TestBatchStreamProvider::new(data);
         let provider = TestL2ChainProvider::default();
-        let mut stream = BatchStream::new(prev, config.clone(), provider);
+        let memory = PipelineMemory::unbounded();
+        let mut stream =
+            BatchStream::new(prev, config.clone(), provider).with_memory(memory.clone());
 
         // The stage should be active.
         assert!(stream.is_active().unwrap());
@@ -371,6 +464,8 @@ mod test {
         } else {
             panic!("Wrong batch type");
         }
+        assert_eq!(memory.usage(MemoryComponent::BatchStream), stream.size());
+        assert_eq!(stream.size(), core::mem::size_of::<SingleBatch>());
 
         let batch = stream.next_batch(Default::default(), &mock_origins).await.unwrap();
         if let Batch::Single(single) = batch {
@@ -383,6 +478,7 @@ mod test {
         let err = stream.next_batch(Default::default(), &mock_origins).await.unwrap_err();
         assert_eq!(err, PipelineError::Eof.temp());
         assert_eq!(stream.span_buffer_size(), 0);
+        assert_eq!(memory.usage(MemoryComponent::BatchStream), 0);
         assert!(stream.span.is_none());
 
         // Add more data into the provider, see if the buffer is re-hydrated.
@@ -4,13 +4,13 @@ use super::NextBatchProvider;
 use crate::{
     errors::{PipelineError, PipelineErrorKind, ResetError},
     traits::{AttributesProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    types::{PipelineEvent, PipelineEvents, PipelineResult, ResetSignal, Signal},
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
-use kona_protocol::{Batch, BatchValidity, BlockInfo, L2BlockInfo, SingleBatch};
+use kona_protocol::{Batch, BatchValidity, BlockInfo, ChannelId, L2BlockInfo, SingleBatch};
 
 /// The [`BatchValidator`] stage is responsible for validating the [`SingleBatch`]es from
 /// the [`BatchStream`] [`AttributesQueue`]'s consumption.
@@ -35,6 +35,8 @@ where
     /// If new L2 Block's L1 origin is not included in this list, fetch and
     /// push it to the list.
     pub l1_blocks: Vec<BlockInfo>,
+    /// The handle that batch events are emitted to.
+    pub events: PipelineEvents,
 }
 
 impl<P> BatchValidator<P>
@@ -43,7 +45,13 @@ where
 {
     /// Create a new [`BatchValidator`] stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, origin: None, l1_blocks: Vec::new() }
+        Self { cfg, prev, origin: None, l1_blocks: Vec::new(), events: PipelineEvents::none() }
+    }
+
+    /// Sets the [`PipelineEvents`] handle of the [`BatchValidator`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
     }
 
     /// Returns `true` if the pipeline origin is behind the parent origin.
@@ -248,22 +256,37 @@ where
         next_batch.parent_hash = parent.block_info.hash;
 
         // Check the validity of the single batch before forwarding it.
-        match next_batch.check_batch(
+        let validity = next_batch.check_batch(
             self.cfg.as_ref(),
             self.l1_blocks.as_ref(),
             parent,
             &stage_origin,
-        ) {
+        );
+        match validity {
             BatchValidity::Accept => {
                 info!(target: "batch_validator", "Found next batch (epoch #{})", next_batch.epoch_num);
+                self.events.emit(PipelineEvent::BatchAccepted {
+                    timestamp: next_batch.timestamp,
+                    origin: stage_origin,
+                });
                 Ok(next_batch)
             }
             BatchValidity::Past => {
                 warn!(target: "batch_validator", "Dropping old batch");
+                self.events.emit(PipelineEvent::BatchDropped {
+                    timestamp: next_batch.timestamp,
+                    origin: stage_origin,
+                    validity,
+                });
                 Err(PipelineError::NotEnoughData.temp())
             }
             BatchValidity::Drop => {
                 warn!(target: "batch_validator", "Invalid singular batch, flushing current channel.");
+                self.events.emit(PipelineEvent::BatchDropped {
+                    timestamp: next_batch.timestamp,
+                    origin: stage_origin,
+                    validity,
+                });
                 self.prev.flush();
                 Err(PipelineError::NotEnoughData.temp())
             }
@@ -278,6 +301,10 @@ where
     fn is_last_in_span(&self) -> bool {
         self.prev.span_buffer_size() == 0
     }
+
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.prev.channel_id()
+    }
 }
 
 impl<P> OriginProvider for BatchValidator<P>
@@ -315,7 +342,10 @@ where
                 self.l1_blocks.clear();
                 self.l1_blocks.push(l1_origin);
             }
-            s @ Signal::Activation(_) | s @ Signal::FlushChannel | s @ Signal::ProvideBlock(_) => {
+            s @ Signal::Activation(_) |
+            s @ Signal::FlushChannel |
+            s @ Signal::FlushChannelById(_) |
+            s @ Signal::ProvideBlock(_) => {
                 self.prev.signal(s).await?;
             }
         }
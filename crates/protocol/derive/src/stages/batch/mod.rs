@@ -15,7 +15,7 @@
 use crate::types::PipelineResult;
 use alloc::boxed::Box;
 use async_trait::async_trait;
-use kona_protocol::{Batch, BlockInfo, L2BlockInfo};
+use kona_protocol::{Batch, BlockInfo, ChannelId, L2BlockInfo};
 
 mod batch_stream;
 pub use batch_stream::{BatchStream, BatchStreamProvider};
@@ -55,4 +55,7 @@ pub trait NextBatchProvider {
     /// Allows the stage to flush the buffer in the [crate::stages::BatchStream]
     /// if an invalid single batch is found. Pre-holocene hardfork, this will be a no-op.
     fn flush(&mut self);
+
+    /// Returns the [`ChannelId`] of the channel the most recent [`Batch`] was read from, if known.
+    fn channel_id(&self) -> Option<ChannelId>;
 }
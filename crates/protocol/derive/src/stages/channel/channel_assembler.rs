@@ -4,7 +4,7 @@ use super::{ChannelReaderProvider, NextFrameProvider};
 use crate::{
     errors::PipelineError,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{ChannelCloseReason, PipelineEvent, PipelineEvents, PipelineResult, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::{Bytes, hex};
@@ -13,7 +13,7 @@ use core::fmt::Debug;
 use kona_genesis::{
     MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD, RollupConfig,
 };
-use kona_protocol::{BlockInfo, Channel};
+use kona_protocol::{BlockInfo, Channel, ChannelId};
 
 /// The [`ChannelAssembler`] stage is responsible for assembling the [`Frame`]s from the
 /// [`FrameQueue`] stage into a raw compressed [`Channel`].
@@ -32,6 +32,10 @@ where
     pub prev: P,
     /// The current [`Channel`] being assembled.
     pub channel: Option<Channel>,
+    /// The [`ChannelId`] of the channel most recently forwarded to the next stage.
+    pub last_channel_id: Option<ChannelId>,
+    /// The handle that channel lifecycle events are emitted to.
+    pub events: PipelineEvents,
 }
 
 impl<P> ChannelAssembler<P>
@@ -40,7 +44,13 @@ where
 {
     /// Creates a new [`ChannelAssembler`] stage with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, channel: None }
+        Self { cfg, prev, channel: None, last_channel_id: None, events: PipelineEvents::none() }
+    }
+
+    /// Sets the [`PipelineEvents`] handle of the [`ChannelAssembler`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
     }
 
     /// Returns whether or not the channel currently being assembled has timed out.
@@ -76,6 +86,11 @@ where
                     origin.number,
                     channel.open_block_number()
                 );
+                self.events.emit(PipelineEvent::ChannelClosed {
+                    id: channel.id().into(),
+                    origin,
+                    reason: ChannelCloseReason::TimedOut,
+                });
                 self.channel = None;
             }
         }
@@ -92,6 +107,7 @@ where
                 origin.number
             );
             self.channel = Some(Channel::new(next_frame.id, origin));
+            self.events.emit(PipelineEvent::ChannelOpened { id: next_frame.id.into(), origin });
         }
 
         let count = if self.channel.is_some() { 1 } else { 0 };
@@ -142,6 +158,11 @@ where
                     hex::encode(channel.id()),
                     channel.size()
                 );
+                self.events.emit(PipelineEvent::ChannelClosed {
+                    id: channel.id().into(),
+                    origin,
+                    reason: ChannelCloseReason::Dropped,
+                });
                 self.channel = None;
                 return Err(PipelineError::NotEnoughData.temp());
             }
@@ -158,6 +179,12 @@ where
                 );
 
                 // Reset the channel and return the compressed bytes.
+                self.events.emit(PipelineEvent::ChannelClosed {
+                    id: channel.id().into(),
+                    origin,
+                    reason: ChannelCloseReason::Ready,
+                });
+                self.last_channel_id = Some(channel.id());
                 self.channel = None;
                 return Ok(Some(channel_bytes));
             }
@@ -167,6 +194,10 @@ where
 
         Err(PipelineError::NotEnoughData.temp())
     }
+
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.last_channel_id
+    }
 }
 
 #[async_trait]
@@ -196,6 +227,7 @@ where
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         self.prev.signal(signal).await?;
         self.channel = None;
+        self.last_channel_id = None;
         Ok(())
     }
 }
@@ -204,15 +236,16 @@ where
 mod test {
     use super::ChannelAssembler;
     use crate::{
-        ChannelReaderProvider, PipelineError,
+        ChannelCloseReason, ChannelReaderProvider, PipelineError, PipelineEvent, PipelineEvents,
         test_utils::{CollectingLayer, TestNextFrameProvider, TraceStorage},
     };
-    use alloc::{sync::Arc, vec};
+    use alloc::{sync::Arc, vec, vec::Vec};
     use kona_genesis::{
         HardForkConfig, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD,
         RollupConfig,
     };
     use kona_protocol::BlockInfo;
+    use spin::Mutex;
     use tracing::Level;
     use tracing_subscriber::layer::SubscriberExt;
 
@@ -310,6 +343,7 @@ mod test {
         // Send in the second frame again. This should return the channel bytes.
         assert!(assembler.next_data().await.unwrap().is_some());
         assert!(assembler.channel.is_none());
+        assert_eq!(assembler.channel_id(), Some([0xFF; 16]));
 
         // Assert that the error log was emitted.
         let error_logs = trace_store.get_by_level(Level::ERROR);
@@ -390,4 +424,31 @@ mod test {
             trace_store_lock.iter().find(|(l, _)| matches!(l, &Level::WARN)).unwrap();
         assert!(message.contains("Compressed channel size exceeded max RLP bytes per channel"));
     }
+
+    #[tokio::test]
+    async fn test_assembler_emits_channel_events() {
+        let frames = [
+            crate::frame!(0xFF, 0, vec![0xDD; 50], false),
+            crate::frame!(0xFF, 1, vec![0xDD; 50], true),
+        ];
+        let mock = TestNextFrameProvider::new(frames.into_iter().rev().map(Ok).collect());
+        let cfg = Arc::new(RollupConfig::default());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        let mut assembler = ChannelAssembler::new(cfg, mock)
+            .with_events(PipelineEvents::new(move |event| sink.lock().push(event)));
+
+        assert_eq!(assembler.next_data().await.unwrap_err(), PipelineError::NotEnoughData.temp());
+        assert!(assembler.next_data().await.unwrap().is_some());
+
+        let origin = BlockInfo::default();
+        let id = [0xFF; 16].into();
+        assert_eq!(
+            *received.lock(),
+            [
+                PipelineEvent::ChannelOpened { id, origin },
+                PipelineEvent::ChannelClosed { id, origin, reason: ChannelCloseReason::Ready },
+            ]
+        );
+    }
 }
@@ -1,8 +1,9 @@
 //! This module contains the `ChannelBank` struct.
 
 use crate::{
-    ChannelReaderProvider, NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError,
-    PipelineErrorKind, PipelineResult, Signal, SignalReceiver,
+    ChannelCloseReason, ChannelReaderProvider, NextFrameProvider, OriginAdvancer, OriginProvider,
+    PipelineError, PipelineErrorKind, PipelineEvent, PipelineEvents, PipelineResult, Signal,
+    SignalReceiver,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::{Bytes, hex, map::HashMap};
@@ -11,12 +12,6 @@ use core::fmt::Debug;
 use kona_genesis::RollupConfig;
 use kona_protocol::{BlockInfo, Channel, ChannelId, Frame};
 
-/// The maximum size of a channel bank.
-pub(crate) const MAX_CHANNEL_BANK_SIZE: usize = 100_000_000;
-
-/// The maximum size of a channel bank after the Fjord Hardfork.
-pub(crate) const FJORD_MAX_CHANNEL_BANK_SIZE: usize = 1_000_000_000;
-
 /// [`ChannelBank`] is a stateful stage that does the following:
 /// 1. Unmarshalls frames from L1 transaction data
 /// 2. Applies those frames to a channel
@@ -39,8 +34,12 @@ where
     pub channels: HashMap<ChannelId, Channel>,
     /// Channels in FIFO order.
     pub channel_queue: VecDeque<ChannelId>,
+    /// The [`ChannelId`] of the channel most recently read out of the bank.
+    pub last_channel_id: Option<ChannelId>,
     /// The previous stage of the derivation pipeline.
     pub prev: P,
+    /// The handle that channel lifecycle events are emitted to.
+    pub events: PipelineEvents,
 }
 
 impl<P> ChannelBank<P>
@@ -49,7 +48,20 @@ where
 {
     /// Create a new [`ChannelBank`] stage.
     pub fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, channels: HashMap::default(), channel_queue: VecDeque::new(), prev }
+        Self {
+            cfg,
+            channels: HashMap::default(),
+            channel_queue: VecDeque::new(),
+            last_channel_id: None,
+            prev,
+            events: PipelineEvents::none(),
+        }
+    }
+
+    /// Sets the [`PipelineEvents`] handle of the [`ChannelBank`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
     }
 
     /// Returns the size of the channel bank by accumulating over all channels.
@@ -62,16 +74,17 @@ where
     pub fn prune(&mut self) -> PipelineResult<()> {
         let mut total_size = self.size();
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
-        let max_channel_bank_size = if self.cfg.is_fjord_active(origin.timestamp) {
-            FJORD_MAX_CHANNEL_BANK_SIZE
-        } else {
-            MAX_CHANNEL_BANK_SIZE
-        };
+        let max_channel_bank_size = self.cfg.max_channel_bank_size(origin.timestamp);
         while total_size > max_channel_bank_size {
             let id =
                 self.channel_queue.pop_front().ok_or(PipelineError::ChannelProviderEmpty.crit())?;
             let channel = self.channels.remove(&id).ok_or(PipelineError::ChannelNotFound.crit())?;
             total_size -= channel.size();
+            self.events.emit(PipelineEvent::ChannelClosed {
+                id: id.into(),
+                origin,
+                reason: ChannelCloseReason::Dropped,
+            });
         }
         Ok(())
     }
@@ -87,6 +100,7 @@ where
                 let channel = Channel::new(frame.id, origin);
                 self.channel_queue.push_back(frame.id);
                 self.channels.insert(frame.id, channel);
+                self.events.emit(PipelineEvent::ChannelOpened { id: frame.id.into(), origin });
                 self.channels.get_mut(&frame.id).expect("Channel must be in queue")
             }
         };
@@ -136,6 +150,11 @@ where
             );
             self.channels.remove(&first);
             self.channel_queue.pop_front();
+            self.events.emit(PipelineEvent::ChannelClosed {
+                id: first.into(),
+                origin,
+                reason: ChannelCloseReason::TimedOut,
+            });
             return Ok(None);
         }
 
@@ -172,6 +191,12 @@ where
         let frame_data = channel.frame_data();
         self.channels.remove(&channel_id);
         self.channel_queue.remove(index);
+        self.last_channel_id = Some(channel_id);
+        self.events.emit(PipelineEvent::ChannelClosed {
+            id: channel_id.into(),
+            origin,
+            reason: ChannelCloseReason::Ready,
+        });
 
         frame_data.ok_or(PipelineError::ChannelProviderEmpty.crit())
     }
@@ -213,6 +238,10 @@ where
         res?;
         Err(PipelineError::NotEnoughData.temp())
     }
+
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.last_channel_id
+    }
 }
 
 impl<P> OriginProvider for ChannelBank<P>
@@ -233,6 +262,7 @@ where
         self.prev.signal(signal).await?;
         self.channels.clear();
         self.channel_queue = VecDeque::with_capacity(10);
+        self.last_channel_id = None;
         Ok(())
     }
 }
@@ -245,7 +275,9 @@ mod tests {
         types::ResetSignal,
     };
     use alloc::{vec, vec::Vec};
-    use kona_genesis::HardForkConfig;
+    use kona_genesis::{
+        HardForkConfig, MAX_CHANNEL_BANK_SIZE_BEDROCK, MAX_CHANNEL_BANK_SIZE_FJORD,
+    };
     use tracing::Level;
     use tracing_subscriber::layer::SubscriberExt;
 
@@ -453,7 +485,7 @@ mod tests {
             current_size = channel_bank.size();
             let next_frame = frames.pop().unwrap();
             channel_bank.ingest_frame(next_frame).unwrap();
-            assert!(channel_bank.size() <= MAX_CHANNEL_BANK_SIZE);
+            assert!(channel_bank.size() <= MAX_CHANNEL_BANK_SIZE_BEDROCK);
         }
         // There should be a bunch of frames leftover
         assert!(!frames.is_empty());
@@ -481,7 +513,7 @@ mod tests {
             current_size = channel_bank.size();
             let next_frame = frames.pop().unwrap();
             channel_bank.ingest_frame(next_frame).unwrap();
-            assert!(channel_bank.size() <= FJORD_MAX_CHANNEL_BANK_SIZE);
+            assert!(channel_bank.size() <= MAX_CHANNEL_BANK_SIZE_FJORD);
         }
         // There should be a bunch of frames leftover
         assert!(!frames.is_empty());
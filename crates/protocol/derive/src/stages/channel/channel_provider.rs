@@ -2,23 +2,28 @@
 
 use super::{ChannelAssembler, ChannelBank, ChannelReaderProvider, NextFrameProvider};
 use crate::{
-    errors::PipelineError,
+    ChannelOrderingPolicy,
+    errors::{PipelineError, PipelineErrorKind},
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{MemoryComponent, PipelineEvents, PipelineMemory, PipelineResult, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
-use kona_protocol::BlockInfo;
+use kona_protocol::{BlockInfo, ChannelId};
 
 /// The [`ChannelProvider`] stage is a mux between the [`ChannelBank`] and [`ChannelAssembler`]
 /// stages.
 ///
 /// Rules:
-/// When Holocene is not active, the [`ChannelBank`] is used.
-/// When Holocene is active, the [`ChannelAssembler`] is used.
+/// When the [`ChannelOrdering`] is interleaved, the [`ChannelBank`] is used.
+/// When the [`ChannelOrdering`] is strict, the [`ChannelAssembler`] is used.
+///
+/// By default, the ordering follows Holocene activation. See [`ChannelOrderingPolicy`].
+///
+/// [`ChannelOrdering`]: crate::ChannelOrdering
 #[derive(Debug)]
 pub struct ChannelProvider<P>
 where
@@ -41,6 +46,12 @@ where
     ///
     /// Must be [`None`] if `prev` or `channel_bank` is [`Some`].
     pub channel_assembler: Option<ChannelAssembler<P>>,
+    /// The policy selecting the active stage.
+    pub ordering_policy: ChannelOrderingPolicy,
+    /// The handle passed on to the active stage to emit channel lifecycle events to.
+    pub events: PipelineEvents,
+    /// The handle that the size of the buffered channels is reported to.
+    pub memory: PipelineMemory,
 }
 
 impl<P> ChannelProvider<P>
@@ -49,34 +60,77 @@ where
 {
     /// Creates a new [`ChannelProvider`] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev: Some(prev), channel_bank: None, channel_assembler: None }
+        Self {
+            cfg,
+            prev: Some(prev),
+            channel_bank: None,
+            channel_assembler: None,
+            ordering_policy: ChannelOrderingPolicy::Hardfork,
+            events: PipelineEvents::none(),
+            memory: PipelineMemory::unbounded(),
+        }
+    }
+
+    /// Sets the [`ChannelOrderingPolicy`] of the [`ChannelProvider`].
+    pub const fn with_ordering_policy(mut self, ordering_policy: ChannelOrderingPolicy) -> Self {
+        self.ordering_policy = ordering_policy;
+        self
+    }
+
+    /// Sets the [`PipelineEvents`] handle of the [`ChannelProvider`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Sets the [`PipelineMemory`] handle of the [`ChannelProvider`].
+    pub fn with_memory(mut self, memory: PipelineMemory) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Returns the size of the channels buffered by the active stage.
+    pub fn size(&self) -> usize {
+        if let Some(channel_assembler) = self.channel_assembler.as_ref() {
+            channel_assembler.channel.as_ref().map_or(0, |c| c.size())
+        } else {
+            self.channel_bank.as_ref().map_or(0, |channel_bank| channel_bank.size())
+        }
     }
 
     /// Attempts to update the active stage of the mux.
     pub(crate) fn attempt_update(&mut self) -> PipelineResult<()> {
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+        let strict = self.ordering_policy.ordering(&self.cfg, origin.timestamp).is_strict();
         if let Some(prev) = self.prev.take() {
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
-            if self.cfg.is_holocene_active(origin.timestamp) {
-                self.channel_assembler = Some(ChannelAssembler::new(self.cfg.clone(), prev));
+            if strict {
+                self.channel_assembler = Some(
+                    ChannelAssembler::new(self.cfg.clone(), prev).with_events(self.events.clone()),
+                );
             } else {
-                self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), prev));
+                self.channel_bank =
+                    Some(ChannelBank::new(self.cfg.clone(), prev).with_events(self.events.clone()));
             }
-        } else if self.channel_bank.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
-            // If the channel bank is active and Holocene is also active, transition to the channel
+        } else if self.channel_bank.is_some() && strict {
+            // If the channel bank is active and strict ordering applies, transition to the channel
             // assembler.
             let channel_bank = self.channel_bank.take().expect("Must have channel bank");
-            self.channel_assembler =
-                Some(ChannelAssembler::new(self.cfg.clone(), channel_bank.prev));
-        } else if self.channel_assembler.is_some() && !self.cfg.is_holocene_active(origin.timestamp)
-        {
+            self.channel_assembler = Some(
+                ChannelAssembler::new(self.cfg.clone(), channel_bank.prev)
+                    .with_events(self.events.clone()),
+            );
+        } else if self.channel_assembler.is_some() && !strict {
             // If the channel assembler is active, and Holocene is not active, it indicates an L1
             // reorg around Holocene activation. Transition back to the channel bank
             // until Holocene re-activates.
             let channel_assembler =
                 self.channel_assembler.take().expect("Must have channel assembler");
-            self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), channel_assembler.prev));
+            self.channel_bank = Some(
+                ChannelBank::new(self.cfg.clone(), channel_assembler.prev)
+                    .with_events(self.events.clone()),
+            );
         }
         Ok(())
     }
@@ -125,13 +179,15 @@ where
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         self.attempt_update()?;
 
-        if let Some(channel_assembler) = self.channel_assembler.as_mut() {
+        let result = if let Some(channel_assembler) = self.channel_assembler.as_mut() {
             channel_assembler.signal(signal).await
         } else if let Some(channel_bank) = self.channel_bank.as_mut() {
             channel_bank.signal(signal).await
         } else {
             Err(PipelineError::NotEnoughData.temp())
-        }
+        };
+        self.memory.set(MemoryComponent::ChannelProvider, self.size());
+        result
     }
 }
 
@@ -143,12 +199,31 @@ where
     async fn next_data(&mut self) -> PipelineResult<Option<Bytes>> {
         self.attempt_update()?;
 
-        if let Some(channel_assembler) = self.channel_assembler.as_mut() {
+        let data = if let Some(channel_assembler) = self.channel_assembler.as_mut() {
             channel_assembler.next_data().await
         } else if let Some(channel_bank) = self.channel_bank.as_mut() {
             channel_bank.next_data().await
         } else {
             Err(PipelineError::NotEnoughData.temp())
+        };
+        self.memory.set(MemoryComponent::ChannelProvider, self.size());
+
+        // Frames are only pulled once no channel is ready, and the downstream stages only pull
+        // channels once they drained their own buffers. If L1 retrieval still holds off, nothing
+        // is left to free memory, and derivation can't make progress without more L1 data.
+        match data {
+            Err(PipelineErrorKind::Temporary(PipelineError::MemoryBudgetExceeded)) => {
+                Err(PipelineError::MemoryBudgetExceeded.crit())
+            }
+            data => data,
+        }
+    }
+
+    fn channel_id(&self) -> Option<ChannelId> {
+        if let Some(channel_assembler) = self.channel_assembler.as_ref() {
+            channel_assembler.channel_id()
+        } else {
+            self.channel_bank.as_ref().and_then(|channel_bank| channel_bank.channel_id())
         }
     }
 }
@@ -156,7 +231,8 @@ where
 #[cfg(test)]
 mod test {
     use crate::{
-        ChannelProvider, ChannelReaderProvider, OriginProvider, PipelineError, ResetSignal,
+        ChannelOrdering, ChannelOrderingPolicy, ChannelProvider, ChannelReaderProvider,
+        MemoryComponent, OriginProvider, PipelineError, PipelineMemory, ResetSignal,
         SignalReceiver, test_utils::TestNextFrameProvider,
     };
     use alloc::{sync::Arc, vec};
@@ -178,6 +254,55 @@ mod test {
         assert!(channel_provider.channel_assembler.is_some());
     }
 
+    #[test]
+    fn test_channel_provider_fixed_ordering_policy() {
+        let provider = TestNextFrameProvider::new(vec![]);
+        let cfg = Arc::new(RollupConfig::default());
+        let mut channel_provider = ChannelProvider::new(cfg, provider)
+            .with_ordering_policy(ChannelOrderingPolicy::Fixed(ChannelOrdering::Strict));
+
+        assert!(channel_provider.attempt_update().is_ok());
+        assert!(channel_provider.channel_bank.is_none());
+        assert!(channel_provider.channel_assembler.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_channel_provider_reports_memory() {
+        let frames = [crate::frame!(0xFF, 0, vec![0xDD; 50], false)];
+        let provider = TestNextFrameProvider::new(frames.into_iter().map(Ok).collect());
+        let cfg = Arc::new(RollupConfig::default());
+        let memory = PipelineMemory::unbounded();
+        let mut channel_provider = ChannelProvider::new(cfg, provider).with_memory(memory.clone());
+
+        // Ingest the frame into the channel bank.
+        let err = channel_provider.next_data().await.unwrap_err();
+        assert_eq!(err, PipelineError::NotEnoughData.temp());
+        assert_eq!(memory.usage(MemoryComponent::ChannelProvider), 250);
+
+        channel_provider.signal(ResetSignal::default().signal()).await.unwrap();
+        assert_eq!(memory.usage(MemoryComponent::ChannelProvider), 0);
+    }
+
+    #[tokio::test]
+    async fn test_channel_provider_memory_budget_exceeded_is_critical() {
+        let provider = TestNextFrameProvider::new(vec![
+            Err(PipelineError::MemoryBudgetExceeded.temp()),
+            Ok(crate::frame!(0xFF, 0, vec![0xDD; 50], false)),
+        ]);
+        let cfg = Arc::new(RollupConfig::default());
+        let memory = PipelineMemory::with_budget(200);
+        let mut channel_provider = ChannelProvider::new(cfg, provider).with_memory(memory.clone());
+
+        let err = channel_provider.next_data().await.unwrap_err();
+        assert_eq!(err, PipelineError::NotEnoughData.temp());
+        assert_eq!(memory.usage(MemoryComponent::ChannelProvider), 250);
+
+        // The pending channel is kept, and the pipeline can't make progress.
+        let err = channel_provider.next_data().await.unwrap_err();
+        assert_eq!(err, PipelineError::MemoryBudgetExceeded.crit());
+        assert_eq!(memory.usage(MemoryComponent::ChannelProvider), 250);
+    }
+
     #[test]
     fn test_channel_provider_bank_active() {
         let provider = TestNextFrameProvider::new(vec![]);
@@ -1,18 +1,20 @@
 //! This module contains the `ChannelReader` struct.
 
 use crate::{
-    BatchStreamProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
-    SignalReceiver,
+    BatchStreamProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineEvent,
+    PipelineEvents, PipelineResult, Signal, SignalReceiver,
 };
 use alloc::{boxed::Box, sync::Arc};
-use alloy_primitives::Bytes;
+use alloy_primitives::{Bytes, hex};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::{
     MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD, RollupConfig,
 };
-use kona_protocol::{Batch, BatchReader, BlockInfo};
-use tracing::{debug, warn};
+use kona_protocol::{
+    Batch, BatchReader, BlockInfo, BrotliDecompressionError, ChannelId, DecompressionError,
+};
+use tracing::{debug, error, warn};
 
 /// The [`ChannelReader`] provider trait.
 #[async_trait]
@@ -22,6 +24,10 @@ pub trait ChannelReaderProvider {
     /// ensure maintain consistency around channel bank pruning which depends upon the order
     /// of operations.
     async fn next_data(&mut self) -> PipelineResult<Option<Bytes>>;
+
+    /// Returns the [`ChannelId`] of the channel most recently returned by
+    /// [`ChannelReaderProvider::next_data`], if it is known.
+    fn channel_id(&self) -> Option<ChannelId>;
 }
 
 /// [`ChannelReader`] is a stateful stage that reads [`Batch`]es from `Channel`s.
@@ -42,8 +48,12 @@ where
     pub prev: P,
     /// The batch reader.
     pub next_batch: Option<BatchReader>,
+    /// The [`ChannelId`] of the channel being read, if known.
+    pub channel_id: Option<ChannelId>,
     /// The rollup configuration.
     pub cfg: Arc<RollupConfig>,
+    /// The handle the stage emits [`PipelineEvent`]s to.
+    pub events: PipelineEvents,
 }
 
 impl<P> ChannelReader<P>
@@ -52,7 +62,13 @@ where
 {
     /// Create a new [`ChannelReader`] stage.
     pub const fn new(prev: P, cfg: Arc<RollupConfig>) -> Self {
-        Self { prev, next_batch: None, cfg }
+        Self { prev, next_batch: None, channel_id: None, cfg, events: PipelineEvents::none() }
+    }
+
+    /// Sets the [`PipelineEvents`] handle of the [`ChannelReader`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
     }
 
     /// Creates the batch reader from available channel data.
@@ -70,6 +86,7 @@ where
 
             self.next_batch =
                 Some(BatchReader::new(&channel[..], max_rlp_bytes_per_channel as usize));
+            self.channel_id = self.prev.channel_id();
             kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_BATCH_READER_SET, 1);
         }
         Ok(())
@@ -79,6 +96,7 @@ where
     /// decoding / decompression state to a fresh start.
     pub fn next_channel(&mut self) {
         self.next_batch = None;
+        self.channel_id = None;
         kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_BATCH_READER_SET, 0);
     }
 }
@@ -109,6 +127,10 @@ where
         self.next_channel();
     }
 
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.channel_id
+    }
+
     async fn next_batch(&mut self) -> PipelineResult<Batch> {
         if let Err(e) = self.set_batch_reader().await {
             debug!(target: "channel_reader", "Failed to set batch reader: {:?}", e);
@@ -138,6 +160,10 @@ where
                     ty as f64
                 );
             }
+            Err(DecompressionError::BrotliError(BrotliDecompressionError::AllocationFailed(n))) => {
+                error!(target: "channel_reader", "Failed to allocate memory to decompress batch");
+                return Err(PipelineError::AllocationFailed(n).crit());
+            }
             Err(err) => {
                 debug!(target: "channel_reader", ?err, "Failed to decompress batch");
                 self.next_channel();
@@ -146,13 +172,29 @@ where
         }
 
         // Read the next batch from the reader's decompressed data
-        match next_batch.next_batch(self.cfg.as_ref()).ok_or(PipelineError::NotEnoughData.temp()) {
+        let next_batch = next_batch.try_next_batch(self.cfg.as_ref()).map_err(|e| {
+            error!(target: "channel_reader", "Failed to allocate memory to decode batch");
+            PipelineError::AllocationFailed(e.0).crit()
+        })?;
+        match next_batch.ok_or(PipelineError::NotEnoughData.temp()) {
             Ok(batch) => {
                 kona_macros::inc!(
                     gauge,
                     crate::metrics::Metrics::PIPELINE_READ_BATCHES,
                     "type" => batch.to_string(),
                 );
+                let (first_timestamp, last_timestamp) = match &batch {
+                    Batch::Single(batch) => (batch.timestamp, batch.timestamp),
+                    Batch::Span(batch) => (batch.starting_timestamp(), batch.final_timestamp()),
+                };
+                if let Some(origin) = self.prev.origin() {
+                    self.events.emit(PipelineEvent::BatchDecoded {
+                        channel_id: self.channel_id.map(Into::into),
+                        first_timestamp,
+                        last_timestamp,
+                        origin,
+                    });
+                }
                 Ok(batch)
             }
             Err(e) => {
@@ -182,8 +224,15 @@ where
             Signal::FlushChannel => {
                 // Drop the current in-progress channel.
                 warn!(target: "channel_reader", "Flushed channel");
-                self.next_batch = None;
-                kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_BATCH_READER_SET, 0);
+                self.next_channel();
+            }
+            Signal::FlushChannelById(id) => {
+                // Only drop the in-progress channel if it is the targeted one. A channel with an
+                // unknown ID is conservatively treated as a match.
+                if self.next_batch.is_some() && self.channel_id.is_none_or(|c| c == id) {
+                    warn!(target: "channel_reader", "Flushed channel (ID: {})", hex::encode(id));
+                    self.next_channel();
+                }
             }
             s => {
                 self.prev.signal(s).await?;
@@ -200,8 +249,9 @@ mod test {
     use crate::{
         errors::PipelineErrorKind, test_utils::TestChannelReaderProvider, types::ResetSignal,
     };
-    use alloc::vec;
+    use alloc::{vec, vec::Vec};
     use kona_genesis::HardForkConfig;
+    use spin::Mutex;
 
     fn new_compressed_batch_data() -> Bytes {
         let file_contents =
@@ -223,6 +273,25 @@ mod test {
         assert!(reader.next_batch.is_none());
     }
 
+    #[tokio::test]
+    async fn test_flush_channel_reader_by_id() {
+        let mut mock = TestChannelReaderProvider::new(vec![Ok(Some(new_compressed_batch_data()))]);
+        mock.channel_id = Some([0xAA; 16]);
+        let mut reader = ChannelReader::new(mock, Arc::new(RollupConfig::default()));
+        assert!(reader.next_batch().await.is_ok());
+        assert_eq!(reader.channel_id, Some([0xAA; 16]));
+
+        // A flush targeting another channel preserves the channel being read.
+        reader.signal(Signal::FlushChannelById([0xBB; 16])).await.unwrap();
+        assert!(reader.next_batch.is_some());
+        assert!(!reader.prev.reset);
+
+        reader.signal(Signal::FlushChannelById([0xAA; 16])).await.unwrap();
+        assert!(reader.next_batch.is_none());
+        assert!(reader.channel_id.is_none());
+        assert!(!reader.prev.reset);
+    }
+
     #[tokio::test]
     async fn test_reset_channel_reader() {
         let mock = TestChannelReaderProvider::new(vec![Ok(None)]);
@@ -276,6 +345,29 @@ mod test {
         assert!(reader.next_batch.is_some());
     }
 
+    #[tokio::test]
+    async fn test_next_batch_emits_decoded_batch() {
+        let mut mock = TestChannelReaderProvider::new(vec![Ok(Some(new_compressed_batch_data()))]);
+        mock.channel_id = Some([0xAA; 16]);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        let mut reader = ChannelReader::new(mock, Arc::new(RollupConfig::default()))
+            .with_events(PipelineEvents::new(move |event| sink.lock().push(event)));
+
+        let Batch::Span(batch) = reader.next_batch().await.unwrap() else {
+            panic!("expected a span batch");
+        };
+        assert_eq!(
+            *received.lock(),
+            [PipelineEvent::BatchDecoded {
+                channel_id: Some([0xAA; 16].into()),
+                first_timestamp: batch.starting_timestamp(),
+                last_timestamp: batch.final_timestamp(),
+                origin: BlockInfo::default(),
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn test_flush_post_holocene() {
         let raw = new_compressed_batch_data();
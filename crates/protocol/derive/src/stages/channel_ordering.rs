@@ -0,0 +1,208 @@
+//! Contains the [ChannelOrdering] rules and the [ChannelOrderingPolicy] that selects between
+//! them for the [FrameQueue] and [ChannelProvider] stages.
+//!
+//! [FrameQueue]: crate::stages::FrameQueue
+//! [ChannelProvider]: crate::stages::ChannelProvider
+
+use alloc::collections::VecDeque;
+use kona_genesis::RollupConfig;
+use kona_protocol::Frame;
+
+/// The rules governing how frames of different channels may be ordered on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelOrdering {
+    /// Pre-Holocene ordering.
+    ///
+    /// Frames of several channels may be interleaved and arrive out of order. Frames are
+    /// buffered per channel by the [ChannelBank] until each channel is complete.
+    ///
+    /// [ChannelBank]: crate::stages::ChannelBank
+    Interleaved,
+    /// Holocene ordering.
+    ///
+    /// Frames must arrive in order, one channel at a time. Frames that break the ordering are
+    /// dropped by the [FrameQueue] and channels are built by the [ChannelAssembler].
+    ///
+    /// [FrameQueue]: crate::stages::FrameQueue
+    /// [ChannelAssembler]: crate::stages::ChannelAssembler
+    Strict,
+}
+
+impl ChannelOrdering {
+    /// Returns `true` if the ordering is [ChannelOrdering::Strict].
+    pub const fn is_strict(&self) -> bool {
+        matches!(self, Self::Strict)
+    }
+
+    /// Returns the metric label for the ordering.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Interleaved => "interleaved",
+            Self::Strict => "strict",
+        }
+    }
+
+    /// Returns the number of frames in the queue that arrive out of channel order, either
+    /// because they skip a frame number within their channel or because they interleave with a
+    /// channel that has not been closed.
+    pub fn count_reordered(queue: &VecDeque<Frame>) -> usize {
+        queue
+            .iter()
+            .zip(queue.iter().skip(1))
+            .filter(|(prev, next)| {
+                if prev.id == next.id {
+                    prev.number.checked_add(1) != Some(next.number)
+                } else {
+                    !prev.is_last
+                }
+            })
+            .count()
+    }
+
+    /// Prunes the frames in the queue that violate the ordering, returning the number of frames
+    /// dropped.
+    ///
+    /// [ChannelOrdering::Interleaved] never drops frames. [ChannelOrdering::Strict] drops
+    /// non-sequential frames, frames following a closed channel's last frame, channels that do
+    /// not start at frame `0`, and unclosed channels that are superseded by a new channel.
+    pub fn prune(&self, queue: &mut VecDeque<Frame>) -> usize {
+        if !self.is_strict() {
+            return 0;
+        }
+
+        let len = queue.len();
+        let mut i = 0;
+        while i + 1 < queue.len() {
+            let prev_frame = &queue[i];
+            let next_frame = &queue[i + 1];
+            let extends_channel = prev_frame.id == next_frame.id;
+
+            // If the frames are in the same channel, and the frame numbers are not sequential,
+            // drop the next frame.
+            if extends_channel && prev_frame.number + 1 != next_frame.number {
+                queue.remove(i + 1);
+                continue;
+            }
+
+            // If the frames are in the same channel, and the previous is last, drop the next frame.
+            if extends_channel && prev_frame.is_last {
+                queue.remove(i + 1);
+                continue;
+            }
+
+            // If the frames are in different channels, the next frame must be first.
+            if !extends_channel && next_frame.number != 0 {
+                queue.remove(i + 1);
+                continue;
+            }
+
+            // If the frames are in different channels, and the current channel is not last, walk
+            // back the channel and drop all prev frames.
+            if !extends_channel && !prev_frame.is_last && next_frame.number == 0 {
+                // Find the index of the first frame in the queue with the same channel ID
+                // as the previous frame.
+                let first_frame =
+                    queue.iter().position(|f| f.id == prev_frame.id).expect("infallible");
+
+                // Drain all frames from the previous channel.
+                let drained = queue.drain(first_frame..=i);
+                i = i.saturating_sub(drained.len());
+                continue;
+            }
+
+            i += 1;
+        }
+        len - queue.len()
+    }
+}
+
+/// Selects the [ChannelOrdering] in effect for an L1 origin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChannelOrderingPolicy {
+    /// Follow the rollup config's hardforks: [ChannelOrdering::Strict] once Holocene is active,
+    /// [ChannelOrdering::Interleaved] before.
+    #[default]
+    Hardfork,
+    /// Always apply the given ordering, regardless of hardfork activation.
+    ///
+    /// Overriding the hardfork schedule diverges from the OP Stack derivation rules, and is only
+    /// meant for custom chains and tests.
+    Fixed(ChannelOrdering),
+}
+
+impl ChannelOrderingPolicy {
+    /// Returns the [ChannelOrdering] in effect for an L1 origin with the given timestamp.
+    pub fn ordering(&self, cfg: &RollupConfig, timestamp: u64) -> ChannelOrdering {
+        match self {
+            Self::Hardfork if cfg.is_holocene_active(timestamp) => ChannelOrdering::Strict,
+            Self::Hardfork => ChannelOrdering::Interleaved,
+            Self::Fixed(ordering) => *ordering,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use kona_genesis::HardForkConfig;
+
+    fn frame(id: u8, number: u16, is_last: bool) -> Frame {
+        Frame { id: [id; 16], number, data: vec![], is_last }
+    }
+
+    #[test]
+    fn test_policy_follows_holocene() {
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig { holocene_time: Some(10), ..Default::default() },
+            ..Default::default()
+        };
+        let policy = ChannelOrderingPolicy::default();
+        assert_eq!(policy.ordering(&cfg, 9), ChannelOrdering::Interleaved);
+        assert_eq!(policy.ordering(&cfg, 10), ChannelOrdering::Strict);
+    }
+
+    #[test]
+    fn test_policy_fixed_overrides_hardfork() {
+        let cfg = RollupConfig::default();
+        let policy = ChannelOrderingPolicy::Fixed(ChannelOrdering::Strict);
+        assert_eq!(policy.ordering(&cfg, 0), ChannelOrdering::Strict);
+    }
+
+    #[test]
+    fn test_count_reordered() {
+        let queue = VecDeque::from(vec![
+            frame(0xAA, 0, false),
+            frame(0xBB, 0, false), // interleaves with an open channel
+            frame(0xAA, 1, true),  // interleaves with an open channel
+            frame(0xCC, 0, false),
+            frame(0xCC, 2, true), // skips a frame number
+        ]);
+        assert_eq!(ChannelOrdering::count_reordered(&queue), 3);
+        assert_eq!(ChannelOrdering::count_reordered(&VecDeque::new()), 0);
+    }
+
+    #[test]
+    fn test_prune_interleaved_keeps_frames() {
+        let mut queue = VecDeque::from(vec![frame(0xAA, 0, false), frame(0xBB, 0, true)]);
+        assert_eq!(ChannelOrdering::Interleaved.prune(&mut queue), 0);
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn test_prune_strict_drops_frames() {
+        let mut queue = VecDeque::from(vec![
+            frame(0xAA, 0, false),
+            frame(0xAA, 2, false),
+            frame(0xBB, 0, false),
+            frame(0xBB, 1, true),
+        ]);
+        assert_eq!(ChannelOrdering::Strict.prune(&mut queue), 2);
+        assert_eq!(queue, VecDeque::from(vec![frame(0xBB, 0, false), frame(0xBB, 1, true)]));
+    }
+
+    #[test]
+    fn test_prune_strict_empty_queue() {
+        assert_eq!(ChannelOrdering::Strict.prune(&mut VecDeque::new()), 0);
+    }
+}
@@ -1,7 +1,8 @@
 //! This module contains the [FrameQueue] stage of the derivation pipeline.
 
 use crate::{
-    NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
+    BatcherTxRef, ChannelOrdering, ChannelOrderingPolicy, NextFrameProvider, OriginAdvancer,
+    OriginProvider, PipelineError, PipelineEvent, PipelineEvents, PipelineResult, Signal,
     SignalReceiver,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
@@ -21,6 +22,12 @@ pub trait FrameQueueProvider {
     /// If there is data, it pushes it into the next stage.
     /// If there is no data, it returns an error.
     async fn next_data(&mut self) -> PipelineResult<Self::Item>;
+
+    /// Returns the batcher transaction the item most recently returned by
+    /// [`FrameQueueProvider::next_data`] was read from, if it is known.
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        None
+    }
 }
 
 /// The [`FrameQueue`] stage of the derivation pipeline.
@@ -38,6 +45,10 @@ where
     pub queue: VecDeque<Frame>,
     /// The rollup config.
     pub rollup_config: Arc<RollupConfig>,
+    /// The policy selecting the [ChannelOrdering] that frames are pruned against.
+    pub ordering_policy: ChannelOrderingPolicy,
+    /// The handle the stage emits [`PipelineEvent`]s to.
+    pub events: PipelineEvents,
 }
 
 impl<P> FrameQueue<P>
@@ -48,7 +59,30 @@ where
     ///
     /// [`L1Retrieval`]: crate::stages::L1Retrieval
     pub const fn new(prev: P, cfg: Arc<RollupConfig>) -> Self {
-        Self { prev, queue: VecDeque::new(), rollup_config: cfg }
+        Self {
+            prev,
+            queue: VecDeque::new(),
+            rollup_config: cfg,
+            ordering_policy: ChannelOrderingPolicy::Hardfork,
+            events: PipelineEvents::none(),
+        }
+    }
+
+    /// Sets the [`PipelineEvents`] handle of the [`FrameQueue`].
+    pub fn with_events(mut self, events: PipelineEvents) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Sets the [ChannelOrderingPolicy] of the [`FrameQueue`].
+    pub const fn with_ordering_policy(mut self, ordering_policy: ChannelOrderingPolicy) -> Self {
+        self.ordering_policy = ordering_policy;
+        self
+    }
+
+    /// Returns the [ChannelOrdering] in effect for the given origin.
+    pub fn ordering(&self, origin: BlockInfo) -> ChannelOrdering {
+        self.ordering_policy.ordering(&self.rollup_config, origin.timestamp)
     }
 
     /// Returns if holocene is active.
@@ -56,52 +90,30 @@ where
         self.rollup_config.is_holocene_active(origin.timestamp)
     }
 
-    /// Prunes frames if Holocene is active.
+    /// Prunes frames that violate the [ChannelOrdering] in effect for the given origin.
     pub fn prune(&mut self, origin: BlockInfo) {
-        if !self.is_holocene_active(origin) {
-            return;
-        }
-
-        let mut i = 0;
-        while i < self.queue.len() - 1 {
-            let prev_frame = &self.queue[i];
-            let next_frame = &self.queue[i + 1];
-            let extends_channel = prev_frame.id == next_frame.id;
-
-            // If the frames are in the same channel, and the frame numbers are not sequential,
-            // drop the next frame.
-            if extends_channel && prev_frame.number + 1 != next_frame.number {
-                self.queue.remove(i + 1);
-                continue;
-            }
-
-            // If the frames are in the same channel, and the previous is last, drop the next frame.
-            if extends_channel && prev_frame.is_last {
-                self.queue.remove(i + 1);
-                continue;
-            }
-
-            // If the frames are in different channels, the next frame must be first.
-            if !extends_channel && next_frame.number != 0 {
-                self.queue.remove(i + 1);
-                continue;
-            }
-
-            // If the frames are in different channels, and the current channel is not last, walk
-            // back the channel and drop all prev frames.
-            if !extends_channel && !prev_frame.is_last && next_frame.number == 0 {
-                // Find the index of the first frame in the queue with the same channel ID
-                // as the previous frame.
-                let first_frame =
-                    self.queue.iter().position(|f| f.id == prev_frame.id).expect("infallible");
-
-                // Drain all frames from the previous channel.
-                let drained = self.queue.drain(first_frame..=i);
-                i = i.saturating_sub(drained.len());
-                continue;
-            }
-
-            i += 1;
+        let ordering = self.ordering(origin);
+
+        #[cfg(feature = "metrics")]
+        metrics::gauge!(
+            crate::metrics::Metrics::PIPELINE_FRAME_QUEUE_REORDERED,
+            "ordering" => ordering.as_str(),
+        )
+        .increment(ChannelOrdering::count_reordered(&self.queue) as f64);
+
+        let dropped = ordering.prune(&mut self.queue);
+        if dropped > 0 {
+            debug!(
+                target: "frame_queue",
+                "Dropped {dropped} frames violating {} channel ordering",
+                ordering.as_str()
+            );
+            #[cfg(feature = "metrics")]
+            metrics::gauge!(
+                crate::metrics::Metrics::PIPELINE_FRAME_QUEUE_DROPPED,
+                "ordering" => ordering.as_str(),
+            )
+            .increment(dropped as f64);
         }
     }
 
@@ -121,13 +133,29 @@ where
             }
         };
 
-        let Ok(frames) = Frame::parse_frames(&data.into()) else {
+        let data: Bytes = data.into();
+        let tx = self.prev.last_source();
+        if let Some(origin) = self.origin() {
+            self.events.emit(PipelineEvent::DataRetrieved { bytes: data.len() as u64, tx, origin });
+        }
+
+        let Ok(frames) = Frame::parse_frames(&data) else {
             // There may be more frames in the queue for the
             // pipeline to advance, so don't return an error here.
             error!(target: "frame_queue", "Failed to parse frames from data.");
             return Ok(());
         };
 
+        let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+        for frame in &frames {
+            self.events.emit(PipelineEvent::FrameRetrieved {
+                channel_id: frame.id.into(),
+                frame_number: frame.number,
+                tx,
+                origin,
+            });
+        }
+
         // Optimistically extend the queue with the new frames.
         self.queue.extend(frames);
 
@@ -140,8 +168,7 @@ where
         let queue_size = self.queue.iter().map(|f| f.size()).sum::<usize>() as f64;
         kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_FRAME_QUEUE_MEM, queue_size);
 
-        // Prune frames if Holocene is active.
-        let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+        // Prune frames that violate the channel ordering.
         self.prune(origin);
 
         Ok(())
@@ -201,8 +228,11 @@ where
 pub(crate) mod tests {
     use super::*;
     use crate::{test_utils::TestFrameQueueProvider, types::ResetSignal};
-    use alloc::vec;
+    use alloc::{vec, vec::Vec};
+    use alloy_primitives::B256;
     use kona_genesis::HardForkConfig;
+    use kona_protocol::DERIVATION_VERSION_0;
+    use spin::Mutex;
 
     #[tokio::test]
     async fn test_frame_queue_reset() {
@@ -214,6 +244,58 @@ pub(crate) mod tests {
         assert!(frame_queue.prev.reset);
     }
 
+    #[test]
+    fn test_frame_queue_fixed_ordering_policy() {
+        let mock = TestFrameQueueProvider::new(vec![]);
+        let mut frame_queue = FrameQueue::new(mock, Default::default())
+            .with_ordering_policy(ChannelOrderingPolicy::Fixed(ChannelOrdering::Strict));
+        assert!(!frame_queue.is_holocene_active(BlockInfo::default()));
+        assert_eq!(frame_queue.ordering(BlockInfo::default()), ChannelOrdering::Strict);
+
+        let frames = [
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false),
+            crate::frame!(0xFF, 0, vec![0xDD; 50], true),
+        ];
+        frame_queue.queue = VecDeque::from(frames.to_vec());
+        frame_queue.prune(BlockInfo::default());
+        assert_eq!(frame_queue.queue, VecDeque::from(frames[1..].to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_frame_queue_emits_retrieved_frames() {
+        let frames = [
+            crate::frame!(0xFF, 0, vec![0xDD; 50], false),
+            crate::frame!(0xFF, 1, vec![0xDD; 50], true),
+        ];
+        let mut data = vec![DERIVATION_VERSION_0];
+        frames.iter().for_each(|frame| data.extend_from_slice(&frame.encode()));
+        let bytes = data.len() as u64;
+        let mut mock = TestFrameQueueProvider::new(vec![Ok(Bytes::from(data))]);
+        mock.set_origin(BlockInfo::default());
+        mock.source = Some(BatcherTxRef { tx_hash: B256::with_last_byte(1), blob_hash: None });
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        let mut frame_queue = FrameQueue::new(mock, Default::default())
+            .with_events(PipelineEvents::new(move |event| sink.lock().push(event)));
+        frame_queue.load_frames().await.unwrap();
+
+        let tx = Some(BatcherTxRef { tx_hash: B256::with_last_byte(1), blob_hash: None });
+        let events = core::iter::once(PipelineEvent::DataRetrieved {
+            bytes,
+            tx,
+            origin: BlockInfo::default(),
+        })
+        .chain(frames.iter().map(|frame| PipelineEvent::FrameRetrieved {
+            channel_id: frame.id.into(),
+            frame_number: frame.number,
+            tx,
+            origin: BlockInfo::default(),
+        }))
+        .collect::<Vec<_>>();
+        assert_eq!(*received.lock(), events);
+    }
+
     #[tokio::test]
     async fn test_frame_queue_empty_bytes() {
         let data = vec![Ok(Bytes::from(vec![0x00]))];
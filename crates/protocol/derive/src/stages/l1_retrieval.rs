@@ -1,8 +1,9 @@
 //! Contains the [L1Retrieval] stage of the derivation pipeline.
 
 use crate::{
-    ActivationSignal, DataAvailabilityProvider, FrameQueueProvider, OriginAdvancer, OriginProvider,
-    PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal, SignalReceiver,
+    ActivationSignal, BatcherTxRef, DataAvailabilityProvider, FrameQueueProvider, OriginAdvancer,
+    OriginProvider, PipelineError, PipelineErrorKind, PipelineMemory, PipelineResult, ResetSignal,
+    Signal, SignalReceiver,
 };
 use alloc::boxed::Box;
 use alloy_primitives::Address;
@@ -31,6 +32,10 @@ pub trait L1RetrievalProvider {
 /// For each L1 [`BlockInfo`] pulled from the [`PollingTraversal`] stage, [`L1Retrieval`] fetches
 /// the associated data from a specified [`DataAvailabilityProvider`].
 ///
+/// While the data buffered by the downstream stages exceeds the budget of its [`PipelineMemory`]
+/// handle, [`L1Retrieval`] applies backpressure by returning
+/// [`PipelineError::MemoryBudgetExceeded`] instead of fetching more data.
+///
 /// [`PollingTraversal`]: crate::PollingTraversal
 #[derive(Debug)]
 pub struct L1Retrieval<DAP, P>
@@ -44,6 +49,8 @@ where
     pub provider: DAP,
     /// The current block ref.
     pub next: Option<BlockInfo>,
+    /// The memory handle checked before fetching more data.
+    pub memory: PipelineMemory,
 }
 
 impl<DAP, P> L1Retrieval<DAP, P>
@@ -55,8 +62,14 @@ where
     /// [`DataAvailabilityProvider`].
     ///
     /// [`PollingTraversal`]: crate::PollingTraversal
-    pub const fn new(prev: P, provider: DAP) -> Self {
-        Self { prev, provider, next: None }
+    pub fn new(prev: P, provider: DAP) -> Self {
+        Self { prev, provider, next: None, memory: PipelineMemory::unbounded() }
+    }
+
+    /// Sets the [`PipelineMemory`] handle of the [`L1Retrieval`] stage.
+    pub fn with_memory(mut self, memory: PipelineMemory) -> Self {
+        self.memory = memory;
+        self
     }
 }
 
@@ -80,6 +93,16 @@ where
     type Item = DAP::Item;
 
     async fn next_data(&mut self) -> PipelineResult<Self::Item> {
+        if self.memory.is_exceeded() {
+            debug!(
+                target: "l1_retrieval",
+                usage = self.memory.total(),
+                budget = ?self.memory.budget(),
+                "Memory budget exceeded, holding off on fetching L1 data"
+            );
+            return Err(PipelineError::MemoryBudgetExceeded.temp());
+        }
+
         if self.next.is_none() {
             self.next = Some(
                 self.prev
@@ -102,6 +125,10 @@ where
             }
         }
     }
+
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        self.provider.last_source()
+    }
 }
 
 impl<DAP, P> OriginProvider for L1Retrieval<DAP, P>
@@ -136,7 +163,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{TestDAP, TraversalTestHelper};
+    use crate::{
+        MemoryComponent,
+        test_utils::{TestDAP, TraversalTestHelper},
+    };
     use alloc::vec;
     use alloy_primitives::Bytes;
 
@@ -243,10 +273,31 @@ mod tests {
     async fn test_l1_retrieval_existing_data_errors() {
         let traversal = TraversalTestHelper::new_populated();
         let dap = TestDAP { results: vec![Err(PipelineError::Eof.temp())] };
-        let mut retrieval =
-            L1Retrieval { prev: traversal, provider: dap, next: Some(BlockInfo::default()) };
+        let mut retrieval = L1Retrieval {
+            prev: traversal,
+            provider: dap,
+            next: Some(BlockInfo::default()),
+            memory: PipelineMemory::unbounded(),
+        };
         let data = retrieval.next_data().await.unwrap_err();
         assert_eq!(data, PipelineError::Eof.temp());
         assert!(retrieval.next.is_none());
     }
+
+    #[tokio::test]
+    async fn test_l1_retrieval_memory_budget_exceeded() {
+        let traversal = TraversalTestHelper::new_populated();
+        let dap = TestDAP { results: vec![Ok(Bytes::default())] };
+        let memory = PipelineMemory::with_budget(100);
+        let mut retrieval = L1Retrieval::new(traversal, dap).with_memory(memory.clone());
+
+        memory.set(MemoryComponent::ChannelProvider, 101);
+        let err = retrieval.next_data().await.unwrap_err();
+        assert_eq!(err, PipelineError::MemoryBudgetExceeded.temp());
+        assert_eq!(retrieval.next, None);
+
+        memory.set(MemoryComponent::ChannelProvider, 100);
+        let data = retrieval.next_data().await.unwrap();
+        assert_eq!(data, Bytes::default());
+    }
 }
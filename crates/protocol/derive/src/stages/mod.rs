@@ -24,6 +24,9 @@ pub use l1_retrieval::{L1Retrieval, L1RetrievalProvider};
 mod frame_queue;
 pub use frame_queue::{FrameQueue, FrameQueueProvider};
 
+mod channel_ordering;
+pub use channel_ordering::{ChannelOrdering, ChannelOrderingPolicy};
+
 mod channel;
 pub use channel::{
     ChannelAssembler, ChannelBank, ChannelProvider, ChannelReader, ChannelReaderProvider,
@@ -10,7 +10,7 @@ use crate::{
 use alloc::{boxed::Box, vec::Vec};
 use alloy_eips::BlockNumHash;
 use async_trait::async_trait;
-use kona_protocol::{BlockInfo, L2BlockInfo, SingleBatch};
+use kona_protocol::{BlockInfo, ChannelId, L2BlockInfo, SingleBatch};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
 /// A mock implementation of the [`AttributesBuilder`] for testing.
@@ -49,6 +49,8 @@ pub struct TestAttributesProvider {
     pub reset: bool,
     /// Tracks if the provider has been flushed.
     pub flushed: bool,
+    /// The channel ID to return.
+    pub channel_id: Option<ChannelId>,
 }
 
 impl OriginProvider for TestAttributesProvider {
@@ -68,7 +70,7 @@ impl OriginAdvancer for TestAttributesProvider {
 impl SignalReceiver for TestAttributesProvider {
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         match signal {
-            Signal::FlushChannel => self.flushed = true,
+            Signal::FlushChannel | Signal::FlushChannelById(_) => self.flushed = true,
             Signal::Reset { .. } => self.reset = true,
             _ => {}
         }
@@ -85,6 +87,10 @@ impl AttributesProvider for TestAttributesProvider {
     fn is_last_in_span(&self) -> bool {
         self.batches.is_empty()
     }
+
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.channel_id
+    }
 }
 
 /// Creates a new [`TestAttributesProvider`] with the given origin and batches.
@@ -92,5 +98,5 @@ pub const fn new_test_attributes_provider(
     origin: Option<BlockInfo>,
     batches: Vec<PipelineResult<SingleBatch>>,
 ) -> TestAttributesProvider {
-    TestAttributesProvider { origin, batches, reset: false, flushed: false }
+    TestAttributesProvider { origin, batches, reset: false, flushed: false, channel_id: None }
 }
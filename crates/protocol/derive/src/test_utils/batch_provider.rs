@@ -8,7 +8,7 @@ use crate::{
 };
 use alloc::{boxed::Box, vec::Vec};
 use async_trait::async_trait;
-use kona_protocol::{Batch, BlockInfo, L2BlockInfo};
+use kona_protocol::{Batch, BlockInfo, ChannelId, L2BlockInfo};
 
 /// A mock provider for the [`NextBatchProvider`] stage.
 #[derive(Debug, Default)]
@@ -21,12 +21,20 @@ pub struct TestNextBatchProvider {
     pub flushed: bool,
     /// Tracks if the reset method was called.
     pub reset: bool,
+    /// The channel ID to return.
+    pub channel_id: Option<ChannelId>,
 }
 
 impl TestNextBatchProvider {
     /// Creates a new [`TestNextBatchProvider`] with the given origin and batches.
     pub fn new(batches: Vec<PipelineResult<Batch>>) -> Self {
-        Self { origin: Some(BlockInfo::default()), batches, flushed: false, reset: false }
+        Self {
+            origin: Some(BlockInfo::default()),
+            batches,
+            flushed: false,
+            reset: false,
+            channel_id: None,
+        }
     }
 }
 
@@ -46,6 +54,10 @@ impl NextBatchProvider for TestNextBatchProvider {
         self.batches.len()
     }
 
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.channel_id
+    }
+
     async fn next_batch(&mut self, _: L2BlockInfo, _: &[BlockInfo]) -> PipelineResult<Batch> {
         self.batches.pop().ok_or(PipelineError::Eof.temp())?
     }
@@ -67,7 +79,7 @@ impl SignalReceiver for TestNextBatchProvider {
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         match signal {
             Signal::Reset { .. } => self.reset = true,
-            Signal::FlushChannel => self.flushed = true,
+            Signal::FlushChannel | Signal::FlushChannelById(_) => self.flushed = true,
             _ => {}
         }
         Ok(())
@@ -8,7 +8,7 @@ use crate::{
 };
 use alloc::{boxed::Box, vec::Vec};
 use async_trait::async_trait;
-use kona_protocol::{Batch, BlockInfo};
+use kona_protocol::{Batch, BlockInfo, ChannelId};
 
 /// A mock provider for the [`BatchStream`] stage.
 ///
@@ -23,12 +23,20 @@ pub struct TestBatchStreamProvider {
     pub reset: bool,
     /// Whether the provider was flushed.
     pub flushed: bool,
+    /// The channel ID to report for the batches.
+    pub channel_id: Option<ChannelId>,
 }
 
 impl TestBatchStreamProvider {
     /// Creates a new [`TestBatchStreamProvider`] with the given origin and batches.
     pub fn new(batches: Vec<PipelineResult<Batch>>) -> Self {
-        Self { origin: Some(BlockInfo::default()), batches, reset: false, flushed: false }
+        Self {
+            origin: Some(BlockInfo::default()),
+            batches,
+            reset: false,
+            flushed: false,
+            channel_id: None,
+        }
     }
 }
 
@@ -42,6 +50,10 @@ impl OriginProvider for TestBatchStreamProvider {
 impl BatchStreamProvider for TestBatchStreamProvider {
     fn flush(&mut self) {}
 
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.channel_id
+    }
+
     async fn next_batch(&mut self) -> PipelineResult<Batch> {
         self.batches.pop().ok_or(PipelineError::Eof.temp())?
     }
@@ -59,7 +71,7 @@ impl SignalReceiver for TestBatchStreamProvider {
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         match signal {
             Signal::Reset { .. } => self.reset = true,
-            Signal::FlushChannel => self.flushed = true,
+            Signal::FlushChannel | Signal::FlushChannelById(_) => self.flushed = true,
             _ => {}
         }
         Ok(())
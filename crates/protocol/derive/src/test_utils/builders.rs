@@ -0,0 +1,300 @@
+//! Builders for the L1 fixtures consumed by the test providers.
+
+use crate::test_utils::TestChainProvider;
+use alloc::vec::Vec;
+use alloy_consensus::{Eip658Value, Header, Receipt, TxEnvelope};
+use alloy_primitives::{Address, B256, Bytes, Log, LogData, map::HashMap};
+use kona_protocol::{Batch, BlockInfo, SingleBatch};
+
+/// A [`Header`] builder.
+#[derive(Debug, Clone, Default)]
+pub struct TestHeaderBuilder {
+    header: Header,
+}
+
+impl TestHeaderBuilder {
+    /// Create a new [`TestHeaderBuilder`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the block number.
+    pub const fn with_number(mut self, number: u64) -> Self {
+        self.header.number = number;
+        self
+    }
+
+    /// Sets the parent hash.
+    pub const fn with_parent_hash(mut self, parent_hash: B256) -> Self {
+        self.header.parent_hash = parent_hash;
+        self
+    }
+
+    /// Sets the timestamp.
+    pub const fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.header.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the base fee per gas.
+    pub const fn with_base_fee(mut self, base_fee: u64) -> Self {
+        self.header.base_fee_per_gas = Some(base_fee);
+        self
+    }
+
+    /// Sets the receipts root.
+    pub const fn with_receipts_root(mut self, receipts_root: B256) -> Self {
+        self.header.receipts_root = receipts_root;
+        self
+    }
+
+    /// Build the [`Header`].
+    pub fn build(self) -> Header {
+        self.header
+    }
+
+    /// Build the [`Header`], along with the [`BlockInfo`] describing it.
+    pub fn build_with_info(self) -> (Header, BlockInfo) {
+        let header = self.header;
+        let info =
+            BlockInfo::new(header.hash_slow(), header.number, header.parent_hash, header.timestamp);
+        (header, info)
+    }
+}
+
+/// A [`Receipt`] builder.
+///
+/// Receipts are successful by default.
+#[derive(Debug, Clone)]
+pub struct TestReceiptBuilder {
+    receipt: Receipt,
+}
+
+impl Default for TestReceiptBuilder {
+    fn default() -> Self {
+        Self { receipt: Receipt { status: Eip658Value::Eip658(true), ..Receipt::default() } }
+    }
+}
+
+impl TestReceiptBuilder {
+    /// Create a new [`TestReceiptBuilder`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the transaction succeeded.
+    pub const fn with_success(mut self, success: bool) -> Self {
+        self.receipt.status = Eip658Value::Eip658(success);
+        self
+    }
+
+    /// Sets the cumulative gas used.
+    pub const fn with_cumulative_gas_used(mut self, gas: u64) -> Self {
+        self.receipt.cumulative_gas_used = gas;
+        self
+    }
+
+    /// Adds a log emitted by the given address.
+    pub fn with_log(mut self, address: Address, topics: Vec<B256>, data: Bytes) -> Self {
+        self.receipt.logs.push(Log { address, data: LogData::new_unchecked(topics, data) });
+        self
+    }
+
+    /// Build the [`Receipt`].
+    pub fn build(self) -> Receipt {
+        self.receipt
+    }
+}
+
+/// A [`SingleBatch`] builder.
+#[derive(Debug, Clone, Default)]
+pub struct TestSingleBatchBuilder {
+    batch: SingleBatch,
+}
+
+impl TestSingleBatchBuilder {
+    /// Create a new [`TestSingleBatchBuilder`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the parent hash of the L2 block.
+    pub const fn with_parent_hash(mut self, parent_hash: B256) -> Self {
+        self.batch.parent_hash = parent_hash;
+        self
+    }
+
+    /// Sets the L1 epoch the batch belongs to.
+    pub const fn with_epoch(mut self, epoch: BlockInfo) -> Self {
+        self.batch.epoch_num = epoch.number;
+        self.batch.epoch_hash = epoch.hash;
+        self
+    }
+
+    /// Sets the timestamp of the L2 block.
+    pub const fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.batch.timestamp = timestamp;
+        self
+    }
+
+    /// Adds an encoded transaction to the batch.
+    pub fn with_transaction(mut self, tx: Bytes) -> Self {
+        self.batch.transactions.push(tx);
+        self
+    }
+
+    /// Build the [`SingleBatch`].
+    pub fn build(self) -> SingleBatch {
+        self.batch
+    }
+
+    /// Build the [`SingleBatch`], wrapped in a [`Batch`].
+    pub fn build_batch(self) -> Batch {
+        Batch::Single(self.batch)
+    }
+}
+
+/// A [`TestChainProvider`] builder that generates a chain of linked L1 blocks.
+#[derive(Debug, Clone)]
+pub struct TestChainBuilder {
+    start_number: u64,
+    start_timestamp: u64,
+    parent_hash: B256,
+    block_time: u64,
+    base_fee: Option<u64>,
+    receipts: HashMap<u64, Vec<Receipt>>,
+    transactions: HashMap<u64, Vec<TxEnvelope>>,
+}
+
+impl Default for TestChainBuilder {
+    fn default() -> Self {
+        Self {
+            start_number: 0,
+            start_timestamp: 0,
+            parent_hash: B256::ZERO,
+            block_time: 12,
+            base_fee: None,
+            receipts: HashMap::default(),
+            transactions: HashMap::default(),
+        }
+    }
+}
+
+impl TestChainBuilder {
+    /// Create a new [`TestChainBuilder`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number and timestamp of the first block.
+    pub const fn with_start(mut self, number: u64, timestamp: u64) -> Self {
+        self.start_number = number;
+        self.start_timestamp = timestamp;
+        self
+    }
+
+    /// Sets the parent hash of the first block.
+    pub const fn with_parent_hash(mut self, parent_hash: B256) -> Self {
+        self.parent_hash = parent_hash;
+        self
+    }
+
+    /// Sets the number of seconds between blocks. Defaults to 12.
+    pub const fn with_block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    /// Sets the base fee of every block.
+    pub const fn with_base_fee(mut self, base_fee: u64) -> Self {
+        self.base_fee = Some(base_fee);
+        self
+    }
+
+    /// Sets the receipts of the block with the given number.
+    pub fn with_receipts(mut self, number: u64, receipts: Vec<Receipt>) -> Self {
+        self.receipts.insert(number, receipts);
+        self
+    }
+
+    /// Sets the transactions of the block with the given number.
+    pub fn with_transactions(mut self, number: u64, txs: Vec<TxEnvelope>) -> Self {
+        self.transactions.insert(number, txs);
+        self
+    }
+
+    /// Build a [`TestChainProvider`] holding `count` linked blocks, along with the [`BlockInfo`]
+    /// of each block in order.
+    ///
+    /// Every block is given a header and receipts, so that the provider serves the full chain.
+    pub fn build(mut self, count: u64) -> (TestChainProvider, Vec<BlockInfo>) {
+        let mut provider = TestChainProvider::default();
+        let mut infos = Vec::with_capacity(count as usize);
+        let mut parent_hash = self.parent_hash;
+
+        for i in 0..count {
+            let number = self.start_number + i;
+            let mut builder = TestHeaderBuilder::new()
+                .with_number(number)
+                .with_parent_hash(parent_hash)
+                .with_timestamp(self.start_timestamp + i * self.block_time);
+            if let Some(base_fee) = self.base_fee {
+                builder = builder.with_base_fee(base_fee);
+            }
+            let (header, info) = builder.build_with_info();
+
+            let txs = self.transactions.remove(&number).unwrap_or_default();
+            let receipts = self.receipts.remove(&number).unwrap_or_default();
+            provider.insert_block_with_transactions(number, info, txs);
+            provider.insert_header(info.hash, header);
+            provider.insert_receipts(info.hash, receipts);
+
+            parent_hash = info.hash;
+            infos.push(info);
+        }
+
+        (provider, infos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ChainProvider;
+    use alloc::vec;
+
+    #[tokio::test]
+    async fn test_chain_builder_links_blocks() {
+        let receipt =
+            TestReceiptBuilder::new().with_log(Address::ZERO, vec![], Bytes::new()).build();
+        let (mut provider, blocks) = TestChainBuilder::new()
+            .with_start(10, 1_000)
+            .with_receipts(11, vec![receipt.clone()])
+            .build(3);
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].parent_hash, blocks[0].hash);
+        assert_eq!(blocks[2].parent_hash, blocks[1].hash);
+        assert_eq!(blocks[2].timestamp, 1_024);
+
+        assert_eq!(provider.block_info_by_number(11).await.unwrap(), blocks[1]);
+        assert_eq!(provider.header_by_hash(blocks[2].hash).await.unwrap().number, 12);
+        assert_eq!(provider.receipts_by_hash(blocks[1].hash).await.unwrap(), vec![receipt]);
+        assert!(provider.receipts_by_hash(blocks[0].hash).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_single_batch_builder() {
+        let epoch = BlockInfo::new(B256::from([1; 32]), 5, B256::ZERO, 60);
+        let batch = TestSingleBatchBuilder::new()
+            .with_epoch(epoch)
+            .with_timestamp(62)
+            .with_transaction(Bytes::from_static(&[0xaa]))
+            .build();
+
+        assert_eq!(batch.epoch_num, 5);
+        assert_eq!(batch.epoch_hash, epoch.hash);
+        assert_eq!(batch.timestamp, 62);
+        assert_eq!(batch.transactions.len(), 1);
+    }
+}
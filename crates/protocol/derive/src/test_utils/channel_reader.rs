@@ -9,7 +9,7 @@ use crate::{
 use alloc::{boxed::Box, vec::Vec};
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
-use kona_protocol::BlockInfo;
+use kona_protocol::{BlockInfo, ChannelId};
 
 /// A mock [`ChannelReaderProvider`] for testing the [`ChannelReader`] stage.
 ///
@@ -22,12 +22,14 @@ pub struct TestChannelReaderProvider {
     pub block_info: Option<BlockInfo>,
     /// Tracks if the channel reader provider has been reset.
     pub reset: bool,
+    /// The channel ID to report for the data.
+    pub channel_id: Option<ChannelId>,
 }
 
 impl TestChannelReaderProvider {
     /// Creates a new [`TestChannelReaderProvider`] with the given data.
     pub fn new(data: Vec<PipelineResult<Option<Bytes>>>) -> Self {
-        Self { data, block_info: Some(BlockInfo::default()), reset: false }
+        Self { data, block_info: Some(BlockInfo::default()), reset: false, channel_id: None }
     }
 }
 
@@ -49,6 +51,10 @@ impl ChannelReaderProvider for TestChannelReaderProvider {
     async fn next_data(&mut self) -> PipelineResult<Option<Bytes>> {
         self.data.pop().unwrap_or(Err(PipelineError::Eof.temp()))
     }
+
+    fn channel_id(&self) -> Option<ChannelId> {
+        self.channel_id
+    }
 }
 
 #[async_trait]
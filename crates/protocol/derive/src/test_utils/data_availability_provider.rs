@@ -14,6 +14,13 @@ pub struct TestDAP {
     pub results: Vec<PipelineResult<Bytes>>,
 }
 
+impl TestDAP {
+    /// Creates a new [`TestDAP`] that yields the given data in order, then [`PipelineError::Eof`].
+    pub fn new(data: Vec<Bytes>) -> Self {
+        Self { results: data.into_iter().rev().map(Ok).collect() }
+    }
+}
+
 #[async_trait]
 impl DataAvailabilityProvider for TestDAP {
     type Item = Bytes;
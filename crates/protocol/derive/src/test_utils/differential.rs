@@ -0,0 +1,491 @@
+//! Differential derivation fixtures.
+//!
+//! A [`DerivationFixture`] records a range of L1 blocks, the L2 safe head derivation started
+//! from, and the payload attributes op-node derived from that range. [`DerivationFixture::derive`]
+//! feeds the same L1 range through a [`DerivationPipeline`], and [`DerivationFixture::diff`]
+//! reports every point where kona's output diverges from op-node's.
+
+use crate::{
+    ActivationSignal, DerivationPipeline, EthereumDataSource, OriginProvider, Pipeline,
+    PipelineBuilder, PipelineError, PipelineErrorKind, PolledAttributesQueueStage, ResetError,
+    ResetSignal, SignalReceiver, StatefulAttributesBuilder, StepResult,
+    test_utils::{TestBlobProvider, TestChainProvider, TestL2ChainProvider},
+};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec::Vec};
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::B256;
+use core::fmt;
+use kona_genesis::{L1ChainConfig, RollupConfig, SystemConfig};
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use op_alloy_consensus::OpBlock;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The maximum number of pipeline steps taken while deriving a fixture.
+pub const MAX_FIXTURE_STEPS: usize = 1 << 16;
+
+/// The derivation pipeline driven by a [`DerivationFixture`].
+pub type FixturePipeline = DerivationPipeline<
+    PolledAttributesQueueStage<
+        EthereumDataSource<TestChainProvider, TestBlobProvider>,
+        TestChainProvider,
+        TestL2ChainProvider,
+        StatefulAttributesBuilder<TestChainProvider, TestL2ChainProvider>,
+    >,
+    TestL2ChainProvider,
+>;
+
+/// A recorded L1 block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BlockFixture {
+    /// The block header.
+    pub header: Header,
+    /// The receipts of the block.
+    #[serde(default)]
+    pub receipts: Vec<Receipt>,
+    /// The transactions of the block.
+    #[serde(default)]
+    pub transactions: Vec<TxEnvelope>,
+}
+
+impl L1BlockFixture {
+    /// Returns the [`BlockInfo`] of the block.
+    pub fn block_info(&self) -> BlockInfo {
+        BlockInfo::new(
+            self.header.hash_slow(),
+            self.header.number,
+            self.header.parent_hash,
+            self.header.timestamp,
+        )
+    }
+}
+
+/// A recorded blob, keyed by its versioned hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobFixture {
+    /// The versioned hash of the blob.
+    pub hash: B256,
+    /// The blob data.
+    pub blob: Box<Blob>,
+}
+
+/// Payload attributes derived by op-node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectedAttributes {
+    /// The derived payload attributes.
+    pub attributes: OpPayloadAttributes,
+    /// The L2 block the attributes build on.
+    pub parent: L2BlockInfo,
+    /// The L1 block the attributes were derived from, if recorded.
+    #[serde(default)]
+    pub derived_from: Option<BlockInfo>,
+    /// The L2 block op-node built from the attributes.
+    ///
+    /// This becomes the L2 cursor of the pipeline once the attributes are derived, standing in
+    /// for the execution layer.
+    pub block: L2BlockInfo,
+}
+
+/// A recorded L1 range along with the payload attributes op-node derived from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationFixture {
+    /// The name of the fixture.
+    pub name: String,
+    /// The hardfork the fixture exercises.
+    pub hardfork: String,
+    /// The rollup config of the chain.
+    pub rollup_config: RollupConfig,
+    /// The L1 chain config.
+    pub l1_config: L1ChainConfig,
+    /// The L1 block derivation starts from.
+    pub l1_origin: BlockInfo,
+    /// The L2 safe head derivation starts from.
+    pub l2_safe_head: L2BlockInfo,
+    /// The recorded L1 blocks, in order, starting at or before the L1 origin.
+    pub l1_blocks: Vec<L1BlockFixture>,
+    /// The blobs referenced by the recorded L1 blocks.
+    #[serde(default)]
+    pub blobs: Vec<BlobFixture>,
+    /// The system config of each L2 block from the safe head onwards, keyed by block number.
+    pub system_configs: BTreeMap<u64, SystemConfig>,
+    /// The full L2 blocks needed to validate overlapping span batches.
+    #[serde(default)]
+    pub l2_blocks: Vec<OpBlock>,
+    /// The payload attributes op-node derived, in order.
+    pub expected: Vec<ExpectedAttributes>,
+}
+
+/// An error deriving a [`DerivationFixture`].
+#[derive(Error, Debug)]
+pub enum DifferentialError {
+    /// The pipeline returned a critical error.
+    #[error("Pipeline error: {0}")]
+    Pipeline(#[from] PipelineErrorKind),
+    /// The pipeline did not exhaust the recorded L1 range within the step limit.
+    #[error("Pipeline did not finish within {0} steps")]
+    StepLimit(usize),
+}
+
+/// A divergence between the attributes derived by kona and by op-node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributesDiff {
+    /// Both derived the attributes at the index, but they differ in the listed fields.
+    Mismatch {
+        /// The index of the attributes.
+        index: usize,
+        /// The names of the fields that differ.
+        fields: Vec<&'static str>,
+    },
+    /// op-node derived the attributes at the index, but kona did not.
+    Missing {
+        /// The index of the attributes.
+        index: usize,
+    },
+    /// kona derived the attributes at the index, but op-node did not.
+    Unexpected {
+        /// The index of the attributes.
+        index: usize,
+    },
+}
+
+impl fmt::Display for AttributesDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch { index, fields } => {
+                write!(f, "attributes #{index} differ in: {}", fields.join(", "))
+            }
+            Self::Missing { index } => write!(f, "attributes #{index} were not derived"),
+            Self::Unexpected { index } => write!(f, "attributes #{index} were not expected"),
+        }
+    }
+}
+
+impl DerivationFixture {
+    /// Returns a copy of the fixture with the recorded L1 range cut down to the first `len`
+    /// blocks.
+    ///
+    /// The expected attributes are kept as-is, so deriving the truncated fixture should yield a
+    /// prefix of them.
+    pub fn truncated(&self, len: usize) -> Self {
+        let mut fixture = self.clone();
+        fixture.l1_blocks.truncate(len);
+        fixture
+    }
+
+    /// Builds the providers serving the recorded data.
+    fn providers(&self) -> (TestChainProvider, TestL2ChainProvider, TestBlobProvider) {
+        let mut chain = TestChainProvider::default();
+        for block in &self.l1_blocks {
+            let info = block.block_info();
+            chain.insert_block_with_transactions(info.number, info, block.transactions.clone());
+            chain.insert_header(info.hash, block.header.clone());
+            chain.insert_receipts(info.hash, block.receipts.clone());
+        }
+
+        let l2_blocks = core::iter::once(self.l2_safe_head)
+            .chain(self.expected.iter().map(|e| e.block))
+            .collect();
+        let l2 = TestL2ChainProvider::new(
+            l2_blocks,
+            self.l2_blocks.clone(),
+            self.system_configs.iter().map(|(n, c)| (*n, *c)).collect(),
+        );
+
+        let mut blobs = TestBlobProvider::default();
+        for blob in &self.blobs {
+            blobs.insert_blob(blob.hash, *blob.blob);
+        }
+
+        (chain, l2, blobs)
+    }
+
+    /// Builds the [`FixturePipeline`] over the recorded data, reset to the L2 safe head.
+    pub async fn pipeline(&self) -> Result<FixturePipeline, DifferentialError> {
+        let cfg = Arc::new(self.rollup_config.clone());
+        let (chain, l2, blobs) = self.providers();
+
+        let builder = StatefulAttributesBuilder::new(
+            cfg.clone(),
+            Arc::new(self.l1_config.clone()),
+            l2.clone(),
+            chain.clone(),
+        );
+        let dap = EthereumDataSource::new_from_parts(chain.clone(), blobs, &cfg);
+
+        let mut pipeline = PipelineBuilder::new()
+            .rollup_config(cfg)
+            .dap_source(dap)
+            .l2_chain_provider(l2)
+            .chain_provider(chain)
+            .builder(builder)
+            .origin(self.l1_origin)
+            .build_polled();
+
+        // Reset the pipeline to populate the initial system configuration in L1 Traversal.
+        pipeline
+            .signal(
+                ResetSignal {
+                    l2_safe_head: self.l2_safe_head,
+                    l1_origin: self.l1_origin,
+                    system_config: None,
+                }
+                .signal(),
+            )
+            .await?;
+
+        Ok(pipeline)
+    }
+
+    /// Derives payload attributes from the recorded L1 range.
+    ///
+    /// The pipeline is stepped the way the node steps it, and the L2 cursor advances to the
+    /// recorded block after each derived payload. Derivation stops once the recorded L1 range is
+    /// exhausted, or once kona derives more attributes than op-node did.
+    pub async fn derive(&self) -> Result<Vec<OpAttributesWithParent>, DifferentialError> {
+        let mut pipeline = self.pipeline().await?;
+        let mut cursor = self.l2_safe_head;
+        let mut derived = Vec::new();
+
+        for _ in 0..MAX_FIXTURE_STEPS {
+            match pipeline.step(cursor).await {
+                StepResult::PreparedAttributes => {
+                    let Some(attributes) = pipeline.next() else { continue };
+                    let expected = self.expected.get(derived.len());
+                    derived.push(attributes);
+                    match expected {
+                        Some(expected) => cursor = expected.block,
+                        None => return Ok(derived),
+                    }
+                }
+                StepResult::AdvancedOrigin => {}
+                // Traversal can't find the next L1 block: the recorded range is exhausted.
+                StepResult::OriginAdvanceErr(PipelineErrorKind::Temporary(_)) => {
+                    return Ok(derived);
+                }
+                StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => match e {
+                    PipelineErrorKind::Temporary(_) => {}
+                    PipelineErrorKind::Reset(e) => {
+                        let l1_origin =
+                            pipeline.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+                        let signal = if matches!(e, ResetError::HoloceneActivation) {
+                            ActivationSignal {
+                                l2_safe_head: cursor,
+                                l1_origin,
+                                system_config: None,
+                            }
+                            .signal()
+                        } else {
+                            ResetSignal { l2_safe_head: cursor, l1_origin, system_config: None }
+                                .signal()
+                        };
+                        pipeline.signal(signal).await?;
+                    }
+                    PipelineErrorKind::Critical(_) => return Err(e.into()),
+                },
+            }
+        }
+
+        Err(DifferentialError::StepLimit(MAX_FIXTURE_STEPS))
+    }
+
+    /// Compares attributes derived by kona against the attributes op-node derived.
+    pub fn diff(&self, derived: &[OpAttributesWithParent]) -> Vec<AttributesDiff> {
+        let mut diffs = Vec::new();
+        for index in 0..self.expected.len().max(derived.len()) {
+            match (self.expected.get(index), derived.get(index)) {
+                (Some(expected), Some(derived)) => {
+                    let fields = diff_fields(expected, derived);
+                    if !fields.is_empty() {
+                        diffs.push(AttributesDiff::Mismatch { index, fields });
+                    }
+                }
+                (Some(_), None) => diffs.push(AttributesDiff::Missing { index }),
+                (None, Some(_)) => diffs.push(AttributesDiff::Unexpected { index }),
+                (None, None) => unreachable!(),
+            }
+        }
+        diffs
+    }
+}
+
+/// Returns the names of the fields in which the derived attributes differ from the expected ones.
+fn diff_fields(
+    expected: &ExpectedAttributes,
+    derived: &OpAttributesWithParent,
+) -> Vec<&'static str> {
+    let (e, d) = (&expected.attributes, &derived.attributes);
+    let (ep, dp) = (&e.payload_attributes, &d.payload_attributes);
+    [
+        ("timestamp", ep.timestamp == dp.timestamp),
+        ("prevRandao", ep.prev_randao == dp.prev_randao),
+        ("suggestedFeeRecipient", ep.suggested_fee_recipient == dp.suggested_fee_recipient),
+        ("withdrawals", ep.withdrawals == dp.withdrawals),
+        ("parentBeaconBlockRoot", ep.parent_beacon_block_root == dp.parent_beacon_block_root),
+        ("transactions", e.transactions == d.transactions),
+        ("noTxPool", e.no_tx_pool == d.no_tx_pool),
+        ("gasLimit", e.gas_limit == d.gas_limit),
+        ("eip1559Params", e.eip_1559_params == d.eip_1559_params),
+        ("minBaseFee", e.min_base_fee == d.min_base_fee),
+        ("parent", expected.parent == derived.parent),
+        ("derivedFrom", expected.derived_from.is_none_or(|f| Some(f) == derived.derived_from)),
+    ]
+    .into_iter()
+    .filter_map(|(field, equal)| (!equal).then_some(field))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::{format, string::ToString, vec};
+    use alloy_primitives::Bytes;
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use proptest::test_runner::TestRunner;
+    use std::{fs, path::Path};
+
+    /// The directory holding the checked-in fixture corpus.
+    const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/differential");
+
+    fn load_corpus() -> Vec<DerivationFixture> {
+        let mut paths = fs::read_dir(Path::new(CORPUS_DIR))
+            .expect("Failed to read corpus directory")
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert!(!paths.is_empty(), "No fixtures in {CORPUS_DIR}, see its README to record some");
+        paths
+            .into_iter()
+            .map(|path| {
+                serde_json::from_slice(&fs::read(&path).unwrap())
+                    .unwrap_or_else(|e| panic!("Invalid fixture {}: {e}", path.display()))
+            })
+            .collect()
+    }
+
+    fn expected_attributes(timestamp: u64) -> ExpectedAttributes {
+        ExpectedAttributes {
+            attributes: OpPayloadAttributes {
+                payload_attributes: PayloadAttributes {
+                    timestamp,
+                    prev_randao: Default::default(),
+                    suggested_fee_recipient: Default::default(),
+                    withdrawals: None,
+                    parent_beacon_block_root: None,
+                },
+                transactions: Some(vec![Bytes::from_static(&[0x7e])]),
+                no_tx_pool: Some(true),
+                gas_limit: Some(30_000_000),
+                eip_1559_params: None,
+                min_base_fee: None,
+            },
+            parent: Default::default(),
+            derived_from: None,
+            block: Default::default(),
+        }
+    }
+
+    fn derived_attributes(expected: &ExpectedAttributes) -> OpAttributesWithParent {
+        OpAttributesWithParent::new(
+            expected.attributes.clone(),
+            expected.parent,
+            Some(BlockInfo::default()),
+            true,
+        )
+    }
+
+    fn fixture(expected: Vec<ExpectedAttributes>) -> DerivationFixture {
+        DerivationFixture {
+            name: "test".to_string(),
+            hardfork: "holocene".to_string(),
+            rollup_config: Default::default(),
+            l1_config: Default::default(),
+            l1_origin: Default::default(),
+            l2_safe_head: Default::default(),
+            l1_blocks: vec![],
+            blobs: vec![],
+            system_configs: Default::default(),
+            l2_blocks: vec![],
+            expected,
+        }
+    }
+
+    #[test]
+    fn test_diff_matching_attributes() {
+        let fixture = fixture(vec![expected_attributes(2), expected_attributes(4)]);
+        let derived = fixture.expected.iter().map(derived_attributes).collect::<Vec<_>>();
+        assert!(fixture.diff(&derived).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_mismatched_fields() {
+        let fixture = fixture(vec![expected_attributes(2)]);
+        let mut derived = derived_attributes(&fixture.expected[0]);
+        derived.attributes.payload_attributes.timestamp = 3;
+        derived.attributes.transactions = None;
+
+        let diffs = fixture.diff(&[derived]);
+        assert_eq!(
+            diffs,
+            vec![AttributesDiff::Mismatch { index: 0, fields: vec!["timestamp", "transactions"] }]
+        );
+        assert_eq!(diffs[0].to_string(), "attributes #0 differ in: timestamp, transactions");
+    }
+
+    #[test]
+    fn test_diff_reports_missing_and_unexpected() {
+        let fixture = fixture(vec![expected_attributes(2), expected_attributes(4)]);
+        let derived = derived_attributes(&fixture.expected[0]);
+        assert_eq!(fixture.diff(&[derived.clone()]), vec![AttributesDiff::Missing { index: 1 }]);
+
+        let extra = derived_attributes(&expected_attributes(6));
+        let derived = [derived, derived_attributes(&fixture.expected[1]), extra];
+        assert_eq!(fixture.diff(&derived), vec![AttributesDiff::Unexpected { index: 2 }]);
+    }
+
+    #[tokio::test]
+    async fn test_differential_corpus() {
+        for fixture in load_corpus() {
+            let derived = fixture.derive().await.unwrap_or_else(|e| {
+                panic!("Failed to derive fixture {} ({}): {e}", fixture.name, fixture.hardfork)
+            });
+            let diffs = fixture.diff(&derived);
+            assert!(
+                diffs.is_empty(),
+                "Fixture {} ({}) diverges from op-node:\n{}",
+                fixture.name,
+                fixture.hardfork,
+                diffs.iter().map(|d| format!("  {d}")).collect::<Vec<_>>().join("\n")
+            );
+        }
+    }
+
+    #[test]
+    fn test_differential_corpus_truncated_range_derives_prefix() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        for fixture in load_corpus() {
+            let len = fixture.l1_blocks.len();
+            TestRunner::default()
+                .run(&(1..=len), |len| {
+                    let truncated = fixture.truncated(len);
+                    let derived = runtime.block_on(truncated.derive()).unwrap();
+                    let diffs = fixture.diff(&derived);
+                    assert!(
+                        diffs.iter().all(|d| matches!(d, AttributesDiff::Missing { .. })),
+                        "Fixture {} truncated to {len} L1 blocks diverges from op-node: {diffs:?}",
+                        fixture.name,
+                    );
+                    Ok(())
+                })
+                .unwrap();
+        }
+    }
+}
@@ -1,8 +1,8 @@
 //! Mock types for the frame queue stage.
 
 use crate::{
-    FrameQueueProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
-    SignalReceiver,
+    BatcherTxRef, FrameQueueProvider, OriginAdvancer, OriginProvider, PipelineError,
+    PipelineResult, Signal, SignalReceiver,
 };
 use alloc::{boxed::Box, vec::Vec};
 use alloy_primitives::Bytes;
@@ -20,12 +20,14 @@ pub struct TestFrameQueueProvider {
     pub origin: Option<BlockInfo>,
     /// Whether the reset method was called.
     pub reset: bool,
+    /// The batcher transaction the data is read from.
+    pub source: Option<BatcherTxRef>,
 }
 
 impl TestFrameQueueProvider {
     /// Creates a new [`TestFrameQueueProvider`] with the given data.
     pub const fn new(data: Vec<PipelineResult<Bytes>>) -> Self {
-        Self { data, origin: None, reset: false }
+        Self { data, origin: None, reset: false, source: None }
     }
 
     /// Sets the origin for the [`TestFrameQueueProvider`].
@@ -54,6 +56,10 @@ impl FrameQueueProvider for TestFrameQueueProvider {
     async fn next_data(&mut self) -> PipelineResult<Self::Item> {
         self.data.pop().unwrap_or(Err(PipelineError::Eof.temp()))
     }
+
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        self.source
+    }
 }
 
 #[async_trait]
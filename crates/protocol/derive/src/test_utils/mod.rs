@@ -45,6 +45,19 @@ pub use tracing::{CollectingLayer, TraceStorage};
 mod sys_config_fetcher;
 pub use sys_config_fetcher::{TestSystemConfigL2Fetcher, TestSystemConfigL2FetcherError};
 
+mod builders;
+pub use builders::{
+    TestChainBuilder, TestHeaderBuilder, TestReceiptBuilder, TestSingleBatchBuilder,
+};
+
+#[cfg(feature = "serde")]
+mod differential;
+#[cfg(feature = "serde")]
+pub use differential::{
+    AttributesDiff, BlobFixture, DerivationFixture, DifferentialError, ExpectedAttributes,
+    FixturePipeline, L1BlockFixture, MAX_FIXTURE_STEPS,
+};
+
 mod frames;
 pub use frames::{FrameQueueAsserter, FrameQueueBuilder};
 
@@ -6,7 +6,7 @@ use crate::PipelineResult;
 use alloc::boxed::Box;
 use alloy_eips::BlockNumHash;
 use async_trait::async_trait;
-use kona_protocol::{L2BlockInfo, OpAttributesWithParent, SingleBatch};
+use kona_protocol::{ChannelId, L2BlockInfo, OpAttributesWithParent, SingleBatch};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
 /// [`AttributesProvider`] is a trait abstraction that generalizes the [`BatchQueue`] stage.
@@ -19,6 +19,9 @@ pub trait AttributesProvider {
 
     /// Returns whether the current batch is the last in its span.
     fn is_last_in_span(&self) -> bool;
+
+    /// Returns the [`ChannelId`] of the channel the current batch was read from, if known.
+    fn channel_id(&self) -> Option<ChannelId>;
 }
 
 /// [`NextAttributes`] defines the interface for pulling attributes from
@@ -49,3 +52,34 @@ pub trait AttributesBuilder: Debug + Send {
         epoch: BlockNumHash,
     ) -> PipelineResult<OpPayloadAttributes>;
 }
+
+/// The [`AttributesPostProcessor`] hooks into the [`StatefulAttributesBuilder`] to modify the
+/// [`OpPayloadAttributes`] it prepares before they are handed to the pipeline, e.g. to inject
+/// chain-specific deposit transactions.
+///
+/// The unit type `()` is a no-op post-processor.
+///
+/// [`StatefulAttributesBuilder`]: crate::attributes::StatefulAttributesBuilder
+#[async_trait]
+pub trait AttributesPostProcessor: Debug + Send {
+    /// Post-processes the [`OpPayloadAttributes`] prepared on top of the given L2 parent, with
+    /// the L1 origin set to the given epoch.
+    async fn post_process(
+        &mut self,
+        attributes: &mut OpPayloadAttributes,
+        l2_parent: L2BlockInfo,
+        epoch: BlockNumHash,
+    ) -> PipelineResult<()>;
+}
+
+#[async_trait]
+impl AttributesPostProcessor for () {
+    async fn post_process(
+        &mut self,
+        _: &mut OpPayloadAttributes,
+        _: L2BlockInfo,
+        _: BlockNumHash,
+    ) -> PipelineResult<()> {
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 //! Contains traits that describe the functionality of various data sources used in the derivation
 //! pipeline's stages.
 
-use crate::{PipelineErrorKind, PipelineResult};
+use crate::{BatcherTxRef, PipelineErrorKind, PipelineResult};
 use alloc::{boxed::Box, fmt::Debug, string::ToString, vec::Vec};
 use alloy_eips::eip4844::{Blob, IndexedBlobHash};
 use alloy_primitives::{Address, Bytes};
@@ -23,6 +23,25 @@ pub trait BlobProvider {
     ) -> Result<Vec<Box<Blob>>, Self::Error>;
 }
 
+/// Provides the input data of alt-DA commitments, for the [`CommitmentType`] it is registered for
+/// in a [`DispatchDataSource`].
+///
+/// [`CommitmentType`]: crate::CommitmentType
+/// [`DispatchDataSource`]: crate::DispatchDataSource
+#[async_trait]
+pub trait AltDaProvider: Debug + Send + Sync {
+    /// Fetches the input data of the given encoded commitment, including its type byte, posted
+    /// in the given L1 block.
+    async fn get_input(
+        &mut self,
+        commitment: &Bytes,
+        block_ref: &BlockInfo,
+    ) -> PipelineResult<Bytes>;
+
+    /// Clears any state kept for the current L1 block.
+    fn clear(&mut self) {}
+}
+
 /// Describes the functionality of a data source that can provide data availability information.
 #[async_trait]
 pub trait DataAvailabilityProvider {
@@ -40,4 +59,10 @@ pub trait DataAvailabilityProvider {
 
     /// Clears the data source for the next block ref.
     fn clear(&mut self);
+
+    /// Returns the batcher transaction the item most recently returned by
+    /// [`DataAvailabilityProvider::next`] was read from, if the source tracks it.
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        None
+    }
 }
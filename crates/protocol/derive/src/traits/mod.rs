@@ -8,10 +8,12 @@ mod providers;
 pub use providers::{BatchValidationProviderDerive, ChainProvider, L2ChainProvider};
 
 mod attributes;
-pub use attributes::{AttributesBuilder, AttributesProvider, NextAttributes};
+pub use attributes::{
+    AttributesBuilder, AttributesPostProcessor, AttributesProvider, NextAttributes,
+};
 
 mod data_sources;
-pub use data_sources::{BlobProvider, DataAvailabilityProvider};
+pub use data_sources::{AltDaProvider, BlobProvider, DataAvailabilityProvider};
 
 mod reset;
 pub use reset::ResetProvider;
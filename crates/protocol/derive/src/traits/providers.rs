@@ -45,6 +45,12 @@ pub trait L2ChainProvider: BatchValidationProviderDerive {
         number: u64,
         rollup_config: Arc<RollupConfig>,
     ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error>;
+
+    /// Drops any L2 chain data memoized by the provider.
+    ///
+    /// Called by the pipeline on a [`Signal::Reset`](crate::Signal::Reset), since the L2 chain
+    /// may have been reorged. Does nothing by default.
+    fn invalidate_cache(&mut self) {}
 }
 
 /// A super-trait for [`BatchValidationProvider`] that binds `Self::Error` to have a conversion into
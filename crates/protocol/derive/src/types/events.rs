@@ -0,0 +1,186 @@
+//! Events emitted by the `kona-derive` pipeline.
+//!
+//! Events are structured notifications of the pipeline's progress, such as channels being opened
+//! and closed or batches being accepted and dropped. They are emitted to an optional
+//! [`PipelineEventSink`] and are intended for monitoring only; they do not affect derivation.
+
+use alloc::sync::Arc;
+use alloy_primitives::{B128, B256};
+use core::fmt::Debug;
+use kona_protocol::{BatchValidity, BlockInfo, L2BlockInfo};
+
+/// An event emitted by the derivation pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")
+)]
+pub enum PipelineEvent {
+    /// The L1 origin of the pipeline advanced.
+    OriginAdvanced {
+        /// The new L1 origin.
+        origin: BlockInfo,
+    },
+    /// A new channel was opened.
+    ChannelOpened {
+        /// The ID of the channel.
+        id: B128,
+        /// The L1 origin the channel was opened at.
+        origin: BlockInfo,
+    },
+    /// A channel was closed.
+    ChannelClosed {
+        /// The ID of the channel.
+        id: B128,
+        /// The L1 origin the channel was closed at.
+        origin: BlockInfo,
+        /// The reason the channel was closed.
+        reason: ChannelCloseReason,
+    },
+    /// Data was read from a batcher transaction, before being parsed into frames.
+    DataRetrieved {
+        /// The size of the data, in bytes.
+        bytes: u64,
+        /// The batcher transaction the data was read from, if the data source tracks it.
+        tx: Option<BatcherTxRef>,
+        /// The L1 block the data was included in.
+        origin: BlockInfo,
+    },
+    /// A frame was read from the data of a batcher transaction.
+    FrameRetrieved {
+        /// The ID of the channel the frame belongs to.
+        channel_id: B128,
+        /// The number of the frame within its channel.
+        frame_number: u16,
+        /// The batcher transaction the frame was read from, if the data source tracks it.
+        tx: Option<BatcherTxRef>,
+        /// The L1 block the frame was included in.
+        origin: BlockInfo,
+    },
+    /// A batch was decoded from a channel.
+    BatchDecoded {
+        /// The ID of the channel the batch was decoded from, if known.
+        channel_id: Option<B128>,
+        /// The timestamp of the first L2 block of the batch.
+        first_timestamp: u64,
+        /// The timestamp of the last L2 block of the batch.
+        last_timestamp: u64,
+        /// The L1 origin the batch was decoded at.
+        origin: BlockInfo,
+    },
+    /// A batch was accepted and forwarded to the attributes queue.
+    BatchAccepted {
+        /// The timestamp of the batch.
+        timestamp: u64,
+        /// The L1 origin the batch was accepted at.
+        origin: BlockInfo,
+    },
+    /// A batch was dropped.
+    BatchDropped {
+        /// The timestamp of the batch.
+        timestamp: u64,
+        /// The L1 origin the batch was dropped at.
+        origin: BlockInfo,
+        /// The validity of the batch that led to it being dropped.
+        validity: BatchValidity,
+    },
+    /// The pipeline was reset.
+    Reset {
+        /// The L2 safe head the pipeline was reset to.
+        l2_safe_head: L2BlockInfo,
+        /// The L1 origin the pipeline was reset to.
+        l1_origin: BlockInfo,
+    },
+}
+
+/// The reason a channel was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum ChannelCloseReason {
+    /// The channel was complete and forwarded to the channel reader.
+    Ready,
+    /// The channel timed out before it was complete.
+    TimedOut,
+    /// The channel was discarded because it exceeded the channel size limits.
+    Dropped,
+}
+
+/// A reference to the batcher transaction some batch data was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BatcherTxRef {
+    /// The hash of the batcher transaction.
+    pub tx_hash: B256,
+    /// The versioned hash of the blob the data was read from, if it was posted as a blob.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub blob_hash: Option<B256>,
+}
+
+/// A sink for [`PipelineEvent`]s.
+pub trait PipelineEventSink: Send + Sync {
+    /// Handles an event emitted by the pipeline.
+    fn emit(&self, event: PipelineEvent);
+}
+
+impl<F> PipelineEventSink for F
+where
+    F: Fn(PipelineEvent) + Send + Sync,
+{
+    fn emit(&self, event: PipelineEvent) {
+        self(event)
+    }
+}
+
+/// A handle to an optional [`PipelineEventSink`], shared between the stages of the pipeline.
+///
+/// Events emitted through a handle without a sink are discarded.
+#[derive(Clone, Default)]
+pub struct PipelineEvents(Option<Arc<dyn PipelineEventSink>>);
+
+impl PipelineEvents {
+    /// Creates a new [`PipelineEvents`] handle without a sink.
+    pub const fn none() -> Self {
+        Self(None)
+    }
+
+    /// Creates a new [`PipelineEvents`] handle emitting to the given sink.
+    pub fn new(sink: impl PipelineEventSink + 'static) -> Self {
+        Self(Some(Arc::new(sink)))
+    }
+
+    /// Emits the given event to the sink, if any.
+    pub fn emit(&self, event: PipelineEvent) {
+        if let Some(sink) = self.0.as_ref() {
+            sink.emit(event);
+        }
+    }
+}
+
+impl Debug for PipelineEvents {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PipelineEvents").field("enabled", &self.0.is_some()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use spin::Mutex;
+
+    #[test]
+    fn test_pipeline_events_emit() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        let events = PipelineEvents::new(move |event| sink.lock().push(event));
+
+        let event = PipelineEvent::OriginAdvanced { origin: BlockInfo::default() };
+        events.emit(event);
+        PipelineEvents::none().emit(event);
+
+        assert_eq!(*received.lock(), [event]);
+    }
+}
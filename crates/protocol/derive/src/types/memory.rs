@@ -0,0 +1,137 @@
+//! Memory accounting for the buffers of the `kona-derive` pipeline.
+//!
+//! The stages buffering L1 data report their usage to a shared [`PipelineMemory`] handle. When a
+//! budget is set, the [`L1Retrieval`] stage stops pulling new L1 data while the budget is
+//! exceeded. Buffered channels are never dropped to stay within it: if the [`ChannelProvider`]
+//! has no channel left to hand downstream while [`L1Retrieval`] holds off, derivation fails with
+//! a critical error.
+//!
+//! [`L1Retrieval`]: crate::stages::L1Retrieval
+//! [`ChannelProvider`]: crate::stages::ChannelProvider
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A pipeline component whose buffers are accounted by [`PipelineMemory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryComponent {
+    /// The channels buffered by the [`ChannelProvider`] stage.
+    ///
+    /// [`ChannelProvider`]: crate::stages::ChannelProvider
+    ChannelProvider,
+    /// The batches buffered by the [`BatchStream`] stage.
+    ///
+    /// [`BatchStream`]: crate::stages::BatchStream
+    BatchStream,
+}
+
+impl MemoryComponent {
+    /// All accounted components.
+    pub const ALL: [Self; 2] = [Self::ChannelProvider, Self::BatchStream];
+
+    /// Returns the metric label of the component.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ChannelProvider => "channel_provider",
+            Self::BatchStream => "batch_stream",
+        }
+    }
+
+    const fn index(&self) -> usize {
+        match self {
+            Self::ChannelProvider => 0,
+            Self::BatchStream => 1,
+        }
+    }
+}
+
+/// The shared state of a [`PipelineMemory`] handle.
+#[derive(Debug, Default)]
+struct MemoryState {
+    /// The maximum number of bytes buffered across all components, if bounded.
+    budget: Option<usize>,
+    /// The bytes buffered by each component, indexed by [`MemoryComponent::index`].
+    usage: [AtomicUsize; MemoryComponent::ALL.len()],
+}
+
+/// A shared handle accounting the bytes buffered by the stages of the derivation pipeline against
+/// an optional global budget.
+///
+/// Clones of the handle share the same accounting. By default, the budget is unbounded and the
+/// usage is only tracked.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineMemory(Arc<MemoryState>);
+
+impl PipelineMemory {
+    /// Creates a new [`PipelineMemory`] handle without a budget.
+    pub fn unbounded() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`PipelineMemory`] handle with the given budget, in bytes.
+    pub fn with_budget(budget: usize) -> Self {
+        kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_MEMORY_BUDGET, budget as f64);
+        Self(Arc::new(MemoryState { budget: Some(budget), ..Default::default() }))
+    }
+
+    /// Returns the budget of the handle, if bounded.
+    pub fn budget(&self) -> Option<usize> {
+        self.0.budget
+    }
+
+    /// Sets the number of bytes buffered by the given component.
+    pub fn set(&self, component: MemoryComponent, bytes: usize) {
+        self.0.usage[component.index()].store(bytes, Ordering::Relaxed);
+        kona_macros::set!(
+            gauge,
+            crate::metrics::Metrics::PIPELINE_MEMORY_USAGE,
+            "component",
+            component.as_str(),
+            bytes as f64
+        );
+    }
+
+    /// Returns the number of bytes buffered by the given component.
+    pub fn usage(&self, component: MemoryComponent) -> usize {
+        self.0.usage[component.index()].load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes buffered across all components.
+    pub fn total(&self) -> usize {
+        MemoryComponent::ALL.iter().map(|c| self.usage(*c)).sum()
+    }
+
+    /// Returns whether the bytes buffered across all components exceed the budget.
+    pub fn is_exceeded(&self) -> bool {
+        self.0.budget.is_some_and(|budget| self.total() > budget)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_memory_unbounded() {
+        let memory = PipelineMemory::unbounded();
+        memory.set(MemoryComponent::ChannelProvider, usize::MAX / 2);
+        assert_eq!(memory.total(), usize::MAX / 2);
+        assert!(!memory.is_exceeded());
+    }
+
+    #[test]
+    fn test_pipeline_memory_budget() {
+        let memory = PipelineMemory::with_budget(100);
+        let shared = memory.clone();
+        shared.set(MemoryComponent::ChannelProvider, 60);
+        memory.set(MemoryComponent::BatchStream, 30);
+        assert_eq!(memory.total(), 90);
+        assert!(!memory.is_exceeded());
+
+        shared.set(MemoryComponent::BatchStream, 50);
+        assert!(memory.is_exceeded());
+
+        memory.set(MemoryComponent::ChannelProvider, 0);
+        assert!(!shared.is_exceeded());
+    }
+}
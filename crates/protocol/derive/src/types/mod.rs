@@ -5,3 +5,11 @@ pub use results::{PipelineResult, StepResult};
 
 mod signals;
 pub use signals::{ActivationSignal, ResetSignal, Signal};
+
+mod events;
+pub use events::{
+    BatcherTxRef, ChannelCloseReason, PipelineEvent, PipelineEventSink, PipelineEvents,
+};
+
+mod memory;
+pub use memory::{MemoryComponent, PipelineMemory};
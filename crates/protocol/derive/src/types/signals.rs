@@ -5,7 +5,7 @@
 //! resetting all stages in the pipeline through message passing.
 
 use kona_genesis::SystemConfig;
-use kona_protocol::{BlockInfo, L2BlockInfo};
+use kona_protocol::{BlockInfo, ChannelId, L2BlockInfo};
 
 /// A signal to send to the pipeline.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +17,9 @@ pub enum Signal {
     Activation(ActivationSignal),
     /// Flush the currently active channel.
     FlushChannel,
+    /// Flush the channel with the given [`ChannelId`], if it is the channel currently being
+    /// read. Other buffered channels are preserved.
+    FlushChannelById(ChannelId),
     /// Provide a new L1 block to the L1 traversal stage.
     ProvideBlock(BlockInfo),
 }
@@ -27,6 +30,7 @@ impl core::fmt::Display for Signal {
             Self::Reset(_) => write!(f, "reset"),
             Self::Activation(_) => write!(f, "activation"),
             Self::FlushChannel => write!(f, "flush_channel"),
+            Self::FlushChannelById(_) => write!(f, "flush_channel_by_id"),
             Self::ProvideBlock(_) => write!(f, "provide_block"),
         }
     }
@@ -39,6 +43,7 @@ impl Signal {
             Self::Reset(reset) => reset.with_system_config(system_config).signal(),
             Self::Activation(activation) => activation.with_system_config(system_config).signal(),
             Self::FlushChannel => Self::FlushChannel,
+            Self::FlushChannelById(id) => Self::FlushChannelById(id),
             Self::ProvideBlock(block) => Self::ProvideBlock(block),
         }
     }
@@ -93,6 +98,7 @@ impl ActivationSignal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
 
     #[test]
     fn test_reset_signal() {
@@ -123,5 +129,15 @@ mod tests {
         );
 
         assert_eq!(Signal::FlushChannel.with_system_config(system_config), Signal::FlushChannel);
+        assert_eq!(
+            Signal::FlushChannelById([0xAA; 16]).with_system_config(system_config),
+            Signal::FlushChannelById([0xAA; 16])
+        );
+    }
+
+    #[test]
+    fn test_signal_display() {
+        assert_eq!(Signal::FlushChannel.to_string(), "flush_channel");
+        assert_eq!(Signal::FlushChannelById([0xAA; 16]).to_string(), "flush_channel_by_id");
     }
 }
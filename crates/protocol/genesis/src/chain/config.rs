@@ -1,14 +1,14 @@
 //! Contains the chain config type.
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use alloy_chains::Chain;
 use alloy_eips::eip1559::BaseFeeParams;
 use alloy_primitives::Address;
 
 use crate::{
-    AddressList, AltDAConfig, BaseFeeConfig, ChainGenesis, GRANITE_CHANNEL_TIMEOUT, HardForkConfig,
-    Roles, RollupConfig, SuperchainLevel, base_fee_params, base_fee_params_canyon,
-    params::base_fee_config, rollup::DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
+    AddressList, AltDAConfig, BaseFeeConfig, ChainGenesis, CustomGasTokenConfig,
+    GRANITE_CHANNEL_TIMEOUT, HardForkConfig, Roles, RollupConfig, SuperchainLevel, base_fee_params,
+    base_fee_params_canyon, params::base_fee_config, rollup::DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
 };
 
 /// L1 chain configuration from the `alloy-genesis` crate.
@@ -86,7 +86,7 @@ pub struct ChainConfig {
     /// The maximum sequencer drift in seconds.
     #[cfg_attr(feature = "serde", serde(rename = "max_sequencer_drift"))]
     pub max_sequencer_drift: u64,
-    /// Gas paying token metadata. Not consumed by downstream OPStack components.
+    /// The L1 address of the gas paying token of custom gas token chains.
     #[cfg_attr(feature = "serde", serde(rename = "GasPayingToken", alias = "gas_paying_token"))]
     pub gas_paying_token: Option<Address>,
     /// Hardfork Config. These values may override the superchain-wide defaults.
@@ -147,6 +147,7 @@ impl ChainConfig {
             max_sequencer_drift: self.max_sequencer_drift,
             hardforks: self.hardfork_config,
             batch_inbox_address: self.batch_inbox_addr,
+            batch_inbox_schedule: Vec::new(),
             deposit_contract_address: self
                 .addresses
                 .as_ref()
@@ -179,6 +180,7 @@ impl ChainConfig {
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
             chain_op_config: self.base_fee_config(),
             alt_da_config: self.alt_da.clone(),
+            custom_gas_token: self.gas_paying_token.map(CustomGasTokenConfig::new),
         }
     }
 }
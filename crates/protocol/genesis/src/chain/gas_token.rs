@@ -0,0 +1,53 @@
+//! Contains the custom gas token config type.
+
+use alloy_primitives::Address;
+
+/// The number of decimals the gas paying token of a custom gas token chain must have.
+pub const CUSTOM_GAS_TOKEN_DECIMALS: u8 = 18;
+
+#[cfg(feature = "serde")]
+const fn default_custom_gas_token_decimals() -> u8 {
+    CUSTOM_GAS_TOKEN_DECIMALS
+}
+
+/// The config of a custom gas token chain, paying for gas with an L1 ERC20 token instead of ETH.
+///
+/// On a custom gas token chain, the native asset of the L2 is the token: the `mint` of deposits is
+/// the amount of the token locked in the portal, and the L1 fees are charged in the token. The L1
+/// fee scalars of the system config are expected to account for the price of the token.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct CustomGasTokenConfig {
+    /// The L1 address of the gas paying token.
+    pub address: Address,
+    /// The number of decimals of the gas paying token.
+    #[cfg_attr(feature = "serde", serde(default = "default_custom_gas_token_decimals"))]
+    pub decimals: u8,
+}
+
+impl CustomGasTokenConfig {
+    /// Creates a new [`CustomGasTokenConfig`] for the gas paying token at the given L1 address.
+    pub const fn new(address: Address) -> Self {
+        Self { address, decimals: CUSTOM_GAS_TOKEN_DECIMALS }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn test_custom_gas_token_config_serde() {
+        let raw = r#"{ "address": "0x0000000000000000000000000000000000000a11" }"#;
+        let config: CustomGasTokenConfig = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            config,
+            CustomGasTokenConfig::new(address!("0000000000000000000000000000000000000a11"))
+        );
+        assert_eq!(config.decimals, CUSTOM_GAS_TOKEN_DECIMALS);
+    }
+}
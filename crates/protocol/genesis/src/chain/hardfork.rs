@@ -74,6 +74,31 @@ pub struct HardForkConfig {
     pub interop_time: Option<u64>,
 }
 
+/// An error returned when the hardforks of a [`HardForkConfig`] are not scheduled in order.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum HardForkOrderError {
+    /// A hardfork is scheduled, but the hardfork preceding it is not.
+    #[error("{fork} is scheduled, but the prior {prior} hardfork is not")]
+    MissingPrior {
+        /// The name of the prior hardfork.
+        prior: &'static str,
+        /// The name of the scheduled hardfork.
+        fork: &'static str,
+    },
+    /// A hardfork activates before the hardfork preceding it.
+    #[error("{fork} activates at {time}, before the prior {prior} hardfork at {prior_time}")]
+    OutOfOrder {
+        /// The name of the prior hardfork.
+        prior: &'static str,
+        /// The activation time of the prior hardfork.
+        prior_time: u64,
+        /// The name of the scheduled hardfork.
+        fork: &'static str,
+        /// The activation time of the scheduled hardfork.
+        time: u64,
+    },
+}
+
 impl Display for HardForkConfig {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         #[inline(always)]
@@ -107,6 +132,39 @@ impl HardForkConfig {
         ]
         .into_iter()
     }
+
+    /// Checks that the hardforks are scheduled in order, from Regolith through Jovian.
+    ///
+    /// A scheduled hardfork requires the hardfork preceding it to be scheduled at or before its
+    /// own activation time. Regolith is implied by Canyon and may be left unscheduled. The Pectra
+    /// blob schedule and Interop are scheduled independently and are not checked.
+    pub fn check_activation_order(&self) -> Result<(), HardForkOrderError> {
+        let ordered = [
+            ("Regolith", self.regolith_time),
+            ("Canyon", self.canyon_time),
+            ("Delta", self.delta_time),
+            ("Ecotone", self.ecotone_time),
+            ("Fjord", self.fjord_time),
+            ("Granite", self.granite_time),
+            ("Holocene", self.holocene_time),
+            ("Isthmus", self.isthmus_time),
+            ("Jovian", self.jovian_time),
+        ];
+
+        for window in ordered.windows(2) {
+            let [(prior, prior_time), (fork, time)] = *window else { continue };
+            let Some(time) = time else { continue };
+            match prior_time {
+                None if prior == "Regolith" => {}
+                None => return Err(HardForkOrderError::MissingPrior { prior, fork }),
+                Some(prior_time) if prior_time > time => {
+                    return Err(HardForkOrderError::OutOfOrder { prior, prior_time, fork, time });
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -236,4 +294,38 @@ mod tests {
         assert_eq!(iter.next(), Some(("Interop", Some(11))));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_hardforks_activation_order() {
+        let hardforks = HardForkConfig {
+            regolith_time: Some(0),
+            canyon_time: Some(10),
+            delta_time: Some(10),
+            ecotone_time: Some(20),
+            pectra_blob_schedule_time: Some(5),
+            interop_time: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(hardforks.check_activation_order(), Ok(()));
+
+        let out_of_order = HardForkConfig { delta_time: Some(30), ..hardforks };
+        assert_eq!(
+            out_of_order.check_activation_order(),
+            Err(HardForkOrderError::OutOfOrder {
+                prior: "Delta",
+                prior_time: 30,
+                fork: "Ecotone",
+                time: 20
+            })
+        );
+
+        let missing_prior = HardForkConfig { granite_time: Some(40), ..hardforks };
+        assert_eq!(
+            missing_prior.check_activation_order(),
+            Err(HardForkOrderError::MissingPrior { prior: "Fjord", fork: "Granite" })
+        );
+
+        let implied_regolith = HardForkConfig { regolith_time: None, ..hardforks };
+        assert_eq!(implied_regolith.check_activation_order(), Ok(()));
+    }
 }
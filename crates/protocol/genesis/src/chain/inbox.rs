@@ -0,0 +1,109 @@
+//! Contains the batch inbox schedule type.
+
+use alloy_primitives::Address;
+
+/// An entry in a rollup's batch inbox schedule.
+///
+/// Chains that rotate their batch inbox address list the rotations as a schedule of entries,
+/// each activating at an L1 block number. The entry with the highest activation block at or
+/// below a given L1 block is the one in effect for that block. Before the first entry activates,
+/// the rollup config's `batch_inbox_address` and the system config's batcher address apply.
+#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct BatchInboxActivation {
+    /// The L1 block number at which this entry takes effect.
+    pub l1_block: u64,
+    /// The batch inbox address that batcher transactions are sent to.
+    pub inbox_address: Address,
+    /// The batcher signer. If unset, the system config's batcher address is used.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub batcher_address: Option<Address>,
+}
+
+impl BatchInboxActivation {
+    /// Returns the entry of the `schedule` that is in effect at the given L1 block number.
+    pub fn active_at(schedule: &[Self], l1_block: u64) -> Option<&Self> {
+        schedule.iter().filter(|e| e.l1_block <= l1_block).max_by_key(|e| e.l1_block)
+    }
+
+    /// Resolves the batch inbox address and batcher signer in effect at the given L1 block
+    /// number, falling back to `inbox_address` and `batcher_address` before the first entry of the
+    /// `schedule` activates.
+    pub fn resolve(
+        schedule: &[Self],
+        l1_block: u64,
+        inbox_address: Address,
+        batcher_address: Address,
+    ) -> (Address, Address) {
+        Self::active_at(schedule, l1_block).map_or((inbox_address, batcher_address), |e| {
+            (e.inbox_address, e.batcher_address.unwrap_or(batcher_address))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::address;
+
+    const INBOX_A: Address = address!("ff00000000000000000000000000000000000001");
+    const INBOX_B: Address = address!("ff00000000000000000000000000000000000002");
+    const BATCHER: Address = address!("0000000000000000000000000000000000000b0b");
+    const SIGNER: Address = address!("000000000000000000000000000000000000a11c");
+
+    #[test]
+    fn test_resolve_batch_inbox() {
+        let schedule = vec![
+            BatchInboxActivation { l1_block: 200, inbox_address: INBOX_B, batcher_address: None },
+            BatchInboxActivation {
+                l1_block: 100,
+                inbox_address: INBOX_A,
+                batcher_address: Some(SIGNER),
+            },
+        ];
+
+        assert_eq!(
+            BatchInboxActivation::resolve(&schedule, 99, Address::ZERO, BATCHER),
+            (Address::ZERO, BATCHER)
+        );
+        assert_eq!(
+            BatchInboxActivation::resolve(&schedule, 100, Address::ZERO, BATCHER),
+            (INBOX_A, SIGNER)
+        );
+        assert_eq!(
+            BatchInboxActivation::resolve(&schedule, 199, Address::ZERO, BATCHER),
+            (INBOX_A, SIGNER)
+        );
+        assert_eq!(
+            BatchInboxActivation::resolve(&schedule, 200, Address::ZERO, BATCHER),
+            (INBOX_B, BATCHER)
+        );
+    }
+
+    #[test]
+    fn test_resolve_empty_schedule() {
+        assert_eq!(BatchInboxActivation::resolve(&[], 0, INBOX_A, BATCHER), (INBOX_A, BATCHER));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_batch_inbox_activation_serde() {
+        let raw = r#"{
+            "l1_block": 100,
+            "inbox_address": "0xff00000000000000000000000000000000000001"
+        }"#;
+        let entry: BatchInboxActivation = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            entry,
+            BatchInboxActivation { l1_block: 100, inbox_address: INBOX_A, batcher_address: None }
+        );
+        assert_eq!(
+            serde_json::from_str::<BatchInboxActivation>(&serde_json::to_string(&entry).unwrap())
+                .unwrap(),
+            entry
+        );
+    }
+}
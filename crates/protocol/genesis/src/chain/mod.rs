@@ -21,8 +21,14 @@ pub use config::{ChainConfig, L1ChainConfig};
 mod altda;
 pub use altda::AltDAConfig;
 
+mod inbox;
+pub use inbox::BatchInboxActivation;
+
+mod gas_token;
+pub use gas_token::{CUSTOM_GAS_TOKEN_DECIMALS, CustomGasTokenConfig};
+
 mod hardfork;
-pub use hardfork::HardForkConfig;
+pub use hardfork::{HardForkConfig, HardForkOrderError};
 
 mod roles;
 pub use roles::Roles;
@@ -52,8 +52,9 @@ pub use system::{
 
 mod chain;
 pub use chain::{
-    AddressList, AltDAConfig, BASE_MAINNET_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID, ChainConfig,
-    HardForkConfig, L1ChainConfig, OP_MAINNET_CHAIN_ID, OP_SEPOLIA_CHAIN_ID, Roles,
+    AddressList, AltDAConfig, BASE_MAINNET_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID, BatchInboxActivation,
+    CUSTOM_GAS_TOKEN_DECIMALS, ChainConfig, CustomGasTokenConfig, HardForkConfig,
+    HardForkOrderError, L1ChainConfig, OP_MAINNET_CHAIN_ID, OP_SEPOLIA_CHAIN_ID, Roles,
 };
 
 mod genesis;
@@ -62,5 +63,6 @@ pub use genesis::ChainGenesis;
 mod rollup;
 pub use rollup::{
     DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW, FJORD_MAX_SEQUENCER_DRIFT, GRANITE_CHANNEL_TIMEOUT,
-    MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD, RollupConfig,
+    MAX_CHANNEL_BANK_SIZE_BEDROCK, MAX_CHANNEL_BANK_SIZE_FJORD, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK,
+    MAX_RLP_BYTES_PER_CHANNEL_FJORD, RollupConfig, RollupConfigError,
 };
@@ -1,6 +1,10 @@
 //! Rollup Config Types
 
-use crate::{AltDAConfig, BaseFeeConfig, ChainGenesis, HardForkConfig, OP_MAINNET_BASE_FEE_CONFIG};
+use crate::{
+    AltDAConfig, BaseFeeConfig, BatchInboxActivation, CUSTOM_GAS_TOKEN_DECIMALS, ChainGenesis,
+    CustomGasTokenConfig, HardForkConfig, HardForkOrderError, OP_MAINNET_BASE_FEE_CONFIG,
+};
+use alloc::vec::Vec;
 use alloy_chains::Chain;
 use alloy_hardforks::{EthereumHardfork, EthereumHardforks, ForkCondition};
 use alloy_op_hardforks::{OpHardfork, OpHardforks};
@@ -12,6 +16,12 @@ pub const MAX_RLP_BYTES_PER_CHANNEL_BEDROCK: u64 = 10_000_000;
 /// The max rlp bytes per channel for the Fjord hardfork.
 pub const MAX_RLP_BYTES_PER_CHANNEL_FJORD: u64 = 100_000_000;
 
+/// The max channel bank size for the Bedrock hardfork.
+pub const MAX_CHANNEL_BANK_SIZE_BEDROCK: usize = 100_000_000;
+
+/// The max channel bank size for the Fjord hardfork.
+pub const MAX_CHANNEL_BANK_SIZE_FJORD: usize = 1_000_000_000;
+
 /// The max sequencer drift when the Fjord hardfork is active.
 pub const FJORD_MAX_SEQUENCER_DRIFT: u64 = 1800;
 
@@ -31,6 +41,74 @@ const fn default_interop_message_expiry_window() -> u64 {
     DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW
 }
 
+/// An error returned by [`RollupConfig::validate`] when a rollup config is malformed.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum RollupConfigError {
+    /// The block time is zero.
+    #[error("block time is zero")]
+    ZeroBlockTime,
+    /// The sequencing window is shorter than 2 L1 blocks.
+    #[error("sequencing window size {0} is less than 2")]
+    InvalidSeqWindowSize(u64),
+    /// The max sequencer drift is zero.
+    #[error("max sequencer drift is zero")]
+    ZeroMaxSequencerDrift,
+    /// A channel timeout is zero.
+    #[error("channel timeout is zero")]
+    ZeroChannelTimeout,
+    /// The L1 chain ID is zero.
+    #[error("L1 chain ID is zero")]
+    ZeroL1ChainId,
+    /// The L2 chain ID is zero.
+    #[error("L2 chain ID is zero")]
+    ZeroL2ChainId,
+    /// The L1 and L2 chain IDs are the same.
+    #[error("L1 and L2 chain IDs are both {0}")]
+    SameChainIds(u64),
+    /// The hash of the L1 genesis block is zero.
+    #[error("L1 genesis block hash is zero")]
+    ZeroL1GenesisHash,
+    /// The hash of the L2 genesis block is zero.
+    #[error("L2 genesis block hash is zero")]
+    ZeroL2GenesisHash,
+    /// The timestamp of the L2 genesis block is zero.
+    #[error("L2 genesis time is zero")]
+    ZeroL2GenesisTime,
+    /// The genesis system config is missing.
+    #[error("genesis system config is missing")]
+    MissingGenesisSystemConfig,
+    /// The batcher address of the genesis system config is zero.
+    #[error("genesis batcher address is zero")]
+    ZeroBatcherAddress,
+    /// The batch inbox address is zero.
+    #[error("batch inbox address is zero")]
+    ZeroBatchInboxAddress,
+    /// The batch inbox address is the batcher address.
+    #[error("batch inbox address {0} is the batcher address")]
+    BatchInboxIsBatcher(Address),
+    /// The deposit contract address is zero.
+    #[error("deposit contract address is zero")]
+    ZeroDepositContractAddress,
+    /// The L1 system config address is zero.
+    #[error("L1 system config address is zero")]
+    ZeroSystemConfigAddress,
+    /// An entry of the batch inbox schedule sets a zero inbox or batcher address.
+    #[error("batch inbox schedule entry at L1 block {0} sets a zero address")]
+    ZeroScheduledAddress(u64),
+    /// Two entries of the batch inbox schedule activate at the same L1 block.
+    #[error("batch inbox schedule has multiple entries at L1 block {0}")]
+    DuplicateScheduledInbox(u64),
+    /// The address of the custom gas token is zero.
+    #[error("custom gas token address is zero")]
+    ZeroGasTokenAddress,
+    /// The custom gas token does not have 18 decimals.
+    #[error("custom gas token has {0} decimals, expected 18")]
+    InvalidGasTokenDecimals(u8),
+    /// The hardforks are not scheduled in order.
+    #[error(transparent)]
+    HardForkOrder(#[from] HardForkOrderError),
+}
+
 /// The Rollup configuration.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -64,6 +142,10 @@ pub struct RollupConfig {
     pub hardforks: HardForkConfig,
     /// `batch_inbox_address` is the L1 address that batches are sent to.
     pub batch_inbox_address: Address,
+    /// `batch_inbox_schedule` lists rotations of the batch inbox address and batcher signer,
+    /// keyed by L1 activation block. Empty for chains that never rotate their inbox.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub batch_inbox_schedule: Vec<BatchInboxActivation>,
     /// `deposit_contract_address` is the L1 address that deposits are sent to.
     pub deposit_contract_address: Address,
     /// `l1_system_config_address` is the L1 address that the system config is stored at.
@@ -94,6 +176,10 @@ pub struct RollupConfig {
     /// `chain_op_config` is the chain-specific EIP1559 config for the rollup.
     #[cfg_attr(feature = "serde", serde(default = "BaseFeeConfig::optimism"))]
     pub chain_op_config: BaseFeeConfig,
+    /// `custom_gas_token` is the config of the gas paying token of custom gas token chains.
+    /// Unset for chains paying for gas with ETH.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub custom_gas_token: Option<CustomGasTokenConfig>,
 }
 
 #[cfg(feature = "arbitrary")]
@@ -119,6 +205,7 @@ impl<'a> arbitrary::Arbitrary<'a> for RollupConfig {
             l2_chain_id: u.arbitrary()?,
             hardforks: HardForkConfig::arbitrary(u)?,
             batch_inbox_address: Address::arbitrary(u)?,
+            batch_inbox_schedule: Vec::<BatchInboxActivation>::arbitrary(u)?,
             deposit_contract_address: Address::arbitrary(u)?,
             l1_system_config_address: Address::arbitrary(u)?,
             protocol_versions_address: Address::arbitrary(u)?,
@@ -128,6 +215,7 @@ impl<'a> arbitrary::Arbitrary<'a> for RollupConfig {
             interop_message_expiry_window: u.arbitrary()?,
             chain_op_config,
             alt_da_config: Option::<AltDAConfig>::arbitrary(u)?,
+            custom_gas_token: Option::<CustomGasTokenConfig>::arbitrary(u)?,
         })
     }
 }
@@ -146,6 +234,7 @@ impl Default for RollupConfig {
             l2_chain_id: Chain::from_id(0),
             hardforks: HardForkConfig::default(),
             batch_inbox_address: Address::ZERO,
+            batch_inbox_schedule: Vec::new(),
             deposit_contract_address: Address::ZERO,
             l1_system_config_address: Address::ZERO,
             protocol_versions_address: Address::ZERO,
@@ -155,6 +244,7 @@ impl Default for RollupConfig {
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
             alt_da_config: None,
             chain_op_config: OP_MAINNET_BASE_FEE_CONFIG,
+            custom_gas_token: None,
         }
     }
 }
@@ -328,6 +418,11 @@ impl RollupConfig {
         self.da_challenge_address.is_some_and(|addr| !addr.is_zero())
     }
 
+    /// Returns true if the chain pays for gas with a custom gas token instead of ETH.
+    pub const fn is_custom_gas_token(&self) -> bool {
+        self.custom_gas_token.is_some()
+    }
+
     /// Returns the max sequencer drift for the given timestamp.
     pub fn max_sequencer_drift(&self, timestamp: u64) -> u64 {
         if self.is_fjord_active(timestamp) {
@@ -346,6 +441,15 @@ impl RollupConfig {
         }
     }
 
+    /// Returns the max channel bank size for the given timestamp.
+    pub fn max_channel_bank_size(&self, timestamp: u64) -> usize {
+        if self.is_fjord_active(timestamp) {
+            MAX_CHANNEL_BANK_SIZE_FJORD
+        } else {
+            MAX_CHANNEL_BANK_SIZE_BEDROCK
+        }
+    }
+
     /// Returns the channel timeout for the given timestamp.
     pub fn channel_timeout(&self, timestamp: u64) -> u64 {
         if self.is_granite_active(timestamp) {
@@ -370,6 +474,88 @@ impl RollupConfig {
         timestamp.saturating_sub(self.genesis.l2_time).saturating_div(self.block_time)
     }
 
+    /// Checks that the rollup config is sane, so that a malformed config is caught before the
+    /// node derives from it.
+    ///
+    /// This checks the block time, sequencing window, sequencer drift and channel timeouts, the
+    /// chain IDs, the genesis block hashes, L2 genesis time and batcher, the batch inbox, deposit
+    /// contract and system config addresses, the batch inbox schedule, the custom gas token, and
+    /// that the hardforks are scheduled in order.
+    pub fn validate(&self) -> Result<(), RollupConfigError> {
+        if self.block_time == 0 {
+            return Err(RollupConfigError::ZeroBlockTime);
+        }
+        if self.seq_window_size < 2 {
+            return Err(RollupConfigError::InvalidSeqWindowSize(self.seq_window_size));
+        }
+        if self.max_sequencer_drift == 0 {
+            return Err(RollupConfigError::ZeroMaxSequencerDrift);
+        }
+        if self.channel_timeout == 0 || self.granite_channel_timeout == 0 {
+            return Err(RollupConfigError::ZeroChannelTimeout);
+        }
+
+        if self.l1_chain_id == 0 {
+            return Err(RollupConfigError::ZeroL1ChainId);
+        }
+        if self.l2_chain_id.id() == 0 {
+            return Err(RollupConfigError::ZeroL2ChainId);
+        }
+        if self.l1_chain_id == self.l2_chain_id.id() {
+            return Err(RollupConfigError::SameChainIds(self.l1_chain_id));
+        }
+
+        if self.genesis.l1.hash.is_zero() {
+            return Err(RollupConfigError::ZeroL1GenesisHash);
+        }
+        if self.genesis.l2.hash.is_zero() {
+            return Err(RollupConfigError::ZeroL2GenesisHash);
+        }
+        if self.genesis.l2_time == 0 {
+            return Err(RollupConfigError::ZeroL2GenesisTime);
+        }
+        let Some(system_config) = &self.genesis.system_config else {
+            return Err(RollupConfigError::MissingGenesisSystemConfig);
+        };
+        if system_config.batcher_address.is_zero() {
+            return Err(RollupConfigError::ZeroBatcherAddress);
+        }
+
+        if self.batch_inbox_address.is_zero() {
+            return Err(RollupConfigError::ZeroBatchInboxAddress);
+        }
+        if self.batch_inbox_address == system_config.batcher_address {
+            return Err(RollupConfigError::BatchInboxIsBatcher(self.batch_inbox_address));
+        }
+        if self.deposit_contract_address.is_zero() {
+            return Err(RollupConfigError::ZeroDepositContractAddress);
+        }
+        if self.l1_system_config_address.is_zero() {
+            return Err(RollupConfigError::ZeroSystemConfigAddress);
+        }
+
+        for (i, entry) in self.batch_inbox_schedule.iter().enumerate() {
+            if entry.inbox_address.is_zero() || entry.batcher_address.is_some_and(|a| a.is_zero()) {
+                return Err(RollupConfigError::ZeroScheduledAddress(entry.l1_block));
+            }
+            if self.batch_inbox_schedule[..i].iter().any(|e| e.l1_block == entry.l1_block) {
+                return Err(RollupConfigError::DuplicateScheduledInbox(entry.l1_block));
+            }
+        }
+
+        if let Some(gas_token) = &self.custom_gas_token {
+            if gas_token.address.is_zero() {
+                return Err(RollupConfigError::ZeroGasTokenAddress);
+            }
+            if gas_token.decimals != CUSTOM_GAS_TOKEN_DECIMALS {
+                return Err(RollupConfigError::InvalidGasTokenDecimals(gas_token.decimals));
+            }
+        }
+
+        self.hardforks.check_activation_order()?;
+        Ok(())
+    }
+
     /// Checks the scalar value in Ecotone.
     pub fn check_ecotone_l1_system_config_scalar(scalar: [u8; 32]) -> Result<(), &'static str> {
         let version_byte = scalar[0];
@@ -490,6 +676,94 @@ mod tests {
         RollupConfig::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
     }
 
+    #[test]
+    fn test_validate_rollup_config() {
+        use crate::SystemConfig;
+        use alloc::vec;
+        use alloy_eips::BlockNumHash;
+        use alloy_primitives::B256;
+
+        let config = RollupConfig {
+            genesis: ChainGenesis {
+                l1: BlockNumHash { number: 1, hash: B256::with_last_byte(1) },
+                l2: BlockNumHash { number: 0, hash: B256::with_last_byte(2) },
+                l2_time: 1_700_000_000,
+                system_config: Some(SystemConfig {
+                    batcher_address: address!("6887246668a3b87f54deb3b94ba47a6f63f32985"),
+                    ..Default::default()
+                }),
+            },
+            block_time: 2,
+            max_sequencer_drift: 600,
+            seq_window_size: 3600,
+            channel_timeout: 300,
+            l1_chain_id: 1,
+            l2_chain_id: Chain::from_id(10),
+            batch_inbox_address: address!("ff00000000000000000000000000000000000010"),
+            deposit_contract_address: address!("beb5fc579115071764c7423a4f12edde41f106ed"),
+            l1_system_config_address: address!("229047fed2591dbec1ef1118d64f7af3db9eb290"),
+            hardforks: HardForkConfig {
+                canyon_time: Some(10),
+                delta_time: Some(20),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.validate(), Ok(()));
+
+        assert_eq!(
+            RollupConfig { block_time: 0, ..config.clone() }.validate(),
+            Err(RollupConfigError::ZeroBlockTime)
+        );
+        assert_eq!(
+            RollupConfig { l2_chain_id: Chain::from_id(1), ..config.clone() }.validate(),
+            Err(RollupConfigError::SameChainIds(1))
+        );
+        assert_eq!(
+            RollupConfig { batch_inbox_address: Address::ZERO, ..config.clone() }.validate(),
+            Err(RollupConfigError::ZeroBatchInboxAddress)
+        );
+
+        let mut no_system_config = config.clone();
+        no_system_config.genesis.system_config = None;
+        assert_eq!(no_system_config.validate(), Err(RollupConfigError::MissingGenesisSystemConfig));
+
+        let duplicate_inbox = BatchInboxActivation {
+            l1_block: 100,
+            inbox_address: address!("ff00000000000000000000000000000000000011"),
+            batcher_address: None,
+        };
+        assert_eq!(
+            RollupConfig {
+                batch_inbox_schedule: vec![duplicate_inbox, duplicate_inbox],
+                ..config.clone()
+            }
+            .validate(),
+            Err(RollupConfigError::DuplicateScheduledInbox(100))
+        );
+
+        let gas_token = CustomGasTokenConfig {
+            address: address!("0000000000000000000000000000000000000a11"),
+            decimals: 6,
+        };
+        assert_eq!(
+            RollupConfig { custom_gas_token: Some(gas_token), ..config.clone() }.validate(),
+            Err(RollupConfigError::InvalidGasTokenDecimals(6))
+        );
+
+        let mut out_of_order = config;
+        out_of_order.hardforks.delta_time = Some(5);
+        assert_eq!(
+            out_of_order.validate(),
+            Err(RollupConfigError::HardForkOrder(HardForkOrderError::OutOfOrder {
+                prior: "Canyon",
+                prior_time: 10,
+                fork: "Delta",
+                time: 5,
+            }))
+        );
+    }
+
     #[test]
     #[cfg(feature = "revm")]
     fn test_revm_spec_id() {
@@ -774,6 +1048,15 @@ mod tests {
         assert_eq!(config.max_sequencer_drift(10), FJORD_MAX_SEQUENCER_DRIFT);
     }
 
+    #[test]
+    fn test_max_channel_bank_size() {
+        let mut config = RollupConfig::default();
+        assert_eq!(config.max_channel_bank_size(10), MAX_CHANNEL_BANK_SIZE_BEDROCK);
+        config.hardforks.fjord_time = Some(10);
+        assert_eq!(config.max_channel_bank_size(0), MAX_CHANNEL_BANK_SIZE_BEDROCK);
+        assert_eq!(config.max_channel_bank_size(10), MAX_CHANNEL_BANK_SIZE_FJORD);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_deserialize_reference_rollup_config() {
@@ -872,6 +1155,7 @@ mod tests {
                 ..Default::default()
             },
             batch_inbox_address: address!("ff00000000000000000000000000000000042069"),
+            batch_inbox_schedule: Vec::new(),
             deposit_contract_address: address!("08073dc48dde578137b8af042bcbc1c2491f1eb2"),
             l1_system_config_address: address!("94ee52a9d8edd72a85dea7fae3ba6d75e4bf1710"),
             protocol_versions_address: Address::ZERO,
@@ -880,6 +1164,7 @@ mod tests {
             da_challenge_address: None,
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
             chain_op_config: OP_MAINNET_BASE_FEE_CONFIG,
+            custom_gas_token: None,
             alt_da_config: None,
         };
 
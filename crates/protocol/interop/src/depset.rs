@@ -1,5 +1,6 @@
-use crate::MESSAGE_EXPIRY_WINDOW;
+use crate::{DependencySetError, MESSAGE_EXPIRY_WINDOW};
 use alloy_primitives::ChainId;
+use kona_genesis::{ChainConfig, RollupConfig};
 use kona_registry::HashMap;
 
 /// Configuration for a dependency of a chain
@@ -21,6 +22,54 @@ pub struct DependencySet {
 }
 
 impl DependencySet {
+    /// Returns the [`DependencySet`] of the given chain in a registry of chains.
+    ///
+    /// The set holds the chains settling on the same L1 that activate Interop at the same time as
+    /// the given chain.
+    pub fn from_registry<'a>(
+        chain_id: ChainId,
+        chains: impl IntoIterator<Item = &'a ChainConfig>,
+    ) -> Result<Self, DependencySetError> {
+        let chains = chains.into_iter().collect::<alloc::vec::Vec<_>>();
+        let chain = chains
+            .iter()
+            .find(|chain| chain.chain_id == chain_id)
+            .ok_or(DependencySetError::UnknownChain(chain_id))?;
+        let interop_time = chain
+            .hardfork_config
+            .interop_time
+            .ok_or(DependencySetError::InteropNotScheduled(chain_id))?;
+
+        let dependencies = chains
+            .iter()
+            .filter(|other| {
+                other.l1_chain_id == chain.l1_chain_id &&
+                    other.hardfork_config.interop_time == Some(interop_time)
+            })
+            .map(|other| (other.chain_id, ChainDependency {}))
+            .collect();
+        Ok(Self { dependencies, override_message_expiry_window: None })
+    }
+
+    /// Returns whether the given chain is part of the dependency set.
+    pub fn contains(&self, chain_id: ChainId) -> bool {
+        self.dependencies.contains_key(&chain_id)
+    }
+
+    /// Validates the dependency set against the rollup config of a chain.
+    ///
+    /// The rollup config must schedule Interop, and its chain must be part of the set.
+    pub fn validate(&self, rollup_config: &RollupConfig) -> Result<(), DependencySetError> {
+        let chain_id = rollup_config.l2_chain_id.id();
+        if rollup_config.hardforks.interop_time.is_none() {
+            return Err(DependencySetError::InteropNotScheduled(chain_id));
+        }
+        if !self.contains(chain_id) {
+            return Err(DependencySetError::MissingChain(chain_id));
+        }
+        Ok(())
+    }
+
     /// Returns the message expiry window associated with this dependency set.
     pub const fn get_message_expiry_window(&self) -> u64 {
         match self.override_message_expiry_window {
@@ -34,6 +83,7 @@ impl DependencySet {
 mod tests {
     use super::*;
     use alloy_primitives::ChainId;
+    use kona_genesis::HardForkConfig;
     use kona_registry::HashMap;
 
     const fn create_dependency_set(
@@ -66,4 +116,78 @@ mod tests {
             "Should return override expiry window when it's non-zero"
         );
     }
+
+    fn chain(chain_id: u64, l1_chain_id: u64, interop_time: Option<u64>) -> ChainConfig {
+        ChainConfig {
+            chain_id,
+            l1_chain_id,
+            hardfork_config: HardForkConfig { interop_time, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn dependency_set(chain_ids: &[ChainId]) -> DependencySet {
+        DependencySet {
+            dependencies: chain_ids.iter().map(|id| (*id, ChainDependency {})).collect(),
+            override_message_expiry_window: None,
+        }
+    }
+
+    #[test]
+    fn test_dependency_set_from_registry() {
+        let chains = [
+            chain(10, 1, Some(1_750_000_000)),
+            chain(8453, 1, Some(1_750_000_000)),
+            chain(7777, 1, Some(1_760_000_000)),
+            chain(11155420, 11155111, Some(1_750_000_000)),
+            chain(1, 1, None),
+        ];
+
+        let set = DependencySet::from_registry(10, &chains).unwrap();
+        assert_eq!(set, dependency_set(&[10, 8453]));
+
+        assert_eq!(
+            DependencySet::from_registry(1, &chains),
+            Err(DependencySetError::InteropNotScheduled(1))
+        );
+        assert_eq!(
+            DependencySet::from_registry(42, &chains),
+            Err(DependencySetError::UnknownChain(42))
+        );
+    }
+
+    #[test]
+    fn test_dependency_set_validate() {
+        let mut rollup_config = RollupConfig {
+            l2_chain_id: 10.into(),
+            hardforks: HardForkConfig { interop_time: Some(1_750_000_000), ..Default::default() },
+            ..Default::default()
+        };
+        let set = dependency_set(&[10, 8453]);
+        set.validate(&rollup_config).unwrap();
+
+        rollup_config.l2_chain_id = 7777.into();
+        assert_eq!(set.validate(&rollup_config), Err(DependencySetError::MissingChain(7777)));
+
+        rollup_config.hardforks.interop_time = None;
+        assert_eq!(
+            set.validate(&rollup_config),
+            Err(DependencySetError::InteropNotScheduled(7777))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_dependency_set_serde() {
+        let json = r#"{
+            "dependencies": { "10": {}, "8453": {} },
+            "overrideMessageExpiryWindow": 3600
+        }"#;
+        let set = serde_json::from_str::<DependencySet>(json).unwrap();
+        assert!(set.contains(10) && set.contains(8453));
+        assert_eq!(set.get_message_expiry_window(), 3600);
+
+        let set = serde_json::from_str::<DependencySet>(r#"{"dependencies":{"10":{}}}"#).unwrap();
+        assert_eq!(set, dependency_set(&[10]));
+    }
 }
@@ -133,3 +133,19 @@ pub enum InteropValidationError {
     #[error("timestamp outside allowed interop window, timestamp: {0}")]
     InvalidInteropTimestamp(u64),
 }
+
+/// An error deriving or validating a [DependencySet].
+///
+/// [DependencySet]: crate::DependencySet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum DependencySetError {
+    /// The chain is unknown to the registry.
+    #[error("Chain {0} is not in the registry")]
+    UnknownChain(u64),
+    /// The chain does not schedule Interop, so it has no dependency set.
+    #[error("Interop is not scheduled for chain {0}")]
+    InteropNotScheduled(u64),
+    /// The chain is not part of the dependency set.
+    #[error("Chain {0} is not part of the dependency set")]
+    MissingChain(u64),
+}
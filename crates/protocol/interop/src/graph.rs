@@ -57,8 +57,13 @@ where
             "Deriving message graph",
         );
 
+        // Visit the blocks in order of chain ID, so that the messages, and the data fetched to
+        // derive them, are ordered independently of the hasher of the map.
+        let mut chain_ids = blocks.keys().copied().collect::<Vec<_>>();
+        chain_ids.sort_unstable();
+
         let mut messages = Vec::with_capacity(blocks.len());
-        for (chain_id, header) in blocks.iter() {
+        for (chain_id, header) in chain_ids.iter().map(|id| (id, &blocks[id])) {
             let receipts = provider.receipts_by_hash(*chain_id, header.hash()).await?;
             let executing_messages = extract_executing_messages(receipts.as_slice());
 
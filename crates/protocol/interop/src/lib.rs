@@ -28,7 +28,8 @@ pub use safety::SafetyLevelParseError;
 
 mod errors;
 pub use errors::{
-    InteropValidationError, MessageGraphError, MessageGraphResult, SuperRootError, SuperRootResult,
+    DependencySetError, InteropValidationError, MessageGraphError, MessageGraphResult,
+    SuperRootError, SuperRootResult,
 };
 
 mod root;
@@ -1,6 +1,6 @@
 //! Optimism Payload attributes that reference the parent L2 block.
 
-use crate::{BlockInfo, L2BlockInfo};
+use crate::{BlockInfo, ChannelId, L2BlockInfo};
 use op_alloy_consensus::OpTxType;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
@@ -16,6 +16,9 @@ pub struct OpAttributesWithParent {
     pub derived_from: Option<BlockInfo>,
     /// Whether the current batch is the last in its span.
     pub is_last_in_span: bool,
+    /// The [`ChannelId`] of the channel the batch was read from, if known.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub channel_id: Option<ChannelId>,
 }
 
 impl OpAttributesWithParent {
@@ -26,7 +29,13 @@ impl OpAttributesWithParent {
         derived_from: Option<BlockInfo>,
         is_last_in_span: bool,
     ) -> Self {
-        Self { attributes, parent, derived_from, is_last_in_span }
+        Self { attributes, parent, derived_from, is_last_in_span, channel_id: None }
+    }
+
+    /// Sets the [`ChannelId`] of the channel the batch was read from.
+    pub const fn with_channel_id(mut self, channel_id: Option<ChannelId>) -> Self {
+        self.channel_id = channel_id;
+        self
     }
 
     /// Returns the L2 block number for the payload attributes if made canonical.
@@ -60,6 +69,11 @@ impl OpAttributesWithParent {
         self.is_last_in_span
     }
 
+    /// Returns the [`ChannelId`] of the channel the batch was read from, if known.
+    pub const fn channel_id(&self) -> Option<ChannelId> {
+        self.channel_id
+    }
+
     /// Returns `true` if all transactions in the payload are deposits.
     pub fn is_deposits_only(&self) -> bool {
         self.attributes
@@ -82,6 +96,7 @@ impl OpAttributesWithParent {
             parent: self.parent,
             derived_from: self.derived_from,
             is_last_in_span: self.is_last_in_span,
+            channel_id: self.channel_id,
         }
     }
 }
@@ -103,6 +118,11 @@ mod tests {
         assert_eq!(op_attributes_with_parent.parent(), &parent);
         assert_eq!(op_attributes_with_parent.is_last_in_span(), is_last_in_span);
         assert_eq!(op_attributes_with_parent.derived_from(), None);
+        assert_eq!(op_attributes_with_parent.channel_id(), None);
+        assert_eq!(
+            op_attributes_with_parent.with_channel_id(Some([0xAA; 16])).channel_id(),
+            Some([0xAA; 16])
+        );
     }
 
     /// Test that the [`OpAttributesWithParent::as_deposits_only`] method strips out all
@@ -1,6 +1,6 @@
 //! Span Batch Element
 
-use crate::SingleBatch;
+use crate::{SingleBatch, SpanBatchError};
 use alloc::vec::Vec;
 use alloy_primitives::Bytes;
 
@@ -8,6 +8,23 @@ use alloy_primitives::Bytes;
 /// or transaction per block allowed in a span batch.
 pub const MAX_SPAN_BATCH_ELEMENTS: u64 = 10_000_000;
 
+/// Allocates a [`Vec`] for `count` span batch elements, each taking at least `min_size` bytes of
+/// the remaining input `r`.
+///
+/// The capacity is bounded by the number of elements that the input can hold, and the allocation
+/// is fallible, so that malformed or oversized span batches surface an error rather than
+/// aborting on memory-constrained targets.
+pub(crate) fn try_alloc_elements<T>(
+    count: u64,
+    min_size: usize,
+    r: &[u8],
+) -> Result<Vec<T>, SpanBatchError> {
+    let capacity = usize::try_from(count).unwrap_or(usize::MAX).min(r.len() / min_size.max(1));
+    let mut elements = Vec::new();
+    elements.try_reserve_exact(capacity).map_err(|_| SpanBatchError::AllocationFailed(capacity))?;
+    Ok(elements)
+}
+
 /// A single batch element is similar to the [`SingleBatch`] type
 /// but does not contain the parent hash and epoch hash since spans
 /// do not contain this data for every block in the span.
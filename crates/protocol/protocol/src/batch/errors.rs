@@ -18,6 +18,9 @@ pub enum SpanBatchError {
     /// Missing L1 origin
     #[error("Missing L1 origin")]
     MissingL1Origin,
+    /// Failed to allocate memory for the elements of the span batch
+    #[error("Failed to allocate memory for {0} span batch elements")]
+    AllocationFailed(usize),
     /// Decoding errors
     #[error("Span batch decoding error: {0}")]
     Decoding(#[from] SpanDecodingError),
@@ -33,7 +33,7 @@ mod r#type;
 pub use r#type::*;
 
 mod reader;
-pub use reader::{BatchReader, DecompressionError};
+pub use reader::{BatchAllocationError, BatchReader, DecompressionError};
 
 mod tx;
 pub use tx::BatchTransaction;
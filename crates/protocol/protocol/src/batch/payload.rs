@@ -1,6 +1,6 @@
 //! Raw Span Batch Payload
 
-use super::MAX_SPAN_BATCH_ELEMENTS;
+use super::{MAX_SPAN_BATCH_ELEMENTS, element::try_alloc_elements};
 use crate::{SpanBatchBits, SpanBatchError, SpanBatchTransactions, SpanDecodingError};
 use alloc::vec::Vec;
 use alloy_primitives::bytes;
@@ -69,7 +69,7 @@ impl SpanBatchPayload {
     pub fn decode_block_tx_counts(&mut self, r: &mut &[u8]) -> Result<(), SpanBatchError> {
         // Initially allocate the vec with the block count, to reduce re-allocations in the first
         // few blocks.
-        let mut block_tx_counts = Vec::with_capacity(self.block_count as usize);
+        let mut block_tx_counts = try_alloc_elements(self.block_count, 1, r)?;
 
         for _ in 0..self.block_count {
             let (block_tx_count, remaining) = unsigned_varint::decode::u64(r)
@@ -1,11 +1,13 @@
 //! Contains the [`BatchReader`] which is used to iteratively consume batches from raw data.
 
-use crate::{Batch, BrotliDecompressionError, decompress_brotli};
+use crate::{
+    Batch, BatchDecodingError, BrotliDecompressionError, SpanBatchError, decompress_brotli,
+};
 use alloc::vec::Vec;
 use alloy_primitives::Bytes;
 use alloy_rlp::Decodable;
 use kona_genesis::RollupConfig;
-use miniz_oxide::inflate::decompress_to_vec_zlib;
+use miniz_oxide::inflate::{TINFLStatus, decompress_to_vec_zlib_with_limit};
 
 /// Error type for decompression failures.
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +29,14 @@ pub enum DecompressionError {
     RlpTooLarge(usize, usize),
 }
 
+/// An allocation failure while reading batches.
+///
+/// Unlike invalid batch data, which ends the reader, allocation failures are not caused by the
+/// data itself and must not be treated as such.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("failed to allocate {0} elements while reading batches")]
+pub struct BatchAllocationError(pub usize);
+
 /// Batch Reader provides a function that iteratively consumes batches from the reader.
 /// The L1Inclusion block is also provided at creation time.
 /// Warning: the batch reader can read every batch-type.
@@ -82,16 +92,17 @@ impl BatchReader {
             if (compression_type & 0x0F) == Self::ZLIB_DEFLATE_COMPRESSION_METHOD ||
                 (compression_type & 0x0F) == Self::ZLIB_RESERVED_COMPRESSION_METHOD
             {
+                // Bound the decompressed channel RLP by the maximum size, rather than growing the
+                // output buffer without limit and checking its size afterwards.
                 self.decompressed =
-                    decompress_to_vec_zlib(&data).map_err(|_| DecompressionError::ZlibError)?;
-
-                // Check the size of the decompressed channel RLP.
-                if self.decompressed.len() > self.max_rlp_bytes_per_channel {
-                    return Err(DecompressionError::RlpTooLarge(
-                        self.decompressed.len(),
-                        self.max_rlp_bytes_per_channel,
-                    ));
-                }
+                    decompress_to_vec_zlib_with_limit(&data, self.max_rlp_bytes_per_channel)
+                        .map_err(|e| match e.status {
+                            TINFLStatus::HasMoreOutput => DecompressionError::RlpTooLarge(
+                                e.output.len(),
+                                self.max_rlp_bytes_per_channel,
+                            ),
+                            _ => DecompressionError::ZlibError,
+                        })?;
             } else if compression_type == Self::CHANNEL_VERSION_BROTLI {
                 self.brotli_used = true;
                 self.decompressed = decompress_brotli(&data[1..], self.max_rlp_bytes_per_channel)?;
@@ -104,24 +115,46 @@ impl BatchReader {
 
     /// Pulls out the next batch from the reader.
     pub fn next_batch(&mut self, cfg: &RollupConfig) -> Option<Batch> {
+        self.try_next_batch(cfg).ok().flatten()
+    }
+
+    /// Pulls out the next batch from the reader, returning `None` once the data holds no further
+    /// valid batch, and a [`BatchAllocationError`] if decompressing or decoding the data failed
+    /// to allocate memory.
+    pub fn try_next_batch(
+        &mut self,
+        cfg: &RollupConfig,
+    ) -> Result<Option<Batch>, BatchAllocationError> {
         // Ensure the data is decompressed.
-        self.decompress().ok()?;
+        match self.decompress() {
+            Ok(()) => {}
+            Err(DecompressionError::BrotliError(BrotliDecompressionError::AllocationFailed(n))) => {
+                return Err(BatchAllocationError(n));
+            }
+            Err(_) => return Ok(None),
+        }
 
         // Decompress and RLP decode the batch data, before finally decoding the batch itself.
         let decompressed_reader = &mut self.decompressed.as_slice()[self.cursor..].as_ref();
-        let bytes = Bytes::decode(decompressed_reader).ok()?;
-        let Ok(batch) = Batch::decode(&mut bytes.as_ref(), cfg) else {
-            return None;
+        let Ok(bytes) = Bytes::decode(decompressed_reader) else {
+            return Ok(None);
+        };
+        let batch = match Batch::decode(&mut bytes.as_ref(), cfg) {
+            Ok(batch) => batch,
+            Err(BatchDecodingError::SpanBatchError(SpanBatchError::AllocationFailed(n))) => {
+                return Err(BatchAllocationError(n));
+            }
+            Err(_) => return Ok(None),
         };
 
         // Confirm that brotli decompression was performed *after* the Fjord hardfork.
         if self.brotli_used && !cfg.is_fjord_active(batch.timestamp()) {
-            return None;
+            return Ok(None);
         }
 
         // Advance the cursor on the reader.
         self.cursor = self.decompressed.len() - decompressed_reader.len();
-        Some(batch)
+        Ok(Some(batch))
     }
 }
 
@@ -131,6 +164,7 @@ mod test {
     use kona_genesis::{
         HardForkConfig, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD,
     };
+    use miniz_oxide::inflate::decompress_to_vec_zlib;
 
     fn new_compressed_batch_data() -> Bytes {
         let file_contents =
@@ -162,4 +196,15 @@ mod test {
             .unwrap();
         assert_eq!(reader.cursor, decompressed_len);
     }
+
+    #[test]
+    fn test_batch_reader_rlp_too_large() {
+        let raw = new_compressed_batch_data();
+        let decompressed_len = decompress_to_vec_zlib(&raw).unwrap().len();
+        let mut reader = BatchReader::new(raw, decompressed_len - 1);
+        assert!(matches!(
+            reader.decompress(),
+            Err(DecompressionError::RlpTooLarge(_, max)) if max == decompressed_len - 1
+        ));
+    }
 }
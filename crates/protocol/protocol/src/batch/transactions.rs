@@ -1,6 +1,7 @@
 //! This module contains the [`SpanBatchTransactions`] type and logic for encoding and decoding
 //! transactions in a span batch.
 
+use super::element::try_alloc_elements;
 use crate::{
     MAX_SPAN_BATCH_ELEMENTS, SpanBatchBits, SpanBatchError, SpanBatchTransactionData,
     SpanDecodingError, read_tx_data,
@@ -150,7 +151,10 @@ impl SpanBatchTransactions {
     /// Decode the transaction signatures from a reader (excluding `v` field).
     pub fn decode_tx_sigs(&mut self, r: &mut &[u8]) -> Result<(), SpanBatchError> {
         let y_parity_bits = SpanBatchBits::decode(r, self.total_block_tx_count as usize)?;
-        let mut sigs = Vec::with_capacity(self.total_block_tx_count as usize);
+        if (r.len() as u64) < self.total_block_tx_count.saturating_mul(64) {
+            return Err(SpanBatchError::Decoding(SpanDecodingError::InvalidTransactionSignature));
+        }
+        let mut sigs = try_alloc_elements(self.total_block_tx_count, 64, r)?;
         for i in 0..self.total_block_tx_count {
             let y_parity = y_parity_bits.get_bit(i as usize).expect("same length");
             let r_val = U256::from_be_slice(&r[..32]);
@@ -164,7 +168,7 @@ impl SpanBatchTransactions {
 
     /// Decode the transaction nonces from a reader.
     pub fn decode_tx_nonces(&mut self, r: &mut &[u8]) -> Result<(), SpanBatchError> {
-        let mut nonces = Vec::with_capacity(self.total_block_tx_count as usize);
+        let mut nonces = try_alloc_elements(self.total_block_tx_count, 1, r)?;
         for _ in 0..self.total_block_tx_count {
             let (nonce, remaining) = unsigned_varint::decode::u64(r)
                 .map_err(|_| SpanBatchError::Decoding(SpanDecodingError::TxNonces))?;
@@ -177,7 +181,7 @@ impl SpanBatchTransactions {
 
     /// Decode the transaction gas limits from a reader.
     pub fn decode_tx_gases(&mut self, r: &mut &[u8]) -> Result<(), SpanBatchError> {
-        let mut gases = Vec::with_capacity(self.total_block_tx_count as usize);
+        let mut gases = try_alloc_elements(self.total_block_tx_count, 1, r)?;
         for _ in 0..self.total_block_tx_count {
             let (gas, remaining) = unsigned_varint::decode::u64(r)
                 .map_err(|_| SpanBatchError::Decoding(SpanDecodingError::TxNonces))?;
@@ -190,9 +194,13 @@ impl SpanBatchTransactions {
 
     /// Decode the `to` addresses of the transactions from a reader.
     pub fn decode_tx_tos(&mut self, r: &mut &[u8]) -> Result<(), SpanBatchError> {
-        let mut tos = Vec::with_capacity(self.total_block_tx_count as usize);
         let contract_creation_count = self.contract_creation_count();
-        for _ in 0..(self.total_block_tx_count - contract_creation_count) {
+        let to_count = self.total_block_tx_count - contract_creation_count;
+        if (r.len() as u64) < to_count.saturating_mul(20) {
+            return Err(SpanBatchError::Decoding(SpanDecodingError::InvalidTransactionData));
+        }
+        let mut tos = try_alloc_elements(to_count, 20, r)?;
+        for _ in 0..to_count {
             let to = Address::from_slice(&r[..20]);
             tos.push(to);
             r.advance(20);
@@ -203,8 +211,8 @@ impl SpanBatchTransactions {
 
     /// Decode the transaction data from a reader.
     pub fn decode_tx_data(&mut self, r: &mut &[u8]) -> Result<(), SpanBatchError> {
-        let mut tx_data = Vec::new();
-        let mut tx_types = Vec::new();
+        let mut tx_data = try_alloc_elements(self.total_block_tx_count, 1, r)?;
+        let mut tx_types = try_alloc_elements(self.total_block_tx_count, 1, r)?;
 
         // Do not need the transaction data header because the RLP stream already includes the
         // length information.
@@ -436,4 +444,25 @@ mod tests {
         assert_eq!(result, Ok(()));
         assert_eq!(span_batch_txs.total_block_tx_count, 1);
     }
+
+    #[test]
+    fn test_span_batch_transactions_decode_truncated_elements() {
+        let mut span_batch_txs = SpanBatchTransactions {
+            total_block_tx_count: MAX_SPAN_BATCH_ELEMENTS,
+            ..Default::default()
+        };
+
+        // The elements are bounded by the remaining input rather than the claimed count.
+        let data = [0u8; 64];
+        assert_eq!(
+            span_batch_txs.decode_tx_tos(&mut data.as_slice()),
+            Err(SpanBatchError::Decoding(SpanDecodingError::InvalidTransactionData))
+        );
+        assert_eq!(
+            span_batch_txs.decode_tx_nonces(&mut data.as_slice()),
+            Err(SpanBatchError::Decoding(SpanDecodingError::TxNonces))
+        );
+        assert!(span_batch_txs.tx_tos.is_empty());
+        assert!(span_batch_txs.tx_nonces.is_empty());
+    }
 }
@@ -0,0 +1,344 @@
+//! The EIP-4844 blob codec of the OP Stack.
+//!
+//! Batchers post channel data to L1 in blobs. A blob holds 4096 field elements of 32 bytes, and
+//! each field element must stay below the BLS12-381 scalar field modulus. The OP Stack keeps every
+//! field element canonical by leaving its two high order bits clear, and packs the remaining 254
+//! bits of each group of 4 field elements with 127 bytes of data.
+//!
+//! # Blob Layout
+//!
+//! ```text
+//! Field element 0: [6 bits of data][version: 1 byte][length: 3 bytes][27 bytes of data]
+//! Field element N: [6 bits of data][31 bytes of data]
+//! ```
+//!
+//! The 4 high order bytes of each group of 4 field elements carry 6 bits each, which reassemble
+//! into 3 more bytes of data. Bytes past the encoded length must be zero.
+//!
+//! [`decode_blob`] and [`encode_blob`] are inverses, and are shared by the derivation pipeline and
+//! batcher tooling.
+
+use alloc::vec;
+use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob};
+use alloy_primitives::Bytes;
+
+/// The version of the blob encoding.
+pub const BLOB_ENCODING_VERSION: u8 = 0;
+
+/// The maximum number of data bytes that fit in a blob.
+pub const BLOB_MAX_DATA_SIZE: usize = (4 * 31 + 3) * 1024 - 4; // 130044
+
+/// The number of encoding rounds in a blob. Each round packs 127 bytes of data into 4 field
+/// elements.
+pub const BLOB_ENCODING_ROUNDS: usize = 1024;
+
+/// The offset of the encoding version in a blob.
+const VERSION_OFFSET: usize = 1;
+
+/// The size of a field element, in bytes.
+const FIELD_ELEMENT_SIZE: usize = 32;
+
+/// An error decoding a blob.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BlobDecodingError {
+    /// The blob has an unknown encoding version.
+    #[error("Invalid blob encoding version {0}, expected {BLOB_ENCODING_VERSION}")]
+    InvalidEncodingVersion(u8),
+    /// The encoded length exceeds the maximum blob data size.
+    #[error("Invalid blob data length {0}, the maximum is {BLOB_MAX_DATA_SIZE}")]
+    InvalidLength(usize),
+    /// A field element has one of its two high order bits set.
+    #[error("Field element {0} has its high order bits set")]
+    InvalidFieldElement(usize),
+    /// The decoded data has non-zero bytes past the encoded length.
+    #[error("Blob data has non-zero bytes past its length of {0}")]
+    TrailingData(usize),
+    /// The blob has non-zero bytes past the last field element holding data.
+    #[error("Blob has a non-zero byte at offset {0}, past its data")]
+    NonZeroPadding(usize),
+}
+
+/// An error encoding data into a blob.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BlobEncodingError {
+    /// The data doesn't fit in a blob.
+    #[error("Data length {0} exceeds the maximum blob data size of {BLOB_MAX_DATA_SIZE}")]
+    DataTooLarge(usize),
+}
+
+/// Returns the versioned hash that commits to a blob, given its KZG commitment.
+///
+/// Blob transactions reference their blobs by versioned hash, which is how the derivation
+/// pipeline matches fetched blobs to batcher transactions.
+#[cfg(feature = "kzg")]
+pub fn blob_versioned_hash(commitment: &alloy_eips::eip4844::Bytes48) -> alloy_primitives::B256 {
+    alloy_eips::eip4844::kzg_to_versioned_hash(commitment.as_slice())
+}
+
+/// Decodes the data packed in a blob.
+///
+/// Every field element that holds data is checked to be canonical, and every byte past the
+/// encoded length must be zero. The high order bits of the first field element are not checked,
+/// as in op-node.
+pub fn decode_blob(blob: &Blob) -> Result<Bytes, BlobDecodingError> {
+    let data = blob.as_slice();
+
+    if data[VERSION_OFFSET] != BLOB_ENCODING_VERSION {
+        return Err(BlobDecodingError::InvalidEncodingVersion(data[VERSION_OFFSET]));
+    }
+
+    // Decode the 3 byte big endian length value into a 4 byte integer.
+    let length = u32::from_be_bytes([0, data[2], data[3], data[4]]) as usize;
+    if length > BLOB_MAX_DATA_SIZE {
+        return Err(BlobDecodingError::InvalidLength(length));
+    }
+
+    // Round 0 copies the remaining 27 bytes of the first field element.
+    let mut output = vec![0u8; BLOB_MAX_DATA_SIZE];
+    output[0..27].copy_from_slice(&data[5..FIELD_ELEMENT_SIZE]);
+
+    // Process the remaining 3 field elements to complete round 0.
+    let mut output_pos = 28;
+    let mut input_pos = FIELD_ELEMENT_SIZE;
+    let mut encoded_bytes = [0u8; 4];
+    encoded_bytes[0] = data[0];
+    for encoded_byte in encoded_bytes.iter_mut().skip(1) {
+        *encoded_byte = decode_field_element(data, &mut output_pos, &mut input_pos, &mut output)?;
+    }
+    output_pos = reassemble_bytes(output_pos, &encoded_bytes, &mut output);
+
+    // In each remaining round, decode 4 field elements (128 bytes) of the input into 127 bytes
+    // of output.
+    for _ in 1..BLOB_ENCODING_ROUNDS {
+        if output_pos >= length {
+            break;
+        }
+
+        for encoded_byte in &mut encoded_bytes {
+            *encoded_byte =
+                decode_field_element(data, &mut output_pos, &mut input_pos, &mut output)?;
+        }
+        output_pos = reassemble_bytes(output_pos, &encoded_bytes, &mut output);
+    }
+
+    if output[length..].iter().any(|b| *b != 0) {
+        return Err(BlobDecodingError::TrailingData(length));
+    }
+    if let Some(offset) = data[input_pos..].iter().position(|b| *b != 0) {
+        return Err(BlobDecodingError::NonZeroPadding(input_pos + offset));
+    }
+
+    output.truncate(length);
+    Ok(Bytes::from(output))
+}
+
+/// Encodes data into a blob. The inverse of [`decode_blob`].
+pub fn encode_blob(data: &[u8]) -> Result<Blob, BlobEncodingError> {
+    if data.len() > BLOB_MAX_DATA_SIZE {
+        return Err(BlobEncodingError::DataTooLarge(data.len()));
+    }
+
+    let mut blob = Blob::ZERO;
+    let mut input = data;
+    let mut output_pos = 0;
+    let mut chunk = [0u8; 31];
+
+    for round in 0..BLOB_ENCODING_ROUNDS {
+        if input.is_empty() {
+            break;
+        }
+
+        // The first field element of round 0 holds the version and the length of the data.
+        if round == 0 {
+            chunk[0] = BLOB_ENCODING_VERSION;
+            chunk[1..4].copy_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+            read_into(&mut input, &mut chunk[4..]);
+        } else {
+            read_into(&mut input, &mut chunk);
+        }
+        let x = read_byte(&mut input);
+        write_field_element(&mut blob, &mut output_pos, x & 0b0011_1111, &chunk);
+
+        read_into(&mut input, &mut chunk);
+        let y = read_byte(&mut input);
+        write_field_element(
+            &mut blob,
+            &mut output_pos,
+            (y & 0b0000_1111) | ((x & 0b1100_0000) >> 2),
+            &chunk,
+        );
+
+        read_into(&mut input, &mut chunk);
+        let z = read_byte(&mut input);
+        write_field_element(&mut blob, &mut output_pos, z & 0b0011_1111, &chunk);
+
+        read_into(&mut input, &mut chunk);
+        write_field_element(
+            &mut blob,
+            &mut output_pos,
+            ((z & 0b1100_0000) >> 2) | ((y & 0b1111_0000) >> 4),
+            &chunk,
+        );
+    }
+
+    Ok(blob)
+}
+
+/// Decodes the next field element by writing its lower 31 bytes into the output, and returns its
+/// high order byte.
+fn decode_field_element(
+    data: &[u8],
+    output_pos: &mut usize,
+    input_pos: &mut usize,
+    output: &mut [u8],
+) -> Result<u8, BlobDecodingError> {
+    let high_order_byte = data[*input_pos];
+
+    // The two highest order bits of each field element must be clear.
+    if high_order_byte & 0b1100_0000 != 0 {
+        return Err(BlobDecodingError::InvalidFieldElement(*input_pos / FIELD_ELEMENT_SIZE));
+    }
+
+    output[*output_pos..*output_pos + 31]
+        .copy_from_slice(&data[*input_pos + 1..*input_pos + FIELD_ELEMENT_SIZE]);
+    *output_pos += FIELD_ELEMENT_SIZE;
+    *input_pos += FIELD_ELEMENT_SIZE;
+    Ok(high_order_byte)
+}
+
+/// Reassembles 4 by 6 bit encoded chunks into 3 bytes of output, and places them in their
+/// output positions.
+fn reassemble_bytes(mut output_pos: usize, encoded_bytes: &[u8; 4], output: &mut [u8]) -> usize {
+    output_pos -= 1;
+    let x = (encoded_bytes[0] & 0b0011_1111) | ((encoded_bytes[1] & 0b0011_0000) << 2);
+    let y = (encoded_bytes[1] & 0b0000_1111) | ((encoded_bytes[3] & 0b0000_1111) << 4);
+    let z = (encoded_bytes[2] & 0b0011_1111) | ((encoded_bytes[3] & 0b0011_0000) << 2);
+    output[output_pos - FIELD_ELEMENT_SIZE] = z;
+    output[output_pos - (FIELD_ELEMENT_SIZE * 2)] = y;
+    output[output_pos - (FIELD_ELEMENT_SIZE * 3)] = x;
+    output_pos
+}
+
+/// Fills the buffer from the front of the input, zero-padding it once the input runs out.
+fn read_into(input: &mut &[u8], buf: &mut [u8]) {
+    let n = buf.len().min(input.len());
+    buf[..n].copy_from_slice(&input[..n]);
+    buf[n..].fill(0);
+    *input = &input[n..];
+}
+
+/// Reads a byte from the front of the input, or zero once the input runs out.
+fn read_byte(input: &mut &[u8]) -> u8 {
+    let Some((byte, rest)) = input.split_first() else {
+        return 0;
+    };
+    *input = rest;
+    *byte
+}
+
+/// Writes a field element made of the given high order byte and 31 lower bytes.
+fn write_field_element(blob: &mut Blob, output_pos: &mut usize, high_order_byte: u8, low: &[u8]) {
+    debug_assert!(*output_pos + FIELD_ELEMENT_SIZE <= BYTES_PER_BLOB);
+    blob[*output_pos] = high_order_byte;
+    blob[*output_pos + 1..*output_pos + FIELD_ELEMENT_SIZE].copy_from_slice(low);
+    *output_pos += FIELD_ELEMENT_SIZE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    fn blob_with_length(length: u32) -> Blob {
+        let mut blob = Blob::ZERO;
+        blob[2..5].copy_from_slice(&length.to_be_bytes()[1..]);
+        blob
+    }
+
+    #[test]
+    fn test_decode_empty_blob() {
+        assert_eq!(decode_blob(&Blob::ZERO), Ok(Bytes::new()));
+    }
+
+    #[test]
+    fn test_decode_invalid_encoding_version() {
+        let mut blob = Blob::ZERO;
+        blob[VERSION_OFFSET] = 1;
+        assert_eq!(decode_blob(&blob), Err(BlobDecodingError::InvalidEncodingVersion(1)));
+    }
+
+    #[test]
+    fn test_decode_invalid_length() {
+        let blob = blob_with_length(0xFFFFFF);
+        assert_eq!(decode_blob(&blob), Err(BlobDecodingError::InvalidLength(0xFFFFFF)));
+    }
+
+    #[test]
+    fn test_decode_invalid_field_element() {
+        let mut blob = blob_with_length(1);
+        blob[2 * FIELD_ELEMENT_SIZE] = 0b1000_0000;
+        assert_eq!(decode_blob(&blob), Err(BlobDecodingError::InvalidFieldElement(2)));
+    }
+
+    #[test]
+    fn test_decode_trailing_data() {
+        let mut blob = blob_with_length(1);
+        blob[33] = 1;
+        assert_eq!(decode_blob(&blob), Err(BlobDecodingError::TrailingData(1)));
+    }
+
+    #[test]
+    fn test_decode_non_zero_padding() {
+        let mut blob = blob_with_length(1);
+        blob[BYTES_PER_BLOB - 1] = 1;
+        assert_eq!(decode_blob(&blob), Err(BlobDecodingError::NonZeroPadding(BYTES_PER_BLOB - 1)));
+    }
+
+    #[test]
+    fn test_encode_too_large() {
+        let data = vec![0u8; BLOB_MAX_DATA_SIZE + 1];
+        assert_eq!(
+            encode_blob(&data),
+            Err(BlobEncodingError::DataTooLarge(BLOB_MAX_DATA_SIZE + 1))
+        );
+    }
+
+    #[test]
+    fn test_encode_layout() {
+        let blob = encode_blob(&[0xFF; 28]).unwrap();
+        assert_eq!(blob[VERSION_OFFSET], BLOB_ENCODING_VERSION);
+        assert_eq!(&blob[2..5], &[0, 0, 28]);
+        assert_eq!(&blob[5..32], &[0xFF; 27]);
+        // The 28th byte is split over the high order bytes of the first two field elements.
+        assert_eq!(blob[0], 0b0011_1111);
+        assert_eq!(blob[FIELD_ELEMENT_SIZE], 0b0011_0000);
+        for field_element in blob.chunks(FIELD_ELEMENT_SIZE) {
+            assert_eq!(field_element[0] & 0b1100_0000, 0);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_max_size() {
+        let data: Vec<u8> = (0..BLOB_MAX_DATA_SIZE).map(|i| i as u8).collect();
+        let blob = encode_blob(&data).unwrap();
+        assert_eq!(decode_blob(&blob).unwrap(), Bytes::from(data));
+    }
+
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn test_blob_versioned_hash() {
+        use alloy_eips::eip4844::{Bytes48, VERSIONED_HASH_VERSION_KZG};
+
+        let hash = blob_versioned_hash(&Bytes48::ZERO);
+        assert_eq!(hash[0], VERSIONED_HASH_VERSION_KZG);
+    }
+
+    proptest! {
+        #[test]
+        fn test_roundtrip(data in proptest::collection::vec(any::<u8>(), 0..=1024)) {
+            let blob = encode_blob(&data).unwrap();
+            prop_assert_eq!(decode_blob(&blob).unwrap(), Bytes::from(data));
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Contains brotli decompression utilities.
 
-use alloc::{vec, vec::Vec};
+use alloc::{boxed::Box, vec::Vec};
 use alloc_no_stdlib::*;
 use brotli::*;
 use core::ops;
@@ -13,6 +13,27 @@ pub enum BrotliDecompressionError {
     /// The buffer exceeds the [`MAX_SPAN_BATCH_ELEMENTS`] protocol parameter.
     #[error("The batch exceeds the maximum number of elements: {max_size}", max_size = MAX_SPAN_BATCH_ELEMENTS)]
     BatchTooLarge,
+    /// Failed to allocate memory for the decompressor or its output.
+    #[error("Failed to allocate {0} elements for brotli decompression")]
+    AllocationFailed(usize),
+}
+
+/// The number of bytes in the scratch buffer of the brotli decompressor.
+pub const BROTLI_U8_BUFFER_SIZE: usize = 32 * 1024 * 1024;
+
+/// The number of `u32`s in the scratch buffer of the brotli decompressor.
+pub const BROTLI_U32_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// The number of [`HuffmanCode`]s in the scratch buffer of the brotli decompressor.
+pub const BROTLI_HC_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+/// Fallibly allocates a buffer of `len` copies of `value`, so that memory-constrained targets
+/// surface an error rather than aborting.
+fn try_alloc<T: Clone>(len: usize, value: T) -> Result<Vec<T>, BrotliDecompressionError> {
+    let mut buffer = Vec::new();
+    buffer.try_reserve_exact(len).map_err(|_| BrotliDecompressionError::AllocationFailed(len))?;
+    buffer.resize(len, value);
+    Ok(buffer)
 }
 
 /// Decompresses the given bytes data using the Brotli decompressor implemented
@@ -23,16 +44,17 @@ pub fn decompress_brotli(
 ) -> Result<Vec<u8>, BrotliDecompressionError> {
     declare_stack_allocator_struct!(MemPool, 4096, stack);
 
-    let mut u8_buffer = vec![0; 32 * 1024 * 1024].into_boxed_slice();
-    let mut u32_buffer = vec![0; 1024 * 1024].into_boxed_slice();
-    let mut hc_buffer = vec![HuffmanCode::default(); 4 * 1024 * 1024].into_boxed_slice();
+    let mut u8_buffer: Box<[u8]> = try_alloc(BROTLI_U8_BUFFER_SIZE, 0)?.into_boxed_slice();
+    let mut u32_buffer: Box<[u32]> = try_alloc(BROTLI_U32_BUFFER_SIZE, 0)?.into_boxed_slice();
+    let mut hc_buffer =
+        try_alloc(BROTLI_HC_BUFFER_SIZE, HuffmanCode::default())?.into_boxed_slice();
     let u8_allocator = MemPool::<u8>::new_allocator(&mut u8_buffer, bzero);
     let u32_allocator = MemPool::<u32>::new_allocator(&mut u32_buffer, bzero);
     let hc_allocator = MemPool::<HuffmanCode>::new_allocator(&mut hc_buffer, bzero);
     let mut brotli_state = BrotliState::new(u8_allocator, u32_allocator, hc_allocator);
 
     // Setup the decompressor inputs and outputs
-    let mut output = vec![0; data.len()];
+    let mut output = try_alloc(data.len(), 0u8)?;
     let mut available_in = data.len();
     let mut input_offset = 0;
     let mut available_out = output.len();
@@ -62,6 +84,9 @@ pub fn decompress_brotli(
                     return Err(BrotliDecompressionError::BatchTooLarge);
                 }
 
+                output
+                    .try_reserve_exact(old_len)
+                    .map_err(|_| BrotliDecompressionError::AllocationFailed(new_len))?;
                 output.resize(new_len, 0);
                 available_out += old_len;
             }
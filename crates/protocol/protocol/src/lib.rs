@@ -11,20 +11,31 @@ extern crate alloc;
 
 mod batch;
 pub use batch::{
-    Batch, BatchDecodingError, BatchEncodingError, BatchReader, BatchTransaction, BatchType,
-    BatchValidationProvider, BatchValidity, BatchWithInclusionBlock, DecompressionError,
-    MAX_SPAN_BATCH_ELEMENTS, RawSpanBatch, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE, SingleBatch,
-    SpanBatch, SpanBatchBits, SpanBatchEip1559TransactionData, SpanBatchEip2930TransactionData,
-    SpanBatchEip7702TransactionData, SpanBatchElement, SpanBatchError,
-    SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix, SpanBatchTransactionData,
-    SpanBatchTransactions, SpanDecodingError,
+    Batch, BatchAllocationError, BatchDecodingError, BatchEncodingError, BatchReader,
+    BatchTransaction, BatchType, BatchValidationProvider, BatchValidity, BatchWithInclusionBlock,
+    DecompressionError, MAX_SPAN_BATCH_ELEMENTS, RawSpanBatch, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE,
+    SingleBatch, SpanBatch, SpanBatchBits, SpanBatchEip1559TransactionData,
+    SpanBatchEip2930TransactionData, SpanBatchEip7702TransactionData, SpanBatchElement,
+    SpanBatchError, SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix,
+    SpanBatchTransactionData, SpanBatchTransactions, SpanDecodingError,
+};
+
+mod blob;
+#[cfg(feature = "kzg")]
+pub use blob::blob_versioned_hash;
+pub use blob::{
+    BLOB_ENCODING_ROUNDS, BLOB_ENCODING_VERSION, BLOB_MAX_DATA_SIZE, BlobDecodingError,
+    BlobEncodingError, decode_blob, encode_blob,
 };
 
 mod brotli;
-pub use brotli::{BrotliDecompressionError, decompress_brotli};
+pub use brotli::{
+    BROTLI_HC_BUFFER_SIZE, BROTLI_U8_BUFFER_SIZE, BROTLI_U32_BUFFER_SIZE, BrotliDecompressionError,
+    decompress_brotli,
+};
 
 mod sync;
-pub use sync::SyncStatus;
+pub use sync::{SyncModeSelection, SyncStatus, SyncStrategy};
 
 mod attributes;
 pub use attributes::OpAttributesWithParent;
@@ -35,6 +46,9 @@ pub use errors::OpBlockConversionError;
 mod block;
 pub use block::{BlockInfo, FromBlockError, L2BlockInfo};
 
+mod payload;
+pub use payload::{compute_block_hash, payload_to_block};
+
 mod frame;
 pub use frame::{
     DERIVATION_VERSION_0, FRAME_OVERHEAD, Frame, FrameDecodingError, FrameParseError, MAX_FRAME_LEN,
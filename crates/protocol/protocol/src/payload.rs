@@ -0,0 +1,170 @@
+//! Reconstruction of the blocks committed to by execution payloads.
+
+use alloy_eips::eip7685::EMPTY_REQUESTS_HASH;
+use alloy_primitives::B256;
+use kona_genesis::RollupConfig;
+use op_alloy_consensus::OpBlock;
+use op_alloy_rpc_types_engine::{OpExecutionPayload, OpPayloadError};
+
+/// Converts an [`OpExecutionPayload`] into the [`OpBlock`] it commits to, applying the header
+/// rules of the hardforks active at the payload's timestamp.
+///
+/// - The withdrawals root is the empty root for Canyon payloads, and the `L2ToL1MessagePasser`
+///   storage root carried by the payload from Isthmus onwards.
+/// - The parent beacon block root is committed to as given, and defaults to the zero hash for
+///   Ecotone payloads.
+/// - The requests hash is the empty requests hash from Isthmus onwards.
+/// - The extra data, which encodes the EIP-1559 parameters from Holocene onwards, is taken from the
+///   payload as is.
+pub fn payload_to_block(
+    payload: OpExecutionPayload,
+    parent_beacon_block_root: Option<B256>,
+    rollup_config: &RollupConfig,
+) -> Result<OpBlock, OpPayloadError> {
+    let is_isthmus = rollup_config.is_isthmus_active(payload.timestamp());
+    payload_to_block_with_requests(payload, parent_beacon_block_root, is_isthmus)
+}
+
+/// Recomputes the hash of the block committed to by an [`OpExecutionPayload`].
+///
+/// See [`payload_to_block`] for the header rules applied across hardforks.
+pub fn compute_block_hash(
+    payload: &OpExecutionPayload,
+    parent_beacon_block_root: Option<B256>,
+    rollup_config: &RollupConfig,
+) -> Result<B256, OpPayloadError> {
+    let block = payload_to_block(payload.clone(), parent_beacon_block_root, rollup_config)?;
+    Ok(block.header.hash_slow())
+}
+
+/// Converts an [`OpExecutionPayload`] into an [`OpBlock`], committing to the empty requests hash
+/// if `with_requests` is set.
+pub(crate) fn payload_to_block_with_requests(
+    payload: OpExecutionPayload,
+    parent_beacon_block_root: Option<B256>,
+    with_requests: bool,
+) -> Result<OpBlock, OpPayloadError> {
+    let parent_beacon_block_root = match payload {
+        OpExecutionPayload::V3(_) | OpExecutionPayload::V4(_) => {
+            Some(parent_beacon_block_root.unwrap_or_default())
+        }
+        _ => parent_beacon_block_root,
+    };
+
+    let mut block: OpBlock = payload.try_into_block()?;
+    block.header.parent_beacon_block_root = parent_beacon_block_root;
+    if with_requests {
+        block.header.requests_hash = Some(EMPTY_REQUESTS_HASH);
+    }
+    Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use alloy_consensus::{BlockBody, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH, Header};
+    use alloy_primitives::{Bytes, b256};
+    use alloy_rpc_types_engine::{ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3};
+    use kona_genesis::HardForkConfig;
+    use op_alloy_rpc_types_engine::OpExecutionPayloadV4;
+
+    const PARENT_BEACON_BLOCK_ROOT: B256 =
+        b256!("0101010101010101010101010101010101010101010101010101010101010101");
+
+    fn block(header: Header, with_withdrawals: bool) -> OpBlock {
+        OpBlock {
+            header: Header {
+                number: 1,
+                timestamp: 2,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(1),
+                extra_data: Bytes::from_static(&[0, 0, 0, 0, 250, 0, 0, 0, 6]),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                transactions_root: EMPTY_ROOT_HASH,
+                withdrawals_root: with_withdrawals.then_some(EMPTY_ROOT_HASH),
+                ..header
+            },
+            body: BlockBody {
+                transactions: Vec::new(),
+                ommers: Vec::new(),
+                withdrawals: with_withdrawals.then(Default::default),
+            },
+        }
+    }
+
+    fn isthmus_config() -> RollupConfig {
+        RollupConfig {
+            hardforks: HardForkConfig { isthmus_time: Some(0), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compute_block_hash_bedrock() {
+        let block = block(Header::default(), false);
+        let payload = OpExecutionPayload::V1(ExecutionPayloadV1::from_block_slow(&block));
+
+        let hash = compute_block_hash(&payload, None, &RollupConfig::default()).unwrap();
+        assert_eq!(hash, block.header.hash_slow());
+    }
+
+    #[test]
+    fn test_compute_block_hash_canyon() {
+        let block = block(Header::default(), true);
+        let payload = OpExecutionPayload::V2(ExecutionPayloadV2::from_block_slow(&block));
+
+        let hash = compute_block_hash(&payload, None, &RollupConfig::default()).unwrap();
+        assert_eq!(hash, block.header.hash_slow());
+    }
+
+    #[test]
+    fn test_compute_block_hash_ecotone() {
+        let header = Header {
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(PARENT_BEACON_BLOCK_ROOT),
+            ..Default::default()
+        };
+        let block = block(header, true);
+        let payload = OpExecutionPayload::V3(ExecutionPayloadV3::from_block_slow(&block));
+
+        let hash =
+            compute_block_hash(&payload, Some(PARENT_BEACON_BLOCK_ROOT), &RollupConfig::default())
+                .unwrap();
+        assert_eq!(hash, block.header.hash_slow());
+
+        // A missing parent beacon block root defaults to the zero hash.
+        let block = payload_to_block(payload, None, &RollupConfig::default()).unwrap();
+        assert_eq!(block.header.parent_beacon_block_root, Some(B256::ZERO));
+    }
+
+    #[test]
+    fn test_compute_block_hash_isthmus() {
+        let withdrawals_root =
+            b256!("0202020202020202020202020202020202020202020202020202020202020202");
+        let header = Header {
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(PARENT_BEACON_BLOCK_ROOT),
+            requests_hash: Some(EMPTY_REQUESTS_HASH),
+            ..Default::default()
+        };
+        let mut block = block(header, true);
+        let payload = OpExecutionPayload::V4(OpExecutionPayloadV4::from_v3_with_withdrawals_root(
+            ExecutionPayloadV3::from_block_slow(&block),
+            withdrawals_root,
+        ));
+        block.header.withdrawals_root = Some(withdrawals_root);
+
+        let hash = compute_block_hash(&payload, Some(PARENT_BEACON_BLOCK_ROOT), &isthmus_config())
+            .unwrap();
+        assert_eq!(hash, block.header.hash_slow());
+
+        // Before Isthmus, the block does not commit to a requests hash.
+        let hash =
+            compute_block_hash(&payload, Some(PARENT_BEACON_BLOCK_ROOT), &RollupConfig::default())
+                .unwrap();
+        assert_ne!(hash, block.header.hash_slow());
+    }
+}
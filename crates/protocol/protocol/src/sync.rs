@@ -1,6 +1,7 @@
 //! Common sync types
 
 use crate::{BlockInfo, L2BlockInfo};
+use alloc::string::String;
 
 /// The [`SyncStatus`][ss] of an Optimism Rollup Node.
 ///
@@ -55,6 +56,12 @@ pub struct SyncStatus {
     /// This points to the L2 block that was derived fully from finalized L1 information, thus
     /// irreversible.
     pub finalized_l2: L2BlockInfo,
+    /// The pending safe L2 block ref.
+    ///
+    /// This points to the L2 block processed from the batch, but not consolidated to the safe
+    /// block yet, as the span batch it belongs to has not been fully validated.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pending_safe_l2: L2BlockInfo,
     /// Cross unsafe L2 block ref.
     ///
     /// This is an unsafe L2 block, that has been verified to match cross-L2 dependencies.
@@ -64,4 +71,40 @@ pub struct SyncStatus {
     ///
     /// This is an L2 block derived from L1, not yet verified to have valid cross-L2 dependencies.
     pub local_safe_l2: L2BlockInfo,
+    /// The current sync strategy of the node, selected on startup or when the unsafe head got too
+    /// far ahead of the safe head.
+    ///
+    /// This is not part of op-node's sync status, and is omitted if unknown.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub sync_mode: Option<SyncModeSelection>,
+    /// The number of the lowest L2 block the node may still need to derive from after a reset.
+    /// The execution layer can safely prune the L2 chain state below it.
+    ///
+    /// This is not part of op-node's sync status, and is omitted if unknown.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub retention_horizon: Option<u64>,
+}
+
+/// The strategy a rollup node syncs the L2 chain with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, derive_more::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum SyncStrategy {
+    /// The L2 chain is derived from the L1 chain.
+    #[display("consensus-layer")]
+    ConsensusLayer,
+    /// The execution layer syncs the L2 chain from its peers, up to a checkpoint.
+    #[display("execution-layer")]
+    ExecutionLayer,
+}
+
+/// The sync strategy selected by a rollup node, and the rationale of the selection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct SyncModeSelection {
+    /// The selected strategy.
+    pub strategy: SyncStrategy,
+    /// Why the strategy was selected.
+    pub reason: String,
 }
@@ -0,0 +1,151 @@
+//! Hash map types of the registry.
+//!
+//! [`HashMap`] is hashed with the [`RegistryState`], whose type does not depend on the features of
+//! the crate. By default, it builds the seeded hashers of the
+//! [`alloy_primitives::map::HashMap`]. With the `deterministic` feature, it builds the unseeded
+//! hashers of the [`FixedState`] instead, so that the iteration order of a map only depends on its
+//! contents, as required by zkVM targets.
+
+#[cfg(not(feature = "deterministic"))]
+use alloy_primitives::map::DefaultHashBuilder;
+use core::{
+    fmt,
+    hash::{BuildHasher, Hasher},
+};
+
+/// The hash map type used throughout the registry and the crates built on it.
+pub type HashMap<K, V> = alloy_primitives::map::HashMap<K, V, RegistryState>;
+
+/// The [`BuildHasher`] of the registry's [`HashMap`].
+///
+/// Builds seeded hashers by default, and the unseeded hashers of the [`FixedState`] with the
+/// `deterministic` feature.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryState {
+    /// The seeded state the hashers are built from.
+    #[cfg(not(feature = "deterministic"))]
+    inner: DefaultHashBuilder,
+    /// The unseeded state the hashers are built from.
+    #[cfg(feature = "deterministic")]
+    inner: FixedState,
+}
+
+impl BuildHasher for RegistryState {
+    type Hasher = RegistryHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        RegistryHasher { inner: self.inner.build_hasher() }
+    }
+}
+
+/// The [`Hasher`] built by the [`RegistryState`].
+pub struct RegistryHasher {
+    /// The hasher of the seeded state.
+    #[cfg(not(feature = "deterministic"))]
+    inner: <DefaultHashBuilder as BuildHasher>::Hasher,
+    /// The hasher of the unseeded state.
+    #[cfg(feature = "deterministic")]
+    inner: FnvHasher,
+}
+
+impl fmt::Debug for RegistryHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistryHasher").finish_non_exhaustive()
+    }
+}
+
+impl Hasher for RegistryHasher {
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.write(bytes)
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.inner.write_u64(i)
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.inner.write_usize(i)
+    }
+}
+
+/// A [`BuildHasher`] of [`FnvHasher`]s, which are not seeded, producing the same hashes on every
+/// run and target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedState;
+
+impl BuildHasher for FixedState {
+    type Hasher = FnvHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        FnvHasher::default()
+    }
+}
+
+/// A 64-bit FNV-1a [`Hasher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FnvHasher(u64);
+
+impl FnvHasher {
+    /// The FNV-1a 64-bit offset basis.
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    /// The FNV-1a 64-bit prime.
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+}
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_fnv_hasher() {
+        // Reference vectors of the 64-bit FNV-1a hash.
+        let mut hasher = FixedState.build_hasher();
+        assert_eq!(hasher.finish(), 0xcbf29ce484222325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_registry_state_hashes_consistently() {
+        let state = RegistryState::default();
+        assert_eq!(state.hash_one(10u64), state.hash_one(10u64));
+
+        let map = (0..64u64).map(|i| (i, i)).collect::<HashMap<_, _>>();
+        assert!((0..64u64).all(|i| map[&i] == i));
+    }
+
+    #[test]
+    fn test_fixed_state_order() {
+        let keys = || {
+            (0..64u64)
+                .map(|i| (i, i))
+                .collect::<alloy_primitives::map::HashMap<_, _, FixedState>>()
+                .into_keys()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(), keys());
+    }
+}
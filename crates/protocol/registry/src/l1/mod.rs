@@ -6,8 +6,9 @@ use alloy_genesis::EthashConfig;
 use core::{fmt::Display, ops::Deref};
 use kona_genesis::L1ChainConfig;
 
+use crate::HashMap;
 use alloy_chains::NamedChain;
-use alloy_primitives::{Address, U256, address, map::HashMap};
+use alloy_primitives::{Address, U256, address};
 
 /// L1 chain configuration.
 /// Simple wrapper around the [`L1ChainConfig`] type from the `alloy-genesis` crate.
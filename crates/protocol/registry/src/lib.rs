@@ -9,9 +9,12 @@
 
 extern crate alloc;
 
-pub use alloy_primitives::map::HashMap;
+use alloc::vec::Vec;
 use kona_genesis::L1ChainConfig;
-pub use kona_genesis::{Chain, ChainConfig, ChainList, RollupConfig};
+pub use kona_genesis::{Chain, ChainConfig, ChainList, RollupConfig, RollupConfigError};
+
+mod hash;
+pub use hash::{FixedState, FnvHasher, HashMap, RegistryHasher, RegistryState};
 
 pub mod superchain;
 pub use superchain::Registry;
@@ -20,6 +23,11 @@ pub use superchain::Registry;
 pub mod l1;
 pub use l1::L1Config;
 
+#[cfg(feature = "local")]
+mod local;
+#[cfg(feature = "local")]
+pub use local::LocalRegistryError;
+
 #[cfg(test)]
 pub mod test_utils;
 
@@ -41,15 +49,80 @@ lazy_static::lazy_static! {
     pub static ref L1_CONFIGS: HashMap<u64, L1ChainConfig> = _INIT.l1_configs.clone();
 }
 
+/// A [Registry] installed at runtime, supplementing the statically embedded configurations.
+static LOCAL_REGISTRY: spin::Once<Registry> = spin::Once::new();
+
+/// Installs a [Registry] that supplements the embedded superchain registry at runtime.
+///
+/// Chains in the installed registry take precedence over embedded chains with the same chain ID
+/// in the lookup functions of this crate (e.g. [rollup_config_by_chain_id]). The statics
+/// ([CHAINS], [OPCHAINS], [ROLLUP_CONFIGS]) are left untouched.
+///
+/// Only the first installed registry is kept. Returns `false` if a registry was already installed.
+pub fn install_local_registry(registry: Registry) -> bool {
+    let mut installed = false;
+    LOCAL_REGISTRY.call_once(|| {
+        installed = true;
+        registry
+    });
+    installed
+}
+
+/// Returns the [Registry] installed via [install_local_registry], if any.
+pub fn local_registry() -> Option<&'static Registry> {
+    LOCAL_REGISTRY.get()
+}
+
+/// Returns the [Chain] with the given identifier, preferring the local registry.
+pub fn chain_by_ident(ident: &str) -> Option<&'static Chain> {
+    local_registry()
+        .and_then(|r| r.chain_list.get_chain_by_ident(ident))
+        .or_else(|| CHAINS.get_chain_by_ident(ident))
+}
+
+/// Returns the [ChainConfig] for the given chain ID, preferring the local registry.
+pub fn chain_config_by_chain_id(chain_id: u64) -> Option<&'static ChainConfig> {
+    local_registry().and_then(|r| r.op_chains.get(&chain_id)).or_else(|| OPCHAINS.get(&chain_id))
+}
+
+/// Returns the [ChainConfig] of every OP chain by chain ID, preferring the local registry.
+pub fn op_chains() -> HashMap<u64, &'static ChainConfig> {
+    let mut chains = OPCHAINS.iter().map(|(id, config)| (*id, config)).collect::<HashMap<_, _>>();
+    if let Some(registry) = local_registry() {
+        chains.extend(registry.op_chains.iter().map(|(id, config)| (*id, config)));
+    }
+    chains
+}
+
+/// Returns the [RollupConfig] for the given chain ID, preferring the local registry.
+pub fn rollup_config_by_chain_id(chain_id: u64) -> Option<&'static RollupConfig> {
+    local_registry()
+        .and_then(|r| r.rollup_configs.get(&chain_id))
+        .or_else(|| ROLLUP_CONFIGS.get(&chain_id))
+}
+
 /// Returns a [RollupConfig] by its identifier.
-pub fn scr_rollup_config_by_ident(ident: &str) -> Option<&RollupConfig> {
-    let chain_id = CHAINS.get_chain_by_ident(ident)?.chain_id;
-    ROLLUP_CONFIGS.get(&chain_id)
+pub fn scr_rollup_config_by_ident(ident: &str) -> Option<&'static RollupConfig> {
+    let chain_id = chain_by_ident(ident)?.chain_id;
+    rollup_config_by_chain_id(chain_id)
 }
 
 /// Returns a [RollupConfig] by its identifier.
-pub fn scr_rollup_config_by_alloy_ident(chain: &alloy_chains::Chain) -> Option<&RollupConfig> {
-    ROLLUP_CONFIGS.get(&chain.id())
+pub fn scr_rollup_config_by_alloy_ident(
+    chain: &alloy_chains::Chain,
+) -> Option<&'static RollupConfig> {
+    rollup_config_by_chain_id(chain.id())
+}
+
+/// Validates the [RollupConfig] of every chain, preferring the local registry.
+///
+/// Returns the chain ID and error of each invalid rollup config, sorted by chain ID.
+pub fn validate_rollup_configs() -> Vec<(u64, RollupConfigError)> {
+    let mut registry = _INIT.clone();
+    if let Some(local) = local_registry() {
+        registry.extend(local.clone());
+    }
+    registry.validate()
 }
 
 #[cfg(test)]
@@ -81,6 +154,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_op_chains() {
+        let chains = op_chains();
+        assert_eq!(chains.len(), OPCHAINS.len());
+        assert_eq!(chains[&10], chain_config_by_chain_id(10).unwrap());
+    }
+
     #[test]
     fn test_chain_by_ident() {
         const ALLOY_BASE: AlloyChain = AlloyChain::base_mainnet();
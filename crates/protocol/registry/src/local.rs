@@ -0,0 +1,293 @@
+//! Loads chain configurations from a local directory in the superchain-registry format.
+//!
+//! This allows newly launched chains to be used before a release of this crate picks them up from
+//! the upstream [superchain-registry][scr].
+//!
+//! [scr]: https://github.com/ethereum-optimism/superchain-registry
+
+use crate::Registry;
+use kona_genesis::{
+    Chain, ChainConfig, ChainList, Superchain, SuperchainConfig, SuperchainParent, Superchains,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of the superchain configuration file within a superchain directory.
+const SUPERCHAIN_CONFIG_FILE: &str = "superchain.toml";
+
+/// The name of the optional chain list file at the root of the registry directory.
+const CHAIN_LIST_FILE: &str = "chainList.json";
+
+/// An error that can occur while loading a local registry.
+#[derive(Debug, thiserror::Error)]
+pub enum LocalRegistryError {
+    /// The registry directory does not exist.
+    #[error("Registry directory {0} does not exist")]
+    MissingDirectory(PathBuf),
+    /// A file could not be read.
+    #[error("Failed to read {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    /// A TOML configuration file could not be parsed.
+    #[error("Failed to parse {0}: {1}")]
+    Toml(PathBuf, toml::de::Error),
+    /// A JSON file could not be parsed.
+    #[error("Failed to parse {0}: {1}")]
+    Json(PathBuf, serde_json::Error),
+    /// A superchain directory is missing its `superchain.toml`.
+    #[error("Superchain directory {0} is missing a superchain.toml")]
+    MissingSuperchainConfig(PathBuf),
+    /// A superchain configuration is missing its protocol versions address.
+    #[error("Superchain {0} is missing a protocol versions address")]
+    MissingProtocolVersions(String),
+    /// The same chain ID is defined more than once.
+    #[error("Chain ID {0} is defined more than once")]
+    DuplicateChainId(u64),
+}
+
+impl Registry {
+    /// Loads a [`Registry`] from a local directory in the superchain-registry format.
+    ///
+    /// The directory may either be the root of a superchain-registry checkout (containing
+    /// `superchain/configs`), or the `configs` directory itself. Each subdirectory is a
+    /// superchain, holding a `superchain.toml` and one TOML file per chain.
+    ///
+    /// If a `chainList.json` file is present at the root of the directory, it is used as the
+    /// chain list. Otherwise, chain list entries are synthesized from the chain configurations,
+    /// identified as `<superchain>/<file name>`.
+    pub fn from_directory(path: impl AsRef<Path>) -> Result<Self, LocalRegistryError> {
+        let root = path.as_ref();
+        if !root.is_dir() {
+            return Err(LocalRegistryError::MissingDirectory(root.to_path_buf()));
+        }
+
+        let configs_dir = root.join("superchain").join("configs");
+        let configs_dir = if configs_dir.is_dir() { configs_dir } else { root.to_path_buf() };
+
+        let mut superchains = Superchains::default();
+        let mut chain_list = ChainList::default();
+        for entry in read_dir_sorted(&configs_dir)? {
+            if !entry.is_dir() {
+                continue;
+            }
+            let (superchain, chains) = read_superchain(&entry)?;
+            chain_list.chains.extend(chains);
+            superchains.superchains.push(superchain);
+        }
+
+        let chain_list_path = root.join(CHAIN_LIST_FILE);
+        if chain_list_path.is_file() {
+            let contents = fs::read_to_string(&chain_list_path)
+                .map_err(|e| LocalRegistryError::Io(chain_list_path.clone(), e))?;
+            chain_list = serde_json::from_str(&contents)
+                .map_err(|e| LocalRegistryError::Json(chain_list_path, e))?;
+        }
+
+        let mut chain_ids = Vec::new();
+        for chain in superchains.superchains.iter().flat_map(|s| s.chains.iter()) {
+            if chain_ids.contains(&chain.chain_id) {
+                return Err(LocalRegistryError::DuplicateChainId(chain.chain_id));
+            }
+            chain_ids.push(chain.chain_id);
+        }
+
+        let mut registry = Self::from_superchains(chain_list, superchains);
+        // L1 configurations are not part of the superchain-registry format.
+        registry.l1_configs.clear();
+        Ok(registry)
+    }
+}
+
+/// Reads a superchain directory, returning the [`Superchain`] and synthesized [`Chain`] entries
+/// for each of its chains.
+fn read_superchain(dir: &Path) -> Result<(Superchain, Vec<Chain>), LocalRegistryError> {
+    let name = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    let config_path = dir.join(SUPERCHAIN_CONFIG_FILE);
+    if !config_path.is_file() {
+        return Err(LocalRegistryError::MissingSuperchainConfig(dir.to_path_buf()));
+    }
+    let config: SuperchainConfig = read_toml(&config_path)?;
+    if config.protocol_versions_addr.is_none() {
+        return Err(LocalRegistryError::MissingProtocolVersions(name));
+    }
+
+    let mut chains = vec![];
+    let mut chain_list = vec![];
+    for path in read_dir_sorted(dir)? {
+        let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+        if !path.is_file() || !is_toml || path.ends_with(SUPERCHAIN_CONFIG_FILE) {
+            continue;
+        }
+
+        let chain: ChainConfig = read_toml(&path)?;
+        let short_name =
+            path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        chain_list.push(Chain {
+            name: chain.name.clone(),
+            identifier: format!("{name}/{short_name}"),
+            chain_id: chain.chain_id,
+            rpc: vec![chain.public_rpc.clone()],
+            explorers: vec![chain.explorer.clone()],
+            superchain_level: chain.superchain_level as u64,
+            governed_by_optimism: Some(chain.governed_by_optimism),
+            data_availability_type: chain.data_availability_type.clone(),
+            parent: SuperchainParent { r#type: String::from("L2"), chain: name.clone() },
+            gas_paying_token: None,
+            fault_proofs: None,
+        });
+        chains.push(chain);
+    }
+
+    Ok((Superchain { name, config, chains }, chain_list))
+}
+
+/// Returns the entries of a directory, sorted by path for deterministic loading.
+fn read_dir_sorted(dir: &Path) -> Result<Vec<PathBuf>, LocalRegistryError> {
+    let entries = fs::read_dir(dir).map_err(|e| LocalRegistryError::Io(dir.to_path_buf(), e))?;
+    let mut paths = entries
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| LocalRegistryError::Io(dir.to_path_buf(), e))?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Reads and parses a TOML file.
+fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, LocalRegistryError> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| LocalRegistryError::Io(path.to_path_buf(), e))?;
+    toml::from_str(&contents).map_err(|e| LocalRegistryError::Toml(path.to_path_buf(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPERCHAIN_TOML: &str = r#"
+name = "Localnet"
+protocol_versions_addr = "0x0000000000000000000000000000000000000001"
+superchain_config_addr = "0x0000000000000000000000000000000000000002"
+
+[hardforks]
+canyon_time = 0
+delta_time = 0
+ecotone_time = 0
+fjord_time = 0
+granite_time = 0
+holocene_time = 0
+
+[l1]
+chain_id = 11155111
+public_rpc = "https://ethereum-sepolia-rpc.publicnode.com"
+explorer = "https://sepolia.etherscan.io"
+"#;
+
+    const BOB_CHAIN_ID: u64 = 60808;
+
+    const BOB_TOML: &str = r#"
+name = "BOB Local"
+public_rpc = "http://localhost:8545"
+sequencer_rpc = "http://localhost:8545"
+explorer = "http://localhost:4000"
+superchain_level = 0
+governed_by_optimism = false
+data_availability_type = "eth-da"
+chain_id = 60808
+batch_inbox_addr = "0xff00000000000000000000000000000000060808"
+block_time = 1
+seq_window_size = 7200
+max_sequencer_drift = 600
+
+[hardforks]
+canyon_time = 0
+delta_time = 0
+ecotone_time = 0
+fjord_time = 0
+granite_time = 0
+holocene_time = 1736445601
+
+[optimism]
+eip1559_elasticity = 6
+eip1559_denominator = 50
+eip1559_denominator_canyon = 250
+
+[genesis]
+l2_time = 1712872800
+
+[genesis.l1]
+hash = "0x2a7a8cc7cd6e1b2bfea5d06fa0bcd7bcd2f3cd86e0c38be2b06e00f8f8e1d6a2"
+number = 19634432
+
+[genesis.l2]
+hash = "0x48d7c9b9a0c4e0a8a25e7d27e2bf5d8c4b07cfd1d6c1e1b0e3bd1e7d7b0e3c1a"
+number = 0
+
+[genesis.system_config]
+batcherAddress = "0x6b0fb79f9fc4ca4c2cc5ba7ef9c7a2cd0c4b9a5e"
+overhead = "0x0000000000000000000000000000000000000000000000000000000000000000"
+scalar = "0x010000000000000000000000000000000000000000000000000c5fc500000558"
+gasLimit = 30000000
+"#;
+
+    #[test]
+    fn test_missing_directory() {
+        let err = Registry::from_directory("/does/not/exist").unwrap_err();
+        assert!(matches!(err, LocalRegistryError::MissingDirectory(_)));
+    }
+
+    #[test]
+    fn test_missing_superchain_config() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("localnet")).unwrap();
+
+        let err = Registry::from_directory(dir.path()).unwrap_err();
+        assert!(matches!(err, LocalRegistryError::MissingSuperchainConfig(_)));
+    }
+
+    #[test]
+    fn test_empty_superchain() {
+        let dir = tempfile::tempdir().unwrap();
+        let superchain_dir = dir.path().join("localnet");
+        fs::create_dir_all(&superchain_dir).unwrap();
+        fs::write(superchain_dir.join(SUPERCHAIN_CONFIG_FILE), SUPERCHAIN_TOML).unwrap();
+
+        let registry = Registry::from_directory(dir.path()).unwrap();
+        assert!(registry.chain_list.chains.is_empty());
+        assert!(registry.rollup_configs.is_empty());
+        assert!(registry.l1_configs.is_empty());
+    }
+
+    #[test]
+    fn test_local_chain_takes_precedence() {
+        // Overrides an embedded chain that no other test looks up, as the installed registry is
+        // global to the test binary.
+        let builtin = crate::ROLLUP_CONFIGS.get(&BOB_CHAIN_ID).unwrap();
+        assert_ne!(builtin.block_time, 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let superchain_dir = dir.path().join("superchain").join("configs").join("mainnet");
+        fs::create_dir_all(&superchain_dir).unwrap();
+        fs::write(superchain_dir.join(SUPERCHAIN_CONFIG_FILE), SUPERCHAIN_TOML).unwrap();
+        fs::write(superchain_dir.join("bob.toml"), BOB_TOML).unwrap();
+
+        let registry = Registry::from_directory(dir.path()).unwrap();
+        let chain = registry.chain_list.get_chain_by_ident("mainnet/bob").unwrap();
+        assert_eq!(chain.chain_id, BOB_CHAIN_ID);
+        assert_eq!(chain.name, "BOB Local");
+        assert!(crate::install_local_registry(registry));
+
+        let chain = crate::chain_by_ident("mainnet/bob").unwrap();
+        assert_eq!(chain.name, "BOB Local");
+        assert_eq!(chain.rpc, ["http://localhost:8545"]);
+
+        let rollup_config = crate::rollup_config_by_chain_id(BOB_CHAIN_ID).unwrap();
+        assert_eq!(rollup_config.block_time, 1);
+        assert_eq!(rollup_config.seq_window_size, 7200);
+        assert_eq!(rollup_config.genesis.l2_time, 1712872800);
+        assert_eq!(rollup_config.hardforks.holocene_time, Some(1736445601));
+        assert_eq!(rollup_config.l1_chain_id, 11155111);
+        assert_eq!(crate::scr_rollup_config_by_ident("mainnet/bob"), Some(rollup_config));
+    }
+}
@@ -3,8 +3,9 @@
 use crate::L1Config;
 
 use super::ChainList;
-use alloy_primitives::map::HashMap;
-use kona_genesis::{ChainConfig, L1ChainConfig, RollupConfig, Superchains};
+use crate::HashMap;
+use alloc::vec::Vec;
+use kona_genesis::{ChainConfig, L1ChainConfig, RollupConfig, RollupConfigError, Superchains};
 
 /// The registry containing all the superchain configurations.
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -36,6 +37,12 @@ impl Registry {
     pub fn from_chain_list() -> Self {
         let chain_list = Self::read_chain_list();
         let superchains = Self::read_superchain_configs();
+        Self::from_superchains(chain_list, superchains)
+    }
+
+    /// Builds a [`Registry`] from the given [`ChainList`] and [`Superchains`], deriving the rollup
+    /// configuration of every chain.
+    pub fn from_superchains(chain_list: ChainList, superchains: Superchains) -> Self {
         let mut op_chains = HashMap::default();
         let mut rollup_configs = HashMap::default();
 
@@ -58,6 +65,32 @@ impl Registry {
 
         Self { chain_list, op_chains, rollup_configs, l1_configs: L1Config::build_l1_configs() }
     }
+
+    /// Extends the registry with the chains of `other`.
+    ///
+    /// Chains in `other` take precedence over chains with the same chain ID in `self`.
+    pub fn extend(&mut self, other: Self) {
+        for chain in other.chain_list.chains {
+            self.chain_list.chains.retain(|c| c.chain_id != chain.chain_id);
+            self.chain_list.chains.push(chain);
+        }
+        self.op_chains.extend(other.op_chains);
+        self.rollup_configs.extend(other.rollup_configs);
+        self.l1_configs.extend(other.l1_configs);
+    }
+
+    /// Validates the rollup config of every chain in the registry.
+    ///
+    /// Returns the chain ID and error of each invalid rollup config, sorted by chain ID.
+    pub fn validate(&self) -> Vec<(u64, RollupConfigError)> {
+        let mut errors: Vec<_> = self
+            .rollup_configs
+            .iter()
+            .filter_map(|(chain_id, config)| config.validate().err().map(|e| (*chain_id, e)))
+            .collect();
+        errors.sort_unstable_by_key(|(chain_id, _)| *chain_id);
+        errors
+    }
 }
 
 #[cfg(test)]
@@ -118,6 +151,39 @@ mod tests {
         assert_eq!(*superchains.op_chains.get(&8453).unwrap(), base_config);
     }
 
+    #[test]
+    fn test_extend_overrides_existing_chains() {
+        let mut registry = Registry::from_chain_list();
+        let len = registry.chain_list.len();
+
+        let mut rollup = crate::test_utils::OP_MAINNET_CONFIG;
+        rollup.block_time = 1;
+        let mut chain = registry.chain_list.get_chain_by_id(10).unwrap().clone();
+        chain.name = String::from("Local OP Mainnet");
+
+        let mut other = Registry::default();
+        other.chain_list.chains.push(chain);
+        other.rollup_configs.insert(10, rollup.clone());
+
+        registry.extend(other);
+        assert_eq!(registry.chain_list.len(), len);
+        assert_eq!(registry.chain_list.get_chain_by_id(10).unwrap().name, "Local OP Mainnet");
+        assert_eq!(*registry.rollup_configs.get(&10).unwrap(), rollup);
+        assert_eq!(
+            *registry.rollup_configs.get(&8453).unwrap(),
+            crate::test_utils::BASE_MAINNET_CONFIG
+        );
+    }
+
+    #[test]
+    fn test_validate_registry() {
+        let mut registry = Registry::from_chain_list();
+        assert_eq!(registry.validate(), Vec::new());
+
+        registry.rollup_configs.get_mut(&10).unwrap().block_time = 0;
+        assert_eq!(registry.validate(), [(10, RollupConfigError::ZeroBlockTime)]);
+    }
+
     #[test]
     fn test_read_rollup_configs() {
         let superchains = Registry::from_chain_list();
@@ -127,6 +193,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_registry_hardfork_activation_order() {
+        let superchains = Registry::from_chain_list();
+        for (chain_id, config) in &superchains.rollup_configs {
+            assert_eq!(config.hardforks.check_activation_order(), Ok(()), "chain {chain_id}");
+        }
+    }
+
     #[test]
     fn test_isthmus_timestamps() {
         let superchains = Registry::from_chain_list();
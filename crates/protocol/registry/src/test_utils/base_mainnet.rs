@@ -1,5 +1,6 @@
 //! Base Mainnet Rollup Config.
 
+use alloc::vec::Vec;
 use alloy_chains::Chain;
 use alloy_eips::BlockNumHash;
 use alloy_op_hardforks::{
@@ -61,6 +62,7 @@ pub const BASE_MAINNET_CONFIG: RollupConfig = RollupConfig {
         interop_time: None,
     },
     batch_inbox_address: address!("ff00000000000000000000000000000000008453"),
+    batch_inbox_schedule: Vec::new(),
     deposit_contract_address: address!("49048044d57e1c92a77f79988d21fa8faf74e97e"),
     l1_system_config_address: address!("73a79fab69143498ed3712e519a88a918e1f4072"),
     protocol_versions_address: address!("8062abc286f5e7d9428a0ccb9abd71e50d93b935"),
@@ -70,4 +72,5 @@ pub const BASE_MAINNET_CONFIG: RollupConfig = RollupConfig {
     interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
     alt_da_config: None,
     chain_op_config: BASE_MAINNET_BASE_FEE_CONFIG,
+    custom_gas_token: None,
 };
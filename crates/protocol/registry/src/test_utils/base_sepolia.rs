@@ -1,5 +1,6 @@
 //! Base Sepolia Rollup Config.
 
+use alloc::vec::Vec;
 use alloy_chains::Chain;
 use alloy_eips::BlockNumHash;
 use alloy_op_hardforks::{
@@ -48,6 +49,7 @@ pub const BASE_SEPOLIA_CONFIG: RollupConfig = RollupConfig {
     l1_chain_id: 11155111,
     l2_chain_id: Chain::base_sepolia(),
     chain_op_config: BASE_SEPOLIA_BASE_FEE_CONFIG,
+    custom_gas_token: None,
     alt_da_config: None,
     hardforks: HardForkConfig {
         regolith_time: None,
@@ -63,6 +65,7 @@ pub const BASE_SEPOLIA_CONFIG: RollupConfig = RollupConfig {
         interop_time: None,
     },
     batch_inbox_address: address!("ff00000000000000000000000000000000084532"),
+    batch_inbox_schedule: Vec::new(),
     deposit_contract_address: address!("49f53e41452c74589e85ca1677426ba426459e85"),
     l1_system_config_address: address!("f272670eb55e895584501d564afeb048bed26194"),
     protocol_versions_address: address!("79add5713b383daa0a138d3c4780c7a1804a8090"),
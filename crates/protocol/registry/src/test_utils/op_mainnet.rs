@@ -1,5 +1,6 @@
 //! OP Mainnet Rollup Config.
 
+use alloc::vec::Vec;
 use alloy_chains::Chain;
 use alloy_eips::BlockNumHash;
 use alloy_op_hardforks::{
@@ -48,6 +49,7 @@ pub const OP_MAINNET_CONFIG: RollupConfig = RollupConfig {
     l1_chain_id: 1_u64,
     l2_chain_id: Chain::optimism_mainnet(),
     chain_op_config: OP_MAINNET_BASE_FEE_CONFIG,
+    custom_gas_token: None,
     alt_da_config: None,
     hardforks: HardForkConfig {
         regolith_time: None,
@@ -63,6 +65,7 @@ pub const OP_MAINNET_CONFIG: RollupConfig = RollupConfig {
         interop_time: None,
     },
     batch_inbox_address: address!("ff00000000000000000000000000000000000010"),
+    batch_inbox_schedule: Vec::new(),
     deposit_contract_address: address!("beb5fc579115071764c7423a4f12edde41f106ed"),
     l1_system_config_address: address!("229047fed2591dbec1ef1118d64f7af3db9eb290"),
     protocol_versions_address: address!("8062abc286f5e7d9428a0ccb9abd71e50d93b935"),
@@ -1,5 +1,6 @@
 //! OP Sepolia Rollup Config.
 
+use alloc::vec::Vec;
 use alloy_chains::Chain;
 use alloy_eips::BlockNumHash;
 use alloy_op_hardforks::{
@@ -48,6 +49,7 @@ pub const OP_SEPOLIA_CONFIG: RollupConfig = RollupConfig {
     l1_chain_id: 11155111,
     l2_chain_id: Chain::optimism_sepolia(),
     chain_op_config: OP_SEPOLIA_BASE_FEE_CONFIG,
+    custom_gas_token: None,
     alt_da_config: None,
     hardforks: HardForkConfig {
         regolith_time: None,
@@ -63,6 +65,7 @@ pub const OP_SEPOLIA_CONFIG: RollupConfig = RollupConfig {
         interop_time: None,
     },
     batch_inbox_address: address!("ff00000000000000000000000000000011155420"),
+    batch_inbox_schedule: Vec::new(),
     deposit_contract_address: address!("16fc5058f25648194471939df75cf27a2fdc48bc"),
     l1_system_config_address: address!("034edd2a225f7f429a63e0f1d2084b9e0a93b538"),
     protocol_versions_address: address!("79add5713b383daa0a138d3c4780c7a1804a8090"),
@@ -6,7 +6,7 @@ use crate::blobs::BoxedBlobWithIndex;
 use alloy_eips::eip4844::IndexedBlobHash;
 use alloy_rpc_types_beacon::sidecar::{BeaconBlobBundle, GetBlobsResponse};
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use std::{boxed::Box, format, string::String, vec::Vec};
 
 /// The config spec engine api method.
@@ -121,6 +121,44 @@ impl OnlineBeaconClient {
         self
     }
 
+    /// Returns whether the beacon node serves any blobs for the given slot.
+    ///
+    /// Beacon nodes prune blobs past the data availability window, so this can be used to check
+    /// that the blobs of a slot are still retrievable before they are needed. A slot without
+    /// blobs is reported as unavailable.
+    pub async fn blobs_available(&self, slot: u64) -> Result<bool, reqwest::Error> {
+        kona_macros::inc!(gauge, Metrics::BEACON_CLIENT_REQUESTS, "method" => "blobs_available");
+
+        let result = async {
+            let response = self
+                .inner
+                .get(format!("{}/{}/{}", self.base, BLOBS_METHOD_PREFIX, slot))
+                .send()
+                .await?;
+            if response.status().is_success() {
+                return Ok(!response.json::<GetBlobsResponse>().await?.data.is_empty());
+            }
+
+            // As when fetching blobs, fall back to the deprecated sidecars endpoint.
+            let response = self
+                .inner
+                .get(format!("{}/{}/{}", self.base, SIDECARS_METHOD_PREFIX_DEPRECATED, slot))
+                .send()
+                .await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(false);
+            }
+            Ok(!response.error_for_status()?.json::<BeaconBlobBundle>().await?.data.is_empty())
+        }
+        .await;
+
+        if result.is_err() {
+            kona_macros::inc!(gauge, Metrics::BEACON_CLIENT_ERRORS, "method" => "blobs_available");
+        }
+
+        result
+    }
+
     async fn filtered_beacon_blobs(
         &self,
         slot: u64,
@@ -0,0 +1,568 @@
+//! Contains the [CelestiaProvider], an [AltDaProvider] resolving Celestia commitments with a
+//! Celestia light node.
+//!
+//! Batcher transactions of chains posting their data to Celestia carry a generic alt-DA
+//! commitment with the [`CELESTIA_DA_LAYER`] byte, followed by the height of the Celestia block
+//! holding the blob and the share commitment of the blob. The blob is fetched from the light node
+//! along with its inclusion proofs, and verified locally before its data is handed to the
+//! derivation pipeline: the share commitment is recomputed from the data of the blob, and its
+//! shares are proven against the row roots of the data availability header of the block.
+
+use alloy_primitives::{B256, Bytes};
+use alloy_rpc_client::ReqwestClient;
+use alloy_transport::TransportError;
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use kona_derive::{AltDaProvider, PipelineError, PipelineErrorKind, PipelineResult};
+use kona_protocol::BlockInfo;
+use reqwest::{
+    Url,
+    header::{AUTHORIZATION, HeaderMap, HeaderValue},
+};
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use tracing::{debug, warn};
+
+/// The byte identifying Celestia in generic alt-DA commitments.
+pub const CELESTIA_DA_LAYER: u8 = 0x0c;
+
+/// The size of a Celestia namespace, in bytes.
+pub const NAMESPACE_SIZE: usize = 29;
+
+/// The size of a Celestia share, in bytes.
+const SHARE_SIZE: usize = 512;
+
+/// The size of the sequence length prefixed to the data of the first share of a blob.
+const SEQUENCE_LEN_SIZE: usize = 4;
+
+/// The maximum number of subtree roots a blob commitment is built from.
+const SUBTREE_ROOT_THRESHOLD: usize = 64;
+
+/// The size of a namespaced hash: the minimum and maximum namespaces of the subtree, followed by
+/// its SHA-256 digest.
+const NMT_HASH_SIZE: usize = 2 * NAMESPACE_SIZE + 32;
+
+/// The namespace of the parity shares, which is ignored when computing the maximum namespace of
+/// the row roots.
+const PARITY_NAMESPACE: [u8; NAMESPACE_SIZE] = [0xff; NAMESPACE_SIZE];
+
+/// A Celestia namespace.
+type Namespace = [u8; NAMESPACE_SIZE];
+
+/// A Celestia share.
+type Share = [u8; SHARE_SIZE];
+
+/// A node of a namespaced Merkle tree.
+type NmtHash = [u8; NMT_HASH_SIZE];
+
+/// An error resolving a Celestia commitment.
+#[derive(Debug, thiserror::Error)]
+pub enum CelestiaError {
+    /// The request to the light node failed.
+    #[error("Celestia light node request failed: {0}")]
+    Rpc(#[from] TransportError),
+    /// The HTTP client of the light node could not be built.
+    #[error("Failed to build the Celestia light node client: {0}")]
+    Client(String),
+    /// The blob is not in the namespace of the chain.
+    #[error("Blob namespace mismatch")]
+    NamespaceMismatch,
+    /// The blob uses a share version that is not supported.
+    #[error("Unsupported share version {0}")]
+    UnsupportedShareVersion(u8),
+    /// The share commitment of the blob does not match the commitment of the batcher transaction.
+    #[error("Blob commitment mismatch: expected {expected}, got {actual}")]
+    CommitmentMismatch {
+        /// The commitment of the batcher transaction.
+        expected: B256,
+        /// The commitment recomputed from the blob.
+        actual: B256,
+    },
+    /// The shares of the blob are not proven to be included in the block.
+    #[error("Invalid blob inclusion proof: {0}")]
+    InvalidProof(&'static str),
+}
+
+impl From<CelestiaError> for PipelineErrorKind {
+    fn from(e: CelestiaError) -> Self {
+        // The light node may serve the blob once it synced the block, or after a restart.
+        PipelineError::Provider(format!("Celestia: {e}")).temp()
+    }
+}
+
+/// The configuration of a [`CelestiaProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CelestiaConfig {
+    /// The URL of the RPC of the Celestia light node.
+    pub rpc: Url,
+    /// The authentication token of the light node RPC, if it requires one.
+    pub auth_token: Option<String>,
+    /// The namespace the batcher posts the blobs of the chain to.
+    pub namespace: [u8; NAMESPACE_SIZE],
+}
+
+/// A reference to a blob posted to Celestia, decoded from a generic alt-DA commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CelestiaCommitment {
+    /// The height of the Celestia block holding the blob.
+    pub height: u64,
+    /// The share commitment of the blob.
+    pub commitment: B256,
+}
+
+impl CelestiaCommitment {
+    /// Decodes an encoded generic commitment, starting with its type byte and the
+    /// [`CELESTIA_DA_LAYER`] byte, followed by the little-endian height and the share commitment.
+    pub fn decode(commitment: &[u8]) -> Option<Self> {
+        let [_, CELESTIA_DA_LAYER, rest @ ..] = commitment else { return None };
+        let (height, commitment) = rest.split_first_chunk::<8>()?;
+        Some(Self {
+            height: u64::from_le_bytes(*height),
+            commitment: B256::try_from(commitment).ok()?,
+        })
+    }
+}
+
+/// A blob served by the light node.
+#[derive(Debug, Clone, Deserialize)]
+struct CelestiaBlob {
+    /// The namespace of the blob.
+    #[serde(deserialize_with = "base64_bytes")]
+    namespace: Vec<u8>,
+    /// The data of the blob.
+    #[serde(deserialize_with = "base64_bytes")]
+    data: Vec<u8>,
+    /// The share version of the blob.
+    share_version: u8,
+    /// The index of the first share of the blob in the original data square.
+    index: i64,
+}
+
+/// A namespaced Merkle tree proof of a range of shares of a row.
+#[derive(Debug, Clone, Deserialize)]
+struct NmtProof {
+    /// The index of the first proven share in the row.
+    start: usize,
+    /// The index after the last proven share in the row.
+    end: usize,
+    /// The roots of the subtrees outside of the proven range, from left to right.
+    #[serde(default, deserialize_with = "base64_list")]
+    nodes: Vec<Vec<u8>>,
+}
+
+/// The data availability header of a Celestia block.
+#[derive(Debug, Clone, Deserialize)]
+struct DataAvailabilityHeader {
+    /// The roots of the rows of the extended data square.
+    #[serde(deserialize_with = "base64_list")]
+    row_roots: Vec<Vec<u8>>,
+}
+
+/// The part of the extended header of a Celestia block the provider reads.
+#[derive(Debug, Clone, Deserialize)]
+struct ExtendedHeader {
+    /// The data availability header of the block.
+    dah: DataAvailabilityHeader,
+}
+
+/// An [`AltDaProvider`] resolving Celestia commitments with a Celestia light node, to be
+/// registered for the generic commitments of the [`CELESTIA_DA_LAYER`].
+///
+/// Commitments that cannot be decoded resolve to empty data, which the frame queue drops.
+#[derive(Debug, Clone)]
+pub struct CelestiaProvider {
+    /// The RPC client of the light node.
+    client: ReqwestClient,
+    /// The namespace the batcher posts the blobs of the chain to.
+    namespace: Namespace,
+}
+
+impl CelestiaProvider {
+    /// Creates a new [`CelestiaProvider`] from the given [`CelestiaConfig`].
+    pub fn new(config: CelestiaConfig) -> Result<Self, CelestiaError> {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = &config.auth_token {
+            let value = HeaderValue::from_str(&format!("Bearer {token}"))
+                .map_err(|e| CelestiaError::Client(e.to_string()))?;
+            headers.insert(AUTHORIZATION, value);
+        }
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|e| CelestiaError::Client(e.to_string()))?;
+        let client = ReqwestClient::new(Http::with_client(http, config.rpc), false);
+        Ok(Self { client, namespace: config.namespace })
+    }
+
+    /// Fetches the blob with the given commitment, and verifies it against the commitment and
+    /// the data availability header of its block.
+    async fn fetch_blob(&self, commitment: CelestiaCommitment) -> Result<Bytes, CelestiaError> {
+        let CelestiaCommitment { height, commitment } = commitment;
+        let params = (height, STANDARD.encode(self.namespace), STANDARD.encode(commitment));
+        let blob: CelestiaBlob = self.client.request("blob.Get", params.clone()).await?;
+        if blob.namespace != self.namespace {
+            return Err(CelestiaError::NamespaceMismatch);
+        }
+        if blob.share_version != 0 {
+            return Err(CelestiaError::UnsupportedShareVersion(blob.share_version));
+        }
+
+        let shares = blob_shares(&self.namespace, &blob.data);
+        let actual = blob_commitment(&self.namespace, &shares);
+        if actual != commitment {
+            return Err(CelestiaError::CommitmentMismatch { expected: commitment, actual });
+        }
+
+        let proofs: Vec<NmtProof> = self.client.request("blob.GetProof", params).await?;
+        let header: ExtendedHeader = self.client.request("header.GetByHeight", (height,)).await?;
+        verify_inclusion(&self.namespace, &shares, blob.index, &proofs, &header.dah.row_roots)?;
+        Ok(blob.data.into())
+    }
+}
+
+#[async_trait]
+impl AltDaProvider for CelestiaProvider {
+    async fn get_input(&mut self, commitment: &Bytes, _: &BlockInfo) -> PipelineResult<Bytes> {
+        let Some(commitment) = CelestiaCommitment::decode(commitment) else {
+            warn!(target: "celestia", %commitment, "Invalid Celestia commitment, skipping");
+            return Ok(Bytes::new());
+        };
+        debug!(target: "celestia", height = commitment.height, "Fetching Celestia blob");
+        Ok(self.fetch_blob(commitment).await?)
+    }
+}
+
+/// Splits the data of a blob into shares of share version 0.
+fn blob_shares(namespace: &Namespace, data: &[u8]) -> Vec<Share> {
+    let mut shares = Vec::new();
+    let mut rest = data;
+    loop {
+        let first = shares.is_empty();
+        let mut share = [0u8; SHARE_SIZE];
+        share[..NAMESPACE_SIZE].copy_from_slice(namespace);
+        // The share version, followed by the sequence start flag.
+        share[NAMESPACE_SIZE] = u8::from(first);
+        let mut offset = NAMESPACE_SIZE + 1;
+        if first {
+            share[offset..offset + SEQUENCE_LEN_SIZE]
+                .copy_from_slice(&(data.len() as u32).to_be_bytes());
+            offset += SEQUENCE_LEN_SIZE;
+        }
+        let len = rest.len().min(SHARE_SIZE - offset);
+        share[offset..offset + len].copy_from_slice(&rest[..len]);
+        rest = &rest[len..];
+        shares.push(share);
+        if rest.is_empty() {
+            return shares;
+        }
+    }
+}
+
+/// Returns the share commitment of a blob: the Merkle root of the namespaced Merkle tree roots of
+/// its subtrees.
+fn blob_commitment(namespace: &Namespace, shares: &[Share]) -> B256 {
+    let leaves = shares.iter().map(|share| nmt_leaf(namespace, share)).collect::<Vec<_>>();
+    let mut start = 0;
+    let subtree_roots = mountain_range_sizes(shares.len(), subtree_width(shares.len()))
+        .into_iter()
+        .map(|size| {
+            let root = nmt_root(&leaves[start..start + size]);
+            start += size;
+            root
+        })
+        .collect::<Vec<_>>();
+    merkle_root(&subtree_roots)
+}
+
+/// Verifies that the shares of a blob starting at the given index of the original data square are
+/// included in the rows of the extended data square with the given roots.
+fn verify_inclusion(
+    namespace: &Namespace,
+    shares: &[Share],
+    index: i64,
+    proofs: &[NmtProof],
+    row_roots: &[Vec<u8>],
+) -> Result<(), CelestiaError> {
+    let width = row_roots.len() / 2;
+    let index = usize::try_from(index).map_err(|_| CelestiaError::InvalidProof("unknown index"))?;
+    if width == 0 {
+        return Err(CelestiaError::InvalidProof("empty data square"));
+    }
+
+    let leaves = shares.iter().map(|share| nmt_leaf(namespace, share)).collect::<Vec<_>>();
+    let mut proven = 0;
+    for (row, proof) in (index / width..).zip(proofs) {
+        let expected_start = if proven == 0 { index % width } else { 0 };
+        let len = proof.end.saturating_sub(proof.start);
+        if proof.start != expected_start || len == 0 || proven + len > leaves.len() {
+            return Err(CelestiaError::InvalidProof("proof does not cover the blob"));
+        }
+        let root = row_roots.get(row).ok_or(CelestiaError::InvalidProof("row out of range"))?;
+        if !verify_range(proof, &leaves[proven..proven + len], root)? {
+            return Err(CelestiaError::InvalidProof("row root mismatch"));
+        }
+        proven += len;
+    }
+    if proven != leaves.len() {
+        return Err(CelestiaError::InvalidProof("proof does not cover the blob"));
+    }
+    Ok(())
+}
+
+/// Verifies a namespaced Merkle tree proof of the given range of leaves against a row root.
+fn verify_range(proof: &NmtProof, leaves: &[NmtHash], root: &[u8]) -> Result<bool, CelestiaError> {
+    let nodes = proof
+        .nodes
+        .iter()
+        .map(|node| NmtHash::try_from(node.as_slice()))
+        .collect::<Result<VecDeque<_>, _>>()
+        .map_err(|_| CelestiaError::InvalidProof("invalid proof node"))?;
+    let mut verifier = RangeVerifier { proof, leaves: leaves.iter(), nodes };
+
+    // The proof holds the subtree roots of the smallest subtree containing the range, followed by
+    // the roots of the subtrees to its right.
+    let subtree = (split_point(proof.end) * 2).max(1);
+    let mut computed = verifier
+        .compute_root(0, subtree)?
+        .ok_or(CelestiaError::InvalidProof("missing proof nodes"))?;
+    while let Some(node) = verifier.nodes.pop_front() {
+        computed = nmt_node(&computed, &node);
+    }
+    Ok(verifier.leaves.len() == 0 && computed.as_slice() == root)
+}
+
+/// Computes the root of a namespaced Merkle tree from a range of its leaves and a proof.
+#[derive(Debug)]
+struct RangeVerifier<'a> {
+    /// The proof of the range.
+    proof: &'a NmtProof,
+    /// The leaves of the range that were not consumed yet.
+    leaves: core::slice::Iter<'a, NmtHash>,
+    /// The proof nodes that were not consumed yet.
+    nodes: VecDeque<NmtHash>,
+}
+
+impl RangeVerifier<'_> {
+    /// Computes the root of the subtree spanning the leaves `start..end`. Returns [`None`] if the
+    /// subtree lies beyond the last leaf of the tree.
+    fn compute_root(&mut self, start: usize, end: usize) -> Result<Option<NmtHash>, CelestiaError> {
+        if end - start == 1 {
+            if (self.proof.start..self.proof.end).contains(&start) {
+                let leaf = self.leaves.next().ok_or(CelestiaError::InvalidProof("missing leaf"))?;
+                return Ok(Some(*leaf));
+            }
+            return Ok(self.nodes.pop_front());
+        }
+        if end <= self.proof.start || start >= self.proof.end {
+            return Ok(self.nodes.pop_front());
+        }
+
+        let split = start + split_point(end - start);
+        let left = self.compute_root(start, split)?;
+        let right = self.compute_root(split, end)?;
+        match (left, right) {
+            (Some(left), Some(right)) => Ok(Some(nmt_node(&left, &right))),
+            (left, None) => Ok(left),
+            (None, Some(_)) => Err(CelestiaError::InvalidProof("missing left subtree")),
+        }
+    }
+}
+
+/// Returns the namespaced hash of a share.
+fn nmt_leaf(namespace: &Namespace, share: &Share) -> NmtHash {
+    let digest =
+        Sha256::new().chain_update([0u8]).chain_update(namespace).chain_update(share).finalize();
+    let mut hash = [0; NMT_HASH_SIZE];
+    hash[..NAMESPACE_SIZE].copy_from_slice(namespace);
+    hash[NAMESPACE_SIZE..2 * NAMESPACE_SIZE].copy_from_slice(namespace);
+    hash[2 * NAMESPACE_SIZE..].copy_from_slice(&digest);
+    hash
+}
+
+/// Returns the namespaced hash of an inner node, ignoring the parity namespace in its maximum
+/// namespace.
+fn nmt_node(left: &NmtHash, right: &NmtHash) -> NmtHash {
+    let (left_min, left_max) = (&left[..NAMESPACE_SIZE], &left[NAMESPACE_SIZE..2 * NAMESPACE_SIZE]);
+    let (right_min, right_max) =
+        (&right[..NAMESPACE_SIZE], &right[NAMESPACE_SIZE..2 * NAMESPACE_SIZE]);
+    let max = if right_min == PARITY_NAMESPACE { left_max } else { left_max.max(right_max) };
+
+    let digest =
+        Sha256::new().chain_update([1u8]).chain_update(left).chain_update(right).finalize();
+    let mut hash = [0; NMT_HASH_SIZE];
+    hash[..NAMESPACE_SIZE].copy_from_slice(left_min.min(right_min));
+    hash[NAMESPACE_SIZE..2 * NAMESPACE_SIZE].copy_from_slice(max);
+    hash[2 * NAMESPACE_SIZE..].copy_from_slice(&digest);
+    hash
+}
+
+/// Returns the root of the namespaced Merkle tree over the given leaves.
+fn nmt_root(leaves: &[NmtHash]) -> NmtHash {
+    if let [leaf] = leaves {
+        return *leaf;
+    }
+    let (left, right) = leaves.split_at(split_point(leaves.len()));
+    nmt_node(&nmt_root(left), &nmt_root(right))
+}
+
+/// Returns the root of the RFC 6962 Merkle tree over the given items.
+fn merkle_root(items: &[NmtHash]) -> B256 {
+    let digest = match items {
+        [] => Sha256::new().finalize(),
+        [item] => Sha256::new().chain_update([0u8]).chain_update(item).finalize(),
+        _ => {
+            let (left, right) = items.split_at(split_point(items.len()));
+            Sha256::new()
+                .chain_update([1u8])
+                .chain_update(merkle_root(left))
+                .chain_update(merkle_root(right))
+                .finalize()
+        }
+    };
+    B256::from_slice(&digest)
+}
+
+/// Returns the largest power of two strictly less than `n`, or zero if `n` is at most one.
+const fn split_point(n: usize) -> usize {
+    if n <= 1 { 0 } else { 1 << (usize::BITS - (n - 1).leading_zeros() - 1) }
+}
+
+/// Returns the width of the subtrees a blob of the given number of shares is committed to.
+fn subtree_width(shares: usize) -> usize {
+    let width = shares.div_ceil(SUBTREE_ROOT_THRESHOLD).next_power_of_two();
+    let sqrt = shares.isqrt();
+    let sqrt = if sqrt * sqrt < shares { sqrt + 1 } else { sqrt };
+    width.min(sqrt.next_power_of_two())
+}
+
+/// Returns the sizes of the trees of a Merkle mountain range over the given number of leaves,
+/// with trees of at most `max_size` leaves.
+fn mountain_range_sizes(mut leaves: usize, max_size: usize) -> Vec<usize> {
+    let mut sizes = Vec::new();
+    while leaves > 0 {
+        let size = if leaves >= max_size { max_size } else { 1 << leaves.ilog2() };
+        sizes.push(size);
+        leaves -= size;
+    }
+    sizes
+}
+
+/// Deserializes base64 encoded bytes.
+fn base64_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    STANDARD.decode(encoded).map_err(serde::de::Error::custom)
+}
+
+/// Deserializes a list of base64 encoded byte strings, which may be `null`.
+fn base64_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error> {
+    Option::<Vec<String>>::deserialize(deserializer)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|encoded| STANDARD.decode(encoded).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMESPACE: Namespace = {
+        let mut namespace = [0; NAMESPACE_SIZE];
+        namespace[NAMESPACE_SIZE - 1] = 0x42;
+        namespace
+    };
+
+    #[test]
+    fn test_decode_celestia_commitment() {
+        let mut encoded = vec![1, CELESTIA_DA_LAYER];
+        encoded.extend_from_slice(&7u64.to_le_bytes());
+        encoded.extend_from_slice(&[0xaa; 32]);
+        assert_eq!(
+            CelestiaCommitment::decode(&encoded),
+            Some(CelestiaCommitment { height: 7, commitment: B256::repeat_byte(0xaa) })
+        );
+
+        assert_eq!(CelestiaCommitment::decode(&encoded[..41]), None);
+        encoded[1] = 0x0d;
+        assert_eq!(CelestiaCommitment::decode(&encoded), None);
+    }
+
+    #[test]
+    fn test_blob_shares() {
+        let shares = blob_shares(&NAMESPACE, &[7; 1000]);
+        // 478 bytes fit in the first share, and 482 in each continuation share.
+        assert_eq!(shares.len(), 3);
+        assert_eq!(shares[0][NAMESPACE_SIZE], 1);
+        assert_eq!(shares[0][NAMESPACE_SIZE + 1..NAMESPACE_SIZE + 5], 1000u32.to_be_bytes());
+        assert_eq!(shares[1][NAMESPACE_SIZE], 0);
+        assert_eq!(shares[2][NAMESPACE_SIZE + 1..NAMESPACE_SIZE + 41], [7; 40]);
+        assert!(shares[2][NAMESPACE_SIZE + 41..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn test_subtree_layout() {
+        assert_eq!(split_point(1), 0);
+        assert_eq!(split_point(4), 2);
+        assert_eq!(split_point(5), 4);
+        assert_eq!(subtree_width(1), 1);
+        assert_eq!(subtree_width(128), 2);
+        assert_eq!(subtree_width(4096), 64);
+        assert_eq!(mountain_range_sizes(11, 4), [4, 4, 2, 1]);
+    }
+
+    #[test]
+    fn test_verify_inclusion() {
+        // A square of width 4, whose second row holds a blob of 3 shares after another share.
+        let shares = blob_shares(&NAMESPACE, &[7; 1000]);
+        let mut other = NAMESPACE;
+        other[NAMESPACE_SIZE - 1] = 0x01;
+        let padding = blob_shares(&other, &[1]);
+        let row = [
+            nmt_leaf(&other, &padding[0]),
+            nmt_leaf(&NAMESPACE, &shares[0]),
+            nmt_leaf(&NAMESPACE, &shares[1]),
+            nmt_leaf(&NAMESPACE, &shares[2]),
+        ];
+        let root = nmt_root(&row);
+        let row_roots = vec![
+            vec![0; NMT_HASH_SIZE],
+            root.to_vec(),
+            vec![0; NMT_HASH_SIZE],
+            vec![0; NMT_HASH_SIZE],
+        ];
+        let proof = NmtProof { start: 1, end: 4, nodes: vec![row[0].to_vec()] };
+        verify_inclusion(&NAMESPACE, &shares, 5, &[proof.clone()], &row_roots).unwrap();
+
+        // The proof must start at the index of the blob, and prove all of its shares.
+        assert!(verify_inclusion(&NAMESPACE, &shares, 4, &[proof.clone()], &row_roots).is_err());
+        assert!(verify_inclusion(&NAMESPACE, &shares[..2], 5, &[proof], &row_roots).is_err());
+
+        let proof = NmtProof { start: 1, end: 4, nodes: vec![[0; NMT_HASH_SIZE].to_vec()] };
+        assert!(verify_inclusion(&NAMESPACE, &shares, 5, &[proof], &row_roots).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_light_node_responses() {
+        let blob: CelestiaBlob = serde_json::from_value(serde_json::json!({
+            "namespace": STANDARD.encode(NAMESPACE),
+            "data": STANDARD.encode(b"frames"),
+            "share_version": 0,
+            "commitment": STANDARD.encode([0xaa; 32]),
+            "index": 12,
+        }))
+        .unwrap();
+        assert_eq!(blob.namespace, NAMESPACE);
+        assert_eq!(blob.data, b"frames");
+
+        let proof: NmtProof = serde_json::from_value(serde_json::json!({
+            "start": 0,
+            "end": 2,
+            "nodes": null,
+            "is_max_namespace_ignored": true,
+        }))
+        .unwrap();
+        assert!(proof.nodes.is_empty());
+    }
+}
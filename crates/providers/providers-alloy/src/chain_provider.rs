@@ -11,7 +11,30 @@ use async_trait::async_trait;
 use kona_derive::{ChainProvider, PipelineError, PipelineErrorKind};
 use kona_protocol::BlockInfo;
 use lru::LruCache;
-use std::{boxed::Box, num::NonZeroUsize, vec::Vec};
+use std::{boxed::Box, fmt::Debug, num::NonZeroUsize, sync::Arc, vec::Vec};
+
+/// A source of L1 blocks fetched outside of an [AlloyChainProvider], e.g. by an L1 watcher shared
+/// between the nodes of several chains, or read from an L1 node running in the same process.
+pub trait PrefetchedL1Blocks: Debug + Send + Sync {
+    /// Returns the info of the L1 block with the given hash and its transactions sent to the batch
+    /// inbox of the chain, if the source holds them.
+    fn batch_inbox_transactions(&self, hash: B256) -> Option<(BlockInfo, Vec<TxEnvelope>)>;
+
+    /// Returns the header of the L1 block with the given hash, if the source holds it.
+    fn header(&self, _hash: B256) -> Option<Header> {
+        None
+    }
+
+    /// Returns the info of the canonical L1 block with the given number, if the source holds it.
+    fn block_info_by_number(&self, _number: u64) -> Option<BlockInfo> {
+        None
+    }
+
+    /// Returns the receipts of the L1 block with the given hash, if the source holds them.
+    fn receipts(&self, _hash: B256) -> Option<Vec<Receipt>> {
+        None
+    }
+}
 
 /// The [AlloyChainProvider] is a concrete implementation of the [ChainProvider] trait, providing
 /// data over Ethereum JSON-RPC using an alloy provider as the backend.
@@ -27,6 +50,8 @@ pub struct AlloyChainProvider {
     receipts_by_hash_cache: LruCache<B256, Vec<Receipt>>,
     /// `block_info_and_transactions_by_hash` LRU cache.
     block_info_and_transactions_by_hash_cache: LruCache<B256, (BlockInfo, Vec<TxEnvelope>)>,
+    /// The blocks fetched outside of the provider, looked up before the RPC.
+    prefetched_blocks: Option<Arc<dyn PrefetchedL1Blocks>>,
 }
 
 impl AlloyChainProvider {
@@ -51,9 +76,19 @@ impl AlloyChainProvider {
             block_info_and_transactions_by_hash_cache: LruCache::new(
                 NonZeroUsize::new(cache_size).unwrap(),
             ),
+            prefetched_blocks: None,
         }
     }
 
+    /// Looks up the headers, receipts and blocks in the given [PrefetchedL1Blocks] before fetching
+    /// them from the RPC.
+    ///
+    /// The prefetched blocks may only hold the transactions sent to the batch inbox, so the
+    /// provider must only be used by the data sources of the derivation pipeline.
+    pub fn with_prefetched_blocks(self, prefetched_blocks: Arc<dyn PrefetchedL1Blocks>) -> Self {
+        Self { prefetched_blocks: Some(prefetched_blocks), ..self }
+    }
+
     /// Creates a new [AlloyChainProvider] from the provided [reqwest::Url].
     pub fn new_http(url: reqwest::Url, cache_size: usize) -> Self {
         let inner = RootProvider::new_http(url);
@@ -150,6 +185,13 @@ impl ChainProvider for AlloyChainProvider {
 
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "header_by_hash");
 
+        if let Some(header) = self.prefetched_blocks.as_ref().and_then(|blocks| blocks.header(hash))
+        {
+            self.header_by_hash_cache.put(hash, header.clone());
+            kona_macros::inc!(gauge, Metrics::CACHE_ENTRIES, "cache" => "header_by_hash");
+            return Ok(header);
+        }
+
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "header_by_hash");
 
         let block = self
@@ -173,6 +215,12 @@ impl ChainProvider for AlloyChainProvider {
     }
 
     async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        if let Some(block_info) =
+            self.prefetched_blocks.as_ref().and_then(|blocks| blocks.block_info_by_number(number))
+        {
+            return Ok(block_info);
+        }
+
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "block_by_number");
 
         let block = self
@@ -202,6 +250,14 @@ impl ChainProvider for AlloyChainProvider {
 
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "receipts_by_hash");
 
+        if let Some(receipts) =
+            self.prefetched_blocks.as_ref().and_then(|blocks| blocks.receipts(hash))
+        {
+            self.receipts_by_hash_cache.put(hash, receipts.clone());
+            kona_macros::inc!(gauge, Metrics::CACHE_ENTRIES, "cache" => "receipts_by_hash");
+            return Ok(receipts);
+        }
+
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "receipts_by_hash");
 
         let receipts = self
@@ -237,6 +293,14 @@ impl ChainProvider for AlloyChainProvider {
 
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "block_info_and_tx");
 
+        if let Some(block_info_and_txs) =
+            self.prefetched_blocks.as_ref().and_then(|blocks| blocks.batch_inbox_transactions(hash))
+        {
+            self.block_info_and_transactions_by_hash_cache.put(hash, block_info_and_txs.clone());
+            kona_macros::inc!(gauge, Metrics::CACHE_ENTRIES, "cache" => "block_info_and_tx");
+            return Ok(block_info_and_txs);
+        }
+
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "block_by_hash");
 
         let block = self
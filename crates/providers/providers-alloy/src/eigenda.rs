@@ -0,0 +1,724 @@
+//! Contains the [EigenDaProvider], an [AltDaProvider] resolving EigenDA certificates with an
+//! EigenDA proxy.
+//!
+//! Batcher transactions of chains posting their data to EigenDA carry a generic alt-DA commitment
+//! with the [`EIGENDA_DA_LAYER`] byte, followed by the version byte of the certificate and the
+//! RLP encoded certificate. The certificate is verified locally before its data is fetched from the
+//! proxy, which retrieves the blob from the EigenDA operators and checks it against the KZG
+//! commitment of the certificate. Certificates that fail verification are dropped, as mandated by
+//! the EigenDA derivation rules.
+//!
+//! The KZG commitment is not recomputed locally, as it requires the EigenDA structured reference
+//! string, so the proxy is trusted to serve the data committed to by the certificate. It must be
+//! run by the operator of the node.
+
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use alloy_rpc_types_eth::TransactionRequest;
+use alloy_sol_types::{SolCall, SolValue};
+use alloy_transport::TransportError;
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use kona_derive::{AltDaProvider, PipelineError, PipelineErrorKind, PipelineResult};
+use kona_protocol::BlockInfo;
+use reqwest::{StatusCode, Url};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// The byte identifying EigenDA in generic alt-DA commitments.
+pub const EIGENDA_DA_LAYER: u8 = 0x00;
+
+/// The version byte of EigenDA V1 certificates.
+pub const EIGENDA_CERT_V1: u8 = 0x00;
+
+/// The size of a symbol of an EigenDA blob, in bytes.
+const SYMBOL_SIZE: usize = 32;
+
+#[allow(missing_docs, unreachable_pub)]
+mod abi {
+    alloy_sol_types::sol! {
+        struct G1Point {
+            uint256 X;
+            uint256 Y;
+        }
+
+        struct QuorumBlobParam {
+            uint8 quorumNumber;
+            uint8 adversaryThresholdPercentage;
+            uint8 confirmationThresholdPercentage;
+            uint32 chunkLength;
+        }
+
+        struct BlobHeader {
+            G1Point commitment;
+            uint32 dataLength;
+            QuorumBlobParam[] quorumBlobParams;
+        }
+
+        struct BatchHeader {
+            bytes32 blobHeadersRoot;
+            bytes quorumNumbers;
+            bytes signedStakeForQuorums;
+            uint32 referenceBlockNumber;
+        }
+
+        function batchIdToBatchMetadataHash(uint32 batchId) external view returns (bytes32);
+    }
+}
+
+/// An error resolving an EigenDA certificate.
+#[derive(Debug, thiserror::Error)]
+pub enum EigenDaError {
+    /// The request to the proxy failed.
+    #[error("EigenDA proxy request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The proxy responded with an unexpected status.
+    #[error("EigenDA proxy responded with status {0}")]
+    Status(StatusCode),
+    /// The request to the L1 RPC failed.
+    #[error("L1 request failed: {0}")]
+    Rpc(#[from] TransportError),
+    /// The certificate version is not supported, so the chain cannot be derived.
+    #[error("Unsupported EigenDA certificate version {0}")]
+    UnsupportedVersion(u8),
+    /// The certificate is invalid, and must be dropped.
+    #[error("Invalid EigenDA certificate: {0}")]
+    InvalidCert(&'static str),
+    /// The URL of the proxy cannot be the base of the requests to it.
+    #[error("Invalid EigenDA proxy URL {0}")]
+    InvalidProxy(Url),
+}
+
+impl EigenDaError {
+    /// Returns whether the request may succeed when retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Http(_) => true,
+            Self::Status(status) => status.is_server_error(),
+            _ => false,
+        }
+    }
+}
+
+impl From<EigenDaError> for PipelineErrorKind {
+    fn from(e: EigenDaError) -> Self {
+        match e {
+            EigenDaError::UnsupportedVersion(_) => {
+                PipelineError::Provider(format!("EigenDA: {e}")).crit()
+            }
+            e => PipelineError::Provider(format!("EigenDA: {e}")).temp(),
+        }
+    }
+}
+
+/// The configuration of an [`EigenDaProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EigenDaConfig {
+    /// The URL of the EigenDA proxy.
+    pub proxy: Url,
+    /// The number of L1 blocks after the reference block of a certificate within which it must
+    /// be included. Certificates are not checked for recency if unset.
+    pub recency_window: Option<u64>,
+    /// The timeout of each request to the proxy.
+    pub timeout: Duration,
+    /// The number of times a request to the proxy is retried after a network or server failure.
+    pub max_retries: usize,
+    /// The delay before the first retry.
+    pub min_backoff: Duration,
+    /// The maximum delay between retries.
+    pub max_backoff: Duration,
+}
+
+impl EigenDaConfig {
+    /// Creates a new [`EigenDaConfig`] for the proxy at the given URL, with default timeouts and
+    /// retries, and without recency checks.
+    pub const fn new(proxy: Url) -> Self {
+        Self {
+            proxy,
+            recency_window: None,
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            min_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+
+    /// Returns the backoff between the retries of a request.
+    fn backoff(&self) -> ExponentialBuilder {
+        ExponentialBuilder::default()
+            .with_min_delay(self.min_backoff)
+            .with_max_delay(self.max_backoff)
+            .with_max_times(self.max_retries)
+    }
+}
+
+/// The KZG commitment of a blob.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct G1Commitment {
+    x: Bytes,
+    y: Bytes,
+}
+
+/// The security parameters of a quorum a blob was dispersed to.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct BlobQuorumParam {
+    quorum_number: u32,
+    adversary_threshold_percentage: u32,
+    confirmation_threshold_percentage: u32,
+    chunk_length: u32,
+}
+
+/// The header of a blob.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct BlobHeader {
+    commitment: G1Commitment,
+    data_length: u32,
+    blob_quorum_params: Vec<BlobQuorumParam>,
+}
+
+/// The header of the batch a blob was confirmed in.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct BatchHeader {
+    batch_root: Bytes,
+    quorum_numbers: Bytes,
+    quorum_signed_percentages: Bytes,
+    reference_block_number: u32,
+}
+
+/// The metadata of the batch a blob was confirmed in.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct BatchMetadata {
+    batch_header: BatchHeader,
+    signatory_record_hash: Bytes,
+    fee: Bytes,
+    confirmation_block_number: u32,
+    batch_header_hash: Bytes,
+}
+
+/// The proof of the inclusion of a blob in a confirmed batch.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct BlobVerificationProof {
+    batch_id: u32,
+    blob_index: u32,
+    batch_metadata: BatchMetadata,
+    inclusion_proof: Bytes,
+    quorum_indexes: Bytes,
+}
+
+/// An EigenDA V1 certificate.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct BlobInfo {
+    blob_header: BlobHeader,
+    blob_verification_proof: BlobVerificationProof,
+}
+
+impl BlobInfo {
+    /// Decodes the certificate of an encoded generic commitment, starting with its type byte and
+    /// the [`EIGENDA_DA_LAYER`] byte.
+    fn decode_commitment(commitment: &[u8]) -> Result<Self, EigenDaError> {
+        let [_, EIGENDA_DA_LAYER, version, cert @ ..] = commitment else {
+            return Err(EigenDaError::InvalidCert("malformed commitment"));
+        };
+        if *version != EIGENDA_CERT_V1 {
+            return Err(EigenDaError::UnsupportedVersion(*version));
+        }
+        let mut cert = cert;
+        let info = Self::decode(&mut cert).map_err(|_| EigenDaError::InvalidCert("malformed"))?;
+        if !cert.is_empty() {
+            return Err(EigenDaError::InvalidCert("trailing bytes"));
+        }
+        Ok(info)
+    }
+
+    /// Returns the maximum size of the payload of the blob.
+    const fn max_payload_len(&self) -> usize {
+        self.blob_header.data_length as usize * SYMBOL_SIZE
+    }
+
+    /// Returns the hash of the metadata of the batch the blob was confirmed in, as stored by the
+    /// EigenDA service manager.
+    fn batch_metadata_hash(&self) -> B256 {
+        let metadata = &self.blob_verification_proof.batch_metadata;
+        keccak256(
+            [
+                metadata.batch_header_hash.as_ref(),
+                metadata.signatory_record_hash.as_ref(),
+                &metadata.confirmation_block_number.to_be_bytes(),
+            ]
+            .concat(),
+        )
+    }
+
+    /// Verifies the certificate, included in the L1 block with the given number.
+    fn verify(
+        &self,
+        l1_inclusion_block: u64,
+        recency_window: Option<u64>,
+    ) -> Result<(), EigenDaError> {
+        let proof = &self.blob_verification_proof;
+        let batch_header = &proof.batch_metadata.batch_header;
+
+        let reference_block = u64::from(batch_header.reference_block_number);
+        if recency_window.is_some_and(|window| {
+            l1_inclusion_block <= reference_block || l1_inclusion_block > reference_block + window
+        }) {
+            return Err(EigenDaError::InvalidCert("outside of the recency window"));
+        }
+
+        // The blob must be dispersed to its quorums with the security parameters they signed for.
+        let params = &self.blob_header.blob_quorum_params;
+        if params.is_empty() || params.len() != proof.quorum_indexes.len() {
+            return Err(EigenDaError::InvalidCert("quorum mismatch"));
+        }
+        for (param, index) in params.iter().zip(proof.quorum_indexes.iter()) {
+            let index = *index as usize;
+            let quorum = batch_header.quorum_numbers.get(index).copied().map(u32::from);
+            let signed = batch_header.quorum_signed_percentages.get(index).copied().map(u32::from);
+            if quorum != Some(param.quorum_number) {
+                return Err(EigenDaError::InvalidCert("quorum mismatch"));
+            }
+            if param.confirmation_threshold_percentage <= param.adversary_threshold_percentage {
+                return Err(EigenDaError::InvalidCert("invalid quorum thresholds"));
+            }
+            if signed < Some(param.confirmation_threshold_percentage) {
+                return Err(EigenDaError::InvalidCert("quorum threshold not met"));
+            }
+        }
+
+        // The batch header must hash to the header hash of the certificate.
+        let batch_root = B256::try_from(batch_header.batch_root.as_ref())
+            .map_err(|_| EigenDaError::InvalidCert("malformed batch root"))?;
+        let batch_header_hash = keccak256(
+            abi::BatchHeader {
+                blobHeadersRoot: batch_root,
+                quorumNumbers: batch_header.quorum_numbers.clone(),
+                signedStakeForQuorums: batch_header.quorum_signed_percentages.clone(),
+                referenceBlockNumber: batch_header.reference_block_number,
+            }
+            .abi_encode(),
+        );
+        if batch_header_hash.as_slice() != proof.batch_metadata.batch_header_hash.as_ref() {
+            return Err(EigenDaError::InvalidCert("batch header hash mismatch"));
+        }
+
+        // The blob header must be included in the batch.
+        let leaf = keccak256(self.blob_header.abi_hash()?);
+        if merkle_root(leaf, &proof.inclusion_proof, proof.blob_index)? != batch_root {
+            return Err(EigenDaError::InvalidCert("blob header not included in the batch"));
+        }
+        Ok(())
+    }
+}
+
+impl BlobHeader {
+    /// Returns the hash of the ABI encoded blob header.
+    fn abi_hash(&self) -> Result<B256, EigenDaError> {
+        let param = |param: &BlobQuorumParam| {
+            let to_u8 = |value: u32| {
+                u8::try_from(value).map_err(|_| EigenDaError::InvalidCert("invalid quorum param"))
+            };
+            Ok(abi::QuorumBlobParam {
+                quorumNumber: to_u8(param.quorum_number)?,
+                adversaryThresholdPercentage: to_u8(param.adversary_threshold_percentage)?,
+                confirmationThresholdPercentage: to_u8(param.confirmation_threshold_percentage)?,
+                chunkLength: param.chunk_length,
+            })
+        };
+        let header = abi::BlobHeader {
+            commitment: abi::G1Point {
+                X: U256::try_from_be_slice(&self.commitment.x)
+                    .ok_or(EigenDaError::InvalidCert("malformed commitment"))?,
+                Y: U256::try_from_be_slice(&self.commitment.y)
+                    .ok_or(EigenDaError::InvalidCert("malformed commitment"))?,
+            },
+            dataLength: self.data_length,
+            quorumBlobParams: self
+                .blob_quorum_params
+                .iter()
+                .map(param)
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(keccak256(header.abi_encode()))
+    }
+}
+
+/// Returns the root of the keccak256 Merkle tree with the given leaf at the given index.
+fn merkle_root(leaf: B256, proof: &[u8], mut index: u32) -> Result<B256, EigenDaError> {
+    if proof.len() % 32 != 0 {
+        return Err(EigenDaError::InvalidCert("malformed inclusion proof"));
+    }
+    let root = proof.chunks_exact(32).fold(leaf, |node, sibling| {
+        let node = if index % 2 == 0 {
+            keccak256([node.as_slice(), sibling].concat())
+        } else {
+            keccak256([sibling, node.as_slice()].concat())
+        };
+        index /= 2;
+        node
+    });
+    Ok(root)
+}
+
+/// An [`AltDaProvider`] resolving EigenDA certificates with an EigenDA proxy, to be registered
+/// for the generic commitments of the [`EIGENDA_DA_LAYER`].
+///
+/// Certificates are verified locally before their data is fetched: the quorums of the blob must
+/// have signed for it above their confirmation thresholds, and its header must be included in the
+/// batch of the certificate. If an EigenDA service manager is set, the batch must also have been
+/// confirmed on L1. Invalid certificates resolve to empty data, which the frame queue drops.
+///
+/// The data of a valid certificate is not checked against its KZG commitment, so the proxy must be
+/// trusted. It also disperses the data of batchers with [`EigenDaProvider::disperse`].
+#[derive(Debug, Clone)]
+pub struct EigenDaProvider {
+    /// The HTTP client of the proxy.
+    client: reqwest::Client,
+    /// The configuration of the provider.
+    config: EigenDaConfig,
+    /// The L1 provider and the address of the EigenDA service manager batches are confirmed by.
+    service_manager: Option<(RootProvider, Address)>,
+}
+
+impl EigenDaProvider {
+    /// Creates a new [`EigenDaProvider`] from the given [`EigenDaConfig`].
+    pub fn new(mut config: EigenDaConfig) -> Result<Self, EigenDaError> {
+        if config.proxy.cannot_be_a_base() {
+            return Err(EigenDaError::InvalidProxy(config.proxy));
+        }
+        // Joining a path to the proxy URL replaces its last segment unless it ends with a slash.
+        if !config.proxy.path().ends_with('/') {
+            let path = format!("{}/", config.proxy.path());
+            config.proxy.set_path(&path);
+        }
+
+        let client = reqwest::Client::builder().timeout(config.timeout).build()?;
+        Ok(Self { client, config, service_manager: None })
+    }
+
+    /// Checks that the batches of the certificates were confirmed by the EigenDA service manager
+    /// at the given address.
+    pub fn with_service_manager(self, l1_provider: RootProvider, address: Address) -> Self {
+        Self { service_manager: Some((l1_provider, address)), ..self }
+    }
+
+    /// Verifies the certificate of the given commitment, and fetches its data from the proxy.
+    async fn resolve(
+        &self,
+        commitment: &Bytes,
+        block_ref: &BlockInfo,
+    ) -> Result<Bytes, EigenDaError> {
+        let cert = BlobInfo::decode_commitment(commitment)?;
+        cert.verify(block_ref.number, self.config.recency_window)?;
+        if let Some((l1_provider, address)) = &self.service_manager {
+            let batch_id = cert.blob_verification_proof.batch_id;
+            let call = abi::batchIdToBatchMetadataHashCall { batchId: batch_id };
+            let request = TransactionRequest::default()
+                .to(*address)
+                .input(Bytes::from(call.abi_encode()).into());
+            let output = l1_provider.call(request).await?;
+            let confirmed = abi::batchIdToBatchMetadataHashCall::abi_decode_returns(&output)
+                .map_err(|_| EigenDaError::InvalidCert("malformed batch metadata hash"))?;
+            if confirmed != cert.batch_metadata_hash() {
+                return Err(EigenDaError::InvalidCert("batch not confirmed on L1"));
+            }
+        }
+
+        let url = self
+            .config
+            .proxy
+            .join(&format!("get/{commitment}"))
+            .map_err(|_| EigenDaError::InvalidCert("malformed commitment"))?;
+        let data = (|| async {
+            let response = self.client.get(url.clone()).send().await?;
+            match response.status() {
+                StatusCode::OK => Ok(response.bytes().await?),
+                // The proxy rejects certificates that fail its own verification with a teapot.
+                StatusCode::IM_A_TEAPOT => Err(EigenDaError::InvalidCert("rejected by the proxy")),
+                status => Err(EigenDaError::Status(status)),
+            }
+        })
+        .retry(self.config.backoff())
+        .when(EigenDaError::is_retryable)
+        .notify(|err, delay| {
+            debug!(target: "eigenda", %err, ?delay, "Retrying EigenDA proxy request");
+        })
+        .await?;
+
+        if data.len() > cert.max_payload_len() {
+            return Err(EigenDaError::InvalidCert("payload exceeds the blob length"));
+        }
+        Ok(data.into())
+    }
+
+    /// Disperses `data` to EigenDA through the proxy, and returns the generic commitment to post
+    /// to the batch inbox for it.
+    pub async fn disperse(&self, data: &[u8]) -> Result<Bytes, EigenDaError> {
+        let mut url = self
+            .config
+            .proxy
+            .join("put")
+            .map_err(|_| EigenDaError::InvalidProxy(self.config.proxy.clone()))?;
+        url.query_pairs_mut().append_pair("commitment_mode", "optimism_generic");
+
+        let commitment = (|| async {
+            let response = self.client.post(url.clone()).body(data.to_vec()).send().await?;
+            match response.status() {
+                StatusCode::OK => Ok(response.bytes().await?),
+                status => Err(EigenDaError::Status(status)),
+            }
+        })
+        .retry(self.config.backoff())
+        .when(EigenDaError::is_retryable)
+        .notify(|err, delay| {
+            debug!(target: "eigenda", %err, ?delay, "Retrying EigenDA dispersal");
+        })
+        .await?;
+
+        BlobInfo::decode_commitment(&commitment)?;
+        Ok(commitment.into())
+    }
+}
+
+#[async_trait]
+impl AltDaProvider for EigenDaProvider {
+    async fn get_input(
+        &mut self,
+        commitment: &Bytes,
+        block_ref: &BlockInfo,
+    ) -> PipelineResult<Bytes> {
+        match self.resolve(commitment, block_ref).await {
+            Ok(data) => Ok(data),
+            Err(err @ EigenDaError::InvalidCert(_)) => {
+                warn!(target: "eigenda", %err, "Dropping EigenDA certificate");
+                Ok(Bytes::new())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    /// Serves a single request with the given status and body on a local port. Returns the URL of
+    /// the server, without a trailing slash, and the request line and body of the request.
+    async fn serve_once(status: u16, body: Vec<u8>) -> (Url, JoinHandle<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/eigenda", listener.local_addr().unwrap()).parse().unwrap();
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let head_len = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let head = String::from_utf8(request[..head_len].to_vec()).unwrap();
+            let content_length = head
+                .lines()
+                .find_map(|line| {
+                    line.to_lowercase().strip_prefix("content-length:")?.trim().parse().ok()
+                })
+                .unwrap_or(0usize);
+            while request.len() < head_len + content_length {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+
+            let response = format!(
+                "HTTP/1.1 {status} Status\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.write_all(&body).await.unwrap();
+            let request_line = head.lines().next().unwrap().to_string();
+            (request_line, request[head_len..].to_vec())
+        });
+        (url, handle)
+    }
+
+    /// Returns the generic commitment of the given certificate.
+    fn encode_commitment(cert: &BlobInfo) -> Bytes {
+        let mut encoded = vec![1, EIGENDA_DA_LAYER, EIGENDA_CERT_V1];
+        cert.encode(&mut encoded);
+        encoded.into()
+    }
+
+    /// Returns a valid certificate of a blob at index 1 of a batch of two blobs, referencing L1
+    /// block 100.
+    fn valid_cert() -> BlobInfo {
+        let blob_header = BlobHeader {
+            commitment: G1Commitment { x: vec![1; 32].into(), y: vec![2; 32].into() },
+            data_length: 4,
+            blob_quorum_params: vec![
+                BlobQuorumParam {
+                    quorum_number: 0,
+                    adversary_threshold_percentage: 33,
+                    confirmation_threshold_percentage: 55,
+                    chunk_length: 1,
+                },
+                BlobQuorumParam {
+                    quorum_number: 1,
+                    adversary_threshold_percentage: 33,
+                    confirmation_threshold_percentage: 55,
+                    chunk_length: 1,
+                },
+            ],
+        };
+        let sibling = B256::repeat_byte(0xaa);
+        let leaf = keccak256(blob_header.abi_hash().unwrap());
+        let batch_root = keccak256([sibling.as_slice(), leaf.as_slice()].concat());
+        let batch_header = BatchHeader {
+            batch_root: batch_root.to_vec().into(),
+            quorum_numbers: vec![0, 1].into(),
+            quorum_signed_percentages: vec![90, 60].into(),
+            reference_block_number: 100,
+        };
+        let batch_header_hash = keccak256(
+            abi::BatchHeader {
+                blobHeadersRoot: batch_root,
+                quorumNumbers: batch_header.quorum_numbers.clone(),
+                signedStakeForQuorums: batch_header.quorum_signed_percentages.clone(),
+                referenceBlockNumber: 100,
+            }
+            .abi_encode(),
+        );
+        BlobInfo {
+            blob_header,
+            blob_verification_proof: BlobVerificationProof {
+                batch_id: 7,
+                blob_index: 1,
+                batch_metadata: BatchMetadata {
+                    batch_header,
+                    signatory_record_hash: vec![3; 32].into(),
+                    fee: Bytes::new(),
+                    confirmation_block_number: 101,
+                    batch_header_hash: batch_header_hash.to_vec().into(),
+                },
+                inclusion_proof: sibling.to_vec().into(),
+                quorum_indexes: vec![0, 1].into(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_decode_eigenda_commitment() {
+        let cert = valid_cert();
+        let mut encoded = encode_commitment(&cert).to_vec();
+        assert_eq!(BlobInfo::decode_commitment(&encoded).unwrap(), cert);
+
+        encoded.push(0);
+        assert!(matches!(BlobInfo::decode_commitment(&encoded), Err(EigenDaError::InvalidCert(_))));
+        assert!(matches!(
+            BlobInfo::decode_commitment(&[1, EIGENDA_DA_LAYER, 0x02, 0xc0]),
+            Err(EigenDaError::UnsupportedVersion(0x02))
+        ));
+        assert!(matches!(
+            BlobInfo::decode_commitment(&[1, 0x0c, EIGENDA_CERT_V1]),
+            Err(EigenDaError::InvalidCert(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_cert() {
+        let cert = valid_cert();
+        cert.verify(150, Some(100)).unwrap();
+        cert.verify(1_000, None).unwrap();
+        assert_eq!(cert.max_payload_len(), 128);
+    }
+
+    #[test]
+    fn test_verify_cert_recency() {
+        let cert = valid_cert();
+        assert!(cert.verify(100, Some(100)).is_err());
+        assert!(cert.verify(201, Some(100)).is_err());
+        cert.verify(200, Some(100)).unwrap();
+    }
+
+    #[test]
+    fn test_verify_cert_rejects_tampering() {
+        let mut cert = valid_cert();
+        cert.blob_header.data_length = 5;
+        assert!(cert.verify(150, None).is_err());
+
+        let mut cert = valid_cert();
+        cert.blob_verification_proof.blob_index = 0;
+        assert!(cert.verify(150, None).is_err());
+
+        let mut cert = valid_cert();
+        cert.blob_verification_proof.batch_metadata.batch_header.quorum_signed_percentages =
+            vec![90, 50].into();
+        assert!(cert.verify(150, None).is_err());
+
+        let mut cert = valid_cert();
+        cert.blob_verification_proof.quorum_indexes = vec![1, 0].into();
+        assert!(cert.verify(150, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_from_proxy() {
+        let commitment = encode_commitment(&valid_cert());
+        let (proxy, request) = serve_once(200, b"data".to_vec()).await;
+        let mut provider = EigenDaProvider::new(EigenDaConfig::new(proxy)).unwrap();
+
+        let block_ref = BlockInfo { number: 150, ..Default::default() };
+        let data = provider.get_input(&commitment, &block_ref).await.unwrap();
+        assert_eq!(data, Bytes::from_static(b"data"));
+        let (request_line, _) = request.await.unwrap();
+        assert_eq!(request_line, format!("GET /eigenda/get/{commitment} HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_drops_oversized_payload() {
+        let commitment = encode_commitment(&valid_cert());
+        let (proxy, _) = serve_once(200, vec![0; 129]).await;
+        let mut provider = EigenDaProvider::new(EigenDaConfig::new(proxy)).unwrap();
+
+        let block_ref = BlockInfo { number: 150, ..Default::default() };
+        assert!(provider.get_input(&commitment, &block_ref).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disperse_to_proxy() {
+        let commitment = encode_commitment(&valid_cert());
+        let (proxy, request) = serve_once(200, commitment.to_vec()).await;
+        let provider = EigenDaProvider::new(EigenDaConfig::new(proxy)).unwrap();
+
+        assert_eq!(provider.disperse(b"data").await.unwrap(), commitment);
+        let (request_line, body) = request.await.unwrap();
+        assert_eq!(request_line, "POST /eigenda/put?commitment_mode=optimism_generic HTTP/1.1");
+        assert_eq!(body, b"data");
+    }
+
+    #[tokio::test]
+    async fn test_disperse_rejects_malformed_commitment() {
+        let (proxy, _) = serve_once(200, vec![1, 0x0c]).await;
+        let provider = EigenDaProvider::new(EigenDaConfig::new(proxy)).unwrap();
+        assert!(matches!(provider.disperse(b"data").await, Err(EigenDaError::InvalidCert(_))));
+    }
+
+    #[test]
+    fn test_eigenda_error_kinds() {
+        assert!(EigenDaError::Status(StatusCode::BAD_GATEWAY).is_retryable());
+        assert!(!EigenDaError::Status(StatusCode::NOT_FOUND).is_retryable());
+        assert!(matches!(
+            PipelineErrorKind::from(EigenDaError::UnsupportedVersion(2)),
+            PipelineErrorKind::Critical(_)
+        ));
+        assert!(matches!(
+            PipelineErrorKind::from(EigenDaError::Status(StatusCode::NOT_FOUND)),
+            PipelineErrorKind::Temporary(_)
+        ));
+    }
+}
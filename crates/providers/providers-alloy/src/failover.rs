@@ -0,0 +1,428 @@
+//! Contains the [FailoverDataSource], a composite [DataAvailabilityProvider] that serves L1 block
+//! data from blobs or calldata with a configurable fallback ordering.
+
+#[cfg(feature = "metrics")]
+use crate::Metrics;
+use alloy_primitives::{Address, Bytes};
+use async_trait::async_trait;
+use core::{fmt::Debug, time::Duration};
+use kona_derive::{
+    BatcherTxRef, BlobProvider, BlobSource, CalldataSource, ChainProvider,
+    DataAvailabilityProvider, PipelineError, PipelineErrorKind, PipelineResult,
+};
+use kona_genesis::RollupConfig;
+use kona_protocol::BlockInfo;
+use std::{boxed::Box, format, vec, vec::Vec};
+
+/// The kind of data source backing a [FailoverDataSource].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataSourceKind {
+    /// Batcher data read from blob transactions, including calldata batcher transactions in the
+    /// same block.
+    Blobs,
+    /// Batcher data read from calldata transactions only.
+    Calldata,
+}
+
+impl DataSourceKind {
+    /// Returns the metric label for the data source kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Blobs => "blobs",
+            Self::Calldata => "calldata",
+        }
+    }
+}
+
+impl core::fmt::Display for DataSourceKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A [DataAvailabilityProvider] that fails over between blobs and calldata.
+///
+/// Before ecotone only calldata batcher transactions exist, so the calldata source is always
+/// used. Once ecotone is active, the sources are tried in the configured order for every new L1
+/// block: the first source that returns data serves the rest of that block. The next source is
+/// only tried once a source has definitively no data for the block, i.e. returns
+/// [PipelineError::Eof].
+///
+/// Errors, including timeouts, are returned as is, without trying the next source. A source that
+/// fails is cleared, so that the pipeline retries the block from the first source. Serving a block
+/// from another source after a transient error could skip the batcher data that only the failed
+/// source holds, making the derived chain depend on the availability of the provider.
+#[derive(Debug, Clone)]
+pub struct FailoverDataSource<C, B>
+where
+    C: ChainProvider + Send + Clone,
+    B: BlobProvider + Send + Clone,
+{
+    /// The ecotone timestamp.
+    pub ecotone_timestamp: Option<u64>,
+    /// The blob source.
+    pub blob_source: BlobSource<C, B>,
+    /// The calldata source.
+    pub calldata_source: CalldataSource<C>,
+    /// The order in which sources are tried once ecotone is active.
+    pub order: Vec<DataSourceKind>,
+    /// The timeout applied to each request made to the blob source.
+    pub blob_timeout: Option<Duration>,
+    /// The timeout applied to each request made to the calldata source.
+    pub calldata_timeout: Option<Duration>,
+    /// The source serving the current L1 block, if one has been selected.
+    pub active: Option<DataSourceKind>,
+}
+
+impl<C, B> FailoverDataSource<C, B>
+where
+    C: ChainProvider + Send + Clone + Debug,
+    B: BlobProvider + Send + Clone + Debug,
+{
+    /// Instantiates a new [`FailoverDataSource`] that prefers blobs and falls back to calldata.
+    pub fn new(
+        blob_source: BlobSource<C, B>,
+        calldata_source: CalldataSource<C>,
+        cfg: &RollupConfig,
+    ) -> Self {
+        Self {
+            ecotone_timestamp: cfg.hardforks.ecotone_time,
+            blob_source,
+            calldata_source,
+            order: vec![DataSourceKind::Blobs, DataSourceKind::Calldata],
+            blob_timeout: None,
+            calldata_timeout: None,
+            active: None,
+        }
+    }
+
+    /// Instantiates a new [`FailoverDataSource`] from parts.
+    pub fn new_from_parts(provider: C, blobs: B, cfg: &RollupConfig) -> Self {
+        Self::new(
+            BlobSource::new(provider.clone(), blobs, cfg.batch_inbox_address)
+                .with_inbox_schedule(cfg.batch_inbox_schedule.clone()),
+            CalldataSource::new(provider, cfg.batch_inbox_address)
+                .with_inbox_schedule(cfg.batch_inbox_schedule.clone()),
+            cfg,
+        )
+    }
+
+    /// Sets the order in which sources are tried once ecotone is active.
+    pub fn with_order(mut self, order: impl IntoIterator<Item = DataSourceKind>) -> Self {
+        self.order.clear();
+        for kind in order {
+            if !self.order.contains(&kind) {
+                self.order.push(kind);
+            }
+        }
+        self
+    }
+
+    /// Sets the timeout applied to each request made to the given source.
+    pub const fn with_timeout(mut self, kind: DataSourceKind, timeout: Duration) -> Self {
+        match kind {
+            DataSourceKind::Blobs => self.blob_timeout = Some(timeout),
+            DataSourceKind::Calldata => self.calldata_timeout = Some(timeout),
+        }
+        self
+    }
+}
+
+impl<C, B> FailoverDataSource<C, B>
+where
+    C: ChainProvider + Send + Sync + Clone + Debug,
+    B: BlobProvider + Send + Sync + Clone + Debug,
+{
+    /// Returns the sources to try for the given L1 block, in order.
+    fn sources_for(&self, block_ref: &BlockInfo) -> Vec<DataSourceKind> {
+        let ecotone_enabled =
+            self.ecotone_timestamp.map(|e| block_ref.timestamp >= e).unwrap_or(false);
+        if ecotone_enabled { self.order.clone() } else { vec![DataSourceKind::Calldata] }
+    }
+
+    /// Pulls the next item from a single source, applying its timeout.
+    async fn next_from(
+        &mut self,
+        kind: DataSourceKind,
+        block_ref: &BlockInfo,
+        batcher_address: Address,
+    ) -> PipelineResult<Bytes> {
+        let (fut, timeout) = match kind {
+            DataSourceKind::Blobs => {
+                (self.blob_source.next(block_ref, batcher_address), self.blob_timeout)
+            }
+            DataSourceKind::Calldata => {
+                (self.calldata_source.next(block_ref, batcher_address), self.calldata_timeout)
+            }
+        };
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, fut).await.unwrap_or_else(|_| {
+                Err(PipelineError::Provider(format!(
+                    "{kind} source timed out after {}ms",
+                    timeout.as_millis()
+                ))
+                .temp())
+            }),
+            None => fut.await,
+        }
+    }
+
+    /// Clears the state of a single source.
+    fn clear_source(&mut self, kind: DataSourceKind) {
+        match kind {
+            DataSourceKind::Blobs => self.blob_source.clear(),
+            DataSourceKind::Calldata => self.calldata_source.clear(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, B> DataAvailabilityProvider for FailoverDataSource<C, B>
+where
+    C: ChainProvider + Send + Sync + Clone + Debug,
+    B: BlobProvider + Send + Sync + Clone + Debug,
+{
+    type Item = Bytes;
+
+    async fn next(
+        &mut self,
+        block_ref: &BlockInfo,
+        batcher_address: Address,
+    ) -> PipelineResult<Self::Item> {
+        if let Some(kind) = self.active {
+            return self.next_from(kind, block_ref, batcher_address).await;
+        }
+
+        for kind in self.sources_for(block_ref) {
+            match self.next_from(kind, block_ref, batcher_address).await {
+                Ok(data) => {
+                    self.active = Some(kind);
+                    #[cfg(feature = "metrics")]
+                    kona_macros::inc!(
+                        counter,
+                        Metrics::DATA_SOURCE_SERVED,
+                        "source" => kind.as_str()
+                    );
+                    return Ok(data);
+                }
+                Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => {
+                    #[cfg(feature = "metrics")]
+                    kona_macros::inc!(
+                        counter,
+                        Metrics::DATA_SOURCE_FAILOVERS,
+                        "source" => kind.as_str()
+                    );
+                }
+                Err(e) => {
+                    self.clear_source(kind);
+                    return Err(e);
+                }
+            }
+        }
+
+        Err(PipelineError::Eof.temp())
+    }
+
+    fn clear(&mut self) {
+        self.blob_source.clear();
+        self.calldata_source.clear();
+        self.active = None;
+    }
+
+    fn last_source(&self) -> Option<BatcherTxRef> {
+        match self.active? {
+            DataSourceKind::Blobs => self.blob_source.last_source(),
+            DataSourceKind::Calldata => self.calldata_source.last_source(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{
+        SignableTransaction, TxEip1559, TxEip4844, TxEip4844Variant, TxEnvelope,
+    };
+    use alloy_eips::eip4844::{Blob, IndexedBlobHash};
+    use alloy_primitives::{B256, TxKind};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use kona_derive::{
+        BlobProviderError,
+        test_utils::{TestBlobProvider, TestChainProvider},
+    };
+    use kona_genesis::{BatchInboxActivation, HardForkConfig};
+
+    /// A [BlobProvider] that never resolves.
+    #[derive(Debug, Clone, Default)]
+    struct StalledBlobProvider;
+
+    #[async_trait]
+    impl BlobProvider for StalledBlobProvider {
+        type Error = BlobProviderError;
+
+        async fn get_and_validate_blobs(
+            &mut self,
+            _: &BlockInfo,
+            _: &[IndexedBlobHash],
+        ) -> Result<Vec<Box<Blob>>, Self::Error> {
+            core::future::pending().await
+        }
+    }
+
+    const CALLDATA: Bytes = Bytes::from_static(&[0xca, 0x11]);
+
+    fn batcher() -> PrivateKeySigner {
+        PrivateKeySigner::from_bytes(&B256::repeat_byte(0x11)).unwrap()
+    }
+
+    fn test_cfg() -> RollupConfig {
+        RollupConfig {
+            hardforks: HardForkConfig { ecotone_time: Some(0), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn calldata_tx() -> TxEnvelope {
+        let tx =
+            TxEip1559 { to: TxKind::Call(Address::ZERO), input: CALLDATA, ..Default::default() };
+        let sig = batcher().sign_hash_sync(&tx.signature_hash()).unwrap();
+        tx.into_signed(sig).into()
+    }
+
+    fn blob_tx() -> TxEnvelope {
+        let tx = TxEip4844Variant::TxEip4844(TxEip4844 {
+            to: Address::ZERO,
+            blob_versioned_hashes: vec![B256::repeat_byte(0x01)],
+            ..Default::default()
+        });
+        let sig = batcher().sign_hash_sync(&tx.signature_hash()).unwrap();
+        tx.into_signed(sig).into()
+    }
+
+    fn provider_with(txs: Vec<TxEnvelope>) -> TestChainProvider {
+        let mut provider = TestChainProvider::default();
+        provider.insert_block_with_transactions(0, BlockInfo::default(), txs);
+        provider
+    }
+
+    fn failing_blobs() -> TestBlobProvider {
+        TestBlobProvider { should_error: true, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_failover_prefers_primary_source() {
+        let mut source = FailoverDataSource::new_from_parts(
+            provider_with(vec![calldata_tx()]),
+            TestBlobProvider::default(),
+            &test_cfg(),
+        );
+
+        let data = source.next(&BlockInfo::default(), batcher().address()).await.unwrap();
+        assert_eq!(data, CALLDATA);
+        assert_eq!(source.active, Some(DataSourceKind::Blobs));
+        assert_eq!(
+            source.next(&BlockInfo::default(), batcher().address()).await,
+            Err(PipelineError::Eof.temp())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failover_retries_blobs_on_blob_error() {
+        let mut source = FailoverDataSource::new_from_parts(
+            provider_with(vec![blob_tx(), calldata_tx()]),
+            failing_blobs(),
+            &test_cfg(),
+        );
+
+        // The calldata source is not used, as it can't serve the frames carried by the blobs.
+        let err = source.next(&BlockInfo::default(), batcher().address()).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Provider(_))));
+        assert_eq!(source.active, None);
+        assert!(!source.blob_source.open);
+        assert!(!source.calldata_source.open);
+    }
+
+    #[tokio::test]
+    async fn test_failover_from_parts_uses_inbox_schedule() {
+        let schedule = vec![BatchInboxActivation {
+            l1_block: 0,
+            inbox_address: Address::repeat_byte(0x01),
+            batcher_address: None,
+        }];
+        let cfg = RollupConfig { batch_inbox_schedule: schedule.clone(), ..test_cfg() };
+        let mut source = FailoverDataSource::new_from_parts(
+            provider_with(vec![calldata_tx()]),
+            TestBlobProvider::default(),
+            &cfg,
+        );
+        assert_eq!(source.blob_source.inbox_schedule, schedule);
+        assert_eq!(source.calldata_source.inbox_schedule, schedule);
+
+        // The calldata was sent to the rotated-out inbox, so neither source serves it.
+        let err = source.next(&BlockInfo::default(), batcher().address()).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Eof)));
+    }
+
+    #[tokio::test]
+    async fn test_failover_on_no_data() {
+        let mut source = FailoverDataSource::new_from_parts(
+            provider_with(vec![blob_tx()]),
+            failing_blobs(),
+            &test_cfg(),
+        )
+        .with_order([DataSourceKind::Calldata, DataSourceKind::Blobs]);
+
+        // The calldata source has no data for the block, so the blob source is tried.
+        let err = source.next(&BlockInfo::default(), batcher().address()).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Provider(_))));
+        assert_eq!(source.active, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_timeout_is_returned() {
+        let mut source = FailoverDataSource::new_from_parts(
+            provider_with(vec![blob_tx(), calldata_tx()]),
+            StalledBlobProvider,
+            &test_cfg(),
+        )
+        .with_timeout(DataSourceKind::Blobs, Duration::from_secs(1));
+
+        let err = source.next(&BlockInfo::default(), batcher().address()).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Temporary(PipelineError::Provider(_))));
+        assert_eq!(source.active, None);
+        assert!(!source.calldata_source.open);
+    }
+
+    #[tokio::test]
+    async fn test_failover_custom_order() {
+        let mut source = FailoverDataSource::new_from_parts(
+            provider_with(vec![calldata_tx()]),
+            TestBlobProvider::default(),
+            &test_cfg(),
+        )
+        .with_order([
+            DataSourceKind::Calldata,
+            DataSourceKind::Blobs,
+            DataSourceKind::Calldata,
+        ]);
+        assert_eq!(source.order, vec![DataSourceKind::Calldata, DataSourceKind::Blobs]);
+
+        source.next(&BlockInfo::default(), batcher().address()).await.unwrap();
+        assert_eq!(source.active, Some(DataSourceKind::Calldata));
+    }
+
+    #[tokio::test]
+    async fn test_failover_pre_ecotone_uses_calldata() {
+        let mut source = FailoverDataSource::new_from_parts(
+            provider_with(vec![blob_tx(), calldata_tx()]),
+            failing_blobs(),
+            &RollupConfig::default(),
+        );
+
+        let data = source.next(&BlockInfo::default(), batcher().address()).await.unwrap();
+        assert_eq!(data, CALLDATA);
+        assert_eq!(source.active, Some(DataSourceKind::Calldata));
+        assert!(!source.blob_source.open);
+    }
+}
@@ -19,10 +19,28 @@ mod blobs;
 pub use blobs::{BoxedBlobWithIndex, OnlineBlobProvider};
 
 mod chain_provider;
-pub use chain_provider::{AlloyChainProvider, AlloyChainProviderError};
+pub use chain_provider::{AlloyChainProvider, AlloyChainProviderError, PrefetchedL1Blocks};
 
 mod l2_chain_provider;
 pub use l2_chain_provider::{AlloyL2ChainProvider, AlloyL2ChainProviderError};
 
+mod failover;
+pub use failover::{DataSourceKind, FailoverDataSource};
+
+#[cfg(feature = "celestia")]
+mod celestia;
+#[cfg(feature = "celestia")]
+pub use celestia::{
+    CELESTIA_DA_LAYER, CelestiaCommitment, CelestiaConfig, CelestiaError, CelestiaProvider,
+    NAMESPACE_SIZE,
+};
+
+#[cfg(feature = "eigenda")]
+mod eigenda;
+#[cfg(feature = "eigenda")]
+pub use eigenda::{
+    EIGENDA_CERT_V1, EIGENDA_DA_LAYER, EigenDaConfig, EigenDaError, EigenDaProvider,
+};
+
 mod pipeline;
 pub use pipeline::OnlinePipeline;
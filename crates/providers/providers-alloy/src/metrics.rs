@@ -44,6 +44,13 @@ impl Metrics {
     /// Identifier for the gauge that tracks cache memory usage.
     pub const CACHE_MEMORY_USAGE: &str = "kona_providers_cache_memory_bytes";
 
+    /// Identifier for the counter of the L1 blocks served by each source of the failover data
+    /// source.
+    pub const DATA_SOURCE_SERVED: &str = "kona_providers_data_source_served";
+
+    /// Identifier for the counter of failovers away from a data source.
+    pub const DATA_SOURCE_FAILOVERS: &str = "kona_providers_data_source_failovers";
+
     /// Initializes metrics for the Alloy providers.
     ///
     /// This does two things:
@@ -107,6 +114,14 @@ impl Metrics {
             Self::CACHE_MEMORY_USAGE,
             "Memory usage of provider caches in bytes"
         );
+        metrics::describe_counter!(
+            Self::DATA_SOURCE_SERVED,
+            "Number of L1 blocks served by each failover data source"
+        );
+        metrics::describe_counter!(
+            Self::DATA_SOURCE_FAILOVERS,
+            "Number of failovers away from each data source"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -144,10 +159,12 @@ impl Metrics {
         kona_macros::set!(gauge, Self::BEACON_CLIENT_REQUESTS, "method", "spec", 0);
         kona_macros::set!(gauge, Self::BEACON_CLIENT_REQUESTS, "method", "genesis", 0);
         kona_macros::set!(gauge, Self::BEACON_CLIENT_REQUESTS, "method", "blob_sidecars", 0);
+        kona_macros::set!(gauge, Self::BEACON_CLIENT_REQUESTS, "method", "blobs_available", 0);
 
         kona_macros::set!(gauge, Self::BEACON_CLIENT_ERRORS, "method", "spec", 0);
         kona_macros::set!(gauge, Self::BEACON_CLIENT_ERRORS, "method", "genesis", 0);
         kona_macros::set!(gauge, Self::BEACON_CLIENT_ERRORS, "method", "blob_sidecars", 0);
+        kona_macros::set!(gauge, Self::BEACON_CLIENT_ERRORS, "method", "blobs_available", 0);
 
         // L2 chain provider metrics
         kona_macros::set!(
@@ -206,5 +223,11 @@ impl Metrics {
         kona_macros::set!(gauge, Self::CACHE_MEMORY_USAGE, "cache", "header_by_hash", 0);
         kona_macros::set!(gauge, Self::CACHE_MEMORY_USAGE, "cache", "receipts_by_hash", 0);
         kona_macros::set!(gauge, Self::CACHE_MEMORY_USAGE, "cache", "block_info_and_tx", 0);
+
+        // Failover data source metrics
+        kona_macros::set!(counter, Self::DATA_SOURCE_SERVED, "source", "blobs", 0);
+        kona_macros::set!(counter, Self::DATA_SOURCE_SERVED, "source", "calldata", 0);
+        kona_macros::set!(counter, Self::DATA_SOURCE_FAILOVERS, "source", "blobs", 0);
+        kona_macros::set!(counter, Self::DATA_SOURCE_FAILOVERS, "source", "calldata", 0);
     }
 }
@@ -4,24 +4,25 @@ use crate::{AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, Online
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{
-    DerivationPipeline, EthereumDataSource, IndexedAttributesQueueStage, L2ChainProvider,
-    OriginProvider, Pipeline, PipelineBuilder, PipelineErrorKind, PipelineResult,
+    AltDaProvider, CachedL2ChainProvider, CommitmentType, DerivationPipeline, DispatchDataSource,
+    EthereumDataSource, IndexedAttributesQueueStage, L2ChainProvider, OriginProvider, Pipeline,
+    PipelineBuilder, PipelineErrorKind, PipelineEvents, PipelineMemory, PipelineResult,
     PolledAttributesQueueStage, ResetSignal, Signal, SignalReceiver, StatefulAttributesBuilder,
     StepResult,
 };
 use kona_genesis::{L1ChainConfig, RollupConfig, SystemConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 /// An online polled derivation pipeline.
 type OnlinePolledDerivationPipeline = DerivationPipeline<
     PolledAttributesQueueStage<
         OnlineDataProvider,
         AlloyChainProvider,
-        AlloyL2ChainProvider,
+        OnlineL2ChainProvider,
         OnlineAttributesBuilder,
     >,
-    AlloyL2ChainProvider,
+    OnlineL2ChainProvider,
 >;
 
 /// An online managed derivation pipeline.
@@ -29,19 +30,28 @@ type OnlineManagedDerivationPipeline = DerivationPipeline<
     IndexedAttributesQueueStage<
         OnlineDataProvider,
         AlloyChainProvider,
-        AlloyL2ChainProvider,
+        OnlineL2ChainProvider,
         OnlineAttributesBuilder,
     >,
-    AlloyL2ChainProvider,
+    OnlineL2ChainProvider,
 >;
 
-/// An RPC-backed Ethereum data source.
-type OnlineDataProvider =
-    EthereumDataSource<AlloyChainProvider, OnlineBlobProvider<OnlineBeaconClient>>;
+/// The number of L2 blocks memoized by the [`OnlineL2ChainProvider`] shared by the stages of an
+/// online pipeline.
+const L2_CHAIN_CACHE_SIZE: usize = 1024;
+
+/// An RPC-backed L2 chain provider, whose lookups are memoized across the stages of the pipeline.
+type OnlineL2ChainProvider = CachedL2ChainProvider<AlloyL2ChainProvider>;
+
+/// An RPC-backed Ethereum data source, resolving alt-DA commitments with the registered
+/// providers.
+type OnlineDataProvider = DispatchDataSource<
+    EthereumDataSource<AlloyChainProvider, OnlineBlobProvider<OnlineBeaconClient>>,
+>;
 
 /// An RPC-backed payload attributes builder for the `AttributesQueue` stage of the derivation
 /// pipeline.
-type OnlineAttributesBuilder = StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider>;
+type OnlineAttributesBuilder = StatefulAttributesBuilder<AlloyChainProvider, OnlineL2ChainProvider>;
 
 /// An online derivation pipeline.
 #[derive(Debug)]
@@ -69,6 +79,9 @@ impl OnlinePipeline {
             blob_provider,
             chain_provider,
             l2_chain_provider.clone(),
+            BTreeMap::new(),
+            PipelineEvents::none(),
+            PipelineMemory::unbounded(),
         );
 
         // Reset the pipeline to populate the initial L1/L2 cursor and system configuration in L1
@@ -92,7 +105,10 @@ impl OnlinePipeline {
 
     /// Constructs a new polled derivation pipeline that is uninitialized.
     ///
-    /// Uses online providers as specified by the arguments.
+    /// Uses online providers as specified by the arguments. Alt-DA commitments are resolved with
+    /// the given providers, by [`CommitmentType`]. The pipeline's events are emitted to the given
+    /// [`PipelineEvents`] handle, and its buffers are accounted against the given
+    /// [`PipelineMemory`] handle.
     ///
     /// Before using the returned pipeline, a [`ResetSignal`] must be sent to
     /// instantiate the pipeline state. [`Self::new`] is a convenience method that
@@ -103,14 +119,23 @@ impl OnlinePipeline {
         blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
         chain_provider: AlloyChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
+        alt_da_providers: BTreeMap<CommitmentType, Box<dyn AltDaProvider>>,
+        events: PipelineEvents,
+        memory: PipelineMemory,
     ) -> Self {
+        let l2_chain_provider = CachedL2ChainProvider::new(l2_chain_provider, L2_CHAIN_CACHE_SIZE);
         let attributes = StatefulAttributesBuilder::new(
             cfg.clone(),
             l1_cfg,
             l2_chain_provider.clone(),
             chain_provider.clone(),
         );
-        let dap = EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, &cfg);
+        let mut dap = DispatchDataSource::new(EthereumDataSource::new_from_parts(
+            chain_provider.clone(),
+            blob_provider,
+            &cfg,
+        ));
+        dap.providers = alt_da_providers;
 
         let pipeline = PipelineBuilder::new()
             .rollup_config(cfg)
@@ -119,6 +144,8 @@ impl OnlinePipeline {
             .chain_provider(chain_provider)
             .builder(attributes)
             .origin(BlockInfo::default())
+            .events(events)
+            .memory(memory)
             .build_polled();
 
         Self::Polled(pipeline)
@@ -126,7 +153,10 @@ impl OnlinePipeline {
 
     /// Constructs a new indexed derivation pipeline that is uninitialized.
     ///
-    /// Uses online providers as specified by the arguments.
+    /// Uses online providers as specified by the arguments. Alt-DA commitments are resolved with
+    /// the given providers, by [`CommitmentType`]. The pipeline's events are emitted to the given
+    /// [`PipelineEvents`] handle, and its buffers are accounted against the given
+    /// [`PipelineMemory`] handle.
     ///
     /// Before using the returned pipeline, a [`ResetSignal`] must be sent to
     /// instantiate the pipeline state. [`Self::new`] is a convenience method that
@@ -137,14 +167,23 @@ impl OnlinePipeline {
         blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
         chain_provider: AlloyChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
+        alt_da_providers: BTreeMap<CommitmentType, Box<dyn AltDaProvider>>,
+        events: PipelineEvents,
+        memory: PipelineMemory,
     ) -> Self {
+        let l2_chain_provider = CachedL2ChainProvider::new(l2_chain_provider, L2_CHAIN_CACHE_SIZE);
         let attributes = StatefulAttributesBuilder::new(
             cfg.clone(),
             l1_cfg,
             l2_chain_provider.clone(),
             chain_provider.clone(),
         );
-        let dap = EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, &cfg);
+        let mut dap = DispatchDataSource::new(EthereumDataSource::new_from_parts(
+            chain_provider.clone(),
+            blob_provider,
+            &cfg,
+        ));
+        dap.providers = alt_da_providers;
 
         let pipeline = PipelineBuilder::new()
             .rollup_config(cfg)
@@ -153,6 +192,8 @@ impl OnlinePipeline {
             .chain_provider(chain_provider)
             .builder(attributes)
             .origin(BlockInfo::default())
+            .events(events)
+            .memory(memory)
             .build_indexed();
 
         Self::Managed(pipeline)
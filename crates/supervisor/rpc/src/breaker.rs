@@ -0,0 +1,335 @@
+//! A circuit breaker around a [`CheckAccessListClient`].
+//!
+//! Interop transactions are validated against the supervisor before they enter the mempool of the
+//! sequencer. While the supervisor is down, each validation would otherwise wait for the request
+//! to time out. Once the supervisor failed a number of consecutive times, the breaker opens, and
+//! validations are answered locally according to the [`FallbackPolicy`] until the supervisor is
+//! probed again.
+
+use crate::{CheckAccessListClient, SupervisorClientError, SupervisorHealth};
+use alloy_primitives::B256;
+use kona_interop::{ExecutingDescriptor, SafetyLevel};
+use std::{
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+use tokio::{sync::watch, time::Instant};
+use tracing::{info, warn};
+
+/// How access lists are validated while the circuit breaker is open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum FallbackPolicy {
+    /// Access lists are accepted without validation, favouring liveness over safety.
+    FailOpen,
+    /// Access lists are rejected without calling the supervisor.
+    #[default]
+    FailClosed,
+}
+
+/// The configuration of a [`CircuitBreakerClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// The number of consecutive failures of the supervisor after which the breaker opens.
+    pub failure_threshold: u32,
+    /// The time the breaker stays open before the supervisor is probed again.
+    pub cooldown: Duration,
+    /// How access lists are validated while the breaker is open.
+    pub policy: FallbackPolicy,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+            policy: FallbackPolicy::default(),
+        }
+    }
+}
+
+/// The state of a [`CircuitBreakerClient`].
+#[derive(Debug, Default)]
+struct BreakerState {
+    /// The number of consecutive failures of the supervisor.
+    consecutive_failures: u32,
+    /// The time the breaker opened, if it is open.
+    opened_at: Option<Instant>,
+    /// Whether a probe of the supervisor is in flight.
+    probing: bool,
+}
+
+/// Ends the probe of the supervisor started by [`CircuitBreakerClient::admit`] when dropped, so
+/// that a probe cancelled before its outcome was recorded does not keep the breaker open.
+#[derive(Debug)]
+struct ProbeGuard<'a> {
+    /// The state of the breaker, if the call probes the supervisor.
+    state: Option<&'a Mutex<BreakerState>>,
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state {
+            state.lock().unwrap_or_else(PoisonError::into_inner).probing = false;
+        }
+    }
+}
+
+/// A [`CheckAccessListClient`] that stops calling the supervisor after
+/// [`CircuitBreakerConfig::failure_threshold`] consecutive failures.
+///
+/// While the breaker is open, access lists are accepted or rejected locally, according to the
+/// [`FallbackPolicy`]. Once the [`CircuitBreakerConfig::cooldown`] elapsed, a single validation
+/// probes the supervisor: the breaker closes if it succeeds, and opens again otherwise.
+///
+/// The health of the supervisor is exported as the `kona_supervisor_client_health` gauge, and
+/// can be followed with [`Self::subscribe_health`] to be served by a healthcheck.
+#[derive(Debug)]
+pub struct CircuitBreakerClient<C> {
+    /// The client calling the supervisor.
+    inner: C,
+    /// The configuration of the breaker.
+    config: CircuitBreakerConfig,
+    /// The state of the breaker.
+    state: Mutex<BreakerState>,
+    /// The health of the supervisor.
+    health: watch::Sender<SupervisorHealth>,
+}
+
+impl<C> CircuitBreakerClient<C> {
+    /// The name of the gauge of the [`SupervisorHealth`].
+    pub const HEALTH_GAUGE: &'static str = "kona_supervisor_client_health";
+
+    /// The name of the counter of access lists validated locally, labelled by the `policy`.
+    pub const FALLBACK_COUNTER: &'static str = "kona_supervisor_client_fallbacks";
+
+    /// Wraps the given client in a circuit breaker.
+    pub fn new(inner: C, config: CircuitBreakerConfig) -> Self {
+        metrics::gauge!(Self::HEALTH_GAUGE).set(SupervisorHealth::Healthy.gauge());
+        Self {
+            inner,
+            config,
+            state: Mutex::new(BreakerState::default()),
+            health: watch::Sender::new(SupervisorHealth::Healthy),
+        }
+    }
+
+    /// Returns the current health of the supervisor.
+    pub fn health(&self) -> SupervisorHealth {
+        *self.health.borrow()
+    }
+
+    /// Returns a receiver of the health of the supervisor.
+    pub fn subscribe_health(&self) -> watch::Receiver<SupervisorHealth> {
+        self.health.subscribe()
+    }
+
+    /// Returns whether the supervisor should be called, starting a probe if the cooldown
+    /// elapsed. The probe ends when the returned [`ProbeGuard`] is dropped.
+    fn admit(&self) -> Option<ProbeGuard<'_>> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(opened_at) = state.opened_at else { return Some(ProbeGuard { state: None }) };
+        if state.probing || opened_at.elapsed() < self.config.cooldown {
+            return None;
+        }
+        state.probing = true;
+        self.set_health(SupervisorHealth::Probing);
+        Some(ProbeGuard { state: Some(&self.state) })
+    }
+
+    /// Records the outcome of a call to the supervisor. Only network failures count as failures
+    /// of the supervisor: rejecting the messages is an answer.
+    fn record(&self, result: &Result<(), SupervisorClientError>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.probing = false;
+        if !result.as_ref().is_err_and(SupervisorClientError::is_network) {
+            if state.opened_at.take().is_some() {
+                info!(target: "supervisor_client", "Supervisor recovered, closing the circuit breaker");
+            }
+            state.consecutive_failures = 0;
+            self.set_health(SupervisorHealth::Healthy);
+            return;
+        }
+
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.opened_at.is_some() || state.consecutive_failures >= self.config.failure_threshold
+        {
+            if state.opened_at.is_none() {
+                warn!(
+                    target: "supervisor_client",
+                    failures = state.consecutive_failures,
+                    policy = ?self.config.policy,
+                    "Supervisor unavailable, opening the circuit breaker"
+                );
+            }
+            state.opened_at = Some(Instant::now());
+            self.set_health(SupervisorHealth::Unavailable);
+        }
+    }
+
+    /// Validates an access list locally, according to the [`FallbackPolicy`].
+    fn fallback(&self) -> Result<(), SupervisorClientError> {
+        metrics::counter!(Self::FALLBACK_COUNTER, "policy" => format!("{:?}", self.config.policy))
+            .increment(1);
+        match self.config.policy {
+            FallbackPolicy::FailOpen => Ok(()),
+            FallbackPolicy::FailClosed => Err(SupervisorClientError::Unavailable),
+        }
+    }
+
+    /// Updates the health of the supervisor.
+    fn set_health(&self, health: SupervisorHealth) {
+        metrics::gauge!(Self::HEALTH_GAUGE).set(health.gauge());
+        self.health.send_replace(health);
+    }
+}
+
+impl<C: CheckAccessListClient + Sync> CheckAccessListClient for CircuitBreakerClient<C> {
+    async fn check_access_list(
+        &self,
+        inbox_entries: &[B256],
+        min_safety: SafetyLevel,
+        executing_descriptor: ExecutingDescriptor,
+    ) -> Result<(), SupervisorClientError> {
+        let Some(_probe) = self.admit() else {
+            return self.fallback();
+        };
+
+        let result =
+            self.inner.check_access_list(inbox_entries, min_safety, executing_descriptor).await;
+        self.record(&result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    /// A client whose supervisor is up or down, counting its calls.
+    #[derive(Debug, Default)]
+    struct MockClient {
+        down: AtomicBool,
+        hang: AtomicBool,
+        calls: AtomicU32,
+    }
+
+    impl CheckAccessListClient for MockClient {
+        async fn check_access_list(
+            &self,
+            _: &[B256],
+            _: SafetyLevel,
+            _: ExecutingDescriptor,
+        ) -> Result<(), SupervisorClientError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.hang.load(Ordering::SeqCst) {
+                std::future::pending::<()>().await;
+            }
+            if self.down.load(Ordering::SeqCst) {
+                return Err(SupervisorClientError::network(std::io::Error::other("down")));
+            }
+            Ok(())
+        }
+    }
+
+    async fn check(client: &CircuitBreakerClient<MockClient>) -> Result<(), SupervisorClientError> {
+        client
+            .check_access_list(&[], SafetyLevel::CrossUnsafe, ExecutingDescriptor::default())
+            .await
+    }
+
+    fn breaker(policy: FallbackPolicy) -> CircuitBreakerClient<MockClient> {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(10),
+            policy,
+        };
+        CircuitBreakerClient::new(MockClient::default(), config)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_breaker_fail_closed() {
+        let client = breaker(FallbackPolicy::FailClosed);
+        client.inner.down.store(true, Ordering::SeqCst);
+
+        for _ in 0..3 {
+            assert!(matches!(check(&client).await, Err(SupervisorClientError::Network(_))));
+        }
+        assert_eq!(client.health(), SupervisorHealth::Unavailable);
+
+        // Rejected locally, without calling the supervisor.
+        assert!(matches!(check(&client).await, Err(SupervisorClientError::Unavailable)));
+        assert_eq!(client.inner.calls.load(Ordering::SeqCst), 3);
+
+        // A failed probe opens the breaker again.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert!(matches!(check(&client).await, Err(SupervisorClientError::Network(_))));
+        assert_eq!(client.inner.calls.load(Ordering::SeqCst), 4);
+        assert!(matches!(check(&client).await, Err(SupervisorClientError::Unavailable)));
+
+        // A successful probe closes it.
+        client.inner.down.store(false, Ordering::SeqCst);
+        tokio::time::advance(Duration::from_secs(10)).await;
+        check(&client).await.unwrap();
+        assert_eq!(client.health(), SupervisorHealth::Healthy);
+        check(&client).await.unwrap();
+        assert_eq!(client.inner.calls.load(Ordering::SeqCst), 6);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_breaker_fail_open() {
+        let client = breaker(FallbackPolicy::FailOpen);
+        let mut health = client.subscribe_health();
+        client.inner.down.store(true, Ordering::SeqCst);
+
+        for _ in 0..3 {
+            assert!(check(&client).await.is_err());
+        }
+        assert!(health.has_changed().unwrap());
+        assert_eq!(*health.borrow_and_update(), SupervisorHealth::Unavailable);
+
+        // Accepted locally, without calling the supervisor.
+        check(&client).await.unwrap();
+        assert_eq!(client.inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_breaker_recovers_from_cancelled_probe() {
+        let client = breaker(FallbackPolicy::FailClosed);
+        client.inner.down.store(true, Ordering::SeqCst);
+        for _ in 0..3 {
+            assert!(check(&client).await.is_err());
+        }
+
+        // The probe is cancelled before the supervisor answers.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        client.inner.hang.store(true, Ordering::SeqCst);
+        assert!(tokio::time::timeout(Duration::from_secs(1), check(&client)).await.is_err());
+        assert_eq!(client.health(), SupervisorHealth::Probing);
+
+        // The next validation probes the supervisor again, and closes the breaker.
+        client.inner.hang.store(false, Ordering::SeqCst);
+        client.inner.down.store(false, Ordering::SeqCst);
+        check(&client).await.unwrap();
+        assert_eq!(client.health(), SupervisorHealth::Healthy);
+        assert_eq!(client.inner.calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_breaker_resets_failures_on_success() {
+        let client = breaker(FallbackPolicy::FailClosed);
+        for _ in 0..3 {
+            client.inner.down.store(true, Ordering::SeqCst);
+            assert!(check(&client).await.is_err());
+            assert!(check(&client).await.is_err());
+            client.inner.down.store(false, Ordering::SeqCst);
+            check(&client).await.unwrap();
+        }
+        assert_eq!(client.health(), SupervisorHealth::Healthy);
+    }
+}
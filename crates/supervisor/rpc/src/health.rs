@@ -0,0 +1,35 @@
+//! The health of the supervisor interop messages are validated against.
+
+/// The health of the supervisor, as seen by a `CircuitBreakerClient`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum SupervisorHealth {
+    /// The supervisor answers, and access lists are validated against it.
+    #[default]
+    Healthy,
+    /// The supervisor is failing, and access lists are validated locally until the next probe.
+    Unavailable,
+    /// The cooldown elapsed, and the next validation probes the supervisor.
+    Probing,
+}
+
+impl SupervisorHealth {
+    /// Returns whether access lists are validated against the supervisor.
+    pub const fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+
+    /// Returns the value of the health in the `kona_supervisor_client_health` gauge.
+    #[cfg(feature = "reqwest")]
+    pub(crate) const fn gauge(&self) -> f64 {
+        match self {
+            Self::Healthy => 0.0,
+            Self::Unavailable => 1.0,
+            Self::Probing => 2.0,
+        }
+    }
+}
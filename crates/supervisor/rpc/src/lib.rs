@@ -20,7 +20,17 @@ pub use server::SupervisorRpcServer;
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
 #[cfg(feature = "reqwest")]
-pub use reqwest::{CheckAccessListClient, SupervisorClient, SupervisorClientError};
+pub use reqwest::{
+    CheckAccessListClient, SupervisorClient, SupervisorClientConfig, SupervisorClientError,
+};
+
+#[cfg(feature = "reqwest")]
+pub mod breaker;
+#[cfg(feature = "reqwest")]
+pub use breaker::{CircuitBreakerClient, CircuitBreakerConfig, FallbackPolicy};
+
+pub mod health;
+pub use health::SupervisorHealth;
 
 pub mod response;
 pub use response::{
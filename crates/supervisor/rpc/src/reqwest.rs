@@ -5,17 +5,39 @@ use alloy_primitives::B256;
 #[cfg(feature = "reqwest")]
 use alloy_rpc_client::ReqwestClient;
 #[cfg(feature = "reqwest")]
-use derive_more::Constructor;
+use alloy_transport::{RpcError, TransportErrorKind};
+#[cfg(feature = "reqwest")]
+use alloy_transport_http::Http;
+#[cfg(feature = "reqwest")]
+use backon::{ExponentialBuilder, Retryable};
 #[cfg(feature = "reqwest")]
 use kona_interop::{ExecutingDescriptor, SafetyLevel};
+#[cfg(feature = "reqwest")]
+use std::time::Duration;
+#[cfg(feature = "reqwest")]
+use url::Url;
 
 /// Error types for supervisor RPC interactions
 #[cfg(feature = "reqwest")]
 #[derive(Debug, thiserror::Error)]
 pub enum SupervisorClientError {
+    /// The supervisor could not be reached, or did not answer in time.
+    #[error("Supervisor network error: {0}")]
+    Network(Box<dyn std::error::Error + Send + Sync>),
+    /// The supervisor answered the request with an error, e.g. rejecting the messages.
+    #[error("Supervisor error {code}: {message}")]
+    Application {
+        /// The JSON-RPC error code.
+        code: i64,
+        /// The error message.
+        message: String,
+    },
     /// RPC client error
     #[error("RPC client error: {0}")]
     Client(Box<dyn std::error::Error + Send + Sync>),
+    /// The supervisor is unavailable, and the request was rejected by the circuit breaker.
+    #[error("Supervisor unavailable, rejected by the circuit breaker")]
+    Unavailable,
 }
 
 #[cfg(feature = "reqwest")]
@@ -24,6 +46,84 @@ impl SupervisorClientError {
     pub fn client(err: impl std::error::Error + Send + Sync + 'static) -> Self {
         Self::Client(Box::new(err))
     }
+
+    /// Creates a new network error
+    pub fn network(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Network(Box::new(err))
+    }
+
+    /// Classifies a failed request to the supervisor.
+    pub fn from_rpc_error(err: RpcError<TransportErrorKind>) -> Self {
+        match err {
+            RpcError::ErrorResp(payload) => {
+                Self::Application { code: payload.code, message: payload.message.into_owned() }
+            }
+            RpcError::Transport(_) => Self::network(err),
+            err => Self::client(err),
+        }
+    }
+
+    /// Returns whether the supervisor could not be reached, in which case the request may
+    /// succeed when retried.
+    pub const fn is_network(&self) -> bool {
+        matches!(self, Self::Network(_))
+    }
+}
+
+/// The configuration of a [`SupervisorClient`].
+///
+/// The client sits on the hot path of the sequencer, validating interop transactions before they
+/// enter the mempool, so its defaults favour failing fast over waiting on the supervisor.
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupervisorClientConfig {
+    /// The timeout of each request, including the connection and the response.
+    pub timeout: Duration,
+    /// The timeout of the connection to the supervisor.
+    pub connect_timeout: Duration,
+    /// The number of times a request is retried after a network failure. Requests rejected by
+    /// the supervisor are not retried.
+    pub max_retries: usize,
+    /// The delay before the first retry.
+    pub min_backoff: Duration,
+    /// The maximum delay between retries.
+    pub max_backoff: Duration,
+    /// Whether the delays between retries are randomized, so that concurrent requests do not
+    /// retry in lockstep.
+    pub jitter: bool,
+    /// The maximum number of idle connections kept open to the supervisor.
+    pub pool_max_idle: usize,
+    /// The time after which idle connections are closed. Idle connections are kept open
+    /// indefinitely if unset.
+    pub pool_idle_timeout: Option<Duration>,
+}
+
+#[cfg(feature = "reqwest")]
+impl Default for SupervisorClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(2),
+            connect_timeout: Duration::from_secs(1),
+            max_retries: 2,
+            min_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_millis(500),
+            jitter: true,
+            pool_max_idle: 32,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl SupervisorClientConfig {
+    /// Returns the backoff between the retries of a request.
+    fn backoff(&self) -> ExponentialBuilder {
+        let backoff = ExponentialBuilder::default()
+            .with_min_delay(self.min_backoff)
+            .with_max_delay(self.max_backoff)
+            .with_max_times(self.max_retries);
+        if self.jitter { backoff.with_jitter() } else { backoff }
+    }
 }
 
 /// Subset of `op-supervisor` API, used for validating interop events.
@@ -40,10 +140,43 @@ pub trait CheckAccessListClient {
 
 /// A supervisor client.
 #[cfg(feature = "reqwest")]
-#[derive(Debug, Clone, Constructor)]
+#[derive(Debug, Clone)]
 pub struct SupervisorClient {
     /// The inner RPC client.
     client: ReqwestClient,
+    /// The configuration of the client.
+    config: SupervisorClientConfig,
+}
+
+#[cfg(feature = "reqwest")]
+impl SupervisorClient {
+    /// Creates a new [`SupervisorClient`] from an RPC client, retrying requests with the default
+    /// [`SupervisorClientConfig`].
+    pub fn new(client: ReqwestClient) -> Self {
+        Self { client, config: SupervisorClientConfig::default() }
+    }
+
+    /// Creates a new [`SupervisorClient`] for the supervisor at the given URL, with the timeouts,
+    /// retries and connection pool of the given [`SupervisorClientConfig`].
+    pub fn from_config(
+        url: Url,
+        config: SupervisorClientConfig,
+    ) -> Result<Self, SupervisorClientError> {
+        let http = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .pool_max_idle_per_host(config.pool_max_idle)
+            .pool_idle_timeout(config.pool_idle_timeout)
+            .build()
+            .map_err(SupervisorClientError::client)?;
+        let client = ReqwestClient::new(Http::with_client(http, url), false);
+        Ok(Self { client, config })
+    }
+
+    /// Returns the configuration of the client.
+    pub const fn config(&self) -> &SupervisorClientConfig {
+        &self.config
+    }
 }
 
 #[cfg(feature = "reqwest")]
@@ -54,12 +187,62 @@ impl CheckAccessListClient for SupervisorClient {
         min_safety: SafetyLevel,
         executing_descriptor: ExecutingDescriptor,
     ) -> Result<(), SupervisorClientError> {
-        self.client
-            .request(
-                "supervisor_checkAccessList",
-                (inbox_entries, min_safety, executing_descriptor),
-            )
+        (|| async {
+            self.client
+                .request(
+                    "supervisor_checkAccessList",
+                    (inbox_entries, min_safety, executing_descriptor.clone()),
+                )
+                .await
+                .map_err(SupervisorClientError::from_rpc_error)
+        })
+        .retry(self.config.backoff())
+        .when(SupervisorClientError::is_network)
+        .notify(|err, delay| {
+            tracing::debug!(
+                target: "supervisor_client",
+                %err,
+                ?delay,
+                "Retrying supervisor_checkAccessList"
+            );
+        })
+        .await
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::ErrorPayload;
+
+    #[test]
+    fn test_classify_rpc_error() {
+        let err = SupervisorClientError::from_rpc_error(RpcError::ErrorResp(ErrorPayload {
+            code: -320600,
+            message: "conflicting data".into(),
+            data: None,
+        }));
+        assert!(matches!(err, SupervisorClientError::Application { code: -320600, .. }));
+        assert!(!err.is_network());
+
+        let err = SupervisorClientError::from_rpc_error(TransportErrorKind::backend_gone());
+        assert!(err.is_network());
+    }
+
+    #[tokio::test]
+    async fn test_check_access_list_unreachable() {
+        let config = SupervisorClientConfig {
+            max_retries: 1,
+            min_backoff: Duration::from_millis(1),
+            ..Default::default()
+        };
+        // Nothing listens on the discard port.
+        let client =
+            SupervisorClient::from_config("http://127.0.0.1:9".parse().unwrap(), config).unwrap();
+        let err = client
+            .check_access_list(&[], SafetyLevel::CrossUnsafe, ExecutingDescriptor::default())
             .await
-            .map_err(SupervisorClientError::client)
+            .unwrap_err();
+        assert!(err.is_network(), "{err}");
     }
 }
@@ -1,5 +1,6 @@
 //! Error types for CLI utilities.
 
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Errors that can occur in CLI operations.
@@ -20,6 +21,26 @@ pub enum CliError {
     /// Error initializing metrics.
     #[error("Failed to initialize metrics")]
     MetricsInitialization(#[from] metrics_exporter_prometheus::BuildError),
+
+    /// Error initializing the global tracing subscriber.
+    #[error("Failed to initialize tracing subscriber: {0}")]
+    TracingInitialization(#[from] tracing_subscriber::util::TryInitError),
+
+    /// Error creating the log file appender.
+    #[error("Failed to create log file appender: {0}")]
+    LogFile(String),
+
+    /// Error reading the log directives file.
+    #[error("Failed to read log directives file {0}: {1}")]
+    LogDirectivesFile(PathBuf, std::io::Error),
+
+    /// Error creating the OTLP trace exporter.
+    #[error("Failed to create OTLP trace exporter: {0}")]
+    Otlp(String),
+
+    /// Error parsing a log directive.
+    #[error("Invalid log directive `{0}`: {1}")]
+    LogDirective(String, tracing_subscriber::filter::ParseError),
 }
 
 /// Type alias for CLI results.
@@ -4,7 +4,7 @@ use alloy_chains::Chain;
 use alloy_primitives::Address;
 use clap::Parser;
 use kona_genesis::RollupConfig;
-use kona_registry::OPCHAINS;
+use kona_registry::chain_config_by_chain_id;
 
 use crate::{CliError, CliResult, LogArgs, MetricsArgs, OverrideArgs};
 
@@ -44,8 +44,7 @@ impl GlobalArgs {
     /// Returns the signer [`Address`] from the rollup config for the given l2 chain id.
     pub fn genesis_signer(&self) -> CliResult<Address> {
         let id = self.l2_chain_id;
-        OPCHAINS
-            .get(&id.id())
+        chain_config_by_chain_id(id.id())
             .ok_or(CliError::ChainConfigNotFound(id.id()))?
             .roles
             .as_ref()
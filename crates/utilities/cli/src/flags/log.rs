@@ -57,6 +57,37 @@ pub struct LogArgs {
     /// If set, new log files will be created every interval.
     #[arg(long = "logs.file.rotation", default_value = "never", env = "KONA_LOG_FILE_ROTATION")]
     pub file_rotation: LogRotation,
+    /// The maximum size of a log file in megabytes.
+    /// If set, the log file is rotated once it exceeds this size, instead of on an interval.
+    #[arg(
+        long = "logs.file.max-size",
+        env = "KONA_LOG_FILE_MAX_SIZE",
+        conflicts_with = "file_rotation"
+    )]
+    pub file_max_size: Option<u64>,
+    /// The maximum number of rotated log files to keep. Older log files are deleted.
+    /// If not set, all time-rotated log files are kept, and 5 size-rotated log files are kept.
+    #[arg(long = "logs.file.max-files", env = "KONA_LOG_FILE_MAX_FILES")]
+    pub file_max_files: Option<usize>,
+    /// Path to a file of per-target log level directives, one per line (e.g. `engine=debug`).
+    ///
+    /// Directives use the `RUST_LOG` syntax and take precedence over the global verbosity
+    /// level. Empty lines and lines starting with `#` are ignored.
+    #[arg(long = "logs.directives-file", env = "KONA_LOG_DIRECTIVES_FILE")]
+    pub directives_file: Option<PathBuf>,
+    /// The OTLP/HTTP endpoint to export traces to, e.g. `http://localhost:4318/v1/traces`.
+    /// If not set, traces are not exported.
+    #[cfg(feature = "otlp")]
+    #[arg(long = "tracing.otlp.endpoint", env = "KONA_TRACING_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+    /// The service name reported with exported traces.
+    #[cfg(feature = "otlp")]
+    #[arg(
+        long = "tracing.otlp.service-name",
+        default_value = "kona",
+        env = "KONA_TRACING_OTLP_SERVICE_NAME"
+    )]
+    pub otlp_service_name: String,
 }
 
 #[cfg(test)]
@@ -80,6 +111,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_file_rotation_flags() {
+        let cli = TestCli::parse_from([
+            "test_app",
+            "--logs.file.directory",
+            "/tmp/kona",
+            "--logs.file.max-size",
+            "100",
+            "--logs.file.max-files",
+            "3",
+            "--logs.directives-file",
+            "/tmp/kona/directives",
+        ]);
+        assert_eq!(cli.global.file_max_size, Some(100));
+        assert_eq!(cli.global.file_max_files, Some(3));
+        assert_eq!(cli.global.directives_file, Some(PathBuf::from("/tmp/kona/directives")));
+    }
+
+    #[test]
+    fn test_file_max_size_conflicts_with_rotation() {
+        let res = TestCli::try_parse_from([
+            "test_app",
+            "--logs.file.max-size",
+            "100",
+            "--logs.file.rotation",
+            "daily",
+        ]);
+        assert!(res.is_err());
+    }
+
     #[test]
     fn test_verbosity_count() {
         let cli_v1 = TestCli::parse_from(["test_app", "-v"]);
@@ -27,6 +27,14 @@ pub mod backtrace;
 mod tracing;
 pub use tracing::{LogFormat, init_test_tracing};
 
+mod rolling;
+pub use rolling::SizeRollingAppender;
+
+#[cfg(feature = "otlp")]
+mod otlp;
+#[cfg(feature = "otlp")]
+pub use otlp::{OtlpConfig, shutdown_otlp_tracing};
+
 mod prometheus;
 pub use prometheus::init_prometheus_server;
 
@@ -32,6 +32,11 @@ pub struct FileLogConfig {
     pub format: LogFormat,
     /// The rotation of the log files.
     pub rotation: LogRotation,
+    /// The maximum size of a log file in bytes. If set, log files are rotated by size instead of
+    /// by [`LogRotation`].
+    pub max_size: Option<u64>,
+    /// The maximum number of rotated log files to keep.
+    pub max_files: Option<usize>,
 }
 
 /// Configuration for stdout logging.
@@ -51,6 +56,11 @@ pub struct LogConfig {
     pub stdout_logs: Option<StdoutLogConfig>,
     /// The configuration for file logging.
     pub file_logs: Option<FileLogConfig>,
+    /// An optional file of per-target log level directives.
+    pub directives_file: Option<PathBuf>,
+    /// The configuration for exporting traces over OTLP.
+    #[cfg(feature = "otlp")]
+    pub otlp: Option<crate::OtlpConfig>,
 }
 
 impl Default for LogConfig {
@@ -59,6 +69,9 @@ impl Default for LogConfig {
             global_level: LevelFilter::INFO,
             stdout_logs: Some(StdoutLogConfig { format: LogFormat::Full }),
             file_logs: None,
+            directives_file: None,
+            #[cfg(feature = "otlp")]
+            otlp: None,
         }
     }
 }
@@ -90,8 +103,22 @@ impl LogConfig {
             directory_path: path.clone(),
             format: args.file_format,
             rotation: args.file_rotation,
+            max_size: args.file_max_size.map(|mb| mb.saturating_mul(1024 * 1024)),
+            max_files: args.file_max_files,
         });
 
-        Self { global_level: level, stdout_logs, file_logs }
+        #[cfg(feature = "otlp")]
+        let otlp = args
+            .otlp_endpoint
+            .map(|endpoint| crate::OtlpConfig { endpoint, service_name: args.otlp_service_name });
+
+        Self {
+            global_level: level,
+            stdout_logs,
+            file_logs,
+            directives_file: args.directives_file,
+            #[cfg(feature = "otlp")]
+            otlp,
+        }
     }
 }
@@ -0,0 +1,61 @@
+//! OpenTelemetry trace export over OTLP.
+
+use std::sync::OnceLock;
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{Resource, trace::SdkTracerProvider};
+use tracing::Subscriber;
+use tracing_subscriber::{Layer, registry::LookupSpan};
+
+use crate::{CliError, CliResult};
+
+/// The installed tracer provider, kept so that buffered spans can be flushed on shutdown.
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Configuration for exporting traces over OTLP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtlpConfig {
+    /// The OTLP/HTTP traces endpoint, e.g. `http://localhost:4318/v1/traces`.
+    pub endpoint: String,
+    /// The service name reported with the exported spans.
+    pub service_name: String,
+}
+
+impl OtlpConfig {
+    /// Builds a [`Layer`] that exports spans to the configured OTLP endpoint, and installs the
+    /// tracer provider globally.
+    ///
+    /// The exporter uses a blocking HTTP client, so this must be called outside of an async
+    /// runtime.
+    pub fn layer<S>(&self) -> CliResult<impl Layer<S> + Send + Sync + 'static>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(&self.endpoint)
+            .build()
+            .map_err(|e| CliError::Otlp(e.to_string()))?;
+
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(self.service_name.clone()).build())
+            .build();
+        let tracer = provider.tracer("kona");
+
+        let _ = TRACER_PROVIDER.set(provider.clone());
+        opentelemetry::global::set_tracer_provider(provider);
+
+        Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+    }
+}
+
+/// Flushes any buffered spans and shuts down the OTLP exporter, if one was installed.
+pub fn shutdown_otlp_tracing() {
+    if let Some(provider) = TRACER_PROVIDER.get() &&
+        let Err(e) = provider.shutdown()
+    {
+        eprintln!("Failed to shut down OTLP trace exporter: {e}");
+    }
+}
@@ -0,0 +1,136 @@
+//! A size-based rolling file appender.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// A file appender that rolls over to a new file once the active log file exceeds a maximum size.
+///
+/// The active log file is always written to `<directory>/<file_name>`. When it is rolled, the
+/// existing files are shifted to `<file_name>.1`, `<file_name>.2`, and so on, with the oldest
+/// files beyond `max_files` being deleted.
+///
+/// The appender is cheap to clone, and all clones write to the same file.
+#[derive(Debug, Clone)]
+pub struct SizeRollingAppender {
+    inner: Arc<Mutex<SizeRollingState>>,
+}
+
+/// The shared state of a [`SizeRollingAppender`].
+#[derive(Debug)]
+struct SizeRollingState {
+    /// The path of the active log file.
+    path: PathBuf,
+    /// The maximum size of a log file in bytes.
+    max_size: u64,
+    /// The maximum number of rolled log files to keep.
+    max_files: usize,
+    /// The active log file.
+    file: File,
+    /// The number of bytes written to the active log file.
+    written: u64,
+}
+
+impl SizeRollingAppender {
+    /// Creates a new [`SizeRollingAppender`], appending to any existing log file in the directory.
+    pub fn new(
+        directory: impl AsRef<Path>,
+        file_name: &str,
+        max_size: u64,
+        max_files: usize,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(directory.as_ref())?;
+        let path = directory.as_ref().join(file_name);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+        let state = SizeRollingState { path, max_size, max_files, file, written };
+        Ok(Self { inner: Arc::new(Mutex::new(state)) })
+    }
+}
+
+impl SizeRollingState {
+    /// Returns the path of the rolled log file with the given index.
+    fn rolled_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    /// Shifts the rolled log files and opens a fresh active log file.
+    fn roll(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rolled_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.rolled_path(index);
+                if from.exists() {
+                    fs::rename(from, self.rolled_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rolled_path(1))?;
+        }
+
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for SizeRollingAppender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.inner.lock().map_err(|_| io::Error::other("log appender poisoned"))?;
+        if state.written > 0 && state.written.saturating_add(buf.len() as u64) > state.max_size {
+            state.roll()?;
+        }
+        let written = state.file.write(buf)?;
+        state.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self.inner.lock().map_err(|_| io::Error::other("log appender poisoned"))?;
+        state.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_rolling_appender_rolls() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("logs");
+
+        let mut appender = SizeRollingAppender::new(&dir, "kona.log", 8, 2).unwrap();
+        for line in ["aaaaaa\n", "bbbbbb\n", "cccccc\n", "dddddd\n"] {
+            appender.write_all(line.as_bytes()).unwrap();
+        }
+        appender.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("kona.log")).unwrap(), "dddddd\n");
+        assert_eq!(fs::read_to_string(dir.join("kona.log.1")).unwrap(), "cccccc\n");
+        assert_eq!(fs::read_to_string(dir.join("kona.log.2")).unwrap(), "bbbbbb\n");
+        assert!(!dir.join("kona.log.3").exists());
+    }
+
+    #[test]
+    fn test_size_rolling_appender_resumes_existing_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::write(dir.join("kona.log"), "existing\n").unwrap();
+
+        let mut appender = SizeRollingAppender::new(dir, "kona.log", 10, 1).unwrap();
+        appender.write_all(b"new\n").unwrap();
+        appender.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("kona.log")).unwrap(), "new\n");
+        assert_eq!(fs::read_to_string(dir.join("kona.log.1")).unwrap(), "existing\n");
+    }
+}
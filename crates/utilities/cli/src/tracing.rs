@@ -1,21 +1,24 @@
 //! [tracing_subscriber] utilities.
 
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
     Layer,
+    filter::Directive,
     fmt::{
         format::{FormatEvent, FormatFields, Writer},
         time::{FormatTime, SystemTime},
+        writer::BoxMakeWriter,
     },
     prelude::__tracing_subscriber_SubscriberExt,
     registry::LookupSpan,
-    util::{SubscriberInitExt, TryInitError},
+    util::SubscriberInitExt,
 };
 
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{fmt, path::Path};
 use tracing_subscriber::EnvFilter;
 
-use crate::{LogConfig, LogRotation};
+use crate::{CliError, CliResult, FileLogConfig, LogConfig, LogRotation, SizeRollingAppender};
 
 /// The format of the logs.
 #[derive(
@@ -72,50 +75,75 @@ where
     }
 }
 
+/// The name of the active log file.
+const LOG_FILE_NAME: &str = "kona.log";
+
+/// The default number of size-rotated log files to keep.
+const DEFAULT_MAX_SIZE_ROTATED_FILES: usize = 5;
+
+impl FileLogConfig {
+    /// Creates the writer for the log files, rotating them by size or by interval.
+    fn writer(&self) -> CliResult<BoxMakeWriter> {
+        if let Some(max_size) = self.max_size {
+            let appender = SizeRollingAppender::new(
+                &self.directory_path,
+                LOG_FILE_NAME,
+                max_size,
+                self.max_files.unwrap_or(DEFAULT_MAX_SIZE_ROTATED_FILES),
+            )
+            .map_err(|e| CliError::LogFile(e.to_string()))?;
+            return Ok(BoxMakeWriter::new(move || appender.clone()));
+        }
+
+        let rotation = match self.rotation {
+            LogRotation::Minutely => Rotation::MINUTELY,
+            LogRotation::Hourly => Rotation::HOURLY,
+            LogRotation::Daily => Rotation::DAILY,
+            LogRotation::Never => Rotation::NEVER,
+        };
+        let mut builder =
+            RollingFileAppender::builder().rotation(rotation).filename_prefix(LOG_FILE_NAME);
+        if let Some(max_files) = self.max_files {
+            builder = builder.max_log_files(max_files);
+        }
+        let appender =
+            builder.build(&self.directory_path).map_err(|e| CliError::LogFile(e.to_string()))?;
+        Ok(BoxMakeWriter::new(appender))
+    }
+}
+
 impl LogConfig {
     /// Initializes the tracing subscriber
     ///
     /// # Arguments
-    /// * `verbosity_level` - The verbosity level (0-5). If `0`, no logs are printed.
     /// * `env_filter` - Optional environment filter for the subscriber.
     ///
     /// # Returns
-    /// * `Result<()>` - Ok if successful, Err otherwise.
-    pub fn init_tracing_subscriber(
-        &self,
-        env_filter: Option<EnvFilter>,
-    ) -> Result<(), TryInitError> {
-        let file_layer = self.file_logs.as_ref().map(|file_logs| {
-            let directory_path = file_logs.directory_path.clone();
-
-            let appender = match file_logs.rotation {
-                LogRotation::Minutely => {
-                    tracing_appender::rolling::minutely(directory_path, "kona.log")
-                }
-                LogRotation::Hourly => {
-                    tracing_appender::rolling::hourly(directory_path, "kona.log")
-                }
-                LogRotation::Daily => tracing_appender::rolling::daily(directory_path, "kona.log"),
-                LogRotation::Never => tracing_appender::rolling::never(directory_path, "kona.log"),
-            };
-
-            match file_logs.format {
-                LogFormat::Full => tracing_subscriber::fmt::layer().with_writer(appender).boxed(),
-                LogFormat::Json => {
-                    tracing_subscriber::fmt::layer().json().with_writer(appender).boxed()
-                }
-                LogFormat::Pretty => {
-                    tracing_subscriber::fmt::layer().pretty().with_writer(appender).boxed()
-                }
-                LogFormat::Compact => {
-                    tracing_subscriber::fmt::layer().compact().with_writer(appender).boxed()
-                }
-                LogFormat::Logfmt => tracing_subscriber::fmt::layer()
-                    .event_format(LogfmtFormatter)
-                    .with_writer(appender)
-                    .boxed(),
-            }
-        });
+    /// * `CliResult<()>` - Ok if successful, Err otherwise.
+    pub fn init_tracing_subscriber(&self, env_filter: Option<EnvFilter>) -> CliResult<()> {
+        let file_layer = self
+            .file_logs
+            .as_ref()
+            .map(|file_logs| -> CliResult<_> {
+                let writer = file_logs.writer()?;
+                Ok(match file_logs.format {
+                    LogFormat::Full => tracing_subscriber::fmt::layer().with_writer(writer).boxed(),
+                    LogFormat::Json => {
+                        tracing_subscriber::fmt::layer().json().with_writer(writer).boxed()
+                    }
+                    LogFormat::Pretty => {
+                        tracing_subscriber::fmt::layer().pretty().with_writer(writer).boxed()
+                    }
+                    LogFormat::Compact => {
+                        tracing_subscriber::fmt::layer().compact().with_writer(writer).boxed()
+                    }
+                    LogFormat::Logfmt => tracing_subscriber::fmt::layer()
+                        .event_format(LogfmtFormatter)
+                        .with_writer(writer)
+                        .boxed(),
+                })
+            })
+            .transpose()?;
 
         let stdout_layer = self.stdout_logs.as_ref().map(|stdout_logs| match stdout_logs.format {
             LogFormat::Full => tracing_subscriber::fmt::layer().boxed(),
@@ -127,18 +155,50 @@ impl LogConfig {
             }
         });
 
-        let env_filter = env_filter
-            .unwrap_or(EnvFilter::from_default_env())
-            .add_directive(self.global_level.into());
+        let env_filter = self.env_filter(env_filter)?;
+
+        #[cfg(feature = "otlp")]
+        let otlp_layer = self.otlp.as_ref().map(|otlp| otlp.layer()).transpose()?;
+        #[cfg(not(feature = "otlp"))]
+        let otlp_layer: Option<tracing_subscriber::layer::Identity> = None;
 
         tracing_subscriber::registry()
             .with(env_filter)
             .with(file_layer)
             .with(stdout_layer)
+            .with(otlp_layer)
             .try_init()?;
 
         Ok(())
     }
+
+    /// Builds the filter of the tracing subscriber from the given environment filter, the global
+    /// level and the directives file.
+    fn env_filter(&self, env_filter: Option<EnvFilter>) -> CliResult<EnvFilter> {
+        let mut env_filter = env_filter
+            .unwrap_or(EnvFilter::from_default_env())
+            .add_directive(self.global_level.into());
+        if let Some(path) = &self.directives_file {
+            for directive in Self::read_directives(path)? {
+                env_filter = env_filter.add_directive(directive);
+            }
+        }
+        Ok(env_filter)
+    }
+
+    /// Reads per-target log level directives from the given file, one per line.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn read_directives(path: &Path) -> CliResult<Vec<Directive>> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| CliError::LogDirectivesFile(path.to_path_buf(), e))?;
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.parse().map_err(|e| CliError::LogDirective(line.to_string(), e)))
+            .collect()
+    }
 }
 
 /// This provides function for init tracing in testing
@@ -150,3 +210,35 @@ impl LogConfig {
 pub fn init_test_tracing() {
     let _ = LogConfig::default().init_tracing_subscriber(None::<EnvFilter>);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_directives() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("directives");
+        std::fs::write(&path, "# engine logs\nengine=debug\n\n  derive=trace  \n").unwrap();
+
+        let directives = LogConfig::read_directives(&path).unwrap();
+        let directives = directives.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(directives, vec!["engine=debug", "derive=trace"]);
+    }
+
+    #[test]
+    fn test_read_directives_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("directives");
+        std::fs::write(&path, "engine=notalevel\n").unwrap();
+
+        let err = LogConfig::read_directives(&path).unwrap_err();
+        assert!(matches!(err, CliError::LogDirective(..)));
+    }
+
+    #[test]
+    fn test_read_directives_missing_file() {
+        let err = LogConfig::read_directives(Path::new("/does/not/exist")).unwrap_err();
+        assert!(matches!(err, CliError::LogDirectivesFile(..)));
+    }
+}
@@ -149,7 +149,8 @@ export const sidebar: SidebarItem[] = [
               { text: "Introduction", link: "/sdk/protocol/derive/intro" },
               { text: "Custom Providers", link: "/sdk/protocol/derive/providers" },
               { text: "Stage Swapping", link: "/sdk/protocol/derive/stages" },
-              { text: "Signaling", link: "/sdk/protocol/derive/signaling" }
+              { text: "Signaling", link: "/sdk/protocol/derive/signaling" },
+              { text: "WebAssembly", link: "/sdk/protocol/derive/wasm" }
             ]
           },
           {
@@ -21,7 +21,7 @@ use clap::Parser;
 use discv5::enr::CombinedKey;
 use kona_cli::{LogArgs, LogConfig};
 use kona_disc::LocalNode;
-use kona_node_service::{NetworkActor, NetworkConfig, NetworkContext, NodeActor};
+use kona_node_service::{NetworkActor, NetworkConfig, NetworkContext, NodeActor, metered_channel};
 use kona_registry::ROLLUP_CONFIGS;
 use libp2p::{Multiaddr, identity::Keypair};
 use std::{
@@ -85,6 +85,7 @@ impl GossipCommand {
             NetworkConfig {
                 discovery_address: disc_addr,
                 gossip_address: gossip_addr,
+                gossip_quic_address: None,
                 unsafe_block_signer: signer,
                 discovery_config: discv5::ConfigBuilder::new(discv5::ListenConfig::Ipv4 {
                     ip: disc_ip,
@@ -100,6 +101,10 @@ impl GossipCommand {
                 monitor_peers: Default::default(),
                 bootstore: None,
                 gater_config: Default::default(),
+                peer_targets: Default::default(),
+                latency_preference: Default::default(),
+                payload_validation: Default::default(),
+                recent_payloads: 64,
                 bootnodes: Default::default(),
                 rollup_config: rollup_config.clone(),
                 gossip_signer: None,
@@ -108,7 +113,8 @@ impl GossipCommand {
             .into(),
         );
 
-        let (unsafe_blocks_tx, mut unsafe_blocks_rx) = tokio::sync::mpsc::channel(1024);
+        let (unsafe_blocks_tx, mut unsafe_blocks_rx) =
+            metered_channel("unsafe_blocks", 1024, Default::default());
 
         network
             .start(NetworkContext {